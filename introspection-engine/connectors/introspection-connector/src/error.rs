@@ -20,12 +20,13 @@ impl ConnectorError {
         }
     }
 
+    /// The connection string is part of the message with its credentials masked.
     pub fn url_parse_error(err: impl Display, url: &str) -> Self {
         ConnectorError {
             user_facing_error: None,
             kind: ErrorKind::Generic(format_err!(
                 "Could not parse the database connection string `{}`: {}",
-                url,
+                secret_string::redact(url),
                 err
             )),
         }
@@ -15,6 +15,8 @@ pub trait IntrospectionConnector: Send + Sync + 'static {
     async fn get_database_description(&self) -> ConnectorResult<String>;
 
     async fn introspect(&self) -> ConnectorResult<Datamodel>;
+
+    async fn list_routines(&self) -> ConnectorResult<Vec<RoutineSignature>>;
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -22,3 +24,21 @@ pub struct DatabaseMetadata {
     pub table_count: usize,
     pub size_in_bytes: usize,
 }
+
+/// A stored procedure or function with the database types of its parameters and return value.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RoutineSignature {
+    pub name: String,
+    /// `procedure` or `function`.
+    pub kind: String,
+    pub parameters: Vec<RoutineParameterSignature>,
+    pub return_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RoutineParameterSignature {
+    pub name: Option<String>,
+    /// `in`, `out` or `inOut`.
+    pub mode: String,
+    pub data_type: String,
+}
@@ -16,6 +16,7 @@ pub fn calculate_model(schema: &SqlSchema) -> SqlIntrospectionResult<Datamodel>
         .iter()
         .filter(|table| !is_migration_table(&table))
         .filter(|table| !is_prisma_join_table(&table))
+        .filter(|table| !is_audit_history_table(schema, &table))
     {
         debug!("Calculating model: {}", table.name);
         let mut model = Model::new(table.name.clone(), None);
@@ -62,6 +63,13 @@ pub fn calculate_model(schema: &SqlSchema) -> SqlIntrospectionResult<Datamodel>
             model.id_fields = table.primary_key_columns();
         }
 
+        model.table_options = calculate_table_options(&table.options);
+        model.is_audited = is_audited_table(schema, &table);
+
+        if let Some(partitioning) = &table.partitioning {
+            model.documentation = Some(calculate_partitioning_documentation(partitioning));
+        }
+
         data_model.add_model(model);
     }
 
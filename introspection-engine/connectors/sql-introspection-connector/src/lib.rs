@@ -6,9 +6,12 @@ mod sanitize_datamodel_names;
 mod schema_describer_loading;
 
 use datamodel::Datamodel;
-use introspection_connector::{ConnectorError, ConnectorResult, DatabaseMetadata, IntrospectionConnector};
+use introspection_connector::{
+    ConnectorError, ConnectorResult, DatabaseMetadata, IntrospectionConnector, RoutineParameterSignature,
+    RoutineSignature,
+};
 use quaint::prelude::ConnectionInfo;
-use sql_schema_describer::{SqlSchema, SqlSchemaDescriberBackend};
+use sql_schema_describer::{ParameterMode, RoutineKind, SqlSchema, SqlSchemaDescriberBackend};
 use std::future::Future;
 use tracing_futures::Instrument;
 
@@ -61,6 +64,35 @@ impl SqlIntrospectionConnector {
     async fn describe(&self) -> SqlIntrospectionResult<SqlSchema> {
         Ok(self.describer.describe(self.connection_info.schema_name()).await?)
     }
+
+    async fn list_routines_internal(&self) -> SqlIntrospectionResult<Vec<RoutineSignature>> {
+        let routines = self.describer.list_routines(self.connection_info.schema_name()).await?;
+
+        Ok(routines
+            .into_iter()
+            .map(|routine| RoutineSignature {
+                name: routine.name,
+                kind: match routine.kind {
+                    RoutineKind::Procedure => "procedure".to_owned(),
+                    RoutineKind::Function => "function".to_owned(),
+                },
+                parameters: routine
+                    .parameters
+                    .into_iter()
+                    .map(|parameter| RoutineParameterSignature {
+                        name: parameter.name,
+                        mode: match parameter.mode {
+                            ParameterMode::In => "in".to_owned(),
+                            ParameterMode::Out => "out".to_owned(),
+                            ParameterMode::InOut => "inOut".to_owned(),
+                        },
+                        data_type: parameter.data_type,
+                    })
+                    .collect(),
+                return_type: routine.return_type,
+            })
+            .collect())
+    }
 }
 
 #[async_trait::async_trait]
@@ -87,4 +119,8 @@ impl IntrospectionConnector for SqlIntrospectionConnector {
         tracing::debug!("Calculating datamodel is done: {:?}", sql_schema);
         Ok(data_model)
     }
+
+    async fn list_routines(&self) -> ConnectorResult<Vec<RoutineSignature>> {
+        Ok(self.catch(self.list_routines_internal()).await?)
+    }
 }
@@ -1,17 +1,36 @@
 use datamodel::common::names::NameNormalizer;
 use datamodel::{
-    DefaultValue, Field, FieldArity, FieldType, IndexDefinition, Model, OnDeleteStrategy, RelationInfo, ScalarType,
-    ScalarValue, ValueGenerator,
+    DefaultValue, Field, FieldArity, FieldType, IndexDefinition, IndexFieldOptions, Model, OnDeleteStrategy,
+    RelationInfo, ScalarType, ScalarValue, ValueGenerator,
 };
 use log::debug;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use sql_schema_describer::{Column, ColumnArity, ColumnTypeFamily, ForeignKey, Index, IndexType, SqlSchema, Table};
+use sql_schema_describer::{
+    Column, ColumnArity, ColumnTypeFamily, ForeignKey, Index, IndexType, Partitioning, SqlSchema, Table, TableOptions,
+};
 
 //checks
 
+/// The tables of the engines, including the idempotency keys the query engine stores.
 pub fn is_migration_table(table: &Table) -> bool {
-    table.name == "_Migration"
+    table.name == "_Migration" || table.name == "_Seed" || table.name == "_prisma_idempotency_keys"
+}
+
+/// The tables with the audit triggers the migration engine generates for `@@audited` models.
+pub(crate) fn is_audited_table(schema: &SqlSchema, table: &Table) -> bool {
+    schema
+        .triggers
+        .iter()
+        .any(|trigger| trigger.table == table.name && trigger.is_audit_trigger())
+}
+
+/// The history tables written by the audit triggers, they are part of the `@@audited` models.
+pub(crate) fn is_audit_history_table(schema: &SqlSchema, table: &Table) -> bool {
+    schema
+        .triggers
+        .iter()
+        .any(|trigger| trigger.is_audit_trigger() && trigger.history_table_name() == table.name)
 }
 
 pub(crate) fn is_prisma_join_table(table: &Table) -> bool {
@@ -50,6 +69,9 @@ pub fn calculate_many_to_many_field(foreign_key: &ForeignKey, relation_name: Str
         to: foreign_key.referenced_table.clone(),
         to_fields: foreign_key.referenced_columns.clone(),
         on_delete: OnDeleteStrategy::None,
+        deferrable: false,
+        join_table: None,
+        join_column: None,
     });
 
     let basename = foreign_key.referenced_table.camel_case();
@@ -70,20 +92,62 @@ pub fn calculate_many_to_many_field(foreign_key: &ForeignKey, relation_name: Str
         documentation: None,
         is_generated: false,
         is_updated_at: false,
+        computed: None,
+        native_type: None,
         data_source_fields: vec![],
     }
 }
 
+pub(crate) fn calculate_partitioning_documentation(partitioning: &Partitioning) -> String {
+    let mut documentation = format!("This table is partitioned by {}.", partitioning.key);
+
+    if !partitioning.partitions.is_empty() {
+        documentation.push_str(&format!(" Partitions: {}", partitioning.partitions.join(", ")));
+    }
+
+    documentation
+}
+
+/// Only carries the options that differ from what a table gets without them. The default character set depends on
+/// the server and database configuration, so it is left out.
+pub(crate) fn calculate_table_options(options: &TableOptions) -> datamodel::dml::TableOptions {
+    datamodel::dml::TableOptions {
+        engine: options
+            .engine
+            .clone()
+            .filter(|engine| !engine.eq_ignore_ascii_case("InnoDB")),
+        charset: None,
+        tablespace: options.tablespace.clone(),
+        fillfactor: options.fillfactor,
+    }
+}
+
 pub(crate) fn calculate_index(index: &Index) -> IndexDefinition {
     debug!("Handling index  {:?}", index);
     let tpe = match index.tpe {
         IndexType::Unique => datamodel::dml::IndexType::Unique,
         IndexType::Normal => datamodel::dml::IndexType::Normal,
     };
+    let field_options = index
+        .column_options
+        .iter()
+        .map(|(column, options)| {
+            let field_options = IndexFieldOptions {
+                sort_order: match options.sort_order {
+                    sql_schema_describer::SortOrder::Asc => datamodel::dml::SortOrder::Asc,
+                    sql_schema_describer::SortOrder::Desc => datamodel::dml::SortOrder::Desc,
+                },
+                length: options.length,
+            };
+
+            (column.clone(), field_options)
+        })
+        .collect();
     let index_definition: IndexDefinition = IndexDefinition {
         name: Some(index.name.clone()),
         fields: index.columns.clone(),
         tpe,
+        field_options,
     };
     index_definition
 }
@@ -94,6 +158,7 @@ pub(crate) fn calculate_compound_index(index: &Index, name: String) -> IndexDefi
         name: Some(index.name.clone()),
         fields: vec![name],
         tpe: datamodel::dml::IndexType::Normal,
+        field_options: Default::default(),
     }
 }
 
@@ -110,6 +175,11 @@ pub(crate) fn calculate_scalar_field(schema: &SqlSchema, table: &Table, column:
     let is_id = is_id(&column, &table);
     let default_value = calculate_default(&column, &arity);
     let is_unique = table.is_column_unique(&column.name) && !is_id;
+    let native_type = if column.tpe.is_citext() {
+        Some("Citext".to_owned())
+    } else {
+        None
+    };
 
     Field {
         name: column.name.clone(),
@@ -122,6 +192,8 @@ pub(crate) fn calculate_scalar_field(schema: &SqlSchema, table: &Table, column:
         documentation: None,
         is_generated: false,
         is_updated_at: false,
+        computed: None,
+        native_type,
         data_source_fields: vec![],
     }
 }
@@ -145,6 +217,9 @@ pub(crate) fn calculate_relation_field(schema: &SqlSchema, table: &Table, foreig
             to: foreign_key.referenced_table.clone(),
             to_fields: foreign_key.referenced_columns.clone(),
             on_delete: OnDeleteStrategy::None,
+            deferrable: foreign_key.deferrable,
+            join_table: None,
+            join_column: None,
         });
 
         let columns: Vec<&Column> = foreign_key
@@ -177,6 +252,8 @@ pub(crate) fn calculate_relation_field(schema: &SqlSchema, table: &Table, foreig
             documentation: None,
             is_generated: false,
             is_updated_at: false,
+            computed: None,
+            native_type: None,
             data_source_fields: vec![],
         }
     }
@@ -201,6 +278,9 @@ pub(crate) fn calculate_backrelation_field(
         to: model.name.clone(),
         to_fields: vec![relation_field.name.clone()],
         on_delete,
+        deferrable: false,
+        join_table: None,
+        join_column: None,
     });
 
     let other_is_unique = || match &relation_field.database_names.len() {
@@ -239,6 +319,8 @@ pub(crate) fn calculate_backrelation_field(
         documentation: None,
         is_generated: false,
         is_updated_at: false,
+        computed: None,
+        native_type: None,
         data_source_fields: vec![],
     }
 }
@@ -255,7 +337,12 @@ pub(crate) fn calculate_default(column: &Column, arity: &FieldArity) -> Option<D
             false => parse_int(d).map(|x| DefaultValue::Single(ScalarValue::Int(x))),
         },
         (Some(d), ColumnTypeFamily::Float) => parse_float(d).map(|x| DefaultValue::Single(ScalarValue::Float(x))),
+        (Some(d), ColumnTypeFamily::String) if is_uuid_function(d) => {
+            Some(DefaultValue::Expression(ValueGenerator::new_uuid()))
+        }
         (Some(d), ColumnTypeFamily::String) => Some(DefaultValue::Single(ScalarValue::String(d.to_string()))),
+        // The describer reads enum defaults back as the bare variant, e.g. `black` for `'black'::color`.
+        (Some(d), ColumnTypeFamily::Enum(_)) => Some(DefaultValue::Single(ScalarValue::ConstantLiteral(d.to_string()))),
         (Some(_), ColumnTypeFamily::DateTime) => None, //todo
         (None, _) if column.auto_increment => Some(DefaultValue::Expression(ValueGenerator::new_autoincrement())),
         (_, _) => None,
@@ -324,6 +411,9 @@ pub(crate) fn calculate_field_type(schema: &SqlSchema, column: &Column, table: &
                 to: fk.referenced_table.clone(),
                 to_fields: vec![referenced_col.clone()],
                 on_delete: OnDeleteStrategy::None,
+                deferrable: fk.deferrable,
+                join_table: None,
+                join_column: None,
             })
         }
         _ => {
@@ -335,6 +425,7 @@ pub(crate) fn calculate_field_type(schema: &SqlSchema, column: &Column, table: &
                 ColumnTypeFamily::Int => FieldType::Base(ScalarType::Int),
                 ColumnTypeFamily::String => FieldType::Base(ScalarType::String),
                 ColumnTypeFamily::Enum(name) => FieldType::Enum(name.clone()),
+                ColumnTypeFamily::Unknown => FieldType::Unsupported(column.tpe.raw.clone()),
                 // XXX: We made a conscious decision to punt on mapping of ColumnTypeFamily
                 // variants that don't yet have corresponding PrismaType variants
                 _ => FieldType::Base(ScalarType::String),
@@ -393,6 +484,14 @@ fn parse_int(value: &str) -> Option<i32> {
     }
 }
 
+/// The random UUID functions of Postgres, e.g. `(public.gen_random_uuid())`.
+static RE_UUID_FUNCTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\(?(?:[^.()]+\.)?(gen_random_uuid|uuid_generate_v4)\(\)\)?$").expect("compile regex"));
+
+fn is_uuid_function(value: &str) -> bool {
+    RE_UUID_FUNCTION.is_match(value)
+}
+
 fn parse_bool(value: &str) -> Option<bool> {
     debug!("Parsing bool '{}'", value);
     value.to_lowercase().parse().ok()
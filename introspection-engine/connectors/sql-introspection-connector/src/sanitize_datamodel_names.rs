@@ -55,6 +55,11 @@ pub fn sanitize_datamodel_names(datamodel: &mut Datamodel) {
 
         for index in &mut model.indices {
             index.fields = index.fields.iter().map(|f| sanitize_name(f.clone()).0).collect();
+            index.field_options = index
+                .field_options
+                .iter()
+                .map(|(f, options)| (sanitize_name(f.clone()).0, *options))
+                .collect();
         }
 
         model.name = sanitized_model_name;
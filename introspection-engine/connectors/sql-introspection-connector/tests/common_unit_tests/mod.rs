@@ -6,6 +6,7 @@ use datamodel::{
 use pretty_assertions::assert_eq;
 use sql_introspection_connector::calculate_datamodel::calculate_model;
 use sql_schema_describer::*;
+use std::collections::BTreeMap;
 
 #[test]
 fn a_data_model_can_be_generated_from_a_schema() {
@@ -35,6 +36,11 @@ fn a_data_model_can_be_generated_from_a_schema() {
             is_embedded: false,
             is_generated: false,
             is_commented_out: true,
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
+            table_options: dml::TableOptions::default(),
+            is_audited: false,
             indices: vec![],
             id_fields: vec![],
             fields: col_types
@@ -61,6 +67,8 @@ fn a_data_model_can_be_generated_from_a_schema() {
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     }
                 })
@@ -83,6 +91,8 @@ fn a_data_model_can_be_generated_from_a_schema() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 })
                 .collect(),
             indices: vec![],
@@ -91,9 +101,13 @@ fn a_data_model_can_be_generated_from_a_schema() {
                 sequence: None,
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -109,6 +123,11 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
             documentation: None,
             is_embedded: false,
             is_commented_out: false,
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
+            table_options: dml::TableOptions::default(),
+            is_audited: false,
             fields: vec![
                 Field {
                     name: "optional".to_string(),
@@ -121,6 +140,8 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -134,6 +155,8 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -147,6 +170,8 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
             ],
@@ -170,6 +195,8 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "required".to_string(),
@@ -180,6 +207,8 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: true,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "list".to_string(),
@@ -190,6 +219,8 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
             ],
             indices: vec![],
@@ -198,9 +229,13 @@ fn arity_is_preserved_when_generating_data_model_from_a_schema() {
                 sequence: None,
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -216,6 +251,11 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
             documentation: None,
             is_embedded: false,
             is_commented_out: false,
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
+            table_options: dml::TableOptions::default(),
+            is_audited: false,
             fields: vec![
                 Field {
                     name: "no_default".to_string(),
@@ -228,6 +268,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -241,6 +283,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -254,6 +298,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -267,6 +313,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -280,6 +328,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
             ],
@@ -288,6 +338,7 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                 name: Some("unique".into()),
                 fields: vec!["no_default".into(), "int_default".into()],
                 tpe: dml::IndexType::Unique,
+                field_options: BTreeMap::new(),
             }],
             id_fields: vec![],
         }],
@@ -307,6 +358,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "int_default".to_string(),
@@ -317,6 +370,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: Some("'1'".to_string()),
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "bool_default".to_string(),
@@ -327,6 +382,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: Some("'1'".to_string()),
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "float_default".to_string(),
@@ -337,6 +394,8 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: Some("'1.0'".to_string()),
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "string_default".to_string(),
@@ -347,18 +406,25 @@ fn defaults_are_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: Some("default".to_string()),
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
             ],
             indices: vec![Index {
                 name: "unique".to_string(),
                 columns: vec!["no_default".into(), "int_default".into()],
                 tpe: IndexType::Unique,
+                column_options: BTreeMap::new(),
             }],
             primary_key: None,
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -376,6 +442,11 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                 documentation: None,
                 is_embedded: false,
                 is_commented_out: false,
+                datasource: None,
+                soft_delete_field: None,
+                cache_ttl: None,
+                table_options: dml::TableOptions::default(),
+                is_audited: false,
                 fields: vec![Field {
                     name: "primary".to_string(),
                     arity: FieldArity::Required,
@@ -387,6 +458,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 }],
                 is_generated: false,
@@ -400,6 +473,11 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                 documentation: None,
                 is_embedded: false,
                 is_commented_out: false,
+                datasource: None,
+                soft_delete_field: None,
+                cache_ttl: None,
+                table_options: dml::TableOptions::default(),
+                is_audited: false,
                 fields: vec![Field {
                     name: "primary".to_string(),
                     arity: FieldArity::Required,
@@ -411,6 +489,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 }],
                 is_generated: false,
@@ -424,6 +504,11 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                 documentation: None,
                 is_embedded: false,
                 is_commented_out: false,
+                datasource: None,
+                soft_delete_field: None,
+                cache_ttl: None,
+                table_options: dml::TableOptions::default(),
+                is_audited: false,
                 fields: vec![Field {
                     name: "primary".to_string(),
                     arity: FieldArity::Required,
@@ -435,6 +520,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 }],
                 is_generated: false,
@@ -458,6 +545,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: true,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 }],
                 indices: vec![],
                 primary_key: Some(PrimaryKey {
@@ -465,6 +554,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     sequence: None,
                 }),
                 foreign_keys: vec![],
+                partitioning: None,
+                options: TableOptions::default(),
             },
             Table {
                 name: "Table2".to_string(),
@@ -477,6 +568,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 }],
                 indices: vec![],
                 primary_key: Some(PrimaryKey {
@@ -484,6 +577,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     sequence: None,
                 }),
                 foreign_keys: vec![],
+                partitioning: None,
+                options: TableOptions::default(),
             },
             Table {
                 name: "Table3".to_string(),
@@ -496,6 +591,8 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: true,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 }],
                 indices: vec![],
                 primary_key: Some(PrimaryKey {
@@ -507,10 +604,14 @@ fn primary_key_is_preserved_when_generating_data_model_from_a_schema() {
                     }),
                 }),
                 foreign_keys: vec![],
+                partitioning: None,
+                options: TableOptions::default(),
             },
         ],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -526,6 +627,11 @@ fn uniqueness_is_preserved_when_generating_data_model_from_a_schema() {
             documentation: None,
             is_embedded: false,
             is_commented_out: false,
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
+            table_options: dml::TableOptions::default(),
+            is_audited: false,
             fields: vec![
                 Field {
                     name: "non_unique".to_string(),
@@ -538,6 +644,8 @@ fn uniqueness_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -551,6 +659,8 @@ fn uniqueness_is_preserved_when_generating_data_model_from_a_schema() {
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
             ],
@@ -574,6 +684,8 @@ fn uniqueness_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "unique".to_string(),
@@ -584,18 +696,25 @@ fn uniqueness_is_preserved_when_generating_data_model_from_a_schema() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
             ],
             indices: vec![Index {
                 name: "unique".to_string(),
                 columns: vec!["unique".to_string()],
                 tpe: IndexType::Unique,
+                column_options: BTreeMap::new(),
             }],
             primary_key: None,
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -613,6 +732,11 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                 documentation: None,
                 is_embedded: false,
                 is_commented_out: false,
+                datasource: None,
+                soft_delete_field: None,
+                cache_ttl: None,
+                table_options: dml::TableOptions::default(),
+                is_audited: false,
                 fields: vec![
                     Field {
                         name: "id".to_string(),
@@ -625,6 +749,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                     Field {
@@ -638,6 +764,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                 ],
@@ -651,6 +779,11 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                 documentation: None,
                 is_embedded: false,
                 is_commented_out: false,
+                datasource: None,
+                soft_delete_field: None,
+                cache_ttl: None,
+                table_options: dml::TableOptions::default(),
+                is_audited: false,
                 fields: vec![
                     Field {
                         name: "id".to_string(),
@@ -663,6 +796,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                     Field {
@@ -673,6 +808,9 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                             to: "City".to_string(),
                             to_fields: vec!["id".to_string()],
                             on_delete: OnDeleteStrategy::None,
+                            deferrable: false,
+                            join_table: None,
+                            join_column: None,
                         }),
                         database_names: Vec::new(),
                         default_value: None,
@@ -681,6 +819,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                     Field {
@@ -691,6 +831,9 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                             to: "City".to_string(),
                             to_fields: vec!["name".to_string()],
                             on_delete: OnDeleteStrategy::None,
+                            deferrable: false,
+                            join_table: None,
+                            join_column: None,
                         }),
                         database_names: Vec::new(),
                         default_value: None,
@@ -699,6 +842,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                 ],
@@ -724,6 +869,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         },
                         default: None,
                         auto_increment: true,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                     Column {
                         name: "name".to_string(),
@@ -734,6 +881,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         },
                         default: None,
                         auto_increment: false,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                 ],
                 indices: vec![],
@@ -742,6 +891,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                     sequence: None,
                 }),
                 foreign_keys: vec![],
+                partitioning: None,
+                options: TableOptions::default(),
             },
             Table {
                 name: "User".to_string(),
@@ -755,6 +906,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         },
                         default: None,
                         auto_increment: true,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                     Column {
                         name: "city-id".to_string(),
@@ -765,6 +918,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         },
                         default: None,
                         auto_increment: false,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                     Column {
                         name: "city-name".to_string(),
@@ -775,6 +930,8 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                         },
                         default: None,
                         auto_increment: false,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                 ],
                 indices: vec![],
@@ -785,12 +942,17 @@ fn compound_foreign_keys_are_preserved_when_generating_data_model_from_a_schema(
                     columns: vec!["city-id".to_string(), "city-name".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::NoAction,
+                    deferrable: false,
                     referenced_columns: vec!["id".to_string(), "name".to_string()],
                 }],
+                partitioning: None,
+                options: TableOptions::default(),
             },
         ],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -806,6 +968,11 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
             documentation: None,
             is_embedded: false,
             is_commented_out: false,
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
+            table_options: dml::TableOptions::default(),
+            is_audited: false,
             fields: vec![
                 Field {
                     name: "id".to_string(),
@@ -818,6 +985,8 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -831,6 +1000,8 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
                 Field {
@@ -844,6 +1015,8 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
                     documentation: None,
                     is_generated: false,
                     is_updated_at: false,
+                    computed: None,
+                    native_type: None,
                     data_source_fields: vec![],
                 },
             ],
@@ -852,6 +1025,7 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
                 name: Some("name_last_name_unique".to_string()),
                 fields: vec!["name".to_string(), "lastname".to_string()],
                 tpe: datamodel::dml::IndexType::Unique,
+                field_options: BTreeMap::new(),
             }],
             id_fields: vec![],
         }],
@@ -871,6 +1045,8 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
                     },
                     default: None,
                     auto_increment: true,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "name".to_string(),
@@ -881,6 +1057,8 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "lastname".to_string(),
@@ -891,21 +1069,28 @@ fn multi_field_uniques_are_preserved_when_generating_data_model_from_a_schema()
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
             ],
             indices: vec![Index {
                 name: "name_last_name_unique".to_string(),
                 columns: vec!["name".to_string(), "lastname".to_string()],
                 tpe: IndexType::Unique,
+                column_options: BTreeMap::new(),
             }],
             primary_key: Some(PrimaryKey {
                 columns: vec!["id".to_string()],
                 sequence: None,
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -922,6 +1107,11 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                 documentation: None,
                 is_embedded: false,
                 is_commented_out: false,
+                datasource: None,
+                soft_delete_field: None,
+                cache_ttl: None,
+                table_options: dml::TableOptions::default(),
+                is_audited: false,
                 fields: vec![
                     Field {
                         name: "id".to_string(),
@@ -934,6 +1124,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                     Field {
@@ -947,6 +1139,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                     Field {
@@ -957,6 +1151,9 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                             to_fields: vec!["city_id".to_string()],
                             name: "CityToUser".to_string(),
                             on_delete: OnDeleteStrategy::None,
+                            deferrable: false,
+                            join_table: None,
+                            join_column: None,
                         }),
                         database_names: Vec::new(),
                         default_value: None,
@@ -965,6 +1162,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                 ],
@@ -978,6 +1177,11 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                 documentation: None,
                 is_embedded: false,
                 is_commented_out: false,
+                datasource: None,
+                soft_delete_field: None,
+                cache_ttl: None,
+                table_options: dml::TableOptions::default(),
+                is_audited: false,
                 fields: vec![
                     Field {
                         name: "id".to_string(),
@@ -990,6 +1194,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                     Field {
@@ -1000,6 +1206,9 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                             to: "City".to_string(),
                             to_fields: vec!["id".to_string()],
                             on_delete: OnDeleteStrategy::None,
+                            deferrable: false,
+                            join_table: None,
+                            join_column: None,
                         }),
                         database_names: Vec::new(),
                         default_value: None,
@@ -1008,6 +1217,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         documentation: None,
                         is_generated: false,
                         is_updated_at: false,
+                        computed: None,
+                        native_type: None,
                         data_source_fields: vec![],
                     },
                 ],
@@ -1033,6 +1244,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         },
                         default: None,
                         auto_increment: true,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                     Column {
                         name: "name".to_string(),
@@ -1043,6 +1256,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         },
                         default: None,
                         auto_increment: false,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                 ],
                 indices: vec![],
@@ -1051,6 +1266,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                     sequence: None,
                 }),
                 foreign_keys: vec![],
+                partitioning: None,
+                options: TableOptions::default(),
             },
             Table {
                 name: "User".to_string(),
@@ -1064,6 +1281,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         },
                         default: None,
                         auto_increment: true,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                     Column {
                         name: "city_id".to_string(),
@@ -1074,6 +1293,8 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                         },
                         default: None,
                         auto_increment: false,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                 ],
                 indices: vec![],
@@ -1086,12 +1307,17 @@ fn foreign_keys_are_preserved_when_generating_data_model_from_a_schema() {
                     columns: vec!["city_id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::NoAction,
+                    deferrable: false,
                     referenced_columns: vec!["id".to_string()],
                 }],
+                partitioning: None,
+                options: TableOptions::default(),
             },
         ],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
@@ -1118,8 +1344,72 @@ fn enums_are_preserved_when_generating_data_model_from_a_schema() {
             values: enum_values,
         }],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let data_model = calculate_model(&schema).expect("calculate data model");
 
     assert_eq!(data_model, ref_data_model);
 }
+
+#[test]
+fn audited_tables_are_introspected_without_their_history_table() {
+    let column = |name: &str, family: ColumnTypeFamily, arity: ColumnArity| Column {
+        name: name.to_string(),
+        tpe: ColumnType {
+            raw: "".to_string(),
+            family,
+            arity,
+        },
+        default: None,
+        auto_increment: false,
+        generated: None,
+        on_update_current_timestamp: false,
+    };
+    let table = |name: &str, columns: Vec<Column>, id: &str| Table {
+        name: name.to_string(),
+        columns,
+        indices: vec![],
+        primary_key: Some(PrimaryKey {
+            columns: vec![id.to_string()],
+            sequence: None,
+        }),
+        foreign_keys: vec![],
+        partitioning: None,
+        options: TableOptions::default(),
+    };
+
+    let schema = SqlSchema {
+        tables: vec![
+            table(
+                "Post",
+                vec![column("id", ColumnTypeFamily::Int, ColumnArity::Required)],
+                "id",
+            ),
+            table(
+                "Post_history",
+                vec![
+                    column("changed_at", ColumnTypeFamily::DateTime, ColumnArity::Required),
+                    column("history_id", ColumnTypeFamily::Int, ColumnArity::Required),
+                    column("operation", ColumnTypeFamily::String, ColumnArity::Required),
+                    column("row_data", ColumnTypeFamily::Json, ColumnArity::Nullable),
+                ],
+                "history_id",
+            ),
+        ],
+        enums: vec![],
+        sequences: vec![],
+        views: vec![],
+        triggers: vec![Trigger {
+            name: "Post_audit".to_string(),
+            table: "Post".to_string(),
+            definition: "CREATE TRIGGER \"Post_audit\" AFTER INSERT OR UPDATE OR DELETE ON public.\"Post\" FOR EACH ROW EXECUTE PROCEDURE prisma_audit()".to_string(),
+            columns: vec![],
+        }],
+    };
+    let data_model = calculate_model(&schema).expect("calculate data model");
+
+    assert_eq!(data_model.models.len(), 1);
+    assert_eq!(data_model.models[0].name, "Post");
+    assert!(data_model.models[0].is_audited);
+}
@@ -278,6 +278,37 @@ async fn introspecting_a_table_enums_should_work(api: &TestApi) {
     custom_assert(&result4, dm);
 }
 
+#[test_each_connector(tags("postgres"))]
+async fn introspecting_a_table_with_an_enum_default_should_work(api: &TestApi) {
+    let sql = format!("CREATE Type color as ENUM ( 'black', 'white')");
+
+    api.database().execute_raw(&sql, &[]).await.unwrap();
+
+    api.barrel()
+        .execute(|migration| {
+            migration.create_table("Book", |t| {
+                t.add_column("id", types::primary());
+                t.inject_custom("color  color Not Null Default 'white'");
+            });
+        })
+        .await;
+
+    let dm = r#"
+        model Book {
+            color   color   @default(white)
+            id      Int     @default(autoincrement()) @id
+        }
+
+        enum color{
+            black
+            white
+        }
+    "#;
+
+    let result = dbg!(api.introspect().await);
+    custom_assert(&result, dm);
+}
+
 #[test_each_connector(tags("postgres"))]
 async fn introspecting_a_table_enums_should_return_alphabetically_even_when_in_different_order(api: &TestApi) {
     let sql1 = format!("CREATE Type color as ENUM ( 'black', 'white')");
@@ -381,3 +412,87 @@ async fn introspecting_a_table_without_uniques_should_comment_it_out(api: &TestA
     let result = dbg!(api.introspect().await);
     assert_eq!(&result, dm);
 }
+
+#[test_each_connector(tags("postgres"))]
+async fn introspecting_a_partitioned_table_should_only_return_the_parent(api: &TestApi) {
+    let sql = format!(
+        "CREATE TABLE \"{0}\".\"Measurement\" (id INT NOT NULL, logdate DATE NOT NULL, CONSTRAINT measurement_unique UNIQUE (id, logdate)) PARTITION BY RANGE (logdate)",
+        api.schema_name()
+    );
+    let sql2 = format!(
+        "CREATE TABLE \"{0}\".\"measurement_y2019\" PARTITION OF \"{0}\".\"Measurement\" FOR VALUES FROM ('2019-01-01') TO ('2020-01-01')",
+        api.schema_name()
+    );
+    let sql3 = format!(
+        "CREATE TABLE \"{0}\".\"measurement_y2020\" PARTITION OF \"{0}\".\"Measurement\" FOR VALUES FROM ('2020-01-01') TO ('2021-01-01')",
+        api.schema_name()
+    );
+
+    api.database().execute_raw(&sql, &[]).await.unwrap();
+    api.database().execute_raw(&sql2, &[]).await.unwrap();
+    api.database().execute_raw(&sql3, &[]).await.unwrap();
+
+    let dm = r#"
+        /// This table is partitioned by RANGE (logdate). Partitions: measurement_y2019, measurement_y2020
+        model Measurement {
+            id      Int
+            logdate DateTime
+            @@unique([id, logdate], name: "measurement_unique")
+        }
+    "#;
+
+    let result = dbg!(api.introspect().await);
+    custom_assert(&result, dm);
+}
+
+#[test_each_connector(tags("postgres"))]
+async fn introspecting_a_table_with_unsupported_types_must_work(api: &TestApi) {
+    let sql = format!(
+        "CREATE TABLE \"{0}\".\"Network\" (id SERIAL PRIMARY KEY, address inet NOT NULL, mask cidr)",
+        api.schema_name()
+    );
+
+    api.database().execute_raw(&sql, &[]).await.unwrap();
+
+    let dm = r#"
+        model Network {
+            address Unsupported("inet")
+            id      Int                  @id @default(autoincrement())
+            mask    Unsupported("cidr")?
+        }
+    "#;
+
+    let result = dbg!(api.introspect().await);
+    custom_assert(&result, dm);
+}
+
+#[test_each_connector(tags("postgres"))]
+async fn introspecting_a_table_with_citext_columns_must_work(api: &TestApi) {
+    api.database()
+        .execute_raw("CREATE EXTENSION IF NOT EXISTS citext", &[])
+        .await
+        .unwrap();
+
+    let sql = format!(
+        "CREATE TABLE \"{0}\".\"User\" (id SERIAL PRIMARY KEY, email citext NOT NULL UNIQUE, name text)",
+        api.schema_name()
+    );
+
+    api.database().execute_raw(&sql, &[]).await.unwrap();
+
+    let dm = r#"
+        generator client {
+            provider        = "prisma-client-js"
+            previewFeatures = ["nativeTypes"]
+        }
+
+        model User {
+            email String  @unique @db.Citext
+            id    Int     @id @default(autoincrement())
+            name  String?
+        }
+    "#;
+
+    let result = dbg!(api.introspect().await);
+    custom_assert(&result, dm);
+}
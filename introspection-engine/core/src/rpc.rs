@@ -1,8 +1,9 @@
 use crate::command_error::CommandError;
 use crate::error::Error;
 use crate::error_rendering::render_jsonrpc_error;
+use datamodel::preview_features;
 use futures::{FutureExt, TryFutureExt};
-use introspection_connector::{DatabaseMetadata, IntrospectionConnector};
+use introspection_connector::{DatabaseMetadata, IntrospectionConnector, RoutineSignature};
 use jsonrpc_derive::rpc;
 use serde_derive::*;
 use sql_introspection_connector::SqlIntrospectionConnector;
@@ -24,6 +25,12 @@ pub trait Rpc {
 
     #[rpc(name = "introspect")]
     fn introspect(&self, input: IntrospectionInput) -> RpcFutureResult<String>;
+
+    #[rpc(name = "listRoutines")]
+    fn list_routines(&self, input: IntrospectionInput) -> RpcFutureResult<Vec<RoutineSignature>>;
+
+    #[rpc(name = "serverInfo")]
+    fn server_info(&self, input: IntrospectionInput) -> RpcResult<ServerInfo>;
 }
 
 pub(crate) struct RpcImpl;
@@ -44,6 +51,14 @@ impl Rpc for RpcImpl {
     fn introspect(&self, input: IntrospectionInput) -> RpcFutureResult<String> {
         Box::new(Self::introspect_internal(input.schema).boxed().compat())
     }
+
+    fn list_routines(&self, input: IntrospectionInput) -> RpcFutureResult<Vec<RoutineSignature>> {
+        Box::new(Self::list_routines_internal(input.schema).boxed().compat())
+    }
+
+    fn server_info(&self, input: IntrospectionInput) -> RpcResult<ServerInfo> {
+        Self::server_info_internal(input.schema)
+    }
 }
 
 impl RpcImpl {
@@ -60,7 +75,7 @@ impl RpcImpl {
             .url()
             .to_owned()
             .value;
-        Ok(Box::new(SqlIntrospectionConnector::new(&url).await?))
+        Ok(Box::new(SqlIntrospectionConnector::new(url.expose()).await?))
     }
 
     pub(crate) async fn introspect_internal(schema: String) -> RpcResult<String> {
@@ -80,7 +95,18 @@ impl RpcImpl {
             Ok(dm) if dm.models.is_empty() && dm.enums.is_empty() => Err(render_jsonrpc_error(Error::from(
                 CommandError::IntrospectionResultEmpty(url.to_string()),
             ))),
-            Ok(dm) => Ok(datamodel::render_datamodel_and_config_to_string(&dm, &config).map_err(Error::from)?),
+            Ok(mut dm) => {
+                // Native types can only be rendered into schemas that enable them.
+                if !config.preview_features().contains(&preview_features::NATIVE_TYPES) {
+                    for model in dm.models_mut() {
+                        for field in model.fields_mut() {
+                            field.native_type = None;
+                        }
+                    }
+                }
+
+                Ok(datamodel::render_datamodel_and_config_to_string(&dm, &config).map_err(Error::from)?)
+            }
             Err(e) => Err(render_jsonrpc_error(Error::from(e))),
         }
     }
@@ -99,9 +125,42 @@ impl RpcImpl {
         let connector = RpcImpl::load_connector(&schema).await?;
         Ok(connector.get_metadata().await.map_err(Error::from)?)
     }
+
+    pub(crate) async fn list_routines_internal(schema: String) -> RpcResult<Vec<RoutineSignature>> {
+        let connector = RpcImpl::load_connector(&schema).await?;
+        Ok(connector.list_routines().await.map_err(Error::from)?)
+    }
+
+    /// The connector is the one of the datasource of the schema, the database is not connected.
+    pub(crate) fn server_info_internal(schema: String) -> RpcResult<ServerInfo> {
+        let config = datamodel::parse_configuration(&schema).map_err(Error::from)?;
+        let connector = config
+            .datasources
+            .first()
+            .ok_or_else(|| CommandError::Generic(anyhow::anyhow!("There is no datasource in the schema.")))
+            .map_err(Error::from)?
+            .connector_type()
+            .to_owned();
+
+        Ok(ServerInfo {
+            commit: env!("GIT_HASH"),
+            version: env!("CARGO_PKG_VERSION"),
+            connector,
+            features: config.preview_features().into_iter().map(String::from).collect(),
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IntrospectionInput {
     pub(crate) schema: String,
 }
+
+/// Version of the engine, the connector and the enabled features, so that clients can gate behavior on them.
+#[derive(Debug, Serialize)]
+pub struct ServerInfo {
+    pub commit: &'static str,
+    pub version: &'static str,
+    pub connector: String,
+    pub features: Vec<String>,
+}
@@ -43,9 +43,11 @@ impl Argument {
     }
 
     pub fn new_function(name: &str, fn_name: &str, value: Vec<Expression>) -> Argument {
+        let args = value.into_iter().map(|value| Argument::new("", value)).collect();
+
         Argument {
             name: Identifier::new(name),
-            value: Expression::Function(fn_name.to_string(), value, Span::empty()),
+            value: Expression::Function(fn_name.to_string(), args, Span::empty()),
             span: Span::empty(),
         }
     }
@@ -15,8 +15,9 @@ pub enum Expression {
     /// Any literal constant, basically a string which was not inside "...".
     /// This is used for representing builtin enums.
     ConstantValue(String, Span),
-    /// A function with a name and arguments, which is evaluated at client side.
-    Function(String, Vec<Expression>, Span),
+    /// A function with a name and arguments, which is evaluated at client side. Unnamed arguments have an empty
+    /// name, like the unnamed arguments of directives.
+    Function(String, Vec<Argument>, Span),
     /// An array of other values.
     Array(Vec<Expression>, Span),
 }
@@ -30,7 +31,13 @@ impl Expression {
             Expression::ConstantValue(v, s) => Expression::ConstantValue(v.clone(), lift_span(&s, offset)),
             Expression::Function(v, a, s) => Expression::Function(
                 v.clone(),
-                a.iter().map(|elem| elem.with_lifted_span(offset)).collect(),
+                a.iter()
+                    .map(|arg| Argument {
+                        name: arg.name.clone(),
+                        value: arg.value.with_lifted_span(offset),
+                        span: lift_span(&arg.span, offset),
+                    })
+                    .collect(),
                 lift_span(&s, offset),
             ),
             Expression::Array(v, s) => Expression::Array(
@@ -50,11 +50,18 @@ pub fn parse_expression(token: &pest::iterators::Pair<'_, Rule>) -> Expression {
 
 fn parse_function(token: &pest::iterators::Pair<'_, Rule>) -> Expression {
     let mut name: Option<String> = None;
-    let mut arguments: Vec<Expression> = vec![];
+    let mut arguments: Vec<Argument> = vec![];
 
     match_children! { token, current,
         Rule::identifier => name = Some(current.as_str().to_string()),
-        Rule::argument_value => arguments.push(parse_arg_value(&current)),
+        // This is a named arg.
+        Rule::argument => arguments.push(parse_directive_arg(&current)),
+        // This is a an unnamed arg.
+        Rule::argument_value => arguments.push(Argument {
+            name: Identifier::new(""),
+            value: parse_arg_value(&current),
+            span: Span::from_pest(current.as_span())
+        }),
         _ => unreachable!("Encountered impossible function during parsing: {:?}", current.tokens())
     };
 
@@ -169,10 +176,19 @@ fn parse_directive(token: &pest::iterators::Pair<'_, Rule>) -> Directive {
 fn parse_base_type(token: &pest::iterators::Pair<'_, Rule>) -> String {
     match_first! { token, current,
         Rule::identifier => current.as_str().to_string(),
+        Rule::unsupported_type => parse_unsupported_type(&current),
         _ => unreachable!("Encountered impossible type during parsing: {:?}", current.tokens())
     }
 }
 
+/// Unsupported types are kept as type name in the form `Unsupported("<database type>")`, without whitespace.
+pub(crate) fn parse_unsupported_type(token: &pest::iterators::Pair<'_, Rule>) -> String {
+    match_first! { token, current,
+        Rule::string_literal => format!("Unsupported(\"{}\")", parse_string_literal(&current)),
+        _ => unreachable!("Encountered impossible unsupported type during parsing: {:?}", current.tokens())
+    }
+}
+
 fn parse_field_type(token: &pest::iterators::Pair<'_, Rule>) -> Result<(FieldArity, String), DatamodelError> {
     match_first! { token, current,
         Rule::optional_type => Ok((FieldArity::Optional, parse_base_type(&current))),
@@ -503,6 +519,7 @@ fn rule_to_string(rule: Rule) -> &'static str {
         Rule::optional_type => "optional type",
         Rule::base_type => "type",
         Rule::list_type => "list type",
+        Rule::unsupported_type => "unsupported type",
         Rule::field_type => "field type",
         Rule::field_declaration => "field declaration",
         Rule::type_declaration => "type declaration",
@@ -385,8 +385,10 @@ impl Reformatter {
 
     fn get_identifier(token: &Token) -> String {
         for current in token.clone().into_inner() {
-            if let Rule::identifier = current.as_rule() {
-                return current.as_str().to_string();
+            match current.as_rule() {
+                Rule::identifier => return current.as_str().to_string(),
+                Rule::unsupported_type => return parse_unsupported_type(&current),
+                _ => {}
             }
         }
 
@@ -527,6 +529,13 @@ impl Reformatter {
                     target.write(current.as_str());
                     target.write("(");
                 }
+                Rule::argument => {
+                    if expr_count > 0 {
+                        target.write(", ");
+                    }
+                    Self::reformat_directive_arg(target, &current);
+                    expr_count += 1;
+                }
                 Rule::argument_value => {
                     if expr_count > 0 {
                         target.write(", ");
@@ -318,15 +318,20 @@ impl<'a> Renderer<'a> {
         };
     }
 
-    fn render_func(target: &mut dyn LineWriteable, name: &str, vals: &[ast::Expression]) {
+    fn render_func(target: &mut dyn LineWriteable, name: &str, args: &[ast::Argument]) {
         target.write(name);
         target.write("(");
-        for (idx, val) in vals.iter().enumerate() {
+        for (idx, arg) in args.iter().enumerate() {
             if idx > 0 {
                 target.write(", ");
             }
 
-            Self::render_value(target, val);
+            if !arg.name.name.is_empty() {
+                target.write(&arg.name.name);
+                target.write(": ");
+            }
+
+            Self::render_value(target, &arg.value);
         }
         target.write(")");
     }
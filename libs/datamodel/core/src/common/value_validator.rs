@@ -5,6 +5,7 @@ use crate::{dml, ValueGenerator};
 use super::FromStrAndSpan;
 use super::ScalarType;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use std::error;
 
 /// Wraps a value and provides convenience methods for
@@ -123,12 +124,11 @@ impl ValueValidator {
         }
     }
 
-    // TODO: Ask which decimal type to take.
-    /// Tries to convert the wrapped value to a Prisma Decimal.
-    pub fn as_decimal(&self) -> Result<f32, DatamodelError> {
+    /// Tries to convert the wrapped value to a Prisma Decimal, without losing precision.
+    pub fn as_decimal(&self) -> Result<Decimal, DatamodelError> {
         match &self.value {
-            ast::Expression::NumericValue(value, _) => self.wrap_error_from_result(value.parse::<f32>(), "numeric"),
-            ast::Expression::Any(value, _) => self.wrap_error_from_result(value.parse::<f32>(), "numeric"),
+            ast::Expression::NumericValue(value, _) => self.wrap_error_from_result(value.parse::<Decimal>(), "numeric"),
+            ast::Expression::Any(value, _) => self.wrap_error_from_result(value.parse::<Decimal>(), "numeric"),
             _ => Err(self.construct_type_mismatch_error("numeric")),
         }
     }
@@ -187,6 +187,14 @@ impl ValueValidator {
         }
     }
 
+    /// Unwraps the wrapped value as a function call, returning its name and arguments.
+    pub fn as_function(&self) -> Result<(String, Vec<ast::Argument>), DatamodelError> {
+        match &self.value {
+            ast::Expression::Function(name, args, _) => Ok((name.to_string(), args.clone())),
+            _ => Err(self.construct_type_mismatch_error("function")),
+        }
+    }
+
     /// Unwraps the wrapped value as a constant literal..
     pub fn as_array(&self) -> Result<Vec<ValueValidator>, DatamodelError> {
         match &self.value {
@@ -1,7 +1,7 @@
 use crate::{
     ast,
     common::{arguments::Arguments, value_validator::ValueListValidator},
-    configuration::Generator,
+    configuration::{preview_features::PREVIEW_FEATURES, Generator},
     error::*,
 };
 use std::collections::HashMap;
@@ -9,7 +9,8 @@ use std::collections::HashMap;
 const PROVIDER_KEY: &str = "provider";
 const OUTPUT_KEY: &str = "output";
 const BINARY_TARGETS_KEY: &str = "binaryTargets";
-const FIRST_CLASS_PROPERTIES: &[&str] = &[PROVIDER_KEY, OUTPUT_KEY, BINARY_TARGETS_KEY];
+const PREVIEW_FEATURES_KEY: &str = "previewFeatures";
+const FIRST_CLASS_PROPERTIES: &[&str] = &[PROVIDER_KEY, OUTPUT_KEY, BINARY_TARGETS_KEY, PREVIEW_FEATURES_KEY];
 
 pub struct GeneratorLoader {}
 
@@ -53,6 +54,24 @@ impl GeneratorLoader {
             None => Vec::new(),
         };
 
+        let mut preview_features = Vec::new();
+
+        if let Ok(arg) = args.arg(PREVIEW_FEATURES_KEY) {
+            for value in arg.as_array()? {
+                let feature = value.as_str()?;
+
+                if !PREVIEW_FEATURES.contains(&feature.as_str()) {
+                    return Err(DatamodelError::new_preview_feature_not_known_error(
+                        &feature,
+                        PREVIEW_FEATURES,
+                        value.span(),
+                    ));
+                }
+
+                preview_features.push(feature);
+            }
+        }
+
         for prop in &ast_generator.properties {
             let is_first_class_prop = FIRST_CLASS_PROPERTIES.iter().any(|k| *k == prop.name.name);
             if is_first_class_prop {
@@ -67,6 +86,7 @@ impl GeneratorLoader {
             provider,
             output,
             binary_targets,
+            preview_features,
             config: properties,
             documentation: ast_generator.documentation.clone().map(|comment| comment.text),
         })
@@ -103,6 +123,15 @@ impl GeneratorLoader {
             arguments.push(ast::Argument::new_array("binaryTargets", platform_values));
         }
 
+        let preview_features: Vec<ast::Expression> = generator
+            .preview_features
+            .iter()
+            .map(|feature| ast::Expression::StringValue(feature.to_string(), ast::Span::empty()))
+            .collect();
+        if !preview_features.is_empty() {
+            arguments.push(ast::Argument::new_array(PREVIEW_FEATURES_KEY, preview_features));
+        }
+
         for (key, value) in &generator.config {
             arguments.push(ast::Argument::new_string(&key, &value));
         }
@@ -12,8 +12,17 @@ pub struct Generator {
     output: Option<String>,
     #[serde(default = "Vec::new")]
     binary_targets: Vec<String>,
+    #[serde(default = "Vec::new")]
+    preview_features: Vec<String>,
     // Todo: This is a bad choice, PrismaValue is probably better.
     config: HashMap<String, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     documentation: Option<String>,
 }
+
+impl Generator {
+    /// The experimental features this generator enables, see `preview_features`.
+    pub fn preview_features(&self) -> &[String] {
+        &self.preview_features
+    }
+}
@@ -1,9 +1,11 @@
 mod generator;
+pub mod preview_features;
 mod source;
 
 pub use generator::*;
 pub use source::*;
 
+use secret_string::SecretString;
 use serde::{Deserialize, Serialize};
 
 pub struct Configuration {
@@ -11,9 +13,41 @@ pub struct Configuration {
     pub datasources: Vec<Box<dyn Source + Send + Sync>>,
 }
 
+impl Configuration {
+    /// The preview features enabled by any of the generators.
+    pub fn preview_features(&self) -> Vec<&str> {
+        preview_features_of(&self.generators)
+    }
+}
+
+/// The preview features enabled by any of the generators, without duplicates.
+pub(crate) fn preview_features_of(generators: &[Generator]) -> Vec<&str> {
+    let mut features: Vec<&str> = generators
+        .iter()
+        .flat_map(|generator| generator.preview_features().iter().map(String::as_str))
+        .collect();
+
+    features.sort();
+    features.dedup();
+    features
+}
+
 #[serde(rename_all = "camelCase")]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StringFromEnvVar {
     pub from_env_var: Option<String>,
-    pub value: String,
+    /// The connection string of a datasource, masked when printed. It is serialized in full, the configuration is
+    /// handed to the clients connecting with it.
+    #[serde(serialize_with = "secret_string::serialize_exposed")]
+    pub value: SecretString,
+}
+
+/// Replaces the URL of a datasource, e.g. to run the same data model against a test database.
+/// The `url` argument of the replaced datasource is not evaluated, so an environment variable it reads doesn't
+/// need to be set.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SourceOverride {
+    /// Name of the datasource block.
+    pub name: String,
+    pub url: SecretString,
 }
@@ -0,0 +1,11 @@
+//! Experimental features, only available if a generator block enables them with `previewFeatures`.
+
+/// Native database types of fields, e.g. `@db.Citext`.
+pub const NATIVE_TYPES: &str = "nativeTypes";
+
+/// Database defaults calling the UUID function of the database for `@default(uuid())` fields, where the database
+/// has one.
+pub const DATABASE_UUID_DEFAULTS: &str = "databaseUuidDefaults";
+
+/// All known preview features. Unknown ones are rejected when the generator blocks are loaded.
+pub const PREVIEW_FEATURES: &[&str] = &[NATIVE_TYPES, DATABASE_UUID_DEFAULTS];
@@ -6,6 +6,7 @@ pub const MYSQL_SOURCE_NAME: &str = "mysql";
 pub struct MySqlSource {
     pub(super) name: String,
     pub(super) url: StringFromEnvVar,
+    pub(super) relation_mode: RelationMode,
     pub(super) documentation: Option<String>,
 }
 
@@ -25,7 +26,7 @@ impl Source for MySqlSource {
     fn set_url(&mut self, url: &str) {
         self.url = StringFromEnvVar {
             from_env_var: None,
-            value: url.to_string(),
+            value: url.into(),
         };
     }
 
@@ -33,6 +34,10 @@ impl Source for MySqlSource {
         &self.documentation
     }
 
+    fn relation_mode(&self) -> RelationMode {
+        self.relation_mode
+    }
+
     fn connector(&self) -> Box<dyn Connector> {
         Box::new(ExampleConnector::empty())
     }
@@ -18,11 +18,13 @@ impl SourceDefinition for MySqlSourceDefinition {
         &self,
         name: &str,
         url: StringFromEnvVar,
+        relation_mode: RelationMode,
         documentation: &Option<String>,
     ) -> Result<Box<dyn Source + Send + Sync>, DatamodelError> {
         Ok(Box::new(MySqlSource {
             name: String::from(name),
             url: url,
+            relation_mode,
             documentation: documentation.clone(),
         }))
     }
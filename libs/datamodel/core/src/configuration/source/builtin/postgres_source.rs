@@ -6,6 +6,7 @@ pub const POSTGRES_SOURCE_NAME: &str = "postgresql";
 pub struct PostgresSource {
     pub(super) name: String,
     pub(super) url: StringFromEnvVar,
+    pub(super) relation_mode: RelationMode,
     pub(super) documentation: Option<String>,
 }
 
@@ -23,7 +24,7 @@ impl Source for PostgresSource {
     fn set_url(&mut self, url: &str) {
         self.url = StringFromEnvVar {
             from_env_var: None,
-            value: url.to_string(),
+            value: url.into(),
         };
     }
 
@@ -31,6 +32,10 @@ impl Source for PostgresSource {
         &self.documentation
     }
 
+    fn relation_mode(&self) -> RelationMode {
+        self.relation_mode
+    }
+
     fn connector(&self) -> Box<dyn Connector> {
         Box::new(ExampleConnector::postgres())
     }
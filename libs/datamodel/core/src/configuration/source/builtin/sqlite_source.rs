@@ -6,6 +6,7 @@ pub const SQLITE_SOURCE_NAME: &str = "sqlite";
 pub struct SqliteSource {
     pub(super) name: String,
     pub(super) url: StringFromEnvVar,
+    pub(super) relation_mode: RelationMode,
     pub(super) documentation: Option<String>,
 }
 
@@ -24,7 +25,7 @@ impl Source for SqliteSource {
     fn set_url(&mut self, url: &str) {
         self.url = StringFromEnvVar {
             from_env_var: None,
-            value: url.to_string(),
+            value: url.into(),
         };
     }
 
@@ -32,6 +33,10 @@ impl Source for SqliteSource {
         &self.documentation
     }
 
+    fn relation_mode(&self) -> RelationMode {
+        self.relation_mode
+    }
+
     fn connector(&self) -> Box<dyn Connector> {
         Box::new(ExampleConnector::empty())
     }
@@ -18,11 +18,13 @@ impl SourceDefinition for SqliteSourceDefinition {
         &self,
         name: &str,
         url: StringFromEnvVar,
+        relation_mode: RelationMode,
         documentation: &Option<String>,
     ) -> Result<Box<dyn Source + Send + Sync>, DatamodelError> {
         Ok(Box::new(SqliteSource {
             name: String::from(name),
             url: url,
+            relation_mode,
             documentation: documentation.clone(),
         }))
     }
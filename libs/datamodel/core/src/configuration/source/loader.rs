@@ -1,25 +1,42 @@
 use super::{
+    super::SourceOverride,
     builtin::{MySqlSourceDefinition, PostgresSourceDefinition, SqliteSourceDefinition},
     traits::{Source, SourceDefinition},
+    RelationMode,
 };
 use crate::ast;
-use crate::common::arguments::Arguments;
+use crate::common::{arguments::Arguments, FromStrAndSpan};
 use crate::error::{DatamodelError, ErrorCollection};
 use crate::StringFromEnvVar;
+use std::sync::Arc;
 
 /// Helper struct to load and validate source configuration blocks.
 pub struct SourceLoader {
-    source_declarations: Vec<Box<dyn SourceDefinition>>,
+    source_declarations: Vec<Arc<dyn SourceDefinition + Send + Sync>>,
+    overrides: Vec<SourceOverride>,
 }
 
 impl SourceLoader {
-    /// Creates a new, empty source loader.
+    /// Creates a new source loader for the built-in and registered sources.
     pub fn new() -> Self {
+        #[allow(unused_mut)]
+        let mut source_declarations = get_builtin_sources();
+
+        #[cfg(feature = "connector-registry")]
+        source_declarations.extend(super::registry::registered_source_definitions());
+
         Self {
-            source_declarations: get_builtin_sources(),
+            source_declarations,
+            overrides: Vec::new(),
         }
     }
 
+    /// Replaces the URLs of the given datasources. A later override of the same datasource wins.
+    pub fn with_overrides(mut self, overrides: &[SourceOverride]) -> Self {
+        self.overrides.extend_from_slice(overrides);
+        self
+    }
+
     /// Loads all source config blocks form the given AST,
     /// and returns a Source instance for each.
     pub fn load_sources(
@@ -56,10 +73,20 @@ impl SourceLoader {
         ignore_env_var_errors: bool,
     ) -> Result<Option<Box<dyn Source + Send + Sync>>, DatamodelError> {
         let mut args = Arguments::new(&ast_source.properties, ast_source.span);
-        let (env_var_for_url, url) = match args.arg("url")?.as_str_from_env() {
-            Ok((env_var, url)) => (env_var, url),
-            Err(_) if ignore_env_var_errors => (None, "dummy://url".to_owned()), // the flag is only used by the vs code plugin
-            Err(err) => return Err(err),
+        let url_arg = args.arg("url")?;
+        let url_override = self
+            .overrides
+            .iter()
+            .rev()
+            .find(|source_override| source_override.name == ast_source.name.name);
+
+        let (env_var_for_url, url) = match url_override {
+            Some(source_override) => (None, source_override.url.expose().to_owned()),
+            None => match url_arg.as_str_from_env() {
+                Ok((env_var, url)) => (env_var, url),
+                Err(_) if ignore_env_var_errors => (None, "dummy://url".to_owned()), // the flag is only used by the vs code plugin
+                Err(err) => return Err(err),
+            },
         };
         let provider_arg = args.arg("provider")?;
         let provider = provider_arg.as_str()?;
@@ -71,6 +98,11 @@ impl SourceLoader {
             ));
         }
 
+        let relation_mode = match args.arg("relationMode") {
+            Ok(arg) => RelationMode::from_str_and_span(&arg.as_str()?, arg.span())?,
+            Err(_) => RelationMode::default(),
+        };
+
         let is_enabled = match args.arg("enabled") {
             Ok(arg) => {
                 let (env_var, is_enabled) = arg.as_bool_from_env()?;
@@ -103,8 +135,9 @@ impl SourceLoader {
                     &ast_source.name.name,
                     StringFromEnvVar {
                         from_env_var: env_var_for_url,
-                        value: url,
+                        value: url.into(),
                     },
+                    relation_mode,
                     &ast_source.documentation.clone().map(|comment| comment.text),
                 )?));
             }
@@ -117,10 +150,10 @@ impl SourceLoader {
     }
 }
 
-fn get_builtin_sources() -> Vec<Box<dyn SourceDefinition>> {
+fn get_builtin_sources() -> Vec<Arc<dyn SourceDefinition + Send + Sync>> {
     vec![
-        Box::new(MySqlSourceDefinition::new()),
-        Box::new(PostgresSourceDefinition::new()),
-        Box::new(SqliteSourceDefinition::new()),
+        Arc::new(MySqlSourceDefinition::new()),
+        Arc::new(PostgresSourceDefinition::new()),
+        Arc::new(SqliteSourceDefinition::new()),
     ]
 }
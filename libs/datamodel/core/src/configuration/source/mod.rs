@@ -1,12 +1,19 @@
 mod loader;
+mod relation_mode;
 mod serializer;
 mod traits;
 
+#[cfg(feature = "connector-registry")]
+mod registry;
+
 pub mod builtin;
 
 // TODO: i think these constants should move to a more central place.
 pub use builtin::{MYSQL_SOURCE_NAME, POSTGRES_SOURCE_NAME, SQLITE_SOURCE_NAME};
 //pub use json::{render_sources_to_json, render_sources_to_json_value, sources_from_json_value_with_plugins};
 pub use loader::*;
+#[cfg(feature = "connector-registry")]
+pub use registry::register_source_definition;
+pub use relation_mode::RelationMode;
 pub use serializer::*;
 pub use traits::*;
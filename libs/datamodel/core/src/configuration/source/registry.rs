@@ -0,0 +1,19 @@
+use super::traits::SourceDefinition;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, RwLock};
+
+static SOURCE_DEFINITIONS: Lazy<RwLock<Vec<Arc<dyn SourceDefinition + Send + Sync>>>> =
+    Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Registers a source definition, making its provider valid in datasource blocks next to the built-in ones.
+/// Used by connectors shipped outside of the engines. Replaces a definition registered before for the same provider.
+pub fn register_source_definition(definition: Arc<dyn SourceDefinition + Send + Sync>) {
+    let mut definitions = SOURCE_DEFINITIONS.write().unwrap();
+
+    definitions.retain(|registered| registered.connector_type() != definition.connector_type());
+    definitions.push(definition);
+}
+
+pub(super) fn registered_source_definitions() -> Vec<Arc<dyn SourceDefinition + Send + Sync>> {
+    SOURCE_DEFINITIONS.read().unwrap().clone()
+}
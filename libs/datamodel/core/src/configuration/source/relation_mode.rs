@@ -0,0 +1,46 @@
+use crate::dml::Parsable;
+use serde::{Deserialize, Serialize};
+
+/// Enforcement of the relations between models, set by the `relationMode` argument of a datasource.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RelationMode {
+    /// Foreign keys in the database.
+    ForeignKeys,
+    /// The query engine emulates the referential actions of foreign keys, and the migration engine creates none.
+    /// For databases forbidding foreign keys, e.g. Vitess.
+    Prisma,
+}
+
+impl RelationMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationMode::ForeignKeys => "foreignKeys",
+            RelationMode::Prisma => "prisma",
+        }
+    }
+
+    pub fn uses_foreign_keys(&self) -> bool {
+        *self == RelationMode::ForeignKeys
+    }
+}
+
+impl Default for RelationMode {
+    fn default() -> Self {
+        RelationMode::ForeignKeys
+    }
+}
+
+impl Parsable for RelationMode {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "foreignKeys" => Some(RelationMode::ForeignKeys),
+            "prisma" => Some(RelationMode::Prisma),
+            _ => None,
+        }
+    }
+
+    fn descriptor() -> &'static str {
+        "relation mode"
+    }
+}
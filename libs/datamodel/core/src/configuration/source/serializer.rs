@@ -27,10 +27,17 @@ impl SourceSerializer {
                 arguments.push(ast::Argument::new_function("url", "env", values));
             }
             None => {
-                arguments.push(ast::Argument::new_string("url", &source.url().value));
+                arguments.push(ast::Argument::new_string("url", source.url().value.expose()));
             }
         }
 
+        if !source.relation_mode().uses_foreign_keys() {
+            arguments.push(ast::Argument::new_string(
+                "relationMode",
+                source.relation_mode().as_str(),
+            ));
+        }
+
         ast::SourceConfig {
             name: ast::Identifier::new(source.name()),
             properties: arguments,
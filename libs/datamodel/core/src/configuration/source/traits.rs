@@ -1,3 +1,4 @@
+use super::RelationMode;
 use crate::error::DatamodelError;
 use crate::StringFromEnvVar;
 use datamodel_connector::Connector;
@@ -22,6 +23,9 @@ pub trait Source: Send + Sync {
     /// Documentation of this source.
     fn documentation(&self) -> &Option<String>;
 
+    /// Whether foreign keys or the query engine enforce the relations.
+    fn relation_mode(&self) -> RelationMode;
+
     fn connector(&self) -> Box<dyn Connector>;
 }
 
@@ -36,6 +40,7 @@ pub trait SourceDefinition {
         &self,
         name: &str,
         url: StringFromEnvVar,
+        relation_mode: RelationMode,
         documentation: &Option<String>,
     ) -> Result<Box<dyn Source + Send + Sync>, DatamodelError>;
 }
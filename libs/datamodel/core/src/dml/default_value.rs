@@ -29,7 +29,7 @@ impl DefaultValue {
                 ScalarValue::Float(x) => x.try_into().expect("Can't convert float to decimal"),
                 ScalarValue::String(x) => PrismaValue::String(x.clone()),
                 ScalarValue::DateTime(x) => PrismaValue::DateTime(x),
-                ScalarValue::Decimal(x) => x.try_into().expect("Can't convert float to decimal"),
+                ScalarValue::Decimal(x) => PrismaValue::Decimal(x),
                 ScalarValue::ConstantLiteral(x) => PrismaValue::Enum(x.clone()),
             })
             .unwrap_or_else(|| PrismaValue::Null)
@@ -41,6 +41,14 @@ impl DefaultValue {
             Self::Expression(vg) => vg.return_type(),
         }
     }
+
+    /// Whether this is `now()`, the current timestamp.
+    pub fn is_now(&self) -> bool {
+        match self {
+            Self::Expression(vg) => vg.generator == ValueGeneratorFn::Now,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -62,6 +70,10 @@ impl ValueGenerator {
         ValueGenerator::new("autoincrement".to_owned(), vec![]).unwrap()
     }
 
+    pub fn new_uuid() -> Self {
+        ValueGenerator::new("uuid".to_owned(), vec![]).unwrap()
+    }
+
     pub fn return_type(&self) -> ScalarType {
         self.generator.return_type()
     }
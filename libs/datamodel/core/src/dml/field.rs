@@ -27,6 +27,9 @@ pub enum FieldType {
     ConnectorSpecific(ScalarFieldType),
     /// Base (built-in scalar) type.
     Base(ScalarType),
+    /// A database type without Prisma equivalent, e.g. `geometry(Point,4326)`.
+    /// Migrations preserve the type verbatim, the query engine does not expose the field.
+    Unsupported(String),
 }
 
 impl FieldType {
@@ -36,6 +39,13 @@ impl FieldType {
             _ => false,
         }
     }
+
+    pub fn is_unsupported(&self) -> bool {
+        match self {
+            Self::Unsupported(_) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Describes a singular field on a data source.
@@ -98,11 +108,37 @@ pub struct Field {
     /// automatically.
     pub is_updated_at: bool,
 
+    /// If set, the field is read-only and its value is the given SQL expression over the
+    /// columns of the model, e.g. `first_name || ' ' || last_name`. There is no column for it.
+    pub computed: Option<String>,
+
+    /// The database type of the field if it differs from the default one of its type, e.g. `Citext` for
+    /// `@db.Citext`.
+    pub native_type: Option<String>,
+
     /// The data source field specifics, like backing fields and defaults.
     pub data_source_fields: Vec<DataSourceField>,
 }
 
 impl Field {
+    /// The precision and scale of the `Decimal(p,s)` native type, given by `@db.Decimal`.
+    pub fn decimal_precision(&self) -> Option<(u32, u32)> {
+        let native_type = self.native_type.as_ref()?;
+
+        if !native_type.starts_with("Decimal(") || !native_type.ends_with(')') {
+            return None;
+        }
+
+        let mut modifiers = native_type["Decimal(".len()..native_type.len() - 1]
+            .split(',')
+            .map(|modifier| modifier.trim().parse::<u32>().ok());
+
+        match (modifiers.next(), modifiers.next(), modifiers.next()) {
+            (Some(Some(precision)), Some(Some(scale)), None) => Some((precision, scale)),
+            _ => None,
+        }
+    }
+
     pub fn points_to_model(&self, name: &str) -> bool {
         match &self.field_type {
             FieldType::Relation(rel_info) if rel_info.to == name => true,
@@ -168,6 +204,8 @@ impl Field {
             documentation: None,
             is_generated: false,
             is_updated_at: false,
+            computed: None,
+            native_type: None,
             data_source_fields: vec![],
         }
     }
@@ -184,6 +222,8 @@ impl Field {
             documentation: None,
             is_generated: true,
             is_updated_at: false,
+            computed: None,
+            native_type: None,
             data_source_fields: vec![],
         }
     }
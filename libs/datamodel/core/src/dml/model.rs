@@ -1,4 +1,5 @@
 use super::*;
+use std::collections::BTreeMap;
 
 /// Represents a model in a prisma datamodel.
 #[derive(Debug, PartialEq, Clone)]
@@ -21,6 +22,35 @@ pub struct Model {
     pub is_generated: bool,
     /// Indicates if this model has to be commented out.
     pub is_commented_out: bool,
+    /// The datasource this model is stored in. Models without one are stored in the first datasource.
+    pub datasource: Option<String>,
+    /// The optional DateTime field marking records as deleted, see `@@softDelete`.
+    pub soft_delete_field: Option<String>,
+    /// Seconds the results of reads on this model are cached for, see `@@cache`.
+    pub cache_ttl: Option<u32>,
+    /// The storage options of the model's table, see `@@table`.
+    pub table_options: TableOptions,
+    /// Indicates if the changes to the rows of this model are recorded in a history table, see `@@audited`.
+    pub is_audited: bool,
+}
+
+/// The storage options of a table, e.g. `@@table(engine: "InnoDB", charset: "utf8mb4")`.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TableOptions {
+    /// The storage engine. MySQL only.
+    pub engine: Option<String>,
+    /// The default character set. MySQL only.
+    pub charset: Option<String>,
+    /// The tablespace. PostgreSQL only.
+    pub tablespace: Option<String>,
+    /// The fillfactor in percent. PostgreSQL only.
+    pub fillfactor: Option<u32>,
+}
+
+impl TableOptions {
+    pub fn is_empty(&self) -> bool {
+        *self == TableOptions::default()
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -28,6 +58,8 @@ pub struct IndexDefinition {
     pub name: Option<String>,
     pub fields: Vec<String>,
     pub tpe: IndexType,
+    /// The options of the fields that don't use the defaults, by field name.
+    pub field_options: BTreeMap<String, IndexFieldOptions>,
 }
 
 impl IndexDefinition {
@@ -37,6 +69,36 @@ impl IndexDefinition {
             _ => false,
         }
     }
+
+    pub fn options_of_field(&self, field_name: &str) -> IndexFieldOptions {
+        self.field_options.get(field_name).cloned().unwrap_or_default()
+    }
+}
+
+/// The options of a field in an index, e.g. `@@index([title(length: 100, sort: Desc)])`.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct IndexFieldOptions {
+    pub sort_order: SortOrder,
+    /// Only the first `length` characters are indexed. MySQL only.
+    pub length: Option<u32>,
+}
+
+impl IndexFieldOptions {
+    pub fn is_default(&self) -> bool {
+        *self == IndexFieldOptions::default()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder::Asc
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -58,6 +120,11 @@ impl Model {
             is_embedded: false,
             is_generated: false,
             is_commented_out: false,
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
+            table_options: TableOptions::default(),
+            is_audited: false,
         }
     }
 
@@ -12,6 +12,13 @@ pub struct RelationInfo {
     /// A strategy indicating what happens when
     /// a related node is deleted.
     pub on_delete: OnDeleteStrategy,
+    /// The foreign key of the relation is only checked when the transaction
+    /// commits (`DEFERRABLE INITIALLY DEFERRED`), instead of after each statement.
+    pub deferrable: bool,
+    /// The name of the relation table of a many-to-many relation. Defaults to the relation name prefixed with `_`.
+    pub join_table: Option<String>,
+    /// The column of the relation table that references the model of this field. Defaults to `A` or `B`.
+    pub join_column: Option<String>,
 }
 
 impl RelationInfo {
@@ -23,6 +30,9 @@ impl RelationInfo {
             to_fields: Vec::new(),
             name: String::new(),
             on_delete: OnDeleteStrategy::None,
+            deferrable: false,
+            join_table: None,
+            join_column: None,
         }
     }
 }
@@ -71,6 +71,9 @@ pub enum DatamodelError {
     #[fail(display = "Datasource provider not known: \"{}\".", source_name)]
     SourceNotKnownError { source_name: String, span: Span },
 
+    #[fail(display = "The preview feature \"{}\" is not known. Expected one of: {}", preview_feature, expected_preview_features)]
+    PreviewFeatureNotKnownError { preview_feature: String, expected_preview_features: String, span: Span },
+
     #[fail(display = "\"{}\" is not a valid value for {}.", raw_value, literal_type)]
     LiteralParseError { literal_type: String, raw_value: String, span: Span },
 
@@ -285,6 +288,18 @@ impl DatamodelError {
         DatamodelError::SourceNotKnownError { source_name: String::from(source_name), span }
     }
 
+    pub fn new_preview_feature_not_known_error(
+        preview_feature: &str,
+        expected_preview_features: &[&str],
+        span: Span,
+    ) -> DatamodelError {
+        DatamodelError::PreviewFeatureNotKnownError {
+            preview_feature: String::from(preview_feature),
+            expected_preview_features: expected_preview_features.join(", "),
+            span,
+        }
+    }
+
     pub fn new_value_parser_error(expected_type: &str, parser_error: &str, raw: &str, span: Span) -> DatamodelError {
         DatamodelError::ValueParserError {
             expected_type: String::from(expected_type),
@@ -315,6 +330,7 @@ impl DatamodelError {
             DatamodelError::ReservedScalarTypeError { span, .. } => *span,
             DatamodelError::FunctionNotKnownError { span, .. } => *span,
             DatamodelError::SourceNotKnownError { span, .. } => *span,
+            DatamodelError::PreviewFeatureNotKnownError { span, .. } => *span,
             DatamodelError::LiteralParseError { span, .. } => *span,
             DatamodelError::TypeNotFoundError { span, .. } => *span,
             DatamodelError::ScalarTypeNotFoundError { span, .. } => *span,
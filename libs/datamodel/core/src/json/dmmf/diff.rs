@@ -0,0 +1,199 @@
+use super::*;
+use crate::dml;
+
+/// A difference between the DMMF of two data models, e.g. for client generators to produce upgrade hints.
+/// Changed models, fields and enums list the DMMF properties that differ, e.g. `isRequired` or `dbName`.
+#[serde(tag = "type", rename_all = "camelCase")]
+#[derive(Debug, PartialEq, serde::Serialize)]
+pub enum DmmfChange {
+    ModelAdded {
+        model: String,
+    },
+    ModelRemoved {
+        model: String,
+    },
+    ModelChanged {
+        model: String,
+        properties: Vec<String>,
+    },
+    FieldAdded {
+        model: String,
+        field: String,
+    },
+    FieldRemoved {
+        model: String,
+        field: String,
+    },
+    FieldChanged {
+        model: String,
+        field: String,
+        properties: Vec<String>,
+    },
+    EnumAdded {
+        #[serde(rename = "enum")]
+        enum_name: String,
+    },
+    EnumRemoved {
+        #[serde(rename = "enum")]
+        enum_name: String,
+    },
+    EnumChanged {
+        #[serde(rename = "enum")]
+        enum_name: String,
+        properties: Vec<String>,
+    },
+    EnumValueAdded {
+        #[serde(rename = "enum")]
+        enum_name: String,
+        value: String,
+    },
+    EnumValueRemoved {
+        #[serde(rename = "enum")]
+        enum_name: String,
+        value: String,
+    },
+}
+
+/// The changes of the DMMF from `previous` to `next`. Models, fields and enums are matched by name, so renames are
+/// reported as a removal and an addition.
+pub fn diff_dmmf(previous: &dml::Datamodel, next: &dml::Datamodel) -> Vec<DmmfChange> {
+    let previous = schema_to_dmmf(previous);
+    let next = schema_to_dmmf(next);
+    let mut changes = Vec::new();
+
+    for model in previous.models.iter() {
+        if !next.models.iter().any(|next_model| next_model.name == model.name) {
+            changes.push(DmmfChange::ModelRemoved {
+                model: model.name.clone(),
+            });
+        }
+    }
+
+    for model in next.models.iter() {
+        match previous
+            .models
+            .iter()
+            .find(|previous_model| previous_model.name == model.name)
+        {
+            Some(previous_model) => diff_model(previous_model, model, &mut changes),
+            None => changes.push(DmmfChange::ModelAdded {
+                model: model.name.clone(),
+            }),
+        }
+    }
+
+    for en in previous.enums.iter() {
+        if !next.enums.iter().any(|next_enum| next_enum.name == en.name) {
+            changes.push(DmmfChange::EnumRemoved {
+                enum_name: en.name.clone(),
+            });
+        }
+    }
+
+    for en in next.enums.iter() {
+        match previous
+            .enums
+            .iter()
+            .find(|previous_enum| previous_enum.name == en.name)
+        {
+            Some(previous_enum) => diff_enum(previous_enum, en, &mut changes),
+            None => changes.push(DmmfChange::EnumAdded {
+                enum_name: en.name.clone(),
+            }),
+        }
+    }
+
+    changes
+}
+
+fn diff_model(previous: &Model, next: &Model, changes: &mut Vec<DmmfChange>) {
+    let properties = changed_properties(previous, next, &["fields"]);
+
+    if !properties.is_empty() {
+        changes.push(DmmfChange::ModelChanged {
+            model: next.name.clone(),
+            properties,
+        });
+    }
+
+    for field in previous.fields.iter() {
+        if !next.fields.iter().any(|next_field| next_field.name == field.name) {
+            changes.push(DmmfChange::FieldRemoved {
+                model: next.name.clone(),
+                field: field.name.clone(),
+            });
+        }
+    }
+
+    for field in next.fields.iter() {
+        match previous
+            .fields
+            .iter()
+            .find(|previous_field| previous_field.name == field.name)
+        {
+            Some(previous_field) => {
+                let properties = changed_properties(previous_field, field, &[]);
+
+                if !properties.is_empty() {
+                    changes.push(DmmfChange::FieldChanged {
+                        model: next.name.clone(),
+                        field: field.name.clone(),
+                        properties,
+                    });
+                }
+            }
+            None => changes.push(DmmfChange::FieldAdded {
+                model: next.name.clone(),
+                field: field.name.clone(),
+            }),
+        }
+    }
+}
+
+fn diff_enum(previous: &Enum, next: &Enum, changes: &mut Vec<DmmfChange>) {
+    let properties = changed_properties(previous, next, &["values"]);
+
+    if !properties.is_empty() {
+        changes.push(DmmfChange::EnumChanged {
+            enum_name: next.name.clone(),
+            properties,
+        });
+    }
+
+    for value in previous.values.iter().filter(|value| !next.values.contains(value)) {
+        changes.push(DmmfChange::EnumValueRemoved {
+            enum_name: next.name.clone(),
+            value: value.clone(),
+        });
+    }
+
+    for value in next.values.iter().filter(|value| !previous.values.contains(value)) {
+        changes.push(DmmfChange::EnumValueAdded {
+            enum_name: next.name.clone(),
+            value: value.clone(),
+        });
+    }
+}
+
+/// The names of the DMMF properties with different values, other than the ignored ones. Properties missing on one
+/// side count as changed.
+fn changed_properties<T: serde::Serialize>(previous: &T, next: &T, ignored: &[&str]) -> Vec<String> {
+    let previous = serde_json::to_value(previous).expect("Failed to render JSON");
+    let next = serde_json::to_value(next).expect("Failed to render JSON");
+
+    let (previous, next) = match (previous, next) {
+        (serde_json::Value::Object(previous), serde_json::Value::Object(next)) => (previous, next),
+        _ => return Vec::new(),
+    };
+
+    let mut properties: Vec<String> = previous
+        .keys()
+        .chain(next.keys().filter(|key| !previous.contains_key(*key)))
+        .filter(|key| !ignored.contains(&key.as_str()))
+        .filter(|key| previous.get(*key) != next.get(*key))
+        .cloned()
+        .collect();
+
+    properties.sort();
+    properties
+}
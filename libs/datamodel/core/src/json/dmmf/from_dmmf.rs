@@ -39,6 +39,11 @@ fn model_from_dmmf(model: &Model) -> dml::Model {
         documentation: model.documentation.clone(),
         is_generated: model.is_generated.unwrap_or(false),
         is_commented_out: false,
+        datasource: model.datasource.clone(),
+        soft_delete_field: model.soft_delete_field.clone(),
+        cache_ttl: model.cache_ttl,
+        table_options: dml::TableOptions::default(),
+        is_audited: false,
     }
 }
 
@@ -65,6 +70,8 @@ fn field_from_dmmf(field: &Field) -> dml::Field {
         is_unique: field.is_unique,
         is_generated: field.is_generated.unwrap_or(false),
         is_updated_at: field.is_updated_at.unwrap_or(false),
+        computed: field.computed.clone(),
+        native_type: field.native_type.clone(),
         documentation: field.documentation.clone(),
         data_source_fields: vec![],
     }
@@ -104,7 +111,7 @@ fn parse_as_scalar_value(json: &serde_json::Value, scalar_type: &ScalarType) ->
         (serde_json::Value::Number(val), ScalarType::Float) => dml::ScalarValue::Float(val.as_f64().unwrap() as f32),
         (serde_json::Value::Number(val), ScalarType::Int) => dml::ScalarValue::Int(val.as_i64().unwrap() as i32),
         (serde_json::Value::Number(val), ScalarType::Decimal) => {
-            dml::ScalarValue::Decimal(val.as_f64().unwrap() as f32)
+            dml::ScalarValue::Decimal(val.to_string().parse().unwrap())
         }
         (serde_json::Value::String(val), ScalarType::DateTime) => {
             dml::ScalarValue::DateTime(String::from(val.as_str()).parse::<DateTime<Utc>>().unwrap())
@@ -151,9 +158,13 @@ fn get_field_type(field: &Field) -> dml::FieldType {
             to_fields: field.relation_to_fields.clone().unwrap_or_default(),
             name: field.relation_name.clone().unwrap_or(String::new()),
             on_delete: get_on_delete_strategy(&field.relation_on_delete),
+            deferrable: field.relation_deferrable.unwrap_or(false),
+            join_table: field.relation_join_table.clone(),
+            join_column: field.relation_join_column.clone(),
         }),
         "enum" => dml::FieldType::Enum(field.field_type.clone()),
         "scalar" => dml::FieldType::Base(type_from_string(&field.field_type)),
+        "unsupported" => dml::FieldType::Unsupported(field.field_type.clone()),
         _ => panic!(format!("Unknown field kind {}.", &field.kind)),
     }
 }
@@ -1,9 +1,11 @@
+mod diff;
 mod from_dmmf;
 mod to_dmmf;
 
 use serde;
 use serde_json;
 
+pub use diff::{diff_dmmf, DmmfChange};
 pub use from_dmmf::parse_from_dmmf;
 pub use from_dmmf::schema_from_dmmf;
 pub use to_dmmf::render_to_dmmf;
@@ -31,10 +33,20 @@ pub struct Field {
     pub relation_to_fields: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relation_on_delete: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relation_deferrable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relation_join_table: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relation_join_column: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_generated: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_updated_at: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub computed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub documentation: Option<String>,
 }
@@ -59,6 +71,12 @@ pub struct Model {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub documentation: Option<String>,
     pub id_fields: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datasource: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_field: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_ttl: Option<u32>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -1,6 +1,7 @@
 use super::*;
 use crate::common::ScalarType;
 use crate::dml;
+use rust_decimal::prelude::ToPrimitive;
 use serde_json;
 
 pub fn render_to_dmmf(schema: &dml::Datamodel) -> String {
@@ -13,7 +14,7 @@ pub fn render_to_dmmf_value(schema: &dml::Datamodel) -> serde_json::Value {
     serde_json::to_value(&dmmf).expect("Failed to render JSON")
 }
 
-fn schema_to_dmmf(schema: &dml::Datamodel) -> Datamodel {
+pub(super) fn schema_to_dmmf(schema: &dml::Datamodel) -> Datamodel {
     let mut datamodel = Datamodel {
         models: vec![],
         enums: vec![],
@@ -48,6 +49,9 @@ fn model_to_dmmf(model: &dml::Model) -> Model {
         is_generated: Some(model.is_generated),
         documentation: model.documentation.clone(),
         id_fields: model.id_fields.clone(),
+        datasource: model.datasource.clone(),
+        soft_delete_field: model.soft_delete_field.clone(),
+        cache_ttl: model.cache_ttl,
     }
 }
 
@@ -64,9 +68,14 @@ fn field_to_dmmf(field: &dml::Field) -> Field {
         relation_name: get_relation_name(field),
         relation_to_fields: get_relation_to_fields(field),
         relation_on_delete: get_relation_delete_strategy(field),
+        relation_deferrable: get_relation_deferrable(field),
+        relation_join_table: get_relation_info(field).and_then(|info| info.join_table.clone()),
+        relation_join_column: get_relation_info(field).and_then(|info| info.join_column.clone()),
         field_type: get_field_type(field),
         is_generated: Some(field.is_generated),
         is_updated_at: Some(field.is_updated_at),
+        computed: field.computed.clone(),
+        native_type: field.native_type.clone(),
         documentation: field.documentation.clone(),
     }
 }
@@ -76,6 +85,7 @@ fn get_field_kind(field: &dml::Field) -> String {
         dml::FieldType::Relation(_) => String::from("object"),
         dml::FieldType::Enum(_) => String::from("enum"),
         dml::FieldType::Base(_) => String::from("scalar"),
+        dml::FieldType::Unsupported(_) => String::from("unsupported"),
         _ => unimplemented!("DMMF does not support field type {:?}", field.field_type),
     }
 }
@@ -86,6 +96,7 @@ fn get_field_type(field: &dml::Field) -> String {
         dml::FieldType::Enum(t) => t.clone(),
         dml::FieldType::Base(t) => type_to_string(t),
         dml::FieldType::ConnectorSpecific(sft) => type_to_string(&sft.prisma_type()),
+        dml::FieldType::Unsupported(database_type) => database_type.clone(),
     }
 }
 
@@ -107,7 +118,9 @@ fn value_to_serde(value: &dml::ScalarValue) -> serde_json::Value {
         dml::ScalarValue::ConstantLiteral(val) => serde_json::Value::String(val.clone()),
         dml::ScalarValue::Float(val) => serde_json::Value::Number(serde_json::Number::from_f64(*val as f64).unwrap()),
         dml::ScalarValue::Int(val) => serde_json::Value::Number(serde_json::Number::from_f64(*val as f64).unwrap()),
-        dml::ScalarValue::Decimal(val) => serde_json::Value::Number(serde_json::Number::from_f64(*val as f64).unwrap()),
+        dml::ScalarValue::Decimal(val) => {
+            serde_json::Value::Number(serde_json::Number::from_f64(val.to_f64().unwrap()).unwrap())
+        }
         dml::ScalarValue::DateTime(val) => serde_json::Value::String(val.to_rfc3339()),
     }
 }
@@ -142,3 +155,17 @@ fn get_relation_delete_strategy(field: &dml::Field) -> Option<String> {
         _ => None,
     }
 }
+
+fn get_relation_deferrable(field: &dml::Field) -> Option<bool> {
+    match &field.field_type {
+        dml::FieldType::Relation(relation_info) if relation_info.deferrable => Some(true),
+        _ => None,
+    }
+}
+
+fn get_relation_info(field: &dml::Field) -> Option<&dml::RelationInfo> {
+    match &field.field_type {
+        dml::FieldType::Relation(relation_info) => Some(relation_info),
+        _ => None,
+    }
+}
@@ -7,6 +7,8 @@ pub struct SourceConfig {
     pub name: String,
     pub connector_type: String,
     pub url: StringFromEnvVar,
+    #[serde(default, skip_serializing_if = "configuration::RelationMode::uses_foreign_keys")]
+    pub relation_mode: configuration::RelationMode,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub documentation: Option<String>,
 }
@@ -36,6 +38,7 @@ fn source_to_json_struct(source: &dyn configuration::Source) -> SourceConfig {
         name: source.name().clone(),
         connector_type: String::from(source.connector_type()),
         url: source.url().clone(),
+        relation_mode: source.relation_mode(),
         documentation: source.documentation().clone(),
     }
 }
@@ -71,10 +74,14 @@ fn source_from_json(
             arguments.push(ast::Argument::new_function("url", "env", values));
         }
         None => {
-            arguments.push(ast::Argument::new_string("url", &source.url.value));
+            arguments.push(ast::Argument::new_string("url", source.url.value.expose()));
         }
     }
 
+    if !source.relation_mode.uses_foreign_keys() {
+        arguments.push(ast::Argument::new_string("relationMode", source.relation_mode.as_str()));
+    }
+
     let ast_source = ast::SourceConfig {
         name: ast::Identifier::new(&source.name),
         properties: arguments,
@@ -65,17 +65,25 @@ use validator::ValidationPipeline;
 
 /// Parses and validates a datamodel string, using core attributes only.
 pub fn parse_datamodel(datamodel_string: &str) -> Result<Datamodel, error::ErrorCollection> {
-    parse_datamodel_internal(datamodel_string, false)
+    parse_datamodel_internal(datamodel_string, false, &[])
 }
 
 pub fn parse_datamodel_and_ignore_env_errors(datamodel_string: &str) -> Result<Datamodel, error::ErrorCollection> {
-    parse_datamodel_internal(datamodel_string, true)
+    parse_datamodel_internal(datamodel_string, true, &[])
+}
+
+/// Parses and validates a datamodel string, with the URLs of the given datasources replaced.
+pub fn parse_datamodel_with_overrides(
+    datamodel_string: &str,
+    overrides: &[SourceOverride],
+) -> Result<Datamodel, error::ErrorCollection> {
+    parse_datamodel_internal(datamodel_string, false, overrides)
 }
 
 /// Parses and validates a datamodel string, using core attributes only.
 /// In case of an error, a pretty, colorful string is returned.
 pub fn parse_datamodel_or_pretty_error(datamodel_string: &str, file_name: &str) -> Result<Datamodel, String> {
-    match parse_datamodel_internal(datamodel_string, false) {
+    match parse_datamodel_internal(datamodel_string, false, &[]) {
         Ok(dml) => Ok(dml),
         Err(errs) => {
             let mut buffer = std::io::Cursor::new(Vec::<u8>::new());
@@ -95,20 +103,28 @@ pub fn parse_datamodel_or_pretty_error(datamodel_string: &str, file_name: &str)
 fn parse_datamodel_internal(
     datamodel_string: &str,
     ignore_env_var_errors: bool,
+    overrides: &[SourceOverride],
 ) -> Result<Datamodel, error::ErrorCollection> {
     let ast = ast::parser::parse(datamodel_string)?;
-    let sources = load_sources(&ast, ignore_env_var_errors)?;
-    let validator = ValidationPipeline::with_sources(&sources);
+    let sources = load_sources(&ast, ignore_env_var_errors, overrides)?;
+    let preview_features = load_preview_features(&ast)?;
+    let validator = ValidationPipeline::with_sources(&sources).with_preview_features(preview_features);
 
     validator.validate(&ast)
 }
 
 /// Validates a [Schema AST](/ast/struct.SchemaAst.html) and returns its
 /// [Datamodel](/struct.Datamodel.html).
+/// The ASTs are rendered by the engines from validated datamodels without their generator blocks, so all preview
+/// features are enabled.
 pub fn lift_ast(ast: &ast::SchemaAst) -> Result<Datamodel, error::ErrorCollection> {
     let mut errors = error::ErrorCollection::new();
-    let sources = load_sources(ast, false)?;
-    let validator = ValidationPipeline::with_sources(&sources);
+    let sources = load_sources(ast, false, &[])?;
+    let preview_features = preview_features::PREVIEW_FEATURES
+        .iter()
+        .map(|feature| String::from(*feature))
+        .collect();
+    let validator = ValidationPipeline::with_sources(&sources).with_preview_features(preview_features);
 
     match validator.validate(&ast) {
         Ok(src) => Ok(src),
@@ -125,8 +141,16 @@ pub fn parse_schema_ast(datamodel_string: &str) -> Result<SchemaAst, error::Erro
 
 /// Loads all configuration blocks from a datamodel using the built-in source definitions.
 pub fn parse_configuration(datamodel_string: &str) -> Result<Configuration, error::ErrorCollection> {
+    parse_configuration_with_overrides(datamodel_string, &[])
+}
+
+/// Loads all configuration blocks from a datamodel, with the URLs of the given datasources replaced.
+pub fn parse_configuration_with_overrides(
+    datamodel_string: &str,
+    overrides: &[SourceOverride],
+) -> Result<Configuration, error::ErrorCollection> {
     let ast = ast::parser::parse(datamodel_string)?;
-    let datasources = load_sources(&ast, false)?;
+    let datasources = load_sources(&ast, false, overrides)?;
     let generators = GeneratorLoader::load_generators_from_ast(&ast)?;
 
     Ok(Configuration {
@@ -138,11 +162,21 @@ pub fn parse_configuration(datamodel_string: &str) -> Result<Configuration, erro
 fn load_sources(
     schema_ast: &SchemaAst,
     ignore_env_var_errors: bool,
+    overrides: &[SourceOverride],
 ) -> Result<Vec<Box<dyn Source + Send + Sync>>, error::ErrorCollection> {
-    let source_loader = SourceLoader::new();
+    let source_loader = SourceLoader::new().with_overrides(overrides);
     source_loader.load_sources(&schema_ast, ignore_env_var_errors)
 }
 
+fn load_preview_features(schema_ast: &SchemaAst) -> Result<Vec<String>, error::ErrorCollection> {
+    let generators = GeneratorLoader::load_generators_from_ast(&schema_ast)?;
+
+    Ok(configuration::preview_features_of(&generators)
+        .into_iter()
+        .map(String::from)
+        .collect())
+}
+
 //
 //  ************** RENDERING FUNCTIONS **************
 //
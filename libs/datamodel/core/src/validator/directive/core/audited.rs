@@ -0,0 +1,30 @@
+use crate::error::DatamodelError;
+use crate::validator::directive::{Args, DirectiveValidator};
+use crate::{ast, dml};
+
+/// Prismas builtin `@@audited` directive. The migration engine records the changes to the rows of the model in a
+/// history table, through triggers.
+pub struct AuditedDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for AuditedDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"audited"
+    }
+
+    fn validate_and_apply(&self, _args: &mut Args, obj: &mut dml::Model) -> Result<(), DatamodelError> {
+        obj.is_audited = true;
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        model: &dml::Model,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        if model.is_audited {
+            return Ok(vec![ast::Directive::new(self.directive_name(), vec![])]);
+        }
+
+        Ok(vec![])
+    }
+}
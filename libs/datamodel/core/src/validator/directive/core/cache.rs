@@ -0,0 +1,44 @@
+use crate::error::DatamodelError;
+use crate::validator::directive::{Args, DirectiveValidator};
+use crate::{ast, dml};
+
+/// Prismas builtin `@@cache` directive. The query engine caches the results of reads on the model for the given
+/// number of seconds, until a write on the model commits.
+pub struct CacheDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for CacheDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"cache"
+    }
+
+    fn validate_and_apply(&self, args: &mut Args, obj: &mut dml::Model) -> Result<(), DatamodelError> {
+        let ttl = args.default_arg("ttl")?.as_int()?;
+
+        if ttl <= 0 {
+            return self
+                .new_directive_validation_error("The cache ttl must be a positive number of seconds.", args.span());
+        }
+
+        obj.cache_ttl = Some(ttl as u32);
+
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        model: &dml::Model,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        if let Some(ttl) = model.cache_ttl {
+            return Ok(vec![ast::Directive::new(
+                self.directive_name(),
+                vec![ast::Argument::new(
+                    "ttl",
+                    ast::Expression::NumericValue(ttl.to_string(), ast::Span::empty()),
+                )],
+            )]);
+        }
+
+        Ok(vec![])
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error::DatamodelError;
+use crate::validator::directive::{Args, DirectiveValidator};
+use crate::{ast, dml};
+
+/// Prismas builtin `@computed` directive. The field is read-only, its value is the given SQL expression over the
+/// columns of the model.
+pub struct ComputedDirectiveValidator {}
+
+impl DirectiveValidator<dml::Field> for ComputedDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"computed"
+    }
+
+    fn validate_and_apply(&self, args: &mut Args, obj: &mut dml::Field) -> Result<(), DatamodelError> {
+        let expression = args.default_arg("expression")?.as_str()?;
+
+        if expression.trim().is_empty() {
+            return self
+                .new_directive_validation_error("The expression of a computed field must not be empty.", args.span());
+        }
+
+        match obj.field_type {
+            dml::FieldType::Base(_) | dml::FieldType::Enum(_) => (),
+            _ => {
+                return self.new_directive_validation_error(
+                    "Fields that are marked with @computed must be of a scalar or enum type.",
+                    args.span(),
+                )
+            }
+        }
+
+        if obj.arity == dml::FieldArity::List {
+            return self.new_directive_validation_error(
+                "Fields that are marked with @computed can not be lists.",
+                args.span(),
+            );
+        }
+
+        obj.computed = Some(expression);
+
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        field: &dml::Field,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        if let Some(expression) = &field.computed {
+            return Ok(vec![ast::Directive::new(
+                self.directive_name(),
+                vec![ast::Argument::new_string("", expression)],
+            )]);
+        }
+
+        Ok(vec![])
+    }
+}
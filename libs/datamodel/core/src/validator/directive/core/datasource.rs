@@ -0,0 +1,32 @@
+use crate::error::DatamodelError;
+use crate::validator::directive::{Args, DirectiveValidator};
+use crate::{ast, dml};
+
+/// Prismas builtin `@@datasource` directive, storing a model in another datasource than the first one.
+pub struct DatasourceDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for DatasourceDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"datasource"
+    }
+
+    fn validate_and_apply(&self, args: &mut Args, obj: &mut dml::Model) -> Result<(), DatamodelError> {
+        obj.datasource = Some(args.default_arg("name")?.as_str()?);
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        model: &dml::Model,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        if let Some(datasource) = &model.datasource {
+            return Ok(vec![ast::Directive::new(
+                self.directive_name(),
+                vec![ast::Argument::new_string("", datasource)],
+            )]);
+        }
+
+        Ok(vec![])
+    }
+}
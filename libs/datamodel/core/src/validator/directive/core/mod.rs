@@ -1,11 +1,18 @@
 use crate::dml;
 use crate::validator::directive::DirectiveListValidator;
 
+mod audited;
+mod cache;
+mod computed;
+mod datasource;
 mod default;
 mod embedded;
 mod id;
 mod map;
+mod native_types;
 mod relation;
+mod soft_delete;
+mod table;
 mod unique_and_index;
 mod updated_at;
 mod utils;
@@ -20,6 +27,9 @@ pub fn new_builtin_field_directives() -> DirectiveListValidator<dml::Field> {
     validator.add(Box::new(default::DefaultDirectiveValidator {}));
     validator.add(Box::new(relation::RelationDirectiveValidator {}));
     validator.add(Box::new(updated_at::UpdatedAtDirectiveValidator {}));
+    validator.add(Box::new(computed::ComputedDirectiveValidator {}));
+    validator.add(Box::new(native_types::CitextDirectiveValidator {}));
+    validator.add(Box::new(native_types::DecimalDirectiveValidator {}));
 
     validator
 }
@@ -33,6 +43,11 @@ pub fn new_builtin_model_directives() -> DirectiveListValidator<dml::Model> {
     validator.add(Box::new(unique_and_index::ModelLevelUniqueDirectiveValidator {}));
     validator.add(Box::new(unique_and_index::ModelLevelIndexDirectiveValidator {}));
     validator.add(Box::new(id::ModelLevelIdDirectiveValidator {}));
+    validator.add(Box::new(datasource::DatasourceDirectiveValidator {}));
+    validator.add(Box::new(soft_delete::SoftDeleteDirectiveValidator {}));
+    validator.add(Box::new(cache::CacheDirectiveValidator {}));
+    validator.add(Box::new(table::TableDirectiveValidator {}));
+    validator.add(Box::new(audited::AuditedDirectiveValidator {}));
 
     validator
 }
@@ -0,0 +1,123 @@
+use crate::error::DatamodelError;
+use crate::validator::directive::{Args, DirectiveValidator};
+use crate::{ast, dml};
+
+/// The native type of case-insensitive text, `citext` on PostgreSQL.
+const CITEXT: &str = "Citext";
+
+/// Prismas builtin `@db.Citext` directive. The field is stored as case-insensitive text, so that comparisons and
+/// unique constraints ignore the case.
+pub struct CitextDirectiveValidator {}
+
+impl DirectiveValidator<dml::Field> for CitextDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"db.Citext"
+    }
+
+    fn validate_and_apply(&self, args: &mut Args, obj: &mut dml::Field) -> Result<(), DatamodelError> {
+        match obj.field_type {
+            dml::FieldType::Base(dml::ScalarType::String) => (),
+            _ => {
+                return self.new_directive_validation_error(
+                    "Fields that are marked with @db.Citext must be of type String.",
+                    args.span(),
+                )
+            }
+        }
+
+        obj.native_type = Some(CITEXT.to_owned());
+
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        field: &dml::Field,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        if field.native_type.as_ref().map(String::as_str) == Some(CITEXT) {
+            return Ok(vec![ast::Directive::new(self.directive_name(), vec![])]);
+        }
+
+        Ok(vec![])
+    }
+}
+
+/// The native type of exact numbers, `Decimal(p,s)` for the precision `p` and scale `s`.
+const DECIMAL: &str = "Decimal";
+
+/// The largest precision and scale all SQL connectors support, those of MySQL.
+const MAX_DECIMAL_PRECISION: u32 = 65;
+const MAX_DECIMAL_SCALE: u32 = 30;
+
+/// Prismas builtin `@db.Decimal(p, scale: s)` directive. The field is stored as `NUMERIC(p,s)`, the scale defaults
+/// to 0 like in SQL.
+pub struct DecimalDirectiveValidator {}
+
+impl DirectiveValidator<dml::Field> for DecimalDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"db.Decimal"
+    }
+
+    fn validate_and_apply(&self, args: &mut Args, obj: &mut dml::Field) -> Result<(), DatamodelError> {
+        match obj.field_type {
+            dml::FieldType::Base(dml::ScalarType::Decimal) => (),
+            _ => {
+                return self.new_directive_validation_error(
+                    "Fields that are marked with @db.Decimal must be of type Decimal.",
+                    args.span(),
+                )
+            }
+        }
+
+        let precision = args.default_arg("precision")?.as_int()?;
+        let scale = match args.optional_arg("scale") {
+            Some(scale) => scale.as_int()?,
+            None => 0,
+        };
+
+        if precision < 1 || precision as u32 > MAX_DECIMAL_PRECISION {
+            return self.new_directive_validation_error(
+                &format!("The precision must be between 1 and {}.", MAX_DECIMAL_PRECISION),
+                args.span(),
+            );
+        }
+
+        if scale < 0 || scale > precision || scale as u32 > MAX_DECIMAL_SCALE {
+            return self.new_directive_validation_error(
+                &format!(
+                    "The scale must be between 0 and the precision, and at most {}.",
+                    MAX_DECIMAL_SCALE
+                ),
+                args.span(),
+            );
+        }
+
+        obj.native_type = Some(format!("{}({},{})", DECIMAL, precision, scale));
+
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        field: &dml::Field,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        if let Some((precision, scale)) = field.decimal_precision() {
+            let arguments = vec![
+                ast::Argument::new(
+                    "",
+                    ast::Expression::NumericValue(precision.to_string(), ast::Span::empty()),
+                ),
+                ast::Argument::new(
+                    "scale",
+                    ast::Expression::NumericValue(scale.to_string(), ast::Span::empty()),
+                ),
+            ];
+
+            return Ok(vec![ast::Directive::new(self.directive_name(), arguments)]);
+        }
+
+        Ok(vec![])
+    }
+}
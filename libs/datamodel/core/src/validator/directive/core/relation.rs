@@ -28,10 +28,21 @@ impl DirectiveValidator<dml::Field> for RelationDirectiveValidator {
                 relation_info.to_fields = related_fields.as_array()?.to_literal_vec()?;
             }
 
-            // TODO: bring `onDelete` back once `prisma migrate` is a thing
-            //            if let Ok(on_delete) = args.arg("onDelete") {
-            //                relation_info.on_delete = on_delete.parse_literal::<dml::OnDeleteStrategy>()?;
-            //            }
+            if let Ok(deferrable) = args.arg("deferrable") {
+                relation_info.deferrable = deferrable.as_bool()?;
+            }
+
+            if let Ok(join_table) = args.arg("table") {
+                relation_info.join_table = Some(join_table.as_str()?);
+            }
+
+            if let Ok(join_column) = args.arg("column") {
+                relation_info.join_column = Some(join_column.as_str()?);
+            }
+
+            if let Ok(on_delete) = args.arg("onDelete") {
+                relation_info.on_delete = on_delete.parse_literal::<dml::OnDeleteStrategy>()?;
+            }
 
             Ok(())
         } else {
@@ -88,6 +99,21 @@ impl DirectiveValidator<dml::Field> for RelationDirectiveValidator {
                 ));
             }
 
+            if relation_info.deferrable {
+                args.push(ast::Argument::new(
+                    "deferrable",
+                    ast::Expression::BooleanValue(String::from("true"), ast::Span::empty()),
+                ));
+            }
+
+            if let Some(join_table) = &relation_info.join_table {
+                args.push(ast::Argument::new_string("table", join_table));
+            }
+
+            if let Some(join_column) = &relation_info.join_column {
+                args.push(ast::Argument::new_string("column", join_column));
+            }
+
             if !args.is_empty() {
                 return Ok(vec![ast::Directive::new(self.directive_name(), args)]);
             }
@@ -0,0 +1,58 @@
+use crate::error::DatamodelError;
+use crate::validator::directive::{Args, DirectiveValidator};
+use crate::{ast, dml};
+
+/// Prismas builtin `@@softDelete` directive. Deletes of the model set the given optional DateTime field instead of
+/// removing records, and reads skip records with the field set.
+pub struct SoftDeleteDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for SoftDeleteDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"softDelete"
+    }
+
+    fn validate_and_apply(&self, args: &mut Args, obj: &mut dml::Model) -> Result<(), DatamodelError> {
+        let field_name = args.default_arg("field")?.as_constant_literal()?;
+
+        let field = match obj.find_field(&field_name) {
+            Some(field) => field,
+            None => {
+                return self.new_directive_validation_error(
+                    &format!("The soft delete field `{}` is not defined on the model.", field_name),
+                    args.span(),
+                )
+            }
+        };
+
+        if field.field_type != dml::FieldType::Base(dml::ScalarType::DateTime)
+            || field.arity != dml::FieldArity::Optional
+        {
+            return self.new_directive_validation_error(
+                &format!(
+                    "The soft delete field `{}` must be an optional DateTime field.",
+                    field_name
+                ),
+                args.span(),
+            );
+        }
+
+        obj.soft_delete_field = Some(field_name);
+
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        model: &dml::Model,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        if let Some(field_name) = &model.soft_delete_field {
+            return Ok(vec![ast::Directive::new(
+                self.directive_name(),
+                vec![ast::Argument::new_constant("field", field_name)],
+            )]);
+        }
+
+        Ok(vec![])
+    }
+}
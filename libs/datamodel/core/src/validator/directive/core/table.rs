@@ -0,0 +1,107 @@
+use crate::common::value_validator::ValueValidator;
+use crate::error::DatamodelError;
+use crate::validator::directive::{Args, DirectiveValidator};
+use crate::{ast, dml};
+
+/// Prismas builtin `@@table` directive. Sets the storage options of the model's table, e.g. the MySQL engine or the
+/// PostgreSQL fillfactor.
+pub struct TableDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for TableDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"table"
+    }
+
+    fn validate_and_apply(&self, args: &mut Args, obj: &mut dml::Model) -> Result<(), DatamodelError> {
+        let mut options = dml::TableOptions::default();
+
+        if let Some(engine) = args.optional_arg("engine") {
+            options.engine = Some(self.identifier(&engine)?);
+        }
+
+        if let Some(charset) = args.optional_arg("charset") {
+            options.charset = Some(self.identifier(&charset)?);
+        }
+
+        if let Some(tablespace) = args.optional_arg("tablespace") {
+            options.tablespace = Some(self.identifier(&tablespace)?);
+        }
+
+        if let Some(fillfactor) = args.optional_arg("fillfactor") {
+            let value = fillfactor.as_int()?;
+
+            if value < 10 || value > 100 {
+                return self
+                    .new_directive_validation_error("The fillfactor must be between 10 and 100.", fillfactor.span());
+            }
+
+            options.fillfactor = Some(value as u32);
+        }
+
+        if options.is_empty() {
+            return self.new_directive_validation_error(
+                "The table directive needs at least one of the arguments `engine`, `charset`, `tablespace` or `fillfactor`.",
+                args.span(),
+            );
+        }
+
+        obj.table_options = options;
+
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        model: &dml::Model,
+        _datamodel: &dml::Datamodel,
+    ) -> Result<Vec<ast::Directive>, DatamodelError> {
+        let options = &model.table_options;
+
+        if options.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut args = Vec::new();
+
+        if let Some(engine) = &options.engine {
+            args.push(ast::Argument::new_string("engine", engine));
+        }
+
+        if let Some(charset) = &options.charset {
+            args.push(ast::Argument::new_string("charset", charset));
+        }
+
+        if let Some(tablespace) = &options.tablespace {
+            args.push(ast::Argument::new_string("tablespace", tablespace));
+        }
+
+        if let Some(fillfactor) = options.fillfactor {
+            args.push(ast::Argument::new(
+                "fillfactor",
+                ast::Expression::NumericValue(fillfactor.to_string(), ast::Span::empty()),
+            ));
+        }
+
+        Ok(vec![ast::Directive::new(self.directive_name(), args)])
+    }
+}
+
+impl TableDirectiveValidator {
+    /// The options end up in the DDL unquoted, so they must be plain identifiers.
+    fn identifier(&self, value: &ValueValidator) -> Result<String, DatamodelError> {
+        let identifier = value.as_str()?;
+
+        if identifier.is_empty() || !identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(DatamodelError::new_directive_validation_error(
+                &format!(
+                    "`{}` is not a valid table option. Only letters, digits and underscores are allowed.",
+                    identifier
+                ),
+                self.directive_name(),
+                value.span(),
+            ));
+        }
+
+        Ok(identifier)
+    }
+}
@@ -1,6 +1,8 @@
+use crate::common::value_validator::ValueValidator;
 use crate::error::DatamodelError;
 use crate::validator::directive::{Args, DirectiveValidator};
-use crate::{ast, dml, IndexDefinition, IndexType};
+use crate::{ast, dml, IndexDefinition, IndexFieldOptions, IndexType, SortOrder};
+use std::collections::BTreeMap;
 
 /// Prismas builtin `@unique` directive.
 pub struct FieldLevelUniqueDirectiveValidator {}
@@ -106,6 +108,7 @@ trait IndexDirectiveBase<T>: DirectiveValidator<T> {
             name: None,
             fields: vec![],
             tpe: index_type,
+            field_options: BTreeMap::new(),
         };
         let name = match args.optional_arg("name") {
             Some(name_arg) => Some(name_arg.as_str()?),
@@ -115,8 +118,17 @@ trait IndexDirectiveBase<T>: DirectiveValidator<T> {
 
         match args.default_arg("fields")?.as_array() {
             Ok(fields) => {
-                let fields = fields.iter().map(|f| f.as_constant_literal().unwrap()).collect();
-                index_def.fields = fields;
+                for field in fields {
+                    let (field_name, options) = self
+                        .parse_index_field(&field)
+                        .map_err(|err| self.wrap_in_directive_validation_error(&err))?;
+
+                    if !options.is_default() {
+                        index_def.field_options.insert(field_name.clone(), options);
+                    }
+
+                    index_def.fields.push(field_name);
+                }
             }
             Err(err) => return Err(self.wrap_in_directive_validation_error(&err)),
         }
@@ -148,6 +160,58 @@ trait IndexDirectiveBase<T>: DirectiveValidator<T> {
         Ok(index_def)
     }
 
+    /// A field is either given by name, or with options, like `title(length: 100, sort: Desc)`.
+    fn parse_index_field(&self, field: &ValueValidator) -> Result<(String, IndexFieldOptions), DatamodelError> {
+        if let Ok(field_name) = field.as_constant_literal() {
+            return Ok((field_name, IndexFieldOptions::default()));
+        }
+
+        let (field_name, args) = field.as_function()?;
+        let mut options = IndexFieldOptions::default();
+
+        for arg in args {
+            let value = ValueValidator::new(&arg.value);
+
+            match arg.name.name.as_str() {
+                "length" => {
+                    let length = value.as_int()?;
+
+                    if length <= 0 {
+                        return Err(DatamodelError::new_validation_error(
+                            "The length of an index field must be positive.",
+                            arg.span,
+                        ));
+                    }
+
+                    options.length = Some(length as u32);
+                }
+                "sort" => {
+                    options.sort_order = match value.as_constant_literal()?.as_str() {
+                        "Asc" => SortOrder::Asc,
+                        "Desc" => SortOrder::Desc,
+                        other => {
+                            return Err(DatamodelError::new_validation_error(
+                                &format!("Unknown sort order `{}`, expected `Asc` or `Desc`.", other),
+                                arg.span,
+                            ))
+                        }
+                    }
+                }
+                name => {
+                    return Err(DatamodelError::new_validation_error(
+                        &format!(
+                            "Unknown argument `{}` of the index field `{}`, expected `length` or `sort`.",
+                            name, field_name
+                        ),
+                        arg.span,
+                    ))
+                }
+            }
+        }
+
+        Ok((field_name, options))
+    }
+
     fn serialize_index_definitions(
         &self,
         model: &dml::Model,
@@ -165,7 +229,7 @@ trait IndexDirectiveBase<T>: DirectiveValidator<T> {
                     index_def
                         .fields
                         .iter()
-                        .map(|f| ast::Expression::ConstantValue(f.to_string(), ast::Span::empty()))
+                        .map(|f| serialize_index_field(f, index_def.options_of_field(f)))
                         .collect(),
                 ));
                 if let Some(name) = &index_def.name {
@@ -179,3 +243,24 @@ trait IndexDirectiveBase<T>: DirectiveValidator<T> {
         Ok(directives)
     }
 }
+
+fn serialize_index_field(field_name: &str, options: IndexFieldOptions) -> ast::Expression {
+    if options.is_default() {
+        return ast::Expression::ConstantValue(field_name.to_string(), ast::Span::empty());
+    }
+
+    let mut args = Vec::new();
+
+    if let Some(length) = options.length {
+        args.push(ast::Argument::new(
+            "length",
+            ast::Expression::NumericValue(length.to_string(), ast::Span::empty()),
+        ));
+    }
+
+    if options.sort_order == SortOrder::Desc {
+        args.push(ast::Argument::new_constant("sort", "Desc"));
+    }
+
+    ast::Expression::Function(field_name.to_string(), args, ast::Span::empty())
+}
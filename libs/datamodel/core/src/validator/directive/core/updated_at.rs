@@ -25,6 +25,17 @@ impl DirectiveValidator<dml::Field> for UpdatedAtDirectiveValidator {
             );
         }
 
+        // Defaults are applied first, `now()` stamps the field on creation as well.
+        match &obj.default_value {
+            Some(default) if !default.is_now() => {
+                return self.new_directive_validation_error(
+                    "Fields that are marked with @updatedAt can only have a default value of now().",
+                    args.span(),
+                )
+            }
+            _ => (),
+        }
+
         obj.is_updated_at = true;
 
         Ok(())
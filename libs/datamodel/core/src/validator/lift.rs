@@ -163,7 +163,9 @@ impl LiftAstToDml {
     ) -> Result<(dml::FieldType, Vec<ast::Directive>), DatamodelError> {
         let type_name = &ast_field.field_type.name;
 
-        if let Ok(scalar_type) = ScalarType::from_str(type_name) {
+        if let Some(database_type) = unsupported_database_type(type_name) {
+            Ok((dml::FieldType::Unsupported(database_type.to_owned()), vec![]))
+        } else if let Ok(scalar_type) = ScalarType::from_str(type_name) {
             if USE_CONNECTORS_FOR_CUSTOM_TYPES {
                 let pg_connector = ExampleConnector::postgres();
                 let args = vec![]; // TODO: figure out args
@@ -245,3 +247,14 @@ impl LiftAstToDml {
         }
     }
 }
+
+/// The database type of an `Unsupported("<database type>")` type name.
+fn unsupported_database_type(type_name: &str) -> Option<&str> {
+    let (prefix, suffix) = ("Unsupported(\"", "\")");
+
+    if type_name.len() >= prefix.len() + suffix.len() && type_name.starts_with(prefix) && type_name.ends_with(suffix) {
+        Some(&type_name[prefix.len()..type_name.len() - suffix.len()])
+    } else {
+        None
+    }
+}
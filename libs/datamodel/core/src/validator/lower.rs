@@ -118,8 +118,12 @@ impl LowerDmlToAst {
         match dv {
             dml::DefaultValue::Single(v) => Self::lower_scalar_value(&v),
             dml::DefaultValue::Expression(e) => {
-                let exprs = e.args.iter().map(Self::lower_scalar_value).collect();
-                ast::Expression::Function(e.name, exprs, ast::Span::empty())
+                let args = e
+                    .args
+                    .iter()
+                    .map(|arg| ast::Argument::new("", Self::lower_scalar_value(arg)))
+                    .collect();
+                ast::Expression::Function(e.name, args, ast::Span::empty())
             }
         }
     }
@@ -147,6 +151,9 @@ impl LowerDmlToAst {
             dml::FieldType::Base(tpe) => ast::Identifier::new(&tpe.to_string()),
             dml::FieldType::Enum(tpe) => ast::Identifier::new(&tpe.to_string()),
             dml::FieldType::Relation(rel) => ast::Identifier::new(&rel.to),
+            dml::FieldType::Unsupported(database_type) => {
+                ast::Identifier::new(&format!("Unsupported(\"{}\")", database_type))
+            }
             _ => unimplemented!("Connector specific types are not supported atm."),
         }
     }
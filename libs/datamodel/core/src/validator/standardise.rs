@@ -155,6 +155,11 @@ impl Standardiser {
             id_fields: vec![],
             is_generated: true,
             is_commented_out: false,
+            datasource: a_model.datasource.clone(),
+            soft_delete_field: None,
+            cache_ttl: None,
+            table_options: dml::TableOptions::default(),
+            is_audited: false,
         }
     }
 
@@ -166,6 +171,9 @@ impl Standardiser {
                 to_fields: model.id_field_names(),
                 name: String::from(relation_name), // Will be corrected in later step
                 on_delete: dml::OnDeleteStrategy::None,
+                deferrable: false,
+                join_table: None,
+                join_column: None,
             }),
         )
     }
@@ -296,6 +304,9 @@ impl Standardiser {
                         to_fields: vec![],
                         name: rel.name.clone(),
                         on_delete: OnDeleteStrategy::None,
+                        deferrable: false,
+                        join_table: None,
+                        join_column: None,
                     };
 
                     let (arity, field_name) = if field.arity.is_singular() {
@@ -399,6 +410,7 @@ impl Standardiser {
                     dml::FieldType::Relation(rel_info) => {
                         self.get_datasource_fields_for_relation_field(&field, &rel_info, &datamodel)
                     }
+                    dml::FieldType::Unsupported(_) => self.get_datasource_fields_for_unsupported_field(&field),
                     dml::FieldType::ConnectorSpecific(_) => {
                         unimplemented!("ConnectorSpecific is not supported here as it will be removed soon.")
                     }
@@ -452,6 +464,17 @@ impl Standardiser {
         vec![datasource_field]
     }
 
+    /// The scalar type of the data source field is a placeholder, the query engine ignores unsupported fields.
+    fn get_datasource_fields_for_unsupported_field(&self, field: &dml::Field) -> Vec<DataSourceField> {
+        let datasource_field = dml::DataSourceField {
+            name: field.final_single_database_name().to_owned(),
+            field_type: dml::ScalarType::String,
+            arity: field.arity,
+            default_value: None,
+        };
+        vec![datasource_field]
+    }
+
     fn get_datasource_fields_for_relation_field(
         &self,
         field: &dml::Field,
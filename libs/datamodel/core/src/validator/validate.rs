@@ -9,6 +9,9 @@ use crate::{
 /// When validating, we check if the datamodel is valid, and generate errors otherwise.
 pub struct Validator<'a> {
     source: Option<&'a Box<dyn configuration::Source + Send + Sync>>,
+    sources: &'a [Box<dyn configuration::Source + Send + Sync>],
+    /// The preview features enabled by the generators.
+    pub preview_features: Vec<String>,
 }
 
 /// State error message. Seeing this error means something went really wrong internally. It's the datamodel equivalent of a bluescreen.
@@ -16,8 +19,13 @@ const STATE_ERROR: &str = "Failed lookup of model, field or optional property du
 
 impl<'a> Validator<'a> {
     /// Creates a new instance, with all builtin directives registered.
-    pub fn new(source: Option<&'a Box<dyn configuration::Source + Send + Sync>>) -> Validator {
-        Self { source }
+    /// Connector specific validations use the first source.
+    pub fn new(sources: &'a [Box<dyn configuration::Source + Send + Sync>]) -> Validator {
+        Self {
+            source: sources.first(),
+            sources,
+            preview_features: Vec::new(),
+        }
     }
 
     pub fn validate(&self, ast_schema: &ast::SchemaAst, schema: &mut dml::Datamodel) -> Result<(), ErrorCollection> {
@@ -35,12 +43,55 @@ impl<'a> Validator<'a> {
             if let Err(err) = self.validate_embedded_types_have_no_back_relation(ast_schema, schema, model) {
                 errors.push(err);
             }
+            if let Err(err) = self.validate_datasource(ast_schema, schema, model) {
+                errors.push(err);
+            }
+            if let Err(ref mut the_errors) = self.validate_relation_tables(ast_schema, schema, model) {
+                errors.append(the_errors);
+            }
 
             if let Err(ref mut the_errors) =
                 self.validate_field_arities(ast_schema.find_model(&model.name).expect(STATE_ERROR), model)
             {
                 errors.append(the_errors);
             }
+
+            if let Err(ref mut the_errors) =
+                self.validate_computed_fields(ast_schema.find_model(&model.name).expect(STATE_ERROR), model)
+            {
+                errors.append(the_errors);
+            }
+
+            if let Err(ref mut the_errors) =
+                self.validate_native_types(ast_schema.find_model(&model.name).expect(STATE_ERROR), model)
+            {
+                errors.append(the_errors);
+            }
+
+            if let Err(ref mut the_errors) =
+                self.validate_deferrable_relations(ast_schema.find_model(&model.name).expect(STATE_ERROR), model)
+            {
+                errors.append(the_errors);
+            }
+
+            if let Err(ref mut the_errors) =
+                self.validate_table_options(ast_schema.find_model(&model.name).expect(STATE_ERROR), model)
+            {
+                errors.append(the_errors);
+            }
+
+            if let Err(err) = self.validate_audited(ast_schema.find_model(&model.name).expect(STATE_ERROR), model) {
+                errors.push(err);
+            }
+        }
+
+        // Enum level validations.
+        for r#enum in schema.enums() {
+            if let Err(err) =
+                self.validate_enum_has_values(ast_schema.find_enum(&r#enum.name).expect(STATE_ERROR), r#enum)
+            {
+                errors.push(err);
+            }
         }
 
         if errors.has_errors() {
@@ -50,6 +101,18 @@ impl<'a> Validator<'a> {
         }
     }
 
+    /// Fields of an enum without values could never hold a value, and there is no value to default existing rows to.
+    fn validate_enum_has_values(&self, ast_enum: &ast::Enum, r#enum: &dml::Enum) -> Result<(), DatamodelError> {
+        if r#enum.values.is_empty() {
+            return Err(DatamodelError::new_validation_error(
+                &format!("The enum \"{}\" must have at least one value.", r#enum.name),
+                ast_enum.span,
+            ));
+        }
+
+        Ok(())
+    }
+
     fn validate_field_arities(&self, ast_model: &ast::Model, model: &dml::Model) -> Result<(), ErrorCollection> {
         let mut errors = ErrorCollection::new();
 
@@ -82,6 +145,332 @@ impl<'a> Validator<'a> {
         }
     }
 
+    /// Computed fields have no column, so they can't be written or keyed on. Checked here as the directives of
+    /// a field are applied in no particular order relative to `@computed`.
+    fn validate_computed_fields(&self, ast_model: &ast::Model, model: &dml::Model) -> Result<(), ErrorCollection> {
+        let mut errors = ErrorCollection::new();
+
+        for field in model.fields().filter(|field| field.computed.is_some()) {
+            let message = if field.is_id {
+                "Computed fields can not be id fields."
+            } else if field.is_unique {
+                "Computed fields can not be unique."
+            } else if field.default_value.is_some() {
+                "Computed fields can not have a default value."
+            } else if field.is_updated_at {
+                "Computed fields can not be marked with @updatedAt."
+            } else {
+                continue;
+            };
+
+            let directive = ast_model
+                .fields
+                .iter()
+                .find(|ast_field| ast_field.name.name == field.name)
+                .and_then(|ast_field| ast_field.directives.iter().find(|d| d.name.name == "computed"))
+                .expect(STATE_ERROR);
+
+            errors.push(DatamodelError::new_directive_validation_error(
+                message,
+                "computed",
+                directive.span,
+            ));
+        }
+
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Native types are database specific, `@db.Citext` is only available on PostgreSQL. `@db.Decimal` is available
+    /// on all SQL connectors.
+    fn validate_native_types(&self, ast_model: &ast::Model, model: &dml::Model) -> Result<(), ErrorCollection> {
+        let mut errors = ErrorCollection::new();
+
+        let native_types_enabled = self
+            .preview_features
+            .iter()
+            .any(|feature| feature == configuration::preview_features::NATIVE_TYPES);
+
+        let connector_type = self
+            .datasource_of(model)
+            .and_then(|name| self.sources.iter().find(|source| source.name() == name))
+            .map(|source| source.connector_type());
+
+        let on_postgres = match connector_type {
+            Some(connector_type) => connector_type == configuration::POSTGRES_SOURCE_NAME,
+            None => true,
+        };
+
+        for field in model.fields() {
+            // The name of the native type without its modifiers, e.g. `Decimal` for `Decimal(10,2)`.
+            let native_type = match &field.native_type {
+                Some(native_type) => native_type.split('(').next().unwrap(),
+                None => continue,
+            };
+
+            let supported_by_connector = on_postgres || field.decimal_precision().is_some();
+            let directive_name = format!("db.{}", native_type);
+            let directive = ast_model
+                .fields
+                .iter()
+                .find(|ast_field| ast_field.name.name == field.name)
+                .and_then(|ast_field| ast_field.directives.iter().find(|d| d.name.name == directive_name))
+                .expect(STATE_ERROR);
+
+            let message = if !native_types_enabled {
+                format!(
+                    "The native type `@{}` is a preview feature. Enable it with `previewFeatures = [\"{}\"]` in the generator block.",
+                    directive_name,
+                    configuration::preview_features::NATIVE_TYPES
+                )
+            } else if !supported_by_connector {
+                format!("The native type `@{}` is only supported on PostgreSQL.", directive_name)
+            } else {
+                continue;
+            };
+
+            errors.push(DatamodelError::new_directive_validation_error(
+                &message,
+                &directive_name,
+                directive.span,
+            ));
+        }
+
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Deferred foreign keys, `@relation(deferrable: true)`, are only available on PostgreSQL.
+    fn validate_deferrable_relations(&self, ast_model: &ast::Model, model: &dml::Model) -> Result<(), ErrorCollection> {
+        let mut errors = ErrorCollection::new();
+
+        let supported_by_connector = match self
+            .datasource_of(model)
+            .and_then(|name| self.sources.iter().find(|source| source.name() == name))
+        {
+            Some(source) => source.connector_type() == configuration::POSTGRES_SOURCE_NAME,
+            None => true,
+        };
+
+        if supported_by_connector {
+            return Ok(());
+        }
+
+        for field in model.fields() {
+            match &field.field_type {
+                dml::FieldType::Relation(relation_info) if relation_info.deferrable => (),
+                _ => continue,
+            }
+
+            let argument = ast_model
+                .fields
+                .iter()
+                .find(|ast_field| ast_field.name.name == field.name)
+                .and_then(|ast_field| ast_field.directives.iter().find(|d| d.name.name == "relation"))
+                .and_then(|directive| directive.arguments.iter().find(|arg| arg.name.name == "deferrable"))
+                .expect(STATE_ERROR);
+
+            errors.push(DatamodelError::new_directive_validation_error(
+                "Deferrable relations are only supported on PostgreSQL.",
+                "relation",
+                argument.span,
+            ));
+        }
+
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The options of `@@table` are database specific: `engine` and `charset` are only available on MySQL,
+    /// `tablespace` and `fillfactor` only on PostgreSQL.
+    fn validate_table_options(&self, ast_model: &ast::Model, model: &dml::Model) -> Result<(), ErrorCollection> {
+        let mut errors = ErrorCollection::new();
+
+        let connector_type = match self
+            .datasource_of(model)
+            .and_then(|name| self.sources.iter().find(|source| source.name() == name))
+        {
+            Some(source) => source.connector_type(),
+            None => return Ok(()),
+        };
+
+        let (mysql, postgres) = (configuration::MYSQL_SOURCE_NAME, configuration::POSTGRES_SOURCE_NAME);
+        let options = &model.table_options;
+        let option_connectors = [
+            ("engine", options.engine.is_some(), mysql),
+            ("charset", options.charset.is_some(), mysql),
+            ("tablespace", options.tablespace.is_some(), postgres),
+            ("fillfactor", options.fillfactor.is_some(), postgres),
+        ];
+
+        for (name, is_set, supported_connector) in option_connectors.iter() {
+            if !*is_set || connector_type == *supported_connector {
+                continue;
+            }
+
+            let argument = ast_model
+                .directives
+                .iter()
+                .find(|directive| directive.name.name == "table")
+                .and_then(|directive| directive.arguments.iter().find(|arg| arg.name.name == *name))
+                .expect(STATE_ERROR);
+
+            errors.push(DatamodelError::new_directive_validation_error(
+                &format!(
+                    "The table option `{}` is only supported on {}.",
+                    name,
+                    if *supported_connector == mysql {
+                        "MySQL"
+                    } else {
+                        "PostgreSQL"
+                    }
+                ),
+                "table",
+                argument.span,
+            ));
+        }
+
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The `table` and `column` arguments of `@relation` name the relation table of a many-to-many relation and
+    /// its columns, so both sides of the relation have to agree on them.
+    fn validate_relation_tables(
+        &self,
+        ast_schema: &ast::SchemaAst,
+        datamodel: &dml::Datamodel,
+        model: &dml::Model,
+    ) -> Result<(), ErrorCollection> {
+        let mut errors = ErrorCollection::new();
+
+        for field in model.fields() {
+            let relation_info = match &field.field_type {
+                dml::FieldType::Relation(relation_info)
+                    if relation_info.join_table.is_some() || relation_info.join_column.is_some() =>
+                {
+                    relation_info
+                }
+                _ => continue,
+            };
+
+            let directive = ast_schema
+                .find_field(&model.name, &field.name)
+                .and_then(|ast_field| ast_field.directives.iter().find(|d| d.name.name == "relation"))
+                .expect(STATE_ERROR);
+            let argument_span = |name: &str| {
+                directive
+                    .arguments
+                    .iter()
+                    .find(|arg| arg.name.name == name)
+                    .map(|arg| arg.span)
+                    .unwrap_or(directive.span)
+            };
+
+            let related_field = datamodel
+                .find_model(&relation_info.to)
+                .and_then(|related_model| related_model.related_field(&model.name, &relation_info.name, &field.name));
+
+            let related_relation_info = match related_field {
+                Some(related_field)
+                    if field.arity == dml::FieldArity::List && related_field.arity == dml::FieldArity::List =>
+                {
+                    match &related_field.field_type {
+                        dml::FieldType::Relation(related_relation_info) => related_relation_info,
+                        _ => continue,
+                    }
+                }
+                _ => {
+                    errors.push(DatamodelError::new_directive_validation_error(
+                        "The arguments `table` and `column` are only allowed on many-to-many relations.",
+                        "relation",
+                        directive.span,
+                    ));
+                    continue;
+                }
+            };
+
+            if let (Some(join_table), Some(related_join_table)) =
+                (&relation_info.join_table, &related_relation_info.join_table)
+            {
+                if join_table != related_join_table {
+                    errors.push(DatamodelError::new_directive_validation_error(
+                        &format!(
+                            "Both sides of the relation must use the same relation table, found `{}` and `{}`.",
+                            join_table, related_join_table
+                        ),
+                        "relation",
+                        argument_span("table"),
+                    ));
+                }
+            }
+
+            if let (Some(join_column), Some(related_join_column)) =
+                (&relation_info.join_column, &related_relation_info.join_column)
+            {
+                if join_column == related_join_column {
+                    errors.push(DatamodelError::new_directive_validation_error(
+                        &format!(
+                            "Both sides of the relation use the column `{}` of the relation table.",
+                            join_column
+                        ),
+                        "relation",
+                        argument_span("column"),
+                    ));
+                }
+            }
+        }
+
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The history tables of `@@audited` models are written by triggers, only generated on PostgreSQL and MySQL.
+    fn validate_audited(&self, ast_model: &ast::Model, model: &dml::Model) -> Result<(), DatamodelError> {
+        if !model.is_audited {
+            return Ok(());
+        }
+
+        let connector_type = match self
+            .datasource_of(model)
+            .and_then(|name| self.sources.iter().find(|source| source.name() == name))
+        {
+            Some(source) => source.connector_type(),
+            None => return Ok(()),
+        };
+
+        if connector_type == configuration::MYSQL_SOURCE_NAME || connector_type == configuration::POSTGRES_SOURCE_NAME {
+            return Ok(());
+        }
+
+        let directive = ast_model
+            .directives
+            .iter()
+            .find(|directive| directive.name.name == "audited")
+            .expect(STATE_ERROR);
+
+        Err(DatamodelError::new_directive_validation_error(
+            "Audited models are only supported on PostgreSQL and MySQL.",
+            "audited",
+            directive.span,
+        ))
+    }
+
     fn validate_model_has_id(&self, ast_model: &ast::Model, model: &dml::Model) -> Result<(), DatamodelError> {
         let multiple_single_field_id_error = Err(DatamodelError::new_model_validation_error(
             "At most one field must be marked as the id field with the `@id` directive.",
@@ -136,6 +525,64 @@ impl<'a> Validator<'a> {
         Ok(())
     }
 
+    /// Ensures that a model is stored in a declared datasource, and only relates to models of the same datasource.
+    fn validate_datasource(
+        &self,
+        ast_schema: &ast::SchemaAst,
+        datamodel: &dml::Datamodel,
+        model: &dml::Model,
+    ) -> Result<(), DatamodelError> {
+        let ast_model = ast_schema.find_model(&model.name).expect(STATE_ERROR);
+
+        if let Some(name) = &model.datasource {
+            if !self.sources.iter().any(|source| source.name() == name) {
+                let span = ast_model
+                    .directives
+                    .iter()
+                    .find(|directive| directive.name.name == "datasource")
+                    .map(|directive| directive.span)
+                    .unwrap_or(ast_model.span);
+
+                return Err(DatamodelError::new_directive_validation_error(
+                    &format!("The datasource `{}` is not defined.", name),
+                    "datasource",
+                    span,
+                ));
+            }
+        }
+
+        for field in model.fields() {
+            if let dml::FieldType::Relation(rel) = &field.field_type {
+                let related = datamodel.find_model(&rel.to).expect(STATE_ERROR);
+
+                if self.datasource_of(model) != self.datasource_of(related) {
+                    return Err(DatamodelError::new_model_validation_error(
+                        &format!(
+                            "Relations between models of different datasources are not supported. The field `{}` in model `{}` refers to `{}`, which is stored in the datasource `{}` instead of `{}`.",
+                            &field.name,
+                            &model.name,
+                            &related.name,
+                            self.datasource_of(related).unwrap_or_default(),
+                            self.datasource_of(model).unwrap_or_default(),
+                        ),
+                        &model.name,
+                        ast_schema.find_field(&model.name, &field.name).expect(STATE_ERROR).span,
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The name of the datasource the model is stored in.
+    fn datasource_of<'m>(&'m self, model: &'m dml::Model) -> Option<&'m str> {
+        match &model.datasource {
+            Some(name) => Some(name),
+            None => self.sources.first().map(|source| source.name().as_str()),
+        }
+    }
+
     /// Elegantly checks if any relations in the model are ambigious.
     fn validate_relations_not_ambiguous(
         &self,
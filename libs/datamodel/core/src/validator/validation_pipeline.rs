@@ -14,14 +14,19 @@ impl<'a> ValidationPipeline<'a> {
     ///
     /// The directives defined by the given sources will be namespaced.
     pub fn with_sources(sources: &'a [Box<dyn configuration::Source + Send + Sync>]) -> ValidationPipeline<'a> {
-        let source = sources.first();
         ValidationPipeline {
             lifter: LiftAstToDml::with_sources(sources),
-            validator: Validator::new(source),
+            validator: Validator::new(sources),
             standardiser: Standardiser::new(),
         }
     }
 
+    /// Enables the given preview features, e.g. native types.
+    pub fn with_preview_features(mut self, preview_features: Vec<String>) -> ValidationPipeline<'a> {
+        self.validator.preview_features = preview_features;
+        self
+    }
+
     /// Validates an AST semantically and promotes it to a datamodel/schema.
     ///
     /// This method will attempt to
@@ -1,5 +1,5 @@
 use crate::common::*;
-use datamodel::common::ScalarType;
+use datamodel::{ast::Span, common::ScalarType, error::DatamodelError};
 
 #[test]
 fn parse_basic_model() {
@@ -47,6 +47,21 @@ fn parse_basic_enum() {
     role_enum.assert_has_value("HHorse99");
 }
 
+#[test]
+fn fail_on_enum_without_values() {
+    let dml = r#"
+    enum Roles {
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_validation_error(
+        "The enum \"Roles\" must have at least one value.",
+        Span::new(5, 23),
+    ));
+}
+
 #[test]
 fn parse_comments() {
     let dml = r#"
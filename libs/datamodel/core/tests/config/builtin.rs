@@ -181,6 +181,89 @@ fn enable_and_disable_source_through_boolean_env_var() {
     assert_eq!(source3.url().value, "file:../db/five.db");
 }
 
+#[test]
+fn relation_mode_is_loaded_and_serialized() {
+    const DATAMODEL: &str = r#"
+        datasource pg1 {
+            provider = "postgresql"
+            url = "https://localhost/postgres1"
+            relationMode = "prisma"
+        }
+    "#;
+
+    let config = datamodel::parse_configuration(DATAMODEL).unwrap();
+    let source = &config.datasources[0];
+
+    assert_eq!(source.relation_mode(), datamodel::configuration::RelationMode::Prisma);
+
+    let rendered = datamodel::json::mcf::render_sources_to_json(&config.datasources);
+
+    let expected = r#"[
+  {
+    "name": "pg1",
+    "connectorType": "postgresql",
+    "url": {
+      "fromEnvVar": null,
+      "value": "https://localhost/postgres1"
+    },
+    "relationMode": "prisma"
+  }
+]"#;
+
+    assert_eq_json(&rendered, expected);
+}
+
+#[test]
+fn fail_to_load_sources_with_an_invalid_relation_mode() {
+    const DATAMODEL: &str = r#"
+        datasource pg1 {
+            provider = "postgresql"
+            url = "https://localhost/postgres1"
+            relationMode = "triggers"
+        }
+    "#;
+
+    let res = datamodel::parse_configuration(DATAMODEL);
+
+    if let Err(error) = res {
+        error.assert_is(DatamodelError::new_literal_parser_error(
+            "relation mode",
+            "triggers",
+            datamodel::ast::Span::new(137, 147),
+        ));
+    } else {
+        panic!("Expected error.")
+    }
+}
+
+#[test]
+fn overrides_replace_the_url_of_a_source() {
+    const DATAMODEL: &str = r#"
+        datasource pg1 {
+            provider = "postgresql"
+            url = env("PG1_URL_NOT_SET")
+        }
+
+        datasource pg2 {
+            provider = "postgresql"
+            url = "https://localhost/postgres2"
+        }
+    "#;
+
+    let overrides = vec![datamodel::SourceOverride {
+        name: "pg1".to_owned(),
+        url: "https://localhost/test".into(),
+    }];
+
+    assert!(datamodel::parse_configuration(DATAMODEL).is_err());
+
+    let config = datamodel::parse_configuration_with_overrides(DATAMODEL, &overrides).unwrap();
+
+    assert_eq!(config.datasources[0].url().value, "https://localhost/test");
+    assert_eq!(config.datasources[0].url().from_env_var, None);
+    assert_eq!(config.datasources[1].url().value, "https://localhost/postgres2");
+}
+
 fn assert_eq_json(a: &str, b: &str) {
     let json_a: serde_json::Value = serde_json::from_str(a).expect("The String a was not valid JSON.");
     let json_b: serde_json::Value = serde_json::from_str(b).expect("The String b was not valid JSON.");
@@ -10,6 +10,7 @@ generator js1 {
 generator go {
     provider = "go"
     binaryTargets = ["a", "b"]
+    previewFeatures = ["nativeTypes"]
 }"#;
 
 #[test]
@@ -23,6 +24,7 @@ fn serialize_generators_to_cmf() {
     "provider": "javascript",
     "output": "../../js",
     "binaryTargets": [],
+    "previewFeatures": [],
     "config": {}
   },
   {
@@ -30,6 +32,7 @@ fn serialize_generators_to_cmf() {
     "provider": "go",
     "output": null,
     "binaryTargets": ["a","b"],
+    "previewFeatures": ["nativeTypes"],
     "config": {}
   }
 ]"#;
@@ -39,6 +42,47 @@ fn serialize_generators_to_cmf() {
     assert_eq_json(&rendered, expected);
 }
 
+#[test]
+fn preview_features_of_all_generators_are_enabled() {
+    let config = datamodel::parse_configuration(DATAMODEL).unwrap();
+
+    assert_eq!(config.preview_features(), vec!["nativeTypes"]);
+}
+
+#[test]
+fn preview_features_must_render_back_into_the_generator() {
+    let config = datamodel::parse_configuration(DATAMODEL).unwrap();
+    let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+    let rendered = datamodel::render_datamodel_and_config_to_string(&datamodel, &config).unwrap();
+
+    assert_eq!(
+        datamodel::parse_configuration(&rendered).unwrap().preview_features(),
+        vec!["nativeTypes"]
+    );
+}
+
+#[test]
+fn fail_to_load_generator_with_unknown_preview_features() {
+    let dml = r#"
+generator js1 {
+    provider = "javascript"
+    previewFeatures = ["nativeTypes", "teleportation"]
+}
+"#;
+
+    let res = datamodel::parse_configuration(dml);
+
+    if let Err(error) = res {
+        error.assert_is(DatamodelError::new_preview_feature_not_known_error(
+            "teleportation",
+            &["nativeTypes", "databaseUuidDefaults"],
+            datamodel::ast::Span::new(83, 98),
+        ));
+    } else {
+        panic!("Expected error.")
+    }
+}
+
 fn assert_eq_json(a: &str, b: &str) {
     let json_a: serde_json::Value = serde_json::from_str(a).expect("The String a was not valid JSON.");
     let json_b: serde_json::Value = serde_json::from_str(b).expect("The String b was not valid JSON.");
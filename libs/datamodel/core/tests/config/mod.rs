@@ -1,3 +1,5 @@
 pub mod builtin;
 pub mod generators;
+#[cfg(feature = "connector-registry")]
+pub mod registry;
 pub mod source_plugin;
@@ -0,0 +1,69 @@
+use datamodel::{
+    ast::Span, builtin::SqliteSourceDefinition, error::DatamodelError, register_source_definition, RelationMode,
+    Source, SourceDefinition, StringFromEnvVar,
+};
+use std::sync::Arc;
+
+/// A provider backed by the SQLite source, or failing to create its sources if it is invalid.
+struct CustomSourceDefinition {
+    connector_type: &'static str,
+    valid: bool,
+}
+
+impl SourceDefinition for CustomSourceDefinition {
+    fn connector_type(&self) -> &'static str {
+        self.connector_type
+    }
+
+    fn create(
+        &self,
+        name: &str,
+        url: StringFromEnvVar,
+        relation_mode: RelationMode,
+        documentation: &Option<String>,
+    ) -> Result<Box<dyn Source + Send + Sync>, DatamodelError> {
+        if self.valid {
+            SqliteSourceDefinition::new().create(name, url, relation_mode, documentation)
+        } else {
+            Err(DatamodelError::new_validation_error(
+                "The source definition is invalid.",
+                Span::empty(),
+            ))
+        }
+    }
+}
+
+fn register(connector_type: &'static str, valid: bool) {
+    register_source_definition(Arc::new(CustomSourceDefinition { connector_type, valid }));
+}
+
+fn datasource(provider: &str) -> String {
+    format!(
+        r#"
+        datasource db {{
+            provider = "{}"
+            url      = "file:dev.db"
+        }}
+        "#,
+        provider
+    )
+}
+
+#[test]
+fn registered_providers_are_valid_in_datasources() {
+    assert!(datamodel::parse_configuration(&datasource("registry-a")).is_err());
+
+    register("registry-a", true);
+
+    let config = datamodel::parse_configuration(&datasource("registry-a")).unwrap();
+    assert_eq!(config.datasources.len(), 1);
+}
+
+#[test]
+fn registering_a_provider_again_replaces_its_definition() {
+    register("registry-replaced", false);
+    assert!(datamodel::parse_configuration(&datasource("registry-replaced")).is_err());
+
+    register("registry-replaced", true);
+    assert!(datamodel::parse_configuration(&datasource("registry-replaced")).is_ok());
+}
@@ -0,0 +1,67 @@
+use crate::common::*;
+use datamodel::{ast::Span, error::DatamodelError, render_datamodel_to_string};
+
+#[test]
+fn audited_directive_must_work() {
+    let dml = r#"
+    datasource db {
+        provider = "postgresql"
+        url      = "postgresql://localhost:5432"
+    }
+
+    model Post {
+        id Int @id
+
+        @@audited
+    }
+
+    model User {
+        id Int @id
+    }
+    "#;
+
+    let schema = parse(dml);
+
+    assert!(schema.assert_has_model("Post").is_audited);
+    assert!(!schema.assert_has_model("User").is_audited);
+}
+
+#[test]
+fn audited_directive_must_serialize_to_valid_dml() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@audited
+    }
+    "#;
+
+    let schema = parse(dml);
+    let rendered = parse(&render_datamodel_to_string(&schema).unwrap());
+
+    assert!(rendered.assert_has_model("Post").is_audited);
+}
+
+#[test]
+fn audited_directive_must_fail_on_sqlite() {
+    let dml = r#"
+    datasource db {
+        provider = "sqlite"
+        url      = "file:dev.db"
+    }
+
+    model Post {
+        id Int @id
+
+        @@audited
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Audited models are only supported on PostgreSQL and MySQL.",
+        "audited",
+        Span::new(134, 143),
+    ));
+}
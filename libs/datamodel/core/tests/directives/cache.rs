@@ -0,0 +1,56 @@
+use crate::common::*;
+use datamodel::{ast::Span, error::DatamodelError, render_datamodel_to_string};
+
+#[test]
+fn cache_directive_must_work() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@cache(ttl: 60)
+    }
+
+    model User {
+        id Int @id
+    }
+    "#;
+
+    let schema = parse(dml);
+    assert_eq!(schema.assert_has_model("Post").cache_ttl, Some(60));
+    assert_eq!(schema.assert_has_model("User").cache_ttl, None);
+}
+
+#[test]
+fn cache_directive_must_serialize_to_valid_dml() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@cache(ttl: 60)
+    }
+    "#;
+
+    let schema = parse(dml);
+    let rendered = parse(&render_datamodel_to_string(&schema).unwrap());
+
+    assert_eq!(rendered.assert_has_model("Post").cache_ttl, Some(60));
+}
+
+#[test]
+fn cache_directive_must_fail_for_non_positive_ttls() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@cache(ttl: 0)
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The cache ttl must be a positive number of seconds.",
+        "cache",
+        Span::new(48, 61),
+    ));
+}
@@ -0,0 +1,94 @@
+use crate::common::*;
+use datamodel::{ast::Span, error::DatamodelError, render_datamodel_to_string};
+
+#[test]
+fn computed_directive_must_work() {
+    let dml = r#"
+    model User {
+        id        Int    @id
+        firstName String @map("first_name")
+        lastName  String @map("last_name")
+        fullName  String @computed("first_name || ' ' || last_name")
+    }
+    "#;
+
+    let schema = parse(dml);
+    let user_model = schema.assert_has_model("User");
+
+    assert_eq!(
+        user_model.assert_has_field("fullName").computed,
+        Some("first_name || ' ' || last_name".to_owned())
+    );
+    assert_eq!(user_model.assert_has_field("firstName").computed, None);
+}
+
+#[test]
+fn computed_directive_must_serialize_to_valid_dml() {
+    let dml = r#"
+    model User {
+        id       Int    @id
+        fullName String @computed("first_name || ' ' || last_name")
+    }
+    "#;
+
+    let schema = parse(dml);
+    let rendered = parse(&render_datamodel_to_string(&schema).unwrap());
+
+    assert_eq!(
+        rendered.assert_has_model("User").assert_has_field("fullName").computed,
+        Some("first_name || ' ' || last_name".to_owned())
+    );
+}
+
+#[test]
+fn computed_directive_must_fail_for_lists() {
+    let dml = r#"
+    model User {
+        id   Int      @id
+        tags String[] @computed("tags")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Fields that are marked with @computed can not be lists.",
+        "computed",
+        Span::new(67, 83),
+    ));
+}
+
+#[test]
+fn computed_directive_must_fail_with_a_default_value() {
+    let dml = r#"
+    model User {
+        id       Int    @id
+        fullName String @default("") @computed("first_name || ' ' || last_name")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Computed fields can not have a default value.",
+        "computed",
+        Span::new(84, 126),
+    ));
+}
+
+#[test]
+fn computed_directive_must_fail_on_id_fields() {
+    let dml = r#"
+    model User {
+        id Int @id @computed("1")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Computed fields can not be id fields.",
+        "computed",
+        Span::new(38, 51),
+    ));
+}
@@ -0,0 +1,108 @@
+use crate::common::*;
+use datamodel::{ast::Span, error::DatamodelError};
+
+#[test]
+fn datasource_directive_assigns_models_to_datasources() {
+    let dml = r#"
+    datasource main {
+        provider = "postgresql"
+        url = "postgresql://localhost:5432/main"
+    }
+
+    datasource analytics {
+        provider = "mysql"
+        url = "mysql://localhost:3306/analytics"
+    }
+
+    model User {
+        id Int @id
+    }
+
+    model Event {
+        id Int @id
+
+        @@datasource("analytics")
+    }
+    "#;
+
+    let schema = parse(dml);
+    assert_eq!(schema.assert_has_model("User").datasource, None);
+    assert_eq!(
+        schema.assert_has_model("Event").datasource,
+        Some("analytics".to_owned())
+    );
+}
+
+#[test]
+fn relations_between_datasources_must_fail() {
+    let dml = r#"
+    datasource main {
+        provider = "postgresql"
+        url = "postgresql://localhost:5432/main"
+    }
+
+    datasource analytics {
+        provider = "mysql"
+        url = "mysql://localhost:3306/analytics"
+    }
+
+    model User {
+        id Int @id
+        events Event[]
+    }
+
+    model Event {
+        id Int @id
+        user User
+
+        @@datasource("analytics")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+    errors.assert_length(2);
+    errors.assert_is_at(
+        0,
+        DatamodelError::new_model_validation_error(
+            "Relations between models of different datasources are not supported. The field `events` in model `User` refers to `Event`, which is stored in the datasource `analytics` instead of `main`.",
+            "User",
+            Span::new(265, 279),
+        ),
+    );
+    errors.assert_is_at(
+        1,
+        DatamodelError::new_model_validation_error(
+            "Relations between models of different datasources are not supported. The field `user` in model `Event` refers to `User`, which is stored in the datasource `main` instead of `analytics`.",
+            "Event",
+            Span::new(332, 341),
+        ),
+    );
+}
+
+#[test]
+fn undefined_datasources_must_fail() {
+    let dml = r#"
+    datasource main {
+        provider = "postgresql"
+        url = "postgresql://localhost:5432/main"
+    }
+
+    datasource analytics {
+        provider = "mysql"
+        url = "mysql://localhost:3306/analytics"
+    }
+
+    model Event {
+        id Int @id
+
+        @@datasource("warehouse")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The datasource `warehouse` is not defined.",
+        "datasource",
+        Span::new(269, 292),
+    ));
+}
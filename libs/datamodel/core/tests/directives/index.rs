@@ -1,4 +1,7 @@
-use datamodel::{ast::Span, error::*, render_datamodel_to_string, IndexDefinition, IndexType};
+use datamodel::{
+    ast::Span, error::*, render_datamodel_to_string, IndexDefinition, IndexFieldOptions, IndexType, SortOrder,
+};
+use std::collections::BTreeMap;
 
 use crate::common::*;
 
@@ -20,6 +23,7 @@ fn basic_index_must_work() {
         name: None,
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Normal,
+        field_options: BTreeMap::new(),
     });
 }
 
@@ -44,6 +48,7 @@ fn indexes_on_relation_fields_must_work() {
         name: None,
         fields: vec!["identification".to_string()],
         tpe: IndexType::Normal,
+        field_options: BTreeMap::new(),
     });
 }
 
@@ -65,6 +70,7 @@ fn the_name_argument_must_work() {
         name: Some("MyIndexName".to_string()),
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Normal,
+        field_options: BTreeMap::new(),
     });
 }
 
@@ -88,12 +94,14 @@ fn multiple_index_must_work() {
         name: None,
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Normal,
+        field_options: BTreeMap::new(),
     });
 
     user_model.assert_has_index(IndexDefinition {
         name: Some("MyIndexName".to_string()),
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Normal,
+        field_options: BTreeMap::new(),
     });
 }
 
@@ -131,3 +139,57 @@ fn index_directives_must_serialize_to_valid_dml() {
 
     assert!(datamodel::parse_datamodel(&render_datamodel_to_string(&schema).unwrap()).is_ok());
 }
+
+#[test]
+fn index_field_options_must_work() {
+    let dml = r#"
+    model Post {
+        id     Int    @id
+        title  String
+        author String
+
+        @@index([title(length: 100, sort: Desc), author(sort: Asc)])
+    }
+    "#;
+
+    let schema = parse(dml);
+    let post_model = schema.assert_has_model("Post");
+
+    let mut field_options = BTreeMap::new();
+    field_options.insert(
+        "title".to_string(),
+        IndexFieldOptions {
+            sort_order: SortOrder::Desc,
+            length: Some(100),
+        },
+    );
+
+    post_model.assert_has_index(IndexDefinition {
+        name: None,
+        fields: vec!["title".to_string(), "author".to_string()],
+        tpe: IndexType::Normal,
+        field_options,
+    });
+
+    let rendered = render_datamodel_to_string(&schema).unwrap();
+
+    assert!(rendered.contains("@@index([title(length: 100, sort: Desc), author])"));
+    assert_eq!(parse(&rendered), schema);
+}
+
+#[test]
+fn must_error_on_unknown_index_field_options() {
+    let dml = r#"
+    model Post {
+        id    Int    @id
+        title String
+
+        @@index([title(size: 100)])
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    assert_eq!(errors.errors.len(), 1);
+    assert!(format!("{}", errors.errors[0]).contains("Unknown argument `size` of the index field `title`"));
+}
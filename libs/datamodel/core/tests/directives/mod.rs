@@ -1,15 +1,22 @@
+pub mod audited;
 pub mod builtin_directives;
+pub mod cache;
+pub mod computed;
+pub mod datasource;
 pub mod default_negative;
 pub mod default_positive;
 pub mod id_negative;
 pub mod id_positive;
 pub mod index;
 pub mod map;
+pub mod native_types;
 pub mod relations_basic;
 pub mod relations_consistency;
 pub mod relations_legacy;
 pub mod relations_negative;
 pub mod relations_positive;
+pub mod soft_delete;
+pub mod table;
 pub mod unique;
 pub mod updated_at_negative;
 pub mod updated_at_positive;
@@ -0,0 +1,220 @@
+use crate::common::*;
+use datamodel::{ast::Span, error::DatamodelError, render_datamodel_and_config_to_string};
+
+#[test]
+fn citext_directive_must_work() {
+    let dml = r#"
+    generator client {
+        provider        = "prisma-client-js"
+        previewFeatures = ["nativeTypes"]
+    }
+
+    datasource db {
+        provider = "postgresql"
+        url      = "postgresql://localhost:5432"
+    }
+
+    model User {
+        id    Int    @id
+        email String @unique @db.Citext
+        name  String
+    }
+    "#;
+
+    let schema = parse(dml);
+    let user_model = schema.assert_has_model("User");
+
+    assert_eq!(
+        user_model.assert_has_field("email").native_type,
+        Some("Citext".to_owned())
+    );
+    assert_eq!(user_model.assert_has_field("name").native_type, None);
+}
+
+#[test]
+fn citext_directive_must_serialize_to_valid_dml() {
+    let dml = r#"
+    generator client {
+        provider        = "prisma-client-js"
+        previewFeatures = ["nativeTypes"]
+    }
+
+    model User {
+        id    Int    @id
+        email String @db.Citext
+    }
+    "#;
+
+    let schema = parse(dml);
+    let config = datamodel::parse_configuration(dml).unwrap();
+    let rendered = parse(&render_datamodel_and_config_to_string(&schema, &config).unwrap());
+
+    assert_eq!(
+        rendered.assert_has_model("User").assert_has_field("email").native_type,
+        Some("Citext".to_owned())
+    );
+}
+
+#[test]
+fn citext_directive_must_fail_on_non_string_fields() {
+    let dml = r#"
+    model User {
+        id  Int @id
+        age Int @db.Citext
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Fields that are marked with @db.Citext must be of type String.",
+        "db.Citext",
+        Span::new(55, 64),
+    ));
+}
+
+#[test]
+fn citext_directive_must_fail_outside_of_postgres() {
+    let dml = r#"
+    generator client {
+        provider        = "prisma-client-js"
+        previewFeatures = ["nativeTypes"]
+    }
+
+    datasource db {
+        provider = "mysql"
+        url      = "mysql://localhost:3306"
+    }
+
+    model User {
+        id    Int    @id
+        email String @unique @db.Citext
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The native type `@db.Citext` is only supported on PostgreSQL.",
+        "db.Citext",
+        Span::new(288, 297),
+    ));
+}
+
+#[test]
+fn citext_directive_must_fail_without_the_preview_feature() {
+    let dml = r#"
+    model User {
+        id    Int    @id
+        email String @db.Citext
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The native type `@db.Citext` is a preview feature. Enable it with `previewFeatures = [\"nativeTypes\"]` in the generator block.",
+        "db.Citext",
+        Span::new(65, 74),
+    ));
+}
+
+#[test]
+fn decimal_directive_must_work_on_all_sql_connectors() {
+    let dml = r#"
+    generator client {
+        provider        = "prisma-client-js"
+        previewFeatures = ["nativeTypes"]
+    }
+
+    datasource db {
+        provider = "mysql"
+        url      = "mysql://localhost:3306"
+    }
+
+    model Product {
+        id     Int     @id
+        price  Decimal @db.Decimal(10, scale: 2)
+        weight Decimal @db.Decimal(8)
+    }
+    "#;
+
+    let schema = parse(dml);
+    let product_model = schema.assert_has_model("Product");
+
+    assert_eq!(
+        product_model.assert_has_field("price").native_type,
+        Some("Decimal(10,2)".to_owned())
+    );
+    assert_eq!(
+        product_model.assert_has_field("price").decimal_precision(),
+        Some((10, 2))
+    );
+    assert_eq!(
+        product_model.assert_has_field("weight").decimal_precision(),
+        Some((8, 0))
+    );
+}
+
+#[test]
+fn decimal_directive_must_serialize_to_valid_dml() {
+    let dml = r#"
+    generator client {
+        provider        = "prisma-client-js"
+        previewFeatures = ["nativeTypes"]
+    }
+
+    model Product {
+        id    Int     @id
+        price Decimal @db.Decimal(10, scale: 2)
+    }
+    "#;
+
+    let schema = parse(dml);
+    let config = datamodel::parse_configuration(dml).unwrap();
+    let rendered = parse(&render_datamodel_and_config_to_string(&schema, &config).unwrap());
+
+    assert_eq!(
+        rendered
+            .assert_has_model("Product")
+            .assert_has_field("price")
+            .decimal_precision(),
+        Some((10, 2))
+    );
+}
+
+#[test]
+fn decimal_directive_must_fail_on_non_decimal_fields() {
+    let dml = r#"
+    model Product {
+        id    Int @id
+        price Int @db.Decimal(10)
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Fields that are marked with @db.Decimal must be of type Decimal.",
+        "db.Decimal",
+        Span::new(62, 76),
+    ));
+}
+
+#[test]
+fn decimal_directive_must_fail_on_a_scale_larger_than_the_precision() {
+    let dml = r#"
+    model Product {
+        id    Int     @id
+        price Decimal @db.Decimal(4, scale: 5)
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The scale must be between 0 and the precision, and at most 30.",
+        "db.Decimal",
+        Span::new(70, 93),
+    ));
+}
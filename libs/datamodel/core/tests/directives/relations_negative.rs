@@ -205,3 +205,106 @@ fn should_fail_on_conflicting_generated_back_relation_fields() {
         Span::new(98, 152),
     ));
 }
+
+#[test]
+fn should_fail_on_deferrable_relations_outside_of_postgres() {
+    let dml = r#"
+    datasource db {
+        provider = "mysql"
+        url      = "mysql://localhost:3306"
+    }
+
+    model User {
+        id Int @id
+        profile Profile @relation(references: [id], deferrable: true)
+    }
+
+    model Profile {
+        id Int @id
+        user User
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Deferrable relations are only supported on PostgreSQL.",
+        "relation",
+        Span::new(187, 203),
+    ));
+}
+
+#[test]
+fn should_fail_on_relation_tables_outside_of_many_to_many_relations() {
+    let dml = r#"
+    model User {
+        id Int @id
+        posts Post[] @relation(table: "user_posts")
+    }
+
+    model Post {
+        id Int @id
+        user User
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The arguments `table` and `column` are only allowed on many-to-many relations.",
+        "relation",
+        Span::new(59, 88),
+    ));
+}
+
+#[test]
+fn should_fail_on_conflicting_relation_table_names() {
+    let dml = r#"
+    model Post {
+        id Int @id
+        tags Tag[] @relation(table: "post_tags")
+    }
+
+    model Tag {
+        id Int @id
+        posts Post[] @relation(table: "tags_of_posts")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_length(2).assert_is_at(
+        0,
+        DatamodelError::new_directive_validation_error(
+            "Both sides of the relation must use the same relation table, found `post_tags` and `tags_of_posts`.",
+            "relation",
+            Span::new(66, 84),
+        ),
+    );
+}
+
+#[test]
+fn should_fail_on_relation_table_columns_used_by_both_sides() {
+    let dml = r#"
+    model Post {
+        id Int @id
+        tags Tag[] @relation(column: "id")
+    }
+
+    model Tag {
+        id Int @id
+        posts Post[] @relation(column: "id")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_length(2).assert_is_at(
+        0,
+        DatamodelError::new_directive_validation_error(
+            "Both sides of the relation use the column `id` of the relation table.",
+            "relation",
+            Span::new(66, 78),
+        ),
+    );
+}
@@ -107,3 +107,72 @@ fn must_generate_back_relation_fields_for_named_relation_fields() {
         .assert_relation_name("AssignedTodos")
         .assert_relation_to("Todo");
 }
+
+#[test]
+fn deferrable_relations_must_work_and_serialize() {
+    let dml = r#"
+    model User {
+        id Int @id
+        profile Profile @relation(references: [id], deferrable: true)
+    }
+
+    model Profile {
+        id Int @id
+        user User
+    }
+    "#;
+
+    let schema = parse(dml);
+    let rendered = parse(&datamodel::render_datamodel_to_string(&schema).unwrap());
+
+    for datamodel in &[schema, rendered] {
+        let user_model = datamodel.assert_has_model("User");
+        let profile_model = datamodel.assert_has_model("Profile");
+
+        match &user_model.assert_has_field("profile").field_type {
+            dml::FieldType::Relation(relation_info) => assert!(relation_info.deferrable),
+            _ => panic!("Expected a relation field."),
+        }
+
+        match &profile_model.assert_has_field("user").field_type {
+            dml::FieldType::Relation(relation_info) => assert!(!relation_info.deferrable),
+            _ => panic!("Expected a relation field."),
+        }
+    }
+}
+
+#[test]
+fn relation_table_names_must_work_and_serialize() {
+    let dml = r#"
+    model Post {
+        id Int @id
+        tags Tag[] @relation(table: "post_tags", column: "post_id")
+    }
+
+    model Tag {
+        id Int @id
+        posts Post[] @relation(column: "tag_id")
+    }
+    "#;
+
+    let schema = parse(dml);
+    let rendered = parse(&datamodel::render_datamodel_to_string(&schema).unwrap());
+
+    for datamodel in &[schema, rendered] {
+        match &datamodel.assert_has_model("Post").assert_has_field("tags").field_type {
+            dml::FieldType::Relation(relation_info) => {
+                assert_eq!(relation_info.join_table.as_deref(), Some("post_tags"));
+                assert_eq!(relation_info.join_column.as_deref(), Some("post_id"));
+            }
+            _ => panic!("Expected a relation field."),
+        }
+
+        match &datamodel.assert_has_model("Tag").assert_has_field("posts").field_type {
+            dml::FieldType::Relation(relation_info) => {
+                assert_eq!(relation_info.join_table, None);
+                assert_eq!(relation_info.join_column.as_deref(), Some("tag_id"));
+            }
+            _ => panic!("Expected a relation field."),
+        }
+    }
+}
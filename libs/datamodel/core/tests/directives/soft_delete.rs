@@ -0,0 +1,84 @@
+use crate::common::*;
+use datamodel::{ast::Span, error::DatamodelError, render_datamodel_to_string};
+
+#[test]
+fn soft_delete_directive_must_work() {
+    let dml = r#"
+    model Post {
+        id        Int       @id
+        deletedAt DateTime?
+
+        @@softDelete(field: deletedAt)
+    }
+
+    model User {
+        id Int @id
+    }
+    "#;
+
+    let schema = parse(dml);
+    assert_eq!(
+        schema.assert_has_model("Post").soft_delete_field,
+        Some("deletedAt".to_owned())
+    );
+    assert_eq!(schema.assert_has_model("User").soft_delete_field, None);
+}
+
+#[test]
+fn soft_delete_directive_must_serialize_to_valid_dml() {
+    let dml = r#"
+    model Post {
+        id        Int       @id
+        deletedAt DateTime?
+
+        @@softDelete(field: deletedAt)
+    }
+    "#;
+
+    let schema = parse(dml);
+    let rendered = parse(&render_datamodel_to_string(&schema).unwrap());
+
+    assert_eq!(
+        rendered.assert_has_model("Post").soft_delete_field,
+        Some("deletedAt".to_owned())
+    );
+}
+
+#[test]
+fn soft_delete_directive_must_fail_for_unknown_fields() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@softDelete(field: deletedAt)
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The soft delete field `deletedAt` is not defined on the model.",
+        "softDelete",
+        Span::new(48, 76),
+    ));
+}
+
+#[test]
+fn soft_delete_directive_must_fail_for_required_fields() {
+    let dml = r#"
+    model Post {
+        id        Int      @id
+        deletedAt DateTime
+
+        @@softDelete(field: deletedAt)
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The soft delete field `deletedAt` must be an optional DateTime field.",
+        "softDelete",
+        Span::new(87, 115),
+    ));
+}
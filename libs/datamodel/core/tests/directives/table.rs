@@ -0,0 +1,132 @@
+use crate::common::*;
+use datamodel::{ast::Span, error::DatamodelError, render_datamodel_to_string};
+
+#[test]
+fn table_directive_must_work_on_mysql() {
+    let dml = r#"
+    datasource db {
+        provider = "mysql"
+        url      = "mysql://localhost:3306"
+    }
+
+    model Post {
+        id Int @id
+
+        @@table(engine: "MyISAM", charset: "latin1")
+    }
+
+    model User {
+        id Int @id
+    }
+    "#;
+
+    let schema = parse(dml);
+    let options = &schema.assert_has_model("Post").table_options;
+
+    assert_eq!(options.engine.as_deref(), Some("MyISAM"));
+    assert_eq!(options.charset.as_deref(), Some("latin1"));
+    assert!(schema.assert_has_model("User").table_options.is_empty());
+}
+
+#[test]
+fn table_directive_must_work_on_postgres() {
+    let dml = r#"
+    datasource db {
+        provider = "postgresql"
+        url      = "postgresql://localhost:5432"
+    }
+
+    model Post {
+        id Int @id
+
+        @@table(tablespace: "fast_disk", fillfactor: 70)
+    }
+    "#;
+
+    let schema = parse(dml);
+    let options = &schema.assert_has_model("Post").table_options;
+
+    assert_eq!(options.tablespace.as_deref(), Some("fast_disk"));
+    assert_eq!(options.fillfactor, Some(70));
+}
+
+#[test]
+fn table_directive_must_serialize_to_valid_dml() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@table(engine: "InnoDB", fillfactor: 70)
+    }
+    "#;
+
+    let schema = parse(dml);
+    let rendered = parse(&render_datamodel_to_string(&schema).unwrap());
+
+    assert_eq!(
+        rendered.assert_has_model("Post").table_options,
+        schema.assert_has_model("Post").table_options
+    );
+}
+
+#[test]
+fn table_directive_must_fail_for_out_of_range_fillfactors() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@table(fillfactor: 5)
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The fillfactor must be between 10 and 100.",
+        "table",
+        Span::new(66, 67),
+    ));
+}
+
+#[test]
+fn table_directive_must_fail_for_invalid_identifiers() {
+    let dml = r#"
+    model Post {
+        id Int @id
+
+        @@table(engine: "InnoDB; DROP TABLE Post")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "`InnoDB; DROP TABLE Post` is not a valid table option. Only letters, digits and underscores are allowed.",
+        "table",
+        Span::new(62, 87),
+    ));
+}
+
+#[test]
+fn table_directive_must_fail_for_options_of_other_databases() {
+    let dml = r#"
+    datasource db {
+        provider = "postgresql"
+        url      = "postgresql://localhost:5432"
+    }
+
+    model Post {
+        id Int @id
+
+        @@table(engine: "InnoDB")
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "The table option `engine` is only supported on MySQL.",
+        "table",
+        Span::new(162, 178),
+    ));
+}
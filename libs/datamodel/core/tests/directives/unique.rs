@@ -1,4 +1,5 @@
 use datamodel::{ast::Span, error::*, render_datamodel_to_string, IndexDefinition, IndexType};
+use std::collections::BTreeMap;
 
 use crate::common::*;
 
@@ -20,6 +21,7 @@ fn basic_unique_index_must_work() {
         name: None,
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Unique,
+        field_options: BTreeMap::new(),
     });
 }
 
@@ -44,6 +46,7 @@ fn unique_indexes_on_relation_fields_must_work() {
         name: None,
         fields: vec!["identification".to_string()],
         tpe: IndexType::Unique,
+        field_options: BTreeMap::new(),
     });
 }
 
@@ -65,6 +68,7 @@ fn the_name_argument_must_work() {
         name: Some("MyIndexName".to_string()),
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Unique,
+        field_options: BTreeMap::new(),
     });
 }
 
@@ -88,12 +92,14 @@ fn multiple_unique_must_work() {
         name: None,
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Unique,
+        field_options: BTreeMap::new(),
     });
 
     user_model.assert_has_index(IndexDefinition {
         name: Some("MyIndexName".to_string()),
         fields: vec!["firstName".to_string(), "lastName".to_string()],
         tpe: IndexType::Unique,
+        field_options: BTreeMap::new(),
     });
 }
 
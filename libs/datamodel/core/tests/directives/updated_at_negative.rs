@@ -36,3 +36,21 @@ fn should_fail_if_field_arity_is_list() {
         Span::new(66, 75),
     ));
 }
+
+#[test]
+fn should_fail_if_the_default_value_is_not_now() {
+    let dml = r#"
+    model User {
+        id Int @id
+        lastSeen DateTime @default("2020-01-01T00:00:00Z") @updatedAt
+    }
+    "#;
+
+    let errors = parse_error(dml);
+
+    errors.assert_is(DatamodelError::new_directive_validation_error(
+        "Fields that are marked with @updatedAt can only have a default value of now().",
+        "updatedAt",
+        Span::new(97, 106),
+    ));
+}
@@ -1,5 +1,5 @@
 use crate::common::*;
-use datamodel::common::ScalarType;
+use datamodel::{common::ScalarType, DefaultValue, ValueGenerator};
 
 #[test]
 fn should_apply_updated_at_directive() {
@@ -18,3 +18,22 @@ fn should_apply_updated_at_directive() {
         .assert_is_updated_at(true);
     user_model.assert_has_field("id").assert_is_updated_at(false);
 }
+
+#[test]
+fn should_apply_updated_at_directive_with_a_default_of_now() {
+    let dml = r#"
+    model User {
+        id Int @id
+        lastSeen DateTime @default(now()) @updatedAt
+    }
+    "#;
+
+    let schema = parse(dml);
+    let user_model = schema.assert_has_model("User");
+    user_model
+        .assert_has_field("lastSeen")
+        .assert_default_value(DefaultValue::Expression(
+            ValueGenerator::new("now".to_owned(), Vec::new()).unwrap(),
+        ))
+        .assert_is_updated_at(true);
+}
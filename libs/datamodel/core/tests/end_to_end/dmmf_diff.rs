@@ -0,0 +1,100 @@
+use crate::common::*;
+use datamodel::json::dmmf::{diff_dmmf, DmmfChange};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn dmmf_diff_must_report_added_removed_and_changed_elements() {
+    let previous = parse(
+        r#"
+    model User {
+        id    Int     @id
+        name  String
+        email String
+        role  Role
+    }
+
+    model Profile {
+        id Int @id
+    }
+
+    enum Role {
+        USER
+        EDITOR
+    }
+    "#,
+    );
+
+    let next = parse(
+        r#"
+    model User {
+        id    Int     @id
+        name  String?
+        email String  @unique
+        role  Role
+        bio   String?
+
+        @@map("users")
+    }
+
+    model Post {
+        id Int @id
+    }
+
+    enum Role {
+        USER
+        ADMIN
+    }
+    "#,
+    );
+
+    let changes = diff_dmmf(&previous, &next);
+
+    assert_eq!(
+        changes,
+        vec![
+            DmmfChange::ModelRemoved {
+                model: "Profile".to_owned()
+            },
+            DmmfChange::ModelChanged {
+                model: "User".to_owned(),
+                properties: vec!["dbName".to_owned()],
+            },
+            DmmfChange::FieldChanged {
+                model: "User".to_owned(),
+                field: "name".to_owned(),
+                properties: vec!["isRequired".to_owned()],
+            },
+            DmmfChange::FieldChanged {
+                model: "User".to_owned(),
+                field: "email".to_owned(),
+                properties: vec!["isUnique".to_owned()],
+            },
+            DmmfChange::FieldAdded {
+                model: "User".to_owned(),
+                field: "bio".to_owned(),
+            },
+            DmmfChange::ModelAdded {
+                model: "Post".to_owned()
+            },
+            DmmfChange::EnumValueRemoved {
+                enum_name: "Role".to_owned(),
+                value: "EDITOR".to_owned(),
+            },
+            DmmfChange::EnumValueAdded {
+                enum_name: "Role".to_owned(),
+                value: "ADMIN".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn dmmf_diff_must_be_empty_for_equal_datamodels() {
+    let dml = r#"
+    model User {
+        id Int @id
+    }
+    "#;
+
+    assert!(diff_dmmf(&parse(dml), &parse(dml)).is_empty());
+}
@@ -1,3 +1,4 @@
+pub mod dmmf_diff;
 pub mod parser_renderer_ast;
 pub mod parser_renderer_dml;
 pub mod parser_renderer_dmmf;
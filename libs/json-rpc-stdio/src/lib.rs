@@ -1,13 +1,79 @@
-use futures::compat::*;
+use futures::{
+    channel::mpsc,
+    compat::*,
+    future::{AbortHandle, Abortable},
+    stream::{FuturesUnordered, StreamExt},
+};
 use jsonrpc_core::IoHandler;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt};
 
+/// Method of the notification the client sends to cancel a running request, with the id of the request as `id`
+/// parameter. The request is answered with a `REQUEST_CANCELLED` error.
+pub const CANCEL_REQUEST_METHOD: &str = "$/cancelRequest";
+
+/// Error code of the responses to cancelled requests.
+pub const REQUEST_CANCELLED: i64 = -32800;
+
+/// Error code of the responses to requests with the id of a running request, the JSON-RPC invalid request code.
+pub const DUPLICATE_REQUEST_ID: i64 = -32600;
+
+/// Sends notifications to the client while requests are running, e.g. the progress of long running requests.
+#[derive(Clone, Debug)]
+pub struct Notifier {
+    sender: mpsc::UnboundedSender<String>,
+}
+
+impl Notifier {
+    /// Notifications sent after the server stopped are dropped.
+    pub fn notify(&self, method: &str, params: Value) {
+        let notification = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        let _ = self.sender.unbounded_send(notification.to_string());
+    }
+}
+
+/// The notifications of the `Notifier`s of the channel, written to the output by `run_with_notifications`.
+#[derive(Debug)]
+pub struct Notifications {
+    receiver: mpsc::UnboundedReceiver<String>,
+}
+
+pub fn notification_channel() -> (Notifier, Notifications) {
+    let (sender, receiver) = mpsc::unbounded();
+
+    (Notifier { sender }, Notifications { receiver })
+}
+
 pub async fn run(handler: &IoHandler) -> std::io::Result<()> {
-    run_with_io(handler, tokio::io::stdin(), tokio::io::stdout()).await
+    run_with_io(handler, None, &[], tokio::io::stdin(), tokio::io::stdout()).await
 }
 
+/// Like `run`, additionally writing the notifications of the channel to stdout. Requests of the
+/// `uncancellable_methods` run to completion when the client cancels them, e.g. the ones changing a database.
+pub async fn run_with_notifications(
+    handler: &IoHandler,
+    notifications: Notifications,
+    uncancellable_methods: &[&str],
+) -> std::io::Result<()> {
+    run_with_io(
+        handler,
+        Some(notifications),
+        uncancellable_methods,
+        tokio::io::stdin(),
+        tokio::io::stdout(),
+    )
+    .await
+}
+
+/// Handles the requests of the input concurrently, one per line. Responses are written as soon as they are
+/// ready, so they don't necessarily follow the order of the requests. Runs until the input is closed and all
+/// requests are answered. Requests with the id of a running request are answered with a `DUPLICATE_REQUEST_ID`
+/// error without running them.
 async fn run_with_io(
     handler: &IoHandler,
+    notifications: Option<Notifications>,
+    uncancellable_methods: &[&str],
     input: impl AsyncRead + Unpin,
     output: impl AsyncWrite + Unpin,
 ) -> std::io::Result<()> {
@@ -15,16 +81,143 @@ async fn run_with_io(
     let mut input_lines = input.lines();
     let mut output = tokio::io::BufWriter::new(output);
 
-    while let Some(line) = input_lines.next_line().await? {
-        let response = handle_request(&handler, &line).await;
-        output.write_all(response.as_bytes()).await?;
-        output.write_all(b"\n").await?;
-        output.flush().await?;
+    let mut notifications = notifications.map(|notifications| notifications.receiver);
+    let mut requests = FuturesUnordered::new();
+    // The abort handles of the running requests by id, `None` for the ones that can't be cancelled.
+    let mut running: HashMap<String, Option<AbortHandle>> = HashMap::new();
+    let mut input_closed = false;
+
+    while !input_closed || !requests.is_empty() {
+        tokio::select! {
+            line = input_lines.next_line(), if !input_closed => {
+                let line = match line? {
+                    Some(line) => line,
+                    None => {
+                        input_closed = true;
+                        continue;
+                    }
+                };
+
+                if let Some(id) = cancelled_request_id(&line) {
+                    match running.get(&id.to_string()) {
+                        Some(Some(abort_handle)) => abort_handle.abort(),
+                        Some(None) => tracing::info!("Request {} can't be cancelled, it runs to completion.", id),
+                        None => tracing::info!("Cancelled request {} is not running.", id),
+                    }
+
+                    continue;
+                }
+
+                let id = request_id(&line);
+                let (abort_handle, abort_registration) = AbortHandle::new_pair();
+
+                if let Some(id) = &id {
+                    if running.contains_key(&id.to_string()) {
+                        write_line(&mut output, &duplicate_id_response(id)).await?;
+                        continue;
+                    }
+
+                    let cancellable = request_method(&line)
+                        .map(|method| !uncancellable_methods.contains(&method.as_str()))
+                        .unwrap_or(true);
+
+                    running.insert(id.to_string(), Some(abort_handle).filter(|_| cancellable));
+                }
+
+                requests.push(async move {
+                    let response = Abortable::new(handle_request(&handler, &line), abort_registration).await;
+
+                    (id, response)
+                });
+            }
+            Some((id, response)) = requests.next(), if !requests.is_empty() => {
+                let response = match (response, &id) {
+                    (Ok(response), _) => response,
+                    (Err(_), Some(id)) => cancelled_response(id),
+                    (Err(_), None) => String::new(),
+                };
+
+                if let Some(id) = &id {
+                    running.remove(&id.to_string());
+                }
+
+                write_line(&mut output, &response).await?;
+            }
+            Some(notification) = next_notification(&mut notifications) => {
+                write_line(&mut output, &notification).await?;
+            }
+        }
     }
 
     Ok(())
 }
 
+async fn write_line(output: &mut (impl AsyncWrite + Unpin), line: &str) -> std::io::Result<()> {
+    output.write_all(line.as_bytes()).await?;
+    output.write_all(b"\n").await?;
+    output.flush().await
+}
+
+/// The next notification, pending forever without notifications.
+async fn next_notification(notifications: &mut Option<mpsc::UnboundedReceiver<String>>) -> Option<String> {
+    match notifications {
+        Some(receiver) => receiver.next().await,
+        None => futures::future::pending().await,
+    }
+}
+
+/// The id of a single request, batches and notifications can't be cancelled.
+fn request_id(input: &str) -> Option<Value> {
+    match serde_json::from_str::<Value>(input) {
+        Ok(Value::Object(mut request)) => request.remove("id").filter(|id| !id.is_null()),
+        _ => None,
+    }
+}
+
+fn request_method(input: &str) -> Option<String> {
+    match serde_json::from_str::<Value>(input) {
+        Ok(Value::Object(mut request)) => match request.remove("method") {
+            Some(Value::String(method)) => Some(method),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn cancelled_request_id(input: &str) -> Option<Value> {
+    let mut request = match serde_json::from_str::<Value>(input) {
+        Ok(Value::Object(request)) => request,
+        _ => return None,
+    };
+
+    if request.get("method").and_then(Value::as_str) != Some(CANCEL_REQUEST_METHOD) {
+        return None;
+    }
+
+    match request.remove("params") {
+        Some(Value::Object(mut params)) => params.remove("id"),
+        _ => None,
+    }
+}
+
+fn cancelled_response(id: &Value) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": REQUEST_CANCELLED, "message": "Request cancelled" },
+        "id": id,
+    })
+    .to_string()
+}
+
+fn duplicate_id_response(id: &Value) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": DUPLICATE_REQUEST_ID, "message": format!("Request {} is already running", id) },
+        "id": id,
+    })
+    .to_string()
+}
+
 /// Process a request asynchronously
 async fn handle_request(io: &IoHandler, input: &str) -> String {
     let response = io.handle_request(input).compat().await;
@@ -36,3 +229,110 @@ async fn handle_request(io: &IoHandler, input: &str) -> String {
             String::from("")
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{FutureExt, TryFutureExt};
+    use jsonrpc_core::futures::future;
+    use std::time::Duration;
+
+    fn handler() -> IoHandler {
+        let mut handler = IoHandler::default();
+
+        handler.add_method("hang", |_| future::empty());
+        handler.add_method("ping", |_| future::ok(Value::String("pong".to_owned())));
+        handler.add_method("sleep", |_| {
+            async {
+                tokio::time::delay_for(Duration::from_millis(50)).await;
+                Ok(Value::String("slept".to_owned()))
+            }
+            .boxed()
+            .compat()
+        });
+
+        handler
+    }
+
+    async fn responses(input: &str, notifications: Option<Notifications>) -> Vec<Value> {
+        let mut output = Vec::new();
+
+        run_with_io(&handler(), notifications, &["sleep"], input.as_bytes(), &mut output)
+            .await
+            .unwrap();
+
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn running_requests_do_not_block_later_requests_and_can_be_cancelled() {
+        let input = r#"{"jsonrpc": "2.0", "method": "hang", "params": [], "id": 1}
+{"jsonrpc": "2.0", "method": "ping", "params": [], "id": 2}
+{"jsonrpc": "2.0", "method": "$/cancelRequest", "params": {"id": 1}}
+"#;
+
+        let responses = responses(input, None).await;
+
+        assert_eq!(
+            responses,
+            vec![
+                json!({ "jsonrpc": "2.0", "result": "pong", "id": 2 }),
+                json!({ "jsonrpc": "2.0", "error": { "code": -32800, "message": "Request cancelled" }, "id": 1 }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn notifications_are_written_while_requests_run() {
+        let (notifier, notifications) = notification_channel();
+        notifier.notify("progress", json!({ "done": 1 }));
+
+        let input = r#"{"jsonrpc": "2.0", "method": "hang", "params": [], "id": 1}
+{"jsonrpc": "2.0", "method": "$/cancelRequest", "params": {"id": 1}}
+"#;
+
+        let responses = responses(input, Some(notifications)).await;
+
+        assert!(responses.contains(&json!({ "jsonrpc": "2.0", "method": "progress", "params": { "done": 1 } })));
+        assert!(responses.contains(
+            &json!({ "jsonrpc": "2.0", "error": { "code": -32800, "message": "Request cancelled" }, "id": 1 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn uncancellable_requests_run_to_completion() {
+        let input = r#"{"jsonrpc": "2.0", "method": "sleep", "params": [], "id": 1}
+{"jsonrpc": "2.0", "method": "$/cancelRequest", "params": {"id": 1}}
+"#;
+
+        let responses = responses(input, None).await;
+
+        assert_eq!(responses, vec![json!({ "jsonrpc": "2.0", "result": "slept", "id": 1 })]);
+    }
+
+    #[tokio::test]
+    async fn requests_with_the_id_of_a_running_request_are_rejected() {
+        let input = r#"{"jsonrpc": "2.0", "method": "hang", "params": [], "id": 1}
+{"jsonrpc": "2.0", "method": "ping", "params": [], "id": 1}
+{"jsonrpc": "2.0", "method": "$/cancelRequest", "params": {"id": 1}}
+"#;
+
+        let responses = responses(input, None).await;
+
+        assert_eq!(
+            responses,
+            vec![
+                json!({
+                    "jsonrpc": "2.0",
+                    "error": { "code": -32600, "message": "Request 1 is already running" },
+                    "id": 1,
+                }),
+                json!({ "jsonrpc": "2.0", "error": { "code": -32800, "message": "Request cancelled" }, "id": 1 }),
+            ]
+        );
+    }
+}
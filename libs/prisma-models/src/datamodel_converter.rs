@@ -1,6 +1,7 @@
 use crate::*;
-use datamodel::{dml, DefaultValue, WithDatabaseName};
+use datamodel::{dml, DefaultValue, RelationMode, WithDatabaseName};
 use itertools::Itertools;
+use std::collections::HashMap;
 
 pub struct DatamodelConverter<'a> {
     datamodel: &'a dml::Datamodel,
@@ -30,6 +31,10 @@ impl<'a> DatamodelConverter<'a> {
             relations: self.convert_relations(),
             enums: self.convert_enums(),
             version: Some("v2".to_string()),
+            relation_mode: RelationMode::default(),
+            datasource_db_names: HashMap::new(),
+            updated_at_in_database: false,
+            identifier_delimiter: None,
         }
     }
 
@@ -53,13 +58,18 @@ impl<'a> DatamodelConverter<'a> {
                 manifestation: model.single_database_name().map(|s| s.to_owned()),
                 id_field_names: model.id_fields.clone(),
                 indexes: self.convert_indexes(model),
+                datasource: model.datasource.clone(),
+                soft_delete_field: model.soft_delete_field.clone(),
+                cache_ttl: model.cache_ttl,
             })
             .collect()
     }
 
+    /// Fields of unsupported types are left out, they can only be read and written with raw queries.
     fn convert_fields(&self, model: &dml::Model) -> Vec<FieldTemplate> {
         model
             .fields()
+            .filter(|field| !field.field_type.is_unsupported())
             .map(|field| match field.field_type {
                 dml::FieldType::Relation(ref ri) => {
                     let relation = self
@@ -110,8 +120,9 @@ impl<'a> DatamodelConverter<'a> {
             .iter()
             .map(|r| RelationTemplate {
                 name: r.name(),
-                model_a_on_delete: OnDelete::SetNull,
-                model_b_on_delete: OnDelete::SetNull,
+                model_a_on_delete: r.model_a_on_delete(),
+                model_b_on_delete: r.model_b_on_delete(),
+                deferrable: r.is_deferrable(),
                 manifestation: r.manifestation(),
                 model_a_name: r.model_a.name.clone(),
                 model_b_name: r.model_b.name.clone(),
@@ -123,6 +134,14 @@ impl<'a> DatamodelConverter<'a> {
         model
             .indices
             .iter()
+            .filter(|i| {
+                i.fields.iter().all(|name| {
+                    model
+                        .find_field(name)
+                        .map(|field| !field.field_type.is_unsupported())
+                        .unwrap_or(true)
+                })
+            })
             .map(|i| IndexTemplate {
                 name: i.name.clone(),
                 fields: i.fields.clone(),
@@ -192,20 +211,35 @@ impl<'a> DatamodelConverter<'a> {
                             to_fields,
                         ),
                         // SELF RELATION CASE
+                        // Both fields are on the same model, so the sides can't be told apart by model name. The
+                        // field holding the foreign key is always side B, the relation columns of inline self
+                        // relations rely on it. Relation tables reference both sides, these are ordered by name.
                         _ => {
-                            let (field_a, field_b) = if field.name < related_field.name {
-                                (field.clone(), related_field.clone())
-                            } else {
-                                (related_field.clone(), field.clone())
+                            let field_is_b = match (to_fields.is_empty(), related_field_info.to_fields.is_empty()) {
+                                (false, true) => true,
+                                (true, false) => false,
+                                _ => related_field.name < field.name,
                             };
-                            (
-                                model.clone(),
-                                related_model.clone(),
-                                field_a,
-                                field_b,
-                                to_fields,
-                                &related_field_info.to_fields,
-                            )
+
+                            if field_is_b {
+                                (
+                                    model.clone(),
+                                    related_model.clone(),
+                                    related_field.clone(),
+                                    field.clone(),
+                                    &related_field_info.to_fields,
+                                    to_fields,
+                                )
+                            } else {
+                                (
+                                    model.clone(),
+                                    related_model.clone(),
+                                    field.clone(),
+                                    related_field.clone(),
+                                    to_fields,
+                                    &related_field_info.to_fields,
+                                )
+                            }
                         }
                     };
                     let inline_on_model_a = TempManifestationHolder::Inline {
@@ -297,22 +331,66 @@ impl TempRelationHolder {
         }
     }
 
+    /// The relation table of a many-to-many relation, either side can name it with `@relation(table: ...)`.
     pub fn table_name(&self) -> String {
-        format!("_{}", self.name())
+        Self::relation_info(&self.field_a)
+            .and_then(|info| info.join_table.clone())
+            .or_else(|| Self::relation_info(&self.field_b).and_then(|info| info.join_table.clone()))
+            .unwrap_or_else(|| format!("_{}", self.name()))
     }
 
+    /// The column referencing model A, named with `@relation(column: ...)` on the field of model A.
     pub fn model_a_column(&self) -> String {
-        "A".to_string()
+        Self::relation_info(&self.field_a)
+            .and_then(|info| info.join_column.clone())
+            .unwrap_or_else(|| Relation::MODEL_A_DEFAULT_COLUMN.to_string())
     }
 
+    /// The column referencing model B, named with `@relation(column: ...)` on the field of model B.
     pub fn model_b_column(&self) -> String {
-        "B".to_string()
+        Self::relation_info(&self.field_b)
+            .and_then(|info| info.join_column.clone())
+            .unwrap_or_else(|| Relation::MODEL_B_DEFAULT_COLUMN.to_string())
+    }
+
+    /// Deleting a record of model A deletes its related records if the field of model A sets `onDelete: CASCADE`.
+    pub fn model_a_on_delete(&self) -> OnDelete {
+        Self::on_delete(&self.field_a)
+    }
+
+    /// Deleting a record of model B deletes its related records if the field of model B sets `onDelete: CASCADE`.
+    pub fn model_b_on_delete(&self) -> OnDelete {
+        Self::on_delete(&self.field_b)
+    }
+
+    fn on_delete(field: &dml::Field) -> OnDelete {
+        match Self::relation_info(field).map(|info| info.on_delete) {
+            Some(dml::OnDeleteStrategy::Cascade) => OnDelete::Cascade,
+            _ => OnDelete::SetNull,
+        }
+    }
+
+    fn relation_info(field: &dml::Field) -> Option<&dml::RelationInfo> {
+        match &field.field_type {
+            dml::FieldType::Relation(relation_info) => Some(relation_info),
+            _ => None,
+        }
     }
 
     pub fn is_one_to_one(&self) -> bool {
         !self.field_a.is_list() && !self.field_b.is_list()
     }
 
+    /// A relation is deferrable when either of its fields says so with `@relation(deferrable: true)`.
+    pub fn is_deferrable(&self) -> bool {
+        [&self.field_a, &self.field_b]
+            .iter()
+            .any(|field| match &field.field_type {
+                dml::FieldType::Relation(relation_info) => relation_info.deferrable,
+                _ => false,
+            })
+    }
+
     fn is_many_to_many(&self) -> bool {
         self.field_a.is_list() && self.field_b.is_list()
     }
@@ -371,7 +449,7 @@ impl DatamodelFieldExtensions for dml::Field {
             dml::FieldType::Base(scalar) => match scalar {
                 dml::ScalarType::Boolean => TypeIdentifier::Boolean,
                 dml::ScalarType::DateTime => TypeIdentifier::DateTime,
-                dml::ScalarType::Decimal => TypeIdentifier::Float,
+                dml::ScalarType::Decimal => TypeIdentifier::Decimal,
                 dml::ScalarType::Float => TypeIdentifier::Float,
                 dml::ScalarType::Int => TypeIdentifier::Int,
                 dml::ScalarType::String => TypeIdentifier::String,
@@ -379,6 +457,7 @@ impl DatamodelFieldExtensions for dml::Field {
             dml::FieldType::ConnectorSpecific { .. } => {
                 unimplemented!("Connector Specific types are not supported here yet")
             }
+            dml::FieldType::Unsupported(_) => unreachable!("Fields of unsupported types are not converted."),
         }
     }
 
@@ -408,6 +487,10 @@ impl DatamodelFieldExtensions for dml::Field {
     fn behaviour(&self) -> Option<FieldBehaviour> {
         if self.is_updated_at {
             Some(FieldBehaviour::UpdatedAt)
+        } else if let Some(expression) = &self.computed {
+            Some(FieldBehaviour::Computed {
+                expression: expression.clone(),
+            })
         } else {
             None
         }
@@ -108,6 +108,7 @@ impl From<&RelationFieldRef> for FieldWeak {
 pub enum TypeIdentifier {
     String,
     Float,
+    Decimal,
     Boolean,
     Enum,
     Json,
@@ -190,7 +191,7 @@ impl From<ScalarType> for TypeIdentifier {
             ScalarType::Int => Self::Int,
             ScalarType::Float => Self::Float,
             ScalarType::Boolean => Self::Boolean,
-            ScalarType::Decimal => Self::Float,
+            ScalarType::Decimal => Self::Decimal,
             ScalarType::DateTime => Self::DateTime,
         }
     }
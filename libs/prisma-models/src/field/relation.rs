@@ -194,6 +194,7 @@ impl RelationField {
             RelationLinkManifestation::Inline(ref m) => {
                 let is_self_rel = relation.is_self_relation();
 
+                // The field holding the foreign key of a self relation is side B, see the `DatamodelConverter`.
                 if is_self_rel && self.relation_side == RelationSide::B {
                     true
                 } else if is_self_rel && self.relation_side == RelationSide::A {
@@ -210,6 +211,32 @@ impl RelationField {
         self.relation().is_inline_relation() && !self.relation_is_inlined_in_parent()
     }
 
+    /// Deleting a record of the model deletes the records related through this field (`onDelete: CASCADE`).
+    pub fn cascades_on_delete(&self) -> bool {
+        let relation = self.relation();
+
+        match self.relation_side {
+            RelationSide::A => relation.model_a_on_delete.is_cascade(),
+            RelationSide::B => relation.model_b_on_delete.is_cascade(),
+        }
+    }
+
+    /// The name of the scalar field exposing the foreign key of a relation inlined on the model, e.g. `authorId` for
+    /// `author`. Only single column foreign keys are exposed, and only if the model has no field of that name.
+    pub fn foreign_key_scalar_name(&self) -> Option<String> {
+        if !self.is_inlined_on_enclosing_model() || self.data_source_fields().len() != 1 {
+            return None;
+        }
+
+        let name = format!("{}Id", self.name);
+
+        if self.model().fields().find_from_all(&name).is_ok() {
+            None
+        } else {
+            Some(name)
+        }
+    }
+
     pub fn related_model(&self) -> ModelRef {
         match self.relation_side {
             RelationSide::A => self.relation().model_b(),
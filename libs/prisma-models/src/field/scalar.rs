@@ -84,6 +84,9 @@ pub enum FieldBehaviour {
     CreatedAt,
     UpdatedAt,
     ScalarList { strategy: ScalarListStrategy },
+
+    /// Read-only field with the value of a SQL expression over the columns of the model instead of a column.
+    Computed { expression: String },
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -163,6 +166,35 @@ impl ScalarField {
         }
     }
 
+    /// `@updatedAt` fields with a `@default(now())` are kept up to date by databases that support it, see
+    /// `InternalDataModel::updated_at_in_database`. Only applies to models of the first datasource.
+    pub fn is_updated_at_in_database(&self) -> bool {
+        let model = self.model();
+        let internal_data_model = model.internal_data_model();
+        let in_first_datasource = model
+            .datasource
+            .as_ref()
+            .map(|datasource| !internal_data_model.datasource_db_names.contains_key(datasource))
+            .unwrap_or(true);
+
+        internal_data_model.updated_at_in_database
+            && in_first_datasource
+            && self.is_updated_at()
+            && self.default_value().map(|default| default.is_now()).unwrap_or(false)
+    }
+
+    /// The SQL expression of a computed field.
+    pub fn computed(&self) -> Option<&str> {
+        match self.behaviour {
+            Some(FieldBehaviour::Computed { ref expression }) => Some(expression),
+            _ => None,
+        }
+    }
+
+    pub fn is_computed(&self) -> bool {
+        self.computed().is_some()
+    }
+
     pub fn unique(&self) -> bool {
         self.is_unique || self.is_id()
     }
@@ -86,15 +86,8 @@ impl Fields {
         self.relation_weak()
             .iter()
             .map(|f| f.upgrade().unwrap())
-            .fold(Vec::new(), |mut acc, rf| {
-                match rf.relation_side {
-                    RelationSide::A if rf.relation().model_a_on_delete.is_cascade() => acc.push(rf),
-                    RelationSide::B if rf.relation().model_b_on_delete.is_cascade() => acc.push(rf),
-                    _ => (),
-                }
-
-                acc
-            })
+            .filter(|rf| rf.cascades_on_delete())
+            .collect()
     }
 
     fn relation_weak(&self) -> &[Weak<RelationField>] {
@@ -1,6 +1,11 @@
 use crate::prelude::*;
+use datamodel::RelationMode;
 use once_cell::sync::OnceCell;
-use std::sync::{Arc, Weak};
+use sql_identifier::Delimiter;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
 
 pub type InternalDataModelRef = Arc<InternalDataModel>;
 pub type InternalDataModelWeakRef = Weak<InternalDataModel>;
@@ -11,6 +16,10 @@ pub struct InternalDataModelTemplate {
     pub relations: Vec<RelationTemplate>,
     pub enums: Vec<InternalEnum>,
     pub version: Option<String>,
+    pub relation_mode: RelationMode,
+    pub datasource_db_names: HashMap<String, String>,
+    pub updated_at_in_database: bool,
+    pub identifier_delimiter: Option<Delimiter>,
 }
 
 #[derive(DebugStub)]
@@ -25,6 +34,21 @@ pub struct InternalDataModel {
     /// influence the `database` part instead.
     pub db_name: String,
 
+    /// Whether referential actions are left to foreign keys in the database or emulated by the engine.
+    pub relation_mode: RelationMode,
+
+    /// The db names of the datasources models are assigned to, by datasource name.
+    /// Models without a datasource, or of a datasource missing here, use `db_name`.
+    pub datasource_db_names: HashMap<String, String>,
+
+    /// Whether the database of the first datasource sets `@updatedAt` fields with a `@default(now())` on updates
+    /// itself, like MySQL with `ON UPDATE CURRENT_TIMESTAMP`.
+    pub updated_at_in_database: bool,
+
+    /// The delimiter of identifiers in the SQL dialect of the first datasource. The query AST delimits the names of
+    /// tables and columns without escaping them, see `identifier`. `None` for connectors without SQL identifiers.
+    pub identifier_delimiter: Option<Delimiter>,
+
     models: OnceCell<Vec<ModelRef>>,
     relations: OnceCell<Vec<RelationRef>>,
     relation_fields: OnceCell<Vec<RelationFieldRef>>,
@@ -63,6 +87,10 @@ impl InternalDataModelTemplate {
             enums: self.enums,
             version: self.version,
             db_name,
+            relation_mode: self.relation_mode,
+            datasource_db_names: self.datasource_db_names,
+            updated_at_in_database: self.updated_at_in_database,
+            identifier_delimiter: self.identifier_delimiter,
             relation_fields: OnceCell::new(),
         });
 
@@ -86,6 +114,15 @@ impl InternalDataModelTemplate {
 }
 
 impl InternalDataModel {
+    /// The db name as handed to the query AST, with the delimiters in it escaped, so that names like `user"s` are
+    /// rendered as one identifier.
+    pub fn identifier(&self, db_name: &str) -> String {
+        match self.identifier_delimiter {
+            Some(delimiter) => sql_identifier::escape(delimiter, db_name).into_owned(),
+            None => db_name.to_owned(),
+        }
+    }
+
     pub fn models(&self) -> &[ModelRef] {
         self.models.get().unwrap()
     }
@@ -3,6 +3,7 @@ use once_cell::sync::OnceCell;
 use std::{
     hash::{Hash, Hasher},
     sync::{Arc, Weak},
+    time::Duration,
 };
 
 pub type ModelRef = Arc<Model>;
@@ -16,6 +17,9 @@ pub struct ModelTemplate {
     pub manifestation: Option<String>,
     pub id_field_names: Vec<String>,
     pub indexes: Vec<IndexTemplate>,
+    pub datasource: Option<String>,
+    pub soft_delete_field: Option<String>,
+    pub cache_ttl: Option<u32>,
 }
 
 #[derive(DebugStub)]
@@ -23,7 +27,12 @@ pub struct Model {
     pub name: String,
     pub is_embedded: bool,
 
+    /// The datasource the model is stored in, `None` for the first one.
+    pub datasource: Option<String>,
+
     manifestation: Option<String>,
+    soft_delete_field: Option<String>,
+    cache_ttl: Option<u32>,
     fields: OnceCell<Fields>,
     indexes: OnceCell<Vec<Index>>,
 
@@ -36,6 +45,9 @@ impl ModelTemplate {
         let model = Arc::new(Model {
             name: self.name,
             is_embedded: self.is_embedded,
+            datasource: self.datasource,
+            soft_delete_field: self.soft_delete_field,
+            cache_ttl: self.cache_ttl,
             fields: OnceCell::new(),
             indexes: OnceCell::new(),
             manifestation: self.manifestation,
@@ -144,6 +156,29 @@ impl Model {
         self.manifestation.as_ref().map(|m| m.as_ref())
     }
 
+    /// The db name of the datasource the model is stored in, see `InternalDataModel::db_name`.
+    pub fn datasource_db_name(&self) -> String {
+        let internal_data_model = self.internal_data_model();
+
+        self.datasource
+            .as_ref()
+            .and_then(|datasource| internal_data_model.datasource_db_names.get(datasource))
+            .unwrap_or(&internal_data_model.db_name)
+            .clone()
+    }
+
+    /// The optional DateTime field marking records as deleted, if the model is soft deleted (`@@softDelete`).
+    pub fn soft_delete_field(&self) -> Option<ScalarFieldRef> {
+        self.soft_delete_field
+            .as_ref()
+            .map(|name| self.fields().find_from_scalar(name).unwrap())
+    }
+
+    /// How long the results of reads on the model are cached, if they are (`@@cache`).
+    pub fn cache_ttl(&self) -> Option<Duration> {
+        self.cache_ttl.map(|secs| Duration::from_secs(secs.into()))
+    }
+
     pub fn internal_data_model(&self) -> InternalDataModelRef {
         self.internal_data_model
             .upgrade()
@@ -159,4 +194,23 @@ impl Model {
             }
         })
     }
+
+    /// The inlined relation field whose foreign key is exposed as the scalar field of the given name, see
+    /// `RelationField::foreign_key_scalar_name`.
+    pub fn find_foreign_key_relation_field(&self, name: &str) -> Option<RelationFieldRef> {
+        self.fields()
+            .relation()
+            .into_iter()
+            .find(|field| field.foreign_key_scalar_name().as_deref() == Some(name))
+    }
+
+    /// The name of the foreign key scalar field reading the column of the given name, if there is one.
+    pub fn map_foreign_key_db_field_name(&self, name: &str) -> Option<String> {
+        self.fields()
+            .relation()
+            .into_iter()
+            .filter(|field| field.is_inlined_on_enclosing_model())
+            .find(|field| field.db_names().any(|db_name| db_name == name))
+            .and_then(|field| field.foreign_key_scalar_name())
+    }
 }
@@ -1,6 +1,6 @@
 use super::{PrismaValue, TypeIdentifier};
 use crate::DomainError;
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 
 // use std::convert::TryFrom;
 
@@ -18,6 +18,7 @@ impl PrismaValueExtensions for PrismaValue {
             (val @ PrismaValue::String(_), TypeIdentifier::String) => val,
             (val @ PrismaValue::Int(_), TypeIdentifier::Int) => val,
             (val @ PrismaValue::Float(_), TypeIdentifier::Float) => val,
+            (val @ PrismaValue::Decimal(_), TypeIdentifier::Decimal) => val,
             (val @ PrismaValue::Boolean(_), TypeIdentifier::Boolean) => val,
             (val @ PrismaValue::DateTime(_), TypeIdentifier::DateTime) => val,
             (val @ PrismaValue::Enum(_), TypeIdentifier::Enum) => val,
@@ -26,6 +27,7 @@ impl PrismaValueExtensions for PrismaValue {
             // Valid String coercions
             (PrismaValue::Int(i), TypeIdentifier::String) => PrismaValue::String(format!("{}", i)),
             (PrismaValue::Float(f), TypeIdentifier::String) => PrismaValue::String(f.to_string()),
+            (PrismaValue::Decimal(d), TypeIdentifier::String) => PrismaValue::String(d.to_string()),
             (PrismaValue::Boolean(b), TypeIdentifier::String) => PrismaValue::String(format!("{}", b)),
             (PrismaValue::DateTime(dt), TypeIdentifier::String) => {
                 PrismaValue::String(prisma_value::stringify_date(&dt))
@@ -45,6 +47,19 @@ impl PrismaValueExtensions for PrismaValue {
             },
             (PrismaValue::Float(f), TypeIdentifier::Int) => PrismaValue::Int(f.trunc().to_i64().unwrap()),
 
+            // Valid Decimal coercions
+            (PrismaValue::String(s), TypeIdentifier::Decimal) => match s.parse() {
+                Ok(d) => PrismaValue::Decimal(d),
+                Err(_) => {
+                    return Err(DomainError::ConversionFailure(
+                        format!("{:?}", s),
+                        format!("{:?}", to_type),
+                    ))
+                }
+            },
+            (PrismaValue::Int(i), TypeIdentifier::Decimal) => PrismaValue::Decimal(Decimal::from(i)),
+            (PrismaValue::Float(f), TypeIdentifier::Decimal) => PrismaValue::Decimal(f),
+
             // Todo other coercions here
 
             // Lists
@@ -2,6 +2,8 @@ use crate::{
     dml::FieldArity, DataSourceFieldRef, DomainError, Field, ModelRef, PrismaValue, PrismaValueExtensions,
     TypeIdentifier,
 };
+use smallvec::SmallVec;
+use std::{iter::FromIterator, sync::Arc};
 
 // Collection of fields that uniquely identify a record of a model.
 // There can be different sets of fields at the same time identifying a model.
@@ -92,7 +94,6 @@ impl ModelIdentifier {
             let fields = self.data_source_fields();
 
             Ok(id
-                .pairs
                 .into_iter()
                 .zip(fields)
                 .map(|((og_field, value), other_field)| {
@@ -118,19 +119,29 @@ impl IntoIterator for ModelIdentifier {
     }
 }
 
+/// Field to value pairs of an identifier, stored inline for the common case of one or two fields.
+pub type IdentifierPairs = SmallVec<[(DataSourceFieldRef, PrismaValue); 2]>;
+
 // Collection of field to value pairs corresponding to a ModelIdentifier the record belongs to.
+// The pairs are shared between clones, which are passed around the query graph a lot, and only copied on write.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RecordIdentifier {
-    pub pairs: Vec<(DataSourceFieldRef, PrismaValue)>,
+    pairs: Arc<IdentifierPairs>,
 }
 
 impl RecordIdentifier {
     pub fn new(pairs: Vec<(DataSourceFieldRef, PrismaValue)>) -> Self {
-        Self { pairs }
+        Self {
+            pairs: Arc::new(SmallVec::from_vec(pairs)),
+        }
     }
 
     pub fn add(&mut self, pair: (DataSourceFieldRef, PrismaValue)) {
-        self.pairs.push(pair);
+        Arc::make_mut(&mut self.pairs).push(pair);
+    }
+
+    pub fn pairs(&self) -> &[(DataSourceFieldRef, PrismaValue)] {
+        &self.pairs
     }
 
     pub fn fields(&self) -> impl Iterator<Item = DataSourceFieldRef> + '_ {
@@ -157,14 +168,13 @@ impl RecordIdentifier {
     where
         V: Into<PrismaValue>,
     {
-        for pair in self.pairs.iter_mut() {
-            if pair.1.is_null() {
-                pair.1 = value.into();
-                return true;
+        match self.pairs.iter().position(|p| p.1.is_null()) {
+            Some(index) => {
+                Arc::make_mut(&mut self.pairs)[index].1 = value.into();
+                true
             }
+            None => false,
         }
-
-        return false;
     }
 
     pub fn single_value(&self) -> PrismaValue {
@@ -179,16 +189,27 @@ impl RecordIdentifier {
 
 impl IntoIterator for RecordIdentifier {
     type Item = (DataSourceFieldRef, PrismaValue);
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = smallvec::IntoIter<[Self::Item; 2]>;
 
+    /// Only copies the pairs if they are shared with other clones.
     fn into_iter(self) -> Self::IntoIter {
-        self.pairs.into_iter()
+        Arc::try_unwrap(self.pairs)
+            .unwrap_or_else(|pairs| (*pairs).clone())
+            .into_iter()
+    }
+}
+
+impl FromIterator<(DataSourceFieldRef, PrismaValue)> for RecordIdentifier {
+    fn from_iter<T: IntoIterator<Item = (DataSourceFieldRef, PrismaValue)>>(iter: T) -> Self {
+        Self {
+            pairs: Arc::new(iter.into_iter().collect()),
+        }
     }
 }
 
 impl From<(DataSourceFieldRef, PrismaValue)> for RecordIdentifier {
     fn from(tup: (DataSourceFieldRef, PrismaValue)) -> Self {
-        Self::new(vec![tup])
+        std::iter::once(tup).collect()
     }
 }
 
@@ -277,8 +298,7 @@ impl Record {
     }
 
     pub fn identifier(&self, field_names: &[String], id: &ModelIdentifier) -> crate::Result<RecordIdentifier> {
-        let pairs: Vec<(DataSourceFieldRef, PrismaValue)> = id
-            .fields()
+        id.fields()
             .into_iter()
             .flat_map(|id_field| {
                 let source_fields = id_field.data_source_fields();
@@ -288,9 +308,7 @@ impl Record {
                         .map(|val| (source_field, val.clone()))
                 })
             })
-            .collect::<crate::Result<Vec<_>>>()?;
-
-        Ok(RecordIdentifier { pairs })
+            .collect()
     }
 
     pub fn get_field_value(&self, field_names: &[String], field: &str) -> crate::Result<&PrismaValue> {
@@ -10,6 +10,7 @@ pub struct RelationTemplate {
     pub name: String,
     pub model_a_on_delete: OnDelete,
     pub model_b_on_delete: OnDelete,
+    pub deferrable: bool,
     pub manifestation: RelationLinkManifestation,
     pub model_a_name: String,
     pub model_b_name: String,
@@ -27,6 +28,11 @@ pub struct Relation {
     pub model_a_on_delete: OnDelete,
     pub model_b_on_delete: OnDelete,
 
+    /// The foreign key of the relation is only checked when the transaction commits, so records referencing each
+    /// other can be written in any order within a transaction. The query graph still orders the writes of nested
+    /// operations by the results they depend on, it doesn't reorder them for deferrable relations.
+    pub deferrable: bool,
+
     model_a: OnceCell<ModelWeakRef>,
     model_b: OnceCell<ModelWeakRef>,
 
@@ -89,6 +95,7 @@ impl RelationTemplate {
             model_b_name: self.model_b_name,
             model_a_on_delete: self.model_a_on_delete,
             model_b_on_delete: self.model_b_on_delete,
+            deferrable: self.deferrable,
             model_a: OnceCell::new(),
             model_b: OnceCell::new(),
             field_a: OnceCell::new(),
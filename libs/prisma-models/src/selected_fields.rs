@@ -181,6 +181,11 @@ impl SelectedFields {
         self.relation.iter().map(|rf| &rf.field)
     }
 
+    /// The selected fields with the value of a SQL expression.
+    pub fn computed_fields(&self) -> Vec<ScalarFieldRef> {
+        self.scalar_fields().filter(|sf| sf.is_computed()).cloned().collect()
+    }
+
     pub fn contains(&self, name: &str) -> bool {
         self.names().find(|fname| fname == &name).is_some()
     }
@@ -88,15 +88,14 @@ impl AsColumns for RelationField {
     fn as_columns(&self) -> ColumnIterator {
         let model = self.model();
         let internal_data_model = model.internal_data_model();
+        let db = internal_data_model.identifier(&model.datasource_db_name());
+        let table = internal_data_model.identifier(model.db_name());
 
         let inner: Vec<_> = self
             .data_source_fields()
             .iter()
             .map(|dsf| {
-                let parts = (
-                    (internal_data_model.db_name.clone(), model.db_name().to_string()),
-                    dsf.name.clone(),
-                );
+                let parts = ((db.clone(), table.clone()), internal_data_model.identifier(&dsf.name));
 
                 Column::from(parts)
             })
@@ -113,11 +112,24 @@ impl AsColumns for &[crate::field::DataSourceFieldRef] {
     }
 }
 
+/// Name of the placeholder column selecting a computed field. Quaint can't render SQL expressions, connectors
+/// replace the quoted placeholder with the expression of the field in the rendered query.
+pub fn computed_marker(field: &ScalarField) -> String {
+    format!("prisma_computed__{}__{}", field.model().db_name(), field.name)
+}
+
 impl AsColumn for ScalarField {
     fn as_column(&self) -> Column<'static> {
-        let db = self.internal_data_model().db_name.clone();
-        let table = self.model().db_name().to_string();
-        let col = self.db_name().to_string();
+        let model = self.model();
+        let internal_data_model = model.internal_data_model();
+
+        if self.is_computed() {
+            return Column::from(internal_data_model.identifier(&computed_marker(self)));
+        }
+
+        let db = internal_data_model.identifier(&model.datasource_db_name());
+        let table = internal_data_model.identifier(model.db_name());
+        let col = internal_data_model.identifier(self.db_name());
 
         Column::from(((db, table), col))
     }
@@ -126,9 +138,10 @@ impl AsColumn for ScalarField {
 impl AsColumn for crate::field::DataSourceField {
     fn as_column(&self) -> Column<'static> {
         let model = self.model_field().model();
-        let db = model.internal_data_model().db_name.clone();
-        let table = model.db_name().to_string();
-        let col = self.name.to_string();
+        let internal_data_model = model.internal_data_model();
+        let db = internal_data_model.identifier(&model.datasource_db_name());
+        let table = internal_data_model.identifier(model.db_name());
+        let col = internal_data_model.identifier(&self.name);
 
         Column::from(((db, table), col))
     }
@@ -87,8 +87,10 @@ impl AsTable for Relation {
     fn as_table(&self) -> Table<'static> {
         match self.manifestation {
             RelationLinkManifestation::RelationTable(ref m) => {
-                let db = self.model_a().internal_data_model().db_name.clone();
-                (db, m.table.clone()).into()
+                let internal_data_model = self.internal_data_model();
+                let db = internal_data_model.identifier(&self.model_a().datasource_db_name());
+
+                (db, internal_data_model.identifier(&m.table)).into()
             }
             RelationLinkManifestation::Inline(ref m) => self
                 .internal_data_model()
@@ -109,10 +111,9 @@ impl RelationExt for Relation {
 
     fn inline_relation_columns(&self) -> Option<ColumnIterator> {
         if let Some(mani) = self.inline_manifestation() {
-            Some(ColumnIterator::from(vec![Column::from(
-                mani.referencing_column.clone(),
-            )
-            .table(self.as_table())]))
+            let column = self.internal_data_model().identifier(&mani.referencing_column);
+
+            Some(ColumnIterator::from(vec![Column::from(column).table(self.as_table())]))
         } else {
             None
         }
@@ -123,7 +124,10 @@ impl RelationExt for Relation {
         use crate::RelationLinkManifestation::*;
 
         match self.manifestation {
-            RelationTable(ref m) => ColumnIterator::from(vec![m.model_a_column.clone().into()]),
+            RelationTable(ref m) => {
+                let column = self.internal_data_model().identifier(&m.model_a_column);
+                ColumnIterator::from(vec![column.into()])
+            }
             Inline(ref m) => {
                 let model_a = self.model_a();
 
@@ -146,7 +150,10 @@ impl RelationExt for Relation {
         use crate::RelationLinkManifestation::*;
 
         match self.manifestation {
-            RelationTable(ref m) => ColumnIterator::from(vec![m.model_b_column.clone().into()]),
+            RelationTable(ref m) => {
+                let column = self.internal_data_model().identifier(&m.model_b_column);
+                ColumnIterator::from(vec![column.into()])
+            }
             Inline(ref m) => {
                 let model_b = self.model_b();
 
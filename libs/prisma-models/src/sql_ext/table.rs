@@ -7,6 +7,12 @@ pub trait AsTable {
 
 impl AsTable for Model {
     fn as_table(&self) -> Table<'static> {
-        (self.internal_data_model().db_name.clone(), self.db_name().to_string()).into()
+        let internal_data_model = self.internal_data_model();
+
+        (
+            internal_data_model.identifier(&self.datasource_db_name()),
+            internal_data_model.identifier(self.db_name()),
+        )
+            .into()
     }
 }
@@ -54,6 +54,7 @@ fn models_with_only_scalar_fields() {
                 id Int @id @default(autoincrement())
                 int Int
                 float Float
+                decimal Decimal
                 boolean Boolean
                 dateTime DateTime
                 stringOpt String?
@@ -75,6 +76,10 @@ fn models_with_only_scalar_fields() {
         .assert_scalar_field("float")
         .assert_type_identifier(TypeIdentifier::Float)
         .assert_no_behaviour();
+    model
+        .assert_scalar_field("decimal")
+        .assert_type_identifier(TypeIdentifier::Decimal)
+        .assert_no_behaviour();
     model
         .assert_scalar_field("boolean")
         .assert_type_identifier(TypeIdentifier::Boolean)
@@ -111,6 +116,26 @@ fn db_names_work() {
     assert_eq!(field.data_source_field().name, "my_column".to_owned(),)
 }
 
+#[test]
+fn computed_fields_work() {
+    let datamodel = convert(
+        r#"
+            model Test {
+                id String @id @default(cuid())
+                fullName String @computed("first_name || ' ' || last_name")
+            }
+        "#,
+    );
+
+    let model = datamodel.assert_model("Test");
+    let field = model.assert_scalar_field("fullName");
+
+    field.assert_behaviour(FieldBehaviour::Computed {
+        expression: "first_name || ' ' || last_name".to_owned(),
+    });
+    assert_eq!(field.computed(), Some("first_name || ' ' || last_name"));
+}
+
 #[test]
 #[ignore]
 fn scalar_lists_work() {
@@ -259,6 +284,44 @@ fn explicit_relation_fields() {
         }));
 }
 
+#[test]
+fn relations_referencing_unique_fields_are_linked_by_those_fields() {
+    let datamodel = convert(
+        r#"
+            model Blog {
+                id Int @id
+                slug String @unique
+                posts Post[]
+            }
+
+            model Post {
+                id Int @id
+                blog Blog @relation(references: [slug])
+            }
+        "#,
+    );
+
+    let blog = datamodel.assert_model("Blog");
+    let post = datamodel.assert_model("Post");
+
+    let blog_links: Vec<_> = blog
+        .assert_relation_field("posts")
+        .linking_fields()
+        .data_source_fields()
+        .map(|dsf| dsf.name.clone())
+        .collect();
+
+    let post_links: Vec<_> = post
+        .assert_relation_field("blog")
+        .linking_fields()
+        .data_source_fields()
+        .map(|dsf| dsf.name.clone())
+        .collect();
+
+    assert_eq!(blog_links, vec!["slug"]);
+    assert_eq!(post_links, vec!["blog"]);
+}
+
 #[test]
 fn many_to_many_relations() {
     let datamodel = convert(
@@ -303,6 +366,33 @@ fn many_to_many_relations() {
         }));
 }
 
+#[test]
+fn many_to_many_relations_with_named_relation_tables() {
+    let datamodel = convert(
+        r#"
+            model Post {
+                id Int @id
+                tags Tag[] @relation(table: "post_tags", column: "post_id")
+            }
+
+            model Tag {
+                id Int @id
+                posts Post[] @relation(column: "tag_id")
+            }
+        "#,
+    );
+
+    datamodel
+        .assert_relation("PostToTag")
+        .assert_model_a("Post")
+        .assert_model_b("Tag")
+        .assert_manifestation(RelationLinkManifestation::RelationTable(RelationTable {
+            table: "post_tags".to_string(),
+            model_a_column: "post_id".to_string(),
+            model_b_column: "tag_id".to_string(),
+        }));
+}
+
 #[test]
 fn implicit_relation_fields() {
     let datamodel = convert(
@@ -383,6 +473,47 @@ fn self_relations() {
     // employee.assert_relation_field("employee");
 }
 
+#[test]
+fn self_relations_hold_the_foreign_key_on_side_b() {
+    let datamodel = convert(
+        r#"
+            model User {
+                id        Int    @id
+                manager   User?  @relation("Management")
+                reports   User[] @relation("Management")
+                mentee    User?  @relation("Mentorship")
+                mentor    User?  @relation("Mentorship")
+                following User[] @relation("Follows")
+                followers User[] @relation("Follows")
+            }
+        "#,
+    );
+
+    let user = datamodel.assert_model("User");
+
+    user.assert_relation_field("reports").assert_side(RelationSide::A);
+    user.assert_relation_field("manager").assert_side(RelationSide::B);
+    datamodel
+        .assert_relation("Management")
+        .assert_manifestation(RelationLinkManifestation::Inline(InlineRelation {
+            in_table_of_model_name: "User".to_string(),
+            referencing_column: "manager".to_string(),
+        }));
+
+    // Without `references`, the field named first holds the foreign key of a one to one relation.
+    user.assert_relation_field("mentor").assert_side(RelationSide::A);
+    user.assert_relation_field("mentee").assert_side(RelationSide::B);
+    datamodel
+        .assert_relation("Mentorship")
+        .assert_manifestation(RelationLinkManifestation::Inline(InlineRelation {
+            in_table_of_model_name: "User".to_string(),
+            referencing_column: "mentee".to_string(),
+        }));
+
+    user.assert_relation_field("followers").assert_side(RelationSide::A);
+    user.assert_relation_field("following").assert_side(RelationSide::B);
+}
+
 #[test]
 fn ambiguous_relations() {
     let datamodel = convert(
@@ -410,6 +541,24 @@ fn ambiguous_relations() {
     post.assert_relation_field("blog2").assert_relation_name("Relation2");
 }
 
+#[test]
+fn unsupported_fields_are_left_out() {
+    let datamodel = convert(
+        r#"
+            model Place {
+                id       Int                                  @id
+                name     String
+                location Unsupported("geometry(Point,4326)")?
+            }
+        "#,
+    );
+
+    let model = datamodel.assert_model("Place");
+    model.assert_scalar_field("name");
+    assert!(model.fields().find_from_all("location").is_err());
+    assert_eq!(model.fields().scalar().len(), 2);
+}
+
 fn convert(datamodel: &str) -> Arc<InternalDataModel> {
     let datamodel = datamodel::parse_datamodel(datamodel).unwrap();
     let template = DatamodelConverter::convert(&datamodel);
@@ -1,6 +1,7 @@
 mod error;
 #[cfg(feature = "sql-ext")]
 pub mod sql_ext;
+mod time_zone;
 
 use chrono::prelude::*;
 use rust_decimal::{
@@ -12,6 +13,7 @@ use std::{convert::TryFrom, fmt, string::FromUtf8Error};
 use uuid::Uuid;
 
 pub use error::ConversionFailure;
+pub use time_zone::*;
 pub type PrismaValueResult<T> = std::result::Result<T, ConversionFailure>;
 pub type PrismaListValue = Vec<PrismaValue>;
 
@@ -32,6 +34,9 @@ pub enum PrismaValue {
     String(String),
     #[serde(serialize_with = "serialize_decimal")]
     Float(Decimal),
+    /// An exact decimal number, serialized as a string to not lose precision.
+    #[serde(serialize_with = "serialize_exact_decimal")]
+    Decimal(Decimal),
     Boolean(bool),
     #[serde(serialize_with = "serialize_date")]
     DateTime(DateTime<Utc>),
@@ -88,6 +93,13 @@ where
     decimal.to_f64().expect("Decimal is not a f64.").serialize(serializer)
 }
 
+fn serialize_exact_decimal<S>(decimal: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    decimal.to_string().serialize(serializer)
+}
+
 impl PrismaValue {
     pub fn is_null(&self) -> bool {
         match self {
@@ -116,6 +128,7 @@ impl fmt::Display for PrismaValue {
         match self {
             PrismaValue::String(x) => x.fmt(f),
             PrismaValue::Float(x) => x.fmt(f),
+            PrismaValue::Decimal(x) => x.fmt(f),
             PrismaValue::Boolean(x) => x.fmt(f),
             PrismaValue::DateTime(x) => x.fmt(f),
             PrismaValue::Enum(x) => x.fmt(f),
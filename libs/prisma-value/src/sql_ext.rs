@@ -40,6 +40,7 @@ impl<'a> From<PrismaValue> for ParameterizedValue<'a> {
         match pv {
             PrismaValue::String(s) => s.into(),
             PrismaValue::Float(f) => f.into(),
+            PrismaValue::Decimal(d) => d.into(),
             PrismaValue::Boolean(b) => b.into(),
             PrismaValue::DateTime(d) => d.into(),
             PrismaValue::Enum(e) => ParameterizedValue::Enum(e.into()),
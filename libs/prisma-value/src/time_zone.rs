@@ -0,0 +1,387 @@
+use crate::PrismaValue;
+use chrono::prelude::*;
+use std::{fmt, str::FromStr};
+
+/// Query parameter of connection strings selecting the time zone policy, e.g. `timezone=local` or
+/// `timezone=+02:00`.
+pub const TIME_ZONE_PARAM: &str = "timezone";
+
+/// How DateTime values are stored in columns without a time zone, e.g. `DATETIME` on MySQL or `timestamp` on
+/// Postgres. Values are instants in the engine, the policy decides which wall clock time a column holds for them.
+///
+/// Columns with a time zone, e.g. `timestamptz` on Postgres, already hold instants and should be used with `Utc`.
+/// MySQL converts `TIMESTAMP` columns by the time zone of the session, which connectors set to the offset of the
+/// policy, so that they hold the same wall clock time as `DATETIME` columns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZonePolicy {
+    /// Wall clock time in UTC.
+    Utc,
+
+    /// Wall clock time in the local time zone of the engine.
+    Local,
+
+    /// Wall clock time at a fixed offset from UTC.
+    Offset(FixedOffset),
+}
+
+impl Default for TimeZonePolicy {
+    fn default() -> Self {
+        TimeZonePolicy::Utc
+    }
+}
+
+impl TimeZonePolicy {
+    /// Reads the policy from the connection string and returns it with the connection string without the parameter,
+    /// databases don't know it.
+    pub fn from_url(url: &str) -> Result<(Self, String), String> {
+        let mut splitted = url.splitn(2, '?');
+        let base = splitted.next().unwrap();
+        let query = splitted.next().unwrap_or("");
+
+        let mut policy = Self::default();
+        let mut params = Vec::new();
+
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let mut parts = param.splitn(2, '=');
+
+            if parts.next() == Some(TIME_ZONE_PARAM) {
+                policy = parts.next().unwrap_or("").parse()?;
+            } else {
+                params.push(param);
+            }
+        }
+
+        if params.is_empty() {
+            Ok((policy, base.to_owned()))
+        } else {
+            Ok((policy, format!("{}?{}", base, params.join("&"))))
+        }
+    }
+
+    /// The value as it is bound to a query. DateTime values are shifted to the wall clock time of the policy,
+    /// drivers bind them as UTC.
+    pub fn to_database(self, value: PrismaValue) -> PrismaValue {
+        match value {
+            PrismaValue::DateTime(dt) if self != TimeZonePolicy::Utc => {
+                PrismaValue::DateTime(Utc.from_utc_datetime(&self.wall_clock(dt)))
+            }
+            PrismaValue::List(values) => PrismaValue::List(values.into_iter().map(|v| self.to_database(v)).collect()),
+            value => value,
+        }
+    }
+
+    /// The value as it is read from a row, the inverse of `to_database`.
+    pub fn from_database(self, value: PrismaValue) -> PrismaValue {
+        match value {
+            PrismaValue::DateTime(dt) if self != TimeZonePolicy::Utc => {
+                PrismaValue::DateTime(self.instant(dt.naive_utc()))
+            }
+            PrismaValue::List(values) => PrismaValue::List(values.into_iter().map(|v| self.from_database(v)).collect()),
+            value => value,
+        }
+    }
+
+    /// The wall clock time of the instant in the time zone of the policy.
+    pub fn wall_clock(self, dt: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            TimeZonePolicy::Utc => dt.naive_utc(),
+            TimeZonePolicy::Local => wall_clock_in(&Local, dt),
+            TimeZonePolicy::Offset(offset) => wall_clock_in(&offset, dt),
+        }
+    }
+
+    /// The offset of the time zone of the policy as `+HH:MM`, e.g. for the time zone of a database session.
+    /// The local time zone uses its current offset.
+    pub fn session_offset(self) -> String {
+        let seconds = match self {
+            TimeZonePolicy::Utc => 0,
+            TimeZonePolicy::Local => Local::now().offset().local_minus_utc(),
+            TimeZonePolicy::Offset(offset) => offset.local_minus_utc(),
+        };
+
+        let sign = if seconds < 0 { '-' } else { '+' };
+        let minutes = seconds.abs() / 60;
+
+        format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+
+    fn instant(self, naive: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            TimeZonePolicy::Utc => Utc.from_utc_datetime(&naive),
+            TimeZonePolicy::Local => instant_in(&Local, naive),
+            TimeZonePolicy::Offset(offset) => instant_in(&offset, naive),
+        }
+    }
+}
+
+fn wall_clock_in<Tz: TimeZone>(tz: &Tz, dt: DateTime<Utc>) -> NaiveDateTime {
+    dt.with_timezone(tz).naive_local()
+}
+
+/// Wall clock times skipped by a daylight saving transition are taken as UTC, repeated ones as the earlier instant.
+fn instant_in<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+impl FromStr for TimeZonePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Offsets may be percent-encoded in connection strings, e.g. `%2B02%3A00`.
+        let normalized = s.to_lowercase().replace("%2b", "+").replace("%3a", ":");
+
+        match normalized.as_str() {
+            "utc" | "z" | "+00:00" => Ok(TimeZonePolicy::Utc),
+            "local" => Ok(TimeZonePolicy::Local),
+            offset => parse_offset(offset).map(TimeZonePolicy::Offset).ok_or_else(|| {
+                format!(
+                    "Invalid time zone `{}`, expected `utc`, `local` or an offset like `+02:00`.",
+                    s
+                )
+            }),
+        }
+    }
+}
+
+impl fmt::Display for TimeZonePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeZonePolicy::Utc => write!(f, "utc"),
+            TimeZonePolicy::Local => write!(f, "local"),
+            TimeZonePolicy::Offset(_) => write!(f, "{}", self.session_offset()),
+        }
+    }
+}
+
+/// Parses `+HH:MM` or `-HH:MM`.
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let sign = match s.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+
+    let mut parts = s[1..].splitn(2, ':');
+    let hours: u32 = parts.next()?.parse().ok()?;
+    let minutes: u32 = parts.next()?.parse().ok()?;
+
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::LocalResult;
+
+    /// Central European time of 2020, summer time is from March 29 to October 25, 01:00 UTC.
+    #[derive(Debug, Clone, Copy)]
+    struct CentralEurope2020;
+
+    impl CentralEurope2020 {
+        fn is_summer_time(utc: NaiveDateTime) -> bool {
+            utc >= NaiveDate::from_ymd(2020, 3, 29).and_hms(1, 0, 0)
+                && utc < NaiveDate::from_ymd(2020, 10, 25).and_hms(1, 0, 0)
+        }
+
+        fn offset(summer_time: bool) -> FixedOffset {
+            FixedOffset::east(if summer_time { 2 * 3600 } else { 3600 })
+        }
+    }
+
+    impl TimeZone for CentralEurope2020 {
+        type Offset = FixedOffset;
+
+        fn from_offset(_offset: &FixedOffset) -> Self {
+            CentralEurope2020
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms(0, 0, 0))
+        }
+
+        /// The summer time offset comes first in repeated wall clock times, it is the earlier instant.
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let offsets: Vec<FixedOffset> = [true, false]
+                .iter()
+                .filter(|summer_time| {
+                    let offset = Self::offset(**summer_time);
+                    Self::is_summer_time(*local - offset) == **summer_time
+                })
+                .map(|summer_time| Self::offset(*summer_time))
+                .collect();
+
+            match offsets.as_slice() {
+                [offset] => LocalResult::Single(*offset),
+                [earlier, later] => LocalResult::Ambiguous(*earlier, *later),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms(0, 0, 0))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            Self::offset(Self::is_summer_time(*utc))
+        }
+    }
+
+    fn naive(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&naive(s))
+    }
+
+    fn offset(s: &str) -> TimeZonePolicy {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn policies_are_parsed() {
+        assert_eq!("utc".parse::<TimeZonePolicy>(), Ok(TimeZonePolicy::Utc));
+        assert_eq!("UTC".parse::<TimeZonePolicy>(), Ok(TimeZonePolicy::Utc));
+        assert_eq!("+00:00".parse::<TimeZonePolicy>(), Ok(TimeZonePolicy::Utc));
+        assert_eq!("local".parse::<TimeZonePolicy>(), Ok(TimeZonePolicy::Local));
+        assert_eq!(
+            "+02:00".parse::<TimeZonePolicy>(),
+            Ok(TimeZonePolicy::Offset(FixedOffset::east(7200)))
+        );
+        assert_eq!(
+            "-05:30".parse::<TimeZonePolicy>(),
+            Ok(TimeZonePolicy::Offset(FixedOffset::west(19800)))
+        );
+        assert_eq!(
+            "%2B02%3A00".parse::<TimeZonePolicy>(),
+            Ok(TimeZonePolicy::Offset(FixedOffset::east(7200)))
+        );
+    }
+
+    #[test]
+    fn invalid_policies_are_rejected() {
+        for s in &["", "berlin", "02:00", "+24:00", "+02:60", "+02"] {
+            assert!(s.parse::<TimeZonePolicy>().is_err(), "{} must be rejected", s);
+        }
+    }
+
+    #[test]
+    fn offsets_are_displayed_as_session_offsets() {
+        assert_eq!(TimeZonePolicy::Utc.to_string(), "utc");
+        assert_eq!(TimeZonePolicy::Local.to_string(), "local");
+        assert_eq!(offset("+02:00").to_string(), "+02:00");
+        assert_eq!(offset("-05:30").session_offset(), "-05:30");
+        assert_eq!(TimeZonePolicy::Utc.session_offset(), "+00:00");
+    }
+
+    #[test]
+    fn the_policy_is_split_off_the_connection_string() {
+        assert_eq!(
+            TimeZonePolicy::from_url("mysql://localhost/db?timezone=%2B02%3A00&connection_limit=5"),
+            Ok((offset("+02:00"), "mysql://localhost/db?connection_limit=5".to_owned()))
+        );
+        assert_eq!(
+            TimeZonePolicy::from_url("mysql://localhost/db?timezone=local"),
+            Ok((TimeZonePolicy::Local, "mysql://localhost/db".to_owned()))
+        );
+        assert_eq!(
+            TimeZonePolicy::from_url("mysql://localhost/db?connection_limit=5"),
+            Ok((
+                TimeZonePolicy::Utc,
+                "mysql://localhost/db?connection_limit=5".to_owned()
+            ))
+        );
+        assert!(TimeZonePolicy::from_url("mysql://localhost/db?timezone=berlin").is_err());
+    }
+
+    #[test]
+    fn values_are_shifted_to_the_wall_clock_time_of_offsets() {
+        let policy = offset("+02:00");
+        let instant = PrismaValue::DateTime(utc("2020-06-01 10:00:00"));
+        let wall_clock = PrismaValue::DateTime(utc("2020-06-01 12:00:00"));
+
+        assert_eq!(policy.to_database(instant.clone()), wall_clock);
+        assert_eq!(policy.from_database(wall_clock), instant);
+    }
+
+    #[test]
+    fn values_in_lists_are_shifted_and_other_values_are_kept() {
+        let policy = offset("-05:00");
+        let list = PrismaValue::List(vec![
+            PrismaValue::DateTime(utc("2020-01-01 03:00:00")),
+            PrismaValue::String("2020-01-01 03:00:00".to_owned()),
+        ]);
+
+        assert_eq!(
+            policy.to_database(list.clone()),
+            PrismaValue::List(vec![
+                PrismaValue::DateTime(utc("2019-12-31 22:00:00")),
+                PrismaValue::String("2020-01-01 03:00:00".to_owned()),
+            ])
+        );
+        assert_eq!(policy.from_database(policy.to_database(list.clone())), list);
+    }
+
+    #[test]
+    fn utc_keeps_values() {
+        let value = PrismaValue::DateTime(utc("2020-03-29 02:30:00"));
+
+        assert_eq!(TimeZonePolicy::Utc.to_database(value.clone()), value);
+        assert_eq!(TimeZonePolicy::Utc.from_database(value.clone()), value);
+    }
+
+    #[test]
+    fn wall_clock_times_follow_daylight_saving_transitions() {
+        assert_eq!(
+            wall_clock_in(&CentralEurope2020, utc("2020-03-29 00:59:59")),
+            naive("2020-03-29 01:59:59")
+        );
+        assert_eq!(
+            wall_clock_in(&CentralEurope2020, utc("2020-03-29 01:00:00")),
+            naive("2020-03-29 03:00:00")
+        );
+        assert_eq!(
+            wall_clock_in(&CentralEurope2020, utc("2020-10-25 00:30:00")),
+            naive("2020-10-25 02:30:00")
+        );
+        assert_eq!(
+            wall_clock_in(&CentralEurope2020, utc("2020-10-25 01:30:00")),
+            naive("2020-10-25 02:30:00")
+        );
+    }
+
+    #[test]
+    fn wall_clock_times_outside_of_transitions_round_trip() {
+        for s in &["2020-01-15 12:00:00", "2020-07-15 12:00:00", "2020-03-29 01:00:00"] {
+            let instant = utc(s);
+
+            assert_eq!(
+                instant_in(&CentralEurope2020, wall_clock_in(&CentralEurope2020, instant)),
+                instant
+            );
+        }
+    }
+
+    #[test]
+    fn skipped_wall_clock_times_are_taken_as_utc() {
+        assert_eq!(
+            instant_in(&CentralEurope2020, naive("2020-03-29 02:30:00")),
+            utc("2020-03-29 02:30:00")
+        );
+    }
+
+    #[test]
+    fn repeated_wall_clock_times_are_the_earlier_instant() {
+        assert_eq!(
+            instant_in(&CentralEurope2020, naive("2020-10-25 02:30:00")),
+            utc("2020-10-25 00:30:00")
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Quoting of SQL identifiers, shared by the query builder and the migration renderers.
+//!
+//! Identifiers are always delimited, so reserved words (`select`), mixed case (`UserProfile`) and dots
+//! (`user.profile`) are taken literally. Delimiters in the identifier itself are escaped by doubling them.
+use std::{borrow::Cow, fmt};
+
+/// The character delimiting identifiers in a SQL dialect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Delimiter {
+    /// Standard SQL, used by PostgreSQL and SQLite.
+    DoubleQuote,
+    /// MySQL and MariaDB.
+    Backtick,
+}
+
+impl Delimiter {
+    fn as_char(self) -> char {
+        match self {
+            Delimiter::DoubleQuote => '"',
+            Delimiter::Backtick => '`',
+        }
+    }
+}
+
+/// An identifier rendered with delimiters when displayed.
+#[derive(Debug, Clone, Copy)]
+pub struct Quoted<T> {
+    delimiter: Delimiter,
+    identifier: T,
+}
+
+impl<T> Quoted<T> {
+    pub fn new(delimiter: Delimiter, identifier: T) -> Self {
+        Self { delimiter, identifier }
+    }
+
+    pub fn postgres(identifier: T) -> Self {
+        Self::new(Delimiter::DoubleQuote, identifier)
+    }
+
+    pub fn mysql(identifier: T) -> Self {
+        Self::new(Delimiter::Backtick, identifier)
+    }
+
+    pub fn sqlite(identifier: T) -> Self {
+        Self::new(Delimiter::DoubleQuote, identifier)
+    }
+}
+
+impl<T> fmt::Display for Quoted<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let delimiter = self.delimiter.as_char();
+        let identifier = self.identifier.to_string();
+
+        write!(f, "{}{}{}", delimiter, escape(self.delimiter, &identifier), delimiter)
+    }
+}
+
+/// Escapes the delimiters in the identifier, without delimiting it. For renderers that add the delimiters
+/// themselves, like the query AST of quaint, which doesn't escape them.
+pub fn escape(delimiter: Delimiter, identifier: &str) -> Cow<'_, str> {
+    let delimiter = delimiter.as_char();
+
+    if identifier.contains(delimiter) {
+        let doubled: String = [delimiter, delimiter].iter().collect();
+        Cow::Owned(identifier.replace(delimiter, &doubled))
+    } else {
+        Cow::Borrowed(identifier)
+    }
+}
+
+/// Quotes a name qualified by its schema, e.g. `"public"."User"`.
+pub fn quote_with_schema(delimiter: Delimiter, schema: &str, name: &str) -> String {
+    format!("{}.{}", Quoted::new(delimiter, schema), Quoted::new(delimiter, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_identifiers_are_delimited() {
+        assert_eq!(Quoted::postgres("User").to_string(), r#""User""#);
+        assert_eq!(Quoted::mysql("User").to_string(), "`User`");
+        assert_eq!(Quoted::sqlite("User").to_string(), r#""User""#);
+    }
+
+    #[test]
+    fn reserved_words_are_delimited() {
+        assert_eq!(Quoted::postgres("select").to_string(), r#""select""#);
+        assert_eq!(Quoted::mysql("select").to_string(), "`select`");
+        assert_eq!(Quoted::sqlite("select").to_string(), r#""select""#);
+    }
+
+    #[test]
+    fn delimiters_in_identifiers_are_escaped() {
+        assert_eq!(Quoted::postgres(r#"user"s"#).to_string(), r#""user""s""#);
+        assert_eq!(Quoted::mysql(r#"user"s"#).to_string(), r#"`user"s`"#);
+        assert_eq!(Quoted::sqlite(r#"user"s"#).to_string(), r#""user""s""#);
+
+        assert_eq!(Quoted::postgres("user`s").to_string(), r#""user`s""#);
+        assert_eq!(Quoted::mysql("user`s").to_string(), "`user``s`");
+        assert_eq!(Quoted::sqlite("user`s").to_string(), r#""user`s""#);
+    }
+
+    #[test]
+    fn escaping_leaves_the_delimiters_out() {
+        assert_eq!(escape(Delimiter::DoubleQuote, r#"user"s"#), r#"user""s"#);
+        assert_eq!(escape(Delimiter::Backtick, "user`s"), "user``s");
+        assert_eq!(escape(Delimiter::Backtick, r#"user"s"#), r#"user"s"#);
+        assert_eq!(escape(Delimiter::DoubleQuote, "select"), "select");
+    }
+
+    #[test]
+    fn dots_are_part_of_the_identifier() {
+        assert_eq!(
+            quote_with_schema(Delimiter::DoubleQuote, "my.schema", "user.profile"),
+            r#""my.schema"."user.profile""#
+        );
+        assert_eq!(
+            quote_with_schema(Delimiter::Backtick, "my.schema", "user.profile"),
+            "`my.schema`.`user.profile`"
+        );
+    }
+}
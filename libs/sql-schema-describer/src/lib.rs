@@ -2,6 +2,7 @@
 
 use failure::Fail;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 pub mod mysql;
@@ -28,6 +29,8 @@ pub trait SqlSchemaDescriberBackend: Send + Sync + 'static {
     async fn get_metadata(&self, schema: &str) -> SqlSchemaDescriberResult<SQLMetadata>;
     /// Describe a database schema.
     async fn describe(&self, schema: &str) -> SqlSchemaDescriberResult<SqlSchema>;
+    /// List the stored procedures and functions of a database schema.
+    async fn list_routines(&self, schema: &str) -> SqlSchemaDescriberResult<Vec<Routine>>;
 }
 
 #[derive(Serialize, Deserialize)]
@@ -46,6 +49,12 @@ pub struct SqlSchema {
     pub enums: Vec<Enum>,
     /// The schema's sequences, unique to Postgres.
     pub sequences: Vec<Sequence>,
+    /// The schema's views, only described on Postgres.
+    #[serde(default)]
+    pub views: Vec<View>,
+    /// The schema's triggers, only described on Postgres.
+    #[serde(default)]
+    pub triggers: Vec<Trigger>,
 }
 
 impl SqlSchema {
@@ -79,11 +88,18 @@ impl SqlSchema {
         self.sequences.iter().find(|x| x.name == name)
     }
 
+    /// Get a view.
+    pub fn get_view(&self, name: &str) -> Option<&View> {
+        self.views.iter().find(|x| x.name == name)
+    }
+
     pub fn empty() -> SqlSchema {
         SqlSchema {
             tables: Vec::new(),
             enums: Vec::new(),
             sequences: Vec::new(),
+            views: Vec::new(),
+            triggers: Vec::new(),
         }
     }
 }
@@ -102,6 +118,12 @@ pub struct Table {
     pub primary_key: Option<PrimaryKey>,
     /// The table's foreign keys.
     pub foreign_keys: Vec<ForeignKey>,
+    /// The table's declarative partitioning, unique to Postgres.
+    #[serde(default)]
+    pub partitioning: Option<Partitioning>,
+    /// The table's storage options.
+    #[serde(default)]
+    pub options: TableOptions,
 }
 
 impl Table {
@@ -150,6 +172,35 @@ impl Table {
         })
     }
 }
+
+/// The declarative partitioning of a table.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Partitioning {
+    /// The partition key definition, e.g. `RANGE (created_at)`.
+    pub key: String,
+    /// The names of the partitions attached to the table.
+    pub partitions: Vec<String>,
+}
+
+/// The storage options of a table. Options that are not set are left to the database.
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableOptions {
+    /// The storage engine, unique to MySQL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+    /// The default character set, unique to MySQL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charset: Option<String>,
+    /// The tablespace, unique to Postgres.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tablespace: Option<String>,
+    /// The fillfactor in percent, unique to Postgres.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fillfactor: Option<u32>,
+}
+
 /// The type of an index.
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -170,12 +221,53 @@ pub struct Index {
     pub columns: Vec<String>,
     /// Type of index.
     pub tpe: IndexType,
+    /// The options of the index columns, by column name. Columns with the default options are left out.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_options: BTreeMap<String, IndexColumnOptions>,
 }
 
 impl Index {
     pub fn is_unique(&self) -> bool {
         self.tpe == IndexType::Unique
     }
+
+    /// The options of the given index column.
+    pub fn options_of_column(&self, column_name: &str) -> IndexColumnOptions {
+        self.column_options.get(column_name).copied().unwrap_or_default()
+    }
+}
+
+/// The options of a column of an index.
+#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexColumnOptions {
+    /// The sort order of the column in the index.
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    /// The length of the indexed prefix of the column (MySQL only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u32>,
+}
+
+impl IndexColumnOptions {
+    pub fn is_default(&self) -> bool {
+        *self == IndexColumnOptions::default()
+    }
+}
+
+/// The sort order of an index column.
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SortOrder {
+    /// Ascending.
+    Asc,
+    /// Descending.
+    Desc,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder::Asc
+    }
 }
 
 /// The primary key of a table.
@@ -207,12 +299,51 @@ pub struct Column {
     pub default: Option<String>,
     /// Is the column auto-incrementing?
     pub auto_increment: bool,
+    /// The generation of the column, if its values are computed from the other columns of the row.
+    #[serde(default)]
+    pub generated: Option<GeneratedColumn>,
+    /// Is the column set to the current timestamp whenever its row is updated? (MySQL's `ON UPDATE CURRENT_TIMESTAMP`)
+    #[serde(default)]
+    pub on_update_current_timestamp: bool,
 }
 
 impl Column {
     pub fn is_required(&self) -> bool {
         self.tpe.arity == ColumnArity::Required
     }
+
+    pub fn is_generated(&self) -> bool {
+        self.generated.is_some()
+    }
+}
+
+/// The generation of a column, e.g. `GENERATED ALWAYS AS (price * quantity) STORED`.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedColumn {
+    /// The generation expression, as the database reports it.
+    pub expression: String,
+    /// How the values are generated.
+    pub kind: GeneratedColumnKind,
+}
+
+/// Whether the values of a generated column are written on insert and update or computed on read.
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GeneratedColumnKind {
+    /// The values are written like the values of other columns. The only kind Postgres supports.
+    Stored,
+    /// The values are computed when rows are read.
+    Virtual,
+}
+
+impl GeneratedColumnKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GeneratedColumnKind::Stored => "STORED",
+            GeneratedColumnKind::Virtual => "VIRTUAL",
+        }
+    }
 }
 
 /// The type of a column.
@@ -235,6 +366,35 @@ impl ColumnType {
             arity,
         }
     }
+
+    /// Case-insensitive text, the `citext` type of PostgreSQL. Arrays of it are described as `_citext`.
+    pub fn is_citext(&self) -> bool {
+        self.family == ColumnTypeFamily::String && self.raw.trim_start_matches('_').eq_ignore_ascii_case("citext")
+    }
+
+    /// The precision and scale of exact numbers, e.g. `(10, 2)` for `numeric(10,2)` or `DECIMAL(10, 2)`. `None` if the
+    /// raw type doesn't name them.
+    pub fn decimal_precision(&self) -> Option<(u32, u32)> {
+        if self.family != ColumnTypeFamily::Float {
+            return None;
+        }
+
+        let raw = self.raw.trim().to_lowercase();
+        let start = raw.find('(')?;
+
+        if !raw.ends_with(')') || !["numeric", "decimal"].contains(&raw[..start].trim()) {
+            return None;
+        }
+
+        let mut modifiers = raw[start + 1..raw.len() - 1]
+            .split(',')
+            .map(|modifier| modifier.trim().parse::<u32>().ok());
+
+        match (modifiers.next(), modifiers.next(), modifiers.next()) {
+            (Some(Some(precision)), Some(Some(scale)), None) => Some((precision, scale)),
+            _ => None,
+        }
+    }
 }
 
 /// Enumeration of column type families.
@@ -351,6 +511,10 @@ pub struct ForeignKey {
     pub referenced_columns: Vec<String>,
     /// Action on deletion.
     pub on_delete_action: ForeignKeyAction,
+    /// Whether the constraint is `DEFERRABLE INITIALLY DEFERRED`, i.e. only checked when the transaction commits
+    /// (Postgres only).
+    #[serde(default)]
+    pub deferrable: bool,
 }
 
 /// A SQL enum.
@@ -374,3 +538,144 @@ pub struct Sequence {
     /// Sequence allocation size.
     pub allocation_size: u32,
 }
+
+/// A view.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct View {
+    /// View name.
+    pub name: String,
+    /// The query of the view, as reconstructed by the database.
+    pub definition: String,
+    /// The columns of the tables and views the view selects from.
+    pub dependencies: Vec<ColumnDependency>,
+}
+
+/// A trigger on a table.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trigger {
+    /// Trigger name.
+    pub name: String,
+    /// The table the trigger is defined on.
+    pub table: String,
+    /// The complete `CREATE TRIGGER` statement.
+    pub definition: String,
+    /// The columns of the table the trigger depends on, e.g. in its `UPDATE OF` or `WHEN` clauses.
+    pub columns: Vec<String>,
+}
+
+impl Trigger {
+    /// Whether the trigger records the row changes of an `@@audited` model in its history table. The migration
+    /// engine names these triggers `{table}_audit` on Postgres and `{table}_audit_{event}` on MySQL.
+    pub fn is_audit_trigger(&self) -> bool {
+        let prefix = format!("{}_audit", self.table);
+
+        self.name == prefix
+            || ["insert", "update", "delete"]
+                .iter()
+                .any(|event| self.name == format!("{}_{}", prefix, event))
+    }
+
+    /// The table audit triggers record the row changes in.
+    pub fn history_table_name(&self) -> String {
+        format!("{}_history", self.table)
+    }
+}
+
+/// A column another database object depends on.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnDependency {
+    /// The table or view of the column.
+    pub table: String,
+    /// Column name.
+    pub column: String,
+}
+
+/// A stored procedure or function.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Routine {
+    /// Routine name, overloaded functions share the same name.
+    pub name: String,
+    /// Procedure or function.
+    pub kind: RoutineKind,
+    /// Parameters in the order of the signature.
+    pub parameters: Vec<RoutineParameter>,
+    /// Data type of the return value, only for functions.
+    pub return_type: Option<String>,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoutineKind {
+    Procedure,
+    Function,
+}
+
+/// A parameter of a routine.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutineParameter {
+    /// Parameter name, unnamed parameters of Postgres functions have none.
+    pub name: Option<String>,
+    /// Whether the parameter is an input, an output or both.
+    pub mode: ParameterMode,
+    /// Data type as reported by the database.
+    pub data_type: String,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParameterMode {
+    In,
+    Out,
+    InOut,
+}
+
+/// Collects routines from the rows of a query over `information_schema.routines` joined with its parameters,
+/// ordered by routine and parameter position.
+fn collect_routines(rows: quaint::connector::ResultSet) -> Vec<Routine> {
+    let mut routines: Vec<(String, Routine)> = Vec::new();
+
+    for row in rows.into_iter() {
+        let column = |name: &str| row.get(name).and_then(|x| x.to_string()).filter(|x| !x.is_empty());
+        let specific_name = column("specific_name").expect("get specific_name");
+
+        if routines.last().map(|(name, _)| name != &specific_name).unwrap_or(true) {
+            let kind = match column("routine_type").as_ref().map(String::as_str) {
+                Some("PROCEDURE") => RoutineKind::Procedure,
+                _ => RoutineKind::Function,
+            };
+
+            let routine = Routine {
+                name: column("routine_name").expect("get routine_name"),
+                kind,
+                parameters: Vec::new(),
+                return_type: column("return_type"),
+            };
+
+            routines.push((specific_name, routine));
+        }
+
+        // Routines without parameters have a single row without parameter, MySQL also lists the return value of
+        // functions as a parameter without mode.
+        let mode = match column("parameter_mode").as_ref().map(String::as_str) {
+            Some("IN") => ParameterMode::In,
+            Some("OUT") => ParameterMode::Out,
+            Some("INOUT") => ParameterMode::InOut,
+            _ => continue,
+        };
+
+        let (_, routine) = routines.last_mut().unwrap();
+
+        routine.parameters.push(RoutineParameter {
+            name: column("parameter_name"),
+            mode,
+            data_type: column("data_type").expect("get data_type"),
+        });
+    }
+
+    routines.into_iter().map(|(_, routine)| routine).collect()
+}
@@ -2,7 +2,7 @@ use super::*;
 use log::debug;
 use once_cell::sync::Lazy;
 use quaint::prelude::Queryable;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
 pub struct SqlSchemaDescriber {
@@ -25,18 +25,43 @@ impl super::SqlSchemaDescriberBackend for SqlSchemaDescriber {
         })
     }
 
+    async fn list_routines(&self, schema: &str) -> SqlSchemaDescriberResult<Vec<Routine>> {
+        debug!("Getting routines");
+        let sql = "SELECT r.specific_name AS specific_name, r.routine_name AS routine_name,
+                r.routine_type AS routine_type, r.dtd_identifier AS return_type, p.parameter_name AS parameter_name,
+                p.parameter_mode AS parameter_mode, p.dtd_identifier AS data_type
+            FROM information_schema.routines r
+            LEFT JOIN information_schema.parameters p
+                ON p.specific_schema = r.routine_schema AND p.specific_name = r.specific_name
+            WHERE r.routine_schema = ?
+            ORDER BY r.routine_name, r.specific_name, p.ordinal_position";
+        let rows = self
+            .conn
+            .query_raw(sql, &[schema.into()])
+            .await
+            .expect("querying for routines");
+
+        let routines = collect_routines(rows);
+        debug!("Found routines: {:?}", routines);
+
+        Ok(routines)
+    }
+
     async fn describe(&self, schema: &str) -> SqlSchemaDescriberResult<SqlSchema> {
         debug!("describing schema '{}'", schema);
 
         let table_names = self.get_table_names(schema).await;
         let mut tables = Vec::with_capacity(table_names.len());
-        let mut columns = get_all_columns(self.conn.as_ref(), schema).await;
+        let json_columns = get_json_columns(self.conn.as_ref(), schema).await;
+        let mut columns = get_all_columns(self.conn.as_ref(), schema, &json_columns).await;
         let mut indexes = get_all_indexes(self.conn.as_ref(), schema).await;
         let mut fks = get_foreign_keys(self.conn.as_ref(), schema).await;
+        let mut table_options = get_all_table_options(self.conn.as_ref(), schema).await;
+        let triggers = self.get_triggers(schema).await;
 
         let mut enums = vec![];
         for table_name in &table_names {
-            let (table, enms) = self.get_table(table_name, &mut columns, &mut indexes, &mut fks);
+            let (table, enms) = self.get_table(table_name, &mut columns, &mut indexes, &mut fks, &mut table_options);
             tables.push(table);
             enums.extend(enms.iter().cloned());
         }
@@ -45,6 +70,9 @@ impl super::SqlSchemaDescriberBackend for SqlSchemaDescriber {
             tables,
             enums,
             sequences: vec![],
+            // Views are only described on Postgres.
+            views: vec![],
+            triggers,
         })
     }
 }
@@ -97,6 +125,60 @@ impl SqlSchemaDescriber {
         names
     }
 
+    async fn get_triggers(&self, schema: &str) -> Vec<Trigger> {
+        debug!("Getting triggers");
+        let sql = "SELECT
+                trigger_name as trigger_name,
+                event_object_table as table_name,
+                action_timing as action_timing,
+                event_manipulation as event_manipulation,
+                action_statement as action_statement
+            FROM information_schema.triggers
+            WHERE trigger_schema = ?
+            ORDER BY event_object_table, trigger_name";
+
+        let rows = self
+            .conn
+            .query_raw(sql, &[schema.into()])
+            .await
+            .expect("querying for triggers");
+
+        let triggers = rows
+            .into_iter()
+            .map(|row| {
+                debug!("Got trigger row: {:?}", row);
+                let get = |column: &str| {
+                    row.get(column)
+                        .and_then(|x| x.to_string())
+                        .unwrap_or_else(|| panic!("get {}", column))
+                };
+                let (name, table) = (get("trigger_name"), get("table_name"));
+
+                // MySQL doesn't keep the `CREATE TRIGGER` statement, it is put back together from its parts.
+                let definition = format!(
+                    "CREATE TRIGGER `{}`.`{}` {} {} ON `{}`.`{}` FOR EACH ROW {}",
+                    schema,
+                    name,
+                    get("action_timing"),
+                    get("event_manipulation"),
+                    schema,
+                    table,
+                    get("action_statement"),
+                );
+
+                Trigger {
+                    name,
+                    table,
+                    definition,
+                    columns: Vec::new(),
+                }
+            })
+            .collect();
+
+        debug!("Found triggers: {:?}", triggers);
+        triggers
+    }
+
     async fn get_size(&self, schema: &str) -> usize {
         debug!("Getting db size");
         let sql = r#"
@@ -121,12 +203,14 @@ impl SqlSchemaDescriber {
         columns: &mut HashMap<String, (Vec<Column>, Vec<Enum>)>,
         indexes: &mut HashMap<String, (BTreeMap<String, Index>, Option<PrimaryKey>)>,
         foreign_keys: &mut HashMap<String, Vec<ForeignKey>>,
+        table_options: &mut HashMap<String, TableOptions>,
     ) -> (Table, Vec<Enum>) {
         debug!("Getting table '{}'", name);
         let (columns, enums) = columns.remove(name).expect("table columns not found");
         let (indices, primary_key) = indexes.remove(name).unwrap_or_else(|| (BTreeMap::new(), None));
 
         let foreign_keys = foreign_keys.remove(name).unwrap_or_default();
+        let options = table_options.remove(name).unwrap_or_default();
         (
             Table {
                 name: name.to_string(),
@@ -134,13 +218,130 @@ impl SqlSchemaDescriber {
                 foreign_keys,
                 indices: indices.into_iter().map(|(_k, v)| v).collect(),
                 primary_key,
+                partitioning: None,
+                options,
             },
             enums,
         )
     }
 }
 
-async fn get_all_columns(conn: &dyn Queryable, schema_name: &str) -> HashMap<String, (Vec<Column>, Vec<Enum>)> {
+async fn get_all_table_options(conn: &dyn Queryable, schema_name: &str) -> HashMap<String, TableOptions> {
+    // The character set is not part of `information_schema.tables`, only the collation is.
+    let sql = "
+            SELECT
+                tables.table_name table_name,
+                tables.engine engine,
+                collations.character_set_name charset
+            FROM information_schema.tables tables
+            LEFT JOIN information_schema.collation_character_set_applicability collations
+                ON collations.collation_name = tables.table_collation
+            WHERE tables.table_schema = ?
+            AND tables.table_type = 'BASE TABLE'
+        ";
+
+    let rows = conn
+        .query_raw(sql, &[schema_name.into()])
+        .await
+        .expect("querying for table options");
+
+    let mut map = HashMap::new();
+
+    for row in rows {
+        debug!("Got table options: {:?}", row);
+
+        let table_name = row
+            .get("table_name")
+            .and_then(|x| x.to_string())
+            .expect("get table name");
+
+        let options = TableOptions {
+            engine: row.get("engine").and_then(|x| x.to_string()),
+            charset: row.get("charset").and_then(|x| x.to_string()),
+            tablespace: None,
+            fillfactor: None,
+        };
+
+        map.insert(table_name, options);
+    }
+
+    map
+}
+
+/// The `longtext` columns validated as JSON by MariaDB, as (table, column). On MariaDB, `JSON` is only an alias of
+/// `LONGTEXT` with a `json_valid()` check constraint on the column. MySQL has a type of its own for JSON columns.
+async fn get_json_columns(conn: &dyn Queryable, schema_name: &str) -> HashSet<(String, String)> {
+    let rows = conn
+        .query_raw("SELECT @@GLOBAL.version version", &[])
+        .await
+        .expect("querying for the version");
+
+    let is_mariadb = rows
+        .first()
+        .and_then(|row| row.get("version").and_then(|version| version.to_string()))
+        .map(|version| version.contains("MariaDB"))
+        .unwrap_or(false);
+
+    if !is_mariadb {
+        return HashSet::new();
+    }
+
+    let sql = "
+            SELECT
+                table_name table_name,
+                check_clause check_clause
+            FROM information_schema.check_constraints
+            WHERE constraint_schema = ?
+        ";
+
+    // The check constraints are only listed since MariaDB 10.2.22.
+    let rows = match conn.query_raw(sql, &[schema_name.into()]).await {
+        Ok(rows) => rows,
+        Err(err) => {
+            debug!("Could not read the check constraints: {}", err);
+            return HashSet::new();
+        }
+    };
+
+    rows.into_iter()
+        .filter_map(|row| {
+            let table_name = row.get("table_name").and_then(|x| x.to_string())?;
+            let check_clause = row.get("check_clause").and_then(|x| x.to_string())?;
+
+            json_valid_column(&check_clause).map(|column| (table_name, column.to_owned()))
+        })
+        .collect()
+}
+
+/// The column a check clause validates as JSON, e.g. ``json_valid(`data`)``.
+fn json_valid_column(check_clause: &str) -> Option<&str> {
+    const JSON_VALID: &str = "json_valid(";
+
+    let check_clause = check_clause.trim();
+    let is_json_valid = check_clause
+        .get(..JSON_VALID.len())
+        .map(|prefix| prefix.eq_ignore_ascii_case(JSON_VALID))
+        .unwrap_or(false);
+
+    if !is_json_valid || !check_clause.ends_with(')') {
+        return None;
+    }
+
+    let column = check_clause[JSON_VALID.len()..check_clause.len() - 1].trim();
+    let column = column.trim_matches('`');
+
+    if column.is_empty() || column.contains(|c| c == '(' || c == ',') {
+        return None;
+    }
+
+    Some(column)
+}
+
+async fn get_all_columns(
+    conn: &dyn Queryable,
+    schema_name: &str,
+    json_columns: &HashSet<(String, String)>,
+) -> HashMap<String, (Vec<Column>, Vec<Enum>)> {
     // We alias all the columns because MySQL column names are case-insensitive in queries, but the
     // information schema column names became upper-case in MySQL 8, causing the code fetching
     // the result values by column name below to fail.
@@ -152,6 +353,7 @@ async fn get_all_columns(conn: &dyn Queryable, schema_name: &str) -> HashMap<Str
                 column_default column_default,
                 is_nullable is_nullable,
                 extra extra,
+                generation_expression generation_expression,
                 table_name table_name
             FROM information_schema.columns
             WHERE table_schema = ?
@@ -197,7 +399,9 @@ async fn get_all_columns(conn: &dyn Queryable, schema_name: &str) -> HashMap<Str
         } else {
             ColumnArity::Nullable
         };
-        let (tpe, enum_option) = get_column_type_and_enum(&table_name, &name, &data_type, &full_data_type, arity);
+        let is_json = json_columns.contains(&(table_name.clone(), name.clone()));
+        let (tpe, enum_option) =
+            get_column_type_and_enum(&table_name, &name, &data_type, &full_data_type, is_json, arity);
         let extra = col
             .get("extra")
             .and_then(|x| x.to_string())
@@ -207,6 +411,8 @@ async fn get_all_columns(conn: &dyn Queryable, schema_name: &str) -> HashMap<Str
             "auto_increment" => true,
             _ => false,
         };
+        let generated = get_generated_column(&extra, col.get("generation_expression").and_then(|x| x.to_string()));
+        let on_update_current_timestamp = extra.contains("on update current_timestamp");
 
         let entry = map.entry(table_name).or_insert((Vec::new(), Vec::new()));
 
@@ -223,6 +429,8 @@ async fn get_all_columns(conn: &dyn Queryable, schema_name: &str) -> HashMap<Str
                 .and_then(sanitize_default_value)
                 .map(String::from),
             auto_increment,
+            generated,
+            on_update_current_timestamp,
         };
 
         entry.0.push(col);
@@ -231,6 +439,26 @@ async fn get_all_columns(conn: &dyn Queryable, schema_name: &str) -> HashMap<Str
     map
 }
 
+/// Generated columns are marked in `extra`, e.g. `STORED GENERATED`. MariaDB used to call stored columns
+/// `PERSISTENT`, MySQL 8 marks defaults with expressions as `DEFAULT_GENERATED`. The expression has its quotes
+/// escaped.
+fn get_generated_column(extra: &str, expression: Option<String>) -> Option<GeneratedColumn> {
+    let kind = if extra.contains("virtual generated") {
+        GeneratedColumnKind::Virtual
+    } else if extra.contains("stored generated") || extra.contains("persistent generated") {
+        GeneratedColumnKind::Stored
+    } else {
+        return None;
+    };
+
+    expression
+        .filter(|expression| !expression.is_empty())
+        .map(|expression| GeneratedColumn {
+            expression: expression.replace("\\'", "'"),
+            kind,
+        })
+}
+
 async fn get_all_indexes(
     conn: &dyn Queryable,
     schema_name: &str,
@@ -246,7 +474,9 @@ async fn get_all_indexes(
                 non_unique AS non_unique,
                 column_name AS column_name,
                 seq_in_index AS seq_in_index,
-                table_name AS table_name
+                table_name AS table_name,
+                collation AS collation,
+                sub_part AS sub_part
             FROM INFORMATION_SCHEMA.STATISTICS
             WHERE table_schema = ?
             ORDER BY index_name, seq_in_index
@@ -265,6 +495,15 @@ async fn get_all_indexes(
         let index_name = row.get("index_name").and_then(|x| x.to_string()).expect("index_name");
         let is_unique = !row.get("non_unique").and_then(|x| x.as_bool()).expect("non_unique");
         let column_name = row.get("column_name").and_then(|x| x.to_string()).expect("column_name");
+        // The collation is `D` for descending index columns (MySQL 8 and up), and the sub_part the length of
+        // indexed prefixes.
+        let column_options = IndexColumnOptions {
+            sort_order: match row.get("collation").and_then(|x| x.to_string()).as_deref() {
+                Some("D") => SortOrder::Desc,
+                _ => SortOrder::Asc,
+            },
+            length: row.get("sub_part").and_then(|x| x.as_i64()).map(|length| length as u32),
+        };
 
         // Multi-column indices will return more than one row (with different column_name values).
         // We cannot assume that one row corresponds to one index.
@@ -297,23 +536,21 @@ async fn get_all_indexes(
                 }
             };
         } else {
-            if indexes_map.contains_key(&index_name) {
-                indexes_map.get_mut(&index_name).map(|index: &mut Index| {
-                    index.columns.push(column_name);
-                });
-            } else {
-                indexes_map.insert(
-                    index_name.clone(),
-                    Index {
-                        name: index_name,
-                        columns: vec![column_name],
-                        tpe: match is_unique {
-                            true => IndexType::Unique,
-                            false => IndexType::Normal,
-                        },
-                    },
-                );
+            let index = indexes_map.entry(index_name.clone()).or_insert_with(|| Index {
+                name: index_name,
+                columns: vec![],
+                tpe: match is_unique {
+                    true => IndexType::Unique,
+                    false => IndexType::Normal,
+                },
+                column_options: BTreeMap::new(),
+            });
+
+            if !column_options.is_default() {
+                index.column_options.insert(column_name.clone(), column_options);
             }
+
+            index.columns.push(column_name);
         }
     }
 
@@ -421,6 +658,7 @@ async fn get_foreign_keys(conn: &dyn Queryable, schema_name: &str) -> HashMap<St
                     referenced_table,
                     referenced_columns: vec![referenced_column],
                     on_delete_action,
+                    deferrable: false,
                 };
                 intermediate_fks.insert(constraint_name, fk);
             }
@@ -443,6 +681,7 @@ fn get_column_type_and_enum(
     column_name: &str,
     data_type: &str,
     full_data_type: &str,
+    is_json: bool,
     arity: ColumnArity,
 ) -> (ColumnType, Option<Enum>) {
     let family = match (data_type, full_data_type) {
@@ -466,6 +705,7 @@ fn get_column_type_and_enum(
         ("text", _) => ColumnTypeFamily::String,
         ("tinytext", _) => ColumnTypeFamily::String,
         ("mediumtext", _) => ColumnTypeFamily::String,
+        ("longtext", _) if is_json => ColumnTypeFamily::Json,
         ("longtext", _) => ColumnTypeFamily::String,
         ("enum", _) => ColumnTypeFamily::Enum(format!("{}_{}", table, column_name)),
         // XXX: Is this correct?
@@ -545,4 +785,13 @@ mod tests {
 
         assert_eq!(unquote_mariadb_strings("heh "), "heh ");
     }
+
+    #[test]
+    fn json_valid_check_clauses_name_their_column() {
+        assert_eq!(json_valid_column("json_valid(`row_data`)"), Some("row_data"));
+        assert_eq!(json_valid_column("JSON_VALID(data)"), Some("data"));
+        assert_eq!(json_valid_column("json_valid(`a`) and `b` > 0"), None);
+        assert_eq!(json_valid_column("json_valid(concat(`a`,`b`))"), None);
+        assert_eq!(json_valid_column("`price` > 0"), None);
+    }
 }
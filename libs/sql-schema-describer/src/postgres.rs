@@ -4,6 +4,7 @@ use log::debug;
 use once_cell::sync::Lazy;
 use quaint::prelude::Queryable;
 use regex::Regex;
+use sql_identifier::{quote_with_schema, Delimiter};
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::sync::Arc;
@@ -28,23 +29,61 @@ impl super::SqlSchemaDescriberBackend for SqlSchemaDescriber {
         })
     }
 
+    async fn list_routines(&self, schema: &str) -> SqlSchemaDescriberResult<Vec<Routine>> {
+        debug!("Getting routines");
+        let sql = "SELECT r.specific_name AS specific_name, r.routine_name AS routine_name,
+                r.routine_type AS routine_type, r.data_type AS return_type, p.parameter_name AS parameter_name,
+                p.parameter_mode AS parameter_mode, p.data_type AS data_type
+            FROM information_schema.routines r
+            LEFT JOIN information_schema.parameters p
+                ON p.specific_schema = r.specific_schema AND p.specific_name = r.specific_name
+            WHERE r.routine_schema = $1
+            ORDER BY r.routine_name, r.specific_name, p.ordinal_position";
+        let rows = self
+            .conn
+            .query_raw(sql, &[schema.into()])
+            .await
+            .expect("querying for routines");
+
+        let routines = collect_routines(rows);
+        debug!("Found routines: {:?}", routines);
+
+        Ok(routines)
+    }
+
     async fn describe(&self, schema: &str) -> SqlSchemaDescriberResult<SqlSchema> {
         debug!("describing schema '{}'", schema);
         let sequences = self.get_sequences(schema).await?;
         let enums = self.get_enums(schema).await?;
         let mut columns = self.get_columns(schema, &enums).await;
+        let mut partitionings = self.get_partitionings(schema).await;
+        let mut table_options = self.get_table_options(schema).await;
+        let views = self.get_views(schema).await;
+        let triggers = self.get_triggers(schema).await;
 
         let table_names = self.get_table_names(schema).await;
         let mut tables = Vec::with_capacity(table_names.len());
 
         for table_name in &table_names {
-            tables.push(self.get_table(schema, &table_name, &sequences, &mut columns).await);
+            tables.push(
+                self.get_table(
+                    schema,
+                    &table_name,
+                    &sequences,
+                    &mut columns,
+                    &mut partitionings,
+                    &mut table_options,
+                )
+                .await,
+            );
         }
 
         Ok(SqlSchema {
             enums,
             sequences,
             tables,
+            views,
+            triggers,
         })
     }
 }
@@ -78,6 +117,15 @@ impl SqlSchemaDescriber {
             WHERE table_schema = $1
             -- Views are not supported yet
             AND table_type = 'BASE TABLE'
+            -- Partitions are described as part of their partitioned parent table
+            AND table_name NOT IN (
+                SELECT child.relname
+                FROM pg_inherits inh
+                JOIN pg_class parent ON parent.oid = inh.inhparent
+                JOIN pg_class child ON child.oid = inh.inhrelid
+                JOIN pg_namespace ns ON ns.oid = child.relnamespace
+                WHERE parent.relkind = 'p' AND ns.nspname = $1
+            )
             ORDER BY table_name";
         let rows = self
             .conn
@@ -119,36 +167,147 @@ impl SqlSchemaDescriber {
         name: &str,
         sequences: &Vec<Sequence>,
         columns: &mut HashMap<String, Vec<Column>>,
+        partitionings: &mut HashMap<String, Partitioning>,
+        table_options: &mut HashMap<String, TableOptions>,
     ) -> Table {
         debug!("Getting table '{}'", name);
         let (indices, primary_key) = self.get_indices(schema, name, sequences).await;
         let foreign_keys = self.get_foreign_keys(schema, name).await;
         let columns = columns.remove(name).expect("could not get columns");
+        let partitioning = partitionings.remove(name);
+        let options = table_options.remove(name).unwrap_or_default();
         Table {
             name: name.to_string(),
             columns,
             foreign_keys,
             indices,
             primary_key,
+            partitioning,
+            options,
         }
     }
 
+    /// Returns the tablespace and fillfactor of each table in the schema that sets them explicitly.
+    async fn get_table_options(&self, schema: &str) -> HashMap<String, TableOptions> {
+        debug!("Getting table options");
+        // Tables in the database's default tablespace have a `reltablespace` of 0.
+        let sql = "SELECT
+                cls.relname as table_name,
+                spc.spcname as tablespace,
+                (
+                    SELECT split_part(opt, '=', 2)::int4
+                    FROM unnest(cls.reloptions) opt
+                    WHERE opt LIKE 'fillfactor=%'
+                ) as fillfactor
+            FROM pg_class cls
+            JOIN pg_namespace ns ON ns.oid = cls.relnamespace
+            LEFT JOIN pg_tablespace spc ON spc.oid = cls.reltablespace
+            WHERE ns.nspname = $1
+            AND cls.relkind IN ('r', 'p')";
+
+        let rows = self
+            .conn
+            .query_raw(sql, &[schema.into()])
+            .await
+            .expect("querying for table options");
+
+        let mut table_options = HashMap::new();
+
+        for row in rows {
+            debug!("Got table options row: {:?}", row);
+            let table_name = row
+                .get("table_name")
+                .and_then(|x| x.to_string())
+                .expect("get table_name");
+
+            let options = TableOptions {
+                engine: None,
+                charset: None,
+                tablespace: row.get("tablespace").and_then(|x| x.to_string()),
+                fillfactor: row.get("fillfactor").and_then(|x| x.as_i64()).map(|x| x as u32),
+            };
+
+            table_options.insert(table_name, options);
+        }
+
+        table_options
+    }
+
+    /// Returns the partition key and the attached partitions of each partitioned table in the schema.
+    async fn get_partitionings(&self, schema: &str) -> HashMap<String, Partitioning> {
+        debug!("Getting partitioned tables");
+        let sql = "SELECT
+                parent.relname as table_name,
+                pg_get_partkeydef(parent.oid) as partition_key,
+                child.relname as partition_name
+            FROM pg_class parent
+            JOIN pg_namespace ns ON ns.oid = parent.relnamespace
+            LEFT JOIN pg_inherits inh ON inh.inhparent = parent.oid
+            LEFT JOIN pg_class child ON child.oid = inh.inhrelid
+            WHERE ns.nspname = $1
+            AND parent.relkind = 'p'
+            ORDER BY table_name, partition_name";
+
+        let rows = match self.conn.query_raw(sql, &[schema.into()]).await {
+            Ok(rows) => rows,
+            // Declarative partitioning (and `pg_get_partkeydef`) only exists since Postgres 10.
+            Err(err) => {
+                debug!("Could not query partitioned tables: {}", err);
+                return HashMap::new();
+            }
+        };
+
+        let mut partitionings: HashMap<String, Partitioning> = HashMap::new();
+
+        for row in rows {
+            debug!("Got partition row: {:?}", row);
+            let table_name = row
+                .get("table_name")
+                .and_then(|x| x.to_string())
+                .expect("get table_name");
+            let key = row
+                .get("partition_key")
+                .and_then(|x| x.to_string())
+                .expect("get partition_key");
+            let partition_name = row.get("partition_name").and_then(|x| x.to_string());
+
+            let partitioning = partitionings.entry(table_name).or_insert_with(|| Partitioning {
+                key,
+                partitions: Vec::new(),
+            });
+
+            if let Some(partition_name) = partition_name {
+                partitioning.partitions.push(partition_name);
+            }
+        }
+
+        debug!("Found partitioned tables: {:?}", partitionings);
+        partitionings
+    }
+
     async fn get_columns(&self, schema: &str, enums: &Vec<Enum>) -> HashMap<String, Vec<Column>> {
         let mut columns: HashMap<String, Vec<Column>> = HashMap::new();
 
+        // `formatted_type` includes the type modifiers, e.g. `geometry(Point,4326)`, which `udt_name` lacks.
         let sql = r#"
             SELECT
-                table_name,
-                column_name,
-                data_type,
-                udt_name as full_data_type,
-                column_default,
-                is_nullable,
-                is_identity,
-                data_type
-            FROM information_schema.columns
-            WHERE table_schema = $1
-            ORDER BY column_name
+                info.table_name,
+                info.column_name,
+                info.data_type,
+                info.udt_name as full_data_type,
+                format_type(att.atttypid, att.atttypmod) as formatted_type,
+                info.column_default,
+                info.is_nullable,
+                info.is_identity,
+                info.is_generated,
+                info.generation_expression,
+                info.data_type
+            FROM information_schema.columns info
+            LEFT JOIN pg_attribute att
+                ON att.attrelid = (quote_ident(info.table_schema) || '.' || quote_ident(info.table_name))::regclass
+                AND att.attname = info.column_name
+            WHERE info.table_schema = $1
+            ORDER BY info.column_name
             COLLATE "default"
         "#;
 
@@ -201,12 +360,25 @@ impl SqlSchemaDescriber {
             } else {
                 ColumnArity::Nullable
             };
-            let tpe = get_column_type(data_type.as_ref(), &full_data_type, arity, enums);
+            let mut tpe = get_column_type(data_type.as_ref(), &full_data_type, arity, enums);
+
+            // Types without a family are preserved as they are, so they need their modifiers.
+            if tpe.family == ColumnTypeFamily::Unknown {
+                if let Some(formatted_type) = col.get("formatted_type").and_then(|x| x.to_string()) {
+                    tpe.raw = formatted_type.trim_end_matches("[]").to_owned();
+                }
+            }
 
             let default = col.get("column_default").and_then(|param_value| {
                 param_value
                     .to_string()
                     .map(|x| x.replace("\'", "").replace("::text", ""))
+                    .map(|x| match tpe.family {
+                        // Enum and citext defaults are cast to their type, e.g. `'ADMIN'::"Role"`.
+                        ColumnTypeFamily::Enum(_) => x.split("::").next().unwrap_or_default().to_owned(),
+                        _ if tpe.is_citext() => x.trim_end_matches("::citext").to_owned(),
+                        _ => x,
+                    })
             });
             let is_auto_increment = is_identity
                 || match default {
@@ -214,11 +386,25 @@ impl SqlSchemaDescriber {
                     _ => false,
                 };
 
+            // Postgres only has stored generated columns.
+            let generated = match col.get("is_generated").and_then(|x| x.to_string()) {
+                Some(ref is_generated) if is_generated.eq_ignore_ascii_case("always") => col
+                    .get("generation_expression")
+                    .and_then(|x| x.to_string())
+                    .map(|expression| GeneratedColumn {
+                        expression,
+                        kind: GeneratedColumnKind::Stored,
+                    }),
+                _ => None,
+            };
+
             let col = Column {
                 name: col_name,
                 tpe,
                 default,
                 auto_increment: is_auto_increment,
+                generated,
+                on_update_current_timestamp: false,
             };
 
             columns.entry(table_name).or_default().push(col);
@@ -238,6 +424,7 @@ impl SqlSchemaDescriber {
                 cl.relname as "parent_table",
                 att.attname as "parent_column",
                 con.confdeltype,
+                con.condeferrable AND con.condeferred as deferred,
                 conname as constraint_name,
                 child,
                 parent
@@ -250,7 +437,9 @@ impl SqlSchemaDescriber {
                     con1.confrelid,
                     con1.conrelid,
                     con1.conname,
-                    con1.confdeltype
+                    con1.confdeltype,
+                    con1.condeferrable,
+                    con1.condeferred
                 FROM
                     pg_class cl
                     join pg_namespace ns on cl.relnamespace = ns.oid
@@ -302,6 +491,7 @@ impl SqlSchemaDescriber {
                 .get("constraint_name")
                 .and_then(|x| x.to_string())
                 .expect("get constraint_name");
+            let deferrable = row.get("deferred").and_then(|x| x.as_bool()).expect("get deferred");
             let on_delete_action = match confdeltype {
                 'a' => ForeignKeyAction::NoAction,
                 'r' => ForeignKeyAction::Restrict,
@@ -322,6 +512,7 @@ impl SqlSchemaDescriber {
                         referenced_table,
                         referenced_columns: vec![referenced_column],
                         on_delete_action,
+                        deferrable,
                     };
                     intermediate_fks.insert(id, fk);
                 }
@@ -353,7 +544,10 @@ impl SqlSchemaDescriber {
         let sql = r#"
         SELECT
             indexInfos.relname as name,
-            array_agg(columnInfos.attname) as column_names,
+            -- the columns in the order of the index, not in the order the join finds them
+            array_agg(columnInfos.attname ORDER BY rawIndex.indkeyidx) as column_names,
+            -- bit 0 of the indoption of a column is set for DESC columns
+            array_agg(CASE WHEN (rawIndex.indoption::int & 1) = 1 THEN 'DESC' ELSE 'ASC' END ORDER BY rawIndex.indkeyidx) as column_orders,
             rawIndex.indisunique as is_unique, rawIndex.indisprimary as is_primary_key
         FROM
             -- pg_class stores infos about tables, indices etc: https://www.postgresql.org/docs/current/catalog-pg-class.html
@@ -367,6 +561,7 @@ impl SqlSchemaDescriber {
                     indisunique,
                     indisprimary,
                     unnest(array_agg(pg_index.indkey)) AS indkey,
+                    unnest(array_agg(pg_index.indoption)) AS indoption,
                     generate_subscripts(array_agg(pg_index.indkey), 1) AS indkeyidx
                 FROM pg_index
                 GROUP BY indrelid, indexrelid, indisunique, indisprimary
@@ -384,8 +579,8 @@ impl SqlSchemaDescriber {
             -- find table columns
             AND columnInfos.attrelid = tableInfos.oid
             AND columnInfos.attnum = rawIndex.indkey
-            -- we only consider ordinary tables
-            AND tableInfos.relkind = 'r'
+            -- we only consider ordinary and partitioned tables
+            AND tableInfos.relkind IN ('r', 'p')
             -- we only consider stuff out of one specific schema
             AND tableInfos.relnamespace = schemaInfo.oid
             AND schemaInfo.nspname = $1
@@ -417,6 +612,24 @@ impl SqlSchemaDescriber {
                 pk = Some(self.infer_primary_key(schema, table_name, columns, sequences).await);
             } else {
                 let is_unique = index.get("is_unique").and_then(|x| x.as_bool()).expect("is_unique");
+                let column_orders = index
+                    .get("column_orders")
+                    .and_then(|x| x.clone().into_vec::<String>())
+                    .expect("column_orders");
+                let column_options = columns
+                    .iter()
+                    .zip(column_orders)
+                    .filter(|(_, order)| order == "DESC")
+                    .map(|(column, _)| {
+                        let options = IndexColumnOptions {
+                            sort_order: SortOrder::Desc,
+                            length: None,
+                        };
+
+                        (column.clone(), options)
+                    })
+                    .collect();
+
                 indices.push(Index {
                     name: index.get("name").and_then(|x| x.to_string()).expect("name"),
                     columns,
@@ -424,6 +637,7 @@ impl SqlSchemaDescriber {
                         true => IndexType::Unique,
                         false => IndexType::Normal,
                     },
+                    column_options,
                 })
             }
         }
@@ -441,8 +655,9 @@ impl SqlSchemaDescriber {
     ) -> PrimaryKey {
         let sequence = if columns.len() == 1 {
             let sql = format!(
-                "SELECT pg_get_serial_sequence('\"{}\".\"{}\"', '{}') as sequence",
-                schema, table_name, columns[0]
+                "SELECT pg_get_serial_sequence('{}', '{}') as sequence",
+                quote_with_schema(Delimiter::DoubleQuote, schema, table_name).replace('\'', "''"),
+                columns[0].replace('\'', "''")
             );
             debug!(
                 "Querying for sequence seeding primary key column '{}': '{}'",
@@ -510,6 +725,133 @@ impl SqlSchemaDescriber {
         Ok(sequences)
     }
 
+    /// Returns the views of the schema, with the table and view columns their rewrite rules depend on.
+    async fn get_views(&self, schema: &str) -> Vec<View> {
+        debug!("Getting views");
+        let sql = "SELECT
+                view.relname as view_name,
+                pg_get_viewdef(view.oid) as definition,
+                source.relname as table_name,
+                att.attname as column_name
+            FROM pg_class view
+            JOIN pg_namespace ns ON ns.oid = view.relnamespace
+            LEFT JOIN pg_rewrite rw ON rw.ev_class = view.oid
+            LEFT JOIN pg_depend dep ON dep.objid = rw.oid AND dep.refobjid <> view.oid AND dep.refobjsubid > 0
+            LEFT JOIN pg_class source ON source.oid = dep.refobjid
+            LEFT JOIN pg_attribute att ON att.attrelid = dep.refobjid AND att.attnum = dep.refobjsubid
+            WHERE ns.nspname = $1
+            AND view.relkind = 'v'
+            ORDER BY view.relname, source.relname, att.attname";
+
+        let rows = self
+            .conn
+            .query_raw(sql, &[schema.into()])
+            .await
+            .expect("querying for views");
+
+        let mut views: Vec<View> = Vec::new();
+
+        for row in rows {
+            debug!("Got view row: {:?}", row);
+            let view_name = row.get("view_name").and_then(|x| x.to_string()).expect("get view_name");
+
+            if views.last().map(|view| view.name != view_name).unwrap_or(true) {
+                views.push(View {
+                    name: view_name,
+                    definition: row
+                        .get("definition")
+                        .and_then(|x| x.to_string())
+                        .expect("get definition"),
+                    dependencies: Vec::new(),
+                });
+            }
+
+            let view = views.last_mut().unwrap();
+            let table = row.get("table_name").and_then(|x| x.to_string());
+            let column = row.get("column_name").and_then(|x| x.to_string());
+
+            if let (Some(table), Some(column)) = (table, column) {
+                let dependency = ColumnDependency { table, column };
+
+                if !view.dependencies.contains(&dependency) {
+                    view.dependencies.push(dependency);
+                }
+            }
+        }
+
+        debug!("Found views: {:?}", views);
+        views
+    }
+
+    /// Returns the user defined triggers of the schema, with the columns their definitions depend on.
+    async fn get_triggers(&self, schema: &str) -> Vec<Trigger> {
+        debug!("Getting triggers");
+        let sql = "SELECT
+                trg.tgname as trigger_name,
+                tbl.relname as table_name,
+                pg_get_triggerdef(trg.oid) as definition,
+                att.attname as column_name
+            FROM pg_trigger trg
+            JOIN pg_class tbl ON tbl.oid = trg.tgrelid
+            JOIN pg_namespace ns ON ns.oid = tbl.relnamespace
+            LEFT JOIN pg_depend dep ON dep.objid = trg.oid
+                AND dep.classid = 'pg_trigger'::regclass
+                AND dep.refobjid = trg.tgrelid
+                AND dep.refobjsubid > 0
+            LEFT JOIN pg_attribute att ON att.attrelid = dep.refobjid AND att.attnum = dep.refobjsubid
+            WHERE ns.nspname = $1
+            AND NOT trg.tgisinternal
+            ORDER BY tbl.relname, trg.tgname, att.attname";
+
+        let rows = self
+            .conn
+            .query_raw(sql, &[schema.into()])
+            .await
+            .expect("querying for triggers");
+
+        let mut triggers: Vec<Trigger> = Vec::new();
+
+        for row in rows {
+            debug!("Got trigger row: {:?}", row);
+            let trigger_name = row
+                .get("trigger_name")
+                .and_then(|x| x.to_string())
+                .expect("get trigger_name");
+            let table_name = row
+                .get("table_name")
+                .and_then(|x| x.to_string())
+                .expect("get table_name");
+
+            let is_new_trigger = triggers
+                .last()
+                .map(|trigger| trigger.name != trigger_name || trigger.table != table_name)
+                .unwrap_or(true);
+
+            if is_new_trigger {
+                triggers.push(Trigger {
+                    name: trigger_name,
+                    table: table_name,
+                    definition: row
+                        .get("definition")
+                        .and_then(|x| x.to_string())
+                        .expect("get definition"),
+                    columns: Vec::new(),
+                });
+            }
+
+            let trigger = triggers.last_mut().unwrap();
+
+            if let Some(column) = row.get("column_name").and_then(|x| x.to_string()) {
+                if !trigger.columns.contains(&column) {
+                    trigger.columns.push(column);
+                }
+            }
+        }
+
+        debug!("Found triggers: {:?}", triggers);
+        triggers
+    }
+
     async fn get_enums(&self, schema: &str) -> SqlSchemaDescriberResult<Vec<Enum>> {
         debug!("Getting enums");
         let sql = "SELECT t.typname as name, e.enumlabel as value
@@ -557,6 +899,7 @@ fn get_column_type<'a>(data_type: &str, full_data_type: &'a str, arity: ColumnAr
         "bool" | "_bool" => Boolean,
         "text" | "_text" => String,
         "varchar" | "_varchar" => String,
+        "citext" | "_citext" => String,
         "date" | "_date" => DateTime,
         "bytea" | "_bytea" => Binary,
         "json" | "_json" => Json,
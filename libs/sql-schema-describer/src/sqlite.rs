@@ -3,7 +3,8 @@ use super::*;
 use failure::_core::convert::TryInto;
 use log::debug;
 use quaint::{ast::ParameterizedValue, prelude::Queryable};
-use std::collections::HashMap;
+use sql_identifier::Quoted;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 pub struct SqlSchemaDescriber {
@@ -26,6 +27,11 @@ impl super::SqlSchemaDescriberBackend for SqlSchemaDescriber {
         })
     }
 
+    /// SQLite has no stored procedures, functions are registered by the application.
+    async fn list_routines(&self, _schema: &str) -> SqlSchemaDescriberResult<Vec<Routine>> {
+        Ok(Vec::new())
+    }
+
     async fn describe(&self, schema: &str) -> SqlSchemaDescriberResult<SqlSchema> {
         debug!("describing schema '{}'", schema);
         let table_names: Vec<String> = self.get_table_names(schema).await;
@@ -42,6 +48,9 @@ impl super::SqlSchemaDescriberBackend for SqlSchemaDescriber {
             // There are no sequences in SQLite.
             sequences: vec![],
             tables: tables,
+            // Views and triggers are only described on Postgres.
+            views: vec![],
+            triggers: vec![],
         })
     }
 }
@@ -71,7 +80,10 @@ impl SqlSchemaDescriber {
     }
 
     async fn get_table_names(&self, schema: &str) -> Vec<String> {
-        let sql = format!(r#"SELECT name FROM "{}".sqlite_master WHERE type='table'"#, schema);
+        let sql = format!(
+            "SELECT name FROM {}.sqlite_master WHERE type='table'",
+            Quoted::sqlite(schema)
+        );
         debug!("describing table names with query: '{}'", sql);
         let result_set = self.conn.query_raw(&sql, &[]).await.expect("get table names");
         let names = result_set
@@ -106,11 +118,17 @@ impl SqlSchemaDescriber {
             indices,
             primary_key,
             foreign_keys,
+            partitioning: None,
+            options: TableOptions::default(),
         }
     }
 
     async fn get_columns(&self, schema: &str, table: &str) -> (Vec<Column>, Option<PrimaryKey>) {
-        let sql = format!(r#"PRAGMA "{}".table_info ("{}")"#, schema, table);
+        let sql = format!(
+            "PRAGMA {}.table_info ({})",
+            Quoted::sqlite(schema),
+            Quoted::sqlite(table)
+        );
         debug!("describing table columns, query: '{}'", sql);
         let result_set = self.conn.query_raw(&sql, &[]).await.unwrap();
         let mut pk_cols: HashMap<i64, String> = HashMap::new();
@@ -138,6 +156,9 @@ impl SqlSchemaDescriber {
                     tpe,
                     default: default_value.clone(),
                     auto_increment: false,
+                    // `table_info` leaves out generated columns, the bundled SQLite predates them anyway.
+                    generated: None,
+                    on_update_current_timestamp: false,
                 };
                 if pk_col > 0 {
                     pk_cols.insert(pk_col, col.name.clone());
@@ -202,7 +223,11 @@ impl SqlSchemaDescriber {
             pub on_delete_action: ForeignKeyAction,
         }
 
-        let sql = format!(r#"PRAGMA "{}".foreign_key_list("{}");"#, schema, table);
+        let sql = format!(
+            "PRAGMA {}.foreign_key_list({});",
+            Quoted::sqlite(schema),
+            Quoted::sqlite(table)
+        );
         debug!("describing table foreign keys, SQL: '{}'", sql);
         let result_set = self.conn.query_raw(&sql, &[]).await.expect("querying for foreign keys");
 
@@ -277,6 +302,7 @@ impl SqlSchemaDescriber {
                     referenced_table: intermediate_fk.referenced_table.to_owned(),
                     referenced_columns,
                     on_delete_action: intermediate_fk.on_delete_action.to_owned(),
+                    deferrable: false,
 
                     // Not relevant in SQLite since we cannot ALTER or DROP foreign keys by
                     // constraint name.
@@ -293,7 +319,11 @@ impl SqlSchemaDescriber {
     }
 
     async fn get_indices(&self, schema: &str, table: &str) -> Vec<Index> {
-        let sql = format!(r#"PRAGMA "{}".index_list("{}");"#, schema, table);
+        let sql = format!(
+            "PRAGMA {}.index_list({});",
+            Quoted::sqlite(schema),
+            Quoted::sqlite(table)
+        );
         debug!("describing table indices, SQL: '{}'", sql);
         let result_set = self.conn.query_raw(&sql, &[]).await.expect("querying for indices");
         debug!("Got indices description results: {:?}", result_set);
@@ -314,15 +344,33 @@ impl SqlSchemaDescriber {
                     false => IndexType::Normal,
                 },
                 columns: vec![],
+                column_options: BTreeMap::new(),
             };
 
-            let sql = format!(r#"PRAGMA "{}".index_info("{}");"#, schema, name);
+            // Unlike index_info, index_xinfo also returns the sort order of the columns. It lists the auxiliary
+            // rowid column too, which is not a key column.
+            let sql = format!(
+                "PRAGMA {}.index_xinfo({});",
+                Quoted::sqlite(schema),
+                Quoted::sqlite(&name)
+            );
             debug!("describing table index '{}', SQL: '{}'", name, sql);
             let result_set = self.conn.query_raw(&sql, &[]).await.expect("querying for index info");
             debug!("Got index description results: {:?}", result_set);
-            for row in result_set.into_iter() {
+            for row in result_set
+                .into_iter()
+                .filter(|row| row.get("key").and_then(|x| x.as_bool()) == Some(true))
+            {
                 let pos = row.get("seqno").and_then(|x| x.as_i64()).expect("get seqno") as usize;
                 let col_name = row.get("name").and_then(|x| x.to_string()).expect("get name");
+                let is_desc = row.get("desc").and_then(|x| x.as_bool()).expect("get desc");
+                if is_desc {
+                    let options = IndexColumnOptions {
+                        sort_order: SortOrder::Desc,
+                        length: None,
+                    };
+                    index.column_options.insert(col_name.clone(), options);
+                }
                 if index.columns.len() <= pos {
                     index.columns.resize(pos + 1, "".to_string());
                 }
@@ -349,6 +397,7 @@ fn get_column_type(tpe: &str, arity: ColumnArity) -> ColumnType {
         "text" => ColumnTypeFamily::String,
         s if s.contains("char") => ColumnTypeFamily::String,
         s if s.contains("numeric") => ColumnTypeFamily::Float,
+        s if s.contains("decimal") => ColumnTypeFamily::Float,
         "date" => ColumnTypeFamily::DateTime,
         "datetime" => ColumnTypeFamily::DateTime,
         "binary" => ColumnTypeFamily::Binary,
@@ -2,6 +2,7 @@ use barrel::types;
 use pretty_assertions::assert_eq;
 use quaint::prelude::SqlFamily;
 use sql_schema_describer::*;
+use std::collections::BTreeMap;
 use test_macros::test_each_connector;
 
 mod common;
@@ -52,6 +53,8 @@ async fn is_required_must_work(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "column2".to_string(),
@@ -62,6 +65,8 @@ async fn is_required_must_work(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
 
@@ -101,6 +106,8 @@ async fn foreign_keys_must_work(api: &TestApi) {
         },
         default: None,
         auto_increment: false,
+        generated: None,
+        on_update_current_timestamp: false,
     }];
 
     let on_delete_action = match api.sql_family() {
@@ -112,6 +119,7 @@ async fn foreign_keys_must_work(api: &TestApi) {
             name: "city".to_owned(),
             columns: vec!["city".to_owned()],
             tpe: IndexType::Normal,
+            column_options: BTreeMap::new(),
         }]
     } else {
         vec![]
@@ -134,7 +142,10 @@ async fn foreign_keys_must_work(api: &TestApi) {
                 referenced_columns: vec!["id".to_string()],
                 referenced_table: "City".to_string(),
                 on_delete_action,
+                deferrable: false,
             }],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -183,6 +194,8 @@ async fn multi_column_foreign_keys_must_work(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "city_name".to_string(),
@@ -193,6 +206,8 @@ async fn multi_column_foreign_keys_must_work(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
 
@@ -201,6 +216,7 @@ async fn multi_column_foreign_keys_must_work(api: &TestApi) {
             name: "city_name".to_owned(),
             columns: vec!["city_name".to_owned(), "city".to_owned()],
             tpe: IndexType::Normal,
+            column_options: BTreeMap::new(),
         }]
     } else {
         vec![]
@@ -229,7 +245,10 @@ async fn multi_column_foreign_keys_must_work(api: &TestApi) {
                 referenced_columns: vec!["name".to_string(), "id".to_string(),],
                 referenced_table: "City".to_string(),
                 on_delete_action,
+                deferrable: false,
             },],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -254,6 +273,8 @@ async fn names_with_hyphens_must_work(api: &TestApi) {
         },
         default: None,
         auto_increment: false,
+        generated: None,
+        on_update_current_timestamp: false,
     }];
     assert_eq!(user_table.columns, expected_columns);
 }
@@ -298,6 +319,8 @@ async fn composite_primary_keys_must_work(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "name".to_string(),
@@ -308,6 +331,8 @@ async fn composite_primary_keys_must_work(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
     expected_columns.sort_unstable_by_key(|c| c.name.to_owned());
@@ -323,6 +348,8 @@ async fn composite_primary_keys_must_work(api: &TestApi) {
                 sequence: None,
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -354,6 +381,8 @@ async fn indices_must_work(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "id".to_string(),
@@ -365,6 +394,8 @@ async fn indices_must_work(api: &TestApi) {
 
             default,
             auto_increment: true,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
     let pk_sequence = match api.sql_family() {
@@ -384,12 +415,15 @@ async fn indices_must_work(api: &TestApi) {
                 name: "count".to_string(),
                 columns: vec!["count".to_string()],
                 tpe: IndexType::Normal,
+                column_options: BTreeMap::new(),
             },],
             primary_key: Some(PrimaryKey {
                 columns: vec!["id".to_string()],
                 sequence: pk_sequence,
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -418,6 +452,8 @@ async fn column_uniqueness_must_be_detected(api: &TestApi) {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "uniq2".to_string(),
@@ -429,18 +465,22 @@ async fn column_uniqueness_must_be_detected(api: &TestApi) {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
     let mut expected_indices = vec![Index {
         name: "uniq".to_string(),
         columns: vec!["uniq2".to_string()],
         tpe: IndexType::Unique,
+        column_options: BTreeMap::new(),
     }];
     match api.sql_family() {
         SqlFamily::Mysql => expected_indices.push(Index {
             name: "uniq1".to_string(),
             columns: vec!["uniq1".to_string()],
             tpe: IndexType::Unique,
+            column_options: BTreeMap::new(),
         }),
         SqlFamily::Postgres => expected_indices.insert(
             0,
@@ -448,12 +488,14 @@ async fn column_uniqueness_must_be_detected(api: &TestApi) {
                 name: "User_uniq1_key".to_string(),
                 columns: vec!["uniq1".to_string()],
                 tpe: IndexType::Unique,
+                column_options: BTreeMap::new(),
             },
         ),
         SqlFamily::Sqlite => expected_indices.push(Index {
             name: "sqlite_autoindex_User_1".to_string(),
             columns: vec!["uniq1".to_string()],
             tpe: IndexType::Unique,
+            column_options: BTreeMap::new(),
         }),
     };
     assert_eq!(
@@ -464,6 +506,8 @@ async fn column_uniqueness_must_be_detected(api: &TestApi) {
             indices: expected_indices,
             primary_key: None,
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
     assert!(
@@ -499,6 +543,8 @@ async fn defaults_must_work(api: &TestApi) {
 
         default: Some(default),
         auto_increment: false,
+        generated: None,
+        on_update_current_timestamp: false,
     }];
     assert_eq!(
         user_table,
@@ -508,6 +554,8 @@ async fn defaults_must_work(api: &TestApi) {
             indices: vec![],
             primary_key: None,
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
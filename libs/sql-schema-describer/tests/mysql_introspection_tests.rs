@@ -1,6 +1,7 @@
 use barrel::{types, Migration};
 use pretty_assertions::assert_eq;
 use sql_schema_describer::*;
+use std::collections::BTreeMap;
 
 mod mysql;
 
@@ -70,6 +71,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: true,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "int_col".to_string(),
@@ -81,6 +84,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "smallint_col".to_string(),
@@ -92,6 +97,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "tinyint4_col".to_string(),
@@ -102,6 +109,8 @@ async fn all_mysql_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "tinyint1_col".to_string(),
@@ -113,6 +122,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "mediumint_col".to_string(),
@@ -124,6 +135,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "bigint_col".to_string(),
@@ -135,6 +148,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "decimal_col".to_string(),
@@ -146,6 +161,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "numeric_col".to_string(),
@@ -157,6 +174,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "float_col".to_string(),
@@ -168,6 +187,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "double_col".to_string(),
@@ -179,6 +200,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "date_col".to_string(),
@@ -190,6 +213,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "time_col".to_string(),
@@ -201,6 +226,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "datetime_col".to_string(),
@@ -212,6 +239,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "timestamp_col".to_string(),
@@ -223,6 +252,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: Some("CURRENT_TIMESTAMP".to_string()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "year_col".to_string(),
@@ -234,6 +265,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "char_col".to_string(),
@@ -245,6 +278,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "varchar_col".to_string(),
@@ -256,6 +291,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "text_col".to_string(),
@@ -267,6 +304,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "tinytext_col".to_string(),
@@ -278,6 +317,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "mediumtext_col".to_string(),
@@ -289,6 +330,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "longtext_col".to_string(),
@@ -300,6 +343,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "enum_col".to_string(),
@@ -311,6 +356,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "set_col".to_string(),
@@ -322,6 +369,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "binary_col".to_string(),
@@ -333,6 +382,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "varbinary_col".to_string(),
@@ -344,6 +395,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "blob_col".to_string(),
@@ -355,6 +408,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "tinyblob_col".to_string(),
@@ -366,6 +421,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "mediumblob_col".to_string(),
@@ -377,6 +434,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "longblob_col".to_string(),
@@ -388,6 +447,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "geometry_col".to_string(),
@@ -399,6 +460,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "point_col".to_string(),
@@ -410,6 +473,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "linestring_col".to_string(),
@@ -421,6 +486,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "polygon_col".to_string(),
@@ -432,6 +499,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "multipoint_col".to_string(),
@@ -443,6 +512,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "multilinestring_col".to_string(),
@@ -454,6 +525,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "multipolygon_col".to_string(),
@@ -465,6 +538,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "geometrycollection_col".to_string(),
@@ -476,6 +551,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "json_col".to_string(),
@@ -487,6 +564,8 @@ async fn all_mysql_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
     expected_columns.sort_unstable_by_key(|c| c.name.to_owned());
@@ -502,6 +581,8 @@ async fn all_mysql_column_types_must_work() {
                 sequence: None,
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -543,6 +624,8 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_cascade".to_string(),
@@ -553,6 +636,8 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_restrict".to_string(),
@@ -563,6 +648,8 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_set_null".to_string(),
@@ -573,6 +660,8 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "id".to_string(),
@@ -584,28 +673,34 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
 
                     default: None,
                     auto_increment: true,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
             ],
             indices: vec![
                 Index {
                     name: "city".to_owned(),
                     columns: vec!["city".to_owned(),],
-                    tpe: IndexType::Normal
+                    tpe: IndexType::Normal,
+                    column_options: BTreeMap::new(),
                 },
                 Index {
                     name: "city_cascade".to_owned(),
                     columns: vec!["city_cascade".to_owned(),],
-                    tpe: IndexType::Normal
+                    tpe: IndexType::Normal,
+                    column_options: BTreeMap::new(),
                 },
                 Index {
                     name: "city_restrict".to_owned(),
                     columns: vec!["city_restrict".to_owned(),],
-                    tpe: IndexType::Normal
+                    tpe: IndexType::Normal,
+                    column_options: BTreeMap::new(),
                 },
                 Index {
                     name: "city_set_null".to_owned(),
                     columns: vec!["city_set_null".to_owned(),],
-                    tpe: IndexType::Normal
+                    tpe: IndexType::Normal,
+                    column_options: BTreeMap::new(),
                 }
             ],
             primary_key: Some(PrimaryKey {
@@ -619,6 +714,7 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::NoAction,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: Some("User_ibfk_2".to_owned()),
@@ -626,6 +722,7 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::Cascade,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: Some("User_ibfk_3".to_owned()),
@@ -633,6 +730,7 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::Restrict,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: Some("User_ibfk_4".to_owned()),
@@ -640,8 +738,11 @@ async fn mysql_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::SetNull,
+                    deferrable: false,
                 },
             ],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -668,11 +769,68 @@ async fn mysql_multi_field_indexes_must_be_inferred() {
         &[Index {
             name: "age_and_name_index".into(),
             columns: vec!["name".to_owned(), "age".to_owned()],
-            tpe: IndexType::Unique
+            tpe: IndexType::Unique,
+            column_options: BTreeMap::new(),
         }]
     );
 }
 
+#[tokio::test]
+async fn mysql_generated_columns_must_be_inferred() {
+    let db_name = "mysql_generated_columns_must_be_inferred";
+
+    let full_sql = format!(
+        "CREATE TABLE `{0}`.`Item` (
+            id INT PRIMARY KEY,
+            price INT NOT NULL,
+            quantity INT NOT NULL,
+            total INT GENERATED ALWAYS AS (price * quantity) STORED,
+            label VARCHAR(20) GENERATED ALWAYS AS (concat('#', id)) VIRTUAL
+        )",
+        db_name
+    );
+    let inspector = get_mysql_describer_for_schema(&full_sql, db_name).await;
+    let result = inspector.describe(db_name).await.expect("describing");
+    let table = result.get_table("Item").expect("couldn't get Item table");
+
+    assert_eq!(table.column_bang("price").generated, None);
+    assert_eq!(
+        table.column_bang("total").generated,
+        Some(GeneratedColumn {
+            expression: "(`price` * `quantity`)".into(),
+            kind: GeneratedColumnKind::Stored,
+        })
+    );
+
+    let label = table
+        .column_bang("label")
+        .generated
+        .as_ref()
+        .expect("label is generated");
+    assert_eq!(label.kind, GeneratedColumnKind::Virtual);
+    assert!(label.expression.contains("'#'"));
+}
+
+#[tokio::test]
+async fn mysql_on_update_current_timestamp_must_be_inferred() {
+    let db_name = "mysql_on_update_current_timestamp_must_be_inferred";
+
+    let full_sql = format!(
+        "CREATE TABLE `{0}`.`Post` (
+            id INT PRIMARY KEY,
+            createdAt DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+            updatedAt DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3)
+        )",
+        db_name
+    );
+    let inspector = get_mysql_describer_for_schema(&full_sql, db_name).await;
+    let result = inspector.describe(db_name).await.expect("describing");
+    let table = result.get_table("Post").expect("couldn't get Post table");
+
+    assert!(!table.column_bang("createdAt").on_update_current_timestamp);
+    assert!(table.column_bang("updatedAt").on_update_current_timestamp);
+}
+
 #[tokio::test]
 async fn mysql_join_table_unique_indexes_must_be_inferred() {
     let db_name = "mysql_join_table_unique_indexes_must_be_inferred";
@@ -706,7 +864,8 @@ async fn mysql_join_table_unique_indexes_must_be_inferred() {
         &[Index {
             name: "cat_and_human_index".into(),
             columns: vec!["cat".to_owned(), "human".to_owned()],
-            tpe: IndexType::Unique
+            tpe: IndexType::Unique,
+            column_options: BTreeMap::new(),
         }]
     );
 }
@@ -746,6 +905,7 @@ async fn constraints_from_other_databases_should_not_be_introspected() {
             referenced_table: "User".into(),
             referenced_columns: vec!["id".into()],
             on_delete_action: ForeignKeyAction::Cascade,
+            deferrable: false,
         }]
     );
 
@@ -777,6 +937,7 @@ async fn constraints_from_other_databases_should_not_be_introspected() {
             referenced_table: "User".into(),
             referenced_columns: vec!["id".into()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 }
@@ -1,6 +1,7 @@
 use barrel::{types, Migration};
 use pretty_assertions::assert_eq;
 use sql_schema_describer::*;
+use std::collections::BTreeMap;
 
 mod common;
 mod postgres;
@@ -75,6 +76,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "array_bool_col".into(),
@@ -85,6 +88,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "array_date_col".into(),
@@ -95,6 +100,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "array_double_col".into(),
@@ -105,6 +112,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "array_float_col".into(),
@@ -115,6 +124,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "array_int_col".into(),
@@ -125,6 +136,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "array_text_col".into(),
@@ -135,6 +148,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "array_varchar_col".into(),
@@ -145,6 +160,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "binary_col".into(),
@@ -155,6 +172,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "boolean_col".into(),
@@ -165,6 +184,8 @@ async fn all_postgres_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "date_time_col".into(),
@@ -176,6 +197,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "double_col".into(),
@@ -187,6 +210,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "float_col".into(),
@@ -198,6 +223,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "int_col".into(),
@@ -209,6 +236,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "primary_col".into(),
@@ -220,6 +249,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: Some(format!("nextval(\"{}\".\"User_primary_col_seq\"::regclass)", SCHEMA)),
             auto_increment: true,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "string1_col".into(),
@@ -231,6 +262,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "string2_col".into(),
@@ -242,6 +275,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "bigint_col".into(),
@@ -253,6 +288,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "bigserial_col".into(),
@@ -264,6 +301,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: Some(format!("nextval(\"{}\".\"User_bigserial_col_seq\"::regclass)", SCHEMA)),
             auto_increment: true,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "bit_col".into(),
@@ -275,6 +314,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "bit_varying_col".into(),
@@ -286,6 +327,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "box_col".into(),
@@ -297,6 +340,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "char_col".into(),
@@ -308,6 +353,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "circle_col".into(),
@@ -319,6 +366,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "interval_col".into(),
@@ -330,6 +379,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "line_col".into(),
@@ -341,6 +392,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "lseg_col".into(),
@@ -352,6 +405,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "numeric_col".into(),
@@ -363,6 +418,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "path_col".into(),
@@ -374,6 +431,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "pg_lsn_col".into(),
@@ -385,6 +444,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "polygon_col".into(),
@@ -396,6 +457,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "smallint_col".into(),
@@ -407,6 +470,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "smallserial_col".into(),
@@ -421,6 +486,8 @@ async fn all_postgres_column_types_must_work() {
                 SCHEMA
             )),
             auto_increment: true,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "serial_col".into(),
@@ -432,6 +499,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: Some(format!("nextval(\"{}\".\"User_serial_col_seq\"::regclass)", SCHEMA)),
             auto_increment: true,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "time_col".into(),
@@ -443,6 +512,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "time_with_zone_col".into(),
@@ -454,6 +525,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "timestamp_col".into(),
@@ -465,6 +538,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "timestamp_with_zone_col".into(),
@@ -476,6 +551,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "tsquery_col".into(),
@@ -487,6 +564,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "tsvector_col".into(),
@@ -498,6 +577,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "txid_col".into(),
@@ -509,6 +590,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "json_col".into(),
@@ -520,6 +603,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "jsonb_col".into(),
@@ -531,6 +616,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "uuid_col".into(),
@@ -542,6 +629,8 @@ async fn all_postgres_column_types_must_work() {
 
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
     expected_columns.sort_unstable_by_key(|c| c.name.to_owned());
@@ -555,6 +644,7 @@ async fn all_postgres_column_types_must_work() {
                 name: "User_uuid_col_key".into(),
                 columns: vec!["uuid_col".into(),],
                 tpe: IndexType::Unique,
+                column_options: BTreeMap::new(),
             },],
             primary_key: Some(PrimaryKey {
                 columns: vec!["primary_col".into()],
@@ -565,6 +655,8 @@ async fn all_postgres_column_types_must_work() {
                 },),
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -604,6 +696,8 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_cascade".into(),
@@ -614,6 +708,8 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_restrict".into(),
@@ -624,6 +720,8 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_set_default".into(),
@@ -634,6 +732,8 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_set_null".into(),
@@ -644,6 +744,8 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "id".into(),
@@ -655,6 +757,8 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
 
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
             ],
             indices: vec![],
@@ -669,6 +773,7 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".into()],
                     referenced_table: "City".into(),
                     on_delete_action: ForeignKeyAction::NoAction,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: Some("User_city_cascade_fkey".to_owned()),
@@ -676,6 +781,7 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".into()],
                     referenced_table: "City".into(),
                     on_delete_action: ForeignKeyAction::Cascade,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: Some("User_city_restrict_fkey".to_owned()),
@@ -683,6 +789,7 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".into()],
                     referenced_table: "City".into(),
                     on_delete_action: ForeignKeyAction::Restrict,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: Some("User_city_set_default_fkey".to_owned()),
@@ -690,6 +797,7 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".into()],
                     referenced_table: "City".into(),
                     on_delete_action: ForeignKeyAction::SetDefault,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: Some("User_city_set_null_fkey".to_owned()),
@@ -697,12 +805,41 @@ async fn postgres_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".into()],
                     referenced_table: "City".into(),
                     on_delete_action: ForeignKeyAction::SetNull,
+                    deferrable: false,
                 },
             ],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
 
+#[tokio::test]
+async fn postgres_generated_columns_must_work() {
+    let sql = format!(
+        "CREATE TABLE \"{0}\".\"Item\" (
+            id INT PRIMARY KEY,
+            price INT NOT NULL,
+            quantity INT NOT NULL,
+            total INT GENERATED ALWAYS AS (price * quantity) STORED
+        );",
+        SCHEMA
+    );
+    let inspector = get_postgres_describer(&sql, "postgres_generated_columns_must_work").await;
+
+    let schema = inspector.describe(SCHEMA).await.expect("describing");
+    let table = schema.get_table("Item").expect("get Item table");
+
+    assert_eq!(table.column_bang("price").generated, None);
+    assert_eq!(
+        table.column_bang("total").generated,
+        Some(GeneratedColumn {
+            expression: "(price * quantity)".into(),
+            kind: GeneratedColumnKind::Stored,
+        })
+    );
+}
+
 #[tokio::test]
 async fn postgres_enums_must_work() {
     let inspector = get_postgres_describer(
@@ -744,3 +881,76 @@ async fn postgres_sequences_must_work() {
         },
     );
 }
+
+#[tokio::test]
+async fn postgres_routines_must_work() {
+    let inspector = get_postgres_describer(
+        &format!(
+            "CREATE FUNCTION \"{}\".\"add\"(a integer, b integer) RETURNS integer AS 'SELECT a + b' LANGUAGE SQL",
+            SCHEMA
+        ),
+        "postgres_routines_must_work",
+    )
+    .await;
+
+    let routines = inspector.list_routines(SCHEMA).await.expect("listing routines");
+
+    assert_eq!(
+        routines,
+        vec![Routine {
+            name: "add".into(),
+            kind: RoutineKind::Function,
+            parameters: vec![
+                RoutineParameter {
+                    name: Some("a".into()),
+                    mode: ParameterMode::In,
+                    data_type: "integer".into(),
+                },
+                RoutineParameter {
+                    name: Some("b".into()),
+                    mode: ParameterMode::In,
+                    data_type: "integer".into(),
+                },
+            ],
+            return_type: Some("integer".into()),
+        }],
+    );
+}
+
+#[tokio::test]
+async fn postgres_views_and_triggers_must_work() {
+    let sql = format!(
+        r#"
+            CREATE TABLE "{0}"."User" (id SERIAL PRIMARY KEY, name TEXT NOT NULL, age INTEGER);
+            CREATE VIEW "{0}"."UserName" AS SELECT id, name FROM "{0}"."User";
+            CREATE TRIGGER "keep_name" BEFORE UPDATE OF name ON "{0}"."User" FOR EACH ROW EXECUTE PROCEDURE suppress_redundant_updates_trigger();
+        "#,
+        SCHEMA
+    );
+    let inspector = get_postgres_describer(&sql, "postgres_views_and_triggers_must_work").await;
+
+    let schema = inspector.describe(SCHEMA).await.expect("describing");
+
+    let view = schema.get_view("UserName").expect("get view");
+    assert!(view.definition.contains("SELECT"));
+    assert_eq!(
+        view.dependencies,
+        vec![
+            ColumnDependency {
+                table: "User".into(),
+                column: "id".into(),
+            },
+            ColumnDependency {
+                table: "User".into(),
+                column: "name".into(),
+            },
+        ]
+    );
+
+    assert_eq!(schema.triggers.len(), 1);
+    let trigger = &schema.triggers[0];
+    assert_eq!(trigger.name, "keep_name");
+    assert_eq!(trigger.table, "User");
+    assert!(trigger.definition.starts_with("CREATE TRIGGER keep_name"));
+    assert_eq!(trigger.columns, vec!["name".to_owned()]);
+}
@@ -6,7 +6,7 @@ use log::{debug, LevelFilter};
 use pretty_assertions::assert_eq;
 use quaint::connector::{Queryable, Sqlite as SqliteDatabaseClient};
 use sql_schema_describer::*;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs::File;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -32,6 +32,8 @@ fn database_schema_is_serializable() {
                         },
                         default: None,
                         auto_increment: true,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                     Column {
                         name: "column2".to_string(),
@@ -42,6 +44,8 @@ fn database_schema_is_serializable() {
                         },
                         default: Some("default value".to_string()),
                         auto_increment: false,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                     Column {
                         name: "column3".to_string(),
@@ -52,12 +56,15 @@ fn database_schema_is_serializable() {
                         },
                         default: None,
                         auto_increment: false,
+                        generated: None,
+                        on_update_current_timestamp: false,
                     },
                 ],
                 indices: vec![Index {
                     name: "column2".to_string(),
                     columns: vec!["column2".to_string()],
                     tpe: IndexType::Normal,
+                    column_options: BTreeMap::new(),
                 }],
                 primary_key: Some(PrimaryKey {
                     columns: vec!["column1".to_string()],
@@ -69,7 +76,10 @@ fn database_schema_is_serializable() {
                     referenced_table: "table2".to_string(),
                     referenced_columns: vec!["id".to_string()],
                     on_delete_action: ForeignKeyAction::NoAction,
+                    deferrable: false,
                 }],
+                partitioning: None,
+                options: TableOptions::default(),
             },
             Table {
                 name: "table2".to_string(),
@@ -82,6 +92,8 @@ fn database_schema_is_serializable() {
                     },
                     default: None,
                     auto_increment: true,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 }],
                 indices: vec![],
                 primary_key: Some(PrimaryKey {
@@ -89,6 +101,8 @@ fn database_schema_is_serializable() {
                     sequence: None,
                 }),
                 foreign_keys: vec![],
+                partitioning: None,
+                options: TableOptions::default(),
             },
         ],
         enums: vec![Enum {
@@ -100,6 +114,8 @@ fn database_schema_is_serializable() {
             initial_value: 1,
             allocation_size: 32,
         }],
+        views: vec![],
+        triggers: vec![],
     };
     let ref_schema_json = include_str!("./resources/schema.json");
     let ref_schema: SqlSchema = serde_json::from_str(ref_schema_json).expect("deserialize reference schema");
@@ -127,13 +143,19 @@ fn database_schema_without_primary_key_is_serializable() {
                 },
                 default: None,
                 auto_increment: false,
+                generated: None,
+                on_update_current_timestamp: false,
             }],
             indices: vec![],
             primary_key: None,
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let ref_schema_json = include_str!("./resources/schema-without-primary-key.json");
     let ref_schema: SqlSchema = serde_json::from_str(ref_schema_json).expect("deserialize reference schema");
@@ -175,6 +197,8 @@ fn database_schema_is_serializable_for_every_column_type_family() {
         },
         default: None,
         auto_increment: false,
+        generated: None,
+        on_update_current_timestamp: false,
     })
     .collect();
     let schema = SqlSchema {
@@ -184,9 +208,13 @@ fn database_schema_is_serializable_for_every_column_type_family() {
             indices: vec![],
             primary_key: None,
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let ref_schema_json = include_str!("./resources/schema-all-column-type-families.json");
     let ref_schema: SqlSchema = serde_json::from_str(ref_schema_json).expect("deserialize reference schema");
@@ -215,6 +243,8 @@ fn database_schema_is_serializable_for_every_column_arity() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         })
         .collect();
     let schema = SqlSchema {
@@ -224,9 +254,13 @@ fn database_schema_is_serializable_for_every_column_arity() {
             indices: vec![],
             primary_key: None,
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let ref_schema_json = include_str!("./resources/schema-all-column-arities.json");
     let ref_schema: SqlSchema = serde_json::from_str(ref_schema_json).expect("deserialize reference schema");
@@ -255,6 +289,8 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                         arity: ColumnArity::Nullable,
                     },
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                     default: None,
                 },
                 Column {
@@ -265,6 +301,8 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                         arity: ColumnArity::Nullable,
                     },
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                     default: None,
                 },
                 Column {
@@ -275,6 +313,8 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                         arity: ColumnArity::Nullable,
                     },
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                     default: None,
                 },
                 Column {
@@ -285,6 +325,8 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                         arity: ColumnArity::Nullable,
                     },
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                     default: None,
                 },
                 Column {
@@ -295,6 +337,8 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                         arity: ColumnArity::Nullable,
                     },
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                     default: None,
                 },
             ],
@@ -307,6 +351,7 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                     referenced_table: "table2".to_string(),
                     referenced_columns: vec!["id".to_string()],
                     on_delete_action: ForeignKeyAction::NoAction,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -314,6 +359,7 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                     referenced_table: "table2".to_string(),
                     referenced_columns: vec!["id".to_string()],
                     on_delete_action: ForeignKeyAction::Restrict,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -321,6 +367,7 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                     referenced_table: "table2".to_string(),
                     referenced_columns: vec!["id".to_string()],
                     on_delete_action: ForeignKeyAction::Cascade,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -328,6 +375,7 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                     referenced_table: "table2".to_string(),
                     referenced_columns: vec!["id".to_string()],
                     on_delete_action: ForeignKeyAction::SetNull,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -335,11 +383,16 @@ fn database_schema_is_serializable_for_every_foreign_key_action() {
                     referenced_table: "table2".to_string(),
                     referenced_columns: vec!["id".to_string()],
                     on_delete_action: ForeignKeyAction::SetDefault,
+                    deferrable: false,
                 },
             ],
+            partitioning: None,
+            options: TableOptions::default(),
         }],
         enums: vec![],
         sequences: vec![],
+        views: vec![],
+        triggers: vec![],
     };
     let ref_schema_json = include_str!("./resources/schema-all-foreign-key-actions.json");
     let ref_schema: SqlSchema = serde_json::from_str(ref_schema_json).expect("deserialize reference schema");
@@ -32,6 +32,8 @@ async fn sqlite_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "text_col".to_string(),
@@ -42,6 +44,8 @@ async fn sqlite_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "real_col".to_string(),
@@ -52,6 +56,8 @@ async fn sqlite_column_types_must_work() {
             },
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         },
         Column {
             name: "primary_col".to_string(),
@@ -62,6 +68,8 @@ async fn sqlite_column_types_must_work() {
             },
             default: None,
             auto_increment: true,
+            generated: None,
+            on_update_current_timestamp: false,
         },
     ];
     expected_columns.sort_unstable_by_key(|c| c.name.to_owned());
@@ -77,6 +85,8 @@ async fn sqlite_column_types_must_work() {
                 sequence: None,
             }),
             foreign_keys: vec![],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -115,6 +125,8 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_cascade".to_string(),
@@ -125,6 +137,8 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_restrict".to_string(),
@@ -135,6 +149,8 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_set_default".to_string(),
@@ -145,6 +161,8 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "city_set_null".to_string(),
@@ -155,6 +173,8 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: false,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
                 Column {
                     name: "id".to_string(),
@@ -165,6 +185,8 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     },
                     default: None,
                     auto_increment: true,
+                    generated: None,
+                    on_update_current_timestamp: false,
                 },
             ],
             indices: vec![],
@@ -179,6 +201,7 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::NoAction,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -186,6 +209,7 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::Cascade,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -193,6 +217,7 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::Restrict,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -200,6 +225,7 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::SetDefault,
+                    deferrable: false,
                 },
                 ForeignKey {
                     constraint_name: None,
@@ -207,8 +233,11 @@ async fn sqlite_foreign_key_on_delete_must_be_handled() {
                     referenced_columns: vec!["id".to_string()],
                     referenced_table: "City".to_string(),
                     on_delete_action: ForeignKeyAction::SetNull,
+                    deferrable: false,
                 },
             ],
+            partitioning: None,
+            options: TableOptions::default(),
         }
     );
 }
@@ -235,6 +235,18 @@ pub fn sqlite_test_config(db_name: &str) -> String {
     )
 }
 
+/// A data model on an in-memory SQLite database, for tests that need a working connector but no data.
+pub const IN_MEMORY_SQLITE_DATAMODEL: &str = r#"
+    datasource db {
+        provider = "sqlite"
+        url      = "file::memory:"
+    }
+
+    model User {
+        id String @id
+    }
+"#;
+
 /// The maximum length of identifiers on mysql is 64 bytes.
 ///
 /// Source: https://dev.mysql.com/doc/mysql-reslimits-excerpt/5.5/en/identifier-length.html
@@ -19,7 +19,7 @@ pub trait UserFacingError: serde::Serialize {
     fn message(&self) -> String;
 }
 
-#[derive(Serialize, PartialEq, Debug)]
+#[derive(Serialize, PartialEq, Debug, Clone)]
 pub struct KnownError {
     pub message: String,
     pub meta: serde_json::Value,
@@ -36,20 +36,20 @@ impl KnownError {
     }
 }
 
-#[derive(Serialize, PartialEq, Debug)]
+#[derive(Serialize, PartialEq, Debug, Clone)]
 pub struct UnknownError {
     pub message: String,
     pub backtrace: Option<String>,
 }
 
-#[derive(Serialize, PartialEq, Debug)]
+#[derive(Serialize, PartialEq, Debug, Clone)]
 pub struct Error {
     is_panic: bool,
     #[serde(flatten)]
     inner: ErrorType,
 }
 
-#[derive(Serialize, PartialEq, Debug)]
+#[derive(Serialize, PartialEq, Debug, Clone)]
 #[serde(untagged)]
 enum ErrorType {
     Known(KnownError),
@@ -64,6 +64,14 @@ impl Error {
         }
     }
 
+    /// The error code of known errors, e.g. `P2002`.
+    pub fn error_code(&self) -> Option<&str> {
+        match &self.inner {
+            ErrorType::Known(err) => Some(err.error_code),
+            ErrorType::Unknown(_) => None,
+        }
+    }
+
     pub fn new_non_panic_with_current_backtrace(message: String) -> Self {
         Error {
             inner: ErrorType::Unknown(UnknownError {
@@ -27,6 +27,16 @@ struct MigrationRollback {
     pub database_error: String,
 }
 
+#[derive(Debug, UserFacingError, Serialize)]
+#[user_facing(
+    code = "P3003",
+    message = "The following migrations were edited after they were applied: ${migration_names}. Applied migrations must not be modified, create a new migration instead."
+)]
+pub struct EditedMigrations {
+    /// The names of the edited migrations, comma separated.
+    pub migration_names: String,
+}
+
 // Tests
 
 #[cfg(test)]
@@ -115,11 +115,39 @@ pub fn render_quaint_error(kind: &ErrorKind, connection_info: &ConnectionInfo) -
         })
         .ok(),
 
-        (ErrorKind::UniqueConstraintViolation { constraint }, _) => KnownError::new(query_engine::UniqueKeyViolation {
-            constraint: constraint.into(),
+        // A refused connection surfaces as an IO error of the driver.
+        (ErrorKind::IoError(_), ConnectionInfo::Postgres(url)) => KnownError::new(common::DatabaseNotReachable {
+            database_port: url.port(),
+            database_host: url.host().to_owned(),
+        })
+        .ok(),
+
+        (ErrorKind::IoError(_), ConnectionInfo::Mysql(url)) => KnownError::new(common::DatabaseNotReachable {
+            database_port: url.port(),
+            database_host: url.host().to_owned(),
         })
         .ok(),
 
+        (ErrorKind::UniqueConstraintViolation { constraint }, _) => {
+            let column_names = match constraint {
+                quaint::error::DatabaseConstraint::Fields(fields) => fields.to_vec(),
+                quaint::error::DatabaseConstraint::Index(_) => Vec::new(),
+            };
+
+            KnownError::new(query_engine::UniqueKeyViolation {
+                constraint: constraint.into(),
+                column_names,
+            })
+            .ok()
+        }
+
+        (ErrorKind::NullConstraintViolation { constraint }, _) => {
+            KnownError::new(query_engine::NullConstraintViolation {
+                constraint: constraint.into(),
+            })
+            .ok()
+        }
+
         (ErrorKind::TlsError { message }, _) => KnownError::new(common::TlsConnectionError {
             message: message.into(),
         })
@@ -7,6 +7,8 @@ use user_facing_error_macros::*;
 pub enum DatabaseConstraint {
     Fields(Vec<String>),
     Index(String),
+    /// A foreign key the database doesn't name in its error, e.g. on SQLite.
+    ForeignKey,
 }
 
 impl fmt::Display for DatabaseConstraint {
@@ -17,6 +19,7 @@ impl fmt::Display for DatabaseConstraint {
                 write!(f, "fields: ({})", quoted_fields.join(","))
             }
             Self::Index(index) => write!(f, "constraint: `{}`", index),
+            Self::ForeignKey => write!(f, "foreign key"),
         }
     }
 }
@@ -30,7 +33,7 @@ impl From<Vec<String>> for DatabaseConstraint {
 #[derive(Debug, UserFacingError, Serialize)]
 #[user_facing(
     code = "P2000",
-    message = "The value ${field_value} for the field ${field_name} is too long for the field's type"
+    message = "The provided value for the column is too long for the column's type. Column: ${column_name}"
 )]
 pub struct InputValueTooLong {
     /// Concrete value provided for a field on a model in Prisma schema. Should be peeked/truncated
@@ -39,6 +42,9 @@ pub struct InputValueTooLong {
 
     /// Field name from one model from Prisma schema
     pub field_name: String,
+
+    /// Column name in the database, if the database reports it
+    pub column_name: String,
 }
 
 #[derive(Debug, UserFacingError, Serialize)]
@@ -64,16 +70,21 @@ pub struct UniqueKeyViolation {
     /// Field name from one model from Prisma schema
     #[serde(rename = "target")]
     pub constraint: DatabaseConstraint,
+
+    /// Columns of the violated constraint, if they are known
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub column_names: Vec<String>,
 }
 
 #[derive(Debug, UserFacingError, Serialize)]
-#[user_facing(
-    code = "P2003",
-    message = "Foreign key constraint failed on the field: `${field_name}`"
-)]
+#[user_facing(code = "P2003", message = "Foreign key constraint failed on the ${constraint}")]
 pub struct ForeignKeyViolation {
     /// Field name from one model from Prisma schema
     pub field_name: String,
+
+    /// The violated foreign key, by name or by its columns
+    #[serde(rename = "target")]
+    pub constraint: DatabaseConstraint,
 }
 
 #[derive(Debug, UserFacingError, Serialize)]
@@ -151,3 +162,103 @@ pub struct RawQueryFailed {
     pub code: String,
     pub message: String,
 }
+
+#[derive(Debug, UserFacingError, Serialize)]
+#[user_facing(
+    code = "P2011",
+    message = "Query exceeds the ${limit} limit: ${value} is above the maximum of ${maximum}"
+)]
+pub struct QueryLimitExceeded {
+    /// Name of the exceeded limit, e.g. depth or nested writes
+    pub limit: String,
+
+    /// Value measured for the incoming query
+    pub value: usize,
+
+    /// Configured maximum of the limit
+    pub maximum: usize,
+}
+
+#[derive(Debug, UserFacingError, Serialize)]
+#[user_facing(code = "P2012", message = "Null constraint violation on the ${constraint}")]
+pub struct NullConstraintViolation {
+    /// The column or constraint that doesn't allow null values
+    #[serde(rename = "target")]
+    pub constraint: DatabaseConstraint,
+}
+
+#[derive(Debug, UserFacingError, Serialize)]
+#[user_facing(code = "P2013", message = "The access policy doesn't allow to ${operation}.")]
+pub struct AccessDenied {
+    /// The denied operation, e.g. `delete records of the model User` or `read the field User.password`
+    pub operation: String,
+}
+
+#[derive(Debug, UserFacingError, Serialize)]
+#[user_facing(
+    code = "P2014",
+    message = "Reading all records of the model ${model_name} at once is not allowed, the query needs a `take` argument."
+)]
+pub struct PaginationRequired {
+    /// Model configured to require pagination
+    pub model_name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KnownError, UserFacingError};
+
+    #[test]
+    fn constraint_violations_carry_their_target() {
+        let err = ForeignKeyViolation {
+            field_name: "Post_authorId_fkey".into(),
+            constraint: DatabaseConstraint::Index("Post_authorId_fkey".into()),
+        };
+
+        assert_eq!(
+            err.message(),
+            "Foreign key constraint failed on the constraint: `Post_authorId_fkey`"
+        );
+
+        let err = KnownError::new(UniqueKeyViolation {
+            constraint: DatabaseConstraint::Index("User.email".into()),
+            column_names: vec!["email".into()],
+        })
+        .unwrap();
+
+        assert_eq!(err.error_code, "P2002");
+        assert_eq!(
+            err.meta,
+            serde_json::json!({ "target": "User.email", "column_names": ["email"] })
+        );
+
+        let err = KnownError::new(ForeignKeyViolation {
+            field_name: "(not available)".into(),
+            constraint: DatabaseConstraint::ForeignKey,
+        })
+        .unwrap();
+
+        assert_eq!(err.message, "Foreign key constraint failed on the foreign key");
+        assert_eq!(
+            err.meta,
+            serde_json::json!({ "field_name": "(not available)", "target": null })
+        );
+    }
+
+    #[test]
+    fn value_too_long_keeps_the_field_keys() {
+        let err = KnownError::new(InputValueTooLong {
+            field_value: "(not available)".into(),
+            field_name: "name".into(),
+            column_name: "name".into(),
+        })
+        .unwrap();
+
+        assert_eq!(err.error_code, "P2000");
+        assert_eq!(
+            err.meta,
+            serde_json::json!({ "field_value": "(not available)", "field_name": "name", "column_name": "name" })
+        );
+    }
+}
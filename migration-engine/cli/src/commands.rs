@@ -8,6 +8,7 @@ use futures::FutureExt;
 use migration_connector::{ConnectorError, ErrorKind, MigrationConnector};
 use migration_core::CoreResult;
 use quaint::prelude::SqlFamily;
+use secret_string::SecretString;
 use sql_migration_connector::SqlMigrationConnector;
 use std::collections::HashMap;
 use structopt::StructOpt;
@@ -16,8 +17,8 @@ use url::Url;
 #[derive(Debug, StructOpt)]
 pub(crate) struct Cli {
     /// The connection string to the database
-    #[structopt(long = "datasource", short = "d")]
-    datasource: String,
+    #[structopt(long = "datasource", short = "d", parse(from_str))]
+    datasource: SecretString,
     #[structopt(subcommand)]
     command: CliCommand,
 }
@@ -51,9 +52,9 @@ impl Cli {
 
     pub(crate) async fn run_inner(&self) -> Result<String, CliError> {
         match self.command {
-            CliCommand::CreateDatabase => create_database(&self.datasource).await,
+            CliCommand::CreateDatabase => create_database(self.datasource.expose()).await,
             CliCommand::CanConnectToDatabase => {
-                create_conn(&self.datasource, false).await?;
+                create_conn(self.datasource.expose(), false).await?;
                 Ok("Connection successful".to_owned())
             }
         }
@@ -142,8 +143,8 @@ async fn create_database(datasource: &str) -> Result<String, CliError> {
             Ok(format!("Database '{}' created successfully.", db_name))
         }
         None => Err(CliError::Other(anyhow::anyhow!(
-            "Invalid URL or unsupported connector in the datasource ({:?})",
-            url
+            "Invalid URL or unsupported connector in the datasource ({})",
+            secret_string::redact(datasource)
         ))),
     }
 }
@@ -84,7 +84,7 @@ async fn start_engine(datamodel_location: &str, single_cmd: bool) -> ! {
     } else {
         match RpcApi::new(&datamodel).await {
             // Block the thread and handle IO in async until EOF.
-            Ok(api) => json_rpc_stdio::run(api.io_handler()).await.unwrap(),
+            Ok(api) => api.serve_stdio().await.unwrap(),
             Err(err) => {
                 let (error, exit_code) = match &err {
                     CoreError::DatamodelError(errors) => {
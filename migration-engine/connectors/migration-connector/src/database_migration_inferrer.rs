@@ -18,4 +18,12 @@ pub trait DatabaseMigrationInferrer<T>: Send + Sync {
         next: &Datamodel,
         steps: &[MigrationStep],
     ) -> ConnectorResult<T>;
+
+    /// Infer a database migration between two serialized descriptions of the database, in the format of the
+    /// connector. Like `infer_from_datamodels`, this does not rely on the current state of the database.
+    async fn infer_from_database_schemas(
+        &self,
+        previous: &serde_json::Value,
+        next: &serde_json::Value,
+    ) -> ConnectorResult<T>;
 }
@@ -4,13 +4,23 @@ use crate::*;
 /// i.e. the [associated type on MigrationConnector](trait.MigrationConnector.html#associatedtype.DatabaseMigration).
 #[async_trait::async_trait]
 pub trait DatabaseMigrationStepApplier<T>: Send + Sync {
-    /// Applies the step to the database
+    /// Applies the step to the database, recording the executed statements in `logs`.
     /// Returns true to signal to the caller that there are more steps to apply.
-    async fn apply_step(&self, database_migration: &T, step: usize) -> ConnectorResult<bool>;
+    async fn apply_step(
+        &self,
+        database_migration: &T,
+        step: usize,
+        logs: &mut Vec<ExecutedStatement>,
+    ) -> ConnectorResult<bool>;
 
-    /// Applies the step to the database.
+    /// Applies the step to the database, recording the executed statements in `logs`.
     /// Returns true to signal to the caller that there are more steps to unapply.
-    async fn unapply_step(&self, database_migration: &T, step: usize) -> ConnectorResult<bool>;
+    async fn unapply_step(
+        &self,
+        database_migration: &T,
+        step: usize,
+        logs: &mut Vec<ExecutedStatement>,
+    ) -> ConnectorResult<bool>;
 
     /// Render steps for the CLI. Each step will contain the raw field.
     fn render_steps_pretty(&self, database_migration: &T) -> ConnectorResult<Vec<serde_json::Value>>;
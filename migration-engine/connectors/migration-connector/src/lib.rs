@@ -6,6 +6,7 @@ mod destructive_changes_checker;
 mod error;
 mod migration_applier;
 mod migration_persistence;
+mod seed_applier;
 
 pub mod steps;
 
@@ -15,6 +16,7 @@ pub use destructive_changes_checker::*;
 pub use error::*;
 pub use migration_applier::*;
 pub use migration_persistence::*;
+pub use seed_applier::*;
 pub use steps::MigrationStep;
 
 use std::fmt::Debug;
@@ -56,6 +58,9 @@ pub trait MigrationConnector: Send + Sync + 'static {
     /// See [DestructiveChangesChecker](trait.DestructiveChangesChecker.html).
     fn destructive_changes_checker<'a>(&'a self) -> Box<dyn DestructiveChangesChecker<Self::DatabaseMigration> + 'a>;
 
+    /// See [SeedApplier](trait.SeedApplier.html).
+    fn seed_applier<'a>(&'a self) -> Box<dyn SeedApplier + 'a>;
+
     // TODO: figure out if this is the best way to do this or move to a better place/interface
     // this is placed here so i can use the associated type
     fn deserialize_database_migration(&self, json: serde_json::Value) -> Self::DatabaseMigration;
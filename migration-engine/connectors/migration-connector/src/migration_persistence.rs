@@ -1,7 +1,8 @@
 use crate::{error::ConnectorError, steps::*};
 use chrono::{DateTime, Utc};
 use datamodel::{ast::SchemaAst, Datamodel};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// This trait is implemented by each connector. It provides a generic API to store and retrieve [Migration](struct.Migration.html) records.
 #[async_trait::async_trait]
@@ -79,15 +80,19 @@ pub struct Migration {
     pub rolled_back: usize,
     pub datamodel_string: String,
     pub datamodel_steps: Vec<MigrationStep>,
+    /// The checksum of the datamodel steps when the migration was created, see `Migration::checksum_of_steps`.
+    pub checksum: String,
     pub database_migration: serde_json::Value,
     pub errors: Vec<String>,
+    /// The statements executed while applying and unapplying the migration.
+    pub logs: Vec<ExecutedStatement>,
     pub started_at: DateTime<Utc>,
     pub finished_at: Option<DateTime<Utc>>,
 }
 
 impl Migration {
     pub fn parse_datamodel(&self) -> Datamodel {
-        datamodel::parse_datamodel(&self.datamodel_string).unwrap()
+        datamodel::lift_ast(&self.parse_schema_ast()).unwrap()
     }
 
     pub fn parse_schema_ast(&self) -> SchemaAst {
@@ -105,6 +110,7 @@ pub struct MigrationUpdateParams {
     pub applied: usize,
     pub rolled_back: usize,
     pub errors: Vec<String>,
+    pub logs: Vec<ExecutedStatement>,
     pub finished_at: Option<DateTime<Utc>>,
 }
 
@@ -115,10 +121,25 @@ impl MigrationUpdateParams {
     }
 }
 
+/// A statement executed against the database by a migration, for auditing and debugging.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutedStatement {
+    /// The exact statement sent to the database.
+    pub statement: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// The error returned by the database, if the statement failed.
+    pub error: Option<String>,
+}
+
 pub trait IsWatchMigration {
     fn is_watch_migration(&self) -> bool;
 }
 
+/// The prefix of the checksums of the current scheme, see `Migration::checksum_of_steps`.
+const CHECKSUM_VERSION: &str = "v1:";
+
 impl Migration {
     pub fn new(name: String) -> Migration {
         Migration {
@@ -129,8 +150,10 @@ impl Migration {
             applied: 0,
             rolled_back: 0,
             datamodel_steps: Vec::new(),
+            checksum: Self::checksum_of_steps(&[]),
             database_migration: serde_json::to_value("{}").unwrap(),
             errors: Vec::new(),
+            logs: Vec::new(),
             started_at: Self::timestamp_without_nanos(),
             finished_at: None,
         }
@@ -145,10 +168,39 @@ impl Migration {
             applied: self.applied,
             rolled_back: self.rolled_back,
             errors: self.errors.clone(),
+            logs: self.logs.clone(),
             finished_at: self.finished_at.clone(),
         }
     }
 
+    /// Replaces the datamodel steps, updating the checksum.
+    pub fn set_datamodel_steps(&mut self, steps: Vec<MigrationStep>) {
+        self.checksum = Self::checksum_of_steps(&steps);
+        self.datamodel_steps = steps;
+    }
+
+    /// The checksum of the steps, to detect migrations that were edited after they were applied. It is the hex
+    /// encoded SHA-256 of the canonical JSON of the steps, prefixed with the version of the scheme: object keys
+    /// are sorted and null values left out, so that the order of the fields and new optional fields of future
+    /// engine versions don't change the checksums of existing migrations. A change of the scheme itself has to
+    /// bump `CHECKSUM_VERSION`.
+    pub fn checksum_of_steps(steps: &[MigrationStep]) -> String {
+        let steps_json = serde_json::to_value(steps).expect("Failed to render the migration steps");
+        let mut canonical_json = String::new();
+        write_canonical_json(&steps_json, &mut canonical_json);
+
+        let digest = Sha256::digest(canonical_json.as_bytes());
+        let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        format!("{}{}", CHECKSUM_VERSION, hex)
+    }
+
+    /// Whether the steps don't match the stored checksum. Checksums of other schemes, like the ones stored before
+    /// the scheme was versioned, can't be compared and count as unchanged.
+    pub fn steps_were_edited(&self, steps: &[MigrationStep]) -> bool {
+        self.checksum.starts_with(CHECKSUM_VERSION) && self.checksum != Self::checksum_of_steps(steps)
+    }
+
     // SQLite does not store nano precision. Therefore we cut it so we can assert equality in our tests.
     pub fn timestamp_without_nanos() -> DateTime<Utc> {
         let timestamp = Utc::now().timestamp_millis();
@@ -267,3 +319,88 @@ impl MigrationPersistence for EmptyMigrationPersistence {
         Ok(datamodel::ast::SchemaAst { tops: Vec::new() })
     }
 }
+
+fn write_canonical_json(value: &serde_json::Value, out: &mut String) {
+    use serde_json::Value;
+
+    match value {
+        Value::Array(values) => {
+            out.push('[');
+
+            for (idx, value) in values.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+
+                write_canonical_json(value, out);
+            }
+
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            let mut fields: Vec<(&String, &Value)> = fields.iter().filter(|(_, value)| !value.is_null()).collect();
+            fields.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+            out.push('{');
+
+            for (idx, (key, value)) in fields.into_iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(value, out);
+            }
+
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_field(arity: FieldArity) -> MigrationStep {
+        MigrationStep::CreateField(CreateField {
+            model: "Blog".to_owned(),
+            field: "title".to_owned(),
+            tpe: "String".to_owned(),
+            arity,
+        })
+    }
+
+    #[test]
+    fn checksums_do_not_depend_on_the_order_of_keys_or_null_fields() {
+        let mut canonical = String::new();
+        write_canonical_json(
+            &serde_json::json!({ "tag": "UpdateModel", "model": "Blog", "newName": null }),
+            &mut canonical,
+        );
+
+        let mut reordered = String::new();
+        write_canonical_json(
+            &serde_json::json!({ "model": "Blog", "tag": "UpdateModel" }),
+            &mut reordered,
+        );
+
+        assert_eq!(canonical, r#"{"model":"Blog","tag":"UpdateModel"}"#);
+        assert_eq!(canonical, reordered);
+    }
+
+    #[test]
+    fn checksums_are_versioned_and_detect_edited_steps() {
+        let mut migration = Migration::new("migration".to_owned());
+        migration.set_datamodel_steps(vec![create_field(FieldArity::Required)]);
+
+        assert!(migration.checksum.starts_with(CHECKSUM_VERSION));
+        assert!(!migration.steps_were_edited(&[create_field(FieldArity::Required)]));
+        assert!(migration.steps_were_edited(&[create_field(FieldArity::Optional)]));
+
+        // Checksums stored before the scheme was versioned can't be compared.
+        migration.checksum = "0a1b2c".to_owned();
+        assert!(!migration.steps_were_edited(&[create_field(FieldArity::Optional)]));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::ConnectorResult;
+
+/// Runs seed data against the database after migrating it. Applied seeds are recorded in the database, so that
+/// each seed runs only once per database.
+#[async_trait::async_trait]
+pub trait SeedApplier: Send + Sync {
+    /// Whether the seed with this name already ran against the database.
+    async fn seed_was_applied(&self, name: &str) -> ConnectorResult<bool>;
+
+    /// Runs the script, then inserts the records and records the seed. Seeds that fail are not recorded, so they
+    /// run again the next time.
+    async fn apply_seed(&self, seed: &Seed) -> ConnectorResult<()>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Seed {
+    /// Identifies the seed in the database, e.g. the file name of the script.
+    pub name: String,
+    /// Statements in the dialect of the database, separated by semicolons.
+    pub script: Option<String>,
+    pub records: Vec<SeedRecords>,
+}
+
+/// Records to insert into one table, with the values of each record by column name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeedRecords {
+    pub table: String,
+    pub rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
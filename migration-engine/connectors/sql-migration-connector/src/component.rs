@@ -1,4 +1,5 @@
 use crate::{DatabaseInfo, SqlMigrationConnector, SqlResult};
+use datamodel::RelationMode;
 use quaint::prelude::{ConnectionInfo, Queryable, SqlFamily};
 
 #[async_trait::async_trait]
@@ -21,12 +22,28 @@ pub(crate) trait Component {
         &self.connector().database_info
     }
 
+    fn relation_mode(&self) -> RelationMode {
+        self.connector().relation_mode
+    }
+
+    /// The described schema is cached until the connector changes the schema.
     async fn describe(&self) -> SqlResult<sql_schema_describer::SqlSchema> {
-        Ok(self
+        let cache = &self.connector().described_schema_cache;
+
+        let version = match cache.get() {
+            (_, Some(schema)) => return Ok(schema),
+            (version, None) => version,
+        };
+
+        let schema = self
             .connector()
             .database_describer
             .describe(&self.schema_name())
-            .await?)
+            .await?;
+
+        cache.store(version, &schema);
+
+        Ok(schema)
     }
 
     fn sql_family(&self) -> SqlFamily {
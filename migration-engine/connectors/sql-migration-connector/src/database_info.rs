@@ -1,4 +1,5 @@
 use super::SqlResult;
+use prisma_models::TimeZonePolicy;
 use quaint::{
     prelude::{ConnectionInfo, Queryable, SqlFamily},
     single::Quaint,
@@ -8,15 +9,31 @@ use quaint::{
 pub struct DatabaseInfo {
     connection_info: ConnectionInfo,
     database_version: Option<String>,
+    time_zone: TimeZonePolicy,
+    uuid_function: Option<&'static str>,
+    uuid_defaults: bool,
 }
 
+/// Generates random UUIDs. Built into Postgres 13+, before that it comes with the `pgcrypto` extension.
+const POSTGRES_GEN_RANDOM_UUID: &str = "gen_random_uuid()";
+/// Generates random UUIDs, comes with the `uuid-ossp` extension.
+const POSTGRES_UUID_GENERATE_V4: &str = "uuid_generate_v4()";
+
 impl DatabaseInfo {
-    pub(crate) async fn new(connection: &Quaint, connection_info: ConnectionInfo) -> SqlResult<Self> {
+    pub(crate) async fn new(
+        connection: &Quaint,
+        connection_info: ConnectionInfo,
+        time_zone: TimeZonePolicy,
+    ) -> SqlResult<Self> {
         let database_version = get_database_version(connection, &connection_info).await?;
+        let uuid_function = get_uuid_function(connection, &connection_info).await?;
 
         Ok(DatabaseInfo {
             connection_info,
             database_version,
+            time_zone,
+            uuid_function,
+            uuid_defaults: false,
         })
     }
 
@@ -36,6 +53,62 @@ impl DatabaseInfo {
     pub(crate) fn connection_info(&self) -> &ConnectionInfo {
         &self.connection_info
     }
+
+    /// How DateTime defaults are rendered, like the query engine stores DateTime values.
+    pub(crate) fn time_zone(&self) -> TimeZonePolicy {
+        self.time_zone
+    }
+
+    /// The database function generating `@default(uuid())` values, if the database has one and its defaults are
+    /// enabled. The query engine still generates the values it writes, the column default serves writes from other
+    /// clients.
+    pub(crate) fn uuid_function(&self) -> Option<&'static str> {
+        self.uuid_function.filter(|_| self.uuid_defaults)
+    }
+
+    pub(crate) fn set_uuid_defaults(&mut self, uuid_defaults: bool) {
+        self.uuid_defaults = uuid_defaults;
+    }
+}
+
+/// The UUID function a column default calls, as the describer returns it, e.g. `(public.gen_random_uuid())`.
+pub(crate) fn parse_uuid_function(default: &str) -> Option<&'static str> {
+    let function = default.trim_start_matches('(').trim_end_matches(')');
+    let function = function.rsplit('.').next().unwrap_or(function);
+
+    [POSTGRES_GEN_RANDOM_UUID, POSTGRES_UUID_GENERATE_V4]
+        .iter()
+        .find(|uuid_function| uuid_function.trim_end_matches("()") == function)
+        .copied()
+}
+
+async fn get_uuid_function(connection: &Quaint, connection_info: &ConnectionInfo) -> SqlResult<Option<&'static str>> {
+    match connection_info.sql_family() {
+        SqlFamily::Postgres => {
+            let query = r#"
+                SELECT
+                    current_setting('server_version_num')::int4 >= 130000
+                        OR EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pgcrypto') AS has_gen_random_uuid,
+                    EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'uuid-ossp') AS has_uuid_generate_v4
+            "#;
+
+            let rows = connection.query_raw(query, &[]).await?;
+            let row = rows.get(0);
+            let has_function =
+                |column: &str| row.and_then(|row| row.get(column)).and_then(|value| value.as_bool()) == Some(true);
+
+            let uuid_function = if has_function("has_gen_random_uuid") {
+                Some(POSTGRES_GEN_RANDOM_UUID)
+            } else if has_function("has_uuid_generate_v4") {
+                Some(POSTGRES_UUID_GENERATE_V4)
+            } else {
+                None
+            };
+
+            Ok(uuid_function)
+        }
+        _ => Ok(None),
+    }
 }
 
 async fn get_database_version(connection: &Quaint, connection_info: &ConnectionInfo) -> SqlResult<Option<String>> {
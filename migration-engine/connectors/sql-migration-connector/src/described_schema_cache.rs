@@ -0,0 +1,75 @@
+use sql_schema_describer::SqlSchema;
+use std::sync::Mutex;
+
+/// The last described schema of the connection, so that the commands of an engine run don't describe the same
+/// schema again and again. Everything that changes the schema through the connector invalidates it, which starts a
+/// new schema version.
+#[derive(Debug, Default)]
+pub(crate) struct DescribedSchemaCache {
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    version: u64,
+    schema: Option<SqlSchema>,
+}
+
+impl DescribedSchemaCache {
+    /// The cached schema of the current version, and the current version for `store`.
+    pub(crate) fn get(&self) -> (u64, Option<SqlSchema>) {
+        let state = self.state.lock().unwrap();
+
+        (state.version, state.schema.clone())
+    }
+
+    /// Caches a schema described at `version`. Schemas described before the last invalidation are dropped, they
+    /// may miss the latest changes.
+    pub(crate) fn store(&self, version: u64, schema: &SqlSchema) {
+        let mut state = self.state.lock().unwrap();
+
+        if state.version == version {
+            state.schema = Some(schema.clone());
+        }
+    }
+
+    pub(crate) fn invalidate(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        state.version += 1;
+        state.schema = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_with_enum(name: &str) -> SqlSchema {
+        let mut schema = SqlSchema::empty();
+        schema.enums.push(sql_schema_describer::Enum {
+            name: name.to_owned(),
+            values: Vec::new(),
+        });
+        schema
+    }
+
+    #[test]
+    fn schemas_described_before_an_invalidation_are_not_cached() {
+        let cache = DescribedSchemaCache::default();
+
+        let (version, cached) = cache.get();
+        assert!(cached.is_none());
+
+        cache.invalidate();
+        cache.store(version, &schema_with_enum("Stale"));
+        let (version, cached) = cache.get();
+        assert!(cached.is_none());
+
+        cache.store(version, &schema_with_enum("Fresh"));
+        assert_eq!(cache.get().1, Some(schema_with_enum("Fresh")));
+
+        cache.invalidate();
+        assert!(cache.get().1.is_none());
+    }
+}
@@ -59,6 +59,16 @@ pub enum SqlError {
         cause: QuaintKind,
     },
 
+    #[error("The index definition on model `{model_name}` refers to the unknown field `{field_name}`.")]
+    UnknownIndexField { model_name: String, field_name: String },
+
+    #[error("The enum `{enum_name}` of field `{field_name}` on model `{model_name}` has no values to default to.")]
+    EnumWithoutValues {
+        enum_name: String,
+        model_name: String,
+        field_name: String,
+    },
+
     #[error("Unique constraint violation")]
     UniqueConstraintViolation {
         constraint: DatabaseConstraint,
@@ -126,6 +136,9 @@ impl SqlError {
                     },
                 }
             }
+            error @ SqlError::UnknownIndexField { .. } | error @ SqlError::EnumWithoutValues { .. } => {
+                ConnectorError::from_kind(ErrorKind::Generic(error.into()))
+            }
             error => ConnectorError::from_kind(ErrorKind::QueryError(error.into())),
         }
     }
@@ -1,5 +1,6 @@
 mod component;
 mod database_info;
+mod described_schema_cache;
 mod error;
 mod sql_database_migration_inferrer;
 mod sql_database_step_applier;
@@ -10,13 +11,17 @@ mod sql_renderer;
 mod sql_schema_calculator;
 mod sql_schema_differ;
 mod sql_schema_helpers;
+mod sql_seed_applier;
 
 pub use error::*;
 pub use sql_migration::*;
 
 use component::Component;
 use database_info::DatabaseInfo;
+use datamodel::RelationMode;
+use described_schema_cache::DescribedSchemaCache;
 use migration_connector::*;
+use prisma_models::TimeZonePolicy;
 use quaint::{
     error::ErrorKind,
     prelude::{ConnectionInfo, Queryable, SqlFamily},
@@ -26,7 +31,9 @@ use sql_database_migration_inferrer::*;
 use sql_database_step_applier::*;
 use sql_destructive_changes_checker::*;
 use sql_migration_persistence::*;
+use sql_renderer::{mysql_quoted, postgres_quoted};
 use sql_schema_describer::SqlSchemaDescriberBackend;
+use sql_seed_applier::*;
 use std::{fs, path::PathBuf, sync::Arc, time::Duration};
 use tracing::debug;
 
@@ -37,12 +44,19 @@ pub struct SqlMigrationConnector {
     pub database: Arc<dyn Queryable + Send + Sync + 'static>,
     pub database_info: DatabaseInfo,
     pub database_describer: Arc<dyn SqlSchemaDescriberBackend + Send + Sync + 'static>,
+    /// Foreign keys are only created when they are used for referential integrity.
+    pub relation_mode: RelationMode,
+    described_schema_cache: DescribedSchemaCache,
 }
 
 impl SqlMigrationConnector {
     pub async fn new(database_str: &str, provider: &str) -> ConnectorResult<Self> {
         validate_database_str(database_str, provider)?;
 
+        let (time_zone, url) =
+            TimeZonePolicy::from_url(database_str).map_err(|err| ConnectorError::url_parse_error(err, database_str))?;
+        let database_str = url.as_str();
+
         let connection_info =
             ConnectionInfo::from_url(database_str).map_err(|err| ConnectorError::url_parse_error(err, database_str))?;
 
@@ -70,7 +84,7 @@ impl SqlMigrationConnector {
                 SqlError::from(ErrorKind::ConnectTimeout("Tokio timer".into())).into_connector_error(&connection_info)
             })??;
 
-        let database_info = DatabaseInfo::new(&connection, connection.connection_info().clone())
+        let database_info = DatabaseInfo::new(&connection, connection.connection_info().clone(), time_zone)
             .await
             .map_err(|sql_error| sql_error.into_connector_error(&connection_info))?;
 
@@ -90,20 +104,41 @@ impl SqlMigrationConnector {
             schema_name,
             database: conn,
             database_describer: Arc::clone(&describer),
+            relation_mode: RelationMode::default(),
+            described_schema_cache: DescribedSchemaCache::default(),
         })
     }
 
+    /// Leaves out the foreign keys of relations if referential integrity is emulated by the query engine.
+    pub fn with_relation_mode(mut self, relation_mode: RelationMode) -> Self {
+        self.relation_mode = relation_mode;
+        self
+    }
+
+    /// Renders the UUID function of the database as the default of `@default(uuid())` columns. Off by default, so
+    /// that creating an extension doesn't change the defaults of all UUID columns on the next migration.
+    pub fn with_database_uuid_defaults(mut self, enabled: bool) -> Self {
+        self.database_info.set_uuid_defaults(enabled);
+        self
+    }
+
+    /// Drops the cached description of the schema. The connector does this itself when it changes the schema,
+    /// changes made over other connections have to be announced with this.
+    pub fn invalidate_described_schema(&self) {
+        self.described_schema_cache.invalidate();
+    }
+
     async fn create_database_impl(&self, db_name: &str) -> SqlResult<()> {
         match self.database_info.sql_family() {
             SqlFamily::Postgres => {
-                let query = format!("CREATE DATABASE \"{}\"", db_name);
+                let query = format!("CREATE DATABASE {}", postgres_quoted(db_name));
                 self.database.query_raw(&query, &[]).await?;
 
                 Ok(())
             }
             SqlFamily::Sqlite => Ok(()),
             SqlFamily::Mysql => {
-                let query = format!("CREATE DATABASE `{}`", db_name);
+                let query = format!("CREATE DATABASE {}", mysql_quoted(db_name));
                 self.database.query_raw(&query, &[]).await?;
 
                 Ok(())
@@ -123,7 +158,7 @@ impl SqlMigrationConnector {
                 }
             }
             ConnectionInfo::Postgres(_) => {
-                let schema_sql = format!("CREATE SCHEMA IF NOT EXISTS \"{}\";", &self.schema_name);
+                let schema_sql = format!("CREATE SCHEMA IF NOT EXISTS {};", postgres_quoted(&self.schema_name));
 
                 debug!("{}", schema_sql);
 
@@ -131,8 +166,8 @@ impl SqlMigrationConnector {
             }
             ConnectionInfo::Mysql(_) => {
                 let schema_sql = format!(
-                    "CREATE SCHEMA IF NOT EXISTS `{}` DEFAULT CHARACTER SET latin1;",
-                    &self.schema_name
+                    "CREATE SCHEMA IF NOT EXISTS {} DEFAULT CHARACTER SET latin1;",
+                    mysql_quoted(&self.schema_name)
                 );
 
                 debug!("{}", schema_sql);
@@ -154,7 +189,7 @@ impl SqlMigrationConnector {
         catch(self.connection_info(), async {
             match &self.connection_info() {
                 ConnectionInfo::Postgres(_) => {
-                    let sql_str = format!(r#"DROP SCHEMA "{}" CASCADE;"#, self.schema_name());
+                    let sql_str = format!("DROP SCHEMA {} CASCADE;", postgres_quoted(self.schema_name()));
                     debug!("{}", sql_str);
 
                     self.conn().query_raw(&sql_str, &[]).await.ok();
@@ -176,7 +211,7 @@ impl SqlMigrationConnector {
                         .await?;
                 }
                 ConnectionInfo::Mysql(_) => {
-                    let sql_str = format!(r#"DROP SCHEMA `{}`;"#, self.schema_name());
+                    let sql_str = format!("DROP SCHEMA {};", mysql_quoted(self.schema_name()));
                     debug!("{}", sql_str);
                     self.conn().query_raw(&sql_str, &[]).await?;
                 }
@@ -203,7 +238,9 @@ impl MigrationConnector for SqlMigrationConnector {
     }
 
     async fn initialize(&self) -> ConnectorResult<()> {
-        catch(self.connection_info(), self.initialize_impl()).await?;
+        let result = catch(self.connection_info(), self.initialize_impl()).await;
+        self.invalidate_described_schema();
+        result?;
 
         self.migration_persistence().init().await?;
 
@@ -212,7 +249,9 @@ impl MigrationConnector for SqlMigrationConnector {
 
     async fn reset(&self) -> ConnectorResult<()> {
         self.migration_persistence().reset().await?;
-        self.drop_database().await?;
+        let result = self.drop_database().await;
+        self.invalidate_described_schema();
+        result?;
 
         Ok(())
     }
@@ -233,6 +272,10 @@ impl MigrationConnector for SqlMigrationConnector {
         Box::new(SqlDestructiveChangesChecker { connector: self })
     }
 
+    fn seed_applier<'a>(&'a self) -> Box<dyn SeedApplier + 'a> {
+        Box::new(SqlSeedApplier { connector: self })
+    }
+
     fn deserialize_database_migration(&self, json: serde_json::Value) -> SqlMigration {
         serde_json::from_value(json).expect("Deserializing the database migration failed.")
     }
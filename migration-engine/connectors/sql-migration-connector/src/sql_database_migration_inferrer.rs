@@ -1,3 +1,4 @@
+mod postgres;
 mod sqlite;
 
 use crate::sql_schema_calculator::SqlSchemaCalculator;
@@ -22,19 +23,25 @@ impl Component for SqlDatabaseMigrationInferrer<'_> {
 impl DatabaseMigrationInferrer<SqlMigration> for SqlDatabaseMigrationInferrer<'_> {
     async fn infer(
         &self,
-        _previous: &Datamodel,
+        previous: &Datamodel,
         next: &Datamodel,
         _steps: &[MigrationStep],
     ) -> ConnectorResult<SqlMigration> {
         let fut = async {
             let current_database_schema: SqlSchema = self.describe().await?;
-            let expected_database_schema = SqlSchemaCalculator::calculate(next, self.database_info())?;
-            infer(
+            let mut expected_database_schema =
+                SqlSchemaCalculator::calculate(next, self.database_info(), self.relation_mode())?;
+            preserve_generated_columns(&current_database_schema, &mut expected_database_schema);
+            let mut migration = infer(
                 &current_database_schema,
                 &expected_database_schema,
                 self.schema_name(),
                 self.sql_family(),
-            )
+            )?;
+            migration.placeholder_defaults = SqlSchemaCalculator::placeholder_defaults(next);
+            migration.rollback_placeholder_defaults = SqlSchemaCalculator::placeholder_defaults(previous);
+
+            Ok(migration)
         };
 
         catch(&self.connection_info(), fut).await
@@ -47,18 +54,69 @@ impl DatabaseMigrationInferrer<SqlMigration> for SqlDatabaseMigrationInferrer<'_
         _steps: &[MigrationStep],
     ) -> ConnectorResult<SqlMigration> {
         let result: SqlResult<SqlMigration> = (|| {
-            let current_database_schema: SqlSchema = SqlSchemaCalculator::calculate(previous, self.database_info())?;
-            let expected_database_schema = SqlSchemaCalculator::calculate(next, self.database_info())?;
-            infer(
+            let current_database_schema: SqlSchema =
+                SqlSchemaCalculator::calculate(previous, self.database_info(), self.relation_mode())?;
+            let expected_database_schema =
+                SqlSchemaCalculator::calculate(next, self.database_info(), self.relation_mode())?;
+            let mut migration = infer(
                 &current_database_schema,
                 &expected_database_schema,
                 self.schema_name(),
                 self.sql_family(),
-            )
+            )?;
+            migration.placeholder_defaults = SqlSchemaCalculator::placeholder_defaults(next);
+            migration.rollback_placeholder_defaults = SqlSchemaCalculator::placeholder_defaults(previous);
+
+            Ok(migration)
         })();
 
         result.map_err(|sql_error| sql_error.into_connector_error(self.connection_info()))
     }
+
+    async fn infer_from_database_schemas(
+        &self,
+        previous: &serde_json::Value,
+        next: &serde_json::Value,
+    ) -> ConnectorResult<SqlMigration> {
+        let parse_schema = |json: &serde_json::Value| {
+            serde_json::from_value::<SqlSchema>(json.clone())
+                .map_err(|err| ConnectorError::from_kind(migration_connector::ErrorKind::Generic(err.into())))
+        };
+
+        let current_database_schema = parse_schema(previous)?;
+        let expected_database_schema = parse_schema(next)?;
+
+        infer(
+            &current_database_schema,
+            &expected_database_schema,
+            self.schema_name(),
+            self.sql_family(),
+        )
+        .map_err(|sql_error| sql_error.into_connector_error(self.connection_info()))
+    }
+}
+
+/// The datamodel can't declare generated columns, so calculated schemas have none. The generated columns of the
+/// database are kept as they are: columns of fields keep their generation, the others are added back to their
+/// tables. Otherwise migrations would drop them, or lose them when SQLite tables are recreated.
+fn preserve_generated_columns(current_database_schema: &SqlSchema, expected_database_schema: &mut SqlSchema) {
+    for table in expected_database_schema.tables.iter_mut() {
+        let current_table = match current_database_schema.table(&table.name) {
+            Ok(current_table) => current_table,
+            Err(_) => continue,
+        };
+
+        for current_column in current_table.columns.iter().filter(|column| column.is_generated()) {
+            match table
+                .columns
+                .iter_mut()
+                .find(|column| column.name == current_column.name)
+            {
+                Some(column) => column.generated = current_column.generated.clone(),
+                None => table.columns.push(current_column.clone()),
+            }
+        }
+    }
 }
 
 fn infer(
@@ -85,6 +143,8 @@ fn infer(
         original_steps,
         corrected_steps,
         rollback,
+        placeholder_defaults: Vec::new(),
+        rollback_placeholder_defaults: Vec::new(),
     })
 }
 
@@ -102,6 +162,12 @@ fn infer_database_migration_steps_and_fix(
         fix_id_column_type_change(&from, &to, schema_name, diff.into_steps(), sql_family)?
     };
 
+    let corrected_steps = if sql_family.is_postgres() {
+        postgres::wrap_dependent_views_and_triggers(corrected_steps, &from, &schema_name)
+    } else {
+        corrected_steps
+    };
+
     Ok((
         SqlSchemaDiffer::diff(&from, &to, sql_family).into_steps(),
         corrected_steps,
@@ -150,7 +216,7 @@ fn fix_id_column_type_change(
         let tables_to_drop: Vec<String> = from
             .tables
             .iter()
-            .filter(|t| t.name != "_Migration")
+            .filter(|t| !crate::sql_schema_differ::is_engine_table(t))
             .map(|t| t.name.clone())
             .collect();
         radical_steps.push(SqlMigrationStep::DropTables(DropTables { names: tables_to_drop }));
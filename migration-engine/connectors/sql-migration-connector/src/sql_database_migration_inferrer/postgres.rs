@@ -0,0 +1,161 @@
+use crate::{sql_migration::*, sql_renderer::postgres_quoted};
+use sql_schema_describer::{SqlSchema, Trigger, View};
+
+/// Postgres refuses to alter the type of columns that views or triggers depend on. The dependent views and
+/// triggers are dropped before the steps and recreated from their described definitions afterwards.
+pub(super) fn wrap_dependent_views_and_triggers(
+    steps: Vec<SqlMigrationStep>,
+    current_database_schema: &SqlSchema,
+    schema_name: &str,
+) -> Vec<SqlMigrationStep> {
+    let altered_columns: Vec<(&str, &str)> = steps
+        .iter()
+        .filter_map(|step| match step {
+            SqlMigrationStep::AlterTable(alter_table) => Some(alter_table),
+            _ => None,
+        })
+        .flat_map(|alter_table| {
+            alter_table.changes.iter().filter_map(move |change| match change {
+                TableChange::AlterColumn(alter_column) => {
+                    Some((alter_table.table.name.as_str(), alter_column.name.as_str()))
+                }
+                _ => None,
+            })
+        })
+        .collect();
+
+    if altered_columns.is_empty() {
+        return steps;
+    }
+
+    let views = dependent_views(current_database_schema, &altered_columns);
+    let triggers: Vec<&Trigger> = current_database_schema
+        .triggers
+        .iter()
+        .filter(|trigger| {
+            trigger
+                .columns
+                .iter()
+                .any(|column| altered_columns.contains(&(trigger.table.as_str(), column.as_str())))
+        })
+        .collect();
+
+    if views.is_empty() && triggers.is_empty() {
+        return steps;
+    }
+
+    let mut result = Vec::with_capacity(steps.len() + 2 * (views.len() + triggers.len()));
+
+    result.extend(triggers.iter().map(|trigger| SqlMigrationStep::RawSql {
+        raw: format!(
+            "DROP TRIGGER {} ON {}.{}",
+            postgres_quoted(&trigger.name),
+            postgres_quoted(schema_name),
+            postgres_quoted(&trigger.table)
+        ),
+    }));
+
+    // Views depending on other views are dropped first.
+    result.extend(views.iter().rev().map(|view| SqlMigrationStep::RawSql {
+        raw: format!(
+            "DROP VIEW {}.{}",
+            postgres_quoted(schema_name),
+            postgres_quoted(&view.name)
+        ),
+    }));
+
+    result.extend(steps);
+
+    result.extend(views.iter().map(|view| SqlMigrationStep::RawSql {
+        raw: format!(
+            "CREATE VIEW {}.{} AS {}",
+            postgres_quoted(schema_name),
+            postgres_quoted(&view.name),
+            view.definition.trim().trim_end_matches(';')
+        ),
+    }));
+
+    result.extend(triggers.iter().map(|trigger| SqlMigrationStep::RawSql {
+        raw: trigger.definition.clone(),
+    }));
+
+    result
+}
+
+/// The views depending on the altered columns, directly or through other views, ordered so that every view comes
+/// after the views it depends on.
+fn dependent_views<'a>(schema: &'a SqlSchema, altered_columns: &[(&str, &str)]) -> Vec<&'a View> {
+    let mut result: Vec<&View> = Vec::new();
+
+    loop {
+        let next_view = schema.views.iter().find(|view| {
+            !result.iter().any(|added| added.name == view.name)
+                && view.dependencies.iter().any(|dependency| {
+                    altered_columns.contains(&(dependency.table.as_str(), dependency.column.as_str()))
+                        || result.iter().any(|added| added.name == dependency.table)
+                })
+        });
+
+        match next_view {
+            Some(view) => result.push(view),
+            None => break,
+        }
+    }
+
+    // A view may have been added before another affected view it depends on.
+    let mut ordered: Vec<&View> = Vec::with_capacity(result.len());
+
+    while ordered.len() < result.len() {
+        let next_view = result.iter().find(|view| {
+            !ordered.iter().any(|added| added.name == view.name)
+                && view.dependencies.iter().all(|dependency| {
+                    dependency.table == view.name
+                        || !result.iter().any(|affected| affected.name == dependency.table)
+                        || ordered.iter().any(|added| added.name == dependency.table)
+                })
+        });
+
+        match next_view {
+            Some(view) => ordered.push(view),
+            // Postgres doesn't allow dependency cycles between views.
+            None => break,
+        }
+    }
+
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sql_schema_describer::ColumnDependency;
+
+    fn view(name: &str, dependencies: &[(&str, &str)]) -> View {
+        View {
+            name: name.to_owned(),
+            definition: format!("SELECT 1 AS {};", name),
+            dependencies: dependencies
+                .iter()
+                .map(|(table, column)| ColumnDependency {
+                    table: (*table).to_owned(),
+                    column: (*column).to_owned(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn dependent_views_are_ordered_by_their_dependencies() {
+        let mut schema = SqlSchema::empty();
+        schema.views = vec![
+            view("outer", &[("inner", "id")]),
+            view("unrelated", &[("Cat", "name")]),
+            view("inner", &[("Cat", "id")]),
+        ];
+
+        let views = dependent_views(&schema, &[("Cat", "id")]);
+        let names: Vec<&str> = views.iter().map(|view| view.name.as_str()).collect();
+
+        assert_eq!(names, &["inner", "outer"]);
+    }
+}
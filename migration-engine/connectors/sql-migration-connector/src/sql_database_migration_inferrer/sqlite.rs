@@ -63,6 +63,8 @@ fn needs_fix(alter_table: &AlterTable) -> bool {
         TableChange::DropColumn(_) => true,
         TableChange::AlterColumn(_) => true,
         TableChange::DropForeignKey(_) => true,
+        TableChange::AlterOptions(_) => true,
+        TableChange::AlterPrimaryKey(_) => true,
     });
 
     change_that_does_not_work_on_sqlite.is_some()
@@ -152,8 +154,10 @@ fn copy_current_table_into_new_table(
                 && columns.next.default.is_some()
         })
         .collect();
+    // Generated columns compute their values themselves.
     let intersection_columns: Vec<&str> = differ
         .column_pairs()
+        .filter(|columns| !columns.next.is_generated())
         .filter(|columns| {
             !columns_that_became_required_with_a_default
                 .iter()
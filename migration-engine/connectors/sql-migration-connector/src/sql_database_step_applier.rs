@@ -1,11 +1,14 @@
 use crate::*;
+use sql_migration::has_declared_default;
 use sql_renderer::{
     mysql_quoted, mysql_quoted_string, postgres_quoted, postgres_quoted_string, postgres_render_column_type,
-    IteratorJoin, SqlRenderer,
+    postgres_render_enum_default, IteratorJoin, SqlRenderer,
 };
+use sql_schema_calculator::POSTGRES_AUDIT_FUNCTION;
 use sql_schema_describer::*;
 use sql_schema_helpers::{walk_columns, ColumnRef};
 use std::fmt::Write as _;
+use std::time::Instant;
 use tracing_futures::Instrument;
 
 pub struct SqlDatabaseStepApplier<'a> {
@@ -20,7 +23,12 @@ impl crate::component::Component for SqlDatabaseStepApplier<'_> {
 
 #[async_trait::async_trait]
 impl DatabaseMigrationStepApplier<SqlMigration> for SqlDatabaseStepApplier<'_> {
-    async fn apply_step(&self, database_migration: &SqlMigration, index: usize) -> ConnectorResult<bool> {
+    async fn apply_step(
+        &self,
+        database_migration: &SqlMigration,
+        index: usize,
+        logs: &mut Vec<ExecutedStatement>,
+    ) -> ConnectorResult<bool> {
         let renderer = self.renderer();
         let fut = self
             .apply_next_step(
@@ -29,13 +37,20 @@ impl DatabaseMigrationStepApplier<SqlMigration> for SqlDatabaseStepApplier<'_> {
                 renderer.as_ref(),
                 &database_migration.before,
                 &database_migration.after,
+                &database_migration.placeholder_defaults,
+                logs,
             )
             .instrument(tracing::debug_span!("ApplySqlStep", index));
 
         crate::catch(self.connection_info(), fut).await
     }
 
-    async fn unapply_step(&self, database_migration: &SqlMigration, index: usize) -> ConnectorResult<bool> {
+    async fn unapply_step(
+        &self,
+        database_migration: &SqlMigration,
+        index: usize,
+        logs: &mut Vec<ExecutedStatement>,
+    ) -> ConnectorResult<bool> {
         let renderer = self.renderer();
         let fut = self
             .apply_next_step(
@@ -44,6 +59,8 @@ impl DatabaseMigrationStepApplier<SqlMigration> for SqlDatabaseStepApplier<'_> {
                 renderer.as_ref(),
                 &database_migration.after,
                 &database_migration.before,
+                &database_migration.rollback_placeholder_defaults,
+                logs,
             )
             .instrument(tracing::debug_span!("UnapplySqlStep", index));
 
@@ -75,6 +92,8 @@ impl SqlDatabaseStepApplier<'_> {
         renderer: &(dyn SqlRenderer + Send + Sync),
         current_schema: &SqlSchema,
         next_schema: &SqlSchema,
+        placeholder_defaults: &[(String, String)],
+        logs: &mut Vec<ExecutedStatement>,
     ) -> SqlResult<bool> {
         let has_this_one = steps.get(index).is_some();
         if !has_this_one {
@@ -84,12 +103,29 @@ impl SqlDatabaseStepApplier<'_> {
         let step = &steps[index];
         tracing::debug!(?step);
 
-        for sql_string in render_raw_sql(&step, renderer, self.database_info(), current_schema, next_schema)
-            .map_err(|err: anyhow::Error| SqlError::Generic(err))?
+        for sql_string in render_raw_sql(
+            &step,
+            renderer,
+            self.database_info(),
+            current_schema,
+            next_schema,
+            placeholder_defaults,
+        )
+        .map_err(|err: anyhow::Error| SqlError::Generic(err))?
         {
             tracing::debug!(index, %sql_string);
 
+            let started_at = Migration::timestamp_without_nanos();
+            let start = Instant::now();
             let result = self.conn().query_raw(&sql_string, &[]).await;
+            self.connector.invalidate_described_schema();
+
+            logs.push(ExecutedStatement {
+                statement: sql_string,
+                started_at,
+                duration_ms: start.elapsed().as_millis() as u64,
+                error: result.as_ref().err().map(|err| err.to_string()),
+            });
 
             // TODO: this does not evaluate the results of SQLites PRAGMA foreign_key_check
             result?;
@@ -100,7 +136,7 @@ impl SqlDatabaseStepApplier<'_> {
     }
 
     fn renderer<'a>(&'a self) -> Box<dyn SqlRenderer + Send + Sync + 'a> {
-        SqlRenderer::for_family(&self.sql_family())
+        SqlRenderer::for_database(self.database_info())
     }
 }
 
@@ -114,11 +150,16 @@ fn render_steps_pretty(
     let mut steps = Vec::with_capacity(database_migration.corrected_steps.len());
 
     for step in &database_migration.corrected_steps {
-        let sql = render_raw_sql(&step, renderer, database_info, current_schema, next_schema)
-            .map_err(|err: anyhow::Error| {
-                ConnectorError::from_kind(migration_connector::ErrorKind::Generic(err.into()))
-            })?
-            .join(";\n");
+        let sql = render_raw_sql(
+            &step,
+            renderer,
+            database_info,
+            current_schema,
+            next_schema,
+            &database_migration.placeholder_defaults,
+        )
+        .map_err(|err: anyhow::Error| ConnectorError::from_kind(migration_connector::ErrorKind::Generic(err.into())))?
+        .join(";\n");
 
         if !sql.is_empty() {
             steps.push(PrettySqlMigrationStep {
@@ -131,12 +172,16 @@ fn render_steps_pretty(
     Ok(steps)
 }
 
+/// `citext` columns are only available once the extension of the same name is installed.
+const POSTGRES_CREATE_CITEXT_EXTENSION: &str = "CREATE EXTENSION IF NOT EXISTS citext";
+
 fn render_raw_sql(
     step: &SqlMigrationStep,
     renderer: &(dyn SqlRenderer + Send + Sync),
     database_info: &DatabaseInfo,
     current_schema: &SqlSchema,
     next_schema: &SqlSchema,
+    placeholder_defaults: &[(String, String)],
 ) -> Result<Vec<String>, anyhow::Error> {
     let sql_family = renderer.sql_family();
     let schema_name = database_info.connection_info().schema_name().to_string();
@@ -194,16 +239,24 @@ fn render_raw_sql(
                     write!(
                         create_table,
                         "FOREIGN KEY ({constrained_columns}) {references}{comma}",
-                        constrained_columns = fk.columns.iter().map(|col| format!(r#""{}""#, col)).join(","),
+                        constrained_columns = fk.columns.iter().map(|col| renderer.quote(col)).join(","),
                         references = renderer.render_references(&schema_name, fk),
                         comma = if fks.peek().is_some() { ",\n" } else { "" },
                     )?;
                 }
             }
 
-            write!(create_table, "\n) {}", create_table_suffix(sql_family))?;
+            write!(create_table, "\n) {}", create_table_suffix(renderer, &table.options))?;
+
+            let mut stmts = Vec::with_capacity(2);
+
+            if sql_family == SqlFamily::Postgres && table.columns.iter().any(|column| column.tpe.is_citext()) {
+                stmts.push(POSTGRES_CREATE_CITEXT_EXTENSION.to_owned());
+            }
 
-            Ok(vec![create_table])
+            stmts.push(create_table);
+
+            Ok(stmts)
         }
         SqlMigrationStep::DropTable(DropTable { name }) => Ok(vec![format!(
             "DROP TABLE {};",
@@ -254,7 +307,21 @@ fn render_raw_sql(
             }
         },
         SqlMigrationStep::AlterTable(AlterTable { table, changes }) => {
+            let mut stmts = Vec::with_capacity(2);
+
+            let adds_citext_columns = changes.iter().any(|change| match change {
+                TableChange::AddColumn(AddColumn { column }) | TableChange::AlterColumn(AlterColumn { column, .. }) => {
+                    column.tpe.is_citext()
+                }
+                _ => false,
+            });
+
+            if sql_family == SqlFamily::Postgres && adds_citext_columns {
+                stmts.push(POSTGRES_CREATE_CITEXT_EXTENSION.to_owned());
+            }
+
             let mut lines = Vec::new();
+            let mut updates = Vec::new();
             for change in changes {
                 match change {
                     TableChange::AddColumn(AddColumn { column }) => {
@@ -271,12 +338,24 @@ fn render_raw_sql(
                         lines.push(format!("DROP COLUMN {}", name));
                     }
                     TableChange::AlterColumn(AlterColumn { name, column }) => {
-                        match safe_alter_column(
-                            renderer,
-                            current_schema.get_table(&table.name).unwrap().column(&name).unwrap(),
-                            &column,
-                        ) {
+                        let column = ColumnRef {
+                            schema: next_schema,
+                            table,
+                            column,
+                        };
+
+                        let previous_column = current_schema.get_table(&table.name).unwrap().column(&name).unwrap();
+
+                        match safe_alter_column(renderer, &schema_name, previous_column, &column) {
                             Some(safe_sql) => {
+                                updates.extend(render_replace_nulls_with_default(
+                                    renderer,
+                                    &schema_name,
+                                    previous_column,
+                                    &column,
+                                    placeholder_defaults,
+                                ));
+
                                 for line in safe_sql {
                                     lines.push(line)
                                 }
@@ -284,11 +363,6 @@ fn render_raw_sql(
                             None => {
                                 let name = renderer.quote(&name);
                                 lines.push(format!("DROP COLUMN {}", name));
-                                let column = ColumnRef {
-                                    schema: next_schema,
-                                    table,
-                                    column,
-                                };
                                 let col_sql = renderer.render_column(&schema_name, column, true);
                                 lines.push(format!("ADD COLUMN {}", col_sql));
                             }
@@ -305,13 +379,40 @@ fn render_raw_sql(
                         }
                         _ => (),
                     },
+                    TableChange::AlterOptions(AlterOptions { options }) => {
+                        lines.extend(render_alter_table_options(renderer, options));
+                    }
+                    TableChange::AlterPrimaryKey(AlterPrimaryKey { columns }) => {
+                        let had_primary_key = current_schema
+                            .get_table(&table.name)
+                            .map(|previous_table| previous_table.primary_key.is_some())
+                            .unwrap_or(false);
+
+                        match sql_family {
+                            SqlFamily::Mysql if had_primary_key => lines.push("DROP PRIMARY KEY".to_owned()),
+                            // The primary key is dropped along with its columns, and Postgres names it after the table.
+                            SqlFamily::Postgres if had_primary_key => lines.push(format!(
+                                "DROP CONSTRAINT IF EXISTS {}",
+                                renderer.quote(&format!("{}_pkey", table.name))
+                            )),
+                            _ => (),
+                        }
+
+                        lines.push(format!(
+                            "ADD PRIMARY KEY ({})",
+                            columns.iter().map(|column| renderer.quote(column)).join(", ")
+                        ));
+                    }
                 };
             }
-            Ok(vec![format!(
+            stmts.extend(updates);
+            stmts.push(format!(
                 "ALTER TABLE {} {};",
                 renderer.quote_with_schema(&schema_name, &table.name),
                 lines.join(",\n")
-            )])
+            ));
+
+            Ok(stmts)
         }
         SqlMigrationStep::CreateIndex(CreateIndex { table, index }) => {
             Ok(vec![render_create_index(renderer, database_info, table, index)])
@@ -377,17 +478,60 @@ fn render_raw_sql(
             )]),
             SqlFamily::Sqlite => unimplemented!("Index renaming on SQLite."),
         },
+        SqlMigrationStep::CreateTrigger(CreateTrigger { trigger }) => match sql_family {
+            SqlFamily::Postgres => Ok(vec![
+                postgres_create_audit_function(&schema_name),
+                trigger.definition.clone(),
+            ]),
+            _ => Ok(vec![trigger.definition.clone()]),
+        },
+        SqlMigrationStep::DropTrigger(DropTrigger { table, name }) => match sql_family {
+            SqlFamily::Postgres => Ok(vec![format!(
+                "DROP TRIGGER {} ON {}",
+                renderer.quote(name),
+                renderer.quote_with_schema(&schema_name, table)
+            )]),
+            _ => Ok(vec![format!(
+                "DROP TRIGGER {}",
+                renderer.quote_with_schema(&schema_name, name)
+            )]),
+        },
         SqlMigrationStep::RawSql { raw } => Ok(vec![raw.to_owned()]),
     }
 }
 
+/// The function the audit triggers of `@@audited` models execute on Postgres. It records the changed row in the
+/// `{table}_history` table next to the table the trigger fired on.
+fn postgres_create_audit_function(schema_name: &str) -> String {
+    format!(
+        r#"CREATE OR REPLACE FUNCTION {schema}.{function}() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        EXECUTE format('INSERT INTO %I.%I (operation, changed_at, row_data) VALUES ($1, now(), $2)', TG_TABLE_SCHEMA, TG_TABLE_NAME || '_history') USING TG_OP, to_jsonb(OLD);
+        RETURN OLD;
+    END IF;
+
+    EXECUTE format('INSERT INTO %I.%I (operation, changed_at, row_data) VALUES ($1, now(), $2)', TG_TABLE_SCHEMA, TG_TABLE_NAME || '_history') USING TG_OP, to_jsonb(NEW);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql"#,
+        schema = postgres_quoted(schema_name),
+        function = POSTGRES_AUDIT_FUNCTION,
+    )
+}
+
 fn render_create_index(
     renderer: &dyn SqlRenderer,
     database_info: &DatabaseInfo,
     table_name: &str,
     index: &Index,
 ) -> String {
-    let Index { name, columns, tpe } = index;
+    let Index {
+        name,
+        columns,
+        tpe,
+        column_options: _,
+    } = index;
     let index_type = match tpe {
         IndexType::Unique => "UNIQUE",
         IndexType::Normal => "",
@@ -401,7 +545,10 @@ fn render_create_index(
         SqlFamily::Sqlite => renderer.quote(table_name),
         _ => renderer.quote_with_schema(database_info.connection_info().schema_name(), table_name),
     };
-    let columns: Vec<String> = columns.iter().map(|c| renderer.quote(c)).collect();
+    let columns: Vec<String> = columns
+        .iter()
+        .map(|c| render_index_column(renderer, sql_family, c, index.options_of_column(c)))
+        .collect();
 
     format!(
         "CREATE {} INDEX {} ON {}({})",
@@ -412,6 +559,26 @@ fn render_create_index(
     )
 }
 
+/// Renders a column of an index, with its prefix length (MySQL only) and sort order, like `"title"(100) DESC`.
+fn render_index_column(
+    renderer: &dyn SqlRenderer,
+    sql_family: SqlFamily,
+    column_name: &str,
+    options: IndexColumnOptions,
+) -> String {
+    let mut rendered = renderer.quote(column_name);
+
+    if let (SqlFamily::Mysql, Some(length)) = (sql_family, options.length) {
+        rendered.push_str(&format!("({})", length));
+    }
+
+    if options.sort_order == SortOrder::Desc {
+        rendered.push_str(" DESC");
+    }
+
+    rendered
+}
+
 fn mysql_drop_index(
     renderer: &dyn SqlRenderer,
     schema_name: &str,
@@ -427,24 +594,74 @@ fn mysql_drop_index(
     Ok(drop_index)
 }
 
-fn create_table_suffix(sql_family: SqlFamily) -> &'static str {
-    match sql_family {
-        SqlFamily::Sqlite => "",
-        SqlFamily::Postgres => "",
-        SqlFamily::Mysql => "\nDEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci",
+fn create_table_suffix(renderer: &dyn SqlRenderer, options: &TableOptions) -> String {
+    let mut suffix = Vec::new();
+
+    match renderer.sql_family() {
+        SqlFamily::Sqlite => (),
+        SqlFamily::Postgres => {
+            if let Some(fillfactor) = options.fillfactor {
+                suffix.push(format!("WITH (fillfactor = {})", fillfactor));
+            }
+
+            if let Some(tablespace) = &options.tablespace {
+                suffix.push(format!("TABLESPACE {}", renderer.quote(tablespace)));
+            }
+        }
+        SqlFamily::Mysql => {
+            if let Some(engine) = &options.engine {
+                suffix.push(format!("ENGINE = {}", engine));
+            }
+
+            match &options.charset {
+                Some(charset) => suffix.push(format!("DEFAULT CHARACTER SET {}", charset)),
+                None => suffix.push("DEFAULT CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci".to_owned()),
+            }
+        }
     }
+
+    suffix.iter().map(|option| format!("\n{}", option)).collect()
+}
+
+fn render_alter_table_options(renderer: &dyn SqlRenderer, options: &TableOptions) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    match renderer.sql_family() {
+        SqlFamily::Sqlite => (),
+        SqlFamily::Postgres => {
+            if let Some(fillfactor) = options.fillfactor {
+                lines.push(format!("SET (fillfactor = {})", fillfactor));
+            }
+
+            if let Some(tablespace) = &options.tablespace {
+                lines.push(format!("SET TABLESPACE {}", renderer.quote(tablespace)));
+            }
+        }
+        SqlFamily::Mysql => {
+            if let Some(engine) = &options.engine {
+                lines.push(format!("ENGINE = {}", engine));
+            }
+
+            if let Some(charset) = &options.charset {
+                lines.push(format!("CONVERT TO CHARACTER SET {}", charset));
+            }
+        }
+    }
+
+    lines
 }
 
 fn safe_alter_column(
     renderer: &dyn SqlRenderer,
+    schema_name: &str,
     previous_column: &Column,
-    next_column: &Column,
+    next_column: &ColumnRef<'_>,
 ) -> Option<Vec<String>> {
     use crate::sql_migration::expanded_alter_column::*;
 
     let expanded = crate::sql_migration::expanded_alter_column::expand_alter_column(
         previous_column,
-        next_column,
+        next_column.column,
         &renderer.sql_family(),
     )?;
 
@@ -455,14 +672,24 @@ fn safe_alter_column(
             .into_iter()
             .map(|step| match step {
                 PostgresAlterColumn::DropDefault => format!("{} DROP DEFAULT", &alter_column_prefix),
-                PostgresAlterColumn::SetDefault(new_default) => {
-                    format!("{} SET DEFAULT '{}'", &alter_column_prefix, new_default)
-                }
+                PostgresAlterColumn::SetDefault(new_default) => match &next_column.column_type().family {
+                    ColumnTypeFamily::Enum(enum_name) => format!(
+                        "{} SET DEFAULT {}",
+                        &alter_column_prefix,
+                        postgres_render_enum_default(&new_default, enum_name)
+                    ),
+                    _ => match crate::database_info::parse_uuid_function(&new_default) {
+                        Some(uuid_function) => format!("{} SET DEFAULT {}", &alter_column_prefix, uuid_function),
+                        None => format!("{} SET DEFAULT '{}'", &alter_column_prefix, new_default),
+                    },
+                },
                 PostgresAlterColumn::DropNotNull => format!("{} DROP NOT NULL", &alter_column_prefix),
+                PostgresAlterColumn::SetNotNull => format!("{} SET NOT NULL", &alter_column_prefix),
                 PostgresAlterColumn::SetType(ty) => format!(
-                    "{} SET DATA TYPE {}",
+                    "{} SET DATA TYPE {}{}",
                     &alter_column_prefix,
-                    postgres_render_column_type(&ty)
+                    postgres_render_column_type(&ty),
+                    postgres_render_using_cast(previous_column, &ty),
                 ),
             })
             .collect(),
@@ -473,6 +700,9 @@ fn safe_alter_column(
                 MysqlAlterColumn::SetDefault(new_default) => {
                     format!("{} SET DEFAULT '{}'", &alter_column_prefix, new_default)
                 }
+                MysqlAlterColumn::Modify => {
+                    format!("MODIFY {}", renderer.render_column(schema_name, *next_column, true))
+                }
             })
             .collect(),
         ExpandedAlterColumn::Sqlite(_steps) => vec![],
@@ -481,6 +711,56 @@ fn safe_alter_column(
     Some(steps)
 }
 
+/// Postgres only casts between some types implicitly, e.g. from int to text. The other type changes cast the values
+/// explicitly, enums through their text representation.
+fn postgres_render_using_cast(previous_column: &Column, next_type: &ColumnType) -> String {
+    if previous_column.tpe.family == next_type.family {
+        return String::new();
+    }
+
+    let array = if next_type.arity == ColumnArity::List { "[]" } else { "" };
+
+    let via_text = match (&previous_column.tpe.family, &next_type.family) {
+        (ColumnTypeFamily::Enum(_), ColumnTypeFamily::Enum(_)) => format!("::text{}", array),
+        _ => String::new(),
+    };
+
+    format!(
+        " USING ({column_name}{via_text}::{next_type})",
+        column_name = postgres_quoted(&previous_column.name),
+        via_text = via_text,
+        next_type = postgres_render_column_type(next_type).trim_end(),
+    )
+}
+
+/// Existing NULL values of a column that becomes required are replaced with the default the datamodel declares, so
+/// that the column can be altered in place. Without one, the destructive changes checker warns about the NULL values.
+fn render_replace_nulls_with_default(
+    renderer: &dyn SqlRenderer,
+    schema_name: &str,
+    previous_column: &Column,
+    next_column: &ColumnRef<'_>,
+    placeholder_defaults: &[(String, String)],
+) -> Option<String> {
+    if previous_column.tpe.arity != ColumnArity::Nullable
+        || !next_column.is_required()
+        || !has_declared_default(placeholder_defaults, next_column.table().name(), next_column.column)
+    {
+        return None;
+    }
+
+    let default = renderer.render_default_value(next_column)?;
+    let column_name = renderer.quote(&previous_column.name);
+
+    Some(format!(
+        "UPDATE {} SET {} = {} WHERE {} IS NULL;",
+        renderer.quote_with_schema(schema_name, next_column.table().name()),
+        column_name,
+        default,
+        column_name,
+    ))
+}
+
 fn render_create_enum(
     renderer: &(dyn SqlRenderer + Send + Sync),
     create_enum: &CreateEnum,
@@ -567,20 +847,34 @@ fn postgres_alter_enum(
             });
 
             for column in affected_columns {
-                let sql = format!(
+                // Enum arrays are cast element-wise through text arrays, and have no default.
+                let (array, default) = if column.column_type().arity == ColumnArity::List {
+                    ("[]", None)
+                } else {
+                    ("", column.default())
+                };
+
+                let mut sql = format!(
                     "ALTER TABLE {schema_name}.{table_name} \
                         ALTER COLUMN {column_name} DROP DEFAULT,
-                        ALTER COLUMN {column_name} TYPE {tmp_name} \
-                            USING ({old_name}::text::{tmp_name}),
-                        ALTER COLUMN {column_name} SET DEFAULT {new_enum_default}",
+                        ALTER COLUMN {column_name} TYPE {tmp_name}{array} \
+                            USING ({column_name}::text{array}::{tmp_name}{array})",
                     schema_name = postgres_quoted(schema_name),
                     table_name = postgres_quoted(column.table().name()),
                     column_name = postgres_quoted(column.name()),
                     tmp_name = postgres_quoted(&tmp_name),
-                    old_name = alter_enum.name,
-                    new_enum_default = postgres_quoted_string(new_enum.values.first().unwrap()),
+                    array = array,
                 );
 
+                if let Some(default) = default {
+                    write!(
+                        sql,
+                        ", ALTER COLUMN {column_name} SET DEFAULT {default}",
+                        column_name = postgres_quoted(column.name()),
+                        default = postgres_render_enum_default(default, &tmp_name),
+                    )?;
+                }
+
                 stmts.push(sql);
             }
         }
@@ -1,8 +1,8 @@
 mod sql_unexecutable_migration;
 
 use crate::{
-    AddColumn, AlterColumn, Component, DropColumn, DropTable, DropTables, SqlError, SqlMigration, SqlMigrationStep,
-    SqlResult, TableChange,
+    sql_migration::has_declared_default, sql_schema_differ::ColumnTypeChange, AddColumn, AlterColumn, Component,
+    DropColumn, DropTable, DropTables, SqlError, SqlMigration, SqlMigrationStep, SqlResult, TableChange,
 };
 use migration_connector::{
     ConnectorResult, DestructiveChangeDiagnostics, DestructiveChangesChecker, MigrationWarning, UnexecutableMigration,
@@ -156,15 +156,18 @@ impl SqlDestructiveChangesChecker<'_> {
     ///
     /// - renamings on SQLite
     /// - default changes on SQLite
-    /// - Arity changes from required to optional on SQLite
+    /// - Arity changes on SQLite
+    /// - Type changes that safely cast the values, e.g. from int to float
     ///
-    /// Are considered unexecutable:
+    /// Emit a warning:
     ///
-    /// - Making an optional column required without a default, when there are existing rows in the table.
+    /// - Making an optional column required without a default, when there are NULL values in the column.
+    /// - Type changes that cast the values, where the cast can fail or lose precision.
     async fn check_alter_column(
         &self,
         alter_column: &AlterColumn,
         previous_table: &sql_schema_describer::Table,
+        placeholder_defaults: &[(String, String)],
         diagnostics: &mut DestructiveChangeDiagnostics,
     ) -> SqlResult<()> {
         let previous_column = previous_table
@@ -176,16 +179,32 @@ impl SqlDestructiveChangesChecker<'_> {
             next: &alter_column.column,
         };
 
+        self.check_for_column_arity_change(previous_table, &differ, placeholder_defaults, diagnostics)
+            .await?;
+
         if self.alter_column_is_safe(&differ) {
             return Ok(());
         }
 
-        self.check_for_column_arity_change(&previous_table.name, &differ, diagnostics)
-            .await?;
-
         let values_count = self.count_values_in_column(&alter_column.name, previous_table).await?;
 
-        if values_count > 0 {
+        let type_change = differ.type_change(self.sql_family());
+
+        if values_count > 0
+            && type_change == Some(ColumnTypeChange::RiskyCast)
+            && self.alter_column_keeps_values(&differ)
+        {
+            diagnostics.add_warning(MigrationWarning {
+                description: format!(
+                    "You are about to change the type of the column `{column_name}` on the `{table_name}` table from `{previous_type}` to `{next_type}`, which still contains {values_count} non-null values. Casting some of the values may fail or lose precision.",
+                    column_name = alter_column.name,
+                    table_name = &previous_table.name,
+                    previous_type = previous_column.tpe.family,
+                    next_type = alter_column.column.tpe.family,
+                    values_count = values_count,
+                ),
+            })
+        } else if values_count > 0 {
             diagnostics.add_warning(MigrationWarning {
                 description: format!(
                                  "You are about to alter the column `{column_name}` on the `{table_name}` table, which still contains {values_count} non-null values. The data in that column will be lost.",
@@ -212,11 +231,16 @@ impl SqlDestructiveChangesChecker<'_> {
     fn alter_column_is_safe(&self, differ: &crate::sql_schema_differ::ColumnDiffer<'_>) -> bool {
         use crate::sql_migration::expanded_alter_column::*;
 
+        let type_change_is_safe = match differ.type_change(self.sql_family()) {
+            Some(ColumnTypeChange::SafeCast) | None => true,
+            Some(ColumnTypeChange::RiskyCast) | Some(ColumnTypeChange::NotCastable) => false,
+        };
+
         match self.sql_family() {
             SqlFamily::Sqlite => {
                 let arity_change_is_safe = match (&differ.previous.tpe.arity, &differ.next.tpe.arity) {
-                    // column became required
-                    (ColumnArity::Nullable, ColumnArity::Required) => false,
+                    // column became required, NULL values are checked separately
+                    (ColumnArity::Nullable, ColumnArity::Required) => true,
                     // column became nullable
                     (ColumnArity::Required, ColumnArity::Nullable) => true,
                     // nothing changed
@@ -227,7 +251,7 @@ impl SqlDestructiveChangesChecker<'_> {
                     (ColumnArity::List, _) | (_, ColumnArity::List) => unreachable!(),
                 };
 
-                !differ.all_changes().type_changed() && arity_change_is_safe
+                type_change_is_safe && arity_change_is_safe
             }
             SqlFamily::Postgres => {
                 let expanded = expand_postgres_alter_column(differ);
@@ -240,8 +264,9 @@ impl SqlDestructiveChangesChecker<'_> {
                         match step {
                             PostgresAlterColumn::SetDefault(_)
                             | PostgresAlterColumn::DropDefault
-                            | PostgresAlterColumn::DropNotNull => (),
-                            PostgresAlterColumn::SetType(_) => is_safe = false,
+                            | PostgresAlterColumn::DropNotNull
+                            | PostgresAlterColumn::SetNotNull => (),
+                            PostgresAlterColumn::SetType(_) => is_safe = type_change_is_safe,
                         }
                     }
 
@@ -255,11 +280,12 @@ impl SqlDestructiveChangesChecker<'_> {
 
                 // We keep the match here to keep the exhaustiveness checking for when we add variants.
                 if let Some(steps) = expanded {
-                    let is_safe = true;
+                    let mut is_safe = true;
 
                     for step in steps {
                         match step {
                             MysqlAlterColumn::SetDefault(_) | MysqlAlterColumn::DropDefault => (),
+                            MysqlAlterColumn::Modify => is_safe = type_change_is_safe,
                         }
                     }
 
@@ -271,30 +297,56 @@ impl SqlDestructiveChangesChecker<'_> {
         }
     }
 
+    /// Whether the values of the column survive the migration, as opposed to the column being dropped and recreated.
+    fn alter_column_keeps_values(&self, differ: &crate::sql_schema_differ::ColumnDiffer<'_>) -> bool {
+        use crate::sql_migration::expanded_alter_column::*;
+
+        match self.sql_family() {
+            // The values are copied into the redefined table.
+            SqlFamily::Sqlite => true,
+            SqlFamily::Postgres => expand_postgres_alter_column(differ).is_some(),
+            SqlFamily::Mysql => expand_mysql_alter_column(differ).is_some(),
+        }
+    }
+
+    /// Making a column required fails when it contains NULL values and the datamodel declares no default to replace
+    /// them with.
     async fn check_for_column_arity_change(
         &self,
-        table_name: &str,
+        previous_table: &sql_schema_describer::Table,
         differ: &crate::sql_schema_differ::ColumnDiffer<'_>,
+        placeholder_defaults: &[(String, String)],
         diagnostics: &mut DestructiveChangeDiagnostics,
     ) -> SqlResult<()> {
-        let rows_count = self.count_rows_in_table(table_name).await?;
-
         if !differ.all_changes().arity_changed()
             || !differ.next.tpe.arity.is_required()
-            || rows_count == 0
-            || differ.next.default.is_some()
+            || has_declared_default(placeholder_defaults, &previous_table.name, differ.next)
         {
             return Ok(());
         }
 
-        let typed_unexecutable = sql_unexecutable_migration::SqlUnexecutableMigration::MadeOptionalFieldRequired {
-            table: table_name.to_owned(),
-            column: differ.previous.name.clone(),
+        let rows_count = self.count_rows_in_table(&previous_table.name).await?;
+
+        // Columns that can't be altered in place are recreated without values.
+        let null_values_count = if self.alter_column_is_safe(differ) {
+            rows_count
+                - self
+                    .count_values_in_column(&differ.previous.name, previous_table)
+                    .await?
+        } else {
+            rows_count
         };
 
-        diagnostics.unexecutable_migrations.push(UnexecutableMigration {
-            description: format!("{}", typed_unexecutable),
-        });
+        if null_values_count > 0 {
+            diagnostics.add_warning(MigrationWarning {
+                description: format!(
+                    "You are about to make the column `{column_name}` on the `{table_name}` table required, but there are {null_values_count} existing NULL values. Add a default value to the field, or replace the NULL values before migrating.",
+                    column_name = differ.previous.name,
+                    table_name = previous_table.name,
+                    null_values_count = null_values_count,
+                ),
+            })
+        }
 
         Ok(())
     }
@@ -303,6 +355,7 @@ impl SqlDestructiveChangesChecker<'_> {
         &self,
         steps: &[SqlMigrationStep],
         before: &SqlSchema,
+        placeholder_defaults: &[(String, String)],
     ) -> SqlResult<DestructiveChangeDiagnostics> {
         let mut diagnostics = DestructiveChangeDiagnostics::new();
 
@@ -321,8 +374,13 @@ impl SqlDestructiveChangesChecker<'_> {
                                         .await?
                                 }
                                 TableChange::AlterColumn(ref alter_column) => {
-                                    self.check_alter_column(alter_column, before_table, &mut diagnostics)
-                                        .await?
+                                    self.check_alter_column(
+                                        alter_column,
+                                        before_table,
+                                        placeholder_defaults,
+                                        &mut diagnostics,
+                                    )
+                                    .await?
                                 }
                                 TableChange::AddColumn(ref add_column) => {
                                     self.check_add_column(add_column, before_table, &mut diagnostics)
@@ -356,14 +414,22 @@ impl SqlDestructiveChangesChecker<'_> {
 #[async_trait::async_trait]
 impl DestructiveChangesChecker<SqlMigration> for SqlDestructiveChangesChecker<'_> {
     async fn check(&self, database_migration: &SqlMigration) -> ConnectorResult<DestructiveChangeDiagnostics> {
-        self.check_impl(&database_migration.original_steps, &database_migration.before)
-            .await
-            .map_err(|sql_error| sql_error.into_connector_error(&self.connection_info()))
+        self.check_impl(
+            &database_migration.original_steps,
+            &database_migration.before,
+            &database_migration.placeholder_defaults,
+        )
+        .await
+        .map_err(|sql_error| sql_error.into_connector_error(&self.connection_info()))
     }
 
     async fn check_unapply(&self, database_migration: &SqlMigration) -> ConnectorResult<DestructiveChangeDiagnostics> {
-        self.check_impl(&database_migration.rollback, &database_migration.after)
-            .await
-            .map_err(|sql_error| sql_error.into_connector_error(&self.connection_info()))
+        self.check_impl(
+            &database_migration.rollback,
+            &database_migration.after,
+            &database_migration.rollback_placeholder_defaults,
+        )
+        .await
+        .map_err(|sql_error| sql_error.into_connector_error(&self.connection_info()))
     }
 }
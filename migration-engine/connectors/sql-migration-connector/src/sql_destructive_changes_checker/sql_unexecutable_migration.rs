@@ -5,10 +5,6 @@ pub(crate) enum SqlUnexecutableMigration {
         column: String,
         rows_count: Option<u64>,
     },
-    MadeOptionalFieldRequired {
-        table: String,
-        column: String,
-    },
     // TODO:
     // AddedUnimplementableUniqueConstraint {
     //     table: String,
@@ -30,9 +26,6 @@ impl std::fmt::Display for SqlUnexecutableMigration {
             SqlUnexecutableMigration::AddedRequiredFieldToTable { table, column, rows_count } => {
                 write!(f, "Added the required column `{column}` to the `{table}` table without a default value. There are {rows_count:?} rows in this table, it is not possible.", table = table, column = column, rows_count = rows_count)?
             },
-            SqlUnexecutableMigration::MadeOptionalFieldRequired { table, column } => {
-                write!(f, "Made the column `{column}` on table `{table}` required, but there are existing NULL values.", column = column, table = table)?
-            },
             // TODO
             //
             // SqlUnexecutableMigration::AddedUnimplementableUniqueConstraint { table, constrained_columns } => write!(f, "Added a unique constraint that would not hold given existing data on `{table}`.{constrained_columns:?}", table = table, constrained_columns = constrained_columns)?,
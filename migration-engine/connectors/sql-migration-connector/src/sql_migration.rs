@@ -2,7 +2,7 @@ pub(crate) mod expanded_alter_column;
 
 use migration_connector::DatabaseMigrationMarker;
 use serde::{Deserialize, Serialize};
-use sql_schema_describer::{Column, ForeignKey, Index, SqlSchema, Table};
+use sql_schema_describer::{Column, ForeignKey, Index, SqlSchema, Table, TableOptions, Trigger};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SqlMigration {
@@ -14,6 +14,14 @@ pub struct SqlMigration {
     /// by the database.
     pub corrected_steps: Vec<SqlMigrationStep>,
     pub rollback: Vec<SqlMigrationStep>,
+    /// The columns of `after` whose default only exists so that required columns can be added to tables with rows,
+    /// by table and column name. The datamodel declares no default for them, so their NULL values are not replaced
+    /// with it when they become required.
+    #[serde(default)]
+    pub placeholder_defaults: Vec<(String, String)>,
+    /// The `placeholder_defaults` of `before`, for the `rollback`.
+    #[serde(default)]
+    pub rollback_placeholder_defaults: Vec<(String, String)>,
 }
 
 impl SqlMigration {
@@ -24,10 +32,20 @@ impl SqlMigration {
             original_steps: Vec::new(),
             corrected_steps: Vec::new(),
             rollback: Vec::new(),
+            placeholder_defaults: Vec::new(),
+            rollback_placeholder_defaults: Vec::new(),
         }
     }
 }
 
+/// Whether the default of the column is declared in the datamodel, see `SqlMigration::placeholder_defaults`.
+pub(crate) fn has_declared_default(placeholder_defaults: &[(String, String)], table: &str, column: &Column) -> bool {
+    column.default.is_some()
+        && !placeholder_defaults
+            .iter()
+            .any(|(table_name, column_name)| table_name == table && column_name == &column.name)
+}
+
 impl DatabaseMigrationMarker for SqlMigration {
     fn serialize(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap()
@@ -49,6 +67,8 @@ pub enum SqlMigrationStep {
     CreateEnum(CreateEnum),
     DropEnum(DropEnum),
     AlterEnum(AlterEnum),
+    CreateTrigger(CreateTrigger),
+    DropTrigger(DropTrigger),
 }
 
 /// A helper struct to serialize an [SqlMigrationStep](/sql-migration/enum.SqlMigrationStep.html)
@@ -89,6 +109,8 @@ pub enum TableChange {
     /// This is actually producing SQL only on MySQL, where we have to drop the foreign key
     /// constraint before any column that is part of it.
     DropForeignKey(DropForeignKey),
+    AlterOptions(AlterOptions),
+    AlterPrimaryKey(AlterPrimaryKey),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -107,6 +129,18 @@ pub struct AlterColumn {
     pub column: Column,
 }
 
+/// Only the options that change are set.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlterOptions {
+    pub options: TableOptions,
+}
+
+/// Replaces the primary key of the table. The columns are in the order of the new primary key.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlterPrimaryKey {
+    pub columns: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AddForeignKey {
     pub table: String,
@@ -160,3 +194,15 @@ impl AlterEnum {
         self.created_variants.is_empty() && self.dropped_variants.is_empty()
     }
 }
+
+/// Only the audit triggers of `@@audited` models are created and dropped by migrations.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CreateTrigger {
+    pub trigger: Trigger,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DropTrigger {
+    pub table: String,
+    pub name: String,
+}
@@ -1,6 +1,6 @@
-use crate::sql_schema_differ::{ColumnChange, ColumnDiffer};
+use crate::sql_schema_differ::{ColumnChange, ColumnDiffer, ColumnTypeChange};
 use quaint::prelude::SqlFamily;
-use sql_schema_describer::{Column, ColumnArity, ColumnType, ColumnTypeFamily};
+use sql_schema_describer::{Column, ColumnArity, ColumnType};
 
 pub(crate) fn expand_alter_column(
     previous_column: &Column,
@@ -32,10 +32,30 @@ pub(crate) fn expand_mysql_alter_column(columns: &ColumnDiffer) -> Option<Vec<My
                 (_, Some(next_default)) => changes.push(MysqlAlterColumn::SetDefault(next_default.clone())),
                 (_, None) => changes.push(MysqlAlterColumn::DropDefault),
             },
+            ColumnChange::OnUpdate => changes.push(MysqlAlterColumn::Modify),
+            // The redefined column converts the values to the new type.
+            ColumnChange::Type => match columns.type_change(SqlFamily::Mysql) {
+                Some(ColumnTypeChange::SafeCast) | Some(ColumnTypeChange::RiskyCast) => {
+                    changes.push(MysqlAlterColumn::Modify)
+                }
+                _ => return None,
+            },
+            ColumnChange::Arity => match (&columns.previous.tpe.arity, &columns.next.tpe.arity) {
+                (ColumnArity::Nullable, ColumnArity::Required) => changes.push(MysqlAlterColumn::Modify),
+                _ => return None,
+            },
             _ => return None,
         }
     }
 
+    // The redefined column includes the other changes.
+    if changes.iter().any(|change| match change {
+        MysqlAlterColumn::Modify => true,
+        _ => false,
+    }) {
+        return Some(vec![MysqlAlterColumn::Modify]);
+    }
+
     Some(changes)
 }
 
@@ -50,15 +70,27 @@ pub(crate) fn expand_postgres_alter_column(columns: &ColumnDiffer) -> Option<Vec
             },
             ColumnChange::Arity => match (&columns.previous.tpe.arity, &columns.next.tpe.arity) {
                 (ColumnArity::Required, ColumnArity::Nullable) => changes.push(PostgresAlterColumn::DropNotNull),
+                (ColumnArity::Nullable, ColumnArity::Required) => changes.push(PostgresAlterColumn::SetNotNull),
                 _ => return None,
             },
-            ColumnChange::Type => match (&columns.previous.tpe.family, &columns.next.tpe.family) {
-                // Ints can be cast to text.
-                (ColumnTypeFamily::Int, ColumnTypeFamily::String) => {
-                    changes.push(PostgresAlterColumn::SetType(columns.next.tpe.clone()))
+            ColumnChange::Type => match columns.type_change(SqlFamily::Postgres) {
+                Some(ColumnTypeChange::SafeCast) | Some(ColumnTypeChange::RiskyCast) => {
+                    // The previous default can't always be cast, it is set again after the type change.
+                    if columns.previous.default.is_some() {
+                        changes.push(PostgresAlterColumn::DropDefault);
+                    }
+
+                    changes.push(PostgresAlterColumn::SetType(columns.next.tpe.clone()));
+
+                    if let (Some(next_default), false) =
+                        (&columns.next.default, columns.all_changes().default_changed())
+                    {
+                        changes.push(PostgresAlterColumn::SetDefault(next_default.clone()));
+                    }
                 }
                 _ => return None,
             },
+            ColumnChange::OnUpdate => return None,
             ColumnChange::Renaming => unreachable!("column renaming"),
         }
     }
@@ -79,9 +111,11 @@ pub(crate) enum PostgresAlterColumn {
     SetDefault(String),
     DropDefault,
     DropNotNull,
+    /// Existing NULL values have to be replaced first.
+    SetNotNull,
+    /// The values are cast to the new type, with a `USING` expression where Postgres has no implicit cast.
     SetType(ColumnType),
     // Not used yet:
-    // SetNotNull,
     // Rename { previous_name: String, next_name: String },
 }
 
@@ -90,6 +124,8 @@ pub(crate) enum PostgresAlterColumn {
 pub(crate) enum MysqlAlterColumn {
     SetDefault(String),
     DropDefault,
+    /// Redefines the whole column, e.g. to change its `ON UPDATE` clause.
+    Modify,
     // Not used yet:
     // Rename { previous_name: String, next_name: String },
 }
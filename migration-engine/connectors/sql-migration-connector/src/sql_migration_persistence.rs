@@ -20,25 +20,38 @@ impl Component for SqlMigrationPersistence<'_> {
 impl MigrationPersistence for SqlMigrationPersistence<'_> {
     async fn init(&self) -> Result<(), ConnectorError> {
         let fut = async {
-            let sql_str = match self.sql_family() {
-                SqlFamily::Sqlite => {
-                    let mut m = barrel::Migration::new().schema(self.schema_name());
-                    m.create_table_if_not_exists(TABLE_NAME, migration_table_setup_sqlite);
-                    m.make_from(barrel::SqlVariant::Sqlite)
-                }
-                SqlFamily::Postgres => {
-                    let mut m = barrel::Migration::new().schema(self.schema_name());
-                    m.create_table(TABLE_NAME, migration_table_setup_postgres);
-                    m.make_from(barrel::SqlVariant::Pg)
-                }
-                SqlFamily::Mysql => {
-                    let mut m = barrel::Migration::new().schema(self.schema_name());
-                    m.create_table(TABLE_NAME, migration_table_setup_mysql);
-                    m.make_from(barrel::SqlVariant::Mysql)
+            let schema = self.describe().await?;
+
+            let table = match schema.get_table(TABLE_NAME) {
+                Some(table) => table,
+                None => {
+                    self.conn().query_raw(&self.create_table_sql(), &[]).await?;
+                    self.connector.invalidate_described_schema();
+
+                    return Ok(());
                 }
             };
 
-            self.conn().query_raw(&sql_str, &[]).await.ok();
+            // Migration tables created before checksums and logs were stored lack their columns.
+            let missing_columns = [CHECKSUM_COLUMN, LOGS_COLUMN]
+                .iter()
+                .filter(|column| !table.has_column(column));
+
+            for column in missing_columns {
+                let add_column = format!(
+                    "ALTER TABLE {} ADD COLUMN {} {}",
+                    self.quoted_table_name(),
+                    column,
+                    match self.sql_family() {
+                        SqlFamily::Mysql => "LONGTEXT",
+                        _ => "TEXT",
+                    }
+                );
+
+                self.conn().query_raw(&add_column, &[]).await?;
+            }
+
+            self.connector.invalidate_described_schema();
 
             Ok(())
         };
@@ -101,6 +114,7 @@ impl MigrationPersistence for SqlMigrationPersistence<'_> {
         let model_steps_json = serde_json::to_string(&migration.datamodel_steps).unwrap();
         let database_migration_json = serde_json::to_string(&migration.database_migration).unwrap();
         let errors_json = serde_json::to_string(&migration.errors).unwrap();
+        let logs_json = serde_json::to_string(&migration.logs).unwrap();
 
         let insert = Insert::single_into(self.table())
             .value(DATAMODEL_COLUMN, migration.datamodel_string)
@@ -109,8 +123,10 @@ impl MigrationPersistence for SqlMigrationPersistence<'_> {
             .value(APPLIED_COLUMN, migration.applied)
             .value(ROLLED_BACK_COLUMN, migration.rolled_back)
             .value(DATAMODEL_STEPS_COLUMN, model_steps_json)
+            .value(CHECKSUM_COLUMN, migration.checksum)
             .value(DATABASE_MIGRATION_COLUMN, database_migration_json)
             .value(ERRORS_COLUMN, errors_json)
+            .value(LOGS_COLUMN, logs_json)
             .value(STARTED_AT_COLUMN, self.convert_datetime(migration.started_at))
             .value(FINISHED_AT_COLUMN, ParameterizedValue::Null);
 
@@ -141,12 +157,14 @@ impl MigrationPersistence for SqlMigrationPersistence<'_> {
                 None => ParameterizedValue::Null,
             };
             let errors_json = serde_json::to_string(&params.errors).unwrap();
+            let logs_json = serde_json::to_string(&params.logs).unwrap();
             let query = Update::table(self.table())
                 .set(NAME_COLUMN, params.new_name.clone())
                 .set(STATUS_COLUMN, params.status.code())
                 .set(APPLIED_COLUMN, params.applied)
                 .set(ROLLED_BACK_COLUMN, params.rolled_back)
                 .set(ERRORS_COLUMN, errors_json)
+                .set(LOGS_COLUMN, logs_json)
                 .set(FINISHED_AT_COLUMN, finished_at_value)
                 .so_that(
                     NAME_COLUMN
@@ -186,13 +204,34 @@ fn migration_table_setup(
     t.add_column(APPLIED_COLUMN, types::integer());
     t.add_column(ROLLED_BACK_COLUMN, types::integer());
     t.add_column(DATAMODEL_STEPS_COLUMN, unlimited_text_type.clone());
+    t.add_column(CHECKSUM_COLUMN, unlimited_text_type.clone().nullable(true));
     t.add_column(DATABASE_MIGRATION_COLUMN, unlimited_text_type.clone());
     t.add_column(ERRORS_COLUMN, unlimited_text_type.clone());
+    t.add_column(LOGS_COLUMN, unlimited_text_type.clone().nullable(true));
     t.add_column(STARTED_AT_COLUMN, datetime_type.clone());
     t.add_column(FINISHED_AT_COLUMN, datetime_type.clone().nullable(true));
 }
 
 impl<'a> SqlMigrationPersistence<'a> {
+    fn create_table_sql(&self) -> String {
+        let mut m = barrel::Migration::new().schema(self.schema_name());
+
+        match self.sql_family() {
+            SqlFamily::Sqlite => {
+                m.create_table_if_not_exists(TABLE_NAME, migration_table_setup_sqlite);
+                m.make_from(barrel::SqlVariant::Sqlite)
+            }
+            SqlFamily::Postgres => {
+                m.create_table(TABLE_NAME, migration_table_setup_postgres);
+                m.make_from(barrel::SqlVariant::Pg)
+            }
+            SqlFamily::Mysql => {
+                m.create_table(TABLE_NAME, migration_table_setup_mysql);
+                m.make_from(barrel::SqlVariant::Mysql)
+            }
+        }
+    }
+
     fn table(&self) -> Table {
         match self.sql_family() {
             SqlFamily::Sqlite => {
@@ -203,6 +242,14 @@ impl<'a> SqlMigrationPersistence<'a> {
         }
     }
 
+    fn quoted_table_name(&self) -> String {
+        match self.sql_family() {
+            SqlFamily::Sqlite => format!("\"{}\"", TABLE_NAME),
+            SqlFamily::Postgres => format!("\"{}\".\"{}\"", self.schema_name(), TABLE_NAME),
+            SqlFamily::Mysql => format!("`{}`.`{}`", self.schema_name(), TABLE_NAME),
+        }
+    }
+
     fn convert_datetime(&self, datetime: DateTime<Utc>) -> ParameterizedValue {
         match self.sql_family() {
             SqlFamily::Sqlite => ParameterizedValue::Integer(datetime.timestamp_millis()),
@@ -244,13 +291,24 @@ fn parse_rows_new(result_set: ResultSet) -> Vec<Migration> {
                 x => Some(convert_parameterized_date_value(x)),
             };
 
-            let datamodel_steps =
+            let datamodel_steps: Vec<MigrationStep> =
                 serde_json::from_str(&datamodel_steps_json).expect("Error parsing the migration steps");
 
+            // Migrations applied before checksums were stored are checked against their stored steps.
+            let checksum = row[CHECKSUM_COLUMN]
+                .to_string()
+                .unwrap_or_else(|| Migration::checksum_of_steps(&datamodel_steps));
+
             let database_migration_json =
                 serde_json::from_str(&database_migration_string).expect("Error parsing the database migration steps");
             let errors: Vec<String> = serde_json::from_str(&errors_json).unwrap();
 
+            // Migrations applied before the logs were stored have none.
+            let logs: Vec<ExecutedStatement> = row[LOGS_COLUMN]
+                .to_string()
+                .map(|logs_json| serde_json::from_str(&logs_json).expect("Error parsing the migration logs"))
+                .unwrap_or_default();
+
             Migration {
                 name: row[NAME_COLUMN].to_string().unwrap(),
                 revision: row[REVISION_COLUMN].as_i64().unwrap() as usize,
@@ -259,8 +317,10 @@ fn parse_rows_new(result_set: ResultSet) -> Vec<Migration> {
                 applied: row[APPLIED_COLUMN].as_i64().unwrap() as usize,
                 rolled_back: row[ROLLED_BACK_COLUMN].as_i64().unwrap() as usize,
                 datamodel_steps,
+                checksum,
                 database_migration: database_migration_json,
                 errors,
+                logs,
                 started_at: convert_parameterized_date_value(&row[STARTED_AT_COLUMN]),
                 finished_at,
             }
@@ -276,7 +336,9 @@ static STATUS_COLUMN: &str = "status";
 static APPLIED_COLUMN: &str = "applied";
 static ROLLED_BACK_COLUMN: &str = "rolled_back";
 static DATAMODEL_STEPS_COLUMN: &str = "datamodel_steps";
+static CHECKSUM_COLUMN: &str = "checksum";
 static DATABASE_MIGRATION_COLUMN: &str = "database_migration";
 static ERRORS_COLUMN: &str = "errors";
+static LOGS_COLUMN: &str = "logs";
 static STARTED_AT_COLUMN: &str = "started_at";
 static FINISHED_AT_COLUMN: &str = "finished_at";
@@ -11,19 +11,35 @@ pub(crate) fn render_nullability(column: &ColumnRef<'_>) -> &'static str {
 }
 
 pub(crate) fn render_default(column: &ColumnRef<'_>) -> String {
-    match column.default() {
-        Some(value) => match &column.column_type().family {
-            ColumnTypeFamily::String | ColumnTypeFamily::DateTime | ColumnTypeFamily::Enum(_) => format!(
-                "DEFAULT '{}'",
-                // TODO: remove once sql-schema-describer does unescaping, and perform escaping again here.
-                value
-                    .trim_matches('\\')
-                    .trim_matches('"')
-                    .trim_matches('\'')
-                    .trim_matches('\\')
-            ),
-            _ => format!("DEFAULT {}", value),
-        },
+    match render_default_value(column) {
+        Some(value) => format!("DEFAULT {}", value),
+        None => "".to_string(),
+    }
+}
+
+/// The default of the column as an expression, e.g. to assign it in an `UPDATE`.
+pub(crate) fn render_default_value(column: &ColumnRef<'_>) -> Option<String> {
+    column.default().map(|value| match &column.column_type().family {
+        ColumnTypeFamily::String | ColumnTypeFamily::DateTime | ColumnTypeFamily::Enum(_) => format!(
+            "'{}'",
+            // TODO: remove once sql-schema-describer does unescaping, and perform escaping again here.
+            value
+                .trim_matches('\\')
+                .trim_matches('"')
+                .trim_matches('\'')
+                .trim_matches('\\')
+        ),
+        _ => value.to_owned(),
+    })
+}
+
+pub(crate) fn render_generated(column: &ColumnRef<'_>) -> String {
+    match column.generated() {
+        Some(generated) => format!(
+            "GENERATED ALWAYS AS ({}) {}",
+            generated.expression,
+            generated.kind.as_str()
+        ),
         None => "".to_string(),
     }
 }
@@ -10,11 +10,11 @@ pub(crate) use common::IteratorJoin;
 pub(crate) use mysql_renderer::{quoted as mysql_quoted, quoted_string as mysql_quoted_string};
 pub(crate) use postgres_renderer::{
     quoted as postgres_quoted, quoted_string as postgres_quoted_string,
-    render_column_type as postgres_render_column_type,
+    render_column_type as postgres_render_column_type, render_enum_default as postgres_render_enum_default,
 };
 pub(crate) use sqlite_renderer::quoted as sqlite_quoted;
 
-use crate::sql_schema_helpers::ColumnRef;
+use crate::{sql_schema_helpers::ColumnRef, DatabaseInfo};
 use mysql_renderer::MySqlRenderer;
 use postgres_renderer::PostgresRenderer;
 use sqlite_renderer::SqliteRenderer;
@@ -42,14 +42,21 @@ pub(crate) trait SqlRenderer {
 
     fn render_references(&self, schema_name: &str, foreign_key: &ForeignKey) -> String;
 
+    /// The default of the column as an expression, without the `DEFAULT` keyword.
+    fn render_default_value(&self, column: &ColumnRef<'_>) -> Option<String> {
+        common::render_default_value(column)
+    }
+
     fn sql_family(&self) -> SqlFamily;
 }
 
 impl dyn SqlRenderer {
-    pub fn for_family<'a>(sql_family: &SqlFamily) -> Box<dyn SqlRenderer + Send + Sync + 'a> {
-        match sql_family {
+    pub fn for_database<'a>(database_info: &DatabaseInfo) -> Box<dyn SqlRenderer + Send + Sync + 'a> {
+        match database_info.sql_family() {
             SqlFamily::Postgres => Box::new(PostgresRenderer {}),
-            SqlFamily::Mysql => Box::new(MySqlRenderer {}),
+            SqlFamily::Mysql => Box::new(MySqlRenderer {
+                is_mariadb: database_info.is_mariadb(),
+            }),
             SqlFamily::Sqlite => Box::new(SqliteRenderer {}),
         }
     }
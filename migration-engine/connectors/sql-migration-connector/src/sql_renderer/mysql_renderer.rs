@@ -1,11 +1,15 @@
 use super::common::*;
 use crate::{sql_schema_helpers::ColumnRef, SqlFamily};
+use sql_identifier::Quoted;
 use sql_schema_describer::*;
 use std::fmt::Write as _;
 
 const VARCHAR_LENGTH_PREFIX: &str = "(191)";
 
-pub struct MySqlRenderer {}
+pub struct MySqlRenderer {
+    /// MariaDB speaks the MySQL protocol, but differs in the column types it supports.
+    pub is_mariadb: bool,
+}
 
 impl super::SqlRenderer for MySqlRenderer {
     fn sql_family(&self) -> SqlFamily {
@@ -23,16 +27,31 @@ impl super::SqlRenderer for MySqlRenderer {
     fn render_column(&self, _schema_name: &str, column: ColumnRef<'_>, _add_fk_prefix: bool) -> String {
         let column_name = self.quote(column.name());
         let tpe_str = self.render_column_type(&column).unwrap();
+        // The generation must directly follow the type.
+        let tpe_str = match column.generated() {
+            Some(_) => format!("{} {}", tpe_str, render_generated(&column)),
+            None => tpe_str,
+        };
         let nullability_str = render_nullability(&column);
         let default_str = render_default(&column);
+        let default_str = if column.on_update_current_timestamp() {
+            // The precision must match the one of the `datetime(3)` column.
+            format!("{} ON UPDATE CURRENT_TIMESTAMP(3)", default_str)
+        } else {
+            default_str
+        };
         let foreign_key = column.table().foreign_key_for_column(column.name());
         let auto_increment_str = if column.auto_increment() { "AUTO_INCREMENT" } else { "" };
+        let check_str = self.render_check(&column);
 
         match foreign_key {
-            Some(_) => format!("{} {} {} {}", column_name, tpe_str, nullability_str, default_str),
-            None => format!(
+            Some(_) => format!(
                 "{} {} {} {} {}",
-                column_name, tpe_str, nullability_str, default_str, auto_increment_str
+                column_name, tpe_str, nullability_str, default_str, check_str
+            ),
+            None => format!(
+                "{} {} {} {} {} {}",
+                column_name, tpe_str, nullability_str, default_str, auto_increment_str, check_str
             ),
         }
     }
@@ -45,9 +64,8 @@ impl super::SqlRenderer for MySqlRenderer {
             .join(",");
 
         format!(
-            "REFERENCES `{}`.`{}`({}) {}",
-            schema_name,
-            foreign_key.referenced_table,
+            "REFERENCES {}({}) {}",
+            self.quote_with_schema(schema_name, &foreign_key.referenced_table),
             referenced_columns,
             render_on_delete(&foreign_key.on_delete_action)
         )
@@ -59,8 +77,14 @@ impl MySqlRenderer {
         match &column.column_type().family {
             ColumnTypeFamily::Boolean => Ok(format!("boolean")),
             ColumnTypeFamily::DateTime => Ok(format!("datetime(3)")),
-            ColumnTypeFamily::Float => Ok(format!("Decimal(65,30)")),
+            ColumnTypeFamily::Float => match column.column_type().decimal_precision() {
+                Some((precision, scale)) => Ok(format!("Decimal({},{})", precision, scale)),
+                None => Ok(format!("Decimal(65,30)")),
+            },
             ColumnTypeFamily::Int => Ok(format!("int")),
+            // JSON is an alias of LONGTEXT on MariaDB, see `render_check` for the validation.
+            ColumnTypeFamily::Json if self.is_mariadb => Ok(format!("longtext")),
+            ColumnTypeFamily::Json => Ok(format!("json")),
             // we use varchar right now as mediumtext doesn't allow default values
             // a bigger length would not allow to use such a column as primary key
             ColumnTypeFamily::String => Ok(format!("varchar{}", VARCHAR_LENGTH_PREFIX)),
@@ -74,25 +98,23 @@ impl MySqlRenderer {
 
                 Ok(format!("ENUM({})", variants))
             }
+            ColumnTypeFamily::Unknown => Ok(column.column_type().raw.clone()),
             x => unimplemented!("{:?} not handled yet", x),
         }
     }
-}
 
-pub(crate) fn quoted<T: std::fmt::Display>(t: T) -> MysqlQuoted<T> {
-    MysqlQuoted(t)
+    /// MariaDB only validates JSON through a `CHECK` constraint, which the `JSON` alias adds implicitly since 10.4.3.
+    /// It is rendered explicitly so that the column is the same on all versions.
+    fn render_check(&self, column: &ColumnRef<'_>) -> String {
+        match &column.column_type().family {
+            ColumnTypeFamily::Json if self.is_mariadb => format!("CHECK (json_valid({}))", self.quote(column.name())),
+            _ => String::new(),
+        }
+    }
 }
 
-#[derive(Debug)]
-pub(crate) struct MysqlQuoted<T>(T);
-
-impl<T> std::fmt::Display for MysqlQuoted<T>
-where
-    T: std::fmt::Display,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "`{}`", self.0)
-    }
+pub(crate) fn quoted<T: std::fmt::Display>(t: T) -> Quoted<T> {
+    Quoted::mysql(t)
 }
 
 pub(crate) fn quoted_string<T: std::fmt::Display>(t: T) -> MysqlQuotedString<T> {
@@ -1,5 +1,6 @@
 use super::common::*;
-use crate::{sql_schema_helpers::*, SqlFamily};
+use crate::{database_info::parse_uuid_function, sql_schema_helpers::*, SqlFamily};
+use sql_identifier::Quoted;
 use sql_schema_describer::*;
 use std::fmt::Write as _;
 
@@ -21,25 +22,38 @@ impl super::SqlRenderer for PostgresRenderer {
         let column_name = self.quote(column.name());
         let tpe_str = render_column_type(column.column_type());
         let nullability_str = render_nullability(&column);
-        let default_str = render_default(&column);
+        let default_str = render_postgres_default(&column);
+        let generated_str = render_generated(&column);
         let is_serial = column.auto_increment();
 
         if is_serial {
             format!("{} SERIAL", column_name)
         } else {
-            format!("{} {} {} {}", column_name, tpe_str, nullability_str, default_str)
+            format!(
+                "{} {} {} {} {}",
+                column_name, tpe_str, nullability_str, default_str, generated_str
+            )
         }
     }
 
+    fn render_default_value(&self, column: &ColumnRef<'_>) -> Option<String> {
+        render_postgres_default_value(column)
+    }
+
     fn render_references(&self, schema_name: &str, foreign_key: &ForeignKey) -> String {
         let referenced_columns = foreign_key.referenced_columns.iter().map(quoted).join(",");
 
         format!(
-            "REFERENCES {}.{}({}) {}",
+            "REFERENCES {}.{}({}) {}{}",
             quoted(schema_name),
             quoted(&foreign_key.referenced_table),
             referenced_columns,
-            render_on_delete(&foreign_key.on_delete_action)
+            render_on_delete(&foreign_key.on_delete_action),
+            if foreign_key.deferrable {
+                " DEFERRABLE INITIALLY DEFERRED"
+            } else {
+                ""
+            }
         )
     }
 }
@@ -53,14 +67,49 @@ pub(crate) fn render_column_type(t: &ColumnType) -> String {
     match &t.family {
         ColumnTypeFamily::Boolean => format!("boolean {}", array),
         ColumnTypeFamily::DateTime => format!("timestamp(3) {}", array),
-        ColumnTypeFamily::Float => format!("Decimal(65,30) {}", array),
+        ColumnTypeFamily::Float => match t.decimal_precision() {
+            Some((precision, scale)) => format!("Decimal({},{}) {}", precision, scale, array),
+            None => format!("Decimal(65,30) {}", array),
+        },
         ColumnTypeFamily::Int => format!("integer {}", array),
+        ColumnTypeFamily::String if t.is_citext() => format!("citext {}", array),
         ColumnTypeFamily::String => format!("text {}", array),
         ColumnTypeFamily::Enum(name) => format!("{}{}", quoted(name), array),
+        ColumnTypeFamily::Json => format!("jsonb {}", array),
+        // Types of `Unsupported(...)` fields, e.g. PostGIS geometries, are preserved verbatim.
+        ColumnTypeFamily::Unknown => format!("{}{}", t.raw, array),
         x => unimplemented!("{:?} not handled yet", x),
     }
 }
 
+/// Enum defaults are cast to the enum type, e.g. `DEFAULT 'ADMIN'::"Role"`. Enum arrays have no defaults.
+fn render_postgres_default(column: &ColumnRef<'_>) -> String {
+    match render_postgres_default_value(column) {
+        Some(value) => format!("DEFAULT {}", value),
+        None => "".to_string(),
+    }
+}
+
+fn render_postgres_default_value(column: &ColumnRef<'_>) -> Option<String> {
+    let column_type = column.column_type();
+
+    match (&column_type.family, column.default()) {
+        (ColumnTypeFamily::Enum(enum_name), Some(value)) if column_type.arity != ColumnArity::List => {
+            Some(render_enum_default(value, enum_name))
+        }
+        // Function calls must not be quoted like string defaults.
+        (ColumnTypeFamily::String, Some(value)) => parse_uuid_function(value)
+            .map(String::from)
+            .or_else(|| render_default_value(column)),
+        _ => render_default_value(column),
+    }
+}
+
+/// The enum value cast to the enum type, e.g. `'ADMIN'::"Role"`.
+pub(crate) fn render_enum_default(value: &str, enum_name: &str) -> String {
+    format!("{}::{}", quoted_string(value), quoted(enum_name))
+}
+
 pub(crate) fn quoted_string<T>(t: T) -> PostgresQuotedString<T> {
     PostgresQuotedString(t)
 }
@@ -77,18 +126,6 @@ where
     }
 }
 
-pub(crate) fn quoted<T>(t: T) -> PostgresQuoted<T> {
-    PostgresQuoted(t)
-}
-
-#[derive(Debug)]
-pub(crate) struct PostgresQuoted<T>(T);
-
-impl<T> std::fmt::Display for PostgresQuoted<T>
-where
-    T: std::fmt::Display,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, r#""{}""#, self.0)
-    }
+pub(crate) fn quoted<T>(t: T) -> Quoted<T> {
+    Quoted::postgres(t)
 }
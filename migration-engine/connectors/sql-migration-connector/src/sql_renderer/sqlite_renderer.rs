@@ -1,5 +1,6 @@
 use super::common::*;
 use crate::{sql_schema_helpers::*, SqlFamily};
+use sql_identifier::Quoted;
 use sql_schema_describer::*;
 use std::fmt::Write as _;
 
@@ -23,6 +24,7 @@ impl super::SqlRenderer for SqliteRenderer {
         let tpe_str = self.render_column_type(column.column_type());
         let nullability_str = render_nullability(&column);
         let default_str = render_default(&column);
+        let generated_str = render_generated(&column);
         let auto_increment_str = if column.auto_increment() {
             "PRIMARY KEY AUTOINCREMENT"
         } else {
@@ -30,13 +32,13 @@ impl super::SqlRenderer for SqliteRenderer {
         };
 
         format!(
-            "{} {} {} {} {}",
-            column_name, tpe_str, nullability_str, default_str, auto_increment_str
+            "{} {} {} {} {} {}",
+            column_name, tpe_str, nullability_str, default_str, generated_str, auto_increment_str
         )
     }
 
     fn render_references(&self, _schema_name: &str, foreign_key: &ForeignKey) -> String {
-        let referenced_fields = foreign_key.referenced_columns.iter().map(SqliteQuoted).join(",");
+        let referenced_fields = foreign_key.referenced_columns.iter().map(quoted).join(",");
 
         format!(
             "REFERENCES {referenced_table}({referenced_fields}) {on_delete_action}",
@@ -52,29 +54,22 @@ impl SqliteRenderer {
         match &t.family {
             ColumnTypeFamily::Boolean => format!("BOOLEAN"),
             ColumnTypeFamily::DateTime => format!("DATE"),
-            ColumnTypeFamily::Float => format!("REAL"),
+            // SQLite stores decimals with numeric affinity, the precision and scale are kept for introspection.
+            ColumnTypeFamily::Float => match t.decimal_precision() {
+                Some((precision, scale)) => format!("DECIMAL({},{})", precision, scale),
+                None => format!("REAL"),
+            },
             ColumnTypeFamily::Int => format!("INTEGER"),
             ColumnTypeFamily::String => format!("TEXT"),
+            ColumnTypeFamily::Unknown => t.raw.clone(),
             x => unimplemented!("{:?} not handled yet", x),
         }
     }
 }
 
-pub(crate) fn quoted<T>(t: T) -> SqliteQuoted<T>
+pub(crate) fn quoted<T>(t: T) -> Quoted<T>
 where
     T: std::fmt::Display,
 {
-    SqliteQuoted(t)
-}
-
-#[derive(Debug)]
-pub(crate) struct SqliteQuoted<T>(T);
-
-impl<T> std::fmt::Display for SqliteQuoted<T>
-where
-    T: std::fmt::Display,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, r#""{}""#, self.0)
-    }
+    Quoted::sqlite(t)
 }
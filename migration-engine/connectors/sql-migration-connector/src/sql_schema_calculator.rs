@@ -1,6 +1,9 @@
+mod audit;
 mod datamodel_helpers;
 
-use crate::{sql_renderer::IteratorJoin, DatabaseInfo, SqlResult};
+pub(crate) use audit::POSTGRES_AUDIT_FUNCTION;
+
+use crate::{sql_renderer::IteratorJoin, DatabaseInfo, SqlError, SqlResult};
 use chrono::*;
 use datamodel::common::*;
 use datamodel::*;
@@ -8,21 +11,41 @@ use datamodel_helpers::{FieldRef, ModelRef, TypeRef};
 use prisma_models::{DatamodelConverter, TempManifestationHolder, TempRelationHolder};
 use quaint::prelude::SqlFamily;
 use sql_schema_describer::{self as sql, ColumnArity};
+use std::collections::BTreeMap;
 
 pub struct SqlSchemaCalculator<'a> {
     data_model: &'a Datamodel,
     database_info: &'a DatabaseInfo,
+    relation_mode: RelationMode,
 }
 
 impl<'a> SqlSchemaCalculator<'a> {
-    pub fn calculate(data_model: &Datamodel, database_info: &DatabaseInfo) -> SqlResult<sql::SqlSchema> {
+    pub fn calculate(
+        data_model: &Datamodel,
+        database_info: &DatabaseInfo,
+        relation_mode: RelationMode,
+    ) -> SqlResult<sql::SqlSchema> {
         let calculator = SqlSchemaCalculator {
             data_model,
             database_info,
+            relation_mode,
         };
         calculator.calculate_internal()
     }
 
+    /// The required columns that get a placeholder default from `migration_value_new`, by table and column name.
+    pub fn placeholder_defaults(data_model: &Datamodel) -> Vec<(String, String)> {
+        datamodel_helpers::walk_fields(data_model)
+            .filter(|f| match f.field_type() {
+                TypeRef::Base(_) | TypeRef::Enum(_) => true,
+                _ => false,
+            })
+            .filter(|f| !f.is_computed() && !f.is_id())
+            .filter(|f| f.arity() == FieldArity::Required && f.default_value().is_none())
+            .map(|f| (f.model().db_name().to_owned(), f.db_name().to_owned()))
+            .collect()
+    }
+
     fn calculate_internal(&self) -> SqlResult<sql::SqlSchema> {
         let mut tables = Vec::new();
         let model_tables_without_inline_relations = self.calculate_model_tables()?;
@@ -32,6 +55,9 @@ impl<'a> SqlSchemaCalculator<'a> {
         tables.append(&mut model_tables);
         tables.append(&mut relation_tables);
 
+        let (mut history_tables, triggers) = self.calculate_audit_tables_and_triggers(&tables);
+        tables.append(&mut history_tables);
+
         // guarantee same sorting as in the sql-schema-describer
         for table in &mut tables {
             table
@@ -46,9 +72,33 @@ impl<'a> SqlSchemaCalculator<'a> {
             tables,
             enums,
             sequences,
+            views: Vec::new(),
+            triggers,
         })
     }
 
+    fn calculate_audit_tables_and_triggers(&self, tables: &[sql::Table]) -> (Vec<sql::Table>, Vec<sql::Trigger>) {
+        let sql_family = self.database_info.sql_family();
+        let schema_name = self.database_info.connection_info().schema_name();
+        let mut history_tables = Vec::new();
+        let mut triggers = Vec::new();
+
+        let audited_tables = datamodel_helpers::walk_models(self.data_model)
+            .filter(|model| model.model().is_audited)
+            .filter_map(|model| tables.iter().find(|table| table.name == model.database_name()));
+
+        for table in audited_tables {
+            let mut table_triggers = audit::audit_triggers(table, sql_family, schema_name);
+
+            if !table_triggers.is_empty() {
+                history_tables.push(audit::history_table(table));
+                triggers.append(&mut table_triggers);
+            }
+        }
+
+        (history_tables, triggers)
+    }
+
     fn calculate_enums(&self) -> Vec<sql::Enum> {
         match self.database_info.sql_family() {
             SqlFamily::Postgres => self
@@ -92,13 +142,15 @@ impl<'a> SqlSchemaCalculator<'a> {
     fn calculate_model_tables(&self) -> SqlResult<Vec<ModelTable>> {
         datamodel_helpers::walk_models(self.data_model)
             .map(|model| {
-                let columns = model
-                    .fields()
-                    .flat_map(|f| match f.field_type() {
-                        TypeRef::Base(_) => Some(sql::Column {
+                let mut columns = Vec::new();
+
+                // Computed fields are expressions over the other columns, they have no column of their own.
+                for f in model.fields().filter(|f| !f.is_computed()) {
+                    let column = match f.field_type() {
+                        TypeRef::Base(_) => sql::Column {
                             name: f.db_name().to_owned(),
                             tpe: column_type(&f),
-                            default: migration_value_new(&f),
+                            default: migration_value_new(&f, self.database_info)?,
                             auto_increment: {
                                 match f.default_value() {
                                     Some(DefaultValue::Expression(ValueGenerator {
@@ -109,19 +161,39 @@ impl<'a> SqlSchemaCalculator<'a> {
                                     _ => false,
                                 }
                             },
-                        }),
+                            generated: None,
+                            // The query engine leaves stamping these fields on updates to MySQL.
+                            on_update_current_timestamp: self.database_info.sql_family() == SqlFamily::Mysql
+                                && f.is_updated_at_with_default_now(),
+                        },
                         TypeRef::Enum(r#enum) => {
                             let enum_db_name = r#enum.db_name();
-                            Some(sql::Column {
+                            sql::Column {
                                 name: f.db_name().to_owned(),
                                 tpe: enum_column_type(&f, &self.database_info, enum_db_name),
-                                default: migration_value_new(&f),
+                                default: migration_value_new(&f, self.database_info)?,
                                 auto_increment: false,
-                            })
+                                generated: None,
+                                on_update_current_timestamp: false,
+                            }
                         }
-                        _ => None,
-                    })
-                    .collect();
+                        TypeRef::Unsupported(db_type) => sql::Column {
+                            name: f.db_name().to_owned(),
+                            tpe: sql::ColumnType {
+                                raw: db_type.to_owned(),
+                                family: sql::ColumnTypeFamily::Unknown,
+                                arity: column_arity(&f),
+                            },
+                            default: None,
+                            auto_increment: false,
+                            generated: None,
+                            on_update_current_timestamp: false,
+                        },
+                        _ => continue,
+                    };
+
+                    columns.push(column);
+                }
 
                 let primary_key = sql::PrimaryKey {
                     columns: model.id_fields().map(|field| field.db_name().to_owned()).collect(),
@@ -134,6 +206,7 @@ impl<'a> SqlSchemaCalculator<'a> {
                             name: format!("{}.{}", &model.db_name(), &f.db_name()),
                             columns: vec![f.db_name().to_owned()],
                             tpe: sql::IndexType::Unique,
+                            column_options: BTreeMap::new(),
                         })
                     } else {
                         None
@@ -144,10 +217,15 @@ impl<'a> SqlSchemaCalculator<'a> {
                     let referenced_fields: Vec<FieldRef> = index_definition
                         .fields
                         .iter()
-                        .map(|field_name| model.find_field(field_name).expect("Unknown field in index directive."))
-                        .collect();
+                        .map(|field_name| {
+                            model.find_field(field_name).ok_or_else(|| SqlError::UnknownIndexField {
+                                model_name: model.name().to_owned(),
+                                field_name: field_name.to_owned(),
+                            })
+                        })
+                        .collect::<SqlResult<_>>()?;
 
-                    sql::Index {
+                    Ok::<_, SqlError>(sql::Index {
                         name: index_definition.name.clone().unwrap_or_else(|| {
                             format!(
                                 "{}.{}",
@@ -166,15 +244,31 @@ impl<'a> SqlSchemaCalculator<'a> {
                         } else {
                             sql::IndexType::Normal
                         },
-                    }
+                        column_options: referenced_fields
+                            .iter()
+                            .map(|field| {
+                                (
+                                    field.db_name().to_owned(),
+                                    index_definition.options_of_field(field.name()),
+                                )
+                            })
+                            .map(|(column, options)| (column, self.calculate_index_column_options(options)))
+                            .filter(|(_, options)| !options.is_default())
+                            .collect(),
+                    })
                 });
 
                 let table = sql::Table {
                     name: model.database_name().to_owned(),
                     columns,
-                    indices: single_field_indexes.chain(multiple_field_indexes).collect(),
+                    indices: single_field_indexes
+                        .map(Ok)
+                        .chain(multiple_field_indexes)
+                        .collect::<SqlResult<_>>()?,
                     primary_key: Some(primary_key),
                     foreign_keys: Vec::new(),
+                    partitioning: None,
+                    options: self.calculate_table_options(&model.model().table_options),
                 };
 
                 Ok(ModelTable {
@@ -249,6 +343,8 @@ impl<'a> SqlSchemaCalculator<'a> {
                                 ),
                                 default: None,
                                 auto_increment: false,
+                                generated: None,
+                                on_update_current_timestamp: false,
                             }]
                         } else {
                             referenced_fields
@@ -261,6 +357,8 @@ impl<'a> SqlSchemaCalculator<'a> {
                                     ),
                                     default: None,
                                     auto_increment: false,
+                                    generated: None,
+                                    on_update_current_timestamp: false,
                                 })
                                 .collect()
                         };
@@ -277,10 +375,17 @@ impl<'a> SqlSchemaCalculator<'a> {
                                 ColumnArity::Required => sql::ForeignKeyAction::Restrict,
                                 _ => sql::ForeignKeyAction::SetNull,
                             },
+                            // Only Postgres supports deferred foreign keys.
+                            deferrable: self.database_info.sql_family() == SqlFamily::Postgres
+                                && relation.is_deferrable(),
                         };
 
                         model_table.table.columns.extend(columns);
-                        model_table.table.foreign_keys.push(foreign_key);
+
+                        // Without foreign keys, the query engine takes care of the referential actions.
+                        if self.relation_mode.uses_foreign_keys() {
+                            model_table.table.foreign_keys.push(foreign_key);
+                        }
 
                         if relation.is_one_to_one() {
                             add_one_to_one_relation_unique_index(&mut model_table.table, column_name)
@@ -310,13 +415,14 @@ impl<'a> SqlSchemaCalculator<'a> {
                     let a_columns = relation_table_columns(&model_a, relation.model_a_column());
                     let mut b_columns = relation_table_columns(&model_b, relation.model_b_column());
 
-                    let foreign_keys = vec![
+                    let mut foreign_keys = vec![
                         sql::ForeignKey {
                             constraint_name: None,
                             columns: a_columns.iter().map(|col| col.name.clone()).collect(),
                             referenced_table: model_a.db_name().to_owned(),
                             referenced_columns: model_a.id_fields().map(|field| field.db_name().to_owned()).collect(),
                             on_delete_action: sql::ForeignKeyAction::Cascade,
+                            deferrable: false,
                         },
                         sql::ForeignKey {
                             constraint_name: None,
@@ -324,9 +430,14 @@ impl<'a> SqlSchemaCalculator<'a> {
                             referenced_table: model_b.db_name().to_owned(),
                             referenced_columns: model_b.id_fields().map(|field| field.db_name().to_owned()).collect(),
                             on_delete_action: sql::ForeignKeyAction::Cascade,
+                            deferrable: false,
                         },
                     ];
 
+                    if !self.relation_mode.uses_foreign_keys() {
+                        foreign_keys.clear();
+                    }
+
                     let mut columns = a_columns;
                     columns.append(&mut b_columns);
 
@@ -335,6 +446,7 @@ impl<'a> SqlSchemaCalculator<'a> {
                         name: format!("{}_AB_unique", relation.table_name()),
                         columns: columns.iter().map(|col| col.name.clone()).collect(),
                         tpe: sql::IndexType::Unique,
+                        column_options: BTreeMap::new(),
                     };
 
                     let table = sql::Table {
@@ -343,6 +455,8 @@ impl<'a> SqlSchemaCalculator<'a> {
                         indices: vec![index],
                         primary_key: None,
                         foreign_keys,
+                        partitioning: None,
+                        options: sql::TableOptions::default(),
                     };
                     result.push(table);
                 }
@@ -352,6 +466,32 @@ impl<'a> SqlSchemaCalculator<'a> {
         Ok(result)
     }
 
+    fn calculate_index_column_options(&self, options: IndexFieldOptions) -> sql::IndexColumnOptions {
+        sql::IndexColumnOptions {
+            sort_order: match options.sort_order {
+                SortOrder::Asc => sql::SortOrder::Asc,
+                SortOrder::Desc => sql::SortOrder::Desc,
+            },
+            // Only MySQL indexes column prefixes.
+            length: options
+                .length
+                .filter(|_| self.database_info.sql_family() == SqlFamily::Mysql),
+        }
+    }
+
+    fn calculate_table_options(&self, options: &TableOptions) -> sql::TableOptions {
+        let sql_family = self.database_info.sql_family();
+        let is_mysql = sql_family == SqlFamily::Mysql;
+        let is_postgres = sql_family == SqlFamily::Postgres;
+
+        sql::TableOptions {
+            engine: options.engine.clone().filter(|_| is_mysql),
+            charset: options.charset.clone().filter(|_| is_mysql),
+            tablespace: options.tablespace.clone().filter(|_| is_postgres),
+            fillfactor: options.fillfactor.filter(|_| is_postgres),
+        }
+    }
+
     fn calculate_relations(&self) -> Vec<TempRelationHolder> {
         DatamodelConverter::calculate_relations(&self.data_model)
     }
@@ -365,6 +505,8 @@ fn relation_table_columns(referenced_model: &ModelRef<'_>, reference_field_name:
             tpe: column_type(&id_field),
             default: None,
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         }]
     } else {
         referenced_model
@@ -378,6 +520,8 @@ fn relation_table_columns(referenced_model: &ModelRef<'_>, reference_field_name:
                 tpe: column_type(&referenced_field),
                 default: None,
                 auto_increment: false,
+                generated: None,
+                on_update_current_timestamp: false,
             })
             .collect()
     }
@@ -389,15 +533,26 @@ struct ModelTable {
     model: Model,
 }
 
-fn migration_value_new(field: &FieldRef<'_>) -> Option<String> {
+fn migration_value_new(field: &FieldRef<'_>, database_info: &DatabaseInfo) -> SqlResult<Option<String>> {
+    // The database generates UUIDs itself where it can, ids included.
+    if let Some(DefaultValue::Expression(ValueGenerator {
+        generator: ValueGeneratorFn::UUID,
+        ..
+    })) = field.default_value()
+    {
+        if let Some(uuid_function) = database_info.uuid_function() {
+            return Ok(Some(uuid_function.to_owned()));
+        }
+    }
+
     let value = match (&field.default_value(), field.arity()) {
         (Some(df), _) => match df {
             dml::DefaultValue::Single(s) => s.clone(),
-            dml::DefaultValue::Expression(_) => default_migration_value(&field.field_type()),
+            dml::DefaultValue::Expression(_) => default_migration_value(field)?,
         },
         // This is a temporary hack until we can report impossible unexecutable migrations.
-        (None, FieldArity::Required) => default_migration_value(&field.field_type()),
-        (None, _) => return None,
+        (None, FieldArity::Required) => default_migration_value(field)?,
+        (None, _) => return Ok(None),
     };
 
     let result = match value {
@@ -413,42 +568,42 @@ fn migration_value_new(field: &FieldRef<'_>) -> Option<String> {
         ScalarValue::Decimal(x) => format!("{}", x),
         ScalarValue::String(x) => format!("{}", x),
 
-        ScalarValue::DateTime(x) => {
-            let mut raw = format!("{}", x); // this will produce a String 1970-01-01 00:00:00 UTC
-            raw.truncate(raw.len() - 4); // strip the UTC suffix
-            format!("{}", raw)
-        }
+        // The wall clock time the query engine stores for the instant, e.g. 1970-01-01 00:00:00 in UTC.
+        ScalarValue::DateTime(x) => format!("{}", database_info.time_zone().wall_clock(x)),
         ScalarValue::ConstantLiteral(x) => format!("{}", x), // this represents enum values
     };
 
     if field.is_id() {
-        None
+        Ok(None)
     } else {
-        Some(result)
+        Ok(Some(result))
     }
 }
 
-fn default_migration_value(field_type: &TypeRef<'_>) -> ScalarValue {
-    match field_type {
+fn default_migration_value(field: &FieldRef<'_>) -> SqlResult<ScalarValue> {
+    let value = match field.field_type() {
         TypeRef::Base(ScalarType::Boolean) => ScalarValue::Boolean(false),
         TypeRef::Base(ScalarType::Int) => ScalarValue::Int(0),
         TypeRef::Base(ScalarType::Float) => ScalarValue::Float(0.0),
         TypeRef::Base(ScalarType::String) => ScalarValue::String("".to_string()),
-        TypeRef::Base(ScalarType::Decimal) => ScalarValue::Decimal(0.0),
+        TypeRef::Base(ScalarType::Decimal) => ScalarValue::Decimal(0.into()),
         TypeRef::Base(ScalarType::DateTime) => {
             let naive = NaiveDateTime::from_timestamp(0, 0);
             let datetime: DateTime<Utc> = DateTime::from_utc(naive, Utc);
             ScalarValue::DateTime(datetime)
         }
         TypeRef::Enum(inum) => {
-            let first_value = inum
-                .values()
-                .first()
-                .expect(&format!("Enum {} did not contain any values.", inum.name()));
+            let first_value = inum.values().first().ok_or_else(|| SqlError::EnumWithoutValues {
+                enum_name: inum.name().to_owned(),
+                model_name: field.model().name().to_owned(),
+                field_name: field.name().to_owned(),
+            })?;
             ScalarValue::String(first_value.to_string())
         }
         _ => unimplemented!("this functions must only be called for scalar fields"),
-    }
+    };
+
+    Ok(value)
 }
 
 fn enum_column_type(field: &FieldRef<'_>, database_info: &DatabaseInfo, db_name: &str) -> sql::ColumnType {
@@ -464,7 +619,20 @@ fn enum_column_type(field: &FieldRef<'_>, database_info: &DatabaseInfo, db_name:
 }
 
 fn column_type(field: &FieldRef<'_>) -> sql::ColumnType {
-    column_type_for_scalar_type(&scalar_type_for_field(field), column_arity(field))
+    let mut column_type = column_type_for_scalar_type(&scalar_type_for_field(field), column_arity(field));
+
+    // `@db.Citext` is validated to only occur on String fields of PostgreSQL models.
+    if field.native_type() == Some("Citext") {
+        column_type.raw = "citext".to_owned();
+    }
+
+    // Decimals are stored as exact numbers, with the precision and scale of `@db.Decimal` if there is one.
+    if scalar_type_for_field(field) == ScalarType::Decimal {
+        let (precision, scale) = field.decimal_precision().unwrap_or((65, 30));
+        column_type.raw = format!("numeric({},{})", precision, scale);
+    }
+
+    column_type
 }
 
 fn scalar_type_for_field(field: &FieldRef<'_>) -> ScalarType {
@@ -494,7 +662,8 @@ fn column_type_for_scalar_type(scalar_type: &ScalarType, column_arity: ColumnAri
         ScalarType::Boolean => sql::ColumnType::pure(sql::ColumnTypeFamily::Boolean, column_arity),
         ScalarType::String => sql::ColumnType::pure(sql::ColumnTypeFamily::String, column_arity),
         ScalarType::DateTime => sql::ColumnType::pure(sql::ColumnTypeFamily::DateTime, column_arity),
-        ScalarType::Decimal => unimplemented!(),
+        // Decimals get their `numeric(p,s)` raw type in `column_type`.
+        ScalarType::Decimal => sql::ColumnType::pure(sql::ColumnTypeFamily::Float, column_arity),
     }
 }
 
@@ -503,6 +672,7 @@ fn add_one_to_one_relation_unique_index(table: &mut sql::Table, column_name: &st
         name: format!("{}_{}", table.name, column_name),
         columns: vec![column_name.to_string()],
         tpe: sql::IndexType::Unique,
+        column_options: BTreeMap::new(),
     };
 
     table.indices.push(index);
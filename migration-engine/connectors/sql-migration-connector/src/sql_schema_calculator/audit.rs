@@ -0,0 +1,113 @@
+//! The history tables and triggers recording the row changes of `@@audited` models.
+
+use crate::sql_renderer::{mysql_quoted, mysql_quoted_string, postgres_quoted, IteratorJoin};
+use quaint::prelude::SqlFamily;
+use sql_schema_describer::{self as sql, ColumnArity, ColumnType, ColumnTypeFamily};
+
+/// The function called by the audit triggers on Postgres. It finds the history table from the name of the table
+/// the trigger fired on, so one function serves all audited tables of the schema.
+pub(crate) const POSTGRES_AUDIT_FUNCTION: &str = "prisma_audit";
+
+/// The history table of an audited table. Every inserted, updated or deleted row is recorded as JSON.
+pub(super) fn history_table(table: &sql::Table) -> sql::Table {
+    let column = |name: &str, family: ColumnTypeFamily, arity: ColumnArity| sql::Column {
+        name: name.to_owned(),
+        tpe: ColumnType::pure(family, arity),
+        default: None,
+        auto_increment: false,
+        generated: None,
+        on_update_current_timestamp: false,
+    };
+
+    let mut history_id = column("history_id", ColumnTypeFamily::Int, ColumnArity::Required);
+    history_id.auto_increment = true;
+
+    sql::Table {
+        name: format!("{}_history", table.name),
+        columns: vec![
+            history_id,
+            column("operation", ColumnTypeFamily::String, ColumnArity::Required),
+            column("changed_at", ColumnTypeFamily::DateTime, ColumnArity::Required),
+            column("row_data", ColumnTypeFamily::Json, ColumnArity::Nullable),
+        ],
+        indices: Vec::new(),
+        primary_key: Some(sql::PrimaryKey {
+            columns: vec!["history_id".to_owned()],
+            sequence: None,
+        }),
+        foreign_keys: Vec::new(),
+        partitioning: None,
+        options: Default::default(),
+    }
+}
+
+/// The triggers writing the changes of the rows of `table` to its history table. SQLite has no audited models.
+pub(super) fn audit_triggers(table: &sql::Table, sql_family: SqlFamily, schema_name: &str) -> Vec<sql::Trigger> {
+    match sql_family {
+        SqlFamily::Postgres => vec![postgres_audit_trigger(table, schema_name)],
+        SqlFamily::Mysql => ["INSERT", "UPDATE", "DELETE"]
+            .iter()
+            .map(|event| mysql_audit_trigger(table, event, schema_name))
+            .collect(),
+        SqlFamily::Sqlite => Vec::new(),
+    }
+}
+
+fn postgres_audit_trigger(table: &sql::Table, schema_name: &str) -> sql::Trigger {
+    let name = format!("{}_audit", table.name);
+    let definition = format!(
+        "CREATE TRIGGER {} AFTER INSERT OR UPDATE OR DELETE ON {}.{} FOR EACH ROW EXECUTE PROCEDURE {}.{}()",
+        postgres_quoted(&name),
+        postgres_quoted(schema_name),
+        postgres_quoted(&table.name),
+        postgres_quoted(schema_name),
+        POSTGRES_AUDIT_FUNCTION,
+    );
+
+    sql::Trigger {
+        name,
+        table: table.name.clone(),
+        definition,
+        columns: Vec::new(),
+    }
+}
+
+/// MySQL triggers can't look up their table dynamically, the JSON object lists the columns of the table.
+fn mysql_audit_trigger(table: &sql::Table, event: &str, schema_name: &str) -> sql::Trigger {
+    let name = format!("{}_audit_{}", table.name, event.to_lowercase());
+    let row = if event == "DELETE" { "OLD" } else { "NEW" };
+    let row_data = table
+        .columns
+        .iter()
+        .map(|column| {
+            format!(
+                "{}, {}.{}",
+                mysql_quoted_string(&column.name),
+                row,
+                mysql_quoted(&column.name)
+            )
+        })
+        .join(", ");
+
+    let mut trigger = sql::Trigger {
+        name,
+        table: table.name.clone(),
+        definition: String::new(),
+        // Like the described MySQL triggers, the columns listed in the body are not tracked.
+        columns: Vec::new(),
+    };
+
+    trigger.definition = format!(
+        "CREATE TRIGGER {schema}.{name} AFTER {event} ON {schema}.{table} FOR EACH ROW \
+         INSERT INTO {schema}.{history_table} (`operation`, `changed_at`, `row_data`) \
+         VALUES ('{event}', CURRENT_TIMESTAMP(3), JSON_OBJECT({row_data}))",
+        schema = mysql_quoted(schema_name),
+        name = mysql_quoted(&trigger.name),
+        event = event,
+        table = mysql_quoted(&table.name),
+        history_table = mysql_quoted(trigger.history_table_name()),
+        row_data = row_data,
+    );
+
+    trigger
+}
@@ -109,6 +109,7 @@ impl<'a> FieldRef<'a> {
                 r#enum: self.datamodel.find_enum(name).unwrap(),
             }),
             FieldType::Base(scalar_type) => TypeRef::Base(*scalar_type),
+            FieldType::Unsupported(db_type) => TypeRef::Unsupported(db_type),
             _ => TypeRef::Other,
         }
     }
@@ -117,6 +118,24 @@ impl<'a> FieldRef<'a> {
         self.field.is_unique
     }
 
+    pub(super) fn is_computed(&self) -> bool {
+        self.field.computed.is_some()
+    }
+
+    /// The native type of the field, e.g. `Citext` for `@db.Citext`.
+    pub(super) fn native_type(&self) -> Option<&'a str> {
+        self.field.native_type.as_ref().map(String::as_str)
+    }
+
+    pub(super) fn decimal_precision(&self) -> Option<(u32, u32)> {
+        self.field.decimal_precision()
+    }
+
+    /// `@updatedAt` with `@default(now())`, the database can keep these up to date.
+    pub(super) fn is_updated_at_with_default_now(&self) -> bool {
+        self.field.is_updated_at && self.default_value().map(|default| default.is_now()).unwrap_or(false)
+    }
+
     pub(super) fn is_id(&self) -> bool {
         self.field.is_id
     }
@@ -137,6 +156,8 @@ impl<'a> FieldRef<'a> {
 pub(super) enum TypeRef<'a> {
     Enum(EnumRef<'a>),
     Base(ScalarType),
+    /// The raw database type of an `Unsupported(...)` field.
+    Unsupported(&'a str),
     Other,
 }
 
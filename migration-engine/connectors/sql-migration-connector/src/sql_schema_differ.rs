@@ -1,9 +1,11 @@
 mod column;
+mod column_type_change;
 mod enums;
 mod index;
 mod table;
 
 pub(crate) use column::{ColumnChange, ColumnDiffer};
+pub(crate) use column_type_change::ColumnTypeChange;
 pub(crate) use table::TableDiffer;
 
 use crate::*;
@@ -12,6 +14,14 @@ use sql_schema_describer::*;
 use tracing::debug;
 
 const MIGRATION_TABLE_NAME: &str = "_Migration";
+const SEED_TABLE_NAME: &str = "_Seed";
+/// Created by the query engine for idempotency keys, on the first request with a key.
+const IDEMPOTENCY_TABLE_NAME: &str = "_prisma_idempotency_keys";
+
+/// The tables of the engines themselves, they are not part of the datamodel.
+pub(crate) fn is_engine_table(table: &Table) -> bool {
+    table.name == MIGRATION_TABLE_NAME || table.name == SEED_TABLE_NAME || table.name == IDEMPOTENCY_TABLE_NAME
+}
 
 #[derive(Debug)]
 pub struct SqlSchemaDiffer<'a> {
@@ -32,11 +42,15 @@ pub struct SqlSchemaDiff {
     pub create_enums: Vec<CreateEnum>,
     pub drop_enums: Vec<DropEnum>,
     pub alter_enums: Vec<AlterEnum>,
+    pub create_triggers: Vec<CreateTrigger>,
+    pub drop_triggers: Vec<DropTrigger>,
 }
 
 impl SqlSchemaDiff {
     pub fn into_steps(self) -> Vec<SqlMigrationStep> {
-        wrap_as_step(self.create_enums, SqlMigrationStep::CreateEnum)
+        // Order matters: audit triggers insert into their history table, they are dropped before it.
+        wrap_as_step(self.drop_triggers, SqlMigrationStep::DropTrigger)
+            .chain(wrap_as_step(self.create_enums, SqlMigrationStep::CreateEnum))
             .chain(wrap_as_step(self.alter_enums, SqlMigrationStep::AlterEnum))
             .chain(wrap_as_step(self.drop_indexes, SqlMigrationStep::DropIndex))
             // Order matters: we must create tables before `alter_table`s because we could
@@ -54,6 +68,8 @@ impl SqlSchemaDiff {
             .chain(wrap_as_step(self.drop_tables, SqlMigrationStep::DropTable))
             .chain(wrap_as_step(self.drop_enums, SqlMigrationStep::DropEnum))
             .chain(wrap_as_step(self.alter_indexes, SqlMigrationStep::AlterIndex))
+            // Order matters: the history tables the audit triggers insert into must exist.
+            .chain(wrap_as_step(self.create_triggers, SqlMigrationStep::CreateTrigger))
             .collect()
     }
 }
@@ -70,12 +86,15 @@ impl<'schema> SqlSchemaDiffer<'schema> {
 
     fn diff_internal(&self) -> SqlSchemaDiff {
         let alter_indexes: Vec<_> = self.alter_indexes();
+        let alter_tables = self.alter_tables();
 
         SqlSchemaDiff {
             add_foreign_keys: self.add_foreign_keys(),
             drop_tables: self.drop_tables(),
             create_tables: self.create_tables(),
-            alter_tables: self.alter_tables(),
+            create_triggers: self.create_triggers(&alter_tables),
+            drop_triggers: self.drop_triggers(&alter_tables),
+            alter_tables,
             create_indexes: self.create_indexes(),
             drop_indexes: self.drop_indexes(),
             alter_indexes,
@@ -101,6 +120,43 @@ impl<'schema> SqlSchemaDiffer<'schema> {
             .collect()
     }
 
+    fn create_triggers(&self, alter_tables: &[AlterTable]) -> Vec<CreateTrigger> {
+        self.next_audit_triggers()
+            .filter(|next| {
+                !self
+                    .previous_audit_triggers()
+                    .any(|previous| triggers_match(previous, next))
+                    || self.trigger_is_recreated(next, alter_tables)
+            })
+            .map(|trigger| CreateTrigger {
+                trigger: trigger.clone(),
+            })
+            .collect()
+    }
+
+    fn drop_triggers(&self, alter_tables: &[AlterTable]) -> Vec<DropTrigger> {
+        self.previous_audit_triggers()
+            // Triggers are dropped with their table.
+            .filter(|previous| self.next.has_table(&previous.table))
+            .filter(|previous| {
+                !self.next_audit_triggers().any(|next| triggers_match(previous, next))
+                    || self.trigger_is_recreated(previous, alter_tables)
+            })
+            .map(|trigger| DropTrigger {
+                table: trigger.table.clone(),
+                name: trigger.name.clone(),
+            })
+            .collect()
+    }
+
+    /// The audit triggers on MySQL list the columns of their table, they are recreated when the table changes.
+    fn trigger_is_recreated(&self, trigger: &Trigger, alter_tables: &[AlterTable]) -> bool {
+        self.sql_family == SqlFamily::Mysql
+            && alter_tables
+                .iter()
+                .any(|alter_table| alter_table.table.name == trigger.table)
+    }
+
     fn add_foreign_keys(&self) -> Vec<AddForeignKey> {
         let mut add_foreign_keys = Vec::new();
 
@@ -111,7 +167,6 @@ impl<'schema> SqlSchemaDiffer<'schema> {
     }
 
     fn alter_tables(&self) -> Vec<AlterTable> {
-        // TODO: this does not diff primary key columns yet
         let mut result = Vec::new();
         for previous_table in &self.previous.tables {
             if let Ok(next_table) = self.next.table(&previous_table.name) {
@@ -124,6 +179,8 @@ impl<'schema> SqlSchemaDiffer<'schema> {
                     .chain(Self::drop_columns(&differ))
                     .chain(Self::add_columns(&differ))
                     .chain(Self::alter_columns(&differ))
+                    .chain(Self::alter_options(&differ))
+                    .chain(Self::alter_primary_key(&differ))
                     .collect();
 
                 if !changes.is_empty() {
@@ -178,6 +235,24 @@ impl<'schema> SqlSchemaDiffer<'schema> {
         })
     }
 
+    fn alter_options(differ: &TableDiffer<'schema>) -> impl Iterator<Item = TableChange> {
+        differ
+            .changed_options()
+            .map(|options| TableChange::AlterOptions(AlterOptions { options }))
+            .into_iter()
+    }
+
+    fn alter_primary_key(differ: &TableDiffer<'schema>) -> impl Iterator<Item = TableChange> {
+        differ
+            .changed_primary_key()
+            .map(|primary_key| {
+                TableChange::AlterPrimaryKey(AlterPrimaryKey {
+                    columns: primary_key.columns.clone(),
+                })
+            })
+            .into_iter()
+    }
+
     fn drop_foreign_keys<'a>(differ: &'a TableDiffer<'schema>) -> impl Iterator<Item = TableChange> + 'a {
         differ
             .dropped_foreign_keys()
@@ -323,17 +398,11 @@ impl<'schema> SqlSchemaDiffer<'schema> {
     }
 
     fn previous_tables(&self) -> impl Iterator<Item = &Table> {
-        self.previous
-            .tables
-            .iter()
-            .filter(|table| table.name != MIGRATION_TABLE_NAME)
+        self.previous.tables.iter().filter(|table| !is_engine_table(table))
     }
 
     fn next_tables(&self) -> impl Iterator<Item = &Table> {
-        self.next
-            .tables
-            .iter()
-            .filter(|table| table.name != MIGRATION_TABLE_NAME)
+        self.next.tables.iter().filter(|table| !is_engine_table(table))
     }
 
     fn enum_pairs(&self) -> impl Iterator<Item = EnumDiffer<'_>> {
@@ -361,6 +430,18 @@ impl<'schema> SqlSchemaDiffer<'schema> {
     fn next_enums(&self) -> impl Iterator<Item = &Enum> {
         self.next.enums.iter()
     }
+
+    /// Other triggers are left alone, they are not part of the data model.
+    fn previous_audit_triggers(&self) -> impl Iterator<Item = &Trigger> {
+        self.previous
+            .triggers
+            .iter()
+            .filter(|trigger| trigger.is_audit_trigger())
+    }
+
+    fn next_audit_triggers(&self) -> impl Iterator<Item = &Trigger> {
+        self.next.triggers.iter().filter(|trigger| trigger.is_audit_trigger())
+    }
 }
 
 fn push_created_foreign_keys<'a, 'schema>(
@@ -404,6 +485,7 @@ fn foreign_keys_match(previous: &ForeignKey, next: &ForeignKey) -> bool {
         && previous.referenced_columns == next.referenced_columns
         && previous.columns == next.columns
         && previous.on_delete_action == next.on_delete_action
+        && previous.deferrable == next.deferrable
 }
 
 fn tables_match(previous: &Table, next: &Table) -> bool {
@@ -413,3 +495,7 @@ fn tables_match(previous: &Table, next: &Table) -> bool {
 fn enums_match(previous: &Enum, next: &Enum) -> bool {
     previous.name == next.name
 }
+
+fn triggers_match(previous: &Trigger, next: &Trigger) -> bool {
+    previous.table == next.table && previous.name == next.name
+}
@@ -1,3 +1,6 @@
+use super::column_type_change::{column_type_change, ColumnTypeChange};
+use crate::database_info::parse_uuid_function;
+use quaint::prelude::SqlFamily;
 use sql_schema_describer::{Column, ColumnTypeFamily};
 
 #[derive(Debug)]
@@ -30,7 +33,11 @@ impl<'a> ColumnDiffer<'a> {
             None
         };
 
-        let r#type = if self.previous.tpe.family != self.next.tpe.family {
+        let r#type = if self.previous.tpe.family != self.next.tpe.family
+            || !self.unsupported_types_match()
+            || self.previous.tpe.is_citext() != self.next.tpe.is_citext()
+            || !self.decimal_precisions_match()
+        {
             Some(ColumnChange::Type)
         } else {
             None
@@ -42,8 +49,42 @@ impl<'a> ColumnDiffer<'a> {
             None
         };
 
+        let on_update = if self.previous.on_update_current_timestamp != self.next.on_update_current_timestamp {
+            Some(ColumnChange::OnUpdate)
+        } else {
+            None
+        };
+
         ColumnChanges {
-            changes: [renaming, r#type, arity, default],
+            changes: [renaming, r#type, arity, default, on_update],
+        }
+    }
+
+    /// How the values of the column survive the change of its type, `None` if the type did not change.
+    pub(crate) fn type_change(&self, sql_family: SqlFamily) -> Option<ColumnTypeChange> {
+        if !self.all_changes().type_changed() {
+            return None;
+        }
+
+        Some(column_type_change(&self.previous.tpe, &self.next.tpe, sql_family))
+    }
+
+    /// Columns of unsupported types only have their raw database type, e.g. `geometry(Point,4326)`, to compare.
+    fn unsupported_types_match(&self) -> bool {
+        match (&self.previous.tpe.family, &self.next.tpe.family) {
+            (ColumnTypeFamily::Unknown, ColumnTypeFamily::Unknown) => {
+                self.previous.tpe.raw.eq_ignore_ascii_case(&self.next.tpe.raw)
+            }
+            _ => true,
+        }
+    }
+
+    /// The precision and scale of decimals are only compared if both columns name them, the describers don't report
+    /// them on every database.
+    fn decimal_precisions_match(&self) -> bool {
+        match (self.previous.tpe.decimal_precision(), self.next.tpe.decimal_precision()) {
+            (Some(previous), Some(next)) => previous == next,
+            _ => true,
         }
     }
 
@@ -67,6 +108,7 @@ impl<'a> ColumnDiffer<'a> {
             ColumnTypeFamily::Float => float_default(previous_value) == float_default(next_value),
             ColumnTypeFamily::Int => int_default(previous_value) == int_default(next_value),
             ColumnTypeFamily::Boolean => bool_default(previous_value) == bool_default(next_value),
+            ColumnTypeFamily::Enum(_) => enum_default(previous_value) == enum_default(next_value),
             _ => true,
         }
     }
@@ -88,6 +130,11 @@ fn bool_default(s: Option<&str>) -> Option<bool> {
     })
 }
 
+/// The variant of an enum default, without the quotes and the cast to the enum type, e.g. `ADMIN` for `'ADMIN'::"Role"`.
+fn enum_default(s: Option<&str>) -> Option<&str> {
+    s.map(|s| s.split("::").next().unwrap_or(s).trim_matches('\''))
+}
+
 fn string_defaults_match(previous: Option<&str>, next: Option<&str>) -> bool {
     match (previous, next) {
         (Some(_), None) | (None, Some(_)) => false,
@@ -97,6 +144,11 @@ fn string_defaults_match(previous: Option<&str>, next: Option<&str>) -> bool {
                 return true;
             }
 
+            // Function defaults are described with parentheses and schema, e.g. `(public.gen_random_uuid())`.
+            if let (Some(previous), Some(next)) = (parse_uuid_function(previous), parse_uuid_function(next)) {
+                return previous == next;
+            }
+
             previous == next
         }
     }
@@ -112,11 +164,13 @@ pub(crate) enum ColumnChange {
     Arity,
     Default,
     Type,
+    /// MySQL's `ON UPDATE CURRENT_TIMESTAMP`.
+    OnUpdate,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct ColumnChanges {
-    changes: [Option<ColumnChange>; 4],
+    changes: [Option<ColumnChange>; 5],
 }
 
 impl ColumnChanges {
@@ -131,6 +185,10 @@ impl ColumnChanges {
     pub(crate) fn arity_changed(&self) -> bool {
         self.changes.iter().any(|c| c.as_ref() == Some(&ColumnChange::Arity))
     }
+
+    pub(crate) fn default_changed(&self) -> bool {
+        self.changes.iter().any(|c| c.as_ref() == Some(&ColumnChange::Default))
+    }
 }
 
 #[cfg(test)]
@@ -145,6 +203,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::String, ColumnArity::Required),
             default: Some("abc".to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         let col_b = Column {
@@ -152,6 +212,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::String, ColumnArity::Required),
             default: Some(r##""abc""##.to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         let col_c = Column {
@@ -159,6 +221,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::String, ColumnArity::Required),
             default: Some(r##"'abc'"##.to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         assert!(ColumnDiffer {
@@ -187,6 +251,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::DateTime, ColumnArity::Required),
             default: Some("2019-09-01T08:00:00Z".to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         let col_b = Column {
@@ -194,6 +260,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::DateTime, ColumnArity::Required),
             default: Some("2019-09-01 18:00:00 UTC".to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         assert!(ColumnDiffer {
@@ -210,6 +278,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::Float, ColumnArity::Required),
             default: Some("0.33".to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         let col_b = Column {
@@ -217,6 +287,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::Float, ColumnArity::Required),
             default: Some("0.33000".to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         assert!(ColumnDiffer {
@@ -230,6 +302,8 @@ mod tests {
             tpe: ColumnType::pure(ColumnTypeFamily::Float, ColumnArity::Required),
             default: Some("0.34".to_owned()),
             auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
         };
 
         assert!(!ColumnDiffer {
@@ -238,4 +312,109 @@ mod tests {
         }
         .defaults_match());
     }
+
+    #[test]
+    fn uuid_function_defaults_match() {
+        let column_with_default = |default: &str| Column {
+            name: "A".to_owned(),
+            tpe: ColumnType::pure(ColumnTypeFamily::String, ColumnArity::Required),
+            default: Some(default.to_owned()),
+            auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
+        };
+
+        let calculated = column_with_default("gen_random_uuid()");
+        let described = column_with_default("(public.gen_random_uuid())");
+        let other_function = column_with_default("(uuid_generate_v4())");
+
+        assert!(ColumnDiffer {
+            previous: &described,
+            next: &calculated,
+        }
+        .defaults_match());
+
+        assert!(!ColumnDiffer {
+            previous: &other_function,
+            next: &calculated,
+        }
+        .defaults_match());
+    }
+
+    #[test]
+    fn enum_defaults_match() {
+        let column_with_default = |default: Option<&str>| Column {
+            name: "A".to_owned(),
+            tpe: ColumnType::pure(ColumnTypeFamily::Enum("Role".to_owned()), ColumnArity::Required),
+            default: default.map(String::from),
+            auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
+        };
+
+        let calculated = column_with_default(Some("ADMIN"));
+        let described = column_with_default(Some(r#"'ADMIN'::"Role""#));
+        let other_variant = column_with_default(Some("USER"));
+        let without_default = column_with_default(None);
+
+        assert!(ColumnDiffer {
+            previous: &described,
+            next: &calculated,
+        }
+        .defaults_match());
+
+        assert!(!ColumnDiffer {
+            previous: &other_variant,
+            next: &calculated,
+        }
+        .defaults_match());
+
+        assert!(!ColumnDiffer {
+            previous: &without_default,
+            next: &calculated,
+        }
+        .defaults_match());
+    }
+
+    #[test]
+    fn decimal_precisions_match() {
+        let column_with_raw_type = |raw: &str| Column {
+            name: "A".to_owned(),
+            tpe: ColumnType {
+                raw: raw.to_owned(),
+                family: ColumnTypeFamily::Float,
+                arity: ColumnArity::Required,
+            },
+            default: None,
+            auto_increment: false,
+            generated: None,
+            on_update_current_timestamp: false,
+        };
+
+        let calculated = column_with_raw_type("numeric(10,2)");
+        let described = column_with_raw_type("DECIMAL(10, 2)");
+        let other_scale = column_with_raw_type("numeric(10,4)");
+        let without_precision = column_with_raw_type("decimal");
+
+        assert!(!ColumnDiffer {
+            previous: &described,
+            next: &calculated,
+        }
+        .all_changes()
+        .type_changed());
+
+        assert!(ColumnDiffer {
+            previous: &other_scale,
+            next: &calculated,
+        }
+        .all_changes()
+        .type_changed());
+
+        assert!(!ColumnDiffer {
+            previous: &without_precision,
+            next: &calculated,
+        }
+        .all_changes()
+        .type_changed());
+    }
 }
@@ -0,0 +1,369 @@
+use quaint::prelude::SqlFamily;
+use sql_schema_describer::{ColumnType, ColumnTypeFamily};
+
+/// What happens to the existing values of a column when its type changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ColumnTypeChange {
+    /// Every value can be cast to the new type, e.g. an int to a float.
+    SafeCast,
+    /// Values can be cast, but the cast can fail or lose precision, e.g. a string to an int.
+    RiskyCast,
+    /// The values can't be cast, the column is dropped and recreated.
+    NotCastable,
+}
+
+pub(crate) fn column_type_change(previous: &ColumnType, next: &ColumnType, sql_family: SqlFamily) -> ColumnTypeChange {
+    if let Some(change) = raw_type_change(&previous.raw, &next.raw, sql_family) {
+        return change;
+    }
+
+    match sql_family {
+        SqlFamily::Postgres => postgres_column_type_change(&previous.family, &next.family),
+        SqlFamily::Mysql => mysql_column_type_change(&previous.family, &next.family),
+        SqlFamily::Sqlite => sqlite_column_type_change(&previous.family, &next.family),
+    }
+}
+
+/// Compares the raw types of the columns if both are known, e.g. `varchar(100)` and `varchar(50)`. Types of the same
+/// name compare their length, or precision and scale, integer and floating point types their size. SQLite doesn't
+/// enforce the sizes of its types, the type families decide there.
+fn raw_type_change(previous: &str, next: &str, sql_family: SqlFamily) -> Option<ColumnTypeChange> {
+    if sql_family == SqlFamily::Sqlite {
+        return None;
+    }
+
+    let previous = RawType::parse(previous)?;
+    let next = RawType::parse(next)?;
+
+    let holds_previous = if previous.name == next.name {
+        next.holds(&previous)
+    } else {
+        match (type_size(&previous.name, sql_family), type_size(&next.name, sql_family)) {
+            (Some((previous_kind, previous_size)), Some((next_kind, next_size))) if previous_kind == next_kind => {
+                next_size >= previous_size
+            }
+            _ => return None,
+        }
+    };
+
+    if holds_previous {
+        Some(ColumnTypeChange::SafeCast)
+    } else {
+        Some(ColumnTypeChange::RiskyCast)
+    }
+}
+
+/// A raw type with its numeric modifiers, e.g. `varchar(100)` or `numeric(10,2)`.
+#[derive(Debug, PartialEq)]
+struct RawType {
+    name: String,
+    modifiers: Vec<u32>,
+}
+
+impl RawType {
+    /// Arrays and types with other modifiers, e.g. `geometry(Point,4326)` or `int(10) unsigned`, aren't compared.
+    fn parse(raw: &str) -> Option<RawType> {
+        let raw = raw.trim().to_lowercase();
+
+        if raw.is_empty() || raw.starts_with('_') || raw.ends_with("[]") {
+            return None;
+        }
+
+        let (name, modifiers) = match raw.find('(') {
+            Some(start) => {
+                let end = raw.rfind(')')?;
+
+                if end < start || !raw[end + 1..].trim().is_empty() {
+                    return None;
+                }
+
+                let modifiers = raw[start + 1..end]
+                    .split(',')
+                    .map(|modifier| modifier.trim().parse().ok())
+                    .collect::<Option<Vec<u32>>>()?;
+
+                (raw[..start].trim(), modifiers)
+            }
+            None => (raw.as_str(), Vec::new()),
+        };
+
+        let name = match name {
+            "character varying" => "varchar",
+            "character" | "bpchar" => "char",
+            "decimal" => "numeric",
+            name => name,
+        };
+
+        Some(RawType {
+            name: name.to_owned(),
+            modifiers,
+        })
+    }
+
+    /// Whether the values of the previous type of the same name fit this type. Types without modifiers, e.g.
+    /// `varchar` or `numeric` on Postgres, don't limit their values.
+    fn holds(&self, previous: &RawType) -> bool {
+        match (previous.modifiers.as_slice(), self.modifiers.as_slice()) {
+            (_, []) => true,
+            ([], _) => false,
+            ([previous_length], [next_length]) => next_length >= previous_length,
+            // The digits before the decimal point and the scale must both fit.
+            ([previous_precision, previous_scale], [next_precision, next_scale]) => {
+                next_scale >= previous_scale
+                    && next_precision.saturating_sub(*next_scale) >= previous_precision.saturating_sub(*previous_scale)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The size in bytes of integer (`'i'`) and floating point (`'f'`) types.
+fn type_size(name: &str, sql_family: SqlFamily) -> Option<(char, u8)> {
+    match (sql_family, name) {
+        (SqlFamily::Mysql, "tinyint") => Some(('i', 1)),
+        (_, "smallint") | (SqlFamily::Postgres, "int2") => Some(('i', 2)),
+        (SqlFamily::Mysql, "mediumint") => Some(('i', 3)),
+        (_, "int") | (_, "integer") | (SqlFamily::Postgres, "int4") => Some(('i', 4)),
+        (_, "bigint") | (SqlFamily::Postgres, "int8") => Some(('i', 8)),
+        (SqlFamily::Mysql, "float") | (SqlFamily::Postgres, "float4") | (SqlFamily::Postgres, "real") => Some(('f', 4)),
+        (SqlFamily::Mysql, "double") | (SqlFamily::Postgres, "float8") | (SqlFamily::Postgres, "double precision") => {
+            Some(('f', 8))
+        }
+        _ => None,
+    }
+}
+
+fn postgres_column_type_change(previous: &ColumnTypeFamily, next: &ColumnTypeFamily) -> ColumnTypeChange {
+    use ColumnTypeChange::*;
+    use ColumnTypeFamily::*;
+
+    match (previous, next) {
+        // We know nothing about the casts between unsupported types.
+        (Unknown, _) | (_, Unknown) => NotCastable,
+        // E.g. text and citext.
+        (previous, next) if previous == next => SafeCast,
+        (Int, Float) | (Boolean, Int) => SafeCast,
+        (Int, String) | (Float, String) | (Boolean, String) | (DateTime, String) | (Enum(_), String) => SafeCast,
+        (Uuid, String) | (Json, String) => SafeCast,
+        (Float, Int) | (Int, Boolean) => RiskyCast,
+        (String, Int) | (String, Float) | (String, Boolean) | (String, DateTime) | (String, Enum(_)) => RiskyCast,
+        (String, Uuid) | (String, Json) => RiskyCast,
+        // Through the text representation of the variants.
+        (Enum(_), Enum(_)) => RiskyCast,
+        _ => NotCastable,
+    }
+}
+
+fn mysql_column_type_change(previous: &ColumnTypeFamily, next: &ColumnTypeFamily) -> ColumnTypeChange {
+    use ColumnTypeChange::*;
+    use ColumnTypeFamily::*;
+
+    match (previous, next) {
+        (Unknown, _) | (_, Unknown) => NotCastable,
+        (previous, next) if previous == next => SafeCast,
+        // Booleans are `tinyint(1)` columns.
+        (Int, Float) | (Boolean, Int) | (Boolean, Float) => SafeCast,
+        (Int, String) | (Float, String) | (Boolean, String) | (DateTime, String) | (Enum(_), String) => SafeCast,
+        (Json, String) => SafeCast,
+        // Out of range values fail in strict mode.
+        (Float, Int) | (Int, Boolean) | (Float, Boolean) => RiskyCast,
+        (String, Int) | (String, Float) | (String, Boolean) | (String, DateTime) | (String, Enum(_)) => RiskyCast,
+        (String, Json) => RiskyCast,
+        // MySQL enums are specific to their column, values missing from the new enum fail.
+        (Enum(_), Enum(_)) => RiskyCast,
+        _ => NotCastable,
+    }
+}
+
+fn sqlite_column_type_change(previous: &ColumnTypeFamily, next: &ColumnTypeFamily) -> ColumnTypeChange {
+    use ColumnTypeChange::*;
+    use ColumnTypeFamily::*;
+
+    // The values are copied into the redefined table, where the type affinity of the column converts them.
+    match (previous, next) {
+        (Unknown, _) | (_, Unknown) => NotCastable,
+        (previous, next) if previous == next => SafeCast,
+        (Int, Float) | (Boolean, Int) => SafeCast,
+        (Int, String) | (Float, String) | (Boolean, String) | (DateTime, String) => SafeCast,
+        // Values without a numeric representation are kept as text.
+        (Float, Int) | (Int, Boolean) => RiskyCast,
+        (String, Int) | (String, Float) | (String, Boolean) | (String, DateTime) => RiskyCast,
+        _ => NotCastable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sql_schema_describer::ColumnArity;
+
+    fn change(previous: ColumnTypeFamily, next: ColumnTypeFamily, sql_family: SqlFamily) -> ColumnTypeChange {
+        column_type_change(
+            &ColumnType::pure(previous, ColumnArity::Required),
+            &ColumnType::pure(next, ColumnArity::Required),
+            sql_family,
+        )
+    }
+
+    #[test]
+    fn widening_casts_are_safe() {
+        for sql_family in &[SqlFamily::Postgres, SqlFamily::Mysql, SqlFamily::Sqlite] {
+            assert_eq!(
+                change(ColumnTypeFamily::Int, ColumnTypeFamily::Float, *sql_family),
+                ColumnTypeChange::SafeCast
+            );
+            assert_eq!(
+                change(ColumnTypeFamily::Int, ColumnTypeFamily::String, *sql_family),
+                ColumnTypeChange::SafeCast
+            );
+        }
+    }
+
+    #[test]
+    fn narrowing_casts_are_risky() {
+        for sql_family in &[SqlFamily::Postgres, SqlFamily::Mysql, SqlFamily::Sqlite] {
+            assert_eq!(
+                change(ColumnTypeFamily::Float, ColumnTypeFamily::Int, *sql_family),
+                ColumnTypeChange::RiskyCast
+            );
+            assert_eq!(
+                change(ColumnTypeFamily::String, ColumnTypeFamily::DateTime, *sql_family),
+                ColumnTypeChange::RiskyCast
+            );
+        }
+
+        assert_eq!(
+            change(
+                ColumnTypeFamily::Enum("A".to_owned()),
+                ColumnTypeFamily::Enum("B".to_owned()),
+                SqlFamily::Postgres
+            ),
+            ColumnTypeChange::RiskyCast
+        );
+    }
+
+    fn raw_change(previous: &str, next: &str, family: ColumnTypeFamily, sql_family: SqlFamily) -> ColumnTypeChange {
+        let column_type = |raw: &str| ColumnType {
+            raw: raw.to_owned(),
+            family: family.clone(),
+            arity: ColumnArity::Required,
+        };
+
+        column_type_change(&column_type(previous), &column_type(next), sql_family)
+    }
+
+    #[test]
+    fn shorter_lengths_are_risky() {
+        for sql_family in &[SqlFamily::Postgres, SqlFamily::Mysql] {
+            assert_eq!(
+                raw_change("VARCHAR(100)", "VARCHAR(50)", ColumnTypeFamily::String, *sql_family),
+                ColumnTypeChange::RiskyCast
+            );
+            assert_eq!(
+                raw_change("varchar(50)", "varchar(100)", ColumnTypeFamily::String, *sql_family),
+                ColumnTypeChange::SafeCast
+            );
+        }
+
+        assert_eq!(
+            raw_change(
+                "character varying(20)",
+                "varchar(10)",
+                ColumnTypeFamily::String,
+                SqlFamily::Postgres
+            ),
+            ColumnTypeChange::RiskyCast
+        );
+        assert_eq!(
+            raw_change("varchar(100)", "varchar", ColumnTypeFamily::String, SqlFamily::Postgres),
+            ColumnTypeChange::SafeCast
+        );
+    }
+
+    #[test]
+    fn smaller_integer_and_float_types_are_risky() {
+        assert_eq!(
+            raw_change("BIGINT", "INT", ColumnTypeFamily::Int, SqlFamily::Mysql),
+            ColumnTypeChange::RiskyCast
+        );
+        assert_eq!(
+            raw_change("int8", "int4", ColumnTypeFamily::Int, SqlFamily::Postgres),
+            ColumnTypeChange::RiskyCast
+        );
+        assert_eq!(
+            raw_change("int4", "int8", ColumnTypeFamily::Int, SqlFamily::Postgres),
+            ColumnTypeChange::SafeCast
+        );
+        assert_eq!(
+            raw_change("double", "float", ColumnTypeFamily::Float, SqlFamily::Mysql),
+            ColumnTypeChange::RiskyCast
+        );
+    }
+
+    #[test]
+    fn lower_precision_or_scale_is_risky() {
+        for sql_family in &[SqlFamily::Postgres, SqlFamily::Mysql] {
+            assert_eq!(
+                raw_change("DECIMAL(10,2)", "DECIMAL(5,2)", ColumnTypeFamily::Float, *sql_family),
+                ColumnTypeChange::RiskyCast
+            );
+            assert_eq!(
+                raw_change("decimal(10,2)", "decimal(10,4)", ColumnTypeFamily::Float, *sql_family),
+                ColumnTypeChange::RiskyCast
+            );
+            assert_eq!(
+                raw_change("decimal(10,2)", "numeric(12, 3)", ColumnTypeFamily::Float, *sql_family),
+                ColumnTypeChange::SafeCast
+            );
+        }
+    }
+
+    #[test]
+    fn unsupported_types_of_the_same_name_compare_their_modifiers() {
+        assert_eq!(
+            raw_change("bit(8)", "bit(4)", ColumnTypeFamily::Unknown, SqlFamily::Postgres),
+            ColumnTypeChange::RiskyCast
+        );
+        assert_eq!(
+            raw_change(
+                "geometry(Point,4326)",
+                "geometry(Polygon,4326)",
+                ColumnTypeFamily::Unknown,
+                SqlFamily::Postgres
+            ),
+            ColumnTypeChange::NotCastable
+        );
+    }
+
+    #[test]
+    fn sqlite_doesnt_enforce_lengths() {
+        assert_eq!(
+            raw_change(
+                "VARCHAR(100)",
+                "VARCHAR(50)",
+                ColumnTypeFamily::String,
+                SqlFamily::Sqlite
+            ),
+            ColumnTypeChange::SafeCast
+        );
+    }
+
+    #[test]
+    fn unrelated_types_are_not_castable() {
+        for sql_family in &[SqlFamily::Postgres, SqlFamily::Mysql, SqlFamily::Sqlite] {
+            assert_eq!(
+                change(ColumnTypeFamily::DateTime, ColumnTypeFamily::Int, *sql_family),
+                ColumnTypeChange::NotCastable
+            );
+        }
+
+        assert_eq!(
+            change(
+                ColumnTypeFamily::Unknown,
+                ColumnTypeFamily::Unknown,
+                SqlFamily::Postgres
+            ),
+            ColumnTypeChange::NotCastable
+        );
+    }
+}
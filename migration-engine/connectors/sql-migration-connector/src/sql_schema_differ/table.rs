@@ -1,5 +1,5 @@
 use super::column::ColumnDiffer;
-use sql_schema_describer::{Column, ForeignKey, Index, Table};
+use sql_schema_describer::{Column, ForeignKey, Index, PrimaryKey, Table, TableOptions};
 
 pub(crate) struct TableDiffer<'schema> {
     pub(crate) previous: &'schema Table,
@@ -33,6 +33,44 @@ impl<'schema> TableDiffer<'schema> {
         })
     }
 
+    /// The options that are set on the next table and differ from the previous table. Options that are not set are
+    /// left to the database, so they never count as changed.
+    pub(crate) fn changed_options(&self) -> Option<TableOptions> {
+        let previous = &self.previous.options;
+        let next = &self.next.options;
+
+        let changed_name = |previous: &Option<String>, next: &Option<String>| match (previous, next) {
+            (Some(previous), Some(next)) if previous.eq_ignore_ascii_case(next) => None,
+            (_, next) => next.clone(),
+        };
+
+        let changed_options = TableOptions {
+            engine: changed_name(&previous.engine, &next.engine),
+            charset: changed_name(&previous.charset, &next.charset),
+            tablespace: changed_name(&previous.tablespace, &next.tablespace),
+            fillfactor: next
+                .fillfactor
+                .filter(|fillfactor| previous.fillfactor != Some(*fillfactor)),
+        };
+
+        if changed_options == TableOptions::default() {
+            None
+        } else {
+            Some(changed_options)
+        }
+    }
+
+    /// The primary key of the next table, if its columns differ from the previous primary key. The order of the
+    /// columns matters: it is the order of the index, and the order of the rows in MySQL's clustered index.
+    pub(crate) fn changed_primary_key(&self) -> Option<&'schema PrimaryKey> {
+        let previous_columns = self.previous.primary_key.as_ref().map(|pk| &pk.columns);
+
+        self.next
+            .primary_key
+            .as_ref()
+            .filter(|next| previous_columns != Some(&next.columns))
+    }
+
     pub(crate) fn created_foreign_keys(&self) -> impl Iterator<Item = &ForeignKey> {
         self.next_foreign_keys().filter(move |next_fk| {
             self.previous_foreign_keys()
@@ -104,5 +142,5 @@ fn columns_match(a: &Column, b: &Column) -> bool {
 
 /// Compare two SQL indexes and return whether they only differ by name.
 fn indexes_match(first: &Index, second: &Index) -> bool {
-    first.columns == second.columns && first.tpe == second.tpe
+    first.columns == second.columns && first.tpe == second.tpe && first.column_options == second.column_options
 }
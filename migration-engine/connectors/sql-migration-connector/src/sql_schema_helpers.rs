@@ -1,4 +1,4 @@
-use sql_schema_describer::{Column, ColumnType, ForeignKey, SqlSchema, Table};
+use sql_schema_describer::{Column, ColumnType, ForeignKey, GeneratedColumn, SqlSchema, Table};
 
 pub(crate) fn walk_columns<'a>(schema: &'a SqlSchema) -> impl Iterator<Item = ColumnRef<'a>> + 'a {
     schema.tables.iter().flat_map(move |table| {
@@ -9,6 +9,7 @@ pub(crate) fn walk_columns<'a>(schema: &'a SqlSchema) -> impl Iterator<Item = Co
     })
 }
 
+#[derive(Clone, Copy)]
 pub(crate) struct ColumnRef<'a> {
     pub(crate) schema: &'a SqlSchema,
     pub(crate) column: &'a Column,
@@ -32,6 +33,14 @@ impl<'a> ColumnRef<'a> {
         self.column.auto_increment
     }
 
+    pub(crate) fn generated(&self) -> Option<&'a GeneratedColumn> {
+        self.column.generated.as_ref()
+    }
+
+    pub(crate) fn on_update_current_timestamp(&self) -> bool {
+        self.column.on_update_current_timestamp
+    }
+
     pub(crate) fn is_required(&self) -> bool {
         self.column.is_required()
     }
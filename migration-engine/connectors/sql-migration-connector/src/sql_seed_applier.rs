@@ -0,0 +1,201 @@
+use crate::{Component, SqlResult};
+use migration_connector::*;
+use prisma_models::PrismaValue;
+use quaint::{ast::*, prelude::SqlFamily};
+use sql_identifier::{quote_with_schema, Delimiter};
+use std::convert::TryFrom;
+
+static TABLE_NAME: &str = "_Seed";
+static NAME_COLUMN: &str = "name";
+
+pub struct SqlSeedApplier<'a> {
+    pub connector: &'a crate::SqlMigrationConnector,
+}
+
+impl Component for SqlSeedApplier<'_> {
+    fn connector(&self) -> &crate::SqlMigrationConnector {
+        self.connector
+    }
+}
+
+#[async_trait::async_trait]
+impl SeedApplier for SqlSeedApplier<'_> {
+    async fn seed_was_applied(&self, name: &str) -> ConnectorResult<bool> {
+        crate::catch(self.connection_info(), async {
+            self.create_seed_table().await?;
+
+            let query = Select::from_table(self.table()).so_that(NAME_COLUMN.equals(name));
+            let result_set = self.conn().query(query.into()).await?;
+
+            Ok(!result_set.is_empty())
+        })
+        .await
+    }
+
+    /// Applies the seed in a transaction, so that a failing seed leaves neither records nor its marker behind. The
+    /// marker is inserted first: a concurrent run of the same seed waits for it and fails on its primary key. Schema
+    /// changes of the script are committed implicitly on MySQL.
+    async fn apply_seed(&self, seed: &Seed) -> ConnectorResult<()> {
+        crate::catch(self.connection_info(), async {
+            self.create_seed_table().await?;
+            self.conn().query_raw("BEGIN", &[]).await?;
+
+            match self.insert_seed(seed).await {
+                Ok(()) => {
+                    self.conn().query_raw("COMMIT", &[]).await?;
+                    Ok(())
+                }
+                Err(err) => {
+                    if let Err(rollback_err) = self.conn().query_raw("ROLLBACK", &[]).await {
+                        tracing::warn!("Failed to roll back the seed `{}`: {}", seed.name, rollback_err);
+                    }
+
+                    Err(err)
+                }
+            }
+        })
+        .await
+    }
+}
+
+impl SqlSeedApplier<'_> {
+    fn table(&self) -> Table {
+        self.qualified_table(TABLE_NAME)
+    }
+
+    async fn insert_seed(&self, seed: &Seed) -> SqlResult<()> {
+        let insert = Insert::single_into(self.table()).value(NAME_COLUMN, seed.name.as_str());
+        self.conn().query(insert.into()).await?;
+
+        if let Some(script) = &seed.script {
+            for statement in split_statements(script) {
+                let result = self.conn().query_raw(statement, &[]).await;
+                // Scripts can change the schema as well.
+                self.connector.invalidate_described_schema();
+                result?;
+            }
+        }
+
+        for records in seed.records.iter() {
+            for row in records.rows.iter() {
+                self.insert_row(&records.table, row).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn qualified_table(&self, name: &str) -> Table {
+        match self.sql_family() {
+            // Otherwise quaint produces invalid SQL, like in the migration persistence.
+            SqlFamily::Sqlite => name.to_string().into(),
+            _ => (self.schema_name().to_string(), name.to_string()).into(),
+        }
+    }
+
+    async fn create_seed_table(&self) -> SqlResult<()> {
+        let sql = match self.sql_family() {
+            SqlFamily::Postgres => format!(
+                "CREATE TABLE IF NOT EXISTS {} (\"name\" TEXT NOT NULL PRIMARY KEY, \"applied_at\" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP)",
+                quote_with_schema(Delimiter::DoubleQuote, self.schema_name(), TABLE_NAME)
+            ),
+            SqlFamily::Mysql => format!(
+                "CREATE TABLE IF NOT EXISTS {} (`name` VARCHAR(191) NOT NULL PRIMARY KEY, `applied_at` DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3))",
+                quote_with_schema(Delimiter::Backtick, self.schema_name(), TABLE_NAME)
+            ),
+            SqlFamily::Sqlite => format!(
+                "CREATE TABLE IF NOT EXISTS {} (\"name\" TEXT NOT NULL PRIMARY KEY, \"applied_at\" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP)",
+                quote_with_schema(Delimiter::DoubleQuote, self.schema_name(), TABLE_NAME)
+            ),
+        };
+
+        self.conn().query_raw(&sql, &[]).await?;
+
+        Ok(())
+    }
+
+    async fn insert_row(&self, table: &str, row: &serde_json::Map<String, serde_json::Value>) -> SqlResult<()> {
+        let mut insert = Insert::single_into(self.qualified_table(table));
+
+        for (column, value) in row.iter() {
+            let value = match value {
+                // Nested objects are stored as JSON.
+                serde_json::Value::Object(_) => PrismaValue::String(value.to_string()),
+                value => PrismaValue::try_from(value.clone())
+                    .map_err(|err| format!("Invalid seed value for `{}`.`{}`: {}", table, column, err))?,
+            };
+
+            insert = insert.value(column.as_str(), ParameterizedValue::from(value));
+        }
+
+        self.conn().query(insert.into()).await?;
+
+        Ok(())
+    }
+}
+
+/// Splits a script into its statements on the semicolons outside of quotes and line comments. Dollar quoted
+/// strings and procedure bodies are not recognized.
+fn split_statements(script: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut statement_start = 0;
+    let mut quote: Option<char> = None;
+    let mut in_comment = false;
+    let mut chars = script.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        match (quote, c) {
+            _ if in_comment => in_comment = c != '\n',
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '\'') | (None, '"') | (None, '`') => quote = Some(c),
+            (None, '-') if chars.peek().map(|(_, next)| *next) == Some('-') => in_comment = true,
+            (None, ';') => {
+                statements.push(&script[statement_start..idx]);
+                statement_start = idx + 1;
+            }
+            _ => (),
+        }
+    }
+
+    statements.push(&script[statement_start..]);
+
+    statements
+        .into_iter()
+        .map(str::trim)
+        .filter(|statement| !is_blank(statement))
+        .collect()
+}
+
+fn is_blank(statement: &str) -> bool {
+    statement.lines().all(|line| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with("--")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_statements_ignores_semicolons_in_strings_and_comments() {
+        let script = r#"
+            -- Seed the users; admins first.
+            INSERT INTO "User" ("name") VALUES ('Ann; the admin');
+            INSERT INTO "User" ("name") VALUES ("Bob")  ;
+
+            UPDATE `User` SET `name` = 'O''Neil';
+            -- Done.
+        "#;
+
+        assert_eq!(
+            split_statements(script),
+            &[
+                "-- Seed the users; admins first.\n            INSERT INTO \"User\" (\"name\") VALUES ('Ann; the admin')",
+                "INSERT INTO \"User\" (\"name\") VALUES (\"Bob\")",
+                "UPDATE `User` SET `name` = 'O''Neil'",
+            ]
+        );
+    }
+}
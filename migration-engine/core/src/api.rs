@@ -44,15 +44,20 @@ where
 #[async_trait::async_trait]
 pub trait GenericApi: Send + Sync + 'static {
     async fn apply_migration(&self, input: &ApplyMigrationInput) -> CoreResult<MigrationStepsResultOutput>;
+    async fn apply_migrations(&self, input: &ApplyMigrationsInput) -> CoreResult<ApplyMigrationsOutput>;
     async fn calculate_database_steps(
         &self,
         input: &CalculateDatabaseStepsInput,
     ) -> CoreResult<MigrationStepsResultOutput>;
     async fn calculate_datamodel(&self, input: &CalculateDatamodelInput) -> CoreResult<CalculateDatamodelOutput>;
+    async fn diff_database_schemas(&self, input: &DiffDatabaseSchemasInput) -> CoreResult<DiffDatabaseSchemasOutput>;
+    async fn diff_datamodels(&self, input: &DiffDatamodelsInput) -> CoreResult<DiffDatamodelsOutput>;
     async fn infer_migration_steps(&self, input: &InferMigrationStepsInput) -> CoreResult<MigrationStepsResultOutput>;
     async fn list_migrations(&self, input: &serde_json::Value) -> CoreResult<Vec<ListMigrationsOutput>>;
+    async fn migration_logs(&self, input: &MigrationLogsInput) -> CoreResult<MigrationLogsOutput>;
     async fn migration_progress(&self, input: &MigrationProgressInput) -> CoreResult<MigrationProgressOutput>;
     async fn reset(&self, input: &serde_json::Value) -> CoreResult<serde_json::Value>;
+    async fn seed(&self, input: &SeedInput) -> CoreResult<SeedOutput>;
     async fn unapply_migration(&self, input: &UnapplyMigrationInput) -> CoreResult<UnapplyMigrationOutput>;
     fn migration_persistence<'a>(&'a self) -> Box<dyn MigrationPersistence + 'a>;
     fn connector_type(&self) -> &'static str;
@@ -81,6 +86,12 @@ where
             .await
     }
 
+    async fn apply_migrations(&self, input: &ApplyMigrationsInput) -> CoreResult<ApplyMigrationsOutput> {
+        self.handle_command::<ApplyMigrationsCommand>(input)
+            .instrument(tracing::info_span!("ApplyMigrations"))
+            .await
+    }
+
     async fn calculate_database_steps(
         &self,
         input: &CalculateDatabaseStepsInput,
@@ -96,6 +107,18 @@ where
             .await
     }
 
+    async fn diff_database_schemas(&self, input: &DiffDatabaseSchemasInput) -> CoreResult<DiffDatabaseSchemasOutput> {
+        self.handle_command::<DiffDatabaseSchemasCommand>(input)
+            .instrument(tracing::info_span!("DiffDatabaseSchemas"))
+            .await
+    }
+
+    async fn diff_datamodels(&self, input: &DiffDatamodelsInput) -> CoreResult<DiffDatamodelsOutput> {
+        self.handle_command::<DiffDatamodelsCommand>(input)
+            .instrument(tracing::info_span!("DiffDatamodels"))
+            .await
+    }
+
     async fn infer_migration_steps(&self, input: &InferMigrationStepsInput) -> CoreResult<MigrationStepsResultOutput> {
         self.handle_command::<InferMigrationStepsCommand>(input)
             .instrument(tracing::info_span!(
@@ -111,6 +134,15 @@ where
             .await
     }
 
+    async fn migration_logs(&self, input: &MigrationLogsInput) -> CoreResult<MigrationLogsOutput> {
+        self.handle_command::<MigrationLogsCommand>(input)
+            .instrument(tracing::info_span!(
+                "MigrationLogs",
+                migration_id = input.migration_id.as_str()
+            ))
+            .await
+    }
+
     async fn migration_progress(&self, input: &MigrationProgressInput) -> CoreResult<MigrationProgressOutput> {
         self.handle_command::<MigrationProgressCommand>(input)
             .instrument(tracing::info_span!(
@@ -126,6 +158,12 @@ where
             .await
     }
 
+    async fn seed(&self, input: &SeedInput) -> CoreResult<SeedOutput> {
+        self.handle_command::<SeedCommand>(input)
+            .instrument(tracing::info_span!("Seed", seed = input.name.as_str()))
+            .await
+    }
+
     async fn unapply_migration(&self, input: &UnapplyMigrationInput) -> CoreResult<UnapplyMigrationOutput> {
         self.handle_command::<UnapplyMigrationCommand>(input)
             .instrument(tracing::info_span!("UnapplyMigration"))
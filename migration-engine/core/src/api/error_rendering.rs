@@ -18,6 +18,13 @@ pub fn render_error(crate_error: CoreError) -> Error {
                 .unwrap()
                 .into()
         }
+        CoreError::CommandError(CommandError::EditedMigrations(migration_names)) => {
+            KnownError::new(user_facing_errors::migration_engine::EditedMigrations {
+                migration_names: migration_names.join(", "),
+            })
+            .unwrap()
+            .into()
+        }
         _ => Error::from_dyn_error(&crate_error),
     }
 }
@@ -1,26 +1,46 @@
 use super::GenericApi;
 use crate::{commands::*, CoreResult};
-use futures::{FutureExt, TryFutureExt};
+use futures::{
+    future::{self, Either},
+    FutureExt, TryFutureExt,
+};
+use json_rpc_stdio::{Notifications, Notifier};
 use jsonrpc_core::types::error::Error as JsonRpcError;
 use jsonrpc_core::{IoHandler, Params};
-use std::{io, sync::Arc};
+use std::{io, sync::Arc, time::Duration};
 use thiserror::Error;
+use tokio::sync::Mutex;
+
+/// How often the progress of running migrations is sent to the client.
+const MIGRATION_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct RpcApi {
     io_handler: jsonrpc_core::IoHandler<()>,
     executor: Arc<dyn GenericApi>,
+    /// The preview features enabled by the generators of the data model.
+    preview_features: Arc<Vec<String>>,
+    notifier: Notifier,
+    notifications: Notifications,
+    /// Held while commands changing the database schema run, so that they don't interleave.
+    schema_lock: Arc<Mutex<()>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum RpcCommand {
     InferMigrationSteps,
     ListMigrations,
+    MigrationLogs,
     MigrationProgress,
     ApplyMigration,
+    ApplyMigrations,
     UnapplyMigration,
     Reset,
+    Seed,
     CalculateDatamodel,
     CalculateDatabaseSteps,
+    DiffDatabaseSchemas,
+    DiffDatamodels,
+    ServerInfo,
 }
 
 impl RpcCommand {
@@ -28,32 +48,68 @@ impl RpcCommand {
         match self {
             RpcCommand::InferMigrationSteps => "inferMigrationSteps",
             RpcCommand::ListMigrations => "listMigrations",
+            RpcCommand::MigrationLogs => "migrationLogs",
             RpcCommand::MigrationProgress => "migrationProgress",
             RpcCommand::ApplyMigration => "applyMigration",
+            RpcCommand::ApplyMigrations => "applyMigrations",
             RpcCommand::UnapplyMigration => "unapplyMigration",
             RpcCommand::Reset => "reset",
+            RpcCommand::Seed => "seed",
             RpcCommand::CalculateDatamodel => "calculateDatamodel",
             RpcCommand::CalculateDatabaseSteps => "calculateDatabaseSteps",
+            RpcCommand::DiffDatabaseSchemas => "diffDatabaseSchemas",
+            RpcCommand::DiffDatamodels => "diffDatamodels",
+            RpcCommand::ServerInfo => "serverInfo",
+        }
+    }
+
+    /// Commands changing the database schema. They run one at a time, and can't be cancelled, which would leave
+    /// the database in between migrations.
+    fn changes_schema(&self) -> bool {
+        match self {
+            RpcCommand::ApplyMigration
+            | RpcCommand::ApplyMigrations
+            | RpcCommand::UnapplyMigration
+            | RpcCommand::Reset
+            | RpcCommand::Seed => true,
+            _ => false,
         }
     }
 }
 
 static AVAILABLE_COMMANDS: &[RpcCommand] = &[
     RpcCommand::ApplyMigration,
+    RpcCommand::ApplyMigrations,
     RpcCommand::InferMigrationSteps,
     RpcCommand::ListMigrations,
+    RpcCommand::MigrationLogs,
     RpcCommand::MigrationProgress,
     RpcCommand::UnapplyMigration,
     RpcCommand::Reset,
+    RpcCommand::Seed,
     RpcCommand::CalculateDatamodel,
     RpcCommand::CalculateDatabaseSteps,
+    RpcCommand::DiffDatabaseSchemas,
+    RpcCommand::DiffDatamodels,
+    RpcCommand::ServerInfo,
 ];
 
 impl RpcApi {
     pub async fn new(datamodel: &str) -> CoreResult<Self> {
+        let executor = crate::migration_api(datamodel).await?;
+        let preview_features = datamodel::parse_configuration(datamodel)
+            .map(|config| config.preview_features().into_iter().map(String::from).collect())
+            .unwrap_or_default();
+
+        let (notifier, notifications) = json_rpc_stdio::notification_channel();
+
         let mut rpc_api = Self {
             io_handler: IoHandler::default(),
-            executor: crate::migration_api(datamodel).await?,
+            executor,
+            preview_features: Arc::new(preview_features),
+            notifier,
+            notifications,
+            schema_lock: Arc::new(Mutex::new(())),
         };
 
         for cmd in AVAILABLE_COMMANDS {
@@ -67,6 +123,18 @@ impl RpcApi {
         &self.io_handler
     }
 
+    /// Handle requests over stdio concurrently until EOF, sending `migrationProgress` notifications while
+    /// migrations are applied.
+    pub async fn serve_stdio(self) -> io::Result<()> {
+        let uncancellable_methods: Vec<&str> = AVAILABLE_COMMANDS
+            .iter()
+            .filter(|cmd| cmd.changes_schema())
+            .map(|cmd| cmd.name())
+            .collect();
+
+        json_rpc_stdio::run_with_notifications(&self.io_handler, self.notifications, &uncancellable_methods).await
+    }
+
     /// Handle one request over stdio.
     pub fn handle(&self) -> CoreResult<String> {
         let mut json_is_complete = false;
@@ -87,11 +155,25 @@ impl RpcApi {
 
     fn add_command_handler(&mut self, cmd: RpcCommand) {
         let executor = Arc::clone(&self.executor);
+        let preview_features = Arc::clone(&self.preview_features);
+        let notifier = self.notifier.clone();
+        let schema_lock = Arc::clone(&self.schema_lock);
 
         self.io_handler.add_method(cmd.name(), move |params: Params| {
             let cmd = cmd.clone();
             let executor = Arc::clone(&executor);
-            let fut = async move { Self::create_handler(&executor, cmd, &params).await };
+            let preview_features = Arc::clone(&preview_features);
+            let notifier = notifier.clone();
+            let schema_lock = Arc::clone(&schema_lock);
+            let fut = async move {
+                let _guard = if cmd.changes_schema() {
+                    Some(schema_lock.lock().await)
+                } else {
+                    None
+                };
+
+                Self::create_handler(&executor, &preview_features, &notifier, cmd, &params).await
+            };
 
             fut.boxed().compat()
         });
@@ -99,10 +181,13 @@ impl RpcApi {
 
     async fn create_handler(
         executor: &Arc<dyn GenericApi>,
+        preview_features: &[String],
+        notifier: &Notifier,
         cmd: RpcCommand,
         params: &Params,
     ) -> Result<serde_json::Value, JsonRpcError> {
-        let result: Result<serde_json::Value, RunCommandError> = Self::run_command(&executor, cmd, params).await;
+        let result: Result<serde_json::Value, RunCommandError> =
+            Self::run_command(&executor, preview_features, notifier, cmd, params).await;
 
         match result {
             Ok(result) => Ok(result),
@@ -113,6 +198,8 @@ impl RpcApi {
 
     async fn run_command(
         executor: &Arc<dyn GenericApi>,
+        preview_features: &[String],
+        notifier: &Notifier,
         cmd: RpcCommand,
         params: &Params,
     ) -> Result<serde_json::Value, RunCommandError> {
@@ -123,21 +210,33 @@ impl RpcApi {
                 render(executor.infer_migration_steps(&input).await?)
             }
             RpcCommand::ListMigrations => render(executor.list_migrations(&serde_json::Value::Null).await?),
+            RpcCommand::MigrationLogs => {
+                let input: MigrationLogsInput = params.clone().parse()?;
+                render(executor.migration_logs(&input).await?)
+            }
             RpcCommand::MigrationProgress => {
                 let input: MigrationProgressInput = params.clone().parse()?;
                 render(executor.migration_progress(&input).await?)
             }
             RpcCommand::ApplyMigration => {
                 let input: ApplyMigrationInput = params.clone().parse()?;
-                let result = executor.apply_migration(&input).await?;
+                let result = Self::apply_migration(executor, notifier, &input).await?;
                 tracing::debug!("command result: {:?}", result);
                 render(result)
             }
+            RpcCommand::ApplyMigrations => {
+                let input: ApplyMigrationsInput = params.clone().parse()?;
+                render(executor.apply_migrations(&input).await?)
+            }
             RpcCommand::UnapplyMigration => {
                 let input: UnapplyMigrationInput = params.clone().parse()?;
                 render(executor.unapply_migration(&input).await?)
             }
             RpcCommand::Reset => render(executor.reset(&serde_json::Value::Null).await?),
+            RpcCommand::Seed => {
+                let input: SeedInput = params.clone().parse()?;
+                render(executor.seed(&input).await?)
+            }
             RpcCommand::CalculateDatamodel => {
                 let input: CalculateDatamodelInput = params.clone().parse()?;
                 render(executor.calculate_datamodel(&input).await?)
@@ -146,6 +245,53 @@ impl RpcApi {
                 let input: CalculateDatabaseStepsInput = params.clone().parse()?;
                 render(executor.calculate_database_steps(&input).await?)
             }
+            RpcCommand::DiffDatabaseSchemas => {
+                let input: DiffDatabaseSchemasInput = params.clone().parse()?;
+                render(executor.diff_database_schemas(&input).await?)
+            }
+            RpcCommand::DiffDatamodels => {
+                let input: DiffDatamodelsInput = params.clone().parse()?;
+                render(executor.diff_datamodels(&input).await?)
+            }
+            RpcCommand::ServerInfo => render(serde_json::json!({
+                "commit": env!("GIT_HASH"),
+                "version": env!("CARGO_PKG_VERSION"),
+                "connector": executor.connector_type(),
+                "features": preview_features,
+            })),
+        }
+    }
+
+    /// Applies the migration, reporting its progress until it finished.
+    async fn apply_migration(
+        executor: &Arc<dyn GenericApi>,
+        notifier: &Notifier,
+        input: &ApplyMigrationInput,
+    ) -> CoreResult<MigrationStepsResultOutput> {
+        let apply = executor.apply_migration(input).boxed();
+        let progress = Self::report_migration_progress(executor, notifier, &input.migration_id).boxed();
+
+        match future::select(apply, progress).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => unreachable!("Reporting the migration progress never finishes."),
+        }
+    }
+
+    async fn report_migration_progress(executor: &Arc<dyn GenericApi>, notifier: &Notifier, migration_id: &str) {
+        let input = MigrationProgressInput {
+            migration_id: migration_id.to_owned(),
+        };
+
+        loop {
+            tokio::time::delay_for(MIGRATION_PROGRESS_INTERVAL).await;
+
+            // The migration is only persisted once the apply started.
+            if let Ok(progress) = executor.migration_progress(&input).await {
+                notifier.notify(
+                    "migrationProgress",
+                    serde_json::json!({ "migrationId": migration_id, "progress": progress }),
+                );
+            }
         }
     }
 }
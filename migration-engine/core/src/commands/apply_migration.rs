@@ -74,6 +74,16 @@ impl<'a> ApplyMigrationCommand<'a> {
         let connector = engine.connector();
         let migration_persistence = connector.migration_persistence();
 
+        let was_edited = migration_persistence.load_all().await?.iter().any(|migration| {
+            migration.name == self.input.migration_id
+                && migration.status == MigrationStatus::MigrationSuccess
+                && migration.steps_were_edited(&self.input.steps)
+        });
+
+        if was_edited {
+            return Err(CommandError::EditedMigrations(vec![self.input.migration_id.clone()]));
+        }
+
         if migration_persistence
             .migration_is_already_applied(&self.input.migration_id)
             .await?
@@ -124,7 +134,7 @@ impl<'a> ApplyMigrationCommand<'a> {
         let database_migration_json = database_migration.serialize();
 
         let mut migration = Migration::new(self.input.migration_id.clone());
-        migration.datamodel_steps = self.input.steps.clone();
+        migration.set_datamodel_steps(self.input.steps.clone());
         migration.database_migration = database_migration_json;
         migration.datamodel_string =
             datamodel::render_schema_ast_to_string(&next_schema_ast).map_err(CommandError::ProducedBadDatamodel)?;
@@ -0,0 +1,157 @@
+use super::{ApplyMigrationCommand, ApplyMigrationInput, MigrationStepsResultOutput};
+use crate::commands::command::*;
+use crate::migration_engine::MigrationEngine;
+use migration_connector::*;
+use serde::{Deserialize, Serialize};
+
+/// Applies the pending migrations of a migrations folder in one go. Migrations that were already applied are
+/// skipped, and the first migration that fails or has warnings without being forced stops the batch. Nothing is
+/// applied when the steps of already applied migrations changed since they were applied.
+pub struct ApplyMigrationsCommand<'a> {
+    input: &'a ApplyMigrationsInput,
+}
+
+#[async_trait::async_trait]
+impl<'a> MigrationCommand for ApplyMigrationsCommand<'a> {
+    type Input = ApplyMigrationsInput;
+    type Output = ApplyMigrationsOutput;
+
+    async fn execute<C, D>(input: &Self::Input, engine: &MigrationEngine<C, D>) -> CommandResult<Self::Output>
+    where
+        C: MigrationConnector<DatabaseMigration = D>,
+        D: DatabaseMigrationMarker + Send + Sync + 'static,
+    {
+        let cmd = ApplyMigrationsCommand { input };
+        let migration_persistence = engine.connector().migration_persistence();
+        cmd.check_applied_migrations_are_unchanged(migration_persistence.as_ref())
+            .await?;
+
+        let mut migrations = Vec::with_capacity(cmd.input.migrations.len());
+        let mut stopped = false;
+
+        for migration in cmd.input.migrations.iter() {
+            if stopped {
+                migrations.push(BatchMigrationResult::new(migration, BatchMigrationStatus::Skipped));
+                continue;
+            }
+
+            if migration_persistence
+                .migration_is_already_applied(&migration.migration_id)
+                .await?
+            {
+                migrations.push(BatchMigrationResult::new(
+                    migration,
+                    BatchMigrationStatus::AlreadyApplied,
+                ));
+                continue;
+            }
+
+            tracing::debug!(migration_id = migration.migration_id.as_str(), "Applying the migration");
+
+            let applied = match ApplyMigrationCommand::execute(migration, engine).await {
+                Ok(result) if !result.warnings.is_empty() && !migration.force.unwrap_or(false) => {
+                    stopped = true;
+
+                    BatchMigrationResult {
+                        result: Some(result),
+                        ..BatchMigrationResult::new(migration, BatchMigrationStatus::NotAppliedDueToWarnings)
+                    }
+                }
+                Ok(result) => BatchMigrationResult {
+                    result: Some(result),
+                    ..BatchMigrationResult::new(migration, BatchMigrationStatus::Applied)
+                },
+                Err(error) => {
+                    stopped = true;
+
+                    BatchMigrationResult {
+                        error: Some(error.to_string()),
+                        ..BatchMigrationResult::new(migration, BatchMigrationStatus::Failed)
+                    }
+                }
+            };
+
+            migrations.push(applied);
+        }
+
+        Ok(ApplyMigrationsOutput { migrations })
+    }
+}
+
+impl<'a> ApplyMigrationsCommand<'a> {
+    /// Compares the steps of the input migrations that were already applied with the checksums stored when they
+    /// were applied.
+    async fn check_applied_migrations_are_unchanged(
+        &self,
+        migration_persistence: &dyn MigrationPersistence,
+    ) -> CommandResult<()> {
+        let applied_migrations = migration_persistence.load_all().await?;
+
+        let edited_migrations: Vec<String> = self
+            .input
+            .migrations
+            .iter()
+            .filter(|migration| {
+                applied_migrations.iter().any(|applied| {
+                    applied.name == migration.migration_id
+                        && applied.status == MigrationStatus::MigrationSuccess
+                        && applied.steps_were_edited(&migration.steps)
+                })
+            })
+            .map(|migration| migration.migration_id.clone())
+            .collect();
+
+        if edited_migrations.is_empty() {
+            Ok(())
+        } else {
+            Err(CommandError::EditedMigrations(edited_migrations))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyMigrationsInput {
+    /// The migrations in the order they have to be applied.
+    pub migrations: Vec<ApplyMigrationInput>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyMigrationsOutput {
+    /// One entry per migration of the input, in the same order.
+    pub migrations: Vec<BatchMigrationResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchMigrationResult {
+    pub migration_id: String,
+    pub status: BatchMigrationStatus,
+    /// The result of applying the migration, for migrations that were applied or had warnings.
+    pub result: Option<MigrationStepsResultOutput>,
+    /// Why the migration failed.
+    pub error: Option<String>,
+}
+
+impl BatchMigrationResult {
+    fn new(input: &ApplyMigrationInput, status: BatchMigrationStatus) -> Self {
+        BatchMigrationResult {
+            migration_id: input.migration_id.clone(),
+            status,
+            result: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum BatchMigrationStatus {
+    Applied,
+    AlreadyApplied,
+    /// The migration has warnings and was not forced.
+    NotAppliedDueToWarnings,
+    Failed,
+    /// Not attempted because a previous migration stopped the batch.
+    Skipped,
+}
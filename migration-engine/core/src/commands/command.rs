@@ -39,6 +39,10 @@ pub enum CommandError {
 
     #[error("Error in command input. (error: {0})")]
     Input(anyhow::Error),
+
+    /// When the steps of already applied migrations don't match the checksums stored when they were applied.
+    #[error("The following migrations were edited after they were applied: {}", .0.join(", "))]
+    EditedMigrations(Vec<String>),
 }
 
 impl From<migration_connector::ConnectorError> for CommandError {
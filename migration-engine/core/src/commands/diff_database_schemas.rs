@@ -0,0 +1,80 @@
+use crate::commands::command::*;
+use crate::migration_engine::MigrationEngine;
+use crate::parse_datamodel;
+use migration_connector::*;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Computes the database steps between two descriptions of the database, or between two data models, without
+/// looking at the database.
+pub struct DiffDatabaseSchemasCommand<'a> {
+    input: &'a DiffDatabaseSchemasInput,
+}
+
+#[async_trait::async_trait]
+impl<'a> MigrationCommand for DiffDatabaseSchemasCommand<'a> {
+    type Input = DiffDatabaseSchemasInput;
+    type Output = DiffDatabaseSchemasOutput;
+
+    async fn execute<C, D>(input: &Self::Input, engine: &MigrationEngine<C, D>) -> CommandResult<Self::Output>
+    where
+        C: MigrationConnector<DatabaseMigration = D>,
+        D: DatabaseMigrationMarker + 'static,
+    {
+        let cmd = DiffDatabaseSchemasCommand { input };
+        debug!("{:?}", cmd.input);
+
+        let connector = engine.connector();
+        let inferrer = connector.database_migration_inferrer();
+
+        let database_migration = match cmd.input {
+            DiffDatabaseSchemasInput::Schemas {
+                previous_schema,
+                next_schema,
+            } => {
+                inferrer
+                    .infer_from_database_schemas(previous_schema, next_schema)
+                    .await?
+            }
+            DiffDatabaseSchemasInput::Datamodels {
+                previous_datamodel,
+                next_datamodel,
+            } => {
+                let previous = parse_datamodel(previous_datamodel)?;
+                let next = parse_datamodel(next_datamodel)?;
+
+                inferrer.infer_from_datamodels(&previous, &next, &[]).await?
+            }
+        };
+
+        let database_steps = connector
+            .database_migration_step_applier()
+            .render_steps_pretty(&database_migration)?;
+
+        Ok(DiffDatabaseSchemasOutput {
+            database_steps: serde_json::Value::Array(database_steps),
+        })
+    }
+}
+
+/// Either two serialized database descriptions, or two data models.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum DiffDatabaseSchemasInput {
+    #[serde(rename_all = "camelCase")]
+    Schemas {
+        previous_schema: serde_json::Value,
+        next_schema: serde_json::Value,
+    },
+    #[serde(rename_all = "camelCase")]
+    Datamodels {
+        previous_datamodel: String,
+        next_datamodel: String,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffDatabaseSchemasOutput {
+    pub database_steps: serde_json::Value,
+}
@@ -0,0 +1,47 @@
+use crate::commands::command::*;
+use crate::migration_engine::MigrationEngine;
+use crate::parse_datamodel;
+use datamodel::json::dmmf::{diff_dmmf, DmmfChange};
+use migration_connector::*;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Compares two data models at the DMMF level, without looking at the database.
+pub struct DiffDatamodelsCommand<'a> {
+    input: &'a DiffDatamodelsInput,
+}
+
+#[async_trait::async_trait]
+impl<'a> MigrationCommand for DiffDatamodelsCommand<'a> {
+    type Input = DiffDatamodelsInput;
+    type Output = DiffDatamodelsOutput;
+
+    async fn execute<C, D>(input: &Self::Input, _engine: &MigrationEngine<C, D>) -> CommandResult<Self::Output>
+    where
+        C: MigrationConnector<DatabaseMigration = D>,
+        D: DatabaseMigrationMarker + 'static,
+    {
+        let cmd = DiffDatamodelsCommand { input };
+        debug!("{:?}", cmd.input);
+
+        let previous = parse_datamodel(&cmd.input.previous_datamodel)?;
+        let next = parse_datamodel(&cmd.input.next_datamodel)?;
+
+        Ok(DiffDatamodelsOutput {
+            changes: diff_dmmf(&previous, &next),
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffDatamodelsInput {
+    pub previous_datamodel: String,
+    pub next_datamodel: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffDatamodelsOutput {
+    pub changes: Vec<DmmfChange>,
+}
@@ -0,0 +1,54 @@
+use crate::commands::command::*;
+use crate::migration_engine::MigrationEngine;
+use migration_connector::*;
+use serde::{Deserialize, Serialize};
+
+/// Returns the statements executed for a migration, with their timing and outcome.
+pub struct MigrationLogsCommand<'a> {
+    input: &'a MigrationLogsInput,
+}
+
+#[async_trait::async_trait]
+impl<'a> MigrationCommand for MigrationLogsCommand<'a> {
+    type Input = MigrationLogsInput;
+    type Output = MigrationLogsOutput;
+
+    async fn execute<C, D>(input: &Self::Input, engine: &MigrationEngine<C, D>) -> CommandResult<Self::Output>
+    where
+        C: MigrationConnector<DatabaseMigration = D>,
+        D: DatabaseMigrationMarker + 'static,
+    {
+        let cmd = MigrationLogsCommand { input };
+        let migration_persistence = engine.connector().migration_persistence();
+
+        let migration = migration_persistence
+            .by_name(&cmd.input.migration_id)
+            .await?
+            .ok_or_else(|| {
+                let error = anyhow::anyhow!(
+                    "Could not load migration from database. Migration name was: {}",
+                    &cmd.input.migration_id
+                );
+
+                CommandError::Input(error)
+            })?;
+
+        Ok(MigrationLogsOutput {
+            status: migration.status,
+            logs: migration.logs,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationLogsInput {
+    pub migration_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationLogsOutput {
+    pub status: MigrationStatus,
+    pub logs: Vec<ExecutedStatement>,
+}
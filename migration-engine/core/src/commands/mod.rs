@@ -1,21 +1,31 @@
 mod apply_migration;
+mod apply_migrations;
 mod calculate_database_steps;
 mod calculate_datamodel;
 mod command;
+mod diff_database_schemas;
+mod diff_datamodels;
 mod infer_migration_steps;
 mod list_migrations;
+mod migration_logs;
 mod migration_progress;
 mod reset;
+mod seed;
 mod unapply_migration;
 
 pub use apply_migration::*;
+pub use apply_migrations::*;
 pub use calculate_database_steps::*;
 pub use calculate_datamodel::*;
 pub use command::*;
+pub use diff_database_schemas::*;
+pub use diff_datamodels::*;
 pub use infer_migration_steps::*;
 pub use list_migrations::*;
+pub use migration_logs::*;
 pub use migration_progress::*;
 pub use reset::*;
+pub use seed::*;
 pub use unapply_migration::*;
 
 use migration_connector::{MigrationError, MigrationStep, MigrationWarning, UnexecutableMigration};
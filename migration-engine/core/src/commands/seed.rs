@@ -0,0 +1,131 @@
+use crate::commands::command::*;
+use crate::migration_engine::MigrationEngine;
+use datamodel::{Datamodel, WithDatabaseName};
+use migration_connector::*;
+use serde::{Deserialize, Serialize};
+
+/// Runs a seed against the database of the migration engine once. The records are given by model and field
+/// names, and inserted into the tables and columns of the datamodel of the last applied migration.
+pub struct SeedCommand<'a> {
+    input: &'a SeedInput,
+}
+
+#[async_trait::async_trait]
+impl<'a> MigrationCommand for SeedCommand<'a> {
+    type Input = SeedInput;
+    type Output = SeedOutput;
+
+    async fn execute<C, D>(input: &Self::Input, engine: &MigrationEngine<C, D>) -> CommandResult<Self::Output>
+    where
+        C: MigrationConnector<DatabaseMigration = D>,
+        D: DatabaseMigrationMarker + Send + Sync + 'static,
+    {
+        let cmd = SeedCommand { input };
+        let connector = engine.connector();
+        let seed_applier = connector.seed_applier();
+
+        if seed_applier.seed_was_applied(&cmd.input.name).await? {
+            tracing::info!(seed = cmd.input.name.as_str(), "The seed was already applied.");
+
+            return Ok(SeedOutput {
+                status: SeedStatus::AlreadyApplied,
+            });
+        }
+
+        let current_datamodel_ast = connector.migration_persistence().current_datamodel_ast().await?;
+        let current_datamodel =
+            datamodel::lift_ast(&current_datamodel_ast).map_err(CommandError::ProducedBadDatamodel)?;
+
+        let records = cmd
+            .input
+            .records
+            .iter()
+            .map(|records| records.to_table_records(&current_datamodel))
+            .collect::<CommandResult<Vec<SeedRecords>>>()?;
+
+        let seed = Seed {
+            name: cmd.input.name.clone(),
+            script: cmd.input.script.clone(),
+            records,
+        };
+
+        seed_applier.apply_seed(&seed).await?;
+
+        Ok(SeedOutput {
+            status: SeedStatus::Applied,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedInput {
+    /// Identifies the seed, so that it runs only once per database.
+    pub name: String,
+    /// Statements in the dialect of the database, run before the records are created.
+    pub script: Option<String>,
+    #[serde(default)]
+    pub records: Vec<SeedModelRecords>,
+}
+
+/// The records of a model, like the `data` of a `createMany`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedModelRecords {
+    pub model: String,
+    pub data: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl SeedModelRecords {
+    fn to_table_records(&self, datamodel: &Datamodel) -> CommandResult<SeedRecords> {
+        let model = datamodel.find_model(&self.model).ok_or_else(|| {
+            CommandError::Input(anyhow::anyhow!(
+                "The model `{}` of the seed records does not exist.",
+                self.model
+            ))
+        })?;
+
+        let rows = self
+            .data
+            .iter()
+            .map(|record| {
+                record
+                    .iter()
+                    .map(|(field_name, value)| {
+                        let field = model
+                            .find_field(field_name)
+                            .filter(|field| !field.is_relation())
+                            .ok_or_else(|| {
+                                CommandError::Input(anyhow::anyhow!(
+                                    "The model `{}` has no scalar field `{}`.",
+                                    model.name,
+                                    field_name
+                                ))
+                            })?;
+
+                        let column = field.single_database_name().unwrap_or(&field.name).to_owned();
+
+                        Ok((column, value.clone()))
+                    })
+                    .collect::<CommandResult<serde_json::Map<String, serde_json::Value>>>()
+            })
+            .collect::<CommandResult<Vec<_>>>()?;
+
+        Ok(SeedRecords {
+            table: model.single_database_name().unwrap_or(&model.name).to_owned(),
+            rows,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedOutput {
+    pub status: SeedStatus,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone, Copy)]
+pub enum SeedStatus {
+    Applied,
+    AlreadyApplied,
+}
@@ -6,13 +6,18 @@ pub mod error;
 pub mod migration;
 pub mod migration_engine;
 
+#[cfg(feature = "connector-registry")]
+mod registry;
+
 pub use api::GenericApi;
 pub use commands::{ApplyMigrationInput, InferMigrationStepsInput, MigrationStepsResultOutput};
 pub use error::CoreResult;
+#[cfg(feature = "connector-registry")]
+pub use registry::register_migration_connector;
 
 use commands::{CommandError, CommandResult};
 use datamodel::{
-    configuration::{MYSQL_SOURCE_NAME, POSTGRES_SOURCE_NAME, SQLITE_SOURCE_NAME},
+    configuration::{preview_features, MYSQL_SOURCE_NAME, POSTGRES_SOURCE_NAME, SQLITE_SOURCE_NAME},
     dml::Datamodel,
 };
 use error::Error;
@@ -29,7 +34,19 @@ pub async fn migration_api(datamodel: &str) -> CoreResult<Arc<dyn api::GenericAp
     let connector = match source.connector_type() {
         #[cfg(feature = "sql")]
         provider if [MYSQL_SOURCE_NAME, POSTGRES_SOURCE_NAME, SQLITE_SOURCE_NAME].contains(&provider) => {
-            sql_migration_connector::SqlMigrationConnector::new(&source.url().value, provider).await?
+            sql_migration_connector::SqlMigrationConnector::new(source.url().value.expose(), provider)
+                .await?
+                .with_relation_mode(source.relation_mode())
+                .with_database_uuid_defaults(
+                    config
+                        .preview_features()
+                        .contains(&preview_features::DATABASE_UUID_DEFAULTS),
+                )
+        }
+        #[cfg(feature = "connector-registry")]
+        provider if registry::registered_migration_connector(provider).is_some() => {
+            let load = registry::registered_migration_connector(provider).unwrap();
+            return load(source.url().value.expose().to_owned()).await;
         }
         x => unimplemented!("Connector {} is not supported yet", x),
     };
@@ -0,0 +1,87 @@
+//! Registry of migration connectors shipped by other crates, for providers the migration engine doesn't support
+//! itself.
+//!
+//! ```ignore
+//! migration_core::register_migration_connector("clickhouse", |url| ClickHouseMigrationConnector::new(url));
+//! ```
+use crate::{api::MigrationApi, CoreResult, GenericApi};
+use futures::future::{BoxFuture, FutureExt};
+use migration_connector::{ConnectorResult, DatabaseMigrationMarker, MigrationConnector};
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, RwLock},
+};
+
+/// Connects to the database at the given URL and wraps the connector in a migration API.
+pub(crate) type ApiLoader = Arc<dyn Fn(String) -> BoxFuture<'static, CoreResult<Arc<dyn GenericApi>>> + Send + Sync>;
+
+static MIGRATION_CONNECTORS: Lazy<RwLock<HashMap<&'static str, ApiLoader>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Registers the migration connector of a provider, with a function connecting it to the URL of the data source.
+/// Replaces a connector registered before for the same provider.
+pub fn register_migration_connector<C, D, F, Fut>(connector_type: &'static str, load: F)
+where
+    C: MigrationConnector<DatabaseMigration = D>,
+    D: DatabaseMigrationMarker + Send + Sync + 'static,
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ConnectorResult<C>> + Send + 'static,
+{
+    let loader: ApiLoader = Arc::new(move |url| {
+        let connector = load(url);
+
+        async move {
+            let api = MigrationApi::new(connector.await?).await?;
+            Ok(Arc::new(api) as Arc<dyn GenericApi>)
+        }
+        .boxed()
+    });
+
+    MIGRATION_CONNECTORS.write().unwrap().insert(connector_type, loader);
+}
+
+pub(crate) fn registered_migration_connector(connector_type: &str) -> Option<ApiLoader> {
+    MIGRATION_CONNECTORS.read().unwrap().get(connector_type).cloned()
+}
+
+#[cfg(all(test, feature = "sql"))]
+mod tests {
+    use super::*;
+    use migration_connector::{ConnectorError, ErrorKind};
+    use sql_migration_connector::SqlMigrationConnector;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Registers a connector that fails to connect, returning the number of its connection attempts.
+    fn register_failing_connector(connector_type: &'static str) -> Arc<AtomicUsize> {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+
+        register_migration_connector(connector_type, move |_url| {
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            futures::future::ready(Err::<SqlMigrationConnector, _>(ConnectorError::from_kind(
+                ErrorKind::Generic(anyhow::anyhow!("The connector can't connect.")),
+            )))
+        });
+
+        attempts
+    }
+
+    #[test]
+    fn registering_a_provider_again_replaces_its_connector() {
+        let first = register_failing_connector("registry-replaced");
+        let second = register_failing_connector("registry-replaced");
+
+        let load = registered_migration_connector("registry-replaced").unwrap();
+
+        assert!(futures::executor::block_on(load("file:registry.db".to_owned())).is_err());
+        assert_eq!(first.load(Ordering::SeqCst), 0);
+        assert_eq!(second.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn unregistered_providers_have_no_connector() {
+        assert!(registered_migration_connector("registry-unknown").is_none());
+    }
+}
@@ -1,5 +1,7 @@
 use pretty_assertions::assert_eq;
-use sql_schema_describer::{Column, Enum, ForeignKey, Index, IndexType, PrimaryKey, SqlSchema, Table};
+use sql_schema_describer::{
+    Column, ColumnArity, Enum, ForeignKey, Index, IndexType, PrimaryKey, SortOrder, SqlSchema, Table,
+};
 
 pub(crate) type AssertionResult<T> = Result<T, anyhow::Error>;
 
@@ -191,6 +193,24 @@ impl<'a> TableAssertion<'a> {
 
         Ok(self)
     }
+
+    pub fn assert_engine(self, engine: &str) -> AssertionResult<Self> {
+        assert_eq!(self.0.options.engine.as_deref(), Some(engine));
+
+        Ok(self)
+    }
+
+    pub fn assert_charset(self, charset: &str) -> AssertionResult<Self> {
+        assert_eq!(self.0.options.charset.as_deref(), Some(charset));
+
+        Ok(self)
+    }
+
+    pub fn assert_fillfactor(self, fillfactor: Option<u32>) -> AssertionResult<Self> {
+        assert_eq!(self.0.options.fillfactor, fillfactor);
+
+        Ok(self)
+    }
 }
 
 pub struct ColumnAssertion<'a>(&'a Column);
@@ -233,6 +253,18 @@ impl<'a> ColumnAssertion<'a> {
         Ok(self)
     }
 
+    pub fn assert_type_is_json(self) -> AssertionResult<Self> {
+        let found = &self.0.tpe.family;
+
+        anyhow::ensure!(
+            found == &sql_schema_describer::ColumnTypeFamily::Json,
+            "Assertion failed. Expected a JSON column, got {:?}.",
+            found
+        );
+
+        Ok(self)
+    }
+
     pub fn assert_is_required(self) -> AssertionResult<Self> {
         anyhow::ensure!(
             self.0.tpe.arity.is_required(),
@@ -243,6 +275,17 @@ impl<'a> ColumnAssertion<'a> {
 
         Ok(self)
     }
+
+    pub fn assert_is_nullable(self) -> AssertionResult<Self> {
+        anyhow::ensure!(
+            self.0.tpe.arity == ColumnArity::Nullable,
+            "Assertion failed. Expected column `{}` to be nullable, got {:?}",
+            self.0.name,
+            self.0.tpe.arity,
+        );
+
+        Ok(self)
+    }
 }
 
 pub struct PrimaryKeyAssertion<'a>(&'a PrimaryKey);
@@ -270,6 +313,12 @@ impl<'a> ForeignKeyAssertion<'a> {
 
         Ok(self)
     }
+
+    pub fn assert_deferrable(self, deferrable: bool) -> AssertionResult<Self> {
+        assert_eq!(self.0.deferrable, deferrable);
+
+        Ok(self)
+    }
 }
 
 pub struct IndexAssertion<'a>(&'a Index);
@@ -292,4 +341,16 @@ impl<'a> IndexAssertion<'a> {
 
         Ok(self)
     }
+
+    pub fn assert_column_sort_order(self, column: &str, sort_order: SortOrder) -> AssertionResult<Self> {
+        assert_eq!(self.0.options_of_column(column).sort_order, sort_order);
+
+        Ok(self)
+    }
+
+    pub fn assert_column_length(self, column: &str, length: Option<u32>) -> AssertionResult<Self> {
+        assert_eq!(self.0.options_of_column(column).length, length);
+
+        Ok(self)
+    }
 }
@@ -10,5 +10,5 @@ pub use command_helpers::*;
 pub use misc_helpers::*;
 pub use step_helpers::*;
 pub use test_api::*;
-pub use test_macros::test_each_connector;
+pub use test_macros::{test_each_connector, test_one_connector};
 pub use test_setup::*;
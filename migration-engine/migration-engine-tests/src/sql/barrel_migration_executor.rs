@@ -20,11 +20,16 @@ impl BarrelMigrationExecutor<'_> {
 
         let full_sql = migration.make_from(self.sql_variant);
         run_full_sql(&self.api.database(), &full_sql).await?;
+        self.api.invalidate_described_schema();
 
         let mut result = self.api.describe_database().await.expect("Description failed");
 
         // The presence of the _Migration table makes assertions harder. Therefore remove it.
-        result.tables = result.tables.into_iter().filter(|t| t.name != "_Migration").collect();
+        result.tables = result
+            .tables
+            .into_iter()
+            .filter(|t| t.name != "_Migration" && t.name != "_Seed")
+            .collect();
 
         Ok(result)
     }
@@ -16,10 +16,14 @@ use super::{
     sql::barrel_migration_executor::BarrelMigrationExecutor,
     InferAndApplyOutput,
 };
+use datamodel::RelationMode;
 use migration_connector::{MigrationPersistence, MigrationStep};
 use migration_core::{
     api::{GenericApi, MigrationApi},
-    commands::ApplyMigrationInput,
+    commands::{
+        ApplyMigrationInput, ApplyMigrationsInput, ApplyMigrationsOutput, DiffDatabaseSchemasInput,
+        DiffDatabaseSchemasOutput, SeedInput, SeedOutput,
+    },
 };
 use quaint::prelude::{ConnectionInfo, Queryable, SqlFamily};
 use sql_schema_describer::*;
@@ -49,6 +53,11 @@ impl TestApi {
         &self.database
     }
 
+    /// The connector caches the described schema, changes made with `database()` have to be announced.
+    pub fn invalidate_described_schema(&self) {
+        self.api.connector().invalidate_described_schema()
+    }
+
     pub fn is_sqlite(&self) -> bool {
         self.sql_family() == SqlFamily::Sqlite
     }
@@ -97,6 +106,56 @@ impl TestApi {
         }
     }
 
+    /// Applies the migration like `apply_migration`, returning the error of the command instead of panicking.
+    pub async fn try_apply_migration(
+        &self,
+        steps: Vec<MigrationStep>,
+        migration_id: &str,
+    ) -> Result<migration_core::MigrationStepsResultOutput, user_facing_errors::Error> {
+        let input = ApplyMigrationInput {
+            migration_id: migration_id.into(),
+            steps,
+            force: None,
+        };
+
+        self.api
+            .apply_migration(&input)
+            .await
+            .map_err(|err| self.api.render_error(err))
+    }
+
+    pub async fn apply_migrations(
+        &self,
+        migrations: Vec<(&str, Vec<MigrationStep>)>,
+    ) -> Result<ApplyMigrationsOutput, user_facing_errors::Error> {
+        let input = ApplyMigrationsInput {
+            migrations: migrations
+                .into_iter()
+                .map(|(migration_id, steps)| ApplyMigrationInput {
+                    migration_id: migration_id.into(),
+                    steps,
+                    force: None,
+                })
+                .collect(),
+        };
+
+        self.api
+            .apply_migrations(&input)
+            .await
+            .map_err(|err| self.api.render_error(err))
+    }
+
+    pub async fn seed(&self, input: &SeedInput) -> Result<SeedOutput, anyhow::Error> {
+        Ok(self.api.seed(input).await?)
+    }
+
+    pub async fn diff_database_schemas(
+        &self,
+        input: &DiffDatabaseSchemasInput,
+    ) -> Result<DiffDatabaseSchemasOutput, anyhow::Error> {
+        Ok(self.api.diff_database_schemas(input).await?)
+    }
+
     pub fn infer_apply<'a>(&'a self, schema: &'a str) -> InferApply<'a> {
         InferApply::new(&self.api, schema)
     }
@@ -136,7 +195,7 @@ impl TestApi {
         }
     }
 
-    fn describer(&self) -> Box<dyn SqlSchemaDescriberBackend> {
+    pub fn describer(&self) -> Box<dyn SqlSchemaDescriberBackend> {
         let db = Arc::clone(&self.database);
         match self.api.connector_type() {
             "postgresql" => Box::new(sql_schema_describer::postgres::SqlSchemaDescriber::new(db)),
@@ -154,7 +213,11 @@ impl TestApi {
             .expect("Description failed");
 
         // the presence of the _Migration table makes assertions harder. Therefore remove it from the result.
-        result.tables = result.tables.into_iter().filter(|t| t.name != "_Migration").collect();
+        result.tables = result
+            .tables
+            .into_iter()
+            .filter(|t| t.name != "_Migration" && t.name != "_Seed")
+            .collect();
 
         Ok(result)
     }
@@ -335,3 +398,18 @@ pub async fn sqlite_test_api(db_name: &str) -> TestApi {
         api: test_api(connector).await,
     }
 }
+
+/// A SQLite test API migrating with the relation mode of a datasource, see `SqlMigrationConnector::with_relation_mode`.
+pub async fn sqlite_test_api_with_relation_mode(db_name: &str, relation_mode: RelationMode) -> TestApi {
+    let connection_info = ConnectionInfo::from_url(&sqlite_test_url(db_name)).unwrap();
+    let connector = sqlite_migration_connector(db_name)
+        .await
+        .with_relation_mode(relation_mode);
+
+    TestApi {
+        connector_name: "sqlite",
+        connection_info,
+        database: Arc::clone(&connector.database),
+        api: test_api(connector).await,
+    }
+}
@@ -128,6 +128,19 @@ impl<'a> InferApplyAssertion<'a> {
         Ok(self)
     }
 
+    pub fn assert_warnings(self, expected_messages: &[String]) -> AssertionResult<Self> {
+        assert_eq!(self.result.warnings.len(), expected_messages.len());
+
+        for (expected, actual) in expected_messages
+            .iter()
+            .zip(self.result.warnings.iter().map(|w| w.description.as_str()))
+        {
+            assert_eq!(actual, expected);
+        }
+
+        Ok(self)
+    }
+
     pub fn into_inner(self) -> MigrationStepsResultOutput {
         self.result
     }
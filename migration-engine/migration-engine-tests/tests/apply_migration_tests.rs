@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use migration_connector::*;
+use migration_core::commands::BatchMigrationStatus;
 use migration_engine_tests::sql::*;
 
 #[test_each_connector]
@@ -174,3 +175,141 @@ async fn applying_an_already_applied_migration_must_return_an_error(api: &TestAp
 
     Ok(())
 }
+
+#[test_each_connector]
+async fn apply_migrations_applies_pending_migrations_in_order_and_stops_at_the_first_failure(api: &TestApi) {
+    let create_test = vec![
+        create_model_step("Test"),
+        create_field_step("Test", "id", "Int"),
+        create_id_directive_step("Test", "id"),
+    ];
+
+    api.apply_migration(create_test.clone(), "0001-create-test").await;
+
+    let output = api
+        .apply_migrations(vec![
+            ("0001-create-test", create_test),
+            ("0002-add-name", vec![create_field_step("Test", "name", "String")]),
+            ("0003-broken", vec![create_field_step("DoesNotExist", "name", "String")]),
+            ("0004-add-age", vec![create_field_step("Test", "age", "Int")]),
+        ])
+        .await
+        .unwrap();
+
+    let statuses: Vec<(&str, BatchMigrationStatus)> = output
+        .migrations
+        .iter()
+        .map(|migration| (migration.migration_id.as_str(), migration.status))
+        .collect();
+
+    assert_eq!(
+        statuses,
+        &[
+            ("0001-create-test", BatchMigrationStatus::AlreadyApplied),
+            ("0002-add-name", BatchMigrationStatus::Applied),
+            ("0003-broken", BatchMigrationStatus::Failed),
+            ("0004-add-age", BatchMigrationStatus::Skipped),
+        ]
+    );
+    assert!(output.migrations[2].error.is_some());
+
+    let migrations = api.migration_persistence().load_all().await.unwrap();
+    let names: Vec<&str> = migrations.iter().map(|migration| migration.name.as_str()).collect();
+
+    assert_eq!(names, &["0001-create-test", "0002-add-name"]);
+
+    api.assert_schema()
+        .await
+        .unwrap()
+        .assert_table("Test", |table| table.assert_has_column("name"))
+        .unwrap();
+}
+
+#[test_each_connector]
+async fn apply_migrations_must_fail_when_applied_migrations_were_edited(api: &TestApi) {
+    let create_test = vec![
+        create_model_step("Test"),
+        create_field_step("Test", "id", "Int"),
+        create_id_directive_step("Test", "id"),
+    ];
+    let add_name = vec![create_field_step("Test", "name", "String")];
+
+    api.apply_migrations(vec![
+        ("0001-create-test", create_test.clone()),
+        ("0002-add-name", add_name),
+    ])
+    .await
+    .unwrap();
+
+    let error = api
+        .apply_migrations(vec![
+            ("0001-create-test", create_test),
+            ("0002-add-name", vec![create_field_step("Test", "name", "Int")]),
+            ("0003-add-age", vec![create_field_step("Test", "age", "Int")]),
+        ])
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.error_code(), Some("P3003"));
+    assert_eq!(
+        error.message(),
+        "The following migrations were edited after they were applied: 0002-add-name. Applied migrations must not be modified, create a new migration instead."
+    );
+
+    let migrations = api.migration_persistence().load_all().await.unwrap();
+
+    assert_eq!(migrations.len(), 2);
+}
+
+#[test_each_connector]
+async fn apply_migration_must_fail_when_the_applied_migration_was_edited(api: &TestApi) {
+    let steps = vec![
+        create_model_step("Test"),
+        create_field_step("Test", "id", "Int"),
+        create_id_directive_step("Test", "id"),
+    ];
+
+    api.apply_migration(steps, "0001-create-test").await;
+
+    let edited_steps = vec![
+        create_model_step("Test"),
+        create_field_step("Test", "id", "String"),
+        create_id_directive_step("Test", "id"),
+    ];
+
+    let error = api
+        .try_apply_migration(edited_steps, "0001-create-test")
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.error_code(), Some("P3003"));
+    assert_eq!(
+        error.message(),
+        "The following migrations were edited after they were applied: 0001-create-test. Applied migrations must not be modified, create a new migration instead."
+    );
+}
+
+#[test_each_connector]
+async fn applied_migrations_must_log_the_executed_statements(api: &TestApi) {
+    let steps = vec![
+        create_model_step("Test"),
+        create_field_step("Test", "id", "Int"),
+        create_id_directive_step("Test", "id"),
+    ];
+
+    api.apply_migration(steps, "0001-create-test").await;
+
+    let migration = api
+        .migration_persistence()
+        .by_name("0001-create-test")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!migration.logs.is_empty());
+    assert!(migration
+        .logs
+        .iter()
+        .any(|log| log.statement.contains("CREATE TABLE") && log.statement.contains("Test")));
+    assert!(migration.logs.iter().all(|log| log.error.is_none()));
+}
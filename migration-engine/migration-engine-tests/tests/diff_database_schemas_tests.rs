@@ -0,0 +1,59 @@
+use migration_core::commands::DiffDatabaseSchemasInput;
+use migration_engine_tests::sql::*;
+
+#[test_each_connector]
+async fn diffing_datamodels_must_not_touch_the_database(api: &TestApi) -> TestResult {
+    let input = DiffDatabaseSchemasInput::Datamodels {
+        previous_datamodel: String::new(),
+        next_datamodel: r#"
+            model Cat {
+                id Int @id
+                name String
+            }
+        "#
+        .to_owned(),
+    };
+
+    let output = api.diff_database_schemas(&input).await?;
+    let steps = output.database_steps.as_array().unwrap();
+
+    assert_eq!(steps.len(), 1);
+    assert!(steps[0]["raw"].as_str().unwrap().contains("CREATE TABLE"));
+    assert!(!api.describe_database().await?.has_table("Cat"));
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn diffing_database_schemas_must_return_the_steps_between_them(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Cat {
+            id Int @id
+            name String
+        }
+
+        model Dog {
+            id Int @id
+        }
+    "#;
+
+    api.infer_apply(dm).send_assert().await?.assert_green()?;
+
+    let previous_schema = api.describe_database().await?;
+    let mut next_schema = previous_schema.clone();
+    next_schema.tables.retain(|table| table.name != "Dog");
+
+    let input = DiffDatabaseSchemasInput::Schemas {
+        previous_schema: serde_json::to_value(&previous_schema)?,
+        next_schema: serde_json::to_value(&next_schema)?,
+    };
+
+    let output = api.diff_database_schemas(&input).await?;
+    let steps = output.database_steps.as_array().unwrap();
+
+    assert_eq!(steps.len(), 1);
+    assert!(steps[0]["raw"].as_str().unwrap().contains("DROP TABLE"));
+    assert!(api.describe_database().await?.has_table("Dog"));
+
+    Ok(())
+}
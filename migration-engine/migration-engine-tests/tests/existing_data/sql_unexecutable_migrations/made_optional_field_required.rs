@@ -1,7 +1,7 @@
 use migration_engine_tests::sql::*;
 
 #[test_each_connector]
-async fn making_an_optional_field_required_with_data_without_a_default_is_unexecutable(api: &TestApi) -> TestResult {
+async fn making_an_optional_field_required_with_nulls_without_a_default_warns(api: &TestApi) -> TestResult {
     let dm1 = r#"
         model Test {
             id String @id
@@ -26,12 +26,15 @@ async fn making_an_optional_field_required_with_data_without_a_default_is_unexec
         }
     "#;
 
-    // TODO: flip this
-    api.infer_apply(&dm2).send_assert().await?.assert_green()?;
-
-    api.assert_schema()
+    api.infer_apply(&dm2)
+        .send_assert()
         .await?
-        .assert_table("Test", |table| table.assert_does_not_have_column("Int"))?;
+        .assert_executable()?
+        .assert_warnings(&["You are about to make the column `age` on the `Test` table required, but there are 1 existing NULL values. Add a default value to the field, or replace the NULL values before migrating.".into()])?;
+
+    api.assert_schema().await?.assert_table("Test", |table| {
+        table.assert_column("age", |col| col.assert_is_nullable())
+    })?;
 
     let rows = api.select("Test").column("id").column("name").send_debug().await?;
     assert_eq!(rows, &[&[r#"Text("abc")"#, r#"Text("george")"#]]);
@@ -65,7 +68,6 @@ async fn making_an_optional_field_required_with_data_with_a_default_works(api: &
         }
     "#;
 
-    // TODO: flip this
     api.infer_apply(&dm2).send_assert().await?.assert_green()?;
 
     api.assert_schema()
@@ -104,7 +106,6 @@ async fn making_an_optional_field_required_on_an_empty_table_works(api: &TestApi
         }
     "#;
 
-    // TODO: flip this
     api.infer_apply(&dm2).send_assert().await?.assert_green()?;
 
     api.assert_schema()
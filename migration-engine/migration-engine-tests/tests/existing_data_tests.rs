@@ -218,7 +218,7 @@ async fn altering_a_column_with_non_null_values_should_warn(api: &TestApi) -> Te
     let dm2 = r#"
         model Test {
             id String @id @default(cuid())
-            age Float?
+            age DateTime?
         }
     "#;
 
@@ -411,7 +411,7 @@ async fn changing_a_column_from_required_to_optional_should_work(api: &TestApi)
 }
 
 #[test_each_connector]
-async fn changing_a_column_from_optional_to_required_must_warn(api: &TestApi) -> TestResult {
+async fn changing_a_column_from_optional_to_required_with_a_default_replaces_the_nulls(api: &TestApi) -> TestResult {
     let dm = r#"
         model Test {
             id String @id @default(cuid())
@@ -428,6 +428,9 @@ async fn changing_a_column_from_optional_to_required_must_warn(api: &TestApi) ->
 
     api.database().query(insert.into()).await.unwrap();
 
+    let insert = Insert::single_into((api.schema_name(), "Test")).value("id", "c");
+    api.database().query(insert.into()).await.unwrap();
+
     let dm2 = r#"
         model Test {
             id String @id @default(cuid())
@@ -437,32 +440,115 @@ async fn changing_a_column_from_optional_to_required_must_warn(api: &TestApi) ->
 
     let migration_output = api.infer_apply(&dm2).send().await?;
 
-    // The schema should not change because the migration should not run if there are warnings
-    // and the force flag isn't passed.
-    api.assert_schema().await?.assert_equals(&original_database_schema)?;
+    assert!(migration_output.warnings.is_empty());
+    assert!(migration_output.unexecutable_migrations.is_empty());
 
-    assert_eq!(
-        migration_output.warnings,
-        &[MigrationWarning {
-            description:
-                "You are about to alter the column `age` on the `Test` table, which still contains 2 non-null values. \
-                 The data in that column will be lost."
-                    .to_owned()
-        }]
-    );
+    api.assert_schema()
+        .await?
+        .assert_ne(&original_database_schema)?
+        .assert_table("Test", |table| {
+            table.assert_column("age", |col| col.assert_type_is_int()?.assert_is_required())
+        })?;
 
-    // Check that no data was lost.
+    // The existing values are kept, the NULL is replaced with the default.
     {
         let data = api.dump_table("Test").await?;
-        assert_eq!(data.len(), 2);
         let ages: Vec<i64> = data
             .into_iter()
             .map(|row| row.get("age").unwrap().as_i64().unwrap())
             .collect();
 
-        assert_eq!(ages, &[12, 22]);
+        assert_eq!(ages, &[12, 22, 30]);
+    }
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn changing_a_column_with_nulls_from_optional_to_required_without_a_default_must_warn(
+    api: &TestApi,
+) -> TestResult {
+    let dm = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int?
+        }
+    "#;
+
+    api.infer_apply(&dm).send().await?;
+    let original_database_schema = api.describe_database().await?;
+
+    for id in &["a", "b"] {
+        let insert = Insert::single_into((api.schema_name(), "Test")).value("id", *id);
+        api.database().query(insert.into()).await.unwrap();
     }
 
+    let insert = Insert::single_into((api.schema_name(), "Test"))
+        .value("id", "c")
+        .value("age", 12);
+    api.database().query(insert.into()).await.unwrap();
+
+    let dm2 = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int
+        }
+    "#;
+
+    let migration_output = api.infer_apply(&dm2).send().await?;
+
+    // The migration is not applied, because there are warnings and the force flag isn't passed.
+    api.assert_schema().await?.assert_equals(&original_database_schema)?;
+
+    assert_eq!(
+        migration_output.warnings,
+        &[MigrationWarning {
+            description: "You are about to make the column `age` on the `Test` table required, but there are 2 existing NULL values. Add a default value to the field, or replace the NULL values before migrating.".to_owned()
+        }]
+    );
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn changing_a_column_without_nulls_from_optional_to_required_should_work(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int?
+        }
+    "#;
+
+    api.infer_apply(&dm).send().await?;
+
+    let insert = Insert::multi_into((api.schema_name(), "Test"), vec!["id", "age"])
+        .values(("a", 12))
+        .values(("b", 22));
+
+    api.database().query(insert.into()).await.unwrap();
+
+    let dm2 = r#"
+        model Test {
+            id String @id @default(cuid())
+            age Int
+        }
+    "#;
+
+    api.infer_apply(&dm2).send_assert().await?.assert_green()?;
+
+    api.assert_schema().await?.assert_table("Test", |table| {
+        table.assert_column("age", |col| col.assert_type_is_int()?.assert_is_required())
+    })?;
+
+    let ages: Vec<i64> = api
+        .dump_table("Test")
+        .await?
+        .into_iter()
+        .map(|row| row.get("age").unwrap().as_i64().unwrap())
+        .collect();
+
+    assert_eq!(ages, &[12, 22]);
+
     Ok(())
 }
 
@@ -637,7 +723,7 @@ async fn making_a_column_required_in_an_empty_table_should_not_warn(api: &TestAp
 }
 
 #[test_each_connector]
-async fn altering_the_type_of_a_column_in_a_non_empty_table_always_warns(api: &TestApi) -> TestResult {
+async fn risky_column_type_changes_in_a_non_empty_table_warn(api: &TestApi) -> TestResult {
     let dm1 = r#"
         model User {
             id String @id @default(cuid())
@@ -659,7 +745,7 @@ async fn altering_the_type_of_a_column_in_a_non_empty_table_always_warns(api: &T
         model User {
             id String @id @default(cuid())
             name String
-            dogs String
+            dogs Boolean
         }
     "#;
 
@@ -668,9 +754,7 @@ async fn altering_the_type_of_a_column_in_a_non_empty_table_always_warns(api: &T
     assert_eq!(
         response.warnings,
         &[MigrationWarning {
-            // TODO: the message should say that altering the type of a column is not guaranteed to preserve the data, but the database is going to do its best.
-            // Also think about timeouts.
-            description: "You are about to alter the column `dogs` on the `User` table, which still contains 1 non-null values. The data in that column will be lost.".to_owned()
+            description: "You are about to change the type of the column `dogs` on the `User` table from `int` to `boolean`, which still contains 1 non-null values. Casting some of the values may fail or lose precision.".to_owned()
         }]
     );
 
@@ -685,8 +769,8 @@ async fn altering_the_type_of_a_column_in_a_non_empty_table_always_warns(api: &T
         .map(drop)
 }
 
-#[test_each_connector(ignore("mysql"))]
-async fn migrating_a_required_column_from_int_to_string_should_warn_and_cast(api: &TestApi) -> TestResult {
+#[test_each_connector]
+async fn migrating_a_required_column_from_int_to_string_should_cast_without_warnings(api: &TestApi) -> TestResult {
     let dm1 = r#"
         model Test {
             id String @id
@@ -709,8 +793,6 @@ async fn migrating_a_required_column_from_int_to_string_should_warn_and_cast(api
         r#"Some(Text("abcd")) Some(Integer(47))"#
     );
 
-    let original_schema = api.assert_schema().await?.into_schema();
-
     let dm2 = r#"
         model Test {
             id String @id
@@ -718,36 +800,65 @@ async fn migrating_a_required_column_from_int_to_string_should_warn_and_cast(api
         }
     "#;
 
-    let expected_warning = MigrationWarning {
-        description: "You are about to alter the column `serialNumber` on the `Test` table, which still contains 1 non-null values. The data in that column will be lost.".to_owned(),
-    };
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
 
-    // Apply once without forcing
-    {
-        let result = api.infer_apply(dm2).send().await?;
+    api.assert_schema().await?.assert_table("Test", |table| {
+        table.assert_column("serialNumber", |col| col.assert_type_is_string())
+    })?;
 
-        assert_eq!(result.warnings, &[expected_warning.clone()]);
+    let test = api.dump_table("Test").await?;
+    let first_row = test.get(0).unwrap();
+    assert_eq!(
+        format!("{:?} {:?}", first_row.get("id"), first_row.get("serialNumber")),
+        r#"Some(Text("abcd")) Some(Text("47"))"#
+    );
 
-        api.assert_schema().await?.assert_equals(&original_schema)?;
-    }
+    Ok(())
+}
 
-    // Force apply
-    {
-        let result = api.infer_apply(dm2).force(Some(true)).send().await?;
+#[test_each_connector(tags("postgres"))]
+async fn risky_column_type_changes_are_cast_when_forced_on_postgres(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model Test {
+            id String @id
+            serialNumber String
+        }
+    "#;
 
-        assert_eq!(result.warnings, &[expected_warning]);
+    api.infer_apply(dm1).send().await?;
 
-        api.assert_schema().await?.assert_table("Test", |table| {
-            table.assert_column("serialNumber", |col| col.assert_type_is_string())
-        })?;
+    api.insert("Test")
+        .value("id", "abcd")
+        .value("serialNumber", "47")
+        .result_raw()
+        .await?;
 
-        let test = api.dump_table("Test").await?;
-        let first_row = test.get(0).unwrap();
-        assert_eq!(
-            format!("{:?} {:?}", first_row.get("id"), first_row.get("serialNumber")),
-            r#"Some(Text("abcd")) Some(Text("47"))"#
-        );
-    }
+    let dm2 = r#"
+        model Test {
+            id String @id
+            serialNumber Int
+        }
+    "#;
+
+    let result = api.infer_apply(dm2).force(Some(true)).send().await?;
+
+    assert_eq!(
+        result.warnings,
+        &[MigrationWarning {
+            description: "You are about to change the type of the column `serialNumber` on the `Test` table from `string` to `int`, which still contains 1 non-null values. Casting some of the values may fail or lose precision.".to_owned(),
+        }]
+    );
+
+    api.assert_schema().await?.assert_table("Test", |table| {
+        table.assert_column("serialNumber", |col| col.assert_type_is_int())
+    })?;
+
+    let test = api.dump_table("Test").await?;
+    let first_row = test.get(0).unwrap();
+    assert_eq!(
+        format!("{:?} {:?}", first_row.get("id"), first_row.get("serialNumber")),
+        r#"Some(Text("abcd")) Some(Integer(47))"#
+    );
 
     Ok(())
 }
@@ -27,6 +27,30 @@ async fn adding_a_model_for_an_existing_table_must_work(api: &TestApi) -> TestRe
     Ok(())
 }
 
+#[test_each_connector]
+async fn the_idempotency_keys_table_of_the_query_engine_is_not_dropped(api: &TestApi) -> TestResult {
+    api.barrel()
+        .execute(|migration| {
+            migration.create_table("_prisma_idempotency_keys", |t| {
+                t.add_column("key", types::varchar(255).primary(true));
+            });
+        })
+        .await?;
+
+    let dm = r#"
+        model Blog {
+            id Int @id
+        }
+    "#;
+
+    api.infer_apply(dm).send_assert().await?.assert_green()?;
+
+    let rows = api.dump_table("_prisma_idempotency_keys").await?;
+    assert_eq!(rows.len(), 0);
+
+    Ok(())
+}
+
 #[test]
 fn bigint_columns_must_work() {
     // TODO: port when barrel supports arbitray primary keys
@@ -425,3 +449,48 @@ async fn removing_a_default_from_a_non_nullable_foreign_key_column_must_warn(api
 
     Ok(())
 }
+
+#[test_each_connector(tags("postgres"))]
+async fn altering_a_column_with_dependent_views_and_triggers_must_work(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model Cat {
+            id Int @id
+            name String
+            age Int
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+
+    let create_view = format!(
+        r#"CREATE VIEW "{0}"."CatAge" AS SELECT id, age FROM "{0}"."Cat""#,
+        api.schema_name()
+    );
+    let create_trigger = format!(
+        r#"CREATE TRIGGER "keep_age" BEFORE UPDATE OF age ON "{}"."Cat" FOR EACH ROW EXECUTE PROCEDURE suppress_redundant_updates_trigger()"#,
+        api.schema_name()
+    );
+    api.database().execute_raw(&create_view, &[]).await?;
+    api.database().execute_raw(&create_trigger, &[]).await?;
+
+    let dm2 = r#"
+        model Cat {
+            id Int @id
+            name String
+            age String
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+
+    api.assert_schema().await?.assert_table("Cat", |table| {
+        table.assert_column("age", |col| col.assert_type_is_string())
+    })?;
+
+    let schema = api.describe_database().await?;
+    assert!(schema.get_view("CatAge").is_some());
+    assert_eq!(schema.triggers.len(), 1);
+    assert_eq!(schema.triggers[0].name, "keep_age");
+
+    Ok(())
+}
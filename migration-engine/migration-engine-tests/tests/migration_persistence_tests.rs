@@ -63,10 +63,10 @@ async fn create_should_allow_to_create_a_new_migration(api: &TestApi) {
     let mut migration = Migration::new("my_migration".to_string());
     migration.status = MigrationStatus::MigrationSuccess;
     migration.datamodel_string = dm.to_owned();
-    migration.datamodel_steps = vec![MigrationStep::CreateEnum(CreateEnum {
+    migration.set_datamodel_steps(vec![MigrationStep::CreateEnum(CreateEnum {
         r#enum: "MyEnum".to_string(),
         values: vec!["A".to_string(), "B".to_string()],
-    })];
+    })]);
     migration.errors = vec!["error1".to_string(), "error2".to_string()];
 
     let result = persistence.create(migration.clone()).await.unwrap();
@@ -147,3 +147,33 @@ async fn migration_is_already_applied_must_work(api: &TestApi) -> TestResult {
 
     Ok(())
 }
+
+#[test_each_connector]
+async fn init_must_add_the_missing_columns_of_existing_migration_tables(api: &TestApi) -> TestResult {
+    let persistence = api.migration_persistence();
+    persistence.init().await?;
+
+    // SQLite can't drop columns, its migration table is only initialized again.
+    let table_name = match api.sql_family() {
+        SqlFamily::Postgres => Some(format!(r#""{}"."_Migration""#, api.schema_name())),
+        SqlFamily::Mysql => Some(format!("`{}`.`_Migration`", api.schema_name())),
+        SqlFamily::Sqlite => None,
+    };
+
+    if let Some(table_name) = table_name {
+        for column in &["checksum", "logs"] {
+            let drop_column = format!("ALTER TABLE {} DROP COLUMN {}", table_name, column);
+            api.database().query_raw(&drop_column, &[]).await?;
+        }
+    }
+
+    persistence.init().await?;
+
+    let schema = api.describer().describe(api.schema_name()).await.unwrap();
+    let migration_table = schema.table_bang("_Migration");
+
+    assert!(migration_table.has_column("checksum"));
+    assert!(migration_table.has_column("logs"));
+
+    Ok(())
+}
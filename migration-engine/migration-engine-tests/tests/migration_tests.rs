@@ -5,6 +5,7 @@ use pretty_assertions::assert_eq;
 use quaint::prelude::SqlFamily;
 use sql_migration_connector::{AlterIndex, CreateIndex, DropIndex, SqlMigrationStep};
 use sql_schema_describer::*;
+use std::collections::BTreeMap;
 
 #[test_each_connector]
 async fn adding_a_scalar_field_must_work(api: &TestApi) {
@@ -61,6 +62,45 @@ async fn adding_an_optional_field_must_work(api: &TestApi) {
     assert!(column.default.is_none());
 }
 
+#[test_each_connector]
+async fn computed_fields_must_not_get_a_column(api: &TestApi) {
+    let dm2 = r#"
+        model Test {
+            id String @id @default(cuid())
+            firstName String
+            lastName String
+            fullName String @computed("firstName || ' ' || lastName")
+        }
+    "#;
+    let result = api.infer_and_apply(&dm2).await.sql_schema;
+    let table = result.table_bang("Test");
+    assert!(table.column("firstName").is_some());
+    assert!(table.column("fullName").is_none());
+}
+
+#[test_each_connector]
+async fn updated_at_fields_with_a_default_of_now_are_updated_by_mysql(api: &TestApi) {
+    let dm1 = r#"
+        model Test {
+            id String @id @default(cuid())
+            updatedAt DateTime @updatedAt
+        }
+    "#;
+    let result = api.infer_and_apply(&dm1).await.sql_schema;
+    let column = result.table_bang("Test").column_bang("updatedAt");
+    assert!(!column.on_update_current_timestamp);
+
+    let dm2 = r#"
+        model Test {
+            id String @id @default(cuid())
+            updatedAt DateTime @default(now()) @updatedAt
+        }
+    "#;
+    let result = api.infer_and_apply(&dm2).await.sql_schema;
+    let column = result.table_bang("Test").column_bang("updatedAt");
+    assert_eq!(column.on_update_current_timestamp, api.sql_family() == SqlFamily::Mysql);
+}
+
 #[test_each_connector]
 async fn adding_an_id_field_with_a_special_name_must_work(api: &TestApi) {
     let dm2 = r#"
@@ -244,6 +284,7 @@ async fn changing_the_type_of_an_id_field_must_work(api: &TestApi) {
             referenced_table: "B".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 
@@ -272,6 +313,7 @@ async fn changing_the_type_of_an_id_field_must_work(api: &TestApi) {
             referenced_table: "B".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 }
@@ -298,6 +340,155 @@ async fn updating_db_name_of_a_scalar_field_must_work(api: &TestApi) {
     assert_eq!(result.table_bang("A").column("name2").is_some(), true);
 }
 
+#[test_each_connector(tags("postgres"))]
+async fn deferrable_relations_must_work(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model A {
+            id Int @id
+            b B @relation(references: [id], deferrable: true)
+        }
+
+        model B {
+            id Int @id
+            a A?
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("A", |table| {
+        table.assert_fk_on_columns(&["b"], |fk| fk.assert_references("B", &["id"])?.assert_deferrable(true))
+    })?;
+
+    let output = api.infer_apply(dm1).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    let dm2 = r#"
+        model A {
+            id Int @id
+            b B @relation(references: [id])
+        }
+
+        model B {
+            id Int @id
+            a A?
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("A", |table| {
+        table.assert_fk_on_columns(&["b"], |fk| fk.assert_deferrable(false))
+    })?;
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql"))]
+async fn table_engine_and_charset_must_work_on_mysql(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model Cat {
+            id Int @id
+
+            @@table(engine: "MyISAM", charset: "latin1")
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+    api.assert_schema()
+        .await?
+        .assert_table("Cat", |table| table.assert_engine("MyISAM")?.assert_charset("latin1"))?;
+
+    let output = api.infer_apply(dm1).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    let dm2 = r#"
+        model Cat {
+            id Int @id
+
+            @@table(engine: "InnoDB", charset: "utf8mb4")
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+    api.assert_schema()
+        .await?
+        .assert_table("Cat", |table| table.assert_engine("InnoDB")?.assert_charset("utf8mb4"))?;
+
+    // Removing the options leaves the table as it is.
+    let dm3 = r#"
+        model Cat {
+            id Int @id
+        }
+    "#;
+
+    let output = api.infer_apply(dm3).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    Ok(())
+}
+
+#[test_each_connector(tags("postgres"))]
+async fn table_fillfactor_must_work_on_postgres(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model Cat {
+            id Int @id
+
+            @@table(fillfactor: 70)
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+    api.assert_schema()
+        .await?
+        .assert_table("Cat", |table| table.assert_fillfactor(Some(70)))?;
+
+    let output = api.infer_apply(dm1).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    let dm2 = r#"
+        model Cat {
+            id Int @id
+
+            @@table(fillfactor: 90)
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+    api.assert_schema()
+        .await?
+        .assert_table("Cat", |table| table.assert_fillfactor(Some(90)))?;
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn named_relation_tables_must_work(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Post {
+            id Int @id
+            tags Tag[] @relation(table: "post_tags", column: "post_id")
+        }
+
+        model Tag {
+            id Int @id
+            posts Post[] @relation(column: "tag_id")
+        }
+    "#;
+
+    api.infer_apply(dm).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("post_tags", |table| {
+        table
+            .assert_has_column("post_id")?
+            .assert_has_column("tag_id")?
+            .assert_fk_on_columns(&["post_id"], |fk| fk.assert_references("Post", &["id"]))?
+            .assert_fk_on_columns(&["tag_id"], |fk| fk.assert_references("Tag", &["id"]))
+    })?;
+
+    let output = api.infer_apply(dm).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    Ok(())
+}
+
 #[test_each_connector]
 async fn changing_a_relation_field_to_a_scalar_field_must_work(api: &TestApi) -> TestResult {
     let dm1 = r#"
@@ -326,6 +517,7 @@ async fn changing_a_relation_field_to_a_scalar_field_must_work(api: &TestApi) ->
                 referenced_table: "B".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete_action: ForeignKeyAction::Restrict,
+                deferrable: false,
             })
     })?;
 
@@ -396,6 +588,7 @@ async fn changing_a_scalar_field_to_a_relation_field_must_work(api: &TestApi) {
             referenced_table: "B".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 }
@@ -437,6 +630,7 @@ async fn adding_a_many_to_many_relation_must_result_in_a_prisma_style_relation_t
                 referenced_table: "A".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete_action: ForeignKeyAction::Cascade,
+                deferrable: false,
             },
             ForeignKey {
                 constraint_name: match api.sql_family() {
@@ -448,6 +642,7 @@ async fn adding_a_many_to_many_relation_must_result_in_a_prisma_style_relation_t
                 referenced_table: "B".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete_action: ForeignKeyAction::Cascade,
+                deferrable: false,
             },
         ]
     );
@@ -488,6 +683,7 @@ async fn adding_a_many_to_many_relation_with_custom_name_must_work(api: &TestApi
                 referenced_table: "A".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete_action: ForeignKeyAction::Cascade,
+                deferrable: false,
             },
             ForeignKey {
                 constraint_name: match api.sql_family() {
@@ -499,6 +695,7 @@ async fn adding_a_many_to_many_relation_with_custom_name_must_work(api: &TestApi
                 referenced_table: "B".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete_action: ForeignKeyAction::Cascade,
+                deferrable: false,
             }
         ]
     );
@@ -574,6 +771,7 @@ async fn adding_an_inline_relation_must_result_in_a_foreign_key_in_the_model_tab
                 referenced_table: "B".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete_action: ForeignKeyAction::Restrict, // required relations can't set ON DELETE SET NULL
+                deferrable: false,
             },
             ForeignKey {
                 constraint_name: match api.sql_family() {
@@ -585,6 +783,7 @@ async fn adding_an_inline_relation_must_result_in_a_foreign_key_in_the_model_tab
                 referenced_table: "C".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete_action: ForeignKeyAction::SetNull,
+                deferrable: false,
             }
         ]
     );
@@ -618,6 +817,7 @@ async fn specifying_a_db_name_for_an_inline_relation_must_work(api: &TestApi) {
             referenced_table: "B".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 }
@@ -650,6 +850,7 @@ async fn adding_an_inline_relation_to_a_model_with_an_exotic_id_type(api: &TestA
             referenced_table: "B".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 }
@@ -719,6 +920,7 @@ async fn moving_an_inline_relation_to_the_other_side_must_work(api: &TestApi) ->
             referenced_table: "B".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 
@@ -746,6 +948,7 @@ async fn moving_an_inline_relation_to_the_other_side_must_work(api: &TestApi) ->
             referenced_table: "A".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::Restrict,
+            deferrable: false,
         }]
     );
 
@@ -1157,6 +1360,7 @@ async fn index_updates_with_rename_must_work(api: &TestApi) {
                     name: "customNameA".into(),
                     columns: vec!["field".into(), "id".into()],
                     tpe: IndexType::Unique,
+                    column_options: BTreeMap::new(),
                 },
             }),
         ];
@@ -1217,6 +1421,7 @@ async fn reserved_sql_key_words_must_work(api: &TestApi) {
             referenced_table: "Group".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete_action: ForeignKeyAction::SetNull,
+            deferrable: false,
         }]
     );
 }
@@ -1377,6 +1582,72 @@ async fn model_with_multiple_indexes_works(api: &TestApi) -> TestResult {
     Ok(())
 }
 
+// MySQL 5.7 and MariaDB parse, but ignore DESC in index definitions.
+#[test_each_connector(ignore("mysql"))]
+async fn descending_index_columns_must_work(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Post {
+            id Int @id
+            title String
+            publishedAt DateTime
+
+            @@index([title, publishedAt(sort: Desc)])
+        }
+    "#;
+
+    api.infer_apply(dm).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("Post", |table| {
+        table.assert_index_on_columns(&["title", "publishedAt"], |index| {
+            index
+                .assert_column_sort_order("title", SortOrder::Asc)?
+                .assert_column_sort_order("publishedAt", SortOrder::Desc)
+        })
+    })?;
+
+    // The described index must match the calculated one, otherwise it would be recreated on every migration.
+    let output = api.infer_apply(dm).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    Ok(())
+}
+
+#[test_each_connector(tags("mysql"))]
+async fn index_column_prefix_lengths_must_work_on_mysql(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model Post {
+            id Int @id
+            title String
+
+            @@index([title(length: 100)])
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("Post", |table| {
+        table.assert_index_on_columns(&["title"], |index| index.assert_column_length("title", Some(100)))
+    })?;
+
+    // The described index must match the calculated one, otherwise it would be recreated on every migration.
+    let output = api.infer_apply(dm1).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    let dm2 = r#"
+        model Post {
+            id Int @id
+            title String
+
+            @@index([title(length: 50)])
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("Post", |table| {
+        table.assert_index_on_columns(&["title"], |index| index.assert_column_length("title", Some(50)))
+    })?;
+
+    Ok(())
+}
+
 #[test_each_connector]
 async fn foreign_keys_of_inline_one_to_one_relations_have_a_unique_constraint(api: &TestApi) {
     let dm = r#"
@@ -1399,6 +1670,7 @@ async fn foreign_keys_of_inline_one_to_one_relations_have_a_unique_constraint(ap
         name: "Box_cat".into(),
         columns: vec!["cat".into()],
         tpe: IndexType::Unique,
+        column_options: BTreeMap::new(),
     }];
 
     assert_eq!(box_table.indices, expected_indexes);
@@ -1435,6 +1707,48 @@ async fn column_defaults_must_be_migrated(api: &TestApi) -> TestResult {
     Ok(())
 }
 
+#[test_each_connector]
+async fn enum_defaults_must_be_migrated(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model Cat {
+            id Int @id
+            mood Mood @default(HAPPY)
+        }
+
+        enum Mood {
+            HAPPY
+            HUNGRY
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("Cat", |table| {
+        table.assert_column("mood", |col| col.assert_default(Some("HAPPY")))
+    })?;
+
+    let output = api.infer_apply(dm1).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    let dm2 = r#"
+        model Cat {
+            id Int @id
+            mood Mood @default(HUNGRY)
+        }
+
+        enum Mood {
+            HAPPY
+            HUNGRY
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("Cat", |table| {
+        table.assert_column("mood", |col| col.assert_default(Some("HUNGRY")))
+    })?;
+
+    Ok(())
+}
+
 #[test_each_connector]
 async fn escaped_string_defaults_are_not_arbitrarily_migrated(api: &TestApi) -> TestResult {
     use quaint::ast::*;
@@ -1798,6 +2112,67 @@ async fn compound_primary_keys_on_mapped_columns_must_work(api: &TestApi) -> Tes
     Ok(())
 }
 
+#[test_each_connector]
+async fn reordering_the_columns_of_a_compound_primary_key_must_work(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model User {
+            firstName String
+            lastName String
+
+            @@id([firstName, lastName])
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+
+    let dm2 = r#"
+        model User {
+            firstName String
+            lastName String
+
+            @@id([lastName, firstName])
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+
+    api.assert_schema().await?.assert_table("User", |table| {
+        table.assert_pk(|pk| pk.assert_columns(&["lastName", "firstName"]))
+    })?;
+
+    let output = api.infer_apply(dm2).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn changing_the_id_field_of_a_model_must_work(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model User {
+            id String @id
+            email String
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+
+    let dm2 = r#"
+        model User {
+            id String
+            email String @id
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+
+    api.assert_schema()
+        .await?
+        .assert_table("User", |table| table.assert_pk(|pk| pk.assert_columns(&["email"])))?;
+
+    Ok(())
+}
+
 #[test_each_connector]
 async fn references_to_models_with_compound_primary_keys_must_work(api: &TestApi) -> TestResult {
     let dm = r#"
@@ -1935,3 +2310,127 @@ async fn switching_databases_must_work(api: &TestApi) -> TestResult {
 
     Ok(())
 }
+
+#[test_each_connector]
+async fn reserved_words_and_quotes_in_names_must_work(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model User {
+            id     Int    @id
+            select String
+            posts  Post[]
+
+            @@map("user\"s")
+        }
+
+        model Post {
+            id     Int    @id
+            author User   @map("user`s")
+            title  String @map("post.title")
+
+            @@map("order")
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+    api.assert_schema()
+        .await?
+        .assert_table(r#"user\"s"#, |table| table.assert_has_column("select"))?
+        .assert_table("order", |table| {
+            table
+                .assert_has_column("post.title")?
+                .assert_fk_on_columns(&["user`s"], |fk| fk.assert_references(r#"user\"s"#, &["id"]))
+        })?;
+
+    let dm2 = r#"
+        model User {
+            id     Int    @id
+            select String
+            from   String @map("from\"where")
+            posts  Post[]
+
+            @@map("user\"s")
+        }
+
+        model Post {
+            id     Int    @id
+            author User   @map("user`s")
+
+            @@map("order")
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+    api.assert_schema()
+        .await?
+        .assert_table(r#"user\"s"#, |table| table.assert_has_column(r#"from\"where"#))?
+        .assert_table("order", |table| table.assert_does_not_have_column("post.title"))?;
+
+    Ok(())
+}
+
+#[test_each_connector(ignore("sqlite"))]
+async fn audited_models_must_record_the_row_changes_in_a_history_table(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model Post {
+            id Int @id
+            title String
+
+            @@audited
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+
+    let output = api.infer_apply(dm1).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    api.insert("Post")
+        .value("id", 1)
+        .value("title", "first")
+        .result_raw()
+        .await?;
+
+    assert_eq!(api.dump_table("Post_history").await?.len(), 1);
+
+    let dm2 = r#"
+        model Post {
+            id Int @id
+            title String
+            published Boolean @default(false)
+
+            @@audited
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+
+    api.insert("Post")
+        .value("id", 2)
+        .value("title", "second")
+        .result_raw()
+        .await?;
+
+    assert_eq!(api.dump_table("Post_history").await?.len(), 2);
+
+    let dm3 = r#"
+        model Post {
+            id Int @id
+            title String
+            published Boolean @default(false)
+        }
+    "#;
+
+    // Dropping the history table loses the recorded changes.
+    api.infer_apply(dm3)
+        .force(Some(true))
+        .send_assert()
+        .await?
+        .assert_executable()?
+        .assert_no_error()?;
+
+    let schema = api.describe_database().await?;
+    assert!(schema.table("Post_history").is_err());
+    assert!(schema.triggers.is_empty());
+
+    Ok(())
+}
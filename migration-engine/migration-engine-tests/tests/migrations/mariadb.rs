@@ -67,3 +67,26 @@ async fn foreign_keys_to_indexes_being_renamed_must_work(api: &TestApi) -> TestR
 
     Ok(())
 }
+
+#[test_each_connector(tags("mariadb"))]
+async fn json_columns_must_not_be_migrated_again(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Post {
+            id Int @id
+            title String
+
+            @@audited
+        }
+    "#;
+
+    api.infer_apply(dm).send_assert().await?.assert_green()?;
+
+    api.assert_schema().await?.assert_table("Post_history", |table| {
+        table.assert_column("row_data", |column| column.assert_type_is_json())
+    })?;
+
+    let output = api.infer_apply(dm).send().await?;
+    assert_eq!(output.database_steps, serde_json::json!([]));
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
+use migration_core::api::MigrationApi;
 use migration_engine_tests::*;
+use sql_migration_connector::SqlMigrationConnector;
 use sql_schema_describer::{ColumnArity, ColumnTypeFamily};
 
 #[test_each_connector(tags("postgres"))]
@@ -65,3 +67,133 @@ async fn adding_a_scalar_list_for_a_model_with_id_type_int_must_work(api: &TestA
     assert_eq!(enum_column.tpe.family, ColumnTypeFamily::Enum("Status".to_owned()));
     assert_eq!(enum_column.tpe.arity, ColumnArity::List);
 }
+
+#[test_each_connector(tags("postgres"))]
+async fn enum_defaults_and_enum_lists_survive_dropping_enum_variants(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model User {
+            id Int @id
+            role Role @default(ADMIN)
+            roles Role[]
+        }
+
+        enum Role {
+            ADMIN
+            EDITOR
+            GUEST
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("User", |table| {
+        table.assert_column("role", |column| column.assert_default(Some("ADMIN")))
+    })?;
+
+    let dm2 = r#"
+        model User {
+            id Int @id
+            role Role @default(ADMIN)
+            roles Role[]
+        }
+
+        enum Role {
+            ADMIN
+            EDITOR
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+
+    api.assert_schema().await?.assert_table("User", |table| {
+        table.assert_column("role", |column| column.assert_default(Some("ADMIN")))
+    })?;
+
+    let schema = api.describe_database().await?;
+    let roles = schema.table_bang("User").column_bang("roles");
+    assert_eq!(roles.tpe.family, ColumnTypeFamily::Enum("Role".to_owned()));
+    assert_eq!(roles.tpe.arity, ColumnArity::List);
+
+    Ok(())
+}
+
+#[test_each_connector(tags("postgres"))]
+async fn citext_columns_can_be_created_and_altered(api: &TestApi) -> TestResult {
+    let dm1 = r#"
+        model User {
+            id    Int    @id
+            email String @unique
+        }
+    "#;
+
+    api.infer_apply(dm1).send_assert().await?.assert_green()?;
+
+    let dm2 = r#"
+        generator client {
+            provider        = "prisma-client-js"
+            previewFeatures = ["nativeTypes"]
+        }
+
+        model User {
+            id    Int    @id
+            email String @unique @db.Citext
+        }
+    "#;
+
+    api.infer_apply(dm2).send_assert().await?.assert_green()?;
+
+    let schema = api.describe_database().await?;
+    let email = schema.table_bang("User").column_bang("email");
+    assert_eq!(email.tpe.family, ColumnTypeFamily::String);
+    assert!(email.tpe.is_citext());
+
+    api.infer_apply(dm2)
+        .send_assert()
+        .await?
+        .assert_green()?
+        .assert_no_steps()?;
+
+    Ok(())
+}
+
+#[test_one_connector(connector = "postgres")]
+async fn uuid_defaults_are_only_rendered_when_enabled(api: &TestApi) -> TestResult {
+    api.database()
+        .query_raw("CREATE EXTENSION IF NOT EXISTS pgcrypto", &[])
+        .await?;
+
+    let dm = r#"
+        model User {
+            id String @id @default(uuid())
+        }
+    "#;
+
+    api.infer_apply(dm).send_assert().await?.assert_green()?;
+    api.assert_schema().await?.assert_table("User", |table| {
+        table.assert_column("id", |column| column.assert_default(None))
+    })?;
+
+    // The connector probes the UUID function when it connects, after the extension was created.
+    let url = postgres_10_url("uuid_defaults_are_only_rendered_when_enabled");
+    let connector = SqlMigrationConnector::new(&url, "postgresql")
+        .await
+        .unwrap()
+        .with_database_uuid_defaults(true);
+    let uuid_defaults_api = MigrationApi::new(connector).await.unwrap();
+
+    InferApply::new(&uuid_defaults_api, dm)
+        .send_assert()
+        .await?
+        .assert_green()?;
+
+    let schema = api.describe_database().await?;
+    let default = schema.table_bang("User").column_bang("id").default.clone();
+    assert!(default.unwrap().contains("gen_random_uuid()"));
+
+    InferApply::new(&uuid_defaults_api, dm)
+        .send_assert()
+        .await?
+        .assert_green()?
+        .assert_no_steps()?;
+
+    Ok(())
+}
@@ -75,3 +75,88 @@ async fn sqlite_must_recreate_multi_field_indexes(api: &TestApi) -> TestResult {
         })
         .map(drop)
 }
+
+#[test]
+fn relation_mode_prisma_must_not_create_foreign_keys() {
+    test_setup::runtime::run_with_tokio(async {
+        let api = sqlite_test_api_with_relation_mode(
+            "relation_mode_prisma_must_not_create_foreign_keys",
+            datamodel::RelationMode::Prisma,
+        )
+        .await;
+
+        let dm = r#"
+            model User {
+                id     Int     @id
+                posts  Post[]
+                groups Group[]
+            }
+
+            model Post {
+                id     Int  @id
+                author User
+            }
+
+            model Group {
+                id      Int    @id
+                members User[]
+            }
+        "#;
+
+        api.infer_apply(&dm).send().await?;
+
+        api.assert_schema()
+            .await?
+            .assert_table("Post", |table| {
+                table.assert_has_column("author")?.assert_foreign_keys_count(0)
+            })?
+            .assert_table("_GroupToUser", |table| table.assert_foreign_keys_count(0))
+            .map(drop)
+    })
+    .unwrap()
+}
+
+#[test_each_connector(tags("sqlite"))]
+async fn decimal_columns_keep_their_precision_and_scale(api: &TestApi) -> TestResult {
+    let dm = r#"
+        generator client {
+            provider        = "prisma-client-js"
+            previewFeatures = ["nativeTypes"]
+        }
+
+        model Product {
+            id     Int     @id
+            price  Decimal @db.Decimal(10, scale: 2)
+            weight Decimal
+        }
+    "#;
+
+    api.infer_apply(dm).send_assert().await?.assert_green()?;
+
+    let schema = api.describe_database().await?;
+    let product = schema.table_bang("Product");
+    assert_eq!(product.column_bang("price").tpe.decimal_precision(), Some((10, 2)));
+    assert_eq!(product.column_bang("weight").tpe.decimal_precision(), Some((65, 30)));
+
+    api.infer_apply(dm)
+        .send_assert()
+        .await?
+        .assert_green()?
+        .assert_no_steps()?;
+
+    let dm2 = dm.replace("@db.Decimal(10, scale: 2)", "@db.Decimal(12, scale: 4)");
+
+    api.infer_apply(&dm2).send_assert().await?.assert_green()?;
+
+    let schema = api.describe_database().await?;
+    assert_eq!(
+        schema
+            .table_bang("Product")
+            .column_bang("price")
+            .tpe
+            .decimal_precision(),
+        Some((12, 4))
+    );
+
+    Ok(())
+}
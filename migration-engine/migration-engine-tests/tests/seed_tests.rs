@@ -0,0 +1,106 @@
+use migration_core::commands::{SeedInput, SeedModelRecords, SeedStatus};
+use migration_engine_tests::sql::*;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test_each_connector]
+async fn seeds_run_once_with_the_script_before_the_records(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Blog {
+            id Int @id
+            name String @map("blog_name")
+        }
+    "#;
+
+    api.infer_apply(dm).send().await?;
+
+    let quote = if api.is_mysql() { "`" } else { "\"" };
+
+    let input = SeedInput {
+        name: "initial-blogs".to_owned(),
+        script: Some(format!(
+            "INSERT INTO {q}Blog{q} ({q}id{q}, {q}blog_name{q}) VALUES (1, 'Engineering; and more');",
+            q = quote
+        )),
+        records: vec![SeedModelRecords {
+            model: "Blog".to_owned(),
+            data: vec![json!({ "id": 2, "name": "Product" }).as_object().unwrap().clone()],
+        }],
+    };
+
+    assert_eq!(api.seed(&input).await?.status, SeedStatus::Applied);
+    assert_eq!(api.seed(&input).await?.status, SeedStatus::AlreadyApplied);
+
+    let names: Vec<String> = api
+        .dump_table("Blog")
+        .await?
+        .into_iter()
+        .filter_map(|row| row.get("blog_name").and_then(|val| val.to_string()))
+        .collect();
+
+    assert_eq!(names, &["Engineering; and more", "Product"]);
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn seeds_with_unknown_fields_are_not_recorded(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Blog {
+            id Int @id
+        }
+    "#;
+
+    api.infer_apply(dm).send().await?;
+
+    let input = SeedInput {
+        name: "broken-blogs".to_owned(),
+        script: None,
+        records: vec![SeedModelRecords {
+            model: "Blog".to_owned(),
+            data: vec![json!({ "id": 1, "title": "Engineering" }).as_object().unwrap().clone()],
+        }],
+    };
+
+    let error = api.seed(&input).await.unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("The model `Blog` has no scalar field `title`."));
+    assert!(api.dump_table("Blog").await?.is_empty());
+
+    Ok(())
+}
+
+#[test_each_connector]
+async fn failing_seeds_leave_no_records_and_can_be_rerun(api: &TestApi) -> TestResult {
+    let dm = r#"
+        model Blog {
+            id Int @id
+        }
+    "#;
+
+    api.infer_apply(dm).send().await?;
+
+    let quote = if api.is_mysql() { "`" } else { "\"" };
+
+    let mut input = SeedInput {
+        name: "duplicate-blogs".to_owned(),
+        script: Some(format!("INSERT INTO {q}Blog{q} ({q}id{q}) VALUES (1);", q = quote)),
+        records: vec![SeedModelRecords {
+            model: "Blog".to_owned(),
+            data: vec![json!({ "id": 1 }).as_object().unwrap().clone()],
+        }],
+    };
+
+    // The record collides with the row of the script.
+    assert!(api.seed(&input).await.is_err());
+    assert!(api.dump_table("Blog").await?.is_empty());
+
+    input.records[0].data = vec![json!({ "id": 2 }).as_object().unwrap().clone()];
+
+    assert_eq!(api.seed(&input).await?.status, SeedStatus::Applied);
+    assert_eq!(api.dump_table("Blog").await?.len(), 2);
+
+    Ok(())
+}
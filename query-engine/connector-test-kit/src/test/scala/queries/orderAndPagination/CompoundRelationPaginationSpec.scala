@@ -0,0 +1,56 @@
+package queries.orderAndPagination
+
+import org.scalatest.{FlatSpec, Matchers}
+import util.ConnectorCapability.JoinRelationLinksCapability
+import util._
+
+class CompoundRelationPaginationSpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  override def runOnlyForCapabilities: Set[ConnectorCapability] = Set(JoinRelationLinksCapability)
+
+  val project = ProjectDsl.fromString {
+    """model Parent {
+      |  id_1     String
+      |  id_2     String
+      |  p        String  @unique
+      |  children Child[]
+      |
+      |  @@id([id_1, id_2])
+      |}
+      |
+      |model Child {
+      |  id     String @id @default(cuid())
+      |  c      String @unique
+      |  parent Parent @relation(references: [id_1, id_2]) @map(["parent_id_1", "parent_id_2"])
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = database.truncateProjectTables(project)
+
+  "Paginating a relation linked by a compound foreign key" should "paginate the children of each parent" in {
+    // The parents share the first id column, only the combination of both columns tells their children apart.
+    createParent("p1", "a", Vector("c11", "c12", "c13"))
+    createParent("p1", "b", Vector("c21", "c22", "c23"))
+
+    server.query("""{ parents { p children(first: 2) { c } } }""", project).toString should be(
+      """{"data":{"parents":[{"p":"p1a","children":[{"c":"c11"},{"c":"c12"}]},{"p":"p1b","children":[{"c":"c21"},{"c":"c22"}]}]}}""")
+
+    server.query("""{ parents { p children(skip: 1, first: 1) { c } } }""", project).toString should be(
+      """{"data":{"parents":[{"p":"p1a","children":[{"c":"c12"}]},{"p":"p1b","children":[{"c":"c22"}]}]}}""")
+  }
+
+  def createParent(id1: String, id2: String, children: Vector[String]): Unit = {
+    val creates = children.map(c => s"""{ c: "$c" }""").mkString(", ")
+
+    server.query(
+      s"""mutation { createParent(data: { id_1: "$id1", id_2: "$id2", p: "$id1$id2", children: { create: [$creates] } }) { p } }""",
+      project
+    )
+  }
+}
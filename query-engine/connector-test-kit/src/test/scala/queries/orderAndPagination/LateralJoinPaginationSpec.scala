@@ -0,0 +1,60 @@
+package queries.orderAndPagination
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+// Postgres paginates the related records of every parent in a LATERAL joined select.
+class LateralJoinPaginationSpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  override def runOnlyForConnectors: Set[ConnectorTag] = Set(ConnectorTag.PostgresConnectorTag)
+
+  val project = ProjectDsl.fromString {
+    """model Parent {
+      |  id       String  @id @default(cuid())
+      |  p        String  @unique
+      |  children Child[]
+      |}
+      |
+      |model Child {
+      |  id     String @id @default(cuid())
+      |  c      String @unique
+      |  parent Parent
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = database.truncateProjectTables(project)
+
+  "Paginating the children of several parents" should "read a page per parent" in {
+    createParent("p1", Vector("c11", "c12", "c13"))
+    createParent("p2", Vector("c21", "c22", "c23"))
+    createParent("p3", Vector())
+
+    server.query("""{ parents(orderBy: p_ASC) { p children(first: 2, orderBy: c_ASC) { c } } }""", project).toString should be(
+      """{"data":{"parents":[{"p":"p1","children":[{"c":"c11"},{"c":"c12"}]},{"p":"p2","children":[{"c":"c21"},{"c":"c22"}]},{"p":"p3","children":[]}]}}""")
+
+    server.query("""{ parents(orderBy: p_ASC) { p children(skip: 1, first: 1, orderBy: c_ASC) { c } } }""", project).toString should be(
+      """{"data":{"parents":[{"p":"p1","children":[{"c":"c12"}]},{"p":"p2","children":[{"c":"c22"}]},{"p":"p3","children":[]}]}}""")
+  }
+
+  "Paginating the children of several parents" should "apply the filter before the page" in {
+    createParent("p1", Vector("a1", "b1", "a2"))
+    createParent("p2", Vector("b2", "a3", "a4"))
+
+    server.query(
+      """{ parents(orderBy: p_ASC) { p children(where: { c_starts_with: "a" }, first: 1, orderBy: c_DESC) { c } } }""",
+      project
+    ).toString should be("""{"data":{"parents":[{"p":"p1","children":[{"c":"a2"}]},{"p":"p2","children":[{"c":"a4"}]}]}}""")
+  }
+
+  def createParent(p: String, children: Vector[String]): Unit = {
+    val creates = children.map(c => s"""{ c: "$c" }""").mkString(", ")
+
+    server.query(s"""mutation { createParent(data: { p: "$p", children: { create: [$creates] } }) { p } }""", project)
+  }
+}
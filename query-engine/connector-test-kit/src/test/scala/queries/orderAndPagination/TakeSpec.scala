@@ -0,0 +1,67 @@
+package queries.orderAndPagination
+
+import org.scalatest.{FlatSpec, Matchers}
+import util.ConnectorCapability.JoinRelationLinksCapability
+import util._
+
+class TakeSpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  override def runOnlyForCapabilities: Set[ConnectorCapability] = Set(JoinRelationLinksCapability)
+
+  val project = ProjectDsl.fromString {
+    """model List {
+      |  id    String @id @default(cuid())
+      |  name  String @unique
+      |  todos Todo[]
+      |}
+      |
+      |model Todo {
+      |  id    String @id @default(cuid())
+      |  title String @unique
+      |  list  List
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = {
+    database.truncateProjectTables(project)
+    createList("l1", Vector("a", "b", "c", "d"))
+    createList("l2", Vector("e", "f", "g"))
+  }
+
+  "A positive take" should "read the first records" in {
+    server.query("""{ todos(take: 2, orderBy: title_ASC) { title } }""", project).toString should be(
+      """{"data":{"todos":[{"title":"a"},{"title":"b"}]}}""")
+  }
+
+  "A negative take" should "read the last records in order" in {
+    server.query("""{ todos(take: -2, orderBy: title_ASC) { title } }""", project).toString should be(
+      """{"data":{"todos":[{"title":"f"},{"title":"g"}]}}""")
+
+    server.query("""{ todos(take: -2, before: { title: "d" }, orderBy: title_ASC) { title } }""", project).toString should be(
+      """{"data":{"todos":[{"title":"b"},{"title":"c"}]}}""")
+  }
+
+  "A negative take" should "read the last related records of every parent in order" in {
+    server.query("""{ lists(orderBy: name_ASC) { name todos(take: -2, orderBy: title_ASC) { title } } }""", project).toString should be(
+      """{"data":{"lists":[{"name":"l1","todos":[{"title":"c"},{"title":"d"}]},{"name":"l2","todos":[{"title":"f"},{"title":"g"}]}]}}""")
+  }
+
+  "Take" should "not be combined with first or last" in {
+    server.queryThatMustFail(
+      """{ todos(take: 2, first: 1) { title } }""",
+      project,
+      errorCode = 0
+    )
+  }
+
+  def createList(name: String, titles: Vector[String]): Unit = {
+    val creates = titles.map(title => s"""{ title: "$title" }""").mkString(", ")
+    server.query(s"""mutation { createList(data: { name: "$name", todos: { create: [$creates] } }) { id } }""", project)
+  }
+}
@@ -0,0 +1,81 @@
+package queries.simple
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+class AliasQuerySpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  val project = ProjectDsl.fromString {
+    """model User {
+      |  id    String @id @default(cuid())
+      |  name  String @unique
+      |  posts Post[]
+      |}
+      |
+      |model Post {
+      |  id        String   @id @default(cuid())
+      |  title     String
+      |  published Boolean
+      |  author    User     @relation(references: [id])
+      |  comments  Comment[]
+      |}
+      |
+      |model Comment {
+      |  id   String @id @default(cuid())
+      |  text String
+      |  post Post   @relation(references: [id])
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = {
+    database.truncateProjectTables(project)
+
+    server.query(
+      """mutation {
+        |  createUser(data: {
+        |    name: "Alice"
+        |    posts: { create: [
+        |      { title: "first", published: true, comments: { create: [{ text: "nice" }] } }
+        |      { title: "second", published: false }
+        |    ]}
+        |  }) { id }
+        |}""".stripMargin,
+      project
+    )
+  }
+
+  "Aliases of scalar fields" should "key the results" in {
+    server.query("""{ user(where: { name: "Alice" }) { userName: name } }""", project).toString should be(
+      """{"data":{"user":{"userName":"Alice"}}}""")
+  }
+
+  "Aliases of nested relations" should "key the results at every level" in {
+    server
+      .query(
+        """{ user(where: { name: "Alice" }) { articles: posts(where: { published: true }) { heading: title replies: comments { body: text } } } }""",
+        project
+      )
+      .toString should be("""{"data":{"user":{"articles":[{"heading":"first","replies":[{"body":"nice"}]}]}}}""")
+  }
+
+  "Two aliases of the same relation" should "be resolved with their own filters" in {
+    server
+      .query(
+        """{
+          |  users {
+          |    name
+          |    published: posts(where: { published: true }) { title }
+          |    drafts: posts(where: { published: false }) { title }
+          |  }
+          |}""".stripMargin,
+        project
+      )
+      .toString should be("""{"data":{"users":[{"name":"Alice","published":[{"title":"first"}],"drafts":[{"title":"second"}]}]}}""")
+  }
+}
@@ -0,0 +1,51 @@
+package queries.simple
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+class ComputedFieldQuerySpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  val project = ProjectDsl.fromString {
+    """model Item {
+      |  id       String @id @default(cuid())
+      |  name     String @unique
+      |  price    Int
+      |  quantity Int
+      |  total    Int    @computed("price * quantity")
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = database.truncateProjectTables(project)
+
+  "A computed field" should "be read from its expression" in {
+    server.query("""mutation { createItem(data: { name: "a", price: 3, quantity: 4 }) { total } }""", project).toString should be(
+      """{"data":{"createItem":{"total":12}}}""")
+
+    server.query("""mutation { createItem(data: { name: "b", price: 5, quantity: 1 }) { id } }""", project)
+
+    server.query("""{ item(where: { name: "b" }) { total } }""", project).toString should be("""{"data":{"item":{"total":5}}}""")
+    server.query("""{ items { name total } }""", project).toString should be(
+      """{"data":{"items":[{"name":"a","total":12},{"name":"b","total":5}]}}""")
+  }
+
+  "A computed field" should "follow updates of the fields of its expression" in {
+    server.query("""mutation { createItem(data: { name: "a", price: 3, quantity: 4 }) { id } }""", project)
+
+    server.query("""mutation { updateItem(where: { name: "a" }, data: { quantity: 10 }) { total } }""", project).toString should be(
+      """{"data":{"updateItem":{"total":30}}}""")
+  }
+
+  "A computed field" should "not be writable" in {
+    server.queryThatMustFail(
+      """mutation { createItem(data: { name: "a", price: 3, quantity: 4, total: 1 }) { id } }""",
+      project,
+      errorCode = 0
+    )
+  }
+}
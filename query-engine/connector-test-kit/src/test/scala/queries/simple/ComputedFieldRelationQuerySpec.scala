@@ -0,0 +1,51 @@
+package queries.simple
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+class ComputedFieldRelationQuerySpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  val project = ProjectDsl.fromString {
+    """model Order {
+      |  id    String @id @default(cuid())
+      |  name  String @unique
+      |  items Item[]
+      |}
+      |
+      |model Item {
+      |  id       String @id @default(cuid())
+      |  name     String @unique
+      |  price    Int
+      |  quantity Int
+      |  total    Int    @computed("price * quantity")
+      |  order    Order
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = database.truncateProjectTables(project)
+
+  // Paginating the items of several orders reads them with the pagination of the connector: a window function, a union
+  // of the pages or a lateral join.
+  "A computed field" should "be read on paginated related records" in {
+    server.query(
+      """mutation { createOrder(data: { name: "o1", items: { create: [{ name: "a", price: 3, quantity: 4 }, { name: "b", price: 5, quantity: 1 }, { name: "c", price: 2, quantity: 2 }] } }) { id } }""",
+      project
+    )
+    server.query(
+      """mutation { createOrder(data: { name: "o2", items: { create: [{ name: "d", price: 1, quantity: 7 }] } }) { id } }""",
+      project
+    )
+
+    server.query("""{ orders(orderBy: name_ASC) { name items(first: 2, orderBy: name_ASC) { name total } } }""", project).toString should be(
+      """{"data":{"orders":[{"name":"o1","items":[{"name":"a","total":12},{"name":"b","total":5}]},{"name":"o2","items":[{"name":"d","total":7}]}]}}""")
+
+    server.query("""{ orders(orderBy: name_ASC) { name items(skip: 1, first: 1, orderBy: name_ASC) { total } } }""", project).toString should be(
+      """{"data":{"orders":[{"name":"o1","items":[{"total":5}]},{"name":"o2","items":[]}]}}""")
+  }
+}
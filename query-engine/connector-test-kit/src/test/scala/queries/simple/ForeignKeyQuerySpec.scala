@@ -0,0 +1,49 @@
+package queries.simple
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+class ForeignKeyQuerySpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  val project = ProjectDsl.fromString {
+    """model User {
+      |  id    String @id
+      |  posts Post[]
+      |}
+      |
+      |model Post {
+      |  id     String @id
+      |  author User?  @relation(references: [id])
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = {
+    database.truncateProjectTables(project)
+
+    server.query(
+      """mutation {
+        |  createUser(data: { id: "alice", posts: { create: [{ id: "first" }] } }) { id }
+        |}""".stripMargin,
+      project
+    )
+    server.query("""mutation { createPost(data: { id: "second" }) { id } }""", project)
+  }
+
+  "The foreign key of an inlined relation" should "be selectable without the relation" in {
+    server
+      .query("""{ posts(orderBy: id_ASC) { id authorId } }""", project)
+      .toString should be("""{"data":{"posts":[{"id":"first","authorId":"alice"},{"id":"second","authorId":null}]}}""")
+  }
+
+  "The foreign key of an inlined relation" should "be selectable next to the relation" in {
+    server
+      .query("""{ post(where: { id: "first" }) { authorId author { id } } }""", project)
+      .toString should be("""{"data":{"post":{"authorId":"alice","author":{"id":"alice"}}}}""")
+  }
+}
@@ -0,0 +1,52 @@
+package writes.datetime
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+class UpdatedAtWithDefaultNowSpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  val project = ProjectDsl.fromString {
+    """model Top {
+      |  id        String   @id @default(cuid())
+      |  top       String   @unique
+      |  updatedAt DateTime @default(now()) @updatedAt
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = database.truncateProjectTables(project)
+
+  "An updatedAt field with a default of now()" should "be set on create" in {
+    val updatedAt = server.query("""mutation { createTop(data: { top: "top1" }) { updatedAt } }""", project).pathAsString("data.createTop.updatedAt")
+
+    updatedAt should not be empty
+  }
+
+  "An updatedAt field with a default of now()" should "change on updates" in {
+    val updatedAt = server.query("""mutation { createTop(data: { top: "top1" }) { updatedAt } }""", project).pathAsString("data.createTop.updatedAt")
+
+    Thread.sleep(1000)
+
+    val changedUpdatedAt = server
+      .query("""mutation { updateTop(where: { top: "top1" }, data: { top: "top2" }) { updatedAt } }""", project)
+      .pathAsString("data.updateTop.updatedAt")
+
+    updatedAt should not equal changedUpdatedAt
+  }
+
+  "An updatedAt field with a default of now()" should "keep an explicitly written value" in {
+    server.query("""mutation { createTop(data: { top: "top1" }) { id } }""", project)
+
+    server
+      .query(
+        """mutation { updateTop(where: { top: "top1" }, data: { top: "top2", updatedAt: "2020-01-01T00:00:00.000Z" }) { updatedAt } }""",
+        project
+      )
+      .pathAsString("data.updateTop.updatedAt") should be("2020-01-01T00:00:00.000Z")
+  }
+}
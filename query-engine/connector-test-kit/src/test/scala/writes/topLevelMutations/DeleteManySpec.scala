@@ -176,6 +176,34 @@ class DeleteManySpec extends FlatSpec with Matchers with ApiSpecBase {
     todoCountShouldBe(3)
   }
 
+  "The delete many Mutation" should "delete at most as many items as the limit" in {
+    createTodo("title1")
+    createTodo("title2")
+    createTodo("title3")
+
+    def deleteTwo() =
+      server
+        .query(
+          """mutation {
+            |  deleteManyTodoes(
+            |    where: { }
+            |    limit: 2
+            |  ){
+            |    count
+            |  }
+            |}
+          """.stripMargin,
+          project
+        )
+        .pathAsLong("data.deleteManyTodoes.count")
+
+    deleteTwo() should equal(2)
+    todoCountShouldBe(1)
+
+    deleteTwo() should equal(1)
+    todoCountShouldBe(0)
+  }
+
   "nested DeleteMany" should "work" in {
 
     val project = ProjectDsl.fromString {
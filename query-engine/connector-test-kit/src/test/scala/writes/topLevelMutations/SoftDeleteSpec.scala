@@ -0,0 +1,109 @@
+package writes.topLevelMutations
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+class SoftDeleteSpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  val project = ProjectDsl.fromString {
+    """model Todo {
+      |  id        String    @id @default(cuid())
+      |  title     String    @unique
+      |  deletedAt DateTime?
+      |
+      |  @@softDelete(field: deletedAt)
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = database.truncateProjectTables(project)
+
+  "A delete of a soft deleted model" should "mark the record as deleted and hide it from reads" in {
+    createTodo("title1")
+    createTodo("title2")
+
+    server.query(
+      """mutation {
+        |  deleteTodo(where: { title: "title1" }) {
+        |    title
+        |  }
+        |}
+      """.stripMargin,
+      project
+    )
+
+    todoTitles(includeDeleted = false) should be("""[{"title":"title2"}]""")
+    todoTitles(includeDeleted = true) should be("""[{"title":"title1"},{"title":"title2"}]""")
+
+    val findOne = server.query("""{ todo(where: { title: "title1" }) { title } }""", project)
+    findOne.toString should be("""{"data":{"todo":null}}""")
+
+    val findOneDeleted =
+      server.query("""{ todo(where: { title: "title1" }, includeDeleted: true) { title } }""", project)
+    findOneDeleted.toString should be("""{"data":{"todo":{"title":"title1"}}}""")
+  }
+
+  "A delete of a soft deleted model" should "fail for records that are already deleted" in {
+    createTodo("title1")
+
+    val deleteTitle1 =
+      """mutation {
+        |  deleteTodo(where: { title: "title1" }) {
+        |    title
+        |  }
+        |}
+      """.stripMargin
+
+    server.query(deleteTitle1, project)
+    server.queryThatMustFail(deleteTitle1, project, errorCode = 0)
+  }
+
+  "A delete many of a soft deleted model" should "only count records that weren't deleted before" in {
+    createTodo("title1")
+    createTodo("title2")
+    createTodo("title3")
+
+    def deleteAll() =
+      server
+        .query(
+          """mutation {
+            |  deleteManyTodoes(where: { }) {
+            |    count
+            |  }
+            |}
+          """.stripMargin,
+          project
+        )
+        .pathAsLong("data.deleteManyTodoes.count")
+
+    deleteAll() should equal(3)
+    deleteAll() should equal(0)
+
+    todoTitles(includeDeleted = false) should be("[]")
+    todoTitles(includeDeleted = true) should be("""[{"title":"title1"},{"title":"title2"},{"title":"title3"}]""")
+  }
+
+  def todoTitles(includeDeleted: Boolean): String = {
+    server
+      .query(s"""{ todoes(orderBy: title_ASC, includeDeleted: $includeDeleted) { title } }""", project)
+      .pathAsJsValue("data.todoes")
+      .toString
+  }
+
+  def createTodo(title: String): Unit = {
+    server.query(
+      s"""mutation {
+        |  createTodo(data: { title: "$title" }) {
+        |    id
+        |  }
+        |}
+      """.stripMargin,
+      project
+    )
+  }
+}
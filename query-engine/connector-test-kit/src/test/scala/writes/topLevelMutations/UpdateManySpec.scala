@@ -88,6 +88,33 @@ class UpdateManySpec extends FlatSpec with Matchers with ApiSpecBase {
     )
   }
 
+  "The update items Mutation" should "update at most as many items as the limit" in {
+    createTodo("title")
+    createTodo("title")
+    createTodo("title")
+
+    def updateTwo() =
+      server
+        .query(
+          """mutation {
+            |  updateManyTodoes(
+            |    where: { title: "title" }
+            |    data: { title: "updated title" }
+            |    limit: 2
+            |  ){
+            |    count
+            |  }
+            |}
+          """.stripMargin,
+          project
+        )
+        .pathAsLong("data.updateManyTodoes.count")
+
+    updateTwo() should equal(2)
+    updateTwo() should equal(1)
+    updateTwo() should equal(0)
+  }
+
   "UpdateMany" should "work between top level types" in {
 
     val project = ProjectDsl.fromString {
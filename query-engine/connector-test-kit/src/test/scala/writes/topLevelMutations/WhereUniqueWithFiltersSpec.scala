@@ -0,0 +1,102 @@
+package writes.topLevelMutations
+
+import org.scalatest.{FlatSpec, Matchers}
+import util._
+
+class WhereUniqueWithFiltersSpec extends FlatSpec with Matchers with ApiSpecBase {
+
+  val project = ProjectDsl.fromString {
+    """model Todo {
+      |  id      String @id
+      |  title   String
+      |  ownerId String
+      |}
+    """.stripMargin
+  }
+
+  override protected def beforeAll(): Unit = {
+    super.beforeAll()
+    database.setup(project)
+  }
+
+  override def beforeEach(): Unit = {
+    database.truncateProjectTables(project)
+    server.query(
+      """mutation {
+        |  createTodo(data: { id: "todo1", title: "title1", ownerId: "owner1" }) {
+        |    id
+        |  }
+        |}
+      """.stripMargin,
+      project
+    )
+  }
+
+  "An update with additional filters" should "update the record if it matches them" in {
+    val result = server.query(
+      """mutation {
+        |  updateTodo(where: { id: "todo1", AND: { ownerId: "owner1" } }, data: { title: "updated" }) {
+        |    title
+        |  }
+        |}
+      """.stripMargin,
+      project
+    )
+
+    result.toString should be("""{"data":{"updateTodo":{"title":"updated"}}}""")
+  }
+
+  "An update with additional filters" should "fail if the record doesn't match them" in {
+    server.queryThatMustFail(
+      """mutation {
+        |  updateTodo(where: { id: "todo1", AND: { ownerId: "owner2" } }, data: { title: "updated" }) {
+        |    title
+        |  }
+        |}
+      """.stripMargin,
+      project,
+      errorCode = 0,
+      errorContains = "Record to update not found."
+    )
+
+    server.query("""{ todo(where: { id: "todo1" }) { title } }""", project).toString should be(
+      """{"data":{"todo":{"title":"title1"}}}""")
+  }
+
+  "A delete with additional filters" should "fail if the record doesn't match them" in {
+    server.queryThatMustFail(
+      """mutation {
+        |  deleteTodo(where: { id: "todo1", AND: { ownerId: "owner2" } }) {
+        |    id
+        |  }
+        |}
+      """.stripMargin,
+      project,
+      errorCode = 0
+    )
+
+    server.query("""{ todo(where: { id: "todo1", AND: { ownerId: "owner2" } }) { title } }""", project).toString should be(
+      """{"data":{"todo":null}}""")
+  }
+
+  "An upsert with additional filters" should "be rejected instead of creating the record" in {
+    server.queryThatMustFail(
+      """mutation {
+        |  upsertTodo(
+        |    where: { id: "todo1", AND: { ownerId: "owner2" } }
+        |    create: { id: "todo1", title: "created", ownerId: "owner2" }
+        |    update: { title: "updated" }
+        |  ) {
+        |    title
+        |  }
+        |}
+      """.stripMargin,
+      project,
+      errorCode = 0,
+      errorContains = "Upserts don't accept AND filters in their where input."
+    )
+
+    server.query("""{ todo(where: { id: "todo1" }) { title ownerId } }""", project).toString should be(
+      """{"data":{"todo":{"title":"title1","ownerId":"owner1"}}}""")
+  }
+}
@@ -0,0 +1,128 @@
+//! Behavior tests of the query engine that every connector has to pass, from filters over nested writes to
+//! pagination. The tests execute query documents through the query core against the connector built by a
+//! `ConnectorFactory`, so the same expectations hold for every connector running them.
+//!
+//! A connector runs the whole suite from one of its integration tests:
+//!
+//! ```ignore
+//! struct MyFactory;
+//!
+//! impl ConnectorFactory for MyFactory { ... }
+//!
+//! connector_test_kit::connector_test_suite!(MyFactory);
+//! ```
+
+mod test_api;
+
+pub mod suites;
+
+pub use test_api::*;
+
+use async_trait::async_trait;
+use connector_interface::{Connector, ConnectorCapabilities};
+use datamodel::{Datamodel, RelationMode};
+
+/// Builds the connectors under test.
+#[async_trait]
+pub trait ConnectorFactory: Sync {
+    /// The name of the connector, as the primary connector of the executor.
+    fn name(&self) -> &'static str;
+
+    /// The name of the database or schema qualifying the tables of the data model in queries.
+    fn db_name(&self) -> &'static str;
+
+    /// The capabilities the query schema of the tests is built with.
+    fn capabilities(&self) -> ConnectorCapabilities;
+
+    /// A connector to an empty database with the tables of the data model. Every test builds its own connector
+    /// and must not see the records of other tests. Runs on the tokio runtime the test executes its queries on.
+    /// The tables get no foreign keys if the relation mode leaves referential integrity to the query engine.
+    async fn connector(&self, datamodel: &Datamodel, relation_mode: RelationMode) -> Box<dyn Connector + Send + Sync>;
+}
+
+/// Runs a single test case against a new connector of the factory.
+#[doc(hidden)]
+pub fn run_test_case(factory: &dyn ConnectorFactory, test_case: fn(&TestApi)) {
+    test_case(&TestApi::new(factory))
+}
+
+/// Runs a single test case that builds its connectors itself, e.g. for a data model of its own.
+#[doc(hidden)]
+pub fn run_factory_test_case(factory: &dyn ConnectorFactory, test_case: fn(&dyn ConnectorFactory)) {
+    test_case(factory)
+}
+
+/// Generates a `#[test]` for every test case of the suites, grouped in one module per suite, running against
+/// connectors of the given factory.
+#[macro_export]
+macro_rules! connector_test_suite {
+    ($factory:expr) => {
+        $crate::connector_test_suite!(@suites $factory; run_test_case;
+            filters {
+                scalar_filters_select_matching_records,
+                comparisons_with_null_match_neither_the_filter_nor_its_negation,
+                null_values_are_filtered_explicitly,
+                filters_are_combined_with_and_or_not,
+                to_many_relation_filters,
+                to_one_relation_filters,
+            }
+            json_writes {
+                json_merges_patch_the_stored_document,
+                json_merges_of_missing_documents_start_empty,
+                json_updates_set_or_merge,
+            }
+            nested_writes {
+                nested_creates_are_read_through_the_relation,
+                nested_connects_move_existing_records,
+                nested_deletes_remove_related_records,
+                failing_nested_writes_roll_back_the_whole_mutation,
+                self_relation_nested_creates_link_both_sides,
+                self_relation_nested_connects_link_existing_records,
+                self_relation_nested_disconnects_unlink_records,
+                compound_unique_nested_connects_and_disconnects,
+                compound_unique_nested_sets_replace_records,
+            }
+            pagination {
+                first_and_skip_read_a_window_of_records,
+                last_reads_the_last_records_in_order,
+                negative_take_reads_the_last_records,
+                cursors_exclude_the_cursor_record,
+                related_records_are_paginated_per_parent,
+                related_records_without_an_order_are_paginated_by_id,
+            }
+        );
+        $crate::connector_test_suite!(@suites $factory; run_factory_test_case;
+            referential_actions {
+                deletes_cascade_to_the_children,
+                cascades_are_restricted_by_required_relations_of_the_children,
+                deletes_null_out_optional_references,
+                deletes_are_restricted_by_required_references,
+                deletes_remove_the_rows_of_relation_tables,
+                updates_of_referenced_ids_are_restricted,
+                updates_of_unreferenced_records_are_not_restricted,
+            }
+            referenced_fields {
+                related_records_are_read_by_the_referenced_fields,
+                paginated_related_records_are_read_by_the_referenced_fields,
+                parents_are_read_by_the_referenced_fields,
+                nested_deletes_read_the_referenced_fields_of_the_parent,
+                nested_deletes_are_restricted_to_the_records_of_the_parent,
+            }
+        );
+    };
+    (@suites $factory:expr; $runner:ident; $($suite:ident { $($test_case:ident,)* })*) => {
+        $(
+            mod $suite {
+                #[allow(unused_imports)]
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $test_case() {
+                        $crate::$runner(&$factory, $crate::suites::$suite::$test_case)
+                    }
+                )*
+            }
+        )*
+    };
+}
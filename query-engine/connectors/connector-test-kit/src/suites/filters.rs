@@ -0,0 +1,142 @@
+use super::emails;
+use crate::test_api::*;
+use query_core::{Operation, QueryValue};
+use serde_json::json;
+
+fn seed(api: &TestApi) {
+    api.create_user("a@prisma.io", Some(20), &["a1", "a2"]).unwrap();
+    api.create_user("b@prisma.io", Some(30), &["b1"]).unwrap();
+    api.create_user("c@prisma.io", None, &[]).unwrap();
+}
+
+fn where_(filter: Vec<(&str, QueryValue)>) -> Vec<(&str, QueryValue)> {
+    vec![("where", object(filter))]
+}
+
+pub fn scalar_filters_select_matching_records(api: &TestApi) {
+    seed(api);
+
+    assert_eq!(
+        emails(api, where_(vec![("age_gt", QueryValue::Int(25))])),
+        vec!["b@prisma.io"]
+    );
+    assert_eq!(
+        emails(api, where_(vec![("email_starts_with", string("a"))])),
+        vec!["a@prisma.io"]
+    );
+    assert_eq!(
+        emails(
+            api,
+            where_(vec![(
+                "email_in",
+                QueryValue::List(vec![string("a@prisma.io"), string("c@prisma.io")])
+            )])
+        ),
+        vec!["a@prisma.io", "c@prisma.io"]
+    );
+}
+
+pub fn comparisons_with_null_match_neither_the_filter_nor_its_negation(api: &TestApi) {
+    seed(api);
+
+    assert_eq!(
+        emails(api, where_(vec![("age", QueryValue::Int(20))])),
+        vec!["a@prisma.io"]
+    );
+    assert_eq!(
+        emails(api, where_(vec![("age_not", QueryValue::Int(20))])),
+        vec!["b@prisma.io"]
+    );
+    assert_eq!(
+        emails(
+            api,
+            where_(vec![(
+                "NOT",
+                QueryValue::List(vec![object(vec![("age", QueryValue::Int(20))])])
+            )])
+        ),
+        vec!["b@prisma.io"]
+    );
+}
+
+pub fn null_values_are_filtered_explicitly(api: &TestApi) {
+    seed(api);
+
+    assert_eq!(
+        emails(api, where_(vec![("age", QueryValue::Null)])),
+        vec!["c@prisma.io"]
+    );
+    assert_eq!(
+        emails(api, where_(vec![("age_not", QueryValue::Null)])),
+        vec!["a@prisma.io", "b@prisma.io"]
+    );
+}
+
+pub fn filters_are_combined_with_and_or_not(api: &TestApi) {
+    seed(api);
+
+    let either = QueryValue::List(vec![
+        object(vec![("email", string("a@prisma.io"))]),
+        object(vec![("age_gt", QueryValue::Int(25))]),
+    ]);
+
+    assert_eq!(
+        emails(api, where_(vec![("OR", either)])),
+        vec!["a@prisma.io", "b@prisma.io"]
+    );
+
+    let both = QueryValue::List(vec![
+        object(vec![("age_gte", QueryValue::Int(20))]),
+        object(vec![("email_not", string("a@prisma.io"))]),
+    ]);
+
+    assert_eq!(emails(api, where_(vec![("AND", both)])), vec!["b@prisma.io"]);
+
+    let neither = QueryValue::List(vec![
+        object(vec![("email", string("a@prisma.io"))]),
+        object(vec![("email", string("b@prisma.io"))]),
+    ]);
+
+    assert_eq!(emails(api, where_(vec![("NOT", neither)])), vec!["c@prisma.io"]);
+}
+
+pub fn to_many_relation_filters(api: &TestApi) {
+    seed(api);
+
+    let title_starts_with_a = || object(vec![("title_starts_with", string("a"))]);
+
+    assert_eq!(
+        emails(api, where_(vec![("posts_some", title_starts_with_a())])),
+        vec!["a@prisma.io"]
+    );
+
+    // Users without posts have no post violating the condition.
+    assert_eq!(
+        emails(api, where_(vec![("posts_every", title_starts_with_a())])),
+        vec!["a@prisma.io", "c@prisma.io"]
+    );
+    assert_eq!(
+        emails(api, where_(vec![("posts_none", title_starts_with_a())])),
+        vec!["b@prisma.io", "c@prisma.io"]
+    );
+}
+
+pub fn to_one_relation_filters(api: &TestApi) {
+    seed(api);
+
+    let posts = api
+        .execute_field(Operation::Read(selection(
+            "findManyPost",
+            vec![
+                (
+                    "where",
+                    object(vec![("author", object(vec![("age_lt", QueryValue::Int(25))]))]),
+                ),
+                ("orderBy", enum_value("title_ASC")),
+            ],
+            vec![field("title")],
+        )))
+        .unwrap();
+
+    assert_eq!(posts, json!([{ "title": "a1" }, { "title": "a2" }]));
+}
@@ -0,0 +1,98 @@
+use crate::test_api::*;
+use query_core::{CoreResult, Operation, QueryValue};
+use serde_json::json;
+
+/// Updates the meta document of the user with the operation, `set` or `merge`.
+fn update_meta(api: &TestApi, email: &str, operation: &str, value: QueryValue) -> CoreResult<serde_json::Value> {
+    api.execute(Operation::Write(selection(
+        "updateOneUser",
+        vec![
+            ("where", object(vec![("email", string(email))])),
+            ("data", object(vec![("meta", object(vec![(operation, value)]))])),
+        ],
+        vec![field("email")],
+    )))
+}
+
+fn meta(api: &TestApi, email: &str) -> serde_json::Value {
+    let user = api
+        .execute_field(Operation::Read(selection(
+            "findOneUser",
+            vec![("where", object(vec![("email", string(email))]))],
+            vec![field("meta")],
+        )))
+        .unwrap();
+
+    match user["meta"].as_str() {
+        Some(json) => serde_json::from_str(json).unwrap(),
+        None => serde_json::Value::Null,
+    }
+}
+
+pub fn json_merges_patch_the_stored_document(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &[]).unwrap();
+
+    update_meta(
+        api,
+        "a@prisma.io",
+        "set",
+        string(r#"{ "theme": "dark", "notifications": { "email": true, "sms": true }, "tags": [1, 2] }"#),
+    )
+    .unwrap();
+
+    let patch = object(vec![
+        (
+            "notifications",
+            object(vec![("sms", QueryValue::Null), ("push", QueryValue::Boolean(false))]),
+        ),
+        ("tags", QueryValue::List(vec![QueryValue::Int(3)])),
+        ("locale", string("en")),
+    ]);
+
+    update_meta(api, "a@prisma.io", "merge", patch).unwrap();
+
+    assert_eq!(
+        meta(api, "a@prisma.io"),
+        json!({
+            "theme": "dark",
+            "notifications": { "email": true, "push": false },
+            "tags": [3],
+            "locale": "en",
+        })
+    );
+}
+
+pub fn json_merges_of_missing_documents_start_empty(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &[]).unwrap();
+
+    let patch = object(vec![(
+        "notifications",
+        object(vec![("email", QueryValue::Boolean(true))]),
+    )]);
+    update_meta(api, "a@prisma.io", "merge", patch).unwrap();
+
+    assert_eq!(meta(api, "a@prisma.io"), json!({ "notifications": { "email": true } }));
+}
+
+pub fn json_updates_set_or_merge(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &[]).unwrap();
+
+    let both = api.execute(Operation::Write(selection(
+        "updateOneUser",
+        vec![
+            ("where", object(vec![("email", string("a@prisma.io"))])),
+            (
+                "data",
+                object(vec![(
+                    "meta",
+                    object(vec![("set", string("{}")), ("merge", string(r#"{ "a": 1 }"#))]),
+                )]),
+            ),
+        ],
+        vec![field("email")],
+    )));
+
+    assert!(both.is_err());
+    assert!(update_meta(api, "a@prisma.io", "merge", string("[1, 2]")).is_err());
+    assert_eq!(meta(api, "a@prisma.io"), serde_json::Value::Null);
+}
@@ -0,0 +1,77 @@
+//! The test cases, one module per suite. Every case gets a `TestApi` with an empty database, or builds its own for
+//! a data model of its own.
+
+pub mod filters;
+pub mod json_writes;
+pub mod nested_writes;
+pub mod pagination;
+pub mod referenced_fields;
+pub mod referential_actions;
+
+use crate::test_api::*;
+use query_core::{Operation, QueryValue};
+
+/// The data model all suites run against.
+pub const DATAMODEL: &str = r#"
+    model User {
+        id    String @id @default(cuid())
+        email String @unique
+        age   Int?
+        meta  Json?
+        posts Post[]
+
+        manager   User?  @relation("Management")
+        reports   User[] @relation("Management")
+        mentee    User?  @relation("Mentorship")
+        mentor    User?  @relation("Mentorship")
+        following User[] @relation("Follows")
+        followers User[] @relation("Follows")
+    }
+
+    model Post {
+        id      String   @id @default(cuid())
+        title   String   @unique
+        author  User
+        editors Editor[]
+    }
+
+    model Editor {
+        id        String @id @default(cuid())
+        firstName String
+        lastName  String
+        posts     Post[]
+
+        @@unique([firstName, lastName])
+    }
+"#;
+
+/// The emails of the users read with the arguments, ordered by email unless the arguments order them.
+fn emails(api: &TestApi, mut arguments: Vec<(&str, QueryValue)>) -> Vec<String> {
+    if !arguments.iter().any(|(name, _)| *name == "orderBy") {
+        arguments.push(("orderBy", enum_value("email_ASC")));
+    }
+
+    let users = api
+        .execute_field(Operation::Read(selection(
+            "findManyUser",
+            arguments,
+            vec![field("email")],
+        )))
+        .unwrap();
+
+    users
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|user| user["email"].as_str().unwrap().to_owned())
+        .collect()
+}
+
+/// All users ordered by email, with their posts read with the arguments.
+fn users_with_posts(post_arguments: Vec<(&str, QueryValue)>) -> Operation {
+    Operation::Read(selection(
+        "findManyUser",
+        vec![("orderBy", enum_value("email_ASC"))],
+        vec![field("email"), selection("posts", post_arguments, vec![field("title")])],
+    ))
+}
@@ -0,0 +1,404 @@
+use super::users_with_posts;
+use crate::test_api::*;
+use query_core::{Operation, QueryValue};
+use serde_json::json;
+
+pub fn nested_creates_are_read_through_the_relation(api: &TestApi) {
+    api.create_user("b@prisma.io", None, &["b1", "b2"]).unwrap();
+    api.create_user("a@prisma.io", None, &["a1"]).unwrap();
+
+    let result = api
+        .execute_field(users_with_posts(vec![("orderBy", enum_value("title_DESC"))]))
+        .unwrap();
+
+    assert_eq!(
+        result,
+        json!([
+            { "email": "a@prisma.io", "posts": [{ "title": "a1" }] },
+            { "email": "b@prisma.io", "posts": [{ "title": "b2" }, { "title": "b1" }] },
+        ])
+    );
+}
+
+pub fn nested_connects_move_existing_records(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &["a1", "a2"]).unwrap();
+
+    let data = object(vec![
+        ("email", string("b@prisma.io")),
+        (
+            "posts",
+            object(vec![(
+                "connect",
+                QueryValue::List(vec![object(vec![("title", string("a1"))])]),
+            )]),
+        ),
+    ]);
+
+    api.execute(Operation::Write(selection(
+        "createOneUser",
+        vec![("data", data)],
+        vec![field("email")],
+    )))
+    .unwrap();
+
+    let result = api.execute_field(users_with_posts(vec![])).unwrap();
+
+    assert_eq!(
+        result,
+        json!([
+            { "email": "a@prisma.io", "posts": [{ "title": "a2" }] },
+            { "email": "b@prisma.io", "posts": [{ "title": "a1" }] },
+        ])
+    );
+}
+
+pub fn nested_deletes_remove_related_records(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &["a1", "a2"]).unwrap();
+
+    let data = object(vec![(
+        "posts",
+        object(vec![(
+            "delete",
+            QueryValue::List(vec![object(vec![("title", string("a1"))])]),
+        )]),
+    )]);
+
+    api.execute(Operation::Write(selection(
+        "updateOneUser",
+        vec![
+            ("where", object(vec![("email", string("a@prisma.io"))])),
+            ("data", data),
+        ],
+        vec![field("email")],
+    )))
+    .unwrap();
+
+    let posts = api
+        .execute_field(Operation::Read(selection("findManyPost", vec![], vec![field("title")])))
+        .unwrap();
+
+    assert_eq!(posts, json!([{ "title": "a2" }]));
+}
+
+pub fn failing_nested_writes_roll_back_the_whole_mutation(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &["a1"]).unwrap();
+
+    // The user is created before its post violates the unique title.
+    assert!(api.create_user("b@prisma.io", None, &["a1"]).is_err());
+
+    let result = api.execute_field(users_with_posts(vec![])).unwrap();
+
+    assert_eq!(
+        result,
+        json!([
+            { "email": "a@prisma.io", "posts": [{ "title": "a1" }] },
+        ])
+    );
+}
+
+pub fn self_relation_nested_creates_link_both_sides(api: &TestApi) {
+    let data = object(vec![
+        ("email", string("a@prisma.io")),
+        (
+            "manager",
+            object(vec![("create", object(vec![("email", string("boss@prisma.io"))]))]),
+        ),
+        (
+            "mentor",
+            object(vec![("create", object(vec![("email", string("mentor@prisma.io"))]))]),
+        ),
+        (
+            "following",
+            object(vec![(
+                "create",
+                QueryValue::List(vec![object(vec![("email", string("f@prisma.io"))])]),
+            )]),
+        ),
+    ]);
+
+    api.execute(Operation::Write(selection(
+        "createOneUser",
+        vec![("data", data)],
+        vec![field("email")],
+    )))
+    .unwrap();
+
+    assert_eq!(
+        user_with_self_relations(api, "a@prisma.io"),
+        json!({
+            "email": "a@prisma.io",
+            "manager": { "email": "boss@prisma.io" },
+            "reports": [],
+            "mentee": null,
+            "mentor": { "email": "mentor@prisma.io" },
+            "following": [{ "email": "f@prisma.io" }],
+            "followers": [],
+        })
+    );
+
+    assert_eq!(
+        user_with_self_relations(api, "boss@prisma.io")["reports"],
+        json!([{ "email": "a@prisma.io" }])
+    );
+    assert_eq!(
+        user_with_self_relations(api, "mentor@prisma.io")["mentee"],
+        json!({ "email": "a@prisma.io" })
+    );
+    assert_eq!(
+        user_with_self_relations(api, "f@prisma.io")["followers"],
+        json!([{ "email": "a@prisma.io" }])
+    );
+}
+
+pub fn self_relation_nested_connects_link_existing_records(api: &TestApi) {
+    for email in &["a@prisma.io", "b@prisma.io", "c@prisma.io"] {
+        api.create_user(email, None, &[]).unwrap();
+    }
+
+    let data = object(vec![
+        (
+            "manager",
+            object(vec![("connect", object(vec![("email", string("b@prisma.io"))]))]),
+        ),
+        (
+            "mentee",
+            object(vec![("connect", object(vec![("email", string("c@prisma.io"))]))]),
+        ),
+        (
+            "followers",
+            object(vec![(
+                "connect",
+                QueryValue::List(vec![
+                    object(vec![("email", string("b@prisma.io"))]),
+                    object(vec![("email", string("c@prisma.io"))]),
+                ]),
+            )]),
+        ),
+    ]);
+
+    update_user(api, "a@prisma.io", data);
+
+    assert_eq!(
+        user_with_self_relations(api, "a@prisma.io"),
+        json!({
+            "email": "a@prisma.io",
+            "manager": { "email": "b@prisma.io" },
+            "reports": [],
+            "mentee": { "email": "c@prisma.io" },
+            "mentor": null,
+            "following": [],
+            "followers": [{ "email": "b@prisma.io" }, { "email": "c@prisma.io" }],
+        })
+    );
+
+    assert_eq!(
+        user_with_self_relations(api, "b@prisma.io"),
+        json!({
+            "email": "b@prisma.io",
+            "manager": null,
+            "reports": [{ "email": "a@prisma.io" }],
+            "mentee": null,
+            "mentor": null,
+            "following": [{ "email": "a@prisma.io" }],
+            "followers": [],
+        })
+    );
+
+    assert_eq!(
+        user_with_self_relations(api, "c@prisma.io")["mentor"],
+        json!({ "email": "a@prisma.io" })
+    );
+}
+
+pub fn self_relation_nested_disconnects_unlink_records(api: &TestApi) {
+    for email in &["a@prisma.io", "b@prisma.io", "c@prisma.io"] {
+        api.create_user(email, None, &[]).unwrap();
+    }
+
+    let connect = object(vec![
+        (
+            "manager",
+            object(vec![("connect", object(vec![("email", string("b@prisma.io"))]))]),
+        ),
+        (
+            "mentor",
+            object(vec![("connect", object(vec![("email", string("b@prisma.io"))]))]),
+        ),
+        (
+            "following",
+            object(vec![(
+                "connect",
+                QueryValue::List(vec![
+                    object(vec![("email", string("b@prisma.io"))]),
+                    object(vec![("email", string("c@prisma.io"))]),
+                ]),
+            )]),
+        ),
+    ]);
+
+    update_user(api, "a@prisma.io", connect);
+
+    let disconnect = object(vec![
+        ("manager", object(vec![("disconnect", QueryValue::Boolean(true))])),
+        ("mentor", object(vec![("disconnect", QueryValue::Boolean(true))])),
+        (
+            "following",
+            object(vec![(
+                "disconnect",
+                QueryValue::List(vec![object(vec![("email", string("b@prisma.io"))])]),
+            )]),
+        ),
+    ]);
+
+    update_user(api, "a@prisma.io", disconnect);
+
+    assert_eq!(
+        user_with_self_relations(api, "a@prisma.io"),
+        json!({
+            "email": "a@prisma.io",
+            "manager": null,
+            "reports": [],
+            "mentee": null,
+            "mentor": null,
+            "following": [{ "email": "c@prisma.io" }],
+            "followers": [],
+        })
+    );
+
+    assert_eq!(
+        user_with_self_relations(api, "b@prisma.io"),
+        json!({
+            "email": "b@prisma.io",
+            "manager": null,
+            "reports": [],
+            "mentee": null,
+            "mentor": null,
+            "following": [],
+            "followers": [],
+        })
+    );
+}
+
+pub fn compound_unique_nested_connects_and_disconnects(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &["a1"]).unwrap();
+    create_editor(api, "Ada", "Lovelace");
+    create_editor(api, "Grace", "Hopper");
+
+    let connect = object(vec![(
+        "editors",
+        object(vec![(
+            "connect",
+            QueryValue::List(vec![editor("Ada", "Lovelace"), editor("Grace", "Hopper")]),
+        )]),
+    )]);
+
+    update_post(api, "a1", connect);
+
+    assert_eq!(
+        post_editors(api, "a1"),
+        json!([{ "firstName": "Ada" }, { "firstName": "Grace" }])
+    );
+
+    let disconnect = object(vec![(
+        "editors",
+        object(vec![("disconnect", QueryValue::List(vec![editor("Grace", "Hopper")]))]),
+    )]);
+
+    update_post(api, "a1", disconnect);
+
+    assert_eq!(post_editors(api, "a1"), json!([{ "firstName": "Ada" }]));
+}
+
+pub fn compound_unique_nested_sets_replace_records(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &["a1"]).unwrap();
+    create_editor(api, "Ada", "Lovelace");
+    create_editor(api, "Grace", "Hopper");
+
+    let connect = object(vec![("editors", object(vec![("connect", editor("Ada", "Lovelace"))]))]);
+
+    update_post(api, "a1", connect);
+
+    let set = object(vec![(
+        "editors",
+        object(vec![("set", QueryValue::List(vec![editor("Grace", "Hopper")]))]),
+    )]);
+
+    update_post(api, "a1", set);
+
+    assert_eq!(post_editors(api, "a1"), json!([{ "firstName": "Grace" }]));
+}
+
+fn update_user(api: &TestApi, email: &str, data: QueryValue) {
+    api.execute(Operation::Write(selection(
+        "updateOneUser",
+        vec![("where", object(vec![("email", string(email))])), ("data", data)],
+        vec![field("email")],
+    )))
+    .unwrap();
+}
+
+/// The user with the users of its self relations.
+fn user_with_self_relations(api: &TestApi, email: &str) -> serde_json::Value {
+    let to_one = |name: &str| selection(name, vec![], vec![field("email")]);
+    let to_many = |name: &str| selection(name, vec![("orderBy", enum_value("email_ASC"))], vec![field("email")]);
+
+    api.execute_field(Operation::Read(selection(
+        "findOneUser",
+        vec![("where", object(vec![("email", string(email))]))],
+        vec![
+            field("email"),
+            to_one("manager"),
+            to_many("reports"),
+            to_one("mentee"),
+            to_one("mentor"),
+            to_many("following"),
+            to_many("followers"),
+        ],
+    )))
+    .unwrap()
+}
+
+fn create_editor(api: &TestApi, first_name: &str, last_name: &str) {
+    let data = object(vec![("firstName", string(first_name)), ("lastName", string(last_name))]);
+
+    api.execute(Operation::Write(selection(
+        "createOneEditor",
+        vec![("data", data)],
+        vec![field("firstName")],
+    )))
+    .unwrap();
+}
+
+/// The unique selector of an editor by the compound `@@unique([firstName, lastName])`.
+fn editor(first_name: &str, last_name: &str) -> QueryValue {
+    object(vec![(
+        "firstName_lastName",
+        object(vec![("firstName", string(first_name)), ("lastName", string(last_name))]),
+    )])
+}
+
+fn update_post(api: &TestApi, title: &str, data: QueryValue) {
+    api.execute(Operation::Write(selection(
+        "updateOnePost",
+        vec![("where", object(vec![("title", string(title))])), ("data", data)],
+        vec![field("title")],
+    )))
+    .unwrap();
+}
+
+/// The first names of the editors of the post, ordered by first name.
+fn post_editors(api: &TestApi, title: &str) -> serde_json::Value {
+    let post = api
+        .execute_field(Operation::Read(selection(
+            "findOnePost",
+            vec![("where", object(vec![("title", string(title))]))],
+            vec![selection(
+                "editors",
+                vec![("orderBy", enum_value("firstName_ASC"))],
+                vec![field("firstName")],
+            )],
+        )))
+        .unwrap();
+
+    post["editors"].clone()
+}
@@ -0,0 +1,147 @@
+use super::{emails, users_with_posts};
+use crate::test_api::*;
+use query_core::{Operation, QueryValue};
+use serde_json::json;
+
+fn seed(api: &TestApi) {
+    for email in &["a@prisma.io", "b@prisma.io", "c@prisma.io", "d@prisma.io"] {
+        api.create_user(email, None, &[]).unwrap();
+    }
+}
+
+fn cursor(email: &str) -> QueryValue {
+    object(vec![("email", string(email))])
+}
+
+pub fn first_and_skip_read_a_window_of_records(api: &TestApi) {
+    seed(api);
+
+    assert_eq!(
+        emails(api, vec![("first", QueryValue::Int(2))]),
+        vec!["a@prisma.io", "b@prisma.io"]
+    );
+    assert_eq!(
+        emails(api, vec![("skip", QueryValue::Int(1)), ("first", QueryValue::Int(2))]),
+        vec!["b@prisma.io", "c@prisma.io"]
+    );
+    assert_eq!(emails(api, vec![("skip", QueryValue::Int(4))]), Vec::<String>::new());
+}
+
+pub fn last_reads_the_last_records_in_order(api: &TestApi) {
+    seed(api);
+
+    assert_eq!(
+        emails(api, vec![("last", QueryValue::Int(2))]),
+        vec!["c@prisma.io", "d@prisma.io"]
+    );
+    assert_eq!(
+        emails(api, vec![("skip", QueryValue::Int(1)), ("last", QueryValue::Int(2))]),
+        vec!["b@prisma.io", "c@prisma.io"]
+    );
+}
+
+pub fn negative_take_reads_the_last_records(api: &TestApi) {
+    seed(api);
+
+    assert_eq!(
+        emails(api, vec![("take", QueryValue::Int(-2))]),
+        emails(api, vec![("last", QueryValue::Int(2))])
+    );
+    assert_eq!(
+        emails(api, vec![("take", QueryValue::Int(2))]),
+        emails(api, vec![("first", QueryValue::Int(2))])
+    );
+}
+
+pub fn cursors_exclude_the_cursor_record(api: &TestApi) {
+    seed(api);
+
+    assert_eq!(
+        emails(api, vec![("after", cursor("b@prisma.io"))]),
+        vec!["c@prisma.io", "d@prisma.io"]
+    );
+    assert_eq!(
+        emails(api, vec![("before", cursor("c@prisma.io"))]),
+        vec!["a@prisma.io", "b@prisma.io"]
+    );
+    assert_eq!(
+        emails(
+            api,
+            vec![("after", cursor("a@prisma.io")), ("before", cursor("d@prisma.io"))]
+        ),
+        vec!["b@prisma.io", "c@prisma.io"]
+    );
+}
+
+pub fn related_records_are_paginated_per_parent(api: &TestApi) {
+    api.create_user("a@prisma.io", None, &["a1", "a2", "a3"]).unwrap();
+    api.create_user("b@prisma.io", None, &["b1", "b2"]).unwrap();
+
+    let result = api
+        .execute_field(users_with_posts(vec![
+            ("orderBy", enum_value("title_ASC")),
+            ("last", QueryValue::Int(2)),
+        ]))
+        .unwrap();
+
+    assert_eq!(
+        result,
+        json!([
+            { "email": "a@prisma.io", "posts": [{ "title": "a2" }, { "title": "a3" }] },
+            { "email": "b@prisma.io", "posts": [{ "title": "b1" }, { "title": "b2" }] },
+        ])
+    );
+}
+
+/// Creates the user with posts of the given ids and titles, in the order given.
+fn create_user_with_posts(api: &TestApi, email: &str, posts: &[(&str, &str)]) {
+    let posts = posts
+        .iter()
+        .map(|(id, title)| object(vec![("id", string(id)), ("title", string(title))]))
+        .collect();
+
+    let data = object(vec![
+        ("email", string(email)),
+        ("posts", object(vec![("create", QueryValue::List(posts))])),
+    ]);
+
+    api.execute(Operation::Write(selection(
+        "createOneUser",
+        vec![("data", data)],
+        vec![field("id")],
+    )))
+    .unwrap();
+}
+
+/// Without an order, pages of related records are taken in the order of their ids, not of their insertion.
+pub fn related_records_without_an_order_are_paginated_by_id(api: &TestApi) {
+    create_user_with_posts(api, "a@prisma.io", &[("p3", "a-x"), ("p1", "a-y"), ("p2", "a-z")]);
+    create_user_with_posts(api, "b@prisma.io", &[("q2", "b-x"), ("q1", "b-y")]);
+
+    let first_page = api
+        .execute_field(users_with_posts(vec![("first", QueryValue::Int(2))]))
+        .unwrap();
+
+    assert_eq!(
+        first_page,
+        json!([
+            { "email": "a@prisma.io", "posts": [{ "title": "a-y" }, { "title": "a-z" }] },
+            { "email": "b@prisma.io", "posts": [{ "title": "b-y" }, { "title": "b-x" }] },
+        ])
+    );
+
+    let second_page = api
+        .execute_field(users_with_posts(vec![
+            ("skip", QueryValue::Int(1)),
+            ("first", QueryValue::Int(2)),
+        ]))
+        .unwrap();
+
+    assert_eq!(
+        second_page,
+        json!([
+            { "email": "a@prisma.io", "posts": [{ "title": "a-z" }, { "title": "a-x" }] },
+            { "email": "b@prisma.io", "posts": [{ "title": "b-x" }] },
+        ])
+    );
+}
@@ -0,0 +1,165 @@
+//! Relations referencing unique fields other than the primary id (`@relation(references: [..])`).
+
+use crate::{test_api::*, ConnectorFactory};
+use datamodel::RelationMode;
+use query_core::{Operation, QueryValue};
+use serde_json::json;
+
+/// The slugs are ordered the other way around than the ids, so linking by the wrong fields mixes up the posts.
+const DATAMODEL: &str = r#"
+    model Blog {
+        id    String @id
+        slug  String @unique
+        posts Post[]
+    }
+
+    model Post {
+        id    String @id
+        title String @unique
+        blog  Blog   @relation(references: [slug])
+    }
+"#;
+
+fn api(factory: &dyn ConnectorFactory) -> TestApi {
+    let api = TestApi::with_datamodel(factory, DATAMODEL, RelationMode::default());
+
+    create_blog(&api, "1", "b", &["b1", "b2", "b3"]);
+    create_blog(&api, "2", "a", &["a1", "a2"]);
+
+    api
+}
+
+fn create_blog(api: &TestApi, id: &str, slug: &str, titles: &[&str]) {
+    let posts: Vec<QueryValue> = titles
+        .iter()
+        .map(|title| object(vec![("id", string(title)), ("title", string(title))]))
+        .collect();
+
+    let data = object(vec![
+        ("id", string(id)),
+        ("slug", string(slug)),
+        ("posts", object(vec![("create", QueryValue::List(posts))])),
+    ]);
+
+    api.execute(Operation::Write(selection(
+        "createOneBlog",
+        vec![("data", data)],
+        vec![field("id")],
+    )))
+    .unwrap();
+}
+
+/// All blogs ordered by slug, with their posts read with the arguments.
+fn blogs_with_posts(api: &TestApi, mut post_arguments: Vec<(&str, QueryValue)>) -> serde_json::Value {
+    post_arguments.push(("orderBy", enum_value("title_ASC")));
+
+    api.execute_field(Operation::Read(selection(
+        "findManyBlog",
+        vec![("orderBy", enum_value("slug_ASC"))],
+        vec![field("slug"), selection("posts", post_arguments, vec![field("title")])],
+    )))
+    .unwrap()
+}
+
+pub fn related_records_are_read_by_the_referenced_fields(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    assert_eq!(
+        blogs_with_posts(&api, vec![]),
+        json!([
+            { "slug": "a", "posts": [{ "title": "a1" }, { "title": "a2" }] },
+            { "slug": "b", "posts": [{ "title": "b1" }, { "title": "b2" }, { "title": "b3" }] },
+        ])
+    );
+}
+
+pub fn paginated_related_records_are_read_by_the_referenced_fields(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    assert_eq!(
+        blogs_with_posts(&api, vec![("skip", QueryValue::Int(1)), ("first", QueryValue::Int(1))]),
+        json!([
+            { "slug": "a", "posts": [{ "title": "a2" }] },
+            { "slug": "b", "posts": [{ "title": "b2" }] },
+        ])
+    );
+}
+
+pub fn parents_are_read_by_the_referenced_fields(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    let posts = api
+        .execute_field(Operation::Read(selection(
+            "findManyPost",
+            vec![("orderBy", enum_value("title_ASC"))],
+            vec![field("title"), selection("blog", vec![], vec![field("slug")])],
+        )))
+        .unwrap();
+
+    assert_eq!(
+        posts,
+        json!([
+            { "title": "a1", "blog": { "slug": "a" } },
+            { "title": "a2", "blog": { "slug": "a" } },
+            { "title": "b1", "blog": { "slug": "b" } },
+            { "title": "b2", "blog": { "slug": "b" } },
+            { "title": "b3", "blog": { "slug": "b" } },
+        ])
+    );
+}
+
+/// Nested writes read the related records by the ids of the parent, which are resolved to the referenced fields.
+pub fn nested_deletes_read_the_referenced_fields_of_the_parent(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    let data = object(vec![(
+        "posts",
+        object(vec![(
+            "delete",
+            QueryValue::List(vec![object(vec![("title", string("b2"))])]),
+        )]),
+    )]);
+
+    api.execute(Operation::Write(selection(
+        "updateOneBlog",
+        vec![("where", object(vec![("slug", string("b"))])), ("data", data)],
+        vec![field("id")],
+    )))
+    .unwrap();
+
+    assert_eq!(
+        blogs_with_posts(&api, vec![]),
+        json!([
+            { "slug": "a", "posts": [{ "title": "a1" }, { "title": "a2" }] },
+            { "slug": "b", "posts": [{ "title": "b1" }, { "title": "b3" }] },
+        ])
+    );
+}
+
+/// Deleting a post of another blog through the blog fails, the post isn't related to it.
+pub fn nested_deletes_are_restricted_to_the_records_of_the_parent(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    let data = object(vec![(
+        "posts",
+        object(vec![(
+            "delete",
+            QueryValue::List(vec![object(vec![("title", string("a1"))])]),
+        )]),
+    )]);
+
+    let result = api.execute(Operation::Write(selection(
+        "updateOneBlog",
+        vec![("where", object(vec![("slug", string("b"))])), ("data", data)],
+        vec![field("id")],
+    )));
+
+    assert!(result.is_err());
+    assert_eq!(
+        blogs_with_posts(&api, vec![]),
+        json!([
+            { "slug": "a", "posts": [{ "title": "a1" }, { "title": "a2" }] },
+            { "slug": "b", "posts": [{ "title": "b1" }, { "title": "b2" }, { "title": "b3" }] },
+        ])
+    );
+}
@@ -0,0 +1,231 @@
+//! The referential actions the query engine executes in place of foreign keys (`relationMode = "prisma"`).
+
+use crate::{test_api::*, ConnectorFactory};
+use datamodel::RelationMode;
+use query_core::{CoreResult, Operation, QueryValue};
+use serde_json::json;
+
+const DATAMODEL: &str = r#"
+    model User {
+        id       String    @id
+        email    String    @unique
+        posts    Post[]    @relation(onDelete: CASCADE)
+        comments Comment[]
+        groups   Group[]
+    }
+
+    model Post {
+        id       String    @id
+        title    String
+        author   User
+        comments Comment[]
+    }
+
+    model Comment {
+        id     String @id
+        text   String
+        author User?
+        post   Post
+    }
+
+    model Group {
+        id      String @id
+        name    String
+        members User[]
+    }
+"#;
+
+fn api(factory: &dyn ConnectorFactory) -> TestApi {
+    TestApi::with_datamodel(factory, DATAMODEL, RelationMode::Prisma)
+}
+
+fn connect(id: &str) -> QueryValue {
+    object(vec![("connect", object(vec![("id", string(id))]))])
+}
+
+fn create(api: &TestApi, model: &str, data: Vec<(&str, QueryValue)>) {
+    api.execute(Operation::Write(selection(
+        &format!("createOne{}", model),
+        vec![("data", object(data))],
+        vec![field("id")],
+    )))
+    .unwrap();
+}
+
+fn create_user(api: &TestApi, id: &str) {
+    create(
+        api,
+        "User",
+        vec![("id", string(id)), ("email", string(&format!("{}@prisma.io", id)))],
+    );
+}
+
+fn create_post(api: &TestApi, id: &str, author: &str) {
+    create(
+        api,
+        "Post",
+        vec![("id", string(id)), ("title", string(id)), ("author", connect(author))],
+    );
+}
+
+fn create_comment(api: &TestApi, id: &str, post: &str, author: Option<&str>) {
+    let mut data = vec![("id", string(id)), ("text", string(id)), ("post", connect(post))];
+
+    if let Some(author) = author {
+        data.push(("author", connect(author)));
+    }
+
+    create(api, "Comment", data);
+}
+
+fn delete(api: &TestApi, model: &str, id: &str) -> CoreResult<serde_json::Value> {
+    api.execute(Operation::Write(selection(
+        &format!("deleteOne{}", model),
+        vec![("where", object(vec![("id", string(id))]))],
+        vec![field("id")],
+    )))
+}
+
+fn update_user(api: &TestApi, id: &str, data: Vec<(&str, QueryValue)>) -> CoreResult<serde_json::Value> {
+    api.execute(Operation::Write(selection(
+        "updateOneUser",
+        vec![("where", object(vec![("id", string(id))])), ("data", object(data))],
+        vec![field("id")],
+    )))
+}
+
+/// The ids of all records of the model, ordered by id.
+fn ids(api: &TestApi, model: &str) -> serde_json::Value {
+    api.execute_field(Operation::Read(selection(
+        &format!("findMany{}", model),
+        vec![("orderBy", enum_value("id_ASC"))],
+        vec![field("id")],
+    )))
+    .unwrap()
+}
+
+pub fn deletes_cascade_to_the_children(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    create_user(&api, "a");
+    create_user(&api, "b");
+    create_post(&api, "a1", "a");
+    create_post(&api, "a2", "a");
+    create_post(&api, "b1", "b");
+
+    delete(&api, "User", "a").unwrap();
+
+    assert_eq!(ids(&api, "User"), json!([{ "id": "b" }]));
+    assert_eq!(ids(&api, "Post"), json!([{ "id": "b1" }]));
+}
+
+pub fn cascades_are_restricted_by_required_relations_of_the_children(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    create_user(&api, "a");
+    create_post(&api, "a1", "a");
+    create_comment(&api, "c1", "a1", None);
+
+    assert!(delete(&api, "User", "a").is_err());
+
+    assert_eq!(ids(&api, "User"), json!([{ "id": "a" }]));
+    assert_eq!(ids(&api, "Post"), json!([{ "id": "a1" }]));
+}
+
+pub fn deletes_null_out_optional_references(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    create_user(&api, "a");
+    create_user(&api, "b");
+    create_post(&api, "b1", "b");
+    create_comment(&api, "c1", "b1", Some("a"));
+
+    delete(&api, "User", "a").unwrap();
+
+    let comments = api
+        .execute_field(Operation::Read(selection(
+            "findManyComment",
+            vec![],
+            vec![field("id"), selection("author", vec![], vec![field("id")])],
+        )))
+        .unwrap();
+
+    assert_eq!(comments, json!([{ "id": "c1", "author": null }]));
+}
+
+pub fn deletes_are_restricted_by_required_references(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    create_user(&api, "a");
+    create_post(&api, "a1", "a");
+    create_comment(&api, "c1", "a1", None);
+
+    assert!(delete(&api, "Post", "a1").is_err());
+
+    assert_eq!(ids(&api, "Post"), json!([{ "id": "a1" }]));
+    assert_eq!(ids(&api, "Comment"), json!([{ "id": "c1" }]));
+}
+
+pub fn deletes_remove_the_rows_of_relation_tables(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    create_user(&api, "a");
+    create_user(&api, "b");
+    create(
+        &api,
+        "Group",
+        vec![
+            ("id", string("g")),
+            ("name", string("g")),
+            (
+                "members",
+                object(vec![(
+                    "connect",
+                    QueryValue::List(vec![
+                        object(vec![("id", string("a"))]),
+                        object(vec![("id", string("b"))]),
+                    ]),
+                )]),
+            ),
+        ],
+    );
+
+    delete(&api, "User", "a").unwrap();
+
+    let groups = api
+        .execute_field(Operation::Read(selection(
+            "findManyGroup",
+            vec![],
+            vec![
+                field("id"),
+                selection("members", vec![("orderBy", enum_value("id_ASC"))], vec![field("id")]),
+            ],
+        )))
+        .unwrap();
+
+    assert_eq!(groups, json!([{ "id": "g", "members": [{ "id": "b" }] }]));
+}
+
+pub fn updates_of_referenced_ids_are_restricted(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    create_user(&api, "a");
+    create_post(&api, "a1", "a");
+
+    assert!(update_user(&api, "a", vec![("id", string("z"))]).is_err());
+
+    assert_eq!(ids(&api, "User"), json!([{ "id": "a" }]));
+}
+
+pub fn updates_of_unreferenced_records_are_not_restricted(factory: &dyn ConnectorFactory) {
+    let api = api(factory);
+
+    create_user(&api, "a");
+    create_user(&api, "b");
+    create_post(&api, "a1", "a");
+
+    update_user(&api, "a", vec![("email", string("z@prisma.io"))]).unwrap();
+    update_user(&api, "b", vec![("id", string("z"))]).unwrap();
+
+    assert_eq!(ids(&api, "User"), json!([{ "id": "a" }, { "id": "z" }]));
+}
@@ -0,0 +1,125 @@
+use crate::{suites::DATAMODEL, ConnectorFactory};
+use connector_interface::Connector;
+use datamodel::RelationMode;
+use query_core::{
+    BuildMode, CoreResult, InterpretingExecutor, Operation, QueryDocument, QueryExecutor, QueryLimits,
+    QuerySchemaBuilder, QuerySchemaRef, QueryValue, Selection,
+};
+use std::{cell::RefCell, collections::BTreeMap, sync::Arc};
+use tokio::runtime::{Builder, Runtime};
+
+/// Executes operations against a connector of a factory, with the query schema of the data model of the suites.
+pub struct TestApi {
+    executor: InterpretingExecutor<Box<dyn Connector + Send + Sync>>,
+    query_schema: QuerySchemaRef,
+    /// Connection pools are bound to the runtime they are created on, so the connector is built and all queries
+    /// are executed on this one.
+    runtime: RefCell<Runtime>,
+}
+
+impl TestApi {
+    pub fn new(factory: &dyn ConnectorFactory) -> Self {
+        Self::with_datamodel(factory, DATAMODEL, RelationMode::default())
+    }
+
+    /// Executes operations against the tables of another data model, with the referential integrity of the relation
+    /// mode.
+    pub fn with_datamodel(factory: &dyn ConnectorFactory, datamodel: &str, relation_mode: RelationMode) -> Self {
+        let lifted_datamodel = datamodel::parse_datamodel(datamodel).unwrap();
+        let mut template = prisma_models::DatamodelConverter::convert(&lifted_datamodel);
+        template.relation_mode = relation_mode;
+
+        let internal_datamodel = template.build(factory.db_name().to_owned());
+        let query_schema =
+            QuerySchemaBuilder::new(&internal_datamodel, &factory.capabilities(), BuildMode::Modern, false).build();
+
+        let mut runtime = Builder::new().basic_scheduler().enable_all().build().unwrap();
+        let connector = runtime.block_on(factory.connector(&lifted_datamodel, relation_mode));
+
+        let executor = InterpretingExecutor::new(connector, factory.name(), false, QueryLimits::default());
+
+        Self {
+            executor,
+            query_schema: Arc::new(query_schema),
+            runtime: RefCell::new(runtime),
+        }
+    }
+
+    /// The response to the operation, as it would be serialized for the client.
+    pub fn execute(&self, operation: Operation) -> CoreResult<serde_json::Value> {
+        let query_doc = QueryDocument {
+            operations: vec![operation],
+        };
+
+        let responses = self
+            .runtime
+            .borrow_mut()
+            .block_on(self.executor.execute(query_doc, Arc::clone(&self.query_schema)))?;
+
+        Ok(serde_json::to_value(&responses).unwrap())
+    }
+
+    /// The value of the field of the single operation in the response data.
+    pub fn execute_field(&self, operation: Operation) -> CoreResult<serde_json::Value> {
+        let name = match &operation {
+            Operation::Read(selection) | Operation::Write(selection) => selection.name.clone(),
+        };
+
+        let mut response = self.execute(operation)?;
+
+        Ok(response["data"][name.as_str()].take())
+    }
+
+    /// Creates a user with a post for each of the titles.
+    pub fn create_user(&self, email: &str, age: Option<i64>, titles: &[&str]) -> CoreResult<serde_json::Value> {
+        let posts: Vec<QueryValue> = titles
+            .iter()
+            .map(|title| object(vec![("title", string(title))]))
+            .collect();
+
+        let data = object(vec![
+            ("email", string(email)),
+            ("age", age.map(QueryValue::Int).unwrap_or(QueryValue::Null)),
+            ("posts", object(vec![("create", QueryValue::List(posts))])),
+        ]);
+
+        self.execute(Operation::Write(selection(
+            "createOneUser",
+            vec![("data", data)],
+            vec![field("email")],
+        )))
+    }
+}
+
+pub fn selection(name: &str, arguments: Vec<(&str, QueryValue)>, nested_selections: Vec<Selection>) -> Selection {
+    Selection {
+        name: name.to_owned(),
+        alias: None,
+        arguments: arguments
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value))
+            .collect(),
+        nested_selections,
+    }
+}
+
+pub fn field(name: &str) -> Selection {
+    selection(name, vec![], vec![])
+}
+
+pub fn object(fields: Vec<(&str, QueryValue)>) -> QueryValue {
+    QueryValue::Object(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+pub fn string(value: &str) -> QueryValue {
+    QueryValue::String(value.to_owned())
+}
+
+pub fn enum_value(value: &str) -> QueryValue {
+    QueryValue::Enum(value.to_owned())
+}
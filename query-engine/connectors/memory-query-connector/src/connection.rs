@@ -0,0 +1,178 @@
+use crate::store::Store;
+use connector_interface::{
+    error::{ConnectorError, ErrorKind},
+    filter::Filter,
+    Connection, IdempotencyRecord, QueryArguments, ReadOperations, Routine, RoutineResult, Transaction, WriteArgs,
+    WriteOperations, IO,
+};
+use prisma_models::prelude::*;
+use std::sync::{Mutex, MutexGuard};
+
+/// A connection to the records of a `MemoryConnector`, which is also used for its transactions. Transactions
+/// write to the records directly and restore the records of the start of the transaction on rollback, they are
+/// not isolated from other connections.
+pub struct MemoryConnection<'a> {
+    store: &'a Mutex<Store>,
+    /// The records at the start of the transaction, `None` outside of transactions.
+    snapshot: Mutex<Option<Store>>,
+}
+
+impl<'a> MemoryConnection<'a> {
+    pub(crate) fn new(store: &'a Mutex<Store>) -> Self {
+        Self {
+            store,
+            snapshot: Mutex::new(None),
+        }
+    }
+
+    fn store(&self) -> MutexGuard<'a, Store> {
+        self.store.lock().unwrap()
+    }
+}
+
+impl<'conn> Connection for MemoryConnection<'conn> {
+    fn start_transaction<'a>(&'a self) -> IO<'a, Box<dyn Transaction<'a> + 'a>> {
+        IO::new(async move {
+            let tx: MemoryConnection<'a> = MemoryConnection {
+                store: self.store,
+                snapshot: Mutex::new(Some(self.store().clone())),
+            };
+
+            Ok(Box::new(tx) as Box<dyn Transaction<'a> + 'a>)
+        })
+    }
+}
+
+impl<'a> Transaction<'a> for MemoryConnection<'a> {
+    fn commit<'b>(&'b self) -> IO<'b, ()> {
+        IO::new(async move {
+            self.snapshot.lock().unwrap().take();
+            Ok(())
+        })
+    }
+
+    fn rollback<'b>(&'b self) -> IO<'b, ()> {
+        IO::new(async move {
+            if let Some(snapshot) = self.snapshot.lock().unwrap().take() {
+                *self.store() = snapshot;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl<'a> ReadOperations for MemoryConnection<'a> {
+    fn get_single_record<'b>(
+        &'b self,
+        model: &'b ModelRef,
+        filter: &'b Filter,
+        selected_fields: &'b SelectedFields,
+    ) -> IO<'b, Option<SingleRecord>> {
+        IO::new(async move { Ok(self.store().get_single_record(model, filter, selected_fields)) })
+    }
+
+    fn get_many_records<'b>(
+        &'b self,
+        model: &'b ModelRef,
+        query_arguments: QueryArguments,
+        selected_fields: &'b SelectedFields,
+    ) -> IO<'b, ManyRecords> {
+        IO::new(async move { Ok(self.store().get_many_records(model, &query_arguments, selected_fields)) })
+    }
+
+    fn get_related_records<'b>(
+        &'b self,
+        from_field: &'b RelationFieldRef,
+        from_record_ids: &'b [RecordIdentifier],
+        query_arguments: QueryArguments,
+        selected_fields: &'b SelectedFields,
+    ) -> IO<'b, ManyRecords> {
+        IO::new(async move {
+            Ok(self
+                .store()
+                .get_related_records(from_field, from_record_ids, &query_arguments, selected_fields))
+        })
+    }
+
+    fn count_by_model<'b>(&'b self, model: &'b ModelRef, query_arguments: QueryArguments) -> IO<'b, usize> {
+        IO::new(async move { Ok(self.store().count_by_model(model, &query_arguments)) })
+    }
+
+    fn list_routines<'b>(&'b self) -> IO<'b, Vec<Routine>> {
+        IO::new(async move { Ok(Vec::new()) })
+    }
+
+    fn get_idempotency_record<'b>(&'b self, key: &'b str) -> IO<'b, Option<IdempotencyRecord>> {
+        IO::new(async move {
+            Ok(self
+                .store()
+                .idempotency_records
+                .iter()
+                .find(|record| record.key == key)
+                .cloned())
+        })
+    }
+}
+
+impl<'a> WriteOperations for MemoryConnection<'a> {
+    fn create_record<'b>(&'b self, model: &'b ModelRef, args: WriteArgs) -> IO<RecordIdentifier> {
+        IO::new(async move { self.store().create_record(model, args) })
+    }
+
+    fn update_records<'b>(&'b self, model: &'b ModelRef, where_: Filter, args: WriteArgs) -> IO<Vec<RecordIdentifier>> {
+        IO::new(async move { self.store().update_records(model, &where_, args) })
+    }
+
+    fn delete_records<'b>(&'b self, model: &'b ModelRef, where_: Filter) -> IO<usize> {
+        IO::new(async move { Ok(self.store().delete_records(model, &where_)) })
+    }
+
+    fn connect<'b>(
+        &'b self,
+        field: &'b RelationFieldRef,
+        parent_id: &'b RecordIdentifier,
+        child_ids: &'b [RecordIdentifier],
+    ) -> IO<()> {
+        IO::new(async move {
+            self.store().connect(field, parent_id, child_ids);
+            Ok(())
+        })
+    }
+
+    fn disconnect<'b>(
+        &'b self,
+        field: &'b RelationFieldRef,
+        parent_id: &'b RecordIdentifier,
+        child_ids: &'b [RecordIdentifier],
+    ) -> IO<()> {
+        IO::new(async move {
+            self.store().disconnect(field, parent_id, child_ids);
+            Ok(())
+        })
+    }
+
+    fn disconnect_all<'b>(&'b self, field: &'b RelationFieldRef, parent_ids: &'b [RecordIdentifier]) -> IO<()> {
+        IO::new(async move {
+            self.store().disconnect_all(field, parent_ids);
+            Ok(())
+        })
+    }
+
+    fn execute_raw<'b>(&'b self, _query: String, _parameters: Vec<PrismaValue>) -> IO<serde_json::Value> {
+        IO::new(async move { Err(unsupported("Raw queries")) })
+    }
+
+    fn call_routine<'b>(&'b self, _routine: &'b Routine, _arguments: Vec<PrismaValue>) -> IO<RoutineResult> {
+        IO::new(async move { Err(unsupported("Routines")) })
+    }
+
+    fn store_idempotency_record<'b>(&'b self, record: IdempotencyRecord) -> IO<()> {
+        IO::new(async move { self.store().store_idempotency_record(record) })
+    }
+}
+
+fn unsupported(operation: &str) -> ConnectorError {
+    let message = format!("{} are not supported by the in-memory connector.", operation);
+    ConnectorError::from_kind(ErrorKind::QueryError(message.into()))
+}
@@ -0,0 +1,142 @@
+use crate::{
+    store::{self, Row, Store},
+    value,
+};
+use connector_interface::filter::*;
+use prisma_models::*;
+use std::cmp::Ordering;
+
+impl Store {
+    /// Evaluates the filter on a record of the model like a `WHERE` condition, `None` if the result is unknown
+    /// because of a comparison with null. Only records with a result of `Some(true)` match.
+    pub(crate) fn evaluate(&self, row: &Row, filter: &Filter) -> Option<bool> {
+        match filter {
+            Filter::And(filters) => filters.iter().fold(Some(true), |acc, filter| match acc {
+                Some(false) => acc,
+                _ => and(acc, self.evaluate(row, filter)),
+            }),
+            Filter::Or(filters) => filters.iter().fold(Some(false), |acc, filter| match acc {
+                Some(true) => acc,
+                _ => or(acc, self.evaluate(row, filter)),
+            }),
+            Filter::Not(filters) => filters.iter().fold(Some(true), |acc, filter| match acc {
+                Some(false) => acc,
+                _ => and(acc, self.evaluate(row, filter).map(|result| !result)),
+            }),
+            Filter::Scalar(filter) => evaluate_scalar(store::get(row, &filter.field.name), &filter.condition),
+            Filter::ScalarList(filter) => evaluate_scalar_list(
+                store::get(row, &filter.field.data_source_field().name),
+                &filter.condition,
+            ),
+            Filter::OneRelationIsNull(filter) => {
+                if filter.field.relation_is_inlined_in_parent() {
+                    Some(filter.field.db_names().all(|name| store::get(row, name).is_null()))
+                } else {
+                    Some(self.related_rows(&filter.field, row).is_empty())
+                }
+            }
+            Filter::Relation(filter) => {
+                let related = self.related_rows(&filter.field, row);
+                let matching = |expected: bool| {
+                    related
+                        .iter()
+                        .any(|related| self.evaluate(related, &filter.nested_filter) == Some(expected))
+                };
+
+                match filter.condition {
+                    RelationCondition::EveryRelatedRecord => Some(!matching(false)),
+                    RelationCondition::AtLeastOneRelatedRecord => Some(matching(true)),
+                    RelationCondition::NoRelatedRecord => Some(!matching(true)),
+                    RelationCondition::ToOneRelatedRecord => Some(matching(true)),
+                }
+            }
+            Filter::BoolFilter(b) => Some(*b),
+            Filter::Empty => Some(true),
+            Filter::NodeSubscription => unreachable!("Node subscription filters are never built by the query engine."),
+        }
+    }
+
+    /// The records of the table matching the filter.
+    pub(crate) fn filter<'a>(&self, rows: Vec<&'a Row>, filter: Option<&Filter>) -> Vec<&'a Row> {
+        match filter {
+            Some(filter) => rows
+                .into_iter()
+                .filter(|row| self.evaluate(row, filter) == Some(true))
+                .collect(),
+            None => rows,
+        }
+    }
+}
+
+fn and(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+    match (left, right) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+fn or(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+    match (left, right) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+fn evaluate_scalar(value: &PrismaValue, condition: &ScalarCondition) -> Option<bool> {
+    let ordering = |other: &PrismaValue, expected: &[Ordering]| {
+        value::compare(value, other).map(|ordering| expected.contains(&ordering))
+    };
+
+    let pattern = |other: &PrismaValue, matches: fn(&str, &str) -> bool| {
+        let text = value::text(value)?;
+        let pattern = value::text(other)?;
+
+        Some(matches(&text, &pattern))
+    };
+
+    let is_in = |values: &[PrismaValue]| {
+        if value.is_null() {
+            None
+        } else {
+            Some(values.iter().any(|other| value::equals(value, other) == Some(true)))
+        }
+    };
+
+    match condition {
+        ScalarCondition::Equals(PrismaValue::Null) => Some(value.is_null()),
+        ScalarCondition::NotEquals(PrismaValue::Null) => Some(!value.is_null()),
+        ScalarCondition::Equals(other) => value::equals(value, other),
+        ScalarCondition::NotEquals(other) => value::equals(value, other).map(|result| !result),
+        ScalarCondition::Contains(other) => pattern(other, |text, pattern| text.contains(pattern)),
+        ScalarCondition::NotContains(other) => pattern(other, |text, pattern| !text.contains(pattern)),
+        ScalarCondition::StartsWith(other) => pattern(other, |text, pattern| text.starts_with(pattern)),
+        ScalarCondition::NotStartsWith(other) => pattern(other, |text, pattern| !text.starts_with(pattern)),
+        ScalarCondition::EndsWith(other) => pattern(other, |text, pattern| text.ends_with(pattern)),
+        ScalarCondition::NotEndsWith(other) => pattern(other, |text, pattern| !text.ends_with(pattern)),
+        ScalarCondition::LessThan(other) => ordering(other, &[Ordering::Less]),
+        ScalarCondition::LessThanOrEquals(other) => ordering(other, &[Ordering::Less, Ordering::Equal]),
+        ScalarCondition::GreaterThan(other) => ordering(other, &[Ordering::Greater]),
+        ScalarCondition::GreaterThanOrEquals(other) => ordering(other, &[Ordering::Greater, Ordering::Equal]),
+        ScalarCondition::In(values) => is_in(values),
+        ScalarCondition::NotIn(values) => is_in(values).map(|result| !result),
+    }
+}
+
+fn evaluate_scalar_list(value: &PrismaValue, condition: &ScalarListCondition) -> Option<bool> {
+    let list = match value {
+        PrismaValue::List(list) => list.as_slice(),
+        PrismaValue::Null => &[],
+        _ => return None,
+    };
+
+    let contains = |other: &PrismaValue| list.iter().any(|element| value::equals(element, other) == Some(true));
+
+    match condition {
+        ScalarListCondition::Contains(other) => Some(contains(other)),
+        ScalarListCondition::ContainsEvery(others) => Some(others.iter().all(contains)),
+        ScalarListCondition::ContainsSome(others) => Some(others.iter().any(contains)),
+        ScalarListCondition::ContainsNone => Some(list.is_empty()),
+    }
+}
@@ -0,0 +1,41 @@
+//! A connector keeping all records in memory, for tests of the query core that don't need a database.
+//!
+//! Records are stored as maps from data source field names to values, one table per model and relation table.
+//! Filters, ordering and pagination follow the semantics of the SQL connectors, with the comparisons of `NULL`
+//! values evaluating to neither true nor false.
+
+mod connection;
+mod filter;
+mod read;
+mod store;
+mod value;
+mod write;
+
+pub use connection::MemoryConnection;
+
+use connector_interface::{Connection, Connector, PoolState, IO};
+use std::sync::Mutex;
+use store::Store;
+
+/// The connector, holding the records of all of its connections.
+#[derive(Debug, Default)]
+pub struct MemoryConnector {
+    store: Mutex<Store>,
+}
+
+impl MemoryConnector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Connector for MemoryConnector {
+    fn get_connection<'a>(&'a self) -> IO<Box<dyn Connection + 'a>> {
+        IO::new(async move { Ok(Box::new(MemoryConnection::new(&self.store)) as Box<dyn Connection + 'a>) })
+    }
+
+    /// There is no pool, connections are always available.
+    fn pool_state<'a>(&'a self) -> IO<'a, PoolState> {
+        IO::new(async move { Ok(PoolState { in_use: 0, capacity: 1 }) })
+    }
+}
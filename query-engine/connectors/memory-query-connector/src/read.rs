@@ -0,0 +1,209 @@
+use crate::{
+    store::{self, Row, Store},
+    value,
+};
+use connector_interface::*;
+use prisma_models::*;
+use std::cmp::Ordering;
+
+impl Store {
+    pub(crate) fn get_single_record(
+        &self,
+        model: &ModelRef,
+        filter: &Filter,
+        selected_fields: &SelectedFields,
+    ) -> Option<SingleRecord> {
+        let field_names: Vec<String> = selected_fields.db_names().map(String::from).collect();
+
+        self.rows(model.db_name())
+            .iter()
+            .find(|row| self.evaluate(row, filter) == Some(true))
+            .map(|row| SingleRecord::new(record(row, &field_names), field_names.clone()))
+    }
+
+    pub(crate) fn get_many_records(
+        &self,
+        model: &ModelRef,
+        query_arguments: &QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ManyRecords {
+        let field_names: Vec<String> = selected_fields.db_names().map(String::from).collect();
+        let rows = self.rows(model.db_name()).iter().collect();
+
+        let records = self
+            .apply_arguments(model, rows, query_arguments)
+            .into_iter()
+            .map(|row| record(row, &field_names))
+            .collect();
+
+        ManyRecords { records, field_names }
+    }
+
+    /// Reads the related records of every parent separately, so the query arguments paginate the related records
+    /// of each parent. Like in the SQL connectors, the values of the fields linking the parent are appended to the
+    /// values of every record.
+    pub(crate) fn get_related_records(
+        &self,
+        from_field: &RelationFieldRef,
+        from_record_ids: &[RecordIdentifier],
+        query_arguments: &QueryArguments,
+        selected_fields: &SelectedFields,
+    ) -> ManyRecords {
+        let parent_model = from_field.model();
+        let related_model = from_field.related_model();
+
+        let parent_link_fields = if from_field.relation_is_inlined_in_parent() {
+            parent_model.primary_identifier()
+        } else {
+            from_field.linking_fields()
+        };
+
+        let field_names: Vec<String> = selected_fields.db_names().map(String::from).collect();
+        let mut records = Vec::new();
+
+        let parents = self
+            .rows(parent_model.db_name())
+            .iter()
+            .filter(|row| from_record_ids.iter().any(|id| store::is_identified_by(row, id)));
+
+        for parent in parents {
+            let parent_id = store::identifier(parent, &parent_link_fields);
+            let related = self.related_rows(from_field, parent);
+
+            for row in self.apply_arguments(&related_model, related, query_arguments) {
+                let mut record = record(row, &field_names);
+
+                record.values.extend(parent_id.values());
+                record.set_parent_id(parent_id.clone());
+                records.push(record);
+            }
+        }
+
+        ManyRecords { records, field_names }
+    }
+
+    pub(crate) fn count_by_model(&self, model: &ModelRef, query_arguments: &QueryArguments) -> usize {
+        let rows = self.rows(model.db_name()).iter().collect();
+        self.apply_arguments(model, rows, query_arguments).len()
+    }
+
+    /// Filters, orders and paginates the records. Reads of the `last` records return them in the requested
+    /// order, with the record read in excess of the page first.
+    fn apply_arguments<'a>(
+        &self,
+        model: &ModelRef,
+        rows: Vec<&'a Row>,
+        query_arguments: &QueryArguments,
+    ) -> Vec<&'a Row> {
+        let mut rows = self.filter(rows, query_arguments.filter.as_ref());
+        let order_fields = order_fields(model, query_arguments);
+
+        if let Some(ref after) = query_arguments.after {
+            rows = self.after_cursor(model, rows, after, &order_fields, Ordering::Greater);
+        }
+
+        if let Some(ref before) = query_arguments.before {
+            rows = self.after_cursor(model, rows, before, &order_fields, Ordering::Less);
+        }
+
+        let directions = query_arguments.ordering_directions();
+
+        if directions.needs_implicit_id_ordering {
+            let id_fields = store::names(&model.primary_identifier())
+                .into_iter()
+                .map(|name| (name, SortOrder::Ascending));
+
+            let sort_fields: Vec<(String, SortOrder)> = order_fields.iter().cloned().chain(id_fields).collect();
+
+            rows.sort_by(|left, right| {
+                sort_fields
+                    .iter()
+                    .map(|(name, sort_order)| {
+                        let ordering = value::sort_order(store::get(left, name), store::get(right, name));
+
+                        match sort_order {
+                            SortOrder::Ascending => ordering,
+                            SortOrder::Descending => ordering.reverse(),
+                        }
+                    })
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+
+        if directions.needs_to_be_reverse_order {
+            rows.reverse();
+        }
+
+        let SkipAndLimit { skip, limit } = query_arguments.skip_and_limit();
+        let mut rows: Vec<&Row> = rows
+            .into_iter()
+            .skip(skip)
+            .take(limit.unwrap_or(usize::max_value()))
+            .collect();
+
+        if directions.needs_to_be_reverse_order {
+            rows.reverse();
+        }
+
+        rows
+    }
+
+    /// The records after the cursor in the order of the read, or before it with an `expected` ordering of `Less`.
+    /// Records with the same value of the order field as the cursor are compared by the fields of the cursor.
+    fn after_cursor<'a>(
+        &self,
+        model: &ModelRef,
+        rows: Vec<&'a Row>,
+        cursor: &[(ScalarFieldRef, PrismaValue)],
+        order_fields: &[(String, SortOrder)],
+        expected: Ordering,
+    ) -> Vec<&'a Row> {
+        let cursor_names: Vec<String> = cursor.iter().map(|(field, _)| field.db_name().to_owned()).collect();
+        let cursor_values: Vec<&PrismaValue> = cursor.iter().map(|(_, value)| value).collect();
+
+        let cursor_row = self
+            .rows(model.db_name())
+            .iter()
+            .find(|row| store::all_equal(&store::values(row, &cursor_names), &cursor_values));
+
+        let cursor_row = match cursor_row {
+            Some(row) => row,
+            None => return Vec::new(),
+        };
+
+        let (order_names, sort_orders): (Vec<String>, Vec<SortOrder>) = order_fields.iter().cloned().unzip();
+        let cursor_order_values = store::values(cursor_row, &order_names);
+
+        let beyond_cursor = match sort_orders.first() {
+            Some(SortOrder::Descending) => expected.reverse(),
+            _ => expected,
+        };
+
+        rows.into_iter()
+            .filter(
+                |row| match value::compare_all(&store::values(row, &order_names), &cursor_order_values) {
+                    Some(Ordering::Equal) => {
+                        value::compare_all(&store::values(row, &cursor_names), &cursor_values) == Some(expected)
+                    }
+                    ordering => ordering == Some(beyond_cursor),
+                },
+            )
+            .collect()
+    }
+}
+
+/// The fields the records are ordered by, the id of the model without an explicit order.
+fn order_fields(model: &ModelRef, query_arguments: &QueryArguments) -> Vec<(String, SortOrder)> {
+    match query_arguments.order_by {
+        Some(ref order_by) => vec![(order_by.field.db_name().to_owned(), order_by.sort_order)],
+        None => store::names(&model.primary_identifier())
+            .into_iter()
+            .map(|name| (name, SortOrder::Ascending))
+            .collect(),
+    }
+}
+
+fn record(row: &Row, field_names: &[String]) -> Record {
+    Record::new(store::values(row, field_names).into_iter().cloned().collect())
+}
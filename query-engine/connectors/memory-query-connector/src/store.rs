@@ -0,0 +1,157 @@
+use crate::value;
+use connector_interface::IdempotencyRecord;
+use prisma_models::*;
+use std::collections::HashMap;
+
+/// A stored record, by data source field name. Fields without a value are null.
+pub(crate) type Row = HashMap<String, PrismaValue>;
+
+static NULL: PrismaValue = PrismaValue::Null;
+
+/// The records of all models and relation tables, by table name.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Store {
+    tables: HashMap<String, Vec<Row>>,
+    pub(crate) idempotency_records: Vec<IdempotencyRecord>,
+}
+
+impl Store {
+    pub(crate) fn rows(&self, table: &str) -> &[Row] {
+        self.tables.get(table).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub(crate) fn rows_mut(&mut self, table: &str) -> &mut Vec<Row> {
+        self.tables.entry(table.to_owned()).or_insert_with(Vec::new)
+    }
+
+    /// The records of the model of the relation field linked to the given parent record of the field.
+    pub(crate) fn related_rows(&self, field: &RelationFieldRef, parent: &Row) -> Vec<&Row> {
+        let related_table = self.rows(field.related_model().db_name());
+
+        match Link::of(field) {
+            Link::Inline {
+                parent_fields,
+                child_fields,
+            } => {
+                let parent_values = values(parent, &parent_fields);
+
+                if parent_values.iter().any(|value| value.is_null()) {
+                    return Vec::new();
+                }
+
+                related_table
+                    .iter()
+                    .filter(|row| all_equal(&values(row, &child_fields), &parent_values))
+                    .collect()
+            }
+            Link::Table {
+                table,
+                parent_column,
+                child_column,
+                parent_id,
+                child_id,
+            } => {
+                let parent_id = get(parent, &parent_id);
+
+                let child_ids: Vec<&PrismaValue> = self
+                    .rows(&table)
+                    .iter()
+                    .filter(|link| value::equals(get(link, &parent_column), parent_id) == Some(true))
+                    .map(|link| get(link, &child_column))
+                    .collect();
+
+                related_table
+                    .iter()
+                    .filter(|row| {
+                        child_ids
+                            .iter()
+                            .any(|id| value::equals(get(row, &child_id), id) == Some(true))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// How the records of a relation are linked, seen from one of its fields. Inline relations link the parent and
+/// the related records by equal values of their fields, relation tables link the ids of both in rows of their own.
+pub(crate) enum Link {
+    Inline {
+        parent_fields: Vec<String>,
+        child_fields: Vec<String>,
+    },
+    Table {
+        table: String,
+        parent_column: String,
+        child_column: String,
+        parent_id: String,
+        child_id: String,
+    },
+}
+
+impl Link {
+    pub(crate) fn of(field: &RelationFieldRef) -> Self {
+        let relation = field.relation();
+
+        match relation.manifestation {
+            RelationLinkManifestation::RelationTable(ref m) => {
+                let (parent_column, child_column) = match field.relation_side {
+                    RelationSide::A => (m.model_a_column.clone(), m.model_b_column.clone()),
+                    RelationSide::B => (m.model_b_column.clone(), m.model_a_column.clone()),
+                };
+
+                Link::Table {
+                    table: m.table.clone(),
+                    parent_column,
+                    child_column,
+                    parent_id: names(&field.model().primary_identifier()).remove(0),
+                    child_id: names(&field.related_model().primary_identifier()).remove(0),
+                }
+            }
+            RelationLinkManifestation::Inline(_) if field.relation_is_inlined_in_parent() => Link::Inline {
+                parent_fields: field.db_names().map(String::from).collect(),
+                child_fields: names(&field.related_field().linking_fields()),
+            },
+            RelationLinkManifestation::Inline(_) => Link::Inline {
+                parent_fields: names(&field.linking_fields()),
+                child_fields: field.related_field().db_names().map(String::from).collect(),
+            },
+        }
+    }
+}
+
+/// The data source field names of the identifier.
+pub(crate) fn names(identifier: &ModelIdentifier) -> Vec<String> {
+    identifier.data_source_fields().map(|dsf| dsf.name.clone()).collect()
+}
+
+pub(crate) fn get<'a>(row: &'a Row, name: &str) -> &'a PrismaValue {
+    row.get(name).unwrap_or(&NULL)
+}
+
+pub(crate) fn values<'a>(row: &'a Row, names: &[String]) -> Vec<&'a PrismaValue> {
+    names.iter().map(|name| get(row, name)).collect()
+}
+
+pub(crate) fn all_equal(left: &[&PrismaValue], right: &[&PrismaValue]) -> bool {
+    left.len() == right.len() && left.iter().zip(right).all(|(l, r)| value::equals(l, r) == Some(true))
+}
+
+/// Whether the record has the values of the identifier.
+pub(crate) fn is_identified_by(row: &Row, id: &RecordIdentifier) -> bool {
+    id.pairs()
+        .iter()
+        .all(|(dsf, value)| value::equals(get(row, &dsf.name), value) == Some(true))
+}
+
+/// The identifier of the record made of the given fields.
+pub(crate) fn identifier(row: &Row, fields: &ModelIdentifier) -> RecordIdentifier {
+    fields
+        .data_source_fields()
+        .map(|dsf| {
+            let value = get(row, &dsf.name).clone();
+            (dsf, value)
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
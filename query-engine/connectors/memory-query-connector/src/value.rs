@@ -0,0 +1,107 @@
+use prisma_value::{GraphqlId, PrismaValue};
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+
+/// Ids and enums are compared like the strings and numbers they wrap, floats like decimals.
+fn normalize(value: &PrismaValue) -> PrismaValue {
+    match value {
+        PrismaValue::GraphqlId(GraphqlId::String(s)) => PrismaValue::String(s.clone()),
+        PrismaValue::GraphqlId(GraphqlId::Int(i)) => PrismaValue::Int(*i as i64),
+        PrismaValue::GraphqlId(GraphqlId::UUID(u)) => PrismaValue::Uuid(*u),
+        PrismaValue::Enum(s) => PrismaValue::String(s.clone()),
+        PrismaValue::Float(d) => PrismaValue::Decimal(*d),
+        value => value.clone(),
+    }
+}
+
+/// Compares two values, `None` if one of them is null or the values can't be compared.
+pub(crate) fn compare(left: &PrismaValue, right: &PrismaValue) -> Option<Ordering> {
+    match (normalize(left), normalize(right)) {
+        (PrismaValue::String(l), PrismaValue::String(r)) => Some(l.cmp(&r)),
+        (PrismaValue::Int(l), PrismaValue::Int(r)) => Some(l.cmp(&r)),
+        (PrismaValue::Int(l), PrismaValue::Decimal(r)) => Some(Decimal::from(l).cmp(&r)),
+        (PrismaValue::Decimal(l), PrismaValue::Int(r)) => Some(l.cmp(&Decimal::from(r))),
+        (PrismaValue::Decimal(l), PrismaValue::Decimal(r)) => Some(l.cmp(&r)),
+        (PrismaValue::Boolean(l), PrismaValue::Boolean(r)) => Some(l.cmp(&r)),
+        (PrismaValue::DateTime(l), PrismaValue::DateTime(r)) => Some(l.cmp(&r)),
+        (PrismaValue::Uuid(l), PrismaValue::Uuid(r)) => Some(l.cmp(&r)),
+        _ => None,
+    }
+}
+
+/// Compares the values pairwise until they differ, like a comparison of rows in SQL.
+pub(crate) fn compare_all(left: &[&PrismaValue], right: &[&PrismaValue]) -> Option<Ordering> {
+    for (l, r) in left.iter().zip(right.iter()) {
+        match compare(l, r)? {
+            Ordering::Equal => continue,
+            ordering => return Some(ordering),
+        }
+    }
+
+    Some(Ordering::Equal)
+}
+
+/// Whether the values are equal, `None` if one of them is null.
+pub(crate) fn equals(left: &PrismaValue, right: &PrismaValue) -> Option<bool> {
+    match (left, right) {
+        (PrismaValue::Null, _) | (_, PrismaValue::Null) => None,
+        _ => match compare(left, right) {
+            Some(ordering) => Some(ordering == Ordering::Equal),
+            None => Some(normalize(left) == normalize(right)),
+        },
+    }
+}
+
+/// The order of the values in a sorted read. Null values come first, values that can't be compared are kept in
+/// the order they were stored in.
+pub(crate) fn sort_order(left: &PrismaValue, right: &PrismaValue) -> Ordering {
+    match (left, right) {
+        (PrismaValue::Null, PrismaValue::Null) => Ordering::Equal,
+        (PrismaValue::Null, _) => Ordering::Less,
+        (_, PrismaValue::Null) => Ordering::Greater,
+        _ => compare(left, right).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// The value as text for pattern comparisons, `None` if it is null.
+pub(crate) fn text(value: &PrismaValue) -> Option<String> {
+    match value {
+        PrismaValue::Null => None,
+        value => Some(format!("{}", value)),
+    }
+}
+
+/// Merges the JSON merge patch (RFC 7396) into the stored JSON document. Documents that are null, not valid JSON or
+/// not an object are patched as empty objects, like in the SQL connectors.
+pub(crate) fn merge_json(document: &PrismaValue, patch: &serde_json::Map<String, serde_json::Value>) -> PrismaValue {
+    let mut document = match document {
+        PrismaValue::String(json) => serde_json::from_str(json).unwrap_or(serde_json::Value::Null),
+        _ => serde_json::Value::Null,
+    };
+
+    merge_patch(&mut document, patch);
+
+    PrismaValue::String(document.to_string())
+}
+
+fn merge_patch(document: &mut serde_json::Value, patch: &serde_json::Map<String, serde_json::Value>) {
+    if !document.is_object() {
+        *document = serde_json::Value::Object(serde_json::Map::new());
+    }
+
+    let object = document.as_object_mut().unwrap();
+
+    for (key, value) in patch.iter() {
+        match value {
+            serde_json::Value::Null => {
+                object.remove(key);
+            }
+            serde_json::Value::Object(nested) => {
+                merge_patch(object.entry(key.clone()).or_insert(serde_json::Value::Null), nested)
+            }
+            value => {
+                object.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{
+    store::{self, Link, Row, Store},
+    value,
+};
+use connector_interface::{error::*, Filter, IdempotencyRecord, WriteArgs};
+use prisma_models::*;
+use user_facing_errors::query_engine::DatabaseConstraint;
+
+impl Store {
+    pub(crate) fn create_record(
+        &mut self,
+        model: &ModelRef,
+        args: WriteArgs,
+    ) -> connector_interface::Result<RecordIdentifier> {
+        let mut row: Row = args.args;
+        let primary_identifier = model.primary_identifier();
+
+        // Auto-incremented ids without a value continue after the greatest id of the table.
+        for field in model.fields().scalar() {
+            if field.is_auto_generated_int_id && store::get(&row, field.db_name()).is_null() {
+                let next = self
+                    .rows(model.db_name())
+                    .iter()
+                    .filter_map(|row| match store::get(row, field.db_name()) {
+                        PrismaValue::Int(i) => Some(*i),
+                        _ => None,
+                    })
+                    .max()
+                    .unwrap_or(0)
+                    + 1;
+
+                row.insert(field.db_name().to_owned(), PrismaValue::Int(next));
+            }
+        }
+
+        check_constraints(model, self.rows(model.db_name()), &row, None)?;
+
+        let id = store::identifier(&row, &primary_identifier);
+        self.rows_mut(model.db_name()).push(row);
+
+        Ok(id)
+    }
+
+    pub(crate) fn update_records(
+        &mut self,
+        model: &ModelRef,
+        where_: &Filter,
+        args: WriteArgs,
+    ) -> connector_interface::Result<Vec<RecordIdentifier>> {
+        let primary_identifier = model.primary_identifier();
+        let indexes = self.matching_indexes(model, where_);
+        let mut ids = Vec::with_capacity(indexes.len());
+
+        for index in indexes {
+            let mut row = self.rows(model.db_name())[index].clone();
+            ids.push(store::identifier(&row, &primary_identifier));
+
+            row.extend(args.args.iter().map(|(name, value)| (name.clone(), value.clone())));
+
+            for (name, patch) in args.json_patches.iter() {
+                let merged = value::merge_json(store::get(&row, name), patch);
+                row.insert(name.clone(), merged);
+            }
+            check_constraints(model, self.rows(model.db_name()), &row, Some(index))?;
+
+            self.rows_mut(model.db_name())[index] = row;
+        }
+
+        Ok(ids)
+    }
+
+    pub(crate) fn delete_records(&mut self, model: &ModelRef, where_: &Filter) -> usize {
+        let indexes = self.matching_indexes(model, where_);
+        let rows = self.rows_mut(model.db_name());
+
+        for index in indexes.iter().rev() {
+            rows.remove(*index);
+        }
+
+        indexes.len()
+    }
+
+    /// Inline relations are linked by updates of the fields referencing the parent, only relation tables get
+    /// linked here. Existing links are kept, like `ON CONFLICT DO NOTHING` in the SQL connectors.
+    pub(crate) fn connect(
+        &mut self,
+        field: &RelationFieldRef,
+        parent_id: &RecordIdentifier,
+        child_ids: &[RecordIdentifier],
+    ) {
+        if let Link::Table {
+            table,
+            parent_column,
+            child_column,
+            ..
+        } = Link::of(field)
+        {
+            let parent_id = parent_id.single_value();
+
+            for child_id in child_ids {
+                let child_id = child_id.single_value();
+                let exists = self.rows(&table).iter().any(|link| {
+                    value::equals(store::get(link, &parent_column), &parent_id) == Some(true)
+                        && value::equals(store::get(link, &child_column), &child_id) == Some(true)
+                });
+
+                if !exists {
+                    let mut link = Row::new();
+
+                    link.insert(parent_column.clone(), parent_id.clone());
+                    link.insert(child_column.clone(), child_id);
+                    self.rows_mut(&table).push(link);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn disconnect(
+        &mut self,
+        field: &RelationFieldRef,
+        parent_id: &RecordIdentifier,
+        child_ids: &[RecordIdentifier],
+    ) {
+        if let Link::Table {
+            table,
+            parent_column,
+            child_column,
+            ..
+        } = Link::of(field)
+        {
+            let parent_id = parent_id.single_value();
+            let child_ids: Vec<PrismaValue> = child_ids.iter().map(RecordIdentifier::single_value).collect();
+
+            self.rows_mut(&table).retain(|link| {
+                value::equals(store::get(link, &parent_column), &parent_id) != Some(true)
+                    || !child_ids
+                        .iter()
+                        .any(|id| value::equals(store::get(link, &child_column), id) == Some(true))
+            });
+        }
+    }
+
+    pub(crate) fn disconnect_all(&mut self, field: &RelationFieldRef, parent_ids: &[RecordIdentifier]) {
+        if let Link::Table {
+            table, parent_column, ..
+        } = Link::of(field)
+        {
+            let parent_ids: Vec<PrismaValue> = parent_ids.iter().map(RecordIdentifier::single_value).collect();
+
+            self.rows_mut(&table).retain(|link| {
+                !parent_ids
+                    .iter()
+                    .any(|id| value::equals(store::get(link, &parent_column), id) == Some(true))
+            });
+        }
+    }
+
+    pub(crate) fn store_idempotency_record(&mut self, record: IdempotencyRecord) -> connector_interface::Result<()> {
+        if self
+            .idempotency_records
+            .iter()
+            .any(|existing| existing.key == record.key)
+        {
+            return Err(unique_violation(vec!["key".to_owned()]));
+        }
+
+        self.idempotency_records.push(record);
+        Ok(())
+    }
+
+    /// The positions of the records matching the filter in their table, in ascending order.
+    fn matching_indexes(&self, model: &ModelRef, filter: &Filter) -> Vec<usize> {
+        self.rows(model.db_name())
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| self.evaluate(row, filter) == Some(true))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// Checks the record against the constraints a database would enforce on the table of the model: Required fields
+/// must have a value and the values of the id and of unique fields and indexes must not exist in another record.
+/// The record at `index` is the previous version of an updated record.
+fn check_constraints(
+    model: &ModelRef,
+    rows: &[Row],
+    row: &Row,
+    index: Option<usize>,
+) -> connector_interface::Result<()> {
+    for field in model.fields().scalar_non_list() {
+        if field.is_required && !field.is_computed() && store::get(row, field.db_name()).is_null() {
+            return Err(ConnectorError::from_kind(ErrorKind::NullConstraintViolation {
+                constraint: DatabaseConstraint::Fields(vec![field.db_name().to_owned()]),
+            }));
+        }
+    }
+
+    let mut unique_fields = vec![store::names(&model.primary_identifier())];
+
+    unique_fields.extend(
+        model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|field| field.unique())
+            .map(|field| vec![field.db_name().to_owned()]),
+    );
+
+    unique_fields.extend(
+        model
+            .unique_indexes()
+            .into_iter()
+            .map(|index| index.fields().iter().map(|field| field.db_name().to_owned()).collect()),
+    );
+
+    for names in unique_fields {
+        let values = store::values(row, &names);
+
+        if values.iter().any(|value| value.is_null()) {
+            continue;
+        }
+
+        let exists = rows
+            .iter()
+            .enumerate()
+            .any(|(i, other)| Some(i) != index && store::all_equal(&store::values(other, &names), &values));
+
+        if exists {
+            return Err(unique_violation(names));
+        }
+    }
+
+    Ok(())
+}
+
+fn unique_violation(fields: Vec<String>) -> ConnectorError {
+    ConnectorError::from_kind(ErrorKind::UniqueConstraintViolation {
+        constraint: DatabaseConstraint::Fields(fields),
+    })
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use connector_interface::{Connector, ConnectorCapabilities};
+use connector_test_kit::ConnectorFactory;
+use datamodel::{Datamodel, RelationMode};
+use memory_query_connector::MemoryConnector;
+
+struct MemoryConnectorFactory;
+
+#[async_trait]
+impl ConnectorFactory for MemoryConnectorFactory {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn db_name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn capabilities(&self) -> ConnectorCapabilities {
+        ConnectorCapabilities::empty()
+    }
+
+    /// Tables are created on the first write, every connector starts empty. Without foreign keys in any relation
+    /// mode.
+    async fn connector(
+        &self,
+        _datamodel: &Datamodel,
+        _relation_mode: RelationMode,
+    ) -> Box<dyn Connector + Send + Sync> {
+        Box::new(MemoryConnector::new())
+    }
+}
+
+connector_test_kit::connector_test_suite!(MemoryConnectorFactory);
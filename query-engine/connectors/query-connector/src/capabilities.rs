@@ -0,0 +1,22 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// Features a connector supports beyond the operations every connector has. The query schema only exposes the
+    /// filters and write operations the connector of the data source supports.
+    pub struct ConnectorCapabilities: u32 {
+        /// `Json` fields are stored natively, not as strings.
+        const JSON = 0b0000_0001;
+        /// Enum fields can be filtered on.
+        const ENUMS = 0b0000_0010;
+        /// Scalar list fields can be written, e.g. through `set`.
+        const SCALAR_LISTS = 0b0000_0100;
+        /// Writes can return the written rows in the same statement (`RETURNING`).
+        const RETURNING = 0b0000_1000;
+        /// Subqueries can reference the columns of the enclosing query.
+        const CORRELATED_SUBQUERIES = 0b0001_0000;
+        /// Text columns can be searched with full-text indexes.
+        const FULL_TEXT_SEARCH = 0b0010_0000;
+        /// Joined subqueries can reference the columns of the preceding tables (`LATERAL`).
+        const LATERAL_JOINS = 0b0100_0000;
+    }
+}
@@ -29,6 +29,15 @@ pub enum ErrorKind {
     #[fail(display = "Null constraint failed: {}", constraint)]
     NullConstraintViolation { constraint: DatabaseConstraint },
 
+    #[fail(display = "Foreign key constraint failed: {}", constraint)]
+    ForeignKeyConstraintViolation { constraint: DatabaseConstraint },
+
+    #[fail(display = "The value is too long for the column: {}", column_name)]
+    ValueTooLong { column_name: String },
+
+    #[fail(display = "A constraint failed on the database: {}", database_error)]
+    ConstraintViolation { database_error: String },
+
     #[fail(display = "Record does not exist.")]
     RecordDoesNotExist,
 
@@ -164,6 +164,9 @@ pub fn test_data_model() -> InternalDataModelRef {
             manifestation: None,
             id_field_names: vec![],
             indexes: vec![],
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
         },
         ModelTemplate {
             name: "Site".to_owned(),
@@ -172,6 +175,9 @@ pub fn test_data_model() -> InternalDataModelRef {
             manifestation: None,
             id_field_names: vec![],
             indexes: vec![],
+            datasource: None,
+            soft_delete_field: None,
+            cache_ttl: None,
         },
     ];
 
@@ -180,6 +186,10 @@ pub fn test_data_model() -> InternalDataModelRef {
         relations: vec![],
         enums: vec![],
         version: None,
+        relation_mode: Default::default(),
+        datasource_db_names: Default::default(),
+        updated_at_in_database: false,
+        identifier_delimiter: None,
     };
 
     project_template.build("some_db_name".to_owned())
@@ -0,0 +1,13 @@
+/// The longest idempotency key connectors store, in characters.
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// The stored response of a mutation executed with an idempotency key. A retry of the request with the same key
+/// gets the stored response instead of executing the mutation again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    /// Hash of the request, to tell a retry apart from a different request reusing the key.
+    pub request_hash: String,
+    /// The data of the response.
+    pub response: serde_json::Value,
+}
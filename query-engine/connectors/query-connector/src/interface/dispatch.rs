@@ -48,6 +48,20 @@ impl<'conn, 'tx> ReadOperations for ConnectionLike<'conn, 'tx> {
             Self::Transaction(tx) => tx.count_by_model(model, query_arguments),
         }
     }
+
+    fn list_routines<'a>(&'a self) -> crate::IO<'a, Vec<Routine>> {
+        match self {
+            Self::Connection(c) => c.list_routines(),
+            Self::Transaction(tx) => tx.list_routines(),
+        }
+    }
+
+    fn get_idempotency_record<'a>(&'a self, key: &'a str) -> crate::IO<'a, Option<IdempotencyRecord>> {
+        match self {
+            Self::Connection(c) => c.get_idempotency_record(key),
+            Self::Transaction(tx) => tx.get_idempotency_record(key),
+        }
+    }
 }
 
 impl<'conn, 'tx> WriteOperations for ConnectionLike<'conn, 'tx> {
@@ -101,10 +115,31 @@ impl<'conn, 'tx> WriteOperations for ConnectionLike<'conn, 'tx> {
         }
     }
 
+    fn disconnect_all<'a>(&'a self, field: &'a RelationFieldRef, parent_ids: &'a [RecordIdentifier]) -> crate::IO<()> {
+        match self {
+            Self::Connection(c) => c.disconnect_all(field, parent_ids),
+            Self::Transaction(tx) => tx.disconnect_all(field, parent_ids),
+        }
+    }
+
     fn execute_raw<'a>(&'a self, query: String, parameters: Vec<PrismaValue>) -> crate::IO<serde_json::Value> {
         match self {
             Self::Connection(c) => c.execute_raw(query, parameters),
             Self::Transaction(tx) => tx.execute_raw(query, parameters),
         }
     }
+
+    fn call_routine<'a>(&'a self, routine: &'a Routine, arguments: Vec<PrismaValue>) -> crate::IO<RoutineResult> {
+        match self {
+            Self::Connection(c) => c.call_routine(routine, arguments),
+            Self::Transaction(tx) => tx.call_routine(routine, arguments),
+        }
+    }
+
+    fn store_idempotency_record<'a>(&'a self, record: IdempotencyRecord) -> crate::IO<()> {
+        match self {
+            Self::Connection(c) => c.store_idempotency_record(record),
+            Self::Transaction(tx) => tx.store_idempotency_record(record),
+        }
+    }
 }
@@ -2,12 +2,52 @@ mod dispatch;
 
 pub use dispatch::*;
 
-use crate::{Filter, QueryArguments, WriteArgs};
+use crate::{Filter, IdempotencyRecord, QueryArguments, Routine, RoutineResult, WriteArgs};
 use prisma_models::*;
 use prisma_value::PrismaValue;
+use serde::Serialize;
 
+/// A database the query engine executes queries on. Connectors outside of the engine implement this trait,
+/// together with [Connection](trait.Connection.html) and [Transaction](trait.Transaction.html).
 pub trait Connector {
     fn get_connection<'a>(&'a self) -> crate::IO<Box<dyn Connection + 'a>>;
+
+    /// Current utilization of the connection pool of the connector.
+    fn pool_state<'a>(&'a self) -> crate::IO<'a, PoolState>;
+}
+
+/// Connectors loaded at runtime are boxed trait objects.
+impl<C> Connector for Box<C>
+where
+    C: Connector + ?Sized,
+{
+    fn get_connection<'a>(&'a self) -> crate::IO<Box<dyn Connection + 'a>> {
+        (**self).get_connection()
+    }
+
+    fn pool_state<'a>(&'a self) -> crate::IO<'a, PoolState> {
+        (**self).pool_state()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolState {
+    /// Number of connections currently checked out of the pool.
+    pub in_use: usize,
+    /// Maximum number of connections the pool opens.
+    pub capacity: usize,
+}
+
+impl PoolState {
+    /// Fraction of the pool capacity currently in use, between 0 and 1.
+    pub fn saturation(&self) -> f64 {
+        if self.capacity == 0 {
+            1.0
+        } else {
+            self.in_use as f64 / self.capacity as f64
+        }
+    }
 }
 
 pub trait Connection: ReadOperations + WriteOperations + Send + Sync {
@@ -52,6 +92,13 @@ pub trait ReadOperations {
 
     // This will eventually become a more generic `aggregate`
     fn count_by_model<'a>(&'a self, model: &'a ModelRef, query_arguments: QueryArguments) -> crate::IO<'a, usize>;
+
+    /// The stored procedures and functions of the database whose signatures map to the types of the engine.
+    fn list_routines<'a>(&'a self) -> crate::IO<'a, Vec<Routine>>;
+
+    /// Looks up the stored response of an idempotency key. Connections create the storage of the records if it
+    /// doesn't exist yet, transactions expect it to exist, so keys must be looked up on a connection first.
+    fn get_idempotency_record<'a>(&'a self, key: &'a str) -> crate::IO<'a, Option<IdempotencyRecord>>;
 }
 
 pub trait WriteOperations {
@@ -82,5 +129,15 @@ pub trait WriteOperations {
         child_ids: &'a [RecordIdentifier],
     ) -> crate::IO<()>;
 
+    /// Removes all connections of the given parent records in a relation table, which the database
+    /// would otherwise cascade to when the parents are deleted.
+    fn disconnect_all<'a>(&'a self, field: &'a RelationFieldRef, parent_ids: &'a [RecordIdentifier]) -> crate::IO<()>;
+
     fn execute_raw<'a>(&'a self, query: String, parameters: Vec<PrismaValue>) -> crate::IO<serde_json::Value>;
+
+    /// Calls the routine with one argument per parameter of its signature, returning the rows of its result.
+    fn call_routine<'a>(&'a self, routine: &'a Routine, arguments: Vec<PrismaValue>) -> crate::IO<RoutineResult>;
+
+    /// Stores the response of an idempotency key. Fails if a record for the key exists already.
+    fn store_idempotency_record<'a>(&'a self, record: IdempotencyRecord) -> crate::IO<()>;
 }
@@ -3,17 +3,29 @@
 pub mod error;
 pub mod filter;
 
+mod capabilities;
 mod compare;
+mod idempotency;
 mod interface;
 mod query_arguments;
+mod routine;
 mod write_args;
 
+#[cfg(feature = "connector-registry")]
+mod registry;
+
+pub use capabilities::*;
 pub use compare::*;
 pub use filter::*;
+pub use idempotency::*;
 pub use interface::*;
 pub use query_arguments::*;
+pub use routine::*;
 pub use write_args::*;
 
+#[cfg(feature = "connector-registry")]
+pub use registry::*;
+
 use futures::future::{BoxFuture, FutureExt};
 use std::{
     future::Future,
@@ -19,7 +19,9 @@ pub struct QueryArguments {
 }
 
 impl QueryArguments {
-    fn needs_reversed_order(&self) -> bool {
+    /// Reading the `last` records inverts the order of the query, connectors reverse the read records again to
+    /// return them in the requested order.
+    pub fn needs_reversed_order(&self) -> bool {
         self.last.is_some()
     }
 
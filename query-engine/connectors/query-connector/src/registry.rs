@@ -0,0 +1,95 @@
+//! Registry of connectors shipped by other crates. The engine loads the connector of a data source from here if none
+//! of its built-in connectors handles the provider of the data source.
+//!
+//! ```ignore
+//! query_connector::register_connector(Arc::new(ClickHouseDefinition));
+//! ```
+use crate::{Connector, ConnectorCapabilities, IO};
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+static CONNECTORS: Lazy<RwLock<HashMap<&'static str, Arc<dyn ConnectorDefinition>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Entry point of a connector for the engine, one per provider.
+pub trait ConnectorDefinition: Send + Sync + 'static {
+    /// The provider of the data sources the connector handles, e.g. `clickhouse`.
+    fn connector_type(&self) -> &'static str;
+
+    /// See [ConnectorCapabilities](struct.ConnectorCapabilities.html).
+    fn capabilities(&self) -> ConnectorCapabilities;
+
+    /// Connects to the database at the URL of the data source. Returns the name of the database the models live in
+    /// and the connector.
+    fn load<'a>(&'a self, url: &'a str) -> IO<'a, (String, Box<dyn Connector + Send + Sync>)>;
+}
+
+/// Registers a connector, replacing a connector registered before for the same provider.
+pub fn register_connector(definition: Arc<dyn ConnectorDefinition>) {
+    CONNECTORS
+        .write()
+        .unwrap()
+        .insert(definition.connector_type(), definition);
+}
+
+/// The connector registered for the provider, if any.
+pub fn registered_connector(connector_type: &str) -> Option<Arc<dyn ConnectorDefinition>> {
+    CONNECTORS.read().unwrap().get(connector_type).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestDefinition {
+        connector_type: &'static str,
+        capabilities: ConnectorCapabilities,
+    }
+
+    impl ConnectorDefinition for TestDefinition {
+        fn connector_type(&self) -> &'static str {
+            self.connector_type
+        }
+
+        fn capabilities(&self) -> ConnectorCapabilities {
+            self.capabilities
+        }
+
+        fn load<'a>(&'a self, _url: &'a str) -> IO<'a, (String, Box<dyn Connector + Send + Sync>)> {
+            unreachable!("The registry doesn't load connectors.")
+        }
+    }
+
+    fn register(connector_type: &'static str, capabilities: ConnectorCapabilities) {
+        register_connector(Arc::new(TestDefinition {
+            connector_type,
+            capabilities,
+        }));
+    }
+
+    #[test]
+    fn connectors_are_registered_per_provider() {
+        register("registry-a", ConnectorCapabilities::ENUMS);
+        register("registry-b", ConnectorCapabilities::SCALAR_LISTS);
+
+        let a = registered_connector("registry-a").unwrap();
+        let b = registered_connector("registry-b").unwrap();
+
+        assert_eq!(a.capabilities(), ConnectorCapabilities::ENUMS);
+        assert_eq!(b.capabilities(), ConnectorCapabilities::SCALAR_LISTS);
+        assert!(registered_connector("registry-unknown").is_none());
+    }
+
+    #[test]
+    fn registering_a_provider_again_replaces_its_connector() {
+        register("registry-replaced", ConnectorCapabilities::ENUMS);
+        register("registry-replaced", ConnectorCapabilities::SCALAR_LISTS);
+
+        let definition = registered_connector("registry-replaced").unwrap();
+
+        assert_eq!(definition.capabilities(), ConnectorCapabilities::SCALAR_LISTS);
+    }
+}
@@ -0,0 +1,32 @@
+use prisma_models::{PrismaValue, TypeIdentifier};
+
+/// A stored procedure or function of the database, callable with arguments of the types of its signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Routine {
+    pub name: String,
+    pub kind: RoutineKind,
+    /// The input parameters, in the order of the signature.
+    pub parameters: Vec<RoutineParameter>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoutineKind {
+    /// Invoked with `CALL`, may return result sets.
+    Procedure,
+    /// Invoked in a `SELECT`, returns a value or a table.
+    Function,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutineParameter {
+    pub name: String,
+    pub type_identifier: TypeIdentifier,
+    pub is_list: bool,
+}
+
+/// The rows a routine returned, with the values of each row in the order of the columns.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RoutineResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<PrismaValue>>,
+}
@@ -7,17 +7,22 @@ use std::collections::{hash_map::Keys, HashMap};
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct WriteArgs {
     pub args: HashMap<String, PrismaValue>,
+    /// JSON merge patches (RFC 7396) of JSON fields, merged into the stored documents instead of replacing them.
+    pub json_patches: HashMap<String, serde_json::Map<String, serde_json::Value>>,
 }
 
 impl From<HashMap<String, PrismaValue>> for WriteArgs {
     fn from(args: HashMap<String, PrismaValue>) -> Self {
-        Self { args }
+        Self {
+            args,
+            ..Default::default()
+        }
     }
 }
 
 impl WriteArgs {
     pub fn new() -> Self {
-        Self { args: HashMap::new() }
+        Self::default()
     }
 
     pub fn insert<T, V>(&mut self, key: T, arg: V)
@@ -28,10 +33,21 @@ impl WriteArgs {
         self.args.insert(key.into(), arg.into());
     }
 
+    pub fn merge_json<T>(&mut self, key: T, patch: serde_json::Map<String, serde_json::Value>)
+    where
+        T: Into<String>,
+    {
+        self.json_patches.insert(key.into(), patch);
+    }
+
     pub fn has_arg_for(&self, field: &str) -> bool {
         self.args.contains_key(field)
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty() && self.json_patches.is_empty()
+    }
+
     pub fn get_field_value(&self, field: &str) -> Option<&PrismaValue> {
         self.args.get(field)
     }
@@ -62,9 +78,14 @@ impl WriteArgs {
         }
     }
 
+    /// Fields the database keeps up to date itself are left to it, stamping them here would write them twice.
     pub fn update_datetimes(&mut self, model: ModelRef) {
-        if !self.args.is_empty() {
+        if !self.is_empty() {
             if let Some(field) = model.fields().updated_at() {
+                if field.is_updated_at_in_database() {
+                    return;
+                }
+
                 if let None = self.args.get(field.db_name()) {
                     self.insert(field.db_name().clone(), PrismaValue::DateTime(Utc::now()));
                 }
@@ -0,0 +1,165 @@
+use quaint::{connector::Queryable, pooled::PooledConnection, single::Quaint};
+use std::sync::Arc;
+
+/// Aborts the statement running on a connection if the request executing it is dropped,
+/// e.g. because the client disconnected or the request deadline expired.
+///
+/// Dropping the query future only stops waiting for the result, the database would otherwise keep
+/// running the statement to completion. The abort is sent over a dedicated connection opened for it,
+/// as waiting for a pooled one could deadlock on a saturated pool. The cancelled connection is only
+/// returned to the pool afterwards, so that a cancellation can't hit a statement of the next request
+/// using the connection.
+///
+/// The id of the session is read on every checkout, which costs a round trip: the pool of quaint doesn't
+/// expose which physical connection a checkout hands out, so the id can't be kept across checkouts.
+pub(crate) struct Cancellation {
+    url: Arc<str>,
+    statement: String,
+    connection: Arc<dyn Send + Sync>,
+}
+
+impl Cancellation {
+    /// Cancellation through `pg_cancel_backend` using the process id of the connection.
+    pub(crate) async fn postgres(url: &Arc<str>, conn: Arc<PooledConnection>) -> Option<Self> {
+        let pid = backend_id(&conn, "SELECT pg_backend_pid()").await?;
+        Some(Self::new(url, postgres_statement(pid), conn))
+    }
+
+    /// Cancellation through `KILL QUERY` using the thread id of the connection.
+    pub(crate) async fn mysql(url: &Arc<str>, conn: Arc<PooledConnection>) -> Option<Self> {
+        let thread_id = backend_id(&conn, "SELECT CONNECTION_ID()").await?;
+        Some(Self::new(url, mysql_statement(thread_id), conn))
+    }
+
+    /// `connection` is kept until the statement is aborted.
+    fn new(url: &Arc<str>, statement: String, connection: Arc<dyn Send + Sync>) -> Self {
+        Self {
+            url: Arc::clone(url),
+            statement,
+            connection,
+        }
+    }
+
+    /// Marks a statement as running until the returned guard is disarmed.
+    pub(crate) fn arm(&self) -> StatementGuard<'_> {
+        StatementGuard(Some(self))
+    }
+}
+
+/// Aborts the running statement if dropped without being disarmed.
+pub(crate) struct StatementGuard<'a>(Option<&'a Cancellation>);
+
+impl<'a> StatementGuard<'a> {
+    /// The statement finished, nothing to abort.
+    pub(crate) fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl<'a> Drop for StatementGuard<'a> {
+    fn drop(&mut self) {
+        if let Some(cancellation) = self.0.take() {
+            let url = Arc::clone(&cancellation.url);
+            let statement = cancellation.statement.clone();
+            let connection = Arc::clone(&cancellation.connection);
+
+            tokio::spawn(async move {
+                let result = match Quaint::new(&url).await {
+                    Ok(conn) => conn.execute_raw(&statement, &[]).await.map(|_| ()),
+                    Err(err) => Err(err),
+                };
+
+                if let Err(err) = result {
+                    tracing::warn!("Aborting the statement of a cancelled request failed: {}", err);
+                }
+
+                drop(connection);
+            });
+        }
+    }
+}
+
+fn postgres_statement(pid: i64) -> String {
+    format!("SELECT pg_cancel_backend({})", pid)
+}
+
+fn mysql_statement(thread_id: i64) -> String {
+    format!("KILL QUERY {}", thread_id)
+}
+
+/// The id the database identifies the session of the connection with. Without it, statements on the
+/// connection can't be cancelled, but it is still usable.
+async fn backend_id(conn: &PooledConnection, query: &str) -> Option<i64> {
+    let id = match conn.query_raw(query, &[]).await {
+        Ok(result_set) => result_set
+            .first()
+            .as_ref()
+            .and_then(|row| row.at(0))
+            .and_then(|id| id.as_i64()),
+        Err(err) => {
+            tracing::warn!(
+                "Reading the connection id failed, its statements can't be cancelled: {}",
+                err
+            );
+            return None;
+        }
+    };
+
+    if id.is_none() {
+        tracing::warn!("Unexpected result when reading the connection id, its statements can't be cancelled.");
+    }
+
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A cancellation whose abort fails right away, the url has no supported scheme.
+    fn cancellation(connection: &Arc<()>) -> Cancellation {
+        let url: Arc<str> = "invalid://localhost/db".into();
+        let connection: Arc<dyn Send + Sync> = Arc::clone(connection) as Arc<dyn Send + Sync>;
+
+        Cancellation::new(&url, postgres_statement(42), connection)
+    }
+
+    #[test]
+    fn statements_abort_the_session_of_the_connection() {
+        assert_eq!(postgres_statement(42), "SELECT pg_cancel_backend(42)");
+        assert_eq!(mysql_statement(7), "KILL QUERY 7");
+    }
+
+    #[test]
+    fn disarmed_statements_are_not_aborted() {
+        let connection = Arc::new(());
+        let cancellation = cancellation(&connection);
+
+        // Spawning the abort would panic without a runtime.
+        cancellation.arm().disarm();
+        drop(cancellation);
+
+        assert_eq!(Arc::strong_count(&connection), 1);
+    }
+
+    #[test]
+    fn dropped_statements_are_aborted_before_the_connection_is_released() {
+        let mut runtime = tokio::runtime::Builder::new().basic_scheduler().build().unwrap();
+        let connection = Arc::new(());
+        let cancellation = cancellation(&connection);
+
+        runtime.block_on(async { drop(cancellation.arm()) });
+        drop(cancellation);
+
+        // The abort task holds the connection until it ran.
+        for _ in 0..100 {
+            if Arc::strong_count(&connection) == 1 {
+                break;
+            }
+
+            runtime.block_on(tokio::task::yield_now());
+        }
+
+        assert_eq!(Arc::strong_count(&connection), 1);
+    }
+}
@@ -1,17 +1,25 @@
-use super::transaction::SqlConnectorTransaction;
-use crate::{database::operations::*, query_builder::read::ManyRelatedRecordsQueryBuilder, QueryExt, SqlError};
+use super::{cancellation::Cancellation, transaction::SqlConnectorTransaction, InUseGuard};
+use crate::{
+    database::operations::*, query_builder::read::ManyRelatedRecordsQueryBuilder, QueryExt, SqlError, TimeZoneExt,
+};
 use connector_interface::{
-    self as connector, filter::Filter, Connection, QueryArguments, ReadOperations, Transaction, WriteArgs,
-    WriteOperations, IO,
+    self as connector, filter::Filter, Connection, IdempotencyRecord, QueryArguments, ReadOperations, Routine,
+    RoutineResult, Transaction, WriteArgs, WriteOperations, IO,
 };
 use prisma_models::prelude::*;
-use prisma_value::PrismaValue;
+use prisma_value::{PrismaValue, TimeZonePolicy};
 use quaint::{connector::TransactionCapable, prelude::ConnectionInfo};
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 pub struct SqlConnection<'a, C, T> {
-    inner: C,
+    inner: Arc<C>,
+    cancellation: Option<Cancellation>,
     connection_info: &'a ConnectionInfo,
+    time_zone: TimeZonePolicy,
+    max_fan_out: Option<usize>,
+    /// Inserts on MySQL return the ids with a `RETURNING` clause, see `Mysql`.
+    returning: bool,
+    _in_use: InUseGuard,
     _p: PhantomData<T>,
 }
 
@@ -20,10 +28,23 @@ where
     C: QueryExt + Send + Sync + 'static,
     T: ManyRelatedRecordsQueryBuilder + Send + Sync + 'static,
 {
-    pub fn new(inner: C, connection_info: &'a ConnectionInfo) -> Self {
+    pub(crate) fn new(
+        inner: Arc<C>,
+        cancellation: Option<Cancellation>,
+        connection_info: &'a ConnectionInfo,
+        time_zone: TimeZonePolicy,
+        max_fan_out: Option<usize>,
+        returning: bool,
+        in_use: InUseGuard,
+    ) -> Self {
         Self {
             inner,
+            cancellation,
             connection_info,
+            time_zone,
+            max_fan_out,
+            returning,
+            _in_use: in_use,
             _p: PhantomData,
         }
     }
@@ -32,7 +53,14 @@ where
         &self,
         fut: impl std::future::Future<Output = Result<O, SqlError>>,
     ) -> Result<O, connector_interface::error::ConnectorError> {
-        match fut.await {
+        let statement = self.cancellation.as_ref().map(Cancellation::arm);
+        let result = fut.await;
+
+        if let Some(statement) = statement {
+            statement.disarm();
+        }
+
+        match result {
             Ok(o) => Ok(o),
             Err(err) => Err(err.into_connector_error(&self.connection_info)),
         }
@@ -47,10 +75,17 @@ where
     fn start_transaction<'a>(&'a self) -> IO<'a, Box<dyn Transaction<'a> + 'a>> {
         let fut_tx = self.inner.start_transaction();
         let connection_info = self.connection_info;
+        let cancellation = self.cancellation.as_ref();
+        let time_zone = self.time_zone;
+        let max_fan_out = self.max_fan_out;
+        let returning = self.returning;
 
         IO::new(self.catch(async move {
             let tx: quaint::connector::Transaction<'a> = fut_tx.await.map_err(SqlError::from)?;
-            Ok(Box::new(SqlConnectorTransaction::<T>::new(tx, connection_info)) as Box<dyn Transaction<'a> + 'a>)
+            let tx =
+                SqlConnectorTransaction::<T>::new(tx, cancellation, connection_info, time_zone, max_fan_out, returning);
+
+            Ok(Box::new(tx) as Box<dyn Transaction<'a> + 'a>)
         }))
     }
 }
@@ -66,7 +101,13 @@ where
         filter: &'b Filter,
         selected_fields: &'b SelectedFields,
     ) -> connector::IO<'b, Option<SingleRecord>> {
-        IO::new(self.catch(async move { read::get_single_record(&self.inner, model, filter, selected_fields).await }))
+        IO::new(self.catch(async move {
+            let filter = self.time_zone.bind(filter.clone());
+            let record =
+                read::get_single_record(&*self.inner, self.connection_info, model, &filter, selected_fields).await?;
+
+            Ok(self.time_zone.read(record))
+        }))
     }
 
     fn get_many_records<'b>(
@@ -75,11 +116,19 @@ where
         query_arguments: QueryArguments,
         selected_fields: &'b SelectedFields,
     ) -> connector::IO<'b, ManyRecords> {
-        IO::new(
-            self.catch(
-                async move { read::get_many_records(&self.inner, model, query_arguments, selected_fields).await },
-            ),
-        )
+        IO::new(self.catch(async move {
+            let query_arguments = self.time_zone.bind(query_arguments);
+            let records = read::get_many_records(
+                &*self.inner,
+                self.connection_info,
+                model,
+                query_arguments,
+                selected_fields,
+            )
+            .await?;
+
+            Ok(self.time_zone.read(records))
+        }))
     }
 
     fn get_related_records<'b>(
@@ -90,19 +139,41 @@ where
         selected_fields: &'b SelectedFields,
     ) -> connector::IO<'b, ManyRecords> {
         IO::new(self.catch(async move {
-            read::get_related_records::<T>(
-                &self.inner,
+            let from_record_ids = self.time_zone.bind(from_record_ids.to_vec());
+            let query_arguments = self.time_zone.bind(query_arguments);
+
+            let records = read::get_related_records::<T>(
+                &*self.inner,
+                self.connection_info,
                 from_field,
-                from_record_ids,
+                &from_record_ids,
                 query_arguments,
                 selected_fields,
+                self.max_fan_out,
             )
-            .await
+            .await?;
+
+            Ok(self.time_zone.read(records))
         }))
     }
 
     fn count_by_model<'b>(&'b self, model: &'b ModelRef, query_arguments: QueryArguments) -> connector::IO<'b, usize> {
-        IO::new(self.catch(async move { read::count_by_model(&self.inner, model, query_arguments).await }))
+        IO::new(self.catch(async move {
+            let query_arguments = self.time_zone.bind(query_arguments);
+            read::count_by_model(&*self.inner, model, query_arguments).await
+        }))
+    }
+
+    fn list_routines<'b>(&'b self) -> connector::IO<'b, Vec<Routine>> {
+        IO::new(self.catch(async move { read::list_routines(&*self.inner, self.connection_info).await }))
+    }
+
+    fn get_idempotency_record<'b>(&'b self, key: &'b str) -> connector::IO<'b, Option<IdempotencyRecord>> {
+        IO::new(self.catch(async move {
+            write::create_idempotency_table(&*self.inner, self.connection_info).await?;
+            write::delete_expired_idempotency_records(&*self.inner, self.connection_info).await?;
+            read::get_idempotency_record(&*self.inner, self.connection_info, key).await
+        }))
     }
 }
 
@@ -112,7 +183,11 @@ where
     T: ManyRelatedRecordsQueryBuilder + Send + Sync + 'static,
 {
     fn create_record<'a>(&'a self, model: &'a ModelRef, args: WriteArgs) -> connector::IO<RecordIdentifier> {
-        IO::new(self.catch(async move { write::create_record(&self.inner, model, args).await }))
+        IO::new(self.catch(async move {
+            let id = write::create_record(&*self.inner, model, self.time_zone.bind(args), self.returning).await?;
+
+            Ok(self.time_zone.read(id))
+        }))
     }
 
     fn update_records<'a>(
@@ -121,11 +196,26 @@ where
         where_: Filter,
         args: WriteArgs,
     ) -> connector::IO<Vec<RecordIdentifier>> {
-        IO::new(self.catch(async move { write::update_records(&self.inner, model, where_, args).await }))
+        IO::new(self.catch(async move {
+            let where_ = self.time_zone.bind(where_);
+            let ids = write::update_records(
+                &*self.inner,
+                self.connection_info,
+                model,
+                where_,
+                self.time_zone.bind(args),
+            )
+            .await?;
+
+            Ok(self.time_zone.read(ids))
+        }))
     }
 
     fn delete_records<'a>(&'a self, model: &'a ModelRef, where_: Filter) -> connector::IO<usize> {
-        IO::new(self.catch(async move { write::delete_records(&self.inner, model, where_).await }))
+        IO::new(self.catch(async move {
+            let where_ = self.time_zone.bind(where_);
+            write::delete_records(&*self.inner, model, where_).await
+        }))
     }
 
     fn connect<'a>(
@@ -134,7 +224,12 @@ where
         parent_id: &'a RecordIdentifier,
         child_ids: &'a [RecordIdentifier],
     ) -> connector::IO<()> {
-        IO::new(self.catch(async move { write::connect(&self.inner, field, parent_id, child_ids).await }))
+        IO::new(self.catch(async move {
+            let parent_id = self.time_zone.bind(parent_id.clone());
+            let child_ids = self.time_zone.bind(child_ids.to_vec());
+
+            write::connect(&*self.inner, field, &parent_id, &child_ids).await
+        }))
     }
 
     fn disconnect<'a>(
@@ -143,10 +238,42 @@ where
         parent_id: &'a RecordIdentifier,
         child_ids: &'a [RecordIdentifier],
     ) -> connector::IO<()> {
-        IO::new(self.catch(async move { write::disconnect(&self.inner, field, parent_id, child_ids).await }))
+        IO::new(self.catch(async move {
+            let parent_id = self.time_zone.bind(parent_id.clone());
+            let child_ids = self.time_zone.bind(child_ids.to_vec());
+
+            write::disconnect(&*self.inner, field, &parent_id, &child_ids).await
+        }))
+    }
+
+    fn disconnect_all<'a>(
+        &'a self,
+        field: &'a RelationFieldRef,
+        parent_ids: &'a [RecordIdentifier],
+    ) -> connector::IO<()> {
+        IO::new(self.catch(async move {
+            let parent_ids = self.time_zone.bind(parent_ids.to_vec());
+            write::disconnect_all(&*self.inner, field, &parent_ids).await
+        }))
     }
 
     fn execute_raw<'a>(&'a self, query: String, parameters: Vec<PrismaValue>) -> connector::IO<serde_json::Value> {
-        IO::new(self.catch(async move { write::execute_raw(&self.inner, query, parameters).await }))
+        IO::new(self.catch(async move { write::execute_raw(&*self.inner, query, parameters).await }))
+    }
+
+    fn call_routine<'a>(&'a self, routine: &'a Routine, arguments: Vec<PrismaValue>) -> connector::IO<RoutineResult> {
+        IO::new(
+            self.catch(
+                async move { write::call_routine(&*self.inner, self.connection_info, routine, arguments).await },
+            ),
+        )
+    }
+
+    fn store_idempotency_record<'a>(&'a self, record: IdempotencyRecord) -> connector::IO<()> {
+        IO::new(
+            self.catch(
+                async move { write::store_idempotency_record(&*self.inner, self.connection_info, record).await },
+            ),
+        )
     }
 }
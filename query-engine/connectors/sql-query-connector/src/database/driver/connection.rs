@@ -0,0 +1,113 @@
+use super::{DriverAdapter, DriverError, DriverResultSet, Statement};
+use crate::QueryExt;
+use quaint::{
+    ast::{ParameterizedValue, Query},
+    connector::{Queryable, ResultSet, TransactionCapable, DBIO},
+    error::{Error, ErrorKind},
+    visitor::{self, Visitor},
+};
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use std::sync::{Arc, Mutex};
+
+/// A connection executing the statements of quaint through a driver adapter.
+pub(crate) struct DriverConnection {
+    adapter: Arc<dyn DriverAdapter>,
+    session: Mutex<Option<String>>,
+}
+
+impl DriverConnection {
+    pub(crate) fn new(adapter: Arc<dyn DriverAdapter>) -> Self {
+        Self {
+            adapter,
+            session: Mutex::new(None),
+        }
+    }
+
+    async fn send(&self, sql: String, params: &[ParameterizedValue<'_>]) -> quaint::Result<DriverResultSet> {
+        let statement = Statement {
+            sql,
+            params: params.iter().cloned().map(serde_json::Value::from).collect(),
+            session: self.session.lock().unwrap().clone(),
+        };
+
+        let result_set = self.adapter.execute(statement).await?;
+        *self.session.lock().unwrap() = result_set.session.clone();
+
+        Ok(result_set)
+    }
+
+    async fn query_sql(&self, sql: String, params: &[ParameterizedValue<'_>]) -> quaint::Result<ResultSet> {
+        let result_set = self.send(sql, params).await?;
+        let rows = result_set
+            .rows
+            .into_iter()
+            .map(|row| row.into_iter().map(parameterized_value).collect())
+            .collect();
+
+        let mut quaint_result_set = ResultSet::new(result_set.columns, rows);
+
+        if let Some(id) = result_set.last_insert_id {
+            quaint_result_set.set_last_insert_id(id);
+        }
+
+        Ok(quaint_result_set)
+    }
+}
+
+impl Queryable for DriverConnection {
+    fn query<'a>(&'a self, q: Query<'a>) -> DBIO<'a, ResultSet> {
+        DBIO::new(async move {
+            let (sql, params) = visitor::Sqlite::build(q);
+            self.query_sql(sql, &params).await
+        })
+    }
+
+    fn query_raw<'a>(&'a self, sql: &'a str, params: &'a [ParameterizedValue]) -> DBIO<'a, ResultSet> {
+        DBIO::new(self.query_sql(sql.to_owned(), params))
+    }
+
+    fn execute_raw<'a>(&'a self, sql: &'a str, params: &'a [ParameterizedValue]) -> DBIO<'a, u64> {
+        DBIO::new(async move { Ok(self.send(sql.to_owned(), params).await?.rows_affected) })
+    }
+
+    fn raw_cmd<'a>(&'a self, cmd: &'a str) -> DBIO<'a, ()> {
+        DBIO::new(async move {
+            self.send(cmd.to_owned(), &[]).await?;
+            Ok(())
+        })
+    }
+}
+
+impl TransactionCapable for DriverConnection {}
+impl QueryExt for DriverConnection {}
+
+/// SQLite values are integers, reals, text, blobs or null, blobs are not supported by the JSON transport.
+fn parameterized_value(value: serde_json::Value) -> ParameterizedValue<'static> {
+    match value {
+        serde_json::Value::Null => ParameterizedValue::Null,
+        serde_json::Value::Bool(b) => ParameterizedValue::Boolean(b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => ParameterizedValue::Integer(i),
+            None => n
+                .as_f64()
+                .and_then(Decimal::from_f64)
+                .map(ParameterizedValue::Real)
+                .unwrap_or(ParameterizedValue::Null),
+        },
+        serde_json::Value::String(s) => ParameterizedValue::Text(s.into()),
+        value => ParameterizedValue::Text(value.to_string().into()),
+    }
+}
+
+impl From<DriverError> for Error {
+    fn from(e: DriverError) -> Self {
+        let mut builder = Error::builder(ErrorKind::QueryError(e.message.clone().into()));
+
+        if let Some(code) = e.code {
+            builder.set_original_code(code);
+        }
+
+        builder.set_original_message(e.message);
+        builder.build()
+    }
+}
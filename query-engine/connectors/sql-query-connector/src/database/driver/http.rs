@@ -0,0 +1,87 @@
+use super::{DriverAdapter, DriverError, DriverResultSet, Statement};
+use crate::SqlError;
+use async_trait::async_trait;
+use hyper::{client::HttpConnector, header, Body, Client, Request};
+use hyper_rustls::HttpsConnector;
+use secret_string::SecretString;
+use url::Url;
+
+/// Query parameter of the URL with the token authenticating the requests, sent as a bearer token.
+const AUTH_TOKEN_PARAM: &str = "authToken";
+
+/// Sends statements to an HTTP endpoint, e.g. a proxy in front of a serverless SQLite service.
+///
+/// Every statement is a `POST` of its JSON to the URL of the data source, answered with the JSON of the result set
+/// or, with an error status, of the error.
+pub struct HttpDriver {
+    client: Client<HttpsConnector<HttpConnector>>,
+    url: SecretString,
+    auth_token: Option<String>,
+}
+
+impl HttpDriver {
+    pub fn new(url: &SecretString) -> crate::Result<Self> {
+        let mut url = Url::parse(url.expose()).map_err(|err| SqlError::InvalidConnectionString(err.to_string()))?;
+
+        let auth_token = url
+            .query_pairs()
+            .find(|(key, _)| key == AUTH_TOKEN_PARAM)
+            .map(|(_, value)| value.into_owned());
+
+        // The token is only sent in the header, not as part of the URL ending up in access logs.
+        let params: Vec<(String, String)> = url
+            .query_pairs()
+            .into_owned()
+            .filter(|(key, _)| key != AUTH_TOKEN_PARAM)
+            .collect();
+
+        if params.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(params);
+        }
+
+        Ok(Self {
+            client: Client::builder().build(HttpsConnector::new()),
+            url: url.into_string().into(),
+            auth_token,
+        })
+    }
+}
+
+#[async_trait]
+impl DriverAdapter for HttpDriver {
+    async fn execute(&self, statement: Statement) -> Result<DriverResultSet, DriverError> {
+        let body = serde_json::to_vec(&statement).map_err(transport_error)?;
+
+        let mut req = Request::post(self.url.expose())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .map_err(transport_error)?;
+
+        if let Some(ref token) = self.auth_token {
+            let value = format!("Bearer {}", token).parse().map_err(transport_error)?;
+            req.headers_mut().insert(header::AUTHORIZATION, value);
+        }
+
+        let res = self.client.request(req).await.map_err(transport_error)?;
+        let status = res.status();
+        let bytes = hyper::body::to_bytes(res.into_body()).await.map_err(transport_error)?;
+
+        if status.is_success() {
+            serde_json::from_slice(&bytes).map_err(transport_error)
+        } else {
+            Err(serde_json::from_slice(&bytes).unwrap_or_else(|_| DriverError {
+                code: None,
+                message: format!("The driver responded with status {}.", status),
+            }))
+        }
+    }
+}
+
+fn transport_error(err: impl std::fmt::Display) -> DriverError {
+    DriverError {
+        code: None,
+        message: err.to_string(),
+    }
+}
@@ -0,0 +1,180 @@
+//! Execution of SQL through a driver adapter instead of a database connection of quaint, for databases that are
+//! only reachable through an API, e.g. serverless SQLite services taking statements over HTTP.
+//!
+//! Queries are still built and rendered by quaint, the adapter only transports the SQL and its parameters, and
+//! returns the rows as JSON.
+mod connection;
+mod http;
+
+pub use http::HttpDriver;
+
+use super::{connection::SqlConnection, InUseCounter};
+use crate::{query_builder::ManyRelatedRecordsWithRowNumber, FromSource, SqlError};
+use async_trait::async_trait;
+use connection::DriverConnection;
+use connector_interface::{Connection, Connector, PoolState, IO};
+use datamodel::Source;
+use prisma_value::TimeZonePolicy;
+use quaint::prelude::ConnectionInfo;
+use secret_string::SecretString;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Name of the schema of the database behind a driver, which qualifies the tables in all queries.
+const DRIVER_DB_NAME: &str = "main";
+
+/// Transport of SQL statements to a database.
+#[async_trait]
+pub trait DriverAdapter: Send + Sync {
+    async fn execute(&self, statement: Statement) -> Result<DriverResultSet, DriverError>;
+}
+
+/// A rendered SQL statement with its parameters.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statement {
+    pub sql: String,
+    pub params: Vec<serde_json::Value>,
+    /// Session of the previous statement of the connection, so that statements of a transaction run on the same
+    /// database connection of the driver.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+}
+
+/// The result of a statement, rows are in the order of the columns.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DriverResultSet {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    pub rows_affected: u64,
+    pub last_insert_id: Option<u64>,
+    /// Session to continue with, if the driver keeps one open, e.g. during a transaction.
+    pub session: Option<String>,
+}
+
+/// An error of the database, or of the transport to it.
+#[derive(Debug, Deserialize)]
+pub struct DriverError {
+    /// Error code of the database, if the statement failed there.
+    #[serde(default)]
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// SQLite behind a driver adapter.
+pub struct DriverSqlite {
+    adapter: Arc<dyn DriverAdapter>,
+    connection_info: ConnectionInfo,
+    time_zone: TimeZonePolicy,
+    in_use: InUseCounter,
+}
+
+impl DriverSqlite {
+    pub fn new(adapter: Arc<dyn DriverAdapter>, url: &SecretString) -> Self {
+        // The connection info ends up in error messages and logs, the query parameters hold credentials, e.g. the
+        // `authToken` of the URL.
+        let file_path = url.expose().splitn(2, '?').next().unwrap_or_default();
+
+        Self {
+            adapter,
+            connection_info: ConnectionInfo::Sqlite {
+                file_path: file_path.to_owned(),
+                db_name: DRIVER_DB_NAME.to_owned(),
+            },
+            time_zone: TimeZonePolicy::default(),
+            in_use: InUseCounter::default(),
+        }
+    }
+
+    /// Stores DateTime values by the given policy instead of in UTC.
+    pub fn time_zone(mut self, time_zone: TimeZonePolicy) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Name of the schema the database is attached as, which qualifies the tables in all queries.
+    pub fn db_name(&self) -> &str {
+        DRIVER_DB_NAME
+    }
+
+    /// Whether the URL of a SQLite data source points to a database reached over HTTP.
+    pub fn handles_url(url: &str) -> bool {
+        url.starts_with("https://") || url.starts_with("http://")
+    }
+}
+
+#[async_trait]
+impl FromSource for DriverSqlite {
+    async fn from_source(source: &dyn Source) -> crate::Result<Self> {
+        let (time_zone, url) = super::time_zone_policy(source.url().value.expose())?;
+        let url = SecretString::from(url);
+        let adapter = HttpDriver::new(&url)?;
+
+        Ok(Self::new(Arc::new(adapter), &url).time_zone(time_zone))
+    }
+}
+
+impl Connector for DriverSqlite {
+    fn get_connection<'a>(&'a self) -> IO<Box<dyn Connection + 'a>> {
+        IO::new(async move {
+            // Connections only hold the session of the driver, there is nothing to open up front.
+            let conn = DriverConnection::new(Arc::clone(&self.adapter));
+
+            let conn = SqlConnection::<_, ManyRelatedRecordsWithRowNumber>::new(
+                Arc::new(conn),
+                None,
+                &self.connection_info,
+                self.time_zone,
+                None,
+                false,
+                self.in_use.acquire(),
+            );
+
+            Ok(Box::new(conn) as Box<dyn Connection>)
+        })
+    }
+
+    /// Statements are requests to the driver, there is no pool limiting them.
+    fn pool_state<'a>(&'a self) -> IO<'a, PoolState> {
+        IO::new(async move {
+            Ok(PoolState {
+                in_use: self.in_use.get(),
+                capacity: usize::max_value(),
+            })
+        })
+    }
+}
+
+impl From<DriverError> for SqlError {
+    fn from(e: DriverError) -> Self {
+        SqlError::from(quaint::error::Error::from(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoDatabase;
+
+    #[async_trait]
+    impl DriverAdapter for NoDatabase {
+        async fn execute(&self, _: Statement) -> Result<DriverResultSet, DriverError> {
+            Ok(DriverResultSet::default())
+        }
+    }
+
+    #[test]
+    fn query_parameters_are_left_out_of_the_connection_info() {
+        let driver = DriverSqlite::new(
+            Arc::new(NoDatabase),
+            &"https://db.example.com/sqlite?authToken=s3cr3t".into(),
+        );
+
+        match driver.connection_info {
+            ConnectionInfo::Sqlite { file_path, .. } => assert_eq!(file_path, "https://db.example.com/sqlite"),
+            _ => unreachable!(),
+        }
+    }
+}
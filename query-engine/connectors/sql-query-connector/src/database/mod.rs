@@ -1,4 +1,6 @@
+mod cancellation;
 mod connection;
+mod driver;
 mod mysql;
 mod postgresql;
 mod sqlite;
@@ -6,10 +8,18 @@ mod transaction;
 
 pub(crate) mod operations;
 
+use crate::SqlError;
 use async_trait::async_trait;
-use connector_interface::{error::ConnectorError, Connector};
+use connector_interface::{error::ConnectorError, Connector, PoolState};
 use datamodel::Source;
+use prisma_value::TimeZonePolicy;
+use quaint::pooled::Quaint;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
+pub use driver::*;
 pub use mysql::*;
 pub use postgresql::*;
 pub use sqlite::*;
@@ -30,3 +40,136 @@ async fn catch<O>(
         Err(err) => Err(err.into_connector_error(connection_info)),
     }
 }
+
+/// Splits the time zone policy off the connection string, see `TimeZonePolicy`.
+fn time_zone_policy(url: &str) -> crate::Result<(TimeZonePolicy, String)> {
+    TimeZonePolicy::from_url(url).map_err(SqlError::InvalidConnectionString)
+}
+
+/// Connection string parameter capping the number of parents read by a single related records statement.
+const MAX_RELATION_FAN_OUT_PARAM: &str = "max_relation_fan_out";
+
+/// Splits the maximum relation fan-out off the connection string. Related records of more parents are read in
+/// chunks, which keeps the `UNION ALL` statements of paginated relations on MySQL at a bounded size.
+fn max_relation_fan_out(url: &str) -> crate::Result<(Option<usize>, String)> {
+    let mut splitted = url.splitn(2, '?');
+    let base = splitted.next().unwrap();
+    let query = splitted.next().unwrap_or("");
+
+    let mut max_fan_out = None;
+    let mut params = Vec::new();
+
+    for param in query.split('&').filter(|param| !param.is_empty()) {
+        let mut parts = param.splitn(2, '=');
+
+        if parts.next() == Some(MAX_RELATION_FAN_OUT_PARAM) {
+            let value = parts.next().unwrap_or("");
+            let parsed = value.parse::<usize>().ok().filter(|max| *max > 0).ok_or_else(|| {
+                SqlError::InvalidConnectionString(format!(
+                    "Invalid {} '{}', expected a positive number.",
+                    MAX_RELATION_FAN_OUT_PARAM, value
+                ))
+            })?;
+
+            max_fan_out = Some(parsed);
+        } else {
+            params.push(param);
+        }
+    }
+
+    if params.is_empty() {
+        Ok((max_fan_out, base.to_owned()))
+    } else {
+        Ok((max_fan_out, format!("{}?{}", base, params.join("&"))))
+    }
+}
+
+async fn pool_state(pool: &Quaint, in_use: &InUseCounter) -> PoolState {
+    PoolState {
+        in_use: in_use.get(),
+        capacity: pool.capacity().await as usize,
+    }
+}
+
+/// Counts the connections currently checked out of a connector's pool.
+#[derive(Debug, Default)]
+pub(crate) struct InUseCounter(Arc<AtomicUsize>);
+
+impl InUseCounter {
+    pub(crate) fn acquire(&self) -> InUseGuard {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        InUseGuard(Arc::clone(&self.0))
+    }
+
+    pub(crate) fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Marks a connection as in use until dropped.
+#[derive(Debug)]
+pub(crate) struct InUseGuard(Arc<AtomicUsize>);
+
+impl Drop for InUseGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_max_relation_fan_out_is_split_off_the_connection_string() {
+        let (max_fan_out, url) =
+            max_relation_fan_out("mysql://localhost:3306/db?max_relation_fan_out=500&connection_limit=5").unwrap();
+
+        assert_eq!(max_fan_out, Some(500));
+        assert_eq!(url, "mysql://localhost:3306/db?connection_limit=5");
+
+        let (max_fan_out, url) = max_relation_fan_out("mysql://localhost:3306/db?max_relation_fan_out=500").unwrap();
+
+        assert_eq!(max_fan_out, Some(500));
+        assert_eq!(url, "mysql://localhost:3306/db");
+    }
+
+    #[test]
+    fn connection_strings_without_a_max_relation_fan_out_are_kept() {
+        let url = "mysql://localhost:3306/db?connection_limit=5&socket_timeout=10";
+
+        assert_eq!(max_relation_fan_out(url).unwrap(), (None, url.to_owned()));
+        assert_eq!(
+            max_relation_fan_out("mysql://localhost:3306/db").unwrap(),
+            (None, "mysql://localhost:3306/db".to_owned())
+        );
+    }
+
+    #[test]
+    fn the_max_relation_fan_out_must_be_a_positive_number() {
+        for url in &[
+            "mysql://localhost/db?max_relation_fan_out=0",
+            "mysql://localhost/db?max_relation_fan_out=-1",
+            "mysql://localhost/db?max_relation_fan_out=many",
+            "mysql://localhost/db?max_relation_fan_out",
+        ] {
+            assert!(max_relation_fan_out(url).is_err(), "{} must be rejected", url);
+        }
+    }
+
+    #[test]
+    fn connections_are_counted_as_in_use_until_dropped() {
+        let counter = InUseCounter::default();
+        assert_eq!(counter.get(), 0);
+
+        let first = counter.acquire();
+        let second = counter.acquire();
+        assert_eq!(counter.get(), 2);
+
+        drop(first);
+        assert_eq!(counter.get(), 1);
+
+        drop(second);
+        assert_eq!(counter.get(), 0);
+    }
+}
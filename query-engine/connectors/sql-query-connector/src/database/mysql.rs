@@ -1,31 +1,123 @@
-use super::connection::SqlConnection;
+use super::{cancellation::Cancellation, connection::SqlConnection, InUseCounter};
 use crate::{query_builder::ManyRelatedRecordsWithUnionAll, FromSource, SqlError};
 use async_trait::async_trait;
-use connector_interface::{Connection, Connector, IO};
+use connector_interface::{Connection, Connector, PoolState, IO};
 use datamodel::Source;
-use quaint::pooled::Quaint;
+use prisma_value::TimeZonePolicy;
+use quaint::{connector::Queryable, pooled::Quaint};
+use std::sync::Arc;
 
 pub struct Mysql {
     pool: Quaint,
+    /// Connection string for the dedicated connections cancelling statements.
+    url: Arc<str>,
     connection_info: quaint::prelude::ConnectionInfo,
+    time_zone: TimeZonePolicy,
+    max_relation_fan_out: Option<usize>,
+    /// MariaDB returns the ids of inserted rows with `RETURNING` since 10.5, MySQL doesn't support the clause.
+    returning: bool,
+    in_use: InUseCounter,
 }
 
 #[async_trait]
 impl FromSource for Mysql {
     async fn from_source(source: &dyn Source) -> crate::Result<Self> {
-        let pool = Quaint::new(&source.url().value).await?;
+        let (time_zone, url) = super::time_zone_policy(source.url().value.expose())?;
+        let (max_relation_fan_out, url) = super::max_relation_fan_out(&url)?;
+        let pool = Quaint::new(&url).await?;
         let connection_info = pool.connection_info().to_owned();
-        Ok(Mysql { pool, connection_info })
+        let returning = supports_returning(&pool).await?;
+
+        Ok(Mysql {
+            pool,
+            url: url.into(),
+            connection_info,
+            time_zone,
+            max_relation_fan_out,
+            returning,
+            in_use: InUseCounter::default(),
+        })
     }
 }
 
 impl Connector for Mysql {
     fn get_connection<'a>(&'a self) -> IO<Box<dyn Connection + 'a>> {
         IO::new(super::catch(&self.connection_info, async move {
-            let conn = self.pool.check_out().await.map_err(SqlError::from)?;
-            let conn = SqlConnection::<_, ManyRelatedRecordsWithUnionAll>::new(conn, &self.connection_info);
+            let conn = Arc::new(self.pool.check_out().await.map_err(SqlError::from)?);
+            let cancellation = Cancellation::mysql(&self.url, Arc::clone(&conn)).await;
+
+            // `TIMESTAMP` columns are converted from and to the time zone of the session, `DATETIME` columns are
+            // not. With the session at the offset of the policy, both hold the same wall clock time.
+            let set_time_zone = format!("SET time_zone = '{}'", self.time_zone.session_offset());
+            conn.execute_raw(&set_time_zone, &[]).await.map_err(SqlError::from)?;
+
+            let conn = SqlConnection::<_, ManyRelatedRecordsWithUnionAll>::new(
+                conn,
+                cancellation,
+                &self.connection_info,
+                self.time_zone,
+                self.max_relation_fan_out,
+                self.returning,
+                self.in_use.acquire(),
+            );
 
             Ok(Box::new(conn) as Box<dyn Connection>)
         }))
     }
+
+    fn pool_state<'a>(&'a self) -> IO<'a, PoolState> {
+        IO::new(async move { Ok(super::pool_state(&self.pool, &self.in_use).await) })
+    }
+}
+
+async fn supports_returning(pool: &Quaint) -> crate::Result<bool> {
+    let conn = pool.check_out().await?;
+    let rows = conn.query_raw("SELECT @@GLOBAL.version version", &[]).await?;
+
+    let version = rows
+        .get(0)
+        .and_then(|row| row.get("version").and_then(|version| version.to_string()));
+
+    Ok(version
+        .map(|version| is_mariadb_with_returning(&version))
+        .unwrap_or(false))
+}
+
+/// Whether the server version is the one of MariaDB 10.5 or later.
+fn is_mariadb_with_returning(version: &str) -> bool {
+    if !version.contains("MariaDB") {
+        return false;
+    }
+
+    // The version can be prefixed with `5.5.5-` for clients expecting a MySQL version, e.g. `5.5.5-10.5.8-MariaDB`.
+    let version = version.trim_start_matches("5.5.5-");
+    let mut numbers = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|n| n.parse::<u32>().unwrap_or(0));
+
+    let major = numbers.next().unwrap_or(0);
+    let minor = numbers.next().unwrap_or(0);
+
+    (major, minor) >= (10, 5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mariadb_supports_returning_since_10_5() {
+        assert!(is_mariadb_with_returning("10.5.8-MariaDB-1:10.5.8+maria~focal"));
+        assert!(is_mariadb_with_returning("11.0.2-MariaDB"));
+        assert!(is_mariadb_with_returning("5.5.5-10.6.4-MariaDB-log"));
+
+        assert!(!is_mariadb_with_returning("10.4.17-MariaDB-1:10.4.17+maria~focal"));
+        assert!(!is_mariadb_with_returning("5.5.5-10.0.38-MariaDB"));
+    }
+
+    #[test]
+    fn mysql_doesnt_support_returning() {
+        assert!(!is_mariadb_with_returning("8.0.22"));
+        assert!(!is_mariadb_with_returning("5.7.32-log"));
+    }
 }
@@ -1,16 +1,19 @@
 use crate::{
     query_builder::{
-        self,
+        self, idempotency,
         read::{self, ManyRelatedRecordsBaseQuery, ManyRelatedRecordsQueryBuilder},
+        routine::{self, RoutineRow},
     },
-    QueryExt, SqlError,
+    sql_span, QueryExt, SqlError,
 };
 use connector_interface::*;
 use prisma_models::*;
-use quaint::ast::*;
+use quaint::{ast::*, prelude::ConnectionInfo};
+use tracing_futures::Instrument;
 
 pub async fn get_single_record(
     conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
     model: &ModelRef,
     filter: &Filter,
     selected_fields: &SelectedFields,
@@ -18,9 +21,18 @@ pub async fn get_single_record(
     let query = read::get_records(&model, selected_fields.columns(), filter);
     let field_names = selected_fields.db_names().map(String::from).collect();
     let idents: Vec<_> = selected_fields.types().collect();
+    let computed = selected_fields.computed_fields();
 
-    let record = (match conn.find(query, idents.as_slice()).await {
-        Ok(result) => Ok(Some(result)),
+    let record = (match conn
+        .filter_computed(
+            query.limit(1).into(),
+            idents.as_slice(),
+            &computed,
+            connection_info.sql_family(),
+        )
+        .await
+    {
+        Ok(rows) => Ok(rows.into_iter().next()),
         Err(_e @ SqlError::RecordNotFoundForWhere(_)) => Ok(None),
         Err(_e @ SqlError::RecordDoesNotExist) => Ok(None),
         Err(e) => Err(e),
@@ -33,26 +45,109 @@ pub async fn get_single_record(
 
 pub async fn get_many_records(
     conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
     model: &ModelRef,
     query_arguments: QueryArguments,
     selected_fields: &SelectedFields,
 ) -> crate::Result<ManyRecords> {
     let field_names = selected_fields.db_names().map(String::from).collect();
     let idents: Vec<_> = selected_fields.types().collect();
+    let computed = selected_fields.computed_fields();
+    let reversed = query_arguments.needs_reversed_order();
     let query = read::get_records(model, selected_fields.columns(), query_arguments);
 
-    let records = conn
-        .filter(query.into(), idents.as_slice())
+    let mut records: Vec<Record> = conn
+        .filter_computed(query.into(), idents.as_slice(), &computed, connection_info.sql_family())
         .await?
         .into_iter()
         .map(Record::from)
         .collect();
 
+    if reversed {
+        records.reverse();
+    }
+
     Ok(ManyRecords { records, field_names })
 }
 
+/// Reads the related records of the given parents. With more parents than `max_fan_out`, the parent ids are split
+/// into chunks of at most `max_fan_out` ids, read by one statement each.
 pub async fn get_related_records<T>(
     conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
+    from_field: &RelationFieldRef,
+    from_record_ids: &[RecordIdentifier],
+    query_arguments: QueryArguments,
+    selected_fields: &SelectedFields,
+    max_fan_out: Option<usize>,
+) -> crate::Result<ManyRecords>
+where
+    T: ManyRelatedRecordsQueryBuilder,
+{
+    let reversed = query_arguments.needs_reversed_order();
+    let mut result = get_related_records_in_chunks::<T>(
+        conn,
+        connection_info,
+        from_field,
+        from_record_ids,
+        query_arguments,
+        selected_fields,
+        max_fan_out,
+    )
+    .await?;
+
+    // The records of every parent are in reverse order, reversing all of them restores the order of each parent.
+    if reversed {
+        result.records.reverse();
+    }
+
+    Ok(result)
+}
+
+async fn get_related_records_in_chunks<T>(
+    conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
+    from_field: &RelationFieldRef,
+    from_record_ids: &[RecordIdentifier],
+    query_arguments: QueryArguments,
+    selected_fields: &SelectedFields,
+    max_fan_out: Option<usize>,
+) -> crate::Result<ManyRecords>
+where
+    T: ManyRelatedRecordsQueryBuilder,
+{
+    let mut result = ManyRecords::default();
+
+    for chunk in fan_out_chunks(from_record_ids, max_fan_out) {
+        let records = get_related_records_chunk::<T>(
+            conn,
+            connection_info,
+            from_field,
+            chunk,
+            query_arguments.clone(),
+            selected_fields,
+        )
+        .await?;
+
+        result.field_names = records.field_names;
+        result.records.extend(records.records);
+    }
+
+    Ok(result)
+}
+
+/// The parent ids read by one statement each. Without more parents than `max_fan_out` all of them are read at once,
+/// there is always at least one statement, which also names the selected fields.
+fn fan_out_chunks<T>(ids: &[T], max_fan_out: Option<usize>) -> Vec<&[T]> {
+    match max_fan_out {
+        Some(max_fan_out) if max_fan_out > 0 && ids.len() > max_fan_out => ids.chunks(max_fan_out).collect(),
+        _ => vec![ids],
+    }
+}
+
+async fn get_related_records_chunk<T>(
+    conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
     from_field: &RelationFieldRef,
     from_record_ids: &[RecordIdentifier],
     query_arguments: QueryArguments,
@@ -61,15 +156,24 @@ pub async fn get_related_records<T>(
 where
     T: ManyRelatedRecordsQueryBuilder,
 {
-    // Todo: Does this work with relation fields that are backed by multiple fields?
-    // Q: What is the TypeIdentifier::Relation actually doing?
-    // Q: The following code simply queries both field sides, is this correct?
-    //    Columns that don't exist are ignored? Iterator is empty?
-    // Q: Additionally: field names contains always both, isn't that breaking the above assumption?
+    // The relation columns hold the fields linking the parent to the related records: The primary ID of the parent if
+    // the relation is inlined in the parent or a many-to-many relation, otherwise the fields referenced by the foreign
+    // key of the related records. The opposite columns hold the fields of the related records they are joined on.
+    let (parent_link_fields, child_link_fields) = if from_field.relation_is_inlined_in_parent() {
+        (
+            from_field.model().primary_identifier(),
+            from_field.related_field().linking_fields(),
+        )
+    } else {
+        (
+            from_field.linking_fields(),
+            from_field.related_model().primary_identifier(),
+        )
+    };
+
     let mut idents: Vec<_> = selected_fields.types().collect();
-    idents.extend(from_field.related_field().type_identifiers_with_arities());
-    idents.extend(from_field.linking_fields().type_identifiers_with_arities());
-    idents.extend(from_field.linking_fields().type_identifiers_with_arities());
+    idents.extend(child_link_fields.type_identifiers_with_arities());
+    idents.extend(parent_link_fields.type_identifiers_with_arities());
 
     let field_names: Vec<String> = selected_fields
         .db_names()
@@ -117,41 +221,42 @@ where
         }
     };
 
+    let parent_link_dsfs: Vec<DataSourceFieldRef> = parent_link_fields.data_source_fields().collect();
+    let child_link_count = child_link_fields.data_source_fields().count();
+
+    let computed = selected_fields.computed_fields();
+    let rewrite = if is_with_pagination { T::sql_rewrite() } else { None };
     let records: crate::Result<Vec<Record>> = conn
-        .filter(query, idents.as_slice())
+        .filter_rewritten(
+            query,
+            idents.as_slice(),
+            &computed,
+            connection_info.sql_family(),
+            rewrite,
+        )
         .await?
         .into_iter()
         .map(|mut row| {
-            let relation_cols = from_field.relation_columns(true);
-            let mut parent_ids: Vec<(DataSourceFieldRef, PrismaValue)> = Vec::with_capacity(relation_cols.len());
+            let mut parent_ids: Vec<(DataSourceFieldRef, PrismaValue)> = Vec::with_capacity(parent_link_dsfs.len());
 
             if is_with_pagination && T::uses_row_number() {
                 let _ = row.values.pop();
             }
-            // Todo: This doesn't work with @relation(references ...), it assumes primary ids.
-            // parent id is always the last column
-            for field in from_field.linking_fields().fields() {
-                match field {
-                    Field::Scalar(sf) => {
-                        let val = row.values.pop().ok_or(SqlError::ColumnDoesNotExist)?;
-                        parent_ids.push((sf.data_source_field().clone(), val))
-                    }
-                    Field::Relation(rf) => {
-                        for field in rf.data_source_fields().iter() {
-                            let val = row.values.pop().ok_or(SqlError::ColumnDoesNotExist)?;
-                            parent_ids.push((field.clone(), val))
-                        }
-                    }
-                }
-            }
 
-            // ModelIdentifier fields are in the end, we pop them in reverse
+            // The parent link values are the last columns, we pop them in reverse
             // order so we should flip them before returning.
+            for field in parent_link_dsfs.iter().rev() {
+                let val = row.values.pop().ok_or(SqlError::ColumnDoesNotExist)?;
+                parent_ids.push((field.clone(), val));
+            }
+
             parent_ids.reverse();
 
-            // Relation id is always the second last value. We don't need it
-            // here and we don't need it in the record.
-            let _ = row.values.pop();
+            // The values of the opposite columns are second last. We don't need them
+            // here and we don't need them in the record.
+            for _ in 0..child_link_count {
+                let _ = row.values.pop();
+            }
 
             let mut record = Record::from(row);
 
@@ -181,3 +286,85 @@ pub async fn count_by_model(
 
     Ok(result)
 }
+
+/// Lists the routines of the schema of the connection, see `routine::group_routines`.
+pub async fn list_routines(conn: &dyn QueryExt, connection_info: &ConnectionInfo) -> crate::Result<Vec<Routine>> {
+    let query = match routine::list_routines(connection_info.sql_family()) {
+        Some(query) => query,
+        None => return Ok(Vec::new()),
+    };
+
+    let parameters = [ParameterizedValue::from(connection_info.schema_name())];
+    let result_set = conn
+        .query_raw(query, &parameters)
+        .instrument(sql_span("query_raw"))
+        .await?;
+
+    let rows = result_set.into_iter().map(|row| {
+        let column = |name: &str| row.get(name).and_then(|value| value.to_string());
+
+        RoutineRow {
+            specific_name: column("specific_name").unwrap_or_default(),
+            routine_name: column("routine_name").unwrap_or_default(),
+            routine_type: column("routine_type"),
+            parameter_name: column("parameter_name"),
+            parameter_mode: column("parameter_mode"),
+            data_type: column("data_type"),
+            udt_name: column("udt_name"),
+        }
+    });
+
+    Ok(routine::group_routines(rows))
+}
+
+pub async fn get_idempotency_record(
+    conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
+    key: &str,
+) -> crate::Result<Option<IdempotencyRecord>> {
+    let query = idempotency::get_record(connection_info.schema_name(), key);
+    let result_set = conn.query(query.into()).instrument(sql_span("query")).await?;
+
+    let row = match result_set.into_iter().next() {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    let column = |name: &str| row.get(name).and_then(|value| value.to_string()).unwrap_or_default();
+    let response = serde_json::from_str(&column("response")).map_err(|err| SqlError::ColumnReadFailure(err.into()))?;
+
+    Ok(Some(IdempotencyRecord {
+        key: key.to_owned(),
+        request_hash: column("request_hash"),
+        response,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parents_are_read_at_once_up_to_the_max_fan_out() {
+        let ids = [1, 2, 3];
+
+        assert_eq!(fan_out_chunks(&ids, None), vec![&ids[..]]);
+        assert_eq!(fan_out_chunks(&ids, Some(3)), vec![&ids[..]]);
+        assert_eq!(fan_out_chunks(&ids, Some(10)), vec![&ids[..]]);
+    }
+
+    #[test]
+    fn more_parents_than_the_max_fan_out_are_read_in_chunks() {
+        let ids = [1, 2, 3, 4, 5];
+
+        assert_eq!(fan_out_chunks(&ids, Some(2)), vec![&[1, 2][..], &[3, 4][..], &[5][..]]);
+        assert_eq!(fan_out_chunks(&ids, Some(1)).len(), 5);
+    }
+
+    #[test]
+    fn without_parents_one_statement_is_read() {
+        let ids: [i32; 0] = [];
+
+        assert_eq!(fan_out_chunks(&ids, Some(2)), vec![&ids[..]]);
+    }
+}
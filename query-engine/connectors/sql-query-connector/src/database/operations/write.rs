@@ -1,46 +1,38 @@
-use crate::{error::SqlError, query_builder::write, QueryExt, RawQuery};
+use crate::{
+    error::SqlError,
+    query_builder::{idempotency, json_merge, routine, write},
+    sql_span, QueryExt, RawQuery,
+};
 use connector_interface::*;
 use prisma_models::*;
 use prisma_value::PrismaValue;
-use quaint::error::ErrorKind;
-use std::convert::TryFrom;
-use user_facing_errors::query_engine::DatabaseConstraint;
+use quaint::prelude::ConnectionInfo;
+use std::{collections::HashMap, convert::TryFrom};
+use tracing_futures::Instrument;
 
-pub async fn create_record(conn: &dyn QueryExt, model: &ModelRef, args: WriteArgs) -> crate::Result<RecordIdentifier> {
+/// Creates the record and returns its id. With `returning`, the id is returned by a `RETURNING` clause on MySQL, for
+/// ids generated by defaults of the database that `LAST_INSERT_ID()` doesn't return.
+pub async fn create_record(
+    conn: &dyn QueryExt,
+    model: &ModelRef,
+    args: WriteArgs,
+    returning: bool,
+) -> crate::Result<RecordIdentifier> {
     let (insert, returned_id) = write::create_record(model, args);
 
-    let result_set = match conn.insert(insert).await {
-        Ok(id) => id,
-        Err(e) => match e.kind() {
-            ErrorKind::UniqueConstraintViolation { constraint } => match constraint {
-                quaint::error::DatabaseConstraint::Index(name) => {
-                    let constraint = DatabaseConstraint::Index(name.clone());
-                    return Err(SqlError::UniqueConstraintViolation { constraint });
-                }
-                quaint::error::DatabaseConstraint::Fields(fields) => {
-                    let constraint = DatabaseConstraint::Fields(fields.clone());
-                    return Err(SqlError::UniqueConstraintViolation { constraint });
-                }
-            },
-            ErrorKind::NullConstraintViolation { constraint } => match constraint {
-                quaint::error::DatabaseConstraint::Index(name) => {
-                    let constraint = DatabaseConstraint::Index(name.clone());
-                    return Err(SqlError::NullConstraintViolation { constraint });
-                }
-                quaint::error::DatabaseConstraint::Fields(fields) => {
-                    let constraint = DatabaseConstraint::Fields(fields.clone());
-                    return Err(SqlError::NullConstraintViolation { constraint });
-                }
-            },
-            _ => return Err(SqlError::from(e)),
-        },
-    };
+    let result_set = if returning {
+        let (sql, params) = write::mysql_insert_returning(model, insert);
+        conn.query_raw(&sql, &params).instrument(sql_span("insert")).await
+    } else {
+        conn.insert(insert).instrument(sql_span("insert")).await
+    }
+    .map_err(|err| SqlError::from(err).with_model(model))?;
 
     match (returned_id, result_set.len(), result_set.last_insert_id()) {
         // All values provided in the write arrghs
         (Some(identifier), _, _) if !identifier.misses_autogen_value() => Ok(identifier),
 
-        // PostgreSQL with a working RETURNING statement
+        // PostgreSQL or MariaDB with a working RETURNING statement
         (_, n, _) if n > 0 => Ok(RecordIdentifier::try_from((&model.primary_identifier(), result_set))?),
 
         // We have an auto-incremented id that we got from MySQL or SQLite
@@ -55,9 +47,10 @@ pub async fn create_record(conn: &dyn QueryExt, model: &ModelRef, args: WriteArg
 
 pub async fn update_records(
     conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
     model: &ModelRef,
     where_: Filter,
-    args: WriteArgs,
+    mut args: WriteArgs,
 ) -> crate::Result<Vec<RecordIdentifier>> {
     let ids = conn.filter_ids(model, where_.clone()).await?;
 
@@ -65,13 +58,28 @@ pub async fn update_records(
         return Ok(vec![]);
     }
 
-    let updates = {
+    let patches = std::mem::replace(&mut args.json_patches, HashMap::new());
+
+    let (updates, merges) = {
         let ids: Vec<&RecordIdentifier> = ids.iter().map(|id| &*id).collect();
-        write::update_many(model, ids.as_slice(), args)?
+        let updates = write::update_many(model, ids.as_slice(), args)?;
+        let merges = json_merge::merge_json(model, ids.as_slice(), &patches, connection_info.sql_family());
+
+        (updates, merges)
     };
 
     for update in updates {
-        conn.query(update).await?;
+        conn.query(update)
+            .instrument(sql_span("update"))
+            .await
+            .map_err(|err| SqlError::from(err).with_model(model))?;
+    }
+
+    for merge in merges {
+        conn.execute_raw(merge.query(), merge.parameters())
+            .instrument(sql_span("update"))
+            .await
+            .map_err(|err| SqlError::from(err).with_model(model))?;
     }
 
     Ok(ids)
@@ -87,7 +95,7 @@ pub async fn delete_records(conn: &dyn QueryExt, model: &ModelRef, where_: Filte
     }
 
     for delete in write::delete_many(model, ids.as_slice()) {
-        conn.query(delete).await?;
+        conn.query(delete).instrument(sql_span("delete")).await?;
     }
 
     Ok(count)
@@ -101,7 +109,7 @@ pub async fn connect(
 ) -> crate::Result<()> {
     let query = write::create_relation_table_records(field, parent_id, child_ids);
 
-    conn.query(query).await?;
+    conn.query(query).instrument(sql_span("insert")).await?;
     Ok(())
 }
 
@@ -112,7 +120,25 @@ pub async fn disconnect(
     child_ids: &[RecordIdentifier],
 ) -> crate::Result<()> {
     let query = write::delete_relation_table_records(field, parent_id, child_ids);
-    conn.delete(query).await?;
+    conn.delete(query).instrument(sql_span("delete")).await?;
+
+    Ok(())
+}
+
+pub async fn disconnect_all(
+    conn: &dyn QueryExt,
+    field: &RelationFieldRef,
+    parent_ids: &[RecordIdentifier],
+) -> crate::Result<()> {
+    if parent_ids.is_empty() {
+        return Ok(());
+    }
+
+    let parent_ids: Vec<&RecordIdentifier> = parent_ids.iter().collect();
+
+    for delete in write::delete_all_relation_table_records(field, parent_ids.as_slice()) {
+        conn.query(delete).instrument(sql_span("delete")).await?;
+    }
 
     Ok(())
 }
@@ -125,3 +151,55 @@ pub async fn execute_raw(
     let value = conn.raw_json(RawQuery::new(query, parameters)).await?;
     Ok(value)
 }
+
+pub async fn call_routine(
+    conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
+    routine: &Routine,
+    arguments: Vec<PrismaValue>,
+) -> crate::Result<RoutineResult> {
+    let query = RawQuery::new(
+        routine::call_routine(routine, connection_info.schema_name(), connection_info.sql_family()),
+        arguments,
+    );
+
+    let result_set = conn.raw_rows(query).await?;
+
+    let columns = result_set.columns().iter().map(ToString::to_string).collect();
+    let rows = result_set
+        .into_iter()
+        .map(|row| row.into_iter().map(PrismaValue::from).collect())
+        .collect();
+
+    Ok(RoutineResult { columns, rows })
+}
+
+pub async fn create_idempotency_table(conn: &dyn QueryExt, connection_info: &ConnectionInfo) -> crate::Result<()> {
+    let query = idempotency::create_table(connection_info.schema_name(), connection_info.sql_family());
+    conn.execute_raw(&query, &[])
+        .instrument(sql_span("execute_raw"))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_expired_idempotency_records(
+    conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
+) -> crate::Result<()> {
+    let delete = idempotency::delete_expired_records(connection_info.schema_name());
+    conn.delete(delete).instrument(sql_span("delete")).await?;
+
+    Ok(())
+}
+
+pub async fn store_idempotency_record(
+    conn: &dyn QueryExt,
+    connection_info: &ConnectionInfo,
+    record: IdempotencyRecord,
+) -> crate::Result<()> {
+    let insert = idempotency::store_record(connection_info.schema_name(), record);
+    conn.insert(insert).instrument(sql_span("insert")).await?;
+
+    Ok(())
+}
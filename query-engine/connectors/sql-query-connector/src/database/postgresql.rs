@@ -1,31 +1,86 @@
-use super::connection::SqlConnection;
-use crate::{query_builder::ManyRelatedRecordsWithRowNumber, FromSource, SqlError};
+use super::{cancellation::Cancellation, connection::SqlConnection, InUseCounter};
+use crate::{
+    query_builder::{ManyRelatedRecordsWithLateralJoin, ManyRelatedRecordsWithRowNumber},
+    FromSource, SqlError,
+};
 use async_trait::async_trait;
-use connector_interface::{Connection, Connector, IO};
+use connector_interface::{Connection, Connector, ConnectorCapabilities, PoolState, IO};
 use datamodel::Source;
+use prisma_value::TimeZonePolicy;
 use quaint::pooled::Quaint;
+use std::sync::Arc;
 
 pub struct PostgreSql {
     pool: Quaint,
+    /// Connection string for the dedicated connections cancelling statements.
+    url: Arc<str>,
     connection_info: quaint::prelude::ConnectionInfo,
+    time_zone: TimeZonePolicy,
+    capabilities: ConnectorCapabilities,
+    in_use: InUseCounter,
+}
+
+impl PostgreSql {
+    /// Paginates related records with `LATERAL` joins if the capabilities include them, with window functions
+    /// otherwise.
+    pub fn capabilities(mut self, capabilities: ConnectorCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
 }
 
 #[async_trait]
 impl FromSource for PostgreSql {
     async fn from_source(source: &dyn Source) -> crate::Result<Self> {
-        let pool = Quaint::new(&source.url().value).await?;
+        let (time_zone, url) = super::time_zone_policy(source.url().value.expose())?;
+        let pool = Quaint::new(&url).await?;
         let connection_info = pool.connection_info().to_owned();
-        Ok(PostgreSql { pool, connection_info })
+        Ok(PostgreSql {
+            pool,
+            url: url.into(),
+            connection_info,
+            time_zone,
+            capabilities: ConnectorCapabilities::empty(),
+            in_use: InUseCounter::default(),
+        })
     }
 }
 
 impl Connector for PostgreSql {
     fn get_connection<'a>(&'a self) -> IO<Box<dyn Connection + 'a>> {
         IO::new(super::catch(&self.connection_info, async move {
-            let conn = self.pool.check_out().await.map_err(SqlError::from)?;
-            let conn = SqlConnection::<_, ManyRelatedRecordsWithRowNumber>::new(conn, &self.connection_info);
+            let conn = Arc::new(self.pool.check_out().await.map_err(SqlError::from)?);
+            let cancellation = Cancellation::postgres(&self.url, Arc::clone(&conn)).await;
+
+            if self.capabilities.contains(ConnectorCapabilities::LATERAL_JOINS) {
+                let conn = SqlConnection::<_, ManyRelatedRecordsWithLateralJoin>::new(
+                    conn,
+                    cancellation,
+                    &self.connection_info,
+                    self.time_zone,
+                    None,
+                    false,
+                    self.in_use.acquire(),
+                );
 
-            Ok(Box::new(conn) as Box<dyn Connection>)
+                Ok(Box::new(conn) as Box<dyn Connection>)
+            } else {
+                let conn = SqlConnection::<_, ManyRelatedRecordsWithRowNumber>::new(
+                    conn,
+                    cancellation,
+                    &self.connection_info,
+                    self.time_zone,
+                    None,
+                    false,
+                    self.in_use.acquire(),
+                );
+
+                Ok(Box::new(conn) as Box<dyn Connection>)
+            }
         }))
     }
+
+    fn pool_state<'a>(&'a self) -> IO<'a, PoolState> {
+        IO::new(async move { Ok(super::pool_state(&self.pool, &self.in_use).await) })
+    }
 }
@@ -1,14 +1,37 @@
-use super::connection::SqlConnection;
+use super::{connection::SqlConnection, InUseCounter};
 use crate::{query_builder::ManyRelatedRecordsWithRowNumber, FromSource, SqlError};
 use async_trait::async_trait;
-use connector_interface::{Connection, Connector, IO};
+use connector_interface::{Connection, Connector, PoolState, IO};
 use datamodel::Source;
-use quaint::{connector::SqliteParams, pooled::Quaint, prelude::ConnectionInfo};
-use std::convert::TryFrom;
+use prisma_value::TimeZonePolicy;
+use quaint::{
+    connector::{Queryable, SqliteParams},
+    pooled::{PooledConnection, Quaint},
+    prelude::ConnectionInfo,
+};
+use std::{convert::TryFrom, sync::Arc};
+
+/// File path of in-memory databases, e.g. `file::memory:?cache=shared`.
+const MEMORY_FILE_PATH: &str = ":memory:";
+
+/// Name of the schema an in-memory database is attached as.
+const MEMORY_DB_NAME: &str = "memory";
+
+/// Prefix of the connection string parameters attaching additional database files, e.g. `attach.audit=./audit.db`
+/// makes the tables of `./audit.db` available as `audit.<table>`.
+const ATTACH_PARAM_PREFIX: &str = "attach.";
+
+/// Schema names SQLite reserves for the database of the connection and its temporary tables.
+const RESERVED_SCHEMA_NAMES: &[&str] = &["main", "temp"];
 
 pub struct Sqlite {
     pool: Quaint,
     file_path: String,
+    db_name: String,
+    /// Names and file paths of the additional databases attached to every connection.
+    attachments: Vec<(String, String)>,
+    time_zone: TimeZonePolicy,
+    in_use: InUseCounter,
 }
 
 impl Sqlite {
@@ -16,6 +39,11 @@ impl Sqlite {
         self.file_path.as_str()
     }
 
+    /// Name of the schema the database is attached as, which qualifies the tables in all queries.
+    pub fn db_name(&self) -> &str {
+        self.db_name.as_str()
+    }
+
     fn connection_info(&self) -> &ConnectionInfo {
         self.pool.connection_info()
     }
@@ -24,36 +52,66 @@ impl Sqlite {
 #[async_trait]
 impl FromSource for Sqlite {
     async fn from_source(source: &dyn Source) -> crate::Result<Sqlite> {
-        let params = SqliteParams::try_from(source.url().value.as_str())?;
+        let (time_zone, url) = super::time_zone_policy(source.url().value.expose())?;
+        let params = SqliteParams::try_from(url.as_str())?;
 
         let file_path = params.file_path;
+        let in_memory = file_path == MEMORY_FILE_PATH;
 
-        let url_with_db = {
-            let db_name = std::path::Path::new(&file_path)
+        let db_name = if in_memory {
+            MEMORY_DB_NAME.to_owned()
+        } else {
+            std::path::Path::new(&file_path)
                 .file_stem()
                 .unwrap()
                 .to_str()
                 .unwrap()
-                .to_owned();
+                .to_owned()
+        };
 
-            let mut splitted = source.url().value.split("?");
-            let url = splitted.next().unwrap();
-            let params = splitted.next();
+        let mut splitted = url.splitn(2, '?');
+        let url = splitted.next().unwrap();
+        let query = splitted.next().unwrap_or("");
 
-            let mut params: Vec<&str> = match params {
-                Some(params) => params.split("&").collect(),
-                None => Vec::with_capacity(1),
-            };
+        let mut params = Vec::new();
+        let mut attachments = Vec::new();
 
-            let db_name_param = format!("db_name={}", db_name);
-            params.push(&db_name_param);
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            if param.starts_with(ATTACH_PARAM_PREFIX) {
+                let (name, path) = parse_attachment(&param[ATTACH_PARAM_PREFIX.len()..])?;
 
-            format!("{}?{}", url, params.join("&"))
-        };
+                if name == db_name {
+                    return Err(SqlError::InvalidConnectionString(format!(
+                        "Can't attach a database as `{}`, the database itself is attached under that name.",
+                        name
+                    )));
+                }
+
+                attachments.push((name, path));
+            } else if !(in_memory && param.starts_with("connection_limit=")) {
+                params.push(param.to_owned());
+            }
+        }
+
+        // Every connection to `:memory:` opens a database of its own, a single connection keeps all queries on the
+        // same one.
+        if in_memory {
+            params.push("connection_limit=1".to_owned());
+        }
+
+        params.push(format!("db_name={}", db_name));
 
+        let url_with_db = format!("{}?{}", url, params.join("&"));
         let pool = Quaint::new(url_with_db.as_str()).await?;
 
-        Ok(Sqlite { pool, file_path })
+        Ok(Sqlite {
+            pool,
+            file_path,
+            db_name,
+            attachments,
+            time_zone,
+            in_use: InUseCounter::default(),
+        })
     }
 }
 
@@ -61,9 +119,111 @@ impl Connector for Sqlite {
     fn get_connection<'a>(&'a self) -> IO<Box<dyn Connection + 'a>> {
         IO::new(super::catch(&self.connection_info(), async move {
             let conn = self.pool.check_out().await.map_err(SqlError::from)?;
-            let conn = SqlConnection::<_, ManyRelatedRecordsWithRowNumber>::new(conn, self.connection_info());
+            attach_databases(&conn, &self.attachments).await?;
+
+            // SQLite runs in-process, there is no statement running on a server to abort.
+            let conn = SqlConnection::<_, ManyRelatedRecordsWithRowNumber>::new(
+                Arc::new(conn),
+                None,
+                self.connection_info(),
+                self.time_zone,
+                None,
+                false,
+                self.in_use.acquire(),
+            );
 
             Ok(Box::new(conn) as Box<dyn Connection>)
         }))
     }
+
+    fn pool_state<'a>(&'a self) -> IO<'a, PoolState> {
+        IO::new(async move { Ok(super::pool_state(&self.pool, &self.in_use).await) })
+    }
+}
+
+/// Parses `<name>=<path>` of an `attach.<name>=<path>` parameter. The schema names SQLite reserves can't be used.
+fn parse_attachment(param: &str) -> crate::Result<(String, String)> {
+    let mut parts = param.splitn(2, '=');
+    let name = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("");
+
+    if name.is_empty() || path.is_empty() {
+        return Err(SqlError::InvalidConnectionString(format!(
+            "Expected `{}<name>=<path>` to attach a database, got `{}{}`.",
+            ATTACH_PARAM_PREFIX, ATTACH_PARAM_PREFIX, param
+        )));
+    }
+
+    if RESERVED_SCHEMA_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(name))
+    {
+        return Err(SqlError::InvalidConnectionString(format!(
+            "Can't attach a database as `{}`, the name is reserved by SQLite.",
+            name
+        )));
+    }
+
+    Ok((name.to_owned(), path.to_owned()))
+}
+
+/// Attaches the databases a pooled connection doesn't have yet, connections are reused after the first check out.
+async fn attach_databases(conn: &PooledConnection, attachments: &[(String, String)]) -> crate::Result<()> {
+    if attachments.is_empty() {
+        return Ok(());
+    }
+
+    let attached: Vec<String> = conn
+        .query_raw("PRAGMA database_list", &[])
+        .await?
+        .into_iter()
+        .filter_map(|row| row.get("name").and_then(|name| name.to_string()))
+        .collect();
+
+    for (name, path) in attachments {
+        if !attached.contains(name) {
+            conn.execute_raw("ATTACH DATABASE ? AS ?", &[path.as_str().into(), name.as_str().into()])
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachments_are_parsed_into_name_and_path() {
+        let (name, path) = parse_attachment("audit=./audit.db").unwrap();
+
+        assert_eq!(name, "audit");
+        assert_eq!(path, "./audit.db");
+    }
+
+    #[test]
+    fn attachment_paths_can_contain_equal_signs() {
+        let (name, path) = parse_attachment("audit=./a=b.db").unwrap();
+
+        assert_eq!(name, "audit");
+        assert_eq!(path, "./a=b.db");
+    }
+
+    #[test]
+    fn attachments_need_a_name_and_a_path() {
+        assert!(parse_attachment("audit").is_err());
+        assert!(parse_attachment("audit=").is_err());
+        assert!(parse_attachment("=./audit.db").is_err());
+        assert!(parse_attachment("").is_err());
+    }
+
+    #[test]
+    fn reserved_schema_names_are_rejected() {
+        for name in &["main", "temp", "MAIN", "Temp"] {
+            let err = parse_attachment(&format!("{}=./audit.db", name)).unwrap_err();
+
+            assert!(err.to_string().contains("reserved"), "{}", err);
+        }
+    }
 }
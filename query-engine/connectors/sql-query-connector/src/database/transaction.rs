@@ -1,24 +1,41 @@
+use super::cancellation::Cancellation;
 use crate::database::operations::*;
-use crate::{query_builder::read::ManyRelatedRecordsQueryBuilder, SqlError};
+use crate::{query_builder::read::ManyRelatedRecordsQueryBuilder, SqlError, TimeZoneExt};
 use connector_interface::{
-    self as connector, filter::Filter, QueryArguments, ReadOperations, Transaction, WriteArgs, WriteOperations, IO,
+    self as connector, filter::Filter, IdempotencyRecord, QueryArguments, ReadOperations, Routine, RoutineResult,
+    Transaction, WriteArgs, WriteOperations, IO,
 };
 use prisma_models::prelude::*;
-use prisma_value::PrismaValue;
+use prisma_value::{PrismaValue, TimeZonePolicy};
 use quaint::prelude::ConnectionInfo;
 use std::marker::PhantomData;
 
 pub struct SqlConnectorTransaction<'a, T> {
     inner: quaint::connector::Transaction<'a>,
+    cancellation: Option<&'a Cancellation>,
     connection_info: &'a ConnectionInfo,
+    time_zone: TimeZonePolicy,
+    max_fan_out: Option<usize>,
+    returning: bool,
     _p: PhantomData<T>,
 }
 
 impl<'a, T> SqlConnectorTransaction<'a, T> {
-    pub fn new<'b: 'a>(tx: quaint::connector::Transaction<'a>, connection_info: &'b ConnectionInfo) -> Self {
+    pub(crate) fn new<'b: 'a>(
+        tx: quaint::connector::Transaction<'a>,
+        cancellation: Option<&'a Cancellation>,
+        connection_info: &'b ConnectionInfo,
+        time_zone: TimeZonePolicy,
+        max_fan_out: Option<usize>,
+        returning: bool,
+    ) -> Self {
         Self {
             inner: tx,
+            cancellation,
             connection_info,
+            time_zone,
+            max_fan_out,
+            returning,
             _p: PhantomData,
         }
     }
@@ -27,7 +44,14 @@ impl<'a, T> SqlConnectorTransaction<'a, T> {
         &self,
         fut: impl std::future::Future<Output = Result<O, SqlError>>,
     ) -> Result<O, connector_interface::error::ConnectorError> {
-        match fut.await {
+        let statement = self.cancellation.map(Cancellation::arm);
+        let result = fut.await;
+
+        if let Some(statement) = statement {
+            statement.disarm();
+        }
+
+        match result {
             Ok(o) => Ok(o),
             Err(err) => Err(err.into_connector_error(&self.connection_info)),
         }
@@ -57,7 +81,13 @@ where
         filter: &'b Filter,
         selected_fields: &'b SelectedFields,
     ) -> connector::IO<'b, Option<SingleRecord>> {
-        IO::new(self.catch(async move { read::get_single_record(&self.inner, model, filter, selected_fields).await }))
+        IO::new(self.catch(async move {
+            let filter = self.time_zone.bind(filter.clone());
+            let record =
+                read::get_single_record(&self.inner, self.connection_info, model, &filter, selected_fields).await?;
+
+            Ok(self.time_zone.read(record))
+        }))
     }
 
     fn get_many_records<'b>(
@@ -66,11 +96,19 @@ where
         query_arguments: QueryArguments,
         selected_fields: &'b SelectedFields,
     ) -> connector::IO<'b, ManyRecords> {
-        IO::new(
-            self.catch(
-                async move { read::get_many_records(&self.inner, model, query_arguments, selected_fields).await },
-            ),
-        )
+        IO::new(self.catch(async move {
+            let query_arguments = self.time_zone.bind(query_arguments);
+            let records = read::get_many_records(
+                &self.inner,
+                self.connection_info,
+                model,
+                query_arguments,
+                selected_fields,
+            )
+            .await?;
+
+            Ok(self.time_zone.read(records))
+        }))
     }
 
     fn get_related_records<'b>(
@@ -81,19 +119,37 @@ where
         selected_fields: &'b SelectedFields,
     ) -> connector::IO<'b, ManyRecords> {
         IO::new(self.catch(async move {
-            read::get_related_records::<T>(
+            let from_record_ids = self.time_zone.bind(from_record_ids.to_vec());
+            let query_arguments = self.time_zone.bind(query_arguments);
+
+            let records = read::get_related_records::<T>(
                 &self.inner,
+                self.connection_info,
                 from_field,
-                from_record_ids,
+                &from_record_ids,
                 query_arguments,
                 selected_fields,
+                self.max_fan_out,
             )
-            .await
+            .await?;
+
+            Ok(self.time_zone.read(records))
         }))
     }
 
     fn count_by_model<'b>(&'b self, model: &'b ModelRef, query_arguments: QueryArguments) -> connector::IO<'b, usize> {
-        IO::new(self.catch(async move { read::count_by_model(&self.inner, model, query_arguments).await }))
+        IO::new(self.catch(async move {
+            let query_arguments = self.time_zone.bind(query_arguments);
+            read::count_by_model(&self.inner, model, query_arguments).await
+        }))
+    }
+
+    fn list_routines<'b>(&'b self) -> connector::IO<'b, Vec<Routine>> {
+        IO::new(self.catch(async move { read::list_routines(&self.inner, self.connection_info).await }))
+    }
+
+    fn get_idempotency_record<'b>(&'b self, key: &'b str) -> connector::IO<'b, Option<IdempotencyRecord>> {
+        IO::new(self.catch(async move { read::get_idempotency_record(&self.inner, self.connection_info, key).await }))
     }
 }
 
@@ -102,7 +158,11 @@ where
     T: ManyRelatedRecordsQueryBuilder + Send + Sync + 'static,
 {
     fn create_record<'b>(&'b self, model: &'b ModelRef, args: WriteArgs) -> connector::IO<RecordIdentifier> {
-        IO::new(self.catch(async move { write::create_record(&self.inner, model, args).await }))
+        IO::new(self.catch(async move {
+            let id = write::create_record(&self.inner, model, self.time_zone.bind(args), self.returning).await?;
+
+            Ok(self.time_zone.read(id))
+        }))
     }
 
     fn update_records<'b>(
@@ -111,11 +171,26 @@ where
         where_: Filter,
         args: WriteArgs,
     ) -> connector::IO<Vec<RecordIdentifier>> {
-        IO::new(self.catch(async move { write::update_records(&self.inner, model, where_, args).await }))
+        IO::new(self.catch(async move {
+            let where_ = self.time_zone.bind(where_);
+            let ids = write::update_records(
+                &self.inner,
+                self.connection_info,
+                model,
+                where_,
+                self.time_zone.bind(args),
+            )
+            .await?;
+
+            Ok(self.time_zone.read(ids))
+        }))
     }
 
     fn delete_records<'b>(&'b self, model: &'b ModelRef, where_: Filter) -> connector::IO<usize> {
-        IO::new(self.catch(async move { write::delete_records(&self.inner, model, where_).await }))
+        IO::new(self.catch(async move {
+            let where_ = self.time_zone.bind(where_);
+            write::delete_records(&self.inner, model, where_).await
+        }))
     }
 
     fn connect<'b>(
@@ -124,7 +199,12 @@ where
         parent_id: &'b RecordIdentifier,
         child_ids: &'b [RecordIdentifier],
     ) -> connector::IO<()> {
-        IO::new(self.catch(async move { write::connect(&self.inner, field, parent_id, child_ids).await }))
+        IO::new(self.catch(async move {
+            let parent_id = self.time_zone.bind(parent_id.clone());
+            let child_ids = self.time_zone.bind(child_ids.to_vec());
+
+            write::connect(&self.inner, field, &parent_id, &child_ids).await
+        }))
     }
 
     fn disconnect<'b>(
@@ -133,10 +213,38 @@ where
         parent_id: &'b RecordIdentifier,
         child_ids: &'b [RecordIdentifier],
     ) -> connector::IO<()> {
-        IO::new(self.catch(async move { write::disconnect(&self.inner, field, parent_id, child_ids).await }))
+        IO::new(self.catch(async move {
+            let parent_id = self.time_zone.bind(parent_id.clone());
+            let child_ids = self.time_zone.bind(child_ids.to_vec());
+
+            write::disconnect(&self.inner, field, &parent_id, &child_ids).await
+        }))
+    }
+
+    fn disconnect_all<'b>(
+        &'b self,
+        field: &'b RelationFieldRef,
+        parent_ids: &'b [RecordIdentifier],
+    ) -> connector::IO<()> {
+        IO::new(self.catch(async move {
+            let parent_ids = self.time_zone.bind(parent_ids.to_vec());
+            write::disconnect_all(&self.inner, field, &parent_ids).await
+        }))
     }
 
     fn execute_raw(&self, query: String, parameters: Vec<PrismaValue>) -> connector::IO<serde_json::Value> {
         IO::new(self.catch(async move { write::execute_raw(&self.inner, query, parameters).await }))
     }
+
+    fn call_routine<'b>(&'b self, routine: &'b Routine, arguments: Vec<PrismaValue>) -> connector::IO<RoutineResult> {
+        IO::new(
+            self.catch(async move { write::call_routine(&self.inner, self.connection_info, routine, arguments).await }),
+        )
+    }
+
+    fn store_idempotency_record(&self, record: IdempotencyRecord) -> connector::IO<()> {
+        IO::new(
+            self.catch(async move { write::store_idempotency_record(&self.inner, self.connection_info, record).await }),
+        )
+    }
 }
@@ -1,10 +1,30 @@
 use connector_interface::{error::*, Filter};
 use failure::{Error, Fail};
-use prisma_models::prelude::DomainError;
+use prisma_models::{prelude::DomainError, ModelRef};
 use quaint::error::ErrorKind as QuaintKind;
 use std::{any::Any, string::FromUtf8Error};
 use user_facing_errors::query_engine::DatabaseConstraint;
 
+/// Error code of MySQL (8.0.16 and later) for a violated `CHECK` constraint.
+const MYSQL_CHECK_CONSTRAINT_VIOLATED: &str = "3819";
+
+/// Error code of MariaDB (10.2 and later) for a failed `CHECK` constraint, e.g. `json_valid()` on a JSON column.
+const MARIADB_CONSTRAINT_FAILED: &str = "4025";
+
+/// Error codes of PostgreSQL, MySQL and SQLite for a violated foreign key. MySQL reports deleting or updating a
+/// referenced row and inserting or updating a referencing row separately, with older codes without the key.
+const POSTGRES_FOREIGN_KEY_VIOLATION: &str = "23503";
+const MYSQL_FOREIGN_KEY_VIOLATIONS: &[&str] = &["1216", "1217", "1451", "1452"];
+const SQLITE_FOREIGN_KEY_VIOLATION: &str = "787";
+
+/// Error codes of PostgreSQL and MySQL for a value that doesn't fit the type of its column. SQLite doesn't enforce
+/// the length of column types.
+const POSTGRES_VALUE_TOO_LONG: &str = "22001";
+const MYSQL_VALUE_TOO_LONG: &str = "1406";
+
+/// Stands in for the name of the column or the value if the database doesn't report it.
+const UNKNOWN_COLUMN: &str = "(not available)";
+
 pub struct RawError {
     code: Option<String>,
     message: Option<String>,
@@ -41,11 +61,23 @@ impl From<Box<dyn Any + Send>> for RawError {
 #[derive(Debug, Fail)]
 pub enum SqlError {
     #[fail(display = "Unique constraint failed: {:?}", constraint)]
-    UniqueConstraintViolation { constraint: DatabaseConstraint },
+    UniqueConstraintViolation {
+        constraint: DatabaseConstraint,
+        column_names: Vec<String>,
+    },
 
     #[fail(display = "Null constraint failed: {:?}", constraint)]
     NullConstraintViolation { constraint: DatabaseConstraint },
 
+    #[fail(display = "Foreign key constraint failed: {:?}", constraint)]
+    ForeignKeyConstraintViolation { constraint: DatabaseConstraint },
+
+    #[fail(display = "The value is too long for the column: {}", column_name)]
+    ValueTooLong { column_name: String },
+
+    #[fail(display = "A constraint failed on the database: {}", database_error)]
+    ConstraintViolation { database_error: String },
+
     #[fail(display = "Record does not exist.")]
     RecordDoesNotExist,
 
@@ -95,25 +127,83 @@ pub enum SqlError {
     #[fail(display = "Conversion error: {}", _0)]
     ConversionError(Error),
 
+    #[fail(display = "Invalid connection string: {}", _0)]
+    InvalidConnectionString(String),
+
     #[fail(display = "Database error. error code: {}, error message: {}", code, message)]
     RawError { code: String, message: String },
 }
 
 impl SqlError {
+    /// Looks up the columns of a violated unique index of the model, for databases that only report the name of
+    /// the index.
+    pub(crate) fn with_model(self, model: &ModelRef) -> Self {
+        match self {
+            SqlError::UniqueConstraintViolation {
+                constraint: DatabaseConstraint::Index(name),
+                column_names,
+            } if column_names.is_empty() => SqlError::UniqueConstraintViolation {
+                column_names: unique_index_columns(model, &name),
+                constraint: DatabaseConstraint::Index(name),
+            },
+            err => err,
+        }
+    }
+
     pub(crate) fn into_connector_error(self, connection_info: &quaint::prelude::ConnectionInfo) -> ConnectorError {
         match self {
-            SqlError::UniqueConstraintViolation { constraint } => ConnectorError {
+            SqlError::UniqueConstraintViolation {
+                constraint,
+                column_names,
+            } => ConnectorError {
                 user_facing_error: user_facing_errors::KnownError::new(
                     user_facing_errors::query_engine::UniqueKeyViolation {
                         constraint: constraint.clone(),
+                        column_names,
                     },
                 )
                 .ok(),
                 kind: ErrorKind::UniqueConstraintViolation { constraint },
             },
-            SqlError::NullConstraintViolation { constraint } => {
-                ConnectorError::from_kind(ErrorKind::NullConstraintViolation { constraint })
-            }
+            SqlError::NullConstraintViolation { constraint } => ConnectorError {
+                user_facing_error: user_facing_errors::KnownError::new(
+                    user_facing_errors::query_engine::NullConstraintViolation {
+                        constraint: constraint.clone(),
+                    },
+                )
+                .ok(),
+                kind: ErrorKind::NullConstraintViolation { constraint },
+            },
+            SqlError::ForeignKeyConstraintViolation { constraint } => ConnectorError {
+                user_facing_error: user_facing_errors::KnownError::new(
+                    user_facing_errors::query_engine::ForeignKeyViolation {
+                        field_name: constraint_field_name(&constraint),
+                        constraint: constraint.clone(),
+                    },
+                )
+                .ok(),
+                kind: ErrorKind::ForeignKeyConstraintViolation { constraint },
+            },
+            SqlError::ValueTooLong { column_name } => ConnectorError {
+                user_facing_error: user_facing_errors::KnownError::new(
+                    user_facing_errors::query_engine::InputValueTooLong {
+                        field_value: UNKNOWN_COLUMN.to_owned(),
+                        field_name: column_name.clone(),
+                        column_name: column_name.clone(),
+                    },
+                )
+                .ok(),
+                kind: ErrorKind::ValueTooLong { column_name },
+            },
+            SqlError::ConstraintViolation { database_error } => ConnectorError {
+                user_facing_error: user_facing_errors::KnownError::new(
+                    user_facing_errors::query_engine::ConstraintViolation {
+                        database_error: database_error.clone(),
+                    },
+                )
+                .ok(),
+                kind: ErrorKind::ConstraintViolation { database_error },
+            },
             SqlError::RecordDoesNotExist => ConnectorError::from_kind(ErrorKind::RecordDoesNotExist),
             SqlError::ColumnDoesNotExist => ConnectorError::from_kind(ErrorKind::ColumnDoesNotExist),
             SqlError::ConnectionError(e) => ConnectorError {
@@ -146,6 +236,7 @@ impl SqlError {
             }),
             SqlError::ConversionError(e) => ConnectorError::from_kind(ErrorKind::ConversionError(e)),
             SqlError::QueryError(e) => ConnectorError::from_kind(ErrorKind::QueryError(e)),
+            SqlError::InvalidConnectionString(_) => ConnectorError::from_kind(ErrorKind::InvalidConnectionArguments),
             SqlError::RawError { code, message } => ConnectorError {
                 user_facing_error: user_facing_errors::KnownError::new(
                     user_facing_errors::query_engine::RawQueryFailed {
@@ -162,13 +253,65 @@ impl SqlError {
 
 impl From<quaint::error::Error> for SqlError {
     fn from(e: quaint::error::Error) -> Self {
+        let message = e.original_message().unwrap_or_default();
+
+        match e.original_code() {
+            // Older MySQL versions parse `CHECK` constraints without enforcing them, so only these two report violations.
+            Some(MYSQL_CHECK_CONSTRAINT_VIOLATED) | Some(MARIADB_CONSTRAINT_FAILED) => {
+                return Self::ConstraintViolation {
+                    database_error: message.to_owned(),
+                };
+            }
+            Some(POSTGRES_FOREIGN_KEY_VIOLATION) => {
+                // insert or update on table "Post" violates foreign key constraint "Post_authorId_fkey"
+                let constraint = quoted(message, "constraint \"", '"')
+                    .map(|name| DatabaseConstraint::Index(name.to_owned()))
+                    .unwrap_or(DatabaseConstraint::ForeignKey);
+
+                return Self::ForeignKeyConstraintViolation { constraint };
+            }
+            Some(code) if MYSQL_FOREIGN_KEY_VIOLATIONS.contains(&code) => {
+                return Self::ForeignKeyConstraintViolation {
+                    constraint: mysql_foreign_key(message),
+                };
+            }
+            Some(SQLITE_FOREIGN_KEY_VIOLATION) => {
+                return Self::ForeignKeyConstraintViolation {
+                    constraint: DatabaseConstraint::ForeignKey,
+                };
+            }
+            Some(POSTGRES_VALUE_TOO_LONG) => {
+                // PostgreSQL names the type, but not the column: value too long for type character varying(10)
+                return Self::ValueTooLong {
+                    column_name: UNKNOWN_COLUMN.to_owned(),
+                };
+            }
+            Some(MYSQL_VALUE_TOO_LONG) => {
+                // Data too long for column 'name' at row 1
+                let column_name = quoted(message, "column '", '\'').unwrap_or(UNKNOWN_COLUMN);
+
+                return Self::ValueTooLong {
+                    column_name: column_name.to_owned(),
+                };
+            }
+            _ => (),
+        }
+
         match QuaintKind::from(e) {
             QuaintKind::QueryError(qe) => Self::QueryError(qe),
             e @ QuaintKind::IoError(_) => Self::ConnectionError(e),
             QuaintKind::NotFound => Self::RecordDoesNotExist,
-            QuaintKind::UniqueConstraintViolation { constraint } => Self::UniqueConstraintViolation {
-                constraint: constraint.into(),
-            },
+            QuaintKind::UniqueConstraintViolation { constraint } => {
+                let column_names = match constraint {
+                    quaint::error::DatabaseConstraint::Fields(ref fields) => fields.clone(),
+                    quaint::error::DatabaseConstraint::Index(_) => Vec::new(),
+                };
+
+                Self::UniqueConstraintViolation {
+                    constraint: constraint.into(),
+                    column_names,
+                }
+            }
 
             QuaintKind::NullConstraintViolation { constraint } => Self::NullConstraintViolation {
                 constraint: constraint.into(),
@@ -193,6 +336,68 @@ impl From<quaint::error::Error> for SqlError {
     }
 }
 
+/// The text between `prefix` and the next `end` in the message.
+fn quoted<'a>(message: &'a str, prefix: &str, end: char) -> Option<&'a str> {
+    let start = message.find(prefix)? + prefix.len();
+    let len = message[start..].find(end)?;
+
+    Some(&message[start..start + len])
+}
+
+/// MySQL names the constraint and its columns:
+/// Cannot add or update a child row: a foreign key constraint fails (`db`.`Post`, CONSTRAINT `Post_ibfk_1`
+/// FOREIGN KEY (`authorId`) REFERENCES `User` (`id`))
+fn mysql_foreign_key(message: &str) -> DatabaseConstraint {
+    if let Some(columns) = quoted(message, "FOREIGN KEY (", ')') {
+        let columns = columns
+            .split(',')
+            .map(|column| column.trim().trim_matches('`').to_owned());
+
+        return DatabaseConstraint::Fields(columns.collect());
+    }
+
+    match quoted(message, "CONSTRAINT `", '`') {
+        Some(name) => DatabaseConstraint::Index(name.to_owned()),
+        None => DatabaseConstraint::ForeignKey,
+    }
+}
+
+/// The `field_name` of foreign key violations, which clients read from before the errors named the constraint.
+fn constraint_field_name(constraint: &DatabaseConstraint) -> String {
+    match constraint {
+        DatabaseConstraint::Fields(fields) => fields.join(","),
+        DatabaseConstraint::Index(name) => name.clone(),
+        DatabaseConstraint::ForeignKey => UNKNOWN_COLUMN.to_owned(),
+    }
+}
+
+/// Unique indexes are created as `Model.column` for single fields and `Model.column_column` for compound indexes
+/// without a name. Newer MySQL versions prefix the index with the table in the error.
+fn unique_index_columns(model: &ModelRef, index_name: &str) -> Vec<String> {
+    let matches = |name: &str| name == index_name || format!("{}.{}", model.db_name(), name) == index_name;
+
+    let compound = model.unique_indexes().into_iter().find_map(|index| {
+        let columns: Vec<String> = index.fields().iter().map(|field| field.db_name().to_owned()).collect();
+
+        match index.name {
+            Some(ref name) if matches(name) => Some(columns),
+            None if matches(&format!("{}.{}", model.db_name(), columns.join("_"))) => Some(columns),
+            _ => None,
+        }
+    });
+
+    compound
+        .or_else(|| {
+            model
+                .fields()
+                .scalar()
+                .into_iter()
+                .find(|field| field.unique() && matches(&format!("{}.{}", model.db_name(), field.db_name())))
+                .map(|field| vec![field.db_name().to_owned()])
+        })
+        .unwrap_or_default()
+}
+
 impl From<DomainError> for SqlError {
     fn from(e: DomainError) -> SqlError {
         SqlError::DomainError(e)
@@ -216,3 +421,100 @@ impl From<FromUtf8Error> for SqlError {
         SqlError::ColumnReadFailure(e.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quaint::error::{Error as QuaintError, ErrorKind as QuaintKind};
+
+    fn database_error(code: &str, message: &str) -> SqlError {
+        let mut builder = QuaintError::builder(QuaintKind::QueryError(message.to_owned().into()));
+
+        builder.set_original_code(code.to_owned());
+        builder.set_original_message(message.to_owned());
+
+        builder.build().into()
+    }
+
+    fn foreign_key(err: SqlError) -> DatabaseConstraint {
+        match err {
+            SqlError::ForeignKeyConstraintViolation { constraint } => constraint,
+            err => panic!("Expected a foreign key violation, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn quoted_finds_the_text_up_to_the_end_character() {
+        let message = r#"violates foreign key constraint "Post_authorId_fkey" on table "Post""#;
+
+        assert_eq!(quoted(message, "constraint \"", '"'), Some("Post_authorId_fkey"));
+        assert_eq!(quoted(message, "column '", '\''), None);
+        assert_eq!(quoted("constraint \"unterminated", "constraint \"", '"'), None);
+    }
+
+    #[test]
+    fn postgres_foreign_key_violations_name_the_constraint() {
+        let err = database_error(
+            POSTGRES_FOREIGN_KEY_VIOLATION,
+            r#"insert or update on table "Post" violates foreign key constraint "Post_authorId_fkey""#,
+        );
+
+        assert_eq!(foreign_key(err), DatabaseConstraint::Index("Post_authorId_fkey".into()));
+
+        let err = database_error(POSTGRES_FOREIGN_KEY_VIOLATION, "foreign key violation");
+        assert_eq!(foreign_key(err), DatabaseConstraint::ForeignKey);
+    }
+
+    #[test]
+    fn mysql_foreign_key_violations_name_the_columns() {
+        let message = "Cannot add or update a child row: a foreign key constraint fails (`db`.`Post`, CONSTRAINT \
+                       `Post_ibfk_1` FOREIGN KEY (`authorId`, `tenantId`) REFERENCES `User` (`id`, `tenantId`))";
+
+        for code in MYSQL_FOREIGN_KEY_VIOLATIONS {
+            assert_eq!(
+                foreign_key(database_error(code, message)),
+                DatabaseConstraint::Fields(vec!["authorId".into(), "tenantId".into()])
+            );
+        }
+    }
+
+    #[test]
+    fn mysql_foreign_keys_fall_back_to_the_constraint_name() {
+        let named = "a foreign key constraint fails (`db`.`Post`, CONSTRAINT `Post_ibfk_1`)";
+        let unnamed = "Cannot delete or update a parent row: a foreign key constraint fails";
+
+        assert_eq!(
+            mysql_foreign_key(named),
+            DatabaseConstraint::Index("Post_ibfk_1".into())
+        );
+        assert_eq!(mysql_foreign_key(unnamed), DatabaseConstraint::ForeignKey);
+    }
+
+    #[test]
+    fn mysql_values_too_long_name_the_column() {
+        match database_error(MYSQL_VALUE_TOO_LONG, "Data too long for column 'name' at row 1") {
+            SqlError::ValueTooLong { column_name } => assert_eq!(column_name, "name"),
+            err => panic!("Expected a value too long, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn mysql_check_constraint_violations_are_constraint_violations() {
+        for code in &[MYSQL_CHECK_CONSTRAINT_VIOLATED, MARIADB_CONSTRAINT_FAILED] {
+            match database_error(code, "Check constraint 'age' is violated.") {
+                SqlError::ConstraintViolation { database_error } => {
+                    assert_eq!(database_error, "Check constraint 'age' is violated.")
+                }
+                err => panic!("Expected a constraint violation, got {:?}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn foreign_key_violations_keep_the_field_name() {
+        let constraint = DatabaseConstraint::Fields(vec!["authorId".into(), "tenantId".into()]);
+
+        assert_eq!(constraint_field_name(&constraint), "authorId,tenantId");
+        assert_eq!(constraint_field_name(&DatabaseConstraint::ForeignKey), UNKNOWN_COLUMN);
+    }
+}
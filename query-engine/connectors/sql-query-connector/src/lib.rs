@@ -7,11 +7,13 @@ mod query_builder;
 mod query_ext;
 mod raw_query;
 mod row;
+mod time_zone;
 
 use filter_conversion::*;
-use query_ext::QueryExt;
+use query_ext::{sql_span, QueryExt};
 use raw_query::*;
 use row::*;
+use time_zone::*;
 
 pub use database::*;
 pub use error::SqlError;
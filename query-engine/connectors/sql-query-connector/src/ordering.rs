@@ -34,18 +34,24 @@ impl Ordering {
         )
     }
 
-    pub fn aliased_internal(
+    pub fn aliased_internal<I>(
         alias: &'static str,
         secondary_alias: &'static str,
-        secondary_field: &'static str,
+        secondary_fields: I,
         order_directive: OrderDirections,
-    ) -> OrderVec<'static> {
+    ) -> OrderVec<'static>
+    where
+        I: IntoIterator<Item = String>,
+    {
         Self::by_fields(
-            order_directive
-                .primary_order_by
-                .as_ref()
-                .map(|oby| (alias.to_string(), oby.field.db_name().to_string()).into()),
-            vec![(secondary_alias, secondary_field).into()],
+            order_directive.primary_order_by.as_ref().map(|oby| {
+                let column = oby.field.internal_data_model().identifier(oby.field.db_name());
+                (alias.to_string(), column).into()
+            }),
+            secondary_fields
+                .into_iter()
+                .map(|field| (secondary_alias.to_string(), field).into())
+                .collect(),
             order_directive,
         )
     }
@@ -0,0 +1,65 @@
+use chrono::{Duration, Utc};
+use connector_interface::{IdempotencyRecord, MAX_IDEMPOTENCY_KEY_LENGTH};
+use quaint::{ast::*, prelude::SqlFamily};
+use sql_identifier::{quote_with_schema, Quoted};
+
+/// Table of the engine storing the responses of idempotency keys, created on the first lookup of a key.
+pub const IDEMPOTENCY_TABLE: &str = "_prisma_idempotency_keys";
+
+/// Retries are expected within a day, older keys are deleted on the next lookup of any key.
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+/// Creates the table of idempotency keys if it doesn't exist. MySQL commits open transactions on DDL statements,
+/// so this must not run in a transaction.
+pub fn create_table(schema_name: &str, sql_family: SqlFamily) -> String {
+    let delimiter = super::delimiter(sql_family);
+
+    let key_type = format!("VARCHAR({}) NOT NULL PRIMARY KEY", MAX_IDEMPOTENCY_KEY_LENGTH);
+    let columns = [
+        ("key", key_type.as_str()),
+        ("request_hash", "VARCHAR(64) NOT NULL"),
+        ("response", "TEXT NOT NULL"),
+        ("created_at", "TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP"),
+    ];
+
+    let columns: Vec<String> = columns
+        .iter()
+        .map(|(name, definition)| format!("{} {}", Quoted::new(delimiter, name), definition))
+        .collect();
+
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} ({})",
+        quote_with_schema(delimiter, schema_name, IDEMPOTENCY_TABLE),
+        columns.join(", ")
+    )
+}
+
+pub fn get_record(schema_name: &str, key: &str) -> Select<'static> {
+    Select::from_table(table(schema_name))
+        .column("request_hash")
+        .column("response")
+        .so_that(Column::from("key").equals(key.to_owned()))
+}
+
+/// The creation time is set by the engine rather than the database, so that it is compared in the same time zone
+/// when the keys expire.
+pub fn store_record(schema_name: &str, record: IdempotencyRecord) -> Insert<'static> {
+    let insert = Insert::single_into(table(schema_name))
+        .value("key", record.key)
+        .value("request_hash", record.request_hash)
+        .value("response", record.response.to_string())
+        .value("created_at", Utc::now());
+
+    Insert::from(insert)
+}
+
+/// Deletes the keys older than `IDEMPOTENCY_KEY_TTL_HOURS`.
+pub fn delete_expired_records(schema_name: &str) -> Delete<'static> {
+    let expired_before = Utc::now() - Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS);
+
+    Delete::from_table(table(schema_name)).so_that(Column::from("created_at").less_than(expired_before))
+}
+
+fn table(schema_name: &str) -> Table<'static> {
+    (schema_name.to_owned(), IDEMPOTENCY_TABLE.to_owned()).into()
+}
@@ -0,0 +1,369 @@
+use crate::RawQuery;
+use prisma_models::{ModelRef, PrismaValue, RecordIdentifier};
+use quaint::prelude::SqlFamily;
+use serde_json::{Map, Value};
+use sql_identifier::{quote_with_schema, Quoted};
+use std::collections::HashMap;
+
+/// Merges the JSON merge patches (RFC 7396) into the JSON columns of the records, one statement per chunk of
+/// records. MySQL and SQLite implement merge patches as `JSON_MERGE_PATCH` and `json_patch`, on PostgreSQL the patch
+/// is compiled into `jsonb_set` calls. Documents that are `NULL` or not an object are patched as empty objects.
+pub fn merge_json(
+    model: &ModelRef,
+    ids: &[&RecordIdentifier],
+    patches: &HashMap<String, Map<String, Value>>,
+    sql_family: SqlFamily,
+) -> Vec<RawQuery<'static>> {
+    if patches.is_empty() {
+        return Vec::new();
+    }
+
+    let delimiter = super::delimiter(sql_family);
+
+    let table = quote_with_schema(delimiter, &model.datasource_db_name(), model.db_name());
+
+    ids.chunks(super::PARAMETER_LIMIT)
+        .map(|chunk| {
+            let mut parameters = Vec::new();
+
+            let assignments: Vec<String> = patches
+                .iter()
+                .map(|(column, patch)| {
+                    let column = Quoted::new(delimiter, column).to_string();
+                    let merged = merged_document(&column, patch, sql_family, &mut parameters);
+
+                    format!("{} = {}", column, merged)
+                })
+                .collect();
+
+            let query = format!(
+                "UPDATE {} SET {} WHERE {}",
+                table,
+                assignments.join(", "),
+                id_conditions(chunk, sql_family, &mut parameters)
+            );
+
+            RawQuery::new(query, parameters)
+        })
+        .collect()
+}
+
+/// Selects the records by an `IN` list of their ids, or by a disjunction of the columns of compound ids.
+fn id_conditions(ids: &[&RecordIdentifier], sql_family: SqlFamily, parameters: &mut Vec<PrismaValue>) -> String {
+    let delimiter = super::delimiter(sql_family);
+
+    if ids[0].len() == 1 {
+        let column = Quoted::new(delimiter, &ids[0].pairs()[0].0.name).to_string();
+
+        let placeholders: Vec<String> = ids
+            .iter()
+            .map(|id| placeholder(sql_family, parameters, id.pairs()[0].1.clone()))
+            .collect();
+
+        return format!("{} IN ({})", column, placeholders.join(", "));
+    }
+
+    let conditions: Vec<String> = ids
+        .iter()
+        .map(|id| {
+            let columns: Vec<String> = id
+                .pairs()
+                .iter()
+                .map(|(field, value)| {
+                    let placeholder = placeholder(sql_family, parameters, value.clone());
+                    format!("{} = {}", Quoted::new(delimiter, &field.name), placeholder)
+                })
+                .collect();
+
+            format!("({})", columns.join(" AND "))
+        })
+        .collect();
+
+    conditions.join(" OR ")
+}
+
+fn merged_document(
+    column: &str,
+    patch: &Map<String, Value>,
+    sql_family: SqlFamily,
+    parameters: &mut Vec<PrismaValue>,
+) -> String {
+    let patch_value = PrismaValue::String(Value::Object(patch.clone()).to_string());
+
+    match sql_family {
+        SqlFamily::Mysql => format!(
+            "JSON_MERGE_PATCH(COALESCE({}, '{{}}'), {})",
+            column,
+            placeholder(sql_family, parameters, patch_value)
+        ),
+        SqlFamily::Sqlite => format!(
+            "json_patch(COALESCE({}, '{{}}'), {})",
+            column,
+            placeholder(sql_family, parameters, patch_value)
+        ),
+        SqlFamily::Postgres => postgres_merge(column, &[], patch, parameters),
+    }
+}
+
+/// Merges the patch into the object at the path of the column. Keys of an object are unique, so nested objects are
+/// always merged into the stored object and not into the result of a previous merge.
+fn postgres_merge(
+    column: &str,
+    path: &[&str],
+    patch: &Map<String, Value>,
+    parameters: &mut Vec<PrismaValue>,
+) -> String {
+    let target = if path.is_empty() {
+        format!("{}::jsonb", column)
+    } else {
+        format!("{}::jsonb #> {}", column, postgres_path(path, parameters))
+    };
+
+    let mut merged = format!(
+        "CASE WHEN jsonb_typeof({target}) = 'object' THEN {target} ELSE '{{}}'::jsonb END",
+        target = target
+    );
+
+    for (key, value) in patch.iter() {
+        let key_path = postgres_path(&[key.as_str()], parameters);
+
+        merged = match value {
+            Value::Null => format!("({} #- {})", merged, key_path),
+            Value::Object(nested) => {
+                let mut nested_path = path.to_vec();
+                nested_path.push(key);
+
+                let nested_merged = postgres_merge(column, &nested_path, nested, parameters);
+                format!("jsonb_set({}, {}, {}, true)", merged, key_path, nested_merged)
+            }
+            value => {
+                let value = placeholder(SqlFamily::Postgres, parameters, PrismaValue::String(value.to_string()));
+                format!("jsonb_set({}, {}, {}::jsonb, true)", merged, key_path, value)
+            }
+        };
+    }
+
+    merged
+}
+
+fn postgres_path(path: &[&str], parameters: &mut Vec<PrismaValue>) -> String {
+    let keys: Vec<String> = path
+        .iter()
+        .map(|key| {
+            let key = placeholder(SqlFamily::Postgres, parameters, PrismaValue::String((*key).to_owned()));
+            format!("{}::text", key)
+        })
+        .collect();
+
+    format!("ARRAY[{}]", keys.join(", "))
+}
+
+fn placeholder(sql_family: SqlFamily, parameters: &mut Vec<PrismaValue>, value: PrismaValue) -> String {
+    parameters.push(value);
+
+    match sql_family {
+        SqlFamily::Postgres => format!("${}", parameters.len()),
+        _ => "?".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prisma_models::{DatamodelConverter, InternalDataModelRef};
+    use quaint::ast::ParameterizedValue;
+    use serde_json::json;
+
+    const DATAMODEL: &str = r#"
+        model User {
+            id   String @id
+            meta Json?
+        }
+
+        model Membership {
+            userId String
+            teamId String
+            meta   Json?
+
+            @@id([userId, teamId])
+        }
+    "#;
+
+    fn internal_data_model() -> InternalDataModelRef {
+        let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+        DatamodelConverter::convert(&datamodel).build("db".to_owned())
+    }
+
+    fn ids(model: &ModelRef, values: &[&[&str]]) -> Vec<RecordIdentifier> {
+        values
+            .iter()
+            .map(|values| {
+                model
+                    .primary_identifier()
+                    .data_source_fields()
+                    .zip(values.iter())
+                    .map(|(field, value)| (field, PrismaValue::String((*value).to_owned())))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn patches(patch: Value) -> HashMap<String, Map<String, Value>> {
+        let mut patches = HashMap::new();
+
+        match patch {
+            Value::Object(patch) => patches.insert("meta".to_owned(), patch),
+            patch => panic!("Expected an object, got {}", patch),
+        };
+
+        patches
+    }
+
+    fn merge(model: &str, ids: &[&[&str]], patch: Value, sql_family: SqlFamily) -> Vec<RawQuery<'static>> {
+        let internal_data_model = internal_data_model();
+        let model = internal_data_model.find_model(model).unwrap();
+        let ids = self::ids(&model, ids);
+        let ids: Vec<&RecordIdentifier> = ids.iter().collect();
+
+        merge_json(&model, &ids, &patches(patch), sql_family)
+    }
+
+    fn string(value: &str) -> ParameterizedValue<'static> {
+        ParameterizedValue::from(PrismaValue::String(value.to_owned()))
+    }
+
+    #[test]
+    fn records_are_merged_with_one_statement_on_sqlite() {
+        let queries = merge("User", &[&["1"], &["2"]], json!({ "theme": "dark" }), SqlFamily::Sqlite);
+
+        assert_eq!(queries.len(), 1);
+        assert_eq!(
+            queries[0].query(),
+            r#"UPDATE "db"."User" SET "meta" = json_patch(COALESCE("meta", '{}'), ?) WHERE "id" IN (?, ?)"#
+        );
+        assert_eq!(
+            queries[0].parameters(),
+            &[string(r#"{"theme":"dark"}"#), string("1"), string("2")][..]
+        );
+    }
+
+    #[test]
+    fn records_are_merged_with_one_statement_on_mysql() {
+        let queries = merge("User", &[&["1"], &["2"]], json!({ "theme": "dark" }), SqlFamily::Mysql);
+
+        assert_eq!(queries.len(), 1);
+        assert_eq!(
+            queries[0].query(),
+            "UPDATE `db`.`User` SET `meta` = JSON_MERGE_PATCH(COALESCE(`meta`, '{}'), ?) WHERE `id` IN (?, ?)"
+        );
+        assert_eq!(
+            queries[0].parameters(),
+            &[string(r#"{"theme":"dark"}"#), string("1"), string("2")][..]
+        );
+    }
+
+    #[test]
+    fn records_are_merged_with_one_statement_on_postgres() {
+        let queries = merge(
+            "User",
+            &[&["1"], &["2"]],
+            json!({ "theme": "dark" }),
+            SqlFamily::Postgres,
+        );
+
+        assert_eq!(queries.len(), 1);
+        assert_eq!(
+            queries[0].query(),
+            concat!(
+                r#"UPDATE "db"."User" SET "meta" = jsonb_set(CASE WHEN jsonb_typeof("meta"::jsonb) = 'object' "#,
+                r#"THEN "meta"::jsonb ELSE '{}'::jsonb END, ARRAY[$1::text], $2::jsonb, true) "#,
+                r#"WHERE "id" IN ($3, $4)"#
+            )
+        );
+        assert_eq!(
+            queries[0].parameters(),
+            &[string("theme"), string(r#""dark""#), string("1"), string("2")][..]
+        );
+    }
+
+    #[test]
+    fn null_values_remove_keys_on_postgres() {
+        let queries = merge("User", &[&["1"]], json!({ "theme": null }), SqlFamily::Postgres);
+
+        assert_eq!(
+            queries[0].query(),
+            concat!(
+                r#"UPDATE "db"."User" SET "meta" = (CASE WHEN jsonb_typeof("meta"::jsonb) = 'object' "#,
+                r#"THEN "meta"::jsonb ELSE '{}'::jsonb END #- ARRAY[$1::text]) WHERE "id" IN ($2)"#
+            )
+        );
+    }
+
+    #[test]
+    fn nested_objects_are_merged_into_the_stored_object_on_postgres() {
+        let queries = merge(
+            "User",
+            &[&["1"]],
+            json!({ "ui": { "theme": "dark" } }),
+            SqlFamily::Postgres,
+        );
+
+        assert!(
+            queries[0]
+                .query()
+                .contains(r#"CASE WHEN jsonb_typeof("meta"::jsonb #> ARRAY[$2::text]) = 'object'"#),
+            "{}",
+            queries[0].query()
+        );
+        assert_eq!(
+            queries[0].parameters(),
+            &[
+                string("ui"),
+                string("ui"),
+                string("theme"),
+                string(r#""dark""#),
+                string("1")
+            ][..]
+        );
+    }
+
+    #[test]
+    fn compound_ids_are_selected_by_all_their_columns() {
+        let queries = merge(
+            "Membership",
+            &[&["1", "a"], &["2", "b"]],
+            json!({ "role": "admin" }),
+            SqlFamily::Sqlite,
+        );
+
+        assert!(
+            queries[0]
+                .query()
+                .ends_with(r#"WHERE ("userId" = ? AND "teamId" = ?) OR ("userId" = ? AND "teamId" = ?)"#),
+            "{}",
+            queries[0].query()
+        );
+    }
+
+    #[test]
+    fn records_are_merged_in_chunks_of_the_parameter_limit() {
+        let values: Vec<String> = (0..=super::super::PARAMETER_LIMIT).map(|id| id.to_string()).collect();
+        let values: Vec<[&str; 1]> = values.iter().map(|value| [value.as_str()]).collect();
+        let values: Vec<&[&str]> = values.iter().map(|value| &value[..]).collect();
+
+        let queries = merge("User", &values, json!({ "theme": "dark" }), SqlFamily::Sqlite);
+
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].parameters().len(), super::super::PARAMETER_LIMIT + 1);
+        assert_eq!(queries[1].parameters().len(), 2);
+    }
+
+    #[test]
+    fn nothing_is_merged_without_patches() {
+        let internal_data_model = internal_data_model();
+        let model = internal_data_model.find_model("User").unwrap();
+        let ids = ids(&model, &[&["1"]]);
+
+        assert!(merge_json(&model, &[&ids[0]], &HashMap::new(), SqlFamily::Sqlite).is_empty());
+    }
+}
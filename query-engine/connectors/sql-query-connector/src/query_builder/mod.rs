@@ -1,4 +1,7 @@
+pub mod idempotency;
+pub mod json_merge;
 pub mod read;
+pub mod routine;
 pub mod write;
 
 pub use read::*;
@@ -6,10 +9,22 @@ pub use write::*;
 
 use prisma_models::RecordIdentifier;
 use prisma_value::PrismaValue;
-use quaint::ast::{Column, Comparable, ConditionTree, Query};
+use quaint::{
+    ast::{Column, Comparable, ConditionTree, Query},
+    prelude::SqlFamily,
+};
+use sql_identifier::Delimiter;
 
 const PARAMETER_LIMIT: usize = 10000;
 
+/// The delimiter of identifiers in SQL rendered by the connector itself, instead of by the query AST.
+pub(crate) fn delimiter(sql_family: SqlFamily) -> Delimiter {
+    match sql_family {
+        SqlFamily::Mysql => Delimiter::Backtick,
+        SqlFamily::Postgres | SqlFamily::Sqlite => Delimiter::DoubleQuote,
+    }
+}
+
 pub(super) fn chunked_conditions<F, Q>(
     columns: &[Column<'static>],
     records: &[&RecordIdentifier],
@@ -55,3 +70,103 @@ pub(super) fn conditions<'a>(
             }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use connector_interface::{QueryArguments, WriteArgs};
+    use prisma_models::*;
+    use quaint::visitor::{self, Visitor};
+
+    /// The column is named by a reserved word, the table gets a name with the delimiters of all SQL families.
+    const DATAMODEL: &str = r#"
+        model User {
+            id   String @id @map("select")
+            name String
+        }
+    "#;
+
+    fn internal_data_model(sql_family: SqlFamily) -> InternalDataModelRef {
+        let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+        let mut template = DatamodelConverter::convert(&datamodel);
+
+        template.models[0].manifestation = Some(r#"user"s`"#.to_owned());
+        template.identifier_delimiter = Some(delimiter(sql_family));
+        template.build("db".to_owned())
+    }
+
+    fn render(query: Query<'static>, sql_family: SqlFamily) -> String {
+        let (sql, _) = match sql_family {
+            SqlFamily::Postgres => visitor::Postgres::build(query),
+            SqlFamily::Mysql => visitor::Mysql::build(query),
+            SqlFamily::Sqlite => visitor::Sqlite::build(query),
+        };
+
+        sql
+    }
+
+    /// Renders a read, a create and an update of the user.
+    fn queries(sql_family: SqlFamily) -> Vec<String> {
+        let internal_data_model = internal_data_model(sql_family);
+        let user = internal_data_model.find_model("User").unwrap();
+        let id = RecordIdentifier::from(vec![(
+            user.primary_identifier().data_source_fields().next().unwrap(),
+            PrismaValue::String("1".to_owned()),
+        )]);
+
+        let read = read::get_records(&user, user.primary_identifier().as_columns(), QueryArguments::default());
+
+        let mut args = WriteArgs::new();
+        args.insert("select", PrismaValue::String("1".to_owned()));
+        args.insert("name", PrismaValue::String("Alice".to_owned()));
+        let (create, _) = write::create_record(&user, args.clone());
+
+        let mut queries = vec![render(read.into(), sql_family), render(create.into(), sql_family)];
+
+        for update in write::update_many(&user, &[&id], args).unwrap() {
+            queries.push(render(update, sql_family));
+        }
+
+        queries
+    }
+
+    #[test]
+    fn hostile_names_are_escaped_on_postgres() {
+        for sql in queries(SqlFamily::Postgres) {
+            assert!(sql.contains(r#""db"."user""s`""#), "{}", sql);
+            assert!(sql.contains(r#""select""#), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn hostile_names_are_escaped_on_mysql() {
+        for sql in queries(SqlFamily::Mysql) {
+            assert!(sql.contains(r#"`db`.`user"s```"#), "{}", sql);
+            assert!(sql.contains("`select`"), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn mysql_inserts_return_the_primary_identifier() {
+        let internal_data_model = internal_data_model(SqlFamily::Mysql);
+        let user = internal_data_model.find_model("User").unwrap();
+
+        let mut args = WriteArgs::new();
+        args.insert("name", PrismaValue::String("Alice".to_owned()));
+        let (create, _) = write::create_record(&user, args);
+
+        let (sql, params) = write::mysql_insert_returning(&user, create);
+
+        assert!(sql.starts_with("INSERT INTO `db`.`user\"s```"), "{}", sql);
+        assert!(sql.ends_with(" RETURNING `select`"), "{}", sql);
+        assert_eq!(params.len(), 1);
+    }
+
+    #[test]
+    fn hostile_names_are_escaped_on_sqlite() {
+        for sql in queries(SqlFamily::Sqlite) {
+            assert!(sql.contains(r#""user""s`""#), "{}", sql);
+            assert!(sql.contains(r#""select""#), "{}", sql);
+        }
+    }
+}
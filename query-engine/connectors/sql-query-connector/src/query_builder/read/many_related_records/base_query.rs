@@ -37,8 +37,8 @@ impl<'a> ManyRelatedRecordsBaseQuery<'a> {
         let query = if from_field.relation_is_inlined_in_child() {
             columns.iter().fold(select, |acc, col| acc.column(col.clone()))
         } else {
-            let id_columns: Vec<Column<'static>> =
-                from_field.related_model().primary_identifier().as_columns().collect();
+            // The foreign key of the parent doesn't necessarily reference the primary ID of the related model.
+            let link_columns: Vec<Column<'static>> = from_field.related_field().linking_fields().as_columns().collect();
 
             let opposite_columns: Vec<Column<'static>> = from_field.opposite_columns(true).collect();
 
@@ -46,7 +46,7 @@ impl<'a> ManyRelatedRecordsBaseQuery<'a> {
                 .relation()
                 .as_table()
                 .alias(Relation::TABLE_ALIAS)
-                .on(Row::from(id_columns).equals(Row::from(opposite_columns)));
+                .on(Row::from(link_columns).equals(Row::from(opposite_columns)));
 
             columns
                 .iter()
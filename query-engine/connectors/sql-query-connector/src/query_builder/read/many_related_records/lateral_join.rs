@@ -0,0 +1,138 @@
+use super::*;
+use crate::{error::SqlError, ordering::Ordering, query_builder};
+use connector_interface::SkipAndLimit;
+use prisma_models::sql_ext::{AsColumns, AsTable};
+use quaint::ast::*;
+use sql_identifier::Quoted;
+
+/// Paginates the related records of every parent in a `LATERAL` joined select, which reads only the requested page
+/// per parent instead of numbering all related records with a window function.
+pub struct ManyRelatedRecordsWithLateralJoin;
+
+impl ManyRelatedRecordsQueryBuilder for ManyRelatedRecordsWithLateralJoin {
+    fn with_pagination(base: ManyRelatedRecordsBaseQuery) -> Query {
+        let from_field = base.from_field;
+
+        // The parents are read from their own table, so that every parent is joined exactly once.
+        let parent_link_fields = if from_field.relation_is_inlined_in_parent() {
+            from_field.model().primary_identifier()
+        } else {
+            from_field.linking_fields()
+        };
+
+        let parent_columns: Vec<Column<'static>> = parent_link_fields.as_columns().collect();
+        let internal_data_model = from_field.model().internal_data_model();
+        let aliased_parent_columns: Vec<Column<'static>> = parent_link_fields
+            .data_source_fields()
+            .map(|dsf| Column::from((Self::PARENT_TABLE_ALIAS, internal_data_model.identifier(&dsf.name))))
+            .collect();
+
+        let parents = parent_columns
+            .iter()
+            .fold(Select::from_table(from_field.model().as_table()), |acc, col| {
+                acc.column(col.clone())
+            })
+            .so_that(query_builder::conditions(&parent_columns, base.from_record_ids));
+
+        let relation_columns: Vec<Column<'static>> = from_field.relation_columns(true).collect();
+        let conditions = Row::from(relation_columns)
+            .equals(Row::from(aliased_parent_columns))
+            .and(base.condition)
+            .and(base.cursor);
+
+        let id_columns = from_field.related_model().primary_identifier().as_columns();
+        let order_columns = Ordering::internal(id_columns, base.order_directions);
+
+        let page = match base.skip_and_limit {
+            SkipAndLimit {
+                skip,
+                limit: Some(limit),
+            } => base.query.limit(limit).offset(skip),
+            SkipAndLimit { skip, limit: None } => base.query.offset(skip),
+        };
+
+        let page = order_columns
+            .into_iter()
+            .fold(page.so_that(conditions), |acc, ord| acc.order_by(ord));
+
+        let join = Table::from(page)
+            .alias(Self::LATERAL_TABLE_ALIAS)
+            .on(ConditionTree::NoCondition);
+
+        Select::from_table(Table::from(parents).alias(Self::PARENT_TABLE_ALIAS))
+            .value(Table::from(Self::LATERAL_TABLE_ALIAS).asterisk())
+            .inner_join(join)
+            .into()
+    }
+
+    fn uses_row_number() -> bool {
+        false
+    }
+
+    fn sql_rewrite() -> Option<fn(String) -> crate::Result<String>> {
+        Some(join_laterally)
+    }
+}
+
+/// The query AST can't express `LATERAL`, the join of the page is turned into one in the rendered SQL. Without the
+/// join in the SQL, the page would reference the parents without being joined laterally, which fails.
+fn join_laterally(sql: String) -> crate::Result<String> {
+    let alias = Quoted::postgres(ManyRelatedRecordsWithLateralJoin::PARENT_TABLE_ALIAS);
+    let join = format!("AS {} INNER JOIN (", alias);
+
+    if !sql.contains(&join) {
+        return Err(SqlError::QueryError(
+            format!(
+                "The lateral join of the related records is missing in the query: {}",
+                sql
+            )
+            .into(),
+        ));
+    }
+
+    Ok(sql.replacen(&join, &format!("AS {} INNER JOIN LATERAL (", alias), 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quaint::visitor::{self, Visitor};
+
+    fn paginated_children() -> Select<'static> {
+        let parents = Select::from_table("Parent").column("id");
+        let page = Select::from_table("Child")
+            .so_that(Column::from("parentId").equals(Column::from((
+                ManyRelatedRecordsWithLateralJoin::PARENT_TABLE_ALIAS,
+                "id",
+            ))))
+            .limit(2);
+
+        let join = Table::from(page)
+            .alias(ManyRelatedRecordsWithLateralJoin::LATERAL_TABLE_ALIAS)
+            .on(ConditionTree::NoCondition);
+
+        Select::from_table(Table::from(parents).alias(ManyRelatedRecordsWithLateralJoin::PARENT_TABLE_ALIAS))
+            .value(Table::from(ManyRelatedRecordsWithLateralJoin::LATERAL_TABLE_ALIAS).asterisk())
+            .inner_join(join)
+    }
+
+    #[test]
+    fn the_page_is_joined_laterally() {
+        let (sql, _) = visitor::Postgres::build(paginated_children());
+        let sql = join_laterally(sql).unwrap();
+
+        assert!(
+            sql.contains(r#"AS "prismaParentTableAlias" INNER JOIN LATERAL (SELECT"#),
+            "{}",
+            sql
+        );
+        assert_eq!(sql.matches("LATERAL").count(), 1, "{}", sql);
+    }
+
+    #[test]
+    fn queries_without_the_join_are_rejected() {
+        let (sql, _) = visitor::Postgres::build(Select::from_table("Child"));
+
+        assert!(join_laterally(sql).is_err());
+    }
+}
@@ -1,19 +1,23 @@
 mod base_query;
+mod lateral_join;
 mod row_number;
 mod union_all;
 
 pub use base_query::*;
+pub use lateral_join::*;
 pub use row_number::*;
 pub use union_all::*;
 
 use crate::{ordering::Ordering, query_builder};
-use prisma_models::sql_ext::RelationFieldExt;
+use prisma_models::sql_ext::{AsColumns, RelationFieldExt};
 use quaint::ast::Query;
 
 pub trait ManyRelatedRecordsQueryBuilder {
     const BASE_TABLE_ALIAS: &'static str = "prismaBaseTableAlias";
     const ROW_NUMBER_ALIAS: &'static str = "prismaRowNumberAlias";
     const ROW_NUMBER_TABLE_ALIAS: &'static str = "prismaRowNumberTableAlias";
+    const PARENT_TABLE_ALIAS: &'static str = "prismaParentTableAlias";
+    const LATERAL_TABLE_ALIAS: &'static str = "prismaLateralTableAlias";
 
     fn with_pagination<'a>(base: ManyRelatedRecordsBaseQuery<'a>) -> Query;
 
@@ -24,8 +28,8 @@ pub trait ManyRelatedRecordsQueryBuilder {
             .and(base.condition)
             .and(base.cursor);
 
-        let opposite_columns = base.from_field.opposite_columns(true);
-        let order_columns = Ordering::internal(opposite_columns, base.order_directions);
+        let id_columns = base.from_field.related_model().primary_identifier().as_columns();
+        let order_columns = Ordering::internal(id_columns, base.order_directions);
 
         order_columns
             .into_iter()
@@ -34,4 +38,9 @@ pub trait ManyRelatedRecordsQueryBuilder {
     }
 
     fn uses_row_number() -> bool;
+
+    /// Rewrites the rendered SQL of paginated queries, for syntax the query AST can't express.
+    fn sql_rewrite() -> Option<fn(String) -> crate::Result<String>> {
+        None
+    }
 }
@@ -24,10 +24,19 @@ impl ManyRelatedRecordsQueryBuilder for ManyRelatedRecordsWithRowNumber {
             }
         }
 
+        // The primary identifier of the related records is always selected, ordering by it makes pages stable.
+        let related_model = base.from_field.related_model();
+        let internal_data_model = related_model.internal_data_model();
+        let id_fields: Vec<String> = related_model
+            .primary_identifier()
+            .data_source_fields()
+            .map(|dsf| internal_data_model.identifier(&dsf.name))
+            .collect();
+
         let order_columns = Ordering::aliased_internal(
             Self::BASE_TABLE_ALIAS,
             Self::BASE_TABLE_ALIAS,
-            SelectedFields::RELATED_MODEL_ALIAS,
+            id_fields,
             base.order_directions,
         );
 
@@ -1,7 +1,7 @@
 use super::*;
 use crate::ordering::Ordering;
 use connector_interface::SkipAndLimit;
-use prisma_models::{RecordIdentifier, SelectedFields};
+use prisma_models::{sql_ext::AsColumns, RecordIdentifier};
 use quaint::ast::*;
 
 pub struct ManyRelatedRecordsWithUnionAll;
@@ -15,7 +15,8 @@ impl ManyRelatedRecordsQueryBuilder for ManyRelatedRecordsWithUnionAll {
             ids
         };
 
-        let order_columns = Ordering::internal(vec![SelectedFields::RELATED_MODEL_ALIAS], base.order_directions);
+        let id_columns = base.from_field.related_model().primary_identifier().as_columns();
+        let order_columns = Ordering::internal(id_columns, base.order_directions);
         let base_condition = base.condition.and(base.cursor);
 
         let base_query = match base.skip_and_limit {
@@ -29,27 +30,19 @@ impl ManyRelatedRecordsQueryBuilder for ManyRelatedRecordsWithUnionAll {
         let base_query = order_columns.into_iter().fold(base_query, |acc, ord| acc.order_by(ord));
         let mut distinct_ids = distinct_ids.into_iter();
 
-        //        let build_cond = |ids: RecordIdentifier| {
-        //            let id_cond = ids.into_iter().fold(ConditionTree::NoCondition, |acc, (dsf, val)| {
-        //                let col = dsf.as_column();
-        //                match acc {
-        //                    ConditionTree::NoCondition => col.equals(val).into(),
-        //                    cond => cond.and(col.equals(val)),
-        //                }
-        //            });
-        //
-        //            let conditions = base_condition.clone().and(id_cond);
-        //
-        //            base_query.clone().so_that(conditions)
-        //        };
-
-        let from_field = base.from_field;
+        // One select per parent, matching each relation column against the corresponding value of the parent id,
+        // which covers relations linked by compound foreign keys.
+        let relation_columns: Vec<_> = base.from_field.relation_columns(true).collect();
         let build_cond = |id: RecordIdentifier| {
-            let conditions = base_condition
-                .clone()
-                .and(from_field.relation_column(true).equals(id.single_value()));
-
-            base_query.clone().so_that(conditions)
+            let id_cond = relation_columns.iter().cloned().zip(id.values()).fold(
+                ConditionTree::NoCondition,
+                |acc, (col, val)| match acc {
+                    ConditionTree::NoCondition => col.equals(val).into(),
+                    cond => cond.and(col.equals(val)),
+                },
+            );
+
+            base_query.clone().so_that(base_condition.clone().and(id_cond))
         };
 
         if let Some(id) = distinct_ids.nth(0) {
@@ -0,0 +1,321 @@
+use connector_interface::{Routine, RoutineKind, RoutineParameter};
+use prisma_models::TypeIdentifier;
+use quaint::prelude::SqlFamily;
+use sql_identifier::{quote_with_schema, Quoted};
+
+/// Lists the routines of a schema, one row per parameter, ordered by routine and position of the parameter.
+/// Routines without parameters have a single row without parameter columns.
+pub fn list_routines(sql_family: SqlFamily) -> Option<&'static str> {
+    match sql_family {
+        SqlFamily::Postgres => Some(
+            r#"
+            SELECT r.specific_name AS specific_name, r.routine_name AS routine_name, r.routine_type AS routine_type,
+                   p.parameter_name AS parameter_name, p.parameter_mode AS parameter_mode,
+                   p.data_type AS data_type, p.udt_name AS udt_name
+            FROM information_schema.routines r
+            LEFT JOIN information_schema.parameters p
+                ON p.specific_schema = r.specific_schema AND p.specific_name = r.specific_name
+            WHERE r.routine_schema = $1
+            ORDER BY r.specific_name, p.ordinal_position
+            "#,
+        ),
+        SqlFamily::Mysql => Some(
+            r#"
+            SELECT r.specific_name AS specific_name, r.routine_name AS routine_name, r.routine_type AS routine_type,
+                   p.parameter_name AS parameter_name, p.parameter_mode AS parameter_mode,
+                   p.data_type AS data_type
+            FROM information_schema.routines r
+            LEFT JOIN information_schema.parameters p
+                ON p.specific_schema = r.routine_schema AND p.specific_name = r.specific_name
+                AND p.routine_type = r.routine_type
+            WHERE r.routine_schema = ?
+            ORDER BY r.specific_name, p.ordinal_position
+            "#,
+        ),
+        // SQLite has no stored routines.
+        SqlFamily::Sqlite => None,
+    }
+}
+
+/// A row of the `list_routines` query. The parameter columns are `None` for routines without parameters.
+#[derive(Debug, Default, Clone)]
+pub struct RoutineRow {
+    pub specific_name: String,
+    pub routine_name: String,
+    pub routine_type: Option<String>,
+    pub parameter_name: Option<String>,
+    pub parameter_mode: Option<String>,
+    pub data_type: Option<String>,
+    pub udt_name: Option<String>,
+}
+
+/// Groups the rows of the `list_routines` query into routines. Routines with parameters of types that don't map to the
+/// types of the engine are left out, as are overloads of a routine after the first one, which can't be told apart by
+/// name.
+pub fn group_routines(rows: impl IntoIterator<Item = RoutineRow>) -> Vec<Routine> {
+    // Routines by specific name, which is unique for overloads, `None` if a parameter is not supported.
+    let mut routines: Vec<(String, Option<Routine>)> = Vec::new();
+
+    for row in rows {
+        if routines
+            .last()
+            .map(|(name, _)| name != &row.specific_name)
+            .unwrap_or(true)
+        {
+            let kind = match row.routine_type.as_ref().map(String::as_str) {
+                Some("PROCEDURE") => RoutineKind::Procedure,
+                _ => RoutineKind::Function,
+            };
+
+            let routine = Routine {
+                name: row.routine_name.clone(),
+                kind,
+                parameters: Vec::new(),
+            };
+
+            routines.push((row.specific_name.clone(), Some(routine)));
+        }
+
+        let (_, entry) = routines.last_mut().unwrap();
+
+        let routine = match entry {
+            Some(routine) => routine,
+            None => continue,
+        };
+
+        // Output parameters of functions are columns of the result, procedures can't be called without them.
+        match row.parameter_mode.as_ref().map(String::as_str) {
+            Some("IN") | Some("INOUT") => {
+                let data_type = row.data_type.unwrap_or_default();
+
+                match (
+                    parameter_type(&data_type, row.udt_name.as_ref().map(String::as_str)),
+                    row.parameter_name,
+                ) {
+                    (Some((type_identifier, is_list)), Some(name)) => routine.parameters.push(RoutineParameter {
+                        name,
+                        type_identifier,
+                        is_list,
+                    }),
+                    _ => *entry = None,
+                }
+            }
+            Some("OUT") if routine.kind == RoutineKind::Procedure => *entry = None,
+            _ => (),
+        }
+    }
+
+    let mut result: Vec<Routine> = Vec::new();
+
+    for routine in routines.into_iter().filter_map(|(_, routine)| routine) {
+        if result.iter().all(|existing| existing.name != routine.name) {
+            result.push(routine);
+        }
+    }
+
+    result
+}
+
+/// Maps the type of a parameter to the type identifier of its arguments and whether it takes a list.
+/// Postgres reports arrays as `ARRAY`, with the element type in the `udt_name`, e.g. `_int4`.
+pub fn parameter_type(data_type: &str, udt_name: Option<&str>) -> Option<(TypeIdentifier, bool)> {
+    if data_type == "ARRAY" {
+        udt_name
+            .map(|udt_name| udt_name.trim_start_matches('_'))
+            .and_then(scalar_type)
+            .map(|type_identifier| (type_identifier, true))
+    } else {
+        scalar_type(data_type).map(|type_identifier| (type_identifier, false))
+    }
+}
+
+fn scalar_type(data_type: &str) -> Option<TypeIdentifier> {
+    let type_identifier = match data_type.to_lowercase().as_str() {
+        "integer" | "int" | "int2" | "int4" | "int8" | "smallint" | "mediumint" | "bigint" | "tinyint" => {
+            TypeIdentifier::Int
+        }
+        "numeric" | "decimal" => TypeIdentifier::Decimal,
+        "real" | "float" | "float4" | "float8" | "double" | "double precision" => TypeIdentifier::Float,
+        "boolean" | "bool" => TypeIdentifier::Boolean,
+        "text" | "tinytext" | "mediumtext" | "longtext" | "character varying" | "varchar" | "character" | "char"
+        | "bpchar" | "enum" => TypeIdentifier::String,
+        "date" | "datetime" | "timestamp" | "timestamptz" => TypeIdentifier::DateTime,
+        x if x.starts_with("timestamp") => TypeIdentifier::DateTime,
+        "json" | "jsonb" => TypeIdentifier::Json,
+        "uuid" => TypeIdentifier::UUID,
+        _ => return None,
+    };
+
+    Some(type_identifier)
+}
+
+/// Renders the call of a routine with one placeholder per parameter. Procedures are invoked with `CALL`,
+/// functions are selected from, so that table-valued functions return all of their columns.
+pub fn call_routine(routine: &Routine, schema_name: &str, sql_family: SqlFamily) -> String {
+    let placeholders: Vec<String> = (1..=routine.parameters.len())
+        .map(|position| match sql_family {
+            SqlFamily::Postgres => format!("${}", position),
+            _ => "?".to_owned(),
+        })
+        .collect();
+
+    let delimiter = super::delimiter(sql_family);
+
+    let name = match sql_family {
+        SqlFamily::Sqlite => Quoted::new(delimiter, &routine.name).to_string(),
+        _ => quote_with_schema(delimiter, schema_name, &routine.name),
+    };
+
+    let call = format!("{}({})", name, placeholders.join(", "));
+
+    match (routine.kind, sql_family) {
+        (RoutineKind::Procedure, _) => format!("CALL {}", call),
+        (RoutineKind::Function, SqlFamily::Postgres) => format!("SELECT * FROM {}", call),
+        (RoutineKind::Function, _) => format!("SELECT {} AS {}", call, Quoted::new(delimiter, &routine.name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routine(kind: RoutineKind) -> Routine {
+        Routine {
+            name: "refresh_stats".to_owned(),
+            kind,
+            parameters: vec![
+                RoutineParameter {
+                    name: "since".to_owned(),
+                    type_identifier: TypeIdentifier::DateTime,
+                    is_list: false,
+                },
+                RoutineParameter {
+                    name: "user_ids".to_owned(),
+                    type_identifier: TypeIdentifier::String,
+                    is_list: true,
+                },
+            ],
+        }
+    }
+
+    fn row(specific_name: &str, routine_type: &str, parameter: Option<(&str, &str, &str)>) -> RoutineRow {
+        RoutineRow {
+            specific_name: specific_name.to_owned(),
+            routine_name: specific_name.trim_end_matches(char::is_numeric).to_owned(),
+            routine_type: Some(routine_type.to_owned()),
+            parameter_name: parameter.map(|(name, _, _)| name.to_owned()),
+            parameter_mode: parameter.map(|(_, mode, _)| mode.to_owned()),
+            data_type: parameter.map(|(_, _, data_type)| data_type.to_owned()),
+            udt_name: None,
+        }
+    }
+
+    #[test]
+    fn procedures_are_called() {
+        let procedure = routine(RoutineKind::Procedure);
+
+        assert_eq!(
+            call_routine(&procedure, "public", SqlFamily::Postgres),
+            r#"CALL "public"."refresh_stats"($1, $2)"#
+        );
+        assert_eq!(
+            call_routine(&procedure, "app", SqlFamily::Mysql),
+            "CALL `app`.`refresh_stats`(?, ?)"
+        );
+    }
+
+    #[test]
+    fn functions_are_selected_from() {
+        let function = routine(RoutineKind::Function);
+
+        assert_eq!(
+            call_routine(&function, "public", SqlFamily::Postgres),
+            r#"SELECT * FROM "public"."refresh_stats"($1, $2)"#
+        );
+        assert_eq!(
+            call_routine(&function, "app", SqlFamily::Mysql),
+            "SELECT `app`.`refresh_stats`(?, ?) AS `refresh_stats`"
+        );
+        assert_eq!(
+            call_routine(&function, "main", SqlFamily::Sqlite),
+            r#"SELECT "refresh_stats"(?, ?) AS "refresh_stats""#
+        );
+    }
+
+    #[test]
+    fn names_of_routines_are_escaped() {
+        let mut function = routine(RoutineKind::Function);
+        function.name = r#"refresh"stats"#.to_owned();
+        function.parameters.clear();
+
+        assert_eq!(
+            call_routine(&function, "public", SqlFamily::Postgres),
+            r#"SELECT * FROM "public"."refresh""stats"()"#
+        );
+    }
+
+    #[test]
+    fn parameter_types_are_mapped() {
+        assert_eq!(parameter_type("integer", None), Some((TypeIdentifier::Int, false)));
+        assert_eq!(
+            parameter_type("character varying", None),
+            Some((TypeIdentifier::String, false))
+        );
+        assert_eq!(
+            parameter_type("timestamp with time zone", None),
+            Some((TypeIdentifier::DateTime, false))
+        );
+        assert_eq!(
+            parameter_type("ARRAY", Some("_int4")),
+            Some((TypeIdentifier::Int, true))
+        );
+        assert_eq!(parameter_type("ARRAY", None), None);
+        assert_eq!(parameter_type("geometry", None), None);
+    }
+
+    #[test]
+    fn rows_are_grouped_by_routine() {
+        let routines = group_routines(vec![
+            row("refresh_stats", "PROCEDURE", Some(("since", "IN", "date"))),
+            row("refresh_stats", "PROCEDURE", Some(("limit", "INOUT", "int"))),
+            row("now", "FUNCTION", None),
+        ]);
+
+        assert_eq!(routines.len(), 2);
+        assert_eq!(routines[0].name, "refresh_stats");
+        assert_eq!(routines[0].kind, RoutineKind::Procedure);
+
+        let parameters: Vec<&str> = routines[0].parameters.iter().map(|param| param.name.as_str()).collect();
+        assert_eq!(parameters, &["since", "limit"]);
+
+        assert_eq!(routines[1].name, "now");
+        assert_eq!(routines[1].kind, RoutineKind::Function);
+        assert!(routines[1].parameters.is_empty());
+    }
+
+    #[test]
+    fn routines_with_unsupported_parameters_are_left_out() {
+        let routines = group_routines(vec![
+            row("nearby", "FUNCTION", Some(("location", "IN", "geometry"))),
+            row("nearby", "FUNCTION", Some(("radius", "IN", "int"))),
+            row("archive", "PROCEDURE", Some(("archived", "OUT", "int"))),
+            row("total", "FUNCTION", Some(("total", "OUT", "int"))),
+        ]);
+
+        let names: Vec<&str> = routines.iter().map(|routine| routine.name.as_str()).collect();
+        assert_eq!(names, &["total"]);
+    }
+
+    #[test]
+    fn only_the_first_overload_is_listed() {
+        let routines = group_routines(vec![
+            row("area1", "FUNCTION", Some(("radius", "IN", "float"))),
+            row("area2", "FUNCTION", Some(("width", "IN", "float"))),
+            row("area2", "FUNCTION", Some(("height", "IN", "float"))),
+        ]);
+
+        assert_eq!(routines.len(), 1);
+        assert_eq!(routines[0].name, "area");
+        assert_eq!(routines[0].parameters[0].name, "radius");
+    }
+}
@@ -1,6 +1,11 @@
 use connector_interface::WriteArgs;
 use prisma_models::*;
-use quaint::ast::*;
+use quaint::{
+    ast::*,
+    prelude::SqlFamily,
+    visitor::{self, Visitor},
+};
+use sql_identifier::Quoted;
 
 pub fn create_record(model: &ModelRef, mut args: WriteArgs) -> (Insert<'static>, Option<RecordIdentifier>) {
     let return_id = args.as_record_identifier(model.primary_identifier());
@@ -17,11 +22,12 @@ pub fn create_record(model: &ModelRef, mut args: WriteArgs) -> (Insert<'static>,
         })
         .collect();
 
+    let internal_data_model = model.internal_data_model();
     let insert = fields
         .into_iter()
         .fold(Insert::single_into(model.as_table()), |insert, db_name| {
             let value = args.take_field_value(&db_name).unwrap();
-            insert.value(db_name, value)
+            insert.value(internal_data_model.identifier(&db_name), value)
         });
 
     (
@@ -30,6 +36,19 @@ pub fn create_record(model: &ModelRef, mut args: WriteArgs) -> (Insert<'static>,
     )
 }
 
+/// Renders the insert of `create_record` with a `RETURNING` clause for the primary identifier, which the MySQL visitor
+/// leaves out. MariaDB supports the clause since 10.5.
+pub fn mysql_insert_returning(model: &ModelRef, insert: Insert<'static>) -> (String, Vec<ParameterizedValue<'static>>) {
+    let (sql, params) = visitor::Mysql::build(insert);
+    let columns: Vec<String> = model
+        .primary_identifier()
+        .db_names()
+        .map(|name| Quoted::new(super::delimiter(SqlFamily::Mysql), name).to_string())
+        .collect();
+
+    (format!("{} RETURNING {}", sql, columns.join(", ")), params)
+}
+
 pub fn delete_relation_table_records(
     field: &RelationFieldRef,
     parent_ids: &RecordIdentifier,
@@ -48,6 +67,18 @@ pub fn delete_relation_table_records(
     Delete::from_table(relation.as_table()).so_that(parent_id_criteria.and(child_id_criteria))
 }
 
+pub fn delete_all_relation_table_records(
+    field: &RelationFieldRef,
+    parent_ids: &[&RecordIdentifier],
+) -> Vec<Query<'static>> {
+    let relation = field.relation();
+    let parent_columns: Vec<Column<'static>> = field.relation_columns(false).collect();
+
+    super::chunked_conditions(&parent_columns, parent_ids, |conditions| {
+        Delete::from_table(relation.as_table()).so_that(conditions)
+    })
+}
+
 pub fn delete_many(model: &ModelRef, ids: &[&RecordIdentifier]) -> Vec<Query<'static>> {
     let columns: Vec<_> = model.primary_identifier().as_columns().collect();
 
@@ -84,11 +115,12 @@ pub fn update_many(model: &ModelRef, ids: &[&RecordIdentifier], args: WriteArgs)
         return Ok(Vec::new());
     }
 
+    let internal_data_model = model.internal_data_model();
     let query = args
         .args
         .into_iter()
         .fold(Update::table(model.as_table()), |acc, (name, val)| {
-            acc.set(name, val.clone())
+            acc.set(internal_data_model.identifier(&name), val.clone())
         });
 
     let columns: Vec<_> = model.primary_identifier().as_columns().collect();
@@ -1,4 +1,4 @@
-use crate::{error::*, AliasedCondition, RawQuery, SqlRow, ToSqlRow};
+use crate::{error::*, query_builder, AliasedCondition, RawQuery, SqlRow, ToSqlRow};
 use async_trait::async_trait;
 use connector_interface::filter::Filter;
 use datamodel::FieldArity;
@@ -6,22 +6,69 @@ use futures::future::FutureExt;
 use prisma_models::*;
 use quaint::{
     ast::*,
-    connector::{self, Queryable},
+    connector::{self, Queryable, ResultSet},
     pooled::PooledConnection,
+    prelude::SqlFamily,
+    visitor::{self, Visitor},
 };
 
 use serde_json::{Map, Number, Value};
+use sql_identifier::Quoted;
 use std::{convert::TryFrom, panic::AssertUnwindSafe};
+use tracing_futures::Instrument;
+
+/// Span wrapping the execution of a single SQL statement.
+pub(crate) fn sql_span(kind: &'static str) -> tracing::Span {
+    tracing::info_span!("prisma:sql", kind)
+}
 
 impl<'t> QueryExt for connector::Transaction<'t> {}
 impl QueryExt for PooledConnection {}
 
+/// Renders the query, with the placeholder columns of the computed fields replaced by their SQL expressions and the
+/// SQL passed through `rewrite`. A placeholder missing in the rendered SQL fails the query, as the rows would miss the
+/// column of the field.
+pub(crate) fn render_query<'a>(
+    q: Query<'a>,
+    computed: &[ScalarFieldRef],
+    sql_family: SqlFamily,
+    rewrite: Option<fn(String) -> crate::Result<String>>,
+) -> crate::Result<(String, Vec<ParameterizedValue<'a>>)> {
+    let (mut sql, params) = match sql_family {
+        SqlFamily::Postgres => visitor::Postgres::build(q),
+        SqlFamily::Mysql => visitor::Mysql::build(q),
+        SqlFamily::Sqlite => visitor::Sqlite::build(q),
+    };
+
+    for field in computed {
+        let marker = Quoted::new(query_builder::delimiter(sql_family), computed_marker(field)).to_string();
+
+        if !sql.contains(&marker) {
+            return Err(SqlError::QueryError(
+                format!(
+                    "The column of the computed field {}.{} is missing in the query.",
+                    field.model().name,
+                    field.name
+                )
+                .into(),
+            ));
+        }
+
+        sql = sql.replace(&marker, &format!("({})", field.computed().unwrap()));
+    }
+
+    match rewrite {
+        Some(rewrite) => Ok((rewrite(sql)?, params)),
+        None => Ok((sql, params)),
+    }
+}
+
 /// Functions for querying data.
 /// Basically represents a connection wrapper?
 #[async_trait]
 pub trait QueryExt: Queryable + Send + Sync {
     async fn filter(&self, q: Query<'_>, idents: &[(TypeIdentifier, FieldArity)]) -> crate::Result<Vec<SqlRow>> {
-        let result_set = self.query(q).await?;
+        let result_set = self.query(q).instrument(sql_span("query")).await?;
         let mut sql_rows = Vec::new();
 
         for row in result_set {
@@ -31,36 +78,90 @@ pub trait QueryExt: Queryable + Send + Sync {
         Ok(sql_rows)
     }
 
-    async fn raw_json<'a>(&'a self, q: RawQuery<'a>) -> std::result::Result<Value, crate::error::RawError> {
-        if q.is_select() {
-            let result_set = AssertUnwindSafe(self.query_raw(q.query(), q.parameters()))
-                .catch_unwind()
-                .await??;
+    /// Like `filter`, with the placeholder columns of the computed fields replaced by their SQL expressions.
+    async fn filter_computed(
+        &self,
+        q: Query<'_>,
+        idents: &[(TypeIdentifier, FieldArity)],
+        computed: &[ScalarFieldRef],
+        sql_family: SqlFamily,
+    ) -> crate::Result<Vec<SqlRow>> {
+        self.filter_rewritten(q, idents, computed, sql_family, None).await
+    }
 
-            let columns: Vec<String> = result_set.columns().into_iter().map(ToString::to_string).collect();
-            let mut result = Vec::new();
+    /// Like `filter_computed`, with the rendered SQL passed through `rewrite` before it is executed.
+    async fn filter_rewritten(
+        &self,
+        q: Query<'_>,
+        idents: &[(TypeIdentifier, FieldArity)],
+        computed: &[ScalarFieldRef],
+        sql_family: SqlFamily,
+        rewrite: Option<fn(String) -> crate::Result<String>>,
+    ) -> crate::Result<Vec<SqlRow>> {
+        if computed.is_empty() && rewrite.is_none() {
+            return self.filter(q, idents).await;
+        }
 
-            for row in result_set.into_iter() {
-                let mut object = Map::new();
+        let (sql, params) = render_query(q, computed, sql_family, rewrite)?;
+        let result_set = self.query_raw(&sql, &params).instrument(sql_span("query_raw")).await?;
+        let mut sql_rows = Vec::new();
 
-                for (idx, p_value) in row.into_iter().enumerate() {
-                    let column_name: String = columns[idx].clone();
-                    object.insert(column_name, Value::from(p_value));
-                }
+        for row in result_set {
+            sql_rows.push(row.to_sql_row(idents)?);
+        }
 
-                result.push(Value::Object(object));
-            }
+        Ok(sql_rows)
+    }
 
-            Ok(Value::Array(result))
+    async fn raw_json<'a>(&'a self, q: RawQuery<'a>) -> std::result::Result<Value, crate::error::RawError> {
+        if q.is_select() {
+            self.raw_rows_json(q).await
         } else {
-            let changes = AssertUnwindSafe(self.execute_raw(q.query(), q.parameters()))
-                .catch_unwind()
-                .await??;
+            let changes = AssertUnwindSafe(
+                self.execute_raw(q.query(), q.parameters())
+                    .instrument(sql_span("execute_raw")),
+            )
+            .catch_unwind()
+            .await??;
 
             Ok(Value::Number(Number::from(changes)))
         }
     }
 
+    /// Executes the query and returns its rows, regardless of the kind of statement. Panics of the driver on values
+    /// it can't read are turned into errors.
+    async fn raw_rows<'a>(&'a self, q: RawQuery<'a>) -> std::result::Result<ResultSet, crate::error::RawError> {
+        let result_set = AssertUnwindSafe(
+            self.query_raw(q.query(), q.parameters())
+                .instrument(sql_span("query_raw")),
+        )
+        .catch_unwind()
+        .await??;
+
+        Ok(result_set)
+    }
+
+    /// Executes the query and returns its rows as JSON objects, regardless of the kind of statement.
+    async fn raw_rows_json<'a>(&'a self, q: RawQuery<'a>) -> std::result::Result<Value, crate::error::RawError> {
+        let result_set = self.raw_rows(q).await?;
+
+        let columns: Vec<String> = result_set.columns().into_iter().map(ToString::to_string).collect();
+        let mut result = Vec::new();
+
+        for row in result_set.into_iter() {
+            let mut object = Map::new();
+
+            for (idx, p_value) in row.into_iter().enumerate() {
+                let column_name: String = columns[idx].clone();
+                object.insert(column_name, Value::from(p_value));
+            }
+
+            result.push(Value::Object(object));
+        }
+
+        Ok(Value::Array(result))
+    }
+
     /// Select one row from the database.
     async fn find(&self, q: Select<'_>, idents: &[(TypeIdentifier, FieldArity)]) -> crate::Result<SqlRow> {
         self.filter(q.limit(1).into(), idents)
@@ -122,3 +223,92 @@ pub trait QueryExt: Queryable + Send + Sync {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::{
+        ManyRelatedRecordsBaseQuery, ManyRelatedRecordsQueryBuilder, ManyRelatedRecordsWithLateralJoin,
+        ManyRelatedRecordsWithRowNumber, ManyRelatedRecordsWithUnionAll,
+    };
+    use connector_interface::QueryArguments;
+
+    const DATAMODEL: &str = r#"
+        model User {
+            id    String @id
+            posts Post[]
+        }
+
+        model Post {
+            id       String @id
+            price    Int
+            quantity Int
+            total    Int    @computed("price * quantity")
+            author   User
+        }
+    "#;
+
+    fn internal_data_model() -> InternalDataModelRef {
+        let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+        DatamodelConverter::convert(&datamodel).build("db".to_owned())
+    }
+
+    /// Renders the read of the first two posts of a user with the builder of the related records.
+    fn paginated_posts<T: ManyRelatedRecordsQueryBuilder>(sql_family: SqlFamily) -> crate::Result<String> {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let from_field = user.fields().find_from_relation_fields("posts").unwrap();
+
+        let user_id = user.primary_identifier().data_source_fields().next().unwrap();
+        let from_record_ids = vec![RecordIdentifier::from(vec![(
+            user_id,
+            PrismaValue::String("1".to_owned()),
+        )])];
+
+        let fields = from_field.related_model().fields().scalar();
+        let columns = fields.iter().map(|field| field.as_column()).collect();
+        let computed: Vec<ScalarFieldRef> = fields.into_iter().filter(|field| field.is_computed()).collect();
+
+        let query_arguments = QueryArguments {
+            first: Some(2),
+            ..Default::default()
+        };
+
+        let base = ManyRelatedRecordsBaseQuery::new(&from_field, &from_record_ids, query_arguments, columns);
+
+        render_query(T::with_pagination(base), &computed, sql_family, T::sql_rewrite()).map(|(sql, _)| sql)
+    }
+
+    fn assert_computed(sql: &str) {
+        assert!(sql.contains("(price * quantity)"), "{}", sql);
+        assert!(!sql.contains("prisma_computed__"), "{}", sql);
+    }
+
+    #[test]
+    fn computed_fields_of_related_records_numbered_by_row() {
+        assert_computed(&paginated_posts::<ManyRelatedRecordsWithRowNumber>(SqlFamily::Postgres).unwrap());
+        assert_computed(&paginated_posts::<ManyRelatedRecordsWithRowNumber>(SqlFamily::Sqlite).unwrap());
+    }
+
+    #[test]
+    fn computed_fields_of_related_records_in_unions() {
+        assert_computed(&paginated_posts::<ManyRelatedRecordsWithUnionAll>(SqlFamily::Mysql).unwrap());
+    }
+
+    #[test]
+    fn computed_fields_of_related_records_joined_laterally() {
+        assert_computed(&paginated_posts::<ManyRelatedRecordsWithLateralJoin>(SqlFamily::Postgres).unwrap());
+    }
+
+    #[test]
+    fn computed_fields_missing_in_the_query_are_rejected() {
+        let internal_data_model = internal_data_model();
+        let post = internal_data_model.find_model("Post").unwrap();
+        let total = post.fields().find_from_scalar("total").unwrap();
+
+        let query = Select::from_table("Post").column("id");
+        let result = render_query(query.into(), &[total], SqlFamily::Postgres, None);
+
+        assert!(result.is_err());
+    }
+}
@@ -161,6 +161,22 @@ pub fn row_value_to_prisma_value(
                 return Err(SqlError::ConversionError(error.into()));
             }
         },
+        TypeIdentifier::Decimal => match p_value {
+            ParameterizedValue::Null => PrismaValue::Null,
+            ParameterizedValue::Real(d) => PrismaValue::Decimal(d),
+            ParameterizedValue::Integer(i) => PrismaValue::Decimal(Decimal::from(i)),
+            ParameterizedValue::Text(s) => PrismaValue::Decimal(s.parse().map_err(|_| {
+                let error = io::Error::new(io::ErrorKind::InvalidData, format!("Invalid decimal value '{}'", s));
+                SqlError::ConversionError(error.into())
+            })?),
+            _ => {
+                let error = io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Decimal value not stored as decimal, int or text",
+                );
+                return Err(SqlError::ConversionError(error.into()));
+            }
+        },
         _ => PrismaValue::from(p_value),
     })
 }
@@ -0,0 +1,260 @@
+//! Conversion of the DateTime values of queries and results by the time zone policy of a connector.
+use connector_interface::{
+    filter::{Filter, ScalarCondition, ScalarListCondition},
+    QueryArguments, WriteArgs,
+};
+use prisma_models::{ManyRecords, PrismaValue, Record, RecordIdentifier, SingleRecord, TimeZonePolicy};
+
+pub(crate) trait TimeZoneExt {
+    /// Converts the values bound to a query to the time zone of the database.
+    fn bind<T: MapValues>(self, t: T) -> T;
+
+    /// Converts the values read from the database to instants.
+    fn read<T: MapValues>(self, t: T) -> T;
+}
+
+impl TimeZoneExt for TimeZonePolicy {
+    fn bind<T: MapValues>(self, t: T) -> T {
+        match self {
+            TimeZonePolicy::Utc => t,
+            _ => t.map_values(&|value| self.to_database(value)),
+        }
+    }
+
+    fn read<T: MapValues>(self, t: T) -> T {
+        match self {
+            TimeZonePolicy::Utc => t,
+            _ => t.map_values(&|value| self.from_database(value)),
+        }
+    }
+}
+
+/// Types holding values of records.
+pub(crate) trait MapValues: Sized {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self;
+}
+
+impl MapValues for PrismaValue {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        f(self)
+    }
+}
+
+impl<T: MapValues> MapValues for Vec<T> {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        self.into_iter().map(|t| t.map_values(f)).collect()
+    }
+}
+
+impl<T: MapValues> MapValues for Option<T> {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        self.map(|t| t.map_values(f))
+    }
+}
+
+impl<K> MapValues for (K, PrismaValue) {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        (self.0, f(self.1))
+    }
+}
+
+impl MapValues for Filter {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        match self {
+            Filter::And(filters) => Filter::And(filters.map_values(f)),
+            Filter::Or(filters) => Filter::Or(filters.map_values(f)),
+            Filter::Not(filters) => Filter::Not(filters.map_values(f)),
+            Filter::Scalar(mut sf) => {
+                sf.condition = sf.condition.map_values(f);
+                Filter::Scalar(sf)
+            }
+            Filter::ScalarList(mut slf) => {
+                slf.condition = slf.condition.map_values(f);
+                Filter::ScalarList(slf)
+            }
+            Filter::Relation(mut rf) => {
+                rf.nested_filter = Box::new(rf.nested_filter.map_values(f));
+                Filter::Relation(rf)
+            }
+            filter => filter,
+        }
+    }
+}
+
+impl MapValues for ScalarCondition {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        match self {
+            ScalarCondition::Equals(v) => ScalarCondition::Equals(f(v)),
+            ScalarCondition::NotEquals(v) => ScalarCondition::NotEquals(f(v)),
+            ScalarCondition::Contains(v) => ScalarCondition::Contains(f(v)),
+            ScalarCondition::NotContains(v) => ScalarCondition::NotContains(f(v)),
+            ScalarCondition::StartsWith(v) => ScalarCondition::StartsWith(f(v)),
+            ScalarCondition::NotStartsWith(v) => ScalarCondition::NotStartsWith(f(v)),
+            ScalarCondition::EndsWith(v) => ScalarCondition::EndsWith(f(v)),
+            ScalarCondition::NotEndsWith(v) => ScalarCondition::NotEndsWith(f(v)),
+            ScalarCondition::LessThan(v) => ScalarCondition::LessThan(f(v)),
+            ScalarCondition::LessThanOrEquals(v) => ScalarCondition::LessThanOrEquals(f(v)),
+            ScalarCondition::GreaterThan(v) => ScalarCondition::GreaterThan(f(v)),
+            ScalarCondition::GreaterThanOrEquals(v) => ScalarCondition::GreaterThanOrEquals(f(v)),
+            ScalarCondition::In(values) => ScalarCondition::In(values.map_values(f)),
+            ScalarCondition::NotIn(values) => ScalarCondition::NotIn(values.map_values(f)),
+        }
+    }
+}
+
+impl MapValues for ScalarListCondition {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        match self {
+            ScalarListCondition::Contains(v) => ScalarListCondition::Contains(f(v)),
+            ScalarListCondition::ContainsEvery(values) => ScalarListCondition::ContainsEvery(values.map_values(f)),
+            ScalarListCondition::ContainsSome(values) => ScalarListCondition::ContainsSome(values.map_values(f)),
+            ScalarListCondition::ContainsNone => ScalarListCondition::ContainsNone,
+        }
+    }
+}
+
+impl MapValues for QueryArguments {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        QueryArguments {
+            after: self.after.map_values(f),
+            before: self.before.map_values(f),
+            filter: self.filter.map_values(f),
+            ..self
+        }
+    }
+}
+
+impl MapValues for WriteArgs {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        WriteArgs {
+            args: self.args.into_iter().map(|(name, value)| (name, f(value))).collect(),
+            ..self
+        }
+    }
+}
+
+impl MapValues for RecordIdentifier {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        self.into_iter().map(|pair| pair.map_values(f)).collect()
+    }
+}
+
+impl MapValues for Record {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        Record {
+            values: self.values.map_values(f),
+            parent_id: self.parent_id.map_values(f),
+        }
+    }
+}
+
+impl MapValues for SingleRecord {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        SingleRecord::new(self.record.map_values(f), self.field_names)
+    }
+}
+
+impl MapValues for ManyRecords {
+    fn map_values<F: Fn(PrismaValue) -> PrismaValue>(self, f: &F) -> Self {
+        ManyRecords {
+            records: self.records.map_values(f),
+            field_names: self.field_names,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use connector_interface::ScalarCompare;
+    use prisma_models::{DataSourceFieldRef, DatamodelConverter};
+
+    const DATAMODEL: &str = r#"
+        model Event {
+            id String   @id
+            at DateTime
+        }
+    "#;
+
+    fn at_field() -> DataSourceFieldRef {
+        let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+        let internal_data_model = DatamodelConverter::convert(&datamodel).build("db".to_owned());
+        let event = internal_data_model.find_model("Event").unwrap();
+        let at = event.fields().find_from_scalar("at").unwrap();
+
+        at.data_source_field().clone()
+    }
+
+    fn at(hour: u32) -> PrismaValue {
+        let dt: DateTime<Utc> = DateTime::from_utc(NaiveDate::from_ymd(2020, 6, 1).and_hms(hour, 0, 0), Utc);
+
+        PrismaValue::DateTime(dt)
+    }
+
+    fn plus_two() -> TimeZonePolicy {
+        "+02:00".parse().unwrap()
+    }
+
+    #[test]
+    fn values_of_nested_filters_are_bound_in_the_time_zone() {
+        let field = at_field();
+        let filter = Filter::And(vec![
+            field.greater_than(at(8)),
+            Filter::Or(vec![Filter::Not(vec![field.is_in(vec![at(9), at(10)])])]),
+        ]);
+
+        let expected = Filter::And(vec![
+            field.greater_than(at(10)),
+            Filter::Or(vec![Filter::Not(vec![field.is_in(vec![at(11), at(12)])])]),
+        ]);
+
+        assert_eq!(plus_two().bind(filter), expected);
+    }
+
+    #[test]
+    fn write_arguments_are_bound_in_the_time_zone() {
+        let mut args = WriteArgs::new();
+        args.insert("at", at(8));
+        args.insert("id", PrismaValue::String("1".to_owned()));
+
+        let bound = plus_two().bind(args);
+
+        assert_eq!(bound.get_field_value("at"), Some(&at(10)));
+        assert_eq!(bound.get_field_value("id"), Some(&PrismaValue::String("1".to_owned())));
+    }
+
+    #[test]
+    fn read_records_are_converted_to_instants() {
+        let field = at_field();
+        let records = ManyRecords {
+            records: vec![Record {
+                values: vec![PrismaValue::String("1".to_owned()), at(10)],
+                parent_id: Some(RecordIdentifier::from((field.clone(), at(12)))),
+            }],
+            field_names: vec!["id".to_owned(), "at".to_owned()],
+        };
+
+        let read = plus_two().read(records);
+
+        assert_eq!(read.records[0].values, vec![PrismaValue::String("1".to_owned()), at(8)]);
+        assert_eq!(read.records[0].parent_id, Some(RecordIdentifier::from((field, at(10)))));
+        assert_eq!(read.field_names, vec!["id".to_owned(), "at".to_owned()]);
+    }
+
+    #[test]
+    fn binding_and_reading_round_trip() {
+        let policy: TimeZonePolicy = "-05:30".parse().unwrap();
+        let values = vec![at(0), at(23), PrismaValue::List(vec![at(12)])];
+
+        assert_eq!(policy.read(policy.bind(values.clone())), values);
+    }
+
+    #[test]
+    fn utc_keeps_the_values() {
+        let filter = at_field().equals(at(8));
+
+        assert_eq!(TimeZonePolicy::Utc.bind(filter.clone()), filter);
+        assert_eq!(TimeZonePolicy::Utc.read(vec![at(8)]), vec![at(8)]);
+    }
+}
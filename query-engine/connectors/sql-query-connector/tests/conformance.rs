@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use connector_interface::{Connector, ConnectorCapabilities};
+use connector_test_kit::ConnectorFactory;
+use datamodel::{Datamodel, RelationMode};
+use migration_core::{
+    api::{GenericApi, MigrationApi},
+    ApplyMigrationInput, InferMigrationStepsInput,
+};
+use sql_migration_connector::SqlMigrationConnector;
+use sql_query_connector::{FromSource, Sqlite};
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The file stem of the databases, which is the name SQLite attaches them as.
+const DB_NAME: &str = "conformance";
+
+static DATABASES: AtomicUsize = AtomicUsize::new(0);
+
+struct SqliteConnectorFactory;
+
+impl SqliteConnectorFactory {
+    /// A database file of its own in a new directory, tests run in parallel.
+    fn database_file() -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "sql-query-connector-conformance-{}-{}",
+            std::process::id(),
+            DATABASES.fetch_add(1, Ordering::SeqCst)
+        ));
+
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+
+        directory.join(format!("{}.db", DB_NAME))
+    }
+}
+
+#[async_trait]
+impl ConnectorFactory for SqliteConnectorFactory {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn db_name(&self) -> &'static str {
+        DB_NAME
+    }
+
+    fn capabilities(&self) -> ConnectorCapabilities {
+        ConnectorCapabilities::ENUMS | ConnectorCapabilities::SCALAR_LISTS
+    }
+
+    /// Creates the tables of the data model with the migration engine, as they are created for an application.
+    async fn connector(&self, datamodel: &Datamodel, relation_mode: RelationMode) -> Box<dyn Connector + Send + Sync> {
+        let url = format!("file:{}", Self::database_file().display());
+
+        let migration_connector = SqlMigrationConnector::new(&url, "sqlite")
+            .await
+            .unwrap()
+            .with_relation_mode(relation_mode);
+        let migration_api = MigrationApi::new(migration_connector).await.unwrap();
+
+        let input = InferMigrationStepsInput {
+            migration_id: "conformance".to_owned(),
+            datamodel: datamodel::render_datamodel_to_string(datamodel).unwrap(),
+            assume_to_be_applied: Some(Vec::new()),
+            assume_applied_migrations: None,
+        };
+
+        let steps = migration_api
+            .infer_migration_steps(&input)
+            .await
+            .unwrap()
+            .datamodel_steps;
+
+        let input = ApplyMigrationInput {
+            migration_id: "conformance".to_owned(),
+            steps,
+            force: None,
+        };
+
+        migration_api.apply_migration(&input).await.unwrap();
+
+        let config = datamodel::parse_configuration(&format!(
+            "datasource db {{\n  provider = \"sqlite\"\n  url = \"{}\"\n}}",
+            url
+        ))
+        .unwrap();
+
+        Box::new(Sqlite::from_source(config.datasources[0].as_ref()).await.unwrap())
+    }
+}
+
+connector_test_kit::connector_test_suite!(SqliteConnectorFactory);
@@ -0,0 +1,342 @@
+//! Restricts the operations clients can run on models and the fields they can read and write, for engines that
+//! should only expose part of the database. The query schema builder leaves out everything the policy forbids,
+//! the executor checks the query graphs of all operations against the policy again before they are run.
+//!
+//! Policies are read from JSON, models and fields without rules allow everything:
+//!
+//! ```json
+//! {
+//!   "models": {
+//!     "AuditLog": { "operations": ["read", "create"] },
+//!     "User": { "fields": { "password": { "read": false }, "role": { "write": false } } }
+//!   }
+//! }
+//! ```
+//!
+//! The fields of the primary identifier of a model can always be read, they address the records of all other
+//! operations. A model without the `read` operation only exposes these fields, e.g. in the results of creates.
+//! Filtering, ordering and paginating by a field reveals its values just as reading it does, so it needs the
+//! same access. Raw queries and routines bypass the data model and are only allowed without any rules.
+use crate::{
+    query_ast::{Query, ReadQuery, WriteQuery},
+    query_graph::{Node, QueryGraph},
+    CoreError, CoreResult,
+};
+use connector::{filter::Filter, QueryArguments, WriteArgs};
+use prisma_models::ModelRef;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt};
+
+lazy_static! {
+    /// The policy of engines without a policy file.
+    pub static ref ALLOW_ALL: AccessPolicy = AccessPolicy::default();
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccessPolicy {
+    /// Rules by model name.
+    #[serde(default)]
+    pub models: HashMap<String, ModelAccess>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelAccess {
+    /// The operations clients can run on the model, all of them if not given.
+    #[serde(default = "ModelOperation::all")]
+    pub operations: Vec<ModelOperation>,
+
+    /// Rules by field name.
+    #[serde(default)]
+    pub fields: HashMap<String, FieldAccess>,
+}
+
+/// Operations on the records of a model. Upserts need `create` and `update`, connecting and disconnecting related
+/// records updates the model of the relation field.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModelOperation {
+    Read,
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldAccess {
+    #[serde(default = "allowed")]
+    pub read: bool,
+
+    #[serde(default = "allowed")]
+    pub write: bool,
+}
+
+fn allowed() -> bool {
+    true
+}
+
+impl ModelOperation {
+    fn all() -> Vec<ModelOperation> {
+        vec![
+            ModelOperation::Read,
+            ModelOperation::Create,
+            ModelOperation::Update,
+            ModelOperation::Delete,
+        ]
+    }
+}
+
+impl fmt::Display for ModelOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ModelOperation::Read => "read",
+            ModelOperation::Create => "create",
+            ModelOperation::Update => "update",
+            ModelOperation::Delete => "delete",
+        };
+
+        s.fmt(f)
+    }
+}
+
+impl AccessPolicy {
+    pub fn allows(&self, model: &str, operation: ModelOperation) -> bool {
+        self.models
+            .get(model)
+            .map(|access| access.operations.contains(&operation))
+            .unwrap_or(true)
+    }
+
+    pub fn can_read(&self, model: &ModelRef, field: &str) -> bool {
+        (self.allows(&model.name, ModelOperation::Read) && self.field_access(&model.name, field).read)
+            || model.primary_identifier().names().any(|name| name == field)
+    }
+
+    /// Raw queries and routines can read and write anything, policies with rules don't allow them.
+    pub fn allows_raw_queries(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    pub fn can_write(&self, model: &str, field: &str) -> bool {
+        self.field_access(model, field).write
+    }
+
+    fn field_access(&self, model: &str, field: &str) -> FieldAccess {
+        self.models
+            .get(model)
+            .and_then(|access| access.fields.get(field))
+            .cloned()
+            .unwrap_or(FieldAccess {
+                read: true,
+                write: true,
+            })
+    }
+
+    /// Checks all reads and writes of the query graph.
+    pub fn check_graph(&self, graph: &QueryGraph) -> CoreResult<()> {
+        for node in graph.nodes() {
+            match graph.node_content(&node) {
+                Some(Node::Query(Query::Read(query))) => self.check_read(query)?,
+                Some(Node::Query(Query::Write(query))) => self.check_write(query)?,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_read(&self, query: &ReadQuery) -> CoreResult<()> {
+        let (model, selected_fields, nested) = match query {
+            ReadQuery::RecordQuery(q) => {
+                self.check_opt_filter(q.filter.as_ref())?;
+                (q.model.clone(), &q.selected_fields, &q.nested)
+            }
+            ReadQuery::ManyRecordsQuery(q) => {
+                self.check_query_args(&q.args)?;
+                (q.model.clone(), &q.selected_fields, &q.nested)
+            }
+            ReadQuery::RelatedRecordsQuery(q) => {
+                let parent_model = q.parent_field.model();
+
+                if !self.can_read(&parent_model, &q.parent_field.name) {
+                    return Err(denied_field("read", &parent_model.name, &q.parent_field.name));
+                }
+
+                self.check_query_args(&q.args)?;
+                (q.parent_field.related_model(), &q.selected_fields, &q.nested)
+            }
+            ReadQuery::AggregateRecordsQuery(q) => {
+                return self.check_operation(&q.model.name, ModelOperation::Read);
+            }
+        };
+
+        for selected in selected_fields.scalar.iter() {
+            self.check_read_field(&model, &selected.field.name)?;
+        }
+
+        for query in nested {
+            self.check_read(query)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_write(&self, query: &WriteQuery) -> CoreResult<()> {
+        match query {
+            WriteQuery::CreateRecord(q) => {
+                self.check_operation(&q.model.name, ModelOperation::Create)?;
+                self.check_args(&q.model, &q.args)
+            }
+            WriteQuery::UpdateRecord(q) => {
+                self.check_operation(&q.model.name, ModelOperation::Update)?;
+                self.check_filter(&q.where_)?;
+                self.check_args(&q.model, &q.args)
+            }
+            WriteQuery::UpdateManyRecords(q) => {
+                self.check_operation(&q.model.name, ModelOperation::Update)?;
+                self.check_filter(&q.filter)?;
+                self.check_args(&q.model, &q.args)
+            }
+            WriteQuery::DeleteRecord(q) => {
+                self.check_operation(&q.model.name, ModelOperation::Delete)?;
+                self.check_opt_filter(q.where_.as_ref())
+            }
+            WriteQuery::DeleteManyRecords(q) => {
+                self.check_operation(&q.model.name, ModelOperation::Delete)?;
+                self.check_filter(&q.filter)
+            }
+            WriteQuery::ConnectRecords(q) => {
+                self.check_relation_write(&q.relation_field.model(), &q.relation_field.name)
+            }
+            WriteQuery::DisconnectRecords(q) => {
+                self.check_relation_write(&q.relation_field.model(), &q.relation_field.name)
+            }
+            // Relation table rows of deleted parents aren't a model operation.
+            WriteQuery::DisconnectAllRecords(_) => Ok(()),
+            WriteQuery::CallRoutine(_) | WriteQuery::Raw { .. } => self.check_raw_query(),
+        }
+    }
+
+    /// Cursors, ordering and filters of reads.
+    fn check_query_args(&self, args: &QueryArguments) -> CoreResult<()> {
+        for (field, _) in args.after.iter().chain(args.before.iter()).flatten() {
+            self.check_read_field(&field.model(), &field.name)?;
+        }
+
+        if let Some(ref order_by) = args.order_by {
+            self.check_read_field(&order_by.field.model(), &order_by.field.name)?;
+        }
+
+        self.check_opt_filter(args.filter.as_ref())
+    }
+
+    fn check_opt_filter(&self, filter: Option<&Filter>) -> CoreResult<()> {
+        filter.map(|filter| self.check_filter(filter)).unwrap_or(Ok(()))
+    }
+
+    fn check_filter(&self, filter: &Filter) -> CoreResult<()> {
+        match filter {
+            Filter::And(filters) | Filter::Or(filters) | Filter::Not(filters) => {
+                filters.iter().map(|filter| self.check_filter(filter)).collect()
+            }
+            Filter::Scalar(sf) => {
+                let field = sf.field.model_field();
+                self.check_read_field(&field.model(), field.name())
+            }
+            Filter::ScalarList(lf) => self.check_read_field(&lf.field.model(), &lf.field.name),
+            Filter::OneRelationIsNull(rf) => self.check_read_field(&rf.field.model(), &rf.field.name),
+            Filter::Relation(rf) => {
+                self.check_read_field(&rf.field.model(), &rf.field.name)?;
+                self.check_filter(&rf.nested_filter)
+            }
+            Filter::NodeSubscription | Filter::BoolFilter(_) | Filter::Empty => Ok(()),
+        }
+    }
+
+    fn check_read_field(&self, model: &ModelRef, field: &str) -> CoreResult<()> {
+        if self.can_read(model, field) {
+            Ok(())
+        } else {
+            Err(denied_field("read", &model.name, field))
+        }
+    }
+
+    /// Raw queries are built as queries of their own, without a query graph.
+    pub fn check_raw_query(&self) -> CoreResult<()> {
+        if self.allows_raw_queries() {
+            Ok(())
+        } else {
+            Err(CoreError::AccessDenied(
+                "raw queries and routines with an access policy".to_owned(),
+            ))
+        }
+    }
+
+    fn check_operation(&self, model: &str, operation: ModelOperation) -> CoreResult<()> {
+        if self.allows(model, operation) {
+            Ok(())
+        } else {
+            Err(CoreError::AccessDenied(format!(
+                "{} records of the model {}",
+                operation, model
+            )))
+        }
+    }
+
+    fn check_relation_write(&self, model: &ModelRef, field: &str) -> CoreResult<()> {
+        self.check_operation(&model.name, ModelOperation::Update)?;
+
+        if self.can_write(&model.name, field) {
+            Ok(())
+        } else {
+            Err(denied_field("write", &model.name, field))
+        }
+    }
+
+    /// The arguments are keyed by column. Columns of inlined relations are written along with related records.
+    fn check_args(&self, model: &ModelRef, args: &WriteArgs) -> CoreResult<()> {
+        for field in model.fields().scalar() {
+            let written = args.args.contains_key(field.db_name()) || args.json_patches.contains_key(field.db_name());
+
+            if written && !self.can_write(&model.name, &field.name) {
+                return Err(denied_field("write", &model.name, &field.name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn denied_field(access: &str, model: &str, field: &str) -> CoreError {
+    CoreError::AccessDenied(format!("{} the field {}.{}", access, model, field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn models_and_fields_without_rules_allow_everything() {
+        let policy: AccessPolicy = serde_json::from_str(
+            r#"{ "models": { "User": { "operations": ["read"], "fields": { "password": { "read": false } } } } }"#,
+        )
+        .unwrap();
+
+        assert!(policy.allows("User", ModelOperation::Read));
+        assert!(!policy.allows("User", ModelOperation::Delete));
+        assert!(policy.allows("Post", ModelOperation::Delete));
+
+        assert!(!policy.field_access("User", "password").read);
+        assert!(policy.field_access("User", "password").write);
+        assert!(policy.field_access("User", "email").read);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let policy = serde_json::from_str::<AccessPolicy>(r#"{ "models": { "User": { "operation": ["read"] } } }"#);
+
+        assert!(policy.is_err());
+    }
+}
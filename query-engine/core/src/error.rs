@@ -32,6 +32,28 @@ pub enum CoreError {
 
     #[fail(display = "{}", _0)]
     InterpreterError(InterpreterError),
+
+    #[fail(
+        display = "Query exceeds the {} limit: {} is above the maximum of {}",
+        limit, value, maximum
+    )]
+    LimitExceeded {
+        limit: &'static str,
+        value: usize,
+        maximum: usize,
+    },
+
+    #[fail(display = "Idempotency key '{}' was already used for a different request.", _0)]
+    IdempotencyKeyReused(String),
+
+    #[fail(display = "The access policy doesn't allow to {}.", _0)]
+    AccessDenied(String),
+
+    #[fail(
+        display = "Reading all records of the model {} at once is not allowed, the query needs a `take` argument.",
+        _0
+    )]
+    PaginationRequired(String),
 }
 
 impl From<QueryGraphBuilderError> for CoreError {
@@ -90,6 +112,25 @@ impl From<CoreError> for user_facing_errors::Error {
                 .unwrap()
                 .into()
             }
+            CoreError::LimitExceeded { limit, value, maximum } => {
+                user_facing_errors::KnownError::new(user_facing_errors::query_engine::QueryLimitExceeded {
+                    limit: limit.to_owned(),
+                    value,
+                    maximum,
+                })
+                .unwrap()
+                .into()
+            }
+            CoreError::AccessDenied(operation) => {
+                user_facing_errors::KnownError::new(user_facing_errors::query_engine::AccessDenied { operation })
+                    .unwrap()
+                    .into()
+            }
+            CoreError::PaginationRequired(model_name) => {
+                user_facing_errors::KnownError::new(user_facing_errors::query_engine::PaginationRequired { model_name })
+                    .unwrap()
+                    .into()
+            }
             _ => user_facing_errors::Error::from_dyn_error(&err.compat()),
         }
     }
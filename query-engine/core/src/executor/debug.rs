@@ -0,0 +1,54 @@
+use crate::{query_graph, QueryGraph};
+use std::str::FromStr;
+
+/// Environment variable enabling the debug output of query graphs, `dot` or `json`.
+pub const DEBUG_QUERY_GRAPH_VAR: &str = "PRISMA_DEBUG_QUERY_GRAPH";
+
+/// Log target of the debug output, to filter it with `RUST_LOG`.
+pub const DEBUG_QUERY_GRAPH_TARGET: &str = "prisma::query_graph";
+
+/// Format of the query graphs logged for debugging.
+///
+/// With debugging enabled, the finalized graph of every operation is logged before it is executed, followed by the
+/// nodes the interpreter executed in order. The SQL a node runs is logged by the connector within the
+/// `prisma:query_node` span of the node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    /// The DOT language of Graphviz.
+    Dot,
+    Json,
+}
+
+impl GraphFormat {
+    /// The format configured by `PRISMA_DEBUG_QUERY_GRAPH`, if any.
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var(DEBUG_QUERY_GRAPH_VAR).ok()?;
+
+        match value.parse() {
+            Ok(format) => Some(format),
+            Err(err) => {
+                warn!("Ignoring {}: {}", DEBUG_QUERY_GRAPH_VAR, err);
+                None
+            }
+        }
+    }
+
+    pub fn render(self, graph: &QueryGraph) -> String {
+        match self {
+            GraphFormat::Dot => query_graph::to_dot(graph),
+            GraphFormat::Json => query_graph::to_json(graph).to_string(),
+        }
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(GraphFormat::Dot),
+            "json" => Ok(GraphFormat::Json),
+            _ => Err(format!("Unknown query graph format '{}', expected `dot` or `json`.", s)),
+        }
+    }
+}
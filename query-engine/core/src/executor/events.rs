@@ -0,0 +1,190 @@
+use crossbeam_queue::SegQueue;
+use futures::channel::mpsc;
+use prisma_models::{ModelRef, PrismaValue, RecordIdentifier};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+/// Number of events a subscriber may fall behind before it is disconnected.
+const SUBSCRIBER_BUFFER: usize = 1024;
+
+pub type RecordEventReceiver = mpsc::Receiver<RecordEvent>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordEventKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A change to records of a single model, published after the surrounding query has been committed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordEvent {
+    pub model: String,
+    pub kind: RecordEventKind,
+
+    /// Identifiers of the affected records, keyed by field name.
+    /// Deletes are filter-based and only report the number of affected records.
+    pub ids: Vec<BTreeMap<String, PrismaValue>>,
+    pub count: usize,
+}
+
+impl RecordEvent {
+    pub fn new(model: &ModelRef, kind: RecordEventKind, ids: &[RecordIdentifier]) -> Self {
+        let ids: Vec<_> = ids
+            .iter()
+            .map(|id| {
+                id.pairs()
+                    .iter()
+                    .map(|(field, value)| (field.name.clone(), value.clone()))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            model: model.name.clone(),
+            kind,
+            count: ids.len(),
+            ids,
+        }
+    }
+
+    pub fn deleted(model: &ModelRef, count: usize) -> Self {
+        Self {
+            model: model.name.clone(),
+            kind: RecordEventKind::Deleted,
+            ids: vec![],
+            count,
+        }
+    }
+}
+
+/// Events recorded by an interpreter while executing a single query graph.
+/// Kept separate from the event bus, as events may only be published once the query committed.
+#[derive(Debug, Clone, Default)]
+pub struct RecordedEvents {
+    inner: Arc<SegQueue<RecordEvent>>,
+}
+
+impl RecordedEvents {
+    pub fn push(&self, event: RecordEvent) {
+        if event.count > 0 {
+            self.inner.push(event);
+        }
+    }
+
+    pub fn drain(&self) -> Vec<RecordEvent> {
+        let mut events = Vec::with_capacity(self.inner.len());
+
+        while let Ok(event) = self.inner.pop() {
+            events.push(event);
+        }
+
+        events
+    }
+}
+
+/// Post-commit event bus distributing record events to all current subscribers.
+/// Subscribers not keeping up with the events are disconnected, their receiver ends after the buffered events.
+#[derive(Debug, Default)]
+pub struct EventBus {
+    subscribers: Mutex<Vec<mpsc::Sender<RecordEvent>>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&self) -> RecordEventReceiver {
+        let (sender, receiver) = mpsc::channel(SUBSCRIBER_BUFFER);
+        self.subscribers.lock().unwrap().push(sender);
+
+        receiver
+    }
+
+    /// Publishes the given events. Subscribers that went away or have a full buffer are dropped.
+    pub fn publish(&self, events: Vec<RecordEvent>) {
+        if events.is_empty() {
+            return;
+        }
+
+        let mut subscribers = self.subscribers.lock().unwrap();
+
+        *subscribers = std::mem::take(&mut *subscribers)
+            .into_iter()
+            .filter_map(|mut subscriber| {
+                if events.iter().all(|event| subscriber.try_send(event.clone()).is_ok()) {
+                    Some(subscriber)
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created(count: usize) -> RecordEvent {
+        RecordEvent {
+            model: "User".to_owned(),
+            kind: RecordEventKind::Created,
+            ids: vec![],
+            count,
+        }
+    }
+
+    fn received(receiver: &mut RecordEventReceiver) -> (usize, bool) {
+        let mut count = 0;
+
+        loop {
+            match receiver.try_next() {
+                Ok(Some(_)) => count += 1,
+                Ok(None) => return (count, true),
+                Err(_) => return (count, false),
+            }
+        }
+    }
+
+    #[test]
+    fn subscribers_receive_the_published_events() {
+        let bus = EventBus::default();
+        let mut receiver = bus.subscribe();
+
+        bus.publish(vec![created(1), created(2)]);
+
+        assert_eq!(received(&mut receiver), (2, false));
+        assert_eq!(bus.subscribers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn lagging_subscribers_are_disconnected() {
+        let bus = EventBus::default();
+        let mut lagging = bus.subscribe();
+        let mut reading = bus.subscribe();
+
+        for count in 1..=SUBSCRIBER_BUFFER * 2 {
+            bus.publish(vec![created(count)]);
+            assert_eq!(received(&mut reading), (1, false));
+        }
+
+        let (count, closed) = received(&mut lagging);
+
+        assert_eq!(count, SUBSCRIBER_BUFFER + 1);
+        assert!(closed);
+        assert_eq!(bus.subscribers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn subscribers_that_went_away_are_dropped() {
+        let bus = EventBus::default();
+        drop(bus.subscribe());
+
+        bus.publish(vec![created(1)]);
+
+        assert!(bus.subscribers.lock().unwrap().is_empty());
+    }
+}
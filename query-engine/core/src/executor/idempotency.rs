@@ -0,0 +1,81 @@
+use crate::{CoreError, CoreResult, Item, Responses};
+use connector::IdempotencyRecord;
+
+/// Key of a mutation request given by the client, so that retries of the request are executed only once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyKey {
+    pub key: String,
+    /// Hash of the request, a key reused for a different request is rejected instead of returning a stored response.
+    pub request_hash: String,
+}
+
+impl IdempotencyKey {
+    pub fn new(key: String, request_hash: String) -> Self {
+        Self { key, request_hash }
+    }
+
+    /// The record storing the data of the responses for the key.
+    pub(super) fn record(&self, responses: &Responses) -> CoreResult<IdempotencyRecord> {
+        let mut response =
+            serde_json::to_value(responses).map_err(|err| CoreError::SerializationError(err.to_string()))?;
+
+        Ok(IdempotencyRecord {
+            key: self.key.clone(),
+            request_hash: self.request_hash.clone(),
+            response: response["data"].take(),
+        })
+    }
+
+    /// The responses stored for the key.
+    pub(super) fn replay(&self, record: IdempotencyRecord) -> CoreResult<Responses> {
+        if record.request_hash != self.request_hash {
+            return Err(CoreError::IdempotencyKeyReused(self.key.clone()));
+        }
+
+        let mut responses = Responses::default();
+
+        if let serde_json::Value::Object(data) = record.response {
+            for (key, value) in data {
+                responses.insert_data(key, Item::Json(value));
+            }
+        }
+
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn responses() -> Responses {
+        let mut responses = Responses::default();
+        responses.insert_data("createOneUser".to_owned(), Item::Json(serde_json::json!({ "id": "1" })));
+        responses
+    }
+
+    #[test]
+    fn the_stored_responses_are_replayed() {
+        let key = IdempotencyKey::new("create-user-1".to_owned(), "hash".to_owned());
+        let record = key.record(&responses()).unwrap();
+
+        assert_eq!(record.response, serde_json::json!({ "createOneUser": { "id": "1" } }));
+
+        let replayed = key.replay(record).unwrap();
+        let replayed = serde_json::to_value(&replayed).unwrap();
+
+        assert_eq!(replayed, serde_json::to_value(&responses()).unwrap());
+    }
+
+    #[test]
+    fn keys_reused_for_other_requests_are_rejected() {
+        let key = IdempotencyKey::new("create-user-1".to_owned(), "hash".to_owned());
+        let record = key.record(&responses()).unwrap();
+        let other_request = IdempotencyKey::new("create-user-1".to_owned(), "other hash".to_owned());
+
+        match other_request.replay(record) {
+            Err(CoreError::IdempotencyKeyReused(key)) => assert_eq!(key, "create-user-1"),
+            other => panic!("Expected the reused key to be rejected, got {:?}", other),
+        }
+    }
+}
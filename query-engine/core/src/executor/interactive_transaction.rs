@@ -0,0 +1,88 @@
+use crate::{response_ir::Responses, CoreResult, QueryDocument};
+use futures::channel::{mpsc, oneshot};
+use std::sync::{Arc, Mutex};
+
+/// Number of requests that may wait for a transaction, which executes them one after another. Further requests are
+/// rejected until the transaction caught up.
+const TRANSACTION_BUFFER: usize = 16;
+
+/// Requests to a transaction that stays open across several requests of a client, see
+/// `QueryExecutor::interactive_transaction`.
+#[derive(Debug)]
+pub enum TransactionRequest {
+    /// Executes the document in the transaction. Failing operations leave the transaction open, the client decides
+    /// whether to commit the preceding writes or to roll them back.
+    Execute(QueryDocument, oneshot::Sender<CoreResult<Responses>>),
+    Commit(oneshot::Sender<CoreResult<()>>),
+    Rollback(oneshot::Sender<CoreResult<()>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransactionSendError {
+    /// The transaction has `TRANSACTION_BUFFER` requests waiting already.
+    Busy,
+    /// The transaction ended.
+    Closed,
+}
+
+/// Sends requests to a transaction. Clones share the sender, as every sender of the channel could queue a request
+/// beyond the buffer.
+#[derive(Debug, Clone)]
+pub struct TransactionSender {
+    inner: Arc<Mutex<mpsc::Sender<TransactionRequest>>>,
+}
+
+impl TransactionSender {
+    pub fn send(&self, request: TransactionRequest) -> Result<(), TransactionSendError> {
+        self.inner.lock().unwrap().try_send(request).map_err(|err| {
+            if err.is_full() {
+                TransactionSendError::Busy
+            } else {
+                TransactionSendError::Closed
+            }
+        })
+    }
+}
+
+pub type TransactionReceiver = mpsc::Receiver<TransactionRequest>;
+
+pub fn transaction_channel() -> (TransactionSender, TransactionReceiver) {
+    let (sender, receiver) = mpsc::channel(TRANSACTION_BUFFER);
+    let sender = TransactionSender {
+        inner: Arc::new(Mutex::new(sender)),
+    };
+
+    (sender, receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit() -> TransactionRequest {
+        TransactionRequest::Commit(oneshot::channel().0)
+    }
+
+    #[test]
+    fn requests_beyond_the_buffer_are_rejected() {
+        let (sender, mut receiver) = transaction_channel();
+        let clone = sender.clone();
+
+        for _ in 0..=TRANSACTION_BUFFER {
+            sender.send(commit()).unwrap();
+        }
+
+        assert_eq!(clone.send(commit()), Err(TransactionSendError::Busy));
+
+        receiver.try_next().unwrap().unwrap();
+        assert_eq!(clone.send(commit()), Ok(()));
+    }
+
+    #[test]
+    fn requests_to_ended_transactions_are_rejected() {
+        let (sender, receiver) = transaction_channel();
+        drop(receiver);
+
+        assert_eq!(sender.send(commit()), Err(TransactionSendError::Closed));
+    }
+}
@@ -1,15 +1,37 @@
-use super::{pipeline::QueryPipeline, QueryExecutor};
+use super::{
+    pipeline::QueryPipeline, EventBus, GraphFormat, IdempotencyKey, Invalidation, Middleware, Next, QueryExecutor,
+    QueryLimits, RecordEvent, RecordEventReceiver, ResultCache, TransactionReceiver, TransactionRequest,
+};
 use crate::{
-    CoreResult, IrSerializer, QueryDocument, QueryType, QueryGraphBuilder, QueryInterpreter, QuerySchemaRef, Response, Responses,
+    AccessPolicy, CoreError, CoreResult, FailurePolicy, IrSerializer, Node, Operation, QueryDocument, QueryType, QueryGraphBuilder, QueryInterpreter, QuerySchemaRef, Response, Responses,
 };
 use async_trait::async_trait;
-use connector::{ConnectionLike, Connector};
+use connector::{Connection, ConnectionLike, Connector, PoolState, Routine, Transaction};
+use futures::{FutureExt, StreamExt};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tracing_futures::Instrument;
 
 /// Central query executor and main entry point into the query core.
 pub struct InterpretingExecutor<C> {
     connector: C,
+    /// Connectors of the datasources other than the first one, by datasource name.
+    datasources: Vec<(String, Box<dyn Connector + Send + Sync>)>,
     primary_connector: &'static str,
     force_transactions: bool,
+    limits: QueryLimits,
+    access_policy: AccessPolicy,
+    event_bus: EventBus,
+    /// Results of reads on models with `@@cache`.
+    result_cache: ResultCache,
+    /// Format of the query graphs logged for debugging, if enabled.
+    debug_graph: Option<GraphFormat>,
+    /// Called for every operation, in registration order.
+    middlewares: Vec<Arc<dyn Middleware>>,
+    failure_policy: FailurePolicy,
 }
 
 // Todo:
@@ -22,53 +44,393 @@ where
         connector: C,
         primary_connector: &'static str,
         force_transactions: bool,
+        limits: QueryLimits,
     ) -> Self
     {
         InterpretingExecutor {
             connector,
+            datasources: Vec::new(),
             primary_connector,
             force_transactions,
+            limits,
+            access_policy: AccessPolicy::default(),
+            event_bus: EventBus::default(),
+            result_cache: ResultCache::default(),
+            debug_graph: GraphFormat::from_env(),
+            middlewares: Vec::new(),
+            failure_policy: FailurePolicy::default(),
         }
     }
-}
 
-#[async_trait]
-impl<C> QueryExecutor for InterpretingExecutor<C>
-where
-    C: Connector + Send + Sync,
-{
-    async fn execute(&self, query_doc: QueryDocument, query_schema: QuerySchemaRef) -> CoreResult<Responses> {
+    /// Logs the query graph of every operation in the given format, overriding `PRISMA_DEBUG_QUERY_GRAPH`.
+    pub fn with_debug_graph(mut self, format: Option<GraphFormat>) -> Self {
+        self.debug_graph = format;
+        self
+    }
+
+    /// Rejects operations the policy doesn't allow before they are executed.
+    pub fn with_access_policy(mut self, policy: AccessPolicy) -> Self {
+        self.access_policy = policy;
+        self
+    }
+
+    /// Wraps the execution of every operation with the middleware, after the middlewares registered before.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Handles failing nested reads according to the policy instead of failing the whole operation.
+    pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.failure_policy = policy;
+        self
+    }
+
+    /// Queries on models of the given datasource are executed with `connector`. Raw queries and routines
+    /// always run on the connector of the first datasource.
+    pub fn with_datasource(mut self, name: String, connector: Box<dyn Connector + Send + Sync>) -> Self {
+        self.datasources.push((name, connector));
+        self
+    }
+
+    /// Connections to the given datasources other than the first one.
+    async fn datasource_connections(
+        &self,
+        datasources: &HashSet<String>,
+    ) -> CoreResult<Vec<(&str, Box<dyn Connection + '_>)>> {
+        let mut conns = Vec::with_capacity(datasources.len());
+
+        for (name, connector) in self.datasources.iter() {
+            if datasources.contains(name) {
+                conns.push((name.as_str(), connector.get_connection().await?));
+            }
+        }
+
+        Ok(conns)
+    }
+
+    /// The names of all datasources other than the first one.
+    fn all_datasources(&self) -> HashSet<String> {
+        self.datasources.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Checks the document against the limits and the query graphs of all of its operations against the limits and
+    /// the access policy before any of them is executed, so that a rejected operation doesn't leave the writes of
+    /// the ones before it behind. Adds the default take to the reads of paginated models.
+    ///
+    /// Returns the datasources other than the first one the operations run on, only these are connected to.
+    fn prepare_document(
+        &self,
+        query_doc: &mut QueryDocument,
+        query_schema: &QuerySchemaRef,
+    ) -> CoreResult<HashSet<String>> {
+        self.limits.check_document(query_doc)?;
+        self.limits.paginate(query_doc, query_schema)?;
+
+        let mut datasources = HashSet::new();
+
+        for operation in query_doc.operations.iter() {
+            let (query, _) = self.build_query(operation.clone(), Arc::clone(query_schema))?;
+            datasources.extend(query_datasources(&query));
+        }
+
+        Ok(datasources)
+    }
+
+    /// Builds the query graph of an operation, checking it before it is executed.
+    fn build_query(&self, operation: Operation, query_schema: QuerySchemaRef) -> CoreResult<(QueryType, IrSerializer)> {
+        let (query, info) = tracing::info_span!("prisma:build_query_graph")
+            .in_scope(|| QueryGraphBuilder::new(query_schema).map_operation(operation))?;
+
+        match query {
+            QueryType::Graph(ref graph) => {
+                self.limits.check_graph(graph)?;
+                self.access_policy.check_graph(graph)?;
+            }
+            QueryType::Raw { .. } => self.access_policy.check_raw_query()?,
+        }
+
+        Ok((query, info))
+    }
+
+    /// Executes the document in the open transactions of an interactive transaction. Failing operations result in
+    /// error responses, the events and cache invalidations of the others are collected to be applied on commit.
+    async fn execute_in_transaction(
+        &self,
+        mut query_doc: QueryDocument,
+        query_schema: QuerySchemaRef,
+        tx: &dyn Transaction,
+        datasource_txs: &[(&str, Box<dyn Transaction + '_>)],
+        events: &Mutex<Vec<RecordEvent>>,
+        invalidations: &Mutex<Vec<Invalidation>>,
+    ) -> CoreResult<Responses> {
+        self.prepare_document(&mut query_doc, &query_schema)?;
+
+        let endpoint = |operation: Operation| {
+            self.execute_operation_in_transaction(
+                operation,
+                Arc::clone(&query_schema),
+                tx,
+                datasource_txs,
+                events,
+                invalidations,
+            )
+            .boxed()
+        };
+
+        let mut responses = Responses::with_capacity(query_doc.operations.len());
+
+        for operation in query_doc.operations {
+            match Next::new(&self.middlewares, &endpoint).run(operation).await? {
+                Response::Data(key, item) => responses.insert_data(key, item),
+                Response::PartialData(key, item, errors) => responses.insert_partial_data(key, item, errors),
+                Response::Error(error) => responses.insert_error(error),
+            }
+        }
+
+        Ok(responses)
+    }
+
+    /// Executes an operation in open transactions, at the end of the middleware chain. The events of operations that
+    /// succeeded and the cache invalidations are collected to be applied once the transactions are committed.
+    async fn execute_operation_in_transaction(
+        &self,
+        operation: Operation,
+        query_schema: QuerySchemaRef,
+        tx: &dyn Transaction,
+        datasource_txs: &[(&str, Box<dyn Transaction + '_>)],
+        events: &Mutex<Vec<RecordEvent>>,
+        invalidations: &Mutex<Vec<Invalidation>>,
+    ) -> CoreResult<Response> {
+        let (query, info) = self.build_query(operation, query_schema)?;
+        invalidations.lock().unwrap().extend(Invalidation::of(&query));
+
+        let span = tracing::info_span!("prisma:operation", operation = %info.key);
+        let mut interpreter = QueryInterpreter::new(ConnectionLike::Transaction(tx))
+            .with_failure_policy(self.failure_policy.within_transaction(self.primary_connector));
+
+        for (name, tx) in datasource_txs.iter() {
+            interpreter = interpreter.with_datasource(name.to_string(), ConnectionLike::Transaction(tx.as_ref()));
+        }
+
+        let recorded_events = interpreter.recorded_events();
+        let pipeline = QueryPipeline::new(query, interpreter, info).debug_graph(self.debug_graph);
+        let response = pipeline.execute().instrument(span).await?;
+
+        match response {
+            Response::Error(_) => (),
+            _ => events.lock().unwrap().extend(recorded_events.drain()),
+        }
+
+        Ok(response)
+    }
+
+    /// Executes all operations of the document in a transaction. With an idempotency key, the responses are
+    /// stored with the key in the transaction of the primary connector before it is committed.
+    async fn transaction(
+        &self,
+        mut query_doc: QueryDocument,
+        query_schema: QuerySchemaRef,
+        idempotency_key: Option<&IdempotencyKey>,
+    ) -> CoreResult<Responses> {
+        let datasources = self.prepare_document(&mut query_doc, &query_schema)?;
+
         let conn = self.connector.get_connection().await?;
+        let datasource_conns = self.datasource_connections(&datasources).await?;
+        let (tx, datasource_txs) = start_transactions(conn.as_ref(), &datasource_conns).await?;
+
+        let mut responses = Responses::with_capacity(query_doc.operations.len());
+        let events = Mutex::new(Vec::new());
+        let invalidations = Mutex::new(Vec::new());
+
+        let endpoint = |operation: Operation| {
+            self.execute_operation_in_transaction(
+                operation,
+                Arc::clone(&query_schema),
+                tx.as_ref(),
+                &datasource_txs,
+                &events,
+                &invalidations,
+            )
+            .boxed()
+        };
+
+        for operation in query_doc.operations {
+            match Next::new(&self.middlewares, &endpoint).run(operation).await {
+                Ok(Response::Data(key, item)) => responses.insert_data(key, item),
+                Ok(Response::PartialData(key, item, errors)) => responses.insert_partial_data(key, item, errors),
+                // The data of the preceding operations is rolled back, so only the error is returned.
+                Ok(Response::Error(error)) => {
+                    rollback_all(tx.as_ref(), &datasource_txs).await?;
+
+                    let mut responses = Responses::default();
+                    responses.insert_error(error);
+
+                    return Ok(responses);
+                }
+                Err(err) => {
+                    rollback_all(tx.as_ref(), &datasource_txs).await?;
+                    return Err(err);
+                }
+            }
+        }
+
+        if let Some(key) = idempotency_key {
+            let stored = match key.record(&responses) {
+                Ok(record) => tx.store_idempotency_record(record).await.map_err(CoreError::from),
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = stored {
+                rollback_all(tx.as_ref(), &datasource_txs).await?;
+                return Err(err);
+            }
+        }
+
+        tx.commit().await?;
+
+        for (_, tx) in datasource_txs.iter() {
+            tx.commit().await?;
+        }
+
+        for invalidation in invalidations.into_inner().unwrap().iter() {
+            self.result_cache.invalidate(invalidation);
+        }
+
+        self.event_bus.publish(events.into_inner().unwrap());
+
+        Ok(responses)
+    }
+
+    /// Executes an operation on the connections, at the end of the middleware chain. Writes run in a transaction if
+    /// they need one or transactions are forced.
+    async fn execute_operation(
+        &self,
+        operation: Operation,
+        query_schema: QuerySchemaRef,
+        conn: &dyn Connection,
+        datasource_conns: &[(&str, Box<dyn Connection + '_>)],
+    ) -> CoreResult<Response> {
+        // Reads are cached by the operation, including its arguments.
+        let cache_key = if operation.is_write() {
+            None
+        } else {
+            Some(format!("{:?}", operation))
+        };
+
+        let (query, info) = self.build_query(operation, query_schema)?;
+
+        let cache_slot = match (cache_key, &query) {
+            (Some(key), QueryType::Graph(graph)) => self.result_cache.slot(key, graph),
+            _ => None,
+        };
+
+        if let Some((key, item)) = cache_slot.as_ref().and_then(|slot| self.result_cache.get(slot)) {
+            return Ok(Response::Data(key, item));
+        }
 
-        // Parse, validate, and extract query graphs from query document.
-        let queries: Vec<(QueryType, IrSerializer)> = QueryGraphBuilder::new(query_schema).build(query_doc)?;
+        let invalidation = Invalidation::of(&query);
+        let needs_transaction = self.force_transactions || query.needs_transaction();
+        let span = tracing::info_span!("prisma:operation", operation = %info.key);
+        let start = Instant::now();
 
-        // Create pipelines for all separate queries
-        let mut responses = Responses::with_capacity(queries.len());
+        let result = async {
+            if needs_transaction {
+                let (tx, datasource_txs) = start_transactions(conn, datasource_conns).await?;
 
-        for (query, info) in queries {
-            let needs_transaction = self.force_transactions || query.needs_transaction();
+                let mut interpreter = QueryInterpreter::new(ConnectionLike::Transaction(tx.as_ref()))
+                    .with_failure_policy(self.failure_policy.within_transaction(self.primary_connector));
 
-            let result = if needs_transaction {
-                let tx = conn.start_transaction().await?;
+                for (name, tx) in datasource_txs.iter() {
+                    interpreter =
+                        interpreter.with_datasource(name.to_string(), ConnectionLike::Transaction(tx.as_ref()));
+                }
 
-                let interpreter = QueryInterpreter::new(ConnectionLike::Transaction(tx.as_ref()));
-                let result = QueryPipeline::new(query, interpreter, info).execute().await;
+                let events = interpreter.recorded_events();
+                let result = QueryPipeline::new(query, interpreter, info)
+                    .debug_graph(self.debug_graph)
+                    .execute()
+                    .await;
 
                 if result.is_ok() {
                     tx.commit().await?;
+
+                    for (_, tx) in datasource_txs.iter() {
+                        tx.commit().await?;
+                    }
+
+                    self.event_bus.publish(events.drain());
                 } else {
-                    tx.rollback().await?;
+                    rollback_all(tx.as_ref(), &datasource_txs).await?;
                 }
 
-                result?
+                result
             } else {
-                let interpreter = QueryInterpreter::new(ConnectionLike::Connection(conn.as_ref()));
-                QueryPipeline::new(query, interpreter, info).execute().await?
-            };
+                let mut interpreter =
+                    QueryInterpreter::new(ConnectionLike::Connection(conn)).with_failure_policy(self.failure_policy);
+
+                for (name, conn) in datasource_conns.iter() {
+                    interpreter =
+                        interpreter.with_datasource(name.to_string(), ConnectionLike::Connection(conn.as_ref()));
+                }
+
+                let events = interpreter.recorded_events();
+                let result = QueryPipeline::new(query, interpreter, info)
+                    .debug_graph(self.debug_graph)
+                    .execute()
+                    .await?;
+
+                self.event_bus.publish(events.drain());
+                Ok(result)
+            }
+        }
+        .instrument(span.clone())
+        .await;
+
+        tracing::debug!(
+            parent: &span,
+            duration_ms = start.elapsed().as_millis() as u64,
+            success = result.is_ok(),
+            "Executed operation"
+        );
+
+        // Failed writes may have written records outside of a transaction.
+        if let Some(ref invalidation) = invalidation {
+            self.result_cache.invalidate(invalidation);
+        }
+
+        let result = result?;
+
+        if let Some(slot) = cache_slot {
+            self.result_cache.insert(slot, &result);
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl<C> QueryExecutor for InterpretingExecutor<C>
+where
+    C: Connector + Send + Sync,
+{
+    async fn execute(&self, mut query_doc: QueryDocument, query_schema: QuerySchemaRef) -> CoreResult<Responses> {
+        let datasources = self.prepare_document(&mut query_doc, &query_schema)?;
+
+        let conn = self.connector.get_connection().await?;
+        let datasource_conns = self.datasource_connections(&datasources).await?;
+
+        let endpoint = |operation: Operation| {
+            self.execute_operation(operation, Arc::clone(&query_schema), conn.as_ref(), &datasource_conns)
+                .boxed()
+        };
 
-            match result {
+        let mut responses = Responses::with_capacity(query_doc.operations.len());
+
+        for operation in query_doc.operations {
+            match Next::new(&self.middlewares, &endpoint).run(operation).await? {
                 Response::Data(key, item) => responses.insert_data(key, item),
+                Response::PartialData(key, item, errors) => responses.insert_partial_data(key, item, errors),
                 Response::Error(error) => responses.insert_error(error),
             }
         }
@@ -76,7 +438,186 @@ where
         Ok(responses)
     }
 
+    /// With several datasources, a transaction is started on each of them. They are committed one after
+    /// the other, so a failing commit on one datasource doesn't roll back the commits on the others.
+    async fn execute_transaction(
+        &self,
+        query_doc: QueryDocument,
+        query_schema: QuerySchemaRef,
+    ) -> CoreResult<Responses> {
+        self.transaction(query_doc, query_schema, None).await
+    }
+
+    async fn execute_idempotent(
+        &self,
+        query_doc: QueryDocument,
+        query_schema: QuerySchemaRef,
+        key: IdempotencyKey,
+    ) -> CoreResult<Responses> {
+        // The lookup on a connection creates the storage of the keys, outside of the transaction.
+        let conn = self.connector.get_connection().await?;
+        let record = conn.get_idempotency_record(&key.key).await?;
+        drop(conn);
+
+        if let Some(record) = record {
+            return key.replay(record);
+        }
+
+        match self.transaction(query_doc, query_schema, Some(&key)).await {
+            // A concurrent retry stored the key first, this transaction is rolled back.
+            Err(err) => {
+                let conn = self.connector.get_connection().await?;
+
+                match conn.get_idempotency_record(&key.key).await.ok().flatten() {
+                    Some(record) => key.replay(record),
+                    None => Err(err),
+                }
+            }
+            result => result,
+        }
+    }
+
+    async fn interactive_transaction(
+        &self,
+        query_schema: QuerySchemaRef,
+        mut requests: TransactionReceiver,
+    ) -> CoreResult<()> {
+        // The documents of the transaction are only known once they arrive, so it spans all datasources.
+        let conn = self.connector.get_connection().await?;
+        let datasource_conns = self.datasource_connections(&self.all_datasources()).await?;
+        let (tx, datasource_txs) = start_transactions(conn.as_ref(), &datasource_conns).await?;
+
+        let events = Mutex::new(Vec::new());
+        let invalidations = Mutex::new(Vec::new());
+
+        while let Some(request) = requests.next().await {
+            match request {
+                TransactionRequest::Execute(query_doc, reply) => {
+                    let result = self
+                        .execute_in_transaction(
+                            query_doc,
+                            Arc::clone(&query_schema),
+                            tx.as_ref(),
+                            &datasource_txs,
+                            &events,
+                            &invalidations,
+                        )
+                        .await;
+
+                    let _ = reply.send(result);
+                }
+                TransactionRequest::Commit(reply) => {
+                    let result = async {
+                        tx.commit().await?;
+
+                        for (_, tx) in datasource_txs.iter() {
+                            tx.commit().await?;
+                        }
+
+                        Ok(())
+                    }
+                    .await;
+
+                    if result.is_ok() {
+                        for invalidation in invalidations.into_inner().unwrap().iter() {
+                            self.result_cache.invalidate(invalidation);
+                        }
+
+                        self.event_bus.publish(events.into_inner().unwrap());
+                    }
+
+                    let _ = reply.send(result);
+                    return Ok(());
+                }
+                TransactionRequest::Rollback(reply) => {
+                    let result = rollback_all(tx.as_ref(), &datasource_txs).await;
+
+                    let _ = reply.send(result);
+                    return Ok(());
+                }
+            }
+        }
+
+        // The client went away without ending the transaction.
+        rollback_all(tx.as_ref(), &datasource_txs).await
+    }
+
     fn primary_connector(&self) -> &'static str {
         self.primary_connector
     }
+
+    fn subscribe(&self) -> RecordEventReceiver {
+        self.event_bus.subscribe()
+    }
+
+    async fn check_readiness(&self) -> CoreResult<PoolState> {
+        let conn = self.connector.get_connection().await?;
+        conn.execute_raw("SELECT 1".to_owned(), Vec::new()).await?;
+        drop(conn);
+
+        for (_, conn) in self.datasource_connections(&self.all_datasources()).await? {
+            conn.execute_raw("SELECT 1".to_owned(), Vec::new()).await?;
+        }
+
+        Ok(self.connector.pool_state().await?)
+    }
+
+    async fn pool_state(&self) -> CoreResult<PoolState> {
+        Ok(self.connector.pool_state().await?)
+    }
+
+    async fn routines(&self) -> CoreResult<Vec<Routine>> {
+        let conn = self.connector.get_connection().await?;
+        Ok(conn.list_routines().await?)
+    }
+}
+
+/// The datasources other than the first one the query runs on.
+fn query_datasources(query: &QueryType) -> Vec<String> {
+    let graph = match query {
+        QueryType::Graph(graph) => graph,
+        // Raw queries and routines run on the first datasource.
+        QueryType::Raw { .. } => return Vec::new(),
+    };
+
+    graph
+        .nodes()
+        .into_iter()
+        .filter_map(|node| match graph.node_content(&node) {
+            Some(Node::Query(query)) => query.datasource(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Starts a transaction on the connection and on each of the datasource connections. The transactions already
+/// started are rolled back if one of them fails to start.
+async fn start_transactions<'a>(
+    conn: &'a dyn Connection,
+    datasource_conns: &'a [(&'a str, Box<dyn Connection + 'a>)],
+) -> CoreResult<(Box<dyn Transaction + 'a>, Vec<(&'a str, Box<dyn Transaction + 'a>)>)> {
+    let tx = conn.start_transaction().await?;
+    let mut datasource_txs = Vec::with_capacity(datasource_conns.len());
+
+    for (name, conn) in datasource_conns.iter() {
+        match conn.start_transaction().await {
+            Ok(datasource_tx) => datasource_txs.push((*name, datasource_tx)),
+            Err(err) => {
+                rollback_all(tx.as_ref(), &datasource_txs).await?;
+                return Err(err.into());
+            }
+        }
+    }
+
+    Ok((tx, datasource_txs))
+}
+
+async fn rollback_all(tx: &dyn Transaction, datasource_txs: &[(&str, Box<dyn Transaction + '_>)]) -> CoreResult<()> {
+    tx.rollback().await?;
+
+    for (_, tx) in datasource_txs.iter() {
+        tx.rollback().await?;
+    }
+
+    Ok(())
 }
@@ -0,0 +1,265 @@
+use crate::{
+    CoreError, CoreResult, Operation, QueryDocument, QueryGraph, QuerySchema, QueryTag, QueryValue, SchemaQueryBuilder,
+    Selection,
+};
+
+/// Argument keys designating nested write operations inside of write data.
+const NESTED_WRITE_OPERATIONS: &[&str] = &[
+    "create",
+    "connect",
+    "disconnect",
+    "set",
+    "update",
+    "updateMany",
+    "upsert",
+    "delete",
+    "deleteMany",
+];
+
+/// Arguments limiting the number of records of reads of many records.
+const PAGINATION_ARGUMENTS: &[&str] = &["take", "first", "last"];
+
+/// Upper bounds on the size of incoming queries. A limit of `None` means unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct QueryLimits {
+    /// Maximum depth of nested selections of an operation, the operation itself counting as 1.
+    pub max_depth: Option<usize>,
+
+    /// Maximum number of nested write operations (nested creates, connects, ...) of a single mutation.
+    pub max_nested_writes: Option<usize>,
+
+    /// Maximum number of nodes of a single query graph.
+    pub max_graph_nodes: Option<usize>,
+
+    /// Maximum number of records a read of many records can request with `take`, `first` or `last`.
+    pub max_take: Option<usize>,
+
+    /// Models whose records can't be read all at once: `findMany` queries on them need a `take`, `first` or
+    /// `last` argument.
+    pub paginated_models: Vec<String>,
+
+    /// Records read by `findMany` queries on paginated models without a `take`, `first` or `last` argument. These
+    /// queries are rejected if not set.
+    pub default_take: Option<usize>,
+}
+
+impl QueryLimits {
+    pub fn check_document(&self, query_doc: &QueryDocument) -> CoreResult<()> {
+        for operation in query_doc.operations.iter() {
+            let selection = match operation {
+                Operation::Read(selection) | Operation::Write(selection) => selection,
+            };
+
+            check("depth", selection_depth(selection), self.max_depth)?;
+            check("take", selection_take(selection), self.max_take)?;
+
+            if let Operation::Write(selection) = operation {
+                let nested_writes: usize = selection.arguments.iter().map(|(_, value)| nested_writes(value)).sum();
+                check("nested writes", nested_writes, self.max_nested_writes)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_graph(&self, graph: &QueryGraph) -> CoreResult<()> {
+        check("query graph nodes", graph.node_count(), self.max_graph_nodes)
+    }
+
+    /// Adds the default take to `findMany` queries on paginated models that read all records, or rejects them.
+    pub fn paginate(&self, query_doc: &mut QueryDocument, query_schema: &QuerySchema) -> CoreResult<()> {
+        for operation in query_doc.operations.iter_mut() {
+            let selection = match operation {
+                Operation::Read(selection) => selection,
+                Operation::Write(_) => continue,
+            };
+
+            let is_paginated = |name: &str| self.paginated_models.iter().any(|model| model == name);
+            let model_name = match query_schema.find_query_field(selection.name.clone()) {
+                Some(field) => match field.query_builder {
+                    Some(SchemaQueryBuilder::ModelQueryBuilder(ref builder))
+                        if builder.tag == QueryTag::FindMany && is_paginated(&builder.model.name) =>
+                    {
+                        builder.model.name.clone()
+                    }
+                    _ => continue,
+                },
+                None => continue,
+            };
+
+            if selection
+                .arguments
+                .iter()
+                .any(|(name, _)| PAGINATION_ARGUMENTS.contains(&name.as_str()))
+            {
+                continue;
+            }
+
+            match self.default_take {
+                Some(take) => selection
+                    .arguments
+                    .push(("take".to_owned(), QueryValue::Int(take as i64))),
+                None => return Err(CoreError::PaginationRequired(model_name)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check(limit: &'static str, value: usize, maximum: Option<usize>) -> CoreResult<()> {
+    match maximum {
+        Some(maximum) if value > maximum => Err(CoreError::LimitExceeded { limit, value, maximum }),
+        _ => Ok(()),
+    }
+}
+
+fn selection_depth(selection: &Selection) -> usize {
+    1 + selection
+        .nested_selections
+        .iter()
+        .map(selection_depth)
+        .max()
+        .unwrap_or(0)
+}
+
+/// The largest number of records requested by a read of many records of the selection or its nested selections.
+fn selection_take(selection: &Selection) -> usize {
+    let own = selection
+        .arguments
+        .iter()
+        .filter(|(name, _)| PAGINATION_ARGUMENTS.contains(&name.as_str()))
+        .filter_map(|(_, value)| match value {
+            QueryValue::Int(i) => Some(i.abs() as usize),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0);
+
+    selection
+        .nested_selections
+        .iter()
+        .map(selection_take)
+        .fold(own, std::cmp::max)
+}
+
+fn nested_writes(value: &QueryValue) -> usize {
+    match value {
+        QueryValue::List(values) => values.iter().map(nested_writes).sum(),
+        QueryValue::Object(map) => map
+            .iter()
+            .map(|(key, value)| {
+                let own = if NESTED_WRITE_OPERATIONS.contains(&key.as_str()) {
+                    match value {
+                        QueryValue::List(values) => values.len(),
+                        QueryValue::Object(_) => 1,
+                        _ => 0,
+                    }
+                } else {
+                    0
+                };
+
+                own + nested_writes(value)
+            })
+            .sum(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn selection(name: &str, arguments: Vec<(String, QueryValue)>, nested_selections: Vec<Selection>) -> Selection {
+        Selection {
+            name: name.to_owned(),
+            alias: None,
+            arguments,
+            nested_selections,
+        }
+    }
+
+    fn object(pairs: Vec<(&str, QueryValue)>) -> QueryValue {
+        QueryValue::Object(
+            pairs
+                .into_iter()
+                .map(|(k, v)| (k.to_owned(), v))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn depth_limit_counts_nested_selections() {
+        let posts = selection("posts", vec![], vec![selection("title", vec![], vec![])]);
+        let doc = QueryDocument {
+            operations: vec![Operation::Read(selection("findManyUser", vec![], vec![posts]))],
+        };
+
+        let limits = |max_depth| QueryLimits {
+            max_depth: Some(max_depth),
+            ..Default::default()
+        };
+
+        assert!(limits(3).check_document(&doc).is_ok());
+        assert!(limits(2).check_document(&doc).is_err());
+    }
+
+    #[test]
+    fn take_limit_applies_to_nested_selections() {
+        let posts = selection(
+            "posts",
+            vec![("last".to_owned(), QueryValue::Int(20))],
+            vec![selection("title", vec![], vec![])],
+        );
+
+        let doc = QueryDocument {
+            operations: vec![Operation::Read(selection(
+                "findManyUser",
+                vec![("take".to_owned(), QueryValue::Int(-5))],
+                vec![posts],
+            ))],
+        };
+
+        let limits = |max_take| QueryLimits {
+            max_take: Some(max_take),
+            ..Default::default()
+        };
+
+        assert!(limits(20).check_document(&doc).is_ok());
+        assert!(limits(10).check_document(&doc).is_err());
+    }
+
+    #[test]
+    fn nested_writes_limit_counts_nested_operations() {
+        let data = object(vec![(
+            "posts",
+            object(vec![
+                (
+                    "create",
+                    QueryValue::List(vec![
+                        object(vec![("title", QueryValue::String("a".into()))]),
+                        object(vec![("title", QueryValue::String("b".into()))]),
+                    ]),
+                ),
+                ("connect", object(vec![("id", QueryValue::Int(1))])),
+            ]),
+        )]);
+
+        let doc = QueryDocument {
+            operations: vec![Operation::Write(selection(
+                "createOneUser",
+                vec![("data".to_owned(), data)],
+                vec![],
+            ))],
+        };
+
+        let limits = |max_nested_writes| QueryLimits {
+            max_nested_writes: Some(max_nested_writes),
+            ..Default::default()
+        };
+
+        assert!(limits(3).check_document(&doc).is_ok());
+        assert!(limits(2).check_document(&doc).is_err());
+    }
+}
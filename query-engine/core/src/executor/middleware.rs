@@ -0,0 +1,154 @@
+use crate::{CoreResult, Operation, Response};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::sync::Arc;
+
+/// Wraps the execution of single operations, e.g. to log, cache or rewrite them without changing the core.
+///
+/// The middlewares registered on an executor are called in registration order for every operation of a document,
+/// in transactions as well. A middleware executes the operation, or the operation it rewrote it to, with `next`.
+/// It can also answer it with a response of its own, without calling `next`.
+///
+/// The limits of the document are checked before the middlewares are called, the query graph of the operation
+/// passed to the end of the chain is checked against the limits and the access policy before it's executed.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    async fn handle(&self, operation: Operation, next: Next<'_>) -> CoreResult<Response>;
+}
+
+/// Executes an operation at the end of the middleware chain.
+pub(crate) type Endpoint<'a> = dyn Fn(Operation) -> BoxFuture<'a, CoreResult<Response>> + Send + Sync + 'a;
+
+/// The remaining middlewares of the chain, followed by the execution of the operation.
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    endpoint: &'a Endpoint<'a>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(middlewares: &'a [Arc<dyn Middleware>], endpoint: &'a Endpoint<'a>) -> Self {
+        Self { middlewares, endpoint }
+    }
+
+    pub async fn run(self, operation: Operation) -> CoreResult<Response> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => {
+                let next = Next {
+                    middlewares,
+                    endpoint: self.endpoint,
+                };
+
+                middleware.handle(operation, next).await
+            }
+            None => (self.endpoint)(operation).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CoreError, Item, Selection};
+    use futures::{executor::block_on, FutureExt};
+    use prisma_models::PrismaValue;
+    use std::sync::Mutex;
+
+    fn read(name: &str) -> Operation {
+        Operation::Read(Selection {
+            name: name.to_owned(),
+            alias: None,
+            arguments: vec![],
+            nested_selections: vec![],
+        })
+    }
+
+    fn operation_name(operation: &Operation) -> String {
+        match operation {
+            Operation::Read(selection) | Operation::Write(selection) => selection.name.clone(),
+        }
+    }
+
+    fn response(value: &str) -> Response {
+        Response::Data("result".to_owned(), Item::Value(PrismaValue::String(value.to_owned())))
+    }
+
+    fn response_value(response: Response) -> String {
+        match response {
+            Response::Data(_, Item::Value(PrismaValue::String(value))) => value,
+            response => panic!("Unexpected response {:?}", response),
+        }
+    }
+
+    /// Records the operations it sees and rewrites them to the operation named `rewrite_to`.
+    struct Rewrite {
+        seen: Arc<Mutex<Vec<String>>>,
+        rewrite_to: &'static str,
+    }
+
+    #[async_trait]
+    impl Middleware for Rewrite {
+        async fn handle(&self, operation: Operation, next: Next<'_>) -> CoreResult<Response> {
+            self.seen.lock().unwrap().push(operation_name(&operation));
+            next.run(read(self.rewrite_to)).await
+        }
+    }
+
+    /// Answers operations named `cached` without executing them.
+    struct Cache;
+
+    #[async_trait]
+    impl Middleware for Cache {
+        async fn handle(&self, operation: Operation, next: Next<'_>) -> CoreResult<Response> {
+            if operation_name(&operation) == "cached" {
+                Ok(response("from cache"))
+            } else {
+                next.run(operation).await
+            }
+        }
+    }
+
+    fn execute(middlewares: &[Arc<dyn Middleware>], operation: Operation) -> String {
+        let endpoint =
+            |operation: Operation| async move { Ok::<_, CoreError>(response(&operation_name(&operation))) }.boxed();
+
+        response_value(block_on(Next::new(middlewares, &endpoint).run(operation)).unwrap())
+    }
+
+    #[test]
+    fn without_middlewares_the_operation_is_executed() {
+        assert_eq!(execute(&[], read("findManyUser")), "findManyUser");
+    }
+
+    #[test]
+    fn middlewares_are_called_in_registration_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let middlewares: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(Rewrite {
+                seen: Arc::clone(&seen),
+                rewrite_to: "findManyPost",
+            }),
+            Arc::new(Rewrite {
+                seen: Arc::clone(&seen),
+                rewrite_to: "findManyComment",
+            }),
+        ];
+
+        assert_eq!(execute(&middlewares, read("findManyUser")), "findManyComment");
+        assert_eq!(*seen.lock().unwrap(), &["findManyUser", "findManyPost"]);
+    }
+
+    #[test]
+    fn middlewares_can_answer_operations_without_executing_them() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let middlewares: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(Rewrite {
+                seen: Arc::clone(&seen),
+                rewrite_to: "cached",
+            }),
+            Arc::new(Cache),
+        ];
+
+        assert_eq!(execute(&middlewares, read("findManyUser")), "from cache");
+        assert_eq!(execute(&middlewares[1..], read("findManyUser")), "findManyUser");
+    }
+}
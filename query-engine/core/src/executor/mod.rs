@@ -5,17 +5,69 @@
 //!
 //! What the executor module DOES NOT DO:
 //! - Define low level execution of queries. This is considered an implementation detail of the modules used by the executors.
+mod debug;
+mod events;
+mod idempotency;
+mod interactive_transaction;
 mod interpreting_executor;
+mod limits;
+mod middleware;
 mod pipeline;
+mod result_cache;
 
+pub use debug::*;
+pub use events::*;
+pub use idempotency::*;
+pub use interactive_transaction::*;
 pub use interpreting_executor::*;
+pub use limits::*;
+pub use middleware::*;
+pub use result_cache::*;
 
 use crate::{query_document::QueryDocument, response_ir::Responses, schema::QuerySchemaRef, CoreResult};
 use async_trait::async_trait;
+use connector::{PoolState, Routine};
 
 #[async_trait]
 pub trait QueryExecutor {
     async fn execute(&self, query_doc: QueryDocument, query_schema: QuerySchemaRef) -> CoreResult<Responses>;
 
+    /// Executes all operations of the document in a single transaction. If one of them fails, none of them is committed.
+    async fn execute_transaction(
+        &self,
+        query_doc: QueryDocument,
+        query_schema: QuerySchemaRef,
+    ) -> CoreResult<Responses>;
+
+    /// Executes all operations of the document in a single transaction, like `execute_transaction`, and stores the
+    /// data of the responses with the idempotency key. If the key was used before, the stored data is returned
+    /// instead of executing the document again. Responses with errors are not stored, so the request can be retried.
+    async fn execute_idempotent(
+        &self,
+        query_doc: QueryDocument,
+        query_schema: QuerySchemaRef,
+        key: IdempotencyKey,
+    ) -> CoreResult<Responses>;
+
+    /// Starts a transaction executing the documents of the requests received over the channel, until a commit or a
+    /// rollback is requested. Closing the channel rolls the transaction back. Returns once the transaction ended.
+    async fn interactive_transaction(
+        &self,
+        query_schema: QuerySchemaRef,
+        requests: TransactionReceiver,
+    ) -> CoreResult<()>;
+
     fn primary_connector(&self) -> &'static str;
+
+    /// Subscribes to the record events of all committed writes.
+    fn subscribe(&self) -> RecordEventReceiver;
+
+    /// Checks that a connection can be acquired and used, returning the state of the connection pool.
+    async fn check_readiness(&self) -> CoreResult<PoolState>;
+
+    /// The state of the connection pool, without acquiring a connection.
+    async fn pool_state(&self) -> CoreResult<PoolState>;
+
+    /// The stored procedures and functions of the database that can be exposed in the query schema.
+    async fn routines(&self) -> CoreResult<Vec<Routine>>;
 }
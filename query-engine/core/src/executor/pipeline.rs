@@ -1,9 +1,11 @@
+use super::{GraphFormat, DEBUG_QUERY_GRAPH_TARGET};
 use crate::{CoreResult, Env, Expression, Expressionista, IrSerializer, QueryInterpreter, QueryType, Response};
 
 pub struct QueryPipeline<'conn, 'tx> {
     query: QueryType,
     interpreter: QueryInterpreter<'conn, 'tx>,
     serializer: IrSerializer,
+    debug_graph: Option<GraphFormat>,
 }
 
 impl<'conn, 'tx> QueryPipeline<'conn, 'tx> {
@@ -12,24 +14,52 @@ impl<'conn, 'tx> QueryPipeline<'conn, 'tx> {
             query,
             interpreter,
             serializer,
+            debug_graph: None,
         }
     }
 
+    /// Logs the query graph in the given format, followed by the nodes executed by the interpreter.
+    pub fn debug_graph(mut self, format: Option<GraphFormat>) -> Self {
+        if format.is_some() {
+            self.interpreter = self.interpreter.with_execution_log();
+        }
+
+        self.debug_graph = format;
+        self
+    }
+
     pub async fn execute(self) -> CoreResult<Response> {
         let serializer = self.serializer;
 
         match self.query {
             QueryType::Graph(mut graph) => {
                 // Run final validations and transformations.
-//                println!("BEFORE: {}", graph);
                 graph.finalize()?;
                 trace!("{}", graph);
-//                println!("AFTER: {}", graph);
+
+                if let Some(format) = self.debug_graph {
+                    info!(
+                        target: DEBUG_QUERY_GRAPH_TARGET,
+                        "Query graph of {}:\n{}",
+                        serializer.key,
+                        format.render(&graph)
+                    );
+                }
 
                 let expr = Expressionista::translate(graph)?;
                 let result = self.interpreter.interpret(expr, Env::default(), 0).await;
 
-                trace!("{}", self.interpreter.log_output());
+                if self.debug_graph.is_some() {
+                    info!(
+                        target: DEBUG_QUERY_GRAPH_TARGET,
+                        "Executed nodes of {}:{}",
+                        serializer.key,
+                        self.interpreter.log_output()
+                    );
+                } else {
+                    trace!("{}", self.interpreter.log_output());
+                }
+
                 Ok(serializer.serialize(result?))
             }
             QueryType::Raw { query, parameters } => {
@@ -0,0 +1,404 @@
+use crate::{
+    query_ast::{Query, ReadQuery, WriteQuery},
+    query_graph::{Node, QueryGraph},
+    Item, QueryType, Response,
+};
+use connector::{filter::Filter, QueryArguments};
+use prisma_models::ModelRef;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Entries beyond this are only added once expired entries made room.
+const MAX_ENTRIES: usize = 10_000;
+
+/// In-memory cache of the results of reads on models with `@@cache`, keyed by the operation including its
+/// arguments. Entries expire after the smallest ttl of the models the read touches, and are removed as soon as a
+/// write on one of these models commits through this engine. Writes of other processes on the database only become
+/// visible once the entries expired.
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    inner: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    /// Incremented with every invalidation of a model, so that reads running concurrently to a write don't store
+    /// results of before the write.
+    generations: HashMap<String, u64>,
+    /// Incremented when the whole cache is invalidated.
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    key: String,
+    item: Item,
+    models: Vec<String>,
+    expires_at: Instant,
+}
+
+/// A read that can be answered from the cache, taken before the read is executed.
+#[derive(Debug)]
+pub struct CacheSlot {
+    key: String,
+    ttl: Duration,
+    models: Vec<String>,
+    generations: Vec<u64>,
+    generation: u64,
+}
+
+/// The cache entries a write invalidates once it committed.
+#[derive(Debug)]
+pub enum Invalidation {
+    Models(HashSet<String>),
+    /// Raw queries and routines can write to any table.
+    All,
+}
+
+impl ResultCache {
+    /// The slot of a read-only query graph whose models are all cached, keyed by `operation`.
+    pub fn slot(&self, operation: String, graph: &QueryGraph) -> Option<CacheSlot> {
+        let mut models = Vec::new();
+        let mut ttl: Option<Duration> = None;
+
+        for node in graph.nodes() {
+            match graph.node_content(&node) {
+                Some(Node::Query(Query::Read(query))) => {
+                    for model in read_models(query) {
+                        let model_ttl = model.cache_ttl()?;
+
+                        ttl = Some(ttl.map_or(model_ttl, |ttl| ttl.min(model_ttl)));
+                        models.push(model.name.clone());
+                    }
+                }
+                Some(Node::Query(Query::Write(_))) => return None,
+                _ => (),
+            }
+        }
+
+        let ttl = ttl?;
+
+        models.sort();
+        models.dedup();
+
+        let state = self.inner.lock().unwrap();
+        let generations = models.iter().map(|model| state.model_generation(model)).collect();
+
+        Some(CacheSlot {
+            key: operation,
+            ttl,
+            models,
+            generations,
+            generation: state.generation,
+        })
+    }
+
+    /// The cached response key and data of the read.
+    pub fn get(&self, slot: &CacheSlot) -> Option<(String, Item)> {
+        let mut state = self.inner.lock().unwrap();
+
+        match state.entries.get(&slot.key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some((entry.key.clone(), entry.item.clone())),
+            Some(_) => {
+                state.entries.remove(&slot.key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Stores the result of the read, unless one of its models was invalidated since the slot was taken.
+    pub fn insert(&self, slot: CacheSlot, response: &Response) {
+        let (key, item) = match response {
+            Response::Data(key, item) => (key, item),
+            // Partial data is read again, the failed reads may succeed.
+            Response::PartialData(..) | Response::Error(_) => return,
+        };
+
+        let mut state = self.inner.lock().unwrap();
+
+        let invalidated = state.generation != slot.generation
+            || slot
+                .models
+                .iter()
+                .zip(slot.generations.iter())
+                .any(|(model, generation)| state.model_generation(model) != *generation);
+
+        if invalidated {
+            return;
+        }
+
+        if state.entries.len() >= MAX_ENTRIES {
+            let now = Instant::now();
+            state.entries.retain(|_, entry| entry.expires_at > now);
+
+            if state.entries.len() >= MAX_ENTRIES {
+                return;
+            }
+        }
+
+        state.entries.insert(
+            slot.key,
+            CacheEntry {
+                key: key.clone(),
+                item: item.clone(),
+                models: slot.models,
+                expires_at: Instant::now() + slot.ttl,
+            },
+        );
+    }
+
+    pub fn invalidate(&self, invalidation: &Invalidation) {
+        let mut state = self.inner.lock().unwrap();
+
+        match invalidation {
+            Invalidation::Models(models) => {
+                for model in models.iter() {
+                    *state.generations.entry(model.clone()).or_insert(0) += 1;
+                }
+
+                state
+                    .entries
+                    .retain(|_, entry| !entry.models.iter().any(|model| models.contains(model)));
+            }
+            Invalidation::All => {
+                state.generation += 1;
+                state.entries.clear();
+            }
+        }
+    }
+}
+
+impl CacheState {
+    fn model_generation(&self, model: &str) -> u64 {
+        self.generations.get(model).copied().unwrap_or(0)
+    }
+}
+
+impl Invalidation {
+    /// The invalidation of the writes of the query, `None` if it only reads.
+    pub fn of(query: &QueryType) -> Option<Invalidation> {
+        let graph = match query {
+            QueryType::Graph(graph) => graph,
+            QueryType::Raw { .. } => return Some(Invalidation::All),
+        };
+
+        let mut models = HashSet::new();
+
+        for node in graph.nodes() {
+            let model = match graph.node_content(&node) {
+                Some(Node::Query(Query::Write(query))) => match query {
+                    WriteQuery::CreateRecord(q) => q.model.name.clone(),
+                    WriteQuery::UpdateRecord(q) => q.model.name.clone(),
+                    WriteQuery::UpdateManyRecords(q) => q.model.name.clone(),
+                    WriteQuery::DeleteRecord(q) => q.model.name.clone(),
+                    WriteQuery::DeleteManyRecords(q) => q.model.name.clone(),
+                    // Relation tables are read as part of both sides of the relation.
+                    WriteQuery::ConnectRecords(q) => {
+                        models.insert(q.relation_field.related_model().name.clone());
+                        q.relation_field.model().name.clone()
+                    }
+                    WriteQuery::DisconnectRecords(q) => {
+                        models.insert(q.relation_field.related_model().name.clone());
+                        q.relation_field.model().name.clone()
+                    }
+                    WriteQuery::DisconnectAllRecords(q) => {
+                        models.insert(q.relation_field.related_model().name.clone());
+                        q.relation_field.model().name.clone()
+                    }
+                    WriteQuery::CallRoutine(_) | WriteQuery::Raw { .. } => return Some(Invalidation::All),
+                },
+                _ => continue,
+            };
+
+            models.insert(model);
+        }
+
+        if models.is_empty() {
+            None
+        } else {
+            Some(Invalidation::Models(models))
+        }
+    }
+}
+
+/// The models of the read and its nested reads, including the models of related records their filters compare.
+fn read_models(query: &ReadQuery) -> Vec<ModelRef> {
+    let mut models = Vec::new();
+
+    let nested = match query {
+        ReadQuery::RecordQuery(q) => {
+            models.push(q.model.clone());
+
+            if let Some(ref filter) = q.filter {
+                filter_models(filter, &mut models);
+            }
+
+            &q.nested
+        }
+        ReadQuery::ManyRecordsQuery(q) => {
+            models.push(q.model.clone());
+            args_models(&q.args, &mut models);
+
+            &q.nested
+        }
+        ReadQuery::RelatedRecordsQuery(q) => {
+            models.push(q.parent_field.related_model());
+            args_models(&q.args, &mut models);
+
+            &q.nested
+        }
+        ReadQuery::AggregateRecordsQuery(q) => return vec![q.model.clone()],
+    };
+
+    for query in nested {
+        models.extend(read_models(query));
+    }
+
+    models
+}
+
+fn args_models(args: &QueryArguments, models: &mut Vec<ModelRef>) {
+    if let Some(ref order_by) = args.order_by {
+        models.push(order_by.field.model());
+    }
+
+    if let Some(ref filter) = args.filter {
+        filter_models(filter, models);
+    }
+}
+
+/// Reads filtering by related records change their results with writes to the related model.
+fn filter_models(filter: &Filter, models: &mut Vec<ModelRef>) {
+    match filter {
+        Filter::And(filters) | Filter::Or(filters) | Filter::Not(filters) => {
+            for filter in filters {
+                filter_models(filter, models);
+            }
+        }
+        Filter::Relation(rf) => {
+            models.push(rf.field.related_model());
+            filter_models(&rf.nested_filter, models);
+        }
+        Filter::OneRelationIsNull(rf) => models.push(rf.field.related_model()),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_ast::ManyRecordsQuery;
+    use connector::filter::RelationCompare;
+    use prisma_models::{DatamodelConverter, InternalDataModelRef, SelectedFields};
+
+    const DATAMODEL: &str = r#"
+        model User {
+            id    String @id
+            posts Post[]
+
+            @@cache(ttl: 60)
+        }
+
+        model Post {
+            id     String @id
+            author User
+
+            @@cache(ttl: 30)
+        }
+    "#;
+
+    /// A read of users with at least one post, selecting no post fields. The models of the graph refer to the
+    /// returned data model.
+    fn users_with_posts() -> (InternalDataModelRef, QueryGraph) {
+        let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+        let internal_datamodel = DatamodelConverter::convert(&datamodel).build("db".to_owned());
+        let user = internal_datamodel.find_model("User").unwrap();
+        let posts = user.fields().find_from_relation_fields("posts").unwrap();
+
+        let mut graph = QueryGraph::new();
+        graph.create_node(Query::Read(ReadQuery::ManyRecordsQuery(ManyRecordsQuery {
+            name: "findManyUser".to_owned(),
+            alias: None,
+            model: user,
+            args: QueryArguments {
+                filter: Some(posts.at_least_one_related(Filter::Empty)),
+                ..Default::default()
+            },
+            selected_fields: SelectedFields::default(),
+            nested: vec![],
+            selection_order: vec![],
+        })));
+
+        (internal_datamodel, graph)
+    }
+
+    fn slot(cache: &ResultCache, key: &str, models: &[&str]) -> CacheSlot {
+        let state = cache.inner.lock().unwrap();
+        let models: Vec<String> = models.iter().map(|model| model.to_string()).collect();
+
+        CacheSlot {
+            key: key.to_owned(),
+            ttl: Duration::from_secs(60),
+            generations: models.iter().map(|model| state.model_generation(model)).collect(),
+            generation: state.generation,
+            models,
+        }
+    }
+
+    fn response() -> Response {
+        Response::Data("findManyUser".to_owned(), Item::Json(serde_json::Value::Null))
+    }
+
+    #[test]
+    fn writes_invalidate_the_reads_of_their_models() {
+        let cache = ResultCache::default();
+
+        cache.insert(slot(&cache, "users", &["User"]), &response());
+        cache.insert(slot(&cache, "users_with_posts", &["Post", "User"]), &response());
+        cache.insert(slot(&cache, "tags", &["Tag"]), &response());
+
+        cache.invalidate(&Invalidation::Models(vec!["Post".to_owned()].into_iter().collect()));
+
+        assert!(cache.get(&slot(&cache, "users", &["User"])).is_some());
+        assert!(cache
+            .get(&slot(&cache, "users_with_posts", &["Post", "User"]))
+            .is_none());
+        assert!(cache.get(&slot(&cache, "tags", &["Tag"])).is_some());
+
+        cache.invalidate(&Invalidation::All);
+
+        assert!(cache.get(&slot(&cache, "users", &["User"])).is_none());
+    }
+
+    #[test]
+    fn reads_running_concurrently_to_writes_are_not_stored() {
+        let cache = ResultCache::default();
+        let users = slot(&cache, "users", &["User"]);
+
+        cache.invalidate(&Invalidation::Models(vec!["User".to_owned()].into_iter().collect()));
+        cache.insert(users, &response());
+
+        assert!(cache.get(&slot(&cache, "users", &["User"])).is_none());
+    }
+
+    #[test]
+    fn reads_filtering_by_related_records_are_invalidated_by_writes_to_them() {
+        let cache = ResultCache::default();
+        let (_datamodel, graph) = users_with_posts();
+        let slot = cache.slot("users_with_posts".to_owned(), &graph).unwrap();
+
+        assert_eq!(slot.models, vec!["Post".to_owned(), "User".to_owned()]);
+        assert_eq!(slot.ttl, Duration::from_secs(30));
+
+        cache.insert(slot, &response());
+        cache.invalidate(&Invalidation::Models(vec!["Post".to_owned()].into_iter().collect()));
+
+        let slot = cache.slot("users_with_posts".to_owned(), &graph).unwrap();
+        assert!(cache.get(&slot).is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{CoreError, QueryGraphBuilderError, QueryGraphError};
-use connector::error::ConnectorError;
+use connector::error::{ConnectorError, ErrorKind};
 use prisma_models::DomainError;
 use std::fmt;
 
@@ -20,13 +20,42 @@ pub enum InterpreterError {
     /// Wraps errors coming from the connector during execution.
     ConnectorError(ConnectorError),
 
+    /// Records disappeared between a node and the reload of its records, e.g. because they were deleted concurrently.
+    RecordCountMismatch {
+        model_name: String,
+        expected: usize,
+        found: usize,
+    },
+
     Generic(String),
 }
 
+impl InterpreterError {
+    /// Errors that executing the same query again may not run into, e.g. a lost connection or a timeout.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::ConnectorError(err) => match err.kind {
+                ErrorKind::ConnectionError(_) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
 impl fmt::Display for InterpreterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::QueryGraphBuilderError(e) => write!(f, "{:?}", e),
+            Self::RecordCountMismatch {
+                model_name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected {} records of model '{}' to be reloaded, found {}. Records changed during the query.",
+                expected, model_name, found
+            ),
             _ => write!(f, "Error occurred during query execution:\n{:?}", self),
         }
     }
@@ -152,6 +152,23 @@ impl Expressionista {
                             })),
                         })
                     }
+                    Node::Computation(Computation::CompareCounts(CompareCountsNode {
+                        model_name,
+                        expected,
+                        found,
+                    })) => {
+                        if expected != found {
+                            return Err(InterpreterError::RecordCountMismatch {
+                                model_name,
+                                expected: expected.unwrap_or_default(),
+                                found: found.unwrap_or_default(),
+                            });
+                        }
+
+                        Ok(Expression::Return {
+                            result: ExpressionResult::Empty,
+                        })
+                    }
                     _ => unreachable!(),
                 }),
             })
@@ -0,0 +1,68 @@
+/// What the interpreter does when a nested read fails, e.g. the read of the records of a relation selected along with
+/// a query. Failures of the query itself and of writes always fail the whole operation.
+///
+/// Only transient failures are retried, e.g. lost connections and timeouts. On PostgreSQL, a failed statement aborts
+/// the surrounding transaction: nothing is retried within transactions, and the statements following a partial
+/// failure fail as well.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FailurePolicy {
+    /// How often a nested read failing with a transient error is retried before its failure is handled.
+    pub nested_read_retries: usize,
+
+    pub nested_reads: NestedReadFailure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NestedReadFailure {
+    /// The whole operation fails.
+    Abort,
+
+    /// The field of the read resolves to `null`, and the remaining data is returned with an error at the path of the
+    /// field.
+    Partial,
+}
+
+impl FailurePolicy {
+    /// The policy for the statements of a transaction on the connector.
+    pub fn within_transaction(self, connector: &str) -> Self {
+        match connector {
+            "postgres" => Self {
+                nested_read_retries: 0,
+                ..self
+            },
+            _ => self,
+        }
+    }
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        Self {
+            nested_read_retries: 0,
+            nested_reads: NestedReadFailure::Abort,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postgres_transactions_are_not_retried_in() {
+        let policy = FailurePolicy {
+            nested_read_retries: 2,
+            nested_reads: NestedReadFailure::Partial,
+        };
+
+        assert_eq!(
+            policy.within_transaction("postgres"),
+            FailurePolicy {
+                nested_read_retries: 0,
+                nested_reads: NestedReadFailure::Partial,
+            }
+        );
+        assert_eq!(policy.within_transaction("mysql"), policy);
+        assert_eq!(policy.within_transaction("sqlite"), policy);
+    }
+}
@@ -1,14 +1,15 @@
 use super::{
     expression::*,
     query_interpreters::{read, write},
-    InterpretationResult, InterpreterError,
+    FailurePolicy, InterpretationResult, InterpreterError,
 };
-use crate::{Query, QueryResult};
+use crate::{response_ir::trim_records, Query, QueryResult, RecordedEvents};
 use connector::ConnectionLike;
 use crossbeam_queue::SegQueue;
 use futures::future::{BoxFuture, FutureExt};
 use im::HashMap;
 use prisma_models::prelude::*;
+use tracing_futures::Instrument;
 
 #[derive(Debug, Clone)]
 pub enum ExpressionResult {
@@ -45,14 +46,19 @@ impl ExpressionResult {
                 },
 
                 // We always select IDs, the unwraps are safe.
-                QueryResult::RecordSelection(rs) => Some(
-                    rs.scalars
+                QueryResult::RecordSelection(rs) => {
+                    let mut ids: Vec<RecordIdentifier> = rs
+                        .scalars
                         .identifiers(model_id)
                         .unwrap()
                         .into_iter()
                         .map(|val| val.into())
-                        .collect(),
-                ),
+                        .collect();
+
+                    // Paginated reads return an excess record.
+                    trim_records(&mut ids, &rs.query_arguments);
+                    Some(ids)
+                }
 
                 _ => None,
             },
@@ -111,25 +117,61 @@ impl Env {
 }
 pub struct QueryInterpreter<'conn, 'tx> {
     pub(crate) conn: ConnectionLike<'conn, 'tx>,
+    /// Connections to the datasources other than the first one, by datasource name.
+    datasources: std::collections::HashMap<String, ConnectionLike<'conn, 'tx>>,
     log: SegQueue<String>,
+    log_enabled: bool,
+    events: RecordedEvents,
+    failure_policy: FailurePolicy,
 }
 
 impl<'conn, 'tx> QueryInterpreter<'conn, 'tx>
 where
     'tx: 'conn,
 {
-    fn log_enabled() -> bool {
-        log::max_level() == log::LevelFilter::Trace
-    }
-
     pub fn new(conn: ConnectionLike<'conn, 'tx>) -> QueryInterpreter<'conn, 'tx> {
         let log = SegQueue::new();
+        let log_enabled = log::max_level() == log::LevelFilter::Trace;
 
-        if Self::log_enabled() {
+        if log_enabled {
             log.push("\n".to_string());
         }
 
-        Self { conn, log }
+        Self {
+            conn,
+            datasources: std::collections::HashMap::new(),
+            log,
+            log_enabled,
+            events: RecordedEvents::default(),
+            failure_policy: FailurePolicy::default(),
+        }
+    }
+
+    /// Records the executed expressions regardless of the log level, e.g. to debug a query graph.
+    pub fn with_execution_log(mut self) -> Self {
+        if !self.log_enabled {
+            self.log.push("\n".to_string());
+            self.log_enabled = true;
+        }
+
+        self
+    }
+
+    /// Queries on models of the given datasource are executed on `conn` instead of the primary connection.
+    pub fn with_datasource(mut self, name: String, conn: ConnectionLike<'conn, 'tx>) -> Self {
+        self.datasources.insert(name, conn);
+        self
+    }
+
+    /// Handles failing nested reads according to the policy instead of failing the whole query.
+    pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.failure_policy = policy;
+        self
+    }
+
+    /// Handle to the record events of all writes performed by this interpreter.
+    pub fn recorded_events(&self) -> RecordedEvents {
+        self.events.clone()
     }
 
     pub fn interpret(
@@ -193,25 +235,32 @@ where
             }
 
             Expression::Query { query } => {
+                let span = match &query {
+                    Query::Read(read) => tracing::info_span!("prisma:query_node", kind = "read", query = %read),
+                    Query::Write(write) => tracing::info_span!("prisma:query_node", kind = "write", query = %write),
+                };
+
                 let fut = async move {
+                    let conn = self.conn_for(&query)?;
+
                     match query {
                         Query::Read(read) => {
                             self.log_line(level, || format!("READ {}", read));
 
-                            Ok(read::execute(&self.conn, read, None)
+                            Ok(read::execute(conn, read, None, self.failure_policy)
                                 .await
                                 .map(|res| ExpressionResult::Query(res))?)
                         }
 
                         Query::Write(write) => {
                             self.log_line(level, || format!("WRITE {}", write));
-                            Ok(write::execute(&self.conn, write)
+                            Ok(write::execute(conn, write, &self.events)
                                 .await
                                 .map(|res| ExpressionResult::Query(res))?)
                         }
                     }
                 };
-                fut.boxed()
+                fut.instrument(span).boxed()
             }
 
             Expression::Get { binding_name } => async move {
@@ -258,6 +307,17 @@ where
         }
     }
 
+    /// Queries on models of the first datasource are executed on the primary connection, the ones on models of
+    /// other datasources on the connection of their datasource.
+    fn conn_for(&self, query: &Query) -> InterpretationResult<&ConnectionLike<'conn, 'tx>> {
+        match query.datasource() {
+            None => Ok(&self.conn),
+            Some(name) => self.datasources.get(&name).ok_or_else(|| {
+                InterpreterError::InterpretationError(format!("No connection to the datasource `{}`.", name))
+            }),
+        }
+    }
+
     pub fn log_output(&self) -> String {
         let mut output = String::with_capacity(self.log.len() * 30);
 
@@ -273,7 +333,7 @@ where
         S: AsRef<str>,
         F: FnOnce() -> S,
     {
-        if Self::log_enabled() {
+        if self.log_enabled {
             self.log
                 .push(format!("{:indent$}{}\n", "", f().as_ref(), indent = level * 2));
         }
@@ -1,6 +1,7 @@
 mod error;
 mod expression;
 mod expressionista;
+mod failure_policy;
 mod formatters;
 mod interpreter;
 
@@ -9,6 +10,7 @@ pub(self) mod query_interpreters;
 pub use error::*;
 pub use expression::*;
 pub use expressionista::*;
+pub use failure_policy::*;
 pub use formatters::*;
 pub use interpreter::*;
 
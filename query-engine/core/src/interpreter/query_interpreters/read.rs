@@ -1,18 +1,24 @@
-use crate::{interpreter::InterpretationResult, query_ast::*, result_ast::*};
+use crate::{
+    interpreter::{FailurePolicy, InterpretationResult, NestedReadFailure},
+    query_ast::*,
+    result_ast::*,
+    CoreError, ResponseError,
+};
 use connector::{self, filter::Filter, ConnectionLike, QueryArguments, ReadOperations, ScalarCompare};
 use futures::future::{BoxFuture, FutureExt};
-use prisma_models::{ManyRecords, RecordIdentifier};
+use prisma_models::{ManyRecords, ModelIdentifier, RecordIdentifier, RelationFieldRef, SelectedFields};
 
 pub fn execute<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     query: ReadQuery,
     parent_result: Option<&'a ManyRecords>,
+    policy: FailurePolicy,
 ) -> BoxFuture<'a, InterpretationResult<QueryResult>> {
     let fut = async move {
         match query {
-            ReadQuery::RecordQuery(q) => read_one(tx, q).await,
-            ReadQuery::ManyRecordsQuery(q) => read_many(tx, q).await,
-            ReadQuery::RelatedRecordsQuery(q) => read_related(tx, q, parent_result).await,
+            ReadQuery::RecordQuery(q) => read_one(tx, q, policy).await,
+            ReadQuery::ManyRecordsQuery(q) => read_many(tx, q, policy).await,
+            ReadQuery::RelatedRecordsQuery(q) => read_related(tx, q, parent_result, policy).await,
             ReadQuery::AggregateRecordsQuery(q) => aggregate(tx, q).await,
         }
     };
@@ -24,6 +30,7 @@ pub fn execute<'a, 'b>(
 fn read_one<'conn, 'tx>(
     tx: &'conn ConnectionLike<'conn, 'tx>,
     query: RecordQuery,
+    policy: FailurePolicy,
 ) -> BoxFuture<'conn, InterpretationResult<QueryResult>> {
     let fut = async move {
         let model = query.model;
@@ -36,20 +43,23 @@ fn read_one<'conn, 'tx>(
         match scalars {
             Some(record) => {
                 let records: ManyRecords = record.into();
-                let nested: Vec<QueryResult> = process_nested(tx, query.nested, Some(&records)).await?;
+                let (nested, failed) = process_nested(tx, query.nested, Some(&records), policy).await?;
 
                 Ok(QueryResult::RecordSelection(RecordSelection {
                     name: query.name,
+                    alias: query.alias,
                     fields: query.selection_order,
                     scalars: records,
                     nested,
                     model_id,
+                    failed,
                     ..Default::default()
                 }))
             }
 
             None => Ok(QueryResult::RecordSelection(RecordSelection {
                 name: query.name,
+                alias: query.alias,
                 fields: query.selection_order,
                 model_id,
                 ..Default::default()
@@ -64,6 +74,7 @@ fn read_one<'conn, 'tx>(
 fn read_many<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     query: ManyRecordsQuery,
+    policy: FailurePolicy,
 ) -> BoxFuture<'a, InterpretationResult<QueryResult>> {
     let fut = async move {
         let scalars = tx
@@ -76,15 +87,17 @@ fn read_many<'a, 'b>(
 
         let model_id = query.model.primary_identifier();
         // let ids = scalars.identifiers(&model_id)?;
-        let nested: Vec<QueryResult> = process_nested(tx, query.nested, Some(&scalars)).await?;
+        let (nested, failed) = process_nested(tx, query.nested, Some(&scalars), policy).await?;
 
         Ok(QueryResult::RecordSelection(RecordSelection {
             name: query.name,
+            alias: query.alias,
             fields: query.selection_order,
             query_arguments: query.args,
             model_id,
             scalars,
             nested,
+            failed,
         }))
     };
 
@@ -96,21 +109,18 @@ fn read_related<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     query: RelatedRecordsQuery,
     parent_result: Option<&'a ManyRecords>,
+    policy: FailurePolicy,
 ) -> BoxFuture<'a, InterpretationResult<QueryResult>> {
     let fut = async move {
-        // The query construction must guarantee that the parent result
-        // contains the selected fields necessary to satisfy the relation query ("relation IDs").
-        // There are 2 options:
-        // - The query already has IDs set - use those.
-        // - The IDs need to be extracted from the parent result.
-        let relation_parent_ids = match query.relation_parent_ids {
-            Some(ids) => ids,
-            None => {
-                let relation_id = query.parent_field.linking_fields();
-                parent_result
-                    .expect("No parent results present in the query graph for reading related records.")
-                    .identifiers(&relation_id)?
-            }
+        let parent_identifier = query.parent_field.model().primary_identifier();
+        let parent_link_fields = query.parent_field.linking_fields();
+
+        // The fields of the parent that the relation columns hold: The primary ID if the relation is inlined in the
+        // parent or a many-to-many relation, otherwise the fields the foreign key of the related records references.
+        let relation_link_fields = if query.parent_field.relation_is_inlined_in_parent() {
+            parent_identifier.clone()
+        } else {
+            parent_link_fields.clone()
         };
 
         let relation = query.parent_field.relation();
@@ -120,6 +130,35 @@ fn read_related<'a, 'b>(
         let use_prisma_level_join =
             !relation.is_many_to_many() && parent_result.is_some() && !query.args.is_with_pagination();
 
+        // The query construction must guarantee that the parent result
+        // contains the selected fields necessary to satisfy the relation query ("relation IDs").
+        // There are 2 options:
+        // - The query already has IDs set - use those. Those are primary IDs, so if the relation references other
+        //   fields of the parent, the parent records are read again to get the values of those.
+        // - The IDs need to be extracted from the parent result.
+        let resolved_parents = match query.relation_parent_ids {
+            Some(ref ids) if relation_link_fields != parent_identifier => {
+                Some(read_parent_links(tx, &query.parent_field, ids, &relation_link_fields).await?)
+            }
+            _ => None,
+        };
+
+        let relation_parent_ids = match (query.relation_parent_ids, resolved_parents.as_ref()) {
+            (Some(_), Some(parents)) => parents.identifiers(&relation_link_fields)?,
+            (Some(ids), None) => ids,
+            (None, _) => {
+                let relation_id = if use_prisma_level_join {
+                    &parent_link_fields
+                } else {
+                    &relation_link_fields
+                };
+
+                parent_result
+                    .expect("No parent results present in the query graph for reading related records.")
+                    .identifiers(relation_id)?
+            }
+        };
+
         let mut scalars = if !use_prisma_level_join {
             tx.get_related_records(
                 &query.parent_field,
@@ -147,7 +186,6 @@ fn read_related<'a, 'b>(
                     .into_iter()
                     .map(|id| {
                         let filters = id
-                            .pairs
                             .into_iter()
                             .zip(other_fields.iter())
                             .map(|((_, value), other_field)| other_field.equals(value))
@@ -183,91 +221,132 @@ fn read_related<'a, 'b>(
         };
 
         if use_prisma_level_join {
-            // Write parent IDs into the retrieved records
-            if parent_result.is_some() && query.parent_field.is_inlined_on_enclosing_model() {
-                let parent_identifier = query.parent_field.model().primary_identifier();
-                let field_names = scalars.field_names.clone();
-
-                let parent_link_fields = query.parent_field.linking_fields();
-                let child_link_fields = query.parent_field.related_field().linking_fields();
-
-                let parent_result =
-                    parent_result.expect("No parent results present in the query graph for reading related records.");
-
-                let parent_fields = &parent_result.field_names;
-                let mut additional_records = vec![];
-
-                for mut record in scalars.records.iter_mut() {
-                    let child_link: RecordIdentifier = record.identifier(&field_names, &child_link_fields)?;
-
-                    let mut parent_records = parent_result.records.iter().filter(|record| {
-                        let parent_link = record.identifier(parent_fields, &parent_link_fields).unwrap();
-
-                        child_link.values().eq(parent_link.values())
-                    });
-
-                    let parent_id = parent_records
-                        .next()
-                        .unwrap()
-                        .identifier(parent_fields, &parent_identifier)
-                        .unwrap();
-
-                    record.parent_id = Some(parent_id);
-
-                    for p_record in parent_records {
-                        let parent_id = p_record.identifier(parent_fields, &parent_identifier).unwrap();
-                        let mut record = record.clone();
-
-                        record.parent_id = Some(parent_id);
-                        additional_records.push(record);
-                    }
-                }
+            // Write parent IDs into the retrieved records, matched by the linking fields of both sides.
+            let parent_result =
+                parent_result.expect("No parent results present in the query graph for reading related records.");
+            let child_link_fields = query.parent_field.related_field().linking_fields();
+
+            let links = scalars
+                .records
+                .iter()
+                .map(|record| record.identifier(&scalars.field_names, &child_link_fields))
+                .collect::<prisma_models::Result<Vec<_>>>()?;
+
+            set_parent_ids(
+                &mut scalars,
+                links,
+                parent_result,
+                &parent_link_fields,
+                &parent_identifier,
+            )?;
+        } else if relation_link_fields != parent_identifier {
+            // The connector returns the values of the referenced fields as parent IDs, which are mapped to the
+            // primary IDs of the parents.
+            if let Some(parents) = resolved_parents.as_ref().or(parent_result) {
+                let links = scalars
+                    .records
+                    .iter()
+                    .map(|record| record.parent_id.clone().unwrap_or_default())
+                    .collect();
 
-                scalars.records.extend(additional_records);
-            } else if parent_result.is_some() && query.parent_field.related_field().is_inlined_on_enclosing_model() {
-                let parent_identifier = query.parent_field.model().primary_identifier();
-                let field_names = scalars.field_names.clone();
-                let child_link_fields = query.parent_field.related_field().linking_fields();
-
-                for record in scalars.records.iter_mut() {
-                    let parent_id: RecordIdentifier = record.identifier(&field_names, &child_link_fields)?;
-                    let parent_id = parent_id
-                        .into_iter()
-                        .zip(parent_identifier.data_source_fields())
-                        .map(|((_, value), field)| (field, value))
-                        .collect::<Vec<_>>()
-                        .into();
-
-                    record.parent_id = Some(parent_id);
-                }
-            } else if query.parent_field.relation().is_many_to_many() {
-                // nothing to do for many to many. parent ids are already present
-            } else {
-                panic!(format!(
-                    "parent result: {:?}, relation: {:?}",
-                    &parent_result,
-                    &query.parent_field.relation()
-                ));
+                set_parent_ids(&mut scalars, links, parents, &relation_link_fields, &parent_identifier)?;
             }
         }
 
         let model = query.parent_field.related_model();
         let model_id = model.primary_identifier();
-        let nested: Vec<QueryResult> = process_nested(tx, query.nested, Some(&scalars)).await?;
+        let (nested, failed) = process_nested(tx, query.nested, Some(&scalars), policy).await?;
 
         Ok(QueryResult::RecordSelection(RecordSelection {
             name: query.name,
+            alias: query.alias,
             fields: query.selection_order,
             query_arguments: query.args,
             model_id,
             scalars,
             nested,
+            failed,
         }))
     };
 
     fut.boxed()
 }
 
+/// Reads the parent records with the given primary IDs, with the given linking fields of the parent.
+async fn read_parent_links<'a, 'b>(
+    tx: &'a ConnectionLike<'a, 'b>,
+    parent_field: &RelationFieldRef,
+    parent_ids: &[RecordIdentifier],
+    link_fields: &ModelIdentifier,
+) -> InterpretationResult<ManyRecords> {
+    let parent_identifier = parent_field.model().primary_identifier();
+
+    let filters = parent_ids
+        .iter()
+        .map(|id| {
+            let filters = id
+                .pairs()
+                .iter()
+                .map(|(field, value)| field.equals(value.clone()))
+                .collect();
+
+            Filter::and(filters)
+        })
+        .collect();
+
+    let selected_fields: SelectedFields = parent_identifier
+        .into_iter()
+        .chain(link_fields.clone().into_iter())
+        .collect::<Vec<_>>()
+        .into();
+
+    let parents = tx
+        .get_many_records(&parent_field.model(), Filter::or(filters).into(), &selected_fields)
+        .await?;
+
+    Ok(parents)
+}
+
+/// Sets the parent IDs of the related records to the primary IDs of the parent records they are linked to.
+/// `links` are the values of the parent's `parent_link_fields` each record is linked to. Records linked to
+/// more than one parent are duplicated for each of them.
+fn set_parent_ids(
+    scalars: &mut ManyRecords,
+    links: Vec<RecordIdentifier>,
+    parent_result: &ManyRecords,
+    parent_link_fields: &ModelIdentifier,
+    parent_identifier: &ModelIdentifier,
+) -> InterpretationResult<()> {
+    let parent_fields = &parent_result.field_names;
+    let mut additional_records = vec![];
+
+    for (record, link) in scalars.records.iter_mut().zip(links) {
+        let mut parent_ids = vec![];
+
+        for parent in parent_result.records.iter() {
+            let parent_link = parent.identifier(parent_fields, parent_link_fields)?;
+
+            if link.values().eq(parent_link.values()) {
+                parent_ids.push(parent.identifier(parent_fields, parent_identifier)?);
+            }
+        }
+
+        let mut parent_ids = parent_ids.into_iter();
+        record.parent_id = parent_ids.next();
+
+        for parent_id in parent_ids {
+            let mut record = record.clone();
+
+            record.parent_id = Some(parent_id);
+            additional_records.push(record);
+        }
+    }
+
+    scalars.records.extend(additional_records);
+
+    Ok(())
+}
+
 async fn aggregate<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     query: AggregateRecordsQuery,
@@ -276,21 +355,378 @@ async fn aggregate<'a, 'b>(
     Ok(QueryResult::Count(result))
 }
 
+/// Executes the nested reads of a selection, retrying reads failing with transient errors according to the policy.
+/// With partial failures, the reads that still failed are returned separately from the results.
 fn process_nested<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     nested: Vec<ReadQuery>,
     parent_result: Option<&'a ManyRecords>,
-) -> BoxFuture<'a, InterpretationResult<Vec<QueryResult>>> {
+    policy: FailurePolicy,
+) -> BoxFuture<'a, InterpretationResult<(Vec<QueryResult>, Vec<FailedRead>)>> {
     let fut = async move {
         let mut results = Vec::with_capacity(nested.len());
+        let mut failed = Vec::new();
 
         for query in nested {
-            let result = execute(tx, query, parent_result).await?;
-            results.push(result);
+            let key = query.response_key().to_owned();
+            let mut retries = policy.nested_read_retries;
+
+            let result = loop {
+                match execute(tx, query.clone(), parent_result, policy).await {
+                    Err(err) if retries > 0 && err.is_transient() => {
+                        debug!("Retrying nested read '{}' after error: {}", key, err);
+                        retries -= 1;
+                    }
+                    result => break result,
+                }
+            };
+
+            match (result, policy.nested_reads) {
+                (Ok(result), _) => results.push(result),
+                (Err(err), NestedReadFailure::Partial) => failed.push(FailedRead {
+                    key,
+                    error: ResponseError::from(CoreError::from(err)),
+                }),
+                (Err(err), NestedReadFailure::Abort) => return Err(err),
+            }
         }
 
-        Ok(results)
+        Ok((results, failed))
     };
 
     fut.boxed()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        response_ir::{IrSerializer, Response},
+        schema::QuerySchemaRef,
+        BuildMode, ExpressionResult, QuerySchemaBuilder,
+    };
+    use connector::{
+        error::{ConnectorError, ErrorKind},
+        Connection, ConnectorCapabilities, IdempotencyRecord, Routine, RoutineResult, Transaction, WriteArgs,
+        WriteOperations, IO,
+    };
+    use futures::executor::block_on;
+    use prisma_models::{DatamodelConverter, InternalDataModelRef, ModelRef, PrismaValue, Record, SingleRecord};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    const DATAMODEL: &str = r#"
+        model User {
+            id    String @id
+            posts Post[]
+        }
+
+        model Post {
+            id     String @id
+            author User
+        }
+    "#;
+
+    /// A connection returning a user with a post, where the reads of posts fail with the error for the first
+    /// `failures` times.
+    struct FlakyConnection {
+        failures: usize,
+        error: fn() -> ErrorKind,
+        post_reads: AtomicUsize,
+    }
+
+    impl FlakyConnection {
+        fn new(failures: usize, error: fn() -> ErrorKind) -> Self {
+            Self {
+                failures,
+                error,
+                post_reads: AtomicUsize::new(0),
+            }
+        }
+
+        fn post_reads(&self) -> usize {
+            self.post_reads.load(Ordering::SeqCst)
+        }
+    }
+
+    fn connection_error() -> ErrorKind {
+        ErrorKind::ConnectionError(failure::err_msg("Connection reset by peer"))
+    }
+
+    fn record_does_not_exist() -> ErrorKind {
+        ErrorKind::RecordDoesNotExist
+    }
+
+    fn string(value: &str) -> PrismaValue {
+        PrismaValue::String(value.to_owned())
+    }
+
+    impl ReadOperations for FlakyConnection {
+        fn get_single_record<'a>(
+            &'a self,
+            _model: &'a ModelRef,
+            _filter: &'a Filter,
+            _selected_fields: &'a SelectedFields,
+        ) -> IO<'a, Option<SingleRecord>> {
+            unimplemented!()
+        }
+
+        fn get_many_records<'a>(
+            &'a self,
+            model: &'a ModelRef,
+            _query_arguments: QueryArguments,
+            _selected_fields: &'a SelectedFields,
+        ) -> IO<'a, ManyRecords> {
+            let result = match model.name.as_str() {
+                "User" => Ok(ManyRecords {
+                    records: vec![Record::new(vec![string("a")])],
+                    field_names: vec!["id".to_owned()],
+                }),
+                _ if self.post_reads.fetch_add(1, Ordering::SeqCst) < self.failures => {
+                    Err(ConnectorError::from_kind((self.error)()))
+                }
+                _ => Ok(ManyRecords {
+                    records: vec![Record::new(vec![string("a1"), string("a")])],
+                    field_names: vec!["id".to_owned(), "author".to_owned()],
+                }),
+            };
+
+            IO::new(async move { result })
+        }
+
+        fn get_related_records<'a>(
+            &'a self,
+            _from_field: &'a RelationFieldRef,
+            _from_record_ids: &'a [RecordIdentifier],
+            _query_arguments: QueryArguments,
+            _selected_fields: &'a SelectedFields,
+        ) -> IO<'a, ManyRecords> {
+            unimplemented!()
+        }
+
+        fn count_by_model<'a>(&'a self, _model: &'a ModelRef, _query_arguments: QueryArguments) -> IO<'a, usize> {
+            unimplemented!()
+        }
+
+        fn list_routines<'a>(&'a self) -> IO<'a, Vec<Routine>> {
+            unimplemented!()
+        }
+
+        fn get_idempotency_record<'a>(&'a self, _key: &'a str) -> IO<'a, Option<IdempotencyRecord>> {
+            unimplemented!()
+        }
+    }
+
+    impl WriteOperations for FlakyConnection {
+        fn create_record<'a>(&'a self, _model: &'a ModelRef, _args: WriteArgs) -> IO<RecordIdentifier> {
+            unimplemented!()
+        }
+
+        fn update_records<'a>(
+            &'a self,
+            _model: &'a ModelRef,
+            _where_: Filter,
+            _args: WriteArgs,
+        ) -> IO<Vec<RecordIdentifier>> {
+            unimplemented!()
+        }
+
+        fn delete_records<'a>(&'a self, _model: &'a ModelRef, _where_: Filter) -> IO<usize> {
+            unimplemented!()
+        }
+
+        fn connect<'a>(
+            &'a self,
+            _field: &'a RelationFieldRef,
+            _parent_id: &'a RecordIdentifier,
+            _child_ids: &'a [RecordIdentifier],
+        ) -> IO<()> {
+            unimplemented!()
+        }
+
+        fn disconnect<'a>(
+            &'a self,
+            _field: &'a RelationFieldRef,
+            _parent_id: &'a RecordIdentifier,
+            _child_ids: &'a [RecordIdentifier],
+        ) -> IO<()> {
+            unimplemented!()
+        }
+
+        fn disconnect_all<'a>(&'a self, _field: &'a RelationFieldRef, _parent_ids: &'a [RecordIdentifier]) -> IO<()> {
+            unimplemented!()
+        }
+
+        fn execute_raw<'a>(&'a self, _query: String, _parameters: Vec<PrismaValue>) -> IO<serde_json::Value> {
+            unimplemented!()
+        }
+
+        fn call_routine<'a>(&'a self, _routine: &'a Routine, _arguments: Vec<PrismaValue>) -> IO<RoutineResult> {
+            unimplemented!()
+        }
+
+        fn store_idempotency_record<'a>(&'a self, _record: IdempotencyRecord) -> IO<()> {
+            unimplemented!()
+        }
+    }
+
+    impl Connection for FlakyConnection {
+        fn start_transaction<'a>(&'a self) -> IO<Box<dyn Transaction + 'a>> {
+            unimplemented!()
+        }
+    }
+
+    fn internal_datamodel() -> InternalDataModelRef {
+        let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+        DatamodelConverter::convert(&datamodel).build("db".to_owned())
+    }
+
+    /// All users with the ids of their posts.
+    fn users_with_posts(internal_datamodel: &InternalDataModelRef) -> ReadQuery {
+        let user = internal_datamodel.find_model("User").unwrap();
+        let posts = user.fields().find_from_relation_fields("posts").unwrap();
+
+        ReadQuery::ManyRecordsQuery(ManyRecordsQuery {
+            name: "findManyUser".to_owned(),
+            alias: None,
+            model: user,
+            args: QueryArguments::default(),
+            selected_fields: SelectedFields::default(),
+            nested: vec![ReadQuery::RelatedRecordsQuery(RelatedRecordsQuery {
+                name: "posts".to_owned(),
+                alias: None,
+                parent_field: posts,
+                relation_parent_ids: None,
+                args: QueryArguments::default(),
+                selected_fields: SelectedFields::default(),
+                nested: vec![],
+                selection_order: vec![("id".to_owned(), "id".to_owned())],
+            })],
+            selection_order: vec![
+                ("id".to_owned(), "id".to_owned()),
+                ("posts".to_owned(), "posts".to_owned()),
+            ],
+        })
+    }
+
+    fn policy(nested_read_retries: usize, nested_reads: NestedReadFailure) -> FailurePolicy {
+        FailurePolicy {
+            nested_read_retries,
+            nested_reads,
+        }
+    }
+
+    fn read(conn: &FlakyConnection, query: ReadQuery, policy: FailurePolicy) -> InterpretationResult<RecordSelection> {
+        let result = block_on(execute(&ConnectionLike::Connection(conn), query, None, policy))?;
+
+        match result {
+            QueryResult::RecordSelection(rs) => Ok(rs),
+            result => panic!("Expected a record selection, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn transient_errors_of_nested_reads_are_retried() {
+        let internal_datamodel = internal_datamodel();
+        let conn = FlakyConnection::new(2, connection_error);
+
+        let users = read(
+            &conn,
+            users_with_posts(&internal_datamodel),
+            policy(2, NestedReadFailure::Abort),
+        )
+        .unwrap();
+
+        assert_eq!(conn.post_reads(), 3);
+        assert_eq!(users.nested.len(), 1);
+        assert!(users.failed.is_empty());
+    }
+
+    #[test]
+    fn nested_reads_fail_after_the_last_retry() {
+        let internal_datamodel = internal_datamodel();
+        let conn = FlakyConnection::new(3, connection_error);
+
+        let result = read(
+            &conn,
+            users_with_posts(&internal_datamodel),
+            policy(2, NestedReadFailure::Abort),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(conn.post_reads(), 3);
+    }
+
+    #[test]
+    fn deterministic_errors_of_nested_reads_are_not_retried() {
+        let internal_datamodel = internal_datamodel();
+        let conn = FlakyConnection::new(1, record_does_not_exist);
+
+        let result = read(
+            &conn,
+            users_with_posts(&internal_datamodel),
+            policy(2, NestedReadFailure::Abort),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(conn.post_reads(), 1);
+    }
+
+    #[test]
+    fn partial_failures_return_the_failed_reads_with_the_results() {
+        let internal_datamodel = internal_datamodel();
+        let conn = FlakyConnection::new(1, record_does_not_exist);
+
+        let users = read(
+            &conn,
+            users_with_posts(&internal_datamodel),
+            policy(0, NestedReadFailure::Partial),
+        )
+        .unwrap();
+
+        assert!(users.nested.is_empty());
+        assert_eq!(users.scalars.records.len(), 1);
+        assert_eq!(users.failed.len(), 1);
+        assert_eq!(users.failed[0].key, "posts");
+    }
+
+    #[test]
+    fn failed_list_relations_are_serialized_as_empty_lists() {
+        let internal_datamodel = internal_datamodel();
+        let capabilities = ConnectorCapabilities::empty();
+        let query_schema: QuerySchemaRef =
+            Arc::new(QuerySchemaBuilder::new(&internal_datamodel, &capabilities, BuildMode::Modern, false).build());
+        let conn = FlakyConnection::new(1, record_does_not_exist);
+
+        let users = read(
+            &conn,
+            users_with_posts(&internal_datamodel),
+            policy(0, NestedReadFailure::Partial),
+        )
+        .unwrap();
+
+        let serializer = IrSerializer {
+            key: "findManyUser".to_owned(),
+            output_type: query_schema
+                .find_query_field("findManyUser")
+                .unwrap()
+                .field_type
+                .clone(),
+        };
+
+        match serializer.serialize(ExpressionResult::Query(QueryResult::RecordSelection(users))) {
+            Response::PartialData(key, item, errors) => {
+                assert_eq!(key, "findManyUser");
+                assert_eq!(
+                    serde_json::to_value(&item).unwrap(),
+                    serde_json::json!([{ "id": "a", "posts": [] }])
+                );
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path(), &["findManyUser".to_owned(), "posts".to_owned()][..]);
+            }
+            response => panic!("Expected partial data, got {:?}", response),
+        }
+    }
+}
@@ -1,23 +1,29 @@
 use crate::{
     interpreter::{InterpretationResult, InterpreterError},
     query_ast::*,
-    QueryResult,
+    QueryResult, RecordEvent, RecordEventKind, RecordedEvents,
 };
-use connector::{ConnectionLike, Filter, WriteOperations};
+use chrono::Utc;
+use connector::{ConnectionLike, Filter, ScalarCompare, WriteArgs, WriteOperations};
+use prisma_models::{ModelRef, ScalarFieldRef};
 use prisma_value::PrismaValue;
+use std::sync::Arc;
 
 pub async fn execute<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     write_query: WriteQuery,
+    events: &RecordedEvents,
 ) -> InterpretationResult<QueryResult> {
     match write_query {
-        WriteQuery::CreateRecord(q) => create_one(tx, q).await,
-        WriteQuery::UpdateRecord(q) => update_one(tx, q).await,
-        WriteQuery::DeleteRecord(q) => delete_one(tx, q).await,
-        WriteQuery::UpdateManyRecords(q) => update_many(tx, q).await,
-        WriteQuery::DeleteManyRecords(q) => delete_many(tx, q).await,
+        WriteQuery::CreateRecord(q) => create_one(tx, q, events).await,
+        WriteQuery::UpdateRecord(q) => update_one(tx, q, events).await,
+        WriteQuery::DeleteRecord(q) => delete_one(tx, q, events).await,
+        WriteQuery::UpdateManyRecords(q) => update_many(tx, q, events).await,
+        WriteQuery::DeleteManyRecords(q) => delete_many(tx, q, events).await,
         WriteQuery::ConnectRecords(q) => connect(tx, q).await,
         WriteQuery::DisconnectRecords(q) => disconnect(tx, q).await,
+        WriteQuery::DisconnectAllRecords(q) => disconnect_all(tx, q).await,
+        WriteQuery::CallRoutine(q) => call_routine(tx, q).await,
         WriteQuery::Raw { query, parameters } => execute_raw(tx, query, parameters).await,
     }
 }
@@ -31,19 +37,38 @@ async fn execute_raw<'a, 'b>(
     Ok(QueryResult::Json(res))
 }
 
-async fn create_one<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: CreateRecord) -> InterpretationResult<QueryResult> {
+async fn call_routine<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: CallRoutine) -> InterpretationResult<QueryResult> {
+    let res = tx.call_routine(&q.routine, q.arguments).await?;
+    Ok(QueryResult::Routine(res))
+}
+
+async fn create_one<'a, 'b>(
+    tx: &'a ConnectionLike<'a, 'b>,
+    q: CreateRecord,
+    events: &RecordedEvents,
+) -> InterpretationResult<QueryResult> {
     let res = tx.create_record(&q.model, q.args).await?;
+    events.push(RecordEvent::new(&q.model, RecordEventKind::Created, &[res.clone()]));
 
     Ok(QueryResult::Id(Some(res)))
 }
 
-async fn update_one<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: UpdateRecord) -> InterpretationResult<QueryResult> {
+async fn update_one<'a, 'b>(
+    tx: &'a ConnectionLike<'a, 'b>,
+    q: UpdateRecord,
+    events: &RecordedEvents,
+) -> InterpretationResult<QueryResult> {
     let mut res = tx.update_records(&q.model, Filter::from(q.where_), q.args).await?;
+    events.push(RecordEvent::new(&q.model, RecordEventKind::Updated, &res));
 
     Ok(QueryResult::Id(res.pop()))
 }
 
-async fn delete_one<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: DeleteRecord) -> InterpretationResult<QueryResult> {
+async fn delete_one<'a, 'b>(
+    tx: &'a ConnectionLike<'a, 'b>,
+    q: DeleteRecord,
+    events: &RecordedEvents,
+) -> InterpretationResult<QueryResult> {
     // We need to ensure that we have a record finder, else we delete everything (conversion to empty filter).
     let finder = match q.where_ {
         Some(f) => Ok(f),
@@ -52,7 +77,12 @@ async fn delete_one<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: DeleteRecord) ->
         )),
     }?;
 
+    if let Some(field) = q.model.soft_delete_field() {
+        return soft_delete(tx, &q.model, field, Filter::from(finder), events).await;
+    }
+
     let res = tx.delete_records(&q.model, Filter::from(finder)).await?;
+    events.push(RecordEvent::deleted(&q.model, res));
 
     Ok(QueryResult::Count(res))
 }
@@ -60,8 +90,10 @@ async fn delete_one<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: DeleteRecord) ->
 async fn update_many<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     q: UpdateManyRecords,
+    events: &RecordedEvents,
 ) -> InterpretationResult<QueryResult> {
     let res = tx.update_records(&q.model, q.filter, q.args).await?;
+    events.push(RecordEvent::new(&q.model, RecordEventKind::Updated, &res));
 
     Ok(QueryResult::Count(res.len()))
 }
@@ -69,12 +101,38 @@ async fn update_many<'a, 'b>(
 async fn delete_many<'a, 'b>(
     tx: &'a ConnectionLike<'a, 'b>,
     q: DeleteManyRecords,
+    events: &RecordedEvents,
 ) -> InterpretationResult<QueryResult> {
+    if let Some(field) = q.model.soft_delete_field() {
+        return soft_delete(tx, &q.model, field, q.filter, events).await;
+    }
+
     let res = tx.delete_records(&q.model, q.filter).await?;
+    events.push(RecordEvent::deleted(&q.model, res));
 
     Ok(QueryResult::Count(res))
 }
 
+/// Deletes of models with `@@softDelete` set the soft delete field of the records instead of removing them.
+/// Records that are already deleted are left alone and not counted.
+async fn soft_delete<'a, 'b>(
+    tx: &'a ConnectionLike<'a, 'b>,
+    model: &ModelRef,
+    field: ScalarFieldRef,
+    filter: Filter,
+    events: &RecordedEvents,
+) -> InterpretationResult<QueryResult> {
+    let mut args = WriteArgs::new();
+    args.insert(field.db_name().clone(), PrismaValue::DateTime(Utc::now()));
+    args.update_datetimes(Arc::clone(model));
+
+    let filter = Filter::and(vec![filter, field.equals(PrismaValue::Null)]);
+    let res = tx.update_records(model, filter, args).await?;
+    events.push(RecordEvent::deleted(model, res.len()));
+
+    Ok(QueryResult::Count(res.len()))
+}
+
 async fn connect<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: ConnectRecords) -> InterpretationResult<QueryResult> {
     tx.connect(
         &q.relation_field,
@@ -96,3 +154,12 @@ async fn disconnect<'a, 'b>(tx: &'a ConnectionLike<'a, 'b>, q: DisconnectRecords
 
     Ok(QueryResult::Unit)
 }
+
+async fn disconnect_all<'a, 'b>(
+    tx: &'a ConnectionLike<'a, 'b>,
+    q: DisconnectAllRecords,
+) -> InterpretationResult<QueryResult> {
+    tx.disconnect_all(&q.relation_field, &q.parent_ids).await?;
+
+    Ok(QueryResult::Unit)
+}
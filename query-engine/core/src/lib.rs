@@ -9,6 +9,7 @@ extern crate debug_stub_derive;
 #[macro_use]
 extern crate lazy_static;
 
+pub mod access_policy;
 pub mod error;
 pub mod executor;
 pub mod interpreter;
@@ -21,6 +22,7 @@ pub mod result_ast;
 pub mod schema;
 pub mod schema_builder;
 
+pub use access_policy::*;
 pub use error::*;
 pub use executor::*;
 pub use interpreter::*;
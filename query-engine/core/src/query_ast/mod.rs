@@ -20,6 +20,14 @@ impl Query {
             Self::Write(wq) => wq.returns(ident),
         }
     }
+
+    /// The datasource the query is executed on, `None` for the first datasource.
+    pub fn datasource(&self) -> Option<String> {
+        match self {
+            Self::Read(rq) => rq.datasource(),
+            Self::Write(wq) => wq.datasource(),
+        }
+    }
 }
 
 impl FilteredQuery for Query {
@@ -22,6 +22,18 @@ impl ReadQuery {
         }
     }
 
+    /// The key of the query in the response, its alias if present.
+    pub fn response_key(&self) -> &str {
+        let alias = match self {
+            ReadQuery::RecordQuery(x) => &x.alias,
+            ReadQuery::ManyRecordsQuery(x) => &x.alias,
+            ReadQuery::RelatedRecordsQuery(x) => &x.alias,
+            ReadQuery::AggregateRecordsQuery(x) => &x.alias,
+        };
+
+        alias.as_deref().unwrap_or_else(|| self.name())
+    }
+
     pub fn returns(&self, ident: &ModelIdentifier) -> bool {
         let db_names = ident.db_names();
 
@@ -32,6 +44,16 @@ impl ReadQuery {
             ReadQuery::AggregateRecordsQuery(_x) => false,
         }
     }
+
+    /// The datasource of the model read, `None` for the first datasource.
+    pub fn datasource(&self) -> Option<String> {
+        match self {
+            ReadQuery::RecordQuery(x) => x.model.datasource.clone(),
+            ReadQuery::ManyRecordsQuery(x) => x.model.datasource.clone(),
+            ReadQuery::RelatedRecordsQuery(x) => x.parent_field.related_model().datasource.clone(),
+            ReadQuery::AggregateRecordsQuery(x) => x.model.datasource.clone(),
+        }
+    }
 }
 
 impl FilteredQuery for ReadQuery {
@@ -81,7 +103,7 @@ pub struct RecordQuery {
     pub filter: Option<Filter>,
     pub selected_fields: SelectedFields,
     pub nested: Vec<ReadQuery>,
-    pub selection_order: Vec<String>,
+    pub selection_order: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
@@ -92,7 +114,7 @@ pub struct ManyRecordsQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<ReadQuery>,
-    pub selection_order: Vec<String>,
+    pub selection_order: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
@@ -104,7 +126,7 @@ pub struct RelatedRecordsQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<ReadQuery>,
-    pub selection_order: Vec<String>,
+    pub selection_order: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
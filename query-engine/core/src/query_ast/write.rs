@@ -1,6 +1,6 @@
 //! Write query AST
 use super::FilteredQuery;
-use connector::{filter::Filter, WriteArgs};
+use connector::{filter::Filter, Routine, WriteArgs};
 use prisma_models::prelude::*;
 use std::sync::Arc;
 
@@ -13,6 +13,8 @@ pub enum WriteQuery {
     DeleteManyRecords(DeleteManyRecords),
     ConnectRecords(ConnectRecords),
     DisconnectRecords(DisconnectRecords),
+    DisconnectAllRecords(DisconnectAllRecords),
+    CallRoutine(CallRoutine),
     Raw {
         query: String,
         parameters: Vec<PrismaValue>,
@@ -62,6 +64,8 @@ impl WriteQuery {
             Self::DeleteManyRecords(_) => false,
             Self::ConnectRecords(_) => false,
             Self::DisconnectRecords(_) => false,
+            Self::DisconnectAllRecords(_) => false,
+            Self::CallRoutine(_) => unreachable!("Routine calls are top level mutations, no query depends on them."),
             Self::Raw {
                 query: _,
                 parameters: _,
@@ -69,6 +73,16 @@ impl WriteQuery {
         }
     }
 
+    /// The datasource of the model written, `None` for the first datasource.
+    /// Raw queries and routines always run on the first datasource.
+    pub fn datasource(&self) -> Option<String> {
+        match self {
+            Self::CallRoutine(_) => None,
+            Self::Raw { .. } => None,
+            _ => self.model().datasource.clone(),
+        }
+    }
+
     fn model(&self) -> ModelRef {
         match self {
             Self::CreateRecord(q) => Arc::clone(&q.model),
@@ -78,6 +92,8 @@ impl WriteQuery {
             Self::DeleteManyRecords(q) => Arc::clone(&q.model),
             Self::ConnectRecords(q) => q.relation_field.model(),
             Self::DisconnectRecords(q) => q.relation_field.model(),
+            Self::DisconnectAllRecords(q) => q.relation_field.model(),
+            Self::CallRoutine(_) => unreachable!("Routine calls have no model, their datasource is the first one."),
             Self::Raw {
                 query: _,
                 parameters: _,
@@ -122,6 +138,8 @@ impl std::fmt::Display for WriteQuery {
             Self::DeleteManyRecords(q) => write!(f, "DeleteManyRecords: {}", q.model.name),
             Self::ConnectRecords(_) => write!(f, "ConnectRecords"),
             Self::DisconnectRecords(_) => write!(f, "DisconnectRecords"),
+            Self::DisconnectAllRecords(q) => write!(f, "DisconnectAllRecords: {}", q.relation_field.name),
+            Self::CallRoutine(q) => write!(f, "CallRoutine: {} ({:?})", q.routine.name, q.arguments),
             Self::Raw { query, parameters } => write!(f, "Raw: {} ({:?})", query, parameters),
         }
     }
@@ -173,6 +191,20 @@ pub struct DisconnectRecords {
     pub relation_field: RelationFieldRef,
 }
 
+/// Removes all connections of the parent records in a relation table, e.g. before the parents are deleted.
+#[derive(Debug, Clone)]
+pub struct DisconnectAllRecords {
+    pub parent_ids: Vec<RecordIdentifier>,
+    pub relation_field: RelationFieldRef,
+}
+
+#[derive(Debug, Clone)]
+pub struct CallRoutine {
+    pub routine: Routine,
+    /// One argument per parameter of the routine, in the order of its signature.
+    pub arguments: Vec<PrismaValue>,
+}
+
 impl FilteredQuery for UpdateRecord {
     fn get_filter(&mut self) -> Option<&mut Filter> {
         Some(&mut self.where_)
@@ -0,0 +1,126 @@
+use super::{Operation, QueryDocument, QueryValue, Selection};
+use crate::schema::{ObjectTypeStrongRef, QuerySchema};
+use std::collections::BTreeMap;
+
+/// Prefix of the aliases the identifier fields of the records along the path of deferred selections are selected
+/// under, both in the document and in the deferred read. The records of the two reads are matched by them, they are
+/// removed from the data before it is delivered.
+pub const DEFERRED_ID_PREFIX: &str = "__deferred_id_";
+
+/// Selections of a read that are delivered after the rest of its data, e.g. the fields of a GraphQL fragment marked
+/// with `@defer`. They are left out of the document and read with an operation of their own.
+#[derive(Debug, Clone)]
+pub struct DeferredSelection {
+    /// Response keys of the selections the deferred selections are nested in, starting with the operation.
+    pub path: Vec<String>,
+    /// Identifies the deferred selections to the client.
+    pub label: Option<String>,
+    pub selections: Vec<Selection>,
+}
+
+impl DeferredSelection {
+    /// Selects the identifier fields of the records along the path in the read operation of the document, see
+    /// `DEFERRED_ID_PREFIX`.
+    ///
+    /// `None` if the path doesn't lead through records of models of the schema.
+    pub fn select_ids(&self, query_doc: &mut QueryDocument, query_schema: &QuerySchema) -> Option<()> {
+        let (operation_key, path) = self.path.split_first()?;
+        let selection = query_doc.operations.iter_mut().find_map(|operation| match operation {
+            Operation::Read(selection) if selection.response_key() == operation_key => Some(selection),
+            _ => None,
+        })?;
+
+        let object_type = query_schema
+            .find_query_field(&selection.name)?
+            .field_type
+            .as_object_type()?;
+
+        select_ids_along(selection, object_type, path)
+    }
+
+    /// The read of the deferred selections: the read operation of the document the path starts with, with the same
+    /// arguments along the path, but only selecting the path, the identifiers along it and the deferred selections.
+    /// Records changed in between are told apart by their identifiers, see `restrict_to_records`.
+    ///
+    /// `None` if the path doesn't lead through a read operation of the document.
+    pub fn operation(&self, query_doc: &QueryDocument) -> Option<Operation> {
+        let (operation_key, path) = self.path.split_first()?;
+        let selection = query_doc.operations.iter().find_map(|operation| match operation {
+            Operation::Read(selection) if selection.response_key() == operation_key => Some(selection),
+            _ => None,
+        })?;
+
+        self.select_path(selection, path).map(Operation::Read)
+    }
+
+    fn select_path(&self, selection: &Selection, path: &[String]) -> Option<Selection> {
+        let mut nested_selections: Vec<Selection> = selection
+            .nested_selections
+            .iter()
+            .filter(|s| s.response_key().starts_with(DEFERRED_ID_PREFIX))
+            .cloned()
+            .collect();
+
+        match path.split_first() {
+            Some((key, path)) => {
+                let nested = selection.nested_selections.iter().find(|s| s.response_key() == key)?;
+                nested_selections.push(self.select_path(nested, path)?);
+            }
+            None => nested_selections.extend(self.selections.iter().cloned()),
+        };
+
+        Some(Selection {
+            name: selection.name.clone(),
+            alias: selection.alias.clone(),
+            arguments: selection.arguments.clone(),
+            nested_selections,
+        })
+    }
+}
+
+/// Restricts the deferred read of a list to the records the operation of the document returned, by the values of
+/// their identifier fields. The other arguments are dropped, pagination would apply to the restricted records.
+pub fn restrict_to_records(operation: Operation, records: Vec<BTreeMap<String, QueryValue>>) -> Operation {
+    let records = records.into_iter().map(QueryValue::Object).collect();
+    let mut filter = BTreeMap::new();
+    filter.insert("OR".to_owned(), QueryValue::List(records));
+
+    match operation {
+        Operation::Read(selection) => Operation::Read(Selection {
+            arguments: vec![("where".to_owned(), QueryValue::Object(filter))],
+            ..selection
+        }),
+        operation => operation,
+    }
+}
+
+fn select_ids_along(selection: &mut Selection, object_type: ObjectTypeStrongRef, path: &[String]) -> Option<()> {
+    let model = object_type.model()?;
+
+    for name in model.primary_identifier().names() {
+        let alias = format!("{}{}", DEFERRED_ID_PREFIX, name);
+
+        if selection.nested_selections.iter().all(|s| s.response_key() != alias) {
+            selection.nested_selections.push(Selection {
+                name: name.to_owned(),
+                alias: Some(alias),
+                arguments: vec![],
+                nested_selections: vec![],
+            });
+        }
+    }
+
+    match path.split_first() {
+        Some((key, path)) => {
+            let nested = selection
+                .nested_selections
+                .iter_mut()
+                .find(|s| s.response_key() == key)?;
+
+            let nested_type = object_type.find_field(&nested.name)?.field_type.as_object_type()?;
+
+            select_ids_along(nested, nested_type, path)
+        }
+        None => Some(()),
+    }
+}
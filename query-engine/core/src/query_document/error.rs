@@ -25,13 +25,27 @@ pub enum QueryParserError {
         object_name: String,
         inner: Box<QueryParserError>,
     },
+    /// All errors of a level of the document, e.g. of every invalid argument of a field.
+    Multiple(Vec<QueryParserError>),
 }
 
 impl QueryParserError {
+    /// Collects the errors into one, several errors are kept together as `Multiple`.
+    pub(crate) fn collect(mut errors: Vec<QueryParserError>) -> Option<QueryParserError> {
+        match errors.len() {
+            0 => None,
+            1 => errors.pop(),
+            _ => Some(QueryParserError::Multiple(errors)),
+        }
+    }
+
+    /// The paths of the invalid parts of the document, separated by commas if there are several.
     pub(crate) fn location(&self) -> String {
-        let mut node = self;
+        self.locations().join(", ")
+    }
 
-        std::iter::from_fn(|| match node {
+    fn locations(&self) -> Vec<String> {
+        match self {
             QueryParserError::FieldValidationError {
                 field_name: name,
                 inner,
@@ -41,16 +55,21 @@ impl QueryParserError {
                 object_name: name,
                 inner,
             } => {
-                node = inner.as_ref();
-                Some(name)
+                let inner_locations = inner.locations();
+
+                if inner_locations.is_empty() {
+                    vec![format!(".{}", name)]
+                } else {
+                    inner_locations
+                        .into_iter()
+                        .map(|location| format!(".{}{}", name, location))
+                        .collect()
+                }
             }
-            _ => None,
-        })
-        .fold(String::with_capacity(32), |mut path, elem| {
-            path.push_str(".");
-            path.push_str(elem);
-            path
-        })
+            // Siblings below the same parent, the first one is indented by the parent.
+            QueryParserError::Multiple(errors) => errors.iter().flat_map(|err| err.locations()).collect(),
+            _ => Vec::new(),
+        }
     }
 
     pub fn format(&self, ident: usize) -> String {
@@ -74,6 +93,13 @@ impl QueryParserError {
                 Self::ident(inner.format(ident + 2), ident + 2)
             ),
 
+            // Siblings below the same parent, the first one is indented by the parent.
+            QueryParserError::Multiple(errors) => errors
+                .iter()
+                .map(|err| err.format(ident))
+                .collect::<Vec<_>>()
+                .join(&format!("\n{}↳ ", " ".repeat(ident))),
+
             // Validation leaves
             QueryParserError::AssertionError(reason) => format!("Assertion error: {}.", reason),
             QueryParserError::RequiredValueNotSetError => "A value is required but not set.".into(),
@@ -1,9 +1,11 @@
+mod deferred;
 mod error;
 mod parse_ast;
 mod parser;
 mod query_document;
 mod transformers;
 
+pub use deferred::*;
 pub use error::*;
 pub use parse_ast::*;
 pub use parser::*;
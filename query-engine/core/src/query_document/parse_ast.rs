@@ -54,6 +54,15 @@ impl ParsedArgument {
 
 pub trait ArgumentListLookup {
     fn lookup(&mut self, name: &str) -> Option<ParsedArgument>;
+
+    /// Removes an argument the schema requires, e.g. `data` of a create.
+    fn lookup_required(&mut self, name: &str) -> QueryParserResult<ParsedArgument> {
+        self.lookup(name)
+            .ok_or_else(|| QueryParserError::ArgumentValidationError {
+                argument: name.to_owned(),
+                inner: Box::new(QueryParserError::RequiredValueNotSetError),
+            })
+    }
 }
 
 impl ArgumentListLookup for Vec<ParsedArgument> {
@@ -62,6 +71,20 @@ impl ArgumentListLookup for Vec<ParsedArgument> {
     }
 }
 
+pub trait InputMapLookup {
+    /// Removes a field the input object requires, e.g. `create` of a nested upsert.
+    fn remove_required(&mut self, name: &str) -> QueryParserResult<ParsedInputValue>;
+}
+
+impl InputMapLookup for ParsedInputMap {
+    fn remove_required(&mut self, name: &str) -> QueryParserResult<ParsedInputValue> {
+        self.remove(name).ok_or_else(|| QueryParserError::FieldValidationError {
+            field_name: name.to_owned(),
+            inner: Box::new(QueryParserError::RequiredValueNotSetError),
+        })
+    }
+}
+
 /// Note: Assertions should live on the schema level and run through the validation as any other check.
 ///       This requires a slightly larger refactoring.
 pub trait InputAssertions: Sized {
@@ -78,7 +101,12 @@ impl InputAssertions for ParsedInputValue {
         match self {
             Self::List(v) => v.assert_size(size)?,
             Self::Map(m) => m.assert_size(size)?,
-            _ => unimplemented!(),
+            _ => {
+                return Err(QueryParserError::AssertionError(format!(
+                    "Expected a list or an object of size {}, got a single value.",
+                    size
+                )))
+            }
         };
 
         Ok(())
@@ -143,8 +171,11 @@ impl InputAssertions for Vec<ParsedInputValue> {
 }
 
 impl InputAssertions for PrismaValue {
-    fn assert_size(&self, _size: usize) -> QueryParserResult<()> {
-        unimplemented!()
+    fn assert_size(&self, size: usize) -> QueryParserResult<()> {
+        Err(QueryParserError::AssertionError(format!(
+            "Expected a list or an object of size {}, got a single value.",
+            size
+        )))
     }
 
     fn assert_non_null(&self) -> QueryParserResult<()> {
@@ -14,7 +14,6 @@ use uuid::Uuid;
 pub struct QueryDocumentParser;
 
 // Todo:
-// - Use error collections instead of letting first error win.
 // - UUID ids are not encoded in any useful way in the schema.
 // - Alias handling in query names.
 impl QueryDocumentParser {
@@ -34,54 +33,52 @@ impl QueryDocumentParser {
             });
         }
 
-        selections
+        let results = selections
             .iter()
-            .map(|selection| {
-                let parsed_field = match schema_object.find_field(selection.name.as_str()) {
-                    Some(ref field) => Self::parse_field(selection, field),
-                    None => Err(QueryParserError::FieldValidationError {
-                        field_name: selection.name.clone(),
-                        inner: Box::new(QueryParserError::FieldNotFoundError),
-                    }),
-                };
-
-                parsed_field.map_err(|err| QueryParserError::ObjectValidationError {
-                    object_name: schema_object.name().to_string(),
-                    inner: Box::new(err),
-                })
-            })
-            .collect::<QueryParserResult<Vec<ParsedField>>>()
+            .map(|selection| match schema_object.find_field(selection.name.as_str()) {
+                Some(ref field) => Self::parse_field(selection, field),
+                None => Err(QueryParserError::FieldValidationError {
+                    field_name: selection.name.clone(),
+                    inner: Box::new(QueryParserError::FieldNotFoundError),
+                }),
+            });
+
+        collect_all(results)
             .map(|fields| ParsedObject { fields })
+            .map_err(|err| QueryParserError::ObjectValidationError {
+                object_name: schema_object.name().to_string(),
+                inner: Box::new(err),
+            })
     }
 
     /// Parses and validates a selection against a schema (output) field.
     fn parse_field(selection: &Selection, schema_field: &FieldRef) -> QueryParserResult<ParsedField> {
         // Parse and validate all provided arguments for the field
-        Self::parse_arguments(schema_field, &selection.arguments)
-            .and_then(|arguments| {
-                // If the output type of the field is an object type of any form, validate the sub selection as well.
-                let nested_fields = schema_field
-                    .field_type
-                    .as_object_type()
-                    .map(|obj| Self::parse_object(&selection.nested_selections, &obj));
-
-                let nested_fields = match nested_fields {
-                    Some(sub) => Some(sub?),
-                    None => None,
-                };
-
-                Ok(ParsedField {
-                    name: selection.name.clone(),
-                    alias: selection.alias.clone(),
-                    arguments,
-                    nested_fields,
-                    schema_field: Arc::clone(schema_field),
-                })
-            })
-            .map_err(|err| QueryParserError::FieldValidationError {
-                field_name: schema_field.name.clone(),
-                inner: Box::new(err),
-            })
+        let arguments = Self::parse_arguments(schema_field, &selection.arguments);
+
+        // If the output type of the field is an object type of any form, validate the sub selection as well.
+        let nested_fields = schema_field
+            .field_type
+            .as_object_type()
+            .map(|obj| Self::parse_object(&selection.nested_selections, &obj))
+            .transpose();
+
+        let result = match (arguments, nested_fields) {
+            (Ok(arguments), Ok(nested_fields)) => Ok(ParsedField {
+                name: selection.name.clone(),
+                alias: selection.alias.clone(),
+                arguments,
+                nested_fields,
+                schema_field: Arc::clone(schema_field),
+            }),
+            (Err(arguments_err), Err(nested_err)) => Err(QueryParserError::Multiple(vec![arguments_err, nested_err])),
+            (Err(err), _) | (_, Err(err)) => Err(err),
+        };
+
+        result.map_err(|err| QueryParserError::FieldValidationError {
+            field_name: schema_field.name.clone(),
+            inner: Box::new(err),
+        })
     }
 
     /// Parses and validates selection arguments against a schema defined field.
@@ -95,55 +92,51 @@ impl QueryDocumentParser {
         let diff = Diff::new(&left, &right);
 
         // All arguments that are not in the schema cause an error.
-        diff.right
+        let extra_args = diff
+            .right
             .into_iter()
-            .map(|extra_arg| {
+            .map(|extra_arg| -> QueryParserResult<ParsedArgument> {
                 Err(QueryParserError::ArgumentValidationError {
                     argument: (*extra_arg).to_owned(),
                     inner: Box::new(QueryParserError::ArgumentNotFoundError),
                 })
-            })
-            .collect::<QueryParserResult<Vec<()>>>()?;
+            });
 
         // Check remaining arguments
-        schema_field
-            .arguments
-            .iter()
-            .filter_map(|schema_arg| {
-                // Match schema field to a field in the incoming document
-                let selection_arg: Option<(String, QueryValue)> = given_arguments
-                    .iter()
-                    .find(|given_argument| given_argument.0 == schema_arg.name)
-                    .cloned();
-
-                // If the arg can be found, parse the provided query value into a list / object / PrismaValue.
-                //
-                // If the arg can _not_ be found, pretend the arg was provided with a Null.
-                // Run the validation against the Null value to check if it needs to be provided, but disregard the result if it succeeded.
-                let (selection_arg, retain) = match selection_arg {
-                    Some(arg) => (arg, true),
-                    None => ((schema_arg.name.clone(), QueryValue::Null), false),
-                };
-
-                let result = Self::parse_input_value(selection_arg.1, &schema_arg.argument_type)
-                    .map(|value| ParsedArgument {
-                        name: schema_arg.name.clone(),
-                        value,
-                    })
-                    .map_err(|err| QueryParserError::ArgumentValidationError {
-                        argument: schema_arg.name.clone(),
-                        inner: Box::new(err),
-                    });
-
-                if result.is_err() || retain {
-                    Some(result)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<QueryParserResult<ParsedArgument>>>()
-            .into_iter()
-            .collect()
+        let schema_args = schema_field.arguments.iter().filter_map(|schema_arg| {
+            // Match schema field to a field in the incoming document
+            let selection_arg: Option<(String, QueryValue)> = given_arguments
+                .iter()
+                .find(|given_argument| given_argument.0 == schema_arg.name)
+                .cloned();
+
+            // If the arg can be found, parse the provided query value into a list / object / PrismaValue.
+            //
+            // If the arg can _not_ be found, pretend the arg was provided with a Null.
+            // Run the validation against the Null value to check if it needs to be provided, but disregard the result if it succeeded.
+            let (selection_arg, retain) = match selection_arg {
+                Some(arg) => (arg, true),
+                None => ((schema_arg.name.clone(), QueryValue::Null), false),
+            };
+
+            let result = Self::parse_input_value(selection_arg.1, &schema_arg.argument_type)
+                .map(|value| ParsedArgument {
+                    name: schema_arg.name.clone(),
+                    value,
+                })
+                .map_err(|err| QueryParserError::ArgumentValidationError {
+                    argument: schema_arg.name.clone(),
+                    inner: Box::new(err),
+                });
+
+            if result.is_err() || retain {
+                Some(result)
+            } else {
+                None
+            }
+        });
+
+        collect_all(extra_args.chain(schema_args))
     }
 
     /// Parses and validates a QueryValue against an InputType, recursively.
@@ -180,11 +173,16 @@ impl QueryDocumentParser {
             (QueryValue::String(s), ScalarType::DateTime) => Self::parse_datetime(s.as_str()).map(PrismaValue::DateTime),
             (QueryValue::String(s), ScalarType::JsonList) => Self::parse_json_list(&s),
             (QueryValue::String(s), ScalarType::UUID)     => Self::parse_uuid(s.as_str()).map(PrismaValue::Uuid),
+            (QueryValue::String(s), ScalarType::Decimal)  => Self::parse_decimal(s.as_str()).map(PrismaValue::Decimal),
+            (QueryValue::Int(i), ScalarType::Decimal)     => Ok(PrismaValue::Decimal(Decimal::from(i))),
+            (QueryValue::Float(f), ScalarType::Decimal)   => Self::parse_decimal(&f.to_string()).map(PrismaValue::Decimal),
             (QueryValue::Int(i), ScalarType::Float)       => Ok(PrismaValue::Float(Decimal::from_f64(i as f64).expect("f64 is not a Decimal."))),
             (QueryValue::Int(i), ScalarType::Int)         => Ok(PrismaValue::Int(i)),
             (QueryValue::Float(f), ScalarType::Float)     => Ok(PrismaValue::Float(Decimal::from_f64(f).expect("f64 is not a Decimal."))),
             (QueryValue::Float(f), ScalarType::Int)       => Ok(PrismaValue::Int(f as i64)),
             (QueryValue::Boolean(b), ScalarType::Boolean) => Ok(PrismaValue::Boolean(b)),
+            (QueryValue::String(s), ScalarType::Json)     => Self::parse_json(&s).map(|json| PrismaValue::String(json.to_string())),
+            (qv, ScalarType::Json)                        => Ok(PrismaValue::String(Self::json_value(qv).to_string())),
 
             // All other combinations are invalid.
             (qv, _)                                       => Err(QueryParserError::ValueTypeMismatchError { have: qv, want: InputType::Scalar(scalar_type.clone()) }),
@@ -226,15 +224,36 @@ impl QueryDocumentParser {
         serde_json::from_str(s).map_err(|err| QueryParserError::ValueParseError(format!("Invalid json: {}", err)))
     }
 
+    /// Converts a value written inline in the query, e.g. the object of a JSON field, into JSON.
+    pub fn json_value(value: QueryValue) -> serde_json::Value {
+        match value {
+            QueryValue::Int(i) => serde_json::Value::from(i),
+            QueryValue::Float(f) => serde_json::Value::from(f),
+            QueryValue::String(s) | QueryValue::Enum(s) => serde_json::Value::String(s),
+            QueryValue::Boolean(b) => serde_json::Value::Bool(b),
+            QueryValue::Null => serde_json::Value::Null,
+            QueryValue::List(values) => serde_json::Value::Array(values.into_iter().map(Self::json_value).collect()),
+            QueryValue::Object(values) => serde_json::Value::Object(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::json_value(value)))
+                    .collect(),
+            ),
+        }
+    }
+
     pub fn parse_uuid(s: &str) -> QueryParserResult<Uuid> {
         Uuid::parse_str(s).map_err(|err| QueryParserError::ValueParseError(format!("Invalid UUID: {}", err)))
     }
 
+    /// Decimals are exact, clients should send them as strings, floats are accepted in their shortest representation.
+    pub fn parse_decimal(s: &str) -> QueryParserResult<Decimal> {
+        s.parse()
+            .map_err(|err| QueryParserError::ValueParseError(format!("Invalid Decimal: {}", err)))
+    }
+
     pub fn parse_list(values: Vec<QueryValue>, value_type: &InputType) -> QueryParserResult<Vec<ParsedInputValue>> {
-        values
-            .into_iter()
-            .map(|val| Self::parse_input_value(val, value_type))
-            .collect::<QueryParserResult<Vec<ParsedInputValue>>>()
+        collect_all(values.into_iter().map(|val| Self::parse_input_value(val, value_type)))
     }
 
     pub fn parse_enum(val: QueryValue, typ: &EnumTypeRef) -> QueryParserResult<ParsedInputValue> {
@@ -276,59 +295,44 @@ impl QueryDocumentParser {
         object: BTreeMap<String, QueryValue>,
         schema_object: InputObjectTypeStrongRef,
     ) -> QueryParserResult<ParsedInputMap> {
-        let left: HashSet<&str> = schema_object
+        // First, check that all fields not provided in the query are optional, i.e. run the validation but
+        // disregard the result, or have defaults, in which case the value pair gets added to the result.
+        let defaults = schema_object
             .get_fields()
             .iter()
-            .map(|field| field.name.as_str())
-            .collect();
+            .filter(|field| !object.contains_key(&field.name))
+            .filter_map(|field| match field.default_value {
+                // If the input field has a default, add the default to the result.
+                Some(ref dv) => Some(Ok((
+                    field.name.clone(),
+                    ParsedInputValue::Single(dv.get_as_prisma_value()),
+                ))),
 
-        let right: HashSet<&str> = object.keys().map(|k| k.as_str()).collect();
-        let diff = Diff::new(&left, &right);
+                // Finally, if nothing is found, parse the input value with Null but disregard the result,
+                // except errors, which are propagated.
+                None => match Self::parse_input_field(QueryValue::Null, field) {
+                    Ok(_) => None,
+                    Err(err) => Some(Err(err)),
+                },
+            })
+            .collect::<Vec<_>>();
 
-        // First, check that all fields not provided in the query (left diff) are optional,
-        // i.e. run the validation but disregard the result, or have defaults, in which case the
-        // value pair gets added to the result.
-        diff.left
+        // Checks all fields on the provided input object. This will catch extra, unknown fields and parsing errors.
+        let fields = object
             .into_iter()
-            .filter_map(|unset_field_name| {
-                let field = schema_object.find_field(*unset_field_name).unwrap();
-                let default_pair = field.default_value.clone().map(|def| (&field.name, def));
-
-                match default_pair {
-                    // If the input field has a default, add the default to the result.
-                    Some((k, dv)) => Some(Ok((k.clone(), ParsedInputValue::Single(dv.get_as_prisma_value())))),
-
-                    // Finally, if nothing is found, parse the input value with Null but disregard the result,
-                    // except errors, which are propagated.
-                    None => match Self::parse_input_field(QueryValue::Null, &field) {
-                        Ok(_) => None,
-                        Err(err) => Some(Err(err)),
-                    },
-                }
-            })
-            .collect::<QueryParserResult<Vec<_>>>()
-            .and_then(|defaults| {
-                // Checks all fields on the provided input object. This will catch extra, unknown fields and parsing errors.
-                object
-                    .into_iter()
-                    .map(|(k, v)| match schema_object.find_field(k.as_str()) {
-                        Some(field) => Self::parse_input_field(v, &field).map(|parsed| (k, parsed)),
-
-                        None => Err(QueryParserError::FieldValidationError {
-                            field_name: k.clone(),
-                            inner: Box::new(QueryParserError::FieldNotFoundError),
-                        }),
-                    })
-                    .collect::<QueryParserResult<Vec<_>>>()
-                    .map(|mut tuples| {
-                        tuples.extend(defaults.into_iter());
-                        tuples.into_iter().collect()
-                    })
-            })
-            .map_err(|err| QueryParserError::ObjectValidationError {
-                object_name: schema_object.name.clone(),
-                inner: Box::new(err),
-            })
+            .map(|(k, v)| match schema_object.find_field(k.as_str()) {
+                Some(field) => Self::parse_input_field(v, &field).map(|parsed| (k, parsed)),
+
+                None => Err(QueryParserError::FieldValidationError {
+                    field_name: k.clone(),
+                    inner: Box::new(QueryParserError::FieldNotFoundError),
+                }),
+            });
+
+        collect_all(defaults.into_iter().chain(fields)).map_err(|err| QueryParserError::ObjectValidationError {
+            object_name: schema_object.name.clone(),
+            inner: Box::new(err),
+        })
     }
 
     /// Parses and validates an input query value against a schema input field.
@@ -340,6 +344,27 @@ impl QueryDocumentParser {
     }
 }
 
+/// Collects the parsed values, or all errors instead of only the first one.
+fn collect_all<T, C>(results: impl Iterator<Item = QueryParserResult<T>>) -> QueryParserResult<C>
+where
+    C: std::iter::FromIterator<T>,
+{
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    match QueryParserError::collect(errors) {
+        Some(err) => Err(err),
+        None => Ok(values.into_iter().collect()),
+    }
+}
+
 #[derive(Debug)]
 struct Diff<'a, T: std::cmp::Eq + std::hash::Hash> {
     pub left: Vec<&'a T>,
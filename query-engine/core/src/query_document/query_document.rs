@@ -30,7 +30,7 @@ impl QueryDocument {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Operation {
     Read(Selection),
     Write(Selection),
@@ -44,9 +44,16 @@ impl Operation {
             Self::Write(s) => Self::Write(s.dedup()),
         }
     }
+
+    pub fn is_write(&self) -> bool {
+        match self {
+            Self::Read(_) => false,
+            Self::Write(_) => true,
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Selection {
     pub name: String,
     pub alias: Option<String>,
@@ -55,11 +62,18 @@ pub struct Selection {
 }
 
 impl Selection {
+    /// The key of the selection in the response, the alias if present.
+    pub fn response_key(&self) -> &str {
+        self.alias.as_ref().unwrap_or(&self.name)
+    }
+
+    /// Removes nested selections with the same response key, a field can be selected more than once under different
+    /// aliases.
     pub fn dedup(mut self) -> Self {
         self.nested_selections = self
             .nested_selections
             .into_iter()
-            .unique_by(|s| s.name.clone())
+            .unique_by(|s| s.response_key().to_owned())
             .collect();
 
         self
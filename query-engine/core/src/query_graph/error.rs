@@ -6,4 +6,12 @@ pub enum QueryGraphError {
 
     /// Error expressing an invalid transformation done on graph nodes.
     InvalidNodeTransformation { from: String, to: String },
+
+    /// Error expressing that an edge would close a cycle of dependencies, e.g. a nested create that needs the id of
+    /// its child while the child needs the id of the create. `nodes` are the nodes of the cycle in execution order,
+    /// `relation_fields` the relation fields whose inlining caused the nodes to be swapped.
+    DependencyCycle {
+        nodes: Vec<String>,
+        relation_fields: Vec<String>,
+    },
 }
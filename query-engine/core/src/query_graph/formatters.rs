@@ -11,6 +11,76 @@ pub fn format(graph: &QueryGraph) -> String {
     )
 }
 
+/// Renders the graph in the DOT language of Graphviz, e.g. to be viewed with `dot -Tsvg`. Result nodes are drawn
+/// with a double border.
+pub fn to_dot(graph: &QueryGraph) -> String {
+    let mut lines = vec!["digraph QueryGraph {".to_owned(), "  node [shape=box];".to_owned()];
+
+    for node in graph_nodes(graph) {
+        let shape = if graph.is_result_node(&node) {
+            ", peripheries=2"
+        } else {
+            ""
+        };
+        let content = graph.node_content(&node).map(|n| n.to_string()).unwrap_or_default();
+
+        lines.push(format!(
+            "  {} [label=\"{}: {}\"{}];",
+            node.id(),
+            node.id(),
+            escape_dot(&content),
+            shape
+        ));
+
+        for edge in graph.outgoing_edges(&node) {
+            let dependency = graph.edge_content(&edge).map(|d| d.to_string()).unwrap_or_default();
+
+            lines.push(format!(
+                "  {} -> {} [label=\"{}\"];",
+                node.id(),
+                graph.edge_target(&edge).id(),
+                escape_dot(&dependency)
+            ));
+        }
+    }
+
+    lines.push("}".to_owned());
+    lines.join("\n")
+}
+
+/// Renders the nodes and edges of the graph as JSON.
+pub fn to_json(graph: &QueryGraph) -> serde_json::Value {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+
+    for node in graph_nodes(graph) {
+        nodes.push(serde_json::json!({
+            "id": node.id(),
+            "content": graph.node_content(&node).map(|n| n.to_string()),
+            "result": graph.is_result_node(&node),
+        }));
+
+        for edge in graph.outgoing_edges(&node) {
+            edges.push(serde_json::json!({
+                "id": edge.id(),
+                "from": node.id(),
+                "to": graph.edge_target(&edge).id(),
+                "dependency": graph.edge_content(&edge).map(|d| d.to_string()),
+            }));
+        }
+    }
+
+    serde_json::json!({ "nodes": nodes, "edges": edges })
+}
+
+fn graph_nodes(graph: &QueryGraph) -> Vec<NodeRef> {
+    graph.graph.node_indices().map(|node_ix| NodeRef { node_ix }).collect()
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
 fn stringify_nodes(graph: &QueryGraph, nodes: Vec<NodeRef>, seen_nodes: &mut Vec<NodeRef>) -> Vec<String> {
     let mut rendered_nodes = vec![];
 
@@ -62,6 +132,7 @@ impl Display for Computation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Diff(_) => write!(f, "Diff"),
+            Self::CompareCounts(c) => write!(f, "CompareCounts ({})", c.model_name),
         }
     }
 }
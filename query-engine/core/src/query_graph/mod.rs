@@ -19,8 +19,11 @@ use crate::{interpreter::ExpressionResult, Query, QueryGraphBuilderResult};
 use guard::*;
 use invariance_rules::*;
 use petgraph::{graph::*, visit::EdgeRef as PEdgeRef, *};
-use prisma_models::{ModelIdentifier, RecordIdentifier};
-use std::{borrow::Borrow, collections::HashSet};
+use prisma_models::{ModelIdentifier, RecordIdentifier, RelationFieldRef};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashSet},
+};
 
 pub type QueryGraphResult<T> = std::result::Result<T, QueryGraphError>;
 
@@ -67,6 +70,9 @@ impl Flow {
 // Current limitation: We need to narrow it down to GraphqlID diffs for Hash and EQ.
 pub enum Computation {
     Diff(DiffNode),
+
+    /// Fails if the number of records of a node and of its reload differ, i.e. records disappeared in between.
+    CompareCounts(CompareCountsNode),
 }
 
 impl Computation {
@@ -76,6 +82,14 @@ impl Computation {
             right: HashSet::new(),
         })
     }
+
+    pub fn empty_count_comparison(model_name: String) -> Self {
+        Self::CompareCounts(CompareCountsNode {
+            model_name,
+            expected: None,
+            found: None,
+        })
+    }
 }
 
 pub struct DiffNode {
@@ -83,7 +97,17 @@ pub struct DiffNode {
     pub right: HashSet<RecordIdentifier>,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct CompareCountsNode {
+    pub model_name: String,
+
+    /// Number of records of the node.
+    pub expected: Option<usize>,
+
+    /// Number of records of the reload.
+    pub found: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct NodeRef {
     node_ix: NodeIndex,
 }
@@ -138,7 +162,7 @@ pub enum QueryGraphDependency {
 /// A graph representing an abstract view of queries and their execution dependencies.
 ///
 /// Graph invariants (TODO put checks into the code?):
-/// - Directed, acyclic.
+/// - Directed, acyclic. Edges closing a cycle are rejected on creation.
 ///
 /// - Node IDs are unique and stable.
 ///
@@ -152,6 +176,8 @@ pub enum QueryGraphDependency {
 ///
 /// - Edges are ordered.
 ///   Node: Their evaluation is performed from low to high ordering, unless other rules require reshuffling the edges during translation.
+///   The order is the order of creation, so sibling nested operations are executed in the order the builders
+///   created them, regardless of edge removals. Edges reversed by a swap keep the position of the original edge.
 #[derive(Default)]
 pub struct QueryGraph {
     graph: InnerGraph,
@@ -167,6 +193,13 @@ pub struct QueryGraph {
     /// More docs can be found on `swap_marked`.
     marked_node_pairs: Vec<(NodeRef, NodeRef)>,
 
+    /// The relation fields the marked node pairs were swapped for, as `Model.field`.
+    /// Kept after the swap to name them if the swap results in a cycle.
+    marked_relation_fields: BTreeMap<(NodeRef, NodeRef), String>,
+
+    /// Position of the next edge created in the order of edges.
+    next_edge_order: usize,
+
     finalized: bool,
 
     /// For now a stupid marker if the query graph needs to be run inside a
@@ -175,7 +208,14 @@ pub struct QueryGraph {
 }
 
 /// Implementation detail of the QueryGraph.
-type InnerGraph = Graph<Guard<Node>, Guard<QueryGraphDependency>>;
+type InnerGraph = Graph<Guard<Node>, OrderedEdge>;
+
+/// Implementation detail of the QueryGraph. Edge indices change when edges are removed,
+/// the order of edges is therefore kept with the edge.
+struct OrderedEdge {
+    order: usize,
+    dependency: Guard<QueryGraphDependency>,
+}
 
 impl QueryGraph {
     pub fn new() -> Self {
@@ -244,6 +284,11 @@ impl QueryGraph {
             .collect()
     }
 
+    /// Returns all nodes of the graph.
+    pub fn nodes(&self) -> Vec<NodeRef> {
+        self.graph.node_indices().map(|node_ix| NodeRef { node_ix }).collect()
+    }
+
     /// Creates a node with content `t` and adds it to the graph.
     /// Returns a `NodeRef` to the newly added node.
     pub fn create_node<T>(&mut self, t: T) -> NodeRef
@@ -256,26 +301,104 @@ impl QueryGraph {
     }
 
     /// Creates an edge with given `content`, originating from node `from` and pointing to node `to`.
+    /// Edges that would close a cycle are rejected with a `DependencyCycle` error.
     /// Checks are run after edge creation to ensure validity of the query graph.
     /// Returns an `EdgeRef` to the newly added edge.
-    /// Todo currently panics, change interface to result type.
     pub fn create_edge(
         &mut self,
         from: &NodeRef,
         to: &NodeRef,
         content: QueryGraphDependency,
     ) -> QueryGraphResult<EdgeRef> {
-        let edge_ix = self.graph.add_edge(from.node_ix, to.node_ix, Guard::new(content));
+        let order = self.next_edge_order;
+        self.next_edge_order += 1;
+
+        self.insert_edge(from, to, content, order)
+    }
+
+    fn insert_edge(
+        &mut self,
+        from: &NodeRef,
+        to: &NodeRef,
+        content: QueryGraphDependency,
+        order: usize,
+    ) -> QueryGraphResult<EdgeRef> {
+        // Self edges are rejected by the invariance rules.
+        if from != to {
+            if let Some(path) = self.find_path(to, from, &mut HashSet::new()) {
+                return Err(self.cycle_error(path));
+            }
+        }
+
+        let dependency = Guard::new(content);
+        let edge_ix = self
+            .graph
+            .add_edge(from.node_ix, to.node_ix, OrderedEdge { order, dependency });
         let edge = EdgeRef { edge_ix };
 
         after_edge_creation(self, &edge).map(|_| edge)
     }
 
+    /// Returns the nodes of a path from `from` to `to`, both included, if there is one.
+    /// Nodes in `visited` are known to have no path to `to`.
+    fn find_path(&self, from: &NodeRef, to: &NodeRef, visited: &mut HashSet<NodeRef>) -> Option<Vec<NodeRef>> {
+        if from == to {
+            return Some(vec![*from]);
+        }
+
+        if !visited.insert(*from) {
+            return None;
+        }
+
+        for (_, child) in self.child_pairs(from) {
+            if let Some(mut path) = self.find_path(&child, to, visited) {
+                path.insert(0, *from);
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// The error for an edge from the last node of `path` to the first one.
+    fn cycle_error(&self, path: Vec<NodeRef>) -> QueryGraphError {
+        let closing_pair = (path[path.len() - 1], path[0]);
+        let mut relation_fields = Vec::new();
+
+        for (a, b) in path.windows(2).map(|pair| (pair[0], pair[1])).chain(Some(closing_pair)) {
+            let relation_field = self
+                .marked_relation_fields
+                .get(&(a, b))
+                .or_else(|| self.marked_relation_fields.get(&(b, a)));
+
+            if let Some(relation_field) = relation_field {
+                if !relation_fields.contains(relation_field) {
+                    relation_fields.push(relation_field.clone());
+                }
+            }
+        }
+
+        let nodes = path
+            .iter()
+            .map(|node| match self.node_content(node) {
+                Some(content) => format!("{} ({})", node, content),
+                None => format!("{}", node),
+            })
+            .collect();
+
+        QueryGraphError::DependencyCycle { nodes, relation_fields }
+    }
+
     /// Mark the query graph to need a transaction.
     pub fn flag_transactional(&mut self) {
         self.needs_transaction = true;
     }
 
+    /// The number of nodes of the graph, checked against the query graph limit.
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
     /// If true, the graph should be executed inside of a transaction.
     pub fn needs_transaction(&self) -> bool {
         self.needs_transaction
@@ -288,7 +411,7 @@ impl QueryGraph {
 
     /// Returns a reference to the content of `edge`, if the content is still present.
     pub fn edge_content(&self, edge: &EdgeRef) -> Option<&QueryGraphDependency> {
-        self.graph.edge_weight(edge.edge_ix).unwrap().borrow()
+        self.graph.edge_weight(edge.edge_ix).unwrap().dependency.borrow()
     }
 
     /// Returns the node from where `edge` originates (e.g. source).
@@ -316,7 +439,7 @@ impl QueryGraph {
     /// Removes the edge from the graph but leaves the graph intact by keeping the empty
     /// edge in the graph by plucking the content of the edge, but not the edge itself.
     pub fn pluck_edge(&mut self, edge: &EdgeRef) -> QueryGraphDependency {
-        self.graph.edge_weight_mut(edge.edge_ix).unwrap().unset()
+        self.graph.edge_weight_mut(edge.edge_ix).unwrap().dependency.unset()
     }
 
     /// Removes the node from the graph but leaves the graph intact by keeping the empty
@@ -328,7 +451,7 @@ impl QueryGraph {
     /// Completely removes the edge from the graph, returning it's content.
     /// This operation is destructive on the underlying graph and invalidates
     pub fn remove_edge(&mut self, edge: EdgeRef) -> Option<QueryGraphDependency> {
-        self.graph.remove_edge(edge.edge_ix).unwrap().into_inner()
+        self.graph.remove_edge(edge.edge_ix).unwrap().dependency.into_inner()
     }
 
     /// Checks if `child` is a direct child of `parent`.
@@ -398,21 +521,26 @@ impl QueryGraph {
             .is_some()
     }
 
-    /// Internal utility function to collect all edges of defined direction directed to, or originating from, `node`.
+    /// Internal utility function to collect all edges of defined direction directed to, or originating from, `node`,
+    /// in the order of edges.
     fn collect_edges(&self, node: &NodeRef, direction: Direction) -> Vec<EdgeRef> {
         let mut edges = self
             .graph
             .edges_directed(node.node_ix, direction)
-            .map(|edge| EdgeRef { edge_ix: edge.id() })
+            .map(|edge| (edge.weight().order, EdgeRef { edge_ix: edge.id() }))
             .collect::<Vec<_>>();
 
         edges.sort();
-        edges
+        edges.into_iter().map(|(_, edge)| edge).collect()
     }
 
-    /// Marks a node pair for swapping.
-    pub fn mark_nodes(&mut self, parent_node: &NodeRef, child_node: &NodeRef) {
+    /// Marks a node pair for swapping, because the parent needs the child for `relation_field`.
+    pub fn mark_nodes(&mut self, parent_node: &NodeRef, child_node: &NodeRef, relation_field: &RelationFieldRef) {
+        let relation_field = format!("{}.{}", relation_field.model().name, relation_field.name);
+
         self.marked_node_pairs.push((parent_node.clone(), child_node.clone()));
+        self.marked_relation_fields
+            .insert((parent_node.clone(), child_node.clone()), relation_field);
     }
 
     /// Swaps all marked parent-child pairs.
@@ -542,10 +670,12 @@ impl QueryGraph {
                 .find_edge(parent_node.node_ix, child_node.node_ix)
                 .map(|edge_ix| EdgeRef { edge_ix });
 
-            // Remove edge and reinsert edge in reverse.
+            // Remove edge and reinsert edge in reverse, at the same position in the order of edges.
             if let Some(edge) = existing_edge {
+                let order = self.graph.edge_weight(edge.edge_ix).unwrap().order;
                 let content = self.remove_edge(edge).unwrap();
-                self.create_edge(&child_node, &parent_node, content)?;
+
+                self.insert_edge(&child_node, &parent_node, content, order)?;
             }
         }
 
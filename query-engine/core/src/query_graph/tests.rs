@@ -120,6 +120,92 @@ fn test_invalid_self_connecting_edge() {
         .unwrap();
 }
 
+#[test]
+fn test_cycle_is_rejected() {
+    let mut graph = QueryGraph::new();
+
+    let a = graph.create_node(Node::Empty);
+    let b = graph.create_node(Node::Empty);
+    let c = graph.create_node(Node::Empty);
+
+    graph.create_edge(&a, &b, QueryGraphDependency::ExecutionOrder).unwrap();
+    graph.create_edge(&b, &c, QueryGraphDependency::ExecutionOrder).unwrap();
+
+    match graph.create_edge(&c, &a, QueryGraphDependency::ExecutionOrder) {
+        Err(QueryGraphError::DependencyCycle { nodes, relation_fields }) => {
+            assert_eq!(nodes, vec!["Node 0 (Empty)", "Node 1 (Empty)", "Node 2 (Empty)"]);
+            assert!(relation_fields.is_empty());
+        }
+        _ => panic!("Expected a dependency cycle error."),
+    }
+}
+
+#[test]
+fn test_edge_order_survives_edge_removal() {
+    let mut graph = QueryGraph::new();
+
+    let parent = graph.create_node(Node::Empty);
+    let a = graph.create_node(Node::Empty);
+    let b = graph.create_node(Node::Empty);
+    let c = graph.create_node(Node::Empty);
+
+    let edge_a = graph
+        .create_edge(&parent, &a, QueryGraphDependency::ExecutionOrder)
+        .unwrap();
+    graph
+        .create_edge(&parent, &b, QueryGraphDependency::ExecutionOrder)
+        .unwrap();
+    graph
+        .create_edge(&parent, &c, QueryGraphDependency::ExecutionOrder)
+        .unwrap();
+
+    // Removing an edge moves the last edge to its index.
+    graph.remove_edge(edge_a);
+
+    let children: Vec<NodeRef> = graph.child_pairs(&parent).into_iter().map(|(_, child)| child).collect();
+
+    assert_eq!(children, vec![b, c]);
+}
+
+#[test]
+fn test_graph_to_json() {
+    let mut graph = QueryGraph::new();
+
+    let parent = graph.create_node(Node::Empty);
+    let child = graph.create_node(Node::Empty);
+
+    graph.add_result_node(&child);
+    graph
+        .create_edge(&parent, &child, QueryGraphDependency::ExecutionOrder)
+        .unwrap();
+
+    let json = to_json(&graph);
+
+    assert_eq!(json["nodes"].as_array().unwrap().len(), 2);
+    assert_eq!(json["nodes"][1]["result"], serde_json::json!(true));
+    assert_eq!(
+        json["edges"],
+        serde_json::json!([{ "id": "0", "from": "0", "to": "1", "dependency": "ExecutionOrder" }])
+    );
+}
+
+#[test]
+fn test_graph_to_dot() {
+    let mut graph = QueryGraph::new();
+
+    let parent = graph.create_node(Node::Empty);
+    let child = graph.create_node(Node::Empty);
+
+    graph.add_result_node(&child);
+    graph.create_edge(&parent, &child, QueryGraphDependency::Then).unwrap();
+
+    let dot = to_dot(&graph);
+
+    assert!(dot.starts_with("digraph QueryGraph {"));
+    assert!(dot.contains("  1 [label=\"1: Empty\", peripheries=2];"));
+    assert!(dot.contains("  0 -> 1 [label=\"Then\"];"));
+}
+
 fn dummy_query() -> Query {
     let test_dm = connector::test_data_model();
 
@@ -32,25 +32,33 @@ struct RawArgs {
 }
 
 impl RawArgs {
-    fn add_arg(&mut self, arg: Option<ParsedArgument>) {
+    fn add_arg(&mut self, arg: Option<ParsedArgument>) -> QueryGraphBuilderResult<()> {
         if let Some(arg) = arg {
-            if arg.name == "query" {
-                self.query = arg.into_value().unwrap().into_string().unwrap();
-            } else {
-                self.parameters = arg.into_value().unwrap().into_list().unwrap();
+            let name = arg.name.clone();
+
+            match (name.as_str(), arg.into_value()) {
+                ("query", Some(PrismaValue::String(query))) => self.query = query,
+                (_, Some(PrismaValue::List(parameters))) => self.parameters = parameters,
+                (_, Some(PrismaValue::Null)) | (_, None) if name != "query" => (),
+                _ => {
+                    return Err(QueryGraphBuilderError::InputError(format!(
+                        "Invalid value for the argument `{}` of the raw query.",
+                        name
+                    )))
+                }
             }
         }
+
+        Ok(())
     }
-}
 
-impl From<Vec<ParsedArgument>> for RawArgs {
-    fn from(mut args: Vec<ParsedArgument>) -> Self {
+    fn from_arguments(mut args: Vec<ParsedArgument>) -> QueryGraphBuilderResult<Self> {
         let mut ra = Self::default();
 
-        ra.add_arg(args.pop());
-        ra.add_arg(args.pop());
+        ra.add_arg(args.pop())?;
+        ra.add_arg(args.pop())?;
 
-        ra
+        Ok(ra)
     }
 }
 
@@ -69,7 +77,7 @@ impl QueryGraphBuilder {
     }
 
     /// Maps an operation to a query.
-    fn map_operation(&self, operation: Operation) -> QueryGraphBuilderResult<(QueryType, IrSerializer)> {
+    pub fn map_operation(&self, operation: Operation) -> QueryGraphBuilderResult<(QueryType, IrSerializer)> {
         match operation {
             Operation::Read(selection) => self.map_read_operation(selection),
             Operation::Write(selection) => self.map_write_operation(selection),
@@ -108,7 +116,7 @@ impl QueryGraphBuilder {
         let query_type = match &parsed_field.schema_field.clone().query_builder {
             Some(builder) => Ok(QueryType::Graph(builder.build(parsed_field)?)),
             None if parsed_field.is_raw_query() => {
-                let raw_args = RawArgs::from(parsed_field.arguments);
+                let raw_args = RawArgs::from_arguments(parsed_field.arguments)?;
 
                 Ok(QueryType::Raw {
                     query: raw_args.query,
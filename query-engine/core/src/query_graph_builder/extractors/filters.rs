@@ -1,6 +1,6 @@
 use super::utils;
 use crate::{
-    query_document::{ParsedInputMap, ParsedInputValue},
+    query_document::{InputAssertions, InputMapLookup, ParsedInputMap, ParsedInputValue},
     QueryGraphBuilderError, QueryGraphBuilderResult,
 };
 use connector::{filter::Filter, RelationCompare, ScalarCompare};
@@ -94,6 +94,37 @@ impl FilterOp {
     }
 }
 
+/// Extracts the filter of a `WhereUniqueInput`: the unique field identifying the record, and the filters of the
+/// optional `AND` field the record has to match as well, e.g. `{ id: 1, AND: { ownerId: 2 } }`. A record that
+/// doesn't match them is treated like a record that doesn't exist.
+pub fn extract_unique_filter(mut value_map: ParsedInputMap, model: &ModelRef) -> QueryGraphBuilderResult<Filter> {
+    let additional_filters = value_map.remove("AND");
+
+    value_map.assert_size(1)?;
+    value_map.assert_non_null()?;
+
+    let unique_filter = extract_filter(value_map, model, false)?;
+
+    match additional_filters {
+        None | Some(ParsedInputValue::Single(PrismaValue::Null)) => Ok(unique_filter),
+        Some(ParsedInputValue::Map(map)) => Ok(Filter::and(vec![unique_filter, extract_filter(map, model, true)?])),
+        Some(_) => Err(QueryGraphBuilderError::InputError(
+            "Expected an object of filters for AND.".to_owned(),
+        )),
+    }
+}
+
+/// Upserts create the record if the where input doesn't match, so a record failing the additional filters of a
+/// unique where input would be created instead of being reported as not found.
+pub fn assert_no_additional_filters(value_map: &ParsedInputMap) -> QueryGraphBuilderResult<()> {
+    match value_map.get("AND") {
+        None | Some(ParsedInputValue::Single(PrismaValue::Null)) => Ok(()),
+        Some(_) => Err(QueryGraphBuilderError::InputError(
+            "Upserts don't accept AND filters in their where input.".to_owned(),
+        )),
+    }
+}
+
 pub fn extract_filter(
     value_map: BTreeMap<String, ParsedInputValue>,
     model: &ModelRef,
@@ -102,8 +133,9 @@ pub fn extract_filter(
     let filters = value_map
         .into_iter()
         .map(|(key, value): (String, ParsedInputValue)| {
+            // Every key matches at least the empty suffix of a plain field filter.
             let op = if match_suffix {
-                FilterOp::find_op(key.as_str()).unwrap()
+                FilterOp::find_op(key.as_str()).unwrap_or(FilterOp::Field)
             } else {
                 FilterOp::Field
             };
@@ -118,14 +150,16 @@ pub fn extract_filter(
 
                         ParsedInputValue::Map(map) => extract_filter(map, model, match_suffix).map(|res| vec![res]),
 
-                        _ => unreachable!(),
+                        _ => Err(QueryGraphBuilderError::InputError(format!(
+                            "Expected a list or an object of filters for {}.",
+                            key
+                        ))),
                     };
 
                     value.map(|value| match op {
                         FilterOp::NestedAnd => Filter::and(value),
                         FilterOp::NestedOr => Filter::or(value),
-                        FilterOp::NestedNot => Filter::not(value),
-                        _ => unreachable!(),
+                        _ => Filter::not(value),
                     })
                 }
                 op => {
@@ -161,7 +195,7 @@ fn handle_scalar_field(
     let value: PrismaValue = value.try_into()?;
     let dsf = field.data_source_field();
 
-    Ok(match (op, value) {
+    let filter = match (op, value) {
         (FilterOp::In, PrismaValue::Null) => dsf.equals(PrismaValue::Null),
         (FilterOp::In, PrismaValue::List(values)) => dsf.is_in(values),
         (FilterOp::NotIn, PrismaValue::Null) => dsf.not_equals(PrismaValue::Null),
@@ -178,8 +212,15 @@ fn handle_scalar_field(
         (FilterOp::EndsWith, val) => dsf.ends_with(val),
         (FilterOp::NotEndsWith, val) => dsf.not_ends_with(val),
         (FilterOp::Field, val) => dsf.equals(val),
-        (_, _) => unreachable!(),
-    })
+        (op, val) => {
+            return Err(QueryGraphBuilderError::InputError(format!(
+                "Filter {:?} with value {:?} is not supported on the scalar field {}.",
+                op, val, field.name
+            )))
+        }
+    };
+
+    Ok(filter)
 }
 
 fn handle_relation_field(
@@ -190,7 +231,7 @@ fn handle_relation_field(
 ) -> QueryGraphBuilderResult<Filter> {
     let value: Option<BTreeMap<String, ParsedInputValue>> = value.try_into()?;
 
-    Ok(match (op, value) {
+    let filter = match (op, value) {
         (FilterOp::Some, Some(value)) => {
             field.at_least_one_related(extract_filter(value, &field.related_model(), match_suffix)?)
         }
@@ -202,8 +243,15 @@ fn handle_relation_field(
             field.to_one_related(extract_filter(value, &field.related_model(), match_suffix)?)
         }
         (FilterOp::Field, None) => field.one_relation_is_null(),
-        _ => unreachable!(),
-    })
+        (op, _) => {
+            return Err(QueryGraphBuilderError::InputError(format!(
+                "Filter {:?} is not supported on the relation field {}.",
+                op, field.name
+            )))
+        }
+    };
+
+    Ok(filter)
 }
 
 fn handle_compound_field(fields: Vec<ScalarFieldRef>, value: ParsedInputValue) -> QueryGraphBuilderResult<Filter> {
@@ -212,7 +260,7 @@ fn handle_compound_field(fields: Vec<ScalarFieldRef>, value: ParsedInputValue) -
     let filters: Vec<Filter> = fields
         .into_iter()
         .map(|field| {
-            let value: PrismaValue = value.remove(&field.name).unwrap().try_into()?;
+            let value: PrismaValue = value.remove_required(&field.name)?.try_into()?;
             Ok(field.data_source_field().equals(value))
         })
         .collect::<QueryGraphBuilderResult<Vec<_>>>()?;
@@ -1,8 +1,10 @@
 mod filters;
 mod query_arguments;
+mod soft_delete;
 mod utils;
 
 pub use filters::*;
 pub use query_arguments::*;
+pub use soft_delete::*;
 
 use crate::query_document::*;
@@ -1,6 +1,6 @@
 use super::*;
 use crate::{
-    query_document::{ParsedArgument, ParsedInputMap},
+    query_document::{InputMapLookup, ParsedArgument, ParsedInputMap},
     QueryGraphBuilderError, QueryGraphBuilderResult,
 };
 use connector::QueryArguments;
@@ -10,7 +10,11 @@ use std::convert::TryInto;
 /// Expects the caller to know that it is structurally guaranteed that query arguments can be extracted,
 /// e.g. that the query schema guarantees that required fields are present.
 /// Errors occur if conversions fail unexpectedly.
+///
+/// `take: n` reads the first `n` records, `take: -n` the last `n` records, it can't be combined with `first` or `last`.
 pub fn extract_query_args(arguments: Vec<ParsedArgument>, model: &ModelRef) -> QueryGraphBuilderResult<QueryArguments> {
+    let has_first_or_last = arguments.iter().any(|arg| arg.name == "first" || arg.name == "last");
+
     arguments
         .into_iter()
         .fold(Ok(QueryArguments::default()), |result, arg| {
@@ -31,6 +35,25 @@ pub fn extract_query_args(arguments: Vec<ParsedArgument>, model: &ModelRef) -> Q
                         ..res
                     }),
 
+                    "take" => {
+                        let take: Option<i64> = arg.value.try_into()?;
+
+                        match take {
+                            Some(_) if has_first_or_last => Err(QueryGraphBuilderError::InputError(
+                                "The `take` argument can't be combined with `first` or `last`.".to_owned(),
+                            )),
+                            Some(take) if take < 0 => Ok(QueryArguments {
+                                last: Some(-take),
+                                ..res
+                            }),
+                            Some(take) => Ok(QueryArguments {
+                                first: Some(take),
+                                ..res
+                            }),
+                            None => Ok(res),
+                        }
+                    }
+
                     "after" => Ok(QueryArguments {
                         after: extract_cursor(arg.value, model)?,
                         ..res
@@ -76,7 +99,10 @@ fn extract_cursor(
     let map: ParsedInputMap = value.try_into()?;
     map.assert_size(1)?;
 
-    let (field_name, value): (String, ParsedInputValue) = map.into_iter().nth(0).unwrap();
+    let (field_name, value): (String, ParsedInputValue) = match map.into_iter().next() {
+        Some(pair) => pair,
+        None => return Ok(None),
+    };
 
     // Always try to resolve regular fields first. If that fails, try to resolve compound fields.
     model
@@ -99,8 +125,7 @@ fn extract_cursor(
                     let mut result = vec![];
 
                     for field in fields {
-                        // Unwrap is safe because validation gurantees that the value is present.
-                        let value = compound_map.remove(&field.name).unwrap().try_into()?;
+                        let value = compound_map.remove_required(&field.name)?.try_into()?;
                         result.push((field, value));
                     }
 
@@ -0,0 +1,30 @@
+use crate::{
+    query_document::{ArgumentListLookup, ParsedArgument},
+    QueryGraphBuilderResult,
+};
+use connector::{Filter, ScalarCompare};
+use prisma_models::{ModelRef, PrismaValue};
+use std::convert::TryInto;
+
+/// Removes the `includeDeleted` argument of a read and returns whether soft deleted records are included.
+pub fn extract_include_deleted(arguments: &mut Vec<ParsedArgument>) -> QueryGraphBuilderResult<bool> {
+    let include_deleted: Option<bool> = match arguments.lookup("includeDeleted") {
+        Some(arg) => arg.value.try_into()?,
+        None => None,
+    };
+
+    Ok(include_deleted.unwrap_or(false))
+}
+
+/// Restricts the filter of a read to records that are not soft deleted, if the model is soft deleted.
+pub fn exclude_soft_deleted(filter: Option<Filter>, model: &ModelRef) -> Option<Filter> {
+    let not_deleted = match model.soft_delete_field() {
+        Some(field) => field.equals(PrismaValue::Null),
+        None => return filter,
+    };
+
+    match filter {
+        Some(filter) => Some(Filter::and(vec![filter, not_deleted])),
+        None => Some(not_deleted),
+    }
+}
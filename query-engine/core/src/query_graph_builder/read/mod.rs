@@ -33,30 +33,38 @@ impl Builder<ReadQuery> for ReadQueryBuilder {
     }
 }
 
-pub fn collect_selection_order(from: &[ParsedField]) -> Vec<String> {
+/// The response key (the alias, or the name if there is none) and the name of each selected field, in order.
+pub fn collect_selection_order(from: &[ParsedField]) -> Vec<(String, String)> {
     from.iter()
         .map(|selected_field| {
-            selected_field
+            let key = selected_field
                 .alias
                 .clone()
-                .unwrap_or_else(|| selected_field.name.clone())
+                .unwrap_or_else(|| selected_field.name.clone());
+
+            (key, selected_field.name.clone())
         })
         .collect()
 }
 
 /// Creates SelectedFields from a query selection.
 /// Automatically adds model IDs to the selected fields as well.
-pub fn collect_selected_fields(from: &[ParsedField], model: &ModelRef) -> SelectedFields {
+pub fn collect_selected_fields(from: &[ParsedField], model: &ModelRef) -> QueryGraphBuilderResult<SelectedFields> {
     let selected_fields = from
         .iter()
-        .map(|selected_field| {
-            let model_field = model.fields().find_from_all(&selected_field.name).unwrap();
-            match model_field {
+        .map(|selected_field| -> QueryGraphBuilderResult<SelectedField> {
+            // Selecting the relation field of a foreign key scalar only reads the inlined columns.
+            if let Some(rf) = model.find_foreign_key_relation_field(&selected_field.name) {
+                return Ok(SelectedField::Relation(SelectedRelationField { field: rf }));
+            }
+
+            let model_field = model.fields().find_from_all(&selected_field.name)?;
+            Ok(match model_field {
                 Field::Scalar(ref sf) => SelectedField::Scalar(SelectedScalarField { field: Arc::clone(sf) }),
                 Field::Relation(ref rf) => SelectedField::Relation(SelectedRelationField { field: Arc::clone(rf) }),
-            }
+            })
         })
-        .collect::<Vec<SelectedField>>();
+        .collect::<QueryGraphBuilderResult<Vec<SelectedField>>>()?;
 
     let mut selected_fields = SelectedFields::new(selected_fields);
 
@@ -65,35 +73,32 @@ pub fn collect_selected_fields(from: &[ParsedField], model: &ModelRef) -> Select
         selected_fields.add(field);
     }
 
-    selected_fields
+    Ok(selected_fields)
 }
 
 pub fn collect_nested_queries(from: Vec<ParsedField>, model: &ModelRef) -> QueryGraphBuilderResult<Vec<ReadQuery>> {
     from.into_iter()
-        .filter_map(|selected_field| {
-            let model_field = model.fields().find_from_all(&selected_field.name).unwrap();
-            match model_field {
-                Field::Scalar(_) => None,
-                Field::Relation(ref rf) => {
+        .filter(|selected_field| model.find_foreign_key_relation_field(&selected_field.name).is_none())
+        .filter_map(
+            |selected_field| match model.fields().find_from_all(&selected_field.name) {
+                Ok(Field::Scalar(_)) => None,
+                Ok(Field::Relation(ref rf)) => {
                     let model = rf.related_model();
                     let parent = Arc::clone(&rf);
+                    let builder = ReadRelatedRecordsBuilder::new(model, parent, selected_field);
 
-                    Some(ReadQueryBuilder::ReadRelatedRecordsBuilder(
-                        ReadRelatedRecordsBuilder::new(model, parent, selected_field),
-                    ))
+                    Some(ReadQueryBuilder::ReadRelatedRecordsBuilder(builder).build())
                 }
-            }
-        })
-        .collect::<Vec<ReadQueryBuilder>>()
-        .into_iter()
-        .map(|builder| builder.build())
+                Err(err) => Some(Err(err.into())),
+            },
+        )
         .collect::<QueryGraphBuilderResult<Vec<ReadQuery>>>()
 }
 
-/// Merges inlined fields into the selected fields of the query as required.
+/// Merges linking fields into the selected fields of the query as required.
 /// The reason is that if a query is part of a nested query tree, it needs
-/// to ensure that it fetches all necessary fields of an inlined relation
-/// for dependent queries to succeed.
+/// to ensure that it fetches all necessary fields of an inlined relation,
+/// or the fields referenced by one, for dependent queries to succeed.
 ///
 /// # Parameters:
 /// `selected_fields`: The selected fields as a base for injection.
@@ -107,18 +112,16 @@ pub fn merge_inlined_relation_fields(
     parent_relation: Option<RelationFieldRef>,
     nested_queries: &[ReadQuery],
 ) -> SelectedFields {
-    parent_relation.map(|rf| {
-        let field = rf.related_field();
-
-        if field.is_inlined_on_enclosing_model() {
-            selected_fields.add_relation(field);
+    if let Some(rf) = parent_relation {
+        for field in rf.related_field().linking_fields() {
+            selected_fields.add(field);
         }
-    });
+    }
 
     for nested in nested_queries {
         if let ReadQuery::RelatedRecordsQuery(ref rq) = nested {
-            if rq.parent_field.is_inlined_on_enclosing_model() {
-                selected_fields.add_relation(rq.parent_field.clone());
+            for field in rq.parent_field.linking_fields() {
+                selected_fields.add(field);
             }
         }
     }
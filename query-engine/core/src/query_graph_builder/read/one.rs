@@ -18,24 +18,28 @@ impl Builder<ReadQuery> for ReadOneRecordBuilder {
     /// Builds a read query tree from a parsed top-level field of a query
     /// Unwraps are safe because of query validation that ensures conformity to the query schema.
     fn build(mut self) -> QueryGraphBuilderResult<ReadQuery> {
+        let include_deleted = extractors::extract_include_deleted(&mut self.field.arguments)?;
         let filter = match self.field.arguments.lookup("where") {
             Some(where_arg) => {
                 let arg: ParsedInputMap = where_arg.value.try_into()?;
 
-                arg.assert_size(1)?;
-                arg.assert_non_null()?;
-
-                Some(extractors::extract_filter(arg, &self.model, false)?)
+                Some(extractors::extract_unique_filter(arg, &self.model)?)
             }
             None => None,
         };
 
+        let filter = if include_deleted {
+            filter
+        } else {
+            extractors::exclude_soft_deleted(filter, &self.model)
+        };
+
         let name = self.field.name;
         let alias = self.field.alias;
         let model = self.model;
         let nested_fields = self.field.nested_fields.unwrap().fields;
-        let selection_order: Vec<String> = collect_selection_order(&nested_fields);
-        let selected_fields = collect_selected_fields(&nested_fields, &model);
+        let selection_order: Vec<(String, String)> = collect_selection_order(&nested_fields);
+        let selected_fields = collect_selected_fields(&nested_fields, &model)?;
         let nested = collect_nested_queries(nested_fields, &model)?;
         let selected_fields = merge_inlined_relation_fields(selected_fields, None, &nested);
 
@@ -20,13 +20,19 @@ impl ReadRelatedRecordsBuilder {
 }
 
 impl Builder<ReadQuery> for ReadRelatedRecordsBuilder {
-    fn build(self) -> QueryGraphBuilderResult<ReadQuery> {
-        let args = extractors::extract_query_args(self.field.arguments, &self.model)?;
+    fn build(mut self) -> QueryGraphBuilderResult<ReadQuery> {
+        let include_deleted = extractors::extract_include_deleted(&mut self.field.arguments)?;
+        let mut args = extractors::extract_query_args(self.field.arguments, &self.model)?;
+
+        if !include_deleted {
+            args.filter = extractors::exclude_soft_deleted(args.filter, &self.model);
+        }
+
         let name = self.field.name;
         let alias = self.field.alias;
         let sub_selections = self.field.nested_fields.unwrap().fields;
-        let selection_order: Vec<String> = collect_selection_order(&sub_selections);
-        let selected_fields = collect_selected_fields(&sub_selections, &self.model);
+        let selection_order: Vec<(String, String)> = collect_selection_order(&sub_selections);
+        let selected_fields = collect_selected_fields(&sub_selections, &self.model)?;
         let nested = collect_nested_queries(sub_selections, &self.model)?;
         let parent_field = self.parent;
         let selected_fields = merge_inlined_relation_fields(selected_fields, Some(parent_field.clone()), &nested);
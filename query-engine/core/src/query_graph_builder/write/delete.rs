@@ -10,8 +10,8 @@ use std::{convert::TryInto, sync::Arc};
 
 /// Creates a top level delete record query and adds it to the query graph.
 pub fn delete_record(graph: &mut QueryGraph, model: ModelRef, mut field: ParsedField) -> QueryGraphBuilderResult<()> {
-    let where_arg = field.arguments.lookup("where").unwrap();
-    let filter = extract_filter(where_arg.value.try_into()?, &model, false)?;
+    let where_arg = field.arguments.lookup_required("where")?;
+    let filter = extract_unique_filter(where_arg.value.try_into()?, &model)?;
 
     // Prefetch read query for the delete
     let mut read_query = ReadOneRecordBuilder::new(field, Arc::clone(&model)).build()?;
@@ -26,6 +26,7 @@ pub fn delete_record(graph: &mut QueryGraph, model: ModelRef, mut field: ParsedF
     let delete_node = graph.create_node(delete_query);
 
     utils::insert_deletion_checks(graph, &model, &read_node, &delete_node)?;
+    utils::insert_referential_actions(graph, &model, &read_node, &delete_node)?;
 
     graph.create_edge(
         &read_node,
@@ -59,8 +60,15 @@ pub fn delete_many_records(
         None => Filter::empty(),
     };
 
+    let limit = utils::extract_limit(&mut field)?;
     let model_id = model.primary_identifier();
-    let read_query = utils::read_ids_infallible(model.clone(), model_id, filter.clone());
+
+    // With a limit, only the limited IDs are checked and deleted.
+    let read_query = match limit {
+        Some(limit) => utils::read_limited_ids(model.clone(), filter.clone(), limit),
+        None => utils::read_ids_infallible(model.clone(), model_id.clone(), filter.clone()),
+    };
+
     let delete_many = WriteQuery::DeleteManyRecords(DeleteManyRecords {
         model: model.clone(),
         filter,
@@ -70,11 +78,14 @@ pub fn delete_many_records(
     let delete_many_node = graph.create_node(Query::Write(delete_many));
 
     utils::insert_deletion_checks(graph, &model, &read_query_node, &delete_many_node)?;
-    graph.create_edge(
-        &read_query_node,
-        &delete_many_node,
-        QueryGraphDependency::ExecutionOrder,
-    )?;
+    utils::insert_referential_actions(graph, &model, &read_query_node, &delete_many_node)?;
+
+    let dependency = match limit {
+        Some(_) => utils::restrict_to_parent_ids(model_id),
+        None => QueryGraphDependency::ExecutionOrder,
+    };
+
+    graph.create_edge(&read_query_node, &delete_many_node, dependency)?;
 
     Ok(())
 }
@@ -3,6 +3,7 @@ mod create;
 mod delete;
 mod disconnect;
 mod nested;
+mod routine;
 mod update;
 mod upsert;
 mod utils;
@@ -13,5 +14,6 @@ use super::*;
 // Expose top level write operation builder functions.
 pub use create::create_record;
 pub use delete::{delete_many_records, delete_record};
+pub use routine::call_routine;
 pub use update::{update_many_records, update_record};
 pub use upsert::upsert_record;
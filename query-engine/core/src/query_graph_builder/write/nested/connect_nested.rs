@@ -5,11 +5,12 @@ use crate::{
     query_graph::{Node, NodeRef, QueryGraph, QueryGraphDependency},
     ParsedInputMap, ParsedInputValue, QueryResult,
 };
-use connector::{Filter, ScalarCompare};
+use connector::Filter;
 use itertools::Itertools;
 use prisma_models::{ModelRef, RelationFieldRef};
 use std::convert::TryInto;
 use std::sync::Arc;
+use utils::IdFilter;
 
 /// Handles nested connect cases.
 ///
@@ -30,10 +31,7 @@ pub fn connect_nested_connect(
         .map(|value: ParsedInputValue| {
             let value: ParsedInputMap = value.try_into()?;
 
-            value.assert_size(1)?;
-            value.assert_non_null()?;
-
-            extract_filter(value, &child_model, false)
+            extract_unique_filter(value, &child_model)
         })
         .collect::<QueryGraphBuilderResult<Vec<Filter>>>()?
         .into_iter()
@@ -159,19 +157,19 @@ fn handle_one_to_many(
     let child_model_identifier = parent_relation_field.related_model().primary_identifier();
 
     if parent_relation_field.relation_is_inlined_in_parent() {
-        let read_query = utils::read_ids_infallible(child_model.clone(), child_model_identifier, child_filter);
+        let read_query = utils::read_ids_infallible(child_model.clone(), child_model_identifier.clone(), child_filter);
         let child_node = graph.create_node(read_query);
 
-        // For the injection, we need the name of the field on the inlined side, in this case the parent.
-        let relation_field_name = parent_relation_field.name.clone();
+        // For the injection, we need the linking fields of the inlined side, in this case the parent.
+        let linking_fields = parent_relation_field.linking_fields();
 
         // We need to swap the read node and the parent because the inlining is done in the parent, and we need to fetch the IDs first.
-        graph.mark_nodes(&parent_node, &child_node);
+        graph.mark_nodes(&parent_node, &child_node, parent_relation_field);
 
         graph.create_edge(
                  &parent_node,
                  &child_node,
-                 QueryGraphDependency::ParentIds(parent_model_identifier, Box::new(move |mut child_node, mut parent_ids| {
+                 QueryGraphDependency::ParentIds(child_model_identifier, Box::new(move |mut child_node, mut parent_ids| {
                      let parent_id = match parent_ids.pop() {
                          Some(pid) => Ok(pid),
                          None => Err(QueryGraphBuilderError::AssertionError(format!(
@@ -180,7 +178,7 @@ fn handle_one_to_many(
                      }?;
 
                      if let Node::Query(Query::Write(ref mut wq)) = child_node {
-                         wq.inject_field_arg(relation_field_name, parent_id.single_value());
+                         wq.inject_id_into_args(linking_fields.assimilate(parent_id)?);
                      }
 
                      Ok(child_node)
@@ -191,8 +189,8 @@ fn handle_one_to_many(
         let update_node = utils::update_records_node_placeholder(graph, child_filter, Arc::clone(child_model));
         let check_node = graph.create_node(Node::Empty);
 
-        // For the injection, we need the name of the field on the inlined side, in this case the child.
-        let relation_field_name = parent_relation_field.related_field().name.clone();
+        // For the injection, we need the linking fields of the inlined side, in this case the child.
+        let linking_fields = parent_relation_field.related_field().linking_fields();
 
         graph.create_edge(
              &parent_node,
@@ -206,7 +204,7 @@ fn handle_one_to_many(
                  }?;
 
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-                     wq.inject_field_arg(relation_field_name, parent_id.single_value());
+                     wq.inject_id_into_args(linking_fields.assimilate(parent_id)?);
                  }
 
                  Ok(child_node)
@@ -329,15 +327,6 @@ fn handle_one_to_one(
     filter: Filter,
     child_model: &ModelRef,
 ) -> QueryGraphBuilderResult<()> {
-    let child_model_id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
-    let parent_model_id = parent_relation_field
-        .model()
-        .fields()
-        .find_singular_id()
-        .unwrap()
-        .upgrade()
-        .unwrap();
-
     let parent_model_identifier = parent_relation_field.model().primary_identifier();
     let child_model_identifier = parent_relation_field.related_model().primary_identifier();
 
@@ -360,7 +349,7 @@ fn handle_one_to_one(
     let read_new_child_node = graph.create_node(read_query);
 
     // We always start with the read node in a nested connect 1:1 scenario.
-    graph.mark_nodes(&parent_node, &read_new_child_node);
+    graph.mark_nodes(&parent_node, &read_new_child_node, parent_relation_field);
 
     // Next is the check for (and possible disconnect of) an existing parent.
     // Those checks are performed on the new child node, hence we use the child relation field side ("backrelation").
@@ -368,11 +357,8 @@ fn handle_one_to_one(
         utils::insert_existing_1to1_related_model_checks(graph, &read_new_child_node, &child_relation_field)?;
     }
 
-    let relation_field_name = if relation_inlined_parent {
-        parent_relation_field.name.clone()
-    } else {
-        child_relation_field.name.clone()
-    };
+    let parent_linking_fields = parent_relation_field.linking_fields();
+    let parent_linking_fields_pc = parent_linking_fields.clone();
 
     graph.create_edge(
          &parent_node,
@@ -386,8 +372,7 @@ fn handle_one_to_one(
              // This takes care of cases where the relation is inlined, CREATE ONLY. See doc comment for explanation.
              if relation_inlined_parent && parent_is_create {
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-//                     wq.inject_id(parent_id);
-                     wq.inject_field_arg(relation_field_name, parent_id.single_value());
+                     wq.inject_id_into_args(parent_linking_fields_pc.assimilate(parent_id)?);
                  }
              }
 
@@ -406,7 +391,7 @@ fn handle_one_to_one(
     // If the relation is inlined on the child, we also need to update the child to connect it to the parent.
     if !relation_inlined_parent {
         let update_node = utils::update_records_node_placeholder(graph, Filter::empty(), Arc::clone(child_model));
-        let relation_field_name = child_relation_field.name.clone();
+        let child_linking_fields = child_relation_field.linking_fields();
 
         graph.create_edge(
              &read_new_child_node,
@@ -418,8 +403,7 @@ fn handle_one_to_one(
                  }?;
 
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-//                     wq.inject_id(parent_id);
-                     wq.add_filter(child_model_id_field.clone().data_source_field().equals(parent_id.single_value()));
+                     wq.add_filter(parent_id.filter());
                  }
 
                  Ok(child_node)
@@ -429,14 +413,14 @@ fn handle_one_to_one(
         graph.create_edge(
              &parent_node,
              &update_node,
-             QueryGraphDependency::ParentIds(parent_model_identifier.clone(), Box::new(|mut child_node, mut parent_ids| {
+             QueryGraphDependency::ParentIds(parent_model_identifier.clone(), Box::new(move |mut child_node, mut parent_ids| {
                  let parent_id = match parent_ids.pop() {
                      Some(pid) => Ok(pid),
                      None => Err(QueryGraphBuilderError::AssertionError(format!("[Query Graph] Expected a valid parent ID to be present for a nested connect on a one-to-one relation, updating inlined on child."))),
                  }?;
 
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-                     wq.inject_field_arg(relation_field_name, parent_id.single_value());
+                     wq.inject_id_into_args(child_linking_fields.assimilate(parent_id)?);
                  }
 
                  Ok(child_node)
@@ -446,22 +430,19 @@ fn handle_one_to_one(
         // Relation is inlined on the Parent and a non-create.
         // Create an update node for Parent to set the connection to the child.
         let parent_model = parent_relation_field.model();
-        let relation_field_name = parent_relation_field.name.clone();
-        //        let parent_model_id = parent_model.fields().id();
         let update_node = utils::update_records_node_placeholder(graph, Filter::empty(), parent_model);
 
         graph.create_edge(
              &read_new_child_node,
              &update_node,
-             QueryGraphDependency::ParentIds(parent_model_identifier.clone(), Box::new(|mut child_node, mut parent_ids| {
+             QueryGraphDependency::ParentIds(child_model_identifier.clone(), Box::new(move |mut child_node, mut parent_ids| {
                  let parent_id = match parent_ids.pop() {
                      Some(pid) => Ok(pid),
                      None => Err(QueryGraphBuilderError::AssertionError(format!("[Query Graph] Expected a valid parent ID to be present for a nested connect on a one-to-one relation, updating inlined on parent."))),
                  }?;
 
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-//                     wq.inject_id_into_args(parent_id);
-                     wq.inject_field_arg(relation_field_name, parent_id.single_value());
+                     wq.inject_id_into_args(parent_linking_fields.assimilate(parent_id)?);
                  }
 
                  Ok(child_node)
@@ -478,8 +459,7 @@ fn handle_one_to_one(
                  }?;
 
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-//                     wq.inject_id(parent_id);
-                     wq.add_filter(parent_model_id.clone().data_source_field().equals(parent_id.single_value()));
+                     wq.add_filter(parent_id.filter());
                  }
 
                  Ok(child_node)
@@ -4,9 +4,10 @@ use crate::{
     query_graph::{Node, NodeRef, QueryGraph, QueryGraphDependency},
     ParsedInputValue,
 };
-use connector::{Filter, ScalarCompare};
+use connector::Filter;
 use prisma_models::{ModelRef, RelationFieldRef};
 use std::{convert::TryInto, sync::Arc};
+use utils::IdFilter;
 
 /// Handles nested create cases.
 /// The resulting graph can take multiple forms, based on the relation type to the parent model.
@@ -88,7 +89,7 @@ fn handle_many_to_many(
 /// In case the relation link used to satisfy the relation is NOT the same as the primary ID of the
 /// parent model (i.e. `Create Child` here) and it can be determined that the parent operation does
 /// not return the necessary set of fields, we need to reload (`Reload Child`) the created record to
-/// be able to write the correct inlined data into `Parent`. NOTE: Currently we always insert the reload!
+/// be able to write the correct inlined data into `Parent`.
 ///
 /// Example finalized (with swaps already performed) graph:
 /// ```text
@@ -122,7 +123,7 @@ fn handle_many_to_many(
 /// We can have the parent operation first, then do the child create(s) and
 /// insert the parent ID into the inline relation field. We need to reload the
 /// parent node if it doesn't yield the necessary fields in the result to satisfy
-/// the relation inlining. (Todo same as above, we are always reloading right now)
+/// the relation inlining.
 ///
 /// Example graph for 2 children:
 /// ```text
@@ -160,9 +161,11 @@ fn handle_one_to_many(
             utils::insert_node_reload(graph, &parent_relation_field.related_field(), child_node.clone())?;
 
         // We need to swap the create node (with reload) and the parent because the inlining is done in the parent.
-        graph.mark_nodes(&parent_node, &child_node);
-        // FIXME: AUMFIDARR
-        //        graph.mark_nodes(&parent_node, &reload_child_node);
+        graph.mark_nodes(&parent_node, &child_node, parent_relation_field);
+
+        if reload_child_node != child_node {
+            graph.mark_nodes(&parent_node, &reload_child_node, parent_relation_field);
+        }
 
         let child_model_id = parent_relation_field.related_field().linking_fields();
         let parent_model_id = parent_relation_field.linking_fields();
@@ -340,27 +343,32 @@ fn handle_one_to_one(
     }
 
     // If the relation is inlined on the parent, we swap the create and the parent to have the child ID for inlining.
-    let (parent_node, child_node, relation_field_name) = if relation_inlined_parent {
-        // For the injection, we need the name of the field on the inlined side, in this case the parent.
-        let relation_field_name = parent_relation_field.name.clone();
-
+    // The IDs of the record written first are injected into the linking fields of the inlined side.
+    let (parent_node, child_node, source_identifier, inlined_linking_fields) = if relation_inlined_parent {
         // We need to swap the read node and the parent because the inlining is done in the parent, and we need to fetch the ID first.
-        graph.mark_nodes(&parent_node, &create_node);
+        graph.mark_nodes(&parent_node, &create_node, parent_relation_field);
         // let (parent_node, child_node) = utils::swap_nodes(graph, parent_node, create_node)?;
 
-        (parent_node, create_node, relation_field_name)
+        (
+            parent_node,
+            create_node,
+            child_model_identifier.clone(),
+            parent_relation_field.linking_fields(),
+        )
     } else {
-        // For the injection, we need the name of the field on the inlined side, in this case the child.
-        let relation_field_name = parent_relation_field.related_field().name.clone();
-
-        (parent_node, create_node, relation_field_name)
+        (
+            parent_node,
+            create_node,
+            parent_model_identifier.clone(),
+            child_relation_field.linking_fields(),
+        )
     };
 
-    let relation_field_name_pc = relation_field_name.clone();
+    let inlined_linking_fields_pc = inlined_linking_fields.clone();
     graph.create_edge(
          &parent_node,
          &child_node,
-         QueryGraphDependency::ParentIds(child_model_identifier.clone(), Box::new(move |mut child_node, mut parent_ids| {
+         QueryGraphDependency::ParentIds(source_identifier, Box::new(move |mut child_node, mut parent_ids| {
              let parent_id = match parent_ids.pop() {
                  Some(pid) => Ok(pid),
                  None => Err(QueryGraphBuilderError::AssertionError(format!(
@@ -369,10 +377,9 @@ fn handle_one_to_one(
              }?;
 
              // We ONLY inject creates here. Check doc comment for explanation.
-             if let Node::Query(Query::Write(WriteQuery::CreateRecord(ref mut cr))) = child_node {
-                 for (_dsf, value) in parent_id.pairs {
-                     // FIXME: use relation_field_name_pc works only if there's only one iteration in the loop
-                    cr.args.insert(relation_field_name_pc.clone(), value);
+             if let Node::Query(Query::Write(ref mut wq)) = child_node {
+                 if let WriteQuery::CreateRecord(_) = wq {
+                     wq.inject_id_into_args(inlined_linking_fields_pc.assimilate(parent_id)?);
                  }
              }
 
@@ -385,26 +392,19 @@ fn handle_one_to_one(
     // For explanation see doc comment.
     if relation_inlined_parent && !parent_is_create {
         let parent_model = parent_relation_field.model();
-        let parent_model_id = parent_model
-            .fields()
-            .find_singular_id()
-            .expect("No id field found")
-            .upgrade()
-            .unwrap();
         let update_node = utils::update_records_node_placeholder(graph, Filter::empty(), parent_model);
 
         graph.create_edge(
              &child_node,
              &update_node,
-             QueryGraphDependency::ParentIds(child_model_identifier, Box::new(|mut child_node, mut parent_ids| {
+             QueryGraphDependency::ParentIds(child_model_identifier, Box::new(move |mut child_node, mut parent_ids| {
                  let parent_id = match parent_ids.pop() {
                      Some(pid) => Ok(pid),
                      None => Err(QueryGraphBuilderError::AssertionError(format!("[Query Graph] Expected a valid parent ID to be present for a nested create on a one-to-one relation, updating inlined on parent."))),
                  }?;
 
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-//                     wq.inject_id_into_args(parent_id);
-                     wq.inject_field_arg(relation_field_name, parent_id.single_value());
+                     wq.inject_id_into_args(inlined_linking_fields.assimilate(parent_id)?);
                  }
 
                  Ok(child_node)
@@ -421,8 +421,7 @@ fn handle_one_to_one(
                  }?;
 
                  if let Node::Query(Query::Write(ref mut wq)) = child_node {
-//                     wq.inject_id_into_args(parent_id);
-                     wq.add_filter(parent_model_id.data_source_field().equals(parent_id.single_value()));
+                     wq.add_filter(parent_id.filter());
                  }
 
                  Ok(child_node)
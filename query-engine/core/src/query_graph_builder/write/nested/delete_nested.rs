@@ -2,11 +2,12 @@ use super::*;
 use crate::{
     query_ast::*,
     query_graph::{Node, NodeRef, QueryGraph, QueryGraphDependency},
-    InputAssertions, ParsedInputMap, ParsedInputValue,
+    ParsedInputMap, ParsedInputValue,
 };
-use connector::{Filter, ScalarCompare};
+use connector::Filter;
 use prisma_models::{ModelRef, PrismaValue, RelationFieldRef};
 use std::{convert::TryInto, sync::Arc};
+use utils::IdFilter;
 
 /// Adds a delete (single) record node to the graph and connects it to the parent.
 /// Auxiliary nodes may be added to support the deletion process, e.g. extra read nodes.
@@ -28,7 +29,6 @@ pub fn connect_nested_delete(
     value: ParsedInputValue,
     child_model: &ModelRef,
 ) -> QueryGraphBuilderResult<()> {
-    let parent_model_identifier = parent_relation_field.model().primary_identifier();
     let child_model_identifier = parent_relation_field.related_model().primary_identifier();
 
     if parent_relation_field.is_list {
@@ -37,10 +37,7 @@ pub fn connect_nested_delete(
             .map(|value: ParsedInputValue| {
                 let value: ParsedInputMap = value.try_into()?;
 
-                value.assert_size(1)?;
-                value.assert_non_null()?;
-
-                extract_filter(value, &child_model, false)
+                extract_unique_filter(value, &child_model)
             })
             .collect::<QueryGraphBuilderResult<Vec<Filter>>>()?;
 
@@ -52,16 +49,11 @@ pub fn connect_nested_delete(
         });
 
         let delete_many_node = graph.create_node(Query::Write(delete_many));
-        let id_field = child_model
-            .fields()
-            .find_singular_id()
-            .expect("must have a singular field")
-            .upgrade()
-            .unwrap();
         let find_child_records_node =
             utils::insert_find_children_by_parent_node(graph, parent_node, parent_relation_field, or_filter)?;
 
         utils::insert_deletion_checks(graph, child_model, &find_child_records_node, &delete_many_node)?;
+        utils::insert_referential_actions(graph, child_model, &find_child_records_node, &delete_many_node)?;
 
         let relation_name = parent_relation_field.relation().name.clone();
         let parent_name = parent_relation_field.model().name.clone();
@@ -82,9 +74,7 @@ pub fn connect_nested_delete(
                     }
 
                     if let Node::Query(Query::Write(WriteQuery::DeleteManyRecords(ref mut ur))) = node {
-                        let as_prisma_values = parent_ids.iter().map(|x| x.single_value()).collect();
-                        let ids_filter = id_field.data_source_field().is_in(as_prisma_values);
-                        let new_filter = Filter::and(vec![ur.filter.clone(), ids_filter]);
+                        let new_filter = Filter::and(vec![ur.filter.clone(), parent_ids.filter()]);
 
                         ur.filter = new_filter;
                     }
@@ -98,7 +88,6 @@ pub fn connect_nested_delete(
         let should_delete = if let PrismaValue::Boolean(b) = val { b } else { false };
 
         if should_delete {
-            let id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
             let find_child_records_node =
                 utils::insert_find_children_by_parent_node(graph, parent_node, parent_relation_field, Filter::empty())?;
 
@@ -108,11 +97,12 @@ pub fn connect_nested_delete(
             })));
 
             utils::insert_deletion_checks(graph, child_model, &find_child_records_node, &delete_record_node)?;
+            utils::insert_referential_actions(graph, child_model, &find_child_records_node, &delete_record_node)?;
 
             graph.create_edge(
                  &find_child_records_node,
                  &delete_record_node,
-                 QueryGraphDependency::ParentIds(parent_model_identifier, Box::new(move |mut node, mut parent_ids| {
+                 QueryGraphDependency::ParentIds(child_model_identifier, Box::new(move |mut node, mut parent_ids| {
                      let parent_id = match parent_ids.pop() {
                          Some(pid) => Ok(pid),
                          None => Err(QueryGraphBuilderError::AssertionError(format!(
@@ -121,9 +111,7 @@ pub fn connect_nested_delete(
                      }?;
 
                      if let Node::Query(Query::Write(ref mut wq)) = node {
-//                         wq.add_filter(id_field.equals(parent_id));
-//                         wq.inject_id(parent_id);
-                         wq.add_filter(id_field.data_source_field().equals(parent_id.single_value()));
+                         wq.add_filter(parent_id.filter());
                      }
 
                      Ok(node)
@@ -157,9 +145,8 @@ pub fn connect_nested_delete_many(
         });
 
         let delete_many_node = graph.create_node(Query::Write(delete_many));
-        let id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
-
         utils::insert_deletion_checks(graph, child_model, &find_child_records_node, &delete_many_node)?;
+        utils::insert_referential_actions(graph, child_model, &find_child_records_node, &delete_many_node)?;
 
         graph.create_edge(
             &find_child_records_node,
@@ -168,9 +155,7 @@ pub fn connect_nested_delete_many(
                 child_model_identifier.clone(),
                 Box::new(move |mut node, parent_ids| {
                     if let Node::Query(Query::Write(WriteQuery::DeleteManyRecords(ref mut ur))) = node {
-                        let as_prisma_values = parent_ids.iter().map(|x| x.single_value()).collect();
-                        let ids_filter = id_field.data_source_field().is_in(as_prisma_values);
-                        let new_filter = Filter::and(vec![ur.filter.clone(), ids_filter]);
+                        let new_filter = Filter::and(vec![ur.filter.clone(), parent_ids.filter()]);
 
                         ur.filter = new_filter;
                     }
@@ -1,12 +1,13 @@
 use super::*;
 use crate::{
     query_graph::{Node, NodeRef, QueryGraph, QueryGraphDependency},
-    FilteredQuery, InputAssertions, ParsedInputMap, ParsedInputValue, Query, WriteQuery,
+    FilteredQuery, ParsedInputMap, ParsedInputValue, Query, WriteQuery,
 };
-use connector::{Filter, ScalarCompare};
+use connector::Filter;
 use itertools::Itertools;
 use prisma_models::{ModelRef, PrismaValue, RelationFieldRef};
 use std::convert::TryInto;
+use utils::IdFilter;
 
 /// Handles nested disconnect cases.
 ///
@@ -28,10 +29,7 @@ pub fn connect_nested_disconnect(
             .map(|value: ParsedInputValue| {
                 let value: ParsedInputMap = value.try_into()?;
 
-                value.assert_size(1)?;
-                value.assert_non_null()?;
-
-                extract_filter(value, &child_model, false)
+                extract_unique_filter(value, &child_model)
             })
             .collect::<QueryGraphBuilderResult<Vec<Filter>>>()?
             .into_iter()
@@ -59,10 +57,7 @@ pub fn connect_nested_disconnect(
                     .map(|value: ParsedInputValue| {
                         let value: ParsedInputMap = value.try_into()?;
 
-                        value.assert_size(1)?;
-                        value.assert_non_null()?;
-
-                        extract_filter(value, &child_model, false)
+                        extract_unique_filter(value, &child_model)
                     })
                     .collect::<QueryGraphBuilderResult<Vec<Filter>>>()?
                     .into_iter()
@@ -184,42 +179,47 @@ fn handle_one_to_x(
         node_to_attach,
         node_to_check,
         model_to_update,
-        relation_field_name,
-        id_field,
+        relation_field,
         expected_disconnects,
         primary_identifier,
+        check_identifier,
     ) = if parent_relation_field.relation_is_inlined_in_parent() {
         let parent_model = parent_relation_field.model();
-        let relation_field_name = parent_relation_field.name.clone();
-        let parent_model_id = parent_model.fields().find_singular_id().unwrap().upgrade().unwrap();
         let primary_identifier = parent_model.primary_identifier();
+        let check_identifier = child_relation_field.model().primary_identifier();
 
         (
             parent_node,
             &find_child_records_node,
             parent_model,
-            relation_field_name,
-            parent_model_id,
+            parent_relation_field,
             std::cmp::max(filter_size, 1),
             primary_identifier,
+            check_identifier,
         )
     } else {
         let child_model = child_relation_field.model();
-        let relation_field_name = child_relation_field.name.clone();
-        let child_model_id = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
         let primary_identifier = child_model.primary_identifier();
+        let check_identifier = parent_relation_field.model().primary_identifier();
 
         (
             &find_child_records_node,
             parent_node,
             child_model,
-            relation_field_name,
-            child_model_id,
+            &child_relation_field,
             1,
             primary_identifier,
+            check_identifier,
         )
     };
 
+    // The relation is disconnected by nulling out all foreign key columns of the inlined side.
+    let fk_field_names: Vec<String> = relation_field
+        .data_source_fields()
+        .iter()
+        .map(|dsf| dsf.name.clone())
+        .collect();
+
     let update_node = utils::update_records_node_placeholder(graph, Filter::empty(), model_to_update);
     let relation_name = parent_relation_field.relation().name.clone();
     let parent_name = parent_relation_field.model().name.clone();
@@ -230,7 +230,7 @@ fn handle_one_to_x(
         node_to_attach,
         &update_node,
         QueryGraphDependency::ParentIds(
-            primary_identifier.clone(),
+            primary_identifier,
             Box::new(move |mut child_node, mut parent_ids| {
                 if parent_ids.len() == 0 {
                     return Err(QueryGraphBuilderError::RecordsNotConnected {
@@ -243,27 +243,19 @@ fn handle_one_to_x(
                 // Handle finder / filter injection
                 match child_node {
                     Node::Query(Query::Write(WriteQuery::UpdateManyRecords(ref mut ur))) => {
-                        ur.filter = Filter::or(
-                            parent_ids
-                                .into_iter()
-                                .map(|id| id_field.data_source_field().clone().equals(id.single_value()))
-                                .collect::<Vec<Filter>>(),
-                        )
+                        ur.filter = parent_ids.filter();
                     }
 
-                    Node::Query(Query::Write(ref mut wq)) => wq.add_filter(
-                        id_field
-                            .data_source_field()
-                            .equals(parent_ids.pop().unwrap().single_value()),
-                    ),
+                    Node::Query(Query::Write(ref mut wq)) => wq.add_filter(parent_ids.pop().unwrap().filter()),
 
                     _ => unimplemented!(),
                 };
 
                 // Handle arg injection
                 if let Node::Query(Query::Write(ref mut wq)) = child_node {
-                    //                    wq.inject_non_list_arg(relation_field_name, PrismaValue::Null);
-                    wq.inject_field_arg(relation_field_name, PrismaValue::Null);
+                    for field_name in fk_field_names {
+                        wq.inject_field_arg(field_name, PrismaValue::Null);
+                    }
                 }
 
                 Ok(child_node)
@@ -280,7 +272,7 @@ fn handle_one_to_x(
         node_to_check,
         &update_node,
         QueryGraphDependency::ParentIds(
-            primary_identifier.clone(),
+            check_identifier,
             Box::new(move |child_node, parent_ids| {
                 if parent_ids.len() != expected_disconnects {
                     return Err(QueryGraphBuilderError::RecordsNotConnected {
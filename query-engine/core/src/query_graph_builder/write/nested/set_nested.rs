@@ -1,9 +1,10 @@
 use super::*;
-use crate::{query_ast::*, query_graph::*, InputAssertions, ParsedInputValue};
-use connector::{Filter, ScalarCompare};
+use crate::{query_ast::*, query_graph::*, ParsedInputValue};
+use connector::Filter;
 use itertools::Itertools;
 use prisma_models::{ModelRef, PrismaValue, RelationFieldRef};
 use std::{collections::HashSet, convert::TryInto, iter::FromIterator, sync::Arc};
+use utils::IdFilter;
 
 /// Only for x-to-many relations.
 ///
@@ -25,10 +26,7 @@ pub fn connect_nested_set(
         .map(|value: ParsedInputValue| {
             let value: ParsedInputMap = value.try_into()?;
 
-            value.assert_size(1)?;
-            value.assert_non_null()?;
-
-            extract_filter(value, &child_model, false)
+            extract_unique_filter(value, &child_model)
         })
         .collect::<QueryGraphBuilderResult<Vec<Filter>>>()?
         .into_iter()
@@ -258,7 +256,7 @@ fn handle_one_to_many(
     // Update (connect) case: Check left diff IDs
     let connect_if_node = graph.create_node(Node::Flow(Flow::default_if()));
     let update_connect_node = utils::update_records_node_placeholder(graph, Filter::empty(), Arc::clone(&child_model));
-    let relation_field_name = parent_relation_field.related_field().name.clone();
+    let child_linking_fields = parent_relation_field.related_field().linking_fields();
 
     graph.create_edge(
         &diff_node,
@@ -291,7 +289,7 @@ fn handle_one_to_many(
                 }?;
 
                 if let Node::Query(Query::Write(ref mut wq)) = node {
-                    wq.inject_field_arg(relation_field_name, parent_id.single_value());
+                    wq.inject_id_into_args(child_linking_fields.assimilate(parent_id)?);
                 }
 
                 Ok(node)
@@ -299,7 +297,6 @@ fn handle_one_to_many(
         ),
     )?;
 
-    let id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
     graph.create_edge(
         &diff_node,
         &update_connect_node,
@@ -307,13 +304,7 @@ fn handle_one_to_many(
             let diff_result = result.as_diff_result().unwrap();
 
             if let Node::Query(Query::Write(WriteQuery::UpdateManyRecords(ref mut ur))) = node {
-                ur.filter = Filter::or(
-                    diff_result
-                        .left
-                        .iter()
-                        .map(|id| id_field.data_source_field().clone().equals(id.single_value()))
-                        .collect::<Vec<Filter>>(),
-                );
+                ur.filter = diff_result.left.iter().cloned().collect::<Vec<_>>().filter();
             }
 
             Ok(node)
@@ -324,10 +315,14 @@ fn handle_one_to_many(
     let disconnect_if_node = graph.create_node(Node::Flow(Flow::default_if()));
     let update_disconnect_node =
         utils::update_records_node_placeholder(graph, Filter::empty(), Arc::clone(&child_model));
-    let relation_field_name = parent_relation_field.related_field().name.clone();
+    let child_fk_field_names: Vec<String> = parent_relation_field
+        .related_field()
+        .data_source_fields()
+        .iter()
+        .map(|dsf| dsf.name.clone())
+        .collect();
     let child_side_required = parent_relation_field.related_field().is_required;
     let rf = Arc::clone(parent_relation_field);
-    let id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
 
     graph.create_edge(
         &diff_node,
@@ -357,17 +352,13 @@ fn handle_one_to_many(
             let diff_result = result.as_diff_result().unwrap();
 
             if let Node::Query(Query::Write(WriteQuery::UpdateManyRecords(ref mut ur))) = node {
-                ur.filter = Filter::or(
-                    diff_result
-                        .right
-                        .iter()
-                        .map(|id| id_field.data_source_field().clone().equals(id.single_value()))
-                        .collect::<Vec<Filter>>(),
-                );
+                ur.filter = diff_result.right.iter().cloned().collect::<Vec<_>>().filter();
             }
 
             if let Node::Query(Query::Write(ref mut wq)) = node {
-                wq.inject_field_arg(relation_field_name, PrismaValue::Null);
+                for field_name in child_fk_field_names {
+                    wq.inject_field_arg(field_name, PrismaValue::Null);
+                }
             }
 
             Ok(node)
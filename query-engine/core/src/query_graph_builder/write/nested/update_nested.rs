@@ -3,11 +3,12 @@ use super::*;
 use crate::{
     query_ast::*,
     query_graph::{Node, NodeRef, QueryGraph, QueryGraphDependency},
-    InputAssertions, ParsedInputValue,
+    InputMapLookup, ParsedInputValue,
 };
-use connector::{Filter, ScalarCompare};
+use connector::Filter;
 use prisma_models::{ModelRef, RelationFieldRef};
 use std::{convert::TryInto, sync::Arc};
+use utils::IdFilter;
 
 /// Handles nested update (one) cases.
 /// The graph is expanded with the `Check` and `Update` nodes.
@@ -44,13 +45,10 @@ pub fn connect_nested_update(
             // This finder is used to read the children first, to make sure they're actually connected.
             // The update itself operates on the ID found by the read check.
             let mut map: ParsedInputMap = value.try_into()?;
-            let where_arg: ParsedInputMap = map.remove("where").unwrap().try_into()?;
+            let where_arg: ParsedInputMap = map.remove_required("where")?.try_into()?;
 
-            where_arg.assert_size(1)?;
-            where_arg.assert_non_null()?;
-
-            let filter = extract_filter(where_arg, &child_model, false)?;
-            let data_value = map.remove("data").unwrap();
+            let filter = extract_unique_filter(where_arg, &child_model)?;
+            let data_value = map.remove_required("data")?;
 
             (data_value, filter)
         } else {
@@ -62,8 +60,6 @@ pub fn connect_nested_update(
 
         let update_node =
             update::update_record_node(graph, Filter::empty(), Arc::clone(child_model), data.try_into()?)?;
-        let id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
-
         graph.create_edge(
             &find_child_records_node,
             &update_node,
@@ -78,7 +74,7 @@ pub fn connect_nested_update(
                     }?;
 
                     if let Node::Query(Query::Write(WriteQuery::UpdateRecord(ref mut ur))) = node {
-                        ur.add_filter(id_field.data_source_field().equals(parent_id.single_value()));
+                        ur.add_filter(parent_id.filter());
                     }
 
                     Ok(node)
@@ -101,8 +97,8 @@ pub fn connect_nested_update_many(
 
     for value in utils::coerce_vec(value) {
         let mut map: ParsedInputMap = value.try_into()?;
-        let where_arg = map.remove("where").unwrap();
-        let data_value = map.remove("data").unwrap();
+        let where_arg = map.remove_required("where")?;
+        let data_value = map.remove_required("data")?;
         let data_map: ParsedInputMap = data_value.try_into()?;
         let where_map: ParsedInputMap = where_arg.try_into()?;
 
@@ -121,8 +117,6 @@ pub fn connect_nested_update_many(
         });
 
         let update_many_node = graph.create_node(Query::Write(update_many));
-        let id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
-
         graph.create_edge(
             &find_child_records_node,
             &update_many_node,
@@ -130,9 +124,7 @@ pub fn connect_nested_update_many(
                 child_model_identifier.clone(),
                 Box::new(move |mut node, parent_ids| {
                     if let Node::Query(Query::Write(WriteQuery::UpdateManyRecords(ref mut ur))) = node {
-                        let as_prisma_values = parent_ids.iter().map(|x| x.single_value()).collect();
-                        let ids_filter = id_field.data_source_field().is_in(as_prisma_values);
-                        let new_filter = Filter::and(vec![ur.filter.clone(), ids_filter]);
+                        let new_filter = Filter::and(vec![ur.filter.clone(), parent_ids.filter()]);
 
                         ur.filter = new_filter;
                     }
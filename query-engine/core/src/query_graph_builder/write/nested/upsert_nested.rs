@@ -3,11 +3,12 @@ use crate::query_graph_builder::write::utils::coerce_vec;
 use crate::{
     query_ast::*,
     query_graph::{Flow, Node, NodeRef, QueryGraph, QueryGraphDependency},
-    InputAssertions, ParsedInputMap, ParsedInputValue,
+    InputMapLookup, ParsedInputMap, ParsedInputValue,
 };
-use connector::{Filter, ScalarCompare};
+use connector::Filter;
 use prisma_models::RelationFieldRef;
 use std::{convert::TryInto, sync::Arc};
+use utils::IdFilter;
 
 /// Handles a nested upsert.
 /// The constructed query graph can have different shapes based on the relation
@@ -95,17 +96,15 @@ pub fn connect_nested_upsert(
 
     for value in coerce_vec(value) {
         let mut as_map: ParsedInputMap = value.try_into()?;
-        let create_input = as_map.remove("create").expect("create argument is missing");
-        let update_input = as_map.remove("update").expect("update argument is missing");
+        let create_input = as_map.remove_required("create")?;
+        let update_input = as_map.remove_required("update")?;
 
         // Read child(ren) node
         let filter: Filter = if parent_relation_field.is_list {
-            let where_input: ParsedInputMap = as_map.remove("where").expect("where argument is missing").try_into()?;
+            let where_input: ParsedInputMap = as_map.remove_required("where")?.try_into()?;
+            assert_no_additional_filters(&where_input)?;
 
-            where_input.assert_size(1)?;
-            where_input.assert_non_null()?;
-
-            extract_filter(where_input, &child_model, false)?
+            extract_unique_filter(where_input, &child_model)?
         } else {
             Filter::empty()
         };
@@ -137,8 +136,6 @@ pub fn connect_nested_upsert(
             ),
         )?;
 
-        let id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
-
         graph.create_edge(
              &read_children_node,
              &update_node,
@@ -151,7 +148,7 @@ pub fn connect_nested_upsert(
                          ))),
                      }?;
 
-                     x.add_filter(id_field.data_source_field().equals(parent_id.single_value()));
+                     x.add_filter(parent_id.filter());
                  }
                  Ok(node)
              })),
@@ -167,18 +164,18 @@ pub fn connect_nested_upsert(
         } else {
             if parent_relation_field.relation_is_inlined_in_parent() {
                 let parent_model = parent_relation_field.model();
-                let related_field_name = parent_relation_field.name.clone();
+                let parent_model_identifier = parent_model.primary_identifier();
+                let parent_linking_fields = parent_relation_field.linking_fields();
 
                 // Update parent node
                 let update_node =
                     utils::update_records_node_placeholder(graph, Filter::empty(), Arc::clone(&parent_model));
-                let id_field = parent_model.fields().find_singular_id().unwrap().upgrade().unwrap();
 
                 // Edge to retrieve the finder
                 graph.create_edge(
                      &parent_node,
                      &update_node,
-                     QueryGraphDependency::ParentIds(child_model_identifier.clone(), Box::new(move |mut child_node, mut parent_ids| {
+                     QueryGraphDependency::ParentIds(parent_model_identifier, Box::new(move |mut child_node, mut parent_ids| {
                          let parent_id = match parent_ids.pop() {
                              Some(pid) => Ok(pid),
                              None => Err(QueryGraphBuilderError::AssertionError(format!(
@@ -187,7 +184,7 @@ pub fn connect_nested_upsert(
                          }?;
 
                          if let Node::Query(Query::Write(ref mut wq)) = child_node {
-                             wq.add_filter(id_field.data_source_field().equals(parent_id.single_value()));
+                             wq.add_filter(parent_id.filter());
                          }
 
                          Ok(child_node)
@@ -198,7 +195,7 @@ pub fn connect_nested_upsert(
                 graph.create_edge(
                      &create_node,
                      &update_node,
-                     QueryGraphDependency::ParentIds(child_model_identifier.clone(), Box::new(|mut child_node, mut parent_ids| {
+                     QueryGraphDependency::ParentIds(child_model_identifier.clone(), Box::new(move |mut child_node, mut parent_ids| {
                          let parent_id = match parent_ids.pop() {
                              Some(pid) => Ok(pid),
                              None => Err(QueryGraphBuilderError::AssertionError(format!(
@@ -207,7 +204,7 @@ pub fn connect_nested_upsert(
                          }?;
 
                          if let Node::Query(Query::Write(ref mut wq)) = child_node {
-                             wq.inject_field_arg(related_field_name, parent_id.single_value());
+                             wq.inject_id_into_args(parent_linking_fields.assimilate(parent_id)?);
                          }
 
                          Ok(child_node)
@@ -215,13 +212,14 @@ pub fn connect_nested_upsert(
                  )?;
             } else {
                 // Inlined on child
-                let related_field_name = parent_relation_field.related_field().name.clone();
+                let parent_model_identifier = parent_relation_field.model().primary_identifier();
+                let child_linking_fields = parent_relation_field.related_field().linking_fields();
 
                 // Edge to retrieve the child ID to inject (inject into the create)
                 graph.create_edge(
                      &parent_node,
                      &create_node,
-                     QueryGraphDependency::ParentIds(child_model_identifier.clone(), Box::new(|mut child_node, mut parent_ids| {
+                     QueryGraphDependency::ParentIds(parent_model_identifier, Box::new(move |mut child_node, mut parent_ids| {
                          let parent_id = match parent_ids.pop() {
                              Some(pid) => Ok(pid),
                              None => Err(QueryGraphBuilderError::AssertionError(format!(
@@ -230,7 +228,7 @@ pub fn connect_nested_upsert(
                          }?;
 
                          if let Node::Query(Query::Write(ref mut wq)) = child_node {
-                             wq.inject_field_arg(related_field_name, parent_id.single_value());
+                             wq.inject_id_into_args(child_linking_fields.assimilate(parent_id)?);
                          }
 
                          Ok(child_node)
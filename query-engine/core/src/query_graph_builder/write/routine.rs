@@ -0,0 +1,24 @@
+use super::*;
+use crate::{query_ast::*, query_graph::QueryGraph, ArgumentListLookup, ParsedField};
+use connector::Routine;
+use prisma_models::PrismaValue;
+use std::convert::TryInto;
+
+/// Creates a top level routine call and adds it to the query graph.
+/// Arguments are matched to the parameters by name, parameters without argument are passed null.
+pub fn call_routine(graph: &mut QueryGraph, routine: Routine, mut field: ParsedField) -> QueryGraphBuilderResult<()> {
+    let arguments = routine
+        .parameters
+        .iter()
+        .map(|parameter| match field.arguments.lookup(&parameter.name) {
+            Some(arg) => Ok(arg.value.try_into()?),
+            None => Ok(PrismaValue::Null),
+        })
+        .collect::<QueryGraphBuilderResult<Vec<PrismaValue>>>()?;
+
+    graph.create_node(Query::Write(WriteQuery::CallRoutine(CallRoutine {
+        routine,
+        arguments,
+    })));
+    Ok(())
+}
@@ -3,7 +3,7 @@ use crate::query_graph_builder::write::write_args_parser::*;
 use crate::{
     query_ast::*,
     query_graph::{Node, NodeRef, QueryGraph, QueryGraphDependency},
-    ArgumentListLookup, InputAssertions, ParsedField, ParsedInputMap, ReadOneRecordBuilder,
+    ArgumentListLookup, ParsedField, ParsedInputMap, ReadOneRecordBuilder,
 };
 use connector::filter::Filter;
 use prisma_models::ModelRef;
@@ -13,18 +13,16 @@ use utils::IdFilter;
 /// Creates an update record query and adds it to the query graph, together with it's nested queries and companion read query.
 pub fn update_record(graph: &mut QueryGraph, model: ModelRef, mut field: ParsedField) -> QueryGraphBuilderResult<()> {
     // "where"
-    let where_arg: ParsedInputMap = field.arguments.lookup("where").unwrap().value.try_into()?;
+    let where_arg: ParsedInputMap = field.arguments.lookup_required("where")?.value.try_into()?;
 
-    where_arg.assert_size(1)?;
-    where_arg.assert_non_null()?;
-
-    let filter = extract_filter(where_arg, &model, false)?;
+    let filter = extract_unique_filter(where_arg, &model)?;
 
     // "data"
-    let data_argument = field.arguments.lookup("data").unwrap();
+    let data_argument = field.arguments.lookup_required("data")?;
     let data_map: ParsedInputMap = data_argument.value.try_into()?;
 
     let update_node = update_record_node(graph, filter, Arc::clone(&model), data_map)?;
+    utils::insert_emulated_update_checks(graph, &update_node, None)?;
 
     let read_query = ReadOneRecordBuilder::new(field, model.clone()).build()?;
     let read_node = graph.create_node(Query::Read(read_query));
@@ -66,15 +64,35 @@ pub fn update_many_records(
         None => Filter::empty(),
     };
 
-    let data_argument = field.arguments.lookup("data").unwrap();
+    let data_argument = field.arguments.lookup_required("data")?;
     let data_map: ParsedInputMap = data_argument.value.try_into()?;
     let update_args = WriteArgsParser::from(&model, data_map)?;
 
     let mut args = update_args.args;
     args.update_datetimes(Arc::clone(&model));
 
-    let update_many = WriteQuery::UpdateManyRecords(UpdateManyRecords { model, filter, args });
-    graph.create_node(Query::Write(update_many));
+    let limit = utils::extract_limit(&mut field)?;
+    let update_many = WriteQuery::UpdateManyRecords(UpdateManyRecords {
+        model: Arc::clone(&model),
+        filter: filter.clone(),
+        args,
+    });
+
+    let update_many_node = graph.create_node(Query::Write(update_many));
+
+    if let Some(limit) = limit {
+        let read_query_node = graph.create_node(utils::read_limited_ids(Arc::clone(&model), filter, limit));
+
+        graph.create_edge(
+            &read_query_node,
+            &update_many_node,
+            utils::restrict_to_parent_ids(model.primary_identifier()),
+        )?;
+
+        utils::insert_emulated_update_checks(graph, &update_many_node, Some(&read_query_node))?;
+    } else {
+        utils::insert_emulated_update_checks(graph, &update_many_node, None)?;
+    }
 
     Ok(())
 }
@@ -2,23 +2,21 @@ use super::*;
 use crate::{
     query_ast::*,
     query_graph::{Flow, Node, QueryGraph, QueryGraphDependency},
-    ArgumentListLookup, InputAssertions, ParsedField, ParsedInputMap, ReadOneRecordBuilder,
+    ArgumentListLookup, ParsedField, ParsedInputMap, ReadOneRecordBuilder,
 };
 use prisma_models::ModelRef;
 use std::{convert::TryInto, sync::Arc};
 use utils::IdFilter;
 
 pub fn upsert_record(graph: &mut QueryGraph, model: ModelRef, mut field: ParsedField) -> QueryGraphBuilderResult<()> {
-    let where_arg: ParsedInputMap = field.arguments.lookup("where").unwrap().value.try_into()?;
+    let where_arg: ParsedInputMap = field.arguments.lookup_required("where")?.value.try_into()?;
+    assert_no_additional_filters(&where_arg)?;
 
-    where_arg.assert_size(1)?;
-    where_arg.assert_non_null()?;
-
-    let filter = extract_filter(where_arg, &model, false)?;
+    let filter = extract_unique_filter(where_arg, &model)?;
     let model_id = model.primary_identifier();
 
-    let create_argument = field.arguments.lookup("create").unwrap();
-    let update_argument = field.arguments.lookup("update").unwrap();
+    let create_argument = field.arguments.lookup_required("create")?;
+    let update_argument = field.arguments.lookup_required("update")?;
 
     let child_read_query = utils::read_ids_infallible(model.clone(), model_id.clone(), filter.clone());
     let initial_read_node = graph.create_node(child_read_query);
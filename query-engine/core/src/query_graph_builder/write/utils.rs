@@ -1,12 +1,12 @@
 use crate::{
     query_ast::*,
-    query_graph::{Flow, Node, NodeRef, QueryGraph, QueryGraphDependency},
-    ParsedInputValue, QueryGraphBuilderError, QueryGraphBuilderResult,
+    query_graph::{Computation, Flow, Node, NodeRef, QueryGraph, QueryGraphDependency},
+    ArgumentListLookup, ParsedField, ParsedInputValue, QueryGraphBuilderError, QueryGraphBuilderResult,
 };
 use connector::{Filter, QueryArguments, ScalarCompare, WriteArgs};
 use itertools::Itertools;
 use prisma_models::{ModelIdentifier, ModelRef, PrismaValue, RecordIdentifier, RelationFieldRef, SelectedFields};
-use std::sync::Arc;
+use std::{convert::TryInto, sync::Arc};
 
 pub trait IdFilter {
     fn filter(self) -> Filter;
@@ -14,11 +14,7 @@ pub trait IdFilter {
 
 impl IdFilter for RecordIdentifier {
     fn filter(self) -> Filter {
-        let filters: Vec<Filter> = self
-            .pairs
-            .into_iter()
-            .map(|(field, value)| field.equals(value))
-            .collect();
+        let filters: Vec<Filter> = self.into_iter().map(|(field, value)| field.equals(value)).collect();
 
         Filter::and(filters)
     }
@@ -57,14 +53,20 @@ pub fn read_ids_infallible<T>(model: ModelRef, id: ModelIdentifier, filter: T) -
 where
     T: Into<Filter>,
 {
-    let selected_fields: SelectedFields = id.into();
     let filter: Filter = filter.into();
 
+    read_ids_with_args(model, id, filter.into())
+}
+
+/// Produces a non-failing read query that fetches records model IDs with the given query arguments.
+pub fn read_ids_with_args(model: ModelRef, id: ModelIdentifier, args: QueryArguments) -> Query {
+    let selected_fields: SelectedFields = id.into();
+
     let read_query = ReadQuery::ManyRecordsQuery(ManyRecordsQuery {
         name: "read_ids_infallible".into(), // this name only eases debugging
         alias: None,
         model,
-        args: filter.into(),
+        args,
         selected_fields,
         nested: vec![],
         selection_order: vec![],
@@ -73,6 +75,61 @@ where
     Query::Read(read_query)
 }
 
+/// Extracts the optional `limit` argument of many records writes.
+pub fn extract_limit(field: &mut ParsedField) -> QueryGraphBuilderResult<Option<i64>> {
+    let limit: Option<i64> = match field.arguments.lookup("limit") {
+        Some(arg) => arg.value.try_into()?,
+        None => None,
+    };
+
+    match limit {
+        Some(limit) if limit < 0 => Err(QueryGraphBuilderError::InputError(format!(
+            "The limit of a many records write must not be negative, got {}.",
+            limit
+        ))),
+        limit => Ok(limit),
+    }
+}
+
+/// Produces a read query for the IDs of the records a many records write with a `limit` applies to:
+/// the first `limit` records matching the filter, ordered by ID.
+///
+/// The write is restricted to these IDs with `restrict_to_parent_ids`, so that all connectors run it as a plain
+/// `UPDATE` or `DELETE`, no `LIMIT` clause on writes is required from the database.
+pub fn read_limited_ids(model: ModelRef, filter: Filter, limit: i64) -> Query {
+    let id = model.primary_identifier();
+    let args = QueryArguments {
+        filter: Some(filter),
+        first: Some(limit),
+        ..Default::default()
+    };
+
+    read_ids_with_args(model, id, args)
+}
+
+/// Dependency restricting a many records write to the IDs returned by the parent, in addition to its own filter.
+/// Records that changed in between and don't match the filter anymore are left alone.
+pub fn restrict_to_parent_ids(model_id: ModelIdentifier) -> QueryGraphDependency {
+    QueryGraphDependency::ParentIds(
+        model_id,
+        Box::new(|mut node, parent_ids| {
+            let ids_filter = parent_ids.filter();
+
+            match node {
+                Node::Query(Query::Write(WriteQuery::UpdateManyRecords(ref mut q))) => {
+                    q.filter = Filter::and(vec![q.filter.clone(), ids_filter]);
+                }
+                Node::Query(Query::Write(WriteQuery::DeleteManyRecords(ref mut q))) => {
+                    q.filter = Filter::and(vec![q.filter.clone(), ids_filter]);
+                }
+                _ => (),
+            };
+
+            Ok(node)
+        }),
+    )
+}
+
 /// Adds a read query to the query graph that finds related records by parent ID.
 /// Connects the parent node and the read node with an edge, which takes care of the
 /// node transformation based on the parent ID.
@@ -206,7 +263,6 @@ pub fn insert_existing_1to1_related_model_checks(
     let child_model_identifier = parent_relation_field.related_model().primary_identifier();
 
     let child_model = parent_relation_field.related_model();
-    let child_id_field = child_model.fields().find_singular_id().unwrap().upgrade().unwrap();
     let child_side_required = parent_relation_field.related_field().is_required;
     let relation_inlined_parent = parent_relation_field.relation_is_inlined_in_parent();
     let rf = Arc::clone(&parent_relation_field);
@@ -243,7 +299,13 @@ pub fn insert_existing_1to1_related_model_checks(
         ),
     )?;
 
-    let relation_field_name = parent_relation_field.related_field().name.clone();
+    // The existing child is disconnected by nulling out all of its foreign key columns.
+    let child_fk_field_names: Vec<String> = parent_relation_field
+        .related_field()
+        .data_source_fields()
+        .iter()
+        .map(|dsf| dsf.name.clone())
+        .collect();
 
     graph.create_edge(&if_node, &update_existing_child, QueryGraphDependency::Then)?;
     graph.create_edge(&read_existing_children, &update_existing_child, QueryGraphDependency::ParentIds(child_model_identifier.clone(), Box::new(move |mut child_node, mut child_ids| {
@@ -254,11 +316,11 @@ pub fn insert_existing_1to1_related_model_checks(
              }?;
 
              if let Node::Query(Query::Write(ref mut wq)) = child_node {
-////                 wq.add_filter(child_id.filter());
-//////                 wq.inject_field_arg(relation_field.into(), vec![]);
-////                 wq.inject_id(child_id);
-                 wq.add_filter(child_id_field.data_source_field().equals(child_id.single_value()));
-                 wq.inject_field_arg(relation_field_name, PrismaValue::Null);
+                 wq.add_filter(child_id.filter());
+
+                 for field_name in child_fk_field_names {
+                     wq.inject_field_arg(field_name, PrismaValue::Null);
+                 }
              }
 
              Ok(child_node)
@@ -273,6 +335,9 @@ pub fn insert_existing_1to1_related_model_checks(
 ///
 /// This function is usually part of a delete (`deleteOne` or `deleteMany`).
 /// Expects `parent_node` to return one or more IDs (for records of `model`) to be checked.
+/// Soft deleted models (`@@softDelete`) keep their records, no checks are required.
+/// Records related through a field of `model` with `onDelete: CASCADE` are deleted instead of checked, see
+/// `insert_referential_actions`.
 ///
 /// ## Example for a standard delete scenario
 /// - We have 2 relations, from `A` and `B` to `model`.
@@ -313,8 +378,16 @@ pub fn insert_deletion_checks(
     parent_node: &NodeRef,
     child_node: &NodeRef,
 ) -> QueryGraphBuilderResult<()> {
+    if model.soft_delete_field().is_some() {
+        return Ok(());
+    }
+
     let internal_model = model.internal_data_model();
-    let relation_fields = internal_model.fields_requiring_model(model);
+    let relation_fields: Vec<RelationFieldRef> = internal_model
+        .fields_requiring_model(model)
+        .into_iter()
+        .filter(|rf| !cascades_to_children(&rf.related_field()))
+        .collect();
     let mut check_nodes = vec![];
 
     if relation_fields.len() > 0 {
@@ -361,6 +434,329 @@ pub fn insert_deletion_checks(
     Ok(())
 }
 
+/// Inserts the referential actions of deleting the records of `model` between `parent_node` and `child_node`:
+/// - Records related through a field of `model` with `onDelete: CASCADE` are deleted, see `insert_cascading_deletes`.
+/// - If referential integrity is emulated by the engine (`relationMode = "prisma"`), the actions the foreign keys
+///   would otherwise execute in the database, see `insert_emulated_referential_actions`.
+///
+/// Restricting deletes of records that are required by other records is up to `insert_deletion_checks`.
+/// Expects `parent_node` to return the IDs of the records of `model` that are deleted by `child_node`.
+pub fn insert_referential_actions(
+    graph: &mut QueryGraph,
+    model: &ModelRef,
+    parent_node: &NodeRef,
+    child_node: &NodeRef,
+) -> QueryGraphBuilderResult<()> {
+    insert_referential_actions_along(graph, model, parent_node, child_node, &[])
+}
+
+/// `cascaded` holds the names of the relations the delete of `model` cascaded through.
+fn insert_referential_actions_along(
+    graph: &mut QueryGraph,
+    model: &ModelRef,
+    parent_node: &NodeRef,
+    child_node: &NodeRef,
+    cascaded: &[String],
+) -> QueryGraphBuilderResult<()> {
+    if model.soft_delete_field().is_some() {
+        return Ok(());
+    }
+
+    insert_cascading_deletes(graph, model, parent_node, child_node, cascaded)?;
+
+    if !model.internal_data_model().relation_mode.uses_foreign_keys() {
+        insert_emulated_referential_actions(graph, model, parent_node, child_node)?;
+    }
+
+    Ok(())
+}
+
+/// Deletes the records related through the fields of `model` with `onDelete: CASCADE` before the records of `model`
+/// are deleted by `child_node`. Only related records holding the foreign key of the relation are deleted, like a
+/// foreign key with `ON DELETE CASCADE` does. The related records get the deletion checks and referential actions of
+/// their own model in turn. A relation cascades at most once in a chain of deletes, so the delete of a record of a
+/// self relation cascades to its direct children only.
+///
+/// ```text
+///    ┌────────────────────┐
+///    │ Find Record IDs to │
+/// ┌──│       Delete       │
+/// │  └────────────────────┘
+/// │             │
+/// │             ▼
+/// │  ┌────────────────────┐
+/// │  │    Find Related    │──┐
+/// │  │      Records       │  │
+/// │  └────────────────────┘  │
+/// │             │            │
+/// │             ▼            │
+/// │  ┌────────────────────┐  │
+/// │  │ Delete Related     │  │ (Checks and actions of the related model)
+/// │  │ Records            │◀─┘
+/// │  └────────────────────┘
+/// │             │
+/// │             ▼
+/// │  ┌────────────────────┐
+/// └─▶│       Delete       │
+///    └────────────────────┘
+/// ```
+fn insert_cascading_deletes(
+    graph: &mut QueryGraph,
+    model: &ModelRef,
+    parent_node: &NodeRef,
+    child_node: &NodeRef,
+    cascaded: &[String],
+) -> QueryGraphBuilderResult<()> {
+    for rf in model.fields().cascading_relation() {
+        if !rf.relation_is_inlined_in_child() {
+            continue;
+        }
+
+        let relation_name = rf.relation().name.clone();
+
+        if cascaded.contains(&relation_name) {
+            continue;
+        }
+
+        let related_model = rf.related_model();
+        let read_related_node = insert_find_children_by_parent_node(graph, parent_node, &rf, Filter::empty())?;
+
+        let delete_related_node = graph.create_node(Query::Write(WriteQuery::DeleteManyRecords(DeleteManyRecords {
+            model: Arc::clone(&related_model),
+            filter: Filter::empty(),
+        })));
+
+        let mut cascaded = cascaded.to_vec();
+        cascaded.push(relation_name);
+
+        insert_deletion_checks(graph, &related_model, &read_related_node, &delete_related_node)?;
+        insert_referential_actions_along(
+            graph,
+            &related_model,
+            &read_related_node,
+            &delete_related_node,
+            &cascaded,
+        )?;
+
+        graph.create_edge(
+            &read_related_node,
+            &delete_related_node,
+            restrict_to_parent_ids(related_model.primary_identifier()),
+        )?;
+
+        graph.create_edge(&delete_related_node, child_node, QueryGraphDependency::ExecutionOrder)?;
+    }
+
+    Ok(())
+}
+
+/// Deleting a record of the model of `rf` deletes the related records holding the foreign key of the relation.
+fn cascades_to_children(rf: &RelationFieldRef) -> bool {
+    rf.cascades_on_delete() && rf.relation_is_inlined_in_child()
+}
+
+/// Inserts the referential actions the foreign keys of a relation would otherwise execute in the database,
+/// if referential integrity is emulated by the engine (`relationMode = "prisma"`).
+/// Required relations are already covered by the checks of `insert_deletion_checks`, this takes care of:
+/// - Nulling out the relation on records of related models that hold an optional reference to `model`.
+/// - Removing the rows of relation tables that reference the deleted records.
+///
+/// Expects `parent_node` to return the IDs of the records of `model` that are deleted by `child_node`.
+///
+/// ```text
+///    ┌────────────────────┐
+///    │ Find Record IDs to │
+/// ┌──│       Delete       │──────────┐
+/// │  └────────────────────┘          │
+/// │             │                    │
+/// │             ▼                    ▼
+/// │  ┌────────────────────┐ ┌────────────────┐
+/// │  │ Find Connected     │ │ Disconnect All │
+/// │  │ Optional Children  │ │(Relation Table)│
+/// │  └────────────────────┘ └────────────────┘
+/// │             │                    │
+/// │             ▼                    │
+/// │  ┌────────────────────┐          │
+/// │  │ Null Out Relation  │          │
+/// │  └────────────────────┘          │
+/// │             │                    │
+/// │             ▼                    │
+/// │  ┌────────────────────┐          │
+/// └─▶│       Delete       │◀─────────┘
+///    └────────────────────┘
+/// ```
+fn insert_emulated_referential_actions(
+    graph: &mut QueryGraph,
+    model: &ModelRef,
+    parent_node: &NodeRef,
+    child_node: &NodeRef,
+) -> QueryGraphBuilderResult<()> {
+    let model_identifier = model.primary_identifier();
+
+    for rf in model.fields().relation() {
+        if rf.relation().is_relation_table() {
+            let disconnect_node =
+                graph.create_node(Query::Write(WriteQuery::DisconnectAllRecords(DisconnectAllRecords {
+                    parent_ids: vec![],
+                    relation_field: Arc::clone(&rf),
+                })));
+
+            graph.create_edge(
+                parent_node,
+                &disconnect_node,
+                QueryGraphDependency::ParentIds(
+                    model_identifier.clone(),
+                    Box::new(|mut node, parent_ids| {
+                        if let Node::Query(Query::Write(WriteQuery::DisconnectAllRecords(ref mut q))) = node {
+                            q.parent_ids = parent_ids;
+                        }
+
+                        Ok(node)
+                    }),
+                ),
+            )?;
+
+            graph.create_edge(&disconnect_node, child_node, QueryGraphDependency::ExecutionOrder)?;
+        } else if rf.relation_is_inlined_in_child() && rf.related_field().is_optional() && !cascades_to_children(&rf) {
+            let child_model = rf.related_model();
+            let child_model_identifier = child_model.primary_identifier();
+            let relation_field_name = rf.related_field().name.clone();
+
+            let read_children_node = insert_find_children_by_parent_node(graph, parent_node, &rf, Filter::empty())?;
+            let update_children_node = update_records_node_placeholder(graph, Filter::empty(), child_model);
+
+            graph.create_edge(
+                &read_children_node,
+                &update_children_node,
+                QueryGraphDependency::ParentIds(
+                    child_model_identifier,
+                    Box::new(move |mut node, child_ids| {
+                        if let Node::Query(Query::Write(ref mut wq)) = node {
+                            wq.add_filter(child_ids.filter());
+                            wq.inject_field_arg(relation_field_name, PrismaValue::Null);
+                        }
+
+                        Ok(node)
+                    }),
+                ),
+            )?;
+
+            graph.create_edge(&update_children_node, child_node, QueryGraphDependency::ExecutionOrder)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Inserts the checks of the foreign keys referencing the records of an update, if referential integrity is
+/// emulated by the engine (`relationMode = "prisma"`). Foreign keys don't allow changing the fields they reference
+/// while records still reference them (`ON UPDATE NO ACTION`), so the update fails if a record of a related model
+/// or a row of a relation table references one of the updated records. Nothing is checked if the update doesn't
+/// write a referenced field.
+///
+/// `update_node` is an `UpdateRecord` or `UpdateManyRecords` with the filter of the records to update. If given,
+/// `ids_node` returns the IDs of the updated records, otherwise they are read with the filter of the update.
+///
+/// ```text
+/// ┌────────────────────┐
+/// │ Find Record IDs to │
+/// │       Update       │
+/// └────────────────────┘
+///            │
+///            ▼
+/// ┌────────────────────┐
+/// │ Find Referencing   │
+/// │ Records            │
+/// └────────────────────┘
+///            │ Fail if > 0
+///            ▼
+/// ┌────────────────────┐
+/// │       Empty        │
+/// └────────────────────┘
+///            │
+///            ▼
+/// ┌────────────────────┐
+/// │       Update       │
+/// └────────────────────┘
+/// ```
+pub fn insert_emulated_update_checks(
+    graph: &mut QueryGraph,
+    update_node: &NodeRef,
+    ids_node: Option<&NodeRef>,
+) -> QueryGraphBuilderResult<()> {
+    let (model, filter, written_fields): (ModelRef, Filter, Vec<String>) = match graph.node_content(update_node) {
+        Some(Node::Query(Query::Write(WriteQuery::UpdateRecord(q)))) => {
+            (Arc::clone(&q.model), q.where_.clone(), q.args.keys().cloned().collect())
+        }
+        Some(Node::Query(Query::Write(WriteQuery::UpdateManyRecords(q)))) => {
+            (Arc::clone(&q.model), q.filter.clone(), q.args.keys().cloned().collect())
+        }
+        _ => return Ok(()),
+    };
+
+    if model.internal_data_model().relation_mode.uses_foreign_keys() {
+        return Ok(());
+    }
+
+    let restricting_fields: Vec<RelationFieldRef> = model
+        .fields()
+        .relation()
+        .into_iter()
+        .filter(|rf| {
+            let referenced_fields = if rf.relation().is_relation_table() {
+                model.primary_identifier()
+            } else if rf.relation_is_inlined_in_child() {
+                rf.linking_fields()
+            } else {
+                return false;
+            };
+
+            referenced_fields
+                .data_source_fields()
+                .any(|dsf| written_fields.contains(&dsf.name))
+        })
+        .collect();
+
+    if restricting_fields.is_empty() {
+        return Ok(());
+    }
+
+    let ids_node = match ids_node {
+        Some(ids_node) => *ids_node,
+        None => graph.create_node(read_ids_infallible(
+            Arc::clone(&model),
+            model.primary_identifier(),
+            filter,
+        )),
+    };
+
+    let noop_node = graph.create_node(Node::Empty);
+
+    for rf in restricting_fields {
+        let child_model_identifier = rf.related_model().primary_identifier();
+        let read_node = insert_find_children_by_parent_node(graph, &ids_node, &rf, Filter::empty())?;
+
+        graph.create_edge(
+            &read_node,
+            &noop_node,
+            QueryGraphDependency::ParentIds(
+                child_model_identifier,
+                Box::new(move |node, child_ids| {
+                    if !child_ids.is_empty() {
+                        return Err(QueryGraphBuilderError::RelationViolation((&rf).into()));
+                    }
+
+                    Ok(node)
+                }),
+            ),
+        )?;
+    }
+
+    graph.create_edge(&noop_node, update_node, QueryGraphDependency::ExecutionOrder)?;
+
+    Ok(())
+}
+
 /// Checks if the parent node returns the set of fields required to satisfy the relation .
 /// If not, the parent record (or records) will be reloaded with the necessary fields.
 /// This is usually the case when the relation requires a different set of fields than the
@@ -369,62 +765,227 @@ pub fn insert_deletion_checks(
 /// Returns the reference to the node that children should attach to to get the fields they need
 /// to satisfy a relation.
 ///
-/// Todo: To ensure that the results are equivalent and nothing was dropped in between, we'd need to
-/// compare the result counts from `parent_node` and `reload_node` with an empty node and error out.
+/// To ensure that the results are equivalent and nothing was dropped in between, the result counts
+/// of `parent_node` and `reload_node` are compared by a `Compare Counts` node, which errors out if
+/// they differ. It is the first child of the reload, so it runs before any other child.
 /// ```text
 /// ┌ ─ ─ ─ ─ ─ ─ ─ ─
-///       Parent     │
+///       Parent     │─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ┐
 /// └ ─ ─ ─ ─ ─ ─ ─ ─
+///          │                                  │
 ///          │
-///          │
-///          │
-///          ▼
-/// ┌────────────────┐
-/// │ Reload Parent  │
-/// └────────────────┘
+///          │                                  │
+///          ▼                                  ▼
+/// ┌────────────────┐                 ┌────────────────┐
+/// │ Reload Parent  │────────────────▶│ Compare Counts │
+/// └────────────────┘                 └────────────────┘
 /// ```
 pub fn insert_node_reload(
-    _graph: &mut QueryGraph,
-    _parent_relation_field: &RelationFieldRef,
+    graph: &mut QueryGraph,
+    parent_relation_field: &RelationFieldRef,
     parent_node: NodeRef,
 ) -> QueryGraphBuilderResult<NodeRef> {
-    // FIXME: AUMFIDARR
-    //    if let Some(Node::Query(_q)) = graph.node_content(&parent_node) {
-    //        let required_fields = parent_relation_field.linking_fields();
-    //
-    //        // Todo: Simplification: We currently always reload the node regardless of whether or not the parent actually
-    //        // returns the required fields.
-    //        // if !q.returns(&required_fields) {
-    //        let reload_node = reload_node(graph, parent_relation_field.model(), required_fields.clone());
-    //
-    //        graph.create_edge(
-    //            &parent_node,
-    //            &reload_node,
-    //            QueryGraphDependency::ParentIds(
-    //                required_fields,
-    //                Box::new(move |mut node, parent_ids| {
-    //                    if let Node::Query(ref mut q) = node {
-    //                        q.add_filter(parent_ids.filter());
-    //                    }
-    //
-    //                    Ok(node)
-    //                }),
-    //            ),
-    //        )?;
-    //
-    //        Ok(reload_node)
-    //    // } else {
-    //    //     Ok(parent_node)
-    //    // }
-    //    } else {
-    //        Err(QueryGraphBuilderError::AssertionError(
-    //            "Query graph construction error: Attempted to attach a node reload to a non-query node.".to_owned(),
-    //        ))
-    //    }
-    Ok(parent_node)
+    let model = parent_relation_field.model();
+    let primary_identifier = model.primary_identifier();
+    let required_fields = parent_relation_field.linking_fields();
+
+    let parent_returns_ids = match graph.node_content(&parent_node) {
+        Some(Node::Query(q)) if q.returns(&required_fields) => return Ok(parent_node),
+        Some(Node::Query(q)) => q.returns(&primary_identifier),
+        _ => {
+            return Err(QueryGraphBuilderError::AssertionError(
+                "Query graph construction error: Attempted to attach a node reload to a non-query node.".to_owned(),
+            ))
+        }
+    };
+
+    if !parent_returns_ids {
+        return Err(QueryGraphBuilderError::AssertionError(format!(
+            "Query graph construction error: Unable to reload records of model '{}' from a query that doesn't return their IDs.",
+            model.name
+        )));
+    }
+
+    let reload_node = graph.create_node(read_ids_infallible(
+        model.clone(),
+        required_fields.clone(),
+        Filter::empty(),
+    ));
+    let compare_node = graph.create_node(Node::Computation(Computation::empty_count_comparison(
+        model.name.clone(),
+    )));
+
+    graph.create_edge(
+        &parent_node,
+        &reload_node,
+        QueryGraphDependency::ParentIds(
+            primary_identifier.clone(),
+            Box::new(move |mut node, parent_ids| {
+                if let Node::Query(ref mut q) = node {
+                    q.add_filter(parent_ids.filter());
+                }
+
+                Ok(node)
+            }),
+        ),
+    )?;
+
+    graph.create_edge(
+        &parent_node,
+        &compare_node,
+        QueryGraphDependency::ParentIds(
+            primary_identifier,
+            Box::new(move |mut node, parent_ids| {
+                if let Node::Computation(Computation::CompareCounts(ref mut c)) = node {
+                    c.expected = Some(parent_ids.len());
+                }
+
+                Ok(node)
+            }),
+        ),
+    )?;
+
+    graph.create_edge(
+        &reload_node,
+        &compare_node,
+        QueryGraphDependency::ParentIds(
+            required_fields,
+            Box::new(move |mut node, reloaded_ids| {
+                if let Node::Computation(Computation::CompareCounts(ref mut c)) = node {
+                    c.found = Some(reloaded_ids.len());
+                }
+
+                Ok(node)
+            }),
+        ),
+    )?;
+
+    Ok(reload_node)
 }
 
-// fn reload_node(graph: &mut QueryGraph, model: ModelRef, identifier: ModelIdentifier) -> NodeRef {
-//     let read_query = read_ids_infallible(model, identifier, Filter::empty());
-//     graph.create_node(read_query)
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prisma_models::{DatamodelConverter, InternalDataModelRef};
+
+    const DATAMODEL: &str = r#"
+        model Blog {
+            id    String @id
+            slug  String @unique
+            posts Post[]
+        }
+
+        model Post {
+            id   String @id
+            blog Blog   @relation(references: [slug])
+        }
+    "#;
+
+    /// The data model and the `posts` field of blogs, whose relation links blogs by their slug.
+    fn blog_posts() -> (InternalDataModelRef, RelationFieldRef) {
+        let datamodel = datamodel::parse_datamodel(DATAMODEL).unwrap();
+        let internal_datamodel = DatamodelConverter::convert(&datamodel).build("db".to_owned());
+        let posts = internal_datamodel
+            .find_model("Blog")
+            .unwrap()
+            .fields()
+            .find_from_relation_fields("posts")
+            .unwrap();
+
+        (internal_datamodel, posts)
+    }
+
+    fn assert_assertion_error(result: QueryGraphBuilderResult<NodeRef>) {
+        match result {
+            Err(QueryGraphBuilderError::AssertionError(_)) => (),
+            Err(err) => panic!("Expected an assertion error, got {:?}", err),
+            Ok(_) => panic!("Expected an assertion error, got a node"),
+        }
+    }
+
+    #[test]
+    fn parents_returning_the_linking_fields_are_not_reloaded() {
+        let (_datamodel, posts) = blog_posts();
+        let mut graph = QueryGraph::new();
+        let parent = graph.create_node(read_ids_infallible(
+            posts.model(),
+            posts.linking_fields(),
+            Filter::empty(),
+        ));
+
+        let node = insert_node_reload(&mut graph, &posts, parent).unwrap();
+
+        assert_eq!(node, parent);
+        assert_eq!(graph.node_count(), 1);
+    }
+
+    #[test]
+    fn parents_returning_their_ids_are_reloaded_with_the_linking_fields() {
+        let (_datamodel, posts) = blog_posts();
+        let blog = posts.model();
+        let mut graph = QueryGraph::new();
+        let parent = graph.create_node(read_ids_infallible(
+            Arc::clone(&blog),
+            blog.primary_identifier(),
+            Filter::empty(),
+        ));
+
+        let reload = insert_node_reload(&mut graph, &posts, parent).unwrap();
+
+        assert_ne!(reload, parent);
+        assert_eq!(graph.node_count(), 3);
+
+        match graph.node_content(&reload) {
+            Some(Node::Query(q)) => assert!(q.returns(&posts.linking_fields())),
+            _ => panic!("Expected the reload to be a query."),
+        }
+
+        let parent_children: Vec<NodeRef> = graph
+            .direct_child_pairs(&parent)
+            .into_iter()
+            .map(|(_, child)| child)
+            .collect();
+
+        let reload_children: Vec<NodeRef> = graph
+            .direct_child_pairs(&reload)
+            .into_iter()
+            .map(|(_, child)| child)
+            .collect();
+
+        assert_eq!(parent_children, vec![reload]);
+        assert_eq!(reload_children.len(), 1);
+
+        match graph.node_content(&reload_children[0]) {
+            Some(Node::Computation(Computation::CompareCounts(c))) => {
+                assert_eq!(c.expected, None);
+                assert_eq!(c.found, None);
+            }
+            _ => panic!("Expected the count comparison to be the first child of the reload."),
+        }
+
+        assert!(graph.is_ancestor(&parent, &reload_children[0]));
+    }
+
+    #[test]
+    fn parents_not_returning_their_ids_fail() {
+        let (_datamodel, posts) = blog_posts();
+        let mut graph = QueryGraph::new();
+        let parent = graph.create_node(Query::Write(WriteQuery::DeleteManyRecords(DeleteManyRecords {
+            model: posts.model(),
+            filter: Filter::empty(),
+        })));
+
+        assert_assertion_error(insert_node_reload(&mut graph, &posts, parent));
+        assert_eq!(graph.node_count(), 1);
+    }
+
+    #[test]
+    fn parents_that_are_no_queries_fail() {
+        let (_datamodel, posts) = blog_posts();
+        let mut graph = QueryGraph::new();
+        let parent = graph.create_node(Node::Empty);
+
+        assert_assertion_error(insert_node_reload(&mut graph, &posts, parent));
+        assert_eq!(graph.node_count(), 1);
+    }
+}
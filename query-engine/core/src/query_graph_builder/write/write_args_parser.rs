@@ -1,7 +1,7 @@
 use super::*;
 use crate::query_document::{ParsedInputMap, ParsedInputValue};
 use connector::WriteArgs;
-use prisma_models::{Field, ModelRef, PrismaValue, RelationFieldRef};
+use prisma_models::{Field, ModelRef, PrismaValue, RelationFieldRef, ScalarFieldRef, TypeIdentifier};
 use std::{convert::TryInto, sync::Arc};
 
 #[derive(Default, Debug)]
@@ -17,7 +17,7 @@ impl WriteArgsParser {
         data_map.into_iter().try_fold(
             WriteArgsParser::default(),
             |mut args, (k, v): (String, ParsedInputValue)| {
-                let field = model.fields().find_from_all(&k).unwrap();
+                let field = model.fields().find_from_all(&k)?;
                 match field {
                     Field::Scalar(sf) if sf.is_list => {
                         let vals: ParsedInputMap = v.try_into()?;
@@ -37,10 +37,16 @@ impl WriteArgsParser {
                         args.args.insert(sf.db_name().clone(), set_value)
                     }
 
-                    Field::Scalar(sf) => {
-                        let value: PrismaValue = v.try_into()?;
-                        args.args.insert(sf.db_name().clone(), value)
-                    }
+                    Field::Scalar(sf) => match v {
+                        // Updates of JSON fields either `set` or `merge` the document.
+                        ParsedInputValue::Map(vals) if sf.type_identifier == TypeIdentifier::Json => {
+                            json_update(model, sf, vals, &mut args.args)?
+                        }
+                        v => {
+                            let value: PrismaValue = v.try_into()?;
+                            args.args.insert(sf.db_name().clone(), value)
+                        }
+                    },
 
                     Field::Relation(ref rf) => {
                         args.nested.push((Arc::clone(rf), v.try_into()?));
@@ -52,3 +58,45 @@ impl WriteArgsParser {
         )
     }
 }
+
+fn json_update(
+    model: &ModelRef,
+    field: &ScalarFieldRef,
+    vals: ParsedInputMap,
+    args: &mut WriteArgs,
+) -> QueryGraphBuilderResult<()> {
+    let mut vals: Vec<(String, ParsedInputValue)> = vals.into_iter().collect();
+
+    if vals.len() != 1 {
+        return Err(QueryGraphBuilderError::InputError(format!(
+            "Exactly one of `set` and `merge` must be provided for field `{field_name}` on `{model_name}`",
+            field_name = &field.name,
+            model_name = &model.name,
+        )));
+    }
+
+    let (operation, value) = vals.pop().unwrap();
+    let value: PrismaValue = value.try_into()?;
+
+    if operation == "set" {
+        args.insert(field.db_name().clone(), value);
+        return Ok(());
+    }
+
+    let patch = match value {
+        PrismaValue::String(json) => serde_json::from_str(&json).ok(),
+        _ => None,
+    };
+
+    match patch {
+        Some(serde_json::Value::Object(patch)) => {
+            args.merge_json(field.db_name().clone(), patch);
+            Ok(())
+        }
+        _ => Err(QueryGraphBuilderError::InputError(format!(
+            "The `merge` argument for field `{field_name}` on `{model_name}` must be a JSON object",
+            field_name = &field.name,
+            model_name = &model.name,
+        ))),
+    }
+}
@@ -1,11 +1,11 @@
 use super::*;
 use crate::{
-    schema::{IntoArc, ObjectTypeStrongRef, OutputType, OutputTypeRef, ScalarType},
-    CoreError, CoreResult, EnumType, QueryResult, RecordSelection,
+    schema::{IntoArc, ObjectTypeStrongRef, OutputType, OutputTypeRef},
+    CoreError, CoreResult, QueryResult, RecordSelection, ResponseError,
 };
+use connector::RoutineResult;
 use indexmap::IndexMap;
-use prisma_models::{InternalEnum, PrismaValue, RecordIdentifier};
-use rust_decimal::prelude::ToPrimitive;
+use prisma_models::{PrismaValue, RecordIdentifier};
 use std::{borrow::Borrow, collections::HashMap};
 
 /// A grouping of items to their parent record.
@@ -53,12 +53,29 @@ pub fn serialize_internal(
         }
 
         QueryResult::Json(_) => unimplemented!(),
+        QueryResult::Routine(_) => unreachable!("Routine results are serialized by the top level serializer."),
 
         QueryResult::Id(_) => unimplemented!(),
         QueryResult::Unit => unimplemented!(),
     }
 }
 
+/// Serializes the rows of a routine as a list of objects, keyed by the columns of the result.
+pub fn serialize_routine_result(result: RoutineResult) -> Item {
+    let RoutineResult { columns, rows } = result;
+
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            let map: Map = columns.iter().cloned().zip(row.into_iter().map(Item::Value)).collect();
+
+            Item::Map(map)
+        })
+        .collect();
+
+    Item::List(rows)
+}
+
 fn serialize_record_selection(
     record_selection: RecordSelection,
     typ: &OutputTypeRef,
@@ -141,8 +158,8 @@ fn serialize_objects(mut result: RecordSelection, typ: ObjectTypeStrongRef) -> C
     // Move nested out of result for separate processing.
     let nested = std::mem::replace(&mut result.nested, Vec::new());
 
-    // { <nested field name> -> { parent ID -> items } }
-    let mut nested_mapping: HashMap<String, CheckedItemsWithParents> = process_nested_results(nested, &typ)?;
+    // { <nested field key> -> { parent ID -> items } }
+    let nested_mapping: HashMap<String, CheckedItemsWithParents> = process_nested_results(nested, &typ)?;
 
     // We need the Arcs to solve the issue where we have multiple parents claiming the same data (we want to move the data out of the nested structure
     // to prevent expensive copying during serialization).
@@ -152,13 +169,24 @@ fn serialize_objects(mut result: RecordSelection, typ: ObjectTypeStrongRef) -> C
     let scalar_db_field_names = result.scalars.field_names;
 
     let model = result.model_id.model();
+    // The columns of inlined relations are read by their foreign key scalar fields. Columns without a field keep
+    // their db name, so the names stay aligned with the record values.
     let field_names: Vec<_> = scalar_db_field_names
         .iter()
-        .filter_map(|f| model.map_scalar_db_field_name(f).map(|x| x.name.clone()))
+        .map(|f| {
+            model
+                .map_scalar_db_field_name(f)
+                .map(|x| x.name.clone())
+                .or_else(|| model.map_foreign_key_db_field_name(f))
+                .unwrap_or_else(|| f.clone())
+        })
         .collect();
 
-    // Write all fields, nested and list fields unordered into a map, afterwards order all into the final order.
-    // If nothing is written to the object, write null instead.
+    // Field lookups and type dispatch are the same for every record, so they are only done once.
+    let failed: Vec<String> = result.failed.iter().map(|failed| failed.key.clone()).collect();
+    let plan = SerializationPlan::new(&typ, &result.fields, &field_names, &nested_mapping, &failed)?;
+
+    // Write all fields in the selected order, scalars from the record values and nested results from the mapping.
     for record in result.scalars.records.into_iter() {
         let record_id = Some(record.identifier(&scalar_db_field_names, &result.model_id)?);
 
@@ -166,28 +194,25 @@ fn serialize_objects(mut result: RecordSelection, typ: ObjectTypeStrongRef) -> C
             object_mapping.insert(record.parent_id.clone(), Vec::new());
         }
 
-        // Write scalars, but skip objects and lists, which while they are in the selection, are handled separately.
-        let values = record.values;
-        let mut object = HashMap::with_capacity(values.len());
+        let mut values = record.values;
+        let mut map = Map::with_capacity(plan.fields.len());
 
-        for (val, scalar_field_name) in values.into_iter().zip(field_names.iter()) {
-            let field = typ.find_field(scalar_field_name).unwrap();
+        for (key, field_plan) in plan.fields.iter() {
+            let item = match field_plan {
+                FieldPlan::Scalar(position, serializer) => {
+                    let value = values.get_mut(*position).ok_or_else(|| {
+                        CoreError::SerializationError(format!("No value present for selected field '{}'", key))
+                    })?;
 
-            if !field.field_type.is_object() {
-                object.insert(scalar_field_name.to_owned(), serialize_scalar(val, &field.field_type)?);
-            }
-        }
+                    serializer.serialize(std::mem::replace(value, PrismaValue::Null))?
+                }
 
-        // Write nested results
-        write_nested_items(&record_id, &mut nested_mapping, &mut object, &typ);
+                FieldPlan::Nested(default) => nested_item(&record_id, key, &nested_mapping, default),
+                FieldPlan::Failed(default) => Item::Ref(ItemRef::clone(default)),
+            };
 
-        let map = result
-            .fields
-            .iter()
-            .fold(Map::with_capacity(result.fields.len()), |mut acc, field_name| {
-                acc.insert(field_name.to_owned(), object.remove(field_name).unwrap());
-                acc
-            });
+            map.insert(key.to_owned(), item);
+        }
 
         // TODO: Find out how to easily determine when a result is null.
         // If the object is null or completely empty, coerce into null instead.
@@ -204,47 +229,55 @@ fn serialize_objects(mut result: RecordSelection, typ: ObjectTypeStrongRef) -> C
     Ok(object_mapping)
 }
 
-/// Unwraps are safe due to query validation.
-fn write_nested_items(
-    record_id: &Option<RecordIdentifier>,
-    items_with_parent: &mut HashMap<String, CheckedItemsWithParents>,
-    into: &mut HashMap<String, Item>,
-    enclosing_type: &ObjectTypeStrongRef,
-) {
-    items_with_parent.iter_mut().for_each(|(field_name, inner)| {
-        let val = inner.get(record_id);
-
-        // The value must be a reference (or None - handle default), everything else is an error in the serialization logic.
-        match val {
-            Some(Item::Ref(ref r)) => {
-                into.insert(field_name.to_owned(), Item::Ref(ItemRef::clone(r)));
-            }
+/// The errors of the nested reads that failed within the result, with the response keys leading to their fields,
+/// starting with `path`. The paths don't contain list indices, a nested read fails for all records of a list.
+pub fn failed_reads(result: &QueryResult, path: &[String]) -> Vec<ResponseError> {
+    let mut errors = Vec::new();
+
+    if let QueryResult::RecordSelection(rs) = result {
+        for failed in rs.failed.iter() {
+            let mut failed_path = path.to_vec();
+            failed_path.push(failed.key.clone());
+
+            errors.push(failed.error.clone().with_path(failed_path));
+        }
+
+        for nested in rs.nested.iter() {
+            if let QueryResult::RecordSelection(nested_rs) = nested {
+                let mut nested_path = path.to_vec();
+                nested_path.push(nested_rs.alias.clone().unwrap_or_else(|| nested_rs.name.clone()));
 
-            None => {
-                let field = enclosing_type.find_field(field_name).unwrap();
-                let default = match field.field_type.borrow() {
-                    OutputType::List(_) => Item::List(Vec::new()),
-                    OutputType::Opt(inner) => {
-                        if inner.is_list() {
-                            Item::List(Vec::new())
-                        } else {
-                            Item::Value(PrismaValue::Null)
-                        }
-                    }
-                    _ => panic!(
-                        "Application logic invariant error: received null value for field {} which may not be null",
-                        &field_name
-                    ),
-                };
-
-                into.insert(field_name.to_owned(), Item::Ref(ItemRef::new(default)));
+                errors.extend(failed_reads(nested, &nested_path));
             }
-            _ => panic!("Application logic invariant error: Nested items have to be wrapped as a Item::Ref."),
-        };
-    });
+        }
+    }
+
+    errors
+}
+
+/// Unwraps are safe due to query validation.
+fn nested_item(
+    record_id: &Option<RecordIdentifier>,
+    field_name: &str,
+    items_with_parent: &HashMap<String, CheckedItemsWithParents>,
+    default: &Option<ItemRef>,
+) -> Item {
+    let val = items_with_parent.get(field_name).unwrap().get(record_id);
+
+    // The value must be a reference (or None - handle default), everything else is an error in the serialization logic.
+    match (val, default) {
+        (Some(Item::Ref(ref r)), _) => Item::Ref(ItemRef::clone(r)),
+        (None, Some(default)) => Item::Ref(ItemRef::clone(default)),
+        (None, None) => panic!(
+            "Application logic invariant error: received null value for field {} which may not be null",
+            field_name
+        ),
+        _ => panic!("Application logic invariant error: Nested items have to be wrapped as a Item::Ref."),
+    }
 }
 
-/// Processes nested results into a more ergonomic structure of { <nested field name> -> { parent ID -> item (list, map, ...) } }.
+/// Processes nested results into a more ergonomic structure of { <nested field key> -> { parent ID -> item (list, map, ...) } }.
+/// The key is the alias of the nested selection if present, a relation can be selected more than once with different aliases.
 fn process_nested_results(
     nested: Vec<QueryResult>,
     enclosing_type: &ObjectTypeStrongRef,
@@ -257,107 +290,13 @@ fn process_nested_results(
     for nested_result in nested {
         // todo Workaround, tb changed with flat reads.
         if let QueryResult::RecordSelection(ref rs) = nested_result {
-            let name = rs.name.clone();
-            let field = enclosing_type.find_field(&name).unwrap();
+            let key = rs.alias.clone().unwrap_or_else(|| rs.name.clone());
+            let field = enclosing_type.find_field(&rs.name).unwrap();
             let result = serialize_internal(nested_result, &field.field_type, false, false)?;
 
-            nested_mapping.insert(name, result);
+            nested_mapping.insert(key, result);
         }
     }
 
     Ok(nested_mapping)
 }
-
-fn serialize_scalar(value: PrismaValue, typ: &OutputTypeRef) -> CoreResult<Item> {
-    match (&value, typ.as_ref()) {
-        (PrismaValue::Null, OutputType::Opt(_)) => Ok(Item::Value(PrismaValue::Null)),
-        (_, OutputType::Opt(inner)) => serialize_scalar(value, inner),
-        (_, OutputType::Enum(et)) => match et.borrow() {
-            EnumType::Internal(ref i) => convert_enum(value, i),
-            _ => unreachable!(),
-        },
-        (PrismaValue::List(_), OutputType::List(arc_type)) => match arc_type.as_ref() {
-            OutputType::Scalar(subtype) => {
-                let items = unwrap_prisma_value(value)
-                    .into_iter()
-                    .map(|v| convert_prisma_value(v, subtype))
-                    .map(|pv| pv.map(|x| Item::Value(x)))
-                    .collect::<Result<Vec<Item>, CoreError>>()?;
-                Ok(Item::List(items))
-            }
-            OutputType::Enum(et) => {
-                let items = unwrap_prisma_value(value)
-                    .into_iter()
-                    .map(|v| match et.borrow() {
-                        EnumType::Internal(ref i) => convert_enum(v, i),
-                        _ => unreachable!(),
-                    })
-                    .collect::<Result<Vec<Item>, CoreError>>()?;
-                Ok(Item::List(items))
-            }
-            _ => Err(CoreError::SerializationError(format!(
-                "Attempted to serialize scalar list which contained non-scalar items of type '{:?}'",
-                arc_type
-            ))),
-        },
-        (_, OutputType::Scalar(st)) => Ok(Item::Value(convert_prisma_value(value, st)?)),
-        (pv, ot) => Err(CoreError::SerializationError(format!(
-            "Attempted to serialize scalar '{}' with non-scalar compatible type '{:?}'",
-            pv, ot
-        ))),
-    }
-}
-
-fn convert_prisma_value(value: PrismaValue, st: &ScalarType) -> Result<PrismaValue, CoreError> {
-    let item_value = match (st, value) {
-        (ScalarType::String, PrismaValue::String(s)) => PrismaValue::String(s),
-
-        (ScalarType::Int, PrismaValue::Float(f)) => PrismaValue::Int(f.to_i64().unwrap()),
-        (ScalarType::Int, PrismaValue::Int(i)) => PrismaValue::Int(i),
-
-        (ScalarType::Float, PrismaValue::Float(f)) => PrismaValue::Float(f),
-        (ScalarType::Float, PrismaValue::Int(i)) => {
-            PrismaValue::Int(i.to_i64().expect("Unable to convert Decimal to i64."))
-        }
-
-        (ScalarType::Boolean, PrismaValue::Boolean(b)) => PrismaValue::Boolean(b),
-        (ScalarType::DateTime, PrismaValue::DateTime(dt)) => PrismaValue::DateTime(dt),
-        (ScalarType::UUID, PrismaValue::Uuid(u)) => PrismaValue::Uuid(u),
-
-        (st, pv) => {
-            return Err(CoreError::SerializationError(format!(
-                "Attempted to serialize scalar '{}' with incompatible type '{:?}'",
-                pv, st
-            )))
-        }
-    };
-
-    Ok(item_value)
-}
-
-fn convert_enum(value: PrismaValue, i: &InternalEnum) -> Result<Item, CoreError> {
-    match value {
-        PrismaValue::String(s) | PrismaValue::Enum(s) => {
-            if i.contains(&s) {
-                Ok(Item::Value(PrismaValue::Enum(s)))
-            } else {
-                Err(CoreError::SerializationError(format!(
-                    "Value '{}' not found in enum '{:?}'",
-                    s, i
-                )))
-            }
-        }
-
-        val => Err(CoreError::SerializationError(format!(
-            "Attempted to serialize non-enum-compatible value '{}' with enum '{:?}'",
-            val, i
-        ))),
-    }
-}
-
-fn unwrap_prisma_value(pv: PrismaValue) -> Vec<PrismaValue> {
-    match pv {
-        PrismaValue::List(l) => l,
-        _ => panic!("We want lists!"),
-    }
-}
@@ -9,16 +9,20 @@
 //! Note: The code itself can be considered WIP. It is clear when reading the code that there are missing abstractions
 //! and a restructure might be necessary (good example is the default value handling sprinkled all over the place).
 mod internal;
+mod plan;
 mod utils;
 
 use crate::{ExpressionResult, QueryResult, OutputType, OutputTypeRef};
 use indexmap::IndexMap;
 use internal::*;
+use plan::*;
 use prisma_models::PrismaValue;
 use serde::ser::{Serialize, Serializer, SerializeMap, SerializeSeq};
 use std::{borrow::Borrow, sync::Arc};
 use utils::*;
 
+pub(crate) use utils::trim_records;
+
 /// A `key -> value` map to an IR item
 pub type Map = IndexMap<String, Item>;
 
@@ -28,10 +32,32 @@ pub type List = Vec<Item>;
 /// Convenience type wrapper for Arc<Item>.
 pub type ItemRef = Arc<Item>;
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ResponseError {
     error: String,
     user_facing_error: user_facing_errors::Error,
+    /// Response keys leading to the field whose data failed to load, for errors of partially returned data.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    path: Vec<String>,
+}
+
+impl ResponseError {
+    pub fn message(&self) -> &str {
+        &self.error
+    }
+
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    pub fn with_path(mut self, path: Vec<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    pub fn into_user_facing_error(self) -> user_facing_errors::Error {
+        self.user_facing_error
+    }
 }
 
 impl From<user_facing_errors::Error> for ResponseError {
@@ -39,6 +65,7 @@ impl From<user_facing_errors::Error> for ResponseError {
         ResponseError {
             error: err.message().to_owned(),
             user_facing_error: err,
+            path: Vec::new(),
         }
     }
 }
@@ -48,6 +75,7 @@ impl From<crate::error::CoreError> for ResponseError {
         ResponseError {
             error: format!("{}", err),
             user_facing_error: err.into(),
+            path: Vec::new(),
         }
     }
 }
@@ -57,6 +85,8 @@ impl From<crate::error::CoreError> for ResponseError {
 #[derive(Debug)]
 pub enum Response {
     Data(String, Item),
+    /// Data of which some fields failed to load, with an error at the path of each of them.
+    PartialData(String, Item, Vec<ResponseError>),
     Error(ResponseError),
 }
 
@@ -87,6 +117,19 @@ impl Responses {
     pub fn insert_error(&mut self, error: impl Into<ResponseError>) {
         self.errors.push(error.into());
     }
+
+    pub fn insert_partial_data(&mut self, key: impl Into<String>, item: Item, errors: Vec<ResponseError>) {
+        self.insert_data(key, item);
+        self.errors.extend(errors);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    pub fn into_parts(self) -> (Map, Vec<ResponseError>) {
+        (self.data, self.errors)
+    }
 }
 
 /// An IR item that either expands to a subtype or leaf-record.
@@ -150,7 +193,12 @@ impl IrSerializer {
             ExpressionResult::Query(QueryResult::Json(json)) => {
                 Response::Data(self.key.clone(), Item::Json(json))
             }
+            ExpressionResult::Query(QueryResult::Routine(result)) => {
+                Response::Data(self.key.clone(), serialize_routine_result(result))
+            }
             ExpressionResult::Query(r) => {
+                let errors = failed_reads(&r, &[self.key.clone()]);
+
                 match serialize_internal(r, &self.output_type, false, false) {
                     Ok(result) => {
                         // On the top level, each result boils down to a exactly a single serialized result.
@@ -168,7 +216,11 @@ impl IrSerializer {
                             item
                         };
 
-                        Response::Data(self.key.clone(), result)
+                        if errors.is_empty() {
+                            Response::Data(self.key.clone(), result)
+                        } else {
+                            Response::PartialData(self.key.clone(), result, errors)
+                        }
                     }
                     Err(err) => Response::Error(err.into()),
                 }
@@ -179,3 +231,53 @@ impl IrSerializer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use connector::RoutineResult;
+
+    #[test]
+    fn routine_results_are_serialized_as_objects_of_their_columns() {
+        let serializer = IrSerializer {
+            key: "callRefresh_stats".to_owned(),
+            output_type: Arc::new(OutputType::list(OutputType::json())),
+        };
+
+        let result = RoutineResult {
+            columns: vec!["user_id".to_owned(), "posts".to_owned()],
+            rows: vec![
+                vec![PrismaValue::String("1".to_owned()), PrismaValue::Int(3)],
+                vec![PrismaValue::String("2".to_owned()), PrismaValue::Null],
+            ],
+        };
+
+        let item = match serializer.serialize(ExpressionResult::Query(QueryResult::Routine(result))) {
+            Response::Data(key, item) => {
+                assert_eq!(key, "callRefresh_stats");
+                item
+            }
+            response => panic!("Expected data, got {:?}", response),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&item).unwrap(),
+            serde_json::json!([{ "user_id": "1", "posts": 3 }, { "user_id": "2", "posts": null }])
+        );
+    }
+
+    #[test]
+    fn routines_without_rows_return_an_empty_list() {
+        let serializer = IrSerializer {
+            key: "callRefresh_stats".to_owned(),
+            output_type: Arc::new(OutputType::list(OutputType::json())),
+        };
+
+        let result = ExpressionResult::Query(QueryResult::Routine(RoutineResult::default()));
+
+        match serializer.serialize(result) {
+            Response::Data(_, item) => assert_eq!(serde_json::to_value(&item).unwrap(), serde_json::json!([])),
+            response => panic!("Expected data, got {:?}", response),
+        }
+    }
+}
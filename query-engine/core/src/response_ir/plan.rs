@@ -0,0 +1,388 @@
+use super::*;
+use crate::{
+    schema::{ObjectTypeStrongRef, OutputType, OutputTypeRef, ScalarType},
+    CoreError, CoreResult, EnumType,
+};
+use prisma_models::{InternalEnum, PrismaValue};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use std::collections::{HashMap, HashSet};
+
+/// How the records of a selection are serialized: the response keys of the selected output fields in order, where
+/// their values come from and how they're converted. Computed once per selection instead of once per record.
+pub struct SerializationPlan {
+    pub fields: Vec<(String, FieldPlan)>,
+}
+
+pub enum FieldPlan {
+    /// The value at the given position of the record values.
+    Scalar(usize, ScalarSerializer),
+
+    /// The items of the nested selection of the field. Records without items get the default,
+    /// `None` if the field may not be null.
+    Nested(Option<ItemRef>),
+
+    /// The nested read of the field failed. The field is an empty list for list relations, otherwise `null`.
+    Failed(ItemRef),
+}
+
+impl SerializationPlan {
+    /// `selected_fields` are the response keys and names of the selected fields, in order.
+    /// `value_names` are the names of the fields of the record values, in order.
+    /// `nested` are the keys of the fields with nested selections, `failed` the keys of those whose reads failed.
+    pub fn new<T>(
+        typ: &ObjectTypeStrongRef,
+        selected_fields: &[(String, String)],
+        value_names: &[String],
+        nested: &HashMap<String, T>,
+        failed: &[String],
+    ) -> CoreResult<Self> {
+        let fields = selected_fields
+            .iter()
+            .map(|(key, name)| {
+                let field = typ.find_field(name).ok_or_else(|| {
+                    CoreError::SerializationError(format!("Field '{}' not found on type '{}'", name, typ.name()))
+                })?;
+
+                let plan = if failed.contains(key) {
+                    let default = nested_default(&field.field_type)
+                        .unwrap_or_else(|| ItemRef::new(Item::Value(PrismaValue::Null)));
+
+                    FieldPlan::Failed(default)
+                } else if nested.contains_key(key) {
+                    FieldPlan::Nested(nested_default(&field.field_type))
+                } else {
+                    // A field selected more than once takes the last value.
+                    let position = value_names
+                        .iter()
+                        .rposition(|value_name| value_name == name)
+                        .filter(|_| !field.field_type.is_object())
+                        .ok_or_else(|| {
+                            CoreError::SerializationError(format!("No value present for selected field '{}'", name))
+                        })?;
+
+                    FieldPlan::Scalar(position, ScalarSerializer::new(&field.field_type))
+                };
+
+                Ok((key.clone(), plan))
+            })
+            .collect::<CoreResult<Vec<_>>>()?;
+
+        Ok(Self { fields })
+    }
+}
+
+fn nested_default(typ: &OutputTypeRef) -> Option<ItemRef> {
+    let default = match typ.as_ref() {
+        OutputType::List(_) => Item::List(Vec::new()),
+        OutputType::Opt(inner) => {
+            if inner.is_list() {
+                Item::List(Vec::new())
+            } else {
+                Item::Value(PrismaValue::Null)
+            }
+        }
+        _ => return None,
+    };
+
+    Some(ItemRef::new(default))
+}
+
+/// Converts values for a scalar, enum or scalar list output type.
+pub enum ScalarSerializer {
+    Scalar(ScalarType),
+    Enum(EnumSerializer),
+    Opt(Box<ScalarSerializer>),
+
+    /// Serializer of the items and the list type.
+    List(Box<ScalarSerializer>, OutputTypeRef),
+
+    /// Output types no scalar can be serialized with.
+    Unsupported(OutputTypeRef),
+}
+
+impl ScalarSerializer {
+    pub fn new(typ: &OutputTypeRef) -> Self {
+        match typ.as_ref() {
+            OutputType::Opt(inner) => Self::Opt(Box::new(Self::new(inner))),
+            OutputType::Enum(et) => Self::enumeration(et),
+            OutputType::Scalar(st) => Self::Scalar(st.clone()),
+            OutputType::List(inner) => match inner.as_ref() {
+                OutputType::Scalar(st) => Self::List(Box::new(Self::Scalar(st.clone())), Arc::clone(typ)),
+                OutputType::Enum(et) => Self::List(Box::new(Self::enumeration(et)), Arc::clone(typ)),
+                _ => Self::Unsupported(Arc::clone(typ)),
+            },
+            _ => Self::Unsupported(Arc::clone(typ)),
+        }
+    }
+
+    fn enumeration(et: &EnumType) -> Self {
+        match et {
+            EnumType::Internal(ref i) => Self::Enum(EnumSerializer::new(i)),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn serialize(&self, value: PrismaValue) -> CoreResult<Item> {
+        match (self, value) {
+            (Self::Opt(_), PrismaValue::Null) => Ok(Item::Value(PrismaValue::Null)),
+            (Self::Opt(inner), value) => inner.serialize(value),
+            (Self::Enum(es), value) => es.serialize(value),
+            (Self::Scalar(st), value) => Ok(Item::Value(convert_prisma_value(value, st)?)),
+            (Self::List(items, _), PrismaValue::List(values)) => {
+                let items = values
+                    .into_iter()
+                    .map(|value| items.serialize(value))
+                    .collect::<CoreResult<Vec<Item>>>()?;
+
+                Ok(Item::List(items))
+            }
+            (Self::List(_, ot), pv) | (Self::Unsupported(ot), pv) => Err(CoreError::SerializationError(format!(
+                "Attempted to serialize scalar '{}' with non-scalar compatible type '{:?}'",
+                pv, ot
+            ))),
+        }
+    }
+}
+
+/// Checks values against the values of an enum, with a set for constant time lookups.
+pub struct EnumSerializer {
+    internal_enum: InternalEnum,
+    values: HashSet<String>,
+}
+
+impl EnumSerializer {
+    fn new(internal_enum: &InternalEnum) -> Self {
+        Self {
+            internal_enum: internal_enum.clone(),
+            values: internal_enum.values.iter().cloned().collect(),
+        }
+    }
+
+    fn serialize(&self, value: PrismaValue) -> CoreResult<Item> {
+        match value {
+            PrismaValue::String(s) | PrismaValue::Enum(s) => {
+                if self.values.contains(&s) {
+                    Ok(Item::Value(PrismaValue::Enum(s)))
+                } else {
+                    Err(CoreError::SerializationError(format!(
+                        "Value '{}' not found in enum '{:?}'",
+                        s, self.internal_enum
+                    )))
+                }
+            }
+
+            val => Err(CoreError::SerializationError(format!(
+                "Attempted to serialize non-enum-compatible value '{}' with enum '{:?}'",
+                val, self.internal_enum
+            ))),
+        }
+    }
+}
+
+fn convert_prisma_value(value: PrismaValue, st: &ScalarType) -> Result<PrismaValue, CoreError> {
+    let item_value = match (st, value) {
+        (ScalarType::String, PrismaValue::String(s)) => PrismaValue::String(s),
+
+        (ScalarType::Int, PrismaValue::Float(f)) => PrismaValue::Int(f.to_i64().unwrap()),
+        (ScalarType::Int, PrismaValue::Int(i)) => PrismaValue::Int(i),
+
+        (ScalarType::Float, PrismaValue::Float(f)) => PrismaValue::Float(f),
+        (ScalarType::Float, PrismaValue::Int(i)) => {
+            PrismaValue::Int(i.to_i64().expect("Unable to convert Decimal to i64."))
+        }
+
+        (ScalarType::Decimal, PrismaValue::Decimal(d)) => PrismaValue::Decimal(d),
+        (ScalarType::Decimal, PrismaValue::Float(f)) => PrismaValue::Decimal(f),
+        (ScalarType::Decimal, PrismaValue::Int(i)) => PrismaValue::Decimal(Decimal::from(i)),
+
+        (ScalarType::Boolean, PrismaValue::Boolean(b)) => PrismaValue::Boolean(b),
+        (ScalarType::DateTime, PrismaValue::DateTime(dt)) => PrismaValue::DateTime(dt),
+        (ScalarType::UUID, PrismaValue::Uuid(u)) => PrismaValue::Uuid(u),
+
+        (st, pv) => {
+            return Err(CoreError::SerializationError(format!(
+                "Attempted to serialize scalar '{}' with incompatible type '{:?}'",
+                pv, st
+            )))
+        }
+    };
+
+    Ok(item_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{Field, ObjectType};
+    use serde_json::json;
+
+    fn field(name: &str, typ: OutputType) -> Field {
+        Field {
+            name: name.to_owned(),
+            arguments: Vec::new(),
+            field_type: Arc::new(typ),
+            query_builder: None,
+        }
+    }
+
+    /// A `User` type with scalar fields and relations to the returned `Post` type, which must outlive the user.
+    fn user_type() -> (ObjectTypeStrongRef, ObjectTypeStrongRef) {
+        let post = Arc::new(ObjectType::new("Post", None));
+        post.set_fields(vec![field("id", OutputType::string())]);
+
+        let user = Arc::new(ObjectType::new("User", None));
+        user.set_fields(vec![
+            field("id", OutputType::string()),
+            field("age", OutputType::opt(OutputType::int())),
+            field("posts", OutputType::list(OutputType::object(Arc::downgrade(&post)))),
+            field("pinned", OutputType::opt(OutputType::object(Arc::downgrade(&post)))),
+            field("latest", OutputType::object(Arc::downgrade(&post))),
+        ]);
+
+        (user, post)
+    }
+
+    fn selected(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+        fields
+            .iter()
+            .map(|(key, name)| (key.to_string(), name.to_string()))
+            .collect()
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn json(item: &ItemRef) -> serde_json::Value {
+        serde_json::to_value(&**item).unwrap()
+    }
+
+    #[test]
+    fn scalars_are_read_from_the_last_value_of_the_field() {
+        let (user, _post) = user_type();
+        let plan = SerializationPlan::new::<()>(
+            &user,
+            &selected(&[("id", "id"), ("years", "age")]),
+            &names(&["age", "id", "age"]),
+            &HashMap::new(),
+            &[],
+        )
+        .unwrap();
+
+        let positions: Vec<_> = plan
+            .fields
+            .iter()
+            .map(|(key, plan)| match plan {
+                FieldPlan::Scalar(position, _) => (key.as_str(), *position),
+                _ => panic!("{} must be planned as a scalar", key),
+            })
+            .collect();
+
+        assert_eq!(positions, vec![("id", 1), ("years", 2)]);
+    }
+
+    #[test]
+    fn nested_fields_default_to_empty_lists_or_null_if_they_may_be() {
+        let (user, _post) = user_type();
+        let nested: HashMap<String, ()> = vec![
+            ("posts".to_owned(), ()),
+            ("pinned".to_owned(), ()),
+            ("latest".to_owned(), ()),
+        ]
+        .into_iter()
+        .collect();
+
+        let plan = SerializationPlan::new(
+            &user,
+            &selected(&[("posts", "posts"), ("pinned", "pinned"), ("latest", "latest")]),
+            &[],
+            &nested,
+            &[],
+        )
+        .unwrap();
+
+        let defaults: Vec<_> = plan
+            .fields
+            .iter()
+            .map(|(_, plan)| match plan {
+                FieldPlan::Nested(default) => default.as_ref().map(json),
+                _ => panic!("Fields with nested selections must be planned as nested"),
+            })
+            .collect();
+
+        assert_eq!(defaults, vec![Some(json!([])), Some(json!(null)), None]);
+    }
+
+    #[test]
+    fn failed_nested_reads_are_empty_lists_or_null() {
+        let (user, _post) = user_type();
+        let failed = names(&["posts", "latest"]);
+
+        let plan = SerializationPlan::new::<()>(
+            &user,
+            &selected(&[("posts", "posts"), ("latest", "latest")]),
+            &[],
+            &HashMap::new(),
+            &failed,
+        )
+        .unwrap();
+
+        let defaults: Vec<_> = plan
+            .fields
+            .iter()
+            .map(|(_, plan)| match plan {
+                FieldPlan::Failed(default) => json(default),
+                _ => panic!("Failed fields must be planned as failed"),
+            })
+            .collect();
+
+        assert_eq!(defaults, vec![json!([]), json!(null)]);
+    }
+
+    #[test]
+    fn unknown_fields_and_fields_without_values_are_rejected() {
+        let (user, _post) = user_type();
+
+        for selected_fields in &[selected(&[("email", "email")]), selected(&[("age", "age")])] {
+            let plan = SerializationPlan::new::<()>(&user, selected_fields, &names(&["id"]), &HashMap::new(), &[]);
+
+            assert!(plan.is_err());
+        }
+    }
+
+    #[test]
+    fn scalars_are_converted_to_the_output_type() {
+        let int = ScalarSerializer::new(&Arc::new(OutputType::opt(OutputType::int())));
+
+        assert_eq!(
+            serde_json::to_value(int.serialize(PrismaValue::Float(Decimal::from(3))).unwrap()).unwrap(),
+            json!(3)
+        );
+        assert_eq!(
+            serde_json::to_value(int.serialize(PrismaValue::Null).unwrap()).unwrap(),
+            json!(null)
+        );
+        assert!(int.serialize(PrismaValue::String("3".to_owned())).is_err());
+    }
+
+    #[test]
+    fn enum_lists_only_take_values_of_the_enum() {
+        let role = EnumType::Internal(InternalEnum {
+            name: "Role".to_owned(),
+            values: names(&["ADMIN", "USER"]),
+        });
+        let roles = ScalarSerializer::new(&Arc::new(OutputType::list(role.into())));
+
+        let valid = PrismaValue::List(vec![
+            PrismaValue::String("ADMIN".to_owned()),
+            PrismaValue::Enum("USER".to_owned()),
+        ]);
+        let invalid = PrismaValue::List(vec![PrismaValue::String("GUEST".to_owned())]);
+
+        assert_eq!(
+            serde_json::to_value(roles.serialize(valid).unwrap()).unwrap(),
+            json!(["ADMIN", "USER"])
+        );
+        assert!(roles.serialize(invalid).is_err());
+    }
+}
@@ -3,12 +3,8 @@ use connector::QueryArguments;
 
 /// Removes the excess records added to by the database query layer based on the query arguments
 /// This would be the right place to add pagination markers (has next page, etc.).
-pub fn trim_records(data: &mut Vec<Item>, query_args: &QueryArguments) {
-    // The query engine reverses lists when querying for `last`, so we need to reverse again to have the intended order.
-    if query_args.last.is_some() {
-        data.reverse();
-    }
-
+/// Connectors return the records of `last` reads in the requested order, the excess record comes first.
+pub fn trim_records<T>(data: &mut Vec<T>, query_args: &QueryArguments) {
     match (query_args.first, query_args.last) {
         (Some(f), _) if data.len() > f as usize => drop_right(data, 1),
         (_, Some(l)) if data.len() > l as usize => drop_left(data, 1),
@@ -1,4 +1,5 @@
-use connector::QueryArguments;
+use crate::ResponseError;
+use connector::{QueryArguments, RoutineResult};
 use prisma_models::{ManyRecords, ModelIdentifier, RecordIdentifier};
 
 #[derive(Debug, Clone)]
@@ -8,6 +9,7 @@ pub enum QueryResult {
     RecordSelection(RecordSelection),
     Unit,
     Json(serde_json::Value),
+    Routine(RoutineResult),
 }
 
 // Todo: In theory, much of this info can go into the serializer as soon as the read results are resolved in a flat tree.
@@ -16,8 +18,11 @@ pub struct RecordSelection {
     /// Name of the query.
     pub name: String,
 
-    /// Holds an ordered list of selected field names for each contained record.
-    pub fields: Vec<String>,
+    /// Alias of the query. Nested results are keyed by it instead of the name if present.
+    pub alias: Option<String>,
+
+    /// Holds an ordered list of the response keys and names of the selected fields for each contained record.
+    pub fields: Vec<(String, String)>,
 
     /// Scalar field results
     pub scalars: ManyRecords,
@@ -31,4 +36,14 @@ pub struct RecordSelection {
 
     /// Model ID that can be used to retrieve the IDs of the contained records.
     pub model_id: ModelIdentifier,
+
+    /// Nested reads that failed with the partial failure policy of the interpreter.
+    pub failed: Vec<FailedRead>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FailedRead {
+    /// Response key of the field of the read.
+    pub key: String,
+    pub error: ResponseError,
 }
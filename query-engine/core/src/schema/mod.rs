@@ -1,11 +1,10 @@
 #![warn(warnings)]
 
-mod capability;
 mod enum_type;
 mod query_schema;
 mod renderer;
 
-pub use capability::*;
+pub use connector::ConnectorCapabilities;
 pub use enum_type::*;
 pub use query_schema::*;
 pub use renderer::*;
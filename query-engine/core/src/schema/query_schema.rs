@@ -1,5 +1,6 @@
 use super::*;
-use crate::{ParsedField, QueryGraph, QueryGraphBuilderResult};
+use crate::{query_graph_builder::write, ParsedField, QueryGraph, QueryGraphBuilderResult};
+use connector::Routine;
 use once_cell::sync::OnceCell;
 use prisma_models::{dml, InternalDataModelRef, ModelRef};
 use std::{
@@ -140,6 +141,11 @@ impl ObjectType {
         self.get_fields().into_iter().find(|f| &f.name == name).cloned()
     }
 
+    /// The model of the records of the type, if it is a model type.
+    pub fn model(&self) -> Option<&ModelRef> {
+        self.model.as_ref()
+    }
+
     /// True if fields are empty, false otherwise.
     pub fn is_empty(&self) -> bool {
         self.get_fields().is_empty()
@@ -163,16 +169,20 @@ pub struct Field {
 /// - A `GenericQueryBuilder` is a query builder that requires no additional context but
 ///   the parsed query document data from the incoming query and is thus not associated to any particular
 ///   model. The `ResetData` query is such an example.
+///
+/// - A `RoutineQueryBuilder` builds the call of a stored procedure or function of the database.
 #[derive(Debug)]
 pub enum SchemaQueryBuilder {
     ModelQueryBuilder(ModelQueryBuilder),
     GenericQueryBuilder(GenericQueryBuilder),
+    RoutineQueryBuilder(RoutineQueryBuilder),
 }
 
 impl SchemaQueryBuilder {
     pub fn build(&self, parsed_field: ParsedField) -> QueryGraphBuilderResult<QueryGraph> {
         match self {
             Self::ModelQueryBuilder(m) => m.build(parsed_field),
+            Self::RoutineQueryBuilder(r) => r.build(parsed_field),
             _ => unimplemented!(),
         }
     }
@@ -240,6 +250,24 @@ pub struct GenericQueryBuilder {
     // WIP
 }
 
+#[derive(Debug)]
+pub struct RoutineQueryBuilder {
+    pub routine: Routine,
+}
+
+impl RoutineQueryBuilder {
+    pub fn new(routine: Routine) -> Self {
+        Self { routine }
+    }
+
+    pub fn build(&self, parsed_field: ParsedField) -> QueryGraphBuilderResult<QueryGraph> {
+        let mut graph = QueryGraph::new();
+
+        write::call_routine(&mut graph, self.routine.clone(), parsed_field)?;
+        Ok(graph)
+    }
+}
+
 #[derive(Debug)]
 pub struct Argument {
     pub name: String,
@@ -321,6 +349,10 @@ impl InputType {
         InputType::Scalar(ScalarType::Float)
     }
 
+    pub fn decimal() -> InputType {
+        InputType::Scalar(ScalarType::Decimal)
+    }
+
     pub fn boolean() -> InputType {
         InputType::Scalar(ScalarType::Boolean)
     }
@@ -376,6 +408,10 @@ impl OutputType {
         OutputType::Scalar(ScalarType::Float)
     }
 
+    pub fn decimal() -> OutputType {
+        OutputType::Scalar(ScalarType::Decimal)
+    }
+
     pub fn boolean() -> OutputType {
         OutputType::Scalar(ScalarType::Boolean)
     }
@@ -426,6 +462,7 @@ pub enum ScalarType {
     String,
     Int,
     Float,
+    Decimal,
     Boolean,
     Enum(EnumTypeRef),
     DateTime,
@@ -76,7 +76,7 @@ impl<'a> ArgumentBuilder<'a> {
             })
     }
 
-    /// Builds "where" and "data" arguments intended for the update many field.
+    /// Builds "where", "data" and "limit" arguments intended for the update many field.
     pub fn update_many_arguments(&self, model: ModelRef) -> Vec<Argument> {
         let update_object = self
             .input_type_builder
@@ -85,13 +85,29 @@ impl<'a> ArgumentBuilder<'a> {
 
         let where_arg = self.object_type_builder.into_arc().where_argument(&model);
 
-        vec![argument("data", InputType::object(update_object), None), where_arg]
+        vec![
+            argument("data", InputType::object(update_object), None),
+            where_arg,
+            limit_argument(),
+        ]
     }
 
-    /// Builds "where" argument intended for the delete many field.
+    /// Builds "where" and "limit" arguments intended for the delete many field.
     pub fn delete_many_arguments(&self, model: ModelRef) -> Vec<Argument> {
         let where_arg = self.object_type_builder.into_arc().where_argument(&model);
 
-        vec![where_arg]
+        vec![where_arg, limit_argument()]
     }
 }
+
+/// Optional maximum number of records a many records write applies to, e.g. to chunk large deletes.
+fn limit_argument() -> Argument {
+    argument("limit", InputType::opt(InputType::int()), None)
+}
+
+/// Builds the "includeDeleted" argument of reads, only soft deleted models have one.
+pub fn include_deleted_argument(model: &ModelRef) -> Option<Argument> {
+    model
+        .soft_delete_field()
+        .map(|_| argument("includeDeleted", InputType::opt(InputType::boolean()), None))
+}
@@ -53,7 +53,8 @@ lazy_static! {
     };
 }
 
-pub fn get_field_filters<'a>(field: &ModelField) -> Vec<&'a FilterArgument> {
+/// Filters of a field, without the ones the connector doesn't support.
+pub fn get_field_filters<'a>(field: &ModelField, capabilities: &ConnectorCapabilities) -> Vec<&'a FilterArgument> {
     let args = &FILTER_ARGUMENTS;
 
     let filters = match field {
@@ -66,8 +67,12 @@ pub fn get_field_filters<'a>(field: &ModelField) -> Vec<&'a FilterArgument> {
             TypeIdentifier::String => vec![&args.base, &args.inclusion, &args.alphanumeric, &args.string],
             TypeIdentifier::Int => vec![&args.base, &args.inclusion, &args.alphanumeric],
             TypeIdentifier::Float => vec![&args.base, &args.inclusion, &args.alphanumeric],
+            TypeIdentifier::Decimal => vec![&args.base, &args.inclusion, &args.alphanumeric],
             TypeIdentifier::Boolean => vec![&args.base],
-            TypeIdentifier::Enum => vec![&args.base, &args.inclusion],
+            TypeIdentifier::Enum if capabilities.contains(ConnectorCapabilities::ENUMS) => {
+                vec![&args.base, &args.inclusion]
+            }
+            TypeIdentifier::Enum => vec![],
             TypeIdentifier::DateTime => vec![&args.base, &args.inclusion, &args.alphanumeric],
             TypeIdentifier::Json => vec![],
             TypeIdentifier::Relation => unreachable!(),
@@ -1,9 +1,11 @@
 use super::*;
 
 /// Filter object and scalar filter object type builder.
+/// Fields the access policy doesn't allow to read can't be filtered by, their values could be guessed otherwise.
 #[derive(Debug)]
 pub struct FilterObjectTypeBuilder<'a> {
-    capabilities: &'a SupportedCapabilities,
+    capabilities: &'a ConnectorCapabilities,
+    access_policy: &'a AccessPolicy,
     input_object_cache: TypeRefCache<InputObjectType>, // Caches "xWhereInput" / "xWhereScalarInput" -> Object type ref
 }
 
@@ -20,9 +22,10 @@ impl<'a> CachedBuilder<InputObjectType> for FilterObjectTypeBuilder<'a> {
 }
 
 impl<'a> FilterObjectTypeBuilder<'a> {
-    pub fn new(capabilities: &'a SupportedCapabilities) -> Self {
+    pub fn new(capabilities: &'a ConnectorCapabilities, access_policy: &'a AccessPolicy) -> Self {
         FilterObjectTypeBuilder {
             capabilities,
+            access_policy,
             input_object_cache: TypeRefCache::new(),
         }
     }
@@ -54,7 +57,11 @@ impl<'a> FilterObjectTypeBuilder<'a> {
         ];
 
         let fields: Vec<ScalarFieldRef> = model.fields().scalar();
-        let mut fields: Vec<InputField> = fields.into_iter().flat_map(|f| self.map_input_field(f)).collect();
+        let fields = fields
+            .into_iter()
+            .filter(|f| !f.is_computed())
+            .filter(|f| self.access_policy.can_read(&model, &f.name));
+        let mut fields: Vec<InputField> = fields.flat_map(|f| self.map_input_field(f)).collect();
 
         input_fields.append(&mut fields);
         input_object.set_fields(input_fields);
@@ -63,14 +70,6 @@ impl<'a> FilterObjectTypeBuilder<'a> {
     }
 
     pub fn filter_object_type(&self, model: ModelRef) -> InputObjectTypeRef {
-        if self.capabilities.has(ConnectorCapability::MongoJoinRelationLinks) {
-            self.build_mongo_filter_object(model)
-        } else {
-            self.build_filter_object(model)
-        }
-    }
-
-    fn build_filter_object(&self, model: ModelRef) -> InputObjectTypeRef {
         let name = format!("{}WhereInput", model.name.clone());
         return_cached!(self.input_object_cache, &name);
 
@@ -100,6 +99,8 @@ impl<'a> FilterObjectTypeBuilder<'a> {
             .fields()
             .scalar()
             .into_iter()
+            .filter(|sf| !sf.is_computed())
+            .filter(|sf| self.access_policy.can_read(&model, &sf.name))
             .map(|sf| self.map_input_field(sf))
             .flatten()
             .collect();
@@ -108,6 +109,7 @@ impl<'a> FilterObjectTypeBuilder<'a> {
             .fields()
             .relation()
             .into_iter()
+            .filter(|rf| self.access_policy.can_read(&model, &rf.name))
             .map(|rf| self.map_relation_filter_input_field(rf))
             .flatten()
             .collect();
@@ -119,12 +121,8 @@ impl<'a> FilterObjectTypeBuilder<'a> {
         weak_ref
     }
 
-    fn build_mongo_filter_object(&self, _model: ModelRef) -> InputObjectTypeRef {
-        unimplemented!()
-    }
-
     fn map_input_field(&self, field: ScalarFieldRef) -> Vec<InputField> {
-        get_field_filters(&ModelField::Scalar(Arc::clone(&field))) // wip: take a look at required signatures
+        get_field_filters(&ModelField::Scalar(Arc::clone(&field)), self.capabilities) // wip: take a look at required signatures
             .into_iter()
             .map(|arg| {
                 let field_name = format!("{}{}", field.name, arg.suffix);
@@ -150,7 +148,7 @@ impl<'a> FilterObjectTypeBuilder<'a> {
                 InputType::opt(InputType::object(Weak::clone(&related_input_type))),
                 None,
             )],
-            true => get_field_filters(&ModelField::Relation(Arc::clone(&field)))
+            true => get_field_filters(&ModelField::Relation(Arc::clone(&field)), self.capabilities)
                 .into_iter()
                 .map(|arg| {
                     let field_name = format!("{}{}", field.name, arg.suffix);
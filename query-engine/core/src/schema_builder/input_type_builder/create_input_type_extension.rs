@@ -59,6 +59,7 @@ pub trait CreateInputTypeBuilderExtension<'a>: InputTypeBuilderBase<'a> {
             .fields()
             .relation()
             .into_iter()
+            .filter(|rf| self.access_policy().can_write(&model.name, &rf.name))
             .filter_map(|rf| {
                 let related_model = rf.related_model();
                 let related_field = rf.related_field();
@@ -118,6 +119,6 @@ pub trait CreateInputTypeBuilderExtension<'a>: InputTypeBuilderBase<'a> {
     }
 
     fn field_should_be_kept_for_create_input_type(field: &ScalarFieldRef) -> bool {
-        !field.is_auto_generated_int_id
+        !field.is_auto_generated_int_id && !field.is_computed()
     }
 }
@@ -11,6 +11,7 @@ pub trait InputBuilderExtensions {
             TypeIdentifier::String => InputType::string(),
             TypeIdentifier::Int => InputType::int(),
             TypeIdentifier::Float => InputType::float(),
+            TypeIdentifier::Decimal => InputType::decimal(),
             TypeIdentifier::Boolean => InputType::boolean(),
             TypeIdentifier::GraphQLID => InputType::string(),
             TypeIdentifier::UUID => InputType::uuid(),
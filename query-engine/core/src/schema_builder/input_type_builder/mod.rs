@@ -24,6 +24,12 @@ pub trait InputTypeBuilderBase<'a>: CachedBuilder<InputObjectType> + InputBuilde
         F: Fn(ScalarFieldRef) -> InputType,
     {
         let input_object_name = input_object_name.into();
+        // Fields the access policy doesn't allow to write are left out.
+        let prefiltered_fields: Vec<ScalarFieldRef> = prefiltered_fields
+            .into_iter()
+            .filter(|f| self.access_policy().can_write(&model_name, &f.name))
+            .collect();
+
         let mut non_list_fields: Vec<InputField> = prefiltered_fields
             .iter()
             .filter(|f| !f.is_list)
@@ -37,9 +43,11 @@ pub trait InputTypeBuilderBase<'a>: CachedBuilder<InputObjectType> + InputBuilde
             })
             .collect();
 
+        // Without scalar list support, list fields can't be written and are left out of the input types.
+        let supports_lists = self.capabilities().contains(ConnectorCapabilities::SCALAR_LISTS);
         let mut list_fields: Vec<InputField> = prefiltered_fields
             .into_iter()
-            .filter(|f| f.is_list)
+            .filter(|f| f.is_list && supports_lists)
             .map(|f| {
                 let name = f.name.clone();
                 let set_name = format!("{}{}{}Input", model_name, input_object_name, f.name);
@@ -103,6 +111,7 @@ pub trait InputTypeBuilderBase<'a>: CachedBuilder<InputObjectType> + InputBuilde
             .scalar()
             .iter()
             .filter(|f| f.unique() || f.is_id())
+            .filter(|f| self.access_policy().can_read(model, &f.name))
             .map(|f| Arc::clone(f))
             .collect();
 
@@ -115,6 +124,12 @@ pub trait InputTypeBuilderBase<'a>: CachedBuilder<InputObjectType> + InputBuilde
         let compound_unique_fields: Vec<InputField> = model
             .unique_indexes()
             .into_iter()
+            .filter(|index| {
+                index
+                    .fields()
+                    .iter()
+                    .all(|f| self.access_policy().can_read(model, &f.name))
+            })
             .map(|index| {
                 let typ = self.compound_field_unique_object_type(index.name.as_ref(), index.fields());
                 let name = compound_index_field_name(index);
@@ -135,6 +150,14 @@ pub trait InputTypeBuilderBase<'a>: CachedBuilder<InputObjectType> + InputBuilde
         fields.extend(compound_unique_fields);
         //        fields.extend(compound_id_field);
 
+        // Additional filters the record has to match, e.g. to check the ownership of a record in the same query.
+        let additional_filters = self.get_filter_object_builder().filter_object_type(Arc::clone(model));
+        fields.push(input_field(
+            "AND",
+            InputType::opt(InputType::object(additional_filters)),
+            None,
+        ));
+
         input_object.set_fields(fields);
 
         Arc::downgrade(&input_object)
@@ -174,6 +197,10 @@ pub trait InputTypeBuilderBase<'a>: CachedBuilder<InputObjectType> + InputBuilde
     }
 
     fn get_filter_object_builder(&self) -> Arc<FilterObjectTypeBuilder<'a>>;
+
+    fn capabilities(&self) -> &ConnectorCapabilities;
+
+    fn access_policy(&self) -> &AccessPolicy;
 }
 
 /// Central builder for input types.
@@ -183,6 +210,8 @@ pub trait InputTypeBuilderBase<'a>: CachedBuilder<InputObjectType> + InputBuilde
 #[derive(Debug)]
 pub struct InputTypeBuilder<'a> {
     internal_data_model: InternalDataModelRef,
+    capabilities: &'a ConnectorCapabilities,
+    access_policy: &'a AccessPolicy,
     input_type_cache: TypeRefCache<InputObjectType>,
     filter_object_builder: Weak<FilterObjectTypeBuilder<'a>>,
 }
@@ -203,6 +232,14 @@ impl<'a> InputTypeBuilderBase<'a> for InputTypeBuilder<'a> {
             .upgrade()
             .expect("Invariant violation: Expected input type builder reference to be valid")
     }
+
+    fn capabilities(&self) -> &ConnectorCapabilities {
+        self.capabilities
+    }
+
+    fn access_policy(&self) -> &AccessPolicy {
+        self.access_policy
+    }
 }
 
 impl<'a> InputBuilderExtensions for InputTypeBuilder<'a> {}
@@ -212,10 +249,14 @@ impl<'a> UpdateInputTypeBuilderExtension<'a> for InputTypeBuilder<'a> {}
 impl<'a> InputTypeBuilder<'a> {
     pub fn new(
         internal_data_model: InternalDataModelRef,
+        capabilities: &'a ConnectorCapabilities,
+        access_policy: &'a AccessPolicy,
         filter_object_builder: Weak<FilterObjectTypeBuilder<'a>>,
     ) -> Self {
         InputTypeBuilder {
             internal_data_model,
+            capabilities,
+            access_policy,
             input_type_cache: TypeRefCache::new(),
             filter_object_builder,
         }
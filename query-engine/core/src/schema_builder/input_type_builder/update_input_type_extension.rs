@@ -36,12 +36,42 @@ pub trait UpdateInputTypeBuilderExtension<'a>: InputTypeBuilderBase<'a> + Create
         self.scalar_input_fields(
             model.name.clone(),
             "Update",
-            model.fields().scalar(),
-            |f: ScalarFieldRef| self.map_optional_input_type(f),
+            model
+                .fields()
+                .scalar()
+                .into_iter()
+                .filter(|f| !f.is_computed())
+                .collect(),
+            |f: ScalarFieldRef| match f.type_identifier {
+                TypeIdentifier::Json => self.json_update_input_type(&model, f),
+                _ => self.map_optional_input_type(f),
+            },
             false,
         )
     }
 
+    /// Builds "<x>Update<field>Input" input object type of JSON fields. The document is either replaced with `set`,
+    /// or the JSON merge patch (RFC 7396) of `merge` is merged into it.
+    fn json_update_input_type(&self, model: &ModelRef, field: ScalarFieldRef) -> InputType {
+        let name = format!("{}Update{}Input", model.name, field.name);
+
+        let input_object = match self.get_cache().get(&name) {
+            Some(t) => t,
+            None => {
+                let fields = vec![
+                    input_field("set", self.map_optional_input_type(field), None),
+                    input_field("merge", InputType::opt(InputType::json()), None),
+                ];
+
+                let input_object = Arc::new(input_object_type(name.clone(), fields));
+                self.cache(name, Arc::clone(&input_object));
+                Arc::downgrade(&input_object)
+            }
+        };
+
+        InputType::opt(InputType::object(input_object))
+    }
+
     /// For update input types only. Compute input fields for relational fields.
     /// This recurses into create_input_type (via nested_create_input_field).
     /// Todo: This code is fairly similar to "create" relation computation. Let's see if we can dry it up.
@@ -54,6 +84,7 @@ pub trait UpdateInputTypeBuilderExtension<'a>: InputTypeBuilderBase<'a> + Create
             .fields()
             .relation()
             .into_iter()
+            .filter(|rf| self.access_policy().can_write(&model.name, &rf.name))
             .filter_map(|rf| {
                 let related_model = rf.related_model();
                 let related_field = rf.related_field();
@@ -16,7 +16,7 @@ use input_type_builder::*;
 use object_type_builder::*;
 
 /// Common module imports shared accross submodules.
-use crate::schema::*;
+use crate::{access_policy::*, schema::*};
 use prisma_models::{
     Field as ModelField, Index, InternalDataModelRef, ModelRef, RelationFieldRef, ScalarField, ScalarFieldRef,
     SortOrder, TypeIdentifier,
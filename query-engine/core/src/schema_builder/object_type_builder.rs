@@ -5,7 +5,8 @@ use prisma_models::OrderBy;
 pub struct ObjectTypeBuilder<'a> {
     internal_data_model: InternalDataModelRef,
     with_relations: bool,
-    capabilities: &'a SupportedCapabilities,
+    capabilities: &'a ConnectorCapabilities,
+    access_policy: &'a AccessPolicy,
     input_type_builder: Weak<InputTypeBuilder<'a>>,
     filter_object_type_builder: Weak<FilterObjectTypeBuilder<'a>>,
     object_type_cache: TypeRefCache<ObjectType>,
@@ -28,7 +29,8 @@ impl<'a> ObjectTypeBuilder<'a> {
     pub fn new(
         internal_data_model: InternalDataModelRef,
         with_relations: bool,
-        capabilities: &'a SupportedCapabilities,
+        capabilities: &'a ConnectorCapabilities,
+        access_policy: &'a AccessPolicy,
         filter_object_type_builder: Weak<FilterObjectTypeBuilder<'a>>,
         input_type_builder: Weak<InputTypeBuilder<'a>>,
     ) -> Self {
@@ -36,6 +38,7 @@ impl<'a> ObjectTypeBuilder<'a> {
             internal_data_model,
             with_relations,
             capabilities,
+            access_policy,
             filter_object_type_builder,
             input_type_builder,
             object_type_cache: TypeRefCache::new(),
@@ -71,8 +74,9 @@ impl<'a> ObjectTypeBuilder<'a> {
     }
 
     /// This assumes that the cache has already been initialized.
+    /// Fields the access policy doesn't allow to read are left out.
     fn compute_fields(&self, model: &ModelRef) -> Vec<Field> {
-        model
+        let mut fields: Vec<Field> = model
             .fields()
             .all
             .iter()
@@ -80,8 +84,36 @@ impl<'a> ObjectTypeBuilder<'a> {
                 ModelField::Scalar(_) => true,
                 ModelField::Relation(_) => self.with_relations,
             })
+            .filter(|f| self.access_policy.can_read(model, f.name()))
             .map(|f| self.map_field(f))
-            .collect()
+            .collect();
+
+        fields.extend(
+            model
+                .fields()
+                .relation()
+                .iter()
+                .filter(|rf| self.access_policy.can_read(model, &rf.name))
+                .filter_map(|rf| self.map_foreign_key_field(rf)),
+        );
+
+        fields
+    }
+
+    /// The scalar field reading the foreign key of an inlined relation straight off the record, without fetching
+    /// the related record, see `RelationField::foreign_key_scalar_name`.
+    fn map_foreign_key_field(&self, rf: &RelationFieldRef) -> Option<Field> {
+        let name = rf.foreign_key_scalar_name()?;
+        let (type_identifier, _) = rf.type_identifiers_with_arities().pop()?;
+        let output_type = Self::map_scalar_output_type(&type_identifier);
+
+        let output_type = if rf.is_required {
+            output_type
+        } else {
+            OutputType::opt(output_type)
+        };
+
+        Some(field(name, vec![], output_type, None))
     }
 
     pub fn map_field(&self, model_field: &ModelField) -> Field {
@@ -105,16 +137,8 @@ impl<'a> ObjectTypeBuilder<'a> {
                 }
             }
             ModelField::Scalar(sf) => match sf.type_identifier {
-                TypeIdentifier::String => OutputType::string(),
-                TypeIdentifier::Float => OutputType::float(),
-                TypeIdentifier::Boolean => OutputType::boolean(),
                 TypeIdentifier::Enum => Self::map_enum_field(sf).into(),
-                TypeIdentifier::Json => OutputType::json(),
-                TypeIdentifier::DateTime => OutputType::date_time(),
-                TypeIdentifier::GraphQLID => OutputType::string(),
-                TypeIdentifier::UUID => OutputType::uuid(),
-                TypeIdentifier::Int => OutputType::int(),
-                TypeIdentifier::Relation => unreachable!(), // Scalar fields can't have a Relation type identifier.
+                ref type_identifier => Self::map_scalar_output_type(type_identifier),
             },
         };
 
@@ -127,6 +151,23 @@ impl<'a> ObjectTypeBuilder<'a> {
         }
     }
 
+    fn map_scalar_output_type(type_identifier: &TypeIdentifier) -> OutputType {
+        match type_identifier {
+            TypeIdentifier::String => OutputType::string(),
+            TypeIdentifier::Float => OutputType::float(),
+            TypeIdentifier::Decimal => OutputType::decimal(),
+            TypeIdentifier::Boolean => OutputType::boolean(),
+            TypeIdentifier::Json => OutputType::json(),
+            TypeIdentifier::DateTime => OutputType::date_time(),
+            TypeIdentifier::GraphQLID => OutputType::string(),
+            TypeIdentifier::UUID => OutputType::uuid(),
+            TypeIdentifier::Int => OutputType::int(),
+            // Enums need their field, see `map_enum_field`.
+            TypeIdentifier::Enum => unreachable!(),
+            TypeIdentifier::Relation => unreachable!(), // Scalar fields can't have a Relation type identifier.
+        }
+    }
+
     /// Builds "many records where" arguments based on the given model and field.
     pub fn many_records_field_arguments(&self, field: &ModelField) -> Vec<Argument> {
         match field {
@@ -146,7 +187,7 @@ impl<'a> ObjectTypeBuilder<'a> {
             self.input_type_builder.into_arc().where_unique_object_type(model),
         ));
 
-        vec![
+        let mut args = vec![
             self.where_argument(&model),
             self.order_by_argument(&model),
             argument("skip", InputType::opt(InputType::int()), None),
@@ -154,7 +195,11 @@ impl<'a> ObjectTypeBuilder<'a> {
             argument("before", unique_input_type, None),
             argument("first", InputType::opt(InputType::int()), None),
             argument("last", InputType::opt(InputType::int()), None),
-        ]
+            argument("take", InputType::opt(InputType::int()), None),
+        ];
+
+        append_opt(&mut args, include_deleted_argument(model));
+        args
     }
 
     /// Builds "where" argument.
@@ -167,12 +212,14 @@ impl<'a> ObjectTypeBuilder<'a> {
         argument("where", InputType::opt(InputType::object(where_object)), None)
     }
 
-    // Builds "orderBy" argument.
+    // Builds "orderBy" argument, without the fields the access policy hides.
     pub fn order_by_argument(&self, model: &ModelRef) -> Argument {
         let enum_values: Vec<_> = model
             .fields()
             .scalar_non_list()
             .iter()
+            .filter(|field| !field.is_computed())
+            .filter(|field| self.access_policy.can_read(model, &field.name))
             .map(|field| {
                 vec![
                     (
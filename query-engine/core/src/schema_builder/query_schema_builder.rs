@@ -1,5 +1,6 @@
 use super::*;
 use crate::{query_graph_builder::*, Query, QueryGraph};
+use connector::Routine;
 use prisma_models::dml;
 
 /// Build mode for schema generation.
@@ -47,24 +48,38 @@ pub enum BuildMode {
 pub struct QuerySchemaBuilder<'a> {
     mode: BuildMode,
     internal_data_model: InternalDataModelRef,
-    _capabilities: &'a SupportedCapabilities,
+    capabilities: &'a ConnectorCapabilities,
+    access_policy: &'a AccessPolicy,
     object_type_builder: Arc<ObjectTypeBuilder<'a>>,
     input_type_builder: Arc<InputTypeBuilder<'a>>,
     argument_builder: ArgumentBuilder<'a>,
     filter_object_type_builder: Arc<FilterObjectTypeBuilder<'a>>,
     enable_raw_queries: bool,
+    routines: Vec<Routine>,
 }
 
 impl<'a> QuerySchemaBuilder<'a> {
     pub fn new(
         internal_data_model: &InternalDataModelRef,
-        capabilities: &'a SupportedCapabilities,
+        capabilities: &'a ConnectorCapabilities,
         mode: BuildMode,
         enable_raw_queries: bool,
     ) -> Self {
-        let filter_object_type_builder = Arc::new(FilterObjectTypeBuilder::new(capabilities));
+        Self::with_access_policy(internal_data_model, capabilities, &ALLOW_ALL, mode, enable_raw_queries)
+    }
+
+    fn with_access_policy(
+        internal_data_model: &InternalDataModelRef,
+        capabilities: &'a ConnectorCapabilities,
+        access_policy: &'a AccessPolicy,
+        mode: BuildMode,
+        enable_raw_queries: bool,
+    ) -> Self {
+        let filter_object_type_builder = Arc::new(FilterObjectTypeBuilder::new(capabilities, access_policy));
         let input_type_builder = Arc::new(InputTypeBuilder::new(
             Arc::clone(internal_data_model),
+            capabilities,
+            access_policy,
             Arc::downgrade(&filter_object_type_builder),
         ));
 
@@ -72,6 +87,7 @@ impl<'a> QuerySchemaBuilder<'a> {
             Arc::clone(internal_data_model),
             true,
             capabilities,
+            access_policy,
             Arc::downgrade(&filter_object_type_builder),
             Arc::downgrade(&input_type_builder),
         ));
@@ -83,16 +99,36 @@ impl<'a> QuerySchemaBuilder<'a> {
 
         QuerySchemaBuilder {
             internal_data_model: Arc::clone(internal_data_model),
-            _capabilities: capabilities,
+            capabilities,
+            access_policy,
             mode,
             object_type_builder,
             input_type_builder,
             argument_builder,
             filter_object_type_builder,
             enable_raw_queries,
+            routines: Vec::new(),
         }
     }
 
+    /// Leaves out the operations and fields the policy forbids.
+    pub fn access_policy(self, policy: &'a AccessPolicy) -> Self {
+        Self::with_access_policy(
+            &self.internal_data_model,
+            self.capabilities,
+            policy,
+            self.mode,
+            self.enable_raw_queries,
+        )
+        .routines(self.routines)
+    }
+
+    /// Stored procedures and functions exposed as `call<Routine>` mutations, only if raw queries are enabled.
+    pub fn routines(mut self, val: Vec<Routine>) -> Self {
+        self.routines = val;
+        self
+    }
+
     /// Consumes the builders and collects all types from all builder caches to merge
     /// them into the vectors required to finalize the query schema building.
     /// Unwraps are safe because only the query schema builder holds the strong ref,
@@ -136,6 +172,7 @@ impl<'a> QuerySchemaBuilder<'a> {
         let non_embedded_models = self.non_embedded_models();
         let fields = non_embedded_models
             .into_iter()
+            .filter(|m| self.access_policy.allows(&m.name, ModelOperation::Read))
             .map(|m| {
                 let mut vec = vec![
                     self.all_items_field(Arc::clone(&m)),
@@ -159,22 +196,45 @@ impl<'a> QuerySchemaBuilder<'a> {
         let mut fields: Vec<Field> = non_embedded_models
             .into_iter()
             .map(|model| {
-                let mut vec = vec![self.create_item_field(Arc::clone(&model))];
+                let allows = |operation| self.access_policy.allows(&model.name, operation);
+                let mut vec = vec![];
+
+                if allows(ModelOperation::Create) {
+                    vec.push(self.create_item_field(Arc::clone(&model)));
+                }
+
+                if allows(ModelOperation::Delete) {
+                    append_opt(&mut vec, self.delete_item_field(Arc::clone(&model)));
+                }
 
-                append_opt(&mut vec, self.delete_item_field(Arc::clone(&model)));
-                append_opt(&mut vec, self.update_item_field(Arc::clone(&model)));
-                append_opt(&mut vec, self.upsert_item_field(Arc::clone(&model)));
+                if allows(ModelOperation::Update) {
+                    append_opt(&mut vec, self.update_item_field(Arc::clone(&model)));
+                }
 
-                vec.push(self.update_many_field(Arc::clone(&model)));
-                vec.push(self.delete_many_field(Arc::clone(&model)));
+                if allows(ModelOperation::Create) && allows(ModelOperation::Update) {
+                    append_opt(&mut vec, self.upsert_item_field(Arc::clone(&model)));
+                }
+
+                if allows(ModelOperation::Update) {
+                    vec.push(self.update_many_field(Arc::clone(&model)));
+                }
+
+                if allows(ModelOperation::Delete) {
+                    vec.push(self.delete_many_field(Arc::clone(&model)));
+                }
 
                 vec
             })
             .flatten()
             .collect();
 
-        if self.enable_raw_queries {
+        if self.enable_raw_queries && self.access_policy.allows_raw_queries() {
             fields.push(self.create_execute_raw_field());
+            fields.extend(
+                self.routines
+                    .iter()
+                    .filter_map(|routine| self.call_routine_field(routine)),
+            );
         }
 
         let strong_ref = Arc::new(object_type("Mutation", fields, None));
@@ -200,9 +260,12 @@ impl<'a> QuerySchemaBuilder<'a> {
                 let field_name =
                     self.pluralize_internal(camel_case(model.name.clone()), format!("findOne{}", model.name.clone()));
 
+                let mut args = vec![arg];
+                append_opt(&mut args, include_deleted_argument(&model));
+
                 field(
                     field_name,
-                    vec![arg],
+                    args,
                     OutputType::opt(OutputType::object(
                         self.object_type_builder.map_model_object_type(&model),
                     )),
@@ -292,6 +355,53 @@ impl<'a> QuerySchemaBuilder<'a> {
         )
     }
 
+    /// Builds a call mutation field (e.g. callRefreshStats) for given routine.
+    /// Routines whose names aren't valid in the schema are left out.
+    fn call_routine_field(&self, routine: &Routine) -> Option<Field> {
+        let is_valid_name = |name: &str| {
+            name.chars().next().map(|c| c.is_ascii_alphabetic()).unwrap_or(false)
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+
+        if !is_valid_name(&routine.name) || routine.parameters.iter().any(|param| !is_valid_name(&param.name)) {
+            return None;
+        }
+
+        let args = routine
+            .parameters
+            .iter()
+            .map(|param| {
+                let typ = match param.type_identifier {
+                    TypeIdentifier::String => InputType::string(),
+                    TypeIdentifier::Int => InputType::int(),
+                    TypeIdentifier::Float => InputType::float(),
+                    TypeIdentifier::Decimal => InputType::decimal(),
+                    TypeIdentifier::Boolean => InputType::boolean(),
+                    TypeIdentifier::GraphQLID => InputType::string(),
+                    TypeIdentifier::UUID => InputType::uuid(),
+                    TypeIdentifier::DateTime => InputType::date_time(),
+                    TypeIdentifier::Json => InputType::json(),
+                    TypeIdentifier::Enum | TypeIdentifier::Relation => unreachable!(),
+                };
+
+                let typ = if param.is_list { InputType::list(typ) } else { typ };
+
+                // Database routines accept null for every parameter.
+                argument(param.name.clone(), InputType::opt(typ), None)
+            })
+            .collect();
+
+        // The columns of the result are only known once the routine ran, every row is an object of them.
+        Some(field(
+            format!("call{}", capitalize(routine.name.clone())),
+            args,
+            OutputType::list(OutputType::json()),
+            Some(SchemaQueryBuilder::RoutineQueryBuilder(RoutineQueryBuilder::new(
+                routine.clone(),
+            ))),
+        ))
+    }
+
     /// Builds a create mutation field (e.g. createUser) for given model.
     fn create_item_field(&self, model: ModelRef) -> Field {
         let args = self
@@ -0,0 +1,65 @@
+use connector::error::ConnectorError;
+use datamodel::error::ErrorCollection;
+use failure::Fail;
+use query_core::{response_ir, CoreError};
+
+#[cfg(feature = "sql")]
+use sql_connector::SqlError;
+
+pub type EngineResult<T> = Result<T, EngineError>;
+
+#[derive(Debug, Fail)]
+pub enum EngineError {
+    #[fail(display = "{}", _0)]
+    ConfigurationError(String),
+
+    #[fail(display = "Error in data model: {}", _0)]
+    DatamodelError(ErrorCollection),
+
+    #[fail(display = "{}", _0)]
+    CoreError(CoreError),
+
+    #[fail(display = "{}", _0)]
+    QueryConversionError(String),
+}
+
+impl From<CoreError> for EngineError {
+    fn from(e: CoreError) -> Self {
+        EngineError::CoreError(e)
+    }
+}
+
+impl From<ConnectorError> for EngineError {
+    fn from(e: ConnectorError) -> Self {
+        EngineError::CoreError(CoreError::from(e))
+    }
+}
+
+impl From<ErrorCollection> for EngineError {
+    fn from(e: ErrorCollection) -> Self {
+        EngineError::DatamodelError(e)
+    }
+}
+
+/// Returns engine errors as part of a response.
+impl From<EngineError> for response_ir::ResponseError {
+    fn from(other: EngineError) -> Self {
+        match other {
+            EngineError::CoreError(core_error) => response_ir::ResponseError::from(core_error),
+            err => response_ir::ResponseError::from(user_facing_errors::Error::from_dyn_error(&err.compat())),
+        }
+    }
+}
+
+impl From<url::ParseError> for EngineError {
+    fn from(e: url::ParseError) -> EngineError {
+        EngineError::ConfigurationError(format!("Error parsing connection string: {}", e))
+    }
+}
+
+#[cfg(feature = "sql")]
+impl From<SqlError> for EngineError {
+    fn from(e: SqlError) -> EngineError {
+        EngineError::ConfigurationError(format!("{}", e))
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{EngineError, EngineResult};
+use connector::{Connector, ConnectorCapabilities};
+use datamodel::{
+    configuration::{MYSQL_SOURCE_NAME, POSTGRES_SOURCE_NAME, SQLITE_SOURCE_NAME},
+    Source,
+};
+use query_core::{
+    executor::{InterpretingExecutor, Middleware, QueryExecutor, QueryLimits},
+    AccessPolicy, FailurePolicy,
+};
+use sql_identifier::Delimiter;
+use std::{collections::HashMap, sync::Arc};
+use url::Url;
+
+#[cfg(feature = "sql")]
+use sql_connector::*;
+
+/// Connects to the database of the data source. Returns the name of the database and an executor for it.
+pub async fn load(
+    source: &(dyn Source + Send + Sync),
+    force_transactions: bool,
+    limits: QueryLimits,
+    access_policy: AccessPolicy,
+) -> EngineResult<(String, Box<dyn QueryExecutor + Send + Sync + 'static>)> {
+    let (db_name, connector_type, connector) = load_connector(source).await?;
+    let force_transactions = force_transactions && forces_transactions(source);
+
+    Ok((
+        db_name,
+        Box::new(
+            InterpretingExecutor::new(connector, connector_type, force_transactions, limits)
+                .with_access_policy(access_policy),
+        ),
+    ))
+}
+
+/// Connects to the databases of all data sources. Returns the name of the database of the first data source,
+/// the names of the databases of the others by data source name, and an executor that runs the queries on
+/// each model on the database of its data source, wrapped by the middlewares.
+pub async fn load_all(
+    sources: &[Box<dyn Source + Send + Sync>],
+    force_transactions: bool,
+    limits: QueryLimits,
+    access_policy: AccessPolicy,
+    failure_policy: FailurePolicy,
+    middlewares: Vec<Arc<dyn Middleware>>,
+) -> EngineResult<(
+    String,
+    HashMap<String, String>,
+    Box<dyn QueryExecutor + Send + Sync + 'static>,
+)> {
+    let (primary, others) = sources
+        .split_first()
+        .ok_or_else(|| EngineError::ConfigurationError("No valid data source found".into()))?;
+
+    let (db_name, connector_type, primary_connector) = load_connector(&**primary).await?;
+    let force_transactions = force_transactions && forces_transactions(&**primary);
+
+    let mut executor = InterpretingExecutor::new(primary_connector, connector_type, force_transactions, limits)
+        .with_access_policy(access_policy)
+        .with_failure_policy(failure_policy);
+    let mut db_names = HashMap::new();
+
+    for source in others {
+        let (other_db_name, _, other_connector) = load_connector(&**source).await?;
+
+        db_names.insert(source.name().clone(), other_db_name);
+        executor = executor.with_datasource(source.name().clone(), other_connector);
+    }
+
+    for middleware in middlewares {
+        executor = executor.with_middleware(middleware);
+    }
+
+    Ok((db_name, db_names, Box::new(executor)))
+}
+
+/// Connects to the database of the data source. Returns the name of the database, the name of the connector
+/// and the connector.
+async fn load_connector(
+    source: &(dyn Source + Send + Sync),
+) -> EngineResult<(String, &'static str, Box<dyn Connector + Send + Sync + 'static>)> {
+    match source.connector_type() {
+        #[cfg(feature = "sql")]
+        SQLITE_SOURCE_NAME => sqlite(source).await,
+
+        #[cfg(feature = "sql")]
+        MYSQL_SOURCE_NAME => mysql(source).await,
+
+        #[cfg(feature = "sql")]
+        POSTGRES_SOURCE_NAME => postgres(source).await,
+
+        #[cfg(feature = "connector-registry")]
+        x if connector::registered_connector(x).is_some() => registered(source).await,
+
+        x => Err(EngineError::ConfigurationError(format!(
+            "Unsupported connector type: {}",
+            x
+        ))),
+    }
+}
+
+/// SQLite and MySQL always run single operations without transaction.
+fn forces_transactions(source: &dyn Source) -> bool {
+    match source.connector_type() {
+        SQLITE_SOURCE_NAME | MYSQL_SOURCE_NAME => false,
+        _ => true,
+    }
+}
+
+/// MySQL sets `@updatedAt` fields with a `@default(now())` on updates itself, the migration engine creates their
+/// columns with `ON UPDATE CURRENT_TIMESTAMP`.
+pub fn updates_timestamps(source: &dyn Source) -> bool {
+    source.connector_type() == MYSQL_SOURCE_NAME
+}
+
+/// The delimiter of identifiers in the SQL dialect of the data source, `None` for connectors not speaking SQL.
+pub fn identifier_delimiter(source: &dyn Source) -> Option<Delimiter> {
+    match source.connector_type() {
+        MYSQL_SOURCE_NAME => Some(Delimiter::Backtick),
+        POSTGRES_SOURCE_NAME | SQLITE_SOURCE_NAME => Some(Delimiter::DoubleQuote),
+        _ => None,
+    }
+}
+
+/// The capabilities of the connector of the data source, which decide the filters and inputs of the query schema.
+pub fn capabilities(source: &dyn Source) -> ConnectorCapabilities {
+    match source.connector_type() {
+        POSTGRES_SOURCE_NAME => ConnectorCapabilities::all(),
+        MYSQL_SOURCE_NAME => {
+            ConnectorCapabilities::JSON
+                | ConnectorCapabilities::ENUMS
+                | ConnectorCapabilities::SCALAR_LISTS
+                | ConnectorCapabilities::CORRELATED_SUBQUERIES
+                | ConnectorCapabilities::FULL_TEXT_SEARCH
+        }
+        SQLITE_SOURCE_NAME => {
+            ConnectorCapabilities::ENUMS
+                | ConnectorCapabilities::SCALAR_LISTS
+                | ConnectorCapabilities::CORRELATED_SUBQUERIES
+        }
+        #[cfg(feature = "connector-registry")]
+        x => connector::registered_connector(x)
+            .map(|definition| definition.capabilities())
+            .unwrap_or_else(ConnectorCapabilities::empty),
+
+        #[cfg(not(feature = "connector-registry"))]
+        _ => ConnectorCapabilities::empty(),
+    }
+}
+
+/// The capabilities all connectors of the data sources share. Without data sources, these are all capabilities.
+pub fn shared_capabilities(sources: &[Box<dyn Source + Send + Sync>]) -> ConnectorCapabilities {
+    sources
+        .iter()
+        .fold(ConnectorCapabilities::all(), |capabilities, source| {
+            capabilities & self::capabilities(&**source)
+        })
+}
+
+#[cfg(feature = "connector-registry")]
+async fn registered(
+    source: &(dyn Source + Send + Sync),
+) -> EngineResult<(String, &'static str, Box<dyn Connector + Send + Sync + 'static>)> {
+    let definition = connector::registered_connector(source.connector_type()).unwrap();
+    trace!("Loading registered {} connector...", definition.connector_type());
+
+    let (db_name, connector) = definition.load(source.url().value.expose()).await?;
+
+    trace!("Loaded registered {} connector.", definition.connector_type());
+    Ok((db_name, definition.connector_type(), connector))
+}
+
+#[cfg(feature = "sql")]
+async fn sqlite(
+    source: &(dyn Source + Send + Sync),
+) -> EngineResult<(String, &'static str, Box<dyn Connector + Send + Sync + 'static>)> {
+    trace!("Loading SQLite connector...");
+
+    if DriverSqlite::handles_url(source.url().value.expose()) {
+        let sqlite = DriverSqlite::from_source(source).await?;
+        let db_name = sqlite.db_name().to_owned();
+
+        trace!("Loaded SQLite connector over HTTP.");
+        return Ok((db_name, "sqlite", Box::new(sqlite)));
+    }
+
+    let sqlite = Sqlite::from_source(source).await?;
+    let db_name = sqlite.db_name().to_owned();
+
+    trace!("Loaded SQLite connector.");
+    Ok((db_name, "sqlite", Box::new(sqlite)))
+}
+
+#[cfg(feature = "sql")]
+async fn postgres(
+    source: &(dyn Source + Send + Sync),
+) -> EngineResult<(String, &'static str, Box<dyn Connector + Send + Sync + 'static>)> {
+    trace!("Loading Postgres connector...");
+
+    let url = Url::parse(source.url().value.expose())?;
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+    let db_name = params
+        .get("schema")
+        .map(ToString::to_string)
+        .unwrap_or_else(|| String::from("public"));
+
+    let psql = PostgreSql::from_source(source)
+        .await?
+        .capabilities(capabilities(source));
+
+    trace!("Loaded Postgres connector.");
+    Ok((db_name, "postgres", Box::new(psql)))
+}
+
+#[cfg(feature = "sql")]
+async fn mysql(
+    source: &(dyn Source + Send + Sync),
+) -> EngineResult<(String, &'static str, Box<dyn Connector + Send + Sync + 'static>)> {
+    trace!("Loading MySQL connector...");
+
+    let mysql = Mysql::from_source(source).await?;
+    let url = Url::parse(source.url().value.expose())?;
+    let err_str = "No database found in connection string";
+
+    let mut db_name = url
+        .path_segments()
+        .ok_or_else(|| EngineError::ConfigurationError(err_str.into()))?;
+
+    let db_name = db_name.next().expect(err_str).to_owned();
+
+    trace!("Loaded MySQL connector.");
+    Ok((db_name, "mysql", Box::new(mysql)))
+}
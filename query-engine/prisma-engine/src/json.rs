@@ -0,0 +1,114 @@
+//! Structured JSON queries, an alternative to GraphQL documents that needs no query parsing.
+use crate::{EngineError, EngineResult};
+use query_core::query_document::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// The kind of operation a JSON request executes.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JsonAction {
+    Query,
+    Mutation,
+}
+
+/// A single (possibly nested) selection, the JSON equivalent of a GraphQL field.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSelection {
+    pub name: String,
+    #[serde(default)]
+    pub alias: Option<String>,
+    #[serde(default)]
+    pub arguments: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub selection: Vec<JsonSelection>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSingleQuery {
+    pub action: JsonAction,
+    pub selection: Vec<JsonSelection>,
+}
+
+/// Protocol adapter for JSON -> Query Document.
+///
+/// JSON is mapped as following:
+/// - The `action` designates whether the top-level selections are mapped to `Operation::Read`s or `Operation::Write`s.
+/// - Every top-level selection is mapped to exactly one `Operation`.
+/// - Selections are mapped to `Selection`s, including arguments and nested selections.
+/// - JSON values are mapped to `QueryValue`s. Enum values are expected to be sent as strings.
+pub struct JsonProtocolAdapter;
+
+impl JsonProtocolAdapter {
+    pub fn convert(query: JsonSingleQuery) -> EngineResult<QueryDocument> {
+        let action = query.action;
+        let selections = query
+            .selection
+            .into_iter()
+            .map(Self::convert_selection)
+            .collect::<EngineResult<Vec<Selection>>>()?;
+
+        let operations = selections
+            .into_iter()
+            .map(|selection| match action {
+                JsonAction::Query => Operation::Read(selection),
+                JsonAction::Mutation => Operation::Write(selection),
+            })
+            .collect();
+
+        Ok(QueryDocument { operations }.dedup_operations())
+    }
+
+    fn convert_selection(selection: JsonSelection) -> EngineResult<Selection> {
+        let arguments = selection
+            .arguments
+            .into_iter()
+            .map(|(k, v)| Ok((k, Self::convert_value(v)?)))
+            .collect::<EngineResult<Vec<_>>>()?;
+
+        let nested_selections = selection
+            .selection
+            .into_iter()
+            .map(Self::convert_selection)
+            .collect::<EngineResult<Vec<_>>>()?;
+
+        Ok(Selection {
+            name: selection.name,
+            alias: selection.alias,
+            arguments,
+            nested_selections,
+        })
+    }
+
+    fn convert_value(value: Value) -> EngineResult<QueryValue> {
+        match value {
+            Value::Null => Ok(QueryValue::Null),
+            Value::Bool(b) => Ok(QueryValue::Boolean(b)),
+            Value::String(s) => Ok(QueryValue::String(s)),
+            Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+                (Some(i), _) => Ok(QueryValue::Int(i)),
+                (None, Some(f)) => Ok(QueryValue::Float(f)),
+                (None, None) => Err(EngineError::QueryConversionError(format!("Invalid number: {}", n))),
+            },
+            Value::Array(values) => {
+                let values: Vec<QueryValue> = values
+                    .into_iter()
+                    .map(Self::convert_value)
+                    .collect::<EngineResult<Vec<QueryValue>>>()?;
+
+                Ok(QueryValue::List(values))
+            }
+            Value::Object(map) => {
+                let values = map
+                    .into_iter()
+                    .map(|(k, v)| Self::convert_value(v).map(|v| (k, v)))
+                    .collect::<EngineResult<BTreeMap<String, QueryValue>>>()?;
+
+                Ok(QueryValue::Object(values))
+            }
+        }
+    }
+}
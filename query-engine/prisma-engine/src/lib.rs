@@ -0,0 +1,259 @@
+//! The query engine as a library, for Rust applications that embed it in-process instead of running the
+//! HTTP server binary.
+//!
+//! ```ignore
+//! let engine = Engine::new(datamodel, "postgresql://localhost:5432/db?schema=public").await?;
+//!
+//! let response = engine
+//!     .execute(Operation::Read(Selection {
+//!         name: "findManyUser".to_owned(),
+//!         alias: None,
+//!         arguments: vec![],
+//!         nested_selections: vec![Selection {
+//!             name: "id".to_owned(),
+//!             alias: None,
+//!             arguments: vec![],
+//!             nested_selections: vec![],
+//!         }],
+//!     }))
+//!     .await?;
+//! ```
+#[macro_use]
+extern crate log;
+
+mod error;
+pub mod exec_loader;
+pub mod json;
+
+pub use error::*;
+
+/// Connectors shipped by other crates register here, with a source definition for their provider.
+#[cfg(feature = "connector-registry")]
+pub use connector::{register_connector, ConnectorDefinition};
+#[cfg(feature = "connector-registry")]
+pub use datamodel::configuration::register_source_definition;
+pub use query_core::{
+    response_ir::{Item, Response, Responses},
+    transaction_channel, AccessPolicy, CoreError, CoreResult, FailurePolicy, Middleware, NestedReadFailure, Next,
+    Operation, QueryDocument, QueryLimits, QueryValue, Selection, TransactionReceiver, TransactionRequest,
+    TransactionSendError, TransactionSender,
+};
+
+use datamodel::SourceOverride;
+use prisma_models::DatamodelConverter;
+use query_core::{schema::QuerySchemaRef, BuildMode, QueryExecutor, QuerySchemaBuilder};
+use secret_string::SecretString;
+use std::sync::Arc;
+
+/// A query engine for one data model, connected to the databases of its data sources.
+pub struct Engine {
+    datamodel: datamodel::Datamodel,
+    query_schema: QuerySchemaRef,
+    executor: Box<dyn QueryExecutor + Send + Sync + 'static>,
+}
+
+pub struct EngineBuilder {
+    datamodel: String,
+    url: Option<SecretString>,
+    datasource_overrides: Vec<SourceOverride>,
+    legacy: bool,
+    force_transactions: bool,
+    enable_raw_queries: bool,
+    limits: QueryLimits,
+    access_policy: AccessPolicy,
+    failure_policy: FailurePolicy,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl EngineBuilder {
+    /// Connects to the given database instead of the one configured in the first data source of the data model.
+    pub fn url(mut self, val: &str) -> Self {
+        self.url = Some(val.into());
+        self
+    }
+
+    /// Connects to the given database instead of the one configured in the named data source, e.g. to run the same
+    /// data model against a test database. The `url` of the data source isn't evaluated, so an environment variable
+    /// it reads doesn't need to be set.
+    pub fn datasource_url(mut self, name: &str, url: &str) -> Self {
+        self.datasource_overrides.push(SourceOverride {
+            name: name.to_owned(),
+            url: url.into(),
+        });
+        self
+    }
+
+    /// Builds the query schema of the legacy API.
+    pub fn legacy(mut self, val: bool) -> Self {
+        self.legacy = val;
+        self
+    }
+
+    pub fn force_transactions(mut self, val: bool) -> Self {
+        self.force_transactions = val;
+        self
+    }
+
+    pub fn enable_raw_queries(mut self, val: bool) -> Self {
+        self.enable_raw_queries = val;
+        self
+    }
+
+    pub fn limits(mut self, val: QueryLimits) -> Self {
+        self.limits = val;
+        self
+    }
+
+    /// Leaves the operations and fields the policy forbids out of the query schema and rejects them on execution.
+    pub fn access_policy(mut self, val: AccessPolicy) -> Self {
+        self.access_policy = val;
+        self
+    }
+
+    /// Retries failing nested reads, e.g. of the relations selected along with a query, and returns the data that
+    /// could be read along with their errors instead of failing the whole operation, as configured by the policy.
+    pub fn failure_policy(mut self, val: FailurePolicy) -> Self {
+        self.failure_policy = val;
+        self
+    }
+
+    /// Wraps the execution of every operation with the middleware, e.g. to log, cache or rewrite operations. The
+    /// middlewares are called in the order they were added.
+    pub fn middleware(mut self, val: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(val));
+        self
+    }
+
+    pub async fn build(self) -> EngineResult<Engine> {
+        let datamodel = datamodel::parse_datamodel_with_overrides(&self.datamodel, &self.datasource_overrides)?;
+        let mut configuration =
+            datamodel::parse_configuration_with_overrides(&self.datamodel, &self.datasource_overrides)?;
+
+        let data_source = configuration
+            .datasources
+            .first_mut()
+            .ok_or_else(|| EngineError::ConfigurationError("No valid data source found".into()))?;
+
+        if let Some(ref url) = self.url {
+            data_source.set_url(url.expose());
+        }
+
+        let relation_mode = data_source.relation_mode();
+        let updated_at_in_database = exec_loader::updates_timestamps(&**data_source);
+        let identifier_delimiter = exec_loader::identifier_delimiter(&**data_source);
+        let (db_name, datasource_db_names, executor) = exec_loader::load_all(
+            &configuration.datasources,
+            self.force_transactions,
+            self.limits,
+            self.access_policy.clone(),
+            self.failure_policy,
+            self.middlewares,
+        )
+        .await?;
+
+        let mut template = DatamodelConverter::convert(&datamodel);
+        template.relation_mode = relation_mode;
+        template.datasource_db_names = datasource_db_names;
+        template.updated_at_in_database = updated_at_in_database;
+        template.identifier_delimiter = identifier_delimiter;
+
+        let internal_data_model = template.build(db_name);
+        let capabilities = exec_loader::shared_capabilities(&configuration.datasources);
+
+        // Routines run arbitrary database code, so they are only exposed along with raw queries.
+        let routines = if self.enable_raw_queries {
+            executor.routines().await?
+        } else {
+            Vec::new()
+        };
+
+        let build_mode = if self.legacy {
+            BuildMode::Legacy
+        } else {
+            BuildMode::Modern
+        };
+        let query_schema =
+            QuerySchemaBuilder::new(&internal_data_model, &capabilities, build_mode, self.enable_raw_queries)
+                .routines(routines)
+                .access_policy(&self.access_policy)
+                .build();
+
+        debug!("Initialized engine for the {} connector.", executor.primary_connector());
+
+        Ok(Engine {
+            datamodel,
+            query_schema: Arc::new(query_schema),
+            executor,
+        })
+    }
+}
+
+impl Engine {
+    /// Parses the data model and connects to the database at the given URL.
+    pub async fn new(datamodel: &str, url: &str) -> EngineResult<Self> {
+        Self::builder(datamodel).url(url).build().await
+    }
+
+    pub fn builder(datamodel: &str) -> EngineBuilder {
+        EngineBuilder {
+            datamodel: datamodel.to_owned(),
+            url: None,
+            datasource_overrides: Vec::new(),
+            legacy: false,
+            force_transactions: false,
+            enable_raw_queries: false,
+            limits: QueryLimits::default(),
+            access_policy: AccessPolicy::default(),
+            failure_policy: FailurePolicy::default(),
+            middlewares: Vec::new(),
+        }
+    }
+
+    /// Executes a single query or mutation.
+    pub async fn execute(&self, operation: Operation) -> EngineResult<Responses> {
+        self.execute_document(QueryDocument {
+            operations: vec![operation],
+        })
+        .await
+    }
+
+    /// Executes all operations of the document.
+    pub async fn execute_document(&self, query_doc: QueryDocument) -> EngineResult<Responses> {
+        let responses = self.executor.execute(query_doc, Arc::clone(&self.query_schema)).await?;
+
+        Ok(responses)
+    }
+
+    /// Executes all operations of the document in a single transaction, either all of them are committed or none.
+    pub async fn execute_transaction(&self, query_doc: QueryDocument) -> EngineResult<Responses> {
+        let responses = self
+            .executor
+            .execute_transaction(query_doc, Arc::clone(&self.query_schema))
+            .await?;
+
+        Ok(responses)
+    }
+
+    /// Executes the documents of the requests received over the channel in a single transaction, until a commit or a
+    /// rollback is requested. Closing the channel rolls the transaction back. Returns once the transaction ended.
+    pub async fn interactive_transaction(&self, requests: TransactionReceiver) -> EngineResult<()> {
+        self.executor
+            .interactive_transaction(Arc::clone(&self.query_schema), requests)
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn datamodel(&self) -> &datamodel::Datamodel {
+        &self.datamodel
+    }
+
+    pub fn query_schema(&self) -> &QuerySchemaRef {
+        &self.query_schema
+    }
+
+    /// Access to the executor, e.g. to subscribe to record events or check the readiness of the connection pool.
+    pub fn executor(&self) -> &(dyn QueryExecutor + Send + Sync + 'static) {
+        &*self.executor
+    }
+}
@@ -0,0 +1,42 @@
+use prisma_engine::{Engine, Item, Operation, QueryValue, Responses, Selection};
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+
+fn execute_raw(query: &str) -> Operation {
+    Operation::Write(Selection {
+        name: "executeRaw".to_owned(),
+        alias: None,
+        arguments: vec![("query".to_owned(), QueryValue::String(query.to_owned()))],
+        nested_selections: vec![],
+    })
+}
+
+fn assert_failed(result: Result<Responses, impl std::fmt::Debug>) {
+    match result {
+        Ok(responses) => assert!(responses.has_errors()),
+        Err(_) => (),
+    }
+}
+
+#[tokio::test]
+async fn engines_execute_operations_against_their_data_source() {
+    let engine = Engine::builder(IN_MEMORY_SQLITE_DATAMODEL)
+        .enable_raw_queries(true)
+        .build()
+        .await
+        .unwrap();
+
+    let (data, errors) = engine.execute(execute_raw("SELECT 1")).await.unwrap().into_parts();
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert!(match data.get("executeRaw") {
+        Some(Item::Json(_)) => true,
+        _ => false,
+    });
+}
+
+#[tokio::test]
+async fn raw_queries_are_only_executed_if_enabled() {
+    let engine = Engine::new(IN_MEMORY_SQLITE_DATAMODEL, "file::memory:").await.unwrap();
+
+    assert_failed(engine.execute(execute_raw("SELECT 1")).await);
+}
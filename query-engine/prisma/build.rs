@@ -22,7 +22,12 @@ fn store_git_commit_hash() {
     println!("cargo:rustc-env=GIT_HASH={}", git_hash);
 }
 
+fn compile_protos() {
+    tonic_build::compile_protos("proto/query_engine.proto").expect("Could not compile the gRPC service definitions");
+}
+
 fn main() {
     check_rust_version();
     store_git_commit_hash();
+    compile_protos();
 }
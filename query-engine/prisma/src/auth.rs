@@ -0,0 +1,275 @@
+//! Authentication of requests by bearer token, and authorization of the operations they execute.
+//!
+//! Tokens are either static tokens given on the command line, or JWTs signed by a key of a JSON Web Key Set.
+use crate::{PrismaError, PrismaResult};
+use hyper::{header, Body, Client, Request, Uri};
+use hyper_rustls::HttpsConnector;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use query_core::Operation;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+/// Scope a JWT needs to execute mutations, tokens without it are read-only.
+pub const WRITE_SCOPE: &str = "prisma:write";
+
+/// Minimum time between refreshes of the key set, which are triggered by tokens signed with an unknown key.
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What an authenticated request is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Access {
+    /// Queries only, mutations are rejected.
+    ReadOnly,
+    ReadWrite,
+}
+
+/// Requests whose access wasn't established by authentication can only read.
+impl Default for Access {
+    fn default() -> Self {
+        Access::ReadOnly
+    }
+}
+
+impl Access {
+    pub fn authorize(self, operation: &Operation) -> PrismaResult<()> {
+        match (self, operation) {
+            (Access::ReadOnly, Operation::Write(selection)) => Err(PrismaError::AuthorizationError(format!(
+                "Mutation '{}' is not allowed with a read-only token.",
+                selection.name
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Authenticates requests by their `Authorization: Bearer <token>` header.
+/// Authentication is disabled if neither tokens nor a key set are configured.
+#[derive(Default)]
+pub struct Authenticator {
+    /// SHA-256 digests of the static tokens, so that looking them up doesn't leak their contents through timing.
+    tokens: HashMap<Vec<u8>, Access>,
+    jwt: Option<JwtAuthenticator>,
+}
+
+impl Authenticator {
+    pub fn new(tokens: Vec<(String, Access)>, jwt: Option<JwtAuthenticator>) -> Self {
+        Self {
+            tokens: tokens
+                .into_iter()
+                .map(|(token, access)| (digest(&token), access))
+                .collect(),
+            jwt,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.tokens.is_empty() || self.jwt.is_some()
+    }
+
+    pub async fn authenticate(&self, req: &Request<Body>) -> PrismaResult<Access> {
+        let authorization = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+
+        self.authenticate_authorization(authorization).await
+    }
+
+    /// Authenticates the value of an `Authorization` header, e.g. of the metadata of a gRPC request.
+    pub async fn authenticate_authorization(&self, authorization: Option<&str>) -> PrismaResult<Access> {
+        // Without authentication, every request has full access.
+        if !self.is_enabled() {
+            return Ok(Access::ReadWrite);
+        }
+
+        let token = authorization.and_then(bearer_token).ok_or_else(|| {
+            PrismaError::AuthenticationError("Expected a bearer token in the authorization header.".to_owned())
+        })?;
+
+        if let Some(access) = self.tokens.get(&digest(token)) {
+            return Ok(*access);
+        }
+
+        match self.jwt {
+            Some(ref jwt) => jwt.authenticate(token).await,
+            None => Err(PrismaError::AuthenticationError("Invalid bearer token.".to_owned())),
+        }
+    }
+}
+
+/// Validates JWTs signed with the RSA keys of a JSON Web Key Set, loaded from a URL or a file.
+pub struct JwtAuthenticator {
+    source: String,
+    validation: Validation,
+    keys: RwLock<Keys>,
+}
+
+struct Keys {
+    /// Keys by id, a key without id is stored under the empty string.
+    by_id: HashMap<String, DecodingKey<'static>>,
+    fetched_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    e: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    /// Space separated OAuth 2.0 scopes.
+    #[serde(default)]
+    scope: String,
+}
+
+impl JwtAuthenticator {
+    pub async fn load(source: String, issuer: Option<String>, audience: Option<String>) -> PrismaResult<Self> {
+        let mut validation = Validation {
+            algorithms: vec![Algorithm::RS256, Algorithm::RS384, Algorithm::RS512],
+            iss: issuer,
+            ..Validation::default()
+        };
+
+        if let Some(audience) = audience {
+            validation.set_audience(&[audience]);
+        }
+
+        let keys = fetch_keys(&source).await?;
+        info!("Loaded {} signing key(s) from {}.", keys.len(), source);
+
+        Ok(Self {
+            source,
+            validation,
+            keys: RwLock::new(Keys {
+                by_id: keys,
+                fetched_at: Instant::now(),
+            }),
+        })
+    }
+
+    async fn authenticate(&self, token: &str) -> PrismaResult<Access> {
+        let header = jsonwebtoken::decode_header(token).map_err(invalid_token)?;
+        let kid = header.kid.unwrap_or_default();
+
+        // Keys are rotated by adding them to the key set first, tokens signed with an unknown key trigger a refresh.
+        if self.needs_refresh(&kid) {
+            self.refresh().await;
+        }
+
+        let keys = self.keys.read().unwrap();
+        let key = keys
+            .by_id
+            .get(&kid)
+            .ok_or_else(|| PrismaError::AuthenticationError(format!("Unknown signing key '{}'.", kid)))?;
+
+        let claims = jsonwebtoken::decode::<Claims>(token, key, &self.validation)
+            .map_err(invalid_token)?
+            .claims;
+
+        if claims.scope.split(' ').any(|scope| scope == WRITE_SCOPE) {
+            Ok(Access::ReadWrite)
+        } else {
+            Ok(Access::ReadOnly)
+        }
+    }
+
+    fn needs_refresh(&self, kid: &str) -> bool {
+        let keys = self.keys.read().unwrap();
+        !keys.by_id.contains_key(kid) && keys.fetched_at.elapsed() > JWKS_REFRESH_INTERVAL
+    }
+
+    /// Failing to refresh keeps the current keys.
+    async fn refresh(&self) {
+        match fetch_keys(&self.source).await {
+            Ok(by_id) => {
+                *self.keys.write().unwrap() = Keys {
+                    by_id,
+                    fetched_at: Instant::now(),
+                };
+            }
+            Err(err) => {
+                warn!("Refreshing the signing keys from {} failed: {}", self.source, err);
+                self.keys.write().unwrap().fetched_at = Instant::now();
+            }
+        }
+    }
+}
+
+async fn fetch_keys(source: &str) -> PrismaResult<HashMap<String, DecodingKey<'static>>> {
+    let bytes = if source.starts_with("https://") || source.starts_with("http://") {
+        let uri: Uri = source
+            .parse()
+            .map_err(|err| PrismaError::ConfigurationError(format!("Invalid key set URL '{}': {}", source, err)))?;
+
+        let fetch_error = |err: hyper::Error| {
+            PrismaError::ConfigurationError(format!("Fetching the key set from {} failed: {}", source, err))
+        };
+
+        let client = Client::builder().build::<_, Body>(HttpsConnector::new());
+        let res = client.get(uri).await.map_err(fetch_error)?;
+
+        if !res.status().is_success() {
+            return Err(PrismaError::ConfigurationError(format!(
+                "Fetching the key set from {} failed with status {}.",
+                source,
+                res.status()
+            )));
+        }
+
+        hyper::body::to_bytes(res.into_body())
+            .await
+            .map_err(fetch_error)?
+            .to_vec()
+    } else {
+        std::fs::read(source)?
+    };
+
+    let jwks: JwkSet = serde_json::from_slice(&bytes)?;
+
+    Ok(jwks
+        .keys
+        .into_iter()
+        .filter(|jwk| jwk.kty == "RSA")
+        .filter_map(|jwk| match (jwk.n, jwk.e) {
+            (Some(n), Some(e)) => Some((
+                jwk.kid.unwrap_or_default(),
+                DecodingKey::from_rsa_components(&n, &e).into_static(),
+            )),
+            _ => None,
+        })
+        .collect())
+}
+
+fn bearer_token(value: &str) -> Option<&str> {
+    let prefix = "Bearer ";
+
+    if value.len() > prefix.len() && value[..prefix.len()].eq_ignore_ascii_case(prefix) {
+        Some(value[prefix.len()..].trim())
+    } else {
+        None
+    }
+}
+
+fn digest(token: &str) -> Vec<u8> {
+    Sha256::digest(token.as_bytes()).to_vec()
+}
+
+fn invalid_token(err: jsonwebtoken::errors::Error) -> PrismaError {
+    PrismaError::AuthenticationError(format!("Invalid bearer token: {}", err))
+}
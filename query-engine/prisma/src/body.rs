@@ -0,0 +1,152 @@
+//! Response bodies of query requests.
+//!
+//! Responses are serialized in chunks and streamed to the client as they are produced, instead of
+//! buffering large results in memory first, and are compressed if the client accepts it.
+use async_compression::stream::{BrotliEncoder, GzipEncoder};
+use bytes::Bytes;
+use futures::{channel::mpsc, executor, SinkExt, Stream, StreamExt};
+use hyper::Body;
+use serde::Serialize;
+use std::io;
+
+/// Size of the chunks the serialized response is sent in.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Boundary of the parts of multipart responses, the dashes of the delimiters make it `---`.
+pub const MULTIPART_BOUNDARY: &str = "-";
+
+/// Content coding of a response body, negotiated via `Accept-Encoding`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Identity,
+    Gzip,
+    Brotli,
+}
+
+impl Encoding {
+    /// Picks the preferred coding the client accepts, brotli compresses better than gzip.
+    pub fn negotiate(accept_encoding: Option<&str>) -> Self {
+        let accepted: Vec<&str> = accept_encoding
+            .map(|value| value.split(',').filter_map(accepted_coding).collect())
+            .unwrap_or_default();
+
+        if accepted.contains(&"br") {
+            Encoding::Brotli
+        } else if accepted.contains(&"gzip") {
+            Encoding::Gzip
+        } else {
+            Encoding::Identity
+        }
+    }
+
+    /// Value of the `Content-Encoding` header, if the body is compressed.
+    pub fn content_encoding(self) -> Option<&'static str> {
+        match self {
+            Encoding::Identity => None,
+            Encoding::Gzip => Some("gzip"),
+            Encoding::Brotli => Some("br"),
+        }
+    }
+}
+
+/// Returns the coding of an `Accept-Encoding` entry, unless it is refused with `q=0`.
+fn accepted_coding(entry: &str) -> Option<&str> {
+    let mut parts = entry.split(';').map(str::trim);
+    let coding = parts.next().filter(|coding| !coding.is_empty())?;
+
+    let refused = parts
+        .filter(|param| param.starts_with("q="))
+        .any(|param| param[2..].parse::<f32>().map(|q| q == 0.0).unwrap_or(false));
+
+    if refused {
+        None
+    } else {
+        Some(coding)
+    }
+}
+
+/// Serializes the value as JSON into a streamed, optionally compressed, body.
+pub fn json_body<T>(value: T, encoding: Encoding) -> Body
+where
+    T: Serialize + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(1);
+
+    // Serialization is blocking, it only continues once the previous chunk was picked up by the connection.
+    tokio::task::spawn_blocking(move || {
+        let mut writer = ChunkWriter {
+            sender,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+        };
+
+        let result = serde_json::to_writer(&mut writer, &value).map(|_| io::Write::flush(&mut writer));
+
+        match result {
+            Ok(Ok(())) => (),
+            Ok(Err(err)) => trace!("Client went away while streaming the response: {}", err),
+            Err(err) if err.is_io() => trace!("Client went away while streaming the response: {}", err),
+            Err(err) => error!("Serializing the response failed: {}", err),
+        }
+    });
+
+    let chunks = receiver.map(Ok::<_, io::Error>);
+
+    match encoding {
+        Encoding::Identity => Body::wrap_stream(chunks),
+        Encoding::Gzip => Body::wrap_stream(GzipEncoder::new(chunks)),
+        Encoding::Brotli => Body::wrap_stream(BrotliEncoder::new(chunks)),
+    }
+}
+
+/// Sends every value of the stream as a JSON part of a `multipart/mixed` body as soon as it is available.
+/// Parts are not compressed, compression would hold them back until enough data to compress came together.
+pub fn multipart_body<S, T>(parts: S) -> Body
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: Serialize,
+{
+    let chunks = parts
+        .map(|part| {
+            let mut chunk = format!(
+                "\r\n--{}\r\nContent-Type: application/json; charset=utf-8\r\n\r\n",
+                MULTIPART_BOUNDARY
+            )
+            .into_bytes();
+
+            serde_json::to_writer(&mut chunk, &part)?;
+            Ok::<_, io::Error>(Bytes::from(chunk))
+        })
+        .chain(futures::stream::once(async {
+            Ok(Bytes::from(format!("\r\n--{}--\r\n", MULTIPART_BOUNDARY)))
+        }));
+
+    Body::wrap_stream(chunks)
+}
+
+/// Collects the serializer output and sends it on in chunks of `CHUNK_SIZE`.
+struct ChunkWriter {
+    sender: mpsc::Sender<Bytes>,
+    buffer: Vec<u8>,
+}
+
+impl io::Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = Bytes::from(std::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE)));
+
+        executor::block_on(self.sender.send(chunk)).map_err(|err| io::Error::new(io::ErrorKind::BrokenPipe, err))
+    }
+}
@@ -1,14 +1,23 @@
-use std::{convert::TryFrom, fs::File, io::Read, sync::Arc};
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{Read, Write},
+    sync::Arc,
+};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
-use datamodel::json::dmmf::Datamodel;
+use datamodel::{json::dmmf::Datamodel, SourceOverride};
+use prisma_engine::exec_loader;
 use query_core::{
-    schema::{QuerySchemaRef, SupportedCapabilities},
-    BuildMode, QuerySchemaBuilder,
+    response_ir,
+    schema::{Field, QuerySchemaRef, QuerySchemaRenderer, QueryTag, SchemaQueryBuilder},
+    BuildMode, Operation, QueryDocument, QuerySchemaBuilder, QueryValue, Selection,
 };
 use std::collections::HashMap;
 
+use crate::auth::Access;
 use crate::context::PrismaContext;
 use crate::error::PrismaError;
 use crate::request_handlers::{graphql::*, PrismaRequest, RequestHandler};
@@ -29,18 +38,42 @@ pub struct ExecuteRequest {
     query: String,
     force_transactions: bool,
     enable_raw_queries: bool,
+    datasource_overrides: Vec<SourceOverride>,
 }
 
 pub struct DmmfRequest {
     build_mode: BuildMode,
     enable_raw_queries: bool,
+    format: dmmf::DmmfFormat,
+}
+
+pub struct SdlRequest {
+    pub(crate) build_mode: BuildMode,
+    pub(crate) enable_raw_queries: bool,
+}
+
+pub struct ValidateRequest {
+    legacy: bool,
+    enable_raw_queries: bool,
+    datasource_overrides: Vec<SourceOverride>,
+}
+
+/// Result of reading one model during validation.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelValidation {
+    pub(crate) model: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) errors: Vec<serde_json::Value>,
 }
 
 pub enum CliCommand {
     Dmmf(DmmfRequest),
+    Sdl(SdlRequest),
     DmmfToDml(DmmfToDmlInput),
     GetConfig(String),
     ExecuteRequest(ExecuteRequest),
+    Validate(ValidateRequest),
 }
 
 impl TryFrom<&PrismaOpt> for CliCommand {
@@ -50,16 +83,31 @@ impl TryFrom<&PrismaOpt> for CliCommand {
         match opts.subcommand {
             None => Err(PrismaError::InvocationError(String::from("cli subcommand not present"))),
             Some(Subcommand::Cli(ref cliopts)) => match cliopts {
-                CliOpt::Dmmf => {
+                CliOpt::Dmmf(input) => {
                     let build_mode = if opts.legacy {
                         BuildMode::Legacy
                     } else {
                         BuildMode::Modern
                     };
 
+                    dmmf::negotiate_version(input.dmmf_version)?;
+
                     Ok(CliCommand::Dmmf(DmmfRequest {
                         build_mode,
                         enable_raw_queries: opts.enable_raw_queries,
+                        format: input.format,
+                    }))
+                }
+                CliOpt::Sdl => {
+                    let build_mode = if opts.legacy {
+                        BuildMode::Legacy
+                    } else {
+                        BuildMode::Modern
+                    };
+
+                    Ok(CliCommand::Sdl(SdlRequest {
+                        build_mode,
+                        enable_raw_queries: opts.enable_raw_queries,
                     }))
                 }
                 CliOpt::DmmfToDml(input) => {
@@ -79,6 +127,12 @@ impl TryFrom<&PrismaOpt> for CliCommand {
                     query: input.query.clone(),
                     force_transactions: opts.always_force_transactions,
                     enable_raw_queries: opts.enable_raw_queries,
+                    datasource_overrides: opts.datasources.clone(),
+                })),
+                CliOpt::Validate => Ok(CliCommand::Validate(ValidateRequest {
+                    legacy: opts.legacy,
+                    enable_raw_queries: opts.enable_raw_queries,
+                    datasource_overrides: opts.datasources.clone(),
                 })),
             },
         }
@@ -89,36 +143,56 @@ impl CliCommand {
     pub async fn execute(self) -> PrismaResult<()> {
         match self {
             CliCommand::Dmmf(request) => Self::dmmf(request),
+            CliCommand::Sdl(request) => Self::sdl(request),
             CliCommand::DmmfToDml(input) => Self::dmmf_to_dml(input),
             CliCommand::GetConfig(input) => Self::get_config(input),
             CliCommand::ExecuteRequest(request) => Self::execute_request(request).await,
+            CliCommand::Validate(request) => Self::validate(request).await,
         }
     }
 
     fn dmmf(request: DmmfRequest) -> PrismaResult<()> {
-        let (v2components, template) = load_data_model_components()?;
+        let (datamodel, query_schema) = Self::load_query_schema(request.build_mode, request.enable_raw_queries)?;
 
-        // temporary code duplication
-        let internal_data_model = template.build("".into());
-        let capabilities = SupportedCapabilities::empty();
+        let dmmf = dmmf::render_dmmf(&datamodel, query_schema);
 
-        let schema_builder = QuerySchemaBuilder::new(
-            &internal_data_model,
-            &capabilities,
-            request.build_mode,
-            request.enable_raw_queries,
-        );
+        match request.format {
+            dmmf::DmmfFormat::Json => println!("{}", serde_json::to_string_pretty(&dmmf)?),
+            format => std::io::stdout().write_all(&dmmf.serialize_as(format)?)?,
+        }
 
-        let query_schema: QuerySchemaRef = Arc::new(schema_builder.build());
+        Ok(())
+    }
 
-        let dmmf = dmmf::render_dmmf(&v2components.datamodel, query_schema);
-        let serialized = serde_json::to_string_pretty(&dmmf)?;
+    /// Prints the GraphQL SDL of the full query schema, e.g. for codegen tools and GraphQL IDEs.
+    fn sdl(request: SdlRequest) -> PrismaResult<()> {
+        let (_, query_schema) = Self::load_query_schema(request.build_mode, request.enable_raw_queries)?;
 
-        println!("{}", serialized);
+        println!("{}", GraphQLSchemaRenderer::render(query_schema));
 
         Ok(())
     }
 
+    fn load_query_schema(
+        build_mode: BuildMode,
+        enable_raw_queries: bool,
+    ) -> PrismaResult<(datamodel::Datamodel, QuerySchemaRef)> {
+        let (v2components, template) = load_data_model_components(&[])?;
+
+        // temporary code duplication
+        let internal_data_model = template.build("".into());
+
+        // Without a data source, the schema includes everything any connector supports.
+        let capabilities = exec_loader::shared_capabilities(&v2components.data_sources);
+
+        let schema_builder =
+            QuerySchemaBuilder::new(&internal_data_model, &capabilities, build_mode, enable_raw_queries);
+
+        let query_schema: QuerySchemaRef = Arc::new(schema_builder.build());
+
+        Ok((v2components.datamodel, query_schema))
+    }
+
     fn dmmf_to_dml(input: DmmfToDmlInput) -> PrismaResult<()> {
         let datamodel = datamodel::json::dmmf::schema_from_dmmf(&input.dmmf);
         let config = datamodel::json::mcf::config_from_mcf_json_value(input.config);
@@ -130,7 +204,7 @@ impl CliCommand {
     }
 
     fn get_config(input: String) -> PrismaResult<()> {
-        let config = load_configuration(&input)?;
+        let config = load_configuration(&input, &[])?;
         let json = datamodel::json::mcf::config_to_mcf_json_value(&config);
         let serialized = serde_json::to_string(&json)?;
 
@@ -147,6 +221,7 @@ impl CliCommand {
             .legacy(true)
             .force_transactions(request.force_transactions)
             .enable_raw_queries(request.enable_raw_queries)
+            .datasource_overrides(request.datasource_overrides)
             .build()
             .await?;
 
@@ -154,6 +229,7 @@ impl CliCommand {
             body: serde_json::from_str(&decoded_request).unwrap(),
             headers: HashMap::new(),
             path: String::new(),
+            access: Access::ReadWrite,
         };
 
         let response = GraphQlRequestHandler.handle(req, &Arc::new(ctx)).await;
@@ -164,4 +240,116 @@ impl CliCommand {
 
         Ok(())
     }
+
+    /// Checks every model of the data model against the database and prints a report.
+    async fn validate(request: ValidateRequest) -> PrismaResult<()> {
+        let ctx = PrismaContext::builder()
+            .legacy(request.legacy)
+            .enable_raw_queries(request.enable_raw_queries)
+            .datasource_overrides(request.datasource_overrides)
+            .build()
+            .await?;
+
+        let models = validate_models(&ctx).await?;
+        let valid = models.iter().all(|model| model.errors.is_empty());
+
+        println!(
+            "{}",
+            serde_json::to_string(&json!({ "valid": valid, "models": models }))?
+        );
+
+        if valid {
+            Ok(())
+        } else {
+            Err(PrismaError::ConfigurationError(
+                "The database schema doesn't match the data model.".to_owned(),
+            ))
+        }
+    }
+}
+
+/// Reads the first record of every model with all scalar fields and the relations, so that missing tables and
+/// columns fail. Relation tables are only read for models with records.
+pub(crate) async fn validate_models(ctx: &PrismaContext) -> PrismaResult<Vec<ModelValidation>> {
+    let query_schema = ctx.query_schema();
+    let mut models = Vec::new();
+
+    for field in query_schema.query().get_fields() {
+        let model_name = match field.query_builder {
+            Some(SchemaQueryBuilder::ModelQueryBuilder(ref builder)) if builder.tag == QueryTag::FindMany => {
+                builder.model.name.clone()
+            }
+            _ => continue,
+        };
+
+        let query_doc = QueryDocument {
+            operations: vec![Operation::Read(validation_selection(field))],
+        };
+
+        let responses = match ctx.executor().execute(query_doc, Arc::clone(query_schema)).await {
+            Ok(responses) => responses,
+            Err(err) => {
+                let mut responses = response_ir::Responses::default();
+                responses.insert_error(err);
+                responses
+            }
+        };
+
+        let errors = match serde_json::to_value(&responses)?.get_mut("errors") {
+            Some(serde_json::Value::Array(errors)) => errors.drain(..).collect(),
+            _ => Vec::new(),
+        };
+
+        models.push(ModelValidation {
+            model: model_name,
+            errors,
+        });
+    }
+
+    Ok(models)
+}
+
+/// Selects the first record with all fields of the output type of the query field, and the scalar fields of
+/// related records.
+fn validation_selection(field: &Field) -> Selection {
+    let nested_selections = field
+        .field_type
+        .as_object_type()
+        .map(|object| {
+            object
+                .get_fields()
+                .iter()
+                .map(|field| match field.field_type.as_object_type() {
+                    Some(related) => Selection {
+                        name: field.name.clone(),
+                        alias: None,
+                        arguments: Vec::new(),
+                        nested_selections: related
+                            .get_fields()
+                            .iter()
+                            .filter(|field| !field.field_type.is_object())
+                            .map(|field| scalar_selection(&field.name))
+                            .collect(),
+                    },
+                    None => scalar_selection(&field.name),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Selection {
+        name: field.name.clone(),
+        alias: None,
+        arguments: vec![("take".to_owned(), QueryValue::Int(1))],
+        nested_selections,
+    }
+}
+
+fn scalar_selection(name: &str) -> Selection {
+    Selection {
+        name: name.to_owned(),
+        alias: None,
+        arguments: Vec::new(),
+        nested_selections: Vec::new(),
+    }
 }
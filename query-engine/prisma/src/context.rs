@@ -1,30 +1,34 @@
-use crate::{data_model_loader::*, exec_loader, PrismaError, PrismaResult};
-use query_core::{
-    schema::{QuerySchemaRef, SupportedCapabilities},
-    BuildMode, QueryExecutor, QuerySchemaBuilder,
-};
-// use prisma_models::InternalDataModelRef;
-use std::sync::Arc;
+use crate::{data_model_loader::*, request_handlers::PersistedQueries, PrismaError, PrismaResult};
+use datamodel::SourceOverride;
+use prisma_engine::{Engine, EngineError};
+use query_core::{schema::QuerySchemaRef, AccessPolicy, QueryExecutor, QueryLimits};
 
 /// Prisma request context containing all immutable state of the process.
 /// There is usually only one context initialized per process.
 pub struct PrismaContext {
-    // Internal data model used throughout the query engine.
-    //internal_data_model: InternalDataModelRef,
-    /// The api query schema.
-    query_schema: QuerySchemaRef,
+    /// The data model, its query schema and the executor of the queries.
+    engine: Engine,
 
-    /// DML-based v2 datamodel.
-    dm: datamodel::Datamodel,
+    /// Emit spec-compliant GraphQL errors and partial data instead of the Prisma error format.
+    graphql_errors: bool,
 
-    /// Central query executor.
-    pub executor: Box<dyn QueryExecutor + Send + Sync + 'static>,
+    /// Registered operation documents that can be executed by hash.
+    persisted_queries: PersistedQueries,
+
+    /// Names of the enabled optional features and preview features, reported by the server info.
+    features: Vec<String>,
 }
 
 pub struct ContextBuilder {
     legacy: bool,
     force_transactions: bool,
     enable_raw_queries: bool,
+    limits: QueryLimits,
+    graphql_errors: bool,
+    persisted_queries: PersistedQueries,
+    access_policy: AccessPolicy,
+    datamodel: Option<String>,
+    datasource_overrides: Vec<SourceOverride>,
 }
 
 impl ContextBuilder {
@@ -43,50 +47,109 @@ impl ContextBuilder {
         self
     }
 
+    pub fn limits(mut self, val: QueryLimits) -> Self {
+        self.limits = val;
+        self
+    }
+
+    pub fn graphql_errors(mut self, val: bool) -> Self {
+        self.graphql_errors = val;
+        self
+    }
+
+    pub fn persisted_queries(mut self, val: PersistedQueries) -> Self {
+        self.persisted_queries = val;
+        self
+    }
+
+    /// Leaves the operations and fields the policy forbids out of the query schema and rejects them on execution.
+    pub fn access_policy(mut self, val: AccessPolicy) -> Self {
+        self.access_policy = val;
+        self
+    }
+
+    /// Uses the given data model instead of loading it from the environment.
+    pub fn datamodel(mut self, val: String) -> Self {
+        self.datamodel = Some(val);
+        self
+    }
+
+    /// Replaces the URLs of datasources of the data model.
+    pub fn datasource_overrides(mut self, val: Vec<SourceOverride>) -> Self {
+        self.datasource_overrides = val;
+        self
+    }
+
     pub async fn build(self) -> PrismaResult<PrismaContext> {
-        PrismaContext::new(self.legacy, self.force_transactions, self.enable_raw_queries).await
+        PrismaContext::new(self).await
     }
 }
 
 impl PrismaContext {
     /// Initializes a new Prisma context.
     /// Loads all immutable state for the query engine:
-    /// 1. The data model. This has different options on how to initialize. See data_model_loader module. The Prisma configuration (prisma.yml) is used as fallback.
-    /// 2. The data model is converted to the internal data model.
-    /// 3. The api query schema is constructed from the internal data model.
-    async fn new(legacy: bool, force_transactions: bool, enable_raw_queries: bool) -> PrismaResult<Self> {
-        // Load data model in order of precedence.
-        let (v2components, template) = load_data_model_components()?;
-
-        let (dm, data_sources) = (v2components.datamodel, v2components.data_sources);
-
-        // We only support one data source at the moment, so take the first one (default not exposed yet).
-        let data_source = if data_sources.is_empty() {
-            return Err(PrismaError::ConfigurationError("No valid data source found".into()));
-        } else {
-            data_sources.first().unwrap()
+    /// 1. The data model. This has different options on how to initialize. See data_model_loader module.
+    /// 2. The engine of the data model, see `prisma_engine::EngineBuilder`, with the URLs of the datasources replaced
+    ///    by the overrides.
+    async fn new(builder: ContextBuilder) -> PrismaResult<Self> {
+        let ContextBuilder {
+            legacy,
+            force_transactions,
+            enable_raw_queries,
+            limits,
+            graphql_errors,
+            persisted_queries,
+            access_policy,
+            datamodel,
+            datasource_overrides,
+        } = builder;
+
+        // Load data model in order of precedence, unless given explicitly.
+        let dml_string = match datamodel {
+            Some(dml_string) => dml_string,
+            None => load_dml_string()?,
         };
 
-        // Load executor
-        let (db_name, executor) = exec_loader::load(&**data_source, force_transactions).await?;
-
-        // Build internal data model
-        let internal_data_model = template.build(db_name);
-
-        // Construct query schema
-        let build_mode = if legacy { BuildMode::Legacy } else { BuildMode::Modern };
-        let capabilities = SupportedCapabilities::empty(); // todo connector capabilities.
-
-        let schema_builder =
-            QuerySchemaBuilder::new(&internal_data_model, &capabilities, build_mode, enable_raw_queries);
-
-        let query_schema: QuerySchemaRef = Arc::new(schema_builder.build());
+        // Rejects overrides of unknown datasources, the engine would ignore them.
+        let configuration = load_configuration(&dml_string, &datasource_overrides)?;
+        let preview_features = configuration.preview_features().into_iter().map(String::from);
+
+        let engine_builder = Engine::builder(&dml_string)
+            .legacy(legacy)
+            .force_transactions(force_transactions)
+            .enable_raw_queries(enable_raw_queries)
+            .limits(limits)
+            .access_policy(access_policy);
+
+        let engine = crate::data_model_loader::datasource_overrides(&datasource_overrides)?
+            .into_iter()
+            .fold(engine_builder, |engine_builder, source_override| {
+                engine_builder.datasource_url(&source_override.name, source_override.url.expose())
+            })
+            .build()
+            .await
+            .map_err(|err| match err {
+                EngineError::DatamodelError(errors) => PrismaError::ConversionError(errors, dml_string.clone()),
+                err => PrismaError::from(err),
+            })?;
+
+        let features = vec![
+            ("legacy", legacy),
+            ("force_transactions", force_transactions),
+            ("raw_queries", enable_raw_queries),
+            ("graphql_errors", graphql_errors),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name.to_owned())
+        .chain(preview_features)
+        .collect();
 
         Ok(Self {
-            // internal_data_model,
-            query_schema,
-            dm,
-            executor,
+            engine,
+            graphql_errors,
+            persisted_queries,
+            features,
         })
     }
 
@@ -95,18 +158,41 @@ impl PrismaContext {
             legacy: false,
             force_transactions: false,
             enable_raw_queries: false,
+            limits: QueryLimits::default(),
+            graphql_errors: false,
+            persisted_queries: PersistedQueries::default(),
+            access_policy: AccessPolicy::default(),
+            datamodel: None,
+            datasource_overrides: Vec::new(),
         }
     }
 
     pub fn query_schema(&self) -> &QuerySchemaRef {
-        &self.query_schema
+        self.engine.query_schema()
     }
 
     pub fn datamodel(&self) -> &datamodel::Datamodel {
-        &self.dm
+        self.engine.datamodel()
+    }
+
+    /// Central query executor.
+    pub fn executor(&self) -> &(dyn QueryExecutor + Send + Sync + 'static) {
+        self.engine.executor()
+    }
+
+    pub fn graphql_errors(&self) -> bool {
+        self.graphql_errors
+    }
+
+    pub fn persisted_queries(&self) -> &PersistedQueries {
+        &self.persisted_queries
+    }
+
+    pub fn features(&self) -> &[String] {
+        &self.features
     }
 
     pub fn primary_connector(&self) -> &'static str {
-        self.executor.primary_connector()
+        self.executor().primary_connector()
     }
 }
@@ -1,9 +1,8 @@
 use std::{fs::File, io::Read};
 
-use serde::Deserialize;
 use serde_json;
 
-use datamodel::{Datamodel, Source};
+use datamodel::{Datamodel, Source, SourceOverride};
 use prisma_models::{DatamodelConverter, InternalDataModelTemplate};
 
 use crate::{utilities, PrismaError, PrismaResult};
@@ -12,6 +11,8 @@ use crate::{utilities, PrismaError, PrismaResult};
 pub struct DatamodelV2Components {
     pub datamodel: Datamodel,
     pub data_sources: Vec<Box<dyn Source + Send + Sync>>,
+    /// The preview features enabled by the generators of the data model.
+    pub preview_features: Vec<String>,
 }
 
 /// Private helper trait for operations on PrismaResult<Option<T>>.
@@ -69,9 +70,12 @@ impl<T> PrismaResultOption<T> for PrismaResult<Option<T>> {
 
 /// Loads data model components for the v2 data model.
 /// The v2 data model is provided either as file (PRISMA_DML_PATH) or as string in the env (PRISMA_DML).
-pub fn load_data_model_components() -> PrismaResult<(DatamodelV2Components, InternalDataModelTemplate)> {
+/// The URLs of the datasources are replaced by the given overrides, see `load_configuration`.
+pub fn load_data_model_components(
+    overrides: &[SourceOverride],
+) -> PrismaResult<(DatamodelV2Components, InternalDataModelTemplate)> {
     // Load data model in order of precedence.
-    match load_datamodel_v2()? {
+    match load_datamodel_v2(overrides)? {
         Some(v2components) => {
             let template = DatamodelConverter::convert(&v2components.datamodel);
             Ok((v2components, template))
@@ -84,54 +88,98 @@ pub fn load_data_model_components() -> PrismaResult<(DatamodelV2Components, Inte
     }
 }
 
+/// Loads the v2 data model string from env or file, see `load_data_model_components`.
+pub fn load_dml_string() -> PrismaResult<String> {
+    load_v2_dml_string()?
+        .ok_or_else(|| PrismaError::ConfigurationError("Unable to load data model v2 from any source.".into()))
+}
+
 /// Attempts to construct a Prisma v2 datamodel.
 /// Returns: DatamodelV2Components
 ///     Err      If a source for v2 was found, but conversion failed.
 ///     Ok(Some) If a source for v2 was found, and the conversion suceeded.
 ///     Ok(None) If no source for a v2 data model was found.
-fn load_datamodel_v2() -> PrismaResult<Option<DatamodelV2Components>> {
+fn load_datamodel_v2(overrides: &[SourceOverride]) -> PrismaResult<Option<DatamodelV2Components>> {
     debug!("Trying to load v2 data model...");
 
-    load_v2_dml_string().inner_map(|dml_string| match datamodel::parse_datamodel(&dml_string) {
-        Err(errors) => Err(PrismaError::ConversionError(errors, dml_string.clone())),
-        Ok(dm) => load_configuration(&dml_string).map(|configuration| {
+    load_v2_dml_string().inner_map(|dml_string| parse_datamodel_v2(dml_string, overrides).map(Some))
+}
+
+fn parse_datamodel_v2(dml_string: String, overrides: &[SourceOverride]) -> PrismaResult<DatamodelV2Components> {
+    match datamodel::parse_datamodel_with_overrides(&dml_string, &datasource_overrides(overrides)?) {
+        Err(errors) => Err(PrismaError::ConversionError(errors, dml_string)),
+        Ok(dm) => load_configuration(&dml_string, overrides).map(|configuration| {
             debug!("Loaded Prisma v2 data model.");
-            Some(DatamodelV2Components {
+            DatamodelV2Components {
+                preview_features: configuration.preview_features().into_iter().map(String::from).collect(),
                 datamodel: dm,
                 data_sources: configuration.datasources,
-            })
+            }
         }),
-    })
+    }
 }
 
-pub fn load_configuration(dml_string: &str) -> PrismaResult<datamodel::Configuration> {
+/// Loads the configuration of the data model. The URLs of the datasources are replaced first by the overrides
+/// of the `OVERWRITE_DATASOURCES` env var, a JSON array of `{"name": "...", "url": "..."}` objects, and then by the
+/// given overrides, so that the same data model runs against different databases without changing it.
+///
+/// Overrides of datasources the data model doesn't have are rejected, a misspelled name would otherwise connect to
+/// the database of the data model.
+pub fn load_configuration(dml_string: &str, overrides: &[SourceOverride]) -> PrismaResult<datamodel::Configuration> {
+    let overrides = datasource_overrides(overrides)?;
+
+    for source_override in overrides.iter() {
+        debug!(
+            "overwriting datasource {} with url {}",
+            source_override.name, source_override.url
+        );
+    }
+
+    let configuration = datamodel::parse_configuration_with_overrides(&dml_string, &overrides)
+        .map_err(|errors| PrismaError::ConversionError(errors, dml_string.to_string()))?;
+
+    let unknown = overrides.iter().find(|source_override| {
+        !configuration
+            .datasources
+            .iter()
+            .any(|source| source.name() == &source_override.name)
+    });
+
+    match unknown {
+        Some(source_override) => Err(PrismaError::ConfigurationError(format!(
+            "The data model has no datasource `{}` to override the URL of.",
+            source_override.name
+        ))),
+        None => Ok(configuration),
+    }
+}
+
+/// The overrides of the `OVERWRITE_DATASOURCES` env var, followed by the given ones.
+pub fn datasource_overrides(overrides: &[SourceOverride]) -> PrismaResult<Vec<SourceOverride>> {
     let datasource_overwrites_string =
         load_string_from_env("OVERWRITE_DATASOURCES")?.unwrap_or_else(|| r#"[]"#.to_string());
-    let datasource_overwrites: Vec<SourceOverride> = serde_json::from_str(&datasource_overwrites_string)?;
-
-    match datamodel::parse_configuration(&dml_string) {
-        Err(errors) => Err(PrismaError::ConversionError(errors, dml_string.to_string())),
-        Ok(mut configuration) => {
-            for datasource_override in datasource_overwrites {
-                for datasource in &mut configuration.datasources {
-                    if &datasource_override.name == datasource.name() {
-                        debug!(
-                            "overwriting datasource {} with url {}",
-                            &datasource_override.name, &datasource_override.url
-                        );
-                        datasource.set_url(&datasource_override.url);
-                    }
-                }
-            }
-            Ok(configuration)
-        }
-    }
+    let mut datasource_overwrites: Vec<SourceOverride> = serde_json::from_str(&datasource_overwrites_string)?;
+
+    datasource_overwrites.extend_from_slice(overrides);
+
+    Ok(datasource_overwrites)
 }
 
-#[derive(Deserialize)]
-struct SourceOverride {
-    name: String,
-    url: String,
+/// Parses a datasource override given on the command line as `name=url`.
+pub fn parse_source_override(input: &str) -> Result<SourceOverride, String> {
+    let mut split = input.splitn(2, '=');
+    let name = split.next().unwrap_or_default().trim();
+
+    match split.next() {
+        Some(url) if !name.is_empty() && !url.is_empty() => Ok(SourceOverride {
+            name: name.to_owned(),
+            url: url.into(),
+        }),
+        _ => Err(format!(
+            "Expected a datasource in the form `name=url`, got `{}`.",
+            input
+        )),
+    }
 }
 
 /// Attempts to load a Prisma DML (datamodel v2) string from either env or file.
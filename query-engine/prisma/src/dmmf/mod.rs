@@ -1,5 +1,6 @@
 mod schema;
 
+use crate::{PrismaError, PrismaResult};
 use datamodel;
 use query_core::schema::{QuerySchemaRef, QuerySchemaRenderer};
 use schema::*;
@@ -7,9 +8,58 @@ use serde::{ser::SerializeMap, Serialize, Serializer};
 use std::cmp::Ordering;
 use std::{cell::RefCell, collections::HashMap};
 
+/// Version of the DMMF shape, bumped on breaking changes.
+/// Clients announce the version they understand, see `negotiate_version`.
+pub const DMMF_VERSION: u32 = 1;
+
+/// Serialization formats of the DMMF.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DmmfFormat {
+    Json,
+    /// Compact binary serialization, considerably smaller and faster to produce for large schemas.
+    MessagePack,
+}
+
+impl DmmfFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            DmmfFormat::Json => "application/json",
+            DmmfFormat::MessagePack => "application/msgpack",
+        }
+    }
+}
+
+impl std::str::FromStr for DmmfFormat {
+    type Err = PrismaError;
+
+    fn from_str(s: &str) -> PrismaResult<Self> {
+        match s {
+            "json" => Ok(DmmfFormat::Json),
+            "msgpack" => Ok(DmmfFormat::MessagePack),
+            _ => Err(PrismaError::InvocationError(format!(
+                "Unknown DMMF format '{}', expected 'json' or 'msgpack'.",
+                s
+            ))),
+        }
+    }
+}
+
+/// Checks that the DMMF version requested by the client can be served.
+/// Clients newer than the engine get the current version and decide whether they can handle it.
+pub fn negotiate_version(requested: Option<u32>) -> PrismaResult<u32> {
+    match requested {
+        Some(version) if version < DMMF_VERSION => Err(PrismaError::InvocationError(format!(
+            "DMMF version {} is not supported anymore, the engine renders version {}.",
+            version, DMMF_VERSION
+        ))),
+        _ => Ok(DMMF_VERSION),
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DataModelMetaFormat {
+    pub dmmf_version: u32,
     #[serde(rename = "datamodel")]
     pub data_model: serde_json::Value,
     pub schema: DMMFSchema,
@@ -77,8 +127,20 @@ pub fn render_dmmf(dml: &datamodel::Datamodel, query_schema: QuerySchemaRef) ->
     let datamodel_json = datamodel::json::dmmf::render_to_dmmf_value(&dml);
 
     DataModelMetaFormat {
+        dmmf_version: DMMF_VERSION,
         data_model: datamodel_json,
         schema,
         mappings,
     }
 }
+
+impl DataModelMetaFormat {
+    pub fn serialize_as(&self, format: DmmfFormat) -> PrismaResult<Vec<u8>> {
+        match format {
+            DmmfFormat::Json => Ok(serde_json::to_vec(self)?),
+            DmmfFormat::MessagePack => {
+                rmp_serde::to_vec_named(self).map_err(|err| PrismaError::SerializationError(err.to_string()))
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use datamodel::error::ErrorCollection;
 use failure::{Error, Fail};
 use graphql_parser::query::ParseError as GqlParseError;
+use prisma_engine::EngineError;
 use query_core::{response_ir, CoreError};
 use serde_json;
 
@@ -39,6 +40,12 @@ pub enum PrismaError {
 
     #[fail(display = "{}", _0)]
     QueryConversionError(String),
+
+    #[fail(display = "{}", _0)]
+    AuthenticationError(String),
+
+    #[fail(display = "{}", _0)]
+    AuthorizationError(String),
 }
 
 impl PrismaError {
@@ -93,6 +100,17 @@ impl From<PrismaError> for response_ir::ResponseError {
     }
 }
 
+impl From<EngineError> for PrismaError {
+    fn from(e: EngineError) -> Self {
+        match e {
+            EngineError::ConfigurationError(message) => PrismaError::ConfigurationError(message),
+            EngineError::DatamodelError(errors) => PrismaError::DatamodelError(errors),
+            EngineError::CoreError(core_error) => PrismaError::CoreError(core_error),
+            EngineError::QueryConversionError(message) => PrismaError::QueryConversionError(message),
+        }
+    }
+}
+
 impl From<url::ParseError> for PrismaError {
     fn from(e: url::ParseError) -> PrismaError {
         PrismaError::ConfigurationError(format!("Error parsing connection string: {}", e))
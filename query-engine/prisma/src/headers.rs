@@ -0,0 +1,106 @@
+//! CORS and static headers added to the responses of the HTTP server.
+use crate::{PrismaError, PrismaResult};
+use hyper::{
+    header::{self, HeaderMap, HeaderName, HeaderValue},
+    Body, Response, StatusCode,
+};
+
+/// Headers added to every response, plus the CORS headers for requests from allowed origins.
+#[derive(Debug, Default, Clone)]
+pub struct ResponseHeaders {
+    cors: Option<Cors>,
+    static_headers: HeaderMap,
+}
+
+/// Cross-origin resource sharing configuration, allowing browsers to call the engine directly.
+#[derive(Debug, Clone)]
+pub struct Cors {
+    origins: Vec<String>,
+    allow_headers: HeaderValue,
+    allow_methods: HeaderValue,
+}
+
+impl ResponseHeaders {
+    pub fn new(cors: Option<Cors>, static_headers: Vec<(HeaderName, HeaderValue)>) -> Self {
+        Self {
+            cors,
+            static_headers: static_headers.into_iter().collect(),
+        }
+    }
+
+    /// Answers a CORS preflight (`OPTIONS`) request, if CORS is enabled.
+    /// The origin dependent headers are added by `apply`, same as for any other response.
+    pub fn preflight(&self, origin: Option<&HeaderValue>) -> Option<Response<Body>> {
+        self.cors.as_ref().map(|cors| {
+            let mut res = Response::default();
+            *res.status_mut() = StatusCode::NO_CONTENT;
+
+            if origin.filter(|origin| cors.allows(origin)).is_some() {
+                let headers = res.headers_mut();
+
+                headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, cors.allow_methods.clone());
+                headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, cors.allow_headers.clone());
+            }
+
+            res
+        })
+    }
+
+    /// Adds the configured headers to a response for a request from the given origin.
+    pub fn apply(&self, origin: Option<&HeaderValue>, headers: &mut HeaderMap) {
+        if let Some(ref cors) = self.cors {
+            cors.apply(origin, headers);
+        }
+
+        for (name, value) in self.static_headers.iter() {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+}
+
+impl Cors {
+    /// An origin of `*` allows all origins.
+    pub fn new(origins: Vec<String>, headers: Vec<String>, methods: Vec<String>) -> PrismaResult<Self> {
+        Ok(Self {
+            origins,
+            allow_headers: join(&headers)?,
+            allow_methods: join(&methods)?,
+        })
+    }
+
+    fn allows(&self, origin: &HeaderValue) -> bool {
+        self.origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.as_bytes() == origin.as_bytes())
+    }
+
+    fn apply(&self, origin: Option<&HeaderValue>, headers: &mut HeaderMap) {
+        // The allowed origin depends on the request, caches must not serve it to other origins.
+        headers.append(header::VARY, HeaderValue::from_static("origin"));
+
+        if let Some(origin) = origin.filter(|origin| self.allows(origin)) {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+        }
+    }
+}
+
+/// Parses a header given as `Name: value`.
+pub fn parse_header(input: &str) -> Result<(HeaderName, HeaderValue), String> {
+    let mut split = input.splitn(2, ':');
+    let name = split.next().unwrap_or_default().trim();
+
+    let value = split
+        .next()
+        .ok_or_else(|| format!("Expected a header in the form `Name: value`, got `{}`.", input))?
+        .trim();
+
+    let name = HeaderName::from_bytes(name.as_bytes()).map_err(|err| format!("Invalid header name: {}", err))?;
+    let value = HeaderValue::from_str(value).map_err(|err| format!("Invalid header value: {}", err))?;
+
+    Ok((name, value))
+}
+
+fn join(values: &[String]) -> PrismaResult<HeaderValue> {
+    HeaderValue::from_str(&values.join(", "))
+        .map_err(|err| PrismaError::ConfigurationError(format!("Invalid CORS configuration: {}", err)))
+}
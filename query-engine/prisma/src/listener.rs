@@ -0,0 +1,280 @@
+//! Sockets the HTTP server can listen on besides a plain TCP port.
+use futures::stream::{self, Stream, StreamExt};
+use std::{cmp, fmt, io, net::SocketAddr, str::FromStr, time::Duration};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    time::{delay_for, timeout},
+};
+use tokio_rustls::{server::TlsStream, TlsAcceptor};
+
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::{
+        fs::FileTypeExt,
+        io::{FromRawFd, IntoRawFd, RawFd},
+    },
+    path::PathBuf,
+};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+/// Number of TLS handshakes performed concurrently per listener.
+const CONCURRENT_HANDSHAKES: usize = 64;
+
+/// Time a client has to complete the TLS handshake, before its connection is dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before accepting again after the first failed accept.
+const MIN_ACCEPT_BACKOFF: Duration = Duration::from_millis(5);
+
+/// Upper bound of the delay, which doubles with every consecutive failed accept.
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub enum ListenAddress {
+    /// A TCP port serving plain HTTP.
+    Tcp(SocketAddr),
+    /// A TCP port serving HTTPS, the TLS connection is terminated by the engine.
+    Tls(SocketAddr),
+    /// A unix domain socket at the given path.
+    #[cfg(unix)]
+    Unix(PathBuf),
+    /// A listening socket inherited from the parent process, e.g. through systemd socket activation.
+    #[cfg(unix)]
+    Fd(RawFd),
+}
+
+impl fmt::Display for ListenAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(address) => write!(f, "{}:{}", address.ip(), address.port()),
+            Self::Tls(address) => write!(f, "https://{}:{}", address.ip(), address.port()),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix socket {}", path.display()),
+            #[cfg(unix)]
+            Self::Fd(fd) => write!(f, "inherited socket fd {}", fd),
+        }
+    }
+}
+
+/// Parses `host:port`, `http://host:port`, `https://host:port` and, on unix, `unix:/path/to/socket`.
+impl FromStr for ListenAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let socket_address = |address: &str| {
+            address
+                .parse::<SocketAddr>()
+                .map_err(|err| format!("Invalid address '{}': {}", address, err))
+        };
+
+        if s.starts_with("https://") {
+            socket_address(&s["https://".len()..]).map(Self::Tls)
+        } else if s.starts_with("http://") {
+            socket_address(&s["http://".len()..]).map(Self::Tcp)
+        } else if s.starts_with("unix:") {
+            #[cfg(unix)]
+            return Ok(Self::Unix(PathBuf::from(&s["unix:".len()..])));
+
+            #[cfg(not(unix))]
+            return Err("Unix domain sockets are only supported on unix platforms.".to_owned());
+        } else {
+            socket_address(s).map(Self::Tcp)
+        }
+    }
+}
+
+#[cfg(unix)]
+pub enum Inherited {
+    Tcp(TcpListener),
+    Unix(UnixListener),
+}
+
+/// The file of a bound unix domain socket, removed when dropped so that the server leaves no socket behind on
+/// shutdown.
+#[cfg(unix)]
+pub struct SocketFile(PathBuf);
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.0) {
+            warn!("Failed to remove the socket file {}: {}", self.0.display(), err);
+        }
+    }
+}
+
+/// Binds a unix domain socket, replacing a socket file left behind by a previous run. A socket another server still
+/// accepts connections on is left alone and binding fails.
+#[cfg(unix)]
+pub fn bind_unix(path: &PathBuf) -> io::Result<(UnixListener, SocketFile)> {
+    if let Ok(metadata) = fs::symlink_metadata(path) {
+        if metadata.file_type().is_socket() {
+            match std::os::unix::net::UnixStream::connect(path) {
+                Ok(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AddrInUse,
+                        format!("The socket {} is in use by a running server.", path.display()),
+                    ))
+                }
+                Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => fs::remove_file(path)?,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    let listener = UnixListener::bind(path)?;
+    Ok((listener, SocketFile(path.clone())))
+}
+
+/// Takes ownership of an inherited listening socket, which can either be a TCP or a unix domain socket. Descriptors
+/// that are not a listening socket are rejected and left untouched.
+#[cfg(unix)]
+pub fn inherit(fd: RawFd) -> io::Result<Inherited> {
+    ensure_listening(fd)?;
+
+    // The caller hands over the socket, nothing else in the process is using the descriptor.
+    let tcp = unsafe { std::net::TcpListener::from_raw_fd(fd) };
+
+    // Reading the local address only succeeds for sockets of an internet address family.
+    if tcp.local_addr().is_ok() {
+        tcp.set_nonblocking(true)?;
+        Ok(Inherited::Tcp(TcpListener::from_std(tcp)?))
+    } else {
+        let unix = unsafe { std::os::unix::net::UnixListener::from_raw_fd(tcp.into_raw_fd()) };
+
+        unix.set_nonblocking(true)?;
+        Ok(Inherited::Unix(UnixListener::from_std(unix)?))
+    }
+}
+
+/// Checks that the descriptor is a socket accepting connections, without taking ownership of it.
+#[cfg(unix)]
+fn ensure_listening(fd: RawFd) -> io::Result<()> {
+    let mut accepting: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+
+    // Only writes the option value into `accepting`, which is large enough to hold it.
+    let result = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_ACCEPTCONN,
+            &mut accepting as *mut libc::c_int as *mut libc::c_void,
+            &mut len,
+        )
+    };
+
+    if result != 0 {
+        let err = io::Error::last_os_error();
+        return Err(io::Error::new(
+            err.kind(),
+            format!("File descriptor {} is not a socket: {}", fd, err),
+        ));
+    }
+
+    if accepting == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Socket {} is not listening for connections.", fd),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Delays accepting after failed accepts. Errors like running out of file descriptors persist until other connections
+/// are closed, accepting again right away would only spin the CPU.
+#[derive(Debug, Default)]
+pub struct AcceptBackoff {
+    delay: Option<Duration>,
+}
+
+impl AcceptBackoff {
+    /// The delay after another failed accept.
+    pub fn failed(&mut self) -> Duration {
+        let delay = match self.delay {
+            Some(delay) => cmp::min(delay * 2, MAX_ACCEPT_BACKOFF),
+            None => MIN_ACCEPT_BACKOFF,
+        };
+
+        self.delay = Some(delay);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.delay = None;
+    }
+
+    async fn wait(&mut self, err: io::Error) {
+        let delay = self.failed();
+
+        warn!("Failed to accept a connection, retrying in {:?}: {}", delay, err);
+        delay_for(delay).await;
+    }
+}
+
+/// Accepts connections, failed accepts are logged and retried after a backoff instead of ending the server.
+fn accept_tcp(listener: TcpListener) -> impl Stream<Item = TcpStream> {
+    stream::unfold(
+        (listener, AcceptBackoff::default()),
+        |(mut listener, mut backoff)| async move {
+            loop {
+                match listener.accept().await {
+                    Ok((conn, _)) => {
+                        backoff.reset();
+                        return Some((conn, (listener, backoff)));
+                    }
+                    Err(err) => backoff.wait(err).await,
+                }
+            }
+        },
+    )
+}
+
+pub fn tcp_incoming(listener: TcpListener) -> impl Stream<Item = io::Result<TcpStream>> {
+    accept_tcp(listener).map(Ok)
+}
+
+#[cfg(unix)]
+pub fn unix_incoming(listener: UnixListener) -> impl Stream<Item = io::Result<UnixStream>> {
+    stream::unfold(
+        (listener, AcceptBackoff::default()),
+        |(mut listener, mut backoff)| async move {
+            loop {
+                match listener.accept().await {
+                    Ok((conn, _)) => {
+                        backoff.reset();
+                        return Some((Ok(conn), (listener, backoff)));
+                    }
+                    Err(err) => backoff.wait(err).await,
+                }
+            }
+        },
+    )
+}
+
+/// Terminates TLS on the accepted connections. Connections failing or not completing the handshake within
+/// `HANDSHAKE_TIMEOUT` are dropped.
+pub fn tls_incoming(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+) -> impl Stream<Item = io::Result<TlsStream<TcpStream>>> {
+    accept_tcp(listener)
+        .map(move |conn| timeout(HANDSHAKE_TIMEOUT, acceptor.accept(conn)))
+        .buffer_unordered(CONCURRENT_HANDSHAKES)
+        .filter_map(|conn| async move {
+            match conn {
+                Ok(Ok(conn)) => Some(Ok(conn)),
+                Ok(Err(err)) => {
+                    debug!("TLS handshake failed: {}", err);
+                    None
+                }
+                Err(_) => {
+                    debug!("TLS handshake timed out after {:?}.", HANDSHAKE_TIMEOUT);
+                    None
+                }
+            }
+        })
+}
@@ -3,29 +3,47 @@ extern crate log;
 #[macro_use]
 extern crate rust_embed;
 
-use std::{convert::TryFrom, error::Error, net::SocketAddr, process};
+use std::{convert::TryFrom, error::Error, fs::File, net::SocketAddr, process, time::Duration};
+
+#[cfg(unix)]
+use std::path::PathBuf;
 
 use structopt::StructOpt;
 use tracing::subscriber;
 use tracing_log::LogTracer;
-use tracing_subscriber::{EnvFilter, FmtSubscriber};
+use tracing_subscriber::{layer::SubscriberExt, registry::LookupSpan, EnvFilter, FmtSubscriber};
 
+use auth::{Access, Authenticator, JwtAuthenticator};
 use cli::*;
+use datamodel::SourceOverride;
 use error::*;
+use headers::{Cors, ResponseHeaders};
+use hyper::header::{HeaderName, HeaderValue};
 use lazy_static::lazy_static;
-use request_handlers::{PrismaRequest, PrismaResponse, RequestHandler};
+use listener::ListenAddress;
+use query_core::{AccessPolicy, QueryLimits};
+use request_handlers::{PersistedQueries, PrismaRequest, PrismaResponse, RequestHandler};
 use server::HttpServer;
+use tokio_rustls::TlsAcceptor;
 
+mod auth;
+mod body;
 mod cli;
 mod context;
 mod data_model_loader;
 mod dmmf;
 mod error;
-mod exec_loader;
+mod headers;
+mod listener;
 mod request_handlers;
 mod server;
+mod shutdown;
+mod subscriptions;
+mod telemetry;
+mod tenants;
 #[cfg(test)]
 mod tests;
+mod tls;
 mod utilities;
 
 #[derive(Debug, Clone, PartialEq, Copy)]
@@ -34,7 +52,20 @@ pub enum LogFormat {
     Json,
 }
 
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("Unknown log format '{}', expected `text` or `json`.", s)),
+        }
+    }
+}
+
 lazy_static! {
+    /// Default log format if none is given on the command line.
     pub static ref LOG_FORMAT: LogFormat = {
         match std::env::var("RUST_LOG_FORMAT").as_ref().map(|s| s.as_str()) {
             Ok("devel") => LogFormat::Text,
@@ -52,6 +83,16 @@ pub enum Subcommand {
     Cli(CliOpt),
 }
 
+#[derive(Debug, Clone, StructOpt)]
+pub struct DmmfInput {
+    /// The DMMF version the client understands.
+    #[structopt(long = "dmmf_version")]
+    pub dmmf_version: Option<u32>,
+    /// Serialization format of the DMMF, `json` or `msgpack`.
+    #[structopt(long, default_value = "json")]
+    pub format: dmmf::DmmfFormat,
+}
+
 #[derive(Debug, Clone, StructOpt)]
 pub struct DmmfToDmlInput {
     #[structopt(name = "path")]
@@ -72,7 +113,10 @@ pub struct ExecuteRequestInput {
 pub enum CliOpt {
     /// Output the DMMF from the loaded data model.
     #[structopt(name = "--dmmf")]
-    Dmmf,
+    Dmmf(DmmfInput),
+    /// Output the GraphQL SDL of the query schema generated from the loaded data model.
+    #[structopt(name = "--sdl")]
+    Sdl,
     /// Convert the given DMMF JSON file to a data model.
     #[structopt(name = "--dmmf_to_dml")]
     DmmfToDml(DmmfToDmlInput),
@@ -82,6 +126,10 @@ pub enum CliOpt {
     /// Executes one request and then terminates.
     #[structopt(name = "--execute_request")]
     ExecuteRequest(ExecuteRequestInput),
+    /// Loads the data model, connects to the datasources and reads every model once, to check that the database
+    /// schema matches the data model before deploying it. Prints a report and fails if any model can't be read.
+    #[structopt(name = "--validate", alias = "--dry_run")]
+    Validate,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -93,6 +141,27 @@ pub struct PrismaOpt {
     /// The port the query engine should bind to.
     #[structopt(long, short, env = "PORT", default_value = "4466")]
     port: u16,
+    /// Listens on a unix domain socket at the given path instead of a TCP port.
+    #[cfg(unix)]
+    #[structopt(long = "unix_socket", conflicts_with = "listen_fd")]
+    unix_socket: Option<PathBuf>,
+    /// Listens on an inherited socket instead of a TCP port, e.g. `3` for systemd socket activation.
+    #[cfg(unix)]
+    #[structopt(long = "listen_fd")]
+    listen_fd: Option<i32>,
+    /// Address to listen on, `host:port`, `https://host:port` or `unix:/path/to/socket`. Can be given multiple
+    /// times and replaces the host, port and socket options.
+    #[structopt(long = "bind", env = "BIND_ADDRESSES", use_delimiter = true)]
+    bind: Vec<ListenAddress>,
+    /// Address of the gRPC endpoint, `host:port`. Serves the queries of the data model of the environment.
+    #[structopt(long = "grpc_address", env = "GRPC_ADDRESS", conflicts_with = "multi_tenant")]
+    grpc_address: Option<SocketAddr>,
+    /// PEM encoded certificate chain of the HTTPS endpoints.
+    #[structopt(long = "tls_cert", env = "TLS_CERT", requires = "tls_key")]
+    tls_cert: Option<String>,
+    /// PEM encoded private key of the HTTPS endpoints.
+    #[structopt(long = "tls_key", env = "TLS_KEY", requires = "tls_cert")]
+    tls_key: Option<String>,
     /// Switches query schema generation to Prisma 1 compatible mode.
     #[structopt(long)]
     legacy: bool,
@@ -102,14 +171,112 @@ pub struct PrismaOpt {
     /// Enables raw SQL queries with executeRaw mutation
     #[structopt(long = "enable_raw_queries")]
     enable_raw_queries: bool,
+    /// Exports tracing spans to the given OpenTelemetry collector via OTLP.
+    #[structopt(long = "otlp_endpoint", env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+    /// Log format, `text` or `json`. JSON logs are one object per event, including the request id.
+    #[structopt(long = "log_format", alias = "log-format")]
+    log_format: Option<LogFormat>,
+    /// Seconds to wait for in-flight requests to finish when shutting down.
+    #[structopt(long = "shutdown_timeout", default_value = "10")]
+    shutdown_timeout: u64,
+    /// Maximum size of a request body in bytes.
+    #[structopt(long = "max_body_size")]
+    max_body_size: Option<usize>,
+    /// Maximum depth of nested selections in a query.
+    #[structopt(long = "max_query_depth")]
+    max_query_depth: Option<usize>,
+    /// Maximum number of nested write operations in a single mutation.
+    #[structopt(long = "max_nested_writes")]
+    max_nested_writes: Option<usize>,
+    /// Maximum number of nodes in the query graph of a single operation.
+    #[structopt(long = "max_query_graph_nodes")]
+    max_query_graph_nodes: Option<usize>,
+    /// Maximum number of records a query can read at once with `take`, `first` or `last`.
+    #[structopt(long = "max_take")]
+    max_take: Option<usize>,
+    /// Model whose records can only be read with `take`, `first` or `last` in `findMany` queries. Can be given
+    /// multiple times.
+    #[structopt(long = "paginated_model", use_delimiter = true)]
+    paginated_models: Vec<String>,
+    /// Records read by `findMany` queries on paginated models that don't limit the records themselves, instead of
+    /// rejecting these queries.
+    #[structopt(long = "default_take", requires = "paginated_models")]
+    default_take: Option<usize>,
+    /// Returns spec-compliant GraphQL errors and partial data instead of Prisma-specific errors.
+    #[structopt(long = "graphql_errors")]
+    graphql_errors: bool,
+    /// JSON file containing an array of operation documents that can be executed by their SHA-256 hash.
+    #[structopt(long = "persisted_queries")]
+    persisted_queries: Option<String>,
+    /// Rejects all queries that are not registered as persisted queries.
+    #[structopt(long = "only_persisted_queries", requires = "persisted_queries")]
+    only_persisted_queries: bool,
+    /// JSON file restricting the operations on models and the fields that can be read and written. Forbidden
+    /// operations and fields are left out of the query schema.
+    #[structopt(long = "access_policy", conflicts_with = "multi_tenant")]
+    access_policy: Option<String>,
+    /// Origins allowed to send cross-origin requests, `*` allows all origins. Enables CORS if set.
+    #[structopt(long = "cors_origins", env = "CORS_ORIGINS", use_delimiter = true)]
+    cors_origins: Vec<String>,
+    /// Request headers allowed in cross-origin requests.
+    #[structopt(
+        long = "cors_headers",
+        env = "CORS_HEADERS",
+        use_delimiter = true,
+        default_value = "content-type,authorization,x-request-timeout,traceparent"
+    )]
+    cors_headers: Vec<String>,
+    /// Methods allowed in cross-origin requests.
+    #[structopt(
+        long = "cors_methods",
+        env = "CORS_METHODS",
+        use_delimiter = true,
+        default_value = "GET,POST,OPTIONS"
+    )]
+    cors_methods: Vec<String>,
+    /// Header added to every response, given as `Name: value`. Can be given multiple times,
+    /// the environment variable takes one header per line.
+    #[structopt(
+        long = "response_header",
+        env = "RESPONSE_HEADERS",
+        value_delimiter = "\n",
+        parse(try_from_str = headers::parse_header)
+    )]
+    response_headers: Vec<(HeaderName, HeaderValue)>,
+    /// Serves multiple data models loaded at runtime through the `/tenants` management API,
+    /// instead of the data model of the environment. Loading and unloading tenants requires authentication.
+    #[structopt(long = "multi_tenant", conflicts_with = "persisted_queries")]
+    multi_tenant: bool,
+    /// Bearer token with full access. Enables authentication if set, can be given multiple times.
+    #[structopt(long = "auth_token", env = "AUTH_TOKENS", use_delimiter = true)]
+    auth_tokens: Vec<String>,
+    /// Bearer token allowing queries only, mutations are rejected. Enables authentication if set.
+    #[structopt(long = "read_only_token", env = "READ_ONLY_TOKENS", use_delimiter = true)]
+    read_only_tokens: Vec<String>,
+    /// JSON Web Key Set (URL or file) to validate JWT bearer tokens against. Enables authentication if set.
+    /// Tokens need the `prisma:write` scope to execute mutations.
+    #[structopt(long = "auth_jwks", env = "AUTH_JWKS")]
+    auth_jwks: Option<String>,
+    /// Required issuer (`iss` claim) of JWT bearer tokens.
+    #[structopt(long = "auth_jwt_issuer", env = "AUTH_JWT_ISSUER", requires = "auth_jwks")]
+    auth_jwt_issuer: Option<String>,
+    /// Required audience (`aud` claim) of JWT bearer tokens.
+    #[structopt(long = "auth_jwt_audience", env = "AUTH_JWT_AUDIENCE", requires = "auth_jwks")]
+    auth_jwt_audience: Option<String>,
+    /// Replaces the URL of a datasource of the data model, given as `name=url`. Can be given multiple times,
+    /// and takes precedence over the `OVERWRITE_DATASOURCES` environment variable.
+    #[structopt(long = "datasource", parse(try_from_str = data_model_loader::parse_source_override))]
+    datasources: Vec<SourceOverride>,
     #[structopt(subcommand)]
     subcommand: Option<Subcommand>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), AnyError> {
-    init_logger()?;
     let opts = PrismaOpt::from_args();
+    let log_format = opts.log_format.unwrap_or(*LOG_FORMAT);
+    let _telemetry = init_logger(log_format, opts.otlp_endpoint.as_deref())?;
 
     match CliCommand::try_from(&opts) {
         Ok(cmd) => {
@@ -120,19 +287,130 @@ async fn main() -> Result<(), AnyError> {
             }
         }
         Err(_) => {
-            set_panic_hook()?;
-            let ip = opts.host.parse().expect("Host was not a valid IP address");
-            let address = SocketAddr::new(ip, opts.port);
+            set_panic_hook(log_format)?;
+            let addresses = listen_addresses(&opts);
 
             eprintln!("Printing to stderr for debugging");
-            eprintln!("Listening on {}:{}", opts.host, opts.port);
+
+            for address in addresses.iter() {
+                eprintln!("Listening on {}", address);
+            }
+
+            let tls = match load_tls(&opts, &addresses) {
+                Ok(tls) => tls,
+                Err(err) => {
+                    info!("Encountered error during initialization:");
+                    err.render_as_json().expect("error rendering");
+                    process::exit(1);
+                }
+            };
+
+            let persisted_queries = match opts.persisted_queries {
+                Some(ref path) => PersistedQueries::load(path, opts.only_persisted_queries),
+                None => Ok(PersistedQueries::default()),
+            };
+
+            let persisted_queries = match persisted_queries {
+                Ok(persisted_queries) => persisted_queries,
+                Err(err) => {
+                    info!("Encountered error during initialization:");
+                    err.render_as_json().expect("error rendering");
+                    process::exit(1);
+                }
+            };
+
+            let access_policy = match opts.access_policy {
+                Some(ref path) => load_access_policy(path),
+                None => Ok(AccessPolicy::default()),
+            };
+
+            let access_policy = match access_policy {
+                Ok(access_policy) => access_policy,
+                Err(err) => {
+                    info!("Encountered error during initialization:");
+                    err.render_as_json().expect("error rendering");
+                    process::exit(1);
+                }
+            };
+
+            let cors = if opts.cors_origins.is_empty() {
+                Ok(None)
+            } else {
+                Cors::new(
+                    opts.cors_origins.clone(),
+                    opts.cors_headers.clone(),
+                    opts.cors_methods.clone(),
+                )
+                .map(Some)
+            };
+
+            let response_headers = match cors {
+                Ok(cors) => ResponseHeaders::new(cors, opts.response_headers.clone()),
+                Err(err) => {
+                    info!("Encountered error during initialization:");
+                    err.render_as_json().expect("error rendering");
+                    process::exit(1);
+                }
+            };
+
+            let jwt = match opts.auth_jwks {
+                Some(ref source) => JwtAuthenticator::load(
+                    source.clone(),
+                    opts.auth_jwt_issuer.clone(),
+                    opts.auth_jwt_audience.clone(),
+                )
+                .await
+                .map(Some),
+                None => Ok(None),
+            };
+
+            let authenticator = match jwt {
+                Ok(jwt) => {
+                    let tokens = opts
+                        .auth_tokens
+                        .iter()
+                        .map(|token| (token.clone(), Access::ReadWrite))
+                        .chain(
+                            opts.read_only_tokens
+                                .iter()
+                                .map(|token| (token.clone(), Access::ReadOnly)),
+                        )
+                        .collect();
+
+                    Authenticator::new(tokens, jwt)
+                }
+                Err(err) => {
+                    info!("Encountered error during initialization:");
+                    err.render_as_json().expect("error rendering");
+                    process::exit(1);
+                }
+            };
 
             let builder = HttpServer::builder()
                 .legacy(opts.legacy)
                 .enable_raw_queries(opts.enable_raw_queries)
-                .force_transactions(opts.always_force_transactions);
+                .force_transactions(opts.always_force_transactions)
+                .shutdown_timeout(Duration::from_secs(opts.shutdown_timeout))
+                .max_body_size(opts.max_body_size)
+                .graphql_errors(opts.graphql_errors)
+                .persisted_queries(persisted_queries)
+                .access_policy(access_policy)
+                .response_headers(response_headers)
+                .multi_tenant(opts.multi_tenant)
+                .tls(tls)
+                .authenticator(authenticator)
+                .datasource_overrides(opts.datasources.clone())
+                .grpc_address(opts.grpc_address)
+                .limits(QueryLimits {
+                    max_depth: opts.max_query_depth,
+                    max_nested_writes: opts.max_nested_writes,
+                    max_graph_nodes: opts.max_query_graph_nodes,
+                    max_take: opts.max_take,
+                    paginated_models: opts.paginated_models.clone(),
+                    default_take: opts.default_take,
+                });
 
-            if let Err(err) = builder.build_and_run(address).await {
+            if let Err(err) = builder.build_and_run(addresses).await {
                 info!("Encountered error during initialization:");
                 err.render_as_json().expect("error rendering");
                 process::exit(1);
@@ -143,16 +421,60 @@ async fn main() -> Result<(), AnyError> {
     Ok(())
 }
 
-fn init_logger() -> Result<(), AnyError> {
+fn load_access_policy(path: &str) -> PrismaResult<AccessPolicy> {
+    let file = File::open(path)?;
+    let policy = serde_json::from_reader(file)?;
+
+    Ok(policy)
+}
+
+/// Loads the certificate served on the `https://` addresses. A certificate without such an address is rejected
+/// instead of serving plain HTTP where TLS was intended.
+fn load_tls(opts: &PrismaOpt, addresses: &[ListenAddress]) -> PrismaResult<Option<TlsAcceptor>> {
+    let serves_tls = addresses.iter().any(|address| match address {
+        ListenAddress::Tls(_) => true,
+        _ => false,
+    });
+
+    match (opts.tls_cert.as_ref(), opts.tls_key.as_ref()) {
+        (Some(_), Some(_)) if !serves_tls => Err(PrismaError::ConfigurationError(
+            "A TLS certificate requires an `https://host:port` address to serve it on.".into(),
+        )),
+        (Some(cert), Some(key)) => tls::acceptor(cert, key).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn listen_addresses(opts: &PrismaOpt) -> Vec<ListenAddress> {
+    if !opts.bind.is_empty() {
+        return opts.bind.clone();
+    }
+
+    #[cfg(unix)]
+    {
+        if let Some(ref path) = opts.unix_socket {
+            return vec![ListenAddress::Unix(path.clone())];
+        }
+
+        if let Some(fd) = opts.listen_fd {
+            return vec![ListenAddress::Fd(fd)];
+        }
+    }
+
+    let ip = opts.host.parse().expect("Host was not a valid IP address");
+    vec![ListenAddress::Tcp(SocketAddr::new(ip, opts.port))]
+}
+
+fn init_logger(log_format: LogFormat, otlp_endpoint: Option<&str>) -> Result<Option<telemetry::Uninstall>, AnyError> {
     LogTracer::init()?;
 
-    match *LOG_FORMAT {
+    match log_format {
         LogFormat::Text => {
             let subscriber = FmtSubscriber::builder()
                 .with_env_filter(EnvFilter::from_default_env())
                 .finish();
 
-            subscriber::set_global_default(subscriber)?;
+            set_global_subscriber(subscriber, otlp_endpoint)
         }
         LogFormat::Json => {
             let subscriber = FmtSubscriber::builder()
@@ -160,15 +482,35 @@ fn init_logger() -> Result<(), AnyError> {
                 .with_env_filter(EnvFilter::from_default_env())
                 .finish();
 
-            subscriber::set_global_default(subscriber)?;
+            set_global_subscriber(subscriber, otlp_endpoint)
         }
     }
+}
 
-    Ok(())
+/// Installs the subscriber, adding an OpenTelemetry layer on top if an OTLP endpoint is given.
+fn set_global_subscriber<S>(
+    subscriber: S,
+    otlp_endpoint: Option<&str>,
+) -> Result<Option<telemetry::Uninstall>, AnyError>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span> + Send + Sync + 'static,
+{
+    match otlp_endpoint {
+        Some(endpoint) => {
+            let (layer, uninstall) = telemetry::layer(endpoint);
+            subscriber::set_global_default(subscriber.with(layer))?;
+
+            Ok(Some(uninstall))
+        }
+        None => {
+            subscriber::set_global_default(subscriber)?;
+            Ok(None)
+        }
+    }
 }
 
-fn set_panic_hook() -> Result<(), AnyError> {
-    match *LOG_FORMAT {
+fn set_panic_hook(log_format: LogFormat) -> Result<(), AnyError> {
+    match log_format {
         LogFormat::Text => (),
         LogFormat::Json => {
             std::panic::set_hook(Box::new(|info| {
@@ -1,18 +1,41 @@
-use super::protocol_adapter::GraphQLProtocolAdapter;
-use crate::{context::PrismaContext, PrismaRequest, PrismaResponse, PrismaResult, RequestHandler};
+use super::{
+    introspection,
+    persisted_queries::{DocumentOrigin, PersistedQuery},
+    protocol_adapter::GraphQLProtocolAdapter,
+    response::GraphQlResponse,
+};
+use crate::{
+    auth::Access,
+    context::PrismaContext,
+    request_handlers::{batch_idempotency_error, execute, execute_batch, idempotency_key},
+    PrismaError, PrismaRequest, PrismaResponse, PrismaResult, RequestHandler,
+};
 use async_trait::async_trait;
-use futures::{future, FutureExt};
+use futures::FutureExt;
 use graphql_parser as gql;
-use query_core::{response_ir, CoreError};
+use query_core::{response_ir, IdempotencyKey, Operation, QueryDocument};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, panic::AssertUnwindSafe, sync::Arc};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SingleQuery {
-    query: String,
-    operation_name: Option<String>,
-    variables: HashMap<String, String>,
+    pub(super) query: String,
+    pub(super) operation_name: Option<String>,
+    pub(super) variables: HashMap<String, serde_json::Value>,
+    #[serde(skip)]
+    pub(super) origin: DocumentOrigin,
+}
+
+impl SingleQuery {
+    pub fn new(query: String, operation_name: Option<String>, variables: HashMap<String, serde_json::Value>) -> Self {
+        Self {
+            query,
+            operation_name,
+            variables,
+            origin: DocumentOrigin::AdHoc,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,6 +48,7 @@ pub struct MultiQuery {
 #[serde(rename_all = "camelCase", untagged)]
 pub enum GraphQlBody {
     Single(SingleQuery),
+    Persisted(PersistedQuery),
     Multi(MultiQuery),
 }
 
@@ -40,32 +64,68 @@ impl RequestHandler for GraphQlRequestHandler {
         S: Into<PrismaRequest<Self::Body>> + Send + Sync + 'static,
     {
         let request = req.into();
+        let access = request.access;
+        let idempotency_key = idempotency_key(&request.headers, &request.body);
+
+        let persisted_queries = ctx.persisted_queries();
 
         match request.body {
-            GraphQlBody::Single(query) => handle_single_query(query, ctx.clone()).await,
+            GraphQlBody::Single(query) => match persisted_queries.check_ad_hoc(query) {
+                Ok(query) => handle_single_query(query, ctx.clone(), access, idempotency_key).await,
+                Err(err) => error_response(err),
+            },
+            GraphQlBody::Persisted(query) => match persisted_queries.resolve(query) {
+                Ok(query) => handle_single_query(query, ctx.clone(), access, idempotency_key).await,
+                Err(err) => error_response(err),
+            },
+            GraphQlBody::Multi(_) if idempotency_key.is_some() => error_response(batch_idempotency_error()),
             GraphQlBody::Multi(queries) => {
-                let mut futures = Vec::with_capacity(queries.batch.len());
-
-                for query in queries.batch.into_iter() {
-                    futures.push(tokio::spawn(handle_single_query(query, ctx.clone())));
-                }
+                let queries = match queries
+                    .batch
+                    .into_iter()
+                    .map(|query| persisted_queries.check_ad_hoc(query))
+                    .collect::<PrismaResult<Vec<_>>>()
+                {
+                    Ok(queries) => queries,
+                    Err(err) => return error_response(err),
+                };
 
-                let responses = future::join_all(futures)
-                    .await
+                let futures = queries
                     .into_iter()
-                    .map(|res| res.expect("IO Error in tokio::spawn"))
+                    .map(|query| {
+                        (
+                            query.operation_name.clone(),
+                            handle_single_query(query, ctx.clone(), access, None),
+                        )
+                    })
                     .collect();
 
-                PrismaResponse::Multi(responses)
+                PrismaResponse::Multi(execute_batch(futures).await)
             }
         }
     }
 }
 
-async fn handle_single_query(query: SingleQuery, ctx: Arc<PrismaContext>) -> PrismaResponse {
+pub(super) fn error_response(err: PrismaError) -> PrismaResponse {
+    let mut responses = response_ir::Responses::default();
+    responses.insert_error(err);
+
+    PrismaResponse::Single(responses)
+}
+
+pub(super) async fn handle_single_query(
+    query: SingleQuery,
+    ctx: Arc<PrismaContext>,
+    access: Access,
+    idempotency_key: Option<IdempotencyKey>,
+) -> PrismaResponse {
     use user_facing_errors::Error;
 
-    let responses = match AssertUnwindSafe(handle_graphql_query(query, &*ctx))
+    if ctx.graphql_errors() {
+        return PrismaResponse::GraphQl(handle_compliant_query(query, ctx, access, idempotency_key).await);
+    }
+
+    let responses = match AssertUnwindSafe(handle_graphql_query(query, &*ctx, access, idempotency_key))
         .catch_unwind()
         .await
     {
@@ -88,19 +148,116 @@ async fn handle_single_query(query: SingleQuery, ctx: Arc<PrismaContext>) -> Pri
     PrismaResponse::Single(responses)
 }
 
-async fn handle_graphql_query(body: SingleQuery, ctx: &PrismaContext) -> PrismaResult<response_ir::Responses> {
+async fn handle_graphql_query(
+    body: SingleQuery,
+    ctx: &PrismaContext,
+    access: Access,
+    idempotency_key: Option<IdempotencyKey>,
+) -> PrismaResult<response_ir::Responses> {
     debug!("Incoming GQL query: {:?}", &body.query);
     debug!("Operation: {:?}", body.operation_name);
 
-    let gql_doc = gql::parse_query(&body.query)?;
-    let query_doc = GraphQLProtocolAdapter::convert(gql_doc, body.operation_name)?;
+    let origin = body.origin;
 
-    ctx.executor
-        .execute(query_doc, Arc::clone(ctx.query_schema()))
-        .await
-        .map_err(|err| {
-            debug!("{}", err);
-            let ce: CoreError = err.into();
-            ce.into()
-        })
+    let (query_doc, introspection) = tracing::info_span!("prisma:parse").in_scope(|| -> PrismaResult<_> {
+        let mut gql_doc = gql::parse_query(&body.query)?;
+        let introspection = introspection::resolve(&mut gql_doc, body.operation_name.as_deref(), ctx.query_schema())?;
+
+        Ok((
+            GraphQLProtocolAdapter::convert(gql_doc, body.operation_name, &body.variables)?,
+            introspection,
+        ))
+    })?;
+
+    for operation in query_doc.operations.iter() {
+        access.authorize(operation)?;
+    }
+
+    // Queries only consisting of introspection fields don't touch the database.
+    let mut responses = if query_doc.operations.is_empty() {
+        response_ir::Responses::default()
+    } else {
+        execute(ctx, query_doc, origin, idempotency_key).await?
+    };
+
+    for (key, value) in introspection {
+        responses.insert_data(key, response_ir::Item::Json(value));
+    }
+
+    Ok(responses)
+}
+
+/// Executes every operation of the query separately, so that the data of successful operations is
+/// returned alongside the errors of failed ones, as required by the GraphQL specification.
+/// With an idempotency key, each mutation is stored under the key suffixed with the response key of the mutation.
+async fn handle_compliant_query(
+    body: SingleQuery,
+    ctx: Arc<PrismaContext>,
+    access: Access,
+    idempotency_key: Option<IdempotencyKey>,
+) -> GraphQlResponse {
+    use user_facing_errors::Error;
+
+    let mut gql_doc = match gql::parse_query(&body.query) {
+        Ok(doc) => doc,
+        Err(err) => return GraphQlResponse::request_error(PrismaError::from(err)),
+    };
+
+    let positions = GraphQLProtocolAdapter::root_field_positions(&gql_doc);
+
+    let introspection = match introspection::resolve(&mut gql_doc, body.operation_name.as_deref(), ctx.query_schema()) {
+        Ok(introspection) => introspection,
+        Err(err) => return GraphQlResponse::request_error(err),
+    };
+
+    let query_doc = match GraphQLProtocolAdapter::convert(gql_doc, body.operation_name, &body.variables) {
+        Ok(doc) => doc,
+        Err(err) => return GraphQlResponse::request_error(err),
+    };
+
+    let mut response = GraphQlResponse::default();
+
+    for (key, value) in introspection {
+        let position = positions.get(&key).copied();
+        let mut responses = response_ir::Responses::default();
+
+        responses.insert_data(key.clone(), response_ir::Item::Json(value));
+        response.insert_operation_result(key, position, responses);
+    }
+
+    for operation in query_doc.operations {
+        let key = match &operation {
+            Operation::Read(selection) | Operation::Write(selection) => selection.response_key().to_owned(),
+        };
+
+        let position = positions.get(&key).copied();
+
+        if let Err(err) = access.authorize(&operation) {
+            response.insert_operation_error(key, position, err);
+            continue;
+        }
+
+        let operation_key = idempotency_key.as_ref().map(|idempotency_key| {
+            IdempotencyKey::new(
+                format!("{}:{}", idempotency_key.key, key),
+                idempotency_key.request_hash.clone(),
+            )
+        });
+
+        let query_doc = QueryDocument {
+            operations: vec![operation],
+        };
+
+        match AssertUnwindSafe(execute(&*ctx, query_doc, body.origin, operation_key))
+            .catch_unwind()
+            .await
+        {
+            Ok(Ok(responses)) => response.insert_operation_result(key, position, responses),
+            Ok(Err(err)) => response.insert_operation_error(key, position, err),
+            // panicked
+            Err(err) => response.insert_operation_error(key, position, Error::from_panic_payload(&err)),
+        }
+    }
+
+    response
 }
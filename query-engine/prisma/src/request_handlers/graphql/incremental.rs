@@ -0,0 +1,335 @@
+use super::{
+    handler::{error_response, handle_single_query},
+    introspection,
+    protocol_adapter::GraphQLProtocolAdapter,
+    DocumentOrigin, GraphQlBody, GraphQlRequestHandler, SingleQuery,
+};
+use crate::{
+    context::PrismaContext,
+    request_handlers::{execute, idempotency_key},
+    PrismaRequest, PrismaResponse, PrismaResult, RequestHandler,
+};
+use futures::{
+    future::{self, FutureExt},
+    stream::{self, BoxStream, FuturesUnordered, StreamExt},
+};
+use graphql_parser as gql;
+use query_core::{
+    response_ir::{Item, ResponseError},
+    restrict_to_records, DeferredSelection, Operation, QueryDocument, QueryValue, DEFERRED_ID_PREFIX,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::BTreeMap, panic::AssertUnwindSafe, sync::Arc};
+
+/// The response to a client accepting incremental delivery.
+pub enum IncrementalResponse {
+    /// The request has nothing to defer, or failed before anything could be delivered.
+    Complete(PrismaResponse),
+    /// The data without the deferred selections, followed by a payload for every object deferred selections are
+    /// nested in, and a last payload with `hasNext` false.
+    Incremental(BoxStream<'static, Payload>),
+}
+
+/// A payload of an incremental response, following the GraphQL incremental delivery proposal.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Item>,
+    /// The position of the object the data belongs to in the initial data, `None` for the initial data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<Vec<PathSegment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<ResponseError>,
+    has_next: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Handles the request of a client accepting incremental delivery. Selections of a single query marked with `@defer`
+/// are read after the rest of the query and delivered as they become available, see `DeferredSelection`.
+/// Other requests are handled by the `GraphQlRequestHandler`.
+///
+/// The deferred selections are read with a query of their own. Records are matched with the initial data by their
+/// identifiers, so that records created, deleted or reordered in between never get the data of other records.
+pub async fn handle_incremental(req: PrismaRequest<GraphQlBody>, ctx: &Arc<PrismaContext>) -> IncrementalResponse {
+    let access = req.access;
+    let idempotency_key = idempotency_key(&req.headers, &req.body);
+
+    let query = match req.body {
+        GraphQlBody::Single(query) => query,
+        body => {
+            let req = PrismaRequest { body, ..req };
+            return IncrementalResponse::Complete(GraphQlRequestHandler.handle(req, ctx).await);
+        }
+    };
+
+    let query = match ctx.persisted_queries().check_ad_hoc(query) {
+        Ok(query) => query,
+        Err(err) => return IncrementalResponse::Complete(error_response(err)),
+    };
+
+    let (mut query_doc, deferred, introspection) = match parse_deferred(&query, ctx) {
+        Ok(parsed) => parsed,
+        Err(err) => return IncrementalResponse::Complete(error_response(err)),
+    };
+
+    if deferred.is_empty() {
+        return IncrementalResponse::Complete(handle_single_query(query, ctx.clone(), access, idempotency_key).await);
+    }
+
+    if let Err(err) = query_doc
+        .operations
+        .iter()
+        .try_for_each(|operation| access.authorize(operation))
+    {
+        return IncrementalResponse::Complete(error_response(err));
+    }
+
+    let reads: Vec<(DeferredSelection, Operation)> = deferred
+        .into_iter()
+        .filter(|deferred| deferred.select_ids(&mut query_doc, ctx.query_schema()).is_some())
+        .filter_map(|deferred| deferred.operation(&query_doc).map(|operation| (deferred, operation)))
+        .collect();
+
+    let mut responses = match execute(&*ctx, query_doc, query.origin, idempotency_key).await {
+        Ok(responses) => responses,
+        Err(err) => return IncrementalResponse::Complete(error_response(err)),
+    };
+
+    // Deferred selections of failed queries are not read.
+    if responses.has_errors() {
+        return IncrementalResponse::Complete(PrismaResponse::Single(responses));
+    }
+
+    for (key, value) in introspection {
+        responses.insert_data(key, Item::Json(value));
+    }
+
+    let (data, errors) = responses.into_parts();
+    let data = match serde_json::to_value(Item::Map(data)) {
+        Ok(data) => Arc::new(data),
+        Err(err) => return IncrementalResponse::Complete(error_response(err.into())),
+    };
+
+    let initial = Payload {
+        data: Some(Item::Json(without_ids(Value::clone(&data)))),
+        errors,
+        has_next: true,
+        ..Default::default()
+    };
+
+    let deferred_payloads = reads
+        .into_iter()
+        .map(|(deferred, operation)| {
+            read_deferred(Arc::clone(ctx), deferred, operation, Arc::clone(&data), query.origin)
+        })
+        .collect::<FuturesUnordered<_>>()
+        .map(stream::iter)
+        .flatten();
+
+    let payloads = stream::once(future::ready(initial))
+        .chain(deferred_payloads)
+        .chain(stream::once(future::ready(Payload::default())));
+
+    IncrementalResponse::Incremental(payloads.boxed())
+}
+
+/// Parses the query into a document without the deferred selections, and the results of its introspection fields.
+fn parse_deferred(
+    query: &SingleQuery,
+    ctx: &PrismaContext,
+) -> PrismaResult<(QueryDocument, Vec<DeferredSelection>, Vec<(String, serde_json::Value)>)> {
+    tracing::info_span!("prisma:parse").in_scope(|| -> PrismaResult<_> {
+        let mut gql_doc = gql::parse_query(&query.query)?;
+        let introspection = introspection::resolve(&mut gql_doc, query.operation_name.as_deref(), ctx.query_schema())?;
+        let (query_doc, deferred) =
+            GraphQLProtocolAdapter::convert_deferred(gql_doc, query.operation_name.clone(), &query.variables)?;
+
+        Ok((query_doc, deferred, introspection))
+    })
+}
+
+/// Reads the deferred selections of the records in the initial data, a failed read results in a single payload with
+/// the errors at the path of the selections.
+async fn read_deferred(
+    ctx: Arc<PrismaContext>,
+    deferred: DeferredSelection,
+    operation: Operation,
+    initial: Arc<Value>,
+    origin: DocumentOrigin,
+) -> Vec<Payload> {
+    use user_facing_errors::Error;
+
+    let records = deferred.path.first().and_then(|key| initial.get(key));
+
+    let operation = match records {
+        Some(Value::Array(records)) if records.is_empty() => return vec![],
+        Some(Value::Array(records)) => restrict_to_records(operation, records.iter().map(record_ids).collect()),
+        Some(Value::Object(_)) => operation,
+        // A record that wasn't found has nothing deferred.
+        _ => return vec![],
+    };
+
+    let query_doc = QueryDocument {
+        operations: vec![operation],
+    };
+
+    let errors: Vec<ResponseError> = match AssertUnwindSafe(execute(&*ctx, query_doc, origin, None))
+        .catch_unwind()
+        .await
+    {
+        Ok(Ok(responses)) => {
+            let (data, errors) = responses.into_parts();
+
+            if errors.is_empty() {
+                match serde_json::to_value(Item::Map(data)) {
+                    Ok(data) => return deferred_payloads(&deferred, &initial, &data),
+                    Err(err) => vec![crate::PrismaError::from(err).into()],
+                }
+            } else {
+                errors
+            }
+        }
+        Ok(Err(err)) => vec![err.into()],
+        // panicked
+        Err(err) => vec![Error::from_panic_payload(&err).into()],
+    };
+
+    vec![Payload {
+        path: Some(deferred.path.into_iter().map(PathSegment::Key).collect()),
+        label: deferred.label,
+        errors,
+        has_next: true,
+        ..Default::default()
+    }]
+}
+
+/// A payload for every object at the path of the deferred selections in the initial data, holding only the deferred
+/// fields of the same record in the deferred data.
+fn deferred_payloads(deferred: &DeferredSelection, initial: &Value, data: &Value) -> Vec<Payload> {
+    let objects = |data: &'_ Value| {
+        let mut objects = vec![];
+
+        if let Some((key, path)) = deferred.path.split_first() {
+            if let Some(value) = data.get(key) {
+                collect_objects(value, path, vec![PathSegment::Key(key.clone())], vec![], &mut objects);
+            }
+        }
+
+        objects
+    };
+
+    let deferred_objects = objects(data);
+
+    objects(initial)
+        .into_iter()
+        .filter_map(|(path, ids, _)| {
+            let (_, _, object) = deferred_objects
+                .iter()
+                .find(|(_, deferred_ids, _)| deferred_ids == &ids)?;
+
+            Some(Payload {
+                data: Some(Item::Json(without_ids(Value::clone(object)))),
+                path: Some(path),
+                label: deferred.label.clone(),
+                has_next: true,
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Collects the objects at the path within the value, with their path in the response, including list indices, and
+/// the identifiers of the records along the path.
+fn collect_objects<'a>(
+    value: &'a Value,
+    path: &[String],
+    value_path: Vec<PathSegment>,
+    ids: Vec<Value>,
+    objects: &mut Vec<(Vec<PathSegment>, Vec<Value>, &'a Value)>,
+) {
+    match value {
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                let mut value_path = value_path.clone();
+                value_path.push(PathSegment::Index(index));
+
+                collect_objects(value, path, value_path, ids.clone(), objects);
+            }
+        }
+        Value::Object(object) => {
+            let mut ids = ids;
+            ids.push(Value::Object(
+                object
+                    .iter()
+                    .filter(|(key, _)| key.starts_with(DEFERRED_ID_PREFIX))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            ));
+
+            match path.split_first() {
+                Some((key, path)) => {
+                    if let Some(value) = object.get(key) {
+                        let mut value_path = value_path;
+                        value_path.push(PathSegment::Key(key.clone()));
+
+                        collect_objects(value, path, value_path, ids, objects);
+                    }
+                }
+                None => objects.push((value_path, ids, value)),
+            }
+        }
+        // Objects that are null have nothing deferred.
+        _ => (),
+    }
+}
+
+/// The identifier fields of a record of the initial data, by field name.
+fn record_ids(record: &Value) -> BTreeMap<String, QueryValue> {
+    let object = match record {
+        Value::Object(object) => object,
+        _ => return BTreeMap::new(),
+    };
+
+    object
+        .iter()
+        .filter(|(key, _)| key.starts_with(DEFERRED_ID_PREFIX))
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => QueryValue::String(s.clone()),
+                Value::Bool(b) => QueryValue::Boolean(*b),
+                Value::Number(n) => n
+                    .as_i64()
+                    .map(QueryValue::Int)
+                    .unwrap_or_else(|| QueryValue::Float(n.as_f64().unwrap_or_default())),
+                _ => QueryValue::Null,
+            };
+
+            (key[DEFERRED_ID_PREFIX.len()..].to_owned(), value)
+        })
+        .collect()
+}
+
+/// Removes the identifiers selected to match the records of deferred reads.
+fn without_ids(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .filter(|(key, _)| !key.starts_with(DEFERRED_ID_PREFIX))
+                .map(|(key, value)| (key, without_ids(value)))
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(without_ids).collect()),
+        value => value,
+    }
+}
@@ -0,0 +1,487 @@
+use crate::{PrismaError, PrismaResult};
+use graphql_parser::query::{
+    Definition, Document, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet, Value as GqlValue,
+};
+use indexmap::IndexMap;
+use query_core::schema::{
+    EnumType, InputObjectType, InputType, IntoArc, ObjectType, OutputType, QuerySchema, ScalarType,
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Scalar types of the query schema, `Json` also covers `JsonList`.
+const SCALARS: &[&str] = &["String", "Int", "Float", "Decimal", "Boolean", "DateTime", "Json", "UUID"];
+
+/// Answers the GraphQL introspection fields (`__schema`, `__type` and `__typename`) of a query.
+///
+/// Introspection fields are removed from the root selection sets of the executed operations, so that the remaining
+/// document can be converted and executed as usual. Fragments spread into introspection fields are resolved here, the
+/// fragment definitions stay in the document for the protocol adapter to spread into the remaining fields.
+///
+/// Returns the resolved values keyed by their response key (alias or name).
+pub fn resolve(
+    gql_doc: &mut Document,
+    operation: Option<&str>,
+    query_schema: &QuerySchema,
+) -> PrismaResult<Vec<(String, Value)>> {
+    let (fragments, definitions): (Vec<_>, Vec<_>) = gql_doc.definitions.drain(..).partition(|def| match def {
+        Definition::Fragment(_) => true,
+        _ => false,
+    });
+
+    gql_doc.definitions = definitions;
+
+    let fields: Vec<Field> = gql_doc
+        .definitions
+        .iter_mut()
+        .filter_map(|def| match def {
+            Definition::Operation(OperationDefinition::SelectionSet(s)) if operation.is_none() => Some(s),
+            Definition::Operation(OperationDefinition::Query(q))
+                if operation.is_none() || q.name.as_deref() == operation =>
+            {
+                Some(&mut q.selection_set)
+            }
+            _ => None,
+        })
+        .flat_map(take_introspection_fields)
+        .collect();
+
+    gql_doc.definitions.extend(fragments.iter().cloned());
+
+    if fields.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let fragments = fragments
+        .into_iter()
+        .filter_map(|def| match def {
+            Definition::Fragment(f) => Some((f.name.clone(), f)),
+            _ => None,
+        })
+        .collect();
+
+    let introspection = Introspection::new(query_schema, fragments);
+
+    fields
+        .iter()
+        .map(|field| {
+            let key = field.alias.clone().unwrap_or_else(|| field.name.clone());
+            introspection.resolve_root_field(field).map(|value| (key, value))
+        })
+        .collect()
+}
+
+fn take_introspection_fields(selection_set: &mut SelectionSet) -> Vec<Field> {
+    let (introspection, items): (Vec<_>, Vec<_>) = selection_set.items.drain(..).partition(|item| match item {
+        Selection::Field(f) => f.name.starts_with("__"),
+        _ => false,
+    });
+
+    selection_set.items = items;
+
+    introspection
+        .into_iter()
+        .filter_map(|item| match item {
+            Selection::Field(f) => Some(f),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TypeKind {
+    Scalar,
+    Object,
+    InputObject,
+    Enum,
+}
+
+impl TypeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Scalar => "SCALAR",
+            Self::Object => "OBJECT",
+            Self::InputObject => "INPUT_OBJECT",
+            Self::Enum => "ENUM",
+        }
+    }
+}
+
+/// Reference to a type, with the list and non-null wrappers GraphQL models as separate types.
+#[derive(Debug)]
+enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl TypeRef {
+    fn required(name: &str) -> Self {
+        Self::NonNull(Box::new(Self::Named(name.to_owned())))
+    }
+
+    fn required_list(of: TypeRef) -> Self {
+        Self::NonNull(Box::new(Self::List(Box::new(of))))
+    }
+
+    /// Types of the query schema are required unless wrapped in an `Opt`.
+    fn optional(self) -> Self {
+        match self {
+            Self::NonNull(inner) => *inner,
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TypeInfo {
+    kind: TypeKind,
+    fields: Vec<FieldInfo>,
+    input_fields: Vec<InputValueInfo>,
+    enum_values: Vec<String>,
+}
+
+impl TypeInfo {
+    fn new(kind: TypeKind) -> Self {
+        Self {
+            kind,
+            fields: vec![],
+            input_fields: vec![],
+            enum_values: vec![],
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FieldInfo {
+    name: String,
+    args: Vec<InputValueInfo>,
+    type_ref: TypeRef,
+}
+
+#[derive(Debug)]
+struct InputValueInfo {
+    name: String,
+    type_ref: TypeRef,
+}
+
+/// The type system of a query schema, in the shape of the GraphQL introspection types.
+struct Introspection {
+    types: IndexMap<String, TypeInfo>,
+    query_type: String,
+    mutation_type: String,
+    fragments: HashMap<String, FragmentDefinition>,
+}
+
+impl Introspection {
+    fn new(query_schema: &QuerySchema, fragments: HashMap<String, FragmentDefinition>) -> Self {
+        let query = query_schema.query();
+        let mutation = query_schema.mutation();
+
+        let mut introspection = Self {
+            types: IndexMap::new(),
+            query_type: query.name().to_owned(),
+            mutation_type: mutation.name().to_owned(),
+            fragments,
+        };
+
+        for scalar in SCALARS {
+            introspection
+                .types
+                .insert((*scalar).to_owned(), TypeInfo::new(TypeKind::Scalar));
+        }
+
+        introspection.add_object(&query);
+        introspection.add_object(&mutation);
+        introspection
+    }
+
+    fn add_object(&mut self, object: &ObjectType) {
+        if self.types.contains_key(object.name()) {
+            return;
+        }
+
+        // Registered before visiting the fields, which can reference the object recursively.
+        self.types
+            .insert(object.name().to_owned(), TypeInfo::new(TypeKind::Object));
+
+        let mut fields = Vec::with_capacity(object.get_fields().len());
+
+        for field in object.get_fields() {
+            let mut args = Vec::with_capacity(field.arguments.len());
+
+            for arg in field.arguments.iter() {
+                args.push(InputValueInfo {
+                    name: arg.name.clone(),
+                    type_ref: self.input_type_ref(&arg.argument_type),
+                });
+            }
+
+            fields.push(FieldInfo {
+                name: field.name.clone(),
+                args,
+                type_ref: self.output_type_ref(&field.field_type),
+            });
+        }
+
+        self.types[object.name()].fields = fields;
+    }
+
+    fn add_input_object(&mut self, object: &InputObjectType) {
+        if self.types.contains_key(&object.name) {
+            return;
+        }
+
+        self.types
+            .insert(object.name.clone(), TypeInfo::new(TypeKind::InputObject));
+
+        let mut input_fields = Vec::with_capacity(object.get_fields().len());
+
+        for field in object.get_fields() {
+            input_fields.push(InputValueInfo {
+                name: field.name.clone(),
+                type_ref: self.input_type_ref(&field.field_type),
+            });
+        }
+
+        self.types[&object.name].input_fields = input_fields;
+    }
+
+    fn add_enum(&mut self, enum_type: &EnumType) {
+        if self.types.contains_key(enum_type.name()) {
+            return;
+        }
+
+        let mut info = TypeInfo::new(TypeKind::Enum);
+
+        info.enum_values = match enum_type {
+            EnumType::Internal(i) => i.values.clone(),
+            EnumType::OrderBy(ord) => ord.values.iter().map(|(name, _)| name.to_owned()).collect(),
+        };
+
+        self.types.insert(enum_type.name().to_owned(), info);
+    }
+
+    fn output_type_ref(&mut self, output_type: &OutputType) -> TypeRef {
+        match output_type {
+            OutputType::Opt(inner) => self.output_type_ref(inner).optional(),
+            OutputType::List(inner) => TypeRef::required_list(self.output_type_ref(inner)),
+            OutputType::Object(obj) => {
+                let obj = obj.into_arc();
+
+                self.add_object(&obj);
+                TypeRef::required(obj.name())
+            }
+            OutputType::Enum(et) | OutputType::Scalar(ScalarType::Enum(et)) => {
+                self.add_enum(et);
+                TypeRef::required(et.name())
+            }
+            OutputType::Scalar(scalar) => TypeRef::required(scalar_name(scalar)),
+        }
+    }
+
+    fn input_type_ref(&mut self, input_type: &InputType) -> TypeRef {
+        match input_type {
+            InputType::Opt(inner) => self.input_type_ref(inner).optional(),
+            InputType::List(inner) => TypeRef::required_list(self.input_type_ref(inner)),
+            InputType::Object(obj) => {
+                let obj = obj.into_arc();
+
+                self.add_input_object(&obj);
+                TypeRef::required(&obj.name)
+            }
+            InputType::Enum(et) | InputType::Scalar(ScalarType::Enum(et)) => {
+                self.add_enum(et);
+                TypeRef::required(et.name())
+            }
+            InputType::Scalar(scalar) => TypeRef::required(scalar_name(scalar)),
+        }
+    }
+
+    fn resolve_root_field(&self, field: &Field) -> PrismaResult<Value> {
+        match field.name.as_str() {
+            "__typename" => Ok(json!(self.query_type)),
+            "__schema" => self.resolve_schema(&field.selection_set),
+            "__type" => {
+                let name = field.arguments.iter().find_map(|(arg, value)| match value {
+                    GqlValue::String(name) if arg == "name" => Some(name),
+                    _ => None,
+                });
+
+                match name {
+                    Some(name) if self.types.contains_key(name) => {
+                        self.resolve_type(&TypeRef::Named(name.clone()), &field.selection_set)
+                    }
+                    Some(_) => Ok(Value::Null),
+                    None => Err(PrismaError::QueryConversionError(
+                        "Field '__type' requires a string argument 'name'.".to_owned(),
+                    )),
+                }
+            }
+            name => Err(unknown_field(name, &self.query_type)),
+        }
+    }
+
+    fn resolve_schema(&self, selection_set: &SelectionSet) -> PrismaResult<Value> {
+        self.resolve_object(selection_set, "__Schema", |field| match field.name.as_str() {
+            "description" | "subscriptionType" => Ok(Value::Null),
+            "directives" => Ok(json!([])),
+            "queryType" => self.resolve_type(&TypeRef::Named(self.query_type.clone()), &field.selection_set),
+            "mutationType" => self.resolve_type(&TypeRef::Named(self.mutation_type.clone()), &field.selection_set),
+            "types" => self
+                .types
+                .keys()
+                .map(|name| self.resolve_type(&TypeRef::Named(name.clone()), &field.selection_set))
+                .collect::<PrismaResult<Vec<_>>>()
+                .map(Value::Array),
+            name => Err(unknown_field(name, "__Schema")),
+        })
+    }
+
+    fn resolve_type(&self, type_ref: &TypeRef, selection_set: &SelectionSet) -> PrismaResult<Value> {
+        let (kind, name, info, of_type) = match type_ref {
+            TypeRef::Named(name) => (
+                self.types[name].kind.as_str(),
+                Some(name),
+                Some(&self.types[name]),
+                None,
+            ),
+            TypeRef::List(inner) => ("LIST", None, None, Some(inner)),
+            TypeRef::NonNull(inner) => ("NON_NULL", None, None, Some(inner)),
+        };
+
+        let is = |expected: TypeKind| info.filter(|info| info.kind == expected);
+
+        self.resolve_object(selection_set, "__Type", |field| match field.name.as_str() {
+            "kind" => Ok(json!(kind)),
+            "name" => Ok(json!(name)),
+            "description" | "specifiedByUrl" | "possibleTypes" => Ok(Value::Null),
+            "interfaces" => Ok(is(TypeKind::Object).map(|_| json!([])).unwrap_or(Value::Null)),
+            "fields" => match is(TypeKind::Object) {
+                Some(info) => self.resolve_list(&info.fields, |f| self.resolve_field(f, &field.selection_set)),
+                None => Ok(Value::Null),
+            },
+            "inputFields" => match is(TypeKind::InputObject) {
+                Some(info) => self.resolve_list(&info.input_fields, |f| {
+                    self.resolve_input_value(f, &field.selection_set)
+                }),
+                None => Ok(Value::Null),
+            },
+            "enumValues" => match is(TypeKind::Enum) {
+                Some(info) => {
+                    self.resolve_list(&info.enum_values, |v| self.resolve_enum_value(v, &field.selection_set))
+                }
+                None => Ok(Value::Null),
+            },
+            "ofType" => match of_type {
+                Some(inner) => self.resolve_type(inner, &field.selection_set),
+                None => Ok(Value::Null),
+            },
+            name => Err(unknown_field(name, "__Type")),
+        })
+    }
+
+    fn resolve_field(&self, info: &FieldInfo, selection_set: &SelectionSet) -> PrismaResult<Value> {
+        self.resolve_object(selection_set, "__Field", |field| match field.name.as_str() {
+            "name" => Ok(json!(info.name)),
+            "description" | "deprecationReason" => Ok(Value::Null),
+            "isDeprecated" => Ok(json!(false)),
+            "args" => self.resolve_list(&info.args, |arg| self.resolve_input_value(arg, &field.selection_set)),
+            "type" => self.resolve_type(&info.type_ref, &field.selection_set),
+            name => Err(unknown_field(name, "__Field")),
+        })
+    }
+
+    fn resolve_input_value(&self, info: &InputValueInfo, selection_set: &SelectionSet) -> PrismaResult<Value> {
+        self.resolve_object(selection_set, "__InputValue", |field| match field.name.as_str() {
+            "name" => Ok(json!(info.name)),
+            "description" | "defaultValue" => Ok(Value::Null),
+            "type" => self.resolve_type(&info.type_ref, &field.selection_set),
+            name => Err(unknown_field(name, "__InputValue")),
+        })
+    }
+
+    fn resolve_enum_value(&self, value: &str, selection_set: &SelectionSet) -> PrismaResult<Value> {
+        self.resolve_object(selection_set, "__EnumValue", |field| match field.name.as_str() {
+            "name" => Ok(json!(value)),
+            "description" | "deprecationReason" => Ok(Value::Null),
+            "isDeprecated" => Ok(json!(false)),
+            name => Err(unknown_field(name, "__EnumValue")),
+        })
+    }
+
+    fn resolve_list<T, F>(&self, items: &[T], resolve_item: F) -> PrismaResult<Value>
+    where
+        F: Fn(&T) -> PrismaResult<Value>,
+    {
+        items
+            .iter()
+            .map(resolve_item)
+            .collect::<PrismaResult<Vec<_>>>()
+            .map(Value::Array)
+    }
+
+    /// Resolves the selected fields of an introspection object, `__typename` is available on all of them.
+    fn resolve_object<F>(&self, selection_set: &SelectionSet, type_name: &str, resolve_field: F) -> PrismaResult<Value>
+    where
+        F: Fn(&Field) -> PrismaResult<Value>,
+    {
+        let mut fields = vec![];
+        self.collect_fields(selection_set, &mut fields)?;
+
+        let mut map = Map::with_capacity(fields.len());
+
+        for field in fields {
+            let key = field.alias.clone().unwrap_or_else(|| field.name.clone());
+
+            let value = if field.name == "__typename" {
+                json!(type_name)
+            } else {
+                resolve_field(field)?
+            };
+
+            map.insert(key, value);
+        }
+
+        Ok(Value::Object(map))
+    }
+
+    /// Flattens fragment spreads and inline fragments into the fields they select.
+    fn collect_fields<'a>(&'a self, selection_set: &'a SelectionSet, fields: &mut Vec<&'a Field>) -> PrismaResult<()> {
+        for item in selection_set.items.iter() {
+            match item {
+                Selection::Field(f) => fields.push(f),
+                Selection::InlineFragment(i) => self.collect_fields(&i.selection_set, fields)?,
+                Selection::FragmentSpread(fs) => match self.fragments.get(&fs.fragment_name) {
+                    Some(fragment) => self.collect_fields(&fragment.selection_set, fields)?,
+                    None => {
+                        return Err(PrismaError::QueryConversionError(format!(
+                            "Unknown fragment '{}'.",
+                            fs.fragment_name
+                        )))
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn scalar_name(scalar: &ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::String => "String",
+        ScalarType::Int => "Int",
+        ScalarType::Float => "Float",
+        ScalarType::Decimal => "Decimal",
+        ScalarType::Boolean => "Boolean",
+        ScalarType::DateTime => "DateTime",
+        ScalarType::Json | ScalarType::JsonList => "Json",
+        ScalarType::UUID => "UUID",
+        ScalarType::Enum(et) => unreachable!("Enum {} is handled separately.", et.name()),
+    }
+}
+
+fn unknown_field(name: &str, type_name: &str) -> PrismaError {
+    PrismaError::QueryConversionError(format!("Field '{}' does not exist on type '{}'.", name, type_name))
+}
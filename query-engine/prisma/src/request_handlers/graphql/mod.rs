@@ -1,7 +1,14 @@
 mod handler;
+mod incremental;
+pub mod introspection;
+mod persisted_queries;
 mod protocol_adapter;
+mod response;
 mod schema_renderer;
 
 pub use handler::*;
+pub use incremental::*;
+pub use persisted_queries::*;
 pub use protocol_adapter::*;
+pub use response::*;
 pub use schema_renderer::*;
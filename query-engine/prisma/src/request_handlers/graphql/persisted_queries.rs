@@ -0,0 +1,98 @@
+use super::handler::SingleQuery;
+use crate::{PrismaError, PrismaResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, fs::File};
+
+/// Request referencing a registered operation document by its hash instead of sending the document.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedQuery {
+    id: String,
+    #[serde(default)]
+    variables: HashMap<String, serde_json::Value>,
+}
+
+/// Where the document of a request comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentOrigin {
+    /// Sent along with the request.
+    AdHoc,
+    /// Registered in the `PersistedQueries` and referenced by its hash.
+    Persisted,
+}
+
+/// Documents sent by clients are ad-hoc, unless they are resolved from the registry.
+impl Default for DocumentOrigin {
+    fn default() -> Self {
+        DocumentOrigin::AdHoc
+    }
+}
+
+/// Registry of operation documents, keyed by the hex encoded SHA-256 hash of the document.
+///
+/// If enforced, only registered documents can be executed, which locks down production deployments.
+#[derive(Debug, Default)]
+pub struct PersistedQueries {
+    queries: HashMap<String, String>,
+    enforced: bool,
+}
+
+impl PersistedQueries {
+    /// Loads the operation documents from a JSON file containing an array of GraphQL documents.
+    pub fn load(path: &str, enforced: bool) -> PrismaResult<Self> {
+        let file = File::open(path)?;
+        let documents: Vec<String> = serde_json::from_reader(file)?;
+        let persisted_queries = Self::new(documents, enforced);
+
+        info!("Loaded {} persisted queries.", persisted_queries.queries.len());
+
+        Ok(persisted_queries)
+    }
+
+    pub fn new(documents: Vec<String>, enforced: bool) -> Self {
+        let queries = documents
+            .into_iter()
+            .map(|document| (Self::hash(&document), document))
+            .collect();
+
+        Self { queries, enforced }
+    }
+
+    pub fn hash(document: &str) -> String {
+        format!("{:x}", Sha256::digest(document.as_bytes()))
+    }
+
+    /// Checks that an ad-hoc query is allowed to be executed.
+    pub fn check_ad_hoc(&self, query: SingleQuery) -> PrismaResult<SingleQuery> {
+        self.check(DocumentOrigin::AdHoc).map(|_| query)
+    }
+
+    /// Checks that a document of the given origin is allowed to be executed, which all documents are checked with
+    /// before they are executed, whatever protocol they were sent with.
+    pub fn check(&self, origin: DocumentOrigin) -> PrismaResult<()> {
+        if self.enforced && origin == DocumentOrigin::AdHoc {
+            Err(PrismaError::InvocationError(
+                "Only persisted queries are allowed, send the hash of a registered query as `id`.".to_owned(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Looks up the document of a persisted query.
+    pub fn resolve(&self, query: PersistedQuery) -> PrismaResult<SingleQuery> {
+        match self.queries.get(&query.id) {
+            Some(document) => {
+                let mut query = SingleQuery::new(document.clone(), None, query.variables);
+                query.origin = DocumentOrigin::Persisted;
+
+                Ok(query)
+            }
+            None => Err(PrismaError::InvocationError(format!(
+                "No persisted query registered for id '{}'.",
+                query.id
+            ))),
+        }
+    }
+}
@@ -1,9 +1,17 @@
 use crate::{error::PrismaError, PrismaResult};
-use graphql_parser::query::{
-    Definition, Document, OperationDefinition, Selection as GqlSelection, SelectionSet, Value,
+use graphql_parser::{
+    query::{
+        Definition, Directive, Document, FragmentDefinition, OperationDefinition, Selection as GqlSelection,
+        SelectionSet, Value,
+    },
+    Pos,
 };
 use query_core::query_document::*;
-use std::collections::BTreeMap;
+use serde_json::Value as JsonValue;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+};
 
 /// Protocol adapter for GraphQL -> Query Document.
 ///
@@ -13,16 +21,93 @@ use std::collections::BTreeMap;
 /// - If the JSON payload specifies an operation name, only that specific operation is picked and the rest ignored.
 /// - Fields on the queries are mapped to `Field`s, including arguments.
 /// - Concrete values (e.g. in arguments) are mapped to `QueryValue`s.
+/// - Fragment spreads and inline fragments are spread into the selection sets they're used in, fields selected more
+///   than once under the same response key are merged.
+/// - Selections excluded by `@skip` or `@include` are left out.
+/// - Fields and fragments nested in the fields of a query and marked with `@defer` are left out and returned as
+///   `DeferredSelection`s by `convert_deferred`, `convert` selects them along with the rest.
 ///
 /// Currently unsupported features:
-/// - Fragments in any form.
-/// - Variables.
-/// - Subscription queries.
+/// - Variables, except as the condition of `@skip` and `@include`.
+/// - Subscription queries (only supported over the subscriptions endpoint, see `convert_subscription`).
 /// - Query names are ignored
 pub struct GraphQLProtocolAdapter;
 
+/// The fragment definitions and variables of a document, which the selection sets are normalized with.
+#[derive(Clone, Copy)]
+struct DocumentContext<'a> {
+    fragments: &'a HashMap<String, FragmentDefinition>,
+    variables: &'a HashMap<String, JsonValue>,
+    /// Collects the selections marked with `@defer`, `None` if they are selected along with the rest.
+    deferred: Option<&'a RefCell<Vec<DeferredSelection>>>,
+}
+
+/// A selection marked with `@defer`, the label identifies its payloads to the client.
+struct Deferral {
+    label: Option<String>,
+}
+
+impl<'a> DocumentContext<'a> {
+    fn without_deferral(&self) -> Self {
+        Self {
+            deferred: None,
+            ..*self
+        }
+    }
+
+    fn defer(&self, path: &[String], label: Option<String>, selections: Vec<Selection>) {
+        if let Some(deferred) = self.deferred {
+            deferred.borrow_mut().push(DeferredSelection {
+                path: path.to_vec(),
+                label,
+                selections,
+            });
+        }
+    }
+}
+
 impl GraphQLProtocolAdapter {
-    pub fn convert(gql_doc: Document, operation: Option<String>) -> PrismaResult<QueryDocument> {
+    pub fn convert(
+        gql_doc: Document,
+        operation: Option<String>,
+        variables: &HashMap<String, JsonValue>,
+    ) -> PrismaResult<QueryDocument> {
+        Self::convert_with(gql_doc, operation, variables, None)
+    }
+
+    /// Converts the document, leaving out the selections marked with `@defer`, which are returned separately.
+    pub fn convert_deferred(
+        gql_doc: Document,
+        operation: Option<String>,
+        variables: &HashMap<String, JsonValue>,
+    ) -> PrismaResult<(QueryDocument, Vec<DeferredSelection>)> {
+        let deferred = RefCell::new(vec![]);
+        let query_doc = Self::convert_with(gql_doc, operation, variables, Some(&deferred))?;
+
+        Ok((query_doc, deferred.into_inner()))
+    }
+
+    fn convert_with(
+        gql_doc: Document,
+        operation: Option<String>,
+        variables: &HashMap<String, JsonValue>,
+        deferred: Option<&RefCell<Vec<DeferredSelection>>>,
+    ) -> PrismaResult<QueryDocument> {
+        let fragments: HashMap<String, FragmentDefinition> = gql_doc
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Fragment(f) => Some((f.name.clone(), f.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let ctx = DocumentContext {
+            fragments: &fragments,
+            variables,
+            deferred,
+        };
+
         let operations: Vec<Operation> = match operation {
             Some(ref op) => gql_doc
                 .definitions
@@ -31,12 +116,12 @@ impl GraphQLProtocolAdapter {
                 .ok_or_else(|| {
                     PrismaError::QueryConversionError(format!("Operation '{}' does not match any query.", op))
                 })
-                .and_then(Self::convert_definition),
+                .and_then(|def| Self::convert_definition(def, &ctx)),
 
             None => gql_doc
                 .definitions
                 .into_iter()
-                .map(Self::convert_definition)
+                .map(|def| Self::convert_definition(def, &ctx))
                 .collect::<PrismaResult<Vec<Vec<Operation>>>>()
                 .map(|r| r.into_iter().flatten().collect::<Vec<Operation>>()),
         }?;
@@ -44,31 +129,79 @@ impl GraphQLProtocolAdapter {
         Ok(QueryDocument { operations }.dedup_operations())
     }
 
-    fn convert_definition(def: Definition) -> PrismaResult<Vec<Operation>> {
-        match def {
-            Definition::Fragment(f) => Err(PrismaError::UnsupportedFeatureError(
-                "Fragment definition",
-                format!("Fragment '{}', at position {}.", f.name, f.position),
+    /// Extracts the names of the models to subscribe to from a `subscription { ... }` document.
+    /// Every top-level field of the subscription designates one model, e.g. `subscription { User Post }`.
+    pub fn convert_subscription(gql_doc: Document) -> PrismaResult<Vec<String>> {
+        let subscription = gql_doc.definitions.into_iter().find_map(|def| match def {
+            Definition::Operation(OperationDefinition::Subscription(s)) => Some(s),
+            _ => None,
+        });
+
+        match subscription {
+            Some(s) => {
+                let position = s.position;
+
+                s.selection_set
+                    .items
+                    .into_iter()
+                    .map(|item| match item {
+                        GqlSelection::Field(f) => Ok(f.name),
+                        _ => Err(PrismaError::UnsupportedFeatureError(
+                            "Fragments in subscriptions",
+                            format!("At position {}.", position),
+                        )),
+                    })
+                    .collect()
+            }
+            None => Err(PrismaError::QueryConversionError(
+                "Expected a subscription operation.".to_owned(),
             )),
+        }
+    }
+
+    /// Positions of the top-level fields of all operations, keyed by their response key (alias or name).
+    pub fn root_field_positions(gql_doc: &Document) -> HashMap<String, Pos> {
+        gql_doc
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Operation(OperationDefinition::SelectionSet(s)) => Some(s),
+                Definition::Operation(OperationDefinition::Query(q)) => Some(&q.selection_set),
+                Definition::Operation(OperationDefinition::Mutation(m)) => Some(&m.selection_set),
+                _ => None,
+            })
+            .flat_map(|selection_set| selection_set.items.iter())
+            .filter_map(|item| match item {
+                GqlSelection::Field(f) => Some((f.alias.clone().unwrap_or_else(|| f.name.clone()), f.position)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn convert_definition(def: Definition, ctx: &DocumentContext) -> PrismaResult<Vec<Operation>> {
+        match def {
+            // Fragments are spread into the selection sets that use them.
+            Definition::Fragment(_) => Ok(vec![]),
             Definition::Operation(op) => match op {
                 OperationDefinition::Subscription(s) => Err(PrismaError::UnsupportedFeatureError(
                     "Subscription query",
                     format!("At position {}.", s.position),
                 )),
-                OperationDefinition::SelectionSet(s) => Self::convert_query(s),
-                OperationDefinition::Query(q) => Self::convert_query(q.selection_set),
-                OperationDefinition::Mutation(m) => Self::convert_mutation(m.selection_set),
+                OperationDefinition::SelectionSet(s) => Self::convert_query(s, ctx),
+                OperationDefinition::Query(q) => Self::convert_query(q.selection_set, ctx),
+                OperationDefinition::Mutation(m) => Self::convert_mutation(m.selection_set, ctx),
             },
         }
     }
 
-    fn convert_query(selection_set: SelectionSet) -> PrismaResult<Vec<Operation>> {
-        Self::convert_selection_set(selection_set)
+    fn convert_query(selection_set: SelectionSet, ctx: &DocumentContext) -> PrismaResult<Vec<Operation>> {
+        Self::convert_selection_set(selection_set, ctx, &[], &mut vec![])
             .map(|fields| fields.into_iter().map(|field| Operation::Read(field)).collect())
     }
 
-    fn convert_mutation(selection_set: SelectionSet) -> PrismaResult<Vec<Operation>> {
-        Self::convert_selection_set(selection_set).map(|fields| {
+    /// Selections of mutations are never deferred, reading them later would execute the mutation again.
+    fn convert_mutation(selection_set: SelectionSet, ctx: &DocumentContext) -> PrismaResult<Vec<Operation>> {
+        Self::convert_selection_set(selection_set, &ctx.without_deferral(), &[], &mut vec![]).map(|fields| {
             fields
                 .into_iter()
                 .map(|selection| Operation::Write(selection))
@@ -76,37 +209,218 @@ impl GraphQLProtocolAdapter {
         })
     }
 
-    fn convert_selection_set(selection_set: SelectionSet) -> PrismaResult<Vec<Selection>> {
-        selection_set
-            .items
-            .into_iter()
-            .map(|item| match item {
+    /// `path` are the response keys of the fields the selection set is nested in. `spreads` are the names of the
+    /// fragments currently being spread, to reject fragments spreading themselves.
+    fn convert_selection_set(
+        selection_set: SelectionSet,
+        ctx: &DocumentContext,
+        path: &[String],
+        spreads: &mut Vec<String>,
+    ) -> PrismaResult<Vec<Selection>> {
+        let mut selections = vec![];
+        Self::collect_selections(selection_set, ctx, path, spreads, &mut selections)?;
+
+        Self::merge_selections(selections)
+    }
+
+    /// Converts the fields of the selection set and of the fragments spread into it, leaving out the ones excluded
+    /// by `@skip` or `@include`. Deferred selections are collected into the context instead.
+    fn collect_selections(
+        selection_set: SelectionSet,
+        ctx: &DocumentContext,
+        path: &[String],
+        spreads: &mut Vec<String>,
+        selections: &mut Vec<Selection>,
+    ) -> PrismaResult<()> {
+        for item in selection_set.items {
+            match item {
                 GqlSelection::Field(f) => {
+                    if !Self::is_included(&f.directives, ctx)? {
+                        continue;
+                    }
+
+                    let deferral = Self::deferral(&f.directives, ctx, path)?;
                     let arguments: Vec<(String, QueryValue)> = f
                         .arguments
                         .into_iter()
                         .map(|(k, v)| Ok((k, Self::convert_value(v)?)))
                         .collect::<PrismaResult<Vec<_>>>()?;
 
-                    Ok(Selection {
+                    let mut nested_path = path.to_vec();
+                    nested_path.push(f.alias.clone().unwrap_or_else(|| f.name.clone()));
+
+                    // Deferred fields are read with everything nested in them.
+                    let nested_ctx = if deferral.is_some() {
+                        ctx.without_deferral()
+                    } else {
+                        *ctx
+                    };
+                    let selection = Selection {
                         name: f.name,
                         alias: f.alias,
                         arguments,
-                        nested_selections: Self::convert_selection_set(f.selection_set)?,
-                    })
+                        nested_selections: Self::convert_selection_set(
+                            f.selection_set,
+                            &nested_ctx,
+                            &nested_path,
+                            spreads,
+                        )?,
+                    };
+
+                    match deferral {
+                        Some(deferral) => ctx.defer(path, deferral.label, vec![selection]),
+                        None => selections.push(selection),
+                    }
                 }
 
-                GqlSelection::FragmentSpread(fs) => Err(PrismaError::UnsupportedFeatureError(
-                    "Fragment spread",
-                    format!("Fragment '{}', at position {}.", fs.fragment_name, fs.position),
-                )),
+                GqlSelection::FragmentSpread(fs) => {
+                    if !Self::is_included(&fs.directives, ctx)? {
+                        continue;
+                    }
 
-                GqlSelection::InlineFragment(i) => Err(PrismaError::UnsupportedFeatureError(
-                    "Inline fragment",
-                    format!("At position {}.", i.position),
-                )),
-            })
-            .collect()
+                    if spreads.contains(&fs.fragment_name) {
+                        return Err(PrismaError::QueryConversionError(format!(
+                            "Fragment '{}' spreads itself, at position {}.",
+                            fs.fragment_name, fs.position
+                        )));
+                    }
+
+                    let fragment = ctx.fragments.get(&fs.fragment_name).ok_or_else(|| {
+                        PrismaError::QueryConversionError(format!(
+                            "Unknown fragment '{}', at position {}.",
+                            fs.fragment_name, fs.position
+                        ))
+                    })?;
+
+                    let deferral = Self::deferral(&fs.directives, ctx, path)?;
+
+                    spreads.push(fs.fragment_name);
+                    Self::collect_fragment(fragment.selection_set.clone(), ctx, deferral, path, spreads, selections)?;
+                    spreads.pop();
+                }
+
+                GqlSelection::InlineFragment(i) => {
+                    if Self::is_included(&i.directives, ctx)? {
+                        let deferral = Self::deferral(&i.directives, ctx, path)?;
+                        Self::collect_fragment(i.selection_set, ctx, deferral, path, spreads, selections)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects the selections of a fragment, or defers them together if the fragment is deferred.
+    fn collect_fragment(
+        selection_set: SelectionSet,
+        ctx: &DocumentContext,
+        deferral: Option<Deferral>,
+        path: &[String],
+        spreads: &mut Vec<String>,
+        selections: &mut Vec<Selection>,
+    ) -> PrismaResult<()> {
+        match deferral {
+            Some(deferral) => {
+                let mut deferred = vec![];
+                Self::collect_selections(selection_set, &ctx.without_deferral(), path, spreads, &mut deferred)?;
+                ctx.defer(path, deferral.label, Self::merge_selections(deferred)?);
+
+                Ok(())
+            }
+            None => Self::collect_selections(selection_set, ctx, path, spreads, selections),
+        }
+    }
+
+    /// Merges fields selected more than once under the same response key, e.g. by a field and a fragment, into the
+    /// first of them. They must select the same field with the same arguments.
+    fn merge_selections(selections: Vec<Selection>) -> PrismaResult<Vec<Selection>> {
+        let mut merged: Vec<Selection> = Vec::with_capacity(selections.len());
+
+        for selection in selections {
+            match merged
+                .iter_mut()
+                .find(|existing| existing.response_key() == selection.response_key())
+            {
+                Some(existing) => {
+                    if existing.name != selection.name || existing.arguments != selection.arguments {
+                        return Err(PrismaError::QueryConversionError(format!(
+                            "Fields selected as '{}' conflict, they select different fields or arguments.",
+                            selection.response_key()
+                        )));
+                    }
+
+                    let nested = std::mem::replace(&mut existing.nested_selections, vec![]);
+                    existing.nested_selections =
+                        Self::merge_selections(nested.into_iter().chain(selection.nested_selections).collect())?;
+                }
+                None => merged.push(selection),
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Evaluates the `@skip` and `@include` directives of a selection, other directives are ignored.
+    fn is_included(directives: &[Directive], ctx: &DocumentContext) -> PrismaResult<bool> {
+        for directive in directives {
+            let included = match directive.name.as_str() {
+                "skip" => !Self::directive_condition(directive, ctx)?,
+                "include" => Self::directive_condition(directive, ctx)?,
+                _ => true,
+            };
+
+            if !included {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Evaluates the `@defer` directive of a selection, `Some` if the selection is deferred.
+    /// Only selections nested in fields can be deferred, and only if the context collects deferred selections.
+    fn deferral(directives: &[Directive], ctx: &DocumentContext, path: &[String]) -> PrismaResult<Option<Deferral>> {
+        let directive = match directives.iter().find(|d| d.name == "defer") {
+            Some(directive) if ctx.deferred.is_some() && !path.is_empty() => directive,
+            _ => return Ok(None),
+        };
+
+        let has_condition = directive.arguments.iter().any(|(name, _)| name == "if");
+        if has_condition && !Self::directive_condition(directive, ctx)? {
+            return Ok(None);
+        }
+
+        let label = directive.arguments.iter().find_map(|(name, value)| match value {
+            Value::String(label) if name == "label" => Some(label.clone()),
+            _ => None,
+        });
+
+        Ok(Some(Deferral { label }))
+    }
+
+    /// The `if` argument of a directive, a Boolean or a variable holding one.
+    fn directive_condition(directive: &Directive, ctx: &DocumentContext) -> PrismaResult<bool> {
+        let condition = directive
+            .arguments
+            .iter()
+            .find(|(name, _)| name == "if")
+            .map(|(_, value)| value);
+
+        match condition {
+            Some(Value::Boolean(b)) => Ok(*b),
+            Some(Value::Variable(name)) => match ctx.variables.get(name) {
+                Some(JsonValue::Bool(b)) => Ok(*b),
+                _ => Err(PrismaError::QueryConversionError(format!(
+                    "Variable '{}' of directive '@{}' must be a Boolean.",
+                    name, directive.name
+                ))),
+            },
+            _ => Err(PrismaError::QueryConversionError(format!(
+                "Directive '@{}' requires the Boolean argument 'if', at position {}.",
+                directive.name, directive.position
+            ))),
+        }
     }
 
     /// Checks if the given GraphQL definition matches the operation name that should be executed.
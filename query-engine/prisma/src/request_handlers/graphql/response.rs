@@ -0,0 +1,112 @@
+use graphql_parser::Pos;
+use prisma_models::PrismaValue;
+use query_core::response_ir::{Item, Map, ResponseError, Responses};
+use serde::Serialize;
+
+/// Error code for errors that don't have a Prisma error code.
+const UNKNOWN_ERROR_CODE: &str = "INTERNAL_SERVER_ERROR";
+
+/// Response following the GraphQL specification (https://spec.graphql.org/June2018/#sec-Response-Format).
+///
+/// In contrast to `Responses`, every error carries the path of the operation it occurred in, or of the field whose
+/// data failed to load, and the data of successful operations is returned alongside the errors of failed ones.
+#[derive(Debug, Default, Serialize)]
+pub struct GraphQlResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Map>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GraphQlError {
+    message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    path: Vec<String>,
+    extensions: Extensions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Location {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Extensions {
+    code: String,
+    user_facing_error: user_facing_errors::Error,
+}
+
+impl GraphQlResponse {
+    /// A response for a request that failed before any operation was executed, e.g. a syntax error.
+    /// Per spec, such responses don't contain a `data` entry.
+    pub fn request_error(error: impl Into<ResponseError>) -> Self {
+        Self {
+            data: None,
+            errors: vec![GraphQlError::new(error.into(), Vec::new(), None)],
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Adds the result of executing the operation with the given response key.
+    pub fn insert_operation_result(&mut self, key: String, position: Option<Pos>, responses: Responses) {
+        let (data, errors) = responses.into_parts();
+        let data_map = self.data.get_or_insert_with(Map::new);
+
+        // Errors with a path belong to fields of partially returned data.
+        if errors.iter().all(|error| !error.path().is_empty()) {
+            data_map.extend(data);
+        } else {
+            data_map.insert(key.clone(), Item::Value(PrismaValue::Null));
+        }
+
+        for error in errors {
+            let path = if error.path().is_empty() {
+                vec![key.clone()]
+            } else {
+                error.path().to_vec()
+            };
+
+            self.errors.push(GraphQlError::new(error, path, position));
+        }
+    }
+
+    /// Records the failure of the operation with the given response key, the field resolves to `null`.
+    pub fn insert_operation_error(&mut self, key: String, position: Option<Pos>, error: impl Into<ResponseError>) {
+        self.data
+            .get_or_insert_with(Map::new)
+            .insert(key.clone(), Item::Value(PrismaValue::Null));
+
+        self.errors.push(GraphQlError::new(error.into(), vec![key], position));
+    }
+}
+
+impl GraphQlError {
+    fn new(error: ResponseError, path: Vec<String>, position: Option<Pos>) -> Self {
+        let message = error.message().to_owned();
+        let user_facing_error = error.into_user_facing_error();
+        let code = user_facing_error.error_code().unwrap_or(UNKNOWN_ERROR_CODE).to_owned();
+
+        Self {
+            message,
+            locations: position
+                .map(|pos| Location {
+                    line: pos.line,
+                    column: pos.column,
+                })
+                .into_iter()
+                .collect(),
+            path,
+            extensions: Extensions {
+                code,
+                user_facing_error,
+            },
+        }
+    }
+}
@@ -51,6 +51,7 @@ impl<'a> GqlTypeRenderer<'a> {
                     ScalarType::Int => "Int",
                     ScalarType::Boolean => "Boolean",
                     ScalarType::Float => "Float",
+                    ScalarType::Decimal => "Decimal",
                     ScalarType::DateTime => "DateTime",
                     ScalarType::Json => "DateTime",
                     ScalarType::UUID => "UUID",
@@ -92,6 +93,7 @@ impl<'a> GqlTypeRenderer<'a> {
                     ScalarType::Int => "Int",
                     ScalarType::Boolean => "Boolean",
                     ScalarType::Float => "Float",
+                    ScalarType::Decimal => "Decimal",
                     ScalarType::DateTime => "DateTime",
                     ScalarType::Json => "Json",
                     ScalarType::UUID => "UUID",
@@ -0,0 +1,342 @@
+use super::{
+    proto::{
+        self,
+        query_engine_server::{QueryEngine, QueryEngineServer},
+    },
+    GrpcProtocolAdapter,
+};
+use crate::{
+    auth::{Access, Authenticator},
+    context::PrismaContext,
+    request_handlers::{execute, execute_batch, DocumentOrigin},
+    shutdown::Drain,
+    PrismaError, PrismaResponse, PrismaResult,
+};
+use futures::{channel::oneshot, future, FutureExt};
+use query_core::{
+    response_ir, transaction_channel, CoreResult, QueryDocument, TransactionRequest, TransactionSendError,
+    TransactionSender,
+};
+use std::{
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tonic::{Request, Response, Status};
+use user_facing_errors::Error;
+
+/// Time after which transactions are rolled back if the client didn't give a timeout.
+const DEFAULT_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest timeout clients can give transactions, which hold a connection of the pool while they are open.
+const MAX_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The time after which a transaction with the timeout of the client is rolled back.
+pub fn transaction_timeout(timeout_ms: u32) -> Duration {
+    match timeout_ms {
+        0 => DEFAULT_TRANSACTION_TIMEOUT,
+        ms => std::cmp::min(Duration::from_millis(ms.into()), MAX_TRANSACTION_TIMEOUT),
+    }
+}
+
+/// Request handler for gRPC clients, serving the `QueryEngine` service of `proto/query_engine.proto`.
+/// Requests are authenticated with the bearer token of their `authorization` metadata.
+pub struct GrpcRequestHandler {
+    ctx: Arc<PrismaContext>,
+    authenticator: Arc<Authenticator>,
+    /// The open transactions by id. Removing a transaction closes its channel, which rolls it back unless it was
+    /// committed.
+    transactions: Arc<Mutex<HashMap<String, TransactionSender>>>,
+    /// Open transactions are rolled back when the server shuts down.
+    drain: Drain,
+}
+
+impl GrpcRequestHandler {
+    pub fn new(ctx: Arc<PrismaContext>, authenticator: Arc<Authenticator>, drain: Drain) -> Self {
+        Self {
+            ctx,
+            authenticator,
+            transactions: Arc::new(Mutex::new(HashMap::new())),
+            drain,
+        }
+    }
+
+    pub fn into_service(self) -> QueryEngineServer<Self> {
+        QueryEngineServer::new(self)
+    }
+
+    async fn authenticate<T>(&self, request: &Request<T>) -> Result<Access, Status> {
+        let authorization = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok());
+
+        self.authenticator
+            .authenticate_authorization(authorization)
+            .await
+            .map_err(|err| Status::unauthenticated(err.to_string()))
+    }
+
+    fn transaction(&self, id: &str) -> Result<TransactionSender, Status> {
+        self.transactions
+            .lock()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| transaction_not_found(id))
+    }
+
+    /// Sends the request to end the transaction, which is removed from the open transactions either way.
+    async fn end_transaction<F>(&self, id: &str, request: F) -> Result<Response<proto::Empty>, Status>
+    where
+        F: FnOnce(oneshot::Sender<CoreResult<()>>) -> TransactionRequest,
+    {
+        let sender = self
+            .transactions
+            .lock()
+            .unwrap()
+            .remove(id)
+            .ok_or_else(|| transaction_not_found(id))?;
+
+        let (reply, result) = oneshot::channel();
+
+        sender.send(request(reply)).map_err(|err| match err {
+            TransactionSendError::Busy => transaction_busy(id),
+            TransactionSendError::Closed => transaction_not_found(id),
+        })?;
+
+        match result.await {
+            Ok(Ok(())) => Ok(Response::new(proto::Empty {})),
+            Ok(Err(err)) => Err(Status::aborted(err.to_string())),
+            Err(_) => Err(transaction_not_found(id)),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl QueryEngine for GrpcRequestHandler {
+    async fn execute(&self, request: Request<proto::ExecuteRequest>) -> Result<Response<proto::Response>, Status> {
+        let access = self.authenticate(&request).await?;
+        let request = request.into_inner();
+
+        let transaction = if request.transaction_id.is_empty() {
+            None
+        } else {
+            Some(self.transaction(&request.transaction_id)?)
+        };
+
+        let response = handle_operations(self.ctx.clone(), request.operations, access, transaction).await;
+
+        grpc_response(response).map(Response::new)
+    }
+
+    async fn execute_batch(
+        &self,
+        request: Request<proto::ExecuteBatchRequest>,
+    ) -> Result<Response<proto::Response>, Status> {
+        let access = self.authenticate(&request).await?;
+        let request = request.into_inner();
+
+        let response = if request.transaction {
+            handle_transaction(self.ctx.clone(), request.operations, access).await
+        } else {
+            let futures = request
+                .operations
+                .into_iter()
+                .map(|operation| {
+                    let query = handle_operations(self.ctx.clone(), vec![operation], access, None);
+                    (None, query)
+                })
+                .collect();
+
+            PrismaResponse::Multi(execute_batch(futures).await)
+        };
+
+        grpc_response(response).map(Response::new)
+    }
+
+    async fn start_transaction(
+        &self,
+        request: Request<proto::StartTransactionRequest>,
+    ) -> Result<Response<proto::StartTransactionResponse>, Status> {
+        self.authenticate(&request).await?;
+
+        // Documents executed in transactions are sent over gRPC, so they are never persisted queries.
+        self.ctx
+            .persisted_queries()
+            .check(DocumentOrigin::AdHoc)
+            .map_err(|err| Status::failed_precondition(err.to_string()))?;
+
+        if self.drain.is_shutting_down() {
+            return Err(Status::unavailable("The server is shutting down."));
+        }
+
+        let timeout = transaction_timeout(request.into_inner().timeout_ms);
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let (sender, receiver) = transaction_channel();
+        let ctx = self.ctx.clone();
+        let guard = self.drain.track();
+
+        tokio::spawn(async move {
+            let _guard = guard;
+            let query_schema = Arc::clone(ctx.query_schema());
+
+            if let Err(err) = ctx.executor().interactive_transaction(query_schema, receiver).await {
+                error!("Error in interactive transaction: {}", err);
+            }
+        });
+
+        self.transactions.lock().unwrap().insert(id.clone(), sender);
+
+        let transactions = Arc::clone(&self.transactions);
+        let expired = id.clone();
+        let shutting_down = self.drain.shutting_down();
+
+        // Removing the transaction closes its channel once running requests finished, which rolls it back.
+        tokio::spawn(async move {
+            future::select(Box::pin(tokio::time::delay_for(timeout)), Box::pin(shutting_down)).await;
+            transactions.lock().unwrap().remove(&expired);
+        });
+
+        Ok(Response::new(proto::StartTransactionResponse { transaction_id: id }))
+    }
+
+    async fn commit(&self, request: Request<proto::TransactionRequest>) -> Result<Response<proto::Empty>, Status> {
+        self.authenticate(&request).await?;
+        let id = request.into_inner().transaction_id;
+
+        self.end_transaction(&id, TransactionRequest::Commit).await
+    }
+
+    async fn rollback(&self, request: Request<proto::TransactionRequest>) -> Result<Response<proto::Empty>, Status> {
+        self.authenticate(&request).await?;
+        let id = request.into_inner().transaction_id;
+
+        self.end_transaction(&id, TransactionRequest::Rollback).await
+    }
+}
+
+async fn handle_operations(
+    ctx: Arc<PrismaContext>,
+    operations: Vec<proto::Operation>,
+    access: Access,
+    transaction: Option<TransactionSender>,
+) -> PrismaResponse {
+    let result = AssertUnwindSafe(execute_operations(&*ctx, operations, access, transaction))
+        .catch_unwind()
+        .await;
+
+    single_response(result)
+}
+
+async fn handle_transaction(
+    ctx: Arc<PrismaContext>,
+    operations: Vec<proto::Operation>,
+    access: Access,
+) -> PrismaResponse {
+    let result = AssertUnwindSafe(execute_transaction(&*ctx, operations, access))
+        .catch_unwind()
+        .await;
+
+    single_response(result)
+}
+
+/// Executes the operations in the interactive transaction if one is given.
+async fn execute_operations(
+    ctx: &PrismaContext,
+    operations: Vec<proto::Operation>,
+    access: Access,
+    transaction: Option<TransactionSender>,
+) -> PrismaResult<response_ir::Responses> {
+    let query_doc = authorized_document(ctx, operations, access)?;
+
+    match transaction {
+        Some(transaction) => {
+            let (reply, responses) = oneshot::channel();
+
+            transaction
+                .send(TransactionRequest::Execute(query_doc, reply))
+                .map_err(|err| match err {
+                    TransactionSendError::Busy => PrismaError::InvocationError(
+                        "The transaction has too many requests waiting, retry once they finished.".to_owned(),
+                    ),
+                    TransactionSendError::Closed => transaction_closed(),
+                })?;
+
+            Ok(responses.await.map_err(|_| transaction_closed())??)
+        }
+        None => execute(ctx, query_doc, DocumentOrigin::AdHoc, None).await,
+    }
+}
+
+async fn execute_transaction(
+    ctx: &PrismaContext,
+    operations: Vec<proto::Operation>,
+    access: Access,
+) -> PrismaResult<response_ir::Responses> {
+    let query_doc = authorized_document(ctx, operations, access)?;
+    let query_schema = Arc::clone(ctx.query_schema());
+
+    Ok(ctx.executor().execute_transaction(query_doc, query_schema).await?)
+}
+
+/// Converts the operations, which are ad-hoc documents, checked like the documents executed with `execute` as they
+/// are not necessarily executed with it.
+fn authorized_document(
+    ctx: &PrismaContext,
+    operations: Vec<proto::Operation>,
+    access: Access,
+) -> PrismaResult<QueryDocument> {
+    ctx.persisted_queries().check(DocumentOrigin::AdHoc)?;
+
+    let query_doc = GrpcProtocolAdapter::convert(operations)?;
+
+    for operation in query_doc.operations.iter() {
+        access.authorize(operation)?;
+    }
+
+    Ok(query_doc)
+}
+
+fn single_response(result: std::thread::Result<PrismaResult<response_ir::Responses>>) -> PrismaResponse {
+    let responses = match result {
+        Ok(Ok(responses)) => responses,
+        Ok(Err(err)) => {
+            let mut responses = response_ir::Responses::default();
+            responses.insert_error(err);
+            responses
+        }
+        // panicked
+        Err(err) => {
+            let mut responses = response_ir::Responses::default();
+            responses.insert_error(Error::from_panic_payload(&err));
+            responses
+        }
+    };
+
+    PrismaResponse::Single(responses)
+}
+
+fn grpc_response(response: PrismaResponse) -> Result<proto::Response, Status> {
+    let json = serde_json::to_string(&response)
+        .map_err(|err| Status::internal(format!("Serializing the response failed: {}", err)))?;
+
+    Ok(proto::Response {
+        has_errors: response.has_errors(),
+        json,
+    })
+}
+
+fn transaction_not_found(id: &str) -> Status {
+    Status::not_found(format!("Transaction '{}' doesn't exist or timed out.", id))
+}
+
+fn transaction_busy(id: &str) -> Status {
+    Status::resource_exhausted(format!("Transaction '{}' has too many requests waiting.", id))
+}
+
+fn transaction_closed() -> PrismaError {
+    PrismaError::InvocationError("The transaction was closed.".to_owned())
+}
@@ -0,0 +1,10 @@
+mod handler;
+mod protocol_adapter;
+
+/// Messages and service of `proto/query_engine.proto`.
+pub mod proto {
+    tonic::include_proto!("prisma.query_engine");
+}
+
+pub use handler::*;
+pub use protocol_adapter::*;
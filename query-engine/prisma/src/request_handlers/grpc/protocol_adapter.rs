@@ -0,0 +1,90 @@
+use super::proto::{self, operation::Kind as OperationKind, value::Kind as ValueKind};
+use crate::{PrismaError, PrismaResult};
+use query_core::{Operation, QueryDocument, QueryValue, Selection};
+
+/// Converts the operations of gRPC requests into query documents.
+pub struct GrpcProtocolAdapter;
+
+impl GrpcProtocolAdapter {
+    pub fn convert(operations: Vec<proto::Operation>) -> PrismaResult<QueryDocument> {
+        let operations = operations
+            .into_iter()
+            .map(Self::convert_operation)
+            .collect::<PrismaResult<Vec<Operation>>>()?;
+
+        Ok(QueryDocument { operations })
+    }
+
+    fn convert_operation(operation: proto::Operation) -> PrismaResult<Operation> {
+        let selection = match operation.selection {
+            Some(selection) => Self::convert_selection(selection)?,
+            None => {
+                return Err(PrismaError::QueryConversionError(
+                    "Operations need a selection.".to_owned(),
+                ))
+            }
+        };
+
+        match OperationKind::from_i32(operation.kind) {
+            Some(OperationKind::Read) => Ok(Operation::Read(selection)),
+            Some(OperationKind::Write) => Ok(Operation::Write(selection)),
+            None => Err(PrismaError::QueryConversionError(format!(
+                "Unknown operation kind {}.",
+                operation.kind
+            ))),
+        }
+    }
+
+    /// An empty alias means the selection has none, names can't be empty.
+    fn convert_selection(selection: proto::Selection) -> PrismaResult<Selection> {
+        let arguments = selection
+            .arguments
+            .into_iter()
+            .map(|argument| (argument.name, Self::convert_value(argument.value)))
+            .collect();
+
+        let nested_selections = selection
+            .nested_selections
+            .into_iter()
+            .map(Self::convert_selection)
+            .collect::<PrismaResult<Vec<Selection>>>()?;
+
+        let alias = if selection.alias.is_empty() {
+            None
+        } else {
+            Some(selection.alias)
+        };
+
+        Ok(Selection {
+            name: selection.name,
+            alias,
+            arguments,
+            nested_selections,
+        })
+    }
+
+    /// Values without a kind are null, like fields missing from a message.
+    fn convert_value(value: Option<proto::Value>) -> QueryValue {
+        match value.and_then(|value| value.kind) {
+            Some(ValueKind::Int(i)) => QueryValue::Int(i),
+            Some(ValueKind::Float(f)) => QueryValue::Float(f),
+            Some(ValueKind::String(s)) => QueryValue::String(s),
+            Some(ValueKind::Boolean(b)) => QueryValue::Boolean(b),
+            Some(ValueKind::Enum(e)) => QueryValue::Enum(e),
+            Some(ValueKind::List(list)) => QueryValue::List(
+                list.values
+                    .into_iter()
+                    .map(|value| Self::convert_value(Some(value)))
+                    .collect(),
+            ),
+            Some(ValueKind::Object(object)) => QueryValue::Object(
+                object
+                    .fields
+                    .into_iter()
+                    .map(|(name, value)| (name, Self::convert_value(Some(value))))
+                    .collect(),
+            ),
+            Some(ValueKind::Null(_)) | None => QueryValue::Null,
+        }
+    }
+}
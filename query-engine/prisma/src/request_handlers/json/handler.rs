@@ -0,0 +1,109 @@
+use crate::{
+    auth::Access,
+    context::PrismaContext,
+    request_handlers::{batch_idempotency_error, execute, execute_batch, idempotency_key, DocumentOrigin},
+    PrismaRequest, PrismaResponse, PrismaResult, RequestHandler,
+};
+use async_trait::async_trait;
+use futures::FutureExt;
+use prisma_engine::json::{JsonProtocolAdapter, JsonSingleQuery};
+use query_core::{response_ir, IdempotencyKey};
+use serde::{Deserialize, Serialize};
+use std::{panic::AssertUnwindSafe, sync::Arc};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMultiQuery {
+    batch: Vec<JsonSingleQuery>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", untagged)]
+pub enum JsonBody {
+    Single(JsonSingleQuery),
+    Multi(JsonMultiQuery),
+}
+
+/// Request handler for structured JSON documents, bypassing GraphQL parsing entirely.
+pub struct JsonRequestHandler;
+
+#[async_trait]
+impl RequestHandler for JsonRequestHandler {
+    type Body = JsonBody;
+
+    async fn handle<S>(&self, req: S, ctx: &Arc<PrismaContext>) -> PrismaResponse
+    where
+        S: Into<PrismaRequest<Self::Body>> + Send + Sync + 'static,
+    {
+        let request = req.into();
+        let access = request.access;
+        let idempotency_key = idempotency_key(&request.headers, &request.body);
+
+        match request.body {
+            JsonBody::Single(query) => handle_single_query(query, ctx.clone(), access, idempotency_key).await,
+            JsonBody::Multi(_) if idempotency_key.is_some() => {
+                let mut responses = response_ir::Responses::default();
+                responses.insert_error(batch_idempotency_error());
+
+                PrismaResponse::Single(responses)
+            }
+            JsonBody::Multi(queries) => {
+                let futures = queries
+                    .batch
+                    .into_iter()
+                    .map(|query| (None, handle_single_query(query, ctx.clone(), access, None)))
+                    .collect();
+
+                PrismaResponse::Multi(execute_batch(futures).await)
+            }
+        }
+    }
+}
+
+async fn handle_single_query(
+    query: JsonSingleQuery,
+    ctx: Arc<PrismaContext>,
+    access: Access,
+    idempotency_key: Option<IdempotencyKey>,
+) -> PrismaResponse {
+    use user_facing_errors::Error;
+
+    let responses = match AssertUnwindSafe(handle_json_query(query, &*ctx, access, idempotency_key))
+        .catch_unwind()
+        .await
+    {
+        Ok(Ok(responses)) => responses,
+        Ok(Err(err)) => {
+            let mut responses = response_ir::Responses::default();
+            responses.insert_error(err);
+            responses
+        }
+        // panicked
+        Err(err) => {
+            let mut responses = response_ir::Responses::default();
+            let error = Error::from_panic_payload(&err);
+
+            responses.insert_error(error);
+            responses
+        }
+    };
+
+    PrismaResponse::Single(responses)
+}
+
+async fn handle_json_query(
+    body: JsonSingleQuery,
+    ctx: &PrismaContext,
+    access: Access,
+    idempotency_key: Option<IdempotencyKey>,
+) -> PrismaResult<response_ir::Responses> {
+    debug!("Incoming JSON query: {:?}", &body);
+
+    let query_doc = tracing::info_span!("prisma:parse").in_scope(|| JsonProtocolAdapter::convert(body))?;
+
+    for operation in query_doc.operations.iter() {
+        access.authorize(operation)?;
+    }
+
+    execute(ctx, query_doc, DocumentOrigin::AdHoc, idempotency_key).await
+}
@@ -1,17 +1,120 @@
 pub mod graphql;
+pub mod grpc;
+pub mod json;
 
 pub use graphql::*;
+pub use grpc::*;
+pub use json::*;
 pub use query_core::{response_ir, schema::QuerySchemaRenderer};
 
-use crate::context::PrismaContext;
+use crate::{auth::Access, context::PrismaContext, PrismaError, PrismaResult};
 use async_trait::async_trait;
+use futures::future::{self, AbortHandle, Aborted, Future};
+use query_core::{IdempotencyKey, QueryDocument};
+use sha2::{Digest, Sha256};
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use tracing_futures::Instrument;
+
+/// Header with a key identifying a mutation request, so that retries of the request are executed only once.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
 
 #[derive(Debug, serde::Serialize)]
 #[serde(untagged)]
 pub enum PrismaResponse {
     Single(response_ir::Responses),
-    Multi(Vec<PrismaResponse>),
+    Multi(Vec<BatchResponse>),
+    GraphQl(GraphQlResponse),
+}
+
+impl PrismaResponse {
+    pub fn has_errors(&self) -> bool {
+        match self {
+            Self::Single(responses) => responses.has_errors(),
+            Self::Multi(responses) => responses.iter().any(|res| res.status == BatchStatus::Error),
+            Self::GraphQl(response) => response.has_errors(),
+        }
+    }
+}
+
+/// The response of one operation of a batch, with the position and name of the operation in the request,
+/// so clients can correlate the responses regardless of which operations failed.
+#[derive(Debug)]
+pub struct BatchResponse {
+    pub index: usize,
+    pub operation_name: Option<String>,
+    pub status: BatchStatus,
+    pub response: PrismaResponse,
+}
+
+/// The fields of the response are serialized next to the index, name and status of the operation. The responses
+/// of a nested batch are a list, which can't be merged into the fields, they are serialized as `batch` instead.
+impl serde::Serialize for BatchResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Flattened<'a> {
+            index: usize,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            operation_name: &'a Option<String>,
+            status: BatchStatus,
+            #[serde(flatten)]
+            response: &'a PrismaResponse,
+        }
+
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Nested<'a> {
+            index: usize,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            operation_name: &'a Option<String>,
+            status: BatchStatus,
+            batch: &'a [BatchResponse],
+        }
+
+        match &self.response {
+            PrismaResponse::Multi(batch) => Nested {
+                index: self.index,
+                operation_name: &self.operation_name,
+                status: self.status,
+                batch,
+            }
+            .serialize(serializer),
+            response => Flattened {
+                index: self.index,
+                operation_name: &self.operation_name,
+                status: self.status,
+                response,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BatchStatus {
+    Ok,
+    Error,
+}
+
+impl BatchResponse {
+    pub fn new(index: usize, operation_name: Option<String>, response: PrismaResponse) -> Self {
+        let status = if response.has_errors() {
+            BatchStatus::Error
+        } else {
+            BatchStatus::Ok
+        };
+
+        Self {
+            index,
+            operation_name,
+            status,
+            response,
+        }
+    }
 }
 
 #[async_trait]
@@ -27,4 +130,121 @@ pub struct PrismaRequest<T> {
     pub body: T,
     pub headers: HashMap<String, String>,
     pub path: String,
+    pub access: Access,
+}
+
+/// Executes the queries of a batch in parallel, each given with its operation name, if any.
+/// If the request is dropped before all queries finished, e.g. because the client disconnected
+/// or the request deadline expired, the remaining queries are aborted instead of running detached.
+///
+/// A query that fails outside of its own error handling, e.g. by panicking, results in an error
+/// response for that query only.
+pub(crate) async fn execute_batch<F>(queries: Vec<(Option<String>, F)>) -> Vec<BatchResponse>
+where
+    F: Future<Output = PrismaResponse> + Send + 'static,
+{
+    let mut operation_names = Vec::with_capacity(queries.len());
+    let mut futures = Vec::with_capacity(queries.len());
+    let mut handles = Vec::with_capacity(queries.len());
+
+    for (operation_name, query) in queries.into_iter() {
+        let (query, handle) = future::abortable(query.instrument(tracing::Span::current()));
+
+        operation_names.push(operation_name);
+        futures.push(tokio::spawn(query));
+        handles.push(handle);
+    }
+
+    let _abort = AbortOnDrop(handles);
+
+    future::join_all(futures)
+        .await
+        .into_iter()
+        .zip(operation_names)
+        .enumerate()
+        .map(|(index, (res, operation_name))| {
+            let response = match res {
+                Ok(Ok(response)) => response,
+                Ok(Err(Aborted)) => batch_error(user_facing_errors::Error::new_non_panic_with_current_backtrace(
+                    "Batch query aborted.".to_owned(),
+                )),
+                Err(err) => batch_error(user_facing_errors::Error::from_dyn_error(&err)),
+            };
+
+            BatchResponse::new(index, operation_name, response)
+        })
+        .collect()
+}
+
+fn batch_error(error: user_facing_errors::Error) -> PrismaResponse {
+    let mut responses = response_ir::Responses::default();
+    responses.insert_error(error);
+
+    PrismaResponse::Single(responses)
+}
+
+struct AbortOnDrop(Vec<AbortHandle>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        for handle in self.0.iter() {
+            handle.abort();
+        }
+    }
+}
+
+/// The idempotency key sent with the request, together with the hash of the request body.
+pub(crate) fn idempotency_key<T: serde::Serialize>(
+    headers: &HashMap<String, String>,
+    body: &T,
+) -> Option<IdempotencyKey> {
+    let key = headers.get(IDEMPOTENCY_KEY_HEADER)?;
+    let body = serde_json::to_vec(body).unwrap_or_default();
+
+    Some(IdempotencyKey::new(key.clone(), format!("{:x}", Sha256::digest(&body))))
+}
+
+/// Keys are stored by the connectors, which only store keys up to a maximum length.
+pub(crate) fn check_idempotency_key(key: &IdempotencyKey) -> PrismaResult<()> {
+    if key.key.chars().count() > connector::MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(PrismaError::InvocationError(format!(
+            "Idempotency keys can't be longer than {} characters.",
+            connector::MAX_IDEMPOTENCY_KEY_LENGTH
+        )));
+    }
+
+    Ok(())
+}
+
+/// The operations of a batch are executed independently of each other, a single key can't cover them.
+pub(crate) fn batch_idempotency_error() -> PrismaError {
+    PrismaError::InvocationError("Idempotency keys are not supported for batch requests.".to_owned())
+}
+
+/// Executes the document, only once for an idempotency key if it contains mutations.
+/// Ad-hoc documents are rejected if only persisted queries are allowed.
+pub(crate) async fn execute(
+    ctx: &PrismaContext,
+    query_doc: QueryDocument,
+    origin: DocumentOrigin,
+    idempotency_key: Option<IdempotencyKey>,
+) -> PrismaResult<response_ir::Responses> {
+    ctx.persisted_queries().check(origin)?;
+
+    if let Some(ref key) = idempotency_key {
+        check_idempotency_key(key)?;
+    }
+
+    let query_schema = Arc::clone(ctx.query_schema());
+    let responses = match idempotency_key {
+        Some(key) if query_doc.operations.iter().any(|operation| operation.is_write()) => {
+            ctx.executor().execute_idempotent(query_doc, query_schema, key).await
+        }
+        _ => ctx.executor().execute(query_doc, query_schema).await,
+    };
+
+    responses.map_err(|err| {
+        debug!("{}", err);
+        PrismaError::from(err)
+    })
 }
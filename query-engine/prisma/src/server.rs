@@ -1,39 +1,110 @@
 use super::dmmf;
 use crate::{
+    auth::{Access, Authenticator},
+    body::{self, Encoding},
     context::PrismaContext,
+    headers::ResponseHeaders,
+    listener::{self, ListenAddress},
     request_handlers::{
-        graphql::{GraphQLSchemaRenderer, GraphQlBody, GraphQlRequestHandler},
-        PrismaRequest, RequestHandler,
+        graphql::{self, GraphQLSchemaRenderer, GraphQlBody, GraphQlRequestHandler, IncrementalResponse},
+        grpc::GrpcRequestHandler,
+        json::{JsonBody, JsonRequestHandler},
+        PersistedQueries, PrismaRequest, PrismaResponse, RequestHandler,
     },
-    PrismaResult,
+    shutdown::{self, Drain},
+    subscriptions, telemetry,
+    tenants::{self, Contexts, TenantOptions, Tenants, TENANTS_PATH},
+    PrismaError, PrismaResult,
 };
+use datamodel::SourceOverride;
+use futures::{future, stream::BoxStream};
+use hyper::body::HttpBody;
 use hyper::header;
+use hyper::server::{
+    accept::{self, Accept},
+    Builder,
+};
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Error, Method, Request, Response, Server, StatusCode};
-use query_core::schema::QuerySchemaRenderer;
+use query_core::{response_ir::Responses, schema::QuerySchemaRenderer, AccessPolicy, QueryLimits};
 use serde_json::json;
-use std::net::SocketAddr;
-use std::{sync::Arc, time::Instant};
+use std::{collections::HashMap, future::Future, net::SocketAddr};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpListener,
+};
+use tokio_rustls::TlsAcceptor;
+use tracing_futures::Instrument;
+
+#[cfg(unix)]
+use crate::listener::Inherited;
+
+/// Header with the time in milliseconds a client is willing to wait for the result of a query request.
+const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout";
 
 #[derive(RustEmbed)]
 #[folder = "query-engine/prisma/static_files"]
 struct StaticFiles;
 
 pub(crate) struct RequestContext {
-    context: Arc<PrismaContext>,
+    contexts: Contexts,
     graphql_request_handler: GraphQlRequestHandler,
+    json_request_handler: JsonRequestHandler,
+    max_body_size: Option<usize>,
+    response_headers: ResponseHeaders,
+    authenticator: Arc<Authenticator>,
+    drain: Drain,
 }
 
 impl RequestContext {
-    pub(crate) fn context(&self) -> &Arc<PrismaContext> {
-        &self.context
+    pub(crate) fn new(
+        contexts: Contexts,
+        max_body_size: Option<usize>,
+        response_headers: ResponseHeaders,
+        authenticator: Arc<Authenticator>,
+        drain: Drain,
+    ) -> Self {
+        Self {
+            contexts,
+            graphql_request_handler: GraphQlRequestHandler,
+            json_request_handler: JsonRequestHandler,
+            max_body_size,
+            response_headers,
+            authenticator,
+            drain,
+        }
     }
 }
 
+/// Body of a request loading a tenant.
+#[derive(serde::Deserialize)]
+struct LoadTenant {
+    datamodel: String,
+    /// Replaces the URLs of datasources of the data model.
+    #[serde(default)]
+    datasources: Vec<SourceOverride>,
+}
+
 pub struct HttpServerBuilder {
     legacy_mode: bool,
     force_transactions: bool,
     enable_raw_queries: bool,
+    shutdown_timeout: Duration,
+    limits: QueryLimits,
+    max_body_size: Option<usize>,
+    graphql_errors: bool,
+    persisted_queries: PersistedQueries,
+    access_policy: AccessPolicy,
+    response_headers: ResponseHeaders,
+    multi_tenant: bool,
+    tls: Option<TlsAcceptor>,
+    authenticator: Authenticator,
+    datasource_overrides: Vec<SourceOverride>,
+    grpc_address: Option<SocketAddr>,
 }
 
 impl HttpServerBuilder {
@@ -52,15 +123,108 @@ impl HttpServerBuilder {
         self
     }
 
-    pub async fn build_and_run(self, address: SocketAddr) -> PrismaResult<()> {
-        let ctx = PrismaContext::builder()
-            .legacy(self.legacy_mode)
-            .force_transactions(self.force_transactions)
-            .enable_raw_queries(self.enable_raw_queries)
-            .build()
-            .await?;
+    pub fn shutdown_timeout(mut self, val: Duration) -> Self {
+        self.shutdown_timeout = val;
+        self
+    }
+
+    pub fn limits(mut self, val: QueryLimits) -> Self {
+        self.limits = val;
+        self
+    }
+
+    pub fn max_body_size(mut self, val: Option<usize>) -> Self {
+        self.max_body_size = val;
+        self
+    }
+
+    pub fn graphql_errors(mut self, val: bool) -> Self {
+        self.graphql_errors = val;
+        self
+    }
+
+    pub fn persisted_queries(mut self, val: PersistedQueries) -> Self {
+        self.persisted_queries = val;
+        self
+    }
 
-        HttpServer::run(address, ctx).await
+    /// Restricts the operations and fields of the data model of the environment.
+    pub fn access_policy(mut self, val: AccessPolicy) -> Self {
+        self.access_policy = val;
+        self
+    }
+
+    pub fn response_headers(mut self, val: ResponseHeaders) -> Self {
+        self.response_headers = val;
+        self
+    }
+
+    /// Serves data models loaded at runtime through the management API instead of the one of the environment.
+    pub fn multi_tenant(mut self, val: bool) -> Self {
+        self.multi_tenant = val;
+        self
+    }
+
+    /// Certificate and key of the HTTPS endpoints.
+    pub fn tls(mut self, val: Option<TlsAcceptor>) -> Self {
+        self.tls = val;
+        self
+    }
+
+    /// Authenticates all requests but the status, health and readiness checks.
+    pub fn authenticator(mut self, val: Authenticator) -> Self {
+        self.authenticator = val;
+        self
+    }
+
+    /// Replaces the URLs of datasources of the data model of the environment.
+    pub fn datasource_overrides(mut self, val: Vec<SourceOverride>) -> Self {
+        self.datasource_overrides = val;
+        self
+    }
+
+    /// Address of the gRPC endpoint, which serves the same queries as the HTTP endpoints.
+    pub fn grpc_address(mut self, val: Option<SocketAddr>) -> Self {
+        self.grpc_address = val;
+        self
+    }
+
+    pub async fn build_and_run(self, addresses: Vec<ListenAddress>) -> PrismaResult<()> {
+        let contexts = if self.multi_tenant {
+            Contexts::Tenants(Tenants::new(TenantOptions {
+                legacy: self.legacy_mode,
+                force_transactions: self.force_transactions,
+                enable_raw_queries: self.enable_raw_queries,
+                limits: self.limits,
+                graphql_errors: self.graphql_errors,
+            }))
+        } else {
+            let ctx = PrismaContext::builder()
+                .legacy(self.legacy_mode)
+                .force_transactions(self.force_transactions)
+                .enable_raw_queries(self.enable_raw_queries)
+                .limits(self.limits)
+                .graphql_errors(self.graphql_errors)
+                .persisted_queries(self.persisted_queries)
+                .access_policy(self.access_policy)
+                .datasource_overrides(self.datasource_overrides)
+                .build()
+                .await?;
+
+            Contexts::Single(Arc::new(ctx))
+        };
+
+        HttpServer::run(
+            addresses,
+            contexts,
+            self.shutdown_timeout,
+            self.max_body_size,
+            self.response_headers,
+            self.authenticator,
+            self.tls,
+            self.grpc_address,
+        )
+        .await
     }
 }
 
@@ -72,92 +236,586 @@ impl HttpServer {
             legacy_mode: false,
             force_transactions: false,
             enable_raw_queries: false,
+            shutdown_timeout: Duration::from_secs(10),
+            limits: QueryLimits::default(),
+            max_body_size: None,
+            graphql_errors: false,
+            persisted_queries: PersistedQueries::default(),
+            access_policy: AccessPolicy::default(),
+            response_headers: ResponseHeaders::default(),
+            multi_tenant: false,
+            tls: None,
+            authenticator: Authenticator::default(),
+            datasource_overrides: Vec::new(),
+            grpc_address: None,
         }
     }
 
-    async fn run(address: SocketAddr, context: PrismaContext) -> PrismaResult<()> {
+    async fn run(
+        addresses: Vec<ListenAddress>,
+        contexts: Contexts,
+        shutdown_timeout: Duration,
+        max_body_size: Option<usize>,
+        response_headers: ResponseHeaders,
+        authenticator: Authenticator,
+        tls: Option<TlsAcceptor>,
+        grpc_address: Option<SocketAddr>,
+    ) -> PrismaResult<()> {
         let now = Instant::now();
+        let authenticator = Arc::new(authenticator);
+        let (drain, drain_trigger) = shutdown::drain();
+
+        let grpc_handler = match (grpc_address, &contexts) {
+            (Some(address), Contexts::Single(context)) => Some((
+                address,
+                GrpcRequestHandler::new(Arc::clone(context), Arc::clone(&authenticator), drain.clone()),
+            )),
+            (Some(_), Contexts::Tenants(_)) => {
+                return Err(PrismaError::ConfigurationError(
+                    "The gRPC endpoint can't serve tenants.".to_owned(),
+                ))
+            }
+            (None, _) => None,
+        };
 
-        let ctx = Arc::new(RequestContext {
-            context: Arc::new(context),
-            graphql_request_handler: GraphQlRequestHandler,
-        });
+        let ctx = Arc::new(RequestContext::new(
+            contexts,
+            max_body_size,
+            response_headers,
+            authenticator,
+            drain,
+        ));
+
+        trace!("Initialized in {}ms", now.elapsed().as_millis());
 
+        // All endpoints share the contexts and shut down together.
+        let servers = addresses
+            .into_iter()
+            .map(|address| Self::listen(address, Arc::clone(&ctx), shutdown_timeout, tls.clone()));
+
+        let grpc_server = async {
+            match grpc_handler {
+                Some((address, handler)) => Self::serve_grpc(address, handler, shutdown_timeout).await,
+                None => Ok(()),
+            }
+        };
+
+        future::try_join(future::try_join_all(servers), grpc_server).await?;
+
+        // All requests are finished at this point. Subscriptions and open transactions still hold the contexts.
+        drain_trigger.drain().await;
+
+        // Dropping the last reference to the contexts closes the connection pools.
+        match Arc::try_unwrap(ctx) {
+            Ok(ctx) => drop(ctx),
+            Err(_) => warn!("The contexts are still in use, their connections are closed when the process exits."),
+        }
+
+        info!("Shutdown complete.");
+
+        Ok(())
+    }
+
+    async fn listen(
+        address: ListenAddress,
+        ctx: Arc<RequestContext>,
+        shutdown_timeout: Duration,
+        tls: Option<TlsAcceptor>,
+    ) -> PrismaResult<()> {
+        match address {
+            ListenAddress::Tcp(ref addr) => Self::serve(Server::bind(addr), &address, ctx, shutdown_timeout).await,
+
+            ListenAddress::Tls(ref addr) => {
+                let acceptor = tls.ok_or_else(|| {
+                    PrismaError::ConfigurationError(format!("Serving {} requires a TLS certificate and key.", address))
+                })?;
+
+                let incoming = accept::from_stream(listener::tls_incoming(TcpListener::bind(addr).await?, acceptor));
+                Self::serve(Server::builder(incoming), &address, ctx, shutdown_timeout).await
+            }
+
+            #[cfg(unix)]
+            ListenAddress::Unix(ref path) => {
+                let (unix, _socket_file) = listener::bind_unix(path)?;
+                let incoming = accept::from_stream(listener::unix_incoming(unix));
+
+                Self::serve(Server::builder(incoming), &address, ctx, shutdown_timeout).await
+            }
+
+            #[cfg(unix)]
+            ListenAddress::Fd(fd) => match listener::inherit(fd)? {
+                Inherited::Tcp(tcp) => {
+                    let incoming = accept::from_stream(listener::tcp_incoming(tcp));
+                    Self::serve(Server::builder(incoming), &address, ctx, shutdown_timeout).await
+                }
+                Inherited::Unix(unix) => {
+                    let incoming = accept::from_stream(listener::unix_incoming(unix));
+                    Self::serve(Server::builder(incoming), &address, ctx, shutdown_timeout).await
+                }
+            },
+        }
+    }
+
+    async fn serve<I>(
+        builder: Builder<I>,
+        address: &ListenAddress,
+        ctx: Arc<RequestContext>,
+        shutdown_timeout: Duration,
+    ) -> PrismaResult<()>
+    where
+        I: Accept,
+        I::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        I::Conn: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         let service = make_service_fn(|_| {
             let ctx = ctx.clone();
 
             async { Ok::<_, Error>(service_fn(move |req| Self::routes(ctx.clone(), req))) }
         });
 
-        let server = Server::bind(&address).serve(service);
+        let server = builder
+            .serve(service)
+            .with_graceful_shutdown(shutdown::signal(shutdown_timeout));
 
-        trace!("Initialized in {}ms", now.elapsed().as_millis());
-        info!("Started http server on {}:{}", address.ip(), address.port());
+        info!("Started http server on {}", address);
 
         server.await.unwrap();
 
         Ok(())
     }
 
-    async fn routes(ctx: Arc<RequestContext>, req: Request<Body>) -> std::result::Result<Response<Body>, Error> {
-        let start = Instant::now();
+    async fn serve_grpc(
+        address: SocketAddr,
+        handler: GrpcRequestHandler,
+        shutdown_timeout: Duration,
+    ) -> PrismaResult<()> {
+        let server = tonic::transport::Server::builder()
+            .add_service(handler.into_service())
+            .serve_with_shutdown(address, shutdown::signal(shutdown_timeout));
 
-        let mut res = match (req.method(), req.uri().path()) {
-            (&Method::POST, "/") => {
-                let (parts, body) = req.into_parts();
-
-                let bytes = hyper::body::to_bytes(body).await?;
-
-                match serde_json::from_slice(bytes.as_ref()) {
-                    Ok(body) => {
-                        let req = PrismaRequest {
-                            body,
-                            path: parts.uri.path().into(),
-                            headers: parts
-                                .headers
-                                .iter()
-                                .map(|(k, v)| (format!("{}", k), v.to_str().unwrap().into()))
-                                .collect(),
-                        };
-
-                        Self::http_handler(req, ctx).await
-                    }
-                    Err(_) => {
-                        let mut bad_request = Response::default();
-                        *bad_request.status_mut() = StatusCode::BAD_REQUEST;
-                        bad_request
+        info!("Started gRPC server on {}", address);
+
+        server
+            .await
+            .map_err(|err| PrismaError::ConfigurationError(format!("Serving gRPC on {} failed: {}", address, err)))
+    }
+
+    pub(crate) async fn routes(
+        ctx: Arc<RequestContext>,
+        mut req: Request<Body>,
+    ) -> std::result::Result<Response<Body>, Error> {
+        let start = Instant::now();
+        let request_id = telemetry::request_id(&req);
+        let span = telemetry::request_span(&req, &request_id);
+        let origin = req.headers().get(header::ORIGIN).cloned();
+        let cx = Arc::clone(&ctx);
+
+        let mut res = async move {
+            let res = match (req.method(), req.uri().path()) {
+                (&Method::OPTIONS, _) => match ctx.response_headers.preflight(req.headers().get(header::ORIGIN)) {
+                    Some(res) => res,
+                    None => Self::method_not_allowed(),
+                },
+
+                (&Method::GET, "/status") => Self::status_handler(),
+                (&Method::GET, "/health") => Self::status_handler(),
+                (&Method::GET, "/ready") => match ctx.contexts.resolve(&req) {
+                    Ok((context, _)) => Self::ready_handler(context).await,
+                    Err(err) => Self::error_response(StatusCode::NOT_FOUND, err),
+                },
+
+                _ => match ctx.authenticator.authenticate(&req).await {
+                    Ok(access) => {
+                        req.extensions_mut().insert(access);
+                        Self::authenticated_routes(req, access, ctx).await?
                     }
+                    Err(err) => Self::unauthorized(err),
+                },
+            };
+
+            Ok::<_, Error>(res)
+        }
+        .instrument(span.clone())
+        .await?;
+
+        cx.response_headers.apply(origin.as_ref(), res.headers_mut());
+
+        let elapsed = Instant::now().duration_since(start).as_micros() as u64;
+        res.headers_mut().insert("x-elapsed", elapsed.into());
+
+        if let Ok(request_id) = header::HeaderValue::from_str(&request_id) {
+            res.headers_mut().insert(telemetry::REQUEST_ID_HEADER, request_id);
+        }
+
+        span.in_scope(|| {
+            tracing::info!(
+                status = res.status().as_u16(),
+                duration_ms = elapsed / 1000,
+                "Request finished"
+            )
+        });
+
+        Ok(res)
+    }
+
+    async fn authenticated_routes(
+        req: Request<Body>,
+        access: Access,
+        ctx: Arc<RequestContext>,
+    ) -> std::result::Result<Response<Body>, Error> {
+        let res = match (req.method(), req.uri().path()) {
+            (&Method::GET, TENANTS_PATH) => Self::tenants_handler(ctx),
+            (&Method::PUT, path) | (&Method::DELETE, path) if tenants::tenant_name(path).is_some() => {
+                // Without authentication, anyone able to reach the server could load data models.
+                if !ctx.authenticator.is_enabled() {
+                    let err = PrismaError::AuthorizationError(
+                        "Managing tenants requires authentication, configure `--auth_token` or `--auth_jwks`."
+                            .to_owned(),
+                    );
+                    return Ok(Self::error_response(StatusCode::FORBIDDEN, err));
                 }
+
+                if access == Access::ReadOnly {
+                    let err = PrismaError::AuthorizationError("Managing tenants requires full access.".to_owned());
+                    return Ok(Self::error_response(StatusCode::FORBIDDEN, err));
+                }
+
+                Self::tenant_handler(req, ctx).await?
             }
 
+            _ => match ctx.contexts.resolve(&req) {
+                Ok((context, path)) => Self::context_routes(req, &path, context, ctx).await?,
+                Err(err) => Self::error_response(StatusCode::NOT_FOUND, err),
+            },
+        };
+
+        Ok(res)
+    }
+
+    /// Routes of the context a request is addressed to, with the path relative to the context.
+    async fn context_routes(
+        req: Request<Body>,
+        path: &str,
+        context: Arc<PrismaContext>,
+        ctx: Arc<RequestContext>,
+    ) -> std::result::Result<Response<Body>, Error> {
+        let res = match (req.method(), path) {
+            (&Method::POST, "/") => match Self::request_timeout(&req) {
+                Ok(timeout) => match Self::parse_request::<GraphQlBody>(req, ctx.max_body_size).await? {
+                    Ok(req) => Self::with_deadline(timeout, Self::http_handler(req, context, ctx)).await,
+                    Err(res) => res,
+                },
+                Err(res) => res,
+            },
+
+            (&Method::POST, "/json") => match Self::request_timeout(&req) {
+                Ok(timeout) => match Self::parse_request::<JsonBody>(req, ctx.max_body_size).await? {
+                    Ok(req) => Self::with_deadline(timeout, Self::json_handler(req, context, ctx)).await,
+                    Err(res) => res,
+                },
+                Err(res) => res,
+            },
+
             (&Method::GET, "/") => Self::playground_handler(),
-            (&Method::GET, "/status") => Self::status_handler(),
 
-            (&Method::GET, "/sdl") => Self::sdl_handler(ctx),
-            (&Method::GET, "/dmmf") => Self::dmmf_handler(ctx),
-            (&Method::GET, "/server_info") => Self::server_info_handler(ctx),
+            (&Method::GET, "/subscriptions") => Self::subscriptions_handler(req, context, ctx.drain.clone()),
 
-            _ => {
-                let mut not_found = Response::default();
-                *not_found.status_mut() = StatusCode::NOT_FOUND;
-                not_found
-            }
+            (&Method::GET, "/sdl") => Self::sdl_handler(context),
+            (&Method::GET, "/dmmf") => Self::dmmf_handler(req, context),
+            (&Method::GET, "/server_info") | (&Method::GET, "/server-info") => Self::server_info_handler(context),
+
+            _ => Self::not_found(),
         };
 
-        let elapsed = Instant::now().duration_since(start).as_micros() as u64;
-        res.headers_mut().insert("x-elapsed", elapsed.into());
+        Ok(res)
+    }
+
+    /// Lists the loaded tenants.
+    fn tenants_handler(cx: Arc<RequestContext>) -> Response<Body> {
+        let tenants = match cx.contexts {
+            Contexts::Tenants(ref tenants) => tenants,
+            Contexts::Single(_) => return Self::not_found(),
+        };
+
+        Self::json_response(StatusCode::OK, json!({ "tenants": tenants.names() }))
+    }
+
+    /// Management API of the multi-tenant mode.
+    /// `PUT /tenants/{name}` with a `{"datamodel": "..."}` body loads a tenant, `DELETE /tenants/{name}` unloads it.
+    /// The body can replace the URLs of datasources with `"datasources": [{"name": "...", "url": "..."}]`.
+    async fn tenant_handler(req: Request<Body>, cx: Arc<RequestContext>) -> std::result::Result<Response<Body>, Error> {
+        let tenants = match cx.contexts {
+            Contexts::Tenants(ref tenants) => tenants,
+            Contexts::Single(_) => return Ok(Self::not_found()),
+        };
+
+        let name = tenants::tenant_name(req.uri().path()).unwrap_or_default().to_owned();
+
+        if req.method() == Method::DELETE {
+            let res = if tenants.unload(&name) {
+                Self::json_response(StatusCode::OK, json!({ "tenant": name, "status": "unloaded" }))
+            } else {
+                Self::not_found()
+            };
+
+            return Ok(res);
+        }
+
+        let res = match Self::parse_request::<LoadTenant>(req, cx.max_body_size).await? {
+            Ok(req) => {
+                let LoadTenant { datamodel, datasources } = req.body;
+
+                match tenants.load(name.clone(), datamodel, datasources).await {
+                    Ok(()) => Self::json_response(StatusCode::OK, json!({ "tenant": name, "status": "loaded" })),
+                    Err(err) => Self::error_response(StatusCode::BAD_REQUEST, err),
+                }
+            }
+            Err(res) => res,
+        };
 
         Ok(res)
     }
 
-    async fn http_handler(req: PrismaRequest<GraphQlBody>, cx: Arc<RequestContext>) -> Response<Body> {
-        let result = cx.graphql_request_handler.handle(req, cx.context()).await;
-        let bytes = serde_json::to_vec(&result).unwrap();
+    /// Reads the deadline of a query request from the `x-request-timeout` header, in milliseconds.
+    fn request_timeout(req: &Request<Body>) -> std::result::Result<Option<Duration>, Response<Body>> {
+        match req.headers().get(REQUEST_TIMEOUT_HEADER) {
+            Some(value) => value
+                .to_str()
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(|millis| Some(Duration::from_millis(millis)))
+                .ok_or_else(Self::bad_request),
+            None => Ok(None),
+        }
+    }
+
+    /// Runs the handler until the deadline expires.
+    /// Expiring drops the handler, which cancels the in-flight queries, same as a client disconnecting.
+    async fn with_deadline(timeout: Option<Duration>, handler: impl Future<Output = Response<Body>>) -> Response<Body> {
+        match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, handler).await {
+                Ok(res) => res,
+                Err(_) => Self::deadline_exceeded(timeout),
+            },
+            None => handler.await,
+        }
+    }
+
+    fn deadline_exceeded(timeout: Duration) -> Response<Body> {
+        let time = if timeout.as_millis() < 1000 {
+            format!("{}ms", timeout.as_millis())
+        } else {
+            format!("{}s", timeout.as_secs())
+        };
+
+        let error =
+            user_facing_errors::KnownError::new(user_facing_errors::common::DatabaseOperationTimeout { time }).unwrap();
+
+        let mut responses = Responses::default();
+        responses.insert_error(user_facing_errors::Error::from(error));
+
+        Response::builder()
+            .status(StatusCode::GATEWAY_TIMEOUT)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&responses).unwrap()))
+            .unwrap()
+    }
+
+    /// Reads and deserializes the request body.
+    /// Returns the response to send instead if the body is too large or not valid for the protocol.
+    async fn parse_request<T>(
+        req: Request<Body>,
+        max_body_size: Option<usize>,
+    ) -> std::result::Result<std::result::Result<PrismaRequest<T>, Response<Body>>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let (parts, mut body) = req.into_parts();
+        let mut bytes = Vec::new();
+
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk?;
+
+            match max_body_size {
+                Some(maximum) if bytes.len() + chunk.len() > maximum => {
+                    return Ok(Err(Self::payload_too_large(bytes.len() + chunk.len(), maximum)))
+                }
+                _ => bytes.extend_from_slice(&chunk),
+            }
+        }
+
+        let req = serde_json::from_slice(&bytes).ok().map(|body| PrismaRequest {
+            body,
+            access: parts.extensions.get::<Access>().copied().unwrap_or_default(),
+            path: parts.uri.path().into(),
+            headers: parts
+                .headers
+                .iter()
+                .map(|(k, v)| (format!("{}", k), v.to_str().unwrap().into()))
+                .collect(),
+        });
+
+        Ok(req.ok_or_else(Self::bad_request))
+    }
+
+    fn payload_too_large(size: usize, maximum: usize) -> Response<Body> {
+        let error = user_facing_errors::KnownError::new(user_facing_errors::query_engine::QueryLimitExceeded {
+            limit: "body size".to_owned(),
+            value: size,
+            maximum,
+        })
+        .unwrap();
+
+        let mut responses = Responses::default();
+        responses.insert_error(user_facing_errors::Error::from(error));
+
+        Response::builder()
+            .status(StatusCode::PAYLOAD_TOO_LARGE)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&responses).unwrap()))
+            .unwrap()
+    }
+
+    fn json_response(status: StatusCode, body_data: serde_json::Value) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&body_data).unwrap()))
+            .unwrap()
+    }
+
+    fn error_response(status: StatusCode, err: PrismaError) -> Response<Body> {
+        let mut responses = Responses::default();
+        responses.insert_error(err);
+
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&responses).unwrap()))
+            .unwrap()
+    }
+
+    fn unauthorized(err: PrismaError) -> Response<Body> {
+        let mut res = Self::error_response(StatusCode::UNAUTHORIZED, err);
+
+        res.headers_mut()
+            .insert(header::WWW_AUTHENTICATE, header::HeaderValue::from_static("Bearer"));
+
+        res
+    }
+
+    fn not_found() -> Response<Body> {
+        let mut not_found = Response::default();
+        *not_found.status_mut() = StatusCode::NOT_FOUND;
+        not_found
+    }
+
+    fn method_not_allowed() -> Response<Body> {
+        let mut method_not_allowed = Response::default();
+        *method_not_allowed.status_mut() = StatusCode::METHOD_NOT_ALLOWED;
+        method_not_allowed
+    }
+
+    fn bad_request() -> Response<Body> {
+        let mut bad_request = Response::default();
+        *bad_request.status_mut() = StatusCode::BAD_REQUEST;
+        bad_request
+    }
+
+    async fn http_handler(
+        req: PrismaRequest<GraphQlBody>,
+        context: Arc<PrismaContext>,
+        cx: Arc<RequestContext>,
+    ) -> Response<Body> {
+        let encoding = Encoding::negotiate(req.headers.get("accept-encoding").map(String::as_str));
+
+        if Self::accepts_multipart(&req.headers) {
+            return match graphql::handle_incremental(req, &context).await {
+                IncrementalResponse::Complete(result) => Self::query_response(result, encoding),
+                IncrementalResponse::Incremental(payloads) => Self::multipart_response(payloads),
+            };
+        }
 
+        let result = cx.graphql_request_handler.handle(req, &context).await;
+
+        Self::query_response(result, encoding)
+    }
+
+    /// Clients accepting `multipart/mixed` responses get the selections marked with `@defer` delivered incrementally.
+    fn accepts_multipart(headers: &HashMap<String, String>) -> bool {
+        headers
+            .get("accept")
+            .map(|accept| {
+                accept
+                    .split(',')
+                    .any(|media_type| media_type.trim().starts_with("multipart/mixed"))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Streams the payloads of an incremental response as the parts of a multipart body.
+    fn multipart_response(payloads: BoxStream<'static, graphql::Payload>) -> Response<Body> {
         Response::builder()
+            .status(StatusCode::OK)
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/mixed; boundary=\"{}\"", body::MULTIPART_BOUNDARY),
+            )
+            .body(body::multipart_body(payloads))
+            .unwrap()
+    }
+
+    /// Handler for structured JSON documents, see `prisma_engine::json` for the format.
+    async fn json_handler(
+        req: PrismaRequest<JsonBody>,
+        context: Arc<PrismaContext>,
+        cx: Arc<RequestContext>,
+    ) -> Response<Body> {
+        let encoding = Encoding::negotiate(req.headers.get("accept-encoding").map(String::as_str));
+        let result = cx.json_request_handler.handle(req, &context).await;
+
+        Self::query_response(result, encoding)
+    }
+
+    /// Streams the result of a query request, compressed with the negotiated encoding.
+    fn query_response(result: PrismaResponse, encoding: Encoding) -> Response<Body> {
+        let mut builder = Response::builder()
             .status(StatusCode::OK)
             .header(header::CONTENT_TYPE, "application/json")
-            .body(Body::from(bytes))
+            .header(header::VARY, "accept-encoding");
+
+        if let Some(content_encoding) = encoding.content_encoding() {
+            builder = builder.header(header::CONTENT_ENCODING, content_encoding);
+        }
+
+        builder.body(body::json_body(result, encoding)).unwrap()
+    }
+
+    /// Upgrades the connection to a WebSocket and serves record change subscriptions on it.
+    fn subscriptions_handler(req: Request<Body>, context: Arc<PrismaContext>, drain: Drain) -> Response<Body> {
+        let accept_key = match req.headers().get(header::SEC_WEBSOCKET_KEY) {
+            Some(key) => subscriptions::accept_key(key.as_bytes()),
+            None => return Self::bad_request(),
+        };
+
+        // Upgraded connections aren't drained by hyper, the shutdown waits for the guard instead.
+        let guard = drain.track();
+
+        tokio::spawn(async move {
+            let _guard = guard;
+
+            match req.into_body().on_upgrade().await {
+                Ok(upgraded) => subscriptions::serve(upgraded, context, drain).await,
+                Err(err) => error!("Upgrading subscription connection failed: {}", err),
+            }
+        });
+
+        Response::builder()
+            .status(StatusCode::SWITCHING_PROTOCOLS)
+            .header(header::UPGRADE, "websocket")
+            .header(header::CONNECTION, "upgrade")
+            .header(header::SEC_WEBSOCKET_ACCEPT, accept_key)
+            .body(Body::empty())
             .unwrap()
     }
 
@@ -172,6 +830,38 @@ impl HttpServer {
             .unwrap()
     }
 
+    /// Readiness probe: succeeds if a connection can be acquired and used.
+    /// Reports the saturation of the connection pool either way.
+    async fn ready_handler(context: Arc<PrismaContext>) -> Response<Body> {
+        let (status, body_data) = match context.executor().check_readiness().await {
+            Ok(pool) => (
+                StatusCode::OK,
+                json!({"status": "ready", "pool": pool, "saturation": pool.saturation()}),
+            ),
+            Err(err) => {
+                let pool = context.executor().pool_state().await.ok();
+
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    json!({
+                        "status": "unavailable",
+                        "error": format!("{}", err),
+                        "pool": pool,
+                        "saturation": pool.map(|pool| pool.saturation()),
+                    }),
+                )
+            }
+        };
+
+        let bytes = serde_json::to_vec(&body_data).unwrap();
+
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(bytes))
+            .unwrap()
+    }
+
     fn playground_handler() -> Response<Body> {
         let index_html = StaticFiles::get("playground.html").unwrap();
 
@@ -184,8 +874,8 @@ impl HttpServer {
 
     /// Handler for the playground to work with the SDL-rendered query schema.
     /// Serves a raw SDL string created from the query schema.
-    fn sdl_handler(cx: Arc<RequestContext>) -> Response<Body> {
-        let rendered = GraphQLSchemaRenderer::render(Arc::clone(&cx.context.query_schema()));
+    fn sdl_handler(context: Arc<PrismaContext>) -> Response<Body> {
+        let rendered = GraphQLSchemaRenderer::render(Arc::clone(&context.query_schema()));
 
         Response::builder()
             .status(StatusCode::OK)
@@ -196,24 +886,68 @@ impl HttpServer {
 
     /// Renders the Data Model Meta Format.
     /// Only callable if prisma was initialized using a v2 data model.
-    fn dmmf_handler(cx: Arc<RequestContext>) -> Response<Body> {
-        let dmmf = dmmf::render_dmmf(cx.context.datamodel(), Arc::clone(cx.context.query_schema()));
+    ///
+    /// The client can announce the DMMF version it understands with the `version` query parameter
+    /// and request the compact format with `format=msgpack` or an `Accept: application/msgpack` header.
+    fn dmmf_handler(req: Request<Body>, context: Arc<PrismaContext>) -> Response<Body> {
+        let params: HashMap<String, String> = req
+            .uri()
+            .query()
+            .map(|query| url::form_urlencoded::parse(query.as_bytes()).into_owned().collect())
+            .unwrap_or_default();
+
+        let accepts_msgpack = req
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .map(|accept| accept.contains(dmmf::DmmfFormat::MessagePack.content_type()))
+            .unwrap_or(false);
+
+        let negotiated = params
+            .get("version")
+            .map(|version| {
+                version
+                    .parse()
+                    .map_err(|_| PrismaError::InvocationError(format!("Invalid DMMF version '{}'.", version)))
+            })
+            .transpose()
+            .and_then(dmmf::negotiate_version)
+            .and_then(|_| match params.get("format") {
+                Some(format) => format.parse(),
+                None if accepts_msgpack => Ok(dmmf::DmmfFormat::MessagePack),
+                None => Ok(dmmf::DmmfFormat::Json),
+            });
+
+        let format = match negotiated {
+            Ok(format) => format,
+            Err(err) => {
+                let bytes = serde_json::to_vec(&json!({ "error": format!("{}", err) })).unwrap();
+
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(bytes))
+                    .unwrap();
+            }
+        };
 
-        let bytes = serde_json::to_vec(&dmmf).unwrap();
+        let dmmf = dmmf::render_dmmf(context.datamodel(), Arc::clone(context.query_schema()));
+        let bytes = dmmf.serialize_as(format).unwrap();
 
         Response::builder()
             .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_TYPE, format.content_type())
             .body(Body::from(bytes))
             .unwrap()
     }
 
-    /// Simple status endpoint
-    fn server_info_handler(cx: Arc<RequestContext>) -> Response<Body> {
+    /// Version of the engine, the connector and the enabled features, so that clients can gate behavior on them.
+    fn server_info_handler(context: Arc<PrismaContext>) -> Response<Body> {
         let json = json!({
             "commit": env!("GIT_HASH"),
             "version": env!("CARGO_PKG_VERSION"),
-            "primary_connector": cx.context.primary_connector(),
+            "primary_connector": context.primary_connector(),
+            "features": context.features(),
         });
 
         let bytes = serde_json::to_vec(&json).unwrap();
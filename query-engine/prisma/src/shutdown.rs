@@ -0,0 +1,121 @@
+//! Graceful shutdown of the HTTP server on SIGTERM or Ctrl-C.
+use futures::future::{self, Future};
+use std::{
+    process,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{mpsc, watch};
+
+/// Exit code if in-flight requests did not finish within the shutdown timeout.
+pub const EXIT_CODE_TIMEOUT: i32 = 3;
+
+/// Resolves once the process is asked to terminate, which makes the server stop accepting connections.
+/// Draining the in-flight requests is bounded by `timeout`, after which the process exits forcefully.
+pub async fn signal(timeout: Duration) {
+    wait_for_termination().await;
+
+    info!(
+        "Shutting down, waiting up to {}s for in-flight requests to finish.",
+        timeout.as_secs()
+    );
+
+    tokio::spawn(async move {
+        tokio::time::delay_for(timeout).await;
+
+        error!("In-flight requests did not finish in time, exiting.");
+        process::exit(EXIT_CODE_TIMEOUT);
+    });
+}
+
+#[cfg(unix)]
+async fn wait_for_termination() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate()).expect("Failed to install the SIGTERM handler");
+
+    tokio::select! {
+        _ = terminate.recv() => (),
+        _ = tokio::signal::ctrl_c() => (),
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_termination() {
+    tokio::signal::ctrl_c()
+        .await
+        .expect("Failed to install the Ctrl-C handler");
+}
+
+/// Tracks the work the servers don't drain themselves: subscriptions on upgraded connections and interactive
+/// transactions. Both end once the shutdown starts, and the server waits for them before closing the pools.
+#[derive(Clone)]
+pub struct Drain {
+    shutting_down: watch::Receiver<bool>,
+    /// Taken when the shutdown starts, so that only the guards of running work keep the channel open.
+    tasks: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+}
+
+/// Keeps the shutdown waiting until it is dropped.
+pub struct DrainGuard {
+    _task: Option<mpsc::Sender<()>>,
+}
+
+/// Starts the shutdown of the work tracked by a `Drain`.
+pub struct DrainTrigger {
+    trigger: watch::Sender<bool>,
+    tasks: mpsc::Receiver<()>,
+    sender: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+}
+
+pub fn drain() -> (Drain, DrainTrigger) {
+    let (trigger, shutting_down) = watch::channel(false);
+    let (sender, tasks) = mpsc::channel(1);
+    let sender = Arc::new(Mutex::new(Some(sender)));
+
+    let drain = Drain {
+        shutting_down,
+        tasks: Arc::clone(&sender),
+    };
+
+    (drain, DrainTrigger { trigger, tasks, sender })
+}
+
+impl Drain {
+    /// Tracks work until the guard is dropped. Work started after the shutdown isn't waited for.
+    pub fn track(&self) -> DrainGuard {
+        DrainGuard {
+            _task: self.tasks.lock().unwrap().clone(),
+        }
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutting_down.borrow()
+    }
+
+    /// Resolves once the shutdown started.
+    pub fn shutting_down(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut shutting_down = self.shutting_down.clone();
+
+        async move {
+            while let Some(started) = shutting_down.recv().await {
+                if started {
+                    return;
+                }
+            }
+
+            // The trigger was dropped without starting the shutdown, which never happens then.
+            future::pending::<()>().await
+        }
+    }
+}
+
+impl DrainTrigger {
+    /// Tells the tracked work to end and waits for it.
+    pub async fn drain(mut self) {
+        self.trigger.broadcast(true).ok();
+        self.sender.lock().unwrap().take();
+
+        while self.tasks.recv().await.is_some() {}
+    }
+}
@@ -0,0 +1,118 @@
+//! Record change subscriptions over WebSockets.
+//!
+//! The client opens a WebSocket connection on `/subscriptions` and sends exactly one message
+//! containing a GraphQL subscription document, e.g. `{"query": "subscription { User Post }"}`,
+//! where every top-level field designates a model. The server then pushes one message per
+//! committed create / update / delete of the subscribed models until the connection is closed.
+//! Clients falling too far behind the events are disconnected, and all connections are closed when the
+//! server shuts down.
+use crate::{
+    context::PrismaContext, request_handlers::GraphQLProtocolAdapter, shutdown::Drain, PrismaError, PrismaResult,
+};
+use futures::{
+    future::{self, Either},
+    stream, SinkExt, StreamExt,
+};
+use graphql_parser as gql;
+use hyper::upgrade::Upgraded;
+use query_core::{response_ir::Responses, RecordEvent};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use std::sync::Arc;
+use tokio_tungstenite::{
+    tungstenite::{self, protocol::Role, Message},
+    WebSocketStream,
+};
+
+/// Magic GUID from RFC 6455 used to compute the handshake accept key.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionRequest {
+    query: String,
+}
+
+/// Computes the `Sec-WebSocket-Accept` header value for the given `Sec-WebSocket-Key`.
+pub(crate) fn accept_key(key: &[u8]) -> String {
+    let mut sha1 = Sha1::default();
+    sha1.input(key);
+    sha1.input(WEBSOCKET_GUID.as_bytes());
+
+    base64::encode(&sha1.result())
+}
+
+pub(crate) async fn serve(upgraded: Upgraded, ctx: Arc<PrismaContext>, drain: Drain) {
+    let ws = WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+    let (mut sink, mut stream) = ws.split();
+
+    let models = match future::select(stream.next(), Box::pin(drain.shutting_down())).await {
+        Either::Left((Some(Ok(Message::Text(text))), _)) => parse_subscription(&text, &ctx),
+        _ => return,
+    };
+
+    let models = match models {
+        Ok(models) => models,
+        Err(err) => {
+            let mut responses = Responses::default();
+            responses.insert_error(err);
+
+            let text = serde_json::to_string(&responses).unwrap();
+            sink.send(Message::text(text)).await.ok();
+
+            return;
+        }
+    };
+
+    debug!("Subscribed to events of models: {:?}", models);
+
+    let events = ctx
+        .executor()
+        .subscribe()
+        .filter(move |event| future::ready(models.contains(&event.model)))
+        .map(Some);
+
+    // The events end when the server shuts down, forwarding them then closes the connection.
+    let shutdown = stream::once(drain.shutting_down()).map(|_| None);
+
+    let forward = stream::select(events, shutdown)
+        .take_while(|event| future::ready(event.is_some()))
+        .filter_map(future::ready)
+        .map(|event| Ok::<_, tungstenite::Error>(Message::text(render_event(event))))
+        .forward(sink);
+
+    let closed = async move {
+        while let Some(Ok(message)) = stream.next().await {
+            if message.is_close() {
+                break;
+            }
+        }
+    };
+
+    future::select(Box::pin(forward), Box::pin(closed)).await;
+}
+
+fn parse_subscription(text: &str, ctx: &PrismaContext) -> PrismaResult<Vec<String>> {
+    let request: SubscriptionRequest = serde_json::from_str(text)?;
+    let gql_doc = gql::parse_query(&request.query)?;
+    let models = GraphQLProtocolAdapter::convert_subscription(gql_doc)?;
+
+    let internal_data_model = &ctx.query_schema().internal_data_model;
+
+    for model in models.iter() {
+        if internal_data_model.find_model(model).is_err() {
+            return Err(PrismaError::QueryConversionError(format!(
+                "Unknown model '{}' in subscription.",
+                model
+            )));
+        }
+    }
+
+    Ok(models)
+}
+
+fn render_event(event: RecordEvent) -> String {
+    let mut data = serde_json::Map::new();
+    data.insert(event.model.clone(), serde_json::to_value(&event).unwrap());
+
+    serde_json::to_string(&serde_json::json!({ "data": data })).unwrap()
+}
@@ -0,0 +1,67 @@
+//! OpenTelemetry integration.
+//!
+//! Spans created through `tracing` are exported via OTLP if an endpoint is configured.
+//! Incoming requests continue the trace of the caller if a W3C `traceparent` header is present.
+//!
+//! Every request is identified by a request id, which is recorded on the request span and with that
+//! part of all events logged while handling the request, down to the connectors.
+use hyper::{header::HeaderMap, Body, Request};
+use opentelemetry::{
+    api::{propagation::Extractor, TextMapFormat},
+    sdk::{self, propagation::TraceContextPropagator},
+};
+use tracing::Span;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+use uuid::Uuid;
+
+/// Keeps the OTLP exporter running. Pending spans are flushed when dropped.
+pub type Uninstall = opentelemetry_otlp::Uninstall;
+
+/// Sets up the OTLP exporter sending spans to the given collector endpoint.
+pub fn layer<S>(endpoint: &str) -> (OpenTelemetryLayer<S, sdk::Tracer>, Uninstall)
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    let (tracer, uninstall) = opentelemetry_otlp::new_pipeline()
+        .with_endpoint(endpoint)
+        .with_service_name("prisma-query-engine")
+        .install();
+
+    (tracing_opentelemetry::layer().with_tracer(tracer), uninstall)
+}
+
+/// Header carrying the request id, taken from the request if given and returned with the response.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The request id given by the caller, or a newly generated one.
+pub fn request_id(req: &Request<Body>) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+/// Creates the root span of an HTTP request, as a child of the remote `traceparent` if given.
+pub fn request_span(req: &Request<Body>, request_id: &str) -> Span {
+    let span = tracing::info_span!(
+        "prisma:request",
+        request_id,
+        method = %req.method(),
+        path = req.uri().path()
+    );
+
+    let parent = TraceContextPropagator::new().extract(&HeaderExtractor(req.headers()));
+
+    span.set_parent(&parent);
+    span
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl<'a> Extractor for HeaderExtractor<'a> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+}
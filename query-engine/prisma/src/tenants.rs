@@ -0,0 +1,188 @@
+//! Multi-tenant mode: serving multiple data models from one process.
+//!
+//! Each tenant is a named data model with its own context, i.e. its own query schema and connection pool.
+//! Tenants are loaded and unloaded at runtime through the management API of the HTTP server.
+use crate::{context::PrismaContext, PrismaError, PrismaResult};
+use datamodel::{ast::Expression, SourceOverride};
+use hyper::{Body, Request};
+use query_core::QueryLimits;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+/// Header addressing a request to a tenant, alternatively to the `/tenants/{name}` path prefix.
+pub const TENANT_HEADER: &str = "x-prisma-tenant";
+
+/// Path prefix of the tenant management API and of requests addressed to a tenant.
+pub const TENANTS_PATH: &str = "/tenants";
+
+/// The contexts requests are executed against.
+pub enum Contexts {
+    /// The single data model of the process, loaded at startup.
+    Single(Arc<PrismaContext>),
+    Tenants(Tenants),
+}
+
+impl Contexts {
+    /// Resolves the context a request is addressed to, and the path of the request relative to it.
+    pub fn resolve(&self, req: &Request<Body>) -> PrismaResult<(Arc<PrismaContext>, String)> {
+        let path = req.uri().path();
+
+        let tenants = match self {
+            Self::Single(context) => return Ok((Arc::clone(context), path.to_owned())),
+            Self::Tenants(tenants) => tenants,
+        };
+
+        let tenant_prefix = format!("{}/", TENANTS_PATH);
+
+        let (name, path) = if path.starts_with(&tenant_prefix) {
+            let tenant_path = &path[tenant_prefix.len()..];
+
+            match tenant_path.find('/') {
+                Some(idx) => tenant_path.split_at(idx),
+                None => (tenant_path, "/"),
+            }
+        } else {
+            match req.headers().get(TENANT_HEADER).and_then(|name| name.to_str().ok()) {
+                Some(name) => (name, path),
+                None => {
+                    return Err(PrismaError::InvocationError(format!(
+                        "No tenant given, use the `{}` header or the `{}/{{name}}` path prefix.",
+                        TENANT_HEADER, TENANTS_PATH
+                    )))
+                }
+            }
+        };
+
+        match tenants.get(name) {
+            Some(context) => Ok((context, path.to_owned())),
+            None => Err(PrismaError::InvocationError(format!("Unknown tenant '{}'.", name))),
+        }
+    }
+}
+
+/// The tenant name of a management API path, `/tenants/{name}`.
+pub fn tenant_name(path: &str) -> Option<&str> {
+    let tenant_prefix = format!("{}/", TENANTS_PATH);
+
+    if path.starts_with(&tenant_prefix) {
+        let name = &path[tenant_prefix.len()..];
+
+        if !name.is_empty() && !name.contains('/') {
+            return Some(name);
+        }
+    }
+
+    None
+}
+
+/// Tenant data models are sent through the management API, `env()` would let them read the environment variables of
+/// the server, e.g. the credentials of other tenants. Their URLs are given in the data model or the overrides instead.
+fn reject_env_functions(datamodel: &str) -> PrismaResult<()> {
+    let ast = datamodel::parse_schema_ast(datamodel)
+        .map_err(|errors| PrismaError::ConversionError(errors, datamodel.to_owned()))?;
+
+    let sources = ast
+        .sources()
+        .into_iter()
+        .map(|source| (&source.name.name, &source.properties));
+    let generators = ast
+        .generators()
+        .into_iter()
+        .map(|generator| (&generator.name.name, &generator.properties));
+
+    for (block, properties) in sources.chain(generators) {
+        if let Some(argument) = properties.iter().find(|argument| calls_env(&argument.value)) {
+            return Err(PrismaError::ConfigurationError(format!(
+                "Tenant data models can't use env(), found in `{}` of `{}`.",
+                argument.name.name, block
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn calls_env(expression: &Expression) -> bool {
+    match expression {
+        Expression::Function(name, arguments, _) => {
+            name == "env" || arguments.iter().any(|argument| calls_env(&argument.value))
+        }
+        Expression::Array(values, _) => values.iter().any(calls_env),
+        _ => false,
+    }
+}
+
+/// Settings shared by the contexts of all tenants.
+#[derive(Debug, Clone, Default)]
+pub struct TenantOptions {
+    pub legacy: bool,
+    pub force_transactions: bool,
+    pub enable_raw_queries: bool,
+    pub limits: QueryLimits,
+    pub graphql_errors: bool,
+}
+
+/// The loaded tenants by name.
+pub struct Tenants {
+    options: TenantOptions,
+    contexts: RwLock<HashMap<String, Arc<PrismaContext>>>,
+}
+
+impl Tenants {
+    pub fn new(options: TenantOptions) -> Self {
+        Self {
+            options,
+            contexts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Loads the data model of a tenant, replacing a previously loaded one of the same name.
+    /// Requests in flight against a replaced context finish before its connection pool is closed.
+    pub async fn load(
+        &self,
+        name: String,
+        datamodel: String,
+        datasource_overrides: Vec<SourceOverride>,
+    ) -> PrismaResult<()> {
+        reject_env_functions(&datamodel)?;
+
+        let context = PrismaContext::builder()
+            .legacy(self.options.legacy)
+            .force_transactions(self.options.force_transactions)
+            .enable_raw_queries(self.options.enable_raw_queries)
+            .limits(self.options.limits.clone())
+            .graphql_errors(self.options.graphql_errors)
+            .datamodel(datamodel)
+            .datasource_overrides(datasource_overrides)
+            .build()
+            .await?;
+
+        info!("Loaded tenant '{}'.", name);
+        self.contexts.write().unwrap().insert(name, Arc::new(context));
+
+        Ok(())
+    }
+
+    /// Unloads a tenant. Returns false if no tenant of that name is loaded.
+    pub fn unload(&self, name: &str) -> bool {
+        let unloaded = self.contexts.write().unwrap().remove(name).is_some();
+
+        if unloaded {
+            info!("Unloaded tenant '{}'.", name);
+        }
+
+        unloaded
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<PrismaContext>> {
+        self.contexts.read().unwrap().get(name).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.contexts.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
@@ -1 +1,45 @@
+mod access_policy;
+mod auth;
+mod batch;
+mod body;
+mod capabilities;
+mod cli;
+mod data_model_loader;
+mod datasources;
+mod defer;
 mod dmmf;
+mod fragments;
+mod graphql_response;
+mod grpc;
+mod headers;
+mod idempotency;
+mod introspection;
+mod json;
+mod limits;
+mod listener;
+mod nested_writes;
+mod persisted_queries;
+mod probes;
+mod request_id;
+mod routines;
+mod server_info;
+mod shutdown;
+mod subscriptions;
+mod telemetry;
+mod tenants;
+mod validation;
+
+use prisma_models::InternalDataModelRef;
+use query_core::{BuildMode, ConnectorCapabilities, QuerySchema, QuerySchemaBuilder};
+
+/// The internal data model of a test data model.
+fn internal_datamodel(datamodel: &str) -> InternalDataModelRef {
+    let lifted_datamodel = datamodel::parse_datamodel(datamodel).unwrap();
+
+    prisma_models::DatamodelConverter::convert(&lifted_datamodel).build("blah".to_owned())
+}
+
+/// The query schema of a test data model, as it is built for a connector with the capabilities.
+fn query_schema(datamodel: &str, capabilities: ConnectorCapabilities) -> QuerySchema {
+    QuerySchemaBuilder::new(&internal_datamodel(datamodel), &capabilities, BuildMode::Modern, false).build()
+}
@@ -0,0 +1,106 @@
+use super::internal_datamodel;
+use query_core::{
+    AccessPolicy, BuildMode, ConnectorCapabilities, CoreError, Operation, QueryGraphBuilder, QuerySchema,
+    QuerySchemaBuilder, QueryType, QueryValue, Selection,
+};
+use std::{collections::BTreeMap, sync::Arc};
+
+const DATAMODEL: &str = r#"
+    model User {
+        id       String @id
+        email    String @unique
+        password String
+    }
+"#;
+
+fn policy() -> AccessPolicy {
+    serde_json::from_str(r#"{ "models": { "User": { "fields": { "password": { "read": false } } } } }"#).unwrap()
+}
+
+fn get_query_schema(policy: &AccessPolicy) -> QuerySchema {
+    QuerySchemaBuilder::new(
+        &internal_datamodel(DATAMODEL),
+        &ConnectorCapabilities::empty(),
+        BuildMode::Modern,
+        false,
+    )
+    .access_policy(policy)
+    .build()
+}
+
+fn find_many_users(argument: (&str, QueryValue)) -> Operation {
+    Operation::Read(Selection {
+        name: "findManyUser".to_owned(),
+        alias: None,
+        arguments: vec![(argument.0.to_owned(), argument.1)],
+        nested_selections: vec![Selection {
+            name: "id".to_owned(),
+            alias: None,
+            arguments: vec![],
+            nested_selections: vec![],
+        }],
+    })
+}
+
+fn password_filter() -> (&'static str, QueryValue) {
+    let mut filter = BTreeMap::new();
+    filter.insert("password".to_owned(), QueryValue::String("hunter2".to_owned()));
+
+    ("where", QueryValue::Object(filter))
+}
+
+fn password_order() -> (&'static str, QueryValue) {
+    ("orderBy", QueryValue::Enum("password_ASC".to_owned()))
+}
+
+/// Checks an operation the schema without the policy accepts against the policy, like the executor does.
+fn check(operation: Operation) -> Result<(), CoreError> {
+    let query_schema = Arc::new(get_query_schema(&AccessPolicy::default()));
+    let (query, _) = QueryGraphBuilder::new(query_schema).map_operation(operation).unwrap();
+
+    match query {
+        QueryType::Graph(graph) => policy().check_graph(&graph),
+        QueryType::Raw { .. } => policy().check_raw_query(),
+    }
+}
+
+#[test]
+fn hidden_fields_are_not_filter_or_order_by_inputs() {
+    let policy = policy();
+    let query_schema = Arc::new(get_query_schema(&policy));
+
+    for argument in vec![password_filter(), password_order()] {
+        let result = QueryGraphBuilder::new(Arc::clone(&query_schema)).map_operation(find_many_users(argument));
+
+        assert!(result.is_err());
+    }
+
+    let email = ("orderBy", QueryValue::Enum("email_ASC".to_owned()));
+    let result = QueryGraphBuilder::new(query_schema).map_operation(find_many_users(email));
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn filtering_by_hidden_fields_is_denied() {
+    match check(find_many_users(password_filter())) {
+        Err(CoreError::AccessDenied(operation)) => assert_eq!(operation, "read the field User.password"),
+        result => panic!("Expected the filter to be denied, got {:?}", result),
+    }
+}
+
+#[test]
+fn ordering_by_hidden_fields_is_denied() {
+    match check(find_many_users(password_order())) {
+        Err(CoreError::AccessDenied(operation)) => assert_eq!(operation, "read the field User.password"),
+        result => panic!("Expected the ordering to be denied, got {:?}", result),
+    }
+}
+
+#[test]
+fn filtering_by_readable_fields_is_allowed() {
+    let mut filter = BTreeMap::new();
+    filter.insert("email".to_owned(), QueryValue::String("jane@example.com".to_owned()));
+
+    assert!(check(find_many_users(("where", QueryValue::Object(filter)))).is_ok());
+}
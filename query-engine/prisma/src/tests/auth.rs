@@ -0,0 +1,72 @@
+use crate::auth::{Access, Authenticator};
+use hyper::{header, Body, Request};
+use query_core::{Operation, Selection};
+
+fn request(authorization: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().uri("/");
+
+    if let Some(authorization) = authorization {
+        builder = builder.header(header::AUTHORIZATION, authorization);
+    }
+
+    builder.body(Body::empty()).unwrap()
+}
+
+fn authenticator() -> Authenticator {
+    Authenticator::new(
+        vec![
+            ("admin-token".to_owned(), Access::ReadWrite),
+            ("reader-token".to_owned(), Access::ReadOnly),
+        ],
+        None,
+    )
+}
+
+#[tokio::test]
+async fn static_tokens_are_authenticated_with_their_access() {
+    let authenticator = authenticator();
+
+    let admin = authenticator.authenticate(&request(Some("Bearer admin-token"))).await;
+    let reader = authenticator.authenticate(&request(Some("bearer reader-token"))).await;
+
+    assert_eq!(admin.unwrap(), Access::ReadWrite);
+    assert_eq!(reader.unwrap(), Access::ReadOnly);
+}
+
+#[tokio::test]
+async fn missing_and_unknown_tokens_are_rejected() {
+    let authenticator = authenticator();
+
+    assert!(authenticator.authenticate(&request(None)).await.is_err());
+    assert!(authenticator
+        .authenticate(&request(Some("Bearer other")))
+        .await
+        .is_err());
+    assert!(authenticator.authenticate(&request(Some("admin-token"))).await.is_err());
+}
+
+#[tokio::test]
+async fn requests_have_full_access_without_authentication() {
+    let access = Authenticator::default().authenticate(&request(None)).await;
+
+    assert_eq!(access.unwrap(), Access::ReadWrite);
+}
+
+#[test]
+fn access_that_was_not_authenticated_is_read_only() {
+    assert_eq!(Access::default(), Access::ReadOnly);
+}
+
+#[test]
+fn read_only_access_rejects_mutations() {
+    let selection = || Selection {
+        name: "createOneUser".to_owned(),
+        alias: None,
+        arguments: vec![],
+        nested_selections: vec![],
+    };
+
+    assert!(Access::ReadOnly.authorize(&Operation::Read(selection())).is_ok());
+    assert!(Access::ReadOnly.authorize(&Operation::Write(selection())).is_err());
+    assert!(Access::ReadWrite.authorize(&Operation::Write(selection())).is_ok());
+}
@@ -0,0 +1,89 @@
+use crate::request_handlers::{execute_batch, response_ir::*, BatchResponse, BatchStatus, PrismaResponse};
+use futures::future::{BoxFuture, FutureExt};
+use prisma_models::PrismaValue;
+use serde_json::json;
+
+fn data(value: &str) -> PrismaResponse {
+    let mut responses = Responses::default();
+    responses.insert_data("value", Item::Value(PrismaValue::String(value.to_owned())));
+
+    PrismaResponse::Single(responses)
+}
+
+fn error(message: &str) -> PrismaResponse {
+    let mut responses = Responses::default();
+    responses.insert_error(user_facing_errors::Error::new_non_panic_with_current_backtrace(
+        message.to_owned(),
+    ));
+
+    PrismaResponse::Single(responses)
+}
+
+fn query(response: PrismaResponse) -> BoxFuture<'static, PrismaResponse> {
+    async move { response }.boxed()
+}
+
+#[tokio::test]
+async fn responses_keep_the_position_and_name_of_their_operation() {
+    let responses = execute_batch(vec![
+        (Some("first".to_owned()), query(data("a"))),
+        (None, query(error("failed"))),
+        (Some("third".to_owned()), query(data("c"))),
+    ])
+    .await;
+
+    let summary: Vec<_> = responses
+        .iter()
+        .map(|response| (response.index, response.operation_name.as_deref(), response.status))
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            (0, Some("first"), BatchStatus::Ok),
+            (1, None, BatchStatus::Error),
+            (2, Some("third"), BatchStatus::Ok),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn a_panicking_query_only_fails_its_own_response() {
+    let panicking: BoxFuture<'static, PrismaResponse> = async { panic!("query panicked") }.boxed();
+
+    let responses = execute_batch(vec![(None, query(data("a"))), (None, panicking)]).await;
+
+    assert_eq!(responses[0].status, BatchStatus::Ok);
+    assert_eq!(responses[1].status, BatchStatus::Error);
+    assert!(responses[1].response.has_errors());
+}
+
+#[test]
+fn single_responses_are_serialized_next_to_the_operation() {
+    let response = BatchResponse::new(0, Some("first".to_owned()), data("a"));
+
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({ "index": 0, "operationName": "first", "status": "ok", "data": { "value": "a" } })
+    );
+}
+
+#[test]
+fn nested_batches_are_serialized_under_batch() {
+    let nested = PrismaResponse::Multi(vec![
+        BatchResponse::new(0, None, data("a")),
+        BatchResponse::new(1, None, error("failed")),
+    ]);
+    let response = BatchResponse::new(2, None, nested);
+
+    let json = serde_json::to_value(&response).unwrap();
+
+    assert_eq!(json["index"], json!(2));
+    assert_eq!(json["status"], json!("error"));
+    assert_eq!(
+        json["batch"][0],
+        json!({ "index": 0, "status": "ok", "data": { "value": "a" } })
+    );
+    assert_eq!(json["batch"][1]["status"], json!("error"));
+    assert_eq!(json["batch"][1]["errors"].as_array().unwrap().len(), 1);
+}
@@ -0,0 +1,14 @@
+use crate::body::Encoding;
+
+#[test]
+fn encoding_negotiation_prefers_brotli() {
+    assert_eq!(Encoding::negotiate(Some("gzip, deflate, br")), Encoding::Brotli);
+    assert_eq!(Encoding::negotiate(Some("gzip")), Encoding::Gzip);
+    assert_eq!(Encoding::negotiate(None), Encoding::Identity);
+}
+
+#[test]
+fn encoding_negotiation_skips_refused_codings() {
+    assert_eq!(Encoding::negotiate(Some("br;q=0, gzip;q=0.5")), Encoding::Gzip);
+    assert_eq!(Encoding::negotiate(Some("gzip;q=0")), Encoding::Identity);
+}
@@ -0,0 +1,50 @@
+use super::query_schema;
+use crate::request_handlers::graphql::introspection;
+use query_core::{ConnectorCapabilities, QuerySchema};
+
+const DATAMODEL: &str = r#"
+    model User {
+        id   String   @id
+        role Role
+        tags String[]
+    }
+
+    enum Role {
+        ADMIN
+        USER
+    }
+"#;
+
+fn input_field_names(query_schema: &QuerySchema, type_name: &str) -> Vec<String> {
+    let query = format!("{{ __type(name: \"{}\") {{ inputFields {{ name }} }} }}", type_name);
+    let mut gql_doc = graphql_parser::parse_query(&query).unwrap();
+    let introspection = introspection::resolve(&mut gql_doc, None, query_schema).unwrap();
+
+    introspection[0].1["inputFields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| field["name"].as_str().unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn enum_filters_require_the_enums_capability() {
+    let with_enums = query_schema(DATAMODEL, ConnectorCapabilities::ENUMS);
+    let without_enums = query_schema(DATAMODEL, ConnectorCapabilities::empty());
+
+    assert!(input_field_names(&with_enums, "UserWhereInput").contains(&"role_in".to_owned()));
+    assert!(!input_field_names(&without_enums, "UserWhereInput")
+        .iter()
+        .any(|name| name.starts_with("role")));
+}
+
+#[test]
+fn scalar_list_inputs_require_the_scalar_lists_capability() {
+    let with_lists = query_schema(DATAMODEL, ConnectorCapabilities::SCALAR_LISTS);
+    let without_lists = query_schema(DATAMODEL, ConnectorCapabilities::empty());
+
+    assert!(input_field_names(&with_lists, "UserCreateInput").contains(&"tags".to_owned()));
+    assert!(!input_field_names(&without_lists, "UserCreateInput").contains(&"tags".to_owned()));
+    assert!(input_field_names(&without_lists, "UserCreateInput").contains(&"role".to_owned()));
+}
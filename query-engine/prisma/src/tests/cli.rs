@@ -0,0 +1,134 @@
+use super::internal_datamodel;
+use crate::{
+    auth::Access,
+    cli::{self, CliCommand},
+    context::PrismaContext,
+    request_handlers::{
+        GraphQLSchemaRenderer, GraphQlBody, GraphQlRequestHandler, PrismaRequest, QuerySchemaRenderer, RequestHandler,
+        SingleQuery,
+    },
+    PrismaOpt,
+};
+use prisma_engine::exec_loader;
+use query_core::{BuildMode, QuerySchemaBuilder};
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
+use structopt::StructOpt;
+
+fn command(args: &[&str]) -> CliCommand {
+    CliCommand::try_from(&PrismaOpt::from_iter(args)).unwrap()
+}
+
+#[test]
+fn the_sdl_command_takes_the_schema_options() {
+    match command(&["prisma", "--legacy", "--enable_raw_queries", "cli", "--sdl"]) {
+        CliCommand::Sdl(request) => {
+            assert!(request.enable_raw_queries);
+            assert!(match request.build_mode {
+                BuildMode::Legacy => true,
+                BuildMode::Modern => false,
+            });
+        }
+        _ => panic!("--sdl must be parsed to the SDL command."),
+    }
+
+    match command(&["prisma", "cli", "--sdl"]) {
+        CliCommand::Sdl(request) => {
+            assert!(!request.enable_raw_queries);
+            assert!(match request.build_mode {
+                BuildMode::Modern => true,
+                BuildMode::Legacy => false,
+            });
+        }
+        _ => panic!("--sdl must be parsed to the SDL command."),
+    }
+}
+
+#[test]
+fn the_sdl_covers_the_full_query_schema() {
+    let datamodel = r#"
+        model User {
+            id   String @id
+            name String
+        }
+    "#;
+
+    // The SDL command builds the schema without data sources, with the capabilities of all connectors.
+    let capabilities = exec_loader::shared_capabilities(&[]);
+    let query_schema =
+        QuerySchemaBuilder::new(&internal_datamodel(datamodel), &capabilities, BuildMode::Modern, true).build();
+
+    let sdl = GraphQLSchemaRenderer::render(Arc::new(query_schema));
+
+    assert!(sdl.contains("type Query {"), "{}", sdl);
+    assert!(sdl.contains("type Mutation {"), "{}", sdl);
+    assert!(sdl.contains("type User {"), "{}", sdl);
+    assert!(sdl.contains("findManyUser("), "{}", sdl);
+    assert!(sdl.contains("executeRaw("), "{}", sdl);
+}
+
+#[test]
+fn the_validate_command_is_also_available_as_dry_run() {
+    for flag in &["--validate", "--dry_run"] {
+        assert!(match command(&["prisma", "cli", *flag]) {
+            CliCommand::Validate(_) => true,
+            _ => false,
+        });
+    }
+}
+
+#[tokio::test]
+async fn models_without_a_table_fail_validation() {
+    let name = format!("validate_{}", uuid::Uuid::new_v4().to_simple());
+    let path = std::env::temp_dir().join(format!("{}.db", name));
+
+    let datamodel = format!(
+        r#"
+        datasource db {{
+            provider = "sqlite"
+            url      = "file:{}"
+        }}
+
+        model User {{
+            id String @id
+        }}
+
+        model Post {{
+            id String @id
+        }}
+        "#,
+        path.to_str().unwrap()
+    );
+
+    let ctx = PrismaContext::builder()
+        .datamodel(datamodel)
+        .enable_raw_queries(true)
+        .build()
+        .await
+        .unwrap();
+
+    let create_table = format!(
+        r#"mutation {{ executeRaw(query: "CREATE TABLE \"{}\".\"User\" (id TEXT PRIMARY KEY)") }}"#,
+        name
+    );
+
+    let req = PrismaRequest {
+        body: GraphQlBody::Single(SingleQuery::new(create_table, None, HashMap::new())),
+        headers: HashMap::new(),
+        path: String::new(),
+        access: Access::ReadWrite,
+    };
+
+    let ctx = Arc::new(ctx);
+    assert!(!GraphQlRequestHandler.handle(req, &ctx).await.has_errors());
+
+    let models = cli::validate_models(&ctx).await.unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let mut results: Vec<_> = models
+        .iter()
+        .map(|model| (model.model.as_str(), model.errors.is_empty()))
+        .collect();
+    results.sort();
+
+    assert_eq!(results, vec![("Post", false), ("User", true)]);
+}
@@ -0,0 +1,38 @@
+use crate::data_model_loader::{load_configuration, parse_source_override};
+use datamodel::SourceOverride;
+
+const DATAMODEL: &str = r#"
+    datasource db {
+        provider = "sqlite"
+        url      = "file:dev.db"
+    }
+"#;
+
+#[test]
+fn datasource_overrides_are_parsed_from_name_and_url() {
+    let source_override = parse_source_override("db=postgresql://localhost:5432/test?schema=public").unwrap();
+
+    assert_eq!(
+        source_override,
+        SourceOverride {
+            name: "db".to_owned(),
+            url: "postgresql://localhost:5432/test?schema=public".into(),
+        }
+    );
+
+    assert!(parse_source_override("postgresql://localhost:5432/test").is_err());
+    assert!(parse_source_override("db=").is_err());
+}
+
+#[test]
+fn datasource_overrides_must_name_a_datasource_of_the_data_model() {
+    let source_override = |name: &str| SourceOverride {
+        name: name.to_owned(),
+        url: "file:test.db".into(),
+    };
+
+    let configuration = load_configuration(DATAMODEL, &[source_override("db")]).unwrap();
+    assert_eq!(configuration.datasources[0].url().value.expose(), "file:test.db");
+
+    assert!(load_configuration(DATAMODEL, &[source_override("dB")]).is_err());
+}
@@ -0,0 +1,137 @@
+use crate::{
+    auth::Access,
+    context::PrismaContext,
+    request_handlers::{GraphQlBody, GraphQlRequestHandler, PrismaRequest, RequestHandler, SingleQuery},
+};
+use serde_json::{json, Value};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+/// A SQLite database in a file of its own, removed when dropped. Tables are qualified by the file stem.
+struct Database {
+    path: PathBuf,
+    name: String,
+}
+
+impl Database {
+    fn new(prefix: &str) -> Self {
+        let name = format!("{}_{}", prefix, uuid::Uuid::new_v4().to_simple());
+        let path = std::env::temp_dir().join(format!("{}.db", name));
+
+        Database { path, name }
+    }
+
+    fn url(&self) -> String {
+        format!("file:{}", self.path.to_str().unwrap())
+    }
+}
+
+impl Drop for Database {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+async fn context(datamodel: String, enable_raw_queries: bool) -> Arc<PrismaContext> {
+    let ctx = PrismaContext::builder()
+        .datamodel(datamodel)
+        .enable_raw_queries(enable_raw_queries)
+        .build()
+        .await
+        .unwrap();
+
+    Arc::new(ctx)
+}
+
+async fn request(ctx: &Arc<PrismaContext>, query: &str) -> Value {
+    let req = PrismaRequest {
+        body: GraphQlBody::Single(SingleQuery::new(query.to_owned(), None, HashMap::new())),
+        headers: HashMap::new(),
+        path: String::new(),
+        access: Access::ReadWrite,
+    };
+
+    serde_json::to_value(GraphQlRequestHandler.handle(req, ctx).await).unwrap()
+}
+
+/// A context of the database alone, with a table for the model.
+async fn single_datasource(db: &Database, model: &str) -> Arc<PrismaContext> {
+    let datamodel = format!(
+        r#"
+        datasource db {{
+            provider = "sqlite"
+            url      = "{}"
+        }}
+
+        model {} {{
+            id String @id
+        }}
+        "#,
+        db.url(),
+        model
+    );
+
+    let ctx = context(datamodel, true).await;
+    let create_table = format!(
+        r#"mutation {{ executeRaw(query: "CREATE TABLE \"{}\".\"{}\" (id TEXT PRIMARY KEY)") }}"#,
+        db.name, model
+    );
+
+    request(&ctx, &create_table).await;
+    ctx
+}
+
+#[tokio::test]
+async fn queries_on_models_run_on_the_database_of_their_datasource() {
+    let main = Database::new("main");
+    let analytics = Database::new("analytics");
+
+    let main_ctx = single_datasource(&main, "User").await;
+    let analytics_ctx = single_datasource(&analytics, "Event").await;
+
+    let datamodel = format!(
+        r#"
+        datasource main {{
+            provider = "sqlite"
+            url      = "{}"
+        }}
+
+        datasource analytics {{
+            provider = "sqlite"
+            url      = "{}"
+        }}
+
+        model User {{
+            id String @id
+        }}
+
+        model Event {{
+            id String @id
+
+            @@datasource("analytics")
+        }}
+        "#,
+        main.url(),
+        analytics.url()
+    );
+
+    let ctx = context(datamodel, false).await;
+
+    let created = request(
+        &ctx,
+        r#"mutation { createOneUser(data: { id: "user" }) { id } createOneEvent(data: { id: "event" }) { id } }"#,
+    )
+    .await;
+
+    assert_eq!(created["data"]["createOneUser"], json!({ "id": "user" }));
+    assert_eq!(created["data"]["createOneEvent"], json!({ "id": "event" }));
+
+    let users = request(&main_ctx, "{ findManyUser { id } }").await;
+    let events = request(&analytics_ctx, "{ findManyEvent { id } }").await;
+
+    assert_eq!(users["data"]["findManyUser"], json!([{ "id": "user" }]));
+    assert_eq!(events["data"]["findManyEvent"], json!([{ "id": "event" }]));
+
+    // Reads of the models of the other datasource run on its database as well.
+    let events = request(&ctx, "{ findManyEvent { id } }").await;
+    assert_eq!(events["data"]["findManyEvent"], json!([{ "id": "event" }]));
+}
@@ -0,0 +1,183 @@
+use super::query_schema;
+use crate::request_handlers::graphql::GraphQLProtocolAdapter;
+use query_core::{
+    restrict_to_records, ConnectorCapabilities, DeferredSelection, Operation, QueryDocument, QueryValue, Selection,
+};
+use std::collections::{BTreeMap, HashMap};
+
+const DATAMODEL: &str = r#"
+    model User {
+        id    String @id
+        name  String
+        email String
+        posts Post[]
+    }
+
+    model Post {
+        id       String @id
+        title    String
+        author   User   @relation(references: [id])
+        comments Comment[]
+    }
+
+    model Comment {
+        id   String @id
+        text String
+        post Post   @relation(references: [id])
+    }
+"#;
+
+fn convert_deferred(query: &str) -> (QueryDocument, Vec<DeferredSelection>) {
+    let gql_doc = graphql_parser::parse_query(query).unwrap();
+
+    GraphQLProtocolAdapter::convert_deferred(gql_doc, None, &HashMap::new()).unwrap()
+}
+
+fn selection(operation: &Operation) -> &Selection {
+    match operation {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    }
+}
+
+fn keys(selections: &[Selection]) -> Vec<&str> {
+    selections.iter().map(|s| s.response_key()).collect()
+}
+
+#[test]
+fn deferred_fields_and_fragments_are_left_out_with_their_path() {
+    let (query_doc, deferred) = convert_deferred(
+        r#"
+        {
+            findManyUser(where: { name: "Alice" }) {
+                id
+                posts {
+                    title
+                    comments @defer(label: "comments") { text }
+                }
+                ... @defer { email }
+            }
+        }
+        "#,
+    );
+
+    let user = selection(&query_doc.operations[0]);
+
+    assert_eq!(keys(&user.nested_selections), &["id", "posts"]);
+    assert_eq!(keys(&user.nested_selections[1].nested_selections), &["title"]);
+
+    assert_eq!(deferred.len(), 2);
+    assert_eq!(deferred[0].path, &["findManyUser", "posts"]);
+    assert_eq!(deferred[0].label.as_deref(), Some("comments"));
+    assert_eq!(keys(&deferred[0].selections), &["comments"]);
+    assert_eq!(deferred[1].path, &["findManyUser"]);
+    assert_eq!(keys(&deferred[1].selections), &["email"]);
+
+    // The deferred read has the arguments of the operation, but only selects the path and the deferred fields.
+    let operation = deferred[0].operation(&query_doc).unwrap();
+    let user = selection(&operation);
+
+    assert_eq!(user.arguments, selection(&query_doc.operations[0]).arguments);
+    assert_eq!(keys(&user.nested_selections), &["posts"]);
+    assert_eq!(keys(&user.nested_selections[0].nested_selections), &["comments"]);
+    assert_eq!(
+        keys(&user.nested_selections[0].nested_selections[0].nested_selections),
+        &["text"]
+    );
+}
+
+#[test]
+fn defer_is_ignored_where_selections_cannot_be_deferred() {
+    let (query_doc, deferred) = convert_deferred(
+        r#"
+        {
+            findManyUser @defer {
+                id
+                posts @defer(if: false) { id }
+                comments @defer { text author @defer { name } }
+            }
+        }
+        "#,
+    );
+
+    let user = selection(&query_doc.operations[0]);
+
+    // Root fields and selections nested in deferred ones are selected right away.
+    assert_eq!(keys(&user.nested_selections), &["id", "posts"]);
+    assert_eq!(deferred.len(), 1);
+    assert_eq!(keys(&deferred[0].selections[0].nested_selections), &["text", "author"]);
+
+    let (query_doc, deferred) = convert_deferred("mutation { createUser(data: {}) { id posts @defer { id } } }");
+
+    assert_eq!(
+        keys(&selection(&query_doc.operations[0]).nested_selections),
+        &["id", "posts"]
+    );
+    assert!(deferred.is_empty());
+}
+
+#[test]
+fn deferred_reads_select_the_identifiers_along_the_path() {
+    let (mut query_doc, deferred) = convert_deferred(
+        r#"
+        {
+            findManyUser(where: { name: "Alice" }, first: 2) {
+                posts {
+                    title
+                    comments @defer { text }
+                }
+            }
+        }
+        "#,
+    );
+
+    let query_schema = query_schema(DATAMODEL, ConnectorCapabilities::empty());
+    assert!(deferred[0].select_ids(&mut query_doc, &query_schema).is_some());
+
+    let user = selection(&query_doc.operations[0]);
+
+    assert_eq!(keys(&user.nested_selections), &["posts", "__deferred_id_id"]);
+    assert_eq!(
+        keys(&user.nested_selections[0].nested_selections),
+        &["title", "__deferred_id_id"]
+    );
+
+    // The deferred read selects the same identifiers, to match its records with the ones of the document.
+    let operation = deferred[0].operation(&query_doc).unwrap();
+    let user = selection(&operation);
+
+    assert_eq!(keys(&user.nested_selections), &["__deferred_id_id", "posts"]);
+    assert_eq!(
+        keys(&user.nested_selections[1].nested_selections),
+        &["__deferred_id_id", "comments"]
+    );
+
+    // Reads of lists are restricted to the records the document returned.
+    let mut record = BTreeMap::new();
+    record.insert("id".to_owned(), QueryValue::String("alice".to_owned()));
+
+    let operation = restrict_to_records(operation, vec![record.clone()]);
+    let mut filter = BTreeMap::new();
+    filter.insert("OR".to_owned(), QueryValue::List(vec![QueryValue::Object(record)]));
+
+    assert_eq!(
+        selection(&operation).arguments,
+        vec![("where".to_owned(), QueryValue::Object(filter))]
+    );
+}
+
+#[test]
+fn deferred_selections_outside_of_records_are_not_read() {
+    let (mut query_doc, deferred) =
+        convert_deferred("{ findManyUser { posts { id } } findManyPost { ... @defer { title } } }");
+
+    let unknown = DeferredSelection {
+        path: vec!["findManyUser".to_owned(), "unknown".to_owned()],
+        label: None,
+        selections: vec![],
+    };
+
+    let query_schema = query_schema(DATAMODEL, ConnectorCapabilities::empty());
+
+    assert!(unknown.select_ids(&mut query_doc, &query_schema).is_none());
+    assert!(deferred[0].select_ids(&mut query_doc, &query_schema).is_some());
+}
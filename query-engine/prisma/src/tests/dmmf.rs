@@ -1,13 +1,11 @@
-use query_core::{BuildMode, QuerySchema, QuerySchemaBuilder, SupportedCapabilities};
+use super::query_schema;
+use query_core::{ConnectorCapabilities, QuerySchema};
 use std::sync::Arc;
 
 fn get_query_schema(datamodel_string: &str) -> (QuerySchema, datamodel::dml::Datamodel) {
-    let lifted_datamodel = datamodel::parse_datamodel(datamodel_string).unwrap();
-    let internal_datamodel = prisma_models::DatamodelConverter::convert(&lifted_datamodel).build("blah".to_owned());
-    let supported_capabilities = SupportedCapabilities::empty();
     (
-        QuerySchemaBuilder::new(&internal_datamodel, &supported_capabilities, BuildMode::Modern, false).build(),
-        lifted_datamodel,
+        query_schema(datamodel_string, ConnectorCapabilities::all()),
+        datamodel::parse_datamodel(datamodel_string).unwrap(),
     )
 }
 
@@ -75,3 +73,46 @@ fn dmmf_create_inputs_without_fields_for_parent_records_are_correct() {
         ]
     );
 }
+
+#[test]
+fn dmmf_output_types_expose_the_foreign_keys_of_inlined_relations() {
+    let dm = r#"
+        model User {
+            id    Int    @id
+            posts Post[]
+        }
+
+        model Post {
+            id     Int   @id
+            author User? @relation(references: [id])
+        }
+    "#;
+
+    let (query_schema, datamodel) = get_query_schema(dm);
+
+    let dmmf = crate::dmmf::render_dmmf(&datamodel, Arc::new(query_schema));
+
+    let output_fields = |name: &str| -> Vec<(String, String, bool)> {
+        dmmf.schema
+            .output_types
+            .iter()
+            .find(|output| output.name == name)
+            .expect("finding output type")
+            .fields
+            .iter()
+            .map(|f| (f.name.clone(), f.output_type.typ.clone(), f.output_type.is_required))
+            .collect()
+    };
+
+    assert!(output_fields("Post").contains(&("authorId".to_owned(), "Int".to_owned(), false)));
+    assert!(!output_fields("User").iter().any(|(name, _, _)| name == "postsId"));
+}
+
+#[test]
+fn dmmf_version_negotiation_rejects_outdated_clients() {
+    use crate::dmmf::{negotiate_version, DMMF_VERSION};
+
+    assert_eq!(negotiate_version(None).unwrap(), DMMF_VERSION);
+    assert_eq!(negotiate_version(Some(DMMF_VERSION + 1)).unwrap(), DMMF_VERSION);
+    assert!(negotiate_version(Some(DMMF_VERSION - 1)).is_err());
+}
@@ -0,0 +1,128 @@
+use crate::request_handlers::graphql::GraphQLProtocolAdapter;
+use query_core::{Operation, QueryDocument, Selection};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn convert(query: &str, variables: serde_json::Value) -> crate::PrismaResult<QueryDocument> {
+    let gql_doc = graphql_parser::parse_query(query).unwrap();
+    let variables: HashMap<String, serde_json::Value> = serde_json::from_value(variables).unwrap();
+
+    GraphQLProtocolAdapter::convert(gql_doc, None, &variables)
+}
+
+fn root_selection(query_doc: &QueryDocument) -> &Selection {
+    match &query_doc.operations[0] {
+        Operation::Read(selection) | Operation::Write(selection) => selection,
+    }
+}
+
+fn keys(selections: &[Selection]) -> Vec<&str> {
+    selections.iter().map(|s| s.response_key()).collect()
+}
+
+#[test]
+fn fragment_spreads_and_inline_fragments_are_spread_into_the_selection_set() {
+    let query_doc = convert(
+        r#"
+        query {
+            findManyUser {
+                id
+                ...UserFields
+                ... on User { email }
+            }
+        }
+
+        fragment UserFields on User {
+            name
+            posts { ...PostFields }
+        }
+
+        fragment PostFields on Post { title }
+        "#,
+        json!({}),
+    )
+    .unwrap();
+
+    let user = root_selection(&query_doc);
+
+    assert_eq!(query_doc.operations.len(), 1);
+    assert_eq!(keys(&user.nested_selections), &["id", "name", "posts", "email"]);
+    assert_eq!(keys(&user.nested_selections[2].nested_selections), &["title"]);
+}
+
+#[test]
+fn fields_selected_more_than_once_are_merged() {
+    let query_doc = convert(
+        r#"
+        {
+            findManyUser {
+                posts { id }
+                ... on User { posts { title } }
+            }
+        }
+        "#,
+        json!({}),
+    )
+    .unwrap();
+
+    let user = root_selection(&query_doc);
+
+    assert_eq!(keys(&user.nested_selections), &["posts"]);
+    assert_eq!(keys(&user.nested_selections[0].nested_selections), &["id", "title"]);
+}
+
+#[test]
+fn conflicting_fields_under_the_same_response_key_are_rejected() {
+    let result = convert(
+        r#"
+        {
+            findManyUser {
+                value: id
+                ... on User { value: name }
+            }
+        }
+        "#,
+        json!({}),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn skip_and_include_are_evaluated_against_the_variables() {
+    let query_doc = convert(
+        r#"
+        query($withName: Boolean, $withoutPosts: Boolean) {
+            findManyUser {
+                id
+                name @include(if: $withName)
+                email @skip(if: true)
+                posts @skip(if: $withoutPosts) { id }
+                ... @include(if: false) { createdAt }
+            }
+        }
+        "#,
+        json!({ "withName": true, "withoutPosts": true }),
+    )
+    .unwrap();
+
+    assert_eq!(keys(&root_selection(&query_doc).nested_selections), &["id", "name"]);
+}
+
+#[test]
+fn directives_without_a_boolean_condition_are_rejected() {
+    let query = "{ findManyUser { id name @include(if: $withName) } }";
+
+    assert!(convert(query, json!({})).is_err());
+    assert!(convert(query, json!({ "withName": "yes" })).is_err());
+}
+
+#[test]
+fn unknown_and_recursive_fragments_are_rejected() {
+    assert!(convert("{ findManyUser { ...UserFields } }", json!({})).is_err());
+    assert!(convert(
+        "{ findManyUser { ...UserFields } } fragment UserFields on User { id ...UserFields }",
+        json!({})
+    )
+    .is_err());
+}
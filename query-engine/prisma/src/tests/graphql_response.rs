@@ -0,0 +1,100 @@
+use crate::{
+    auth::Access,
+    context::PrismaContext,
+    request_handlers::{
+        response_ir::{Item, ResponseError, Responses},
+        GraphQlBody, GraphQlRequestHandler, GraphQlResponse, PrismaRequest, RequestHandler, SingleQuery,
+    },
+};
+use prisma_models::PrismaValue;
+use serde_json::{json, Value};
+use std::{collections::HashMap, sync::Arc};
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+
+fn error(message: &str) -> ResponseError {
+    user_facing_errors::Error::new_non_panic_with_current_backtrace(message.to_owned()).into()
+}
+
+fn responses(errors: Vec<ResponseError>) -> Responses {
+    let mut responses = Responses::default();
+    responses.insert_partial_data(
+        "findOneUser",
+        Item::Value(PrismaValue::String("data".to_owned())),
+        errors,
+    );
+    responses
+}
+
+#[test]
+fn errors_of_failed_operations_replace_their_data() {
+    let mut response = GraphQlResponse::default();
+    response.insert_operation_result("findOneUser".to_owned(), None, responses(vec![error("failed")]));
+
+    let json = serde_json::to_value(&response).unwrap();
+
+    assert_eq!(json["data"]["findOneUser"], Value::Null);
+    assert_eq!(json["errors"][0]["path"], json!(["findOneUser"]));
+}
+
+#[test]
+fn errors_of_failed_nested_reads_keep_the_partial_data() {
+    let path = vec!["findOneUser".to_owned(), "posts".to_owned()];
+    let mut response = GraphQlResponse::default();
+    response.insert_operation_result(
+        "findOneUser".to_owned(),
+        None,
+        responses(vec![error("failed").with_path(path)]),
+    );
+
+    let json = serde_json::to_value(&response).unwrap();
+
+    assert_eq!(json["data"]["findOneUser"], "data");
+    assert_eq!(json["errors"][0]["path"], json!(["findOneUser", "posts"]));
+}
+
+/// Sends the query to a context emitting spec-compliant GraphQL errors.
+async fn request(query: &str) -> Value {
+    let ctx = PrismaContext::builder()
+        .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+        .enable_raw_queries(true)
+        .graphql_errors(true)
+        .build()
+        .await
+        .unwrap();
+
+    let req = PrismaRequest {
+        body: GraphQlBody::Single(SingleQuery::new(query.to_owned(), None, HashMap::new())),
+        headers: HashMap::new(),
+        path: String::new(),
+        access: Access::ReadWrite,
+    };
+
+    serde_json::to_value(GraphQlRequestHandler.handle(req, &Arc::new(ctx)).await).unwrap()
+}
+
+#[tokio::test]
+async fn the_data_of_successful_operations_is_returned_with_the_errors_of_failed_ones() {
+    let json = request(
+        "mutation {\n  ok: executeRaw(query: \"SELECT 1\")\n  failed: executeRaw(query: \"SELECT * FROM missing\")\n}",
+    )
+    .await;
+
+    assert_ne!(json["data"]["ok"], Value::Null, "{}", json);
+    assert_eq!(json["data"]["failed"], Value::Null, "{}", json);
+
+    let errors = json["errors"].as_array().unwrap();
+
+    assert_eq!(errors.len(), 1, "{}", json);
+    assert_eq!(errors[0]["path"], json!(["failed"]));
+    assert_eq!(errors[0]["locations"], json!([{ "line": 3, "column": 3 }]));
+    assert!(errors[0]["extensions"]["code"].is_string(), "{}", json);
+}
+
+#[tokio::test]
+async fn requests_failing_before_execution_have_no_data() {
+    let json = request("mutation { executeRaw(").await;
+
+    assert!(json.get("data").is_none(), "{}", json);
+    assert_eq!(json["errors"].as_array().unwrap().len(), 1, "{}", json);
+    assert!(json["errors"][0].get("path").is_none(), "{}", json);
+}
@@ -0,0 +1,90 @@
+use crate::request_handlers::grpc::{
+    proto::{self, operation::Kind as OperationKind, value::Kind as ValueKind},
+    transaction_timeout, GrpcProtocolAdapter,
+};
+use query_core::{Operation, QueryValue};
+use std::time::Duration;
+
+fn value(kind: ValueKind) -> Option<proto::Value> {
+    Some(proto::Value { kind: Some(kind) })
+}
+
+fn selection(name: &str, arguments: Vec<proto::Argument>) -> Option<proto::Selection> {
+    Some(proto::Selection {
+        name: name.to_owned(),
+        alias: String::new(),
+        arguments,
+        nested_selections: vec![proto::Selection {
+            name: "id".to_owned(),
+            ..Default::default()
+        }],
+    })
+}
+
+#[test]
+fn operations_are_converted_to_query_documents() {
+    let argument = proto::Argument {
+        name: "where".to_owned(),
+        value: value(ValueKind::Object(proto::ObjectValue {
+            fields: vec![
+                (
+                    "id".to_owned(),
+                    proto::Value {
+                        kind: Some(ValueKind::Int(1)),
+                    },
+                ),
+                ("name".to_owned(), proto::Value { kind: None }),
+            ]
+            .into_iter()
+            .collect(),
+        })),
+    };
+
+    let operation = proto::Operation {
+        kind: OperationKind::Write as i32,
+        selection: selection("deleteUser", vec![argument]),
+    };
+
+    let query_doc = GrpcProtocolAdapter::convert(vec![operation]).unwrap();
+
+    let selection = match &query_doc.operations[0] {
+        Operation::Write(selection) => selection,
+        Operation::Read(_) => panic!("Expected a write operation."),
+    };
+
+    assert_eq!(selection.name, "deleteUser");
+    assert_eq!(selection.alias, None);
+    assert_eq!(selection.nested_selections[0].name, "id");
+
+    let (name, value) = &selection.arguments[0];
+    let expected = vec![
+        ("id".to_owned(), QueryValue::Int(1)),
+        ("name".to_owned(), QueryValue::Null),
+    ];
+
+    assert_eq!(name, "where");
+    assert_eq!(value, &QueryValue::Object(expected.into_iter().collect()));
+}
+
+#[test]
+fn operations_without_selection_or_with_unknown_kind_are_rejected() {
+    let without_selection = proto::Operation {
+        kind: OperationKind::Read as i32,
+        selection: None,
+    };
+
+    let unknown_kind = proto::Operation {
+        kind: 7,
+        selection: selection("findManyUser", vec![]),
+    };
+
+    assert!(GrpcProtocolAdapter::convert(vec![without_selection]).is_err());
+    assert!(GrpcProtocolAdapter::convert(vec![unknown_kind]).is_err());
+}
+
+#[test]
+fn transaction_timeouts_are_capped() {
+    assert_eq!(transaction_timeout(0), Duration::from_secs(5));
+    assert_eq!(transaction_timeout(1500), Duration::from_millis(1500));
+    assert_eq!(transaction_timeout(std::u32::MAX), Duration::from_secs(60));
+}
@@ -0,0 +1,35 @@
+use crate::headers::{parse_header, Cors, ResponseHeaders};
+use hyper::header::{self, HeaderMap, HeaderValue};
+
+#[test]
+fn response_headers_are_parsed_from_name_and_value() {
+    let (name, value) = parse_header("Cache-Control: no-store").unwrap();
+
+    assert_eq!(name, header::CACHE_CONTROL);
+    assert_eq!(value, "no-store");
+    assert!(parse_header("Cache-Control").is_err());
+}
+
+#[test]
+fn cors_allows_only_configured_origins() {
+    let cors = Cors::new(
+        vec!["http://localhost:3000".to_owned()],
+        vec!["content-type".to_owned()],
+        vec!["POST".to_owned()],
+    )
+    .unwrap();
+
+    let response_headers = ResponseHeaders::new(Some(cors), vec![]);
+
+    let allowed_origin = HeaderValue::from_static("http://localhost:3000");
+    let mut headers = HeaderMap::new();
+    response_headers.apply(Some(&allowed_origin), &mut headers);
+
+    assert_eq!(headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN), Some(&allowed_origin));
+
+    let other_origin = HeaderValue::from_static("http://example.com");
+    let mut headers = HeaderMap::new();
+    response_headers.apply(Some(&other_origin), &mut headers);
+
+    assert!(headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+}
@@ -0,0 +1,44 @@
+use crate::request_handlers::{check_idempotency_key, idempotency_key, IDEMPOTENCY_KEY_HEADER};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[test]
+fn idempotency_key_is_read_from_the_header() {
+    let body = json!({ "query": "mutation { createOneUser(data: { id: \"1\" }) { id } }" });
+    let mut headers = HashMap::new();
+
+    assert!(idempotency_key(&headers, &body).is_none());
+
+    headers.insert(IDEMPOTENCY_KEY_HEADER.to_owned(), "create-user-1".to_owned());
+    let key = idempotency_key(&headers, &body).unwrap();
+
+    assert_eq!(key.key, "create-user-1");
+    assert_eq!(key.request_hash.len(), 64);
+}
+
+#[test]
+fn request_hash_differs_for_different_requests() {
+    let mut headers = HashMap::new();
+    headers.insert(IDEMPOTENCY_KEY_HEADER.to_owned(), "create-user".to_owned());
+
+    let first = idempotency_key(&headers, &json!({ "query": "mutation { a }" })).unwrap();
+    let retry = idempotency_key(&headers, &json!({ "query": "mutation { a }" })).unwrap();
+    let other = idempotency_key(&headers, &json!({ "query": "mutation { b }" })).unwrap();
+
+    assert_eq!(first, retry);
+    assert_ne!(first.request_hash, other.request_hash);
+}
+
+#[test]
+fn keys_longer_than_the_stored_keys_are_rejected() {
+    let mut headers = HashMap::new();
+    headers.insert(IDEMPOTENCY_KEY_HEADER.to_owned(), "k".repeat(255));
+
+    let key = idempotency_key(&headers, &json!({ "query": "mutation { a }" })).unwrap();
+    assert!(check_idempotency_key(&key).is_ok());
+
+    headers.insert(IDEMPOTENCY_KEY_HEADER.to_owned(), "k".repeat(256));
+
+    let key = idempotency_key(&headers, &json!({ "query": "mutation { a }" })).unwrap();
+    assert!(check_idempotency_key(&key).is_err());
+}
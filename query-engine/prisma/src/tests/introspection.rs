@@ -0,0 +1,103 @@
+use super::query_schema;
+use crate::request_handlers::graphql::{introspection, GraphQLProtocolAdapter};
+use query_core::ConnectorCapabilities;
+use serde_json::json;
+use std::collections::HashMap;
+
+const DATAMODEL: &str = r#"
+    model User {
+        id    String @id
+        name  String?
+        posts Post[]
+    }
+
+    model Post {
+        id     String @id
+        author User
+    }
+"#;
+
+#[test]
+fn introspection_fields_are_resolved_and_removed_from_the_document() {
+    let query_schema = query_schema(DATAMODEL, ConnectorCapabilities::all());
+    let mut gql_doc = graphql_parser::parse_query(
+        r#"
+        query {
+            __typename
+            findManyUser { id }
+            userType: __type(name: "User") {
+                kind
+                fields { name type { kind name ofType { kind name } } }
+            }
+        }
+        "#,
+    )
+    .unwrap();
+
+    let introspection = introspection::resolve(&mut gql_doc, None, &query_schema).unwrap();
+    let keys: Vec<&str> = introspection.iter().map(|(key, _)| key.as_str()).collect();
+
+    assert_eq!(keys, &["__typename", "userType"]);
+    assert_eq!(introspection[0].1, json!("Query"));
+
+    let user_type = &introspection[1].1;
+    let name_field = user_type["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|field| field["name"] == "name")
+        .unwrap();
+
+    assert_eq!(user_type["kind"], "OBJECT");
+    assert_eq!(
+        name_field["type"],
+        json!({ "kind": "SCALAR", "name": "String", "ofType": null })
+    );
+
+    let query_doc = GraphQLProtocolAdapter::convert(gql_doc, None, &HashMap::new()).unwrap();
+    assert_eq!(query_doc.operations.len(), 1);
+}
+
+#[test]
+fn introspection_supports_fragments() {
+    let query_schema = query_schema(DATAMODEL, ConnectorCapabilities::all());
+    let mut gql_doc = graphql_parser::parse_query(
+        r#"
+        query IntrospectionQuery {
+            __schema {
+                queryType { name }
+                types { ...FullType }
+            }
+        }
+
+        fragment FullType on __Type {
+            kind
+            name
+            ... on __Type { enumValues { name } }
+        }
+        "#,
+    )
+    .unwrap();
+
+    let introspection = introspection::resolve(&mut gql_doc, Some("IntrospectionQuery"), &query_schema).unwrap();
+    let schema = &introspection[0].1;
+
+    assert_eq!(schema["queryType"]["name"], "Query");
+
+    let types = schema["types"].as_array().unwrap();
+
+    assert!(types
+        .iter()
+        .any(|t| t["name"] == "User" && t["kind"] == "OBJECT" && t["enumValues"].is_null()));
+    assert!(types
+        .iter()
+        .any(|t| t["kind"] == "ENUM" && t["enumValues"].as_array().map_or(false, |v| !v.is_empty())));
+}
+
+#[test]
+fn unknown_introspection_fields_are_rejected() {
+    let query_schema = query_schema(DATAMODEL, ConnectorCapabilities::all());
+    let mut gql_doc = graphql_parser::parse_query("{ __schema { unknownField } }").unwrap();
+
+    assert!(introspection::resolve(&mut gql_doc, None, &query_schema).is_err());
+}
@@ -0,0 +1,131 @@
+use crate::{
+    auth::Access,
+    context::PrismaContext,
+    request_handlers::{JsonBody, JsonRequestHandler, PrismaRequest, RequestHandler},
+};
+use prisma_engine::json::{JsonProtocolAdapter, JsonSingleQuery};
+use query_core::{Operation, QueryValue};
+use serde_json::{json, Value};
+use std::{collections::HashMap, sync::Arc};
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+
+async fn context() -> Arc<PrismaContext> {
+    let ctx = PrismaContext::builder()
+        .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+        .enable_raw_queries(true)
+        .build()
+        .await
+        .unwrap();
+
+    Arc::new(ctx)
+}
+
+async fn request(ctx: &Arc<PrismaContext>, body: Value, access: Access) -> Value {
+    let req = PrismaRequest {
+        body: serde_json::from_value::<JsonBody>(body).unwrap(),
+        headers: HashMap::new(),
+        path: String::new(),
+        access,
+    };
+
+    serde_json::to_value(JsonRequestHandler.handle(req, ctx).await).unwrap()
+}
+
+fn execute_raw() -> Value {
+    json!({
+        "action": "mutation",
+        "selection": [{ "name": "executeRaw", "arguments": { "query": "SELECT 1" } }]
+    })
+}
+
+#[test]
+fn selections_are_converted_to_operations_of_the_action() {
+    let query: JsonSingleQuery = serde_json::from_value(json!({
+        "action": "mutation",
+        "selection": [{
+            "name": "createOneUser",
+            "alias": "user",
+            "arguments": { "data": { "id": "1", "age": 42, "score": 1.5, "tags": ["a"], "bio": null, "admin": true } },
+            "selection": [{ "name": "id" }]
+        }]
+    }))
+    .unwrap();
+
+    let mut document = JsonProtocolAdapter::convert(query).unwrap();
+    assert_eq!(document.operations.len(), 1);
+
+    let selection = match document.operations.pop().unwrap() {
+        Operation::Write(selection) => selection,
+        Operation::Read(_) => panic!("Mutations are converted to writes."),
+    };
+
+    let data = match &selection.arguments[..] {
+        [(name, QueryValue::Object(data))] if name == "data" => data,
+        arguments => panic!("Unexpected arguments {:?}", arguments),
+    };
+
+    assert_eq!(selection.name, "createOneUser");
+    assert_eq!(selection.alias.as_deref(), Some("user"));
+    assert_eq!(selection.nested_selections[0].name, "id");
+    assert_eq!(data["id"], QueryValue::String("1".to_owned()));
+    assert_eq!(data["age"], QueryValue::Int(42));
+    assert_eq!(data["score"], QueryValue::Float(1.5));
+    assert_eq!(data["tags"], QueryValue::List(vec![QueryValue::String("a".to_owned())]));
+    assert_eq!(data["bio"], QueryValue::Null);
+    assert_eq!(data["admin"], QueryValue::Boolean(true));
+}
+
+#[test]
+fn queries_are_converted_to_reads() {
+    let query: JsonSingleQuery = serde_json::from_value(json!({
+        "action": "query",
+        "selection": [{ "name": "findManyUser", "selection": [{ "name": "id" }] }]
+    }))
+    .unwrap();
+
+    let document = JsonProtocolAdapter::convert(query).unwrap();
+
+    assert!(match &document.operations[..] {
+        [Operation::Read(selection)] => selection.name == "findManyUser",
+        _ => false,
+    });
+}
+
+#[tokio::test]
+async fn json_requests_are_executed() {
+    let ctx = context().await;
+    let response = request(&ctx, execute_raw(), Access::ReadWrite).await;
+
+    assert_eq!(response["errors"], Value::Null, "{}", response);
+    assert!(response["data"].get("executeRaw").is_some(), "{}", response);
+}
+
+#[tokio::test]
+async fn json_batches_are_executed_in_order() {
+    let ctx = context().await;
+    let response = request(
+        &ctx,
+        json!({ "batch": [execute_raw(), execute_raw()] }),
+        Access::ReadWrite,
+    )
+    .await;
+    let batch = response.as_array().unwrap();
+
+    assert_eq!(batch.len(), 2);
+    assert_eq!(batch[0]["index"], json!(0));
+    assert_eq!(batch[0]["status"], json!("ok"));
+    assert_eq!(batch[1]["index"], json!(1));
+    assert_eq!(batch[1]["status"], json!("ok"));
+}
+
+#[tokio::test]
+async fn json_mutations_require_write_access() {
+    let ctx = context().await;
+    let response = request(&ctx, execute_raw(), Access::ReadOnly).await;
+
+    assert!(
+        response["errors"].as_array().map(Vec::len).unwrap_or(0) > 0,
+        "{}",
+        response
+    );
+}
@@ -0,0 +1,81 @@
+use crate::{
+    auth::Access,
+    context::PrismaContext,
+    request_handlers::{
+        GraphQLProtocolAdapter, GraphQlBody, GraphQlRequestHandler, PrismaRequest, RequestHandler, SingleQuery,
+    },
+};
+use query_core::{QueryGraphBuilder, QueryLimits, QueryType};
+use serde_json::Value;
+use std::{collections::HashMap, sync::Arc};
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+
+async fn context(limits: QueryLimits) -> Arc<PrismaContext> {
+    let ctx = PrismaContext::builder()
+        .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+        .enable_raw_queries(true)
+        .limits(limits)
+        .build()
+        .await
+        .unwrap();
+
+    Arc::new(ctx)
+}
+
+async fn request(ctx: &Arc<PrismaContext>, query: &str) -> Value {
+    let req = PrismaRequest {
+        body: GraphQlBody::Single(SingleQuery::new(query.to_owned(), None, HashMap::new())),
+        headers: HashMap::new(),
+        path: String::new(),
+        access: Access::ReadWrite,
+    };
+
+    serde_json::to_value(GraphQlRequestHandler.handle(req, ctx).await).unwrap()
+}
+
+fn node_count(ctx: &PrismaContext, query: &str) -> usize {
+    let gql_doc = graphql_parser::parse_query(query).unwrap();
+    let query_doc = GraphQLProtocolAdapter::convert(gql_doc, None, &HashMap::new()).unwrap();
+    let operation = query_doc.operations.into_iter().next().unwrap();
+
+    match QueryGraphBuilder::new(Arc::clone(ctx.query_schema()))
+        .map_operation(operation)
+        .unwrap()
+    {
+        (QueryType::Graph(graph), _) => graph.node_count(),
+        (QueryType::Raw { .. }, _) => unreachable!("The query is not a raw query."),
+    }
+}
+
+#[tokio::test]
+async fn no_operation_is_executed_if_the_graph_of_one_exceeds_the_limits() {
+    let create = r#"createOneUser(data: { id: "1" }) { id }"#;
+    let upsert = r#"upsertOneUser(where: { id: "2" }, create: { id: "2" }, update: {}) { id }"#;
+
+    let setup = context(QueryLimits::default()).await;
+    let create_nodes = node_count(&setup, &format!("mutation {{ {} }}", create));
+    assert!(node_count(&setup, &format!("mutation {{ {} }}", upsert)) > create_nodes);
+
+    let limits = QueryLimits {
+        max_graph_nodes: Some(create_nodes),
+        ..Default::default()
+    };
+
+    // The in-memory database lives as long as the single connection of the context.
+    let ctx = context(limits).await;
+    request(
+        &ctx,
+        r#"mutation { executeRaw(query: "CREATE TABLE \"memory\".\"User\" (id TEXT PRIMARY KEY)") }"#,
+    )
+    .await;
+
+    let response = request(&ctx, &format!("mutation {{ {} {} }}", create, upsert)).await;
+
+    assert!(response["errors"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("query graph nodes"));
+
+    let users = request(&ctx, "{ findManyUser { id } }").await;
+    assert_eq!(users["data"]["findManyUser"], serde_json::json!([]));
+}
@@ -0,0 +1,185 @@
+use crate::{
+    listener::{AcceptBackoff, ListenAddress},
+    load_tls, PrismaOpt,
+};
+use std::{net::SocketAddr, time::Duration};
+use structopt::StructOpt;
+
+fn socket_address(s: &str) -> SocketAddr {
+    s.parse().unwrap()
+}
+
+#[test]
+fn plain_addresses_are_tcp() {
+    match "127.0.0.1:4466".parse::<ListenAddress>().unwrap() {
+        ListenAddress::Tcp(address) => assert_eq!(address, socket_address("127.0.0.1:4466")),
+        address => panic!("Expected a TCP address, got {}", address),
+    }
+}
+
+#[test]
+fn http_addresses_are_tcp() {
+    match "http://[::1]:4466".parse::<ListenAddress>().unwrap() {
+        ListenAddress::Tcp(address) => assert_eq!(address, socket_address("[::1]:4466")),
+        address => panic!("Expected a TCP address, got {}", address),
+    }
+}
+
+#[test]
+fn https_addresses_are_tls() {
+    match "https://0.0.0.0:443".parse::<ListenAddress>().unwrap() {
+        ListenAddress::Tls(address) => assert_eq!(address, socket_address("0.0.0.0:443")),
+        address => panic!("Expected a TLS address, got {}", address),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_addresses_are_socket_paths() {
+    match "unix:/run/prisma.sock".parse::<ListenAddress>().unwrap() {
+        ListenAddress::Unix(path) => assert_eq!(path, std::path::PathBuf::from("/run/prisma.sock")),
+        address => panic!("Expected a unix socket, got {}", address),
+    }
+}
+
+#[test]
+fn invalid_addresses_are_rejected() {
+    let err = "https://localhost".parse::<ListenAddress>().unwrap_err();
+
+    assert!(err.contains("Invalid address 'localhost'"), "{}", err);
+    assert!("4466".parse::<ListenAddress>().is_err());
+    assert!("http://".parse::<ListenAddress>().is_err());
+}
+
+#[test]
+fn accept_backoff_doubles_up_to_a_second() {
+    let mut backoff = AcceptBackoff::default();
+
+    assert_eq!(backoff.failed(), Duration::from_millis(5));
+    assert_eq!(backoff.failed(), Duration::from_millis(10));
+    assert_eq!(backoff.failed(), Duration::from_millis(20));
+
+    for _ in 0..10 {
+        backoff.failed();
+    }
+
+    assert_eq!(backoff.failed(), Duration::from_secs(1));
+}
+
+#[test]
+fn accept_backoff_is_reset_by_accepted_connections() {
+    let mut backoff = AcceptBackoff::default();
+
+    backoff.failed();
+    backoff.failed();
+    backoff.reset();
+
+    assert_eq!(backoff.failed(), Duration::from_millis(5));
+}
+
+#[test]
+fn tls_certificates_without_https_addresses_are_rejected() {
+    let opts = PrismaOpt::from_iter(&["prisma", "--tls_cert", "cert.pem", "--tls_key", "key.pem"]);
+    let addresses = vec![ListenAddress::Tcp(socket_address("127.0.0.1:4466"))];
+
+    let err = load_tls(&opts, &addresses).err().unwrap();
+
+    assert!(
+        err.to_string().contains("requires an `https://host:port` address"),
+        "{}",
+        err
+    );
+}
+
+#[cfg(unix)]
+mod inherited {
+    use crate::listener::{inherit, Inherited};
+    use std::{
+        fs::File,
+        net::UdpSocket,
+        os::unix::{io::AsRawFd, io::IntoRawFd, net::UnixListener},
+    };
+
+    #[tokio::test]
+    async fn listening_tcp_sockets_can_be_inherited() {
+        let fd = std::net::TcpListener::bind("127.0.0.1:0").unwrap().into_raw_fd();
+
+        match inherit(fd).unwrap() {
+            Inherited::Tcp(_) => (),
+            Inherited::Unix(_) => panic!("Expected a TCP listener"),
+        }
+    }
+
+    #[tokio::test]
+    async fn listening_unix_sockets_can_be_inherited() {
+        let path = std::env::temp_dir().join(format!("prisma-inherit-{}.sock", uuid::Uuid::new_v4()));
+        let fd = UnixListener::bind(&path).unwrap().into_raw_fd();
+
+        let inherited = inherit(fd);
+        std::fs::remove_file(&path).unwrap();
+
+        match inherited.unwrap() {
+            Inherited::Unix(_) => (),
+            Inherited::Tcp(_) => panic!("Expected a unix listener"),
+        }
+    }
+
+    #[test]
+    fn sockets_not_listening_are_rejected() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let err = inherit(socket.as_raw_fd()).err().unwrap();
+
+        assert!(err.to_string().contains("is not listening"), "{}", err);
+    }
+
+    #[test]
+    fn descriptors_of_files_are_rejected() {
+        let file = File::open("Cargo.toml").unwrap();
+        let err = inherit(file.as_raw_fd()).err().unwrap();
+
+        assert!(err.to_string().contains("is not a socket"), "{}", err);
+    }
+}
+
+#[cfg(unix)]
+mod unix_sockets {
+    use crate::listener::bind_unix;
+    use std::{os::unix::net::UnixListener, path::PathBuf};
+
+    fn socket_path() -> PathBuf {
+        std::env::temp_dir().join(format!("prisma-bind-{}.sock", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn sockets_of_running_servers_are_not_replaced() {
+        let path = socket_path();
+        let (_listener, _socket_file) = bind_unix(&path).unwrap();
+
+        let err = bind_unix(&path).err().unwrap();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+        assert!(path.exists());
+    }
+
+    #[tokio::test]
+    async fn stale_sockets_are_replaced() {
+        let path = socket_path();
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let (_listener, _socket_file) = bind_unix(&path).unwrap();
+
+        assert!(std::os::unix::net::UnixStream::connect(&path).is_ok());
+    }
+
+    #[tokio::test]
+    async fn sockets_are_removed_on_shutdown() {
+        let path = socket_path();
+        let (listener, socket_file) = bind_unix(&path).unwrap();
+
+        drop(listener);
+        drop(socket_file);
+
+        assert!(!path.exists());
+    }
+}
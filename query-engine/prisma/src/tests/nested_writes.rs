@@ -0,0 +1,229 @@
+use super::query_schema;
+use connector::{Filter, ScalarCompare, WriteArgs};
+use prisma_models::{PrismaValue, RecordIdentifier};
+use query_core::{
+    ConnectorCapabilities, Node, Operation, Query, QueryDocument, QueryGraph, QueryGraphBuilder, QueryGraphDependency,
+    QuerySchemaRef, QueryType, QueryValue, Selection, WriteQuery,
+};
+use std::{collections::BTreeMap, sync::Arc};
+
+const DATAMODEL: &str = r#"
+    model User {
+        firstName String
+        lastName  String
+        age       Int?
+        posts     Post[]
+
+        @@id([firstName, lastName])
+    }
+
+    model Post {
+        id     String @id
+        author User?  @relation(references: [firstName, lastName])
+    }
+"#;
+
+fn get_query_schema() -> QuerySchemaRef {
+    Arc::new(query_schema(DATAMODEL, ConnectorCapabilities::empty()))
+}
+
+fn object(fields: Vec<(&str, QueryValue)>) -> QueryValue {
+    QueryValue::Object(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+fn string(value: &str) -> QueryValue {
+    QueryValue::String(value.to_owned())
+}
+
+fn update_post(author: QueryValue) -> QueryDocument {
+    let selection = Selection {
+        name: "updateOnePost".to_owned(),
+        alias: None,
+        arguments: vec![
+            ("where".to_owned(), object(vec![("id", string("1"))])),
+            ("data".to_owned(), object(vec![("author", author)])),
+        ],
+        nested_selections: vec![Selection {
+            name: "id".to_owned(),
+            alias: None,
+            arguments: vec![],
+            nested_selections: vec![],
+        }],
+    };
+
+    QueryDocument {
+        operations: vec![Operation::Write(selection)],
+    }
+}
+
+fn build_graph(query_schema: QuerySchemaRef, author: QueryValue) -> QueryGraph {
+    let mut queries = QueryGraphBuilder::new(query_schema).build(update_post(author)).unwrap();
+
+    match queries.pop().unwrap().0 {
+        QueryType::Graph(graph) => graph,
+        QueryType::Raw { .. } => panic!("Expected a query graph."),
+    }
+}
+
+/// Applies the edges passing the ids of `parent_model` records to the updates of `model`, as the interpreter does
+/// once the parents are read, and returns the filters and arguments of the transformed updates.
+fn updates_with_parent_ids(
+    graph: &mut QueryGraph,
+    model: &str,
+    parent_model: &str,
+    parent_values: &[&str],
+) -> Vec<(Filter, WriteArgs)> {
+    let mut updates = Vec::new();
+
+    for node in graph.nodes() {
+        let is_update = match graph.node_content(&node) {
+            Some(Node::Query(Query::Write(WriteQuery::UpdateRecord(update)))) => update.model.name == model,
+            Some(Node::Query(Query::Write(WriteQuery::UpdateManyRecords(update)))) => update.model.name == model,
+            _ => false,
+        };
+
+        let edges: Vec<_> = graph
+            .incoming_edges(&node)
+            .into_iter()
+            .filter(|edge| match graph.edge_content(edge) {
+                Some(QueryGraphDependency::ParentIds(identifier, _)) => identifier.model().name == parent_model,
+                _ => false,
+            })
+            .collect();
+
+        if !is_update || edges.is_empty() {
+            continue;
+        }
+
+        let mut content = graph.pluck_node(&node);
+
+        for edge in edges {
+            if let QueryGraphDependency::ParentIds(identifier, transform) = graph.pluck_edge(&edge) {
+                let parent_id: RecordIdentifier = identifier
+                    .data_source_fields()
+                    .zip(parent_values.iter().map(|value| PrismaValue::from(*value)))
+                    .collect();
+
+                content = transform(content, vec![parent_id]).unwrap();
+            }
+        }
+
+        match content {
+            Node::Query(Query::Write(WriteQuery::UpdateRecord(update))) => updates.push((update.where_, update.args)),
+            Node::Query(Query::Write(WriteQuery::UpdateManyRecords(update))) => {
+                updates.push((update.filter, update.args))
+            }
+            _ => unreachable!("The node was selected as an update."),
+        }
+    }
+
+    updates
+}
+
+/// The filter selecting the user by both fields of its compound id.
+fn user_filter(query_schema: &QuerySchemaRef, first_name: &str, last_name: &str) -> Filter {
+    let user_id = query_schema
+        .internal_data_model
+        .find_model("User")
+        .unwrap()
+        .primary_identifier();
+
+    Filter::and(vec![
+        user_id.map_db_name("firstName").unwrap().equals(first_name),
+        user_id.map_db_name("lastName").unwrap().equals(last_name),
+    ])
+}
+
+/// The names of the foreign key columns of `Post.author`.
+fn author_foreign_keys(query_schema: &QuerySchemaRef) -> Vec<String> {
+    let post = query_schema.internal_data_model.find_model("Post").unwrap();
+    let author = post.fields().find_from_relation_fields("author").unwrap();
+
+    author.db_names().map(String::from).collect()
+}
+
+#[test]
+fn nested_upserts_of_models_with_compound_ids_filter_by_the_full_id() {
+    let author = object(vec![(
+        "upsert",
+        object(vec![
+            (
+                "create",
+                object(vec![("firstName", string("Jane")), ("lastName", string("Doe"))]),
+            ),
+            ("update", object(vec![("age", QueryValue::Int(42))])),
+        ]),
+    )]);
+
+    let query_schema = get_query_schema();
+    let mut graph = build_graph(Arc::clone(&query_schema), author);
+
+    let user_updates = updates_with_parent_ids(&mut graph, "User", "User", &["Jane", "Doe"]);
+
+    assert_eq!(user_updates.len(), 1);
+    assert_eq!(user_updates[0].0, user_filter(&query_schema, "Jane", "Doe"));
+}
+
+#[test]
+fn nested_upserts_of_models_with_compound_ids_inject_the_full_id() {
+    let author = object(vec![(
+        "upsert",
+        object(vec![
+            (
+                "create",
+                object(vec![("firstName", string("Jane")), ("lastName", string("Doe"))]),
+            ),
+            ("update", object(vec![("age", QueryValue::Int(42))])),
+        ]),
+    )]);
+
+    let query_schema = get_query_schema();
+    let mut graph = build_graph(Arc::clone(&query_schema), author);
+
+    let post_updates = updates_with_parent_ids(&mut graph, "Post", "User", &["Jane", "Doe"]);
+    let foreign_keys = author_foreign_keys(&query_schema);
+
+    assert_eq!(post_updates.len(), 1);
+    assert_eq!(foreign_keys.len(), 2);
+    assert_eq!(
+        post_updates[0].1.get_field_value(&foreign_keys[0]),
+        Some(&PrismaValue::String("Jane".to_owned()))
+    );
+    assert_eq!(
+        post_updates[0].1.get_field_value(&foreign_keys[1]),
+        Some(&PrismaValue::String("Doe".to_owned()))
+    );
+}
+
+#[test]
+fn nested_disconnects_of_models_with_compound_ids_null_out_the_full_foreign_key() {
+    let author = object(vec![("disconnect", QueryValue::Boolean(true))]);
+
+    let query_schema = get_query_schema();
+    let mut graph = build_graph(Arc::clone(&query_schema), author);
+
+    let post_updates = updates_with_parent_ids(&mut graph, "Post", "Post", &["1"]);
+    let post_id = query_schema
+        .internal_data_model
+        .find_model("Post")
+        .unwrap()
+        .primary_identifier();
+
+    assert_eq!(post_updates.len(), 1);
+    assert_eq!(
+        post_updates[0].0,
+        Filter::or(vec![Filter::and(vec![post_id.map_db_name("id").unwrap().equals("1")])])
+    );
+
+    for foreign_key in author_foreign_keys(&query_schema) {
+        assert_eq!(
+            post_updates[0].1.get_field_value(&foreign_key),
+            Some(&PrismaValue::Null)
+        );
+    }
+}
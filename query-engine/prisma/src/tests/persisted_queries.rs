@@ -0,0 +1,73 @@
+use crate::{
+    auth::{Access, Authenticator},
+    context::PrismaContext,
+    request_handlers::{
+        grpc::proto::{self, query_engine_server::QueryEngine},
+        GrpcRequestHandler, JsonBody, JsonRequestHandler, PersistedQueries, PrismaRequest, RequestHandler,
+    },
+    shutdown,
+};
+use serde_json::json;
+use std::{collections::HashMap, sync::Arc};
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+use tonic::{Code, Request};
+
+async fn context(persisted_queries: PersistedQueries) -> Arc<PrismaContext> {
+    let ctx = PrismaContext::builder()
+        .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+        .persisted_queries(persisted_queries)
+        .build()
+        .await
+        .unwrap();
+
+    Arc::new(ctx)
+}
+
+fn json_request() -> PrismaRequest<JsonBody> {
+    let body = json!({
+        "action": "query",
+        "selection": [{ "name": "findManyUser", "selection": [{ "name": "id" }] }]
+    });
+
+    PrismaRequest {
+        body: serde_json::from_value(body).unwrap(),
+        headers: HashMap::new(),
+        path: String::new(),
+        access: Access::ReadWrite,
+    }
+}
+
+#[tokio::test]
+async fn ad_hoc_json_requests_are_rejected_if_only_persisted_queries_are_allowed() {
+    let ctx = context(PersistedQueries::new(vec![], true)).await;
+    let response = serde_json::to_value(JsonRequestHandler.handle(json_request(), &ctx).await).unwrap();
+
+    assert_eq!(response["data"], serde_json::Value::Null);
+    assert!(response["errors"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("Only persisted queries are allowed"));
+}
+
+#[tokio::test]
+async fn grpc_requests_are_rejected_if_only_persisted_queries_are_allowed() {
+    let ctx = context(PersistedQueries::new(vec![], true)).await;
+    let (drain, _trigger) = shutdown::drain();
+    let handler = GrpcRequestHandler::new(ctx, Arc::new(Authenticator::default()), drain);
+
+    let response = handler
+        .execute(Request::new(proto::ExecuteRequest::default()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(response.has_errors);
+    assert!(response.json.contains("Only persisted queries are allowed"));
+
+    let status = handler
+        .start_transaction(Request::new(proto::StartTransactionRequest::default()))
+        .await
+        .unwrap_err();
+
+    assert_eq!(status.code(), Code::FailedPrecondition);
+}
@@ -0,0 +1,57 @@
+use crate::{
+    auth::Authenticator,
+    context::PrismaContext,
+    headers::ResponseHeaders,
+    server::{HttpServer, RequestContext},
+    shutdown,
+    tenants::Contexts,
+};
+use hyper::{Body, Request, StatusCode};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+
+/// Sends a GET request to the path through the routes of the HTTP server.
+async fn get(path: &str) -> (StatusCode, Value) {
+    let context = PrismaContext::builder()
+        .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+        .build()
+        .await
+        .unwrap();
+
+    let ctx = RequestContext::new(
+        Contexts::Single(Arc::new(context)),
+        None,
+        ResponseHeaders::default(),
+        Arc::new(Authenticator::default()),
+        shutdown::drain().0,
+    );
+
+    let req = Request::get(path).body(Body::empty()).unwrap();
+    let res = HttpServer::routes(Arc::new(ctx), req).await.unwrap();
+    let status = res.status();
+    let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn the_health_probe_reports_the_process_as_up() {
+    let (status, body) = get("/health").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "status": "ok" }));
+}
+
+#[tokio::test]
+async fn the_readiness_probe_reports_the_pool_after_using_a_connection() {
+    let (status, body) = get("/ready").await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["status"], json!("ready"));
+
+    // The connection of the check is back in the pool once the probe responds.
+    assert_eq!(body["pool"]["inUse"], json!(0));
+    assert!(body["pool"]["capacity"].as_u64().unwrap() > 0, "{}", body);
+    assert_eq!(body["saturation"], json!(0.0));
+}
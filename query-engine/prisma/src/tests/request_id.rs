@@ -0,0 +1,112 @@
+use crate::{
+    auth::Authenticator,
+    context::PrismaContext,
+    headers::ResponseHeaders,
+    server::{HttpServer, RequestContext},
+    shutdown,
+    telemetry::REQUEST_ID_HEADER,
+    tenants::Contexts,
+};
+use hyper::{Body, Request, Response};
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+use tracing_subscriber::FmtSubscriber;
+
+/// Collects the log output of a test.
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl Logs {
+    fn lines(&self) -> Vec<serde_json::Value> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+impl io::Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Sends the request through the routes of the HTTP server, logging as JSON.
+fn send(req: Request<Body>) -> (Response<Body>, Logs) {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = FmtSubscriber::builder()
+        .json()
+        .with_writer(move || writer.clone())
+        .finish();
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let res = tracing::subscriber::with_default(subscriber, || {
+        runtime.block_on(async {
+            let context = PrismaContext::builder()
+                .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+                .build()
+                .await
+                .unwrap();
+
+            let ctx = RequestContext::new(
+                Contexts::Single(Arc::new(context)),
+                None,
+                ResponseHeaders::default(),
+                Arc::new(Authenticator::default()),
+                shutdown::drain().0,
+            );
+
+            HttpServer::routes(Arc::new(ctx), req).await.unwrap()
+        })
+    });
+
+    (res, logs)
+}
+
+fn request_id(res: &Response<Body>) -> String {
+    res.headers()[REQUEST_ID_HEADER].to_str().unwrap().to_owned()
+}
+
+/// Whether a line logged in the span of the request carries the request id.
+fn logged(logs: &Logs, request_id: &str) -> bool {
+    logs.lines().iter().any(|line| line.to_string().contains(request_id))
+}
+
+#[test]
+fn the_request_id_of_the_caller_is_returned_and_logged() {
+    let req = Request::get("/status")
+        .header(REQUEST_ID_HEADER, "0f3b2a")
+        .body(Body::empty())
+        .unwrap();
+
+    let (res, logs) = send(req);
+
+    assert_eq!(request_id(&res), "0f3b2a");
+    assert!(logged(&logs, "0f3b2a"), "{:?}", logs.lines());
+}
+
+#[test]
+fn requests_without_an_id_get_a_generated_one() {
+    let req = Request::get("/status").body(Body::empty()).unwrap();
+
+    let (res, logs) = send(req);
+    let request_id = request_id(&res);
+
+    assert!(!request_id.is_empty());
+    assert!(logged(&logs, &request_id), "{:?}", logs.lines());
+}
@@ -0,0 +1,87 @@
+use super::internal_datamodel;
+use crate::request_handlers::graphql::introspection;
+use connector::{Routine, RoutineKind, RoutineParameter};
+use prisma_models::TypeIdentifier;
+use query_core::{BuildMode, ConnectorCapabilities, QuerySchema, QuerySchemaBuilder};
+
+const DATAMODEL: &str = r#"
+    model User {
+        id String @id
+    }
+"#;
+
+fn get_query_schema(enable_raw_queries: bool) -> QuerySchema {
+    let internal_datamodel = internal_datamodel(DATAMODEL);
+    let capabilities = ConnectorCapabilities::empty();
+
+    let routines = vec![
+        Routine {
+            name: "refresh_stats".to_owned(),
+            kind: RoutineKind::Procedure,
+            parameters: vec![
+                RoutineParameter {
+                    name: "since".to_owned(),
+                    type_identifier: TypeIdentifier::DateTime,
+                    is_list: false,
+                },
+                RoutineParameter {
+                    name: "user_ids".to_owned(),
+                    type_identifier: TypeIdentifier::String,
+                    is_list: true,
+                },
+            ],
+        },
+        Routine {
+            name: "not-a-field-name".to_owned(),
+            kind: RoutineKind::Function,
+            parameters: vec![],
+        },
+    ];
+
+    QuerySchemaBuilder::new(
+        &internal_datamodel,
+        &capabilities,
+        BuildMode::Modern,
+        enable_raw_queries,
+    )
+    .routines(routines)
+    .build()
+}
+
+fn mutation_fields(query_schema: &QuerySchema) -> Vec<serde_json::Value> {
+    let query =
+        "{ __type(name: \"Mutation\") { fields { name args { name type { kind name ofType { kind name } } } } } }";
+    let mut gql_doc = graphql_parser::parse_query(query).unwrap();
+    let introspection = introspection::resolve(&mut gql_doc, None, query_schema).unwrap();
+
+    introspection[0].1["fields"].as_array().unwrap().clone()
+}
+
+#[test]
+fn routines_are_exposed_as_call_mutations_with_raw_queries() {
+    let fields = mutation_fields(&get_query_schema(true));
+    let call = fields
+        .iter()
+        .find(|field| field["name"] == "callRefresh_stats")
+        .expect("Expected a mutation calling the routine.");
+
+    let args: Vec<&str> = call["args"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|arg| arg["name"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(args, &["since", "user_ids"]);
+    assert_eq!(call["args"][1]["type"]["kind"], "LIST");
+    assert!(!fields.iter().any(|field| field["name"] == "callNot-a-field-name"));
+}
+
+#[test]
+fn routines_are_not_exposed_without_raw_queries() {
+    let fields = mutation_fields(&get_query_schema(false));
+
+    assert!(!fields
+        .iter()
+        .any(|field| field["name"].as_str().unwrap().starts_with("call")));
+}
@@ -0,0 +1,53 @@
+use crate::{
+    auth::Authenticator,
+    context::PrismaContext,
+    headers::ResponseHeaders,
+    server::{HttpServer, RequestContext},
+    shutdown,
+    tenants::Contexts,
+};
+use hyper::{Body, Request, StatusCode};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+
+/// Requests the server info from a context with raw queries and GraphQL errors enabled.
+async fn server_info(path: &str) -> Value {
+    let context = PrismaContext::builder()
+        .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+        .enable_raw_queries(true)
+        .graphql_errors(true)
+        .build()
+        .await
+        .unwrap();
+
+    let ctx = RequestContext::new(
+        Contexts::Single(Arc::new(context)),
+        None,
+        ResponseHeaders::default(),
+        Arc::new(Authenticator::default()),
+        shutdown::drain().0,
+    );
+
+    let req = Request::get(path).body(Body::empty()).unwrap();
+    let res = HttpServer::routes(Arc::new(ctx), req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn the_server_info_reports_the_connector_and_the_enabled_features() {
+    let info = server_info("/server-info").await;
+
+    assert_eq!(info["version"], json!(env!("CARGO_PKG_VERSION")));
+    assert_eq!(info["commit"], json!(env!("GIT_HASH")));
+    assert_eq!(info["primary_connector"], json!("sqlite"));
+    assert_eq!(info["features"], json!(["raw_queries", "graphql_errors"]));
+}
+
+#[tokio::test]
+async fn the_server_info_is_served_on_the_legacy_path_as_well() {
+    assert_eq!(server_info("/server_info").await, server_info("/server-info").await);
+}
@@ -0,0 +1,147 @@
+use crate::{
+    auth::{Access, Authenticator},
+    context::PrismaContext,
+    request_handlers::{
+        grpc::proto::{self, operation::Kind as OperationKind, query_engine_server::QueryEngine, value::Kind},
+        GraphQlBody, GraphQlRequestHandler, GrpcRequestHandler, PrismaRequest, RequestHandler, SingleQuery,
+    },
+    shutdown,
+};
+use futures::channel::oneshot;
+use serde_json::{json, Value};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+use tonic::{Code, Request};
+
+async fn request(ctx: &Arc<PrismaContext>, query: &str) -> Value {
+    let req = PrismaRequest {
+        body: GraphQlBody::Single(SingleQuery::new(query.to_owned(), None, HashMap::new())),
+        headers: HashMap::new(),
+        path: String::new(),
+        access: Access::ReadWrite,
+    };
+
+    serde_json::to_value(GraphQlRequestHandler.handle(req, ctx).await).unwrap()
+}
+
+fn create_user(id: &str) -> proto::Operation {
+    let data = proto::ObjectValue {
+        fields: vec![(
+            "id".to_owned(),
+            proto::Value {
+                kind: Some(Kind::String(id.to_owned())),
+            },
+        )]
+        .into_iter()
+        .collect(),
+    };
+
+    proto::Operation {
+        kind: OperationKind::Write as i32,
+        selection: Some(proto::Selection {
+            name: "createOneUser".to_owned(),
+            alias: String::new(),
+            arguments: vec![proto::Argument {
+                name: "data".to_owned(),
+                value: Some(proto::Value {
+                    kind: Some(Kind::Object(data)),
+                }),
+            }],
+            nested_selections: vec![proto::Selection {
+                name: "id".to_owned(),
+                ..Default::default()
+            }],
+        }),
+    }
+}
+
+#[tokio::test]
+async fn the_drain_waits_for_the_tracked_work() {
+    let (drain, trigger) = shutdown::drain();
+    let guard = drain.track();
+
+    assert!(!drain.is_shutting_down());
+
+    let (drained, mut drained_receiver) = oneshot::channel();
+
+    tokio::spawn(async move {
+        trigger.drain().await;
+        drained.send(()).ok();
+    });
+
+    drain.shutting_down().await;
+    assert!(drain.is_shutting_down());
+
+    // Work started after the shutdown isn't waited for.
+    let _late_guard = drain.track();
+
+    tokio::time::delay_for(Duration::from_millis(50)).await;
+    assert_eq!(drained_receiver.try_recv(), Ok(None));
+
+    drop(guard);
+
+    tokio::time::timeout(Duration::from_secs(5), drained_receiver)
+        .await
+        .expect("The drain didn't finish after the guard was dropped.")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn open_transactions_are_rolled_back_on_shutdown() {
+    let ctx = PrismaContext::builder()
+        .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+        .enable_raw_queries(true)
+        .build()
+        .await
+        .unwrap();
+
+    // The in-memory database lives as long as the single connection of the context.
+    let ctx = Arc::new(ctx);
+    request(
+        &ctx,
+        r#"mutation { executeRaw(query: "CREATE TABLE \"memory\".\"User\" (id TEXT PRIMARY KEY)") }"#,
+    )
+    .await;
+
+    let (drain, trigger) = shutdown::drain();
+    let handler = GrpcRequestHandler::new(Arc::clone(&ctx), Arc::new(Authenticator::default()), drain);
+
+    let transaction_id = handler
+        .start_transaction(Request::new(proto::StartTransactionRequest { timeout_ms: 60_000 }))
+        .await
+        .unwrap()
+        .into_inner()
+        .transaction_id;
+
+    let response = handler
+        .execute(Request::new(proto::ExecuteRequest {
+            operations: vec![create_user("user")],
+            transaction_id: transaction_id.clone(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(!response.has_errors, "{}", response.json);
+
+    tokio::time::timeout(Duration::from_secs(5), trigger.drain())
+        .await
+        .expect("The transaction wasn't rolled back on shutdown.");
+
+    let status = handler
+        .commit(Request::new(proto::TransactionRequest { transaction_id }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(status.code(), Code::NotFound);
+
+    let status = handler
+        .start_transaction(Request::new(proto::StartTransactionRequest::default()))
+        .await
+        .unwrap_err();
+
+    assert_eq!(status.code(), Code::Unavailable);
+
+    let users = request(&ctx, "{ findManyUser { id } }").await;
+    assert_eq!(users["data"]["findManyUser"], json!([]));
+}
@@ -0,0 +1,31 @@
+use crate::{request_handlers::GraphQLProtocolAdapter, subscriptions::accept_key, PrismaResult};
+
+fn subscription_models(query: &str) -> PrismaResult<Vec<String>> {
+    GraphQLProtocolAdapter::convert_subscription(graphql_parser::parse_query(query).unwrap())
+}
+
+#[test]
+fn accept_keys_follow_the_websocket_handshake() {
+    // The example handshake of RFC 6455, section 1.3.
+    assert_eq!(accept_key(b"dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+}
+
+#[test]
+fn subscriptions_name_the_models_by_their_fields() {
+    let models = subscription_models("subscription { User Post }").unwrap();
+
+    assert_eq!(models, vec!["User".to_owned(), "Post".to_owned()]);
+}
+
+#[test]
+fn only_subscription_operations_are_accepted() {
+    assert!(subscription_models("query { findManyUser { id } }").is_err());
+    assert!(subscription_models("{ findManyUser { id } }").is_err());
+}
+
+#[test]
+fn fragments_in_subscriptions_are_rejected() {
+    let result = subscription_models("subscription { ...Models } fragment Models on Subscription { User }");
+
+    assert!(result.is_err());
+}
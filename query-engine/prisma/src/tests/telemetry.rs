@@ -0,0 +1,44 @@
+use crate::telemetry;
+use hyper::{Body, Request};
+use opentelemetry::{
+    api::{Provider, TraceContextExt},
+    sdk,
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+const TRACE_ID: u128 = 0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736;
+
+/// The trace id of the OpenTelemetry span of the request span.
+fn trace_id(req: &Request<Body>) -> u128 {
+    let tracer = sdk::Provider::default().get_tracer("prisma-query-engine");
+    let subscriber = Registry::default().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = telemetry::request_span(req, "0f3b2a");
+        span.context().span().span_context().trace_id().to_u128()
+    })
+}
+
+#[test]
+fn requests_continue_the_trace_of_the_traceparent() {
+    let req = Request::get("/")
+        .header("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        .body(Body::empty())
+        .unwrap();
+
+    assert_eq!(trace_id(&req), TRACE_ID);
+}
+
+#[test]
+fn requests_without_a_traceparent_start_a_new_trace() {
+    let req = Request::get("/").body(Body::empty()).unwrap();
+    let invalid = Request::get("/")
+        .header("traceparent", "not-a-traceparent")
+        .body(Body::empty())
+        .unwrap();
+
+    assert_ne!(trace_id(&req), TRACE_ID);
+    assert_ne!(trace_id(&req), 0);
+    assert_ne!(trace_id(&invalid), 0);
+}
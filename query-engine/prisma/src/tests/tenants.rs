@@ -0,0 +1,116 @@
+use crate::{
+    context::PrismaContext,
+    tenants::{self, Contexts, TenantOptions, Tenants, TENANT_HEADER},
+};
+use hyper::{Body, Request};
+use std::sync::Arc;
+use test_setup::IN_MEMORY_SQLITE_DATAMODEL;
+
+fn request(path: &str, tenant: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().uri(path);
+
+    if let Some(tenant) = tenant {
+        builder = builder.header(TENANT_HEADER, tenant);
+    }
+
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn tenants() -> Contexts {
+    let tenants = Tenants::new(TenantOptions::default());
+    tenants
+        .load("acme".to_owned(), IN_MEMORY_SQLITE_DATAMODEL.to_owned(), vec![])
+        .await
+        .unwrap();
+
+    Contexts::Tenants(tenants)
+}
+
+fn tenant(contexts: &Contexts, name: &str) -> Arc<PrismaContext> {
+    match contexts {
+        Contexts::Tenants(tenants) => tenants.get(name).unwrap(),
+        Contexts::Single(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn tenant_names_are_read_from_management_paths() {
+    assert_eq!(tenants::tenant_name("/tenants/acme"), Some("acme"));
+    assert_eq!(tenants::tenant_name("/tenants/"), None);
+    assert_eq!(tenants::tenant_name("/tenants"), None);
+    assert_eq!(tenants::tenant_name("/tenants/acme/graphql"), None);
+    assert_eq!(tenants::tenant_name("/tenantsacme"), None);
+}
+
+#[tokio::test]
+async fn the_single_context_serves_every_path() {
+    let context = Arc::new(
+        PrismaContext::builder()
+            .datamodel(IN_MEMORY_SQLITE_DATAMODEL.to_owned())
+            .build()
+            .await
+            .unwrap(),
+    );
+    let contexts = Contexts::Single(Arc::clone(&context));
+
+    let (resolved, path) = contexts.resolve(&request("/tenants/acme", Some("other"))).unwrap();
+
+    assert!(Arc::ptr_eq(&resolved, &context));
+    assert_eq!(path, "/tenants/acme");
+}
+
+#[tokio::test]
+async fn tenants_are_resolved_from_the_path_prefix() {
+    let contexts = tenants().await;
+
+    let (context, path) = contexts.resolve(&request("/tenants/acme/sdl", None)).unwrap();
+    assert!(Arc::ptr_eq(&context, &tenant(&contexts, "acme")));
+    assert_eq!(path, "/sdl");
+
+    let (_, path) = contexts.resolve(&request("/tenants/acme", None)).unwrap();
+    assert_eq!(path, "/");
+}
+
+#[tokio::test]
+async fn tenants_are_resolved_from_the_header() {
+    let contexts = tenants().await;
+
+    let (context, path) = contexts.resolve(&request("/sdl", Some("acme"))).unwrap();
+
+    assert!(Arc::ptr_eq(&context, &tenant(&contexts, "acme")));
+    assert_eq!(path, "/sdl");
+}
+
+#[tokio::test]
+async fn the_path_prefix_takes_precedence_over_the_header() {
+    let contexts = tenants().await;
+
+    let err = contexts
+        .resolve(&request("/tenants/other/sdl", Some("acme")))
+        .err()
+        .unwrap();
+
+    assert!(err.to_string().contains("Unknown tenant 'other'"), "{}", err);
+}
+
+#[tokio::test]
+async fn requests_without_a_known_tenant_are_rejected() {
+    let contexts = tenants().await;
+
+    let err = contexts.resolve(&request("/sdl", None)).err().unwrap();
+    assert!(err.to_string().contains("No tenant given"), "{}", err);
+
+    let err = contexts.resolve(&request("/sdl", Some("other"))).err().unwrap();
+    assert!(err.to_string().contains("Unknown tenant 'other'"), "{}", err);
+}
+
+#[tokio::test]
+async fn tenant_data_models_cant_read_the_environment() {
+    let tenants = Tenants::new(TenantOptions::default());
+    let datamodel = IN_MEMORY_SQLITE_DATAMODEL.replace(r#""file::memory:""#, r#"env("DATABASE_URL")"#);
+
+    let err = tenants.load("acme".to_owned(), datamodel, vec![]).await.err().unwrap();
+
+    assert!(err.to_string().contains("can't use env()"), "{}", err);
+    assert!(tenants.get("acme").is_none());
+}
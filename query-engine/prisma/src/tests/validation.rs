@@ -0,0 +1,45 @@
+use super::query_schema;
+use query_core::{ConnectorCapabilities, CoreError, QueryDocumentParser, QueryValue, Selection};
+use std::collections::BTreeMap;
+
+const DATAMODEL: &str = r#"
+    model User {
+        id   String @id
+        name String
+        age  Int
+    }
+"#;
+
+#[test]
+fn all_validation_errors_of_a_request_are_reported() {
+    let query_schema = query_schema(DATAMODEL, ConnectorCapabilities::empty());
+
+    let mut data = BTreeMap::new();
+    data.insert("id".to_owned(), QueryValue::String("1".to_owned()));
+    data.insert("name".to_owned(), QueryValue::Int(5));
+    data.insert("age".to_owned(), QueryValue::String("five".to_owned()));
+
+    let selection = Selection {
+        name: "createOneUser".to_owned(),
+        alias: None,
+        arguments: vec![
+            ("data".to_owned(), QueryValue::Object(data)),
+            ("unknown".to_owned(), QueryValue::Int(1)),
+        ],
+        nested_selections: vec![Selection {
+            name: "id".to_owned(),
+            alias: None,
+            arguments: vec![],
+            nested_selections: vec![],
+        }],
+    };
+
+    let err = QueryDocumentParser::parse_object(&[selection], &query_schema.mutation()).unwrap_err();
+    let err = user_facing_errors::Error::from(CoreError::from(err));
+    let message = err.message();
+
+    assert_eq!(err.error_code(), Some("P2009"));
+    assert!(message.contains("unknown (argument)"), "{}", message);
+    assert!(message.contains("name (field)"), "{}", message);
+    assert!(message.contains("age (field)"), "{}", message);
+}
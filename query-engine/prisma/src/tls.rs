@@ -0,0 +1,44 @@
+//! TLS termination for HTTPS endpoints.
+use crate::{PrismaError, PrismaResult};
+use std::{fs::File, io::BufReader, sync::Arc};
+use tokio_rustls::{
+    rustls::{
+        internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys},
+        NoClientAuth, PrivateKey, ServerConfig,
+    },
+    TlsAcceptor,
+};
+
+/// Loads the PEM encoded certificate chain and private key (PKCS#8 or RSA) from the given files.
+pub fn acceptor(cert_path: &str, key_path: &str) -> PrismaResult<TlsAcceptor> {
+    let certs = certs(&mut BufReader::new(File::open(cert_path)?))
+        .map_err(|_| PrismaError::ConfigurationError(format!("Invalid certificate file '{}'.", cert_path)))?;
+
+    let key = load_private_key(key_path)?;
+    let mut config = ServerConfig::new(NoClientAuth::new());
+
+    config
+        .set_single_cert(certs, key)
+        .map_err(|err| PrismaError::ConfigurationError(format!("Invalid certificate or key: {}", err)))?;
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+fn load_private_key(key_path: &str) -> PrismaResult<PrivateKey> {
+    let pkcs8_keys = pkcs8_private_keys(&mut BufReader::new(File::open(key_path)?)).unwrap_or_default();
+
+    let mut keys = if pkcs8_keys.is_empty() {
+        rsa_private_keys(&mut BufReader::new(File::open(key_path)?)).unwrap_or_default()
+    } else {
+        pkcs8_keys
+    };
+
+    if keys.is_empty() {
+        Err(PrismaError::ConfigurationError(format!(
+            "No private key found in '{}'.",
+            key_path
+        )))
+    } else {
+        Ok(keys.remove(0))
+    }
+}
@@ -0,0 +1,27 @@
+/* C interface of the query engine, see src/lib.rs for the documentation of the functions. */
+#ifndef QUERY_ENGINE_H
+#define QUERY_ENGINE_H
+
+typedef struct QueryEngine QueryEngine;
+
+typedef struct QueryEngineTransaction QueryEngineTransaction;
+
+QueryEngine *query_engine_connect(const char *datamodel, const char *url, char **error);
+
+void query_engine_disconnect(QueryEngine *engine);
+
+char *query_engine_query(const QueryEngine *engine, const char *request);
+
+char *query_engine_transaction(const QueryEngine *engine, const char *request);
+
+QueryEngineTransaction *query_engine_start_transaction(const QueryEngine *engine);
+
+char *query_engine_transaction_query(const QueryEngineTransaction *transaction, const char *request);
+
+char *query_engine_commit(QueryEngineTransaction *transaction);
+
+char *query_engine_rollback(QueryEngineTransaction *transaction);
+
+void query_engine_string_free(char *s);
+
+#endif
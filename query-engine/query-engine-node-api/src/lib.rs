@@ -0,0 +1,462 @@
+//! C ABI of the query engine, for loading it into the Node.js process (e.g. through an N-API addon or `ffi-napi`)
+//! instead of spawning the binary and talking to it over HTTP.
+//!
+//! All strings are null terminated UTF-8. Strings returned by the engine are owned by the caller and have to be
+//! released with `query_engine_string_free`. Requests use the JSON protocol of `prisma_engine::json`, responses are
+//! `{ "data": ..., "errors": [...] }` documents, like the ones of the HTTP server.
+//!
+//! Queries block the calling thread until they are executed, Node callers should run them on a worker thread.
+//! An engine can be used from multiple threads at the same time. Panics don't unwind into the caller, they are
+//! returned as errors.
+use futures::{channel::oneshot, Future};
+use prisma_engine::{
+    json::{JsonProtocolAdapter, JsonSingleQuery},
+    transaction_channel, CoreResult, Engine, EngineError, EngineResult, QueryDocument, Responses, TransactionRequest,
+    TransactionSendError, TransactionSender,
+};
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+    sync::Arc,
+};
+use tokio::runtime::{self, Runtime};
+
+/// A connected engine, created by `query_engine_connect` and released by `query_engine_disconnect`.
+pub struct QueryEngine {
+    runtime: Runtime,
+    engine: Arc<Engine>,
+}
+
+impl QueryEngine {
+    fn connect(datamodel: &str, url: Option<&str>) -> EngineResult<Self> {
+        let mut runtime = runtime::Builder::new()
+            .threaded_scheduler()
+            .enable_all()
+            .build()
+            .map_err(|err| EngineError::ConfigurationError(format!("Starting the runtime failed: {}", err)))?;
+
+        let mut builder = Engine::builder(datamodel);
+
+        if let Some(url) = url {
+            builder = builder.url(url);
+        }
+
+        let engine = runtime.block_on(builder.build())?;
+
+        Ok(Self {
+            runtime,
+            engine: Arc::new(engine),
+        })
+    }
+
+    /// Starts an interactive transaction on the runtime of the engine, which executes the requests sent to it until
+    /// it is committed or rolled back.
+    fn start_transaction(&self) -> QueryEngineTransaction {
+        let (sender, receiver) = transaction_channel();
+        let engine = Arc::clone(&self.engine);
+
+        self.runtime.spawn(async move {
+            // The error of a failed commit or rollback is returned to the caller as well.
+            let _ = engine.interactive_transaction(receiver).await;
+        });
+
+        QueryEngineTransaction { sender }
+    }
+
+    /// Runs the request on the runtime of the engine and waits for its response.
+    fn execute<F, Fut>(&self, request: &str, f: F) -> Responses
+    where
+        F: FnOnce(Arc<Engine>, QueryDocument) -> Fut,
+        Fut: Future<Output = EngineResult<Responses>> + Send + 'static,
+    {
+        let query_doc = match parse_request(request) {
+            Ok(query_doc) => query_doc,
+            Err(err) => return error_responses(err),
+        };
+
+        let handle = self.runtime.spawn(f(Arc::clone(&self.engine), query_doc));
+
+        match futures::executor::block_on(handle) {
+            Ok(Ok(responses)) => responses,
+            Ok(Err(err)) => error_responses(err),
+            // panicked
+            Err(err) => {
+                let mut responses = Responses::default();
+                responses.insert_error(user_facing_errors::Error::from_dyn_error(&err));
+                responses
+            }
+        }
+    }
+}
+
+/// An interactive transaction, created by `query_engine_start_transaction` and released by `query_engine_commit` or
+/// `query_engine_rollback`.
+pub struct QueryEngineTransaction {
+    sender: TransactionSender,
+}
+
+impl QueryEngineTransaction {
+    fn execute(&self, request: &str) -> Responses {
+        let query_doc = match parse_request(request) {
+            Ok(query_doc) => query_doc,
+            Err(err) => return error_responses(err),
+        };
+
+        match self.request(|reply| TransactionRequest::Execute(query_doc, reply)) {
+            Ok(responses) => responses,
+            Err(err) => error_responses(err),
+        }
+    }
+
+    /// Ends the transaction with a commit or rollback request.
+    fn end<F>(self, request: F) -> Responses
+    where
+        F: FnOnce(oneshot::Sender<CoreResult<()>>) -> TransactionRequest,
+    {
+        match self.request(request) {
+            Ok(()) => Responses::default(),
+            Err(err) => error_responses(err),
+        }
+    }
+
+    /// Sends the request to the transaction and waits for the reply.
+    fn request<T, F>(&self, request: F) -> EngineResult<T>
+    where
+        F: FnOnce(oneshot::Sender<CoreResult<T>>) -> TransactionRequest,
+    {
+        let (reply, result) = oneshot::channel();
+
+        self.sender.send(request(reply)).map_err(|err| match err {
+            TransactionSendError::Busy => EngineError::QueryConversionError(
+                "The transaction has too many requests waiting, retry once they finished.".to_owned(),
+            ),
+            TransactionSendError::Closed => transaction_closed(),
+        })?;
+
+        Ok(futures::executor::block_on(result).map_err(|_| transaction_closed())??)
+    }
+}
+
+/// Parses the data model and connects to its database, or to the database at `url` if it is not null.
+///
+/// Returns null if the engine can't be started, and sets `error` to the message if it is not null.
+///
+/// # Safety
+///
+/// `datamodel` and, if not null, `url` must be valid C strings. `error` must be null or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_connect(
+    datamodel: *const c_char,
+    url: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut QueryEngine {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        to_str(datamodel).and_then(|datamodel| {
+            let url = if url.is_null() { None } else { Some(to_str(url)?) };
+            QueryEngine::connect(datamodel, url)
+        })
+    }));
+
+    let message = match result {
+        Ok(Ok(engine)) => return Box::into_raw(Box::new(engine)),
+        Ok(Err(err)) => err.to_string(),
+        // panicked
+        Err(err) => user_facing_errors::Error::from_panic_payload(&*err)
+            .message()
+            .to_owned(),
+    };
+
+    if !error.is_null() {
+        *error = into_c_string(message);
+    }
+
+    ptr::null_mut()
+}
+
+/// Closes the connections of the engine and releases it. Queries still running are cancelled.
+///
+/// # Safety
+///
+/// `engine` must be null or returned by `query_engine_connect`, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_disconnect(engine: *mut QueryEngine) {
+    if !engine.is_null() {
+        // Shutting down the runtime doesn't panic, and there is nothing to report a panic to.
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(engine))));
+    }
+}
+
+/// Executes a JSON query, each of its operations in its own transaction if it needs one.
+///
+/// # Safety
+///
+/// `engine` must be returned by `query_engine_connect` and `request` must be a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_query(engine: *const QueryEngine, request: *const c_char) -> *mut c_char {
+    respond(|| match to_str(request) {
+        Ok(request) => (*engine).execute(request, |engine, query_doc| async move {
+            engine.execute_document(query_doc).await
+        }),
+        Err(err) => error_responses(err),
+    })
+}
+
+/// Executes the operations of a JSON array of queries in a single transaction, either all of them are committed or
+/// none. Operations are keyed by their alias or name in the response, so they have to be distinct.
+///
+/// # Safety
+///
+/// `engine` must be returned by `query_engine_connect` and `request` must be a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_transaction(engine: *const QueryEngine, request: *const c_char) -> *mut c_char {
+    respond(|| match to_str(request) {
+        Ok(request) => (*engine).execute(request, |engine, query_doc| async move {
+            engine.execute_transaction(query_doc).await
+        }),
+        Err(err) => error_responses(err),
+    })
+}
+
+/// Starts an interactive transaction, in which JSON queries are executed with `query_engine_transaction_query` until
+/// it is ended with `query_engine_commit` or `query_engine_rollback`. Failing queries leave the transaction open,
+/// the caller decides whether to commit the preceding writes or to roll them back.
+///
+/// Returns null if the transaction can't be started.
+///
+/// # Safety
+///
+/// `engine` must be returned by `query_engine_connect`.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_start_transaction(engine: *const QueryEngine) -> *mut QueryEngineTransaction {
+    match panic::catch_unwind(AssertUnwindSafe(|| (*engine).start_transaction())) {
+        Ok(transaction) => Box::into_raw(Box::new(transaction)),
+        // panicked
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Executes a JSON query in the transaction.
+///
+/// # Safety
+///
+/// `transaction` must be returned by `query_engine_start_transaction` and not be ended yet, `request` must be a
+/// valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_transaction_query(
+    transaction: *const QueryEngineTransaction,
+    request: *const c_char,
+) -> *mut c_char {
+    respond(|| match to_str(request) {
+        Ok(request) => (*transaction).execute(request),
+        Err(err) => error_responses(err),
+    })
+}
+
+/// Commits the transaction and releases it. Returns a response with the error if the commit failed.
+///
+/// # Safety
+///
+/// `transaction` must be returned by `query_engine_start_transaction`, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_commit(transaction: *mut QueryEngineTransaction) -> *mut c_char {
+    let transaction = Box::from_raw(transaction);
+    respond(move || transaction.end(TransactionRequest::Commit))
+}
+
+/// Rolls the transaction back and releases it. Returns a response with the error if the rollback failed.
+///
+/// # Safety
+///
+/// `transaction` must be returned by `query_engine_start_transaction`, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_rollback(transaction: *mut QueryEngineTransaction) -> *mut c_char {
+    let transaction = Box::from_raw(transaction);
+    respond(move || transaction.end(TransactionRequest::Rollback))
+}
+
+/// Releases a string returned by the engine.
+///
+/// # Safety
+///
+/// `s` must be null or returned by the engine, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn query_engine_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(CString::from_raw(s))));
+    }
+}
+
+/// A request is either a single query or, for transactions, an array of queries.
+fn parse_request(request: &str) -> EngineResult<QueryDocument> {
+    let value: serde_json::Value = serde_json::from_str(request)
+        .map_err(|err| EngineError::QueryConversionError(format!("Invalid JSON request: {}", err)))?;
+
+    let queries: Vec<JsonSingleQuery> = match value {
+        serde_json::Value::Array(_) => serde_json::from_value(value),
+        _ => serde_json::from_value(value).map(|query| vec![query]),
+    }
+    .map_err(|err| EngineError::QueryConversionError(format!("Invalid JSON query: {}", err)))?;
+
+    let mut operations = Vec::new();
+
+    for query in queries {
+        operations.extend(JsonProtocolAdapter::convert(query)?.operations);
+    }
+
+    Ok(QueryDocument { operations })
+}
+
+/// Serializes the responses of an exported function into a string for the caller, a panic becomes an error response
+/// as it must not unwind across the C ABI.
+fn respond<F>(f: F) -> *mut c_char
+where
+    F: FnOnce() -> Responses,
+{
+    let responses = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|err| {
+        let mut responses = Responses::default();
+        responses.insert_error(user_facing_errors::Error::from_panic_payload(&*err));
+        responses
+    });
+
+    into_c_string(serialize(&responses))
+}
+
+fn transaction_closed() -> EngineError {
+    EngineError::QueryConversionError("The transaction was closed.".to_owned())
+}
+
+fn error_responses(err: EngineError) -> Responses {
+    let mut responses = Responses::default();
+    responses.insert_error(err);
+    responses
+}
+
+fn serialize(responses: &Responses) -> String {
+    serde_json::to_string(responses).expect("Responses are always serializable.")
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> EngineResult<&'a str> {
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|err| EngineError::QueryConversionError(format!("Invalid UTF-8 string: {}", err)))
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    // Null bytes are escaped in JSON, but could be part of error messages.
+    CString::new(s.replace('\0', "")).unwrap().into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prisma_engine::Operation;
+    use serde_json::json;
+
+    const DATAMODEL: &str = r#"
+        datasource db {
+            provider = "sqlite"
+            url      = "file::memory:"
+        }
+
+        model User {
+            id String @id
+        }
+    "#;
+
+    fn c_string(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    /// Parses a string returned by the engine and releases it.
+    unsafe fn take_json(s: *mut c_char) -> serde_json::Value {
+        let value = serde_json::from_str(CStr::from_ptr(s).to_str().unwrap()).unwrap();
+        query_engine_string_free(s);
+
+        value
+    }
+
+    fn error_count(response: &serde_json::Value) -> usize {
+        response["errors"].as_array().map(Vec::len).unwrap_or(0)
+    }
+
+    #[test]
+    fn requests_are_single_queries_or_arrays_of_queries() {
+        let query = json!({
+            "action": "query",
+            "selection": [{ "name": "findManyUser", "selection": [{ "name": "id" }] }]
+        });
+        let mutation = json!({
+            "action": "mutation",
+            "selection": [{ "name": "deleteManyUser", "selection": [{ "name": "count" }] }]
+        });
+
+        let single = parse_request(&query.to_string()).unwrap();
+        assert_eq!(single.operations.len(), 1);
+
+        let operations = parse_request(&json!([query, mutation]).to_string()).unwrap().operations;
+        assert_eq!(operations.len(), 2);
+        assert!(match operations[1] {
+            Operation::Write(_) => true,
+            _ => false,
+        });
+
+        assert!(parse_request("{").is_err());
+        assert!(parse_request(r#"{ "action": "delete" }"#).is_err());
+    }
+
+    #[test]
+    fn panics_are_returned_as_error_responses() {
+        let response = unsafe { take_json(respond(|| panic!("query panicked"))) };
+
+        assert_eq!(error_count(&response), 1);
+    }
+
+    #[test]
+    fn null_bytes_are_removed_from_returned_strings() {
+        let s = into_c_string("a\0b".to_owned());
+
+        assert_eq!(unsafe { CStr::from_ptr(s) }.to_str().unwrap(), "ab");
+        unsafe { query_engine_string_free(s) };
+    }
+
+    #[test]
+    fn failed_connects_return_null_and_the_error() {
+        let datamodel = c_string("model User {");
+        let mut error = ptr::null_mut();
+
+        let engine = unsafe { query_engine_connect(datamodel.as_ptr(), ptr::null(), &mut error) };
+
+        assert!(engine.is_null());
+        assert!(!error.is_null());
+        unsafe { query_engine_string_free(error) };
+    }
+
+    #[test]
+    fn queries_and_transactions_return_json_responses() {
+        let datamodel = c_string(DATAMODEL);
+        let invalid = c_string("{");
+
+        unsafe {
+            let engine = query_engine_connect(datamodel.as_ptr(), ptr::null(), ptr::null_mut());
+            assert!(!engine.is_null());
+
+            assert_eq!(error_count(&take_json(query_engine_query(engine, invalid.as_ptr()))), 1);
+
+            // A failing query leaves the transaction open.
+            let transaction = query_engine_start_transaction(engine);
+            assert!(!transaction.is_null());
+            assert_eq!(
+                error_count(&take_json(query_engine_transaction_query(
+                    transaction,
+                    invalid.as_ptr()
+                ))),
+                1
+            );
+            assert_eq!(take_json(query_engine_rollback(transaction)), json!({}));
+
+            query_engine_disconnect(engine);
+        }
+    }
+}
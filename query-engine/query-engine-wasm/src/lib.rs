@@ -0,0 +1,162 @@
+//! The connector independent parts of the query engine compiled to WebAssembly, for validating data models and
+//! planning queries in browsers and edge runtimes. Queries are executed by a remote executor provided by the host,
+//! e.g. a function sending them to a query engine server.
+//!
+//! Build with `wasm-pack build query-engine/query-engine-wasm`.
+use prisma_engine::{
+    exec_loader,
+    json::{JsonProtocolAdapter, JsonSingleQuery},
+};
+use prisma_models::DatamodelConverter;
+use query_core::{schema::QuerySchemaRef, BuildMode, QueryGraphBuilder, QuerySchemaBuilder};
+use std::{fmt::Display, sync::Arc};
+use wasm_bindgen::prelude::*;
+
+/// Parses and validates the data model, failing with the validation errors.
+#[wasm_bindgen(js_name = validateDatamodel)]
+pub fn validate_datamodel(datamodel: &str) -> Result<(), JsValue> {
+    datamodel::parse_datamodel(datamodel).map_err(js_error)?;
+
+    Ok(())
+}
+
+/// Validates and plans JSON queries against the query schema of a data model.
+#[wasm_bindgen]
+pub struct QueryPlanner {
+    query_schema: QuerySchemaRef,
+    executor: Option<js_sys::Function>,
+}
+
+#[wasm_bindgen]
+impl QueryPlanner {
+    #[wasm_bindgen(constructor)]
+    pub fn new(datamodel: &str, enable_raw_queries: bool) -> Result<QueryPlanner, JsValue> {
+        Ok(Self {
+            query_schema: query_schema(datamodel, enable_raw_queries).map_err(js_error)?,
+            executor: None,
+        })
+    }
+
+    /// Sets the executor of `execute`, a function taking a JSON request and returning a promise of its response.
+    #[wasm_bindgen(js_name = setExecutor)]
+    pub fn set_executor(&mut self, executor: js_sys::Function) {
+        self.executor = Some(executor);
+    }
+
+    /// Parses the JSON request and builds its query graphs, failing if it doesn't match the query schema.
+    pub fn validate(&self, request: &str) -> Result<(), JsValue> {
+        validate_request(&self.query_schema, request).map_err(js_error)
+    }
+
+    /// Validates the JSON request and passes it on to the executor, returning the promise of the executor.
+    /// Invalid requests fail without reaching the executor.
+    pub fn execute(&self, request: &str) -> Result<JsValue, JsValue> {
+        self.validate(request)?;
+
+        let executor = self
+            .executor
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("No executor set, see `setExecutor`."))?;
+
+        executor.call1(&JsValue::NULL, &JsValue::from_str(request))
+    }
+}
+
+/// The query schema of the data model, as it is built by a query engine connected to its data source.
+fn query_schema(datamodel: &str, enable_raw_queries: bool) -> Result<QuerySchemaRef, String> {
+    let configuration = datamodel::parse_configuration(datamodel).map_err(|err| err.to_string())?;
+    let datamodel = datamodel::parse_datamodel(datamodel).map_err(|err| err.to_string())?;
+
+    let data_source = configuration.datasources.first();
+    let mut template = DatamodelConverter::convert(&datamodel);
+
+    if let Some(source) = data_source {
+        template.relation_mode = source.relation_mode();
+        template.updated_at_in_database = exec_loader::updates_timestamps(&**source);
+    }
+
+    // The database name is only used to execute queries.
+    let internal_data_model = template.build("".into());
+
+    // Without a data source, the executor decides what's supported, so nothing is left out of the schema.
+    let capabilities = exec_loader::shared_capabilities(&configuration.datasources);
+
+    let query_schema = QuerySchemaBuilder::new(
+        &internal_data_model,
+        &capabilities,
+        BuildMode::Modern,
+        enable_raw_queries,
+    )
+    .build();
+
+    Ok(Arc::new(query_schema))
+}
+
+fn validate_request(query_schema: &QuerySchemaRef, request: &str) -> Result<(), String> {
+    let query: JsonSingleQuery = serde_json::from_str(request).map_err(|err| err.to_string())?;
+    let query_doc = JsonProtocolAdapter::convert(query).map_err(|err| err.to_string())?;
+
+    QueryGraphBuilder::new(Arc::clone(query_schema))
+        .build(query_doc)
+        .map_err(|err| err.to_string())?;
+
+    Ok(())
+}
+
+fn js_error(err: impl Display) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+// `JsValue`s can only be created on wasm32, the tests run the parts behind the bindings.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DATAMODEL: &str = r#"
+        model User {
+            id    String @id
+            email String @unique
+        }
+    "#;
+
+    fn find_many(model: &str) -> String {
+        json!({
+            "action": "query",
+            "selection": [{ "name": format!("findMany{}", model), "selection": [{ "name": "id" }] }]
+        })
+        .to_string()
+    }
+
+    fn execute_raw() -> String {
+        json!({
+            "action": "mutation",
+            "selection": [{ "name": "executeRaw", "arguments": { "query": "SELECT 1" } }]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn requests_are_validated_against_the_query_schema() {
+        let query_schema = query_schema(DATAMODEL, false).unwrap();
+
+        assert!(validate_request(&query_schema, &find_many("User")).is_ok());
+        assert!(validate_request(&query_schema, &find_many("Post")).is_err());
+        assert!(validate_request(&query_schema, "{").is_err());
+    }
+
+    #[test]
+    fn raw_queries_are_only_planned_if_enabled() {
+        let disabled = query_schema(DATAMODEL, false).unwrap();
+        let enabled = query_schema(DATAMODEL, true).unwrap();
+
+        assert!(validate_request(&disabled, &execute_raw()).is_err());
+        assert!(validate_request(&enabled, &execute_raw()).is_ok());
+    }
+
+    #[test]
+    fn invalid_data_models_have_no_query_schema() {
+        assert!(query_schema("model User {", false).is_err());
+        assert!(query_schema("model User { id Unknown @id }", false).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::{anyhow, bail};
+use datamodel::configuration::SQLITE_SOURCE_NAME;
+use futures::FutureExt;
+use once_cell::sync::Lazy;
+use prisma_engine::{Engine, Operation};
+use std::{panic::AssertUnwindSafe, path::PathBuf, sync::Mutex};
+
+/// The last panic of any thread, with its location.
+static LAST_PANIC: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Records panics instead of printing them, shrinking panics the engine many times.
+pub fn capture_panics() {
+    std::panic::set_hook(Box::new(|info| {
+        *LAST_PANIC.lock().unwrap() = Some(info.to_string());
+    }));
+}
+
+/// The SQLite database of the data model, along with a copy of it without records to reset it to.
+pub struct Database {
+    datamodel: String,
+    path: PathBuf,
+    template: PathBuf,
+}
+
+impl Database {
+    /// Migrates the database to the data model and keeps a copy of the empty database.
+    pub async fn setup(datamodel: &str) -> anyhow::Result<Self> {
+        let configuration = datamodel::parse_configuration(datamodel).map_err(|err| anyhow!("{}", err))?;
+
+        let source = configuration
+            .datasources
+            .first()
+            .ok_or_else(|| anyhow!("There is no datasource in the schema."))?;
+
+        if source.connector_type() != SQLITE_SOURCE_NAME {
+            bail!(
+                "The fuzzer only runs against SQLite, the datasource uses {}.",
+                source.connector_type()
+            );
+        }
+
+        let url = source.url().value.expose();
+        let path = PathBuf::from(url.trim_start_matches("file:").split('?').next().unwrap_or_default());
+        let template = path.with_extension("fuzz-template");
+
+        let api = migration_core::migration_api(datamodel).await?;
+        let migration_id = "query-fuzzer-migration".to_owned();
+
+        let infer_input = migration_core::InferMigrationStepsInput {
+            assume_applied_migrations: Some(Vec::new()),
+            assume_to_be_applied: Some(Vec::new()),
+            datamodel: datamodel.to_owned(),
+            migration_id: migration_id.clone(),
+        };
+
+        api.reset(&serde_json::Value::Null).await?;
+
+        let result = api.infer_migration_steps(&infer_input).await?;
+
+        let apply_input = migration_core::ApplyMigrationInput {
+            force: Some(true),
+            migration_id,
+            steps: result.datamodel_steps,
+        };
+
+        api.apply_migration(&apply_input).await?;
+        std::fs::copy(&path, &template)?;
+
+        Ok(Self {
+            datamodel: datamodel.to_owned(),
+            path,
+            template,
+        })
+    }
+
+    /// An engine on the empty database.
+    pub async fn engine(&self) -> anyhow::Result<Engine> {
+        std::fs::copy(&self.template, &self.path)?;
+
+        Engine::builder(&self.datamodel)
+            .build()
+            .await
+            .map_err(|err| anyhow!("{}", err))
+    }
+
+    /// Executes the operations one after the other on the empty database. Returns the first panic, errors of the
+    /// operations are expected for random arguments and ignored.
+    pub async fn run(&self, operations: &[Operation]) -> anyhow::Result<Option<String>> {
+        let engine = self.engine().await?;
+
+        for operation in operations {
+            let result = AssertUnwindSafe(engine.execute(operation.clone())).catch_unwind().await;
+
+            if result.is_err() {
+                return Ok(LAST_PANIC.lock().unwrap().take());
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,187 @@
+use query_core::{
+    schema::{
+        EnumType, Field, InputObjectType, InputType, IntoArc, ObjectType, QuerySchema, ScalarType, SchemaQueryBuilder,
+    },
+    Operation, QueryValue, Selection,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::collections::BTreeMap;
+
+/// Few distinct values, so that writes run into unique constraints and filters match records of earlier writes.
+const STRINGS: &[&str] = &["", "a", "b", "ab"];
+const DATE_TIMES: &[&str] = &["1970-01-01T00:00:00Z", "2020-01-01T00:00:00Z"];
+const JSON: &[&str] = &["null", "1", "{}", "[]"];
+const UUIDS: &[&str] = &[
+    "2e0a5d57-7a40-4ac5-8a8b-05f6b1ba8f5d",
+    "9b3c6f31-4ac0-4a2e-9f6d-4f35c04c1e7a",
+];
+
+/// Generates operations on the models of a query schema, with arguments of the input types the schema expects.
+pub struct Generator<'a> {
+    rng: StdRng,
+    schema: &'a QuerySchema,
+    max_depth: usize,
+}
+
+impl<'a> Generator<'a> {
+    pub fn new(seed: u64, schema: &'a QuerySchema, max_depth: usize) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            schema,
+            max_depth,
+        }
+    }
+
+    pub fn operations(&mut self, count: usize) -> Vec<Operation> {
+        (0..count).map(|_| self.operation()).collect()
+    }
+
+    fn operation(&mut self) -> Operation {
+        let write = self.rng.gen_bool(0.5);
+        let root = if write {
+            self.schema.mutation()
+        } else {
+            self.schema.query()
+        };
+
+        // Raw queries and routines don't run on the models.
+        let fields: Vec<_> = root
+            .get_fields()
+            .iter()
+            .filter(|field| {
+                if let Some(SchemaQueryBuilder::ModelQueryBuilder(_)) = field.query_builder {
+                    true
+                } else {
+                    false
+                }
+            })
+            .cloned()
+            .collect();
+
+        let field = fields.choose(&mut self.rng).expect("The data model has no models.");
+        let selection = self.selection(field, 0);
+
+        if write {
+            Operation::Write(selection)
+        } else {
+            Operation::Read(selection)
+        }
+    }
+
+    /// Required arguments always get a value, optional ones every other time.
+    fn selection(&mut self, field: &Field, depth: usize) -> Selection {
+        let mut arguments = Vec::new();
+
+        for argument in field.arguments.iter() {
+            if !is_optional(&argument.argument_type) || self.rng.gen_bool(0.5) {
+                arguments.push((argument.name.clone(), self.value(&argument.argument_type, depth)));
+            }
+        }
+
+        let nested_selections = match field.field_type.as_object_type() {
+            Some(object) => self.nested_selections(&object, depth),
+            None => Vec::new(),
+        };
+
+        Selection {
+            name: field.name.clone(),
+            alias: None,
+            arguments,
+            nested_selections,
+        }
+    }
+
+    /// Relations are selected less often than scalars and only up to the maximum depth. At least one scalar is
+    /// selected, an empty selection set isn't valid.
+    fn nested_selections(&mut self, object: &ObjectType, depth: usize) -> Vec<Selection> {
+        let mut selections = Vec::new();
+
+        for field in object.get_fields() {
+            let selected = if field.field_type.as_object_type().is_some() {
+                depth < self.max_depth && self.rng.gen_bool(0.3)
+            } else {
+                self.rng.gen_bool(0.7)
+            };
+
+            if selected {
+                selections.push(self.selection(field, depth + 1));
+            }
+        }
+
+        if selections.is_empty() {
+            if let Some(field) = object
+                .get_fields()
+                .iter()
+                .find(|field| field.field_type.as_object_type().is_none())
+            {
+                selections.push(self.selection(field, depth + 1));
+            }
+        }
+
+        selections
+    }
+
+    fn value(&mut self, input_type: &InputType, depth: usize) -> QueryValue {
+        match input_type {
+            InputType::Opt(_) if self.rng.gen_bool(0.1) => QueryValue::Null,
+            InputType::Opt(inner) => self.value(inner, depth),
+            InputType::List(inner) => {
+                let len = self.rng.gen_range(0, 3);
+                QueryValue::List((0..len).map(|_| self.value(inner, depth)).collect())
+            }
+            InputType::Object(object) => self.object(&object.into_arc(), depth),
+            InputType::Enum(enum_type) => self.enum_value(enum_type),
+            InputType::Scalar(scalar) => self.scalar(scalar),
+        }
+    }
+
+    /// Beyond the maximum depth only required fields get values, so nested inputs end where the schema allows it.
+    fn object(&mut self, object: &InputObjectType, depth: usize) -> QueryValue {
+        let mut fields = BTreeMap::new();
+
+        for field in object.get_fields() {
+            if !is_optional(&field.field_type) || (depth < self.max_depth && self.rng.gen_bool(0.3)) {
+                let value = self.value(&field.field_type, depth + 1);
+                fields.insert(field.name.clone(), value);
+            }
+        }
+
+        QueryValue::Object(fields)
+    }
+
+    fn enum_value(&mut self, enum_type: &EnumType) -> QueryValue {
+        let value = match enum_type {
+            EnumType::Internal(internal) => internal.values.choose(&mut self.rng).cloned(),
+            EnumType::OrderBy(order_by) => order_by.values.choose(&mut self.rng).map(|(name, _)| name.clone()),
+        };
+
+        value.map(QueryValue::Enum).unwrap_or(QueryValue::Null)
+    }
+
+    fn scalar(&mut self, scalar: &ScalarType) -> QueryValue {
+        match scalar {
+            ScalarType::String => self.pick(STRINGS),
+            ScalarType::Int => QueryValue::Int(self.rng.gen_range(-2, 10)),
+            ScalarType::Float | ScalarType::Decimal => {
+                QueryValue::Float(f64::from(self.rng.gen_range(-4i32, 20)) / 2.0)
+            }
+            ScalarType::Boolean => QueryValue::Boolean(self.rng.gen()),
+            ScalarType::Enum(enum_type) => self.enum_value(enum_type),
+            ScalarType::DateTime => self.pick(DATE_TIMES),
+            ScalarType::Json | ScalarType::JsonList => self.pick(JSON),
+            ScalarType::UUID => self.pick(UUIDS),
+        }
+    }
+
+    fn pick(&mut self, values: &[&str]) -> QueryValue {
+        QueryValue::String(values.choose(&mut self.rng).unwrap().to_string())
+    }
+}
+
+fn is_optional(input_type: &InputType) -> bool {
+    if let InputType::Opt(_) = input_type {
+        true
+    } else {
+        false
+    }
+}
@@ -0,0 +1,75 @@
+//! Generates random operations that are valid against the query schema of a data model, reads as well as nested
+//! writes, and executes them against a SQLite database. Runs that panic the engine are shrunk to the smallest
+//! operations that still panic the same way and printed as GraphQL, to be replayed against the server.
+
+mod database;
+mod generator;
+mod render;
+mod shrink;
+
+use anyhow::Context;
+use database::Database;
+use generator::Generator;
+use structopt::*;
+
+#[derive(StructOpt)]
+struct Options {
+    /// The path to the prisma schema file. Its first data source must be a SQLite database, which gets reset.
+    #[structopt(long = "file-path")]
+    file_path: String,
+    /// The number of runs, each on an empty database.
+    #[structopt(long = "runs", default_value = "100")]
+    runs: u64,
+    /// The number of operations executed in a run.
+    #[structopt(long = "operations", default_value = "10")]
+    operations: usize,
+    /// The seed of the first run, the following runs use the next seeds. Random by default.
+    #[structopt(long = "seed")]
+    seed: Option<u64>,
+    /// How deep arguments and selections get nested.
+    #[structopt(long = "max-depth", default_value = "3")]
+    max_depth: usize,
+}
+
+fn main() -> anyhow::Result<()> {
+    let options = Options::from_args();
+    let datamodel = std::fs::read_to_string(&options.file_path).context("error reading the schemafile")?;
+
+    let mut rt = tokio::runtime::Runtime::new()?;
+    let database = rt.block_on(Database::setup(&datamodel))?;
+    let query_schema = rt.block_on(database.engine())?.query_schema().clone();
+
+    database::capture_panics();
+
+    let first_seed = options.seed.unwrap_or_else(|| u64::from(rand::random::<u32>()));
+    let mut failures = 0;
+
+    for seed in first_seed..first_seed + options.runs {
+        let operations = Generator::new(seed, &query_schema, options.max_depth).operations(options.operations);
+
+        let panic = match rt.block_on(database.run(&operations))? {
+            Some(panic) => panic,
+            None => continue,
+        };
+
+        let shrunk = shrink::shrink(operations, |candidate| {
+            rt.block_on(database.run(candidate))
+                .map(|candidate_panic| candidate_panic.as_ref() == Some(&panic))
+                .unwrap_or(false)
+        });
+
+        failures += 1;
+        println!("Seed {} {}\n\n{}\n", seed, panic, render::render(&shrunk));
+    }
+
+    println!(
+        "{} of {} runs starting at seed {} panicked.",
+        failures, options.runs, first_seed
+    );
+
+    if failures > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
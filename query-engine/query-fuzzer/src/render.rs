@@ -0,0 +1,65 @@
+use query_core::{Operation, QueryValue, Selection};
+
+/// The operations as GraphQL, one query or mutation per operation.
+pub fn render(operations: &[Operation]) -> String {
+    operations
+        .iter()
+        .map(|operation| match operation {
+            Operation::Read(selection) => format!("query {{\n{}}}", render_selection(selection, 1)),
+            Operation::Write(selection) => format!("mutation {{\n{}}}", render_selection(selection, 1)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn render_selection(selection: &Selection, indent: usize) -> String {
+    let padding = "  ".repeat(indent);
+    let mut rendered = format!("{}{}", padding, selection.name);
+
+    if !selection.arguments.is_empty() {
+        let arguments: Vec<String> = selection
+            .arguments
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, render_value(value)))
+            .collect();
+
+        rendered.push_str(&format!("({})", arguments.join(", ")));
+    }
+
+    if !selection.nested_selections.is_empty() {
+        rendered.push_str(" {\n");
+
+        for nested in selection.nested_selections.iter() {
+            rendered.push_str(&render_selection(nested, indent + 1));
+        }
+
+        rendered.push_str(&padding);
+        rendered.push('}');
+    }
+
+    rendered.push('\n');
+    rendered
+}
+
+fn render_value(value: &QueryValue) -> String {
+    match value {
+        QueryValue::Int(i) => i.to_string(),
+        QueryValue::Float(f) => f.to_string(),
+        QueryValue::String(s) => serde_json::to_string(s).unwrap(),
+        QueryValue::Boolean(b) => b.to_string(),
+        QueryValue::Null => "null".to_owned(),
+        QueryValue::Enum(e) => e.clone(),
+        QueryValue::List(values) => {
+            let values: Vec<String> = values.iter().map(render_value).collect();
+            format!("[{}]", values.join(", "))
+        }
+        QueryValue::Object(fields) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, render_value(value)))
+                .collect();
+
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use query_core::{Operation, QueryValue, Selection};
+
+/// Replaces the operations by smaller ones as long as they still fail: Without one of the operations, or with one
+/// operation missing an argument, a nested selection or a part of an argument value. Ends when no smaller
+/// candidate fails anymore.
+pub fn shrink<F>(mut operations: Vec<Operation>, mut fails: F) -> Vec<Operation>
+where
+    F: FnMut(&[Operation]) -> bool,
+{
+    'shrinking: loop {
+        for candidate in candidates(&operations) {
+            if fails(&candidate) {
+                operations = candidate;
+                continue 'shrinking;
+            }
+        }
+
+        return operations;
+    }
+}
+
+fn candidates(operations: &[Operation]) -> Vec<Vec<Operation>> {
+    let mut candidates = Vec::new();
+
+    for index in 0..operations.len() {
+        let mut candidate = operations.to_vec();
+        candidate.remove(index);
+        candidates.push(candidate);
+    }
+
+    for (index, operation) in operations.iter().enumerate() {
+        let (selection, is_write) = match operation {
+            Operation::Read(selection) => (selection, false),
+            Operation::Write(selection) => (selection, true),
+        };
+
+        for smaller in smaller_selections(selection) {
+            let mut candidate = operations.to_vec();
+
+            candidate[index] = if is_write {
+                Operation::Write(smaller)
+            } else {
+                Operation::Read(smaller)
+            };
+
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
+
+fn smaller_selections(selection: &Selection) -> Vec<Selection> {
+    let mut smaller = Vec::new();
+
+    for index in 0..selection.arguments.len() {
+        let mut candidate = selection.clone();
+        candidate.arguments.remove(index);
+        smaller.push(candidate);
+    }
+
+    for (index, (_, value)) in selection.arguments.iter().enumerate() {
+        for value in smaller_values(value) {
+            let mut candidate = selection.clone();
+            candidate.arguments[index].1 = value;
+            smaller.push(candidate);
+        }
+    }
+
+    for index in 0..selection.nested_selections.len() {
+        let mut candidate = selection.clone();
+        candidate.nested_selections.remove(index);
+        smaller.push(candidate);
+    }
+
+    for (index, nested) in selection.nested_selections.iter().enumerate() {
+        for nested in smaller_selections(nested) {
+            let mut candidate = selection.clone();
+            candidate.nested_selections[index] = nested;
+            smaller.push(candidate);
+        }
+    }
+
+    smaller
+}
+
+fn smaller_values(value: &QueryValue) -> Vec<QueryValue> {
+    let mut smaller = Vec::new();
+
+    match value {
+        QueryValue::Object(fields) => {
+            for name in fields.keys() {
+                let mut candidate = fields.clone();
+                candidate.remove(name);
+                smaller.push(QueryValue::Object(candidate));
+            }
+
+            for (name, field) in fields {
+                for field in smaller_values(field) {
+                    let mut candidate = fields.clone();
+                    candidate.insert(name.clone(), field);
+                    smaller.push(QueryValue::Object(candidate));
+                }
+            }
+        }
+        QueryValue::List(values) => {
+            for index in 0..values.len() {
+                let mut candidate = values.clone();
+                candidate.remove(index);
+                smaller.push(QueryValue::List(candidate));
+            }
+
+            for (index, value) in values.iter().enumerate() {
+                for value in smaller_values(value) {
+                    let mut candidate = values.clone();
+                    candidate[index] = value;
+                    smaller.push(QueryValue::List(candidate));
+                }
+            }
+        }
+        QueryValue::Int(i) if *i != 0 => smaller.push(QueryValue::Int(0)),
+        QueryValue::String(s) if !s.is_empty() => smaller.push(QueryValue::String(String::new())),
+        _ => (),
+    }
+
+    smaller
+}